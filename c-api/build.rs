@@ -0,0 +1,23 @@
+use std::{env, path};
+
+fn main() {
+    let crate_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let out_dir = path::PathBuf::from(env::var("OUT_DIR").unwrap());
+    let config =
+        cbindgen::Config::from_file(path::Path::new(&crate_dir).join("cbindgen.toml")).unwrap();
+    cbindgen::generate_with_config(&crate_dir, config)
+        .unwrap()
+        .write_to_file(out_dir.join("tract.h"));
+
+    // the C test, linked in the crate unit tests only
+    cc::Build::new()
+        .file("tests/c/api.c")
+        .include(&out_dir)
+        .warnings_into_errors(true)
+        .cargo_metadata(false)
+        .compile("tract_c_api_test");
+    println!("cargo:rustc-link-search=native={}", out_dir.display());
+    println!("cargo:rerun-if-changed=src");
+    println!("cargo:rerun-if-changed=cbindgen.toml");
+    println!("cargo:rerun-if-changed=tests/c/api.c");
+}
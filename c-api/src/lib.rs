@@ -0,0 +1,493 @@
+//! C API for tract.
+//!
+//! All functions return a `TractStatus`. On failure, a description of the
+//! error is written to the caller-provided `error` buffer of `error_len`
+//! bytes, truncated if needed and always NUL-terminated. `error` may be null
+//! if the caller is not interested in the message.
+//!
+//! Ownership: models and tensors handed out by `tract_model_load_onnx`,
+//! `tract_tensor_create` and `tract_model_run` belong to the caller, who must
+//! release them with `tract_model_free` and `tract_tensor_free`. Pointers
+//! obtained from a tensor (`tract_tensor_shape`, `tract_tensor_data_ptr`)
+//! borrow from it: they are valid until the tensor is freed, and must not be
+//! written to. Input tensors passed to `tract_model_run` are only borrowed
+//! for the duration of the call.
+//!
+//! The header is generated by cbindgen at build time, and checked in as
+//! `tract.h`.
+
+use std::ffi::CStr;
+use std::os::raw::{c_char, c_void};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+use tract_core::internal::*;
+
+/// Outcome of a call.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum TractStatus {
+    /// Success.
+    Ok = 0,
+    /// A required pointer argument was null.
+    NullPointer = 1,
+    /// An argument was inconsistent: wrong count, size, or encoding.
+    InvalidArgument = 2,
+    /// tract failed loading or running the model.
+    Error = 3,
+    /// tract panicked. State of the objects involved is unspecified.
+    Panic = 4,
+}
+
+/// Element types of tensors going through the API.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum TractDatumType {
+    Bool,
+    U8,
+    U16,
+    I8,
+    I16,
+    I32,
+    I64,
+    F32,
+    F64,
+}
+
+impl From<TractDatumType> for DatumType {
+    fn from(dt: TractDatumType) -> DatumType {
+        match dt {
+            TractDatumType::Bool => DatumType::Bool,
+            TractDatumType::U8 => DatumType::U8,
+            TractDatumType::U16 => DatumType::U16,
+            TractDatumType::I8 => DatumType::I8,
+            TractDatumType::I16 => DatumType::I16,
+            TractDatumType::I32 => DatumType::I32,
+            TractDatumType::I64 => DatumType::I64,
+            TractDatumType::F32 => DatumType::F32,
+            TractDatumType::F64 => DatumType::F64,
+        }
+    }
+}
+
+impl TractDatumType {
+    fn from_datum_type(dt: DatumType) -> Option<TractDatumType> {
+        let dt = match dt {
+            DatumType::Bool => TractDatumType::Bool,
+            DatumType::U8 => TractDatumType::U8,
+            DatumType::U16 => TractDatumType::U16,
+            DatumType::I8 => TractDatumType::I8,
+            DatumType::I16 => TractDatumType::I16,
+            DatumType::I32 => TractDatumType::I32,
+            DatumType::I64 => TractDatumType::I64,
+            DatumType::F32 => TractDatumType::F32,
+            DatumType::F64 => TractDatumType::F64,
+            _ => return None,
+        };
+        Some(dt)
+    }
+}
+
+/// An optimized model, ready to run.
+pub struct TractModel {
+    plan: TypedSimplePlan<TypedModel>,
+}
+
+/// A tensor, immutable once created.
+pub struct TractTensor(Arc<Tensor>);
+
+struct Failure(TractStatus, String);
+
+impl From<TractError> for Failure {
+    fn from(e: TractError) -> Failure {
+        let causes: Vec<String> = e.iter().map(|e| e.to_string()).collect();
+        Failure(TractStatus::Error, causes.join(": "))
+    }
+}
+
+fn non_null<T>(ptr: *const T, name: &str) -> Result<(), Failure> {
+    if ptr.is_null() {
+        Err(Failure(TractStatus::NullPointer, format!("`{}` is null", name)))
+    } else {
+        Ok(())
+    }
+}
+
+fn invalid<T>(msg: String) -> Result<T, Failure> {
+    Err(Failure(TractStatus::InvalidArgument, msg))
+}
+
+unsafe fn write_error(error: *mut c_char, error_len: usize, msg: &str) {
+    if error.is_null() || error_len == 0 {
+        return;
+    }
+    let len = msg.len().min(error_len - 1);
+    msg.as_ptr().copy_to_nonoverlapping(error as *mut u8, len);
+    *error.add(len) = 0;
+}
+
+unsafe fn wrap(
+    error: *mut c_char,
+    error_len: usize,
+    f: impl FnOnce() -> Result<(), Failure>,
+) -> TractStatus {
+    let Failure(status, msg) = match catch_unwind(AssertUnwindSafe(f)) {
+        Ok(Ok(())) => return TractStatus::Ok,
+        Ok(Err(failure)) => failure,
+        Err(panic) => {
+            let msg = if let Some(s) = panic.downcast_ref::<&str>() {
+                s.to_string()
+            } else if let Some(s) = panic.downcast_ref::<String>() {
+                s.clone()
+            } else {
+                "unknown cause".to_string()
+            };
+            Failure(TractStatus::Panic, format!("tract panicked: {}", msg))
+        }
+    };
+    write_error(error, error_len, &msg);
+    status
+}
+
+/// Load an ONNX model from the file at `path`, and optimize it.
+///
+/// The shapes of the model inputs must be fully determined by the file. On
+/// success, `*model` receives a model to free with `tract_model_free`.
+#[no_mangle]
+pub unsafe extern "C" fn tract_model_load_onnx(
+    path: *const c_char,
+    model: *mut *mut TractModel,
+    error: *mut c_char,
+    error_len: usize,
+) -> TractStatus {
+    wrap(error, error_len, || {
+        non_null(path, "path")?;
+        non_null(model, "model")?;
+        let path = match CStr::from_ptr(path).to_str() {
+            Ok(path) => path,
+            Err(e) => return invalid(format!("`path` is not valid UTF-8: {}", e)),
+        };
+        let typed = tract_onnx::onnx().model_for_path(path)?.into_optimized()?;
+        let plan = SimplePlan::new(typed)?;
+        *model = Box::into_raw(Box::new(TractModel { plan }));
+        Ok(())
+    })
+}
+
+/// Write the number of inputs of `model` to `*count`.
+#[no_mangle]
+pub unsafe extern "C" fn tract_model_input_count(
+    model: *const TractModel,
+    count: *mut usize,
+    error: *mut c_char,
+    error_len: usize,
+) -> TractStatus {
+    wrap(error, error_len, || {
+        non_null(model, "model")?;
+        non_null(count, "count")?;
+        *count = (*model).plan.model().input_outlets()?.len();
+        Ok(())
+    })
+}
+
+/// Write the number of outputs of `model` to `*count`.
+#[no_mangle]
+pub unsafe extern "C" fn tract_model_output_count(
+    model: *const TractModel,
+    count: *mut usize,
+    error: *mut c_char,
+    error_len: usize,
+) -> TractStatus {
+    wrap(error, error_len, || {
+        non_null(model, "model")?;
+        non_null(count, "count")?;
+        *count = (*model).plan.model().output_outlets()?.len();
+        Ok(())
+    })
+}
+
+/// Run `model` on `input_count` tensors.
+///
+/// `inputs` are borrowed for the duration of the call. `outputs` must point
+/// to an array of `output_count` slots, matching the number of model
+/// outputs: on success, each slot receives a new tensor to free with
+/// `tract_tensor_free`. On failure, the slots are left untouched.
+#[no_mangle]
+pub unsafe extern "C" fn tract_model_run(
+    model: *const TractModel,
+    inputs: *const *const TractTensor,
+    input_count: usize,
+    outputs: *mut *mut TractTensor,
+    output_count: usize,
+    error: *mut c_char,
+    error_len: usize,
+) -> TractStatus {
+    wrap(error, error_len, || {
+        non_null(model, "model")?;
+        let plan = &(*model).plan;
+        let expected_inputs = plan.model().input_outlets()?.len();
+        if input_count != expected_inputs {
+            return invalid(format!(
+                "Model expects {} inputs, got {}",
+                expected_inputs, input_count
+            ));
+        }
+        let expected_outputs = plan.model().output_outlets()?.len();
+        if output_count != expected_outputs {
+            return invalid(format!(
+                "Model has {} outputs, got room for {}",
+                expected_outputs, output_count
+            ));
+        }
+        if input_count > 0 {
+            non_null(inputs, "inputs")?;
+        }
+        if output_count > 0 {
+            non_null(outputs, "outputs")?;
+        }
+        let mut values = tvec!();
+        for ix in 0..input_count {
+            let input = *inputs.add(ix);
+            non_null(input, &format!("inputs[{}]", ix))?;
+            values.push((*input).0.as_ref().clone());
+        }
+        for (ix, output) in plan.run(values)?.into_iter().enumerate() {
+            *outputs.add(ix) = Box::into_raw(Box::new(TractTensor(output)));
+        }
+        Ok(())
+    })
+}
+
+/// Release a model. Does nothing if `model` is null.
+#[no_mangle]
+pub unsafe extern "C" fn tract_model_free(
+    model: *mut TractModel,
+    error: *mut c_char,
+    error_len: usize,
+) -> TractStatus {
+    wrap(error, error_len, || {
+        if !model.is_null() {
+            drop(Box::from_raw(model));
+        }
+        Ok(())
+    })
+}
+
+/// Create a tensor of `datum_type` and shape `shape[0..rank]`, copying
+/// `data_len` bytes from `data`.
+///
+/// `data_len` must be the product of the dimensions times the size of the
+/// datum type, and `data` is read as a contiguous row-major buffer of native
+/// endianness. `data` may only be null if `data_len` is 0, and `shape` if
+/// `rank` is 0. The caller keeps ownership of `shape` and `data`. On success,
+/// `*tensor` receives a tensor to free with `tract_tensor_free`.
+#[no_mangle]
+pub unsafe extern "C" fn tract_tensor_create(
+    datum_type: TractDatumType,
+    shape: *const usize,
+    rank: usize,
+    data: *const c_void,
+    data_len: usize,
+    tensor: *mut *mut TractTensor,
+    error: *mut c_char,
+    error_len: usize,
+) -> TractStatus {
+    wrap(error, error_len, || {
+        non_null(tensor, "tensor")?;
+        let shape: &[usize] = if rank == 0 {
+            &[]
+        } else {
+            non_null(shape, "shape")?;
+            std::slice::from_raw_parts(shape, rank)
+        };
+        let dt = DatumType::from(datum_type);
+        let expected = shape.iter().product::<usize>() * dt.size_of();
+        if data_len != expected {
+            return invalid(format!(
+                "A {:?} tensor of shape {:?} needs {} bytes of data, got {}",
+                dt, shape, expected, data_len
+            ));
+        }
+        let t = if data_len == 0 {
+            Tensor::uninitialized_dt(dt, shape)?
+        } else {
+            non_null(data, "data")?;
+            let bytes = std::slice::from_raw_parts(data as *const u8, data_len);
+            if dt == DatumType::Bool && bytes.iter().any(|&b| b > 1) {
+                return invalid("Bool tensor data must be 0 or 1".to_string());
+            }
+            Tensor::from_raw_dt(dt, shape, bytes)?
+        };
+        *tensor = Box::into_raw(Box::new(TractTensor(t.into_arc_tensor())));
+        Ok(())
+    })
+}
+
+/// Write the datum type of `tensor` to `*datum_type`.
+#[no_mangle]
+pub unsafe extern "C" fn tract_tensor_datum_type(
+    tensor: *const TractTensor,
+    datum_type: *mut TractDatumType,
+    error: *mut c_char,
+    error_len: usize,
+) -> TractStatus {
+    wrap(error, error_len, || {
+        non_null(tensor, "tensor")?;
+        non_null(datum_type, "datum_type")?;
+        let dt = (*tensor).0.datum_type();
+        match TractDatumType::from_datum_type(dt) {
+            Some(dt) => *datum_type = dt,
+            None => return invalid(format!("{:?} tensors are not supported", dt)),
+        }
+        Ok(())
+    })
+}
+
+/// Write the rank of `tensor` to `*rank`, and a pointer to its `rank`
+/// dimensions to `*shape`.
+///
+/// The dimensions belong to the tensor.
+#[no_mangle]
+pub unsafe extern "C" fn tract_tensor_shape(
+    tensor: *const TractTensor,
+    shape: *mut *const usize,
+    rank: *mut usize,
+    error: *mut c_char,
+    error_len: usize,
+) -> TractStatus {
+    wrap(error, error_len, || {
+        non_null(tensor, "tensor")?;
+        non_null(shape, "shape")?;
+        non_null(rank, "rank")?;
+        *shape = (*tensor).0.shape().as_ptr();
+        *rank = (*tensor).0.rank();
+        Ok(())
+    })
+}
+
+/// Write a pointer to the data of `tensor` to `*data`, and its size in bytes
+/// to `*data_len`.
+///
+/// The data belongs to the tensor, and is laid out as described in
+/// `tract_tensor_create`. `*data` may be null for an empty tensor.
+#[no_mangle]
+pub unsafe extern "C" fn tract_tensor_data_ptr(
+    tensor: *const TractTensor,
+    data: *mut *const c_void,
+    data_len: *mut usize,
+    error: *mut c_char,
+    error_len: usize,
+) -> TractStatus {
+    wrap(error, error_len, || {
+        non_null(tensor, "tensor")?;
+        non_null(data, "data")?;
+        non_null(data_len, "data_len")?;
+        let t = &(*tensor).0;
+        if TractDatumType::from_datum_type(t.datum_type()).is_none() {
+            return invalid(format!("{:?} tensors are not supported", t.datum_type()));
+        }
+        let bytes = t.as_bytes();
+        *data = bytes.as_ptr() as *const c_void;
+        *data_len = bytes.len();
+        Ok(())
+    })
+}
+
+/// Release a tensor. Does nothing if `tensor` is null.
+#[no_mangle]
+pub unsafe extern "C" fn tract_tensor_free(
+    tensor: *mut TractTensor,
+    error: *mut c_char,
+    error_len: usize,
+) -> TractStatus {
+    wrap(error, error_len, || {
+        if !tensor.is_null() {
+            drop(Box::from_raw(tensor));
+        }
+        Ok(())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::ffi::CString;
+    use std::os::raw::c_int;
+    use std::ptr::{null, null_mut};
+    use tract_core::ops::math;
+
+    #[link(name = "tract_c_api_test", kind = "static")]
+    extern "C" {
+        fn c_api_test(model_path: *const c_char) -> c_int;
+    }
+
+    // y = 2x + 1, on 1x3 matrices
+    fn model_path() -> TractResult<CString> {
+        let mut model = TypedModel::default();
+        let fact = TypedFact::dt_shape(f32::datum_type(), [1usize, 3].as_ref())?;
+        let x = model.add_source("x", fact)?;
+        let x = model.wire_node("mul", math::mul::unary(rctensor0(2f32)), &[x])?;
+        let y = model.wire_node("add", math::add::unary(rctensor0(1f32)), &x)?;
+        model.set_output_outlets(&y)?;
+        let dir = std::env::temp_dir().join(format!("tract-c-api-{}", std::process::id()));
+        std::fs::create_dir_all(&dir)?;
+        let path = dir.join("model.onnx");
+        tract_onnx::export::export_to_onnx(&model, &path)?;
+        Ok(CString::new(path.to_str().unwrap()).unwrap())
+    }
+
+    #[test]
+    fn c() -> TractResult<()> {
+        let path = model_path()?;
+        assert_eq!(unsafe { c_api_test(path.as_ptr()) }, 0);
+        Ok(())
+    }
+
+    #[test]
+    fn errors() {
+        let mut error = [0 as c_char; 16];
+        let mut tensor = null_mut();
+        let status = unsafe {
+            tract_tensor_create(
+                TractDatumType::F32,
+                null(),
+                1,
+                null(),
+                0,
+                &mut tensor,
+                error.as_mut_ptr(),
+                error.len(),
+            )
+        };
+        assert_eq!(status, TractStatus::NullPointer);
+        let msg = unsafe { CStr::from_ptr(error.as_ptr()) };
+        assert_eq!(msg.to_str().unwrap(), "`shape` is null");
+
+        let shape = [2usize];
+        let data = [1u8, 2, 3];
+        let status = unsafe {
+            tract_tensor_create(
+                TractDatumType::Bool,
+                shape.as_ptr(),
+                1,
+                data.as_ptr() as _,
+                data.len(),
+                &mut tensor,
+                error.as_mut_ptr(),
+                error.len(),
+            )
+        };
+        assert_eq!(status, TractStatus::InvalidArgument);
+        // truncated to the buffer
+        let msg = unsafe { CStr::from_ptr(error.as_ptr()) };
+        assert_eq!(msg.to_str().unwrap(), "A Bool tensor o");
+        assert!(tensor.is_null());
+    }
+
+    #[test]
+    fn header_is_up_to_date() {
+        let generated = std::fs::read_to_string(concat!(env!("OUT_DIR"), "/tract.h")).unwrap();
+        let checked_in =
+            std::fs::read_to_string(concat!(env!("CARGO_MANIFEST_DIR"), "/tract.h")).unwrap();
+        assert!(generated == checked_in, "tract.h is stale, copy it from {}", env!("OUT_DIR"));
+    }
+}
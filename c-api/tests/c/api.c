@@ -0,0 +1,75 @@
+#include <stdio.h>
+#include <string.h>
+
+#include "tract.h"
+
+#define CHECK(call)                                                            \
+    do {                                                                       \
+        TractStatus status = (call);                                           \
+        if (status != TRACT_STATUS_OK) {                                       \
+            fprintf(stderr, "%s:%d: %s failed (%d): %s\n", __FILE__, __LINE__, \
+                    #call, status, error);                                     \
+            goto done;                                                         \
+        }                                                                      \
+    } while (0)
+
+/* Runs the model at model_path, expected to compute 2x+1 on 1x3 f32
+ * matrices. Returns 0 on success. */
+int c_api_test(const char *model_path) {
+    char error[256] = "";
+    int result = 1;
+    TractModel *model = NULL;
+    TractTensor *input = NULL;
+    TractTensor *output = NULL;
+
+    CHECK(tract_model_load_onnx(model_path, &model, error, sizeof(error)));
+
+    uintptr_t count = 0;
+    CHECK(tract_model_input_count(model, &count, error, sizeof(error)));
+    if (count != 1) {
+        fprintf(stderr, "expected 1 input, got %lu\n", (unsigned long)count);
+        goto done;
+    }
+
+    uintptr_t shape[2] = {1, 3};
+    float x[3] = {1.0f, 2.0f, 3.0f};
+    CHECK(tract_tensor_create(TRACT_DATUM_TYPE_F32, shape, 2, x, sizeof(x),
+                              &input, error, sizeof(error)));
+
+    /* wrong number of inputs is reported, not fatal */
+    if (tract_model_run(model, NULL, 0, &output, 1, error, sizeof(error)) !=
+            TRACT_STATUS_INVALID_ARGUMENT ||
+        strstr(error, "expects 1 inputs") == NULL) {
+        fprintf(stderr, "input count not checked: %s\n", error);
+        goto done;
+    }
+
+    const TractTensor *inputs[1] = {input};
+    CHECK(tract_model_run(model, inputs, 1, &output, 1, error, sizeof(error)));
+
+    TractDatumType dt;
+    CHECK(tract_tensor_datum_type(output, &dt, error, sizeof(error)));
+    const uintptr_t *output_shape = NULL;
+    uintptr_t rank = 0;
+    CHECK(tract_tensor_shape(output, &output_shape, &rank, error, sizeof(error)));
+    const void *data = NULL;
+    uintptr_t len = 0;
+    CHECK(tract_tensor_data_ptr(output, &data, &len, error, sizeof(error)));
+    if (dt != TRACT_DATUM_TYPE_F32 || rank != 2 || output_shape[0] != 1 ||
+        output_shape[1] != 3 || len != 3 * sizeof(float)) {
+        fprintf(stderr, "unexpected output type or shape\n");
+        goto done;
+    }
+    const float *y = data;
+    if (y[0] != 3.0f || y[1] != 5.0f || y[2] != 7.0f) {
+        fprintf(stderr, "unexpected output %f %f %f\n", y[0], y[1], y[2]);
+        goto done;
+    }
+    result = 0;
+
+done:
+    tract_tensor_free(output, NULL, 0);
+    tract_tensor_free(input, NULL, 0);
+    tract_model_free(model, NULL, 0);
+    return result;
+}
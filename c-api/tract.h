@@ -0,0 +1,188 @@
+/*
+ * C API for tract.
+ *
+ * Every function returns a TractStatus. On failure, a description of the
+ * error is written to `error` (if not NULL), truncated to `error_len` bytes
+ * including the terminating NUL.
+ *
+ * Models and tensors returned by tract_model_load_onnx, tract_tensor_create
+ * and tract_model_run belong to the caller, who releases them with
+ * tract_model_free and tract_tensor_free. Pointers obtained from a tensor
+ * borrow from it, and are valid until it is freed.
+ */
+
+#ifndef TRACT_H
+#define TRACT_H
+
+/* Generated by cbindgen from the tract-c-api sources. Do not edit. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/*
+ Element types of tensors going through the API.
+ */
+typedef enum TractDatumType {
+  TRACT_DATUM_TYPE_BOOL,
+  TRACT_DATUM_TYPE_U8,
+  TRACT_DATUM_TYPE_U16,
+  TRACT_DATUM_TYPE_I8,
+  TRACT_DATUM_TYPE_I16,
+  TRACT_DATUM_TYPE_I32,
+  TRACT_DATUM_TYPE_I64,
+  TRACT_DATUM_TYPE_F32,
+  TRACT_DATUM_TYPE_F64,
+} TractDatumType;
+
+/*
+ Outcome of a call.
+ */
+typedef enum TractStatus {
+  /*
+   Success.
+   */
+  TRACT_STATUS_OK = 0,
+  /*
+   A required pointer argument was null.
+   */
+  TRACT_STATUS_NULL_POINTER = 1,
+  /*
+   An argument was inconsistent: wrong count, size, or encoding.
+   */
+  TRACT_STATUS_INVALID_ARGUMENT = 2,
+  /*
+   tract failed loading or running the model.
+   */
+  TRACT_STATUS_ERROR = 3,
+  /*
+   tract panicked. State of the objects involved is unspecified.
+   */
+  TRACT_STATUS_PANIC = 4,
+} TractStatus;
+
+/*
+ An optimized model, ready to run.
+ */
+typedef struct TractModel TractModel;
+
+/*
+ A tensor, immutable once created.
+ */
+typedef struct TractTensor TractTensor;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/*
+ Load an ONNX model from the file at `path`, and optimize it.
+
+ The shapes of the model inputs must be fully determined by the file. On
+ success, `*model` receives a model to free with `tract_model_free`.
+ */
+enum TractStatus tract_model_load_onnx(const char *path,
+                                       struct TractModel **model,
+                                       char *error,
+                                       uintptr_t error_len);
+
+/*
+ Write the number of inputs of `model` to `*count`.
+ */
+enum TractStatus tract_model_input_count(const struct TractModel *model,
+                                         uintptr_t *count,
+                                         char *error,
+                                         uintptr_t error_len);
+
+/*
+ Write the number of outputs of `model` to `*count`.
+ */
+enum TractStatus tract_model_output_count(const struct TractModel *model,
+                                          uintptr_t *count,
+                                          char *error,
+                                          uintptr_t error_len);
+
+/*
+ Run `model` on `input_count` tensors.
+
+ `inputs` are borrowed for the duration of the call. `outputs` must point
+ to an array of `output_count` slots, matching the number of model
+ outputs: on success, each slot receives a new tensor to free with
+ `tract_tensor_free`. On failure, the slots are left untouched.
+ */
+enum TractStatus tract_model_run(const struct TractModel *model,
+                                 const struct TractTensor *const *inputs,
+                                 uintptr_t input_count,
+                                 struct TractTensor **outputs,
+                                 uintptr_t output_count,
+                                 char *error,
+                                 uintptr_t error_len);
+
+/*
+ Release a model. Does nothing if `model` is null.
+ */
+enum TractStatus tract_model_free(struct TractModel *model, char *error, uintptr_t error_len);
+
+/*
+ Create a tensor of `datum_type` and shape `shape[0..rank]`, copying
+ `data_len` bytes from `data`.
+
+ `data_len` must be the product of the dimensions times the size of the
+ datum type, and `data` is read as a contiguous row-major buffer of native
+ endianness. `data` may only be null if `data_len` is 0, and `shape` if
+ `rank` is 0. The caller keeps ownership of `shape` and `data`. On success,
+ `*tensor` receives a tensor to free with `tract_tensor_free`.
+ */
+enum TractStatus tract_tensor_create(enum TractDatumType datum_type,
+                                     const uintptr_t *shape,
+                                     uintptr_t rank,
+                                     const void *data,
+                                     uintptr_t data_len,
+                                     struct TractTensor **tensor,
+                                     char *error,
+                                     uintptr_t error_len);
+
+/*
+ Write the datum type of `tensor` to `*datum_type`.
+ */
+enum TractStatus tract_tensor_datum_type(const struct TractTensor *tensor,
+                                         enum TractDatumType *datum_type,
+                                         char *error,
+                                         uintptr_t error_len);
+
+/*
+ Write the rank of `tensor` to `*rank`, and a pointer to its `rank`
+ dimensions to `*shape`.
+
+ The dimensions belong to the tensor.
+ */
+enum TractStatus tract_tensor_shape(const struct TractTensor *tensor,
+                                    const uintptr_t **shape,
+                                    uintptr_t *rank,
+                                    char *error,
+                                    uintptr_t error_len);
+
+/*
+ Write a pointer to the data of `tensor` to `*data`, and its size in bytes
+ to `*data_len`.
+
+ The data belongs to the tensor, and is laid out as described in
+ `tract_tensor_create`. `*data` may be null for an empty tensor.
+ */
+enum TractStatus tract_tensor_data_ptr(const struct TractTensor *tensor,
+                                       const void **data,
+                                       uintptr_t *data_len,
+                                       char *error,
+                                       uintptr_t error_len);
+
+/*
+ Release a tensor. Does nothing if `tensor` is null.
+ */
+enum TractStatus tract_tensor_free(struct TractTensor *tensor, char *error, uintptr_t error_len);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* TRACT_H */
@@ -0,0 +1,67 @@
+#[macro_use]
+extern crate criterion;
+extern crate tract_core;
+use criterion::{Criterion, Throughput};
+
+use tract_core::internal::*;
+use tract_core::ops::binary::TypedBinOp;
+use tract_core::ops::cnn::{Conv, PaddingSpec};
+use tract_core::ops::math;
+
+const BATCH: usize = 8;
+const C: usize = 32;
+const HW: usize = 32;
+
+// stack of residual blocks: conv, bias, relu, conv, bias, add, relu
+fn resnet(blocks: usize) -> TypedModel {
+    let mut model = TypedModel::default();
+    let fact = TypedFact::dt_shape(f32::datum_type(), [1, C, HW, HW].as_ref()).unwrap();
+    let mut x = model.add_source("input", fact).unwrap();
+    let kernel = Tensor::from(ndarray::Array4::<f32>::from_elem((C, C, 3, 3), 0.01));
+    for b in 0..blocks {
+        let residual = x;
+        for c in 0..2 {
+            let name = format!("block{}.{}", b, c);
+            let conv = Conv::default().padding(PaddingSpec::SameUpper).kernel_shape(tvec!(3, 3));
+            let conv = conv
+                .to_unary(&[model.outlet_fact(x).unwrap(), &TypedFact::from(kernel.clone())])
+                .unwrap()
+                .unwrap();
+            x = model.wire_node(format!("{}.conv", name), conv, &[x]).unwrap()[0];
+            let bias = math::add::unary(rctensor3(&[[[0.1f32]]; C]));
+            x = model.wire_node(format!("{}.bias", name), bias, &[x]).unwrap()[0];
+            if c == 1 {
+                let add = TypedBinOp(Box::new(math::Add));
+                x = model.wire_node(format!("{}.residual", name), add, &[residual, x]).unwrap()[0];
+            }
+            let relu = math::max::unary(rctensor0(0f32));
+            x = model.wire_node(format!("{}.relu", name), relu, &[x]).unwrap()[0];
+        }
+    }
+    model.set_output_outlets(&[x]).unwrap();
+    model.into_optimized().unwrap()
+}
+
+fn batch() -> Vec<TVec<Tensor>> {
+    (0..BATCH)
+        .map(|i| tvec!(Tensor::from(ndarray::Array4::<f32>::from_elem((1, C, HW, HW), i as f32))))
+        .collect()
+}
+
+fn batch_parallel(c: &mut Criterion) {
+    let plan = SimplePlan::new(resnet(4)).unwrap();
+    let mut group = c.benchmark_group("resnet_batch_8");
+    group.throughput(Throughput::Elements(BATCH as u64));
+    group.bench_function("sequential", |b| {
+        b.iter_with_setup(batch, |inputs| {
+            inputs.into_iter().map(|i| plan.run(i).unwrap()).collect::<Vec<_>>()
+        })
+    });
+    group.bench_function("parallel", |b| {
+        b.iter_with_setup(batch, |inputs| plan.run_batch_parallel(inputs).unwrap())
+    });
+    group.finish();
+}
+
+criterion_group!(benches, batch_parallel);
+criterion_main!(benches);
@@ -0,0 +1,45 @@
+#[macro_use]
+extern crate criterion;
+extern crate tract_core;
+use criterion::{Criterion, Throughput};
+
+use tract_core::internal::*;
+use tract_core::ops::cnn::{Conv, ConvAlgorithmSelector, PaddingSpec};
+
+const C: usize = 64;
+const HW: usize = 56;
+
+// 3x3, 64 to 64 channels, NCHW, same padding
+fn plan(algorithm: ConvAlgorithmSelector) -> SimplePlan<TypedFact, Box<dyn TypedOp>, TypedModel> {
+    let kernel = Tensor::from(ndarray::Array4::<f32>::from_elem((C, C, 3, 3), 0.01));
+    let image = TypedFact::dt_shape(f32::datum_type(), [1, C, HW, HW].as_ref()).unwrap();
+    let conv = Conv::default()
+        .padding(PaddingSpec::SameUpper)
+        .kernel_shape(tvec!(3, 3))
+        .algorithm(algorithm);
+    let unary = conv.to_unary(&[&image, &TypedFact::from(kernel)]).unwrap().unwrap();
+    let mut model = TypedModel::default();
+    let input = model.add_source("input", image).unwrap();
+    let output = model.wire_node("conv", unary, &[input]).unwrap();
+    model.set_output_outlets(&output).unwrap();
+    SimplePlan::new(model.into_optimized().unwrap()).unwrap()
+}
+
+fn conv3x3(c: &mut Criterion) {
+    let mut group = c.benchmark_group("conv3x3_64");
+    group.throughput(Throughput::Elements((HW * HW * C * C * 9) as u64));
+    let image = Tensor::from(ndarray::Array4::<f32>::from_elem((1, C, HW, HW), 1.0));
+    for &(name, algorithm) in &[
+        ("gemm", ConvAlgorithmSelector::Gemm),
+        ("winograd", ConvAlgorithmSelector::Winograd),
+        ("fft", ConvAlgorithmSelector::Fft),
+        ("auto", ConvAlgorithmSelector::Auto),
+    ] {
+        let plan = plan(algorithm);
+        group.bench_function(name, |b| b.iter(|| plan.run(tvec!(image.clone())).unwrap()));
+    }
+    group.finish();
+}
+
+criterion_group!(benches, conv3x3);
+criterion_main!(benches);
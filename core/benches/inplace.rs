@@ -0,0 +1,115 @@
+#[macro_use]
+extern crate criterion;
+extern crate tract_core;
+use criterion::Criterion;
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use tract_core::internal::*;
+use tract_core::ops::binary::{TypedBinOp, UnaryOp};
+use tract_core::ops::cnn::{Conv, PaddingSpec};
+use tract_core::ops::math;
+
+struct CountingAllocator;
+
+static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATED.fetch_add(layout.size(), Ordering::Relaxed);
+        System.alloc(layout)
+    }
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+const C: usize = 16;
+const HW: usize = 32;
+
+fn wire_unary(model: &mut TypedModel, name: &str, op: UnaryOp, input: OutletId) -> OutletId {
+    model.wire_node(name, op, &[input]).unwrap()[0]
+}
+
+// conv, per-channel scale and shift, relu, stack of residual blocks
+fn resnet(blocks: usize) -> TypedModel {
+    let mut model = TypedModel::default();
+    let fact = TypedFact::dt_shape(f32::datum_type(), [1, C, HW, HW].as_ref()).unwrap();
+    let mut x = model.add_source("input", fact.clone()).unwrap();
+    let kernel = Tensor::from(ndarray::Array4::<f32>::from_elem((C, C, 3, 3), 0.01));
+    let channel = rctensor3(&[[[0.5f32]]; C]);
+    for b in 0..blocks {
+        let residual = x;
+        for c in 0..2 {
+            let name = format!("block{}.{}", b, c);
+            let conv = Conv::default().padding(PaddingSpec::SameUpper).kernel_shape(tvec!(3, 3));
+            let conv = conv
+                .to_unary(&[model.outlet_fact(x).unwrap(), &TypedFact::from(kernel.clone())])
+                .unwrap()
+                .unwrap();
+            x = model.wire_node(format!("{}.conv", name), conv, &[x]).unwrap()[0];
+            x = wire_unary(
+                &mut model,
+                &format!("{}.scale", name),
+                math::mul::unary(channel.clone()),
+                x,
+            );
+            x = wire_unary(
+                &mut model,
+                &format!("{}.shift", name),
+                math::add::unary(channel.clone()),
+                x,
+            );
+            if c == 1 {
+                x = model
+                    .wire_node(
+                        format!("{}.residual", name),
+                        TypedBinOp(Box::new(math::Add)),
+                        &[residual, x],
+                    )
+                    .unwrap()[0];
+            }
+            x = wire_unary(
+                &mut model,
+                &format!("{}.relu", name),
+                math::max::unary(rctensor0(0f32)),
+                x,
+            );
+        }
+    }
+    model.set_output_outlets(&[x]).unwrap();
+    model.into_optimized().unwrap()
+}
+
+fn allocated_per_run(plan: &SimplePlan<TypedFact, Box<dyn TypedOp>, TypedModel>) -> usize {
+    let input = Tensor::from(ndarray::Array4::<f32>::from_elem((1, C, HW, HW), 1.0));
+    let before = ALLOCATED.load(Ordering::Relaxed);
+    plan.run(tvec!(input)).unwrap();
+    ALLOCATED.load(Ordering::Relaxed) - before
+}
+
+fn inplace(c: &mut Criterion) {
+    let model = resnet(4);
+    let mut inplace_model = model.clone();
+    let flagged = tract_core::passes::set_inplace(&mut inplace_model).unwrap();
+    let plan = SimplePlan::new(model).unwrap();
+    let inplace_plan = SimplePlan::new(inplace_model).unwrap();
+    eprintln!(
+        "{} ops in place, bytes allocated per run: {} without, {} with",
+        flagged,
+        allocated_per_run(&plan),
+        allocated_per_run(&inplace_plan)
+    );
+    let input = Tensor::from(ndarray::Array4::<f32>::from_elem((1, C, HW, HW), 1.0));
+    let mut group = c.benchmark_group("resnet_blocks");
+    group.bench_function("copy", |b| b.iter(|| plan.run(tvec!(input.clone())).unwrap()));
+    group.bench_function("inplace", |b| b.iter(|| inplace_plan.run(tvec!(input.clone())).unwrap()));
+    group.finish();
+}
+
+criterion_group!(benches, inplace);
+criterion_main!(benches);
@@ -0,0 +1,43 @@
+#[macro_use]
+extern crate criterion;
+extern crate tract_core;
+use criterion::Criterion;
+
+use tract_core::internal::*;
+use tract_core::ops::matmul::MatMul;
+use tract_core::ops::quant::Dequantize4Bit;
+
+const K: usize = 1024;
+const N: usize = 1024;
+
+// one token through a K x N linear layer, with I4 weights
+fn model(fuse: bool) -> TypedModel {
+    let mut model = TypedModel::default();
+    let fact = TypedFact::dt_shape(f32::datum_type(), [1, K].as_ref()).unwrap();
+    let a = model.add_source("a", fact).unwrap();
+    let bytes: Vec<u8> = (0..K * N / 2).map(|ix| (ix * 37 % 256) as u8).collect();
+    let weights = Tensor::from_i4_packed_bytes(&bytes, &[K, N]).unwrap();
+    let w = if fuse {
+        let w = model.add_const("w", weights).unwrap();
+        let op = Dequantize4Bit::new(0.01, 0, f32::datum_type());
+        model.wire_node("dequant", op, &[w]).unwrap()[0]
+    } else {
+        model.add_const("w", weights.dequantize_i4(0.01, 0).unwrap()).unwrap()
+    };
+    let c = model.wire_node("mm", MatMul::default(), &[a, w]).unwrap();
+    model.set_output_outlets(&c).unwrap();
+    model.into_optimized().unwrap()
+}
+
+fn matmul_4bit(c: &mut Criterion) {
+    let input = || Tensor::from(ndarray::Array2::<f32>::from_elem((1, K), 0.5));
+    let fused = SimplePlan::new(model(true)).unwrap();
+    let f32_weights = SimplePlan::new(model(false)).unwrap();
+    let mut group = c.benchmark_group("matmul_4bit");
+    group.bench_function("fused_i4", |b| b.iter(|| fused.run(tvec!(input())).unwrap()));
+    group.bench_function("f32_weights", |b| b.iter(|| f32_weights.run(tvec!(input())).unwrap()));
+    group.finish();
+}
+
+criterion_group!(benches, matmul_4bit);
+criterion_main!(benches);
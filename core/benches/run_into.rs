@@ -0,0 +1,42 @@
+#[macro_use]
+extern crate criterion;
+extern crate tract_core;
+use criterion::Criterion;
+
+use tract_core::internal::*;
+use tract_core::ops::math;
+
+const FRAME: usize = 4096;
+
+// gain, soft clip and offset on one audio frame
+fn plan() -> SimplePlan<TypedFact, Box<dyn TypedOp>, TypedModel> {
+    let mut model = TypedModel::default();
+    let fact = TypedFact::dt_shape(f32::datum_type(), [1, FRAME].as_ref()).unwrap();
+    let x = model.add_source("frame", fact).unwrap();
+    let x = model.wire_node("gain", math::mul::unary(rctensor0(0.8f32)), &[x]).unwrap();
+    let x = model.wire_node("clip", math::tanh(), &x).unwrap();
+    let x = model.wire_node("offset", math::add::unary(rctensor0(0.1f32)), &x).unwrap();
+    model.set_output_outlets(&x).unwrap();
+    SimplePlan::new(model.into_optimized().unwrap()).unwrap()
+}
+
+fn realtime_loop(c: &mut Criterion) {
+    let plan = plan();
+    let frame = Tensor::from(ndarray::Array2::<f32>::from_elem((1, FRAME), 0.5));
+    let mut group = c.benchmark_group("realtime_frame");
+    group.bench_function("run", |b| {
+        let mut buffer = vec![0f32; FRAME];
+        b.iter(|| {
+            let result = plan.run(tvec!(frame.clone())).unwrap();
+            buffer.copy_from_slice(result[0].as_slice::<f32>().unwrap());
+        })
+    });
+    group.bench_function("run_into", |b| {
+        let mut outputs = plan.allocate_outputs().unwrap();
+        b.iter(|| plan.run_into(tvec!(frame.clone()), &mut outputs).unwrap())
+    });
+    group.finish();
+}
+
+criterion_group!(benches, realtime_loop);
+criterion_main!(benches);
@@ -0,0 +1,46 @@
+#[macro_use]
+extern crate criterion;
+extern crate tract_core;
+use criterion::Criterion;
+
+use std::io::{Read, Write};
+use std::path::PathBuf;
+
+use tract_core::internal::*;
+
+const LEN: usize = 500 * 1024 * 1024;
+
+// a 500MB weight file
+fn weights() -> PathBuf {
+    let path = std::env::temp_dir().join("tract-bench-weights.bin");
+    if std::fs::metadata(&path).map(|m| m.len() as usize != LEN).unwrap_or(true) {
+        let mut file = std::fs::File::create(&path).unwrap();
+        let chunk = vec![1u8; 1024 * 1024];
+        for _ in 0..LEN / chunk.len() {
+            file.write_all(&chunk).unwrap();
+        }
+    }
+    path
+}
+
+fn load(c: &mut Criterion) {
+    let path = weights();
+    let shape = [LEN / 4];
+    let mut group = c.benchmark_group("load_500MB");
+    group.sample_size(10);
+    let p = path.clone();
+    group.bench_function("heap", move |b| {
+        b.iter(|| {
+            let mut buffer = vec![];
+            std::fs::File::open(&p).unwrap().read_to_end(&mut buffer).unwrap();
+            unsafe { Tensor::from_raw_dt(f32::datum_type(), &shape, &buffer).unwrap() }
+        })
+    });
+    group.bench_function("mmap", move |b| {
+        b.iter(|| Tensor::from_mmap(&path, 0, &shape, f32::datum_type()).unwrap())
+    });
+    group.finish();
+}
+
+criterion_group!(benches, load);
+criterion_main!(benches);
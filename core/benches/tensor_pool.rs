@@ -0,0 +1,93 @@
+#[macro_use]
+extern crate criterion;
+extern crate tract_core;
+use criterion::Criterion;
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use tract_core::internal::*;
+use tract_core::memory::TensorPool;
+use tract_core::ops::cnn::{Conv, PaddingSpec};
+use tract_core::ops::math;
+use tract_core::plan::SimplePlanOptions;
+
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        ALLOCATED.fetch_add(layout.size(), Ordering::Relaxed);
+        System.alloc(layout)
+    }
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+const C: usize = 16;
+const HW: usize = 32;
+
+type Plan = SimplePlan<TypedFact, Box<dyn TypedOp>, TypedModel>;
+
+// a few conv, bias and relu layers applied to each incoming frame
+fn model() -> TypedModel {
+    let mut model = TypedModel::default();
+    let fact = TypedFact::dt_shape(f32::datum_type(), [1, C, HW, HW].as_ref()).unwrap();
+    let mut x = model.add_source("frame", fact).unwrap();
+    let kernel = Tensor::from(ndarray::Array4::<f32>::from_elem((C, C, 3, 3), 0.01));
+    for layer in 0..4 {
+        let conv = Conv::default().padding(PaddingSpec::SameUpper).kernel_shape(tvec!(3, 3));
+        let conv = conv
+            .to_unary(&[model.outlet_fact(x).unwrap(), &TypedFact::from(kernel.clone())])
+            .unwrap()
+            .unwrap();
+        x = model.wire_node(format!("conv{}", layer), conv, &[x]).unwrap()[0];
+        let bias = math::add::unary(rctensor3(&[[[0.1f32]]; C]));
+        x = model.wire_node(format!("bias{}", layer), bias, &[x]).unwrap()[0];
+        let relu = math::max::unary(rctensor0(0f32));
+        x = model.wire_node(format!("relu{}", layer), relu, &[x]).unwrap()[0];
+    }
+    model.set_output_outlets(&[x]).unwrap();
+    model.into_optimized().unwrap()
+}
+
+fn frame() -> Tensor {
+    Tensor::from(ndarray::Array4::<f32>::from_elem((1, C, HW, HW), 1.0))
+}
+
+// allocations and bytes allocated by one streaming run, after a warm up run
+fn allocations_per_run(plan: &Plan) -> (usize, usize) {
+    plan.run(tvec!(frame())).unwrap();
+    let input = frame();
+    let (count, bytes) = (ALLOCATIONS.load(Ordering::Relaxed), ALLOCATED.load(Ordering::Relaxed));
+    plan.run(tvec!(input)).unwrap();
+    (ALLOCATIONS.load(Ordering::Relaxed) - count, ALLOCATED.load(Ordering::Relaxed) - bytes)
+}
+
+fn tensor_pool(c: &mut Criterion) {
+    let model = model();
+    let plan = SimplePlan::new(model.clone()).unwrap();
+    let pool = Arc::new(TensorPool::default());
+    let options = SimplePlanOptions::default().with_tensor_pool(pool.clone());
+    let pooled = SimplePlan::new_with_options(model, options).unwrap();
+    let (count, bytes) = allocations_per_run(&plan);
+    let (pooled_count, pooled_bytes) = allocations_per_run(&pooled);
+    eprintln!(
+        "per run: {} allocations, {} bytes without pool; {} allocations, {} bytes with pool",
+        count, bytes, pooled_count, pooled_bytes
+    );
+    let mut group = c.benchmark_group("streaming_frames");
+    group.bench_function("no_pool", |b| b.iter(|| plan.run(tvec!(frame())).unwrap()));
+    group.bench_function("pool", |b| b.iter(|| pooled.run(tvec!(frame())).unwrap()));
+    group.finish();
+}
+
+criterion_group!(benches, tensor_pool);
+criterion_main!(benches);
@@ -0,0 +1,32 @@
+//! Chain two linear layers, built as separate models, into a single model.
+use tract_core::internal::*;
+use tract_core::ndarray::arr2;
+use tract_core::ops::{math, matmul::MatMulUnary};
+
+/// A model computing `x.w + b`, for `x` of shape [1, k], `w` [k, n] and `b` [n].
+fn linear(name: &str, w: Tensor, b: Tensor) -> TractResult<TypedModel> {
+    let k = w.shape()[0];
+    let mut model = TypedModel::default();
+    let x = model.add_source(name, TypedFact::dt_shape(f32::datum_type(), [1, k].as_ref())?)?;
+    // x.w is computed as (w'.x')'
+    let matmul = MatMulUnary::new(w.into_arc_tensor(), true, true, true, None);
+    let y = model.wire_node(format!("{}.matmul", name), matmul, &[x])?[0];
+    let y =
+        model.wire_node(format!("{}.bias", name), math::add::unary(b.into_arc_tensor()), &[y])?[0];
+    model.set_output_outlets(&[y])?;
+    Ok(model)
+}
+
+fn main() -> TractResult<()> {
+    let first = linear(
+        "first",
+        arr2(&[[1f32, 0.0, 2.0], [0.0, 1.0, -1.0]]).into(),
+        tensor1(&[0.5f32, 0.0, 0.0]),
+    )?;
+    let second = linear("second", arr2(&[[1f32], [1.0], [1.0]]).into(), tensor1(&[-1f32]))?;
+    // output 0 of the first model feeds input 0 of the second one
+    let model = first.chain(second, &[(0, 0)])?.into_optimized()?;
+    let result = SimplePlan::new(&model)?.run(tvec!(arr2(&[[1f32, 2.0]]).into()))?;
+    println!("{:?}", result[0]);
+    Ok(())
+}
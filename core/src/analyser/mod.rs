@@ -138,6 +138,10 @@ impl<M: BorrowMut<InferenceModel>> Analyser<M> {
                 let outputs: TVec<&InferenceFact> = outputs.iter().collect();
                 let observed: TVec<&InferenceFact> = observed.iter().map(|p| &p.1).collect();
 
+                #[cfg(feature = "tracing")]
+                let span = tracing::trace_span!("analyse", node = %self.model.borrow().node(node));
+                #[cfg(feature = "tracing")]
+                let _entered = span.enter();
                 self.model.borrow_mut().node_mut(node).op.infer(inputs, outputs, observed)?
             };
 
@@ -353,16 +353,31 @@ impl<'rules> Solver<'rules> {
         while changed {
             changed = false;
 
-            for (used, rule) in &mut rules {
+            for (ix, (used, rule)) in rules.iter_mut().enumerate() {
                 // Don't try to apply rules which have already been used.
                 if *used {
                     continue;
                 }
 
-                trace!("  Applying rule {:?}", rule);
+                trace!("  Applying rule #{} {:?}", ix, rule);
+                #[cfg(feature = "tracing")]
+                let span = tracing::trace_span!(
+                    "rule",
+                    index = ix,
+                    rule = ?rule,
+                    before = ?context,
+                    after = tracing::field::Empty
+                );
+                #[cfg(feature = "tracing")]
+                let _entered = span.enter();
                 let (step_used, mut step_added) = rule
                     .apply(&mut context)
                     .map_err(|e| format!("Applying rule {:?}: {:}", rule, e))?;
+                #[cfg(feature = "tracing")]
+                {
+                    span.record("after", &tracing::field::debug(&context));
+                    tracing::trace!(used = step_used, added = step_added.len(), "rule applied");
+                }
                 *used |= step_used;
 
                 // There is a change if the rule was used, or if it added new rules.
@@ -757,4 +772,38 @@ mod tests {
 
         assert_eq!(facts, expected);
     }
+
+    #[cfg(feature = "tracing")]
+    #[test]
+    fn rule_firings_are_traced() -> TractResult<()> {
+        use std::io::Write;
+        use std::sync::Mutex;
+
+        #[derive(Clone, Default)]
+        struct Buffer(Arc<Mutex<Vec<u8>>>);
+        impl Write for Buffer {
+            fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+                self.0.lock().unwrap().extend_from_slice(buf);
+                Ok(buf.len())
+            }
+            fn flush(&mut self) -> std::io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let buffer = Buffer::default();
+        let writer = buffer.clone();
+        let subscriber = tracing_subscriber::fmt()
+            .with_max_level(tracing::Level::TRACE)
+            .with_writer(move || writer.clone())
+            .finish();
+        let mut model = InferenceModel::default();
+        let a = model.add_source("a", InferenceFact::dt_shape(f32::datum_type(), tvec!(2, 3)))?;
+        let neg = model.wire_node("neg", crate::ops::math::neg(), &[a])?;
+        model.set_output_outlets(&neg)?;
+        tracing::subscriber::with_default(subscriber, || model.analyse(false))?;
+        let log = String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+        assert!(log.lines().any(|l| l.contains("rule applied") && l.contains("neg")), "{}", log);
+        Ok(())
+    }
 }
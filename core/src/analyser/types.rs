@@ -287,44 +287,13 @@ pub type TypeFact = GenericFact<DatumType>;
 #[derive(Clone, PartialEq)]
 pub struct ShapeFact {
     open: bool,
-    dims: TVec<GenericFact<i32>>,
-    stream: Option<StreamInfo>,
+    dims: TVec<DimFact>,
 }
 
 impl ShapeFact {
     /// Constructs an open shape fact.
     pub fn open(dims: TVec<DimFact>) -> ShapeFact {
-        if let Some((ix, d)) = dims
-            .iter()
-            .enumerate()
-            .find(|(_ix, d)| d.concretize().map(|d| d.is_stream()).unwrap_or(false))
-        {
-            let stream = Some(StreamInfo { axis: ix, len: d.concretize().unwrap() });
-            ShapeFact {
-                open: true,
-                dims: dims
-                    .iter()
-                    .map(|d| match d {
-                        GenericFact::Only(d) if d.is_stream() => GenericFact::Only(-1),
-                        GenericFact::Only(d) => GenericFact::Only(d.to_integer().unwrap()),
-                        GenericFact::Any => GenericFact::Any,
-                    })
-                    .collect(),
-                stream,
-            }
-        } else {
-            ShapeFact {
-                open: true,
-                dims: dims
-                    .iter()
-                    .map(|d| match d {
-                        GenericFact::Only(d) => GenericFact::Only(d.to_integer().unwrap()),
-                        GenericFact::Any => GenericFact::Any,
-                    })
-                    .collect(),
-                stream: None,
-            }
-        }
+        ShapeFact { open: true, dims }
     }
 
     pub fn is_open(&self) -> bool {
@@ -358,26 +327,12 @@ impl ShapeFact {
         if self.dim(i).as_ref() == Some(&fact) {
             return false;
         }
-        match d.to_integer() {
-            Ok(n) => self.dims[i] = GenericFact::Only(n),
-            Err(_) => {
-                self.dims[i] = GenericFact::Only(-1);
-                self.stream = Some(StreamInfo { axis: i, len: d })
-            }
-        }
+        self.dims[i] = fact;
         return true;
     }
 
     pub fn dims(&self) -> impl Iterator<Item = DimFact> {
-        let stream = self.stream.clone();
-        self.dims.clone().into_iter().map(move |d| match d {
-            GenericFact::Only(-1) => {
-                assert!(stream.is_some(), "-1 dim found with no stream. This is a tract bug.");
-                GenericFact::Only(stream.as_ref().unwrap().len.clone())
-            }
-            GenericFact::Only(d) => GenericFact::Only(d.to_dim()),
-            GenericFact::Any => GenericFact::Any,
-        })
+        self.dims.clone().into_iter()
     }
 
     pub fn stream_info(&self) -> TractResult<Option<StreamInfo>> {
@@ -396,10 +351,14 @@ impl ShapeFact {
     }
 
     pub fn as_concrete_finite(&self) -> TractResult<Option<TVec<usize>>> {
-        if !self.is_concrete() || self.stream_info()?.is_some() {
+        if !self.is_concrete() {
             return Ok(None);
         }
-        Ok(Some(self.dims.iter().map(|i| i.concretize().unwrap() as usize).collect()))
+        Ok(self
+            .dims
+            .iter()
+            .map(|d| d.concretize().unwrap().to_integer().ok().map(|d| d as usize))
+            .collect())
     }
 }
 
@@ -488,15 +447,7 @@ impl fmt::Debug for ShapeFact {
             if ix != 0 {
                 write!(formatter, "x")?
             }
-            if let Some(ref stream) = self.stream {
-                if stream.axis == ix {
-                    write!(formatter, "{:?}", stream.len)?;
-                } else {
-                    write!(formatter, "{:?}", d)?;
-                }
-            } else {
-                write!(formatter, "{:?}", d)?;
-            }
+            write!(formatter, "{:?}", d)?;
         }
         if self.open {
             if self.dims.len() == 0 {
@@ -5,6 +5,7 @@ use crate::tensor::Tensor;
 use crate::TractResult;
 use std::{fmt, ops};
 
+use tract_linalg::bf16::bf16;
 use tract_linalg::f16::f16;
 
 mod arrays;
@@ -47,11 +48,16 @@ pub enum DatumType {
     I32,
     I64,
     F16,
+    BF16,
     F32,
     F64,
     TDim,
     Blob,
     String,
+    /// Signed 4-bit integers, two values packed per byte.
+    I4,
+    /// Unsigned 4-bit integers, two values packed per byte.
+    U4,
 }
 
 impl DatumType {
@@ -83,15 +89,20 @@ impl DatumType {
             DatumType::I32 => &[DatumType::I32, DatumType::I64, DatumType::TDim, DatumType::F32],
             DatumType::I64 => &[DatumType::I64, DatumType::TDim, DatumType::F32],
             DatumType::F16 => &[DatumType::F16, DatumType::F32, DatumType::F64],
+            DatumType::BF16 => &[DatumType::BF16, DatumType::F32, DatumType::F64],
             DatumType::F32 => &[DatumType::F32, DatumType::F64],
             DatumType::F64 => &[DatumType::F64],
             DatumType::String => &[DatumType::String],
             DatumType::Blob => &[DatumType::Blob],
             DatumType::TDim => &[DatumType::TDim],
+            DatumType::I4 => &[DatumType::I4],
+            DatumType::U4 => &[DatumType::U4],
         }
     }
 
-    pub fn super_type_for(i: impl IntoIterator<Item = impl std::borrow::Borrow<DatumType>>) -> Option<DatumType> {
+    pub fn super_type_for(
+        i: impl IntoIterator<Item = impl std::borrow::Borrow<DatumType>>,
+    ) -> Option<DatumType> {
         let mut iter = i.into_iter();
         let mut current = match iter.next() {
             None => return None,
@@ -119,16 +130,42 @@ impl DatumType {
 
     pub fn is_integer(&self) -> bool {
         match self {
-            DatumType::U8 |
-            DatumType::U16 |
-            DatumType::I8 |
-            DatumType::I16 |
-            DatumType::I32 |
-            DatumType::I64 => true,
-            _ => false
+            DatumType::U8
+            | DatumType::U16
+            | DatumType::I8
+            | DatumType::I16
+            | DatumType::I32
+            | DatumType::I64 => true,
+            _ => false,
         }
     }
 
+    pub fn is_float(&self) -> bool {
+        match self {
+            DatumType::F16 | DatumType::BF16 | DatumType::F32 | DatumType::F64 => true,
+            _ => false,
+        }
+    }
+
+    /// Whether several values of this type share a byte.
+    pub fn is_packed(&self) -> bool {
+        match self {
+            DatumType::I4 | DatumType::U4 => true,
+            _ => false,
+        }
+    }
+
+    /// Number of bytes needed to store `len` values of this type.
+    pub fn storage_size(&self, len: usize) -> usize {
+        if self.is_packed() {
+            (len + 1) / 2
+        } else {
+            len * self.size_of()
+        }
+    }
+
+    /// Size of a value in bytes. Packed types report a full byte: use
+    /// `storage_size` to size buffers.
     pub fn size_of(&self) -> usize {
         match self {
             DatumType::Bool => std::mem::size_of::<bool>(),
@@ -139,11 +176,13 @@ impl DatumType {
             DatumType::I32 => std::mem::size_of::<i32>(),
             DatumType::I64 => std::mem::size_of::<i64>(),
             DatumType::F16 => std::mem::size_of::<f16>(),
+            DatumType::BF16 => std::mem::size_of::<bf16>(),
             DatumType::F32 => std::mem::size_of::<f32>(),
             DatumType::F64 => std::mem::size_of::<f64>(),
             DatumType::Blob => std::mem::size_of::<Blob>(),
             DatumType::TDim => std::mem::size_of::<TDim>(),
             DatumType::String => std::mem::size_of::<String>(),
+            DatumType::I4 | DatumType::U4 => 1,
         }
     }
 
@@ -156,6 +195,17 @@ impl DatumType {
     }
 }
 
+/// Value at `ix` in 4-bit packed data, the first value of each byte being
+/// in the low bits.
+pub fn unpack_4bit(bytes: &[u8], ix: usize, signed: bool) -> i8 {
+    let nibble = (bytes[ix / 2] >> (4 * (ix % 2))) & 0x0f;
+    if signed {
+        ((nibble << 4) as i8) >> 4
+    } else {
+        nibble as i8
+    }
+}
+
 pub trait Datum:
     Clone + Send + Sync + fmt::Debug + fmt::Display + Default + 'static + PartialEq + ArrayDatum
 {
@@ -224,6 +274,8 @@ try_into!(u16, f32);
 
 try_into!(u8, i32);
 try_into!(u16, i32);
+try_into!(u8, i64);
+try_into!(u16, i64);
 
 try_into!(i8, f64);
 try_into!(i16, f64);
@@ -320,6 +372,30 @@ impl TryInto<f16> for f64 {
     }
 }
 
+impl TryInto<f32> for bf16 {
+    fn try_into(&self) -> TractResult<f32> {
+        Ok(self.to_f32())
+    }
+}
+
+impl TryInto<f64> for bf16 {
+    fn try_into(&self) -> TractResult<f64> {
+        Ok(self.to_f64())
+    }
+}
+
+impl TryInto<bf16> for f32 {
+    fn try_into(&self) -> TractResult<bf16> {
+        Ok(bf16::from(*self))
+    }
+}
+
+impl TryInto<bf16> for f64 {
+    fn try_into(&self) -> TractResult<bf16> {
+        Ok(bf16::from(*self))
+    }
+}
+
 impl TryInto<String> for f32 {
     fn try_into(&self) -> TractResult<String> {
         Ok(self.to_string())
@@ -341,6 +417,7 @@ impl TryInto<f32> for String {
 
 datum!(bool, Bool);
 datum!(f16, F16);
+datum!(bf16, BF16);
 datum!(f32, F32);
 datum!(f64, F64);
 datum!(i8, I8);
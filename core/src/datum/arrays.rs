@@ -2,6 +2,7 @@ use crate::dim::TDim;
 use crate::datum::Blob;
 use crate::TractResult;
 use ndarray::*;
+use tract_linalg::bf16::bf16;
 use tract_linalg::f16::f16;
 
 pub trait ArrayDatum: Sized {
@@ -55,6 +56,7 @@ macro_rules! impl_stack_views_by_clone(
 );
 
 impl_stack_views_by_copy!(f16);
+impl_stack_views_by_copy!(bf16);
 impl_stack_views_by_copy!(f32);
 impl_stack_views_by_copy!(f64);
 impl_stack_views_by_copy!(bool);
@@ -12,6 +12,9 @@ mod tree;
 
 use self::stack::Stack;
 use crate::TractResult;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
 
 /// A super-trait for value acting as tensor dimensions in tract.
 ///
@@ -66,8 +69,77 @@ impl DimLike for usize {
     }
 }
 
-/// An arithmetic expression built with integer and the special value S for
-/// the streaming dimension.
+/// A named symbol, standing for a dimension only known at runtime.
+///
+/// Names are identifiers, like `B` or `seq_len`. `S` is reserved for the
+/// streaming dimension.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Symbol(Arc<str>);
+
+static FRESH_SYMBOLS: AtomicUsize = AtomicUsize::new(0);
+
+impl Symbol {
+    /// A symbol distinct from every other one, for an operator output
+    /// dimension only known at runtime.
+    ///
+    /// The name is `prefix` followed by `#` and a counter, so it can not be
+    /// spelled in a parsed dimension.
+    pub fn fresh(prefix: &str) -> Symbol {
+        let id = FRESH_SYMBOLS.fetch_add(1, Ordering::Relaxed);
+        Symbol(format!("{}#{}", prefix, id).into())
+    }
+
+    /// The name of the symbol.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<char> for Symbol {
+    fn from(c: char) -> Symbol {
+        Symbol(c.to_string().into())
+    }
+}
+
+impl<'a> From<&'a str> for Symbol {
+    fn from(s: &'a str) -> Symbol {
+        Symbol(s.into())
+    }
+}
+
+impl From<String> for Symbol {
+    fn from(s: String) -> Symbol {
+        Symbol(s.into())
+    }
+}
+
+impl fmt::Debug for Symbol {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        write!(fmt, "{}", self.0)
+    }
+}
+
+impl fmt::Display for Symbol {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        write!(fmt, "{}", self.0)
+    }
+}
+
+#[cfg(feature = "serialize")]
+impl ::serde::Serialize for Symbol {
+    fn serialize<S>(&self, serializer: S) -> ::std::result::Result<S::Ok, S::Error>
+    where
+        S: ::serde::Serializer,
+    {
+        serializer.serialize_str(&self.0)
+    }
+}
+
+/// An arithmetic expression built with integers and symbols.
+///
+/// `S` is reserved for the streaming dimension, other symbols stand for
+/// dimensions (like a batch size) that are only known at runtime.
+/// Expressions involving only integers are folded as they are built.
 #[derive(Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "serialize", derive(Serialize))]
 pub struct TDim(Stack);
@@ -106,18 +178,38 @@ impl TDim {
         Self::s()
     }
 
-    /// Try to convert the value to an integer, if it does not contains S.
+    /// A symbolic value.
+    pub fn sym(s: impl Into<Symbol>) -> TDim {
+        TDim(Stack::sym(s))
+    }
+
+    /// Try to convert the value to an integer, if it does not contains symbols.
     pub fn as_const(&self) -> Option<i32> {
         self.to_integer().ok()
     }
 
     /// Eval the value for a given value of S.
     pub fn eval(&self, s: i32) -> Option<i32> {
-        self.0.eval(&hashmap!('S' => s)).ok()
+        self.0.eval(&hashmap!('S'.into() => s)).ok()
+    }
+
+    /// Eval the value, substituting the symbols with the given values.
+    pub fn eval_with(&self, values: &HashMap<Symbol, i32>) -> Option<i32> {
+        self.0.eval(values).ok()
+    }
+
+    /// Symbols involved in the value, sorted.
+    pub fn symbols(&self) -> Vec<Symbol> {
+        self.0.symbols()
     }
 
     /// Is the value dependend on S ?
     pub fn is_stream(&self) -> bool {
+        self.symbols().contains(&'S'.into())
+    }
+
+    /// Is the value dependend on any symbol ?
+    pub fn is_symbolic(&self) -> bool {
         self.as_const().is_none()
     }
 
@@ -383,17 +475,24 @@ impl<'a> From<&'a usize> for TDim {
     }
 }
 
+/// Parses an integer, a symbol name, or an integer followed by a symbol name
+/// (`12`, `seq_len`, `4B`).
 impl FromStr for TDim {
     type Err = std::num::ParseIntError;
     fn from_str(s: &str) -> Result<TDim, Self::Err> {
-        if s == "S" {
-            Ok(TDim::s())
-        } else if s.ends_with("S") {
-            let number: String = s.chars().take_while(|c| c.is_digit(10)).collect();
-            let number: i32 = number.parse::<i32>().map(|i| i.into())?;
-            Ok(TDim::s() * number)
+        let split = s.find(|c: char| c.is_ascii_alphabetic() || c == '_').unwrap_or(s.len());
+        let (number, name) = s.split_at(split);
+        if name.len() == 0 {
+            return number.parse::<i32>().map(|i| i.into());
+        }
+        if !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+            // not an identifier, report it as an invalid integer
+            return s.parse::<i32>().map(|i| i.into());
+        }
+        if number.len() == 0 {
+            Ok(TDim::sym(name))
         } else {
-            s.parse::<i32>().map(|i| i.into())
+            Ok(TDim::sym(name) * number.parse::<i32>()?)
         }
     }
 }
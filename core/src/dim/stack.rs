@@ -1,4 +1,5 @@
 use super::tree::ExpNode;
+use super::Symbol;
 use crate::model::TVec;
 use crate::TractResult;
 use std::collections::HashMap;
@@ -7,10 +8,10 @@ use std::{fmt, ops};
 #[derive(Clone)]
 pub struct Stack(TVec<StackOp>);
 
-#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
 #[cfg_attr(feature = "serialize", derive(Serialize))]
 pub enum StackOp {
-    Sym(char),
+    Sym(Symbol),
     Val(i32),
     Neg,
     Add,
@@ -52,11 +53,11 @@ impl Stack {
         Stack(tvec!())
     }
 
-    pub fn sym(s: char) -> Stack {
-        Stack(tvec!(StackOp::Sym(s)))
+    pub fn sym(s: impl Into<Symbol>) -> Stack {
+        Stack(tvec!(StackOp::Sym(s.into())))
     }
 
-    pub fn eval(&self, values: &HashMap<char, i32>) -> TractResult<i32> {
+    pub fn eval(&self, values: &HashMap<Symbol, i32>) -> TractResult<i32> {
         use self::StackOp::*;
         let mut stack = tvec![];
         for op in self.as_ops().iter() {
@@ -101,6 +102,17 @@ impl Stack {
         Ok(stack[0])
     }
 
+    pub fn symbols(&self) -> Vec<Symbol> {
+        let mut symbols: Vec<Symbol> = self
+            .as_ops()
+            .iter()
+            .filter_map(|op| if let StackOp::Sym(c) = op { Some(c.clone()) } else { None })
+            .collect();
+        symbols.sort();
+        symbols.dedup();
+        symbols
+    }
+
     pub fn format(&self) -> TractResult<String> {
         Ok(format!("{:?}", ExpNode::from_ops(&self)))
     }
@@ -115,7 +127,7 @@ impl Stack {
 
     pub fn push_all(&mut self, other: &[StackOp]) {
         for i in other {
-            self.push(*i)
+            self.push(i.clone())
         }
     }
 
@@ -156,9 +168,7 @@ impl From<i32> for Stack {
 
 impl From<char> for Stack {
     fn from(s: char) -> Stack {
-        let mut e = Stack::empty();
-        e.push(StackOp::Sym(s));
-        e
+        Stack::sym(s)
     }
 }
 
@@ -348,9 +358,11 @@ mod tests {
     #[test]
     fn substitution() {
         let e = Stack::sym('x');
-        assert_eq!(e.eval(&hashmap! {'x' => 2}).unwrap(), 2);
+        assert_eq!(e.eval(&hashmap! {'x'.into() => 2}).unwrap(), 2);
         let e = Stack::sym('x') + 3;
-        assert_eq!(e.eval(&hashmap! {'x' => 2}).unwrap(), 5);
+        assert_eq!(e.eval(&hashmap! {'x'.into() => 2}).unwrap(), 5);
+        let e = Stack::sym("seq_len") * 2;
+        assert_eq!(e.eval(&hashmap! {"seq_len".into() => 3}).unwrap(), 6);
     }
 
     #[test]
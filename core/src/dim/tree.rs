@@ -2,10 +2,11 @@ use itertools::Itertools;
 use std::fmt;
 
 use super::stack::*;
+use super::Symbol;
 
 #[derive(Clone, PartialEq, Eq, Ord, PartialOrd, Hash)]
 pub enum ExpNode {
-    Sym(char),
+    Sym(Symbol),
     Val(i32),
     Add(Vec<ExpNode>),
     Mul(i32, Vec<ExpNode>),
@@ -24,7 +25,7 @@ impl fmt::Debug for ExpNode {
             Mul(a, b) if *a == 1 => {
                 write!(fmt, "{}", b.iter().map(|x| format!("{:?}", x)).join("*"))
             }
-            Mul(a, b) => write!(fmt, "{}.{}", a, b.iter().map(|x| format!("{:?}", x)).join("")),
+            Mul(a, b) => write!(fmt, "{}.{}", a, b.iter().map(|x| format!("{:?}", x)).join("*")),
             Div(a, b) => write!(fmt, "{:?}/{:?}", a, b),
             Rem(a, b) => write!(fmt, "{:?}%{:?}", a, b),
             DivCeil(a, b) => write!(fmt, "({:?}/{:?}).ceil()", a, b),
@@ -39,7 +40,7 @@ impl ExpNode {
         for op in ops.as_ops().iter() {
             match op {
                 Val(v) => stack.push(ExpNode::Val(*v)),
-                Sym(v) => stack.push(ExpNode::Sym(*v)),
+                Sym(v) => stack.push(ExpNode::Sym(v.clone())),
                 Add => {
                     let b = stack.pop().expect("Too short stack");
                     let a = stack.pop().expect("Too short stack");
@@ -82,7 +83,7 @@ impl ExpNode {
     pub fn to_stack(&self) -> Stack {
         match self {
             ExpNode::Val(i) => Stack::from(*i),
-            ExpNode::Sym(c) => Stack::sym(*c),
+            ExpNode::Sym(c) => Stack::sym(c.clone()),
             ExpNode::Add(vec) => {
                 let (first, rest) = vec.split_first().expect("Empty add node");
                 let mut it = first.to_stack();
@@ -362,23 +363,23 @@ mod tests {
 
     #[test]
     fn reduce_add() {
-        assert_eq!(add(&Sym('S'), &neg(&Sym('S'))).reduce(), Val(0))
+        assert_eq!(add(&Sym('S'.into()), &neg(&Sym('S'.into()))).reduce(), Val(0))
     }
 
     #[test]
     fn reduce_neg_mul() {
-        assert_eq!(neg(&mul(2, &Sym('S'))).reduce(), mul(-2, &Sym('S')))
+        assert_eq!(neg(&mul(2, &Sym('S'.into()))).reduce(), mul(-2, &Sym('S'.into())))
     }
 
     #[test]
     fn reduce_cplx_ex_1() {
         assert_eq!(
             Add(vec![
-                add(&Sym('S'), &Val(-4)),
-                add(&Val(4), &Mul(1, vec![Val(-2), div(&Sym('S'), &Val(2))])),
+                add(&Sym('S'.into()), &Val(-4)),
+                add(&Val(4), &Mul(1, vec![Val(-2), div(&Sym('S'.into()), &Val(2))])),
             ])
             .reduce(),
-            add(&Sym('S'), &mul(-2, &div(&Sym('S'), &Val(2))))
+            add(&Sym('S'.into()), &mul(-2, &div(&Sym('S'.into()), &Val(2))))
         )
     }
 
@@ -386,8 +387,8 @@ mod tests {
     fn reduce_cplx_ex_2() {
         assert_eq!(
             add(
-                &add(&Val(-4), &mul(-2, &div(&Sym('S'), &Val(4)))),
-                &mul(-2, &mul(-1, &div(&Sym('S'), &Val(4))))
+                &add(&Val(-4), &mul(-2, &div(&Sym('S'.into()), &Val(4)))),
+                &mul(-2, &mul(-1, &div(&Sym('S'.into()), &Val(4))))
             )
             .reduce(),
             Val(-4)
@@ -396,7 +397,7 @@ mod tests {
 
     #[test]
     fn reduce_cplx_ex_3() {
-        assert_eq!(div(&Mul(1, vec![Sym('S'), Val(4)]), &Val(4)).reduce(), Sym('S'))
+        assert_eq!(div(&Mul(1, vec![Sym('S'.into()), Val(4)]), &Val(4)).reduce(), Sym('S'.into()))
     }
 
     #[test]
@@ -405,49 +406,52 @@ mod tests {
             div(
                 &Mul(
                     1,
-                    vec![add(&Val(-4), &Mul(1, vec![Val(-8), div(&Sym('S'), &Val(8))])), Val(8),]
+                    vec![
+                        add(&Val(-4), &Mul(1, vec![Val(-8), div(&Sym('S'.into()), &Val(8))])),
+                        Val(8),
+                    ]
                 ),
                 &Val(8)
             )
             .reduce(),
-            add(&Val(-4), &mul(-8, &div(&Sym('S'), &Val(8))))
+            add(&Val(-4), &mul(-8, &div(&Sym('S'.into()), &Val(8))))
         )
     }
 
     #[test]
     fn reduce_cplx_ex_5() {
         assert_eq!(
-            mul(-1, &add(&Sym('S'), &Val(-182))).reduce(),
-            add(&Mul(1, vec![Val(-1), Sym('S')]), &Val(182)).reduce(),
+            mul(-1, &add(&Sym('S'.into()), &Val(-182))).reduce(),
+            add(&Mul(1, vec![Val(-1), Sym('S'.into())]), &Val(182)).reduce(),
         )
     }
 
     #[test]
     fn reduce_mul_1() {
-        assert_eq!(Mul(1, vec![Val(2), Sym('S')]).reduce(), Mul(2, vec![Sym('S')]));
-        assert_eq!(Mul(1, vec![Sym('S'), Val(2)]).reduce(), Mul(2, vec![Sym('S')]));
+        assert_eq!(Mul(1, vec![Val(2), Sym('S'.into())]).reduce(), Mul(2, vec![Sym('S'.into())]));
+        assert_eq!(Mul(1, vec![Sym('S'.into()), Val(2)]).reduce(), Mul(2, vec![Sym('S'.into())]));
     }
 
     #[test]
     fn reduce_mul_mul_1() {
-        assert_eq!(mul(3, &mul(2, &Sym('S'))).reduce(), mul(6, &Sym('S')))
+        assert_eq!(mul(3, &mul(2, &Sym('S'.into()))).reduce(), mul(6, &Sym('S'.into())))
     }
 
     #[test]
     fn reduce_mul_mul_2() {
-        assert_eq!(mul(-2, &mul(-1, &Sym('S'))).reduce(), mul(2, &Sym('S')))
+        assert_eq!(mul(-2, &mul(-1, &Sym('S'.into()))).reduce(), mul(2, &Sym('S'.into())))
     }
 
     #[test]
     fn reduce_mul_div_1() {
         assert_eq!(
-            mul(2, &div(&mul(-1, &Sym('S')), &Val(3))).reduce(),
-            mul(-2, &div(&Sym('S'), &Val(3)))
+            mul(2, &div(&mul(-1, &Sym('S'.into())), &Val(3))).reduce(),
+            mul(-2, &div(&Sym('S'.into()), &Val(3)))
         )
     }
 
     #[test]
     fn reduce_rem_div() {
-        assert_eq!(div(&rem(&Sym('S'), &Val(2)), &Val(2)).reduce(), Val(0))
+        assert_eq!(div(&rem(&Sym('S'.into()), &Val(2)), &Val(2)).reduce(), Val(0))
     }
 }
@@ -1,5 +1,6 @@
 //! error_chain generated types
 #![allow(deprecated)]
+use itertools::Itertools;
 
 error_chain! {
     types {
@@ -16,5 +17,11 @@ error_chain! {
     errors {
         StreamTensor {}
         TFString {}
+        UnimplementedOps(ops: Vec<(String, String, Vec<usize>)>) {
+            description("unimplemented operators")
+            display("Unimplemented operators:\n{}", ops.iter().map(|(name, message, nodes)| {
+                format!("  {} (nodes {}): {}", name, nodes.iter().map(|n| format!("#{}", n)).join(", "), message)
+            }).join("\n"))
+        }
     }
 }
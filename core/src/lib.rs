@@ -40,6 +40,14 @@
 //! real-life use-cases will usually load a TensorFlow or ONNX model using
 //! tract-tensorflow or tract-onnx crates.
 //!
+//! tract-core needs the standard library: the embedded targets it supports
+//! are the ones running an operating system, like ARM Linux and Android.
+//! Bare-metal `no_std` targets, like Cortex-M microcontrollers, are not:
+//! errors go through error-chain, tensors are allocated with `std::alloc`
+//! or mapped with memmap2, and the plan uses `std::time` and `std::sync`.
+//! The serialization crates, tract-pb and tract-flatbuf, load models from
+//! files or byte slices, and build allocated `TypedModel`s from them.
+//!
 
 extern crate bit_set;
 #[macro_use]
@@ -57,6 +65,7 @@ extern crate log;
 #[allow(unused_imports)]
 #[macro_use]
 pub extern crate ndarray;
+extern crate num_complex;
 extern crate num_integer;
 extern crate num_traits;
 #[macro_use]
@@ -73,6 +82,8 @@ extern crate smallvec;
 extern crate serde_derive;
 
 extern crate tract_linalg;
+#[cfg(feature = "tracing")]
+extern crate tracing;
 
 #[macro_use]
 pub mod macros;
@@ -86,20 +97,25 @@ pub mod datum;
 pub mod dim;
 pub mod errors;
 pub mod framework;
+pub mod memory;
 pub mod model;
 mod optim;
+pub mod passes;
 pub mod plan;
+pub mod profiler;
 pub mod pulse;
+pub mod quantize;
 pub mod tensor;
 
 pub use crate::errors::*;
 pub use dyn_clone;
+pub use memmap2;
 
 /// This prelude is meant for code using tract.
 pub mod prelude {
     pub use crate::analyser::types::InferenceFact;
     pub use crate::datum::{Blob, Datum, DatumType};
-    pub use crate::dim::TDim;
+    pub use crate::dim::{Symbol, TDim};
     pub use crate::errors::*;
     pub use crate::framework::Framework;
     pub use crate::model::*;
@@ -116,7 +132,7 @@ pub mod internal {
     pub use crate::analyser::rules::{InferenceResult, InferenceRulesOp, Solver, TensorProxy};
     pub use crate::analyser::types::TypeFact;
     pub use crate::analyser::types::*;
-    pub use crate::dim::{DimLike, TDim, ToDim};
+    pub use crate::dim::{DimLike, Symbol, TDim, ToDim};
     pub use crate::framework::*;
     pub use crate::model::*;
     pub use crate::ops::element_wise::ElementWiseMiniOp;
@@ -131,6 +147,7 @@ pub mod internal {
     pub use std::borrow::Cow;
     pub use std::collections::HashMap;
     pub use std::marker::PhantomData;
+    pub use tract_linalg::bf16::bf16;
     pub use tract_linalg::f16::f16;
 }
 
@@ -0,0 +1,4 @@
+//! Memory management helpers for plan execution.
+pub mod pool;
+
+pub use self::pool::TensorPool;
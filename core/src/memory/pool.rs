@@ -0,0 +1,228 @@
+//! A cache of tensor buffers, reused across plan runs.
+use std::cell::RefCell;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+use crate::internal::*;
+
+/// Tensors kept aside, by datum type and shape, to be handed out by the next
+/// allocation of a tensor of the same type and shape.
+///
+/// A plan using a pool (see `SimplePlanOptions::with_tensor_pool`) gives it
+/// the intermediate tensors it releases, and makes the allocations of
+/// uninitialized tensors performed while it runs take from it first. With
+/// streaming inputs, a run then mostly reuses the buffers of the previous one.
+///
+/// Only tensors of plain datum types (not String, TDim or Blob) are cached.
+/// The pool is meant to be shared between plans and threads.
+#[derive(Debug)]
+pub struct TensorPool {
+    max_per_shape: usize,
+    tensors: Mutex<HashMap<(DatumType, TVec<usize>), Vec<Tensor>>>,
+    hits: AtomicUsize,
+    misses: AtomicUsize,
+}
+
+impl Default for TensorPool {
+    fn default() -> TensorPool {
+        TensorPool::new(4)
+    }
+}
+
+impl PartialEq for TensorPool {
+    fn eq(&self, other: &TensorPool) -> bool {
+        std::ptr::eq(self, other)
+    }
+}
+
+impl TensorPool {
+    /// Create a pool keeping at most `max_per_shape` tensors for each datum
+    /// type and shape.
+    pub fn new(max_per_shape: usize) -> TensorPool {
+        TensorPool {
+            max_per_shape,
+            tensors: Mutex::new(HashMap::new()),
+            hits: AtomicUsize::new(0),
+            misses: AtomicUsize::new(0),
+        }
+    }
+
+    fn cacheable(dt: DatumType) -> bool {
+        dt != DatumType::String && dt != DatumType::TDim && dt != DatumType::Blob
+    }
+
+    /// Take a tensor of this type and shape, with at least this alignment,
+    /// if one is available. Its content is unspecified.
+    pub fn get(&self, dt: DatumType, shape: &[usize], alignment: usize) -> Option<Tensor> {
+        if !Self::cacheable(dt) {
+            return None;
+        }
+        let found = self.tensors.lock().ok().and_then(|mut tensors| {
+            let cached = tensors.get_mut(&(dt, shape.into()))?;
+            let pos = cached.iter().position(|t| t.alignment() >= alignment)?;
+            Some(cached.swap_remove(pos))
+        });
+        if found.is_some() {
+            self.hits.fetch_add(1, Ordering::Relaxed);
+        } else {
+            self.misses.fetch_add(1, Ordering::Relaxed);
+        }
+        found
+    }
+
+    /// Give a tensor back to the pool. It is dropped if it can not be cached,
+    /// or if the pool already holds enough tensors of this type and shape.
+    pub fn put(&self, tensor: Tensor) {
+        if let Some(rejected) = self.offer(tensor) {
+            let _suspended = Suspended::new();
+            drop(rejected)
+        }
+    }
+
+    fn offer(&self, tensor: Tensor) -> Option<Tensor> {
+        if !Self::cacheable(tensor.datum_type())
+            || tensor.is_null()
+            || tensor.is_mmapped()
+            || tensor.len() == 0
+        {
+            return Some(tensor);
+        }
+        let mut tensors = match self.tensors.lock() {
+            Ok(tensors) => tensors,
+            Err(_) => return Some(tensor),
+        };
+        let cached =
+            tensors.entry((tensor.datum_type(), tensor.shape().into())).or_insert_with(Vec::new);
+        if cached.len() < self.max_per_shape {
+            cached.push(tensor);
+            None
+        } else {
+            Some(tensor)
+        }
+    }
+
+    /// Number of tensors currently held.
+    pub fn len(&self) -> usize {
+        self.tensors.lock().map(|t| t.values().map(|v| v.len()).sum()).unwrap_or(0)
+    }
+
+    /// Number of requests served from the pool.
+    pub fn hits(&self) -> usize {
+        self.hits.load(Ordering::Relaxed)
+    }
+
+    /// Number of requests the pool could not serve.
+    pub fn misses(&self) -> usize {
+        self.misses.load(Ordering::Relaxed)
+    }
+
+    /// Drop all the cached tensors.
+    pub fn clear(&self) {
+        if let Ok(mut tensors) = self.tensors.lock() {
+            tensors.clear()
+        }
+    }
+}
+
+thread_local! {
+    static ACTIVE: RefCell<Option<Arc<TensorPool>>> = RefCell::new(None);
+}
+
+/// Makes a pool serve the tensor allocations of the current thread, until
+/// dropped.
+pub(crate) struct ActivePool(Option<Arc<TensorPool>>);
+
+impl ActivePool {
+    pub(crate) fn activate(pool: Arc<TensorPool>) -> ActivePool {
+        ActivePool(ACTIVE.with(|active| active.borrow_mut().replace(pool)))
+    }
+}
+
+impl Drop for ActivePool {
+    fn drop(&mut self) {
+        let previous = self.0.take();
+        ACTIVE.with(|active| *active.borrow_mut() = previous);
+    }
+}
+
+/// Deactivates the pool of the current thread, until dropped.
+struct Suspended(Option<Arc<TensorPool>>);
+
+impl Suspended {
+    fn new() -> Suspended {
+        Suspended(ACTIVE.try_with(|active| active.borrow_mut().take()).unwrap_or(None))
+    }
+}
+
+impl Drop for Suspended {
+    fn drop(&mut self) {
+        if let Some(pool) = self.0.take() {
+            let _ = ACTIVE.try_with(|active| *active.borrow_mut() = Some(pool));
+        }
+    }
+}
+
+/// Take a tensor from the pool active on this thread, if any.
+pub(crate) fn take(dt: DatumType, shape: &[usize], alignment: usize) -> Option<Tensor> {
+    let pool = ACTIVE.try_with(|active| active.borrow().clone()).ok()??;
+    pool.get(dt, shape, alignment)
+}
+
+/// Whether dropped tensors of this type go to a pool on this thread.
+pub(crate) fn recycles(dt: DatumType) -> bool {
+    TensorPool::cacheable(dt)
+        && ACTIVE.try_with(|active| active.borrow().is_some()).unwrap_or(false)
+}
+
+/// Give a tensor being dropped to the pool active on this thread.
+pub(crate) fn recycle(tensor: Tensor) {
+    match ACTIVE.try_with(|active| active.borrow().clone()) {
+        Ok(Some(pool)) => pool.put(tensor),
+        _ => {
+            let _suspended = Suspended::new();
+            drop(tensor)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn get_put() {
+        let pool = TensorPool::new(2);
+        assert!(pool.get(f32::datum_type(), &[2, 3], 4).is_none());
+        for _ in 0..3 {
+            pool.put(tensor2(&[[1f32, 2.0, 3.0], [4.0, 5.0, 6.0]]));
+        }
+        pool.put(tensor1(&["a".to_string()]));
+        assert_eq!(pool.len(), 2);
+        assert!(pool.get(f32::datum_type(), &[3, 2], 4).is_none());
+        assert!(pool.get(i32::datum_type(), &[2, 3], 4).is_none());
+        let t = pool.get(f32::datum_type(), &[2, 3], 4).unwrap();
+        assert_eq!(t.shape(), &[2, 3]);
+        assert_eq!(pool.len(), 1);
+        assert_eq!((pool.hits(), pool.misses()), (1, 3));
+    }
+
+    #[test]
+    fn active_pool() -> TractResult<()> {
+        let pool = Arc::new(TensorPool::default());
+        let t = unsafe { Tensor::uninitialized::<f32>(&[16])? };
+        let ptr = t.as_ptr::<f32>()?;
+        pool.put(t);
+        {
+            let _active = ActivePool::activate(pool.clone());
+            let t = unsafe { Tensor::uninitialized::<f32>(&[16])? };
+            assert_eq!(t.as_ptr::<f32>()?, ptr);
+        }
+        // dropped while active: recycled
+        assert_eq!(pool.len(), 1);
+        drop(unsafe { Tensor::uninitialized::<f32>(&[16])? });
+        assert_eq!(pool.len(), 1);
+        let _ = pool.get(f32::datum_type(), &[16], 4).unwrap();
+        assert_eq!(pool.len(), 0);
+        Ok(())
+    }
+}
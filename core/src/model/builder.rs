@@ -0,0 +1,142 @@
+//! Incremental construction of TypedModel.
+use crate::internal::*;
+
+/// Builds a TypedModel node by node.
+///
+/// ```
+/// # use tract_core::internal::*;
+/// # use tract_core::ops::{binary::TypedBinOp, math, matmul::MatMul};
+/// # fn main() -> TractResult<()> {
+/// // y = relu(x.w1 + b1).w2
+/// let mut builder = TypedModelBuilder::new();
+/// let x = builder.add_source("x", TypedFact::dt_shape(f32::datum_type(), [1usize, 4].as_ref())?)?;
+/// let w1 = builder.add_const("w1", Tensor::from(tract_core::ndarray::Array2::<f32>::ones((4, 3))))?;
+/// let b1 = builder.add_const("b1", tensor2(&[[0f32, -10.0, 1.0]]))?;
+/// let w2 = builder.add_const("w2", Tensor::from(tract_core::ndarray::Array2::<f32>::ones((3, 2))))?;
+/// let h = builder.wire_op(MatMul::default(), &[x, w1])?[0];
+/// let h = builder.wire_op(TypedBinOp(Box::new(math::Add)), &[h, b1])?[0];
+/// let h = builder.wire_op(math::max::unary(rctensor0(0f32)), &[h])?[0];
+/// builder.wire_node("y", MatMul::default(), &[h, w2])?;
+/// builder.set_output_names(&["y"])?;
+/// let model = builder.build()?;
+///
+/// let y = SimplePlan::new(&model)?.run(tvec!(tensor2(&[[1f32, 2.0, 3.0, 4.0]])))?;
+/// assert_eq!(y[0], rctensor2(&[[21f32, 21.0]]));
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct TypedModelBuilder {
+    model: TypedModel,
+    outputs: Option<Vec<OutletId>>,
+}
+
+impl TypedModelBuilder {
+    pub fn new() -> TypedModelBuilder {
+        TypedModelBuilder::default()
+    }
+
+    fn check_name(&self, name: &str) -> TractResult<()> {
+        if self.model.node_by_name(name).is_ok() {
+            bail!("A node named {} already exists", name)
+        }
+        Ok(())
+    }
+
+    /// Add a model input.
+    pub fn add_source(
+        &mut self,
+        name: impl Into<String>,
+        fact: TypedFact,
+    ) -> TractResult<OutletId> {
+        let name = name.into();
+        self.check_name(&name)?;
+        self.model.add_source(name, fact)
+    }
+
+    /// Add a constant.
+    pub fn add_const(
+        &mut self,
+        name: impl Into<String>,
+        v: impl IntoArcTensor,
+    ) -> TractResult<OutletId> {
+        let name = name.into();
+        self.check_name(&name)?;
+        self.model.add_const(name, v)
+    }
+
+    /// Add a node with a generated name, wired to `inputs`.
+    pub fn wire_op(
+        &mut self,
+        op: impl Into<Box<dyn TypedOp>>,
+        inputs: &[OutletId],
+    ) -> TractResult<TVec<OutletId>> {
+        let op = op.into();
+        let name = format!("{}-{}", op.name(), self.model.nodes().len());
+        self.wire_node(name, op, inputs)
+    }
+
+    /// Add a named node, wired to `inputs`.
+    pub fn wire_node(
+        &mut self,
+        name: impl Into<String>,
+        op: impl Into<Box<dyn TypedOp>>,
+        inputs: &[OutletId],
+    ) -> TractResult<TVec<OutletId>> {
+        let name = name.into();
+        self.check_name(&name)?;
+        self.model.wire_node(name, op, inputs)
+    }
+
+    /// Set model outputs by node names.
+    ///
+    /// If neither this nor `set_output_outlets` is called, outputs are the
+    /// outlets without successors.
+    pub fn set_output_names(&mut self, names: &[&str]) -> TractResult<()> {
+        let outputs = names
+            .iter()
+            .map(|name| Ok(OutletId::new(self.model.node_by_name(name)?.id, 0)))
+            .collect::<TractResult<_>>()?;
+        self.outputs = Some(outputs);
+        Ok(())
+    }
+
+    /// Set model outputs.
+    pub fn set_output_outlets(&mut self, outlets: &[OutletId]) -> TractResult<()> {
+        self.outputs = Some(outlets.to_vec());
+        Ok(())
+    }
+
+    /// Finish the model.
+    pub fn build(self) -> TractResult<TypedModel> {
+        let TypedModelBuilder { mut model, outputs } = self;
+        if let Some(outputs) = outputs {
+            model.set_output_outlets(&outputs)?;
+        } else {
+            model.auto_outputs()?;
+        }
+        model.eval_order()?;
+        Ok(model)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ops::math;
+
+    #[test]
+    fn auto_outputs_and_names() -> TractResult<()> {
+        let mut builder = TypedModelBuilder::new();
+        let x =
+            builder.add_source("x", TypedFact::dt_shape(f32::datum_type(), [2usize].as_ref())?)?;
+        let n = builder.wire_op(math::neg(), &[x])?[0];
+        builder.wire_op(math::abs(), &[n])?;
+        assert!(builder.wire_node("x", math::exp(), &[n]).is_err());
+        let model = builder.build()?;
+        assert_eq!(model.node(model.output_outlets()?[0].node).name, "Abs-2");
+        let result = SimplePlan::new(&model)?.run(tvec!(tensor1(&[1f32, -2.0])))?;
+        assert_eq!(result[0], rctensor1(&[1f32, 2.0]));
+        Ok(())
+    }
+}
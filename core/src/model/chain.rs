@@ -0,0 +1,109 @@
+//! Stitching of two models, end to end.
+use crate::internal::*;
+
+fn unique_name(model: &TypedModel, name: &str) -> String {
+    if model.node_by_name(name).is_err() {
+        return name.to_string();
+    }
+    (1..).map(|i| format!("{}.{}", name, i)).find(|n| model.node_by_name(n).is_err()).unwrap()
+}
+
+pub(crate) fn chain(
+    first: TypedModel,
+    next: &TypedModel,
+    output_to_input: &[(usize, usize)],
+) -> TractResult<TypedModel> {
+    let mut model = first;
+    let mut map: HashMap<OutletId, OutletId> = HashMap::new();
+    for &(output, input) in output_to_input {
+        let from = *model
+            .output_outlets()?
+            .get(output)
+            .ok_or_else(|| format!("First model has no output #{}", output))?;
+        let to = *next
+            .input_outlets()?
+            .get(input)
+            .ok_or_else(|| format!("Next model has no input #{}", input))?;
+        if map.contains_key(&to) {
+            bail!("Next model input #{} is wired twice", input)
+        }
+        let from_fact = model.outlet_fact(from)?;
+        let to_fact = next.outlet_fact(to)?;
+        if from_fact.datum_type != to_fact.datum_type || from_fact.shape != to_fact.shape {
+            bail!(
+                "Can not wire output #{} ({:?}) to input #{} ({:?})",
+                output,
+                from_fact,
+                input,
+                to_fact
+            )
+        }
+        map.insert(to, from);
+    }
+    for (ix, input) in next.input_outlets()?.iter().enumerate() {
+        if !map.contains_key(input) {
+            bail!("Next model input #{} is not wired to an output", ix)
+        }
+    }
+    for n in next.eval_order()? {
+        let node = next.node(n);
+        if next.input_outlets()?.iter().any(|i| i.node == n) {
+            continue;
+        }
+        let facts = node.outputs.iter().map(|o| o.fact.clone()).collect();
+        let id = model.add_node(unique_name(&model, &node.name), node.op.clone(), facts)?;
+        for (ix, input) in node.inputs.iter().enumerate() {
+            model.add_edge(map[input], InletId::new(id, ix))?;
+        }
+        for prec in &node.control_inputs {
+            if let Some(prec) = map.get(&OutletId::new(*prec, 0)) {
+                model.node_mut(id).control_inputs.push(prec.node);
+            }
+        }
+        for slot in 0..node.outputs.len() {
+            let outlet = OutletId::new(n, slot);
+            map.insert(outlet, OutletId::new(id, slot));
+            if let Some(label) = next.outlet_label(outlet) {
+                model.set_outlet_label(OutletId::new(id, slot), label.to_string());
+            }
+        }
+    }
+    let outputs: Vec<OutletId> = next.output_outlets()?.iter().map(|o| map[o]).collect();
+    model.set_output_outlets(&outputs)?;
+    Ok(model)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ops::math;
+
+    fn add_one(input: &str, dt: DatumType, len: usize) -> TractResult<TypedModel> {
+        let mut model = TypedModel::default();
+        let x = model.add_source(input, TypedFact::dt_shape(dt, [len].as_ref())?)?;
+        let y = model.wire_node("add", math::add::unary(rctensor0(1f32)), &[x])?[0];
+        model.set_output_outlets(&[y])?;
+        Ok(model)
+    }
+
+    #[test]
+    fn same_names() -> TractResult<()> {
+        let first = add_one("x", f32::datum_type(), 2)?;
+        let model = first.clone().chain(first, &[(0, 0)])?;
+        assert_eq!(model.node_names().collect::<Vec<_>>(), &["x", "add", "add.1"]);
+        let result = SimplePlan::new(&model)?.run(tvec!(tensor1(&[1f32, 2.0])))?;
+        assert_eq!(result[0], rctensor1(&[3f32, 4.0]));
+        Ok(())
+    }
+
+    #[test]
+    fn mismatches() -> TractResult<()> {
+        let first = add_one("x", f32::datum_type(), 2)?;
+        assert!(first.clone().chain(add_one("x", f32::datum_type(), 3)?, &[(0, 0)]).is_err());
+        assert!(first.clone().chain(add_one("x", f64::datum_type(), 2)?, &[(0, 0)]).is_err());
+        assert!(first.clone().chain(first.clone(), &[(0, 1)]).is_err());
+        assert!(first.clone().chain(first.clone(), &[(1, 0)]).is_err());
+        assert!(first.clone().chain(first, &[]).is_err());
+        Ok(())
+    }
+}
@@ -52,6 +52,7 @@ where
         // maintaining order of i/o interface
         new.inputs = old.input_outlets()?.iter().map(|i| map[&i]).collect();
         new.outputs = old.output_outlets()?.iter().map(|o| map[&o]).collect();
+        new.optimized = old.optimized;
         Ok(new)
     }
 }
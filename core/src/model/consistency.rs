@@ -0,0 +1,222 @@
+//! Structural and type checks for hand-built or hand-modified models.
+use crate::internal::*;
+use crate::ops::source::Source;
+use itertools::Itertools;
+use std::fmt;
+
+/// A broken invariant found by `InferenceModel::check_consistency`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConsistencyWarning {
+    /// A model input is not the output of an existing Source node.
+    InputNotASource { input: usize, outlet: OutletId },
+    /// A Source node is not a model input, so it will never be fed.
+    UndeclaredSource { node: usize },
+    /// A model output refers to a node or slot that does not exist.
+    DanglingOutput { output: usize, outlet: OutletId },
+    /// A node input refers to a node or slot that does not exist.
+    DanglingInput { inlet: InletId, outlet: OutletId },
+    /// An edge is only recorded by one of its ends.
+    UnreciprocatedEdge { outlet: OutletId, inlet: InletId },
+    /// The node does not have as many outputs as its op produces.
+    OutputCountMismatch { node: usize, expected: usize, found: usize },
+    /// The node input and output facts violate its op constraints.
+    OpConstraint { node: usize, message: String },
+}
+
+impl fmt::Display for ConsistencyWarning {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        use ConsistencyWarning::*;
+        match self {
+            InputNotASource { input, outlet } => {
+                write!(fmt, "Model input #{} ({:?}) is not a Source node", input, outlet)
+            }
+            UndeclaredSource { node } => write!(fmt, "Source node {} is not a model input", node),
+            DanglingOutput { output, outlet } => {
+                write!(fmt, "Model output #{} refers to missing outlet {:?}", output, outlet)
+            }
+            DanglingInput { inlet, outlet } => {
+                write!(fmt, "Input {:?} refers to missing outlet {:?}", inlet, outlet)
+            }
+            UnreciprocatedEdge { outlet, inlet } => {
+                write!(fmt, "Edge {:?} -> {:?} is only recorded on one end", outlet, inlet)
+            }
+            OutputCountMismatch { node, expected, found } => {
+                write!(fmt, "Node {} has {} outputs, its op produces {}", node, found, expected)
+            }
+            OpConstraint { node, message } => write!(fmt, "Node {}: {}", node, message),
+        }
+    }
+}
+
+fn outlet_exists(model: &InferenceModel, outlet: OutletId) -> bool {
+    model.nodes().get(outlet.node).map(|n| outlet.slot < n.outputs.len()).unwrap_or(false)
+}
+
+impl InferenceModel {
+    /// Look for broken invariants in the model graph and facts.
+    ///
+    /// Unlike `check_edges` or the analyser, this does not stop on the first
+    /// problem: all the issues found are returned, an empty list meaning the
+    /// model is consistent. Op constraints are checked by running the node op
+    /// inference rules once on the current facts, for nodes with valid inputs.
+    pub fn check_consistency(&self) -> TractResult<Vec<ConsistencyWarning>> {
+        use ConsistencyWarning::*;
+        let mut warnings = vec![];
+        for (input, &outlet) in self.inputs.iter().enumerate() {
+            if !outlet_exists(self, outlet) || !self.node(outlet.node).op_is::<Source>() {
+                warnings.push(InputNotASource { input, outlet });
+            }
+        }
+        for (output, &outlet) in self.outputs.iter().enumerate() {
+            if !outlet_exists(self, outlet) {
+                warnings.push(DanglingOutput { output, outlet });
+            }
+        }
+        for node in self.nodes() {
+            if node.op_is::<Source>() && !self.inputs.iter().any(|i| i.node == node.id) {
+                warnings.push(UndeclaredSource { node: node.id });
+            }
+            let mut valid_inputs = true;
+            for (slot, &outlet) in node.inputs.iter().enumerate() {
+                let inlet = InletId::new(node.id, slot);
+                if !outlet_exists(self, outlet) {
+                    warnings.push(DanglingInput { inlet, outlet });
+                    valid_inputs = false;
+                } else if !self.node(outlet.node).outputs[outlet.slot].successors.contains(&inlet) {
+                    warnings.push(UnreciprocatedEdge { outlet, inlet });
+                }
+            }
+            for (slot, output) in node.outputs.iter().enumerate() {
+                let outlet = OutletId::new(node.id, slot);
+                for &inlet in &output.successors {
+                    let reciprocated = self
+                        .nodes()
+                        .get(inlet.node)
+                        .and_then(|n| n.inputs.get(inlet.slot))
+                        .map(|&o| o == outlet)
+                        .unwrap_or(false);
+                    if !reciprocated {
+                        warnings.push(UnreciprocatedEdge { outlet, inlet });
+                    }
+                }
+            }
+            match node.op.nboutputs() {
+                Ok(expected) if expected != node.outputs.len() => {
+                    warnings.push(OutputCountMismatch {
+                        node: node.id,
+                        expected,
+                        found: node.outputs.len(),
+                    });
+                    continue;
+                }
+                Err(e) => {
+                    warnings.push(OpConstraint { node: node.id, message: e.to_string() });
+                    continue;
+                }
+                _ => (),
+            }
+            if valid_inputs {
+                if let Err(e) = self.check_op_constraints(node) {
+                    let message = e.iter().map(|e| e.to_string()).join(": ");
+                    warnings.push(OpConstraint { node: node.id, message });
+                }
+            }
+        }
+        Ok(warnings)
+    }
+
+    fn check_op_constraints(&self, node: &InferenceNode) -> TractResult<()> {
+        let (inputs, outputs) = self.node_facts(node.id)?;
+        let observed: TVec<&InferenceFact> = node
+            .op
+            .observe_outlets(self, node)?
+            .iter()
+            .map(|&o| self.outlet_fact(o))
+            .collect::<TractResult<_>>()?;
+        node.op.clone().infer(inputs, outputs, observed)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ops::math;
+    use ConsistencyWarning::*;
+
+    fn model() -> TractResult<(InferenceModel, OutletId, OutletId)> {
+        let mut model = InferenceModel::default();
+        let fact = InferenceFact::dt_shape(f32::datum_type(), tvec!(2, 3));
+        let a = model.add_source("a", fact.clone())?;
+        let b = model.add_source("b", fact)?;
+        let add = model.wire_node("add", math::add::bin(), &[a, b])?[0];
+        let neg = model.wire_node("neg", math::neg(), &[add])?[0];
+        model.set_output_outlets(&[neg])?;
+        Ok((model, add, neg))
+    }
+
+    #[test]
+    fn consistent() -> TractResult<()> {
+        let (mut model, _, _) = model()?;
+        assert_eq!(model.check_consistency()?, vec!());
+        model.analyse(false)?;
+        assert_eq!(model.check_consistency()?, vec!());
+        Ok(())
+    }
+
+    #[test]
+    fn sources_and_model_outlets() -> TractResult<()> {
+        let (mut model, add, _) = model()?;
+        model.set_input_outlets(&[OutletId::new(0, 0), add])?;
+        model.set_output_outlets(&[OutletId::new(12, 0), OutletId::new(3, 1)])?;
+        assert_eq!(
+            model.check_consistency()?,
+            vec!(
+                InputNotASource { input: 1, outlet: add },
+                DanglingOutput { output: 0, outlet: OutletId::new(12, 0) },
+                DanglingOutput { output: 1, outlet: OutletId::new(3, 1) },
+                UndeclaredSource { node: 1 },
+            )
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn broken_edges() -> TractResult<()> {
+        let (mut model, add, neg) = model()?;
+        // neg now claims to read a missing node, add still lists it
+        model.node_mut(neg.node).inputs[0] = OutletId::new(7, 0);
+        // a forgets it feeds add
+        model.node_mut(0).outputs[0].successors.clear();
+        assert_eq!(
+            model.check_consistency()?,
+            vec!(
+                UnreciprocatedEdge {
+                    outlet: OutletId::new(0, 0),
+                    inlet: InletId::new(add.node, 0)
+                },
+                UnreciprocatedEdge { outlet: add, inlet: InletId::new(neg.node, 0) },
+                DanglingInput { inlet: InletId::new(neg.node, 0), outlet: OutletId::new(7, 0) },
+            )
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn op_constraints() -> TractResult<()> {
+        let (mut model, add, neg) = model()?;
+        model.analyse(false)?;
+        model.set_outlet_fact(neg, InferenceFact::dt_shape(f32::datum_type(), tvec!(4)))?;
+        model.node_mut(add.node).outputs.push(Default::default());
+        let warnings = model.check_consistency()?;
+        assert_eq!(warnings.len(), 2);
+        assert_eq!(warnings[0], OutputCountMismatch { node: add.node, expected: 1, found: 2 });
+        match &warnings[1] {
+            OpConstraint { node, message } if *node == neg.node => {
+                assert!(message.contains("Impossible to unify"), "{}", message)
+            }
+            w => panic!("unexpected warning {:?}", w),
+        }
+        Ok(())
+    }
+}
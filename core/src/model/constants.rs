@@ -0,0 +1,182 @@
+//! Constant subgraphs analysis.
+use crate::internal::*;
+use bit_set::BitSet;
+
+/// A node or an edge of a constant component.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Element {
+    /// A node id.
+    Node(usize),
+    /// A node output, standing for all the edges it feeds.
+    Edge(OutletId),
+}
+
+/// A connected component of the constant underlying graph.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Component {
+    /// Nodes and edges of the component.
+    pub elements: Vec<Element>,
+    /// Outlets feeding nodes outside the component, or model outputs.
+    pub outputs: Vec<OutletId>,
+}
+
+impl Component {
+    /// Nodes of the component.
+    pub fn nodes<'a>(&'a self) -> impl Iterator<Item = usize> + 'a {
+        self.elements.iter().filter_map(|e| if let Element::Node(n) = e { Some(*n) } else { None })
+    }
+}
+
+/// Check if a node belongs to the constant underlying graph, not accounting
+/// for its inputs.
+fn is_const_candidate(node: &TypedNode) -> bool {
+    node.outputs.len() > 0
+        && node.outputs.iter().all(|o| o.fact.konst.is_some())
+        && node.op().as_stateless().is_some()
+}
+
+/// Computes all the connected components of the constant underlying graph.
+///
+/// The constant underlying graph G is constructed using these rules:
+/// - A stateless node with all its outputs constant, according to the
+///   model facts, and all its inputs from nodes in G, is in G.
+/// - An edge between two nodes of G is in G.
+/// - If an edge from a node in G leads to a node outside G, or is a model
+///   output, it is called an "output".
+///
+/// Fails if a component contains a cycle.
+pub fn connected_components(model: &TypedModel) -> TractResult<Vec<Component>> {
+    let mut is_node_const: Vec<bool> = model.nodes().iter().map(is_const_candidate).collect();
+    loop {
+        let mut changed = false;
+        for node in model.nodes() {
+            if is_node_const[node.id] && node.inputs.iter().any(|i| !is_node_const[i.node]) {
+                is_node_const[node.id] = false;
+                changed = true;
+            }
+        }
+        if !changed {
+            break;
+        }
+    }
+
+    let mut components = vec![];
+    let mut is_node_colored = vec![false; model.nodes().len()];
+    let mut stack = vec![];
+
+    for (node, &is_const) in is_node_const.iter().enumerate() {
+        if is_const && !is_node_colored[node] {
+            let mut component = Component { elements: vec![], outputs: vec![] };
+
+            stack.push(node);
+
+            while let Some(node) = stack.pop() {
+                if is_node_colored[node] {
+                    continue;
+                }
+
+                is_node_colored[node] = true;
+                component.elements.push(Element::Node(node));
+
+                for input in &model.node(node).inputs {
+                    if !is_node_colored[input.node] {
+                        stack.push(input.node);
+                    }
+                }
+
+                for (slot, output) in model.node(node).outputs.iter().enumerate() {
+                    let outlet = OutletId::new(node, slot);
+                    component.elements.push(Element::Edge(outlet));
+                    if model.output_outlets()?.contains(&outlet)
+                        || output.successors.iter().any(|s| !is_node_const[s.node])
+                    {
+                        component.outputs.push(outlet);
+                    }
+                    for succ in &output.successors {
+                        if is_node_const[succ.node] && !is_node_colored[succ.node] {
+                            stack.push(succ.node);
+                        }
+                    }
+                }
+            }
+
+            check_acyclic(model, &component)?;
+            components.push(component);
+        }
+    }
+
+    Ok(components)
+}
+
+/// Check that following inputs from the nodes of a component never leads
+/// back to a node being explored.
+fn check_acyclic(model: &TypedModel, component: &Component) -> TractResult<()> {
+    let mut done = BitSet::with_capacity(model.nodes().len());
+    let mut on_stack = BitSet::with_capacity(model.nodes().len());
+    for root in component.nodes() {
+        if done.contains(root) {
+            continue;
+        }
+        let mut stack: Vec<(usize, usize)> = vec![(root, 0)];
+        on_stack.insert(root);
+        while let Some(&(node, ix)) = stack.last() {
+            let inputs = &model.node(node).inputs;
+            if ix == inputs.len() {
+                stack.pop();
+                on_stack.remove(node);
+                done.insert(node);
+                continue;
+            }
+            stack.last_mut().unwrap().1 += 1;
+            let prec = inputs[ix].node;
+            if on_stack.contains(prec) {
+                let cycle: Vec<usize> =
+                    stack.iter().map(|pair| pair.0).skip_while(|&n| n != prec).collect();
+                bail!("Cycle detected in constant subgraph, involving nodes {:?}", cycle)
+            }
+            if !done.contains(prec) {
+                on_stack.insert(prec);
+                stack.push((prec, 0));
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ops::math;
+
+    #[test]
+    fn components() -> TractResult<()> {
+        let mut model = TypedModel::default();
+        let x = model.add_source("x", TypedFact::dt_shape(f32::datum_type(), [2usize].as_ref())?)?;
+        let c = model.add_const("c", rctensor1(&[1f32, 2.0]))?;
+        let n = model.wire_node("n", math::neg(), &[c])?[0];
+        let a = model.wire_node("a", math::add::bin(), &[x, n])?[0];
+        model.set_output_outlets(&[a])?;
+        let components = connected_components(&model)?;
+        assert_eq!(components.len(), 1);
+        assert_eq!(components[0].nodes().collect::<Vec<_>>(), vec!(c.node, n.node));
+        assert_eq!(components[0].outputs, vec!(n));
+        Ok(())
+    }
+
+    #[test]
+    fn cycle() -> TractResult<()> {
+        let mut model = TypedModel::default();
+        let c = model.add_const("c", rctensor0(1f32))?;
+        let a = model.wire_node("a", math::add::bin(), &[c, c])?[0];
+        let n = model.wire_node("n", math::neg(), &[a])?[0];
+        model.add_edge(n, InletId::new(a.node, 1))?;
+        model.set_output_outlets(&[n])?;
+        let (tx, rx) = std::sync::mpsc::channel();
+        std::thread::spawn(move || {
+            tx.send(connected_components(&model).map(|_| ())).unwrap();
+        });
+        let result = rx.recv_timeout(std::time::Duration::from_secs(1)).unwrap();
+        assert!(result.unwrap_err().to_string().contains(&format!("{:?}", vec!(a.node, n.node))));
+        Ok(())
+    }
+}
@@ -0,0 +1,232 @@
+//! Structural comparison of two models.
+//!
+//! Node ids are not stable across passes (compaction renumbers the nodes), so
+//! nodes are matched by name.
+use crate::internal::*;
+use std::collections::HashMap;
+use std::fmt;
+
+/// A node present in both models, with a different op.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChangedNode {
+    pub name: String,
+    pub op_before: String,
+    pub op_after: String,
+}
+
+impl ChangedNode {
+    /// Whether the op was replaced by an op of another type, rather than
+    /// just given different attributes.
+    pub fn op_type_changed(&self) -> bool {
+        self.op_before != self.op_after
+    }
+}
+
+/// A node input, present in both models, that is fed by a different outlet.
+///
+/// An outlet is given as its node name and slot. `None` stands for a missing
+/// input.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RewiredInput {
+    pub node: String,
+    pub input: usize,
+    pub before: Option<(String, usize)>,
+    pub after: Option<(String, usize)>,
+}
+
+/// What changed between two models. Node lists are in node id order.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ModelDiff {
+    /// Names of the nodes only in the second model.
+    pub added: Vec<String>,
+    /// Names of the nodes only in the first model.
+    pub removed: Vec<String>,
+    /// Nodes whose op type or attributes changed.
+    pub changed: Vec<ChangedNode>,
+    /// Edges that were moved to another source.
+    pub rewired: Vec<RewiredInput>,
+}
+
+impl ModelDiff {
+    /// Whether the models are structurally identical.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty()
+            && self.removed.is_empty()
+            && self.changed.is_empty()
+            && self.rewired.is_empty()
+    }
+
+    /// A human-readable summary, one change per line.
+    pub fn display(&self) -> String {
+        self.to_string()
+    }
+}
+
+fn outlet_name(outlet: &Option<(String, usize)>) -> String {
+    match outlet {
+        Some((node, slot)) => format!("{}/{}", node, slot),
+        None => "nothing".to_string(),
+    }
+}
+
+impl fmt::Display for ModelDiff {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        if self.is_empty() {
+            return writeln!(fmt, "no change");
+        }
+        for node in &self.added {
+            writeln!(fmt, "+ {}", node)?;
+        }
+        for node in &self.removed {
+            writeln!(fmt, "- {}", node)?;
+        }
+        for change in &self.changed {
+            if change.op_type_changed() {
+                writeln!(fmt, "~ {}: {} -> {}", change.name, change.op_before, change.op_after)?;
+            } else {
+                writeln!(fmt, "~ {}: {} attributes", change.name, change.op_after)?;
+            }
+        }
+        for rewire in &self.rewired {
+            writeln!(
+                fmt,
+                "> {} input {}: {} -> {}",
+                rewire.node,
+                rewire.input,
+                outlet_name(&rewire.before),
+                outlet_name(&rewire.after)
+            )?;
+        }
+        Ok(())
+    }
+}
+
+fn node_inputs(model: &TypedModel, node: &TypedNode) -> Vec<(String, usize)> {
+    node.inputs.iter().map(|i| (model.node(i.node).name.clone(), i.slot)).collect()
+}
+
+/// Compare two models, typically a model before and after a pass.
+///
+/// Ops are compared by name for their type, and by their debug
+/// representation for their attributes.
+pub fn model_diff(before: &TypedModel, after: &TypedModel) -> ModelDiff {
+    let before_ids: HashMap<&str, usize> =
+        before.nodes().iter().map(|n| (&*n.name, n.id)).collect();
+    let after_ids: HashMap<&str, usize> = after.nodes().iter().map(|n| (&*n.name, n.id)).collect();
+    let mut diff = ModelDiff::default();
+    for node in before.nodes() {
+        if !after_ids.contains_key(&*node.name) {
+            diff.removed.push(node.name.clone());
+        }
+    }
+    for node in after.nodes() {
+        let old = if let Some(&id) = before_ids.get(&*node.name) {
+            before.node(id)
+        } else {
+            diff.added.push(node.name.clone());
+            continue;
+        };
+        if old.op().name() != node.op().name()
+            || format!("{:?}", old.op) != format!("{:?}", node.op)
+        {
+            diff.changed.push(ChangedNode {
+                name: node.name.clone(),
+                op_before: old.op().name().to_string(),
+                op_after: node.op().name().to_string(),
+            });
+        }
+        let old_inputs = node_inputs(before, old);
+        let new_inputs = node_inputs(after, node);
+        for input in 0..old_inputs.len().max(new_inputs.len()) {
+            if old_inputs.get(input) != new_inputs.get(input) {
+                diff.rewired.push(RewiredInput {
+                    node: node.name.clone(),
+                    input,
+                    before: old_inputs.get(input).cloned(),
+                    after: new_inputs.get(input).cloned(),
+                });
+            }
+        }
+    }
+    diff
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ops::math;
+
+    fn model() -> TractResult<TypedModel> {
+        let mut model = TypedModel::default();
+        let fact = TypedFact::dt_shape(f32::datum_type(), [2usize].as_ref())?;
+        let a = model.add_source("a", fact)?;
+        let neg = model.wire_node("neg", math::neg(), &[a])?[0];
+        let abs = model.wire_node("abs", math::abs(), &[a])?[0];
+        let _dangling = model.wire_node("dangling", math::exp(), &[abs])?;
+        model.set_output_outlets(&[neg])?;
+        Ok(model)
+    }
+
+    #[test]
+    fn dead_nodes() -> TractResult<()> {
+        let before = model()?;
+        let mut after = before.clone();
+        crate::passes::eliminate_dead_nodes(&mut after)?;
+        let diff = model_diff(&before, &after);
+        assert_eq!(diff.removed, vec!["abs".to_string(), "dangling".to_string()]);
+        assert!(diff.added.is_empty());
+        assert!(diff.changed.is_empty());
+        assert!(diff.rewired.is_empty());
+        assert_eq!(diff.display(), "- abs\n- dangling\n");
+        assert!(model_diff(&after, &after).is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn changes_and_rewiring() -> TractResult<()> {
+        let before = model()?;
+        let mut after = TypedModel::default();
+        let fact = TypedFact::dt_shape(f32::datum_type(), [2usize].as_ref())?;
+        let a = after.add_source("a", fact)?;
+        let abs = after.wire_node("abs", math::abs(), &[a])?[0];
+        let neg = after.wire_node("neg", math::exp(), &[abs])?[0];
+        let _dangling = after.wire_node("dangling", math::exp(), &[abs])?;
+        let sqrt = after.wire_node("sqrt", math::sqrt(), &[neg])?[0];
+        after.set_output_outlets(&[sqrt])?;
+        let diff = model_diff(&before, &after);
+        assert_eq!(diff.added, vec!["sqrt".to_string()]);
+        assert!(diff.removed.is_empty());
+        assert_eq!(diff.changed.len(), 1);
+        assert_eq!(diff.changed[0].name, "neg");
+        assert!(diff.changed[0].op_type_changed());
+        assert_eq!(
+            diff.rewired,
+            vec![RewiredInput {
+                node: "neg".to_string(),
+                input: 0,
+                before: Some(("a".to_string(), 0)),
+                after: Some(("abs".to_string(), 0)),
+            }]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn audited_declutter() -> TractResult<()> {
+        let mut model = TypedModel::default();
+        let fact = TypedFact::dt_shape(f32::datum_type(), [2usize].as_ref())?;
+        let x = model.add_source("x", fact)?;
+        let c = model.add_const("c", rctensor1(&[1f32, 2.0]))?;
+        let n = model.wire_node("n", math::neg(), &[c])?[0];
+        let y = model.wire_node("y", math::add::bin(), &[x, n])?[0];
+        model.set_output_outlets(&[y])?;
+        let mut audit = PassAudit::default();
+        let decluttered = model.clone().declutter_audited(&mut audit)?;
+        let names: Vec<&str> = audit.steps.iter().map(|(name, _)| &**name).collect();
+        assert_eq!(names, &["PropConst(AlwaysCopy)", "compact", "DeclutterOps", "compact"]);
+        assert_eq!(audit.steps[1].1.removed, vec!["c".to_string(), "n".to_string()]);
+        assert!(audit.display().starts_with("PropConst(AlwaysCopy):\n"));
+        assert_eq!(model_diff(&model.clone().declutter()?, &decluttered), ModelDiff::default());
+        Ok(())
+    }
+}
@@ -0,0 +1,93 @@
+//! Graphviz export.
+use super::Model;
+
+const MAX_LABEL_LEN: usize = 48;
+
+fn escape(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+fn abbreviate(s: String) -> String {
+    if s.chars().count() > MAX_LABEL_LEN {
+        format!("{}...", s.chars().take(MAX_LABEL_LEN).collect::<String>())
+    } else {
+        s
+    }
+}
+
+/// Render a model as a Graphviz DOT directed graph.
+pub trait ToDot {
+    /// Nodes show their id, name, op and output facts. Edges show the
+    /// outlet they come from and its fact.
+    ///
+    /// The result can be rendered with `dot -Tsvg`.
+    fn to_dot(&self) -> String;
+}
+
+impl<M: Model + ?Sized> ToDot for M {
+    fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph model {\n  node [shape=box];\n");
+        for id in 0..self.nodes_len() {
+            let mut label =
+                format!("#{} {}\\n{}", id, escape(self.node_name(id)), self.node_op(id).name());
+            for slot in 0..self.node_output_count(id) {
+                let fact = self.outlet_fact_format(super::OutletId::new(id, slot));
+                label.push_str(&format!("\\n{}", escape(&abbreviate(fact))));
+            }
+            dot.push_str(&format!("  n{} [label=\"{}\"];\n", id, label));
+        }
+        for id in 0..self.nodes_len() {
+            for (slot, input) in self.node_inputs(id).iter().enumerate() {
+                let fact = escape(&abbreviate(self.outlet_fact_format(*input)));
+                dot.push_str(&format!(
+                    "  n{} -> n{} [label=\"{}/{} -> {}\\n{}\"];\n",
+                    input.node, id, input.node, input.slot, slot, fact
+                ));
+            }
+        }
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::internal::*;
+    use crate::ops::math;
+
+    fn check(dot: &str, nodes: usize, edges: usize) {
+        let node_re = regex::Regex::new(r#"(?m)^  n\d+ \[label=".*"\];$"#).unwrap();
+        let edge_re = regex::Regex::new(r#"(?m)^  n\d+ -> n\d+ \[label=".*"\];$"#).unwrap();
+        assert!(dot.starts_with("digraph model {\n"));
+        assert!(dot.ends_with("}\n"));
+        assert_eq!(node_re.find_iter(dot).count(), nodes);
+        assert_eq!(edge_re.find_iter(dot).count(), edges);
+    }
+
+    #[test]
+    fn typed() -> TractResult<()> {
+        let mut model = TypedModel::default();
+        let x =
+            model.add_source("x", TypedFact::dt_shape(f32::datum_type(), [2usize].as_ref())?)?;
+        model.add_const("big", ndarray::Array2::<f32>::zeros((64, 64)).into_arc_tensor())?;
+        let n = model.wire_node("n", math::neg(), &[x])?[0];
+        let a = model.wire_node("a", math::add::bin(), &[x, n])?[0];
+        model.set_output_outlets(&[a])?;
+        let dot = model.to_dot();
+        check(&dot, 4, 3);
+        assert!(dot.contains("2xF32"));
+        assert!(dot.lines().all(|l| l.len() < 4 * MAX_LABEL_LEN));
+        Ok(())
+    }
+
+    #[test]
+    fn inference() -> TractResult<()> {
+        let mut model = InferenceModel::default();
+        let x = model.add_source("x", InferenceFact::dt(f32::datum_type()))?;
+        let n = model.wire_node("n", math::neg(), &[x])?[0];
+        model.set_output_outlets(&[n])?;
+        check(&(&model as &dyn Model).to_dot(), 2, 1);
+        Ok(())
+    }
+}
@@ -213,14 +213,7 @@ impl ModelWireNode<TypedFact, Box<dyn TypedOp>> for TypedModel {
         let output_facts = {
             let input_facts =
                 inputs.iter().map(|o| self.outlet_fact(*o)).collect::<TractResult<TVec<_>>>()?;
-            if input_facts.iter().all(|f| f.konst.is_some()) && op.as_stateless().is_some() {
-                let tensors =
-                    input_facts.iter().map(|f| f.konst.clone().unwrap()).collect::<TVec<_>>();
-                let outputs = op.as_stateless().unwrap().eval(tensors)?;
-                outputs.into_iter().map(|t| TypedFact::from(t)).collect()
-            } else {
-                op.output_facts(&*input_facts)?
-            }
+            super::shapes::output_facts(op.as_ref(), &*input_facts)?
         };
         let id = self.add_node(name, op, output_facts)?;
         inputs
@@ -28,14 +28,7 @@ impl<'a> TryFrom<&'a InferenceFact> for TypedFact {
         if let (Some(datum_type), Some(shape)) =
             (fact.datum_type.concretize(), fact.shape.concretize())
         {
-            let stream_info = shape
-                .iter()
-                .cloned()
-                .enumerate()
-                .find(|d| d.1.to_integer().is_err())
-                .map(|(axis, len)| StreamInfo { axis, len });
-            let shape = shape.iter().map(|d| d.to_integer().unwrap_or(0) as usize).collect();
-            let shape = ShapeInfo { shape, stream_info };
+            let shape = ShapeInfo::from_dims(shape)?;
             Ok(TypedFact { datum_type, shape, konst: fact.value.concretize() })
         } else {
             bail!("Can not make a TypedFact out of {:?}", fact)
@@ -43,7 +36,6 @@ impl<'a> TryFrom<&'a InferenceFact> for TypedFact {
     }
 }
 
-
 impl<'a> From<&'a Tensor> for InferenceFact {
     fn from(t: &'a Tensor) -> InferenceFact {
         InferenceFact::from(t.clone())
@@ -56,7 +48,6 @@ impl<'a> From<&'a InferenceFact> for InferenceFact {
     }
 }
 
-
 /// Streaming information for a streamed tensor.
 #[derive(Debug, Clone, Default, PartialEq)]
 pub struct StreamInfo {
@@ -68,94 +59,89 @@ pub struct StreamInfo {
 
 /// Fully determined dimension of a tensor.
 ///
-/// Tensors in tract can have one streaming dimension. TDim generalize the
-/// regular tensor dimensions (usize) to arithmetic expressions of `S`, the
+/// Dimensions are TDim: plain integers, or arithmetic expressions of symbols
+/// standing for dimensions only known at runtime, like a batch size.
+///
+/// Tensors in tract can have one streaming dimension, involving `S`, the
 /// (sometimes hypothetical) tensor length on the streaming axis.
 #[derive(Clone)]
 pub struct ShapeInfo {
-    shape: TVec<usize>,
+    dims: TVec<TDim>,
+    concrete: Option<TVec<usize>>,
     /// Optional information for streaming tensors. None for regular tensors.
     pub stream_info: Option<StreamInfo>,
 }
 
 impl PartialEq for ShapeInfo {
     fn eq(&self, other: &ShapeInfo) -> bool {
-        self.shape.len() == other.shape.len() && self.iter().zip(other.iter()).all(|(a, b)| a == b)
+        self.rank() == other.rank() && self.iter().zip(other.iter()).all(|(a, b)| a == b)
     }
 }
 
 impl ShapeInfo {
+    fn compute_concrete(&mut self) {
+        self.concrete = self.dims.iter().map(|d| d.to_integer().ok().map(|d| d as usize)).collect();
+    }
+
     /// Rank of the tensor.
     pub fn rank(&self) -> usize {
-        self.shape.len()
+        self.dims.len()
     }
 
     /// Extended dimension of the i-th axis.
     ///
-    /// The TDim will wrap a plain integer for regular (non-streaming) tensors.
+    /// The TDim will wrap a plain integer for regular (non-symbolic) dimensions.
     pub fn dim(&self, i: usize) -> TDim {
         if let Some(ref stream) = self.stream_info {
             if stream.axis == i {
                 return stream.len.clone();
             }
         }
-        self.shape[i].to_dim()
+        self.dims[i].clone()
     }
 
     /// Set the i-th axis dimension.
     pub fn set_dim(&mut self, i: usize, dim: TDim) -> TractResult<()> {
-        if let Some(ref stream) = self.stream_info {
-            if let Ok(int) = dim.to_integer() {
-                self.shape[i] = int as _;
-                if stream.axis == i {
-                    self.stream_info = None;
-                }
-            } else {
-                if stream.axis != i {
-                    bail!("Attempt at building a shape with two streaming dim")
-                } else {
-                    self.stream_info = Some(StreamInfo { len: dim, axis: i })
-                }
-            }
-        } else {
-            if let Ok(int) = dim.to_integer() {
-                self.shape[i] = int as _;
-            } else {
-                self.shape[i] = 0;
-                self.stream_info = Some(StreamInfo { len: dim, axis: i })
+        if dim.is_stream() {
+            if self.stream_info.as_ref().map(|s| s.axis != i).unwrap_or(false) {
+                bail!("Attempt at building a shape with two streaming dim")
             }
+            self.stream_info = Some(StreamInfo { len: dim.clone(), axis: i })
+        } else if self.stream_info.as_ref().map(|s| s.axis == i).unwrap_or(false) {
+            self.stream_info = None;
         }
+        self.dims[i] = dim;
+        self.compute_concrete();
         Ok(())
     }
 
     pub fn rm_axis(&mut self, axis: usize) -> TractResult<()> {
-        self.shape.remove(axis);
+        self.dims.remove(axis);
         if let Some(s) = self.stream_info.as_mut() {
             if s.axis > axis {
                 s.axis -= 1;
             }
         }
+        self.compute_concrete();
         Ok(())
     }
 
-    /// Shape of the tensor, unless it is streaming.
+    /// Shape of the tensor, unless it is streaming or symbolic.
     pub fn as_finite(&self) -> Option<&[usize]> {
         match self.stream_info {
-            None => Some(&*self.shape),
+            None => self.concrete.as_ref().map(|c| &**c),
             _ => None,
         }
     }
 
+    /// Is the shape dependent on symbols, streaming or not ?
+    pub fn is_symbolic(&self) -> bool {
+        self.as_finite().is_none()
+    }
+
     /// Iterator over dimension of the shape.
     pub fn iter<'a>(&'a self) -> impl Iterator<Item = TDim> + 'a {
-        self.shape.clone().into_iter().enumerate().map(move |(ix, d)| {
-            if let Some(ref info) = self.stream_info {
-                if ix == info.axis {
-                    return info.len.clone();
-                }
-            }
-            (d as i64).to_dim()
-        })
+        (0..self.rank()).map(move |ix| self.dim(ix))
     }
 
     /// Convert the shape to an array of extended dimensions.
@@ -179,14 +165,9 @@ impl ShapeInfo {
                 .enumerate()
                 .find(|(_ix, d)| d.is_stream())
                 .map(|(ix, d)| StreamInfo { axis: ix, len: d.clone() });
-            Ok(ShapeInfo {
-                shape: it
-                    .as_ref()
-                    .iter()
-                    .map(|t| t.to_integer().map(|i| i as usize).unwrap_or(0))
-                    .collect(),
-                stream_info,
-            })
+            let mut shape = ShapeInfo { dims: it.as_ref().into(), concrete: None, stream_info };
+            shape.compute_concrete();
+            Ok(shape)
         }
     }
 }
@@ -208,7 +189,11 @@ impl TryFrom<&[TDim]> for ShapeInfo {
 impl TryFrom<&[usize]> for ShapeInfo {
     type Error = TractError;
     fn try_from(it: &[usize]) -> TractResult<ShapeInfo> {
-        Ok(ShapeInfo { shape: it.into(), stream_info: None })
+        Ok(ShapeInfo {
+            dims: it.iter().map(|d| d.to_dim()).collect(),
+            concrete: Some(it.into()),
+            stream_info: None,
+        })
     }
 }
 
@@ -276,7 +261,7 @@ impl From<Arc<Tensor>> for TypedFact {
     fn from(t: Arc<Tensor>) -> TypedFact {
         TypedFact {
             datum_type: t.datum_type(),
-            shape: ShapeInfo { shape: t.shape().into(), stream_info: None },
+            shape: t.shape().try_into().unwrap(),
             konst: Some(t),
         }
     }
@@ -360,3 +345,66 @@ impl<'t> From<&'t Tensor> for NormalizedFact {
         NormalizedFact { datum_type: t.datum_type(), shape: t.shape().try_into().unwrap() }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ops::array::{Concat, Reshape, Slice};
+    use crate::ops::matmul::MatMul;
+
+    #[test]
+    fn symbolic_batch() -> TractResult<()> {
+        let b = TDim::sym('B');
+        let mut model = InferenceModel::default();
+        let shape = vec![b.clone(), 3.to_dim(), 4.to_dim()];
+        let x = model.add_source("x", InferenceFact::dt_shape(f32::datum_type(), shape))?;
+        let spec = model.add_const("spec", rctensor1(&[0i64, -1]))?;
+        let flat = model.wire_node("flat", Reshape::new(), &[x, spec])?[0];
+        let cat = model.wire_node("cat", Concat::new(1), &[flat, flat])?[0];
+        let slice = model.wire_node("slice", Slice::new(1, 0usize, 20usize), &[cat])?[0];
+        let w = model.add_const("w", ndarray::Array2::<f32>::ones((20, 5)).into_arc_tensor())?;
+        let logits = model.wire_node("logits", MatMul::default(), &[slice, w])?[0];
+        model.set_output_outlets(&[logits])?;
+
+        let typed = model.into_typed()?;
+        let fact = typed.outlet_fact(typed.output_outlets()?[0])?;
+        assert_eq!(fact.shape.to_tvec(), tvec!(b.clone(), 5.to_dim()));
+        assert!(fact.shape.is_symbolic());
+        assert!(fact.shape.stream_info.is_none());
+
+        let input = ndarray::Array3::<f32>::ones((2, 3, 4));
+        let output = SimplePlan::new(&typed)?.run(tvec!(input.clone().into()))?;
+        assert_eq!(output[0].shape(), &[2, 5]);
+        let optimized = typed.into_optimized()?;
+        let output = SimplePlan::new(&optimized)?.run(tvec!(input.into()))?;
+        assert_eq!(output[0].shape(), &[2, 5]);
+        Ok(())
+    }
+
+    #[test]
+    fn symbolic_dim_folding() -> TractResult<()> {
+        let b = TDim::sym('B');
+        assert_eq!(TDim::from(2) * 3 + 1, 7.to_dim());
+        assert_eq!((b.clone() * 3 + 1).eval_with(&hashmap!('B'.into() => 2)), Some(7));
+        assert_eq!("4B".parse::<TDim>().unwrap(), b.clone() * 4);
+        assert!(!b.is_stream());
+        let shape = ShapeInfo::from_dims(&[b.clone(), TDim::s(), 2.to_dim()])?;
+        assert_eq!(shape.stream_info.as_ref().map(|s| s.axis), Some(1));
+        assert_eq!(shape.dim(0), b);
+        Ok(())
+    }
+
+    #[test]
+    fn named_symbols() -> TractResult<()> {
+        let seq = TDim::sym("seq_len");
+        assert_eq!("seq_len".parse::<TDim>().unwrap(), seq);
+        assert_eq!("4seq_len".parse::<TDim>().unwrap(), seq.clone() * 4);
+        assert_ne!(seq, TDim::sym('s'));
+        assert_eq!(seq.symbols(), vec!("seq_len".into()));
+        let values = hashmap!("seq_len".into() => 5, 'B'.into() => 2);
+        assert_eq!((seq.clone() * TDim::sym('B') + 1).eval_with(&values), Some(11));
+        assert!("4seq-len".parse::<TDim>().is_err());
+        assert_ne!(Symbol::fresh("N"), Symbol::fresh("N"));
+        Ok(())
+    }
+}
@@ -43,23 +43,37 @@
 use std::collections::HashMap;
 use std::str;
 
+mod builder;
+mod chain;
 pub(crate) mod compact;
+mod consistency;
+pub mod constants;
+pub mod diff;
+mod dot;
 mod dsl;
 mod fact;
 mod model;
 mod node;
 pub mod order;
 mod patch;
+mod shapes;
+mod submodel;
 pub(crate) mod translator;
 
+pub use self::builder::TypedModelBuilder;
+pub use self::consistency::ConsistencyWarning;
+pub use self::diff::{model_diff, ModelDiff};
+pub use self::dot::ToDot;
 pub use self::dsl::*;
 pub use self::fact::*;
 pub use self::model::*;
 pub use self::node::*;
 pub use self::order::eval_order;
 pub use self::patch::ModelPatch;
+pub use self::shapes::infer_shapes;
 pub use crate::analyser::types::InferenceFact;
 pub use crate::ops::{InferenceOp, Op, TypedOp};
+pub use crate::optim::{ConstPropagationStrategy, PassAudit};
 
 use crate::model::translator::Translate;
 use crate::plan::{SimplePlan, SimpleState};
@@ -250,6 +264,10 @@ impl InferenceModel {
 
     /// Attempt full analyse and conversion to TypedModel.
     pub fn into_typed(mut self) -> TractResult<TypedModel> {
+        use crate::errors::TractResultExt;
+        for node in self.nodes() {
+            node.op.validate_attributes().chain_err(|| format!("Invalid attributes for {}", node))?;
+        }
         self.analyse(false)?;
         let m = self.incorporate()?;
 
@@ -288,13 +306,26 @@ impl InferenceModel {
 impl TypedModel {
     /// Perform declutter pass on the network.
     pub fn declutter(self) -> TractResult<TypedModel> {
+        self.declutter_with_audit(None)
+    }
+
+    /// Same as `declutter`, recording in `audit` what each pass changed.
+    pub fn declutter_audited(self, audit: &mut PassAudit) -> TractResult<TypedModel> {
+        self.declutter_with_audit(Some(audit))
+    }
+
+    fn declutter_with_audit(self, mut audit: Option<&mut PassAudit>) -> TractResult<TypedModel> {
         let mut model = self;
         let model_inputs = model.input_outlets()?.len();
         let model_outputs = model.output_outlets()?.len();
         loop {
             let mut done_something = false;
             for p in crate::optim::declutter() {
-                done_something = done_something || p.pass(&mut model)?;
+                let name = format!("{:?}", p);
+                done_something = done_something
+                    || PassAudit::step(audit.as_deref_mut(), &name, &mut model, |m| p.pass(m))?;
+                // patches append their nodes, even when they feed earlier ones
+                model.topo_sort()?;
                 if cfg!(debug_assertions) {
                     model.check_edges()?;
                     assert_eq!(model.input_outlets()?.len(), model_inputs);
@@ -304,18 +335,31 @@ impl TypedModel {
             if !done_something {
                 break;
             }
-            model = compact::compact(&model)?;
+            PassAudit::compact(audit.as_deref_mut(), &mut model)?;
         }
-        compact::compact(&model)
+        PassAudit::compact(audit, &mut model)?;
+        Ok(model)
     }
 
     /// Translate the graph to optimized operators.
     pub fn codegen(self) -> TractResult<TypedModel> {
+        self.codegen_with_audit(None)
+    }
+
+    /// Same as `codegen`, recording in `audit` what each pass changed.
+    pub fn codegen_audited(self, audit: &mut PassAudit) -> TractResult<TypedModel> {
+        self.codegen_with_audit(Some(audit))
+    }
+
+    fn codegen_with_audit(self, mut audit: Option<&mut PassAudit>) -> TractResult<TypedModel> {
         let mut model = self;
         loop {
             let mut done_something = false;
             for p in crate::optim::codegen() {
-                done_something = done_something || p.pass(&mut model)?;
+                let name = format!("{:?}", p);
+                done_something = done_something
+                    || PassAudit::step(audit.as_deref_mut(), &name, &mut model, |m| p.pass(m))?;
+                model.topo_sort()?;
                 if cfg!(debug_assertions) {
                     model.check_edges()?;
                 }
@@ -323,8 +367,9 @@ impl TypedModel {
             if !done_something {
                 break;
             }
-            model = compact::compact(&model)?;
+            PassAudit::compact(audit.as_deref_mut(), &mut model)?;
         }
+        model.optimized = true;
         Ok(model)
     }
 
@@ -332,7 +377,90 @@ impl TypedModel {
         invariants::for_model(self)
     }
 
+    /// Sums the flops of one evaluation of every node, from `ops::cost::op_cost`.
+    ///
+    /// Fails if some shape is symbolic.
+    pub fn total_flops(&self) -> TractResult<u64> {
+        let mut flops = 0;
+        for node in self.nodes() {
+            let inputs = self.node_input_facts(node.id)?;
+            flops += crate::ops::cost::op_cost(node.op.as_ref(), &*inputs)?.flops;
+        }
+        Ok(flops)
+    }
 
+    /// Computes the connected components of the constant part of the graph.
+    ///
+    /// Each component lists the nodes and outlets whose value is known at
+    /// this stage, and the outlets by which it feeds the rest of the graph.
+    ///
+    /// ```
+    /// # use tract_core::internal::*;
+    /// # use tract_core::ops::math;
+    /// # fn main() -> TractResult<()> {
+    /// let mut model = TypedModel::default();
+    /// let fact = TypedFact::dt_shape(f32::datum_type(), [2usize].as_ref())?;
+    /// let x = model.add_source("x", fact)?;
+    /// let c = model.add_const("c", rctensor1(&[1f32, 2.0]))?;
+    /// let n = model.wire_node("n", math::neg(), &[c])?[0];
+    /// let y = model.wire_node("y", math::add::bin(), &[x, n])?[0];
+    /// model.set_output_outlets(&[y])?;
+    ///
+    /// for component in model.constant_subgraphs()? {
+    ///     for node in component.nodes() {
+    ///         println!("constant: {}", model.node(node));
+    ///     }
+    ///     for outlet in &component.outputs {
+    ///         println!("used by the graph through {:?}", outlet);
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn constant_subgraphs(&self) -> TractResult<Vec<constants::Component>> {
+        constants::connected_components(self)
+    }
+
+    /// Replace constant subgraphs by Const nodes, using the given strategy.
+    ///
+    /// `declutter` performs this with `ConstPropagationStrategy::AlwaysCopy`.
+    pub fn propagate_constants_with_strategy(
+        &mut self,
+        strategy: ConstPropagationStrategy,
+    ) -> TractResult<bool> {
+        use crate::optim::TypedPass;
+        crate::optim::PropConst(strategy).pass(self)
+    }
+
+    /// Extract the part of the network between `inputs` and `outputs`.
+    ///
+    /// `inputs` become the sources of the new model, and `outputs` its
+    /// outputs. The nodes in between are copied, others are dropped. Fails if
+    /// an output can not be reached from the inputs, or depends on a value
+    /// that is not computed from the inputs or constants.
+    pub fn extract_submodel(
+        &self,
+        inputs: &[OutletId],
+        outputs: &[OutletId],
+    ) -> TractResult<TypedModel> {
+        submodel::extract_submodel(self, inputs, outputs)
+    }
+
+    /// Append `next` to this model, feeding some of `next` inputs with
+    /// outputs of this one.
+    ///
+    /// Each pair in `output_to_input` wires an output of this model (by
+    /// position) to an input of `next`, which must have the same type and
+    /// shape. All inputs of `next` must be wired. The resulting model has the
+    /// inputs of this one and the outputs of `next`. Nodes of `next` are
+    /// renamed with a numeric suffix if their name is already taken.
+    pub fn chain(
+        self,
+        next: TypedModel,
+        output_to_input: &[(usize, usize)],
+    ) -> TractResult<TypedModel> {
+        chain::chain(self, &next, output_to_input)
+    }
 
     /// Attempt to convert the network to a NormalizedModel.
     pub fn into_normalized(self) -> TractResult<NormalizedModel> {
@@ -346,6 +474,14 @@ impl TypedModel {
         let model = compact::compact(&model)?;
         Ok(model)
     }
+
+    /// Same as `into_optimized`, recording in `audit` what each pass changed.
+    pub fn into_optimized_audited(self, audit: &mut PassAudit) -> TractResult<TypedModel> {
+        let model = self.declutter_audited(audit)?;
+        let mut model = model.codegen_audited(audit)?;
+        PassAudit::compact(Some(audit), &mut model)?;
+        Ok(model)
+    }
 }
 
 impl NormalizedModel {
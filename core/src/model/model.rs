@@ -21,6 +21,8 @@ where
     pub(crate) outputs: Vec<OutletId>,
     /// outlet labels
     pub(crate) outlet_labels: HashMap<OutletId, String>,
+    /// set by codegen: the ops are then bound to the facts of the model
+    pub(crate) optimized: bool,
 }
 
 impl<TI, O> Default for ModelImpl<TI, O>
@@ -35,6 +37,7 @@ where
             inputs: vec![],
             outputs: vec![],
             outlet_labels: HashMap::new(),
+            optimized: false,
         }
     }
 }
@@ -62,6 +65,32 @@ where
         Ok(id)
     }
 
+    /// Whether codegen ran on the model. Its ops may then only be run on
+    /// the input shapes they were optimized for.
+    pub fn is_optimized(&self) -> bool {
+        self.optimized
+    }
+
+    /// Flag a model made of already optimized ops, as one deserialized after
+    /// codegen, as optimized.
+    pub fn set_optimized(&mut self, optimized: bool) {
+        self.optimized = optimized
+    }
+
+    /// Add a node like `add_node`, but fail if its name is already taken.
+    pub fn add_node_named(
+        &mut self,
+        name: impl Into<String>,
+        op: impl Into<O>,
+        output_facts: TVec<TI>,
+    ) -> TractResult<usize> {
+        let name = name.into();
+        if self.nodes_by_name.contains_key(&name) {
+            bail!("A node named {} already exists", name)
+        }
+        self.add_node(name, op, output_facts)
+    }
+
     /// Connect a node outlet to a node inlet.
     pub fn add_edge(&mut self, outlet: OutletId, inlet: InletId) -> TractResult<()> {
         if let Some(previous) = self.nodes[inlet.node].inputs.get(inlet.slot).cloned() {
@@ -131,6 +160,35 @@ where
         self.set_outlet_fact(outlet, fact)
     }
 
+    /// Name of the `ix`-th input: the name of its source node.
+    pub fn input_name(&self, ix: usize) -> TractResult<&str> {
+        let input = self.inputs.get(ix).ok_or_else(|| format!("No input #{}", ix))?;
+        Ok(&*self.nodes[input.node].name)
+    }
+
+    /// Find the position of an input by its name.
+    pub fn input_by_name(&self, name: &str) -> TractResult<usize> {
+        (0..self.inputs.len())
+            .find(|&ix| self.input_name(ix).ok() == Some(name))
+            .ok_or_else(|| format!("No input named \"{}\"", name).into())
+    }
+
+    /// Rename the `ix`-th input, renaming its source node.
+    pub fn rename_input(&mut self, ix: usize, name: &str) -> TractResult<()> {
+        let node = self.inputs.get(ix).ok_or_else(|| format!("No input #{}", ix))?.node;
+        if self.nodes_by_name.get(name).map(|&id| id != node).unwrap_or(false) {
+            bail!("Can not rename input #{} to \"{}\": a node has this name already", ix, name)
+        }
+        self.rename_node(node, name)
+    }
+
+    /// Reorder the inputs: `new_order[i]` is the current position of the
+    /// input that will be at position `i`.
+    pub fn reorder_inputs(&mut self, new_order: &[usize]) -> TractResult<()> {
+        self.inputs = reordered(&self.inputs, new_order)?;
+        Ok(())
+    }
+
     // Outputs
     /// Get model outputs.
     pub fn output_outlets(&self) -> TractResult<&[OutletId]> {
@@ -192,6 +250,36 @@ where
         self.set_outlet_fact(outlet, fact)
     }
 
+    /// Name of the `ix`-th output: the label of its outlet if it has one, the
+    /// name of its node otherwise.
+    pub fn output_name(&self, ix: usize) -> TractResult<&str> {
+        let output = self.outputs.get(ix).ok_or_else(|| format!("No output #{}", ix))?;
+        Ok(self.outlet_label(*output).unwrap_or(&*self.nodes[output.node].name))
+    }
+
+    /// Find the position of an output by its name.
+    pub fn output_by_name(&self, name: &str) -> TractResult<usize> {
+        (0..self.outputs.len())
+            .find(|&ix| self.output_name(ix).ok() == Some(name))
+            .ok_or_else(|| format!("No output named \"{}\"", name).into())
+    }
+
+    /// Rename the `ix`-th output, by setting the label of its outlet.
+    ///
+    /// The node is left alone, as it may feed other nodes too.
+    pub fn rename_output(&mut self, ix: usize, name: &str) -> TractResult<()> {
+        let output = *self.outputs.get(ix).ok_or_else(|| format!("No output #{}", ix))?;
+        self.set_outlet_label(output, name.to_string());
+        Ok(())
+    }
+
+    /// Reorder the outputs: `new_order[i]` is the current position of the
+    /// output that will be at position `i`.
+    pub fn reorder_outputs(&mut self, new_order: &[usize]) -> TractResult<()> {
+        self.outputs = reordered(&self.outputs, new_order)?;
+        Ok(())
+    }
+
     // nodes and their facts
 
     /// Iterate over all node names.
@@ -212,8 +300,33 @@ where
         Ok(&mut self.nodes[*id])
     }
 
+    /// Find an outlet by its label, or by its node name. Outlets other than
+    /// the first are named after their node and slot, as in `lstm:1`.
+    pub fn outlet_by_name(&self, name: &str) -> TractResult<OutletId> {
+        if let Some(outlet) = self.find_outlet_label(name) {
+            return Ok(outlet);
+        }
+        let (node, slot) = match name.rfind(':') {
+            Some(ix) if !self.nodes_by_name.contains_key(name) => {
+                let slot = name[ix + 1..]
+                    .parse::<usize>()
+                    .map_err(|_| format!("No outlet named {} in model", name))?;
+                (&name[..ix], slot)
+            }
+            _ => (name, 0),
+        };
+        let node = self.node_by_name(node)?;
+        if slot >= node.outputs.len() {
+            bail!("Node {} has no output {}", node, slot)
+        }
+        Ok(OutletId::new(node.id, slot))
+    }
+
     pub fn rename_node(&mut self, id: usize, name: &str) -> TractResult<()> {
-        self.node_mut(id).name = name.to_string();
+        let previous = std::mem::replace(&mut self.node_mut(id).name, name.to_string());
+        if self.nodes_by_name.get(&previous) == Some(&id) {
+            self.nodes_by_name.remove(&previous);
+        }
         self.nodes_by_name.insert(name.to_string(), id);
         Ok(())
     }
@@ -349,6 +462,92 @@ where
         }
         Ok(())
     }
+
+    /// Whether every node comes after the nodes it depends on.
+    pub fn is_topo_sorted(&self) -> bool {
+        self.nodes.iter().all(|n| {
+            n.inputs.iter().all(|i| i.node < n.id) && n.control_inputs.iter().all(|&c| c < n.id)
+        })
+    }
+
+    /// Renumber the nodes in topological order, updating all references.
+    ///
+    /// This is Kahn's algorithm, always picking the lowest ready id, so nodes
+    /// that are already in order keep their relative order. Nodes outside the
+    /// paths to the outputs are kept. Fails if the graph has a cycle.
+    pub fn topo_sort(&mut self) -> TractResult<()> {
+        if self.is_topo_sorted() {
+            return Ok(());
+        }
+        let len = self.nodes.len();
+        let mut pending = vec![0; len];
+        let mut dependents: Vec<Vec<usize>> = vec![vec![]; len];
+        for node in &self.nodes {
+            let dependencies =
+                node.inputs.iter().map(|i| i.node).chain(node.control_inputs.iter().cloned());
+            for dependency in dependencies {
+                pending[node.id] += 1;
+                dependents[dependency].push(node.id);
+            }
+        }
+        let mut ready: std::collections::BTreeSet<usize> =
+            (0..len).filter(|&n| pending[n] == 0).collect();
+        let mut order = Vec::with_capacity(len);
+        while let Some(&next) = ready.iter().next() {
+            ready.remove(&next);
+            order.push(next);
+            for &dependent in &dependents[next] {
+                pending[dependent] -= 1;
+                if pending[dependent] == 0 {
+                    ready.insert(dependent);
+                }
+            }
+        }
+        if order.len() != len {
+            bail!("Can not sort a cyclic graph")
+        }
+        let mut new_ids = vec![0; len];
+        for (new, &old) in order.iter().enumerate() {
+            new_ids[old] = new;
+        }
+        let remap = |o: &mut OutletId| o.node = new_ids[o.node];
+        let mut nodes: Vec<Option<BaseNode<TI, O>>> = self.nodes.drain(..).map(Some).collect();
+        for &old in &order {
+            let mut node = nodes[old].take().unwrap();
+            node.id = new_ids[old];
+            node.inputs.iter_mut().for_each(remap);
+            node.control_inputs.iter_mut().for_each(|c| *c = new_ids[*c]);
+            for output in node.outputs.iter_mut() {
+                output.successors.iter_mut().for_each(|s| s.node = new_ids[s.node]);
+            }
+            self.nodes.push(node);
+        }
+        self.nodes_by_name.values_mut().for_each(|id| *id = new_ids[*id]);
+        self.inputs.iter_mut().for_each(remap);
+        self.outputs.iter_mut().for_each(remap);
+        self.outlet_labels = self
+            .outlet_labels
+            .drain()
+            .map(|(mut outlet, label)| {
+                remap(&mut outlet);
+                (outlet, label)
+            })
+            .collect();
+        Ok(())
+    }
+}
+
+fn reordered(outlets: &[OutletId], new_order: &[usize]) -> TractResult<Vec<OutletId>> {
+    let mut seen = vec![false; outlets.len()];
+    for &ix in new_order {
+        if ix >= outlets.len() || std::mem::replace(&mut seen[ix], true) {
+            bail!("{:?} is not a permutation of 0..{}", new_order, outlets.len())
+        }
+    }
+    if new_order.len() != outlets.len() {
+        bail!("{:?} is not a permutation of 0..{}", new_order, outlets.len())
+    }
+    Ok(new_order.iter().map(|&ix| outlets[ix]).collect())
 }
 
 impl<TI, O> Model for ModelImpl<TI, O>
@@ -424,3 +623,107 @@ where
         &self.nodes[outlet.node].outputs[outlet.slot].successors
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::internal::*;
+    use crate::ops::math;
+
+    // a - b and a * b, from inputs a and b
+    fn model() -> TractResult<TypedModel> {
+        let mut model = TypedModel::default();
+        let fact = TypedFact::dt_shape(f32::datum_type(), [2usize].as_ref())?;
+        let a = model.add_source("input.1", fact.clone())?;
+        let b = model.add_source("input.2", fact)?;
+        let sub = model.wire_node("output.47", math::sub::bin(), &[a, b])?[0];
+        let mul = model.wire_node("output.48", math::mul::bin(), &[a, b])?[0];
+        model.set_output_outlets(&[sub, mul])?;
+        Ok(model)
+    }
+
+    fn run(model: &TypedModel, a: &[f32], b: &[f32]) -> TractResult<TVec<Arc<Tensor>>> {
+        SimplePlan::new(model)?.run(tvec!(tensor1(a), tensor1(b)))
+    }
+
+    #[test]
+    fn rename() -> TractResult<()> {
+        let mut model = model()?;
+        model.rename_input(1, "b")?;
+        model.rename_output(0, "difference")?;
+        assert_eq!(model.input_by_name("b")?, 1);
+        assert_eq!(model.input_by_name("input.1")?, 0);
+        assert!(model.input_by_name("input.2").is_err());
+        assert!(model.node_by_name("input.2").is_err());
+        assert_eq!(model.node_by_name("b")?.id, model.input_outlets()?[1].node);
+        assert_eq!(model.output_by_name("difference")?, 0);
+        assert_eq!(model.output_by_name("output.48")?, 1);
+        assert!(model.output_by_name("output.47").is_err());
+        assert!(model.rename_input(0, "b").is_err());
+        assert!(model.rename_input(2, "c").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn reorder() -> TractResult<()> {
+        let mut model = model()?;
+        model.reorder_inputs(&[1, 0])?;
+        model.reorder_outputs(&[1, 0])?;
+        assert_eq!(model.input_name(0)?, "input.2");
+        assert_eq!(model.output_name(0)?, "output.48");
+        // input.2 is fed first, and the product comes first
+        let result = run(&model, &[1.0, 2.0], &[5.0, 7.0])?;
+        assert_eq!(result[0], rctensor1(&[5f32, 14.0]));
+        assert_eq!(result[1], rctensor1(&[4f32, 5.0]));
+        assert!(model.reorder_inputs(&[0, 0]).is_err());
+        assert!(model.reorder_outputs(&[0]).is_err());
+        assert!(model.reorder_outputs(&[0, 2]).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn topo_sort() -> TractResult<()> {
+        let mut model = TypedModel::default();
+        let fact = TypedFact::dt_shape(f32::datum_type(), [2usize].as_ref())?;
+        let a = model.add_source("a", fact.clone())?;
+        // abs is added before the neg it consumes
+        let abs = model.add_node("abs", math::abs(), tvec!(fact.clone()))?;
+        let neg = model.wire_node("neg", math::neg(), &[a])?[0];
+        model.add_edge(neg, InletId::new(abs, 0))?;
+        model.set_output_outlets(&[OutletId::new(abs, 0)])?;
+        model.set_outlet_label(OutletId::new(abs, 0), "result".to_string());
+        assert!(!model.is_topo_sorted());
+        assert!(SimplePlan::new(&model).is_ok());
+
+        model.topo_sort()?;
+        assert!(model.is_topo_sorted());
+        model.check_edges()?;
+        let names: Vec<&str> = model.nodes().iter().map(|n| &*n.name).collect();
+        assert_eq!(names, vec!("a", "neg", "abs"));
+        assert!(model.nodes().iter().enumerate().all(|(ix, n)| n.id == ix));
+        assert_eq!(model.node_by_name("abs")?.id, 2);
+        assert_eq!(model.output_outlets()?, &[OutletId::new(2, 0)]);
+        assert_eq!(model.find_outlet_label("result"), Some(OutletId::new(2, 0)));
+        let result = SimplePlan::new(&model)?.run(tvec!(tensor1(&[-1f32, 2.0])))?;
+        assert_eq!(result[0], rctensor1(&[1f32, 2.0]));
+        Ok(())
+    }
+
+    #[test]
+    fn lookup_by_name() -> TractResult<()> {
+        let mut model = model()?;
+        let fact = TypedFact::dt_shape(f32::datum_type(), [2usize].as_ref())?;
+        let split =
+            model.add_node_named("split", math::neg(), tvec!(fact.clone(), fact.clone()))?;
+        assert!(model.add_node_named("split", math::abs(), tvec!(fact)).is_err());
+        assert_eq!(model.node_by_name("split")?.id, split);
+        assert_eq!(model.node_by_name_mut("split")?.id, split);
+        assert_eq!(model.outlet_by_name("split")?, OutletId::new(split, 0));
+        assert_eq!(model.outlet_by_name("split:1")?, OutletId::new(split, 1));
+        assert!(model.outlet_by_name("split:2").is_err());
+        assert!(model.outlet_by_name("split:x").is_err());
+        assert!(model.outlet_by_name("merge").is_err());
+        model.set_outlet_label(OutletId::new(split, 1), "second".to_string());
+        assert_eq!(model.outlet_by_name("second")?, OutletId::new(split, 1));
+        Ok(())
+    }
+}
@@ -0,0 +1,94 @@
+//! Shape inference for TypedModel.
+use crate::internal::*;
+use crate::model::order::eval_order_for_nodes;
+
+/// Compute the output facts of an operator from its input facts.
+///
+/// If all inputs are constant and the operator is stateless, it is
+/// evaluated so that the output facts carry a constant value.
+pub(crate) fn output_facts(
+    op: &dyn TypedOp,
+    inputs: &[&TypedFact],
+) -> TractResult<TVec<TypedFact>> {
+    if inputs.iter().all(|f| f.konst.is_some()) && op.as_stateless().is_some() {
+        let tensors = inputs.iter().map(|f| f.konst.clone().unwrap()).collect::<TVec<_>>();
+        let outputs = op.as_stateless().unwrap().eval(tensors)?;
+        Ok(outputs.into_iter().map(|t| TypedFact::from(t)).collect())
+    } else {
+        op.output_facts(inputs)
+    }
+}
+
+/// Recompute the output facts of every node of a model.
+///
+/// Nodes are visited in topological order, facts of the model inputs are
+/// left untouched. This is useful after building or modifying a graph by
+/// hand, for instance with `add_node` and `add_edge`.
+pub fn infer_shapes(model: &mut TypedModel) -> TractResult<()> {
+    let inputs: Vec<usize> = model.input_outlets()?.iter().map(|o| o.node).collect();
+    let all: Vec<usize> = (0..model.nodes().len()).collect();
+    for id in eval_order_for_nodes(model.nodes(), &inputs, &all)? {
+        if inputs.contains(&id) {
+            continue;
+        }
+        let facts = {
+            let node = model.node(id);
+            let input_facts = model.node_input_facts(id)?;
+            let facts = output_facts(node.op.as_ref(), &*input_facts)
+                .chain_err(|| format!("Infering shapes for {}", node))?;
+            if facts.len() != node.outputs.len() {
+                bail!(
+                    "Infering shapes for {}: expected {} outputs, got {}",
+                    node,
+                    node.outputs.len(),
+                    facts.len()
+                );
+            }
+            facts
+        };
+        for (ix, fact) in facts.into_iter().enumerate() {
+            model.set_outlet_fact(OutletId::new(id, ix), fact)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ops::math;
+
+    #[test]
+    fn infer_after_manual_wiring() -> TractResult<()> {
+        let mut model = TypedModel::default();
+        let a =
+            model.add_source("a", TypedFact::dt_shape(f32::datum_type(), [2usize, 3].as_ref())?)?;
+        let fact = TypedFact::dt_shape(f32::datum_type(), [0usize].as_ref())?;
+        let neg = model.add_node("neg", math::neg(), tvec!(fact))?;
+        model.add_edge(a, InletId::new(neg, 0))?;
+        model.set_output_outlets(&[neg.into()])?;
+        infer_shapes(&mut model)?;
+        assert_eq!(
+            model.outlet_fact(neg.into())?,
+            &TypedFact::dt_shape(f32::datum_type(), [2usize, 3].as_ref())?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn infer_names_failing_node() -> TractResult<()> {
+        let mut model = TypedModel::default();
+        let a =
+            model.add_source("a", TypedFact::dt_shape(f32::datum_type(), [2usize].as_ref())?)?;
+        let b =
+            model.add_source("b", TypedFact::dt_shape(f32::datum_type(), [3usize].as_ref())?)?;
+        let fact = TypedFact::dt_shape(f32::datum_type(), [0usize].as_ref())?;
+        let add = model.add_node("the_add", math::add::bin(), tvec!(fact))?;
+        model.add_edge(a, InletId::new(add, 0))?;
+        model.add_edge(b, InletId::new(add, 1))?;
+        model.set_output_outlets(&[add.into()])?;
+        let err = infer_shapes(&mut model).unwrap_err();
+        assert!(format!("{}", err).contains("the_add"));
+        Ok(())
+    }
+}
@@ -0,0 +1,121 @@
+//! Submodel extraction.
+use crate::internal::*;
+use crate::model::order::eval_order_for_nodes;
+use bit_set::BitSet;
+
+fn outlet_name(model: &TypedModel, outlet: OutletId) -> String {
+    if let Some(label) = model.outlet_label(outlet) {
+        label.to_string()
+    } else if outlet.slot == 0 {
+        model.node(outlet.node).name.clone()
+    } else {
+        format!("{}:{}", model.node(outlet.node).name, outlet.slot)
+    }
+}
+
+pub(crate) fn extract_submodel(
+    model: &TypedModel,
+    inputs: &[OutletId],
+    outputs: &[OutletId],
+) -> TractResult<TypedModel> {
+    let input_nodes: Vec<usize> = inputs.iter().map(|i| i.node).collect();
+    let output_nodes: Vec<usize> = outputs.iter().map(|o| o.node).collect();
+    let order = eval_order_for_nodes(model.nodes(), &input_nodes, &output_nodes)?;
+
+    let mut reachable = BitSet::with_capacity(model.nodes().len());
+    for &n in &order {
+        if !input_nodes.contains(&n)
+            && model.node(n).inputs.iter().any(|i| inputs.contains(i) || reachable.contains(i.node))
+        {
+            reachable.insert(n);
+        }
+    }
+    for output in outputs {
+        if !inputs.contains(output) && !reachable.contains(output.node) {
+            bail!("Output {:?} is not reachable from submodel inputs {:?}", output, inputs)
+        }
+    }
+
+    let mut sub = TypedModel::default();
+    let mut map: HashMap<OutletId, OutletId> = HashMap::new();
+    for &input in inputs {
+        let mut fact = model.outlet_fact(input)?.clone();
+        fact.konst = None;
+        let source = sub.add_source(outlet_name(model, input), fact)?;
+        map.insert(input, source);
+    }
+    for n in order {
+        if input_nodes.contains(&n) {
+            continue;
+        }
+        let node = model.node(n);
+        if model.input_outlets()?.iter().any(|i| i.node == n) {
+            bail!("{} is a model input, but not a submodel input", node)
+        }
+        let facts = node.outputs.iter().map(|o| o.fact.clone()).collect();
+        let id = sub.add_node(&*node.name, node.op.clone(), facts)?;
+        for (ix, input) in node.inputs.iter().enumerate() {
+            let input = map.get(input).ok_or_else(|| {
+                format!("{} consumes {:?}, which is not a submodel input", node, input)
+            })?;
+            sub.add_edge(*input, InletId::new(id, ix))?;
+        }
+        for prec in &node.control_inputs {
+            if let Some(prec) = map.get(&OutletId::new(*prec, 0)) {
+                sub.node_mut(id).control_inputs.push(prec.node);
+            }
+        }
+        for slot in 0..node.outputs.len() {
+            let outlet = OutletId::new(n, slot);
+            map.insert(outlet, OutletId::new(id, slot));
+            if let Some(label) = model.outlet_label(outlet) {
+                sub.set_outlet_label(OutletId::new(id, slot), label.to_string());
+            }
+        }
+    }
+    let outputs: Vec<OutletId> = outputs.iter().map(|o| map[o]).collect();
+    sub.set_output_outlets(&outputs)?;
+    Ok(sub)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ops::math;
+
+    fn sequential() -> TractResult<TypedModel> {
+        let mut model = TypedModel::default();
+        let x =
+            model.add_source("x", TypedFact::dt_shape(f32::datum_type(), [2usize].as_ref())?)?;
+        let neg = model.wire_node("neg", math::neg(), &[x])?[0];
+        let abs = model.wire_node("abs", math::abs(), &[neg])?[0];
+        let two = model.add_const("two", rctensor0(2f32))?;
+        let mul = model.wire_node("mul", math::mul::bin(), &[abs, two])?[0];
+        model.set_output_outlets(&[mul])?;
+        Ok(model)
+    }
+
+    #[test]
+    fn second_half() -> TractResult<()> {
+        let model = sequential()?;
+        let neg = OutletId::new(model.node_by_name("neg")?.id, 0);
+        let mul = model.output_outlets()?[0];
+        let sub = model.extract_submodel(&[neg], &[mul])?;
+        assert_eq!(sub.nodes().len(), 4);
+        assert_eq!(sub.node(sub.input_outlets()?[0].node).name, "neg");
+        let result = SimplePlan::new(&sub)?.run(tvec!(tensor1(&[-1f32, 3.0])))?;
+        assert_eq!(result[0], rctensor1(&[2f32, 6.0]));
+        Ok(())
+    }
+
+    #[test]
+    fn unreachable_output() -> TractResult<()> {
+        let model = sequential()?;
+        let two = OutletId::new(model.node_by_name("two")?.id, 0);
+        let abs = OutletId::new(model.node_by_name("abs")?.id, 0);
+        assert!(model.extract_submodel(&[abs], &[two]).is_err());
+        let x = model.input_outlets()?[0];
+        assert!(model.extract_submodel(&[abs], &[x]).is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,191 @@
+use crate::internal::*;
+use ndarray::*;
+
+/// Boolean masked selection, as in ONNX.
+///
+/// With an axis, keep the slices of the input along this axis where the
+/// condition is true. Without one, the input is flattened first and the
+/// individual elements are selected. The condition may be shorter than the
+/// selected dimension, missing entries count as false.
+///
+/// The output length along the selected axis depends on the condition value:
+/// unless it is a constant, it is the `count` symbol, distinct for each node.
+#[derive(Debug, Clone, new)]
+pub struct Compress {
+    pub axis: Option<i64>,
+    #[new(value = r#"Symbol::fresh("C")"#)]
+    pub count: Symbol,
+}
+
+impl Compress {
+    fn resolve_axis(&self, rank: usize) -> TractResult<Option<usize>> {
+        match self.axis {
+            None => Ok(None),
+            Some(axis) if axis < -(rank as i64) || axis >= rank as i64 => {
+                bail!("Compress axis {} out of range for rank {}", axis, rank)
+            }
+            Some(axis) if axis < 0 => Ok(Some((axis + rank as i64) as usize)),
+            Some(axis) => Ok(Some(axis as usize)),
+        }
+    }
+
+    fn eval_t<T: Datum>(&self, input: &Tensor, conds: &[bool]) -> TractResult<Tensor> {
+        let input = input.to_array_view::<T>()?;
+        let axis = self.resolve_axis(input.ndim())?;
+        let len = axis.map(|ax| input.shape()[ax]).unwrap_or(input.len());
+        if conds.len() > len {
+            bail!("Compress condition has {} entries for a dimension of {}", conds.len(), len)
+        }
+        let kept: Vec<usize> =
+            conds.iter().enumerate().filter(|(_, c)| **c).map(|(ix, _)| ix).collect();
+        if let Some(ax) = axis {
+            let mut shape: TVec<usize> = input.shape().into();
+            shape[ax] = kept.len();
+            let mut output = ArrayD::<T>::default(&*shape);
+            for (ixo, &ixi) in kept.iter().enumerate() {
+                output.index_axis_mut(Axis(ax), ixo).assign(&input.index_axis(Axis(ax), ixi));
+            }
+            Ok(output.into_tensor())
+        } else {
+            let data: Vec<T> = input
+                .iter()
+                .enumerate()
+                .filter(|(ix, _)| conds.get(*ix) == Some(&true))
+                .map(|(_, x)| x.clone())
+                .collect();
+            Ok(Array::from(data).into_tensor())
+        }
+    }
+}
+
+impl Op for Compress {
+    fn name(&self) -> Cow<str> {
+        "Compress".into()
+    }
+
+    fn info(&self) -> TractResult<Vec<String>> {
+        Ok(vec![format!("axis: {:?}", self.axis)])
+    }
+
+    op_as_typed_op!();
+    not_a_pulsed_op!();
+}
+
+impl StatelessOp for Compress {
+    fn eval(&self, mut inputs: TVec<Arc<Tensor>>) -> TractResult<TVec<Arc<Tensor>>> {
+        let (input, cond) = args_2!(inputs);
+        let output =
+            dispatch_datum!(Self::eval_t(input.datum_type())(self, &input, cond.as_slice()?))?;
+        Ok(tvec!(output.into_arc_tensor()))
+    }
+}
+
+impl InferenceRulesOp for Compress {
+    fn rules<'r, 'p: 'r, 's: 'r>(
+        &'s self,
+        s: &mut Solver<'r>,
+        inputs: &'p [TensorProxy],
+        outputs: &'p [TensorProxy],
+    ) -> InferenceResult {
+        check_input_arity(&inputs, 2)?;
+        check_output_arity(&outputs, 1)?;
+        s.equals(&inputs[0].datum_type, &outputs[0].datum_type)?;
+        s.equals(&inputs[1].datum_type, bool::datum_type())?;
+        s.equals(&inputs[1].rank, 1)?;
+        if self.axis.is_some() {
+            s.equals(&inputs[0].rank, &outputs[0].rank)?;
+            s.given(&inputs[0].rank, move |s, rank| {
+                let rank = rank as usize;
+                let op_axis = self.resolve_axis(rank)?.unwrap();
+                for axis in 0..rank {
+                    if axis != op_axis {
+                        s.equals(&inputs[0].shape[axis], &outputs[0].shape[axis])?;
+                    }
+                }
+                s.given(&inputs[1].value, move |s, cond| {
+                    let count = cond.as_slice::<bool>()?.iter().filter(|c| **c).count();
+                    s.equals(&outputs[0].shape[op_axis], count.to_dim())
+                })
+            })?;
+        } else {
+            s.equals(&outputs[0].rank, 1)?;
+            s.given(&inputs[1].value, move |s, cond| {
+                let count = cond.as_slice::<bool>()?.iter().filter(|c| **c).count();
+                s.equals(&outputs[0].shape[0], count.to_dim())
+            })?;
+        }
+        Ok(())
+    }
+
+    inference_op_as_op!();
+    to_typed!();
+}
+
+impl TypedOp for Compress {
+    fn output_facts(&self, inputs: &[&TypedFact]) -> TractResult<TVec<TypedFact>> {
+        if inputs[1].datum_type != bool::datum_type() || inputs[1].rank() != 1 {
+            bail!("Compress condition must be a 1D boolean tensor, got {:?}", inputs[1])
+        }
+        let count = if let Some(cond) = &inputs[1].konst {
+            cond.as_slice::<bool>()?.iter().filter(|c| **c).count().to_dim()
+        } else {
+            TDim::sym(self.count.clone())
+        };
+        let shape: TVec<TDim> = if let Some(axis) = self.resolve_axis(inputs[0].rank())? {
+            let mut shape: TVec<TDim> = inputs[0].shape.iter().collect();
+            shape[axis] = count;
+            shape
+        } else {
+            tvec!(count)
+        };
+        Ok(tvec!(TypedFact::dt_shape(inputs[0].datum_type, &*shape)?))
+    }
+
+    typed_op_as_op!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cond(c: &[bool]) -> Arc<Tensor> {
+        rctensor1(c)
+    }
+
+    #[test]
+    fn axial() -> TractResult<()> {
+        let input = tensor2(&[[1i32, 2], [3, 4], [5, 6]]);
+        let rows =
+            Compress::new(Some(0)).eval(tvec!(input.clone().into(), cond(&[false, true, true])))?;
+        assert_eq!(*rows[0], tensor2(&[[3i32, 4], [5, 6]]));
+        let cols = Compress::new(Some(-1)).eval(tvec!(input.into(), cond(&[true])))?;
+        assert_eq!(*cols[0], tensor2(&[[1i32], [3], [5]]));
+        Ok(())
+    }
+
+    #[test]
+    fn flattened() -> TractResult<()> {
+        let input = tensor2(&[[1f32, 2.], [3., 4.], [5., 6.]]);
+        let output = Compress::new(None)
+            .eval(tvec!(input.into(), cond(&[false, true, false, true, true])))?;
+        assert_eq!(*output[0], tensor1(&[2f32, 4., 5.]));
+        Ok(())
+    }
+
+    #[test]
+    fn symbolic_output() -> TractResult<()> {
+        let mut model = TypedModel::default();
+        let input =
+            model.add_source("input", TypedFact::dt_shape(f32::datum_type(), [3, 2].as_ref())?)?;
+        let cond =
+            model.add_source("cond", TypedFact::dt_shape(bool::datum_type(), [3].as_ref())?)?;
+        let op = Compress::new(Some(0));
+        let count = TDim::sym(op.count.clone());
+        let output = model.wire_node("compress", op, &[input, cond])?[0];
+        assert_eq!(model.outlet_fact(output)?.shape.dim(0), count);
+        assert_eq!(model.outlet_fact(output)?.shape.dim(1), 2.to_dim());
+        let other = model.wire_node("other", Compress::new(Some(0)), &[input, cond])?[0];
+        assert_ne!(model.outlet_fact(other)?.shape.dim(0), count);
+        Ok(())
+    }
+}
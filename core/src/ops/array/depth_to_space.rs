@@ -0,0 +1,177 @@
+use crate::internal::*;
+
+use super::space_to_depth::{check_block_size, rearrange};
+
+/// Order of the depth axis split into blocks by DepthToSpace.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DepthToSpaceMode {
+    /// Depth, column, row: the block offset is the outer part of the depth
+    /// index, as in TensorFlow and the inverse of SpaceToDepth.
+    DCR,
+    /// Column, row, depth: the block offset is the inner part of the depth
+    /// index, as in PyTorch PixelShuffle.
+    CRD,
+}
+
+impl Default for DepthToSpaceMode {
+    fn default() -> DepthToSpaceMode {
+        DepthToSpaceMode::DCR
+    }
+}
+
+/// Move blocks of channel data to the spatial axes, as ONNX DepthToSpace.
+///
+/// A `[N, C, H, W]` input becomes `[N, C / block², H * block, W * block]`. C
+/// must be a multiple of `block_size²`.
+#[derive(Debug, Clone, new)]
+pub struct DepthToSpace {
+    pub block_size: usize,
+    pub mode: DepthToSpaceMode,
+}
+
+impl DepthToSpace {
+    fn output_shape<D: DimLike>(&self, shape: &[D]) -> TractResult<TVec<D>> {
+        check_block_size("DepthToSpace", self.block_size)?;
+        let b = self.block_size;
+        if shape.len() != 4 {
+            bail!("DepthToSpace expects a [N, C, H, W] input, got {:?}", shape)
+        }
+        if (shape[1].clone() % (b * b)).to_integer().map(|r| r != 0).unwrap_or(false) {
+            bail!("DepthToSpace channels {} must be a multiple of {}", shape[1], b * b)
+        }
+        Ok(tvec!(
+            shape[0].clone(),
+            shape[1].clone() / (b * b),
+            shape[2].clone() * b,
+            shape[3].clone() * b
+        ))
+    }
+
+    fn eval_t<T: Datum>(&self, input: Arc<Tensor>) -> TractResult<Arc<Tensor>> {
+        let b = self.block_size;
+        let shape = self.output_shape(input.shape())?;
+        let (n, c, h, w) = (shape[0], shape[1], input.shape()[2], input.shape()[3]);
+        match self.mode {
+            DepthToSpaceMode::DCR => {
+                rearrange::<T>(input, &[n, b, b, c, h, w], &[0, 3, 4, 1, 5, 2], &shape)
+            }
+            DepthToSpaceMode::CRD => {
+                rearrange::<T>(input, &[n, c, b, b, h, w], &[0, 1, 4, 2, 5, 3], &shape)
+            }
+        }
+    }
+}
+
+impl Op for DepthToSpace {
+    fn name(&self) -> Cow<str> {
+        "DepthToSpace".into()
+    }
+
+    fn info(&self) -> TractResult<Vec<String>> {
+        Ok(vec![format!("block_size: {} mode: {:?}", self.block_size, self.mode)])
+    }
+
+    fn validate_attributes(&self) -> TractResult<()> {
+        check_block_size("DepthToSpace", self.block_size)
+    }
+
+    op_as_typed_op!();
+    not_a_pulsed_op!();
+}
+
+impl StatelessOp for DepthToSpace {
+    fn eval(&self, mut inputs: TVec<Arc<Tensor>>) -> TractResult<TVec<Arc<Tensor>>> {
+        let input = args_1!(inputs);
+        Ok(tvec!(dispatch_datum!(Self::eval_t(input.datum_type())(self, input))?))
+    }
+}
+
+impl InferenceRulesOp for DepthToSpace {
+    fn rules<'r, 'p: 'r, 's: 'r>(
+        &'s self,
+        s: &mut Solver<'r>,
+        inputs: &'p [TensorProxy],
+        outputs: &'p [TensorProxy],
+    ) -> InferenceResult {
+        check_input_arity(&inputs, 1)?;
+        check_output_arity(&outputs, 1)?;
+        s.equals(&outputs[0].datum_type, &inputs[0].datum_type)?;
+        s.equals(&inputs[0].rank, 4)?;
+        s.equals(&outputs[0].rank, 4)?;
+        s.given(&inputs[0].shape, move |s, shape| {
+            s.equals(&outputs[0].shape, ShapeFact::from(self.output_shape(&*shape)?))
+        })
+    }
+
+    inference_op_as_op!();
+    to_typed!();
+}
+
+impl TypedOp for DepthToSpace {
+    fn output_facts(&self, inputs: &[&TypedFact]) -> TractResult<TVec<TypedFact>> {
+        let shape = self.output_shape(&*inputs[0].shape.to_tvec())?;
+        Ok(tvec!(TypedFact::dt_shape(inputs[0].datum_type, &*shape)?))
+    }
+
+    typed_op_as_op!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ops::array::SpaceToDepth;
+    use ndarray::*;
+
+    fn input() -> Arc<Tensor> {
+        Array4::from_shape_fn((1, 4, 1, 2), |(_, c, _, w)| (c * 2 + w) as i32).into_arc_tensor()
+    }
+
+    fn depth_to_space(mode: DepthToSpaceMode) -> TractResult<Arc<Tensor>> {
+        Ok(DepthToSpace::new(2, mode).eval(tvec!(input()))?.remove(0))
+    }
+
+    #[test]
+    fn dcr() -> TractResult<()> {
+        let expected = arr2(&[[0, 2, 1, 3], [4, 6, 5, 7]]).into_shape((1, 1, 2, 4))?;
+        assert_eq!(depth_to_space(DepthToSpaceMode::DCR)?, expected.into_arc_tensor());
+        Ok(())
+    }
+
+    #[test]
+    fn crd() -> TractResult<()> {
+        let input = Array4::from_shape_fn((1, 8, 1, 1), |(_, c, _, _)| c as i32);
+        let dcr = DepthToSpace::new(2, DepthToSpaceMode::DCR);
+        let crd = DepthToSpace::new(2, DepthToSpaceMode::CRD);
+        let dcr = dcr.eval(tvec!(input.clone().into_arc_tensor()))?.remove(0);
+        let crd = crd.eval(tvec!(input.into_arc_tensor()))?.remove(0);
+        let expected = arr3(&[[[0, 2], [4, 6]], [[1, 3], [5, 7]]]).into_shape((1, 2, 2, 2))?;
+        assert_eq!(dcr, expected.into_arc_tensor());
+        let expected = arr3(&[[[0, 1], [2, 3]], [[4, 5], [6, 7]]]).into_shape((1, 2, 2, 2))?;
+        assert_eq!(crd, expected.into_arc_tensor());
+        Ok(())
+    }
+
+    #[test]
+    fn dcr_inverts_space_to_depth() -> TractResult<()> {
+        let space = Array4::from_shape_fn((1, 2, 4, 6), |(_, c, h, w)| (c * 24 + h * 6 + w) as i32);
+        let space = space.into_arc_tensor();
+        let depth = SpaceToDepth::new(2).eval(tvec!(space.clone()))?;
+        let found = DepthToSpace::new(2, DepthToSpaceMode::DCR).eval(depth)?;
+        assert_eq!(found[0], space);
+        Ok(())
+    }
+
+    #[test]
+    fn output_facts() -> TractResult<()> {
+        let op = DepthToSpace::new(2, DepthToSpaceMode::CRD);
+        let fact = TypedFact::dt_shape(
+            f32::datum_type(),
+            [TDim::s(), 8.into(), 2.into(), 3.into()].as_ref(),
+        )?;
+        let output = op.output_facts(&[&fact])?.remove(0);
+        assert_eq!(output.shape.to_tvec(), tvec!(TDim::s(), 2.into(), 4.into(), 6.into()));
+        let fact = TypedFact::dt_shape(f32::datum_type(), [1, 6, 2, 3].as_ref())?;
+        assert!(op.output_facts(&[&fact]).is_err());
+        Ok(())
+    }
+}
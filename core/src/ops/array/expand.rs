@@ -0,0 +1,175 @@
+use crate::internal::*;
+
+use super::TypedMultiBroadcastTo;
+
+/// Broadcast the input to a shape, following NumPy rules, as ONNX Expand.
+///
+/// The second input is the target shape, as a 1D integer tensor. Either
+/// side can have dims of 1, and the shape can be shorter than the input
+/// rank: the output shape is the broadcast of the input shape and of the
+/// target shape.
+///
+/// Once the shape is known, this declutters to a `TypedMultiBroadcastTo`,
+/// and is folded with its input by constant propagation.
+#[derive(Debug, Clone, new, Default)]
+pub struct Expand;
+
+impl Expand {
+    fn output_shape<D: DimLike>(input: &[D], shape: &[D]) -> TractResult<TVec<D>> {
+        crate::broadcast::multi_broadcast(&[input, shape])
+            .ok_or_else(|| format!("Expand can not broadcast {:?} to {:?}", input, shape).into())
+    }
+
+    fn shape_input(shape: &Tensor) -> TractResult<TVec<TDim>> {
+        if shape.rank() != 1 {
+            bail!("Expand shape must be a 1D tensor, got {:?}", shape)
+        }
+        let shape = shape.cast_to::<TDim>()?;
+        let shape = shape.as_slice::<TDim>()?;
+        if let Some(d) = shape.iter().find(|d| d.to_integer().map(|d| d < 0).unwrap_or(false)) {
+            bail!("Expand shape must not be negative, got {:?}", d)
+        }
+        Ok(shape.into())
+    }
+
+    fn eval_t<T: Datum>(input: &Tensor, shape: &[usize]) -> TractResult<Arc<Tensor>> {
+        let input = input.to_array_view::<T>()?;
+        let output = input.broadcast(shape).ok_or("incompatible shapes")?;
+        Ok(output.to_owned().into_arc_tensor())
+    }
+}
+
+impl Op for Expand {
+    fn name(&self) -> Cow<str> {
+        "Expand".into()
+    }
+
+    op_as_typed_op!();
+    not_a_pulsed_op!();
+}
+
+impl StatelessOp for Expand {
+    fn eval(&self, mut inputs: TVec<Arc<Tensor>>) -> TractResult<TVec<Arc<Tensor>>> {
+        let (input, shape) = args_2!(inputs);
+        let shape: TVec<usize> = Self::shape_input(&shape)?
+            .iter()
+            .map(|d| Ok(d.to_integer()? as usize))
+            .collect::<TractResult<_>>()?;
+        let shape = Self::output_shape(input.shape(), &*shape)?;
+        Ok(tvec!(dispatch_datum!(Self::eval_t(input.datum_type())(&*input, &*shape))?))
+    }
+}
+
+impl InferenceRulesOp for Expand {
+    fn rules<'r, 'p: 'r, 's: 'r>(
+        &'s self,
+        s: &mut Solver<'r>,
+        inputs: &'p [TensorProxy],
+        outputs: &'p [TensorProxy],
+    ) -> InferenceResult {
+        check_input_arity(&inputs, 2)?;
+        check_output_arity(&outputs, 1)?;
+        s.equals(&outputs[0].datum_type, &inputs[0].datum_type)?;
+        s.equals(&inputs[1].rank, 1)?;
+        s.given_2(&inputs[0].rank, &inputs[1].shape[0], move |s, rank, len| {
+            if let Ok(len) = len.to_integer() {
+                s.equals(&outputs[0].rank, rank.max(len))?;
+            }
+            Ok(())
+        })?;
+        s.given_2(&inputs[0].shape, &inputs[1].value, move |s, input, shape| {
+            let shape = Self::shape_input(&shape)?;
+            s.equals(&outputs[0].shape, ShapeFact::from(Self::output_shape(&*input, &*shape)?))
+        })
+    }
+
+    inference_op_as_op!();
+    to_typed!();
+}
+
+impl TypedOp for Expand {
+    fn output_facts(&self, inputs: &[&TypedFact]) -> TractResult<TVec<TypedFact>> {
+        let shape = inputs[1].konst.as_ref().ok_or("Expand shape must be a constant")?;
+        let shape = Self::shape_input(shape)?;
+        let input = inputs[0].shape.to_tvec();
+        Ok(tvec!(TypedFact::dt_shape(
+            inputs[0].datum_type,
+            &*Self::output_shape(&*input, &*shape)?
+        )?))
+    }
+
+    fn declutter(
+        &self,
+        model: &TypedModel,
+        node: &TypedNode,
+    ) -> TractResult<Option<TypedModelPatch>> {
+        let op = TypedMultiBroadcastTo::new(node.outputs[0].fact.shape.to_tvec());
+        Ok(Some(TypedModelPatch::single_unary_op(model, node, op)?))
+    }
+
+    typed_op_as_op!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ops::konst::Const;
+    use ndarray::arr2;
+
+    fn expand(input: Tensor, shape: &[i64]) -> TractResult<Arc<Tensor>> {
+        Ok(Expand.eval(tvec!(input.into_arc_tensor(), rctensor1(shape)))?.remove(0))
+    }
+
+    #[test]
+    fn scalar() -> TractResult<()> {
+        assert_eq!(expand(tensor0(3i32), &[2, 3])?, rctensor2(&[[3i32; 3]; 2]));
+        Ok(())
+    }
+
+    #[test]
+    fn vector_to_matrix() -> TractResult<()> {
+        let found = expand(tensor1(&[1f32, 2.0, 3.0]), &[2, 1])?;
+        assert_eq!(found, rctensor2(&[[1f32, 2.0, 3.0], [1.0, 2.0, 3.0]]));
+        let found = expand(tensor2(&[[1f32], [2.0]]), &[3])?;
+        assert_eq!(found, rctensor2(&[[1f32, 1.0, 1.0], [2.0, 2.0, 2.0]]));
+        Ok(())
+    }
+
+    #[test]
+    fn shape_shorter_than_rank() -> TractResult<()> {
+        let input = ndarray::Array3::from_shape_fn((2, 1, 3), |(i, _, k)| (i * 3 + k) as f32);
+        let found = expand(input.into_tensor(), &[4, 1])?;
+        assert_eq!(found.shape(), &[2, 4, 3]);
+        let found = found.to_array_view::<f32>()?;
+        assert_eq!(found.slice(ndarray::s![1, 2, ..]), ndarray::arr1(&[3f32, 4.0, 5.0]));
+        Ok(())
+    }
+
+    #[test]
+    fn incompatible_shapes() {
+        assert!(expand(tensor1(&[1f32, 2.0, 3.0]), &[2]).is_err());
+        assert!(expand(tensor1(&[1f32]), &[-1]).is_err());
+    }
+
+    #[test]
+    fn inferred_and_folded() -> TractResult<()> {
+        let mut model = InferenceModel::default();
+        let x = model
+            .add_source("x", InferenceFact::dt_shape(f32::datum_type(), shapefact!(2, 2, 3)))?;
+        let input = model.add_const("input", rctensor2(&[[1f32], [2.0]]))?;
+        let shape = model.add_const("shape", rctensor1(&[2i64, 1, 3]))?;
+        let expand = model.wire_node("expand", Expand, &[input, shape])?[0];
+        let sum = model.wire_node("sum", crate::ops::math::add::bin(), &[x, expand])?;
+        model.set_output_outlets(&sum)?;
+        model.analyse(false)?;
+        assert_eq!(model.outlet_fact(expand)?.shape, shapefact!(2, 2, 3));
+        let mut model = model.into_typed()?;
+        model.propagate_constants_with_strategy(ConstPropagationStrategy::AlwaysCopy)?;
+        let model = crate::model::compact::compact(&model)?;
+        assert!(model.nodes().iter().all(|n| !n.op_is::<Expand>()));
+        let konst = model.nodes().iter().find(|n| n.op_is::<Const>()).unwrap();
+        let expected = arr2(&[[1f32; 3], [2.0; 3]]).broadcast((2, 2, 3)).unwrap().to_owned();
+        assert_eq!(konst.outputs[0].fact.konst, Some(expected.into_arc_tensor()));
+        Ok(())
+    }
+}
@@ -1,10 +1,15 @@
 use crate::internal::*;
 
-// TODO: canonicalize as Reshape
+use super::FiniteReshape;
 
+/// Reshape to 2D, collapsing the axes before `axis` and from `axis` on.
+///
+/// As in ONNX, `axis` can be negative, counting from the end, and ranges
+/// from `-rank` to `rank` included. Flatten declutters to a FiniteReshape
+/// when the input shape is known.
 #[derive(Debug, Clone, new, Default)]
 pub struct Flatten {
-    axis: usize,
+    axis: i64,
 }
 
 impl Flatten {
@@ -17,10 +22,19 @@ impl Flatten {
         Ok(tvec![input.into_tensor().into_array::<T>()?.into_shape(shape)?.into_arc_tensor()])
     }
 
-    fn compute_shape<D: DimLike>(&self, shape: &[D]) -> [D; 2] {
-        let shape_0 = shape[..self.axis].iter().fold(D::one(), |acc, v| acc * v);
-        let shape_1 = shape[self.axis..].iter().fold(D::one(), |acc, v| acc * v);
-        [shape_0, shape_1]
+    fn resolve_axis(&self, rank: usize) -> TractResult<usize> {
+        let axis = if self.axis < 0 { self.axis + rank as i64 } else { self.axis };
+        if axis < 0 || axis > rank as i64 {
+            bail!("Flatten axis {} is out of range for rank {}", self.axis, rank)
+        }
+        Ok(axis as usize)
+    }
+
+    fn compute_shape<D: DimLike>(&self, shape: &[D]) -> TractResult<[D; 2]> {
+        let axis = self.resolve_axis(shape.len())?;
+        let shape_0 = shape[..axis].iter().fold(D::one(), |acc, v| acc * v);
+        let shape_1 = shape[axis..].iter().fold(D::one(), |acc, v| acc * v);
+        Ok([shape_0, shape_1])
     }
 }
 
@@ -29,6 +43,10 @@ impl Op for Flatten {
         "Flatten".into()
     }
 
+    fn info(&self) -> TractResult<Vec<String>> {
+        Ok(vec![format!("axis: {}", self.axis)])
+    }
+
     op_as_typed_op!();
     not_a_pulsed_op!();
 }
@@ -36,7 +54,7 @@ impl Op for Flatten {
 impl StatelessOp for Flatten {
     fn eval(&self, mut inputs: TVec<Arc<Tensor>>) -> TractResult<TVec<Arc<Tensor>>> {
         let input = args_1!(inputs);
-        let [shape_0, shape_1] = self.compute_shape(input.shape());
+        let [shape_0, shape_1] = self.compute_shape(input.shape())?;
         dispatch_datum!(Self::eval_t(input.datum_type())(self, input, (shape_0, shape_1)))
     }
 }
@@ -48,9 +66,12 @@ impl InferenceRulesOp for Flatten {
         inputs: &'p [TensorProxy],
         outputs: &'p [TensorProxy],
     ) -> InferenceResult {
+        check_input_arity(&inputs, 1)?;
+        check_output_arity(&outputs, 1)?;
         s.equals(&outputs[0].datum_type, &inputs[0].datum_type)?;
+        s.equals(&outputs[0].rank, 2)?;
         s.given(&inputs[0].shape, move |s, shape| {
-            let [shape_0, shape_1] = self.compute_shape(&*shape);
+            let [shape_0, shape_1] = self.compute_shape(&*shape)?;
             s.equals(&outputs[0].shape, ShapeFact::from(vec![shape_0, shape_1]))
         })
     }
@@ -65,7 +86,76 @@ impl TypedOp for Flatten {
     fn output_facts(&self, inputs: &[&TypedFact]) -> TractResult<TVec<TypedFact>> {
         Ok(tvec!(TypedFact::dt_shape(
             inputs[0].datum_type,
-            self.compute_shape(&*inputs[0].shape.to_tvec()).as_ref(),
+            self.compute_shape(&*inputs[0].shape.to_tvec())?.as_ref(),
         )?))
     }
+
+    fn declutter(
+        &self,
+        model: &TypedModel,
+        node: &TypedNode,
+    ) -> TractResult<Option<TypedModelPatch>> {
+        if let Some(shape) = model.outlet_fact(node.inputs[0])?.shape.as_finite() {
+            let shape = self.compute_shape(shape)?;
+            let op = FiniteReshape::new(shape.iter().cloned().collect());
+            return Ok(Some(TypedModelPatch::single_unary_op(model, node, op)?));
+        }
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn shape(axis: i64) -> TractResult<[usize; 2]> {
+        Flatten::new(axis).compute_shape(&[2, 3, 4])
+    }
+
+    #[test]
+    fn axes() -> TractResult<()> {
+        assert_eq!(shape(0)?, [1, 24]);
+        assert_eq!(shape(1)?, [2, 12]);
+        assert_eq!(shape(-1)?, [6, 4]);
+        assert_eq!(shape(3)?, [24, 1]);
+        assert_eq!(shape(-3)?, [1, 24]);
+        assert!(shape(4).is_err());
+        assert!(shape(-4).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn eval() -> TractResult<()> {
+        let input = ndarray::Array3::from_shape_fn((2, 1, 3), |(i, _, k)| (i * 3 + k) as f32);
+        let output = Flatten::new(-1).eval(tvec!(input.into_arc_tensor()))?;
+        assert_eq!(output[0], rctensor2(&[[0f32, 1.0, 2.0], [3.0, 4.0, 5.0]]));
+        Ok(())
+    }
+
+    #[test]
+    fn symbolic_shape() -> TractResult<()> {
+        let mut model = InferenceModel::default();
+        let fact = InferenceFact::dt_shape(f32::datum_type(), shapefact!(S, 3, 4));
+        let x = model.add_source("x", fact)?;
+        let y = model.wire_node("flatten", Flatten::new(-2), &[x])?;
+        model.set_output_outlets(&y)?;
+        model.analyse(false)?;
+        assert_eq!(model.outlet_fact(y[0])?.shape, ShapeFact::from(vec![TDim::s(), 12.to_dim()]));
+        let model = model.into_typed()?.declutter()?;
+        assert!(model.node(y[0].node).op_is::<Flatten>());
+        Ok(())
+    }
+
+    #[test]
+    fn declutter_to_reshape() -> TractResult<()> {
+        let mut model = TypedModel::default();
+        let x =
+            model.add_source("x", TypedFact::dt_shape(f32::datum_type(), [2, 3, 4].as_ref())?)?;
+        let y = model.wire_node("flatten", Flatten::new(1), &[x])?;
+        model.set_output_outlets(&y)?;
+        let model = model.declutter()?;
+        let reshape = model.node(model.output_outlets()?[0].node).op_as::<FiniteReshape>().unwrap();
+        assert_eq!(&*reshape.shape, &[2, 12]);
+        Ok(())
+    }
 }
@@ -0,0 +1,224 @@
+use crate::internal::*;
+use ndarray::*;
+
+/// Gather slices of data at coordinates given by the last axis of indices.
+///
+/// The first `batch_dims` axes of data and indices are batch axes, and the
+/// coordinates index each batch item separately.
+#[derive(Debug, Clone, new, Default)]
+pub struct GatherNd {
+    pub batch_dims: usize,
+}
+
+impl GatherNd {
+    pub fn compute_shape<D: DimLike>(
+        &self,
+        data_shape: &[D],
+        indices_shape: &[D],
+    ) -> TractResult<TVec<D>> {
+        if indices_shape.len() <= self.batch_dims {
+            bail!("GatherND indices must have more than {} axes", self.batch_dims)
+        }
+        let mut shape: TVec<D> = indices_shape.into();
+        let k = shape.pop().unwrap().to_integer()? as usize;
+        if self.batch_dims + k > data_shape.len() {
+            bail!(
+                "GatherND indices last dim is {}, but data rank is {} with {} batch dims",
+                k,
+                data_shape.len(),
+                self.batch_dims
+            )
+        }
+        shape.extend(data_shape[self.batch_dims + k..].iter().cloned());
+        Ok(shape)
+    }
+
+    fn coords<'a, T: Datum>(
+        mut view: ArrayViewD<'a, T>,
+        coords: ArrayView1<i64>,
+    ) -> TractResult<ArrayViewD<'a, T>> {
+        for &c in coords {
+            let dim = view.shape()[0] as i64;
+            let ix = if c < 0 { c + dim } else { c };
+            if ix < 0 || ix >= dim {
+                bail!("GatherND index {} out of bounds for axis of size {}", c, dim)
+            }
+            view.index_axis_inplace(Axis(0), ix as usize);
+        }
+        Ok(view)
+    }
+
+    fn eval_t<T: Datum>(
+        &self,
+        data: Arc<Tensor>,
+        indices: &ArrayViewD<i64>,
+    ) -> TractResult<Arc<Tensor>> {
+        let shape = self.compute_shape(data.shape(), indices.shape())?;
+        if self.batch_dims == 0 && indices.ndim() == 1 {
+            // degenerate case: a single slice, data is shared as is if there is
+            // no coordinate at all
+            if indices.len() == 0 {
+                return Ok(data);
+            }
+            let view = data.to_array_view::<T>()?;
+            return Ok(Self::coords(view, indices.view().into_dimensionality()?)?
+                .to_owned()
+                .into_arc_tensor());
+        }
+        let data = data.to_array_view::<T>()?;
+        let batch: usize = data.shape()[..self.batch_dims].iter().product();
+        let k = indices.shape()[indices.ndim() - 1];
+        let n = indices.len() / batch / k.max(1);
+        let data_batched: TVec<usize> =
+            std::iter::once(batch).chain(data.shape()[self.batch_dims..].iter().cloned()).collect();
+        let data = data.into_shape(&*data_batched)?;
+        let indices = indices.view().into_shape((batch, n, k))?;
+        let slice_shape = &data.shape()[1 + k..];
+        let output_batched: TVec<usize> =
+            [batch, n].iter().chain(slice_shape.iter()).cloned().collect();
+        let mut output = unsafe { T::uninitialized_array(&*output_batched) };
+        for b in 0..batch {
+            for i in 0..n {
+                let src = Self::coords(data.index_axis(Axis(0), b), indices.slice(s![b, i, ..]))?;
+                let mut dst = output.index_axis_mut(Axis(0), b);
+                dst.index_axis_inplace(Axis(0), i);
+                dst.assign(&src);
+            }
+        }
+        Ok(output.into_shape(&*shape)?.into_arc_tensor())
+    }
+}
+
+impl Op for GatherNd {
+    fn name(&self) -> Cow<str> {
+        "GatherNd".into()
+    }
+
+    fn info(&self) -> TractResult<Vec<String>> {
+        Ok(vec![format!("batch_dims: {}", self.batch_dims)])
+    }
+
+    op_as_typed_op!();
+    not_a_pulsed_op!();
+}
+
+impl StatelessOp for GatherNd {
+    fn eval(&self, mut inputs: TVec<Arc<Tensor>>) -> TractResult<TVec<Arc<Tensor>>> {
+        let (data, indices) = args_2!(inputs);
+        let indices = indices.cast_to::<i64>()?;
+        let indices = indices.to_array_view::<i64>()?;
+        Ok(tvec!(dispatch_datum!(Self::eval_t(data.datum_type())(self, data, &indices))?))
+    }
+}
+
+impl InferenceRulesOp for GatherNd {
+    fn rules<'r, 'p: 'r, 's: 'r>(
+        &'s self,
+        s: &mut Solver<'r>,
+        inputs: &'p [TensorProxy],
+        outputs: &'p [TensorProxy],
+    ) -> InferenceResult {
+        check_input_arity(&inputs, 2)?;
+        check_output_arity(&outputs, 1)?;
+        s.equals(&outputs[0].datum_type, &inputs[0].datum_type)?;
+        s.given_2(&inputs[0].shape, &inputs[1].shape, move |s, data_shape, indices_shape| {
+            let shape = self.compute_shape(&*data_shape, &*indices_shape)?;
+            s.equals(&outputs[0].shape, ShapeFact::from(shape))
+        })
+    }
+
+    inference_op_as_op!();
+    to_typed!();
+}
+
+impl TypedOp for GatherNd {
+    fn output_facts(&self, inputs: &[&TypedFact]) -> TractResult<TVec<TypedFact>> {
+        let shape = self.compute_shape(&inputs[0].shape.to_tvec(), &inputs[1].shape.to_tvec())?;
+        Ok(tvec!(TypedFact::dt_shape(inputs[0].datum_type, &*shape)?))
+    }
+
+    typed_op_as_op!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn gather(batch_dims: usize, data: Tensor, indices: Tensor) -> TractResult<Arc<Tensor>> {
+        Ok(GatherNd::new(batch_dims).eval(tvec!(data.into(), indices.into()))?.remove(0))
+    }
+
+    // Examples from the ONNX operator documentation.
+    #[test]
+    fn onnx_example_1() -> TractResult<()> {
+        let output = gather(0, tensor2(&[[0i32, 1], [2, 3]]), tensor2(&[[0i64, 0], [1, 1]]))?;
+        assert_eq!(*output, tensor1(&[0i32, 3]));
+        Ok(())
+    }
+
+    #[test]
+    fn onnx_example_2() -> TractResult<()> {
+        let output = gather(0, tensor2(&[[0i32, 1], [2, 3]]), tensor2(&[[1i64], [0]]))?;
+        assert_eq!(*output, tensor2(&[[2i32, 3], [0, 1]]));
+        Ok(())
+    }
+
+    #[test]
+    fn onnx_example_3() -> TractResult<()> {
+        let data = tensor3(&[[[0i32, 1], [2, 3]], [[4, 5], [6, 7]]]);
+        let output = gather(0, data, tensor2(&[[0i64, 1], [1, 0]]))?;
+        assert_eq!(*output, tensor2(&[[2i32, 3], [4, 5]]));
+        Ok(())
+    }
+
+    #[test]
+    fn onnx_example_4() -> TractResult<()> {
+        let data = tensor3(&[[[0i32, 1], [2, 3]], [[4, 5], [6, 7]]]);
+        let output = gather(0, data, tensor3(&[[[0i64, 1]], [[1, 0]]]))?;
+        assert_eq!(*output, tensor3(&[[[2i32, 3]], [[4, 5]]]));
+        Ok(())
+    }
+
+    #[test]
+    fn onnx_example_5_batch_dims() -> TractResult<()> {
+        let data = tensor3(&[[[0i32, 1], [2, 3]], [[4, 5], [6, 7]]]);
+        let output = gather(1, data, tensor2(&[[1i64], [0]]))?;
+        assert_eq!(*output, tensor2(&[[2i32, 3], [4, 5]]));
+        Ok(())
+    }
+
+    #[test]
+    fn token_selection_batch_dims() -> TractResult<()> {
+        // [batch=2, seq=3, hidden=2], picking one token per sequence
+        let data =
+            tensor3(&[[[0f32, 1.], [2., 3.], [4., 5.]], [[10., 11.], [12., 13.], [14., 15.]]]);
+        let output = gather(1, data, tensor3(&[[[2i64], [0]], [[1], [-1]]]))?;
+        assert_eq!(*output, tensor3(&[[[4f32, 5.], [0., 1.]], [[12., 13.], [14., 15.]]]));
+        Ok(())
+    }
+
+    #[test]
+    fn single_slice() -> TractResult<()> {
+        let data = tensor3(&[[[0i32, 1], [2, 3]], [[4, 5], [6, 7]]]);
+        let output = gather(0, data, tensor1(&[1i64, 0]))?;
+        assert_eq!(*output, tensor1(&[4i32, 5]));
+        Ok(())
+    }
+
+    #[test]
+    fn invalid_indices() {
+        let data = tensor2(&[[0i32, 1], [2, 3]]);
+        assert!(gather(1, data.clone(), tensor2(&[[0i64, 1], [1, 0]])).is_err());
+        assert!(gather(0, data, tensor2(&[[2i64, 0]])).is_err());
+    }
+
+    #[test]
+    fn too_many_batch_dims() {
+        let op = GatherNd::new(3);
+        assert!(op.compute_shape(&[2usize, 2], &[2usize, 2, 2, 0]).is_err());
+        assert!(op.compute_shape(&[2usize, 2, 2], &[2usize, 2, 2, 1]).is_err());
+        assert_eq!(op.compute_shape(&[2usize, 2, 2], &[2usize, 2, 2, 0]).unwrap(), tvec!(2, 2, 2));
+        let data = tensor2(&[[0i32, 1], [2, 3]]);
+        assert!(gather(2, data, tensor3(&[[[0i64], [1]], [[1], [0]]])).is_err());
+    }
+}
@@ -11,7 +11,7 @@
 /// ### ONNX only
 ///
 /// * Unsqueeze, unary, with required list of axes (referring to output)
-/// * (Expand is a broadcasting operators, it does not beling here)
+/// * Expand, binary (input, shape as a tensor), broadcasts input to shape
 ///
 /// ### TF Only
 ///
@@ -43,41 +43,61 @@
 /// * Slice, unary, mandatory attrs are begin and end.
 mod add_dims;
 mod broadcast;
+mod compress;
 pub(crate) mod concat;
 mod constant_like;
 mod constant_of_shape;
 mod crop;
+mod depth_to_space;
+mod expand;
 mod flatten;
 mod gather;
+mod gather_nd;
+mod one_hot;
 mod pad;
 mod permute_axes;
 mod reshape;
+mod reverse_sequence;
 mod rm_dims;
+mod scatter_nd;
 mod shape;
 mod size;
 mod slice;
+mod space_to_depth;
 mod split;
 mod squeeze;
 mod strided_slice;
 mod tile;
+mod topk;
+mod trilu;
 
 pub use self::add_dims::AddDims;
 pub use self::broadcast::{MultiBroadcastTo, TypedMultiBroadcastTo};
-pub use self::concat::{Concat, NormConcat};
+pub use self::compress::Compress;
+pub use self::concat::{Concat, NormConcat, NormConcatSlice};
 pub use self::constant_like::ConstantLike;
 pub use self::constant_like::EyeLike;
 pub use self::constant_of_shape::ConstantOfShape;
 pub use self::crop::Crop;
+pub use self::depth_to_space::{DepthToSpace, DepthToSpaceMode};
+pub use self::expand::Expand;
 pub use self::flatten::Flatten;
 pub use self::gather::Gather;
+pub use self::gather_nd::GatherNd;
+pub use self::one_hot::{OneHot, TypedOneHot};
 pub use self::pad::{Pad, PadMode};
 pub use self::permute_axes::PermuteAxes;
 pub use self::reshape::{FiniteReshape, Reshape, TypedReshape};
+pub use self::reverse_sequence::ReverseSequence;
 pub use self::rm_dims::RmDims;
+pub use self::scatter_nd::{ScatterNd, ScatterReduction};
 pub use self::shape::Shape;
 pub use self::size::Size;
 pub use self::slice::Slice;
+pub use self::space_to_depth::SpaceToDepth;
 pub use self::split::Split;
 pub use self::squeeze::Squeeze;
 pub use self::strided_slice::StridedSlice;
 pub use self::tile::Tile;
+pub use self::topk::TopK;
+pub use self::trilu::Trilu;
@@ -0,0 +1,216 @@
+use crate::internal::*;
+use ndarray::*;
+
+/// One-hot encoding, as in ONNX.
+///
+/// Inputs are the indices (any integer type), the depth (a scalar) and the
+/// values, a two element tensor with the off value then the on value. The
+/// output has the type of the values, and a new axis of length depth inserted
+/// at `axis`. Negative indices count from the end of the depth, indices out of
+/// range produce a row of off values.
+#[derive(Debug, Clone, new)]
+pub struct OneHot {
+    pub axis: i64,
+}
+
+impl Default for OneHot {
+    fn default() -> OneHot {
+        OneHot { axis: -1 }
+    }
+}
+
+impl OneHot {
+    fn resolve_axis(&self, indices_rank: usize) -> TractResult<usize> {
+        let rank = indices_rank as i64 + 1;
+        if self.axis < -rank || self.axis >= rank {
+            bail!("OneHot axis {} out of range for indices of rank {}", self.axis, indices_rank)
+        }
+        Ok(if self.axis < 0 { self.axis + rank } else { self.axis } as usize)
+    }
+
+    fn to_typed_one_hot(
+        &self,
+        indices_rank: usize,
+        depth: &Tensor,
+        values: &Tensor,
+    ) -> TractResult<TypedOneHot> {
+        let axis = self.resolve_axis(indices_rank)?;
+        let depth = depth.cast_to::<i64>()?;
+        let depth = depth.as_slice::<i64>()?;
+        if depth.len() != 1 || depth[0] < 1 {
+            bail!("OneHot depth must be a single positive integer, got {:?}", depth)
+        }
+        if values.len() != 2 {
+            bail!("OneHot values must have two elements, got {:?}", values.shape())
+        }
+        Ok(TypedOneHot::new(axis, depth[0] as usize, values.clone().into_arc_tensor()))
+    }
+}
+
+impl Op for OneHot {
+    fn name(&self) -> Cow<str> {
+        "OneHot".into()
+    }
+
+    fn info(&self) -> TractResult<Vec<String>> {
+        Ok(vec![format!("axis: {}", self.axis)])
+    }
+
+    not_a_typed_op!();
+    not_a_pulsed_op!();
+}
+
+impl StatelessOp for OneHot {
+    fn eval(&self, mut inputs: TVec<Arc<Tensor>>) -> TractResult<TVec<Arc<Tensor>>> {
+        let (indices, depth, values) = args_3!(inputs);
+        self.to_typed_one_hot(indices.rank(), &depth, &values)?.eval(tvec!(indices))
+    }
+}
+
+impl InferenceRulesOp for OneHot {
+    fn rules<'r, 'p: 'r, 's: 'r>(
+        &'s self,
+        s: &mut Solver<'r>,
+        inputs: &'p [TensorProxy],
+        outputs: &'p [TensorProxy],
+    ) -> InferenceResult {
+        check_input_arity(&inputs, 3)?;
+        check_output_arity(&outputs, 1)?;
+        s.equals(&inputs[2].datum_type, &outputs[0].datum_type)?;
+        s.equals(&inputs[2].rank, 1)?;
+        s.equals(&inputs[2].shape[0], 2.to_dim())?;
+        s.equals(inputs[0].rank.bex() + 1, &outputs[0].rank)?;
+        s.given(&inputs[0].rank, move |s, rank| {
+            let rank = rank as usize;
+            let axis = self.resolve_axis(rank)?;
+            for ix in 0..axis {
+                s.equals(&inputs[0].shape[ix], &outputs[0].shape[ix])?;
+            }
+            for ix in axis..rank {
+                s.equals(&inputs[0].shape[ix], &outputs[0].shape[ix + 1])?;
+            }
+            s.given(&inputs[1].value, move |s, depth| {
+                let depth = depth.cast_to_scalar::<i64>()?;
+                s.equals(&outputs[0].shape[axis], depth.to_dim())
+            })
+        })
+    }
+
+    fn to_typed(
+        &self,
+        source: &InferenceModel,
+        node: &InferenceNode,
+        target: &mut TypedModel,
+        mapping: &HashMap<OutletId, OutletId>,
+    ) -> TractResult<TVec<OutletId>> {
+        let depth = source.outlet_fact(node.inputs[1])?.value.concretize();
+        let depth = depth.ok_or("OneHot translation requires a constant depth")?;
+        let values = source.outlet_fact(node.inputs[2])?.value.concretize();
+        let values = values.ok_or("OneHot translation requires constant values")?;
+        let indices = mapping[&node.inputs[0]];
+        let rank = target.outlet_fact(indices)?.rank();
+        let op = self.to_typed_one_hot(rank, &depth, &values)?;
+        target.wire_node(&*node.name, op, &[indices])
+    }
+
+    inference_op_as_op!();
+}
+
+/// OneHot with depth and values known, and axis resolved.
+#[derive(Debug, Clone, new)]
+pub struct TypedOneHot {
+    pub axis: usize,
+    pub depth: usize,
+    pub values: Arc<Tensor>,
+}
+
+impl TypedOneHot {
+    fn eval_t<T: Datum>(&self, indices: &Tensor) -> TractResult<Tensor> {
+        let values = self.values.as_slice::<T>()?;
+        let (off, on) = (&values[0], &values[1]);
+        let indices = indices.cast_to::<i64>()?;
+        let indices = indices.to_array_view::<i64>()?;
+        let mut shape: TVec<usize> = indices.shape().into();
+        shape.insert(self.axis, self.depth);
+        let depth = self.depth as i64;
+        let mut output = ArrayD::from_elem(&*shape, off.clone());
+        for (coords, &ix) in indices.indexed_iter() {
+            let ix = if ix < 0 { ix + depth } else { ix };
+            if ix < 0 || ix >= depth {
+                continue;
+            }
+            let mut coords: TVec<usize> = coords.slice().into();
+            coords.insert(self.axis, ix as usize);
+            output[&*coords] = on.clone();
+        }
+        Ok(output.into_tensor())
+    }
+}
+
+impl Op for TypedOneHot {
+    fn name(&self) -> Cow<str> {
+        "TypedOneHot".into()
+    }
+
+    fn info(&self) -> TractResult<Vec<String>> {
+        Ok(vec![format!("axis: {} depth: {} values: {:?}", self.axis, self.depth, self.values)])
+    }
+
+    op_as_typed_op!();
+    not_a_pulsed_op!();
+}
+
+impl StatelessOp for TypedOneHot {
+    fn eval(&self, inputs: TVec<Arc<Tensor>>) -> TractResult<TVec<Arc<Tensor>>> {
+        let output = dispatch_datum!(Self::eval_t(self.values.datum_type())(self, &inputs[0]))?;
+        Ok(tvec!(output.into_arc_tensor()))
+    }
+}
+
+impl TypedOp for TypedOneHot {
+    fn output_facts(&self, inputs: &[&TypedFact]) -> TractResult<TVec<TypedFact>> {
+        if self.axis > inputs[0].rank() {
+            bail!("OneHot axis {} out of range for indices {:?}", self.axis, inputs[0])
+        }
+        let mut shape: TVec<TDim> = inputs[0].shape.iter().collect();
+        shape.insert(self.axis, self.depth.to_dim());
+        Ok(tvec!(TypedFact::dt_shape(self.values.datum_type(), &*shape)?))
+    }
+
+    typed_op_as_op!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn one_hot(axis: i64, indices: Tensor, depth: i64, values: Tensor) -> TractResult<Tensor> {
+        let inputs = tvec!(indices.into(), rctensor0(depth), values.into());
+        Ok(OneHot::new(axis).eval(inputs)?.remove(0).into_tensor())
+    }
+
+    #[test]
+    fn vector_last_axis() -> TractResult<()> {
+        let output = one_hot(-1, tensor1(&[0i64, 2, -1, 5]), 3, tensor1(&[0f32, 1.]))?;
+        assert_eq!(output, tensor2(&[[1f32, 0., 0.], [0., 0., 1.], [0., 0., 1.], [0., 0., 0.]]));
+        Ok(())
+    }
+
+    #[test]
+    fn matrix_middle_axis() -> TractResult<()> {
+        let output = one_hot(1, tensor2(&[[0i32, 1], [1, 0]]), 3, tensor1(&[0f32, 1.]))?;
+        assert_eq!(output.shape(), &[2, 3, 2]);
+        assert_eq!(
+            output,
+            tensor3(&[[[1f32, 0.], [0., 1.], [0., 0.]], [[0f32, 1.], [1., 0.], [0., 0.]]])
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn int_values() -> TractResult<()> {
+        let output = one_hot(0, tensor1(&[1u8, 0]), 2, tensor1(&[-1i32, 7]))?;
+        assert_eq!(output, tensor2(&[[-1i32, 7], [7, -1]]));
+        Ok(())
+    }
+}
@@ -7,6 +7,7 @@ pub enum PadMode {
     Constant(Arc<Tensor>),
     Reflect,
     Edge,
+    Wrap,
 }
 
 impl Default for PadMode {
@@ -22,11 +23,52 @@ pub struct Pad {
 }
 
 impl Pad {
+    /// Index in the input of the value to copy at position `i`, relative to
+    /// the input start, for the non constant modes.
+    fn source_index(&self, i: isize, len: usize) -> usize {
+        let len = len as isize;
+        let ix = match self.mode {
+            PadMode::Edge => i.max(0).min(len - 1),
+            PadMode::Reflect if i < 0 => -i,
+            PadMode::Reflect => 2 * (len - 1) - i,
+            PadMode::Wrap => i.rem_euclid(len),
+            PadMode::Constant(_) => unreachable!(),
+        };
+        ix as usize
+    }
+
+    fn check_dims<D: DimLike>(&self, shape: &[D]) -> TractResult<()> {
+        if self.pads.len() != shape.len() {
+            bail!("Pad expects one pair of pads per axis, got {:?} for {:?}", self.pads, shape)
+        }
+        if let PadMode::Constant(_) = self.mode {
+            return Ok(());
+        }
+        for (ax, (&(bef, aft), dim)) in self.pads.iter().zip(shape.iter()).enumerate() {
+            if let Ok(dim) = dim.to_integer() {
+                let dim = dim as usize;
+                if dim == 0 && bef + aft > 0 {
+                    bail!("Can not pad empty axis {} in {:?} mode", ax, self.mode)
+                }
+                if self.mode == PadMode::Reflect && (bef >= dim || aft >= dim) {
+                    bail!(
+                        "Reflect padding of axis {} must be less than its size {}, got {:?}",
+                        ax,
+                        dim,
+                        (bef, aft)
+                    )
+                }
+            }
+        }
+        Ok(())
+    }
+
     fn eval_t<T>(&self, input: Arc<Tensor>) -> TractResult<Arc<Tensor>>
     where
         T: Copy + Datum,
     {
         let input = input.to_array_view::<T>()?;
+        self.check_dims(input.shape())?;
         let output_shape: Vec<usize> =
             input.shape().iter().zip(self.pads.iter()).map(|(&d, &(a, b))| d + a + b).collect();
         let element = match &self.mode {
@@ -45,38 +87,17 @@ impl Pad {
             .collect();
         let slice_info = SliceInfo::<_, IxDyn>::new(slice_spec).unwrap();
         output.slice_mut(slice_info.as_ref()).assign(&input);
-        if self.mode == PadMode::Reflect || self.mode == PadMode::Edge {
-            for (ax, &(bef, aft)) in self.pads.iter().enumerate() {
-                let axis = Axis(ax);
-                let dim = output.shape()[ax];
-                {
-                    let (mut pad, data) = output.view_mut().split_at(axis, bef);
-                    for i in 0..bef {
-                        let mut target = pad.slice_axis_mut(axis, Slice::from(i..i + 1));
-                        let source_slice = match self.mode {
-                            PadMode::Edge => 0,
-                            PadMode::Reflect => bef - i,
-                            _ => panic!(),
-                        };
-                        let source =
-                            data.slice_axis(axis, Slice::from(source_slice..source_slice + 1));
-                        target.assign(&source);
-                    }
-                }
-                {
-                    let (data, mut pad) = output.view_mut().split_at(axis, dim - aft);
-                    for i in 0..aft {
-                        let mut target = pad.slice_axis_mut(axis, Slice::from(i..i + 1));
-                        let source_slice = match self.mode {
-                            PadMode::Edge => dim - aft - 1,
-                            PadMode::Reflect => dim - aft - 2 - i,
-                            _ => panic!(),
-                        };
-                        let source =
-                            data.slice_axis(axis, Slice::from(source_slice..source_slice + 1));
-                        target.assign(&source);
-                    }
-                }
+        if let PadMode::Constant(_) = self.mode {
+            return Ok(output.into_arc_tensor());
+        }
+        for (ax, &(bef, aft)) in self.pads.iter().enumerate() {
+            let axis = Axis(ax);
+            let len = input.shape()[ax];
+            let targets = (0..bef).chain(bef + len..bef + len + aft);
+            for target in targets {
+                let source = bef + self.source_index(target as isize - bef as isize, len);
+                let source = output.index_axis(axis, source).to_owned();
+                output.index_axis_mut(axis, target).assign(&source);
             }
         }
         Ok(output.into_arc_tensor())
@@ -134,6 +155,7 @@ impl TypedOp for Pad {
     typed_op_as_op!();
 
     fn output_facts(&self, inputs: &[&TypedFact]) -> TractResult<TVec<TypedFact>> {
+        self.check_dims(&*inputs[0].shape.to_tvec())?;
         let mut fact = inputs[0].clone();
         for (ix, (b, e)) in self.pads.iter().enumerate() {
             fact.shape.set_dim(ix, fact.shape.dim(ix).clone() + *b + *e)?
@@ -179,7 +201,8 @@ impl TypedOp for Pad {
                 }
             },
             PadMode::Edge => bail!("Edge padding mode needs pulse strictly bigger than left padding (pulse={} padding={})", pulse, before),
-            PadMode::Reflect => bail!("Reflect padding mode pulsing is not supported")
+            PadMode::Reflect => bail!("Reflect padding mode pulsing is not supported"),
+            PadMode::Wrap => bail!("Wrap padding mode pulsing is not supported"),
         };
         if extra_delay > 0 {
             input = target.wire_node(
@@ -349,3 +372,54 @@ impl<T: Datum + Copy> PulsedOp for PulsePad<T> {
     pulsed_op_as_op!();
     pulsed_op_to_typed_op!();
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pad(mode: PadMode) -> TractResult<Arc<Tensor>> {
+        let input = tensor2(&[[1i32, 2, 3], [4, 5, 6]]);
+        let op = Pad::new(vec![(1, 0), (2, 1)], mode);
+        Ok(op.eval(tvec!(input.into_arc_tensor()))?.remove(0))
+    }
+
+    #[test]
+    fn constant() -> TractResult<()> {
+        let expected = tensor2(&[[9, 9, 9, 9, 9, 9], [9, 9, 1, 2, 3, 9], [9, 9, 4, 5, 6, 9]]);
+        assert_eq!(*pad(PadMode::Constant(rctensor0(9i32)))?, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn reflect() -> TractResult<()> {
+        let expected = tensor2(&[[6, 5, 4, 5, 6, 5], [3, 2, 1, 2, 3, 2], [6, 5, 4, 5, 6, 5]]);
+        assert_eq!(*pad(PadMode::Reflect)?, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn edge() -> TractResult<()> {
+        let expected = tensor2(&[[1, 1, 1, 2, 3, 3], [1, 1, 1, 2, 3, 3], [4, 4, 4, 5, 6, 6]]);
+        assert_eq!(*pad(PadMode::Edge)?, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn wrap() -> TractResult<()> {
+        let expected = tensor2(&[[5, 6, 4, 5, 6, 4], [2, 3, 1, 2, 3, 1], [5, 6, 4, 5, 6, 4]]);
+        assert_eq!(*pad(PadMode::Wrap)?, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn reflect_too_wide() -> TractResult<()> {
+        let op = Pad::new(vec![(0, 3)], PadMode::Reflect);
+        let fact = TypedFact::dt_shape(f32::datum_type(), [3usize].as_ref())?;
+        assert!(op.output_facts(&[&fact]).is_err());
+        assert!(op.eval(tvec!(rctensor1(&[1f32, 2.0, 3.0]))).is_err());
+        let op = Pad::new(vec![(0, 2)], PadMode::Reflect);
+        let expected = rctensor1(&[1f32, 2.0, 3.0, 2.0, 1.0]);
+        assert_eq!(op.eval(tvec!(rctensor1(&[1f32, 2.0, 3.0])))?[0], expected);
+        Ok(())
+    }
+}
@@ -17,14 +17,17 @@ impl Reshape {
             .map(|(&shape, input)| if shape > 0 { D::from(shape as usize) } else { input.clone() })
             .collect();
         if let Some(minus_one) = shape.iter().position(|d| *d == -1) {
-            let prod_input: usize =
-                input.iter().try_fold(1, |acc, dim| dim.to_integer().map(|a| a as usize * acc))?;
-            let prod_shape: usize = result
+            // dims copied from the input cancel out, so this works with
+            // symbolic dims as long as they are copied
+            let copied = |ix: usize| shape.get(ix) == Some(&0);
+            let prod_input: D = input
                 .iter()
                 .enumerate()
-                .filter(|(ix, _)| *ix != minus_one)
-                .try_fold(1, |acc, (_, dim)| dim.to_integer().map(|a| a as usize * acc))?;
-            result[minus_one] = D::from(prod_input / prod_shape);
+                .filter(|(ix, _)| !copied(*ix))
+                .map(|(_, dim)| dim.clone())
+                .product();
+            let prod_shape: usize = shape.iter().filter(|&&d| d > 0).map(|&d| d as usize).product();
+            result[minus_one] = prod_input / prod_shape;
         }
         Ok(result)
     }
@@ -112,8 +115,17 @@ impl Op for TypedReshape {
 impl StatelessOp for TypedReshape {
     fn eval(&self, mut inputs: TVec<Arc<Tensor>>) -> TractResult<TVec<Arc<Tensor>>> {
         let input = args_1!(inputs);
-        let shape: TVec<usize> =
-            self.shape.iter().map(|d| Ok(d.to_integer()? as usize)).collect::<TractResult<_>>()?;
+        let mut shape: TVec<usize> =
+            self.shape.iter().map(|d| d.to_integer().unwrap_or(0) as usize).collect();
+        let symbolic: TVec<usize> =
+            (0..shape.len()).filter(|&ix| self.shape[ix].to_integer().is_err()).collect();
+        if symbolic.len() == 1 {
+            let others: usize =
+                shape.iter().enumerate().filter(|p| p.0 != symbolic[0]).map(|p| *p.1).product();
+            shape[symbolic[0]] = input.len() / others.max(1);
+        } else if symbolic.len() > 1 {
+            bail!("Can not reshape to {:?}, more than one dim is symbolic", self.shape);
+        }
         let o = unsafe { input.into_tensor().into_shape(&*shape)?.into_arc_tensor() };
         Ok(tvec!(o))
     }
@@ -0,0 +1,158 @@
+use crate::internal::*;
+use ndarray::*;
+
+/// Reverse the beginning of each sequence of a batch, as in ONNX.
+///
+/// For each sample i along `batch_axis`, the first `sequence_lens[i]`
+/// elements along `time_axis` are reversed, the remainder (padding) is left
+/// unchanged. `sequence_lens` can be i32 or i64.
+#[derive(Debug, Clone, new)]
+pub struct ReverseSequence {
+    pub batch_axis: usize,
+    pub time_axis: usize,
+}
+
+impl Default for ReverseSequence {
+    fn default() -> ReverseSequence {
+        ReverseSequence { batch_axis: 1, time_axis: 0 }
+    }
+}
+
+impl ReverseSequence {
+    fn check_axes(&self, rank: usize) -> TractResult<()> {
+        if self.batch_axis == self.time_axis || self.batch_axis >= rank || self.time_axis >= rank {
+            bail!(
+                "ReverseSequence invalid axes (batch: {}, time: {}) for rank {}",
+                self.batch_axis,
+                self.time_axis,
+                rank
+            )
+        }
+        Ok(())
+    }
+
+    fn eval_t<T: Datum>(&self, input: &Tensor, lens: &[i64]) -> TractResult<Tensor> {
+        let input = input.to_array_view::<T>()?;
+        let mut output = input.to_owned();
+        let steps = input.shape()[self.time_axis];
+        for (b, &len) in lens.iter().enumerate() {
+            if len < 0 || len as usize > steps {
+                bail!("ReverseSequence: sequence length {} out of range 0..={}", len, steps)
+            }
+            let len = len as usize;
+            let source = input.index_axis(Axis(self.batch_axis), b);
+            let mut target = output.index_axis_mut(Axis(self.batch_axis), b);
+            // time axis is shifted down if the batch axis came before it
+            let time_axis =
+                if self.batch_axis < self.time_axis { self.time_axis - 1 } else { self.time_axis };
+            for t in 0..len {
+                target
+                    .index_axis_mut(Axis(time_axis), t)
+                    .assign(&source.index_axis(Axis(time_axis), len - 1 - t));
+            }
+        }
+        Ok(output.into_tensor())
+    }
+}
+
+impl Op for ReverseSequence {
+    fn name(&self) -> Cow<str> {
+        "ReverseSequence".into()
+    }
+
+    fn info(&self) -> TractResult<Vec<String>> {
+        Ok(vec![format!("batch_axis: {} time_axis: {}", self.batch_axis, self.time_axis)])
+    }
+
+    op_as_typed_op!();
+    not_a_pulsed_op!();
+}
+
+impl StatelessOp for ReverseSequence {
+    fn eval(&self, mut inputs: TVec<Arc<Tensor>>) -> TractResult<TVec<Arc<Tensor>>> {
+        let (input, lens) = args_2!(inputs);
+        self.check_axes(input.rank())?;
+        let lens = match lens.datum_type() {
+            DatumType::I32 | DatumType::I64 => lens.cast_to::<i64>()?,
+            dt => bail!("ReverseSequence expects i32 or i64 sequence_lens, got {:?}", dt),
+        };
+        let lens = lens.as_slice::<i64>()?;
+        if lens.len() != input.shape()[self.batch_axis] {
+            bail!(
+                "ReverseSequence expects {} sequence lengths, got {}",
+                input.shape()[self.batch_axis],
+                lens.len()
+            )
+        }
+        let output = dispatch_datum!(Self::eval_t(input.datum_type())(self, &input, lens))?;
+        Ok(tvec!(output.into_arc_tensor()))
+    }
+}
+
+impl InferenceRulesOp for ReverseSequence {
+    fn rules<'r, 'p: 'r, 's: 'r>(
+        &'s self,
+        s: &mut Solver<'r>,
+        inputs: &'p [TensorProxy],
+        outputs: &'p [TensorProxy],
+    ) -> InferenceResult {
+        check_input_arity(&inputs, 2)?;
+        check_output_arity(&outputs, 1)?;
+        s.equals(&inputs[0].datum_type, &outputs[0].datum_type)?;
+        s.equals(&inputs[0].shape, &outputs[0].shape)?;
+        s.equals(&inputs[1].rank, 1)?;
+        s.equals(&inputs[1].shape[0], &inputs[0].shape[self.batch_axis])?;
+        Ok(())
+    }
+
+    inference_op_as_op!();
+    to_typed!();
+}
+
+impl TypedOp for ReverseSequence {
+    fn output_facts(&self, inputs: &[&TypedFact]) -> TractResult<TVec<TypedFact>> {
+        self.check_axes(inputs[0].rank())?;
+        if inputs[1].datum_type != i32::datum_type() && inputs[1].datum_type != i64::datum_type() {
+            bail!("ReverseSequence expects i32 or i64 sequence_lens, got {:?}", inputs[1])
+        }
+        Ok(tvec!(TypedFact::dt_shape(inputs[0].datum_type, inputs[0].shape.clone())?))
+    }
+
+    typed_op_as_op!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn time_major() -> TractResult<()> {
+        // time x batch
+        let input = tensor2(&[[1i32, 5], [2, 6], [3, 7], [4, 8]]);
+        let op = ReverseSequence::new(1, 0);
+        let output = op.eval(tvec!(input.into(), rctensor1(&[4i64, 2])))?;
+        assert_eq!(*output[0], tensor2(&[[4i32, 6], [3, 5], [2, 7], [1, 8]]));
+        Ok(())
+    }
+
+    #[test]
+    fn batch_major_with_features() -> TractResult<()> {
+        // batch x time x features
+        let input =
+            tensor3(&[[[1f32, 10.], [2., 20.], [3., 30.]], [[4f32, 40.], [5., 50.], [6., 60.]]]);
+        let op = ReverseSequence::new(0, 1);
+        let output = op.eval(tvec!(input.into(), rctensor1(&[1i32, 2])))?;
+        assert_eq!(
+            *output[0],
+            tensor3(&[[[1f32, 10.], [2., 20.], [3., 30.]], [[5f32, 50.], [4., 40.], [6., 60.]]])
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn too_long() {
+        let input = tensor2(&[[1i32, 2], [3, 4]]);
+        let op = ReverseSequence::new(0, 1);
+        assert!(op.eval(tvec!(input.into(), rctensor1(&[3i64, 1]))).is_err());
+    }
+}
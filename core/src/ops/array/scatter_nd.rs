@@ -0,0 +1,243 @@
+use crate::internal::*;
+use ndarray::*;
+
+/// How updates are combined with the existing data.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ScatterReduction {
+    /// Updates overwrite data, last write wins.
+    None,
+    Add,
+    Mul,
+}
+
+impl Default for ScatterReduction {
+    fn default() -> ScatterReduction {
+        ScatterReduction::None
+    }
+}
+
+#[derive(Debug, Clone, new, Default)]
+pub struct ScatterNd {
+    pub reduction: ScatterReduction,
+}
+
+impl ScatterNd {
+    fn eval_t<T: Datum>(
+        &self,
+        data: Arc<Tensor>,
+        indices: &Tensor,
+        updates: &Tensor,
+        combine: impl Fn(&mut T, &T),
+    ) -> TractResult<Arc<Tensor>> {
+        let mut data = data.into_tensor().into_array::<T>()?;
+        let indices = indices.cast_to::<i64>()?;
+        let indices = indices.to_array_view::<i64>()?;
+        let updates = updates.to_array_view::<T>()?;
+        if indices.ndim() == 0 {
+            bail!("ScatterND indices must be of rank at least 1")
+        }
+        let k = indices.shape()[indices.ndim() - 1];
+        if k > data.ndim() {
+            bail!("ScatterND indices last dim is {}, but data is of rank {}", k, data.ndim())
+        }
+        let updates_shape: TVec<usize> = indices.shape()[..indices.ndim() - 1]
+            .iter()
+            .chain(data.shape()[k..].iter())
+            .cloned()
+            .collect();
+        if updates.shape() != &*updates_shape {
+            bail!(
+                "ScatterND expects updates of shape {:?}, got {:?}",
+                updates_shape,
+                updates.shape()
+            )
+        }
+        let n = indices.len() / k.max(1);
+        let indices = indices.into_shape((n, k))?;
+        let slice_shape: TVec<usize> =
+            std::iter::once(n).chain(data.shape()[k..].iter().cloned()).collect();
+        let updates = updates.into_shape(&*slice_shape)?;
+        for (i, index) in indices.outer_iter().enumerate() {
+            let mut target = data.view_mut();
+            for (axis, &ix) in index.iter().enumerate() {
+                let dim = target.shape()[0] as i64;
+                let ix = if ix < 0 { ix + dim } else { ix };
+                if cfg!(debug_assertions) && (ix < 0 || ix >= dim) {
+                    bail!(
+                        "ScatterND index {} out of bounds for axis {} of size {}",
+                        index[axis],
+                        axis,
+                        dim
+                    )
+                }
+                target = target.index_axis_move(Axis(0), ix as usize);
+            }
+            Zip::from(&mut target).and(&updates.index_axis(Axis(0), i)).apply(|t, u| combine(t, u));
+        }
+        Ok(data.into_arc_tensor())
+    }
+
+    fn eval_assign<T: Datum>(
+        &self,
+        data: Arc<Tensor>,
+        indices: &Tensor,
+        updates: &Tensor,
+    ) -> TractResult<Arc<Tensor>> {
+        self.eval_t::<T>(data, indices, updates, |t, u| *t = u.clone())
+    }
+
+    fn eval_add<T: Datum + Copy + std::ops::Add<Output = T>>(
+        &self,
+        data: Arc<Tensor>,
+        indices: &Tensor,
+        updates: &Tensor,
+    ) -> TractResult<Arc<Tensor>> {
+        self.eval_t::<T>(data, indices, updates, |t, u| *t = *t + *u)
+    }
+
+    fn eval_mul<T: Datum + Copy + std::ops::Mul<Output = T>>(
+        &self,
+        data: Arc<Tensor>,
+        indices: &Tensor,
+        updates: &Tensor,
+    ) -> TractResult<Arc<Tensor>> {
+        self.eval_t::<T>(data, indices, updates, |t, u| *t = *t * *u)
+    }
+}
+
+impl Op for ScatterNd {
+    fn name(&self) -> Cow<str> {
+        "ScatterNd".into()
+    }
+
+    fn info(&self) -> TractResult<Vec<String>> {
+        Ok(vec![format!("reduction: {:?}", self.reduction)])
+    }
+
+    op_as_typed_op!();
+    not_a_pulsed_op!();
+}
+
+impl StatelessOp for ScatterNd {
+    fn eval(&self, mut inputs: TVec<Arc<Tensor>>) -> TractResult<TVec<Arc<Tensor>>> {
+        let (data, indices, updates) = args_3!(inputs);
+        let dt = data.datum_type();
+        let output = match self.reduction {
+            ScatterReduction::None => {
+                dispatch_datum!(Self::eval_assign(dt)(self, data, &indices, &updates))?
+            }
+            ScatterReduction::Add => {
+                dispatch_numbers!(Self::eval_add(dt)(self, data, &indices, &updates))?
+            }
+            ScatterReduction::Mul => {
+                dispatch_numbers!(Self::eval_mul(dt)(self, data, &indices, &updates))?
+            }
+        };
+        Ok(tvec!(output))
+    }
+}
+
+impl InferenceRulesOp for ScatterNd {
+    fn rules<'r, 'p: 'r, 's: 'r>(
+        &'s self,
+        s: &mut Solver<'r>,
+        inputs: &'p [TensorProxy],
+        outputs: &'p [TensorProxy],
+    ) -> InferenceResult {
+        check_input_arity(&inputs, 3)?;
+        check_output_arity(&outputs, 1)?;
+        s.equals(&inputs[0].datum_type, &outputs[0].datum_type)?;
+        s.equals(&inputs[2].datum_type, &outputs[0].datum_type)?;
+        s.equals(&inputs[1].datum_type, i64::datum_type())?;
+        s.equals(&inputs[0].rank, &outputs[0].rank)?;
+        s.equals(&inputs[0].shape, &outputs[0].shape)?;
+        Ok(())
+    }
+
+    inference_op_as_op!();
+    to_typed!();
+}
+
+impl TypedOp for ScatterNd {
+    fn output_facts(&self, inputs: &[&TypedFact]) -> TractResult<TVec<TypedFact>> {
+        Ok(tvec!(TypedFact::dt_shape(inputs[0].datum_type, inputs[0].shape.clone())?))
+    }
+
+    typed_op_as_op!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scatter(
+        reduction: ScatterReduction,
+        data: Tensor,
+        indices: Tensor,
+        updates: Tensor,
+    ) -> TractResult<Arc<Tensor>> {
+        let op = ScatterNd::new(reduction);
+        Ok(op.eval(tvec!(data.into(), indices.into(), updates.into()))?.remove(0))
+    }
+
+    #[test]
+    fn scatter_1d() -> TractResult<()> {
+        let output = scatter(
+            ScatterReduction::None,
+            tensor1(&[1f32, 2., 3., 4., 5., 6., 7., 8.]),
+            tensor2(&[[4i64], [3], [1], [7]]),
+            tensor1(&[9f32, 10., 11., 12.]),
+        )?;
+        assert_eq!(*output, tensor1(&[1f32, 11., 3., 10., 9., 6., 7., 12.]));
+        Ok(())
+    }
+
+    #[test]
+    fn scatter_batch_of_2d() -> TractResult<()> {
+        let data = Tensor::from(Array3::<i32>::zeros((2, 2, 2)));
+        let output = scatter(
+            ScatterReduction::None,
+            data,
+            tensor2(&[[0i64, 1], [1, 0]]),
+            tensor2(&[[1i32, 2], [3, 4]]),
+        )?;
+        assert_eq!(*output, tensor3(&[[[0i32, 0], [1, 2]], [[3, 4], [0, 0]]]));
+        Ok(())
+    }
+
+    #[test]
+    fn duplicate_indices_add() -> TractResult<()> {
+        let output = scatter(
+            ScatterReduction::Add,
+            tensor1(&[1f32, 1., 1.]),
+            tensor2(&[[0i64], [2], [0], [-1]]),
+            tensor1(&[1f32, 2., 3., 4.]),
+        )?;
+        assert_eq!(*output, tensor1(&[5f32, 1., 7.]));
+        Ok(())
+    }
+
+    #[test]
+    fn duplicate_indices_mul() -> TractResult<()> {
+        let output = scatter(
+            ScatterReduction::Mul,
+            tensor1(&[1i64, 2, 3]),
+            tensor2(&[[1i64], [1]]),
+            tensor1(&[3i64, 5]),
+        )?;
+        assert_eq!(*output, tensor1(&[1i64, 30, 3]));
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    fn out_of_bounds() {
+        let output = scatter(
+            ScatterReduction::None,
+            tensor1(&[1f32, 2.]),
+            tensor2(&[[2i64]]),
+            tensor1(&[3f32]),
+        );
+        assert!(output.is_err());
+    }
+}
@@ -0,0 +1,146 @@
+use crate::internal::*;
+use ndarray::*;
+
+/// Move blocks of spatial data to the channel axis, as ONNX SpaceToDepth.
+///
+/// A `[N, C, H, W]` input becomes `[N, C * block², H / block, W / block]`.
+/// The depth axis is ordered by row offset in block, then column offset in
+/// block, then input channel. H and W must be multiples of `block_size`.
+#[derive(Debug, Clone, new)]
+pub struct SpaceToDepth {
+    pub block_size: usize,
+}
+
+/// Reshape the input to `split`, permute its axes and reshape the result to
+/// `shape`, in standard layout.
+pub(super) fn rearrange<T: Datum>(
+    input: Arc<Tensor>,
+    split: &[usize],
+    axes: &[usize],
+    shape: &[usize],
+) -> TractResult<Arc<Tensor>> {
+    let input = input.into_tensor().into_array::<T>()?.into_shape(split)?;
+    let data: Vec<T> = input.permuted_axes(axes).iter().cloned().collect();
+    Ok(ArrayD::from_shape_vec(shape, data)?.into_arc_tensor())
+}
+
+pub(super) fn check_block_size(name: &str, block_size: usize) -> TractResult<()> {
+    if block_size == 0 {
+        bail!("{} block_size must be positive", name)
+    }
+    Ok(())
+}
+
+impl SpaceToDepth {
+    fn output_shape<D: DimLike>(&self, shape: &[D]) -> TractResult<TVec<D>> {
+        check_block_size("SpaceToDepth", self.block_size)?;
+        let b = self.block_size;
+        if shape.len() != 4 {
+            bail!("SpaceToDepth expects a [N, C, H, W] input, got {:?}", shape)
+        }
+        for d in &shape[2..] {
+            if (d.clone() % b).to_integer().map(|r| r != 0).unwrap_or(false) {
+                bail!("SpaceToDepth spatial dims {:?} must be multiples of {}", &shape[2..], b)
+            }
+        }
+        Ok(tvec!(
+            shape[0].clone(),
+            shape[1].clone() * (b * b),
+            shape[2].clone() / b,
+            shape[3].clone() / b
+        ))
+    }
+
+    fn eval_t<T: Datum>(&self, input: Arc<Tensor>) -> TractResult<Arc<Tensor>> {
+        let b = self.block_size;
+        let shape = self.output_shape(input.shape())?;
+        let (n, c, h, w) = (shape[0], input.shape()[1], input.shape()[2], input.shape()[3]);
+        rearrange::<T>(input, &[n, c, h / b, b, w / b, b], &[0, 3, 5, 1, 2, 4], &shape)
+    }
+}
+
+impl Op for SpaceToDepth {
+    fn name(&self) -> Cow<str> {
+        "SpaceToDepth".into()
+    }
+
+    fn info(&self) -> TractResult<Vec<String>> {
+        Ok(vec![format!("block_size: {}", self.block_size)])
+    }
+
+    fn validate_attributes(&self) -> TractResult<()> {
+        check_block_size("SpaceToDepth", self.block_size)
+    }
+
+    op_as_typed_op!();
+    not_a_pulsed_op!();
+}
+
+impl StatelessOp for SpaceToDepth {
+    fn eval(&self, mut inputs: TVec<Arc<Tensor>>) -> TractResult<TVec<Arc<Tensor>>> {
+        let input = args_1!(inputs);
+        Ok(tvec!(dispatch_datum!(Self::eval_t(input.datum_type())(self, input))?))
+    }
+}
+
+impl InferenceRulesOp for SpaceToDepth {
+    fn rules<'r, 'p: 'r, 's: 'r>(
+        &'s self,
+        s: &mut Solver<'r>,
+        inputs: &'p [TensorProxy],
+        outputs: &'p [TensorProxy],
+    ) -> InferenceResult {
+        check_input_arity(&inputs, 1)?;
+        check_output_arity(&outputs, 1)?;
+        s.equals(&outputs[0].datum_type, &inputs[0].datum_type)?;
+        s.equals(&inputs[0].rank, 4)?;
+        s.equals(&outputs[0].rank, 4)?;
+        s.given(&inputs[0].shape, move |s, shape| {
+            s.equals(&outputs[0].shape, ShapeFact::from(self.output_shape(&*shape)?))
+        })
+    }
+
+    inference_op_as_op!();
+    to_typed!();
+}
+
+impl TypedOp for SpaceToDepth {
+    fn output_facts(&self, inputs: &[&TypedFact]) -> TractResult<TVec<TypedFact>> {
+        let shape = self.output_shape(&*inputs[0].shape.to_tvec())?;
+        Ok(tvec!(TypedFact::dt_shape(inputs[0].datum_type, &*shape)?))
+    }
+
+    typed_op_as_op!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn space_to_depth() -> TractResult<()> {
+        let input = Array4::from_shape_fn((1, 1, 2, 4), |(_, _, h, w)| (h * 4 + w) as i32);
+        let output = SpaceToDepth::new(2).eval(tvec!(input.into_arc_tensor()))?.remove(0);
+        let expected = arr3(&[[[0, 2]], [[1, 3]], [[4, 6]], [[5, 7]]]).into_shape((1, 4, 1, 2))?;
+        assert_eq!(output, expected.into_arc_tensor());
+        Ok(())
+    }
+
+    #[test]
+    fn output_facts() -> TractResult<()> {
+        let fact = TypedFact::dt_shape(
+            f32::datum_type(),
+            [TDim::s(), 3.into(), 4.into(), 6.into()].as_ref(),
+        )?;
+        let output = SpaceToDepth::new(2).output_facts(&[&fact])?.remove(0);
+        assert_eq!(output.shape.to_tvec(), tvec!(TDim::s(), 12.into(), 2.into(), 3.into()));
+        Ok(())
+    }
+
+    #[test]
+    fn indivisible_spatial_dims() {
+        let input = Array4::<f32>::zeros((1, 1, 2, 3));
+        assert!(SpaceToDepth::new(2).eval(tvec!(input.into_arc_tensor())).is_err());
+        assert!(SpaceToDepth::new(0).validate_attributes().is_err());
+    }
+}
@@ -0,0 +1,265 @@
+use crate::internal::*;
+use ndarray::*;
+use std::cmp::{Ordering, Reverse};
+use std::collections::BinaryHeap;
+
+/// Top k values of the data along an axis, and their indices.
+///
+/// k is given as the second input. Ties are broken by preferring the lower
+/// index. If `sorted` is false, the k elements are output in no particular
+/// order, saving a full sort of every lane.
+#[derive(Debug, Clone, new)]
+pub struct TopK {
+    pub axis: i64,
+    pub largest: bool,
+    pub sorted: bool,
+}
+
+impl Default for TopK {
+    fn default() -> TopK {
+        TopK { axis: -1, largest: true, sorted: true }
+    }
+}
+
+/// A lane element, ordered so that the greatest is the one that should come
+/// first in the output.
+struct Candidate<T> {
+    value: T,
+    index: usize,
+    largest: bool,
+}
+
+impl<T: PartialOrd> Ord for Candidate<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let by_value = self.value.partial_cmp(&other.value).unwrap_or(Ordering::Equal);
+        let by_value = if self.largest { by_value } else { by_value.reverse() };
+        by_value.then_with(|| other.index.cmp(&self.index))
+    }
+}
+
+impl<T: PartialOrd> PartialOrd for Candidate<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: PartialOrd> PartialEq for Candidate<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<T: PartialOrd> Eq for Candidate<T> {}
+
+impl TopK {
+    fn resolved_axis(&self, rank: usize) -> TractResult<usize> {
+        if 0 <= self.axis && self.axis < rank as i64 {
+            Ok(self.axis as usize)
+        } else if -(rank as i64) <= self.axis && self.axis < 0 {
+            Ok((self.axis + rank as i64) as usize)
+        } else {
+            bail!("Illegal axis {} for rank {}", self.axis, rank)
+        }
+    }
+
+    fn k(k: &Tensor) -> TractResult<usize> {
+        let k = k.cast_to::<i64>()?;
+        match k.as_slice::<i64>()? {
+            &[k] if k >= 0 => Ok(k as usize),
+            k => bail!("TopK expects a single positive k, got {:?}", k),
+        }
+    }
+
+    pub fn output_shape<D: DimLike>(&self, input: &[D], k: usize) -> TractResult<TVec<D>> {
+        let mut shape: TVec<D> = input.into();
+        shape[self.resolved_axis(input.len())?] = D::from(k);
+        Ok(shape)
+    }
+
+    fn select<T: Datum + PartialOrd>(&self, lane: ArrayView1<T>, k: usize) -> Vec<Candidate<T>> {
+        let candidates = lane.iter().enumerate().map(|(index, value)| Candidate {
+            value: value.clone(),
+            index,
+            largest: self.largest,
+        });
+        if self.sorted {
+            let mut all: Vec<_> = candidates.collect();
+            all.sort_by(|a, b| b.cmp(a));
+            all.truncate(k);
+            all
+        } else {
+            let mut heap = BinaryHeap::with_capacity(k + 1);
+            for c in candidates {
+                heap.push(Reverse(c));
+                if heap.len() > k {
+                    heap.pop();
+                }
+            }
+            heap.into_vec().into_iter().map(|c| c.0).collect()
+        }
+    }
+
+    fn eval_t<T: Datum + PartialOrd>(
+        &self,
+        data: &Tensor,
+        k: usize,
+    ) -> TractResult<TVec<Arc<Tensor>>> {
+        let data = data.to_array_view::<T>()?;
+        let axis = self.resolved_axis(data.ndim())?;
+        if k > data.shape()[axis] {
+            bail!("TopK with k={} over an axis of size {}", k, data.shape()[axis])
+        }
+        let shape = self.output_shape(data.shape(), k)?;
+        let mut values = unsafe { T::uninitialized_array(&*shape) };
+        let mut indices = Array::<i64, _>::zeros(&*shape);
+        Zip::from(data.lanes(Axis(axis)))
+            .and(values.lanes_mut(Axis(axis)))
+            .and(indices.lanes_mut(Axis(axis)))
+            .apply(|lane, mut values, mut indices| {
+                for (ix, c) in self.select(lane, k).into_iter().enumerate() {
+                    values[ix] = c.value;
+                    indices[ix] = c.index as i64;
+                }
+            });
+        Ok(tvec!(values.into_arc_tensor(), indices.into_arc_tensor()))
+    }
+}
+
+impl Op for TopK {
+    fn name(&self) -> Cow<str> {
+        "TopK".into()
+    }
+
+    fn info(&self) -> TractResult<Vec<String>> {
+        Ok(vec![format!("axis: {}, largest: {}, sorted: {}", self.axis, self.largest, self.sorted)])
+    }
+
+    op_as_typed_op!();
+    not_a_pulsed_op!();
+}
+
+impl StatelessOp for TopK {
+    fn eval(&self, mut inputs: TVec<Arc<Tensor>>) -> TractResult<TVec<Arc<Tensor>>> {
+        let (data, k) = args_2!(inputs);
+        let k = Self::k(&k)?;
+        dispatch_numbers!(Self::eval_t(data.datum_type())(self, &data, k))
+    }
+}
+
+impl InferenceRulesOp for TopK {
+    fn rules<'r, 'p: 'r, 's: 'r>(
+        &'s self,
+        s: &mut Solver<'r>,
+        inputs: &'p [TensorProxy],
+        outputs: &'p [TensorProxy],
+    ) -> InferenceResult {
+        check_input_arity(&inputs, 2)?;
+        check_output_arity(&outputs, 2)?;
+        s.equals(&inputs[0].datum_type, &outputs[0].datum_type)?;
+        s.equals(&outputs[1].datum_type, i64::datum_type())?;
+        s.equals(&inputs[0].rank, &outputs[0].rank)?;
+        s.equals(&inputs[0].rank, &outputs[1].rank)?;
+        s.given(&inputs[0].rank, move |s, rank| {
+            let axis = self.resolved_axis(rank as usize)?;
+            for d in 0..rank as usize {
+                if d != axis {
+                    s.equals(&inputs[0].shape[d], &outputs[0].shape[d])?;
+                    s.equals(&inputs[0].shape[d], &outputs[1].shape[d])?;
+                }
+            }
+            Ok(())
+        })?;
+        s.given_2(&inputs[0].rank, &inputs[1].value, move |s, rank, k| {
+            let axis = self.resolved_axis(rank as usize)?;
+            let k = Self::k(&k)?;
+            s.equals(&outputs[0].shape[axis], k.to_dim())?;
+            s.equals(&outputs[1].shape[axis], k.to_dim())?;
+            Ok(())
+        })?;
+        Ok(())
+    }
+
+    inference_op_as_op!();
+    to_typed!();
+}
+
+impl TypedOp for TopK {
+    fn output_facts(&self, inputs: &[&TypedFact]) -> TractResult<TVec<TypedFact>> {
+        let k = if let Some(k) = &inputs[1].konst {
+            Self::k(k)?
+        } else {
+            bail!("TopK requires a constant k")
+        };
+        let shape = self.output_shape(&*inputs[0].shape.to_tvec(), k)?;
+        Ok(tvec!(
+            TypedFact::dt_shape(inputs[0].datum_type, &*shape)?,
+            TypedFact::dt_shape(i64::datum_type(), &*shape)?
+        ))
+    }
+
+    typed_op_as_op!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn topk(op: TopK, data: Tensor, k: i64) -> TractResult<(Arc<Tensor>, Arc<Tensor>)> {
+        let mut outputs = op.eval(tvec!(data.into(), rctensor1(&[k])))?;
+        let indices = outputs.pop().unwrap();
+        Ok((outputs.pop().unwrap(), indices))
+    }
+
+    #[test]
+    fn topk_1d() -> TractResult<()> {
+        let (values, indices) = topk(TopK::default(), tensor1(&[1f32, 4., 2., 4., 3.]), 3)?;
+        assert_eq!(*values, tensor1(&[4f32, 4., 3.]));
+        assert_eq!(*indices, tensor1(&[1i64, 3, 4]));
+        Ok(())
+    }
+
+    #[test]
+    fn topk_smallest() -> TractResult<()> {
+        let op = TopK::new(0, false, true);
+        let (values, indices) = topk(op, tensor1(&[3i32, 1, 2, 1]), 2)?;
+        assert_eq!(*values, tensor1(&[1i32, 1]));
+        assert_eq!(*indices, tensor1(&[1i64, 3]));
+        Ok(())
+    }
+
+    #[test]
+    fn topk_batched_2d() -> TractResult<()> {
+        let data = tensor2(&[[0f32, 1., 2., 3.], [4., 5., 6., 7.], [11., 10., 9., 8.]]);
+        let (values, indices) = topk(TopK::default(), data.clone(), 2)?;
+        assert_eq!(*values, tensor2(&[[3f32, 2.], [7., 6.], [11., 10.]]));
+        assert_eq!(*indices, tensor2(&[[3i64, 2], [3, 2], [0, 1]]));
+        let (values, indices) = topk(TopK::new(0, true, true), data, 1)?;
+        assert_eq!(*values, tensor2(&[[11f32, 10., 9., 8.]]));
+        assert_eq!(*indices, tensor2(&[[2i64, 2, 2, 2]]));
+        Ok(())
+    }
+
+    #[test]
+    fn topk_unsorted() -> TractResult<()> {
+        let op = TopK::new(-1, true, false);
+        let (values, indices) = topk(op, tensor1(&[5f32, 1., 5., 0., 3., 2.]), 3)?;
+        let mut found: Vec<(i64, f32)> = indices
+            .as_slice::<i64>()?
+            .iter()
+            .cloned()
+            .zip(values.as_slice::<f32>()?.iter().cloned())
+            .collect();
+        found.sort_by_key(|p| p.0);
+        assert_eq!(found, vec!((0, 5.), (2, 5.), (4, 3.)));
+        Ok(())
+    }
+
+    #[test]
+    fn topk_whole_axis() -> TractResult<()> {
+        let (values, indices) = topk(TopK::default(), tensor1(&[2i64, 3, 1]), 3)?;
+        assert_eq!(*values, tensor1(&[3i64, 2, 1]));
+        assert_eq!(*indices, tensor1(&[1i64, 0, 2]));
+        assert!(topk(TopK::default(), tensor1(&[2i64, 3, 1]), 4).is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,173 @@
+use crate::internal::*;
+
+/// Upper or lower triangle of the matrices formed by the last two axes of
+/// the input, as in ONNX.
+///
+/// The optional second input is the diagonal offset k: with a positive k,
+/// the boundary moves up and right, a negative k moves it down and left.
+/// Elements outside of the triangle are set to zero.
+#[derive(Debug, Clone, new)]
+pub struct Trilu {
+    pub upper: bool,
+}
+
+impl Default for Trilu {
+    fn default() -> Trilu {
+        Trilu { upper: true }
+    }
+}
+
+impl Trilu {
+    fn k(k: Option<&Arc<Tensor>>) -> TractResult<i64> {
+        match k {
+            None => Ok(0),
+            Some(k) => k.cast_to_scalar::<i64>(),
+        }
+    }
+
+    fn keep(&self, row: usize, col: usize, k: i64) -> bool {
+        let offset = col as i64 - row as i64;
+        if self.upper {
+            offset >= k
+        } else {
+            offset <= k
+        }
+    }
+
+    fn eval_t<T: Datum>(&self, data: Arc<Tensor>, k: i64) -> TractResult<Arc<Tensor>> {
+        let mut output = data.into_tensor();
+        let view = output.to_array_view_mut::<T>()?;
+        let rank = view.ndim();
+        let rows = view.shape()[rank - 2];
+        let cols = view.shape()[rank - 1];
+        let matrices = view.len() / (rows * cols).max(1);
+        let mut view = view.into_shape((matrices, rows, cols))?;
+        for mut matrix in view.outer_iter_mut() {
+            for ((row, col), x) in matrix.indexed_iter_mut() {
+                if !self.keep(row, col, k) {
+                    *x = T::default();
+                }
+            }
+        }
+        Ok(output.into_arc_tensor())
+    }
+}
+
+impl Op for Trilu {
+    fn name(&self) -> Cow<str> {
+        "Trilu".into()
+    }
+
+    fn info(&self) -> TractResult<Vec<String>> {
+        Ok(vec![format!("upper: {}", self.upper)])
+    }
+
+    op_as_typed_op!();
+    not_a_pulsed_op!();
+}
+
+impl StatelessOp for Trilu {
+    fn eval(&self, inputs: TVec<Arc<Tensor>>) -> TractResult<TVec<Arc<Tensor>>> {
+        if inputs.is_empty() || inputs.len() > 2 {
+            bail!("Trilu expects 1 or 2 inputs, got {}", inputs.len())
+        }
+        if inputs[0].rank() < 2 {
+            bail!("Trilu expects an input of rank 2 or more, got {:?}", inputs[0].shape())
+        }
+        let k = Self::k(inputs.get(1))?;
+        let data = inputs[0].clone();
+        Ok(tvec!(dispatch_datum!(Self::eval_t(data.datum_type())(self, data, k))?))
+    }
+}
+
+impl InferenceRulesOp for Trilu {
+    fn rules<'r, 'p: 'r, 's: 'r>(
+        &'s self,
+        s: &mut Solver<'r>,
+        inputs: &'p [TensorProxy],
+        outputs: &'p [TensorProxy],
+    ) -> InferenceResult {
+        if inputs.is_empty() || inputs.len() > 2 {
+            bail!("Trilu expects 1 or 2 inputs, got {}", inputs.len())
+        }
+        check_output_arity(&outputs, 1)?;
+        s.equals(&inputs[0].datum_type, &outputs[0].datum_type)?;
+        s.equals(&inputs[0].shape, &outputs[0].shape)?;
+        if let Some(k) = inputs.get(1) {
+            s.equals(&k.rank, 0)?;
+        }
+        Ok(())
+    }
+
+    inference_op_as_op!();
+    to_typed!();
+}
+
+impl TypedOp for Trilu {
+    fn output_facts(&self, inputs: &[&TypedFact]) -> TractResult<TVec<TypedFact>> {
+        if inputs[0].rank() < 2 {
+            bail!("Trilu expects an input of rank 2 or more, got {:?}", inputs[0])
+        }
+        Ok(tvec!(TypedFact::dt_shape(inputs[0].datum_type, inputs[0].shape.clone())?))
+    }
+
+    typed_op_as_op!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn trilu(upper: bool, data: Tensor, k: i64) -> TractResult<Arc<Tensor>> {
+        Ok(Trilu::new(upper).eval(tvec!(data.into(), rctensor0(k)))?.remove(0))
+    }
+
+    fn square() -> Tensor {
+        tensor2(&[[1i32, 2, 3], [4, 5, 6], [7, 8, 9]])
+    }
+
+    #[test]
+    fn square_upper_and_lower() -> TractResult<()> {
+        assert_eq!(*trilu(true, square(), 0)?, tensor2(&[[1i32, 2, 3], [0, 5, 6], [0, 0, 9]]));
+        assert_eq!(*trilu(false, square(), 0)?, tensor2(&[[1i32, 0, 0], [4, 5, 0], [7, 8, 9]]));
+        Ok(())
+    }
+
+    #[test]
+    fn square_offsets() -> TractResult<()> {
+        assert_eq!(*trilu(true, square(), 1)?, tensor2(&[[0i32, 2, 3], [0, 0, 6], [0, 0, 0]]));
+        assert_eq!(*trilu(true, square(), -1)?, tensor2(&[[1i32, 2, 3], [4, 5, 6], [0, 8, 9]]));
+        assert_eq!(*trilu(false, square(), -1)?, tensor2(&[[0i32, 0, 0], [4, 0, 0], [7, 8, 0]]));
+        Ok(())
+    }
+
+    #[test]
+    fn rectangular() -> TractResult<()> {
+        let wide = tensor2(&[[1f32, 2., 3., 4.], [5., 6., 7., 8.]]);
+        assert_eq!(
+            *trilu(true, wide.clone(), 2)?,
+            tensor2(&[[0f32, 0., 3., 4.], [0., 0., 0., 8.]])
+        );
+        assert_eq!(*trilu(false, wide, 1)?, tensor2(&[[1f32, 2., 0., 0.], [5., 6., 7., 0.]]));
+        let tall = tensor2(&[[1f32, 2.], [3., 4.], [5., 6.]]);
+        assert_eq!(*trilu(true, tall.clone(), -1)?, tensor2(&[[1f32, 2.], [3., 4.], [0., 6.]]));
+        assert_eq!(*trilu(false, tall, -2)?, tensor2(&[[0f32, 0.], [0., 0.], [5., 0.]]));
+        Ok(())
+    }
+
+    #[test]
+    fn batched() -> TractResult<()> {
+        let data = tensor3(&[[[1i64, 2], [3, 4]], [[5, 6], [7, 8]]]);
+        assert_eq!(
+            *trilu(true, data.clone(), 1)?,
+            tensor3(&[[[0i64, 2], [0, 0]], [[0, 6], [0, 0]]])
+        );
+        assert_eq!(
+            *trilu(false, data.clone(), -1)?,
+            tensor3(&[[[0i64, 0], [3, 0]], [[0, 0], [7, 0]]])
+        );
+        let no_k = Trilu::default().eval(tvec!(data.into()))?;
+        assert_eq!(*no_k[0], tensor3(&[[[1i64, 2], [0, 4]], [[5, 6], [0, 8]]]));
+        Ok(())
+    }
+}
@@ -445,6 +445,10 @@ fn pulsify_bin(
 pub struct UnaryOp {
     pub mini_op: Box<dyn BinMiniOp>,
     pub a: Arc<Tensor>,
+    /// Write the result over the input tensor instead of allocating a new
+    /// one. Set by `passes::set_inplace` when the input has no other use.
+    #[new(default)]
+    pub inplace: bool,
 }
 
 impl Op for UnaryOp {
@@ -453,7 +457,11 @@ impl Op for UnaryOp {
     }
 
     fn info(&self) -> TractResult<Vec<String>> {
-        Ok(vec![format!("a: {:?}", self.a)])
+        let mut info = vec![format!("a: {:?}", self.a)];
+        if self.inplace {
+            info.push("in-place".to_string());
+        }
+        Ok(info)
     }
 
     fn validation(&self) -> Validation {
@@ -466,8 +474,15 @@ impl Op for UnaryOp {
 }
 
 impl StatelessOp for UnaryOp {
-    fn eval(&self, inputs: TVec<Arc<Tensor>>) -> TractResult<TVec<Arc<Tensor>>> {
-        self.mini_op.eval_broadcast(tvec!(self.a.clone(), inputs[0].clone()))
+    fn eval(&self, mut inputs: TVec<Arc<Tensor>>) -> TractResult<TVec<Arc<Tensor>>> {
+        if self.inplace {
+            // only copies if the input is still referenced somewhere else
+            let mut b = args_1!(inputs).into_tensor();
+            self.mini_op.eval_in_place(&self.a, &mut b)?;
+            Ok(tvec!(b.into_arc_tensor()))
+        } else {
+            self.mini_op.eval_broadcast(tvec!(self.a.clone(), inputs[0].clone()))
+        }
     }
 }
 
@@ -831,12 +846,22 @@ macro_rules! bin_to_super_type {
                 $(
                     $(if a.datum_type() == $typ::datum_type() {
                         let cab: fn(&mut $typ, &$typ, &$typ) -> () = $cab;
-                        let a = a.as_slice::<$typ>()?;
-                        let b = b.as_slice_mut::<$typ>()?;
-                        for i in 0..a.len() {
-                            let mut c = $typ::default();
-                            cab(&mut c, &a[i], &b[i]);
-                            b[i] = c;
+                        if a.shape() == b.shape() {
+                            let a = a.as_slice::<$typ>()?;
+                            let b = b.as_slice_mut::<$typ>()?;
+                            for i in 0..a.len() {
+                                let mut c = $typ::default();
+                                cab(&mut c, &a[i], &b[i]);
+                                b[i] = c;
+                            }
+                        } else {
+                            let a = a.to_array_view::<$typ>()?;
+                            let mut b = b.to_array_view_mut::<$typ>()?;
+                            $crate::ndarray::Zip::from(&mut b).and_broadcast(&a).apply(|b, a| {
+                                let mut c = $typ::default();
+                                cab(&mut c, a, b);
+                                *b = c;
+                            });
                         }
                         return Ok(())
                     }
@@ -931,12 +956,22 @@ macro_rules! bin_to_bool {
                 $(
                     $(if a.datum_type() == $typ::datum_type() {
                         let cab: fn(&mut bool, &bool, &bool) -> () = $cab;
-                        let a = a.as_slice::<bool>()?;
-                        let b = b.as_slice_mut::<bool>()?;
-                        for i in 0..a.len() {
-                            let mut c = bool::default();
-                            cab(&mut c, &a[i], &b[i]);
-                            b[i] = c;
+                        if a.shape() == b.shape() {
+                            let a = a.as_slice::<bool>()?;
+                            let b = b.as_slice_mut::<bool>()?;
+                            for i in 0..a.len() {
+                                let mut c = bool::default();
+                                cab(&mut c, &a[i], &b[i]);
+                                b[i] = c;
+                            }
+                        } else {
+                            let a = a.to_array_view::<bool>()?;
+                            let mut b = b.to_array_view_mut::<bool>()?;
+                            ndarray::Zip::from(&mut b).and_broadcast(&a).apply(|b, a| {
+                                let mut c = bool::default();
+                                cab(&mut c, a, b);
+                                *b = c;
+                            });
                         }
                         return Ok(())
                     }
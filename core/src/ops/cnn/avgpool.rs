@@ -11,8 +11,8 @@ use crate::ops::nn::DataShape;
 
 #[derive(Debug, Clone, new, Default)]
 pub struct AvgPool {
-    pool_spec: PoolSpec,
-    count_include_pad: bool,
+    pub pool_spec: PoolSpec,
+    pub count_include_pad: bool,
 }
 
 impl AvgPool {
@@ -128,6 +128,27 @@ where
     _casper: PhantomData<T>,
 }
 
+impl<T: Datum + Float + Sum> AvgPoolFixed<T>
+where
+    usize: AsPrimitive<T>,
+{
+    pub fn patch(&self) -> &Patch {
+        &self.patch
+    }
+
+    pub fn input_shape(&self) -> &DataShape {
+        &self.input_shape
+    }
+
+    pub fn output_shape(&self) -> &DataShape {
+        &self.output_shape
+    }
+
+    pub fn count_include_pad(&self) -> bool {
+        self.count_include_pad
+    }
+}
+
 impl<T: Datum + Float + Sum> Op for AvgPoolFixed<T>
 where
     usize: AsPrimitive<T>,
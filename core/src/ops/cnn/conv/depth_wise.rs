@@ -15,6 +15,32 @@ where
     bias: Option<Vec<T>>,
 }
 
+impl<T> DepthWise<T>
+where
+    T: Datum + Clone + ndarray::LinalgScalar,
+{
+    pub fn patch(&self) -> &Patch {
+        &self.patch
+    }
+
+    pub fn input_shape(&self) -> &DataShape {
+        &self.input_shape
+    }
+
+    pub fn output_shape(&self) -> &DataShape {
+        &self.output_shape
+    }
+
+    /// Kernel, with channels first.
+    pub fn kernel_chw(&self) -> &ArrayD<T> {
+        &self.kernel_chw
+    }
+
+    pub fn bias(&self) -> Option<&[T]> {
+        self.bias.as_deref()
+    }
+}
+
 impl<T> Op for DepthWise<T>
 where
     T: Datum + Clone + ndarray::LinalgScalar,
@@ -2,10 +2,11 @@ use crate::internal::*;
 
 use super::ConvUnary;
 use crate::dim::DimLike;
-use crate::ops::cnn::conv::KernelFormat;
+use crate::ops::cnn::conv::{ConvAlgorithmSelector, KernelFormat};
 use crate::ops::cnn::PaddingSpec;
 use crate::ops::nn::DataFormat;
-use crate::ops::quant::QParams;
+use crate::ops::quant::{PerChannelQuantizationParams, QParams, RequantizePerChannel};
+use crate::ops::{ComputeCost, OpCost};
 use std::borrow::Borrow;
 
 #[derive(Debug, Clone, Default)]
@@ -31,6 +32,8 @@ pub struct Conv {
 
     pub override_output_datum_type: Option<DatumType>,
     pub override_bias_datum_type: Option<DatumType>,
+
+    pub algorithm: ConvAlgorithmSelector,
 }
 
 impl Conv {
@@ -78,6 +81,10 @@ impl Conv {
         Conv { override_output_datum_type: Some(override_output_datum_type), ..self }
     }
 
+    pub fn algorithm(self, algorithm: ConvAlgorithmSelector) -> Conv {
+        Conv { algorithm, ..self }
+    }
+
     pub fn output_shape<D: DimLike>(&self, ishape: &[D], kshape: &[usize]) -> TVec<D> {
         debug_assert_eq!(ishape.len(), kshape.len(), "Input and kernel should have the same rank");
         let mut result: TVec<D> = ishape.into();
@@ -102,6 +109,73 @@ impl Conv {
         result
     }
 
+    /// Combined requantization factor, input scale times filter scale over
+    /// output scale, for each output channel. A single factor is returned
+    /// when all scales are scalars.
+    fn scales(&self, inputs: &[&TypedFact]) -> TractResult<Option<Vec<f32>>> {
+        if self.x_scale_input.is_none()
+            && self.k_scale_input.is_none()
+            && self.y_scale_input.is_none()
+        {
+            return Ok(None);
+        }
+        let mut scales = vec![1.0f32];
+        for &(slot, what, divide) in &[
+            (self.x_scale_input, "Input", false),
+            (self.k_scale_input, "Filter", false),
+            (self.y_scale_input, "Output", true),
+        ] {
+            if let Some(slot) = slot {
+                let value = if let Some(ref value) = inputs[slot].borrow().konst {
+                    value.clone()
+                } else {
+                    bail!("{} scale must be const", what)
+                };
+                let value = value.as_slice::<f32>()?;
+                let len = scales.len().max(value.len());
+                if (scales.len() != 1 && scales.len() != len)
+                    || (value.len() != 1 && value.len() != len)
+                {
+                    bail!("Per channel scales must all have the same length")
+                }
+                let at = |v: &[f32], c: usize| if v.len() == 1 { v[0] } else { v[c] };
+                scales = (0..len)
+                    .map(|c| {
+                        if divide {
+                            at(&scales, c) / at(value, c)
+                        } else {
+                            at(&scales, c) * at(value, c)
+                        }
+                    })
+                    .collect();
+            }
+        }
+        Ok(Some(scales))
+    }
+
+    /// Requantization parameters, when at least one of the scales varies
+    /// along the output channels.
+    pub fn per_channel_q_params(
+        &self,
+        inputs: &[&TypedFact],
+    ) -> TractResult<Option<PerChannelQuantizationParams>> {
+        let scales = match self.scales(inputs)? {
+            Some(scales) if scales.iter().any(|&s| s != scales[0]) => scales,
+            _ => return Ok(None),
+        };
+        let zero_point = if let Some(slot) = self.y_zero_point_input {
+            if let Some(ref value) = inputs[slot].borrow().konst {
+                value.cast_to::<i32>()?.as_slice::<i32>()?.to_vec()
+            } else {
+                bail!("Output zero point must be const")
+            }
+        } else {
+            vec![0]
+        };
+        let dt = self.override_output_datum_type.unwrap_or(inputs[0].borrow().datum_type);
+        Ok(Some(PerChannelQuantizationParams::new(dt, scales, zero_point)))
+    }
+
     pub fn to_unary(&self, inputs: &[&TypedFact]) -> TractResult<Option<ConvUnary>> {
         let input = &inputs[0].borrow();
         let kernel = &inputs[self.k_input.unwrap_or(1)].borrow();
@@ -116,32 +190,18 @@ impl Conv {
         }
         if let Some(kvalue) = kernel.konst.clone() {
             let mut qp = None;
-            let dt = self.override_output_datum_type.unwrap_or(input.datum_type);
-            let mut scale = 1.0;
-            if let Some(slot) = self.x_scale_input {
-                if let Some(ref value) = inputs[slot].borrow().konst {
-                    scale *= value.to_scalar::<f32>()?;
-                } else {
-                    bail!("Input scale must be const")
-                }
-            }
-            if let Some(slot) = self.k_scale_input {
-                if let Some(ref value) = inputs[slot].borrow().konst {
-                    scale *= value.to_scalar::<f32>()?;
-                } else {
-                    bail!("Filter scale must be const")
-                }
-            }
-            if let Some(slot) = self.y_scale_input {
-                if let Some(ref value) = inputs[slot].borrow().konst {
-                    scale /= value.to_scalar::<f32>()?;
-                } else {
-                    bail!("Output scale must be const")
+            let per_channel = self.per_channel_q_params(inputs)?.is_some();
+            let dt = if per_channel {
+                // accumulate, per channel requantization is performed afterwards
+                i32::datum_type()
+            } else {
+                self.override_output_datum_type.unwrap_or(input.datum_type)
+            };
+            if let Some(scales) = self.scales(inputs)? {
+                if !per_channel && scales[0] != 1.0 {
+                    qp.get_or_insert(QParams::new(dt)).set_scale_factor(scales[0]);
                 }
             }
-            if scale != 1.0 {
-                qp.get_or_insert(QParams::new(dt)).set_scale_factor(scale);
-            }
             if let Some(slot) = self.x_zero_point_input {
                 if let Some(ref value) = inputs[slot].borrow().konst {
                     qp.get_or_insert(QParams::new(dt)).set_zero_point_b(value);
@@ -156,13 +216,16 @@ impl Conv {
                     bail!("Kernel zero point must be const")
                 }
             }
-            if let Some(slot) = self.y_zero_point_input {
+            if let Some(slot) = self.y_zero_point_input.filter(|_| !per_channel) {
                 if let Some(ref value) = inputs[slot].borrow().konst {
                     qp.get_or_insert(QParams::new(dt)).set_zero_point_c(value);
                 } else {
                     bail!("Output zero point must be const")
                 }
             }
+            if per_channel {
+                qp.get_or_insert(QParams::new(dt));
+            }
             let bias = if let Some(slot) = self.bias_input {
                 if let Some(ref value) = inputs[slot].borrow().konst {
                     Some(value.clone())
@@ -188,6 +251,41 @@ impl Op for Conv {
         Validation::Rounding
     }
 
+    fn validate_attributes(&self) -> TractResult<()> {
+        if let PaddingSpec::Explicit(before, after) = &self.padding {
+            if before.len() != after.len() {
+                bail!(
+                    "Explicit padding needs as many values before ({:?}) as after ({:?})",
+                    before,
+                    after
+                )
+            }
+        }
+        let mut ranks = vec![];
+        for (name, values) in &[
+            ("dilations", &self.dilations),
+            ("kernel_shape", &self.kernel_shape),
+            ("strides", &self.strides),
+        ] {
+            if let Some(values) = values {
+                if values.iter().any(|&v| v == 0) {
+                    bail!("Conv {} must be positive, got {:?}", name, values);
+                }
+                ranks.push((*name, values.len()));
+            }
+        }
+        if let PaddingSpec::Explicit(before, _) = &self.padding {
+            ranks.push(("pads", before.len()));
+        }
+        if let Some((name, rank)) = ranks.iter().find(|r| r.1 != ranks[0].1) {
+            bail!("Conv {} has {} spatial axes, but {} has {}", name, rank, ranks[0].0, ranks[0].1)
+        }
+        if self.group == Some(0) {
+            bail!("Conv group must be positive")
+        }
+        Ok(())
+    }
+
     op_as_typed_op!();
     not_a_pulsed_op!();
 }
@@ -195,8 +293,15 @@ impl Op for Conv {
 impl StatelessOp for Conv {
     fn eval(&self, inputs: TVec<Arc<Tensor>>) -> TractResult<TVec<Arc<Tensor>>> {
         let inputs_info: TVec<TypedFact> = inputs.iter().map(|t| TypedFact::from(&**t)).collect();
-        let unary = self.to_unary(&inputs_info.iter().collect::<TVec<_>>())?.unwrap();
-        unary.eval(tvec!(inputs[0].clone()))
+        let inputs_info = inputs_info.iter().collect::<TVec<_>>();
+        let unary = self.to_unary(&*inputs_info)?.unwrap();
+        let output = unary.eval(tvec!(inputs[0].clone()))?;
+        if let Some(q) = self.per_channel_q_params(&*inputs_info)? {
+            let axis = self.data_format.shape(inputs[0].shape()).c_axis();
+            RequantizePerChannel::new(q, axis).eval(output)
+        } else {
+            Ok(output)
+        }
     }
 }
 
@@ -241,6 +346,19 @@ impl InferenceRulesOp for Conv {
                 s.equals(&inputs[bias].shape[0], filter_o)
             })?
         }
+        if let Some(slot) = self.k_scale_input {
+            // a 1D filter scale is a per output channel scale
+            s.given_2(&inputs[slot].rank, &k_input.rank, move |s, rank, krank| {
+                if rank == 1 {
+                    let filter_o = match self.kernel_fmt {
+                        KernelFormat::OIHW => &k_input.shape[0],
+                        KernelFormat::HWIO => &k_input.shape[krank as usize - 1],
+                    };
+                    s.equals(&inputs[slot].shape[0], filter_o)?;
+                }
+                Ok(())
+            })?;
+        }
         s.given_2(&inputs[0].rank, &k_input.rank, move |s, irank, krank| {
             let input_c = if self.data_format == DataFormat::NHWC {
                 &inputs[0].shape[irank as usize - 1]
@@ -294,16 +412,41 @@ impl TypedOp for Conv {
     ) -> TractResult<Option<TypedModelPatch>> {
         let inputs = model.node_input_facts(node.id)?;
         if let Some(op) = self.to_unary(&*inputs)? {
+            if let Some(q) = self.per_channel_q_params(&*inputs)? {
+                let axis = self.data_format.shape(inputs[0].shape.to_tvec()).c_axis();
+                let mut patch = TypedModelPatch::default();
+                let wire = patch.tap_model(model, node.inputs[0])?;
+                let wire = patch.wire_node(&*node.name, op, &[wire])?[0];
+                let wire = patch.wire_node(
+                    format!("{}-requant", node.name),
+                    RequantizePerChannel::new(q, axis),
+                    &[wire],
+                )?[0];
+                patch.shunt_outside(OutletId::new(node.id, 0), wire)?;
+                return Ok(Some(patch));
+            }
             return Ok(Some(TypedModelPatch::single_unary_op(model, node, op)?));
         } else {
             Ok(None)
         }
     }
 
+    fn as_op_cost(&self) -> Option<&dyn OpCost> {
+        Some(self)
+    }
 
     typed_op_as_op!();
 }
 
+impl OpCost for Conv {
+    /// Costed as the ConvUnary it declutters to, kernel input included.
+    fn compute_cost(&self, inputs: &[&TypedFact]) -> TractResult<ComputeCost> {
+        let unary =
+            self.to_unary(&*inputs)?.ok_or_else(|| format!("Can not unarize conv: {:?}", self))?;
+        unary.compute_cost(&[inputs[0]])
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -459,4 +602,159 @@ mod test {
             .unwrap();
         assert_eq!(result, tvec!(rctensor3(&[[[2.0f32]]])));
     }
+
+    fn qlinear_conv() -> Conv {
+        Conv {
+            x_scale_input: Some(1),
+            x_zero_point_input: Some(2),
+            k_input: Some(3),
+            k_scale_input: Some(4),
+            k_zero_point_input: Some(5),
+            y_scale_input: Some(6),
+            y_zero_point_input: Some(7),
+            ..Conv::default()
+        }
+    }
+
+    // Three layers, OIHW, the first output channel of each one getting an
+    // outlier weight: with a single scale for the whole filter, the other
+    // channels only use a few quantization levels.
+    fn three_layers() -> Vec<ArrayD<f32>> {
+        [(3, 2, 3), (3, 3, 1), (2, 3, 2)]
+            .iter()
+            .enumerate()
+            .map(|(ix, &(o, i, k))| {
+                let mut w = Array4::from_shape_fn((o, i, k, k), |(o, i, h, w)| {
+                    let x = (((o * i + i) * k + h) * k + w) as f32;
+                    (0.7 * x + 0.4 * ix as f32).cos() * 0.08
+                });
+                w[(0, 0, 0, 0)] = 1.5;
+                w.into_dyn()
+            })
+            .collect()
+    }
+
+    fn input() -> ArrayD<f32> {
+        Array4::from_shape_fn((1, 2, 6, 6), |(_, c, h, w)| {
+            (1.3 * c as f32 + 0.7 * h as f32 + 0.4 * w as f32).sin()
+        })
+        .into_dyn()
+    }
+
+    fn quantize(x: &ArrayD<f32>, scales: &[f32]) -> ArrayD<i8> {
+        let per_row = x.len() / scales.len();
+        let values = x
+            .iter()
+            .enumerate()
+            .map(|(ix, &x)| (x / scales[ix / per_row]).round().max(-128.0).min(127.0) as i8)
+            .collect();
+        ArrayD::from_shape_vec(x.shape(), values).unwrap()
+    }
+
+    fn max_abs_scale(x: &ArrayD<f32>) -> f32 {
+        x.iter().fold(0.0f32, |m, x| m.max(x.abs())) / 127.0
+    }
+
+    // relative error of the quantized model output to the float one
+    fn run_quantized(per_channel: bool) -> TractResult<f32> {
+        let layers = three_layers();
+        let mut activations = vec![input()];
+        for w in &layers {
+            let x = activations.last().unwrap().clone().into_arc_tensor();
+            let y = Conv::default().eval(tvec!(x, w.clone().into_arc_tensor()))?;
+            activations.push(y[0].clone().into_tensor().into_array::<f32>()?);
+        }
+        let scales: Vec<f32> = activations.iter().map(max_abs_scale).collect();
+
+        let mut model = TypedModel::default();
+        let x = quantize(&activations[0], &[scales[0]]);
+        let mut wire = model.add_source("x", TypedFact::dt_shape(i8::datum_type(), x.shape())?)?;
+        for (ix, w) in layers.iter().enumerate() {
+            let (k_scale, k_zero_point) = if per_channel {
+                let scales: Vec<f32> =
+                    w.outer_iter().map(|c| max_abs_scale(&c.to_owned())).collect();
+                let zero_points = vec![0i8; scales.len()];
+                (tensor1(&scales), tensor1(&zero_points))
+            } else {
+                (tensor0(max_abs_scale(w)), tensor0(0i8))
+            };
+            let kernel = quantize(w, k_scale.as_slice::<f32>()?).into_tensor();
+            let consts = tvec!(
+                tensor0(scales[ix]),
+                tensor0(0i8),
+                kernel,
+                k_scale,
+                k_zero_point,
+                tensor0(scales[ix + 1]),
+                tensor0(0i8)
+            );
+            let mut inputs = tvec!(wire);
+            for (slot, t) in consts.into_iter().enumerate() {
+                inputs.push(model.add_const(format!("conv-{}-{}", ix, slot + 1), t)?);
+            }
+            wire = model.wire_node(format!("conv-{}", ix), qlinear_conv(), &inputs)?[0];
+        }
+        model.set_output_outlets(&[wire])?;
+
+        let plain = SimplePlan::new(model.clone())?.run(tvec!(x.clone().into_tensor()))?;
+        let optimized = SimplePlan::new(model.into_optimized()?)?.run(tvec!(x.into_tensor()))?;
+        assert_eq!(plain[0].datum_type(), i8::datum_type());
+        assert_eq!(plain, optimized);
+
+        let reference = activations.last().unwrap();
+        let found =
+            plain[0].cast_to::<f32>()?.into_owned().into_array::<f32>()? * *scales.last().unwrap();
+        // worst relative error over the output channels
+        let error = found
+            .axis_iter(Axis(1))
+            .zip(reference.axis_iter(Axis(1)))
+            .map(|(f, r)| {
+                (&f - &r).iter().map(|e| e * e).sum::<f32>().sqrt()
+                    / r.iter().map(|r| r * r).sum::<f32>().sqrt()
+            })
+            .fold(0.0f32, f32::max);
+        Ok(error)
+    }
+
+    #[cfg(feature = "nnpack")]
+    #[test]
+    fn algorithm_selectors_agree() -> TractResult<()> {
+        use crate::ops::cnn::conv::nnpack::{is_available, NnpackAlgorithm, NnpackConv};
+        let x = input().into_arc_tensor();
+        let kernel = Array4::from_shape_fn((3, 2, 3, 3), |(o, i, h, w)| {
+            ((o * 18 + i * 9 + h * 3 + w) as f32 * 0.37).sin()
+        });
+        let run = |algorithm| -> TractResult<(Option<NnpackAlgorithm>, Arc<Tensor>)> {
+            let mut model = TypedModel::default();
+            let wire = model.add_source("x", TypedFact::dt_shape(f32::datum_type(), x.shape())?)?;
+            let k = model.add_const("k", kernel.clone().into_tensor())?;
+            let op = Conv::default().padding(PaddingSpec::SameUpper).algorithm(algorithm);
+            let wire = model.wire_node("conv", op, &[wire, k])?;
+            model.set_output_outlets(&wire)?;
+            let model = model.into_optimized()?;
+            let selected =
+                model.nodes().iter().find_map(|n| n.op_as::<NnpackConv>()).map(|op| op.algorithm);
+            let plan = SimplePlan::new(model)?;
+            Ok((selected, plan.run(tvec!(x.as_ref().clone()))?.remove(0)))
+        };
+        let (selected, gemm) = run(ConvAlgorithmSelector::Gemm)?;
+        assert_eq!(selected, None);
+        let expected = |algorithm| if is_available() { Some(algorithm) } else { None };
+        let (selected, winograd) = run(ConvAlgorithmSelector::Winograd)?;
+        assert_eq!(selected, expected(NnpackAlgorithm::Winograd8x8));
+        winograd.close_enough(&gemm, true)?;
+        let (selected, fft) = run(ConvAlgorithmSelector::Fft)?;
+        assert_eq!(selected, expected(NnpackAlgorithm::Fft8x8));
+        fft.close_enough(&gemm, true)?;
+        run(ConvAlgorithmSelector::Auto)?.1.close_enough(&gemm, true)
+    }
+
+    #[test]
+    fn per_channel_beats_per_tensor_quantization() -> TractResult<()> {
+        let per_tensor = run_quantized(false)?;
+        let per_channel = run_quantized(true)?;
+        assert!(per_channel < 0.1, "per channel error: {}", per_channel);
+        assert!(per_channel * 2.0 < per_tensor, "{} vs {}", per_channel, per_tensor);
+        Ok(())
+    }
 }
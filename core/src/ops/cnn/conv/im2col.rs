@@ -76,6 +76,11 @@ impl<T: Copy + Datum + Zero> Im2Col<T> {
         &self.output_shape.shape
     }
 
+    /// Value of the patch elements falling in the padding.
+    pub fn pad_value(&self) -> T {
+        self.pad_value
+    }
+
     pub(super) fn im2col<'i>(&'i self, input: &'i ArrayViewD<'i, T>) -> TractResult<Tensor> {
         let mut packed = unsafe {
             Tensor::uninitialized_aligned::<T>(&*self.output_shape.shape, self.b_pack.alignment())?
@@ -1,8 +1,11 @@
 mod depth_wise;
 mod gen;
 mod im2col;
+#[cfg(feature = "nnpack")]
+mod nnpack;
 mod unary;
 
+pub use self::depth_wise::DepthWise;
 pub use self::gen::Conv;
 pub use self::im2col::Im2Col;
 pub use self::unary::ConvUnary;
@@ -19,6 +22,26 @@ impl Default for KernelFormat {
     }
 }
 
+/// Implementation choice for float convolutions.
+///
+/// `Winograd` and `Fft` require tract to be built with the `nnpack` feature,
+/// and a convolution NNPACK supports. Otherwise, as with `Gemm`, the
+/// convolution runs as im2col and a matrix product. `Auto` benchmarks the
+/// applicable variants when the model is optimized and keeps the fastest.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum ConvAlgorithmSelector {
+    Auto,
+    Gemm,
+    Winograd,
+    Fft,
+}
+
+impl Default for ConvAlgorithmSelector {
+    fn default() -> ConvAlgorithmSelector {
+        ConvAlgorithmSelector::Auto
+    }
+}
+
 impl KernelFormat {
     pub(super) fn h_axis(&self) -> usize {
         match self {
@@ -0,0 +1,326 @@
+//! Convolution through NNPACK fast algorithms.
+//!
+//! NNPACK is linked dynamically (`libnnpack`), and initialized on first use.
+//! When it can not be initialized, typically on unsupported hardware,
+//! convolutions stay on the default im2col and matrix multiplication path.
+use std::os::raw::c_void;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Once;
+use std::time::{Duration, Instant};
+
+use crate::internal::*;
+use crate::ops::cnn::conv::{ConvAlgorithmSelector, ConvUnary};
+use crate::ops::nn::DataFormat;
+
+mod ffi {
+    use std::os::raw::c_void;
+
+    pub const NNP_STATUS_SUCCESS: i32 = 0;
+
+    pub const NNP_CONVOLUTION_ALGORITHM_FT8X8: i32 = 1;
+    pub const NNP_CONVOLUTION_ALGORITHM_FT16X16: i32 = 2;
+    pub const NNP_CONVOLUTION_ALGORITHM_WT8X8: i32 = 3;
+
+    pub const NNP_CONVOLUTION_TRANSFORM_STRATEGY_COMPUTE: i32 = 1;
+
+    pub const NNP_ACTIVATION_IDENTITY: i32 = 0;
+
+    #[repr(C)]
+    pub struct nnp_size {
+        pub width: usize,
+        pub height: usize,
+    }
+
+    #[repr(C)]
+    pub struct nnp_padding {
+        pub top: usize,
+        pub right: usize,
+        pub bottom: usize,
+        pub left: usize,
+    }
+
+    #[link(name = "nnpack")]
+    extern "C" {
+        pub fn nnp_initialize() -> i32;
+        pub fn nnp_convolution_inference(
+            algorithm: i32,
+            transform_strategy: i32,
+            input_channels: usize,
+            output_channels: usize,
+            input_size: nnp_size,
+            input_padding: nnp_padding,
+            kernel_size: nnp_size,
+            output_subsampling: nnp_size,
+            input: *const f32,
+            kernel: *const f32,
+            bias: *const f32,
+            output: *mut f32,
+            workspace_buffer: *mut c_void,
+            workspace_size: *mut usize,
+            activation: i32,
+            activation_parameters: *const c_void,
+            threadpool: *mut c_void,
+            profile: *mut c_void,
+        ) -> i32;
+    }
+}
+
+static INIT: Once = Once::new();
+static AVAILABLE: AtomicBool = AtomicBool::new(false);
+
+/// Whether NNPACK could be initialized on this machine.
+pub fn is_available() -> bool {
+    INIT.call_once(|| {
+        let status = unsafe { ffi::nnp_initialize() };
+        if status != ffi::NNP_STATUS_SUCCESS {
+            warn!("NNPACK initialization failed (status {}), using default convolutions", status);
+        }
+        AVAILABLE.store(status == ffi::NNP_STATUS_SUCCESS, Ordering::SeqCst);
+    });
+    AVAILABLE.load(Ordering::SeqCst)
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum NnpackAlgorithm {
+    Winograd8x8,
+    Fft8x8,
+    Fft16x16,
+}
+
+impl NnpackAlgorithm {
+    fn code(&self) -> i32 {
+        match self {
+            NnpackAlgorithm::Winograd8x8 => ffi::NNP_CONVOLUTION_ALGORITHM_WT8X8,
+            NnpackAlgorithm::Fft8x8 => ffi::NNP_CONVOLUTION_ALGORITHM_FT8X8,
+            NnpackAlgorithm::Fft16x16 => ffi::NNP_CONVOLUTION_ALGORITHM_FT16X16,
+        }
+    }
+
+    /// The variants implementing a selector for a kernel, best first.
+    fn candidates(selector: ConvAlgorithmSelector, kernel: (usize, usize)) -> TVec<Self> {
+        let winograd = kernel == (3, 3);
+        let fft8 = kernel.0 <= 8 && kernel.1 <= 8;
+        let fft16 = kernel.0 <= 16 && kernel.1 <= 16;
+        let mut candidates = tvec!();
+        if winograd && selector != ConvAlgorithmSelector::Fft {
+            candidates.push(NnpackAlgorithm::Winograd8x8);
+        }
+        if selector != ConvAlgorithmSelector::Winograd {
+            if fft8 {
+                candidates.push(NnpackAlgorithm::Fft8x8);
+            }
+            if fft16 {
+                candidates.push(NnpackAlgorithm::Fft16x16);
+            }
+        }
+        candidates
+    }
+}
+
+/// A 2D float convolution, in CHW or NCHW, on NNPACK.
+#[derive(Debug, Clone)]
+pub struct NnpackConv {
+    pub algorithm: NnpackAlgorithm,
+    pub input_shape: TVec<usize>,
+    pub output_shape: TVec<usize>,
+    pub kernel_oihw: Arc<Tensor>,
+    pub bias: Vec<f32>,
+    /// (top, right, bottom, left)
+    pub padding: (usize, usize, usize, usize),
+}
+
+impl NnpackConv {
+    fn batch(&self) -> usize {
+        if self.input_shape.len() == 4 {
+            self.input_shape[0]
+        } else {
+            1
+        }
+    }
+}
+
+impl Op for NnpackConv {
+    fn name(&self) -> Cow<str> {
+        "Conv::Nnpack".into()
+    }
+
+    fn info(&self) -> TractResult<Vec<String>> {
+        Ok(vec![format!("{:?}", self.algorithm)])
+    }
+
+    op_as_typed_op!();
+    not_a_pulsed_op!();
+}
+
+impl StatelessOp for NnpackConv {
+    fn eval(&self, mut inputs: TVec<Arc<Tensor>>) -> TractResult<TVec<Arc<Tensor>>> {
+        let input = args_1!(inputs);
+        let input = input.as_slice::<f32>()?;
+        let mut output = unsafe { Tensor::uninitialized::<f32>(&*self.output_shape)? };
+        let kshape = self.kernel_oihw.shape();
+        let rank = self.input_shape.len();
+        let (ci, h, w) =
+            (self.input_shape[rank - 3], self.input_shape[rank - 2], self.input_shape[rank - 1]);
+        let image_len = input.len() / self.batch();
+        let output_len = output.len() / self.batch();
+        let kernel = self.kernel_oihw.as_slice::<f32>()?;
+        let out = output.as_slice_mut::<f32>()?;
+        for n in 0..self.batch() {
+            let status = unsafe {
+                ffi::nnp_convolution_inference(
+                    self.algorithm.code(),
+                    ffi::NNP_CONVOLUTION_TRANSFORM_STRATEGY_COMPUTE,
+                    ci,
+                    kshape[0],
+                    ffi::nnp_size { width: w, height: h },
+                    ffi::nnp_padding {
+                        top: self.padding.0,
+                        right: self.padding.1,
+                        bottom: self.padding.2,
+                        left: self.padding.3,
+                    },
+                    ffi::nnp_size { width: kshape[3], height: kshape[2] },
+                    ffi::nnp_size { width: 1, height: 1 },
+                    input[n * image_len..].as_ptr(),
+                    kernel.as_ptr(),
+                    self.bias.as_ptr(),
+                    out[n * output_len..].as_mut_ptr(),
+                    std::ptr::null_mut::<c_void>(),
+                    std::ptr::null_mut(),
+                    ffi::NNP_ACTIVATION_IDENTITY,
+                    std::ptr::null(),
+                    std::ptr::null_mut(),
+                    std::ptr::null_mut(),
+                )
+            };
+            if status != ffi::NNP_STATUS_SUCCESS {
+                bail!("NNPACK convolution ({:?}) failed with status {}", self.algorithm, status)
+            }
+        }
+        Ok(tvec!(output.into_arc_tensor()))
+    }
+}
+
+impl TypedOp for NnpackConv {
+    fn output_facts(&self, inputs: &[&TypedFact]) -> TractResult<TVec<TypedFact>> {
+        Ok(tvec!(TypedFact::dt_shape(inputs[0].datum_type, &*self.output_shape)?))
+    }
+
+    fn cost(&self, _inputs: &[&TypedFact]) -> TractResult<TVec<(Cost, TDim)>> {
+        let rank = self.output_shape.len();
+        let output_points = self.output_shape[rank - 2] * self.output_shape[rank - 1];
+        Ok(tvec!((
+            Cost::FMA(f32::datum_type()),
+            (self.batch() * output_points * self.kernel_oihw.len()).to_dim()
+        )))
+    }
+
+    typed_op_as_op!();
+}
+
+fn time(mut f: impl FnMut() -> TractResult<()>) -> TractResult<Duration> {
+    f()?;
+    let mut best = None;
+    for _ in 0..3 {
+        let start = Instant::now();
+        f()?;
+        let elapsed = start.elapsed();
+        if best.map(|b| elapsed < b).unwrap_or(true) {
+            best = Some(elapsed);
+        }
+    }
+    Ok(best.unwrap())
+}
+
+/// Pick the NNPACK implementation of a convolution, if it has one and its
+/// algorithm selector allows it.
+///
+/// With `Auto`, all applicable variants, including the default
+/// implementation, are run on the actual input shape and the fastest wins.
+pub fn for_conv(conv: &ConvUnary, input_full_shape: &[usize]) -> TractResult<Option<NnpackConv>> {
+    let (input_shape, patch, output_shape) = conv.pool_spec.compute_geo(input_full_shape);
+    if conv.algorithm == ConvAlgorithmSelector::Gemm
+        || conv.kernel.datum_type() != f32::datum_type()
+        || conv.q_params.is_some()
+        || conv.group != 1
+        || input_shape.hw_rank() != 2
+        || (input_shape.fmt != DataFormat::NCHW && input_shape.fmt != DataFormat::CHW)
+        || patch.spec.strides.iter().any(|&s| s != 1)
+        || patch.spec.dilations.iter().any(|&d| d != 1)
+    {
+        return Ok(None);
+    }
+    let kernel = (patch.spec.kernel_shape[0], patch.spec.kernel_shape[1]);
+    if kernel == (1, 1) {
+        return Ok(None);
+    }
+    let candidates = NnpackAlgorithm::candidates(conv.algorithm, kernel);
+    if candidates.len() == 0 || !is_available() {
+        return Ok(None);
+    }
+    let kernel_oihw = conv.kernel_as_group_o_ihw::<f32>()?.into_shape((
+        conv.output_channels(),
+        conv.input_channels(),
+        kernel.0,
+        kernel.1,
+    ))?;
+    let bias = if let Some(bias) = &conv.bias {
+        bias.cast_to::<f32>()?.as_slice::<f32>()?.to_vec()
+    } else {
+        vec![0.0; conv.output_channels()]
+    };
+    let op = |algorithm| NnpackConv {
+        algorithm,
+        input_shape: input_full_shape.into(),
+        output_shape: output_shape.shape.clone(),
+        kernel_oihw: kernel_oihw.clone().into_arc_tensor(),
+        bias: bias.clone(),
+        padding: (patch.pad_before[0], patch.pad_after[1], patch.pad_after[0], patch.pad_before[1]),
+    };
+    if conv.algorithm != ConvAlgorithmSelector::Auto {
+        return Ok(Some(op(candidates[0])));
+    }
+    let image = Tensor::from(ndarray::ArrayD::<f32>::zeros(input_full_shape)).into_arc_tensor();
+    let mut best: Option<(Duration, Option<NnpackConv>)> = None;
+    for candidate in candidates.into_iter().map(|c| Some(op(c))).chain(std::iter::once(None)) {
+        let elapsed = if let Some(op) = &candidate {
+            time(|| op.eval(tvec!(image.clone())).map(|_| ()))?
+        } else {
+            let mut model = TypedModel::default();
+            let wire = model
+                .add_source("source", TypedFact::dt_shape(f32::datum_type(), input_full_shape)?)?;
+            let direct = (0..2).all(|ax| conv.pool_spec.padding.valid_dim(ax));
+            let wire = unsafe { conv.wire_as_im2col_pair(&mut model, "gemm", wire, direct)? };
+            model.set_output_outlets(&[wire])?;
+            let plan = SimplePlan::new(model)?;
+            time(|| plan.run(tvec!(image.as_ref().clone())).map(|_| ()))?
+        };
+        debug!(
+            "Conv {:?}: {:?} in {:?}",
+            conv.kernel.shape(),
+            candidate.as_ref().map(|c| c.algorithm),
+            elapsed
+        );
+        if best.as_ref().map(|b| elapsed < b.0).unwrap_or(true) {
+            best = Some((elapsed, candidate));
+        }
+    }
+    Ok(best.and_then(|b| b.1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use NnpackAlgorithm::*;
+
+    #[test]
+    fn candidates() {
+        use ConvAlgorithmSelector::*;
+        assert_eq!(&*NnpackAlgorithm::candidates(Auto, (3, 3)), &[Winograd8x8, Fft8x8, Fft16x16]);
+        assert_eq!(&*NnpackAlgorithm::candidates(Winograd, (3, 3)), &[Winograd8x8]);
+        assert_eq!(&*NnpackAlgorithm::candidates(Fft, (3, 3)), &[Fft8x8, Fft16x16]);
+        assert_eq!(&*NnpackAlgorithm::candidates(Winograd, (5, 5)), &[]);
+        assert_eq!(&*NnpackAlgorithm::candidates(Auto, (11, 3)), &[Fft16x16]);
+        assert_eq!(&*NnpackAlgorithm::candidates(Fft, (17, 3)), &[]);
+    }
+}
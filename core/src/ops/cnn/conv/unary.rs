@@ -12,8 +12,9 @@ use super::depth_wise::DepthWise;
 use super::im2col::Im2Col;
 use super::Conv;
 use crate::ops::array::TypedReshape;
-use crate::ops::cnn::conv::KernelFormat;
+use crate::ops::cnn::conv::{ConvAlgorithmSelector, KernelFormat};
 use crate::ops::cnn::PoolSpec;
+use crate::ops::cost::{self, ComputeCost, OpCost};
 use crate::ops::matmul;
 use crate::ops::matmul::mmm_wrapper::MMMWrapper;
 use crate::ops::nn::DataFormat;
@@ -34,6 +35,7 @@ pub struct ConvUnary {
 
     pub bias: Option<Arc<Tensor>>,
     pub q_params: Option<QParams>,
+    pub algorithm: ConvAlgorithmSelector,
 }
 
 impl ConvUnary {
@@ -66,18 +68,19 @@ impl ConvUnary {
             group,
             bias,
             q_params,
+            algorithm: conv.algorithm,
         };
         Ok(unary)
     }
 
-    fn input_channels(&self) -> usize {
+    pub(super) fn input_channels(&self) -> usize {
         match self.kernel_fmt {
             KernelFormat::OIHW => self.kernel.shape()[1],
             KernelFormat::HWIO => self.kernel.shape()[self.kernel.shape().len() - 2],
         }
     }
 
-    fn output_channels(&self) -> usize {
+    pub(super) fn output_channels(&self) -> usize {
         let kshape = self.kernel.shape();
         match self.kernel_fmt {
             KernelFormat::OIHW => kshape[0],
@@ -85,7 +88,7 @@ impl ConvUnary {
         }
     }
 
-    fn kernel_as_group_o_ihw<T: Datum>(&self) -> TractResult<Array3<T>> {
+    pub(super) fn kernel_as_group_o_ihw<T: Datum>(&self) -> TractResult<Array3<T>> {
         let kernel = self.kernel.to_array_view::<T>()?;
         let final_shape = (
             self.group,
@@ -173,6 +176,11 @@ impl ConvUnary {
                 MMMWrapper::Plain((tract_linalg::ops().smmm)(m, k, n))
             });
         } else if (a, b) == (u8::datum_type(), u8::datum_type()) {
+            if self.q_params.as_ref().map(|q| q.c_datum_type) == Some(u8::datum_type()) {
+                return self.wire_as_im2col_pair_t(model, name, wire, direct, &|m, k, n| {
+                    MMMWrapper::Quant((tract_linalg::ops().qmmm_u8_u8)(m, k, n))
+                });
+            }
             return self.wire_as_im2col_pair_t(model, name, wire, direct, &|m, k, n| {
                 MMMWrapper::Quant((tract_linalg::ops().qmmm_u8_i32)(m, k, n))
             });
@@ -182,6 +190,10 @@ impl ConvUnary {
                     return self.wire_as_im2col_pair_t(model, name, wire, direct, &|m, k, n| {
                         MMMWrapper::Quant((tract_linalg::ops().qmmm_i8_i8)(m, k, n))
                     });
+                } else if q.c_datum_type == i32::datum_type() {
+                    return self.wire_as_im2col_pair_t(model, name, wire, direct, &|m, k, n| {
+                        MMMWrapper::Quant((tract_linalg::ops().qmmm_i8_i32)(m, k, n))
+                    });
                 }
             } else {
                 return self.wire_as_im2col_pair_t(model, name, wire, direct, &|m, k, n| {
@@ -285,6 +297,9 @@ impl ConvUnary {
             None
         };
 
+        let packed_as = self.kernel_as_packed_as(&mmm.as_mmm().a_pack())?;
+        let mut fused_ops = self.bias_as_non_linear()?;
+        mmm.precompute_a_terms(&packed_as, &mut fused_ops)?;
         wire = model.wire_node(
             format!("{}-matmatmul", name),
             matmul::phy::MatMatMulUnaryFinite {
@@ -292,8 +307,8 @@ impl ConvUnary {
                 bc_c_shape: output_shape.shape.clone(),
                 c_fact: TypedFact::dt_shape(TC::datum_type(), &*output_shape.shape)?,
                 c_prefix_dim_and_stride,
-                packed_as: self.kernel_as_packed_as(&mmm.as_mmm().a_pack())?,
-                fused_ops: self.bias_as_non_linear()?,
+                packed_as,
+                fused_ops,
                 mmm,
             },
             &[wire],
@@ -479,7 +494,11 @@ impl StatelessOp for ConvUnary {
 
 impl TypedOp for ConvUnary {
     fn output_facts(&self, inputs: &[&TypedFact]) -> TractResult<TVec<TypedFact>> {
-        self.pool_spec.output_facts(inputs)
+        let mut facts = self.pool_spec.output_facts(inputs)?;
+        if let Some(q) = &self.q_params {
+            facts[0].datum_type = q.c_datum_type;
+        }
+        Ok(facts)
     }
 
     fn invariants(&self, model: &TypedModel, node: &TypedNode) -> TractResult<Invariants> {
@@ -538,6 +557,10 @@ impl TypedOp for ConvUnary {
         )))
     }
 
+    fn as_op_cost(&self) -> Option<&dyn OpCost> {
+        Some(self)
+    }
+
     fn dispose_dummy_axis(
         &self,
         model: &TypedModel,
@@ -589,6 +612,7 @@ impl TypedOp for ConvUnary {
             group: self.group,
             bias: self.bias.clone(),
             q_params: self.q_params.clone(),
+            algorithm: self.algorithm,
         };
         Ok(Some(Box::new(new_op)))
     }
@@ -615,6 +639,12 @@ impl TypedOp for ConvUnary {
         let spatial_rank = input_shape.hw_rank();
         let kernel_spatial_shape = &self.kernel.shape()[self.kernel_fmt.h_axis()..][..spatial_rank];
         if let Some(shape) = input_fact.shape.as_finite() {
+            #[cfg(feature = "nnpack")]
+            {
+                if let Some(op) = super::nnpack::for_conv(self, &shape)? {
+                    return Ok(Some(TypedModelPatch::single_unary_op(model, node, op)?));
+                }
+            }
             unsafe {
                 let dt = input_fact.datum_type;
                 if kernel_spatial_shape.iter().product::<usize>() == 1
@@ -704,6 +734,18 @@ impl TypedOp for ConvUnary {
     typed_op_as_op!();
 }
 
+impl OpCost for ConvUnary {
+    fn compute_cost(&self, inputs: &[&TypedFact]) -> TractResult<ComputeCost> {
+        let held = |t: &Tensor| (t.len() * t.datum_type().size_of()) as u64;
+        Ok(ComputeCost {
+            flops: cost::hinted_flops(self, inputs)?,
+            memory_bytes: cost::io_bytes(self, inputs)?
+                + held(&self.kernel)
+                + self.bias.as_ref().map(|b| held(b)).unwrap_or(0),
+        })
+    }
+}
+
 impl PulsedOp for ConvUnary {
     fn pulsed_output_facts(&self, inputs: &[&PulsedFact]) -> TractResult<TVec<PulsedFact>> {
         self.pool_spec.pulsed_output_facts(inputs)
@@ -8,8 +8,8 @@ use crate::ops::nn::DataShape;
 
 #[derive(Debug, Clone, new, Default)]
 pub struct MaxPool {
-    pool_spec: PoolSpec,
-    with_index_outputs: Option<DatumType>,
+    pub pool_spec: PoolSpec,
+    pub with_index_outputs: Option<DatumType>,
 }
 
 impl MaxPool {
@@ -130,6 +130,25 @@ pub struct MaxPoolFixed<T: Datum + Float> {
     _casper: PhantomData<T>,
 }
 
+impl<T: Datum + Float> MaxPoolFixed<T> {
+    pub fn patch(&self) -> &Patch {
+        &self.patch
+    }
+
+    pub fn input_shape(&self) -> &DataShape {
+        &self.input_shape
+    }
+
+    pub fn output_shape(&self) -> &DataShape {
+        &self.output_shape
+    }
+
+    /// Datum type of the indices output, if the op has one.
+    pub fn with_index_outputs(&self) -> Option<DatumType> {
+        self.with_index_outputs
+    }
+}
+
 impl<T: Datum + Float> Op for MaxPoolFixed<T> {
     fn name(&self) -> Cow<str> {
         format!("MaxPool::Fixed<{:?}>", T::datum_type()).into()
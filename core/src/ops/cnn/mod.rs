@@ -6,9 +6,9 @@ mod patch_axis;
 mod patches;
 pub mod pools;
 
-pub use self::avgpool::AvgPool;
-pub use self::conv::{Conv, ConvUnary, KernelFormat};
-pub use self::maxpool::MaxPool;
+pub use self::avgpool::{AvgPool, AvgPoolFixed};
+pub use self::conv::{Conv, ConvAlgorithmSelector, ConvUnary, KernelFormat};
+pub use self::maxpool::{MaxPool, MaxPoolFixed};
 pub use self::padding::PaddingSpec;
 pub use self::patch_axis::PatchAxis;
 pub use self::patches::{Patch, PatchSpec};
@@ -0,0 +1,306 @@
+use crate::internal::*;
+
+/// Conditional execution of one of two nested models.
+///
+/// Input #0 is a single boolean condition. The other inputs are fed, in
+/// order, to the inputs of the selected branch. Both branches must have the
+/// same number of outputs, with matching types and shapes.
+#[derive(Debug, Clone, Default)]
+pub struct If {
+    pub then_body: TypedModel,
+    pub else_body: TypedModel,
+    decluttered: bool,
+}
+
+impl If {
+    pub fn new(then_body: TypedModel, else_body: TypedModel) -> If {
+        If { then_body, else_body, decluttered: false }
+    }
+
+    pub fn to_codegen_op(&self) -> TractResult<IfCodegen> {
+        trace!("Optimizing(Codegen) branches");
+        let then_plan = SimplePlan::new(self.then_body.clone().into_optimized()?)?;
+        let else_plan = SimplePlan::new(self.else_body.clone().into_optimized()?)?;
+        Ok(IfCodegen { then_plan: Arc::new(then_plan), else_plan: Arc::new(else_plan) })
+    }
+
+    fn declutter_bodies(
+        &self,
+        model: &TypedModel,
+        node: &TypedNode,
+    ) -> TractResult<Option<TypedModelPatch>> {
+        if !self.decluttered {
+            let new = If {
+                then_body: self.then_body.clone().declutter()?,
+                else_body: self.else_body.clone().declutter()?,
+                decluttered: true,
+            };
+            return Ok(Some(TypedModelPatch::replace_single_op(model, node, &node.inputs, new)?));
+        }
+        Ok(None)
+    }
+}
+
+fn branch_inputs(name: &str, body: &TypedModel, inputs: &[&TypedFact]) -> TractResult<()> {
+    if body.input_outlets()?.len() != inputs.len() {
+        bail!(
+            "If {} branch expects {} inputs, got {}",
+            name,
+            body.input_outlets()?.len(),
+            inputs.len()
+        )
+    }
+    for (ix, input) in inputs.iter().enumerate() {
+        let fact = body.input_fact(ix)?;
+        if fact.datum_type != input.datum_type || fact.shape != input.shape {
+            bail!("If {} branch input #{} is {:?}, got {:?}", name, ix, fact, input)
+        }
+    }
+    Ok(())
+}
+
+impl Op for If {
+    fn name(&self) -> Cow<str> {
+        "If".into()
+    }
+
+    fn nested_models(&self) -> Vec<(Cow<str>, &dyn Model, Vec<String>, Vec<String>)> {
+        vec![
+            ("then".into(), &self.then_body, vec![], vec![]),
+            ("else".into(), &self.else_body, vec![], vec![]),
+        ]
+    }
+
+    op_as_typed_op!();
+    not_a_pulsed_op!();
+}
+
+impl StatefullOp for If {
+    fn state(
+        &self,
+        session: &mut SessionState,
+        node_id: usize,
+    ) -> TractResult<Option<Box<dyn OpState>>> {
+        self.to_codegen_op()?.state(session, node_id)
+    }
+}
+
+impl TypedOp for If {
+    typed_op_as_op!();
+
+    fn output_facts(&self, inputs: &[&TypedFact]) -> TractResult<TVec<TypedFact>> {
+        if inputs.len() == 0 {
+            bail!("If expects a condition input")
+        }
+        if inputs[0].datum_type != bool::datum_type()
+            || inputs[0].shape.iter().product::<TDim>() != 1.to_dim()
+        {
+            bail!("If condition must be a single boolean, got {:?}", inputs[0])
+        }
+        branch_inputs("then", &self.then_body, &inputs[1..])?;
+        branch_inputs("else", &self.else_body, &inputs[1..])?;
+        let then_outputs = self.then_body.output_outlets()?.len();
+        let else_outputs = self.else_body.output_outlets()?.len();
+        if then_outputs != else_outputs {
+            bail!(
+                "If branches must have the same number of outputs, got {} and {}",
+                then_outputs,
+                else_outputs
+            )
+        }
+        (0..then_outputs)
+            .map(|ix| {
+                let then_fact = self.then_body.output_fact(ix)?;
+                let else_fact = self.else_body.output_fact(ix)?;
+                if then_fact.datum_type != else_fact.datum_type
+                    || then_fact.shape != else_fact.shape
+                {
+                    bail!(
+                        "If branches mismatch on output #{}: {:?} and {:?}",
+                        ix,
+                        then_fact,
+                        else_fact
+                    )
+                }
+                TypedFact::dt_shape(then_fact.datum_type, then_fact.shape.clone())
+            })
+            .collect()
+    }
+
+    fn declutter(
+        &self,
+        model: &TypedModel,
+        node: &TypedNode,
+    ) -> TractResult<Option<TypedModelPatch>> {
+        self.declutter_bodies(model, node)
+    }
+
+    fn codegen(
+        &self,
+        model: &TypedModel,
+        node: &TypedNode,
+    ) -> TractResult<Option<TypedModelPatch>> {
+        Ok(Some(TypedModelPatch::replace_single_op(
+            &model,
+            node,
+            &node.inputs,
+            self.to_codegen_op()?,
+        )?))
+    }
+
+    fn nested_model_multipliers(&self, _inputs: &[&TypedFact]) -> Vec<(Cow<str>, f32)> {
+        vec![("then".into(), 1.0), ("else".into(), 1.0)]
+    }
+}
+
+/// If, with both branches optimized and planned.
+#[derive(Debug, Clone)]
+pub struct IfCodegen {
+    pub then_plan: Arc<TypedSimplePlan<TypedModel>>,
+    pub else_plan: Arc<TypedSimplePlan<TypedModel>>,
+}
+
+impl Op for IfCodegen {
+    fn name(&self) -> Cow<str> {
+        "IfCodegen".into()
+    }
+
+    fn nested_models(&self) -> Vec<(Cow<str>, &dyn Model, Vec<String>, Vec<String>)> {
+        vec![
+            ("then".into(), self.then_plan.model(), vec![], vec![]),
+            ("else".into(), self.else_plan.model(), vec![], vec![]),
+        ]
+    }
+
+    op_as_typed_op!();
+    not_a_pulsed_op!();
+}
+
+impl StatefullOp for IfCodegen {
+    fn state(
+        &self,
+        _session: &mut SessionState,
+        _node_id: usize,
+    ) -> TractResult<Option<Box<dyn OpState>>> {
+        Ok(Some(Box::new(State {
+            then_state: TypedSimpleState::new(Arc::clone(&self.then_plan))?,
+            else_state: TypedSimpleState::new(Arc::clone(&self.else_plan))?,
+        })))
+    }
+}
+
+impl TypedOp for IfCodegen {
+    typed_op_as_op!();
+
+    fn output_facts(&self, _inputs: &[&TypedFact]) -> TractResult<TVec<TypedFact>> {
+        (0..self.then_plan.model().output_outlets()?.len())
+            .map(|ix| {
+                let fact = self.then_plan.model().output_fact(ix)?;
+                TypedFact::dt_shape(fact.datum_type, fact.shape.clone())
+            })
+            .collect()
+    }
+
+    fn nested_model_multipliers(&self, _inputs: &[&TypedFact]) -> Vec<(Cow<str>, f32)> {
+        vec![("then".into(), 1.0), ("else".into(), 1.0)]
+    }
+}
+
+/// Each branch runs in its own state, so nothing leaks from one branch to
+/// the other, or to the outer session.
+#[derive(Debug, Clone)]
+struct State {
+    then_state: TypedSimpleState<TypedModel, Arc<TypedSimplePlan<TypedModel>>>,
+    else_state: TypedSimpleState<TypedModel, Arc<TypedSimplePlan<TypedModel>>>,
+}
+
+impl OpState for State {
+    fn eval(
+        &mut self,
+        _session: &mut SessionState,
+        _op: &dyn Op,
+        mut inputs: TVec<Arc<Tensor>>,
+    ) -> TractResult<TVec<Arc<Tensor>>> {
+        let cond = inputs.remove(0);
+        let cond = *cond.as_slice::<bool>()?.get(0).ok_or("If condition is empty")?;
+        let inputs = inputs.into_iter().map(|t| t.into_tensor()).collect();
+        if cond {
+            self.then_state.run(inputs).chain_err(|| "Evaluating then branch")
+        } else {
+            self.else_state.run(inputs).chain_err(|| "Evaluating else branch")
+        }
+    }
+
+    fn reset(&mut self) -> TractResult<()> {
+        self.then_state.reset_state()?;
+        self.else_state.reset_state()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ops::{logic, math};
+
+    fn scalar() -> TypedFact {
+        TypedFact::dt_shape(f32::datum_type(), [0usize; 0].as_ref()).unwrap()
+    }
+
+    fn identity() -> TractResult<TypedModel> {
+        let mut model = TypedModel::default();
+        let x = model.add_source("x", scalar())?;
+        model.set_output_outlets(&[x])?;
+        Ok(model)
+    }
+
+    fn outer(then_body: TypedModel, else_body: TypedModel) -> TractResult<TypedModel> {
+        let mut model = TypedModel::default();
+        let x = model.add_source("x", scalar())?;
+        let cond = model.wire_node("cond", logic::lesser::unary(rctensor0(0f32)), &[x])?[0];
+        let y = model.wire_node("if", If::new(then_body, else_body), &[cond, x])?[0];
+        model.set_output_outlets(&[y])?;
+        Ok(model)
+    }
+
+    #[test]
+    fn abs() -> TractResult<()> {
+        let mut else_body = TypedModel::default();
+        let x = else_body.add_source("x", scalar())?;
+        let neg = else_body.wire_node("neg", math::neg(), &[x])?[0];
+        else_body.set_output_outlets(&[neg])?;
+        let model = outer(identity()?, else_body)?;
+        for model in &[model.clone(), model.into_optimized()?] {
+            let plan = SimplePlan::new(model)?;
+            assert_eq!(plan.run(tvec!(tensor0(3f32)))?[0], rctensor0(3f32));
+            assert_eq!(plan.run(tvec!(tensor0(-2f32)))?[0], rctensor0(2f32));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn const_in_branch() -> TractResult<()> {
+        let mut else_body = TypedModel::default();
+        let x = else_body.add_source("x", scalar())?;
+        let ten = else_body.add_const("ten", rctensor0(10f32))?;
+        let y = else_body.wire_node("add", math::add::bin(), &[x, ten])?[0];
+        else_body.set_output_outlets(&[y])?;
+        let model = outer(identity()?, else_body)?.into_optimized()?;
+        let plan = SimplePlan::new(&model)?;
+        let mut state = SimpleState::new(&plan)?;
+        assert_eq!(state.run(tvec!(tensor0(-2f32)))?[0], rctensor0(8f32));
+        assert_eq!(state.run(tvec!(tensor0(1f32)))?[0], rctensor0(1f32));
+        assert_eq!(state.run(tvec!(tensor0(-3f32)))?[0], rctensor0(7f32));
+        Ok(())
+    }
+
+    #[test]
+    fn mismatched_branches() -> TractResult<()> {
+        let mut else_body = TypedModel::default();
+        let x = else_body.add_source("x", scalar())?;
+        let y =
+            else_body.wire_node("cast", crate::ops::cast::Cast::new(i32::datum_type()), &[x])?[0];
+        else_body.set_output_outlets(&[y])?;
+        assert!(outer(identity()?, else_body).is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,288 @@
+use crate::internal::*;
+
+use super::{If, Loop};
+
+fn scalar(dt: DatumType) -> InferenceFact {
+    InferenceFact::dt_shape(dt, ShapeFact::closed(tvec!()))
+}
+
+fn unify(outer: &mut InferenceFact, inner: &mut InferenceFact) -> TractResult<bool> {
+    outer.unify_with_mut(inner)
+}
+
+/// If, as built by the framework parsers, before the branches are typed.
+///
+/// Both branches take the inputs of the op but the condition, in order.
+#[derive(Debug, Clone, new, Default)]
+pub struct InferenceIf {
+    pub then_body: InferenceModel,
+    pub else_body: InferenceModel,
+}
+
+impl InferenceIf {
+    pub fn to_typed_if(&self) -> TractResult<If> {
+        Ok(If::new(self.then_body.clone().into_typed()?, self.else_body.clone().into_typed()?))
+    }
+}
+
+impl Op for InferenceIf {
+    fn name(&self) -> Cow<str> {
+        "If::Inference".into()
+    }
+
+    fn nested_models(&self) -> Vec<(Cow<str>, &dyn Model, Vec<String>, Vec<String>)> {
+        vec![
+            ("then".into(), &self.then_body, vec![], vec![]),
+            ("else".into(), &self.else_body, vec![], vec![]),
+        ]
+    }
+
+    not_a_typed_op!();
+    not_a_pulsed_op!();
+}
+
+impl StatefullOp for InferenceIf {
+    fn state(
+        &self,
+        session: &mut SessionState,
+        node_id: usize,
+    ) -> TractResult<Option<Box<dyn OpState>>> {
+        self.to_typed_if()?.state(session, node_id)
+    }
+}
+
+impl InferenceOp for InferenceIf {
+    fn infer_facts(
+        &mut self,
+        inputs: TVec<&InferenceFact>,
+        outputs: TVec<&InferenceFact>,
+        _observed: TVec<&InferenceFact>,
+    ) -> TractResult<(TVec<InferenceFact>, TVec<InferenceFact>, TVec<InferenceFact>)> {
+        if inputs.len() == 0 {
+            bail!("If expects a condition input")
+        }
+        for body in &[&self.then_body, &self.else_body] {
+            if body.input_outlets()?.len() != inputs.len() - 1 {
+                bail!(
+                    "If branch expects {} inputs, got {}",
+                    body.input_outlets()?.len(),
+                    inputs.len() - 1
+                )
+            }
+            if body.output_outlets()?.len() != outputs.len() {
+                bail!(
+                    "If branch has {} outputs, op has {}",
+                    body.output_outlets()?.len(),
+                    outputs.len()
+                )
+            }
+        }
+        let mut inputs: TVec<InferenceFact> = inputs.into_iter().cloned().collect();
+        let mut outputs: TVec<InferenceFact> = outputs.into_iter().cloned().collect();
+        inputs[0].unify_with(&scalar(bool::datum_type()))?;
+        loop {
+            let mut changed = false;
+            for body in &mut [&mut self.then_body, &mut self.else_body] {
+                for (ix, input) in inputs[1..].iter_mut().enumerate() {
+                    changed |= unify(input, body.input_fact_mut(ix)?)?;
+                }
+                for (ix, output) in outputs.iter_mut().enumerate() {
+                    changed |= unify(output, body.output_fact_mut(ix)?)?;
+                }
+                changed |= body.analyse(false).chain_err(|| "Analysing If branch")?;
+            }
+            if !changed {
+                break;
+            }
+        }
+        Ok((inputs, outputs, tvec!()))
+    }
+
+    fn to_typed(
+        &self,
+        _source: &InferenceModel,
+        node: &InferenceNode,
+        target: &mut TypedModel,
+        mapping: &HashMap<OutletId, OutletId>,
+    ) -> TractResult<TVec<OutletId>> {
+        let inputs = node.inputs.iter().map(|m| mapping[m]).collect::<TVec<_>>();
+        target.wire_node(&*node.name, self.to_typed_if()?, &*inputs)
+    }
+
+    fn nboutputs(&self) -> TractResult<usize> {
+        Ok(self.then_body.output_outlets()?.len())
+    }
+
+    inference_op_as_op!();
+}
+
+/// Loop, as built by the framework parsers, before the body is typed.
+///
+/// The body has the `closures` values it uses from the outer model as its
+/// last inputs, and they are the last inputs of the op. The typed Loop
+/// carries them, unchanged, from one iteration to the next.
+#[derive(Debug, Clone, new, Default)]
+pub struct InferenceLoop {
+    pub body: InferenceModel,
+    pub has_trip_count: bool,
+    pub has_cond: bool,
+    pub closures: usize,
+}
+
+impl InferenceLoop {
+    fn optional_inputs(&self) -> usize {
+        self.has_trip_count as usize + self.has_cond as usize
+    }
+
+    fn carried(&self) -> TractResult<usize> {
+        Ok(self.body.input_outlets()?.len() - 2 - self.closures)
+    }
+
+    pub fn to_typed_loop(&self) -> TractResult<Loop> {
+        let mut body = self.body.clone().into_typed()?;
+        let carried = self.carried()?;
+        let outputs = body.output_outlets()?.to_vec();
+        let mut carried_outputs = outputs[..carried + 1].to_vec();
+        carried_outputs.extend(body.input_outlets()?[carried + 2..].iter().cloned());
+        carried_outputs.extend(outputs[carried + 1..].iter().cloned());
+        body.set_output_outlets(&carried_outputs)?;
+        Ok(Loop::new(body, self.has_trip_count, self.has_cond))
+    }
+}
+
+impl Op for InferenceLoop {
+    fn name(&self) -> Cow<str> {
+        "Loop::Inference".into()
+    }
+
+    fn nested_models(&self) -> Vec<(Cow<str>, &dyn Model, Vec<String>, Vec<String>)> {
+        vec![("body".into(), &self.body, vec![], vec![])]
+    }
+
+    not_a_typed_op!();
+    not_a_pulsed_op!();
+}
+
+impl StatefullOp for InferenceLoop {
+    fn state(
+        &self,
+        session: &mut SessionState,
+        node_id: usize,
+    ) -> TractResult<Option<Box<dyn OpState>>> {
+        let state = self.to_typed_loop()?.state(session, node_id)?.ok_or("Loop without state")?;
+        Ok(Some(Box::new(ClosuresState {
+            state,
+            carried: self.carried()?,
+            closures: self.closures,
+        })))
+    }
+}
+
+/// Drops the closures carried by the typed Loop from its outputs.
+#[derive(Debug, Clone)]
+struct ClosuresState {
+    state: Box<dyn OpState>,
+    carried: usize,
+    closures: usize,
+}
+
+impl OpState for ClosuresState {
+    fn eval(
+        &mut self,
+        session: &mut SessionState,
+        op: &dyn Op,
+        inputs: TVec<Arc<Tensor>>,
+    ) -> TractResult<TVec<Arc<Tensor>>> {
+        let mut outputs = self.state.eval(session, op, inputs)?;
+        outputs.drain(self.carried..self.carried + self.closures);
+        Ok(outputs)
+    }
+
+    fn reset(&mut self) -> TractResult<()> {
+        self.state.reset()
+    }
+}
+
+impl InferenceOp for InferenceLoop {
+    fn infer_facts(
+        &mut self,
+        inputs: TVec<&InferenceFact>,
+        outputs: TVec<&InferenceFact>,
+        _observed: TVec<&InferenceFact>,
+    ) -> TractResult<(TVec<InferenceFact>, TVec<InferenceFact>, TVec<InferenceFact>)> {
+        let optional = self.optional_inputs();
+        let carried = self.carried()?;
+        let body_outputs = self.body.output_outlets()?.len();
+        if inputs.len() != optional + carried + self.closures {
+            bail!(
+                "Loop body expects {} inputs, got {}",
+                optional + carried + self.closures,
+                inputs.len()
+            )
+        }
+        if body_outputs < carried + 1 || outputs.len() != body_outputs - 1 {
+            bail!("Loop body with {} outputs can not carry {} variables", body_outputs, carried)
+        }
+        let mut inputs: TVec<InferenceFact> = inputs.into_iter().cloned().collect();
+        let mut outputs: TVec<InferenceFact> = outputs.into_iter().cloned().collect();
+        if self.has_trip_count {
+            inputs[0].unify_with(&scalar(i64::datum_type()))?;
+        }
+        if self.has_cond {
+            inputs[self.has_trip_count as usize].unify_with(&scalar(bool::datum_type()))?;
+        }
+        self.body.input_fact_mut(0)?.unify_with(&scalar(i64::datum_type()))?;
+        self.body.input_fact_mut(1)?.unify_with(&scalar(bool::datum_type()))?;
+        self.body.output_fact_mut(0)?.unify_with(&scalar(bool::datum_type()))?;
+        loop {
+            let mut changed = false;
+            for ix in 0..carried {
+                let mut facts = self.body.outlets_fact_mut(&[
+                    self.body.input_outlets()?[ix + 2],
+                    self.body.output_outlets()?[ix + 1],
+                ])?;
+                facts.push(&mut inputs[optional + ix]);
+                facts.push(&mut outputs[ix]);
+                changed |= Factoid::unify_all(&mut *facts)?;
+            }
+            for ix in 0..self.closures {
+                let input = &mut inputs[optional + carried + ix];
+                changed |= unify(input, self.body.input_fact_mut(ix + 2 + carried)?)?;
+            }
+            for ix in carried..outputs.len() {
+                let inner = self.body.output_fact(ix + 1)?.clone();
+                let outer = &mut outputs[ix];
+                changed |= outer.datum_type.unify_with(&inner.datum_type)?;
+                if !inner.shape.is_open() {
+                    let dims = std::iter::once(GenericFact::Any).chain(inner.shape.dims());
+                    changed |= outer.shape.unify_with(&ShapeFact::closed(dims.collect()))?;
+                }
+            }
+            changed |= self.body.analyse(false).chain_err(|| "Analysing Loop body")?;
+            if !changed {
+                break;
+            }
+        }
+        Ok((inputs, outputs, tvec!()))
+    }
+
+    fn to_typed(
+        &self,
+        _source: &InferenceModel,
+        node: &InferenceNode,
+        target: &mut TypedModel,
+        mapping: &HashMap<OutletId, OutletId>,
+    ) -> TractResult<TVec<OutletId>> {
+        let inputs = node.inputs.iter().map(|m| mapping[m]).collect::<TVec<_>>();
+        let carried = self.carried()?;
+        let mut outputs = target.wire_node(&*node.name, self.to_typed_loop()?, &*inputs)?;
+        outputs.drain(carried..carried + self.closures);
+        Ok(outputs)
+    }
+
+    fn nboutputs(&self) -> TractResult<usize> {
+        Ok(self.body.output_outlets()?.len() - 1)
+    }
+
+    inference_op_as_op!();
+}
@@ -0,0 +1,393 @@
+use crate::internal::*;
+use ndarray::*;
+
+/// Repeated execution of a nested model, with carried state.
+///
+/// The op inputs are the trip count (an optional i64 scalar), the condition
+/// (an optional boolean scalar), then the initial values of the carried
+/// variables.
+///
+/// The body inputs are the iteration number (an i64 scalar), the condition,
+/// then the carried variables. The body outputs are the new condition, the
+/// updated carried variables, then the scan outputs.
+///
+/// The op outputs are the final carried variables, then the scan outputs,
+/// stacked along a new leading axis. When the iteration count is not known
+/// beforehand, this axis is the `iters` symbol, distinct for each loop.
+#[derive(Debug, Clone)]
+pub struct Loop {
+    pub body: TypedModel,
+    pub has_trip_count: bool,
+    pub has_cond: bool,
+    pub iters: Symbol,
+    decluttered: bool,
+}
+
+impl Loop {
+    pub fn new(body: TypedModel, has_trip_count: bool, has_cond: bool) -> Loop {
+        Loop { body, has_trip_count, has_cond, iters: Symbol::fresh("L"), decluttered: false }
+    }
+
+    fn optional_inputs(&self) -> usize {
+        self.has_trip_count as usize + self.has_cond as usize
+    }
+
+    pub fn to_codegen_op(&self) -> TractResult<LoopCodegen> {
+        trace!("Optimizing(Codegen) loop body");
+        let plan = SimplePlan::new(self.body.clone().into_optimized()?)?;
+        Ok(LoopCodegen {
+            plan: Arc::new(plan),
+            has_trip_count: self.has_trip_count,
+            has_cond: self.has_cond,
+            iters: self.iters.clone(),
+        })
+    }
+
+    fn declutter_body(
+        &self,
+        model: &TypedModel,
+        node: &TypedNode,
+    ) -> TractResult<Option<TypedModelPatch>> {
+        if !self.decluttered {
+            let mut new = self.clone();
+            new.body = self.body.clone().declutter()?;
+            new.decluttered = true;
+            return Ok(Some(TypedModelPatch::replace_single_op(model, node, &node.inputs, new)?));
+        }
+        Ok(None)
+    }
+}
+
+fn is_scalar(fact: &TypedFact, dt: DatumType) -> bool {
+    fact.datum_type == dt && fact.shape.rank() == 0
+}
+
+impl Op for Loop {
+    fn name(&self) -> Cow<str> {
+        "Loop".into()
+    }
+
+    fn nested_models(&self) -> Vec<(Cow<str>, &dyn Model, Vec<String>, Vec<String>)> {
+        vec![("body".into(), &self.body, vec![], vec![])]
+    }
+
+    op_as_typed_op!();
+    not_a_pulsed_op!();
+}
+
+impl StatefullOp for Loop {
+    fn state(
+        &self,
+        session: &mut SessionState,
+        node_id: usize,
+    ) -> TractResult<Option<Box<dyn OpState>>> {
+        self.to_codegen_op()?.state(session, node_id)
+    }
+}
+
+impl TypedOp for Loop {
+    typed_op_as_op!();
+
+    fn output_facts(&self, inputs: &[&TypedFact]) -> TractResult<TVec<TypedFact>> {
+        if !self.has_trip_count && !self.has_cond {
+            bail!("Loop needs a trip count or a condition")
+        }
+        if inputs.len() < self.optional_inputs() {
+            bail!("Loop expects at least {} inputs, got {}", self.optional_inputs(), inputs.len())
+        }
+        if self.has_trip_count && !is_scalar(inputs[0], i64::datum_type()) {
+            bail!("Loop trip count must be an i64 scalar, got {:?}", inputs[0])
+        }
+        if self.has_cond && !is_scalar(inputs[self.has_trip_count as usize], bool::datum_type()) {
+            bail!("Loop condition must be a boolean scalar")
+        }
+        let carried = &inputs[self.optional_inputs()..];
+        let body_inputs = self.body.input_outlets()?.len();
+        let body_outputs = self.body.output_outlets()?.len();
+        if body_inputs != carried.len() + 2 || body_outputs < carried.len() + 1 {
+            bail!(
+                "Loop body with {} inputs and {} outputs can not carry {} variables",
+                body_inputs,
+                body_outputs,
+                carried.len()
+            )
+        }
+        if !is_scalar(self.body.input_fact(0)?, i64::datum_type())
+            || !is_scalar(self.body.input_fact(1)?, bool::datum_type())
+            || !is_scalar(self.body.output_fact(0)?, bool::datum_type())
+        {
+            bail!("Loop body iteration number and condition must be i64 and boolean scalars")
+        }
+        let mut outputs = tvec!();
+        for (ix, input) in carried.iter().enumerate() {
+            let body_input = self.body.input_fact(ix + 2)?;
+            let body_output = self.body.output_fact(ix + 1)?;
+            for fact in &[body_input, body_output] {
+                if fact.datum_type != input.datum_type || fact.shape != input.shape {
+                    bail!("Loop carried variable #{} is {:?}, body has {:?}", ix, input, fact)
+                }
+            }
+            outputs.push(TypedFact::dt_shape(input.datum_type, input.shape.clone())?);
+        }
+        let iters = match &inputs[0].konst {
+            Some(trip) if self.has_trip_count && !self.has_cond => {
+                (*trip.to_scalar::<i64>()?).max(0).to_dim()
+            }
+            _ => TDim::sym(self.iters.clone()),
+        };
+        for ix in carried.len() + 1..body_outputs {
+            let fact = self.body.output_fact(ix)?;
+            let shape: TVec<TDim> =
+                std::iter::once(iters.clone()).chain(fact.shape.iter()).collect();
+            outputs.push(TypedFact::dt_shape(fact.datum_type, &*shape)?);
+        }
+        Ok(outputs)
+    }
+
+    fn declutter(
+        &self,
+        model: &TypedModel,
+        node: &TypedNode,
+    ) -> TractResult<Option<TypedModelPatch>> {
+        self.declutter_body(model, node)
+    }
+
+    fn codegen(
+        &self,
+        model: &TypedModel,
+        node: &TypedNode,
+    ) -> TractResult<Option<TypedModelPatch>> {
+        Ok(Some(TypedModelPatch::replace_single_op(
+            &model,
+            node,
+            &node.inputs,
+            self.to_codegen_op()?,
+        )?))
+    }
+
+    fn nested_model_multipliers(&self, inputs: &[&TypedFact]) -> Vec<(Cow<str>, f32)> {
+        let iters = if self.has_trip_count {
+            inputs[0].konst.as_ref().and_then(|t| t.to_scalar::<i64>().ok()).map(|&t| t as f32)
+        } else {
+            None
+        };
+        vec![("body".into(), iters.unwrap_or(1.0))]
+    }
+}
+
+/// Loop, with its body optimized and planned.
+#[derive(Debug, Clone)]
+pub struct LoopCodegen {
+    pub plan: Arc<TypedSimplePlan<TypedModel>>,
+    pub has_trip_count: bool,
+    pub has_cond: bool,
+    pub iters: Symbol,
+}
+
+impl Op for LoopCodegen {
+    fn name(&self) -> Cow<str> {
+        "LoopCodegen".into()
+    }
+
+    fn nested_models(&self) -> Vec<(Cow<str>, &dyn Model, Vec<String>, Vec<String>)> {
+        vec![("body".into(), self.plan.model(), vec![], vec![])]
+    }
+
+    op_as_typed_op!();
+    not_a_pulsed_op!();
+}
+
+impl StatefullOp for LoopCodegen {
+    fn state(
+        &self,
+        _session: &mut SessionState,
+        _node_id: usize,
+    ) -> TractResult<Option<Box<dyn OpState>>> {
+        Ok(Some(Box::new(State {
+            has_trip_count: self.has_trip_count,
+            has_cond: self.has_cond,
+            model_state: TypedSimpleState::new(Arc::clone(&self.plan))?,
+        })))
+    }
+}
+
+impl TypedOp for LoopCodegen {
+    typed_op_as_op!();
+
+    fn output_facts(&self, inputs: &[&TypedFact]) -> TractResult<TVec<TypedFact>> {
+        let mut op = Loop::new(self.plan.model().clone(), self.has_trip_count, self.has_cond);
+        op.iters = self.iters.clone();
+        op.output_facts(inputs)
+    }
+}
+
+#[derive(Debug, Clone)]
+struct State {
+    has_trip_count: bool,
+    has_cond: bool,
+    model_state: TypedSimpleState<TypedModel, Arc<TypedSimplePlan<TypedModel>>>,
+}
+
+impl State {
+    fn stack_t<T: Datum>(shape: &[usize], slices: &[Arc<Tensor>]) -> TractResult<Tensor> {
+        if slices.len() == 0 {
+            return unsafe { Tensor::uninitialized::<T>(shape) };
+        }
+        let views = slices
+            .iter()
+            .map(|t| Ok(t.to_array_view::<T>()?.insert_axis(Axis(0))))
+            .collect::<TractResult<Vec<_>>>()?;
+        Ok(T::stack_views(0, &views)?.into_tensor())
+    }
+}
+
+impl OpState for State {
+    fn eval(
+        &mut self,
+        _session: &mut SessionState,
+        _op: &dyn Op,
+        mut inputs: TVec<Arc<Tensor>>,
+    ) -> TractResult<TVec<Arc<Tensor>>> {
+        let trip_count =
+            if self.has_trip_count { Some(*inputs.remove(0).to_scalar::<i64>()?) } else { None };
+        let mut cond = if self.has_cond { *inputs.remove(0).to_scalar::<bool>()? } else { true };
+        let mut carried: TVec<Tensor> = inputs.into_iter().map(|t| t.into_tensor()).collect();
+        let carried_len = carried.len();
+        let body_outputs = self.model_state.model().output_outlets()?.len();
+        let mut scans: Vec<Vec<Arc<Tensor>>> = vec![vec![]; body_outputs - carried_len - 1];
+        let mut iter = 0i64;
+        while cond && trip_count.map(|t| iter < t).unwrap_or(true) {
+            let mut body_inputs = tvec!(tensor0(iter), tensor0(cond));
+            body_inputs.extend(carried.drain(..));
+            let mut outputs = self
+                .model_state
+                .run(body_inputs)
+                .chain_err(|| format!("Evaluating loop body, iteration {}", iter))?
+                .into_iter();
+            let cond_out = outputs.next().unwrap();
+            if self.has_cond {
+                cond = *cond_out.to_scalar::<bool>()?;
+            }
+            carried.extend(outputs.by_ref().take(carried_len).map(|t| t.into_tensor()));
+            for (scan, output) in scans.iter_mut().zip(outputs) {
+                scan.push(output);
+            }
+            iter += 1;
+        }
+        let mut outputs: TVec<Arc<Tensor>> =
+            carried.into_iter().map(|t| t.into_arc_tensor()).collect();
+        for (ix, scan) in scans.iter().enumerate() {
+            let fact = self.model_state.model().output_fact(ix + carried_len + 1)?;
+            let mut shape: TVec<usize> = tvec!(0);
+            let slice_shape =
+                fact.shape.as_finite().ok_or("Loop scan outputs must have a known shape")?;
+            shape.extend(slice_shape.iter().cloned());
+            let stacked = dispatch_datum!(Self::stack_t(fact.datum_type)(&*shape, scan))?;
+            outputs.push(stacked.into_arc_tensor());
+        }
+        Ok(outputs)
+    }
+
+    fn reset(&mut self) -> TractResult<()> {
+        self.model_state.reset_state()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ops::{array, logic, math};
+
+    fn scalar<T: Datum>() -> TypedFact {
+        TypedFact::dt_shape(T::datum_type(), [0usize; 0].as_ref()).unwrap()
+    }
+
+    #[test]
+    fn fixed_count_sum() -> TractResult<()> {
+        let vector = TypedFact::dt_shape(f32::datum_type(), [4usize].as_ref())?;
+        let mut body = TypedModel::default();
+        let iter = body.add_source("iter", scalar::<i64>())?;
+        let cond = body.add_source("cond", scalar::<bool>())?;
+        let acc = body.add_source("acc", scalar::<f32>())?;
+        let x = body.add_source("x", vector.clone())?;
+        let item = body.wire_node("item", array::Gather::new(0), &[x, iter])?[0];
+        let sum = body.wire_node("sum", math::add::bin(), &[acc, item])?[0];
+        body.set_output_outlets(&[cond, sum, x, sum])?;
+
+        let mut model = TypedModel::default();
+        let trip = model.add_const("trip", rctensor0(4i64))?;
+        let acc = model.add_const("acc", rctensor0(0f32))?;
+        let x = model.add_source("x", vector)?;
+        let looped = model.wire_node("loop", Loop::new(body, true, false), &[trip, acc, x])?;
+        assert_eq!(model.outlet_fact(looped[2])?.shape.as_finite(), Some(&[4usize][..]));
+        model.set_output_outlets(&[looped[0], looped[2]])?;
+        for model in &[model.clone(), model.into_optimized()?] {
+            let result = SimplePlan::new(model)?.run(tvec!(tensor1(&[1f32, 2.0, 3.0, 4.0])))?;
+            assert_eq!(result[0], rctensor0(10f32));
+            assert_eq!(result[1], rctensor1(&[1f32, 3.0, 6.0, 10.0]));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn conditional_exit() -> TractResult<()> {
+        let mut body = TypedModel::default();
+        let _iter = body.add_source("iter", scalar::<i64>())?;
+        let _cond = body.add_source("cond", scalar::<bool>())?;
+        let acc = body.add_source("acc", scalar::<f32>())?;
+        let double = body.wire_node("double", math::mul::unary(rctensor0(2f32)), &[acc])?[0];
+        let cond = body.wire_node("small", logic::greater::unary(rctensor0(100f32)), &[double])?[0];
+        body.set_output_outlets(&[cond, double, double])?;
+
+        let mut model = TypedModel::default();
+        let cond = model.add_const("cond", rctensor0(true))?;
+        let acc = model.add_source("acc", scalar::<f32>())?;
+        let op = Loop::new(body.clone(), false, true);
+        let iters = TDim::sym(op.iters.clone());
+        let looped = model.wire_node("loop", op, &[cond, acc])?;
+        assert_eq!(model.outlet_fact(looped[1])?.shape.dim(0), iters);
+        let other = model.wire_node("other", Loop::new(body, false, true), &[cond, acc])?;
+        assert_ne!(model.outlet_fact(other[1])?.shape.dim(0), iters);
+        model.set_output_outlets(&looped)?;
+        let optimized = model.clone().into_optimized()?;
+        assert_eq!(optimized.outlet_fact(optimized.output_outlets()?[1])?.shape.dim(0), iters);
+        let result = SimplePlan::new(&model)?.run(tvec!(tensor0(1f32)))?;
+        assert_eq!(result[0], rctensor0(128f32));
+        assert_eq!(result[1], rctensor1(&[2f32, 4.0, 8.0, 16.0, 32.0, 64.0, 128.0]));
+        let result = SimplePlan::new(&model)?.run(tvec!(tensor0(200f32)))?;
+        assert_eq!(result[0], rctensor0(400f32));
+        assert_eq!(result[1].shape(), &[1]);
+        Ok(())
+    }
+
+    #[test]
+    fn reset_nested_state() -> TractResult<()> {
+        use crate::ops::nn::{KVCache, Reducer, TypedReduce};
+        // each iteration appends x to a cache, and outputs the sum of the cache
+        let row = TypedFact::dt_shape(f32::datum_type(), [1, 2].as_ref())?;
+        let mut body = TypedModel::default();
+        let _iter = body.add_source("iter", scalar::<i64>())?;
+        let cond = body.add_source("cond", scalar::<bool>())?;
+        let x = body.add_source("x", row.clone())?;
+        let cache = body.wire_node("cache", KVCache::new(0, 8), &[x, x])?;
+        let sum = body.wire_node("sum", TypedReduce::new(tvec!(0), Reducer::Sum), &cache[0..1])?;
+        body.set_output_outlets(&[cond, x, sum[0]])?;
+
+        let mut model = TypedModel::default();
+        let trip = model.add_const("trip", rctensor0(2i64))?;
+        let x = model.add_source("x", row)?;
+        let looped = model.wire_node("loop", Loop::new(body, true, false), &[trip, x])?;
+        model.set_output_outlets(&looped[1..])?;
+        let plan = SimplePlan::new(model)?;
+        let mut state = SimpleState::new(&plan)?;
+        let mut run = || -> TractResult<Arc<Tensor>> {
+            Ok(state.run(tvec!(tensor2(&[[1f32, 1.0]])))?.remove(0))
+        };
+        let first = run()?;
+        assert_eq!(first, rctensor3(&[[[1f32, 1.0]], [[2.0, 2.0]]]));
+        assert_eq!(run()?, rctensor3(&[[[3f32, 3.0]], [[4.0, 4.0]]]));
+        state.reset_state()?;
+        assert_eq!(state.run(tvec!(tensor2(&[[1f32, 1.0]])))?.remove(0), first);
+        Ok(())
+    }
+}
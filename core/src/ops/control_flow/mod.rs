@@ -0,0 +1,8 @@
+//! Control flow operators, running nested models. Scans are in `ops::scan`.
+mod if_op;
+mod inference;
+mod loop_op;
+
+pub use if_op::{If, IfCodegen};
+pub use inference::{InferenceIf, InferenceLoop};
+pub use loop_op::{Loop, LoopCodegen};
@@ -0,0 +1,145 @@
+//! Concrete cost estimates, for optimisation decisions.
+//!
+//! `TypedOp::cost` counts operations per kind, symbolically, for profiling.
+//! `ComputeCost` boils it down to a flop count and a memory traffic for
+//! fully known input facts, so that passes can compare alternatives.
+use crate::internal::*;
+use std::ops::Add;
+
+/// Work done by one evaluation of an op.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ComputeCost {
+    /// Arithmetic operations, a multiply-add counting for two.
+    pub flops: u64,
+    /// Bytes read and written: inputs, outputs and tensors held by the op.
+    pub memory_bytes: u64,
+}
+
+impl Add for ComputeCost {
+    type Output = ComputeCost;
+    fn add(self, other: ComputeCost) -> ComputeCost {
+        ComputeCost {
+            flops: self.flops + other.flops,
+            memory_bytes: self.memory_bytes + other.memory_bytes,
+        }
+    }
+}
+
+impl std::iter::Sum for ComputeCost {
+    fn sum<I: Iterator<Item = ComputeCost>>(iter: I) -> ComputeCost {
+        iter.fold(ComputeCost::default(), Add::add)
+    }
+}
+
+/// Ops giving their own cost estimate.
+///
+/// Typed ops expose it to `op_cost` through `TypedOp::as_op_cost`.
+pub trait OpCost {
+    fn compute_cost(&self, inputs: &[&TypedFact]) -> TractResult<ComputeCost>;
+}
+
+/// Size in bytes of a tensor of known shape.
+pub fn fact_bytes(fact: &TypedFact) -> TractResult<u64> {
+    let shape = fact
+        .shape
+        .as_finite()
+        .ok_or_else(|| format!("Can not compute the cost of a symbolic shape: {:?}", fact))?;
+    Ok(fact.datum_type.storage_size(shape.iter().product::<usize>()) as u64)
+}
+
+/// Bytes of the inputs and outputs of an op.
+pub fn io_bytes(op: &dyn TypedOp, inputs: &[&TypedFact]) -> TractResult<u64> {
+    let mut bytes = 0;
+    for fact in inputs {
+        bytes += fact_bytes(fact)?;
+    }
+    for fact in op.output_facts(inputs)? {
+        bytes += fact_bytes(&fact)?;
+    }
+    Ok(bytes)
+}
+
+/// Flops from the `TypedOp::cost` hint: two per multiply-add, one per
+/// division. Buffers are not arithmetic.
+pub fn hinted_flops(op: &dyn TypedOp, inputs: &[&TypedFact]) -> TractResult<u64> {
+    let mut flops = 0;
+    for (kind, count) in op.cost(inputs)? {
+        let count = count.to_integer()? as u64;
+        flops += match kind {
+            Cost::FMA(_) => 2 * count,
+            Cost::Div(_) => count,
+            Cost::Buffer(_) => 0,
+        };
+    }
+    Ok(flops)
+}
+
+/// Cost of any typed op: its `OpCost` estimate if it has one, otherwise the
+/// flops from its cost hint and the bytes of its inputs and outputs.
+pub fn op_cost(op: &dyn TypedOp, inputs: &[&TypedFact]) -> TractResult<ComputeCost> {
+    if let Some(op) = op.as_op_cost() {
+        return op.compute_cost(inputs);
+    }
+    Ok(ComputeCost { flops: hinted_flops(op, inputs)?, memory_bytes: io_bytes(op, inputs)? })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ops::matmul::MatMul;
+    use crate::ops::nn::LayerSoftmax;
+
+    fn f32_fact(shape: &[usize]) -> TypedFact {
+        TypedFact::dt_shape(f32::datum_type(), shape).unwrap()
+    }
+
+    #[test]
+    fn matmul_flops() -> TractResult<()> {
+        let (m, k, n) = (3, 5, 7);
+        let a = f32_fact(&[m, k]);
+        let b = f32_fact(&[k, n]);
+        let cost = op_cost(&MatMul::default(), &[&a, &b])?;
+        assert_eq!(cost.flops, 2 * m as u64 * n as u64 * k as u64);
+        assert_eq!(cost.memory_bytes, 4 * (m * k + k * n + m * n) as u64);
+        let batched = f32_fact(&[2, m, k]);
+        assert_eq!(op_cost(&MatMul::default(), &[&batched, &b])?.flops, 2 * 2 * (m * n * k) as u64);
+        Ok(())
+    }
+
+    #[test]
+    fn softmax_cost() -> TractResult<()> {
+        let input = f32_fact(&[4, 10]);
+        let cost = op_cost(&LayerSoftmax::new(1), &[&input])?;
+        assert_eq!(cost, ComputeCost { flops: 5 * 40, memory_bytes: 2 * 4 * 40 });
+        Ok(())
+    }
+
+    #[test]
+    fn symbolic_shapes_have_no_cost() {
+        let a = TypedFact::dt_shape(f32::datum_type(), [TDim::s(), 3.to_dim()].as_ref()).unwrap();
+        let b = f32_fact(&[3, 3]);
+        assert!(op_cost(&MatMul::default(), &[&a, &b]).is_err());
+    }
+
+    #[test]
+    fn packed_bytes() -> TractResult<()> {
+        let fact = TypedFact::dt_shape(DatumType::I4, [3, 5].as_ref())?;
+        assert_eq!(fact_bytes(&fact)?, 8);
+        let fact = TypedFact::dt_shape(DatumType::U4, [4, 4].as_ref())?;
+        assert_eq!(fact_bytes(&fact)?, 8);
+        assert_eq!(fact_bytes(&f32_fact(&[3, 5]))?, 60);
+        Ok(())
+    }
+
+    #[test]
+    fn total_flops() -> TractResult<()> {
+        let mut model = TypedModel::default();
+        let a = model.add_source("a", f32_fact(&[3, 5]))?;
+        let b = model.add_source("b", f32_fact(&[5, 7]))?;
+        let c = model.wire_node("c", MatMul::default(), &[a, b])?[0];
+        let d = model.wire_node("d", crate::ops::math::div::bin(), &[c, c])?;
+        model.set_output_outlets(&d)?;
+        assert_eq!(model.total_flops()?, 2 * 3 * 5 * 7 + 3 * 7);
+        Ok(())
+    }
+}
@@ -0,0 +1,86 @@
+//! Ops helping with model debugging.
+use crate::internal::*;
+
+/// Passes its input through, failing if it contains a NaN or an infinite
+/// value.
+#[derive(Debug, Clone, new, Default, PartialEq)]
+pub struct CheckFinite {
+    /// Name of the node producing the checked value, for error reporting.
+    pub checked: String,
+}
+
+impl CheckFinite {
+    fn all_finite<T: Datum + num_traits::Float>(t: &Tensor) -> TractResult<bool> {
+        Ok(t.as_slice::<T>()?.iter().all(|x| x.is_finite()))
+    }
+}
+
+impl Op for CheckFinite {
+    fn name(&self) -> Cow<str> {
+        "CheckFinite".into()
+    }
+
+    fn info(&self) -> TractResult<Vec<String>> {
+        Ok(vec![format!("checking {}", self.checked)])
+    }
+
+    impl_op_same_as!();
+    op_as_typed_op!();
+}
+
+impl StatelessOp for CheckFinite {
+    fn eval(&self, inputs: TVec<Arc<Tensor>>) -> TractResult<TVec<Arc<Tensor>>> {
+        let input = &inputs[0];
+        let finite = match input.datum_type() {
+            DatumType::F16 => Self::all_finite::<f32>(&*input.cast_to::<f32>()?)?,
+            DatumType::F32 => Self::all_finite::<f32>(input)?,
+            DatumType::F64 => Self::all_finite::<f64>(input)?,
+            _ => true,
+        };
+        if !finite {
+            bail!("Non-finite value found in output of {}", self.checked)
+        }
+        Ok(inputs)
+    }
+}
+
+impl InferenceRulesOp for CheckFinite {
+    fn rules<'r, 'p: 'r, 's: 'r>(
+        &'s self,
+        s: &mut Solver<'r>,
+        inputs: &'p [TensorProxy],
+        outputs: &'p [TensorProxy],
+    ) -> InferenceResult {
+        check_input_arity(&inputs, 1)?;
+        check_output_arity(&outputs, 1)?;
+        s.equals(&inputs[0].datum_type, &outputs[0].datum_type)?;
+        s.equals(&inputs[0].shape, &outputs[0].shape)?;
+        Ok(())
+    }
+
+    inference_op_as_op!();
+    to_typed!();
+}
+
+impl TypedOp for CheckFinite {
+    fn output_facts(&self, inputs: &[&TypedFact]) -> TractResult<TVec<TypedFact>> {
+        Ok(tvec!(inputs[0].clone()))
+    }
+
+    typed_op_as_op!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_nan() {
+        let op = CheckFinite::new("x".to_string());
+        assert!(op.eval(tvec!(rctensor1(&[1f32, 2.0]))).is_ok());
+        assert!(op.eval(tvec!(rctensor1(&[1i32, 2]))).is_ok());
+        let err = op.eval(tvec!(rctensor1(&[1f64, std::f64::NAN]))).unwrap_err();
+        assert!(err.to_string().contains("output of x"));
+        assert!(op.eval(tvec!(rctensor1(&[std::f32::INFINITY]))).is_err());
+    }
+}
@@ -0,0 +1,4 @@
+//! Object detection post-processing.
+pub mod nms;
+
+pub use self::nms::{BoxFormat, NonMaxSuppression};
@@ -0,0 +1,284 @@
+use crate::internal::*;
+use ndarray::*;
+
+/// Layout of the four coordinates of a box.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BoxFormat {
+    /// [y1, x1, y2, x2], the two corners being any diagonal pair.
+    Corners,
+    /// [x_center, y_center, width, height].
+    CenterPoint,
+}
+
+impl Default for BoxFormat {
+    fn default() -> BoxFormat {
+        BoxFormat::Corners
+    }
+}
+
+/// Non maximum suppression, as in ONNX.
+///
+/// Inputs are boxes [batch, boxes, 4], scores [batch, classes, boxes], and
+/// optionally scalars for the maximum number of boxes selected per class
+/// (defaulting to zero), the IoU threshold (defaulting to zero) and the score
+/// threshold (disabled by default).
+///
+/// Output is an int64 [selected, 3] tensor of (batch, class, box) indices,
+/// the number of selected boxes is only known at runtime. It is a symbol of
+/// its own for each node.
+#[derive(Debug, Clone, new)]
+pub struct NonMaxSuppression {
+    pub box_format: BoxFormat,
+    #[new(value = r#"Symbol::fresh("N")"#)]
+    pub selected: Symbol,
+}
+
+impl Default for NonMaxSuppression {
+    fn default() -> NonMaxSuppression {
+        NonMaxSuppression::new(BoxFormat::default())
+    }
+}
+
+impl NonMaxSuppression {
+    /// Returns the box as (y1, x1, y2, x2) with y1 <= y2 and x1 <= x2.
+    fn corners(&self, b: ArrayView1<f32>) -> (f32, f32, f32, f32) {
+        match self.box_format {
+            BoxFormat::Corners => (b[0].min(b[2]), b[1].min(b[3]), b[0].max(b[2]), b[1].max(b[3])),
+            BoxFormat::CenterPoint => {
+                (b[1] - b[3] / 2.0, b[0] - b[2] / 2.0, b[1] + b[3] / 2.0, b[0] + b[2] / 2.0)
+            }
+        }
+    }
+
+    fn iou(&self, a: ArrayView1<f32>, b: ArrayView1<f32>) -> f32 {
+        let a = self.corners(a);
+        let b = self.corners(b);
+        let height = (a.2.min(b.2) - a.0.max(b.0)).max(0.0);
+        let width = (a.3.min(b.3) - a.1.max(b.1)).max(0.0);
+        let inter = height * width;
+        let union = (a.2 - a.0) * (a.3 - a.1) + (b.2 - b.0) * (b.3 - b.1) - inter;
+        if union <= 0.0 {
+            0.0
+        } else {
+            inter / union
+        }
+    }
+}
+
+impl Op for NonMaxSuppression {
+    fn name(&self) -> Cow<str> {
+        "NonMaxSuppression".into()
+    }
+
+    fn info(&self) -> TractResult<Vec<String>> {
+        Ok(vec![format!("box format: {:?}", self.box_format)])
+    }
+
+    op_as_typed_op!();
+    not_a_pulsed_op!();
+}
+
+impl StatelessOp for NonMaxSuppression {
+    fn eval(&self, inputs: TVec<Arc<Tensor>>) -> TractResult<TVec<Arc<Tensor>>> {
+        if inputs.len() < 2 || inputs.len() > 5 {
+            bail!("NonMaxSuppression expects 2 to 5 inputs, got {}", inputs.len())
+        }
+        let boxes = inputs[0].cast_to::<f32>()?;
+        let boxes = boxes.to_array_view::<f32>()?.into_dimensionality::<Ix3>()?;
+        let scores = inputs[1].cast_to::<f32>()?;
+        let scores = scores.to_array_view::<f32>()?.into_dimensionality::<Ix3>()?;
+        let max_boxes = match inputs.get(2) {
+            Some(t) => t.cast_to_scalar::<i64>()?.max(0) as usize,
+            None => 0,
+        };
+        let iou_threshold =
+            inputs.get(3).map(|t| t.cast_to_scalar::<f32>()).transpose()?.unwrap_or(0.0);
+        let score_threshold = inputs.get(4).map(|t| t.cast_to_scalar::<f32>()).transpose()?;
+        if boxes.shape()[0] != scores.shape()[0]
+            || boxes.shape()[1] != scores.shape()[2]
+            || boxes.shape()[2] != 4
+        {
+            bail!("Inconsistent boxes {:?} and scores {:?}", boxes.shape(), scores.shape())
+        }
+        let mut selected: Vec<[i64; 3]> = vec![];
+        for batch in 0..scores.shape()[0] {
+            for class in 0..scores.shape()[1] {
+                let scores = scores.slice(s![batch, class, ..]);
+                let mut candidates: Vec<usize> = (0..scores.len())
+                    .filter(|&ix| score_threshold.map(|t| scores[ix] > t).unwrap_or(true))
+                    .collect();
+                candidates.sort_by(|&a, &b| {
+                    scores[b].partial_cmp(&scores[a]).unwrap_or(std::cmp::Ordering::Equal)
+                });
+                let mut kept: Vec<usize> = vec![];
+                for candidate in candidates {
+                    if kept.len() >= max_boxes {
+                        break;
+                    }
+                    let candidate_box = boxes.slice(s![batch, candidate, ..]);
+                    if kept.iter().all(|&k| {
+                        self.iou(candidate_box, boxes.slice(s![batch, k, ..])) <= iou_threshold
+                    }) {
+                        kept.push(candidate);
+                    }
+                }
+                selected.extend(kept.into_iter().map(|k| [batch as i64, class as i64, k as i64]));
+            }
+        }
+        let output = Array2::from_shape_fn((selected.len(), 3), |(i, j)| selected[i][j]);
+        Ok(tvec!(output.into_arc_tensor()))
+    }
+}
+
+impl InferenceRulesOp for NonMaxSuppression {
+    fn rules<'r, 'p: 'r, 's: 'r>(
+        &'s self,
+        s: &mut Solver<'r>,
+        inputs: &'p [TensorProxy],
+        outputs: &'p [TensorProxy],
+    ) -> InferenceResult {
+        if inputs.len() < 2 || inputs.len() > 5 {
+            bail!("NonMaxSuppression expects 2 to 5 inputs, got {}", inputs.len())
+        }
+        check_output_arity(&outputs, 1)?;
+        s.equals(&inputs[0].rank, 3)?;
+        s.equals(&inputs[1].rank, 3)?;
+        s.equals(&inputs[0].shape[0], &inputs[1].shape[0])?;
+        s.equals(&inputs[0].shape[1], &inputs[1].shape[2])?;
+        s.equals(&inputs[0].shape[2], 4.to_dim())?;
+        for input in &inputs[2..] {
+            s.equals(&input.rank, 0)?;
+        }
+        s.equals(&outputs[0].datum_type, i64::datum_type())?;
+        s.equals(&outputs[0].rank, 2)?;
+        s.equals(&outputs[0].shape[0], TDim::sym(self.selected.clone()))?;
+        s.equals(&outputs[0].shape[1], 3.to_dim())?;
+        Ok(())
+    }
+
+    inference_op_as_op!();
+    to_typed!();
+}
+
+impl TypedOp for NonMaxSuppression {
+    fn output_facts(&self, _inputs: &[&TypedFact]) -> TractResult<TVec<TypedFact>> {
+        let shape = [TDim::sym(self.selected.clone()), 3.to_dim()];
+        Ok(tvec!(TypedFact::dt_shape(i64::datum_type(), shape.as_ref())?))
+    }
+
+    typed_op_as_op!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Reference results from a straightforward python implementation of the
+    // ONNX specification.
+
+    fn boxes() -> Tensor {
+        tensor3(&[[
+            [0.0f32, 0.0, 1.0, 1.0],
+            [0.0, 0.1, 1.0, 1.1],
+            [0.0, -0.1, 1.0, 0.9],
+            [0.0, 10.0, 1.0, 11.0],
+            [0.0, 10.1, 1.0, 11.1],
+            [0.0, 100.0, 1.0, 101.0],
+        ]])
+    }
+
+    fn scores() -> Tensor {
+        tensor3(&[[[0.9f32, 0.75, 0.6, 0.95, 0.5, 0.3]]])
+    }
+
+    fn run(op: NonMaxSuppression, inputs: TVec<Tensor>) -> TractResult<Arc<Tensor>> {
+        Ok(op.eval(inputs.into_iter().map(|t| t.into()).collect())?.remove(0))
+    }
+
+    #[test]
+    fn suppress_by_iou() -> TractResult<()> {
+        let output = run(
+            NonMaxSuppression::default(),
+            tvec!(boxes(), scores(), tensor0(3i64), tensor0(0.5f32), tensor0(0.0f32)),
+        )?;
+        assert_eq!(*output, tensor2(&[[0i64, 0, 3], [0, 0, 0], [0, 0, 5]]));
+        Ok(())
+    }
+
+    #[test]
+    fn score_threshold() -> TractResult<()> {
+        let output = run(
+            NonMaxSuppression::default(),
+            tvec!(boxes(), scores(), tensor0(3i64), tensor0(0.5f32), tensor0(0.4f32)),
+        )?;
+        assert_eq!(*output, tensor2(&[[0i64, 0, 3], [0, 0, 0]]));
+        Ok(())
+    }
+
+    #[test]
+    fn center_point_box() -> TractResult<()> {
+        let boxes = tensor3(&[[
+            [0.5f32, 0.5, 1.0, 1.0],
+            [0.5, 0.6, 1.0, 1.0],
+            [0.5, 0.4, 1.0, 1.0],
+            [0.5, 10.5, 1.0, 1.0],
+            [0.5, 10.6, 1.0, 1.0],
+            [0.5, 100.5, 1.0, 1.0],
+        ]]);
+        let output = run(
+            NonMaxSuppression::new(BoxFormat::CenterPoint),
+            tvec!(boxes, scores(), tensor0(3i64), tensor0(0.5f32), tensor0(0.0f32)),
+        )?;
+        assert_eq!(*output, tensor2(&[[0i64, 0, 3], [0, 0, 0], [0, 0, 5]]));
+        Ok(())
+    }
+
+    #[test]
+    fn batches_and_classes() -> TractResult<()> {
+        let boxes = boxes().into_array::<f32>()?;
+        let boxes = stack(Axis(0), &[boxes.view(), boxes.view()])?;
+        let scores =
+            tensor3(&[[[0.9f32, 0.75, 0.6, 0.95, 0.5, 0.3], [0.1, 0.2, 0.3, 0.4, 0.5, 0.6]]])
+                .into_array::<f32>()?;
+        let scores = stack(Axis(0), &[scores.view(), scores.view()])?;
+        let output = run(
+            NonMaxSuppression::default(),
+            tvec!(boxes.into(), scores.into(), tensor0(2i64), tensor0(0.5f32)),
+        )?;
+        assert_eq!(
+            *output,
+            tensor2(&[
+                [0i64, 0, 3],
+                [0, 0, 0],
+                [0, 1, 5],
+                [0, 1, 4],
+                [1, 0, 3],
+                [1, 0, 0],
+                [1, 1, 5],
+                [1, 1, 4]
+            ])
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn symbolic_output() -> TractResult<()> {
+        let mut model = TypedModel::default();
+        let b = model
+            .add_source("boxes", TypedFact::dt_shape(f32::datum_type(), [1, 6, 4].as_ref())?)?;
+        let s = model
+            .add_source("scores", TypedFact::dt_shape(f32::datum_type(), [1, 1, 6].as_ref())?)?;
+        let max = model.add_const("max", rctensor0(3i64))?;
+        let iou = model.add_const("iou", rctensor0(0.5f32))?;
+        let nms = model.wire_node("nms", NonMaxSuppression::default(), &[b, s, max, iou])?[0];
+        let other = model.wire_node("other", NonMaxSuppression::default(), &[b, s, max])?[0];
+        model.set_output_outlets(&[nms, other])?;
+        let selected = model.outlet_fact(nms)?.shape.dim(0);
+        assert!(selected.is_symbolic());
+        assert_ne!(selected, model.outlet_fact(other)?.shape.dim(0));
+        let output = SimplePlan::new(&model)?.run(tvec!(boxes(), scores()))?;
+        assert_eq!(output[0].shape(), &[3, 3]);
+        assert_eq!(output[1].shape(), &[3, 3]);
+        Ok(())
+    }
+}
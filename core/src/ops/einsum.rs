@@ -0,0 +1,602 @@
+//! Einstein summation.
+use crate::internal::*;
+use itertools::Itertools;
+use ndarray::*;
+use num_traits::Zero;
+
+/// Einstein summation over an arbitrary number of inputs.
+///
+/// Each input axis is labelled by a letter. Axes with labels absent from the
+/// output are summed over, output axes are computed as the product of the
+/// inputs. Dimensions of 1 are broadcast against the other inputs.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Einsum {
+    pub inputs: Vec<Vec<char>>,
+    pub output: Vec<char>,
+}
+
+impl Einsum {
+    /// Parse an equation like "bhqd,bhkd->bhqk".
+    ///
+    /// Without an explicit "->" output, the output is made of the labels
+    /// appearing once, in alphabetical order, like numpy does.
+    pub fn new(expr: &str) -> TractResult<Einsum> {
+        let expr: String = expr.chars().filter(|c| !c.is_whitespace()).collect();
+        let (inputs, output) = match expr.find("->") {
+            Some(pos) => (&expr[..pos], Some(&expr[pos + 2..])),
+            None => (&*expr, None),
+        };
+        if let Some(c) = expr.chars().find(|c| !c.is_ascii_alphabetic() && !"->,".contains(*c)) {
+            bail!("Invalid einsum equation {}: unsupported label {:?}", expr, c)
+        }
+        let inputs: Vec<Vec<char>> = inputs.split(',').map(|i| i.chars().collect()).collect();
+        let output: Vec<char> = match output {
+            Some(output) => output.chars().collect(),
+            None => inputs
+                .iter()
+                .flat_map(|i| i.iter())
+                .cloned()
+                .sorted()
+                .group_by(|&c| c)
+                .into_iter()
+                .filter_map(|(c, group)| if group.count() == 1 { Some(c) } else { None })
+                .collect(),
+        };
+        for (ix, label) in output.iter().enumerate() {
+            if output[..ix].contains(label) {
+                bail!("Invalid einsum equation {}: {} appears twice in output", expr, label)
+            }
+            if !inputs.iter().any(|i| i.contains(label)) {
+                bail!("Invalid einsum equation {}: {} does not appear in inputs", expr, label)
+            }
+        }
+        Ok(Einsum { inputs, output })
+    }
+
+    /// Labels summed over, in order of appearance.
+    fn summed(&self) -> Vec<char> {
+        self.inputs
+            .iter()
+            .flat_map(|i| i.iter())
+            .filter(|c| !self.output.contains(c))
+            .cloned()
+            .unique()
+            .collect()
+    }
+
+    /// The Einsum computing the gradient of `input` from the other inputs
+    /// followed by the output gradient. See `EinsumGrad`.
+    pub fn gradient_equation(&self, input: usize) -> TractResult<Einsum> {
+        let labels =
+            self.inputs.get(input).ok_or_else(|| format!("Einsum has no input #{}", input))?;
+        if labels.iter().unique().count() != labels.len() {
+            bail!(
+                "Gradient of Einsum input #{} ({}) with repeated labels is not supported",
+                input,
+                labels.iter().join("")
+            )
+        }
+        let inputs: Vec<Vec<char>> = self
+            .inputs
+            .iter()
+            .enumerate()
+            .filter(|&(ix, _)| ix != input)
+            .map(|(_, labels)| labels.clone())
+            .chain(std::iter::once(self.output.clone()))
+            .collect();
+        let output =
+            labels.iter().filter(|c| inputs.iter().any(|i| i.contains(c))).cloned().collect();
+        Ok(Einsum { inputs, output })
+    }
+
+    /// Dimension of a label, checking all its occurences are compatible.
+    fn dim<D: DimLike>(&self, shapes: &[&[D]], label: char) -> TractResult<D> {
+        let mut dim: Option<D> = None;
+        for (input, shape) in self.inputs.iter().zip(shapes.iter()) {
+            for (axis, _) in input.iter().enumerate().filter(|(_, &c)| c == label) {
+                let d = &shape[axis];
+                match &dim {
+                    None => dim = Some(d.clone()),
+                    Some(prev) if prev == d || *d == D::one() => (),
+                    Some(prev) if *prev == D::one() => dim = Some(d.clone()),
+                    Some(prev) => {
+                        bail!("Incompatible dimensions for {}: {} and {}", label, prev, d)
+                    }
+                }
+            }
+        }
+        Ok(dim.unwrap())
+    }
+
+    fn check_ranks<D: DimLike>(&self, shapes: &[&[D]]) -> TractResult<()> {
+        if shapes.len() != self.inputs.len() {
+            bail!("Einsum expects {} inputs, got {}", self.inputs.len(), shapes.len())
+        }
+        for (ix, (input, shape)) in self.inputs.iter().zip(shapes.iter()).enumerate() {
+            if input.len() != shape.len() {
+                bail!(
+                    "Einsum input #{} is labelled {}, but has rank {}",
+                    ix,
+                    input.iter().join(""),
+                    shape.len()
+                )
+            }
+        }
+        Ok(())
+    }
+
+    fn output_shape<D: DimLike>(&self, shapes: &[&[D]]) -> TractResult<TVec<D>> {
+        self.check_ranks(shapes)?;
+        for label in self.summed() {
+            self.dim(shapes, label)?;
+        }
+        self.output.iter().map(|&c| self.dim(shapes, c)).collect()
+    }
+
+    fn eval_t<T>(&self, inputs: &[Arc<Tensor>]) -> TractResult<Arc<Tensor>>
+    where
+        T: Datum + Zero + Copy + std::ops::Mul<Output = T>,
+    {
+        let shapes: Vec<&[usize]> = inputs.iter().map(|t| t.shape()).collect();
+        let output_shape = self.output_shape(&shapes)?;
+        let summed = self.summed();
+        let summed_shape =
+            summed.iter().map(|&c| self.dim(&shapes, c)).collect::<TractResult<TVec<usize>>>()?;
+        let loop_labels: Vec<char> = self.output.iter().chain(summed.iter()).cloned().collect();
+        let loop_dims: TVec<usize> =
+            output_shape.iter().chain(summed_shape.iter()).cloned().collect();
+
+        // strides[input][loop axis]. repeated labels (diagonals) add up their
+        // strides, broadcast axes get a null stride.
+        let strides: Vec<TVec<isize>> = self
+            .inputs
+            .iter()
+            .zip(inputs.iter())
+            .map(|(labels, t)| {
+                let mut tensor_strides: TVec<isize> = tvec!(1; t.rank());
+                for axis in (0..t.rank().saturating_sub(1)).rev() {
+                    tensor_strides[axis] = tensor_strides[axis + 1] * t.shape()[axis + 1] as isize;
+                }
+                loop_labels
+                    .iter()
+                    .zip(loop_dims.iter())
+                    .map(|(&label, &dim)| {
+                        labels
+                            .iter()
+                            .enumerate()
+                            .filter(|&(axis, &c)| c == label && !(t.shape()[axis] == 1 && dim > 1))
+                            .map(|(axis, _)| tensor_strides[axis])
+                            .sum()
+                    })
+                    .collect()
+            })
+            .collect();
+        let views: Vec<&[T]> =
+            inputs.iter().map(|t| t.as_slice::<T>()).collect::<TractResult<_>>()?;
+
+        let out_rank = output_shape.len();
+        let out_len: usize = output_shape.iter().product();
+        let summed_len: usize = summed_shape.iter().product();
+        let mut result: Vec<T> = Vec::with_capacity(out_len);
+        let mut out_coords: TVec<usize> = tvec!(0; out_rank);
+        let mut out_offsets: TVec<isize> = tvec!(0; inputs.len());
+        for _ in 0..out_len {
+            // innermost loops over the summed axes
+            let mut acc = T::zero();
+            let mut coords: TVec<usize> = tvec!(0; summed.len());
+            let mut offsets = out_offsets.clone();
+            for _ in 0..summed_len {
+                let mut product = views[0][offsets[0] as usize];
+                for i in 1..views.len() {
+                    product = product * views[i][offsets[i] as usize];
+                }
+                acc = acc + product;
+                Self::next(&mut coords, &summed_shape, &mut offsets, &strides, out_rank);
+            }
+            result.push(acc);
+            Self::next(&mut out_coords, &output_shape, &mut out_offsets, &strides, 0);
+        }
+        Ok(ArrayD::from_shape_vec(&*output_shape, result)?.into_arc_tensor())
+    }
+
+    /// Increment `coords` in `shape`, keeping the input offsets in sync.
+    fn next(
+        coords: &mut [usize],
+        shape: &[usize],
+        offsets: &mut [isize],
+        strides: &[TVec<isize>],
+        first_axis: usize,
+    ) {
+        for axis in (0..coords.len()).rev() {
+            coords[axis] += 1;
+            for (offset, strides) in offsets.iter_mut().zip(strides.iter()) {
+                *offset += strides[first_axis + axis];
+            }
+            if coords[axis] < shape[axis] {
+                return;
+            }
+            for (offset, strides) in offsets.iter_mut().zip(strides.iter()) {
+                *offset -= strides[first_axis + axis] * shape[axis] as isize;
+            }
+            coords[axis] = 0;
+        }
+    }
+}
+
+impl Op for Einsum {
+    fn name(&self) -> Cow<str> {
+        "Einsum".into()
+    }
+
+    fn info(&self) -> TractResult<Vec<String>> {
+        Ok(vec![format!(
+            "{}->{}",
+            self.inputs.iter().map(|i| i.iter().join("")).join(","),
+            self.output.iter().join("")
+        )])
+    }
+
+    impl_op_same_as!();
+    op_as_typed_op!();
+}
+
+impl StatelessOp for Einsum {
+    fn eval(&self, inputs: TVec<Arc<Tensor>>) -> TractResult<TVec<Arc<Tensor>>> {
+        let dt = inputs[0].datum_type();
+        if let Some(other) = inputs.iter().find(|i| i.datum_type() != dt) {
+            bail!(
+                "Einsum inputs must have the same type, found {:?} and {:?}",
+                dt,
+                other.datum_type()
+            )
+        }
+        Ok(tvec!(dispatch_numbers!(Self::eval_t(dt)(self, &*inputs))?))
+    }
+}
+
+impl InferenceRulesOp for Einsum {
+    fn rules<'r, 'p: 'r, 's: 'r>(
+        &'s self,
+        s: &mut Solver<'r>,
+        inputs: &'p [TensorProxy],
+        outputs: &'p [TensorProxy],
+    ) -> InferenceResult {
+        check_input_arity(&inputs, self.inputs.len())?;
+        check_output_arity(&outputs, 1)?;
+        for (input, labels) in inputs.iter().zip(self.inputs.iter()) {
+            s.equals(&input.rank, labels.len() as i32)?;
+            s.equals(&input.datum_type, &outputs[0].datum_type)?;
+        }
+        s.equals(&outputs[0].rank, self.output.len() as i32)?;
+        s.given_all(inputs.iter().map(|i| &i.shape), move |s, shapes| {
+            let shapes: Vec<&[TDim]> = shapes.iter().map(|s| &**s).collect();
+            s.equals(&outputs[0].shape, ShapeFact::from(self.output_shape(&shapes)?))
+        })
+    }
+
+    inference_op_as_op!();
+    to_typed!();
+}
+
+impl TypedOp for Einsum {
+    fn output_facts(&self, inputs: &[&TypedFact]) -> TractResult<TVec<TypedFact>> {
+        let shapes: Vec<TVec<TDim>> = inputs.iter().map(|i| i.shape.to_tvec()).collect();
+        let shapes: Vec<&[TDim]> = shapes.iter().map(|s| &**s).collect();
+        Ok(tvec!(TypedFact::dt_shape(inputs[0].datum_type, &*self.output_shape(&shapes)?)?))
+    }
+
+    typed_op_as_op!();
+}
+
+/// Gradient of an Einsum with respect to one of its inputs.
+///
+/// Inputs are the forward inputs followed by the gradient of the forward
+/// output. As Einsum is linear in each of its inputs, the gradient is itself
+/// an Einsum of the other inputs and the output gradient: for "ij,jk->ik",
+/// the gradient of the first input is "jk,ik->ij", or `grad @ B.T`.
+///
+/// Labels only summed over in the differentiated input do not appear in the
+/// gradient Einsum, and are broadcast afterwards. Axes the input broadcast
+/// from a dimension of 1 are summed back. Repeated labels (diagonals) in the
+/// differentiated input are not supported.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EinsumGrad {
+    pub forward: Einsum,
+    pub input: usize,
+    gradient: Einsum,
+}
+
+impl EinsumGrad {
+    pub fn new(forward: Einsum, input: usize) -> TractResult<EinsumGrad> {
+        let gradient = forward.gradient_equation(input)?;
+        Ok(EinsumGrad { forward, input, gradient })
+    }
+
+    fn shape_t<T>(&self, grad: Arc<Tensor>, shape: &[usize]) -> TractResult<Arc<Tensor>>
+    where
+        T: Datum + Zero + Copy,
+    {
+        let mut grad = grad.into_tensor().into_array::<T>()?;
+        for (axis, label) in self.forward.inputs[self.input].iter().enumerate() {
+            if !self.gradient.output.contains(label) {
+                grad = grad.insert_axis(Axis(axis));
+            } else if shape[axis] == 1 && grad.shape()[axis] != 1 {
+                grad = grad.sum_axis(Axis(axis)).insert_axis(Axis(axis));
+            }
+        }
+        let grad = grad
+            .broadcast(shape)
+            .ok_or_else(|| format!("Can not broadcast Einsum gradient to {:?}", shape))?
+            .to_owned();
+        Ok(grad.into_arc_tensor())
+    }
+}
+
+impl Op for EinsumGrad {
+    fn name(&self) -> Cow<str> {
+        "EinsumGrad".into()
+    }
+
+    fn info(&self) -> TractResult<Vec<String>> {
+        let mut info = self.forward.info()?;
+        info.push(format!("input: #{}, gradient: {}", self.input, self.gradient.info()?[0]));
+        Ok(info)
+    }
+
+    impl_op_same_as!();
+    op_as_typed_op!();
+}
+
+impl StatelessOp for EinsumGrad {
+    fn eval(&self, mut inputs: TVec<Arc<Tensor>>) -> TractResult<TVec<Arc<Tensor>>> {
+        if inputs.len() != self.forward.inputs.len() + 1 {
+            bail!(
+                "EinsumGrad expects {} inputs and the output gradient, got {} tensors",
+                self.forward.inputs.len(),
+                inputs.len()
+            )
+        }
+        let differentiated = inputs.remove(self.input);
+        let grad = self.gradient.eval(inputs)?.remove(0);
+        let grad = dispatch_numbers!(Self::shape_t(differentiated.datum_type())(
+            self,
+            grad,
+            differentiated.shape()
+        ))?;
+        Ok(tvec!(grad))
+    }
+}
+
+impl InferenceRulesOp for EinsumGrad {
+    fn rules<'r, 'p: 'r, 's: 'r>(
+        &'s self,
+        s: &mut Solver<'r>,
+        inputs: &'p [TensorProxy],
+        outputs: &'p [TensorProxy],
+    ) -> InferenceResult {
+        check_input_arity(&inputs, self.forward.inputs.len() + 1)?;
+        check_output_arity(&outputs, 1)?;
+        for input in inputs {
+            s.equals(&input.datum_type, &outputs[0].datum_type)?;
+        }
+        for (input, labels) in inputs.iter().zip(self.forward.inputs.iter()) {
+            s.equals(&input.rank, labels.len() as i32)?;
+        }
+        s.equals(&inputs[self.forward.inputs.len()].rank, self.forward.output.len() as i32)?;
+        s.equals(&outputs[0].shape, &inputs[self.input].shape)
+    }
+
+    inference_op_as_op!();
+    to_typed!();
+}
+
+impl TypedOp for EinsumGrad {
+    fn output_facts(&self, inputs: &[&TypedFact]) -> TractResult<TVec<TypedFact>> {
+        let input = inputs[self.input];
+        Ok(tvec!(TypedFact::dt_shape(input.datum_type, input.shape.clone())?))
+    }
+
+    typed_op_as_op!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn range(shape: &[usize]) -> ArrayD<f32> {
+        let len = shape.iter().product::<usize>();
+        Array1::range(0.0, len as f32, 1.0).into_shape(shape).unwrap()
+    }
+
+    fn run(expr: &str, inputs: &[&ArrayD<f32>]) -> ArrayD<f32> {
+        let op = Einsum::new(expr).unwrap();
+        let inputs = inputs.iter().map(|&i| i.clone().into_arc_tensor()).collect();
+        let output = op.eval(inputs).unwrap().remove(0);
+        output.into_tensor().into_array::<f32>().unwrap()
+    }
+
+    #[test]
+    fn parse() {
+        let op = Einsum::new("bhqd, bhkd -> bhqk").unwrap();
+        assert_eq!(op.inputs, vec!("bhqd".chars().collect::<Vec<_>>(), "bhkd".chars().collect()));
+        assert_eq!(op.output, "bhqk".chars().collect::<Vec<_>>());
+        assert_eq!(op.summed(), vec!('d'));
+        assert_eq!(Einsum::new("jk,ij").unwrap().output, vec!('i', 'k'));
+        assert!(Einsum::new("ij,jk->ix").is_err());
+        assert!(Einsum::new("ij,jk->ii").is_err());
+        assert!(Einsum::new("...ij->ij").is_err());
+    }
+
+    #[test]
+    fn matmul() {
+        let a = range(&[2, 3]);
+        let b = range(&[3, 4]);
+        let expected = a
+            .view()
+            .into_dimensionality::<Ix2>()
+            .unwrap()
+            .dot(&b.view().into_dimensionality::<Ix2>().unwrap());
+        assert_eq!(run("ij,jk->ik", &[&a, &b]), expected.into_dyn());
+        assert_eq!(run("ij,jk", &[&a, &b]), run("ij,jk->ik", &[&a, &b]));
+        assert_eq!(run("ij,jk->ki", &[&a, &b]), run("ij,jk->ik", &[&a, &b]).t());
+    }
+
+    #[test]
+    fn batched_matmul() {
+        let a = range(&[2, 3, 4]);
+        let b = range(&[2, 4, 5]);
+        let c = run("bij,bjk->bik", &[&a, &b]);
+        assert_eq!(c.shape(), &[2, 3, 5]);
+        for batch in 0..2 {
+            let a = a.index_axis(Axis(0), batch).into_dimensionality::<Ix2>().unwrap();
+            let b = b.index_axis(Axis(0), batch).into_dimensionality::<Ix2>().unwrap();
+            assert_eq!(c.index_axis(Axis(0), batch), a.dot(&b).into_dyn());
+        }
+        // broadcasting the batch dimension
+        let b1 = b.slice_axis(Axis(0), (0..1).into()).to_owned();
+        let c = run("bij,bjk->bik", &[&a, &b1]);
+        assert_eq!(c.shape(), &[2, 3, 5]);
+        let b1 = b1.index_axis(Axis(0), 0).into_dimensionality::<Ix2>().unwrap().to_owned();
+        for batch in 0..2 {
+            let a = a.index_axis(Axis(0), batch).into_dimensionality::<Ix2>().unwrap();
+            assert_eq!(c.index_axis(Axis(0), batch), a.dot(&b1).into_dyn());
+        }
+    }
+
+    #[test]
+    fn attention_scores() {
+        let q = range(&[2, 3, 4, 5]);
+        let k = range(&[2, 3, 6, 5]);
+        let scores = run("bhqd,bhkd->bhqk", &[&q, &k]);
+        assert_eq!(scores.shape(), &[2, 3, 4, 6]);
+        for b in 0..2 {
+            for h in 0..3 {
+                let q = q
+                    .index_axis(Axis(0), b)
+                    .index_axis_move(Axis(0), h)
+                    .into_dimensionality::<Ix2>()
+                    .unwrap();
+                let k = k
+                    .index_axis(Axis(0), b)
+                    .index_axis_move(Axis(0), h)
+                    .into_dimensionality::<Ix2>()
+                    .unwrap();
+                assert_eq!(
+                    scores.index_axis(Axis(0), b).index_axis_move(Axis(0), h),
+                    q.dot(&k.t()).into_dyn()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn trace_and_sum() {
+        let a = range(&[3, 3]);
+        assert_eq!(run("ii->", &[&a]), arr0(0.0 + 4.0 + 8.0).into_dyn());
+        assert_eq!(run("ij->", &[&a]), arr0(36.0).into_dyn());
+        assert_eq!(run("ij->j", &[&a]), arr1(&[9.0, 12.0, 15.0]).into_dyn());
+    }
+
+    fn grad(expr: &str, input: usize, inputs: &[&ArrayD<f32>], g: &ArrayD<f32>) -> ArrayD<f32> {
+        let op = EinsumGrad::new(Einsum::new(expr).unwrap(), input).unwrap();
+        let inputs = inputs
+            .iter()
+            .chain(std::iter::once(&g))
+            .map(|&i| i.clone().into_arc_tensor())
+            .collect();
+        let output = op.eval(inputs).unwrap().remove(0);
+        output.into_tensor().into_array::<f32>().unwrap()
+    }
+
+    fn as2(a: &ArrayD<f32>) -> ArrayView2<f32> {
+        a.view().into_dimensionality::<Ix2>().unwrap()
+    }
+
+    #[test]
+    fn gradient_equations() -> TractResult<()> {
+        let matmul = Einsum::new("ij,jk->ik")?;
+        assert_eq!(matmul.gradient_equation(0)?, Einsum::new("jk,ik->ij")?);
+        assert_eq!(matmul.gradient_equation(1)?, Einsum::new("ij,ik->jk")?);
+        let reduce = Einsum::new("ij->i")?;
+        assert_eq!(reduce.gradient_equation(0)?.output, vec!('i'));
+        assert!(Einsum::new("ii->")?.gradient_equation(0).is_err());
+        assert!(matmul.gradient_equation(2).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn matmul_gradient() {
+        // a = torch.arange(6.).reshape(2, 3).requires_grad_()
+        // b = torch.arange(12.).reshape(3, 4).requires_grad_()
+        // (a @ b).sum().backward()
+        let a = range(&[2, 3]);
+        let b = range(&[3, 4]);
+        let ones = ArrayD::ones(vec![2, 4]);
+        assert_eq!(grad("ij,jk->ik", 0, &[&a, &b], &ones), arr2(&[[6., 22., 38.]; 2]).into_dyn());
+        assert_eq!(
+            grad("ij,jk->ik", 1, &[&a, &b], &ones),
+            arr2(&[[3.; 4], [5.; 4], [7.; 4]]).into_dyn()
+        );
+        let g = range(&[2, 4]);
+        assert_eq!(grad("ij,jk->ik", 0, &[&a, &b], &g), as2(&g).dot(&as2(&b).t()).into_dyn());
+        assert_eq!(grad("ij,jk->ik", 1, &[&a, &b], &g), as2(&a).t().dot(&as2(&g)).into_dyn());
+    }
+
+    #[test]
+    fn outer_product_gradient() {
+        // a = torch.tensor([1., 2., 3.], requires_grad=True)
+        // b = torch.tensor([4., 5.], requires_grad=True)
+        // torch.outer(a, b).backward(torch.arange(6.).reshape(3, 2))
+        let a = arr1(&[1f32, 2., 3.]).into_dyn();
+        let b = arr1(&[4f32, 5.]).into_dyn();
+        let g = range(&[3, 2]);
+        assert_eq!(grad("i,j->ij", 0, &[&a, &b], &g), arr1(&[5., 23., 41.]).into_dyn());
+        assert_eq!(grad("i,j->ij", 1, &[&a, &b], &g), arr1(&[16., 22.]).into_dyn());
+    }
+
+    #[test]
+    fn batched_matmul_gradient() {
+        // b is broadcast over the batch, so its gradient sums over it, as
+        // torch.matmul autograd does
+        let a = range(&[2, 3, 4]);
+        let b = range(&[1, 4, 5]);
+        let g = range(&[2, 3, 5]);
+        let grad_a = grad("bij,bjk->bik", 0, &[&a, &b], &g);
+        let grad_b = grad("bij,bjk->bik", 1, &[&a, &b], &g);
+        assert_eq!(grad_a.shape(), &[2, 3, 4]);
+        assert_eq!(grad_b.shape(), &[1, 4, 5]);
+        let b0 = b.index_axis(Axis(0), 0).into_dimensionality::<Ix2>().unwrap();
+        let mut expected_b = Array2::<f32>::zeros((4, 5));
+        for batch in 0..2 {
+            let a = a.index_axis(Axis(0), batch).into_dimensionality::<Ix2>().unwrap();
+            let g = g.index_axis(Axis(0), batch).into_dimensionality::<Ix2>().unwrap();
+            assert_eq!(grad_a.index_axis(Axis(0), batch), g.dot(&b0.t()).into_dyn());
+            expected_b = expected_b + a.t().dot(&g);
+        }
+        assert_eq!(grad_b.index_axis(Axis(0), 0), expected_b.into_dyn());
+    }
+
+    #[test]
+    fn reduction_gradient_is_broadcast() {
+        let a = range(&[2, 3]);
+        let g = arr1(&[1f32, 2.]).into_dyn();
+        assert_eq!(grad("ij->i", 0, &[&a], &g), arr2(&[[1., 1., 1.], [2., 2., 2.]]).into_dyn());
+        assert_eq!(
+            grad("ij->", 0, &[&a], &arr0(3f32).into_dyn()),
+            ArrayD::from_elem(vec![2, 3], 3.)
+        );
+    }
+
+    #[test]
+    fn typed_output_facts() -> TractResult<()> {
+        let op = Einsum::new("bij,jk->bik")?;
+        let a =
+            TypedFact::dt_shape(f32::datum_type(), [TDim::s(), 3.to_dim(), 4.to_dim()].as_ref())?;
+        let b = TypedFact::dt_shape(f32::datum_type(), [4usize, 5].as_ref())?;
+        let facts = op.output_facts(&[&a, &b])?;
+        assert_eq!(facts[0].shape.to_tvec(), tvec!(TDim::s(), 3.to_dim(), 5.to_dim()));
+        let bad = TypedFact::dt_shape(f32::datum_type(), [3usize, 5].as_ref())?;
+        assert!(op.output_facts(&[&a, &bad]).is_err());
+        Ok(())
+    }
+}
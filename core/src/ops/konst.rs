@@ -2,7 +2,7 @@ use crate::internal::*;
 
 #[derive(Debug, Clone, new)]
 pub struct Const {
-    value: Arc<Tensor>,
+    pub value: Arc<Tensor>,
 }
 
 impl Const {
@@ -304,11 +304,13 @@ macro_rules! dispatch_datum {
             DatumType::I32  => $($path)::*::<i32>($($args),*),
             DatumType::I64  => $($path)::*::<i64>($($args),*),
             DatumType::F16  => $($path)::*::<f16>($($args),*),
+            DatumType::BF16 => $($path)::*::<bf16>($($args),*),
             DatumType::F32  => $($path)::*::<f32>($($args),*),
             DatumType::F64  => $($path)::*::<f64>($($args),*),
             DatumType::Blob => $($path)::*::<Blob>($($args),*),
             DatumType::TDim => $($path)::*::<TDim>($($args),*),
             DatumType::String => $($path)::*::<String>($($args),*),
+            DatumType::I4 | DatumType::U4 => bail!("{:?} values are packed, and can not be accessed one by one", $dt),
         }
     } }
 }
@@ -326,6 +328,7 @@ macro_rules! dispatch_copy {
             DatumType::I32  => $($path)::*::<i32>($($args),*),
             DatumType::I64  => $($path)::*::<i64>($($args),*),
             DatumType::F16  => $($path)::*::<f16>($($args),*),
+            DatumType::BF16 => $($path)::*::<bf16>($($args),*),
             DatumType::F32  => $($path)::*::<f32>($($args),*),
             DatumType::F64  => $($path)::*::<f64>($($args),*),
             _ => bail!("{:?} is not Copy", $dt)
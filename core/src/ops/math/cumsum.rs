@@ -0,0 +1,178 @@
+use crate::internal::*;
+use ndarray::*;
+use num_traits::Zero;
+
+/// Cumulative sum of the data along an axis, as in ONNX.
+///
+/// The axis is given as the second input. In `exclusive` mode each element
+/// is the sum of the elements strictly before it, and in `reverse` mode the
+/// summation runs from the end of the axis.
+#[derive(Debug, Clone, new, Default)]
+pub struct CumSum {
+    pub exclusive: bool,
+    pub reverse: bool,
+}
+
+impl CumSum {
+    fn axis(axis: &Tensor, rank: usize) -> TractResult<usize> {
+        let axis = axis.cast_to::<i64>()?;
+        let axis = match axis.as_slice::<i64>()? {
+            &[axis] => axis,
+            axis => bail!("CumSum expects a single axis, got {:?}", axis),
+        };
+        if 0 <= axis && axis < rank as i64 {
+            Ok(axis as usize)
+        } else if -(rank as i64) <= axis && axis < 0 {
+            Ok((axis + rank as i64) as usize)
+        } else {
+            bail!("Illegal axis {} for rank {}", axis, rank)
+        }
+    }
+
+    fn scan<'a, T, I>(&self, xs: I)
+    where
+        T: Datum + Zero + Copy,
+        I: Iterator<Item = &'a mut T>,
+    {
+        let mut acc = T::zero();
+        for x in xs {
+            let v = *x;
+            if self.exclusive {
+                *x = acc;
+                acc = acc + v;
+            } else {
+                acc = acc + v;
+                *x = acc;
+            }
+        }
+    }
+
+    fn eval_t<T: Datum + Zero + Copy>(
+        &self,
+        data: Arc<Tensor>,
+        axis: usize,
+    ) -> TractResult<Arc<Tensor>> {
+        let mut output = data.into_tensor();
+        if output.rank() == 1 {
+            // contiguous fast path: a plain prefix sum over the slice
+            let xs = output.as_slice_mut::<T>()?;
+            if self.reverse {
+                self.scan(xs.iter_mut().rev())
+            } else {
+                self.scan(xs.iter_mut())
+            }
+        } else {
+            for mut lane in output.to_array_view_mut::<T>()?.lanes_mut(Axis(axis)) {
+                if self.reverse {
+                    self.scan(lane.iter_mut().rev())
+                } else {
+                    self.scan(lane.iter_mut())
+                }
+            }
+        }
+        Ok(output.into_arc_tensor())
+    }
+}
+
+impl Op for CumSum {
+    fn name(&self) -> Cow<str> {
+        "CumSum".into()
+    }
+
+    fn info(&self) -> TractResult<Vec<String>> {
+        Ok(vec![format!("exclusive: {}, reverse: {}", self.exclusive, self.reverse)])
+    }
+
+    op_as_typed_op!();
+    not_a_pulsed_op!();
+}
+
+impl StatelessOp for CumSum {
+    fn eval(&self, mut inputs: TVec<Arc<Tensor>>) -> TractResult<TVec<Arc<Tensor>>> {
+        let (data, axis) = args_2!(inputs);
+        let axis = Self::axis(&axis, data.rank())?;
+        let output = match data.datum_type() {
+            DatumType::F32 => self.eval_t::<f32>(data, axis)?,
+            DatumType::F64 => self.eval_t::<f64>(data, axis)?,
+            DatumType::I32 => self.eval_t::<i32>(data, axis)?,
+            DatumType::I64 => self.eval_t::<i64>(data, axis)?,
+            dt => bail!("CumSum does not support {:?}", dt),
+        };
+        Ok(tvec!(output))
+    }
+}
+
+impl InferenceRulesOp for CumSum {
+    fn rules<'r, 'p: 'r, 's: 'r>(
+        &'s self,
+        s: &mut Solver<'r>,
+        inputs: &'p [TensorProxy],
+        outputs: &'p [TensorProxy],
+    ) -> InferenceResult {
+        check_input_arity(&inputs, 2)?;
+        check_output_arity(&outputs, 1)?;
+        s.equals(&inputs[0].datum_type, &outputs[0].datum_type)?;
+        s.equals(&inputs[0].shape, &outputs[0].shape)?;
+        Ok(())
+    }
+
+    inference_op_as_op!();
+    to_typed!();
+}
+
+impl TypedOp for CumSum {
+    fn output_facts(&self, inputs: &[&TypedFact]) -> TractResult<TVec<TypedFact>> {
+        Ok(tvec!(TypedFact::dt_shape(inputs[0].datum_type, inputs[0].shape.clone())?))
+    }
+
+    typed_op_as_op!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cumsum(op: CumSum, data: Tensor, axis: i64) -> TractResult<Arc<Tensor>> {
+        Ok(op.eval(tvec!(data.into(), rctensor0(axis)))?.remove(0))
+    }
+
+    // Reference values from numpy.cumsum, shifted and flipped as the ONNX
+    // exclusive and reverse attributes require.
+    #[test]
+    fn inclusive_forward() -> TractResult<()> {
+        let output = cumsum(CumSum::new(false, false), tensor1(&[1f32, 2., 3., 4., 5.]), 0)?;
+        assert_eq!(*output, tensor1(&[1f32, 3., 6., 10., 15.]));
+        Ok(())
+    }
+
+    #[test]
+    fn exclusive_forward() -> TractResult<()> {
+        let output = cumsum(CumSum::new(true, false), tensor1(&[1f64, 2., 3., 4., 5.]), 0)?;
+        assert_eq!(*output, tensor1(&[0f64, 1., 3., 6., 10.]));
+        Ok(())
+    }
+
+    #[test]
+    fn inclusive_reverse() -> TractResult<()> {
+        let output = cumsum(CumSum::new(false, true), tensor1(&[1i32, 2, 3, 4, 5]), -1)?;
+        assert_eq!(*output, tensor1(&[15i32, 14, 12, 9, 5]));
+        Ok(())
+    }
+
+    #[test]
+    fn exclusive_reverse() -> TractResult<()> {
+        let output = cumsum(CumSum::new(true, true), tensor1(&[1i64, 2, 3, 4, 5]), 0)?;
+        assert_eq!(*output, tensor1(&[14i64, 12, 9, 5, 0]));
+        Ok(())
+    }
+
+    #[test]
+    fn along_axes() -> TractResult<()> {
+        let data = tensor2(&[[1f32, 2., 3.], [4., 5., 6.]]);
+        let output = cumsum(CumSum::default(), data.clone(), 0)?;
+        assert_eq!(*output, tensor2(&[[1f32, 2., 3.], [5., 7., 9.]]));
+        let output = cumsum(CumSum::new(true, true), data, 1)?;
+        assert_eq!(*output, tensor2(&[[5f32, 3., 0.], [11., 6., 0.]]));
+        Ok(())
+    }
+}
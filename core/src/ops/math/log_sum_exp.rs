@@ -0,0 +1,175 @@
+use crate::internal::*;
+use crate::ops::array::RmDims;
+use crate::ops::math::{Exp, Ln};
+use crate::ops::nn::{Reducer, TypedReduce};
+use crate::optim::{is_element_wise, single_use};
+use ndarray::ArrayViewD;
+use num_traits::Float;
+
+/// `log(sum(exp(v)))`, computed as `m + log(sum(exp(v - m)))` with `m` the
+/// max of `v`, so that no exponential overflows.
+///
+/// An infinite max is the result: this avoids the `inf - inf` of the shifted
+/// sum, and gives `-inf` on an empty or all `-inf` input, as `scipy` does.
+pub(crate) fn log_sum_exp_t<'a, T>(v: ArrayViewD<'a, T>) -> T
+where
+    T: Copy + Datum + Float,
+{
+    let max = v.fold(T::neg_infinity(), |acc, &v| acc.max(v));
+    if max.is_infinite() {
+        return max;
+    }
+    max + v.fold(T::zero(), |acc, &v| acc + (v - max).exp()).ln()
+}
+
+fn is_sum(op: &TypedReduce) -> bool {
+    if let Reducer::Sum = op.reducer {
+        true
+    } else {
+        false
+    }
+}
+
+/// Replace `Ln(ReduceSum(Exp(x)))`, which overflows as soon as a value
+/// reaches 89 in f32, by a `LogSumExp` reduction over the same axes.
+///
+/// A `RmDims` between the reduction and the logarithm, from a reduction
+/// without `keepdims`, is kept after the fused reduction.
+pub(crate) fn fuse_log_sum_exp(
+    model: &TypedModel,
+    node: &TypedNode,
+) -> TractResult<Option<TypedModelPatch>> {
+    if !is_element_wise::<Ln>(node) {
+        return Ok(None);
+    }
+    let mut prec = match single_use(model, node.inputs[0]) {
+        Some(prec) => prec,
+        None => return Ok(None),
+    };
+    let rm_dims = prec.op_as::<RmDims>();
+    if rm_dims.is_some() {
+        prec = match single_use(model, prec.inputs[0]) {
+            Some(prec) => prec,
+            None => return Ok(None),
+        };
+    }
+    let reduce = match prec.op_as::<TypedReduce>() {
+        Some(op) if is_sum(op) => op,
+        _ => return Ok(None),
+    };
+    let exp = match single_use(model, prec.inputs[0]) {
+        Some(exp) if is_element_wise::<Exp>(exp) => exp,
+        _ => return Ok(None),
+    };
+    let dt = model.outlet_fact(exp.inputs[0])?.datum_type;
+    if !(dt == f32::datum_type() || dt == f64::datum_type()) {
+        return Ok(None);
+    }
+    let mut patch = TypedModelPatch::default();
+    let x = patch.tap_model(model, exp.inputs[0])?;
+    let op = TypedReduce::new(reduce.axes.clone(), Reducer::LogSumExp);
+    let fused = if let Some(rm_dims) = rm_dims {
+        let wire = patch.wire_node(&*prec.name, op, &[x])?;
+        patch.wire_node(&*node.name, rm_dims.clone(), &wire)?[0]
+    } else {
+        patch.wire_node(&*node.name, op, &[x])?[0]
+    };
+    patch.shunt_outside(OutletId::new(node.id, 0), fused)?;
+    Ok(Some(patch))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ops::math::{exp, ln};
+    use crate::ops::nn::Reduce;
+
+    /// Rows scaled from 1e-2 to 1e3.
+    fn input() -> Tensor {
+        let scales = [1e-2f32, 1e-1, 1.0, 10.0, 100.0, 1000.0];
+        ndarray::Array2::from_shape_fn((6, 4), |(i, j)| scales[i] * [-1.0, 0.5, 1.0, 2.0][j])
+            .into_tensor()
+    }
+
+    // scipy.special.logsumexp(input(), axis=1)
+    const BY_ROW: [f64; 6] =
+        [1.39260288589735, 1.454579493369655, 2.495181898085856, 20.0000457047877, 200.0, 2000.0];
+
+    // scipy.special.logsumexp(input(), axis=0)
+    const BY_COLUMN: [f64; 4] = [0.8166083286860941, 500.0, 1000.0, 2000.0];
+
+    fn f32s(xs: &[f64]) -> Vec<f32> {
+        xs.iter().map(|&x| x as f32).collect()
+    }
+
+    fn reduce(axes: Option<Vec<i64>>, keep_dims: bool, input: Tensor) -> TractResult<Arc<Tensor>> {
+        let op = Reduce::new(axes, keep_dims, Reducer::LogSumExp);
+        Ok(op.eval(tvec!(input.into_arc_tensor()))?.remove(0))
+    }
+
+    #[test]
+    fn matches_scipy() -> TractResult<()> {
+        let found = reduce(Some(vec![1]), false, input())?;
+        found.close_enough(&tensor1(&f32s(&BY_ROW)), true)?;
+        let found = reduce(Some(vec![-2]), false, input())?;
+        found.close_enough(&tensor1(&f32s(&BY_COLUMN)), true)?;
+        let input = input().cast_to::<f64>()?.into_owned();
+        let found = reduce(Some(vec![1]), false, input)?;
+        found.close_enough(&tensor1(&BY_ROW), true)
+    }
+
+    #[test]
+    fn keep_dims_and_all_axes() -> TractResult<()> {
+        let found = reduce(Some(vec![1]), true, input())?;
+        assert_eq!(found.shape(), &[6, 1]);
+        let found = reduce(None, false, input())?;
+        assert_eq!(found.shape(), &[] as &[usize]);
+        found.close_enough(&tensor0(2000f32), true)
+    }
+
+    #[test]
+    fn infinities() -> TractResult<()> {
+        let input = tensor2(&[
+            [f32::NEG_INFINITY, f32::NEG_INFINITY],
+            [f32::NEG_INFINITY, 0.0],
+            [f32::INFINITY, 0.0],
+        ]);
+        let found = reduce(Some(vec![1]), false, input)?;
+        assert_eq!(found, rctensor1(&[f32::NEG_INFINITY, 0.0, f32::INFINITY]));
+        Ok(())
+    }
+
+    fn naive(keep_dims: bool) -> TractResult<TypedModel> {
+        let mut model = TypedModel::default();
+        let x = model.add_source("x", TypedFact::dt_shape(f32::datum_type(), [6, 4].as_ref())?)?;
+        let e = model.wire_node("exp", exp(), &[x])?;
+        let mut wire = model.wire_node("sum", TypedReduce::new(tvec!(1), Reducer::Sum), &e)?;
+        if !keep_dims {
+            wire = model.wire_node("rm", RmDims::new(vec![1]), &wire)?;
+        }
+        let y = model.wire_node("ln", ln(), &wire)?;
+        model.set_output_outlets(&y)?;
+        Ok(model)
+    }
+
+    #[test]
+    fn fuse_naive_log_sum_exp() -> TractResult<()> {
+        for &keep_dims in &[true, false] {
+            let model = naive(keep_dims)?;
+            let overflown = SimplePlan::new(&model)?.run(tvec!(input()))?.remove(0);
+            assert!(overflown.as_slice::<f32>()?[5].is_infinite());
+            let model = model.declutter()?;
+            let op = model.nodes().iter().find_map(|n| n.op_as::<TypedReduce>()).unwrap();
+            assert!(if let Reducer::LogSumExp = op.reducer { true } else { false });
+            assert_eq!(model.nodes().len(), 3 - keep_dims as usize, "{:#?}", model);
+            let found = SimplePlan::new(&model)?.run(tvec!(input()))?.remove(0);
+            if keep_dims {
+                let expected = ndarray::arr1(&f32s(&BY_ROW)).into_shape((6, 1))?;
+                found.close_enough(&expected.into_tensor(), true)?;
+            } else {
+                found.close_enough(&tensor1(&f32s(&BY_ROW)), true)?;
+            }
+        }
+        Ok(())
+    }
+}
@@ -3,25 +3,32 @@ use num_traits::{Float, Zero};
 
 use super::binary::*;
 
+mod cumsum;
+mod log_sum_exp;
+mod shrink;
+pub use self::cumsum::CumSum;
+pub(crate) use self::log_sum_exp::{fuse_log_sum_exp, log_sum_exp_t};
+pub use self::shrink::{shrink, Shrink};
+
 bin_to_super_type!(add, Add,
         flip:commute,
         validation: Validation::Rounding,
-     [f32, i8, i16, i32, i64, u8, u16, f16, f64, TDim] => |c, a, b| *c = a.clone() + b);
+     [f32, i8, i16, i32, i64, u8, u16, f16, bf16, f64, TDim] => |c, a, b| *c = a.clone() + b);
 bin_to_super_type!(sub, Sub, flip:flip_sub,
-     [f32, i8, i16, i32, i64, u8, u16, f16, f64, TDim] => |c, a, b| *c = a.clone() - b);
+     [f32, i8, i16, i32, i64, u8, u16, f16, bf16, f64, TDim] => |c, a, b| *c = a.clone() - b);
 
 bin_to_super_type!(mul, Mul,
         cost: |dt| tvec!((Cost::FMA(dt), 1)),
         declutter_unary: declutter_mul_as_shift,
         flip: commute,
-     [f32, i8, i16, i32, i64, u8, u16, f16, f64, TDim] => |c, a, b| *c = a.clone() * b);
+     [f32, i8, i16, i32, i64, u8, u16, f16, bf16, f64, TDim] => |c, a, b| *c = a.clone() * b);
 bin_to_super_type!(div, Div,
         cost: |dt| tvec!((Cost::Div(dt), 1)),
         declutter_bin: declutter_div_as_shift,
         flip: flip_div,
-     [f32, i8, i16, i32, i64, u8, u16, f16, f64, TDim] => |c, a, b| *c = a.clone() / b);
+     [f32, i8, i16, i32, i64, u8, u16, f16, bf16, f64, TDim] => |c, a, b| *c = a.clone() / b);
 bin_to_super_type!(rem, Rem,
-     [f32, i8, i16, i32, i64, u8, u16, f16, f64, TDim] => |c, a, b| *c = a.clone() % b);
+     [f32, i8, i16, i32, i64, u8, u16, f16, bf16, f64, TDim] => |c, a, b| *c = a.clone() % b);
 bin_to_super_type!(min, Min, flip:commute,
      [f32, f64] => |c,a,b| *c = a.min(*b),
      [i8, i16, i32, i64, u8, u16] => |c, a, b| *c = *a.min(b));
@@ -109,7 +116,7 @@ fn declutter_as_shift(
                 model,
                 node,
                 &node.inputs[0..=0],
-                UnaryOp { a: shift.into_arc_tensor(), mini_op },
+                UnaryOp::new(mini_op, shift.into_arc_tensor()),
             )?));
         }
     }
@@ -0,0 +1,64 @@
+use crate::internal::*;
+use num_traits::{AsPrimitive, Float};
+
+// Shrink, as ONNX: `x + bias` below `-lambd`, `x - bias` above `lambd`, and
+// zero in between. With the default zero bias, this is the hard shrink.
+//
+// tract-linalg has no SIMD kernel for it yet: f32 runs the same scalar loop
+// as the other types.
+element_wise!(shrink, Shrink { bias: f32, lambd: f32 },
+    [f16, f32, f64] => |s, xs| {
+        xs.iter_mut().for_each(|x| *x = shrink_t(*x, s.bias, s.lambd));
+        Ok(())
+});
+
+fn shrink_t<T>(x: T, bias: f32, lambd: f32) -> T
+where
+    T: Datum + Float,
+    f32: AsPrimitive<T>,
+{
+    if x < -lambd.as_() {
+        x + bias.as_()
+    } else if x > lambd.as_() {
+        x - bias.as_()
+    } else {
+        T::zero()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ops::element_wise::ElementWiseOp;
+
+    const INPUT: [f32; 9] = [-3.0, -1.5, -0.5, -0.25, 0.0, 0.25, 0.5, 1.5, 3.0];
+
+    fn run<T: Datum + Copy>(op: ElementWiseOp, xs: &[T]) -> TractResult<Arc<Tensor>> {
+        Ok(op.eval(tvec!(rctensor1(xs)))?.remove(0))
+    }
+
+    #[test]
+    fn hard_shrink() -> TractResult<()> {
+        let found = run(shrink(0.0, 0.5), &INPUT)?;
+        let expected = rctensor1(&[-3f32, -1.5, 0.0, 0.0, 0.0, 0.0, 0.0, 1.5, 3.0]);
+        assert_eq!(found, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn soft_shrink() -> TractResult<()> {
+        let found = run(shrink(1.0, 1.0), &INPUT)?;
+        let expected = rctensor1(&[-2f32, -0.5, 0.0, 0.0, 0.0, 0.0, 0.0, 0.5, 2.0]);
+        assert_eq!(found, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn f32_matches_f64() -> TractResult<()> {
+        let input: Vec<f64> = INPUT.iter().map(|&x| x as f64).collect();
+        let found = run(shrink(0.2, 0.25), &INPUT)?;
+        let expected = run(shrink(0.2, 0.25), &input)?;
+        assert_eq!(found, expected.cast_to::<f32>()?.into_owned().into_arc_tensor());
+        Ok(())
+    }
+}
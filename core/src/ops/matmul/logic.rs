@@ -3,6 +3,7 @@ use std::fmt;
 use std::ops::{Add, Mul};
 
 use crate::internal::*;
+use crate::ops::cost::{self, ComputeCost, OpCost};
 use crate::ops::matmul::*;
 use crate::ops::quant::QParams;
 use ndarray::*;
@@ -17,10 +18,6 @@ fn eval(
 ) -> TractResult<Tensor> {
     if let Some(q) = q_params {
         if (a.datum_type(), b.datum_type()) == (i8::datum_type(), i8::datum_type()) {
-            return eval_t(a, b, a_trans, b_trans, c_trans, q_params, &|m, k, n| {
-                MMMWrapper::Quant((tract_linalg::ops().qmmm_i8_i32)(m, k, n))
-            });
-        } else if (a.datum_type(), b.datum_type()) == (i8::datum_type(), i8::datum_type()) {
             if q.c_datum_type == i32::datum_type() {
                 return eval_t(a, b, a_trans, b_trans, c_trans, q_params, &|m, k, n| {
                     MMMWrapper::Quant((tract_linalg::ops().qmmm_i8_i32)(m, k, n))
@@ -45,6 +42,15 @@ fn eval(
         return eval_t(a, b, a_trans, b_trans, c_trans, q_params, &|m, k, n| {
             MMMWrapper::Plain((tract_linalg::ops().smmm)(m, k, n))
         });
+    } else if (a.datum_type(), b.datum_type()) == (f16::datum_type(), f16::datum_type()) {
+        // no half precision kernel: widen to f32, and narrow the result back
+        let a = a.cast_to::<f32>()?;
+        let b = b.cast_to::<f32>()?;
+        return eval(&a, &b, a_trans, b_trans, c_trans, None)?.to_f16();
+    } else if (a.datum_type(), b.datum_type()) == (bf16::datum_type(), bf16::datum_type()) {
+        let a = a.cast_to::<f32>()?;
+        let b = b.cast_to::<f32>()?;
+        return eval(&a, &b, a_trans, b_trans, c_trans, None)?.cast_to_bf16();
     }
     bail!(
         "Unsupported combination for MatMul eval (a: {:?}, b:{:?} q:{:?})",
@@ -298,11 +304,11 @@ where
     }
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct MatMul {
-    a_trans: bool,
-    b_trans: bool,
-    c_trans: bool,
+    pub(crate) a_trans: bool,
+    pub(crate) b_trans: bool,
+    pub(crate) c_trans: bool,
     q_params: Option<QParams>,
 }
 
@@ -322,6 +328,10 @@ impl MatMul {
     pub fn with_q_params(self, q_params: QParams) -> MatMul {
         MatMul { q_params: Some(q_params), ..self }
     }
+
+    pub fn q_params(&self) -> Option<&QParams> {
+        self.q_params.as_ref()
+    }
 }
 
 impl Op for MatMul {
@@ -329,6 +339,7 @@ impl Op for MatMul {
         "MatMul".into()
     }
 
+    impl_op_same_as!();
     op_as_typed_op!();
     not_a_pulsed_op!();
 }
@@ -410,11 +421,18 @@ impl TypedOp for MatMul {
         let t_konst = [self.a_trans, self.b_trans][konst_ix] ^ flip;
         let t_var = [self.b_trans, self.a_trans][konst_ix] ^ flip;
         let konst = model.outlet_fact(node.inputs[konst_ix])?.konst.clone().unwrap();
+        let mut q_params = self.q_params.clone();
+        if flip {
+            // the constant becomes the A operand, and the variable input the B one
+            if let Some(q) = q_params.as_mut() {
+                std::mem::swap(&mut q.zero_point_a, &mut q.zero_point_b);
+            }
+        }
         let patch = TypedModelPatch::replace_single_op(
             model,
             node,
             &node.inputs[var_ix..][..1],
-            MatMulUnary::new(konst, t_konst, t_var, self.c_trans ^ flip, self.q_params.clone()),
+            MatMulUnary::new(konst, t_konst, t_var, self.c_trans ^ flip, q_params),
         )?;
         return Ok(Some(patch));
     }
@@ -429,16 +447,46 @@ impl TypedOp for MatMul {
         )
     }
 
+    fn as_op_cost(&self) -> Option<&dyn OpCost> {
+        Some(self)
+    }
+
     typed_op_as_op!();
 }
 
+impl OpCost for MatMul {
+    fn compute_cost(&self, inputs: &[&TypedFact]) -> TractResult<ComputeCost> {
+        Ok(ComputeCost {
+            flops: cost::hinted_flops(self, inputs)?,
+            memory_bytes: cost::io_bytes(self, inputs)?,
+        })
+    }
+}
+
 #[derive(Debug, Clone, new)]
 pub struct MatMulUnary {
-    a: Arc<Tensor>,
-    a_trans: bool,
-    b_trans: bool,
-    c_trans: bool,
-    q_params: Option<QParams>,
+    pub a: Arc<Tensor>,
+    pub a_trans: bool,
+    pub b_trans: bool,
+    pub c_trans: bool,
+    pub q_params: Option<QParams>,
+}
+
+impl MatMulUnary {
+    /// The constant A operand.
+    pub fn a(&self) -> &Arc<Tensor> {
+        &self.a
+    }
+
+    /// The equivalent MatMul, taking A as its first input.
+    pub fn to_binary(&self) -> MatMul {
+        MatMul {
+            a_trans: self.a_trans,
+            b_trans: self.b_trans,
+            c_trans: self.c_trans,
+            q_params: self.q_params.clone(),
+        }
+    }
 }
 
 impl Op for MatMulUnary {
@@ -628,6 +676,10 @@ impl TypedOp for MatMulUnary {
         )
     }
 
+    fn as_op_cost(&self) -> Option<&dyn OpCost> {
+        Some(self)
+    }
+
     fn pulsify(
         &self,
         _source: &NormalizedModel,
@@ -650,6 +702,12 @@ impl TypedOp for MatMulUnary {
         node: &TypedNode,
     ) -> TractResult<Option<TypedModelPatch>> {
         let b = args_1!(model.node_input_facts(node.id)?);
+        if (self.a.datum_type(), b.datum_type) == (f16::datum_type(), f16::datum_type())
+            || (self.a.datum_type(), b.datum_type) == (bf16::datum_type(), bf16::datum_type())
+        {
+            // f16 and bf16 are evaluated by widening to f32, there is no kernel to plug
+            return Ok(None);
+        }
         if let Some(b_shape) = b.shape.as_finite() {
             let patch =
                 if (self.a.datum_type(), b.datum_type) == (f32::datum_type(), f32::datum_type()) {
@@ -698,6 +756,40 @@ impl TypedOp for MatMulUnary {
                         self.q_params.as_ref(),
                         &|m, k, n| MMMWrapper::Quant((tract_linalg::ops().qmmm_i8_i32)(m, k, n)),
                     )?
+                } else if (
+                    self.a.datum_type(),
+                    b.datum_type,
+                    self.q_params.as_ref().map(|q| q.c_datum_type),
+                ) == (u8::datum_type(), u8::datum_type(), Some(u8::datum_type()))
+                {
+                    new_mat_mul_unary_finite(
+                        model,
+                        node,
+                        self.a.clone(),
+                        b_shape,
+                        self.a_trans,
+                        self.b_trans,
+                        self.c_trans,
+                        self.q_params.as_ref(),
+                        &|m, k, n| MMMWrapper::Quant((tract_linalg::ops().qmmm_u8_u8)(m, k, n)),
+                    )?
+                } else if (
+                    self.a.datum_type(),
+                    b.datum_type,
+                    self.q_params.as_ref().map(|q| q.c_datum_type),
+                ) == (u8::datum_type(), u8::datum_type(), Some(i32::datum_type()))
+                {
+                    new_mat_mul_unary_finite(
+                        model,
+                        node,
+                        self.a.clone(),
+                        b_shape,
+                        self.a_trans,
+                        self.b_trans,
+                        self.c_trans,
+                        self.q_params.as_ref(),
+                        &|m, k, n| MMMWrapper::Quant((tract_linalg::ops().qmmm_u8_i32)(m, k, n)),
+                    )?
                 } else {
                     bail!(
                         "Unsupported combination for MatMul codegen (a: {:?}, b:{:?}, q: {:?})",
@@ -714,6 +806,16 @@ impl TypedOp for MatMulUnary {
     typed_op_as_op!();
 }
 
+impl OpCost for MatMulUnary {
+    fn compute_cost(&self, inputs: &[&TypedFact]) -> TractResult<ComputeCost> {
+        let a = (self.a.len() * self.a.datum_type().size_of()) as u64;
+        Ok(ComputeCost {
+            flops: cost::hinted_flops(self, inputs)?,
+            memory_bytes: cost::io_bytes(self, inputs)? + a,
+        })
+    }
+}
+
 impl PulsedOp for MatMulUnary {
     fn pulsed_output_facts(&self, inputs: &[&PulsedFact]) -> TractResult<TVec<PulsedFact>> {
         let mut fact = inputs[0].clone();
@@ -832,6 +934,8 @@ where
     } else {
         None
     };
+    let mut fused_ops = None;
+    geo.mm.precompute_a_terms(&packed_as, &mut fused_ops)?;
     wire = patch.wire_node(
         format!("{}-matmatmul", &*node.name),
         phy::MatMatMulUnaryFinite {
@@ -840,7 +944,7 @@ where
             c_fact: TypedFact::dt_shape(TC::datum_type(), &*geo.final_c_shape)?,
             c_prefix_dim_and_stride,
             packed_as,
-            fused_ops: None,
+            fused_ops,
             mmm: geo.mm,
         },
         &[wire],
@@ -873,6 +977,7 @@ fn cost<A: ToDim + Clone, B: ToDim + Clone>(
 #[cfg(test)]
 mod test {
     use super::*;
+    use proptest::prelude::*;
 
     #[test]
     fn bin() {
@@ -893,4 +998,129 @@ mod test {
         let c_found = op.eval(tvec!(b, a)).unwrap().pop().unwrap();
         c.close_enough(&c_found, true).unwrap();
     }
+
+    fn dense(dt: DatumType) -> TractResult<TypedModel> {
+        let w =
+            tensor2(&[[0.5f32, -1.25, 2.0], [0.75, 0.1, -0.3], [1.5, 0.2, 0.05], [-0.6, 0.9, 1.1]]);
+        let bias = tensor1(&[0.1f32, -0.2, 0.3]);
+        let mut model = TypedModel::default();
+        let x = model.add_source("x", TypedFact::dt_shape(dt, [2usize, 4].as_ref())?)?;
+        let w = model.add_const("w", w.cast_to_dt(dt)?.into_owned())?;
+        let bias = model.add_const("bias", bias.cast_to_dt(dt)?.into_owned())?;
+        let y = model.wire_node("mm", MatMul::default(), &[x, w])?[0];
+        let y = model.wire_node("bias", crate::ops::math::add::bin(), &[y, bias])?[0];
+        model.set_output_outlets(&[y])?;
+        model.into_optimized()
+    }
+
+    #[test]
+    fn f16_matches_f32() -> TractResult<()> {
+        let x = tensor2(&[[1f32, 2.0, -0.5, 0.25], [-3.0, 0.125, 4.0, 1.0]]);
+        let expected = SimplePlan::new(dense(DatumType::F32)?)?.run(tvec!(x.clone()))?;
+        let found = SimplePlan::new(dense(DatumType::F16)?)?.run(tvec!(x.to_f16()?))?;
+        assert_eq!(found[0].datum_type(), DatumType::F16);
+        let found = found[0].cast_to::<f32>()?;
+        for (f, e) in found.as_slice::<f32>()?.iter().zip(expected[0].as_slice::<f32>()?) {
+            assert!((f - e).abs() <= 1e-2 * e.abs().max(1.0), "{} != {}", f, e);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn bf16_weights_match_f32() -> TractResult<()> {
+        let x = tensor2(&[[1f32, 2.0, -0.5, 0.25], [-3.0, 0.125, 4.0, 1.0]]);
+        let expected = SimplePlan::new(dense(DatumType::F32)?)?.run(tvec!(x.clone()))?;
+        let found = SimplePlan::new(dense(DatumType::BF16)?)?.run(tvec!(x.cast_to_bf16()?))?;
+        assert_eq!(found[0].datum_type(), DatumType::BF16);
+        let found = found[0].cast_from_bf16()?;
+        for (f, e) in found.as_slice::<f32>()?.iter().zip(expected[0].as_slice::<f32>()?) {
+            assert!((f - e).abs() <= 5e-3 * e.abs(), "{} != {}", f, e);
+        }
+        Ok(())
+    }
+
+    // asymmetric u8 quantization of values in [min, max]
+    fn quantize(values: &[f32], min: f32, max: f32) -> (Vec<u8>, f32, u8) {
+        let scale = (max - min) / 255.0;
+        let zero_point = (-min / scale).round() as u8;
+        let q = values
+            .iter()
+            .map(|&v| ((v / scale).round() + zero_point as f32).max(0.0).min(255.0) as u8)
+            .collect();
+        (q, scale, zero_point)
+    }
+
+    // x.w, with a constant w, both quantized with their own zero point.
+    // Returns the i32 accumulators from the plain eval, from the optimized
+    // model and from a naive reference.
+    fn quantized_matmul(
+        (m, k, n): (usize, usize, usize),
+        (xs, x0): (&[u8], u8),
+        (ws, w0): (&[u8], u8),
+    ) -> TractResult<(Tensor, Tensor, Tensor)> {
+        let x = Tensor::from(Array::from_shape_vec((m, k), xs.to_vec())?);
+        let w = Tensor::from(Array::from_shape_vec((k, n), ws.to_vec())?);
+        let q_params = QParams::new(i32::datum_type())
+            .with_zero_point_a(&rctensor0(x0))
+            .with_zero_point_b(&rctensor0(w0));
+        let op = MatMul::default().with_q_params(q_params);
+        let plain = op.eval(tvec!(x.clone().into(), w.clone().into()))?.remove(0);
+        let mut model = TypedModel::default();
+        let source =
+            model.add_source("x", TypedFact::dt_shape(u8::datum_type(), [m, k].as_ref())?)?;
+        let w = model.add_const("w", w)?;
+        let y = model.wire_node("mm", op, &[source, w])?[0];
+        model.set_output_outlets(&[y])?;
+        let optimized = SimplePlan::new(model.into_optimized()?)?.run(tvec!(x))?.remove(0);
+        let reference = Array::from_shape_fn((m, n), |(i, j)| {
+            (0..k)
+                .map(|l| (xs[i * k + l] as i32 - x0 as i32) * (ws[l * n + j] as i32 - w0 as i32))
+                .sum::<i32>()
+        });
+        Ok((plain.into_tensor(), optimized.into_tensor(), reference.into()))
+    }
+
+    #[test]
+    fn asymmetric_zero_points() -> TractResult<()> {
+        let xs = [12u8, 200, 3, 77, 150, 0, 255, 31];
+        let ws = [5u8, 250, 128, 64, 17, 99, 180, 42, 7, 230, 133, 90];
+        let (plain, optimized, reference) = quantized_matmul((2, 4, 3), (&xs, 37), (&ws, 140))?;
+        assert_eq!(plain, reference);
+        assert_eq!(optimized, reference);
+        Ok(())
+    }
+
+    fn float_reference_strat() -> BoxedStrategy<((usize, usize, usize), Vec<f32>, Vec<f32>)> {
+        (1usize..5, 1usize..20, 1usize..6)
+            .prop_flat_map(|(m, k, n)| {
+                (
+                    Just((m, k, n)),
+                    proptest::collection::vec(-1.0f32..3.0, m * k),
+                    proptest::collection::vec(-2.0f32..0.5, k * n),
+                )
+            })
+            .boxed()
+    }
+
+    proptest! {
+        #[test]
+        fn float_reference((dims, x, w) in float_reference_strat()) {
+            let (m, k, n) = dims;
+            let (xs, x_scale, x0) = quantize(&x, -1.0, 3.0);
+            let (ws, w_scale, w0) = quantize(&w, -2.0, 0.5);
+            let (plain, optimized, reference) = quantized_matmul(dims, (&xs, x0), (&ws, w0)).unwrap();
+            prop_assert_eq!(&plain, &reference);
+            prop_assert_eq!(&optimized, &reference);
+            // each product is off by at most the rounding of both operands
+            let tolerance = k as f32 * (x_scale * 2.0 + w_scale * 3.0 + x_scale * w_scale) / 2.0;
+            let found = optimized.to_array_view::<i32>().unwrap();
+            for i in 0..m {
+                for j in 0..n {
+                    let expected = (0..k).map(|l| x[i * k + l] * w[l * n + j]).sum::<f32>();
+                    let found = found[[i, j]] as f32 * x_scale * w_scale;
+                    prop_assert!((found - expected).abs() <= tolerance, "{} != {}", found, expected);
+                }
+            }
+        }
+    }
 }
@@ -4,6 +4,7 @@ use std::ops::{Add, Deref, Mul};
 
 use crate::internal::*;
 use crate::ops::quant::QParams;
+use ndarray::ArrayD;
 
 use tract_linalg::mmm::{FusedSpec, MatMatMul, QMatMatMul};
 
@@ -93,6 +94,33 @@ where
         }
         Ok(())
     }
+
+    /// Precompute the zero point corrections depending on A for constant,
+    /// already packed, As. They are prepended to the matching fused ops,
+    /// leaving only the corrections depending on B to compute at run time.
+    pub fn precompute_a_terms(
+        &mut self,
+        packed_as: &ArrayD<Arc<Tensor>>,
+        fused_ops: &mut Option<ArrayD<Vec<FusedSpec<TI>>>>,
+    ) -> TractResult<()> {
+        let q = if let Some(q) = self.as_quant_mut() { q } else { return Ok(()) };
+        let mut terms = vec![];
+        for pa in packed_as.iter() {
+            match unsafe { q.zero_point_b_terms(pa.as_ptr::<TA>()?) } {
+                Some(term) => terms.push(term),
+                None => return Ok(()),
+            }
+        }
+        let fused = fused_ops.get_or_insert_with(|| ArrayD::from_elem(packed_as.shape(), vec![]));
+        if fused.shape() != packed_as.shape() {
+            bail!("Fused ops {:?} and packed A {:?} mismatch", fused.shape(), packed_as.shape())
+        }
+        for (ops, term) in fused.iter_mut().zip(terms.into_iter()) {
+            ops.insert(0, term);
+        }
+        unsafe { q.clear_zero_point_b() };
+        Ok(())
+    }
 }
 
 impl<TA, TB, TC, TI> fmt::Display for MMMWrapper<TA, TB, TC, TI>
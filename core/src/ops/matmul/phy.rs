@@ -21,6 +21,33 @@ where
     pub(crate) output_shape: TVec<usize>,
 }
 
+impl<T> MatMatMulPackB<T>
+where
+    T: Copy + Datum + Zero,
+{
+    pub fn new(
+        pack_b: PackB<T>,
+        row_stride: isize,
+        col_stride: isize,
+        output_shape: TVec<usize>,
+    ) -> MatMatMulPackB<T> {
+        MatMatMulPackB { pack_b, row_stride, col_stride, output_shape }
+    }
+
+    pub fn pack_b(&self) -> &PackB<T> {
+        &self.pack_b
+    }
+
+    /// Strides of the unpacked input, in elements.
+    pub fn strides(&self) -> (isize, isize) {
+        (self.row_stride, self.col_stride)
+    }
+
+    pub fn output_shape(&self) -> &[usize] {
+        &self.output_shape
+    }
+}
+
 impl<T> Op for MatMatMulPackB<T>
 where
     T: Copy + Datum + Zero,
@@ -78,7 +105,7 @@ where
 }
 
 #[derive(Debug, Clone)]
-pub(crate) struct MatMatMulUnaryFinite<TA, TB, TC, TI>
+pub struct MatMatMulUnaryFinite<TA, TB, TC, TI>
 where
     TA: Datum + Copy + Zero,
     TB: Datum + Copy + Zero,
@@ -94,6 +121,24 @@ where
     pub(crate) mmm: MMMWrapper<TA, TB, TC, TI>,
 }
 
+impl<TA, TB, TC, TI> MatMatMulUnaryFinite<TA, TB, TC, TI>
+where
+    TA: Datum + Copy + Zero,
+    TB: Datum + Copy + Zero,
+    TC: Datum + Copy,
+    TI: Datum + Copy + Add + Mul + Zero + fmt::Debug,
+{
+    /// The packed A matrices, one for each C prefix.
+    pub fn packed_as(&self) -> &ArrayD<Arc<Tensor>> {
+        &self.packed_as
+    }
+
+    /// The operations fused after the product, one list for each C prefix.
+    pub fn fused_ops(&self) -> Option<&ArrayD<Vec<FusedSpec<TI>>>> {
+        self.fused_ops.as_ref()
+    }
+}
+
 impl<TA, TB, TC, TI> Op for MatMatMulUnaryFinite<TA, TB, TC, TI>
 where
     TA: Datum + Copy + Zero,
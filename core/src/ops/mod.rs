@@ -17,7 +17,12 @@ pub mod invariants;
 pub mod array;
 pub mod cast;
 pub mod cnn;
+pub mod control_flow;
+pub mod cost;
+pub mod debug;
+pub mod detection;
 pub mod downsample;
+pub mod einsum;
 pub mod dummy;
 pub mod identity;
 pub mod konst;
@@ -25,11 +30,17 @@ pub mod logic;
 pub mod math;
 pub mod matmul;
 pub mod nn;
+#[doc(hidden)]
+pub mod physical;
 pub mod quant;
+pub mod recurrent;
 pub mod scan;
+pub mod signal;
 pub mod source;
 pub mod unimpl;
+pub mod vision;
 
+pub use cost::{ComputeCost, OpCost};
 pub use downsample::Downsample;
 pub use invariants::{AxisInfo, Invariants};
 
@@ -69,14 +80,21 @@ pub enum Cost {
 
 use crate::internal::*;
 
-pub trait OpState: fmt::Debug + Send + dyn_clone::DynClone {
+pub trait OpState: fmt::Debug + Send + dyn_clone::DynClone + Downcast {
     fn eval(
         &mut self,
         session: &mut SessionState,
         op: &dyn Op,
         inputs: TVec<Arc<Tensor>>,
     ) -> TractResult<TVec<Arc<Tensor>>>;
+
+    /// Forget what previous evaluations accumulated, as if the state had
+    /// just been created.
+    fn reset(&mut self) -> TractResult<()> {
+        Ok(())
+    }
 }
+impl_downcast!(OpState);
 
 pub trait StatelessOp: Op {
     fn eval(&self, inputs: TVec<Arc<Tensor>>) -> TractResult<TVec<Arc<Tensor>>>;
@@ -142,6 +160,14 @@ pub trait Op: fmt::Debug + dyn_clone::DynClone + Send + Sync + 'static + Downcas
         Validation::Accurate
     }
 
+    /// Check the op attributes make sense, independently of its inputs.
+    ///
+    /// Called on every node by `InferenceModel::into_typed` before analysis,
+    /// so that invalid attributes are reported early and in their own terms.
+    fn validate_attributes(&self) -> TractResult<()> {
+        Ok(())
+    }
+
     /// Compare two ops.
     // Should this one be and Eq or PartialEq impl instead ?
     fn same_as(&self, _other: &dyn Op) -> bool {
@@ -199,6 +225,13 @@ pub trait TypedOp:
         Ok(tvec!())
     }
 
+    /// Reinterpret the TypedOp as an OpCost, if it estimates its own cost.
+    ///
+    /// Ops returning None are costed from `cost` by `cost::op_cost`.
+    fn as_op_cost(&self) -> Option<&dyn OpCost> {
+        None
+    }
+
     /// Transforms the op in an equivalent one, discarding one dummy axis (of dim
     /// assumed to be 1).
     ///
@@ -391,6 +424,7 @@ pub trait InferenceOp:
 impl_downcast!(Op);
 
 dyn_clone::clone_trait_object!(Op);
+dyn_clone::clone_trait_object!(OpState);
 dyn_clone::clone_trait_object!(StatelessOp);
 dyn_clone::clone_trait_object!(TypedOp);
 dyn_clone::clone_trait_object!(InferenceOp);
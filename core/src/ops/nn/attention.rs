@@ -0,0 +1,424 @@
+use crate::internal::*;
+use crate::ops::array::PermuteAxes;
+use crate::ops::binary::{TypedBinOp, UnaryOp};
+use crate::ops::math::{Add, Mul};
+use crate::ops::matmul::MatMul;
+use crate::ops::nn::LayerSoftmax;
+use crate::optim::single_use;
+use ndarray::*;
+
+/// Keys are visited by blocks of this size, keeping a running softmax.
+const BLOCK: usize = 64;
+
+/// Scaled dot-product attention, `softmax(Q.K^T * scale + mask).V`.
+///
+/// Inputs are Q `[.., seq_q, d]`, K `[.., seq_k, d]`, V `[.., seq_k, d_v]`
+/// and an optional additive mask, broadcastable to `[.., seq_q, seq_k]`.
+/// The leading axes of Q, K and V must match.
+///
+/// The softmax is computed online over blocks of keys, so the `[seq_q,
+/// seq_k]` attention matrix is never materialized.
+#[derive(Debug, Clone, new)]
+pub struct ScaledDotProductAttention {
+    pub scale: f32,
+}
+
+impl ScaledDotProductAttention {
+    /// Attention with the usual `1/sqrt(d)` scale.
+    pub fn for_depth(d: usize) -> ScaledDotProductAttention {
+        ScaledDotProductAttention::new((d as f32).sqrt().recip())
+    }
+
+    fn check(&self, q: &[usize], k: &[usize], v: &[usize]) -> TractResult<()> {
+        let rank = q.len();
+        if rank < 2
+            || k.len() != rank
+            || v.len() != rank
+            || q[..rank - 2] != k[..rank - 2]
+            || q[..rank - 2] != v[..rank - 2]
+            || q[rank - 1] != k[rank - 1]
+            || k[rank - 2] != v[rank - 2]
+        {
+            bail!("Inconsistent attention shapes: Q {:?}, K {:?}, V {:?}", q, k, v)
+        }
+        Ok(())
+    }
+
+    fn eval_f32(
+        &self,
+        q: ArrayViewD<f32>,
+        k: ArrayViewD<f32>,
+        v: ArrayViewD<f32>,
+        mask: Option<ArrayViewD<f32>>,
+    ) -> TractResult<ArrayD<f32>> {
+        self.check(q.shape(), k.shape(), v.shape())?;
+        let rank = q.ndim();
+        let (seq_q, seq_k, d_v) = (q.shape()[rank - 2], k.shape()[rank - 2], v.shape()[rank - 1]);
+        let batch: TVec<usize> = q.shape()[..rank - 2].into();
+        let mut scores_shape = batch.clone();
+        scores_shape.push(seq_q);
+        scores_shape.push(seq_k);
+        let mask = if let Some(mask) = &mask {
+            Some(mask.broadcast(&*scores_shape).ok_or_else(|| {
+                format!(
+                    "Attention mask {:?} does not broadcast to {:?}",
+                    mask.shape(),
+                    scores_shape
+                )
+            })?)
+        } else {
+            None
+        };
+        let mut output_shape = batch.clone();
+        output_shape.push(seq_q);
+        output_shape.push(d_v);
+        let mut output = ArrayD::<f32>::zeros(&*output_shape);
+        let mut scores = [0f32; BLOCK];
+        for ix in indices(&*batch) {
+            let ix = ix.slice();
+            let q = matrix(q.view(), ix);
+            let k = matrix(k.view(), ix);
+            let v = matrix(v.view(), ix);
+            let mask = mask.as_ref().map(|m| matrix(m.view(), ix));
+            let mut output = matrix_mut(output.view_mut(), ix);
+            for i in 0..seq_q {
+                let mut max = std::f32::NEG_INFINITY;
+                let mut sum = 0f32;
+                let mut acc = output.row_mut(i);
+                for start in (0..seq_k).step_by(BLOCK) {
+                    let len = BLOCK.min(seq_k - start);
+                    for j in 0..len {
+                        scores[j] = q.row(i).dot(&k.row(start + j)) * self.scale;
+                        if let Some(mask) = &mask {
+                            scores[j] += mask[(i, start + j)];
+                        }
+                    }
+                    let new_max = scores[..len].iter().cloned().fold(max, f32::max);
+                    if new_max == std::f32::NEG_INFINITY {
+                        continue;
+                    }
+                    let correction = (max - new_max).exp();
+                    sum *= correction;
+                    acc.mapv_inplace(|a| a * correction);
+                    for j in 0..len {
+                        let p = (scores[j] - new_max).exp();
+                        sum += p;
+                        acc.scaled_add(p, &v.row(start + j));
+                    }
+                    max = new_max;
+                }
+                acc.mapv_inplace(|a| a / sum);
+            }
+        }
+        Ok(output)
+    }
+}
+
+/// The matrix at index `ix` over the leading axes of `view`.
+fn matrix<'a>(mut view: ArrayViewD<'a, f32>, ix: &[usize]) -> ArrayView2<'a, f32> {
+    for &i in ix {
+        view = view.index_axis_move(Axis(0), i);
+    }
+    view.into_dimensionality().unwrap()
+}
+
+fn matrix_mut<'a>(mut view: ArrayViewMutD<'a, f32>, ix: &[usize]) -> ArrayViewMut2<'a, f32> {
+    for &i in ix {
+        view = view.index_axis_move(Axis(0), i);
+    }
+    view.into_dimensionality().unwrap()
+}
+
+impl Op for ScaledDotProductAttention {
+    fn name(&self) -> Cow<str> {
+        "ScaledDotProductAttention".into()
+    }
+
+    fn info(&self) -> TractResult<Vec<String>> {
+        Ok(vec![format!("scale: {}", self.scale)])
+    }
+
+    fn validation(&self) -> Validation {
+        Validation::Rounding
+    }
+
+    op_as_typed_op!();
+    not_a_pulsed_op!();
+}
+
+impl StatelessOp for ScaledDotProductAttention {
+    fn eval(&self, inputs: TVec<Arc<Tensor>>) -> TractResult<TVec<Arc<Tensor>>> {
+        let mask = if let Some(mask) = inputs.get(3) { Some(mask.to_array_view()?) } else { None };
+        let output = self.eval_f32(
+            inputs[0].to_array_view()?,
+            inputs[1].to_array_view()?,
+            inputs[2].to_array_view()?,
+            mask,
+        )?;
+        Ok(tvec!(output.into_arc_tensor()))
+    }
+}
+
+impl InferenceRulesOp for ScaledDotProductAttention {
+    fn rules<'r, 'p: 'r, 's: 'r>(
+        &'s self,
+        s: &mut Solver<'r>,
+        inputs: &'p [TensorProxy],
+        outputs: &'p [TensorProxy],
+    ) -> InferenceResult {
+        if inputs.len() != 3 && inputs.len() != 4 {
+            bail!("ScaledDotProductAttention expects Q, K, V and an optional mask")
+        }
+        check_output_arity(&outputs, 1)?;
+        for input in inputs {
+            s.equals(&input.datum_type, f32::datum_type())?;
+        }
+        s.equals(&outputs[0].datum_type, f32::datum_type())?;
+        s.equals(&inputs[0].rank, &inputs[1].rank)?;
+        s.equals(&inputs[0].rank, &inputs[2].rank)?;
+        s.equals(&outputs[0].rank, &inputs[0].rank)?;
+        s.given(&inputs[0].rank, move |s, rank| {
+            let rank = rank as usize;
+            for axis in 0..rank - 2 {
+                s.equals(&inputs[0].shape[axis], &inputs[1].shape[axis])?;
+                s.equals(&inputs[0].shape[axis], &inputs[2].shape[axis])?;
+                s.equals(&outputs[0].shape[axis], &inputs[0].shape[axis])?;
+            }
+            s.equals(&inputs[0].shape[rank - 1], &inputs[1].shape[rank - 1])?;
+            s.equals(&inputs[1].shape[rank - 2], &inputs[2].shape[rank - 2])?;
+            s.equals(&outputs[0].shape[rank - 2], &inputs[0].shape[rank - 2])?;
+            s.equals(&outputs[0].shape[rank - 1], &inputs[2].shape[rank - 1])
+        })
+    }
+
+    inference_op_as_op!();
+    to_typed!();
+}
+
+impl TypedOp for ScaledDotProductAttention {
+    fn output_facts(&self, inputs: &[&TypedFact]) -> TractResult<TVec<TypedFact>> {
+        let (q, k, v) = (inputs[0], inputs[1], inputs[2]);
+        if let (Some(qs), Some(ks), Some(vs)) =
+            (q.shape.as_finite(), k.shape.as_finite(), v.shape.as_finite())
+        {
+            self.check(qs, ks, vs)?;
+        }
+        if inputs.iter().any(|f| f.datum_type != f32::datum_type()) {
+            bail!("ScaledDotProductAttention only supports f32")
+        }
+        let rank = q.rank();
+        if rank < 2 {
+            bail!("Attention Q must be at least 2D, got {:?}", q)
+        }
+        let mut shape: TVec<TDim> = q.shape.iter().collect();
+        shape[rank - 1] = v.shape.dim(rank - 1);
+        Ok(tvec!(TypedFact::dt_shape(f32::datum_type(), &*shape)?))
+    }
+
+    fn cost(&self, inputs: &[&TypedFact]) -> TractResult<TVec<(Cost, TDim)>> {
+        let rank = inputs[0].rank();
+        let rows = inputs[0].shape.iter().take(rank - 1).product::<TDim>();
+        let seq_k = inputs[1].shape.dim(rank - 2);
+        let depth = inputs[0].shape.dim(rank - 1) + inputs[2].shape.dim(rank - 1);
+        Ok(tvec!((Cost::FMA(f32::datum_type()), rows * seq_k * depth)))
+    }
+
+    typed_op_as_op!();
+}
+
+fn plain_matmul(node: &TypedNode) -> Option<&MatMul> {
+    node.op_as::<MatMul>().filter(|mm| !mm.a_trans && !mm.c_trans && mm.q_params().is_none())
+}
+
+/// Scalar f32 constant of a unary op.
+fn scalar(op: &UnaryOp) -> Option<f32> {
+    if op.a.len() == 1 && op.a.datum_type() == f32::datum_type() {
+        op.a.as_slice::<f32>().ok().map(|s| s[0])
+    } else {
+        None
+    }
+}
+
+/// Match `Q.K^T`, optionally scaled, returning Q, K and the scale.
+fn scaled_qk(model: &TypedModel, outlet: OutletId) -> Option<(OutletId, OutletId, f32)> {
+    let mut node = single_use(model, outlet)?;
+    let mut scale = 1.0;
+    if let Some(op) = node.op_as::<UnaryOp>() {
+        // a division by a constant is decluttered to a multiplication
+        if !op.mini_op.is::<Mul>() {
+            return None;
+        }
+        scale = scalar(op)?;
+        node = single_use(model, node.inputs[0])?;
+    }
+    let mm = plain_matmul(node)?;
+    if mm.b_trans {
+        return Some((node.inputs[0], node.inputs[1], scale));
+    }
+    let transpose = single_use(model, node.inputs[1])?;
+    let axes = transpose.op_as::<PermuteAxes>()?.axes.as_ref()?;
+    let rank = axes.len();
+    let swapped = rank >= 2
+        && (0..rank - 2).all(|ax| axes[ax] == ax)
+        && axes[rank - 2] == rank - 1
+        && axes[rank - 1] == rank - 2;
+    if swapped {
+        Some((node.inputs[0], transpose.inputs[0], scale))
+    } else {
+        None
+    }
+}
+
+/// Replace `MatMul(Softmax(MatMul(Q, K^T) * scale + mask), V)` by a
+/// `ScaledDotProductAttention`. The scale and mask are optional, the
+/// softmax must be on the last axis.
+pub(crate) fn fuse_attention(
+    model: &TypedModel,
+    node: &TypedNode,
+) -> TractResult<Option<TypedModelPatch>> {
+    if plain_matmul(node).map(|mm| mm.b_trans) != Some(false) {
+        return Ok(None);
+    }
+    let softmax = if let Some(softmax) = single_use(model, node.inputs[0]) {
+        softmax
+    } else {
+        return Ok(None);
+    };
+    let rank = model.outlet_fact(node.inputs[0])?.rank() as isize;
+    match softmax.op_as::<LayerSoftmax>() {
+        Some(op) if op.axis() == -1 || op.axis() == rank - 1 => (),
+        _ => return Ok(None),
+    }
+    enum Mask {
+        Wire(OutletId),
+        Konst(Arc<Tensor>),
+    }
+    let mut matched = None;
+    if let Some(qk) = scaled_qk(model, softmax.inputs[0]) {
+        matched = Some((qk, None));
+    } else if let Some(add) = single_use(model, softmax.inputs[0]) {
+        if add.op_as::<TypedBinOp>().map(|op| op.0.is::<Add>()).unwrap_or(false) {
+            if let Some(qk) = scaled_qk(model, add.inputs[0]) {
+                matched = Some((qk, Some(Mask::Wire(add.inputs[1]))));
+            } else if let Some(qk) = scaled_qk(model, add.inputs[1]) {
+                matched = Some((qk, Some(Mask::Wire(add.inputs[0]))));
+            }
+        } else if let Some(op) = add.op_as::<UnaryOp>().filter(|op| op.mini_op.is::<Add>()) {
+            if let Some(qk) = scaled_qk(model, add.inputs[0]) {
+                matched = Some((qk, Some(Mask::Konst(op.a.clone()))));
+            }
+        }
+    }
+    let ((q, k, scale), mask) = if let Some(matched) = matched {
+        matched
+    } else {
+        return Ok(None);
+    };
+    let mut facts =
+        vec![model.outlet_fact(q)?, model.outlet_fact(k)?, model.outlet_fact(node.inputs[1])?];
+    if let Some(Mask::Wire(mask)) = &mask {
+        facts.push(model.outlet_fact(*mask)?);
+    }
+    if facts.iter().any(|f| f.datum_type != f32::datum_type() || f.rank() != rank as usize) {
+        return Ok(None);
+    }
+    let mut patch = TypedModelPatch::default();
+    let mut inputs = tvec!(
+        patch.tap_model(model, q)?,
+        patch.tap_model(model, k)?,
+        patch.tap_model(model, node.inputs[1])?
+    );
+    match mask {
+        Some(Mask::Wire(mask)) => inputs.push(patch.tap_model(model, mask)?),
+        Some(Mask::Konst(mask)) => {
+            if mask.datum_type() != f32::datum_type() {
+                return Ok(None);
+            }
+            inputs.push(patch.add_const(format!("{}.mask", node.name), mask)?)
+        }
+        None => (),
+    }
+    let op = ScaledDotProductAttention::new(scale);
+    let fused = patch.wire_node(&*node.name, op, &*inputs)?[0];
+    patch.shunt_outside(OutletId::new(node.id, 0), fused)?;
+    Ok(Some(patch))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ops::math;
+
+    fn seq(shape: &[usize], factor: f32) -> Tensor {
+        let len = shape.iter().product::<usize>();
+        let data = (0..len).map(|i| ((i * 7 % 11) as f32 - 5.0) * factor).collect();
+        ArrayD::from_shape_vec(shape, data).unwrap().into_tensor()
+    }
+
+    // Q.K^T / sqrt(d) (+ mask), softmax, .V as separate nodes
+    fn decomposed(q: &[usize], k: &[usize], v: &[usize], mask: Option<Tensor>) -> TypedModel {
+        let mut model = TypedModel::default();
+        let mut wire = |name: &str, shape: &[usize]| {
+            let fact = TypedFact::dt_shape(f32::datum_type(), shape).unwrap();
+            model.add_source(name, fact).unwrap()
+        };
+        let (qw, kw, vw) = (wire("q", q), wire("k", k), wire("v", v));
+        let rank = q.len();
+        let mut axes: Vec<usize> = (0..rank).collect();
+        axes.swap(rank - 2, rank - 1);
+        let kt = model.wire_node("kt", PermuteAxes::new(Some(axes)), &[kw]).unwrap();
+        let s = model.wire_node("qk", MatMul::default(), &[qw, kt[0]]).unwrap();
+        let d = model.add_const("d", rctensor0((q[rank - 1] as f32).sqrt())).unwrap();
+        let div = TypedBinOp(Box::new(math::Div));
+        let mut s = model.wire_node("scaled", div, &[s[0], d]).unwrap();
+        if let Some(mask) = mask {
+            s = model.wire_node("masked", math::add::unary(mask.into_arc_tensor()), &s).unwrap();
+        }
+        let p = model.wire_node("softmax", LayerSoftmax::new(rank as isize - 1), &s).unwrap();
+        let o = model.wire_node("pv", MatMul::default(), &[p[0], vw]).unwrap();
+        model.set_output_outlets(&o).unwrap();
+        model
+    }
+
+    fn check(q: &[usize], k: &[usize], v: &[usize], mask: Option<Tensor>) -> TractResult<()> {
+        let model = decomposed(q, k, v, mask);
+        let inputs = tvec!(seq(q, 0.1), seq(k, 0.2), seq(v, 0.3));
+        let reference = SimplePlan::new(model.clone())?.run(inputs.clone())?.remove(0);
+        let model = model.declutter()?;
+        assert!(model.nodes().iter().any(|n| n.op_is::<ScaledDotProductAttention>()));
+        assert!(model.nodes().iter().all(|n| !n.op_is::<LayerSoftmax>()));
+        let found = SimplePlan::new(model)?.run(inputs)?.remove(0);
+        found.close_enough(&reference, true)
+    }
+
+    #[test]
+    fn fused_matches_decomposed() -> TractResult<()> {
+        check(&[3, 4], &[5, 4], &[5, 2], None)
+    }
+
+    #[test]
+    fn fused_matches_decomposed_long_batched() -> TractResult<()> {
+        // more keys than a block
+        check(&[2, 3, 7, 8], &[2, 3, 150, 8], &[2, 3, 150, 5], None)
+    }
+
+    #[test]
+    fn fused_matches_decomposed_masked() -> TractResult<()> {
+        let causal = Array2::from_shape_fn((4, 4), |(i, j)| if j > i { -1e9f32 } else { 0.0 });
+        check(&[2, 4, 3], &[2, 4, 3], &[2, 4, 6], Some(causal.into_tensor()))
+    }
+
+    #[test]
+    fn output_shape() -> TractResult<()> {
+        let op = ScaledDotProductAttention::for_depth(8);
+        let facts = [
+            TypedFact::dt_shape(f32::datum_type(), [2, 7, 8].as_ref())?,
+            TypedFact::dt_shape(f32::datum_type(), [2, 9, 8].as_ref())?,
+            TypedFact::dt_shape(f32::datum_type(), [2, 9, 3].as_ref())?,
+        ];
+        let output = op.output_facts(&[&facts[0], &facts[1], &facts[2]])?;
+        assert_eq!(output[0].shape.as_finite(), Some(&[2usize, 7, 3][..]));
+        assert!(op.output_facts(&[&facts[0], &facts[0], &facts[2]]).is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,101 @@
+use crate::internal::*;
+use num_traits::{AsPrimitive, Float};
+
+// Celu, as ONNX: `max(0, x) + min(0, alpha * (exp(x / alpha) - 1))`.
+//
+// ONNX forbids a zero alpha. As alpha goes to zero, the negative side goes to
+// `-alpha`, so a zero alpha is evaluated as a Relu instead of dividing by zero.
+element_wise!(celu, Celu { alpha: f32 },
+    [f32] => |e, xs| {
+        if e.alpha == 0.0 {
+            xs.iter_mut().for_each(|x| *x = x.max(0.0));
+        } else {
+            let (alpha, recip) = (e.alpha, e.alpha.recip());
+            xs.iter_mut().for_each(|x| *x = celu_f32(*x, alpha, recip));
+        }
+        Ok(())
+    },
+    [f64] => |e, xs| {
+        xs.iter_mut().for_each(|x| *x = celu_t(*x, e.alpha));
+        Ok(())
+});
+
+/// Both sides are computed and combined with a max and a min, the form a
+/// masked SIMD kernel would take. tract-linalg has no such kernel yet, so
+/// this runs as a scalar loop.
+#[inline(always)]
+fn celu_f32(x: f32, alpha: f32, recip: f32) -> f32 {
+    x.max(0.0) + (alpha * (x * recip).exp_m1()).min(0.0)
+}
+
+fn celu_t<T>(x: T, alpha: f32) -> T
+where
+    T: Datum + Float,
+    f32: AsPrimitive<T>,
+{
+    if x > T::zero() {
+        x
+    } else if alpha == 0.0 {
+        T::zero()
+    } else {
+        let alpha: T = alpha.as_();
+        alpha * (x / alpha).exp_m1()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ops::nn::test_utils::check_against_reference;
+
+    fn reference(x: f64, alpha: f64) -> f64 {
+        x.max(0.0) + (alpha * ((x / alpha).exp() - 1.0)).min(0.0)
+    }
+
+    fn check(alpha: f32, input: &[f32]) -> TractResult<()> {
+        check_against_reference(&celu(alpha), input, |x| reference(x, alpha as f64))
+    }
+
+    fn inputs() -> Vec<f32> {
+        (-40..=40).map(|i| i as f32 * 0.25).collect()
+    }
+
+    #[test]
+    fn alpha_one_is_elu() -> TractResult<()> {
+        check(1.0, &inputs())?;
+        let input = inputs();
+        let elu = crate::ops::nn::elu(1.0).eval(tvec!(rctensor1(&input)))?.remove(0);
+        celu(1.0).eval(tvec!(rctensor1(&input)))?[0].close_enough(&elu, true)
+    }
+
+    #[test]
+    fn alpha_two() -> TractResult<()> {
+        check(2.0, &inputs())?;
+        let found = celu(2.0).eval(tvec!(rctensor1(&[-100f32, 3.0])))?.remove(0);
+        assert_eq!(found, rctensor1(&[-2f32, 3.0]));
+        Ok(())
+    }
+
+    #[test]
+    fn near_zero() -> TractResult<()> {
+        let input = [-1e-3f32, -1e-6, -1e-20, -0.0, 0.0, 1e-20, 1e-6, 1e-3];
+        check(1.0, &input)?;
+        check(0.5, &input)?;
+        let found = celu(1.0).eval(tvec!(rctensor1(&input)))?.remove(0);
+        let found = found.as_slice::<f32>()?;
+        assert_eq!(found[2], -1e-20);
+        assert_eq!(found[5], 1e-20);
+        Ok(())
+    }
+
+    #[test]
+    fn zero_alpha_is_relu() -> TractResult<()> {
+        let input = [-2f32, -1e-3, 0.0, 1e-3, 2.0];
+        let expected = rctensor1(&[0f32, 0.0, 0.0, 1e-3, 2.0]);
+        assert_eq!(celu(0.0).eval(tvec!(rctensor1(&input)))?[0], expected);
+        let input: Vec<f64> = input.iter().map(|&x| x as f64).collect();
+        let found = celu(0.0).eval(tvec!(rctensor1(&input)))?.remove(0);
+        assert_eq!(found, rctensor1(&[0f64, 0.0, 0.0, 1e-3f32 as f64, 2.0]));
+        Ok(())
+    }
+}
@@ -1,6 +1,8 @@
 use crate::internal::*;
 use ndarray::prelude::*;
 
+/// Average over all the spatial axes of an NCHW input, as ONNX
+/// GlobalAveragePool. f32 inputs use the linalg reduction kernel.
 #[derive(Debug, Clone, new, Default)]
 pub struct GlobalAvgPool {
     //    data_is_nhwc: bool, // default is nchw (onnx)
@@ -43,6 +45,10 @@ impl Op for GlobalAvgPool {
 impl StatelessOp for GlobalAvgPool {
     fn eval(&self, mut inputs: TVec<Arc<Tensor>>) -> TractResult<TVec<Arc<Tensor>>> {
         let input = args_1!(inputs);
+        if input.datum_type() == f32::datum_type() {
+            let reducer = (tract_linalg::ops().sreduce)();
+            return eval_f32(&input, |xs| reducer.sum(xs) / xs.len() as f32);
+        }
         dispatch_floatlike!(Self::eval_t(input.datum_type())(self, input))
     }
 }
@@ -142,6 +148,8 @@ impl TypedOp for GlobalLpPool {
     }
 }
 
+/// Maximum over all the spatial axes of an NCHW input, as ONNX
+/// GlobalMaxPool. f32 inputs use the linalg reduction kernel.
 #[derive(Debug, Clone, new, Default)]
 pub struct GlobalMaxPool {
     //    data_is_nhwc: bool, // default is nchw (onnx)
@@ -180,6 +188,10 @@ impl Op for GlobalMaxPool {
 impl StatelessOp for GlobalMaxPool {
     fn eval(&self, mut inputs: TVec<Arc<Tensor>>) -> TractResult<TVec<Arc<Tensor>>> {
         let input = args_1!(inputs);
+        if input.datum_type() == f32::datum_type() {
+            let reducer = (tract_linalg::ops().sreduce)();
+            return eval_f32(&input, |xs| reducer.max(xs));
+        }
         dispatch_floatlike!(Self::eval_t(input.datum_type())(self, input))
     }
 }
@@ -206,6 +218,19 @@ impl TypedOp for GlobalMaxPool {
     }
 }
 
+/// Reduce each (n, c) spatial plane of a contiguous f32 input to a scalar.
+fn eval_f32(input: &Tensor, reduce: impl Fn(&[f32]) -> f32) -> TractResult<TVec<Arc<Tensor>>> {
+    let mut final_shape = input.shape().to_vec();
+    for dim in final_shape[2..].iter_mut() {
+        *dim = 1;
+    }
+    let planes = input.shape()[0] * input.shape()[1];
+    let plane = input.shape()[2..].iter().product::<usize>();
+    let data = input.as_slice::<f32>()?;
+    let result = (0..planes).map(|i| reduce(&data[i * plane..][..plane])).collect();
+    Ok(tvec!(ArrayD::from_shape_vec(final_shape, result)?.into_arc_tensor()))
+}
+
 fn rules<'r, 'p: 'r, 's: 'r>(
     s: &mut Solver<'r>,
     inputs: &'p [TensorProxy],
@@ -232,3 +257,31 @@ fn output_facts(inputs: &[&TypedFact]) -> TractResult<TVec<TypedFact>> {
     }
     Ok(tvec!(output))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn input<D: Datum + num_traits::FromPrimitive>() -> Arc<Tensor> {
+        Array4::from_shape_fn((2, 3, 5, 7), |(n, c, h, w)| {
+            D::from_usize((n * 13 + c * 7 + h * 3 + w * 5) % 17).unwrap()
+        })
+        .into_arc_tensor()
+    }
+
+    #[test]
+    fn avg_f32_matches_f64() -> TractResult<()> {
+        let found = GlobalAvgPool::default().eval(tvec!(input::<f32>()))?.remove(0);
+        let expected = GlobalAvgPool::default().eval(tvec!(input::<f64>()))?.remove(0);
+        assert_eq!(found.shape(), &[2, 3, 1, 1]);
+        found.close_enough(&*expected.cast_to::<f32>()?, true)
+    }
+
+    #[test]
+    fn max_f32_matches_f64() -> TractResult<()> {
+        let found = GlobalMaxPool::default().eval(tvec!(input::<f32>()))?.remove(0);
+        let expected = GlobalMaxPool::default().eval(tvec!(input::<f64>()))?.remove(0);
+        assert_eq!(found, expected.cast_to::<f32>()?.into_owned().into_arc_tensor());
+        Ok(())
+    }
+}
@@ -0,0 +1,171 @@
+use super::layer_norm::mean_and_variance;
+use crate::internal::*;
+use num_traits::Float;
+
+/// Group normalization.
+///
+/// The channels of a [N, C, ...] input are split in `num_groups` groups,
+/// each group of each batch item is normalized over its channels and
+/// spatial axes, then a per-channel scale and bias are applied.
+///
+/// This is equivalent to reshaping the input to [N, groups, C / groups, ...],
+/// normalizing over all axes but the first two, and reshaping back. As each
+/// group is a contiguous block of the input, no reshaped tensor is ever
+/// allocated.
+#[derive(Debug, Clone, new)]
+pub struct GroupNorm {
+    pub num_groups: usize,
+    pub epsilon: f32,
+}
+
+impl GroupNorm {
+    fn check_channels(&self, channels: usize) -> TractResult<()> {
+        if self.num_groups == 0 || channels % self.num_groups != 0 {
+            bail!("GroupNorm: {} channels can not be split in {} groups", channels, self.num_groups)
+        }
+        Ok(())
+    }
+
+    fn eval_t<T: Datum + Float>(&self, inputs: &[Arc<Tensor>]) -> TractResult<Arc<Tensor>> {
+        let (input, scale, bias) =
+            (&inputs[0], inputs[1].as_slice::<T>()?, inputs[2].as_slice::<T>()?);
+        if input.rank() < 2 {
+            bail!("GroupNorm expects a [N, C, ...] input, got {:?}", input.shape())
+        }
+        let channels = input.shape()[1];
+        self.check_channels(channels)?;
+        if scale.len() != channels || bias.len() != channels {
+            bail!("GroupNorm expects scale and bias of size {}", channels)
+        }
+        let spatial: usize = input.shape()[2..].iter().product();
+        let group_channels = channels / self.num_groups;
+        let epsilon = T::from(self.epsilon).unwrap();
+        let mut output = input.clone().into_tensor();
+        for group in output.as_slice_mut::<T>()?.chunks_mut((group_channels * spatial).max(1)) {
+            let (mean, var) = mean_and_variance(&*group);
+            let inv_std_dev = (var + epsilon).sqrt().recip();
+            group.iter_mut().for_each(|x| *x = (*x - mean) * inv_std_dev);
+        }
+        for (c, channel) in output.as_slice_mut::<T>()?.chunks_mut(spatial.max(1)).enumerate() {
+            let (s, b) = (scale[c % channels], bias[c % channels]);
+            channel.iter_mut().for_each(|x| *x = *x * s + b);
+        }
+        Ok(output.into_arc_tensor())
+    }
+}
+
+impl Op for GroupNorm {
+    fn name(&self) -> Cow<str> {
+        "GroupNorm".into()
+    }
+
+    fn info(&self) -> TractResult<Vec<String>> {
+        Ok(vec![format!("groups: {}, epsilon: {}", self.num_groups, self.epsilon)])
+    }
+
+    fn validation(&self) -> Validation {
+        Validation::Rounding
+    }
+
+    op_as_typed_op!();
+    not_a_pulsed_op!();
+}
+
+impl StatelessOp for GroupNorm {
+    fn eval(&self, inputs: TVec<Arc<Tensor>>) -> TractResult<TVec<Arc<Tensor>>> {
+        if inputs.len() != 3 {
+            bail!("GroupNorm expects 3 inputs, got {}", inputs.len())
+        }
+        Ok(tvec!(dispatch_floatlike!(Self::eval_t(inputs[0].datum_type())(self, &*inputs))?))
+    }
+}
+
+impl InferenceRulesOp for GroupNorm {
+    fn rules<'r, 'p: 'r, 's: 'r>(
+        &'s self,
+        s: &mut Solver<'r>,
+        inputs: &'p [TensorProxy],
+        outputs: &'p [TensorProxy],
+    ) -> InferenceResult {
+        check_input_arity(&inputs, 3)?;
+        check_output_arity(&outputs, 1)?;
+        s.equals(&inputs[0].datum_type, &outputs[0].datum_type)?;
+        s.equals(&inputs[1].datum_type, &outputs[0].datum_type)?;
+        s.equals(&inputs[2].datum_type, &outputs[0].datum_type)?;
+        s.equals(&inputs[0].shape, &outputs[0].shape)?;
+        s.equals(&inputs[1].rank, 1)?;
+        s.equals(&inputs[2].rank, 1)?;
+        s.equals(&inputs[1].shape[0], &inputs[0].shape[1])?;
+        s.equals(&inputs[2].shape[0], &inputs[0].shape[1])?;
+        s.given(&inputs[0].shape[1], move |_, c| {
+            if let Ok(c) = c.to_integer() {
+                self.check_channels(c as usize)?;
+            }
+            Ok(())
+        })
+    }
+
+    inference_op_as_op!();
+    to_typed!();
+}
+
+impl TypedOp for GroupNorm {
+    fn output_facts(&self, inputs: &[&TypedFact]) -> TractResult<TVec<TypedFact>> {
+        if inputs[0].rank() < 2 {
+            bail!("GroupNorm expects a [N, C, ...] input, got {:?}", inputs[0])
+        }
+        if let Ok(c) = inputs[0].shape.dim(1).to_integer() {
+            self.check_channels(c as usize)?;
+        }
+        Ok(tvec!(TypedFact::dt_shape(inputs[0].datum_type, inputs[0].shape.clone())?))
+    }
+
+    typed_op_as_op!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Reference values follow torch.nn.GroupNorm(2, 4) definition, computed
+    // in double precision.
+    #[test]
+    fn torch_reference() -> TractResult<()> {
+        let x = ndarray::Array4::from_shape_fn((2, 4, 2, 3), |(n, c, h, w)| {
+            let i = ((n * 4 + c) * 2 + h) * 3 + w;
+            (0.37 * i as f32).sin() * 3.0 + c as f32
+        });
+        let scale = rctensor1(&[1f32, 0.5, -2.0, 1.5]);
+        let bias = rctensor1(&[0f32, 0.25, 1.0, -0.5]);
+        let output = GroupNorm::new(2, 1e-5).eval(tvec!(x.into_arc_tensor(), scale, bias))?;
+        let expected = tensor4(&[
+            [
+                [[-0.981273f32, -0.256137, 0.370856], [0.814844, 1.015737, 0.946343]],
+                [[0.892239, 0.619, 0.274647], [-0.094213, -0.437657, -0.709202]],
+                [[3.57924, 3.648335, 3.427568], [2.946819, 2.271156, 1.492025]],
+                [[0.251812, 0.754296, 1.108394], [1.266183, 1.206305, 0.936866]],
+            ],
+            [
+                [[1.370452, 0.706981, -0.001837], [-0.660068, -1.178622, -1.487317]],
+                [[-0.201052, -0.099898, 0.117247], [0.420993, 0.770229, 1.117688]],
+                [[-0.292991, -1.008423, -1.322222], [-1.191914, -0.635138, 0.272751]],
+                [[0.016113, -0.891827, -1.73273], [-2.392784, -2.782654, -2.849572]],
+            ],
+        ]);
+        let found = output[0].as_slice::<f32>()?;
+        for (f, e) in found.iter().zip(expected.as_slice::<f32>()?.iter()) {
+            assert!((f - e).abs() < 1e-4, "{} != {}", f, e);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn groups_must_divide_channels() -> TractResult<()> {
+        let op = GroupNorm::new(3, 1e-5);
+        let fact = TypedFact::dt_shape(f32::datum_type(), [1usize, 4, 2, 2].as_ref())?;
+        let param = TypedFact::dt_shape(f32::datum_type(), [4usize].as_ref())?;
+        let err = op.output_facts(&[&fact, &param, &param]).unwrap_err();
+        assert!(err.to_string().contains("4 channels can not be split in 3 groups"));
+        Ok(())
+    }
+}
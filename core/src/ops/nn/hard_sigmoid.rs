@@ -0,0 +1,67 @@
+use crate::internal::*;
+
+// HardSigmoid, as ONNX: `clamp(alpha * x + beta, 0, 1)`.
+//
+// tract-linalg has no SIMD kernel for it or for HardSwish yet: they run
+// scalar loops.
+element_wise!(hard_sigmoid, HardSigmoid { alpha: f32, beta: f32 },
+    [f32] => |e, xs| {
+        xs.iter_mut().for_each(|x| *x = hard_sigmoid_f32(*x, e.alpha, e.beta));
+        Ok(())
+    },
+    [f64] => |e, xs| {
+        let (alpha, beta) = (e.alpha as f64, e.beta as f64);
+        xs.iter_mut().for_each(|x| *x = (alpha * *x + beta).min(1.0).max(0.0));
+        Ok(())
+});
+
+// HardSwish, as ONNX and MobileNetV3: `x * HardSigmoid(x; 1/6, 0.5)`, in a
+// single pass over the data.
+element_wise!(hard_swish, HardSwish,
+    [f32] => |_, xs| {
+        xs.iter_mut().for_each(|x| *x *= hard_sigmoid_f32(*x, 1.0 / 6.0, 0.5));
+        Ok(())
+    },
+    [f64] => |_, xs| {
+        xs.iter_mut().for_each(|x| *x *= (*x / 6.0 + 0.5).min(1.0).max(0.0));
+        Ok(())
+};
+    cost: |dt| {tvec!((Cost::FMA(dt), 2))}
+);
+
+#[inline(always)]
+fn hard_sigmoid_f32(x: f32, alpha: f32, beta: f32) -> f32 {
+    (alpha * x + beta).min(1.0).max(0.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ops::element_wise::ElementWiseOp;
+    use crate::ops::nn::test_utils::check_against_reference;
+
+    fn check(op: ElementWiseOp, reference: impl Fn(f64) -> f64) -> TractResult<()> {
+        let input: Vec<f32> = (-24..=24).map(|i| i as f32 * 0.25).collect();
+        check_against_reference(&op, &input, reference)
+    }
+
+    #[test]
+    fn hard_sigmoid_definition() -> TractResult<()> {
+        check(hard_sigmoid(0.2, 0.5), |x| (0.2 * x + 0.5).max(0.0).min(1.0))?;
+        check(hard_sigmoid(1.0, -1.0), |x| (x - 1.0).max(0.0).min(1.0))
+    }
+
+    #[test]
+    fn hard_swish_definition() -> TractResult<()> {
+        let hard_swish_ref = |x: f64| {
+            if x <= -3.0 {
+                0.0
+            } else if x >= 3.0 {
+                x
+            } else {
+                x * (x + 3.0) / 6.0
+            }
+        };
+        check(hard_swish(), hard_swish_ref)
+    }
+}
@@ -0,0 +1,219 @@
+use super::layer_norm::mean_and_variance;
+use super::DataFormat;
+use crate::internal::*;
+use ndarray::*;
+use num_traits::Float;
+
+/// Instance normalization.
+///
+/// Each channel of each batch item is normalized over its spatial axes,
+/// then a per-channel scale and bias are applied. Inputs are the data, in
+/// NCHW or NHWC layout, the scale and the bias, both of shape [C].
+///
+/// The statistics are computed first, then normalization, scale and bias
+/// are applied in a single pass over the output.
+#[derive(Debug, Clone, new)]
+pub struct InstanceNorm {
+    pub data_format: DataFormat,
+    pub epsilon: f32,
+}
+
+impl Default for InstanceNorm {
+    fn default() -> InstanceNorm {
+        InstanceNorm { data_format: DataFormat::NCHW, epsilon: 1e-5 }
+    }
+}
+
+impl InstanceNorm {
+    fn check_format(&self) -> TractResult<()> {
+        match self.data_format {
+            DataFormat::NCHW | DataFormat::NHWC => Ok(()),
+            fmt => bail!("InstanceNorm expects a batched data format, got {:?}", fmt),
+        }
+    }
+
+    fn eval_t<T: Datum + Float>(&self, inputs: &[Arc<Tensor>]) -> TractResult<Arc<Tensor>> {
+        let (input, scale, bias) =
+            (&inputs[0], inputs[1].as_slice::<T>()?, inputs[2].as_slice::<T>()?);
+        if input.rank() < 3 {
+            bail!("InstanceNorm expects a [N, C, ...] input, got {:?}", input.shape())
+        }
+        let shape = self.data_format.shape(input.shape());
+        let channels = *shape.c();
+        if scale.len() != channels || bias.len() != channels {
+            bail!("InstanceNorm expects scale and bias of size {}", channels)
+        }
+        let epsilon = T::from(self.epsilon).unwrap();
+        let mut output = input.clone().into_tensor().into_array::<T>()?;
+        for mut item in output.outer_iter_mut() {
+            // channel axis, the batch axis being gone
+            for (c, mut channel) in item.axis_iter_mut(Axis(shape.c_axis() - 1)).enumerate() {
+                let (mean, var) = mean_and_variance(&channel);
+                let factor = (var + epsilon).sqrt().recip() * scale[c];
+                let b = bias[c];
+                channel.iter_mut().for_each(|x| *x = (*x - mean) * factor + b);
+            }
+        }
+        Ok(output.into_arc_tensor())
+    }
+}
+
+impl Op for InstanceNorm {
+    fn name(&self) -> Cow<str> {
+        "InstanceNorm".into()
+    }
+
+    fn info(&self) -> TractResult<Vec<String>> {
+        Ok(vec![format!("data format: {:?}, epsilon: {}", self.data_format, self.epsilon)])
+    }
+
+    fn validation(&self) -> Validation {
+        Validation::Rounding
+    }
+
+    op_as_typed_op!();
+    not_a_pulsed_op!();
+}
+
+impl StatelessOp for InstanceNorm {
+    fn eval(&self, inputs: TVec<Arc<Tensor>>) -> TractResult<TVec<Arc<Tensor>>> {
+        if inputs.len() != 3 {
+            bail!("InstanceNorm expects 3 inputs, got {}", inputs.len())
+        }
+        self.check_format()?;
+        Ok(tvec!(dispatch_floatlike!(Self::eval_t(inputs[0].datum_type())(self, &*inputs))?))
+    }
+}
+
+impl InferenceRulesOp for InstanceNorm {
+    fn rules<'r, 'p: 'r, 's: 'r>(
+        &'s self,
+        s: &mut Solver<'r>,
+        inputs: &'p [TensorProxy],
+        outputs: &'p [TensorProxy],
+    ) -> InferenceResult {
+        check_input_arity(&inputs, 3)?;
+        check_output_arity(&outputs, 1)?;
+        self.check_format()?;
+        s.equals(&inputs[0].datum_type, &outputs[0].datum_type)?;
+        s.equals(&inputs[1].datum_type, &outputs[0].datum_type)?;
+        s.equals(&inputs[2].datum_type, &outputs[0].datum_type)?;
+        s.equals(&inputs[0].shape, &outputs[0].shape)?;
+        s.equals(&inputs[1].rank, 1)?;
+        s.equals(&inputs[2].rank, 1)?;
+        s.given(&inputs[0].rank, move |s, rank| {
+            if rank < 3 {
+                bail!("InstanceNorm expects a [N, C, ...] input, got rank {}", rank)
+            }
+            let c_axis = match self.data_format {
+                DataFormat::NHWC => rank as usize - 1,
+                _ => 1,
+            };
+            s.equals(&inputs[1].shape[0], &inputs[0].shape[c_axis])?;
+            s.equals(&inputs[2].shape[0], &inputs[0].shape[c_axis])
+        })
+    }
+
+    inference_op_as_op!();
+    to_typed!();
+}
+
+impl TypedOp for InstanceNorm {
+    fn output_facts(&self, inputs: &[&TypedFact]) -> TractResult<TVec<TypedFact>> {
+        self.check_format()?;
+        if inputs[0].rank() < 3 {
+            bail!("InstanceNorm expects a [N, C, ...] input, got {:?}", inputs[0])
+        }
+        Ok(tvec!(TypedFact::dt_shape(inputs[0].datum_type, inputs[0].shape.clone())?))
+    }
+
+    typed_op_as_op!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Reference values follow torch.nn.InstanceNorm2d(affine=True)
+    // definition, computed in double precision.
+
+    fn input() -> Array4<f32> {
+        Array4::from_shape_fn((2, 2, 2, 3), |(n, c, h, w)| {
+            let i = ((n * 2 + c) * 2 + h) * 3 + w;
+            (0.53 * i as f32).cos() * 2.0 + n as f32
+        })
+    }
+
+    fn expected() -> Array4<f32> {
+        tensor4(&[
+            [
+                [[2.07681f32, 1.780277, 0.972042], [-0.126125, -1.212905, -1.990099]],
+                [[0.742102, 0.518214, -0.036481], [-0.769782, -1.480481, -1.973573]],
+            ],
+            [
+                [[2.148505, 1.773981, 0.918674], [-0.182732, -1.228029, -1.930399]],
+                [[0.788643, 0.513511, -0.071884], [-0.806917, -1.489905, -1.933447]],
+            ],
+        ])
+        .into_array::<f32>()
+        .unwrap()
+        .into_dimensionality()
+        .unwrap()
+    }
+
+    fn to_nhwc(a: ArrayView4<f32>) -> Array4<f32> {
+        let (n, c, h, w) = a.dim();
+        Array4::from_shape_fn((n, h, w, c), |(n, h, w, c)| a[(n, c, h, w)])
+    }
+
+    fn params() -> (Arc<Tensor>, Arc<Tensor>) {
+        (rctensor1(&[1.5f32, -1.0]), rctensor1(&[0.25f32, -0.5]))
+    }
+
+    fn assert_close(found: ArrayViewD<f32>, expected: ArrayViewD<f32>) {
+        assert_eq!(found.shape(), expected.shape());
+        for (f, e) in found.iter().zip(expected.iter()) {
+            assert!((f - e).abs() < 1e-4, "{} != {}", f, e);
+        }
+    }
+
+    #[test]
+    fn nchw_single() -> TractResult<()> {
+        let (scale, bias) = params();
+        let x = input().slice(s![0..1, .., .., ..]).to_owned();
+        let output = InstanceNorm::default().eval(tvec!(x.into_arc_tensor(), scale, bias))?;
+        let expected = expected().slice(s![0..1, .., .., ..]).to_owned();
+        assert_close(output[0].to_array_view::<f32>()?, expected.into_dyn().view());
+        Ok(())
+    }
+
+    #[test]
+    fn nchw_batched() -> TractResult<()> {
+        let (scale, bias) = params();
+        let output = InstanceNorm::default().eval(tvec!(input().into_arc_tensor(), scale, bias))?;
+        assert_close(output[0].to_array_view::<f32>()?, expected().into_dyn().view());
+        Ok(())
+    }
+
+    #[test]
+    fn nhwc_single() -> TractResult<()> {
+        let (scale, bias) = params();
+        let x = to_nhwc(input().slice(s![0..1, .., .., ..]));
+        let op = InstanceNorm::new(DataFormat::NHWC, 1e-5);
+        let output = op.eval(tvec!(x.into_arc_tensor(), scale, bias))?;
+        let expected = to_nhwc(expected().slice(s![0..1, .., .., ..]));
+        assert_close(output[0].to_array_view::<f32>()?, expected.into_dyn().view());
+        Ok(())
+    }
+
+    #[test]
+    fn nhwc_batched() -> TractResult<()> {
+        let (scale, bias) = params();
+        let x = to_nhwc(input().view());
+        let op = InstanceNorm::new(DataFormat::NHWC, 1e-5);
+        let output = op.eval(tvec!(x.into_arc_tensor(), scale, bias))?;
+        let expected = to_nhwc(expected().view());
+        assert_close(output[0].to_array_view::<f32>()?, expected.into_dyn().view());
+        Ok(())
+    }
+}
@@ -0,0 +1,281 @@
+use crate::internal::*;
+use ndarray::*;
+
+/// Keys and values of the tokens seen so far, for autoregressive decoding.
+///
+/// Each evaluation appends the new keys and values (its two inputs) along
+/// `axis`, and outputs all the keys and values accumulated since the last
+/// reset. Buffers for `max_len` tokens are allocated on the first call.
+///
+/// The accumulated length is not known when the model is built: it is the
+/// `past` symbol in the output facts, distinct for each cache. The cache is
+/// cleared by `reset_kv_cache` or `reset_state`, on `SessionState` or
+/// `SimpleState`.
+///
+/// The outputs are copied out of the buffers at each evaluation, as a tensor
+/// can not borrow them: decoding `n` tokens one at a time copies `n²/2` keys
+/// and values overall.
+#[derive(Debug, Clone, new)]
+pub struct KVCache {
+    pub axis: usize,
+    pub max_len: usize,
+    #[new(value = r#"Symbol::fresh("P")"#)]
+    pub past: Symbol,
+}
+
+impl Op for KVCache {
+    fn name(&self) -> Cow<str> {
+        "KVCache".into()
+    }
+
+    fn info(&self) -> TractResult<Vec<String>> {
+        Ok(vec![format!("axis: {} max_len: {}", self.axis, self.max_len)])
+    }
+
+    op_as_typed_op!();
+    not_a_pulsed_op!();
+}
+
+impl StatefullOp for KVCache {
+    fn state(
+        &self,
+        _session: &mut SessionState,
+        _node_id: usize,
+    ) -> TractResult<Option<Box<dyn OpState>>> {
+        Ok(Some(Box::new(KVCacheState::default())))
+    }
+}
+
+/// Preallocated keys and values buffers, and the count of tokens in them.
+#[derive(Clone, Debug, Default)]
+pub struct KVCacheState {
+    buffers: Option<(Tensor, Tensor)>,
+    len: usize,
+}
+
+impl KVCacheState {
+    /// Number of tokens in the cache.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    fn append_t<T: Datum>(
+        buffer: &mut Tensor,
+        axis: usize,
+        len: usize,
+        new: &Tensor,
+    ) -> TractResult<Tensor> {
+        let new = new.to_array_view::<T>()?;
+        let added = new.shape()[axis];
+        let mut buffer = buffer.to_array_view_mut::<T>()?;
+        buffer.slice_axis_mut(Axis(axis), (len..len + added).into()).assign(&new);
+        Ok(buffer.slice_axis(Axis(axis), (0..len + added).into()).to_owned().into_tensor())
+    }
+}
+
+impl OpState for KVCacheState {
+    fn eval(
+        &mut self,
+        _session: &mut SessionState,
+        op: &dyn Op,
+        mut inputs: TVec<Arc<Tensor>>,
+    ) -> TractResult<TVec<Arc<Tensor>>> {
+        let op = op.downcast_ref::<KVCache>().ok_or("Wrong Op type")?;
+        let (k, v) = args_2!(inputs);
+        if k.rank() <= op.axis || k.rank() != v.rank() || k.shape()[op.axis] != v.shape()[op.axis] {
+            bail!("Inconsistent keys {:?} and values {:?}", k, v)
+        }
+        let added = k.shape()[op.axis];
+        if self.len + added > op.max_len {
+            bail!("KVCache is full: {} + {} tokens for {} slots", self.len, added, op.max_len)
+        }
+        let allocate = |t: &Tensor| -> TractResult<Tensor> {
+            let mut shape: TVec<usize> = t.shape().into();
+            shape[op.axis] = op.max_len;
+            unsafe { Tensor::uninitialized_dt(t.datum_type(), &shape) }
+        };
+        if self.buffers.is_none() {
+            self.buffers = Some((allocate(&k)?, allocate(&v)?));
+        }
+        let (k_buffer, v_buffer) = self.buffers.as_mut().unwrap();
+        for (buffer, new) in [(&*k_buffer, &k), (&*v_buffer, &v)].iter() {
+            let compatible = buffer.datum_type() == new.datum_type()
+                && (0..new.rank()).all(|ax| ax == op.axis || buffer.shape()[ax] == new.shape()[ax]);
+            if !compatible {
+                bail!("KVCache got {:?} after {:?} tokens of {:?}", new, self.len, buffer)
+            }
+        }
+        let (axis, len) = (op.axis, self.len);
+        let k = dispatch_datum!(Self::append_t(k.datum_type())(k_buffer, axis, len, &k))?;
+        let v = dispatch_datum!(Self::append_t(v.datum_type())(v_buffer, axis, len, &v))?;
+        self.len += added;
+        Ok(tvec!(k.into_arc_tensor(), v.into_arc_tensor()))
+    }
+
+    /// Buffers are kept for the next sequence.
+    fn reset(&mut self) -> TractResult<()> {
+        self.len = 0;
+        Ok(())
+    }
+}
+
+impl InferenceRulesOp for KVCache {
+    fn rules<'r, 'p: 'r, 's: 'r>(
+        &'s self,
+        s: &mut Solver<'r>,
+        inputs: &'p [TensorProxy],
+        outputs: &'p [TensorProxy],
+    ) -> InferenceResult {
+        check_input_arity(&inputs, 2)?;
+        check_output_arity(&outputs, 2)?;
+        for (input, output) in inputs.iter().zip(outputs.iter()) {
+            s.equals(&input.datum_type, &output.datum_type)?;
+            s.equals(&input.rank, &output.rank)?;
+            s.given(&input.rank, move |s, rank| {
+                for axis in 0..rank as usize {
+                    if axis == self.axis {
+                        s.equals(&output.shape[axis], TDim::sym(self.past.clone()))?;
+                    } else {
+                        s.equals(&input.shape[axis], &output.shape[axis])?;
+                    }
+                }
+                Ok(())
+            })?;
+        }
+        Ok(())
+    }
+
+    inference_op_as_op!();
+    to_typed!();
+}
+
+impl TypedOp for KVCache {
+    fn output_facts(&self, inputs: &[&TypedFact]) -> TractResult<TVec<TypedFact>> {
+        inputs
+            .iter()
+            .map(|input| {
+                if input.rank() <= self.axis {
+                    bail!("KVCache axis is {}, got {:?}", self.axis, input)
+                }
+                let mut shape: TVec<TDim> = input.shape.iter().collect();
+                shape[self.axis] = TDim::sym(self.past.clone());
+                TypedFact::dt_shape(input.datum_type, &*shape)
+            })
+            .collect()
+    }
+
+    typed_op_as_op!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ops::nn::ScaledDotProductAttention;
+
+    const D: usize = 4;
+    const STEPS: usize = 10;
+
+    fn token(seed: usize, factor: f32) -> Array3<f32> {
+        Array3::from_shape_fn((1, 1, D), |(_, _, i)| ((seed * 5 + i * 3) % 7) as f32 * factor - 1.0)
+    }
+
+    // one decoding step: attention of the new token over all the previous ones
+    fn step_model() -> TractResult<TypedModel> {
+        let mut model = TypedModel::default();
+        let fact = TypedFact::dt_shape(f32::datum_type(), [1, 1, D].as_ref())?;
+        let q = model.add_source("q", fact.clone())?;
+        let k = model.add_source("k", fact.clone())?;
+        let v = model.add_source("v", fact)?;
+        let kv = model.wire_node("cache", KVCache::new(1, 16), &[k, v])?;
+        let attention = ScaledDotProductAttention::for_depth(D);
+        let o = model.wire_node("attention", attention, &[q, kv[0], kv[1]])?;
+        model.set_output_outlets(&o)?;
+        Ok(model)
+    }
+
+    #[test]
+    fn autoregressive_matches_full_sequence() -> TractResult<()> {
+        let qs: Vec<_> = (0..STEPS).map(|t| token(t, 0.3)).collect();
+        let ks: Vec<_> = (0..STEPS).map(|t| token(t + 1, 0.2)).collect();
+        let vs: Vec<_> = (0..STEPS).map(|t| token(t + 2, 0.5)).collect();
+
+        let plan = SimplePlan::new(step_model()?)?;
+        let mut state = SimpleState::new(&plan)?;
+        let mut steps = vec![];
+        for t in 0..STEPS {
+            let inputs = tvec!(qs[t].clone().into(), ks[t].clone().into(), vs[t].clone().into());
+            steps.push(state.run(inputs)?.remove(0));
+        }
+
+        let concat = |xs: &[Array3<f32>]| -> TractResult<Arc<Tensor>> {
+            let views: Vec<_> = xs.iter().map(|x| x.view()).collect();
+            Ok(ndarray::stack(Axis(1), &views)?.into_arc_tensor())
+        };
+        let causal =
+            Array2::from_shape_fn((STEPS, STEPS), |(i, j)| if j > i { -1e9f32 } else { 0.0 });
+        let full = ScaledDotProductAttention::for_depth(D)
+            .eval(tvec!(concat(&qs)?, concat(&ks)?, concat(&vs)?, causal.into_arc_tensor()))?
+            .remove(0);
+        let full = full.to_array_view::<f32>()?;
+        for (t, found) in steps.iter().enumerate() {
+            let expected = full.slice(s![.., t..t + 1, ..]).to_owned().into_tensor();
+            found.close_enough(&expected, true)?;
+        }
+
+        // a single key: the attention output is its value
+        state.session_state.reset_kv_cache();
+        let inputs = tvec!(qs[3].clone().into(), ks[5].clone().into(), vs[5].clone().into());
+        let restarted = state.run(inputs)?.remove(0);
+        restarted.close_enough(&vs[5].clone().into_tensor(), true)?;
+        Ok(())
+    }
+
+    #[test]
+    fn one_symbol_per_cache() -> TractResult<()> {
+        let mut model = TypedModel::default();
+        let fact = TypedFact::dt_shape(f32::datum_type(), [1, 1, D].as_ref())?;
+        let x = model.add_source("x", fact)?;
+        let a = model.wire_node("a", KVCache::new(1, 16), &[x, x])?;
+        let b = model.wire_node("b", KVCache::new(1, 16), &[x, x])?;
+        let past = |outlet| -> TractResult<TDim> { Ok(model.outlet_fact(outlet)?.shape.dim(1)) };
+        assert!(past(a[0])?.is_symbolic());
+        assert_eq!(past(a[0])?, past(a[1])?);
+        assert_ne!(past(a[0])?, past(b[0])?);
+        Ok(())
+    }
+
+    #[test]
+    fn reset_kv_cache() -> TractResult<()> {
+        let model = step_model()?;
+        let cache = model.node_by_name("cache")?.id;
+        let plan = SimplePlan::new(&model)?;
+        let mut state = SimpleState::new(&plan)?;
+        let len = |state: &SimpleState<_, _, _, _>| {
+            state.states[cache].as_ref().unwrap().downcast_ref::<KVCacheState>().unwrap().len()
+        };
+        for t in 0..3 {
+            state.run(tvec!(token(t, 1.0).into(), token(t, 1.0).into(), token(t, 1.0).into()))?;
+        }
+        assert_eq!(len(&state), 3);
+        state.reset_kv_cache()?;
+        assert_eq!(len(&state), 0);
+        Ok(())
+    }
+
+    #[test]
+    fn full_cache() -> TractResult<()> {
+        let plan = SimplePlan::new(step_model()?)?;
+        let mut state = SimpleState::new(&plan)?;
+        for t in 0..16 {
+            state.run(tvec!(token(t, 1.0).into(), token(t, 1.0).into(), token(t, 1.0).into()))?;
+        }
+        assert!(state
+            .run(tvec!(token(0, 1.0).into(), token(0, 1.0).into(), token(0, 1.0).into()))
+            .is_err());
+        Ok(())
+    }
+}
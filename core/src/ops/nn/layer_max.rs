@@ -1,4 +1,5 @@
 use crate::internal::*;
+use crate::ops::{ComputeCost, OpCost};
 
 #[derive(Debug, Clone, new, Default)]
 pub struct LayerHardmax {
@@ -196,6 +197,10 @@ pub struct LayerSoftmax {
 }
 
 impl LayerSoftmax {
+    pub fn axis(&self) -> isize {
+        self.axis
+    }
+
     fn eval_t<D: Datum + ::num_traits::Float + ::num_traits::FromPrimitive + ::std::iter::Sum>(
         &self,
         input: Arc<Tensor>,
@@ -273,9 +278,23 @@ impl TypedOp for LayerSoftmax {
         pulsify(self, self.axis, node, target, mapping)
     }
 
+    fn as_op_cost(&self) -> Option<&dyn OpCost> {
+        Some(self)
+    }
+
     typed_op_as_op!();
 }
 
+impl OpCost for LayerSoftmax {
+    /// Max, subtraction, exponential, sum and division: five flops per
+    /// input element.
+    fn compute_cost(&self, inputs: &[&TypedFact]) -> TractResult<ComputeCost> {
+        let bytes = crate::ops::cost::fact_bytes(inputs[0])?;
+        let len = bytes / inputs[0].datum_type.size_of() as u64;
+        Ok(ComputeCost { flops: 5 * len, memory_bytes: 2 * bytes })
+    }
+}
+
 impl PulsedOp for LayerSoftmax {
     fn pulsed_output_facts(&self, inputs: &[&PulsedFact]) -> TractResult<TVec<PulsedFact>> {
         Ok(tvec!(inputs[0].clone()))
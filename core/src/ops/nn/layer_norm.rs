@@ -0,0 +1,253 @@
+use crate::internal::*;
+use ndarray::*;
+use num_traits::Float;
+
+/// Mean and (biased) variance of the values, in one pass, using Welford's
+/// algorithm.
+pub(crate) fn mean_and_variance<'a, T: Float + 'a>(xs: impl IntoIterator<Item = &'a T>) -> (T, T) {
+    let mut mean = T::zero();
+    let mut m2 = T::zero();
+    let mut n = 0;
+    for &v in xs {
+        n += 1;
+        let delta = v - mean;
+        mean = mean + delta / T::from(n).unwrap();
+        m2 = m2 + delta * (v - mean);
+    }
+    (mean, m2 / T::from(n.max(1)).unwrap())
+}
+
+/// Layer normalization, as in ONNX opset 17.
+///
+/// Inputs are the data, a scale and an optional bias, the two latter
+/// broadcasting to the normalized shape (the data shape from `axis` on).
+/// Outputs are the normalized data, then the mean and the inverse standard
+/// deviation of each normalized block, with the normalized axes kept as
+/// ones.
+///
+/// Mean and variance are computed in one pass with Welford's algorithm, no
+/// intermediate tensor is materialized.
+#[derive(Debug, Clone, new)]
+pub struct LayerNorm {
+    pub axis: i64,
+    pub epsilon: f32,
+}
+
+impl Default for LayerNorm {
+    fn default() -> LayerNorm {
+        LayerNorm { axis: -1, epsilon: 1e-5 }
+    }
+}
+
+impl LayerNorm {
+    fn resolved_axis(&self, rank: usize) -> TractResult<usize> {
+        if 0 <= self.axis && self.axis < rank as i64 {
+            Ok(self.axis as usize)
+        } else if -(rank as i64) <= self.axis && self.axis < 0 {
+            Ok((self.axis + rank as i64) as usize)
+        } else {
+            bail!("Illegal axis {} for rank {}", self.axis, rank)
+        }
+    }
+
+    fn stats_shape<D: DimLike>(&self, shape: &[D]) -> TractResult<TVec<D>> {
+        let axis = self.resolved_axis(shape.len())?;
+        Ok(shape
+            .iter()
+            .enumerate()
+            .map(|(ix, d)| if ix < axis { d.clone() } else { D::one() })
+            .collect())
+    }
+
+    fn broadcast_param<T: Datum + Float>(
+        param: Option<&Arc<Tensor>>,
+        shape: &[usize],
+        default: T,
+    ) -> TractResult<Vec<T>> {
+        match param {
+            None => Ok(vec![default; shape.iter().product()]),
+            Some(t) => {
+                let view = t.to_array_view::<T>()?;
+                let view = view.broadcast(shape).ok_or_else(|| {
+                    format!("Can not broadcast {:?} to normalized shape {:?}", t.shape(), shape)
+                })?;
+                Ok(view.iter().cloned().collect())
+            }
+        }
+    }
+
+    fn eval_t<T: Datum + Float>(&self, inputs: &[Arc<Tensor>]) -> TractResult<TVec<Arc<Tensor>>> {
+        let input = &inputs[0];
+        let axis = self.resolved_axis(input.rank())?;
+        let norm_shape = &input.shape()[axis..];
+        let inner: usize = norm_shape.iter().product();
+        let scale = Self::broadcast_param(inputs.get(1), norm_shape, T::one())?;
+        let bias = Self::broadcast_param(inputs.get(2), norm_shape, T::zero())?;
+        let epsilon = T::from(self.epsilon).unwrap();
+        let x = input.as_slice::<T>()?;
+        let mut output = Vec::with_capacity(x.len());
+        let mut means = Vec::with_capacity(x.len() / inner.max(1));
+        let mut inv_std_devs = Vec::with_capacity(x.len() / inner.max(1));
+        for row in x.chunks(inner.max(1)) {
+            let (mean, var) = mean_and_variance(row);
+            let inv_std_dev = (var + epsilon).sqrt().recip();
+            output.extend(
+                row.iter()
+                    .zip(scale.iter().zip(bias.iter()))
+                    .map(|(&v, (&s, &b))| (v - mean) * inv_std_dev * s + b),
+            );
+            means.push(mean);
+            inv_std_devs.push(inv_std_dev);
+        }
+        let stats_shape = self.stats_shape(input.shape())?;
+        let output = ArrayD::from_shape_vec(input.shape(), output)?;
+        let means = ArrayD::from_shape_vec(&*stats_shape, means)?;
+        let inv_std_devs = ArrayD::from_shape_vec(&*stats_shape, inv_std_devs)?;
+        Ok(tvec!(output.into_arc_tensor(), means.into_arc_tensor(), inv_std_devs.into_arc_tensor()))
+    }
+}
+
+impl Op for LayerNorm {
+    fn name(&self) -> Cow<str> {
+        "LayerNorm".into()
+    }
+
+    fn info(&self) -> TractResult<Vec<String>> {
+        Ok(vec![format!("axis: {}, epsilon: {}", self.axis, self.epsilon)])
+    }
+
+    fn validation(&self) -> Validation {
+        Validation::Rounding
+    }
+
+    op_as_typed_op!();
+    not_a_pulsed_op!();
+}
+
+impl StatelessOp for LayerNorm {
+    fn eval(&self, inputs: TVec<Arc<Tensor>>) -> TractResult<TVec<Arc<Tensor>>> {
+        if inputs.len() < 2 || inputs.len() > 3 {
+            bail!("LayerNorm expects 2 or 3 inputs, got {}", inputs.len())
+        }
+        dispatch_floatlike!(Self::eval_t(inputs[0].datum_type())(self, &*inputs))
+    }
+}
+
+impl InferenceRulesOp for LayerNorm {
+    fn rules<'r, 'p: 'r, 's: 'r>(
+        &'s self,
+        s: &mut Solver<'r>,
+        inputs: &'p [TensorProxy],
+        outputs: &'p [TensorProxy],
+    ) -> InferenceResult {
+        if inputs.len() < 2 || inputs.len() > 3 {
+            bail!("LayerNorm expects 2 or 3 inputs, got {}", inputs.len())
+        }
+        check_output_arity(&outputs, 3)?;
+        for input in &inputs[1..] {
+            s.equals(&input.datum_type, &inputs[0].datum_type)?;
+        }
+        for output in outputs {
+            s.equals(&output.datum_type, &inputs[0].datum_type)?;
+            s.equals(&output.rank, &inputs[0].rank)?;
+        }
+        s.equals(&outputs[0].shape, &inputs[0].shape)?;
+        s.given(&inputs[0].shape, move |s, shape| {
+            let stats_shape = self.stats_shape(&*shape)?;
+            s.equals(&outputs[1].shape, stats_shape.clone())?;
+            s.equals(&outputs[2].shape, stats_shape)
+        })
+    }
+
+    fn nboutputs(&self) -> TractResult<usize> {
+        Ok(3)
+    }
+
+    inference_op_as_op!();
+    to_typed!();
+}
+
+impl TypedOp for LayerNorm {
+    fn output_facts(&self, inputs: &[&TypedFact]) -> TractResult<TVec<TypedFact>> {
+        let shape = inputs[0].shape.to_tvec();
+        let stats = TypedFact::dt_shape(inputs[0].datum_type, &*self.stats_shape(&*shape)?)?;
+        Ok(tvec!(TypedFact::dt_shape(inputs[0].datum_type, &*shape)?, stats.clone(), stats))
+    }
+
+    typed_op_as_op!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ops::math;
+    use crate::ops::nn::{Reduce, Reducer};
+
+    fn input() -> Tensor {
+        tensor3(&[
+            [[1f32, 2.0, 3.0, 4.0], [-1.0, 0.5, 0.25, 8.0]],
+            [[1000.0, 1000.5, 999.5, 1001.0], [0.0, 0.0, 0.0, 0.0]],
+        ])
+    }
+
+    // reference: the decomposed computation found in older ONNX exports
+    fn decomposed(
+        x: Tensor,
+        scale: Tensor,
+        bias: Tensor,
+        epsilon: f32,
+    ) -> TractResult<TVec<Arc<Tensor>>> {
+        let mut model = InferenceModel::default();
+        let source =
+            model.add_source("x", InferenceFact::dt_shape(f32::datum_type(), x.shape()))?;
+        let scale = model.add_const("scale", scale)?;
+        let bias = model.add_const("bias", bias)?;
+        let two = model.add_const("two", tensor0(2f32))?;
+        let eps = model.add_const("eps", tensor0(epsilon))?;
+        let mean =
+            model.wire_node("mean", Reduce::new(Some(vec![-1]), true, Reducer::Mean), &[source])?
+                [0];
+        let d = model.wire_node("d", math::sub::bin(), &[source, mean])?[0];
+        let sq = model.wire_node("sq", math::pow::bin(), &[d, two])?[0];
+        let var =
+            model.wire_node("var", Reduce::new(Some(vec![-1]), true, Reducer::Mean), &[sq])?[0];
+        let var_eps = model.wire_node("var_eps", math::add::bin(), &[var, eps])?[0];
+        let std = model.wire_node("std", math::sqrt(), &[var_eps])?[0];
+        let norm = model.wire_node("norm", math::div::bin(), &[d, std])?[0];
+        let scaled = model.wire_node("scaled", math::mul::bin(), &[norm, scale])?[0];
+        let output = model.wire_node("output", math::add::bin(), &[scaled, bias])?[0];
+        model.set_output_outlets(&[output])?;
+        let model = model.into_typed()?;
+        SimplePlan::new(&model)?.run(tvec!(x))
+    }
+
+    #[test]
+    fn matches_decomposed() -> TractResult<()> {
+        let scale = tensor1(&[1f32, 0.5, 2.0, -1.0]);
+        let bias = tensor1(&[0f32, 1.0, -1.0, 0.5]);
+        let expected = decomposed(input(), scale.clone(), bias.clone(), 1e-5)?;
+        let found =
+            LayerNorm::new(-1, 1e-5).eval(tvec!(input().into(), scale.into(), bias.into()))?;
+        let found = found[0].to_array_view::<f32>()?;
+        let expected = expected[0].to_array_view::<f32>()?;
+        for (f, e) in found.iter().zip(expected.iter()) {
+            assert!((f - e).abs() < 1e-5, "{} != {}", f, e);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn stats_outputs() -> TractResult<()> {
+        let found = LayerNorm::new(1, 0.0).eval(tvec!(input().into(), rctensor0(1f32)))?;
+        assert_eq!(found[1].shape(), &[2, 1, 1]);
+        let means = found[1].as_slice::<f32>()?;
+        assert!((means[0] - 2.21875).abs() < 1e-5);
+        assert!((means[1] - 500.125).abs() < 1e-3);
+        let inv_std = found[2].as_slice::<f32>()?;
+        let var: f32 =
+            input().as_slice::<f32>()?[..8].iter().map(|x| (x - 2.21875).powi(2)).sum::<f32>()
+                / 8.0;
+        assert!((inv_std[0] - var.sqrt().recip()).abs() < 1e-5);
+        Ok(())
+    }
+}
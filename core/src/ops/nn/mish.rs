@@ -0,0 +1,165 @@
+use crate::internal::*;
+use crate::ops::binary::UnaryOp;
+use crate::ops::math::{Add, Exp, Ln, Mul, Tanh};
+use crate::ops::nn::Softplus;
+use crate::optim::{is_binary, is_element_wise, single_use};
+use num_traits::Float;
+
+// Mish: `x * tanh(softplus(x))`.
+element_wise!(mish, Mish,
+    [f32] => |_, xs| {
+        xs.iter_mut().for_each(|x| *x = mish_t(*x));
+        Ok(())
+    },
+    [f64] => |_, xs| {
+        xs.iter_mut().for_each(|x| *x = mish_t(*x));
+        Ok(())
+};
+    cost: |dt| {tvec!((Cost::FMA(dt), 16), (Cost::Div(dt), 1))}
+);
+
+/// Above this, softplus(x) is x up to rounding, as in PyTorch softplus.
+const SOFTPLUS_THRESHOLD: f64 = 20.0;
+
+fn mish_t<T: Float>(x: T) -> T {
+    if x > T::from(SOFTPLUS_THRESHOLD).unwrap() {
+        // tanh(sp) = 1 - 2 / (exp(2 sp) + 1), with sp = x. exp overflows to
+        // infinity for large x, the result going to x, not to NaN.
+        let two = T::one() + T::one();
+        x * (T::one() - two / ((two * x).exp() + T::one()))
+    } else {
+        x * x.exp().ln_1p().tanh()
+    }
+}
+
+/// The x of a `softplus(x)` or `ln(exp(x) + 1)` subgraph.
+fn softplus_input(model: &TypedModel, node: &TypedNode) -> TractResult<Option<OutletId>> {
+    if is_element_wise::<Softplus>(node) {
+        return Ok(Some(node.inputs[0]));
+    }
+    if !is_element_wise::<Ln>(node) {
+        return Ok(None);
+    }
+    if let Some(add) = single_use(model, node.inputs[0]) {
+        if let Some(op) = add.op_as::<UnaryOp>() {
+            let one = op.a.len() == 1 && op.a.cast_to_scalar::<f32>()? == 1.0;
+            if op.mini_op.is::<Add>() && one {
+                if let Some(exp) = single_use(model, add.inputs[0]) {
+                    if is_element_wise::<Exp>(exp) {
+                        return Ok(Some(exp.inputs[0]));
+                    }
+                }
+            }
+        }
+    }
+    Ok(None)
+}
+
+/// Replace `x * tanh(softplus(x))`, and its `ln(exp(x) + 1)` expansion, as
+/// exported to ONNX before Mish was an ONNX operator, by `Mish(x)`.
+pub(crate) fn fuse_mish(
+    model: &TypedModel,
+    node: &TypedNode,
+) -> TractResult<Option<TypedModelPatch>> {
+    if !is_binary::<Mul>(node) {
+        return Ok(None);
+    }
+    let dt = model.outlet_fact(node.inputs[0])?.datum_type;
+    if !(dt == f32::datum_type() || dt == f64::datum_type()) {
+        return Ok(None);
+    }
+    for &(t, x) in &[(0, 1), (1, 0)] {
+        let tanh = match single_use(model, node.inputs[t]) {
+            Some(tanh) if is_element_wise::<Tanh>(tanh) => tanh,
+            _ => continue,
+        };
+        if let Some(softplus) = single_use(model, tanh.inputs[0]) {
+            if softplus_input(model, softplus)? == Some(node.inputs[x]) {
+                let mut patch = TypedModelPatch::default();
+                let x = patch.tap_model(model, node.inputs[x])?;
+                let fused = patch.wire_node(&*node.name, mish(), &[x])?[0];
+                patch.shunt_outside(OutletId::new(node.id, 0), fused)?;
+                return Ok(Some(patch));
+            }
+        }
+    }
+    Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ops::binary::TypedBinOp;
+    use crate::ops::math::{exp, ln, tanh};
+    use crate::ops::nn::softplus;
+
+    #[test]
+    fn mish_matches_reference() -> TractResult<()> {
+        // x * np.tanh(np.log1p(np.exp(x))), in f64
+        let input = [-10f64, -5., -2., -1., -0.5, 0., 0.5, 1., 2., 5., 10.];
+        let expected = [
+            -0.00045398899185674697f64,
+            -0.033576237730161704,
+            -0.2525014826957089,
+            -0.30340146137410895,
+            -0.22074377465173,
+            0.0,
+            0.3752452113048951,
+            0.8650983882673103,
+            1.9439589595339946,
+            4.999552077529406,
+            9.999999958780672,
+        ];
+        let found = mish().eval(tvec!(rctensor1(&input)))?.remove(0);
+        found.close_enough(&tensor1(&expected), true)?;
+        let input: Vec<f32> = input.iter().map(|&x| x as f32).collect();
+        let expected: Vec<f32> = expected.iter().map(|&x| x as f32).collect();
+        let found = mish().eval(tvec!(rctensor1(&input)))?.remove(0);
+        found.close_enough(&tensor1(&expected), true)
+    }
+
+    #[test]
+    fn large_inputs() -> TractResult<()> {
+        let found = mish().eval(tvec!(rctensor1(&[-200f32, 20.5, 50.0, 1e30])))?.remove(0);
+        let found = found.as_slice::<f32>()?;
+        assert!(found[0].abs() < 1e-30);
+        assert_eq!(&found[1..], &[20.5, 50.0, 1e30]);
+        Ok(())
+    }
+
+    fn decomposed(expanded: bool) -> TractResult<TypedModel> {
+        let mut model = TypedModel::default();
+        let fact = TypedFact::dt_shape(f32::datum_type(), [3, 7].as_ref())?;
+        let x = model.add_source("x", fact)?;
+        let sp = if expanded {
+            let e = model.wire_node("exp", exp(), &[x])?;
+            let one = model.add_const("one", rctensor0(1f32))?;
+            let e1 = model.wire_node("add", TypedBinOp(Box::new(Add)), &[e[0], one])?;
+            model.wire_node("ln", ln(), &e1)?
+        } else {
+            model.wire_node("softplus", softplus(), &[x])?
+        };
+        let t = model.wire_node("tanh", tanh(), &sp)?;
+        let y = model.wire_node("mish", TypedBinOp(Box::new(Mul)), &[x, t[0]])?;
+        model.set_output_outlets(&y)?;
+        Ok(model)
+    }
+
+    #[test]
+    fn fuse_decomposed_mish() -> TractResult<()> {
+        let input = tensor1(&(0..21).map(|i| i as f32 - 10.0).collect::<Vec<_>>())
+            .into_array::<f32>()?
+            .into_shape((3, 7))?
+            .into_tensor();
+        for &expanded in &[false, true] {
+            let model = decomposed(expanded)?;
+            let reference = SimplePlan::new(model.clone())?.run(tvec!(input.clone()))?.remove(0);
+            let model = model.declutter()?;
+            assert_eq!(model.nodes().len(), 2, "{:#?}", model);
+            assert!(is_element_wise::<Mish>(model.node(model.output_outlets()?[0].node)));
+            let found = SimplePlan::new(model)?.run(tvec!(input.clone()))?.remove(0);
+            found.close_enough(&reference, true)?;
+        }
+        Ok(())
+    }
+}
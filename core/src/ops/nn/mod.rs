@@ -1,16 +1,50 @@
 mod arg_max_min;
+mod attention;
+mod celu;
 mod data_formats;
 mod global_pools;
+mod group_norm;
+mod hard_sigmoid;
+mod instance_norm;
+mod kvcache;
 mod layer_max;
+mod layer_norm;
 mod lrn;
+mod mish;
+mod mvn;
 mod reduce;
+mod rms_norm;
+mod rope;
+mod swiglu;
+#[cfg(test)]
+mod test_utils;
+mod thresholded_relu;
 
 pub use self::arg_max_min::ArgMaxMin;
+pub(crate) use self::attention::fuse_attention;
+pub use self::attention::ScaledDotProductAttention;
+pub use self::celu::{celu, Celu};
 pub use self::data_formats::{BaseDataShape, DataFormat, DataShape};
 pub use self::global_pools::{GlobalAvgPool, GlobalLpPool, GlobalMaxPool};
+pub use self::group_norm::GroupNorm;
+pub use self::hard_sigmoid::{hard_sigmoid, hard_swish, HardSigmoid, HardSwish};
+pub use self::instance_norm::InstanceNorm;
+pub use self::kvcache::{KVCache, KVCacheState};
 pub use self::layer_max::{LayerHardmax, LayerLogSoftmax, LayerSoftmax};
+pub use self::layer_norm::LayerNorm;
 pub use self::lrn::Lrn;
-pub use self::reduce::{Reduce, Reducer};
+pub(crate) use self::mish::fuse_mish;
+pub use self::mish::{mish, Mish};
+pub use self::mvn::Mvn;
+pub use self::reduce::{Reduce, Reducer, TypedReduce};
+pub(crate) use self::rms_norm::{fuse_rms_norm, fuse_rms_norm_weight};
+pub use self::rms_norm::RMSNorm;
+pub(crate) use self::rope::fuse_rotary_embedding;
+pub use self::rope::RotaryEmbedding;
+pub(crate) use self::swiglu::{fuse_silu, fuse_swiglu};
+pub use self::swiglu::{ge_glu, silu, swi_glu, GeGLU, Silu, SwiGLU};
+pub(crate) use self::thresholded_relu::fuse_threshold_relu;
+pub use self::thresholded_relu::{threshold_relu, ThresholdRelu};
 
 use num_traits::{AsPrimitive, Float};
 
@@ -39,12 +73,6 @@ element_wise!(elu, Elu { alpha: f32 },
         Ok(())
 });
 
-element_wise!(hard_sigmoid, HardSigmoid { alpha: f32, beta: f32 },
-    [f32, f64] => |e, xs| {
-        xs.iter_mut().for_each(|x| { *x = x.hard_sigmoid(e.alpha, e.beta); });
-        Ok(())
-});
-
 element_wise!(leaky_relu, LeakyRelu { alpha: f32 },
     [f32, f64] => |e, xs| {
         xs.iter_mut().for_each(|x| { *x = x.leaky_relu(e.alpha); });
@@ -69,20 +97,12 @@ element_wise!(selu, Selu { alpha: f32, gamma: f32 },
         Ok(())
 });
 
-element_wise!(threshold_relu, ThresholdRelu { alpha: f32 },
-    [f32, f64] => |e, xs| {
-        xs.iter_mut().for_each(|x| { *x = x.threshold_relu(e.alpha); });
-        Ok(())
-});
-
 trait Activations {
     fn elu(self, alpha: f32) -> Self;
-    fn hard_sigmoid(self, alpha: f32, beta: f32) -> Self;
     fn leaky_relu(self, alpha: f32) -> Self;
     fn parametric_softplus(self, alpha: f32, beta: f32) -> Self;
     fn scaled_tanh(self, alpha: f32, beta: f32) -> Self;
     fn selu(self, alpha: f32, gamma: f32) -> Self;
-    fn threshold_relu(self, alpha: f32) -> Self;
 }
 
 impl<T> Activations for T
@@ -97,9 +117,6 @@ where
             self
         }
     }
-    fn hard_sigmoid(self, alpha: f32, beta: f32) -> Self {
-        (alpha.as_() * self + beta.as_()).min(1.0.as_()).max(0.0.as_())
-    }
     fn leaky_relu(self, alpha: f32) -> Self {
         if self < 0.0.as_() {
             alpha.as_() * self
@@ -120,11 +137,4 @@ where
             gamma.as_() * self
         }
     }
-    fn threshold_relu(self, alpha: f32) -> Self {
-        if self <= alpha.as_() {
-            0.0.as_()
-        } else {
-            self
-        }
-    }
 }
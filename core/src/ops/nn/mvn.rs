@@ -0,0 +1,207 @@
+use crate::internal::*;
+use ndarray::*;
+use num_traits::Float;
+
+/// Mean variance normalization, as in ONNX.
+///
+/// The input is centered over `axes`, then if `normalize_variance` is set,
+/// divided by its standard deviation over the same axes plus `epsilon`.
+///
+/// The mean and the variance are computed in two passes, the variance
+/// being accumulated on the centered values.
+#[derive(Debug, Clone, new)]
+pub struct Mvn {
+    pub axes: Vec<i64>,
+    pub normalize_variance: bool,
+    pub epsilon: f32,
+}
+
+impl Default for Mvn {
+    fn default() -> Mvn {
+        Mvn { axes: vec![0, 2, 3], normalize_variance: true, epsilon: 1e-9 }
+    }
+}
+
+impl Mvn {
+    fn resolved_axes(&self, rank: usize) -> TractResult<Vec<usize>> {
+        let mut axes = self
+            .axes
+            .iter()
+            .map(|&axis| {
+                if 0 <= axis && axis < rank as i64 {
+                    Ok(axis as usize)
+                } else if -(rank as i64) <= axis && axis < 0 {
+                    Ok((axis + rank as i64) as usize)
+                } else {
+                    bail!("Illegal axis {} for rank {}", axis, rank)
+                }
+            })
+            .collect::<TractResult<Vec<usize>>>()?;
+        axes.sort();
+        axes.dedup();
+        Ok(axes)
+    }
+
+    fn reduce<T: Datum + Float>(x: &ArrayD<T>, axes: &[usize], n: T) -> ArrayD<T> {
+        let mut sum = x.clone();
+        for &axis in axes {
+            sum = sum.sum_axis(Axis(axis)).insert_axis(Axis(axis));
+        }
+        sum.mapv_inplace(|s| s / n);
+        sum
+    }
+
+    fn eval_global<T: Datum + Float>(&self, mut output: Tensor) -> TractResult<Tensor> {
+        let xs = output.as_slice_mut::<T>()?;
+        let n = T::from(xs.len().max(1)).unwrap();
+        let mean = xs.iter().fold(T::zero(), |acc, &x| acc + x) / n;
+        xs.iter_mut().for_each(|x| *x = *x - mean);
+        if self.normalize_variance {
+            let var = xs.iter().fold(T::zero(), |acc, &x| acc + x * x) / n;
+            let std = var.sqrt() + T::from(self.epsilon).unwrap();
+            xs.iter_mut().for_each(|x| *x = *x / std);
+        }
+        Ok(output)
+    }
+
+    fn eval_t<T: Datum + Float>(&self, input: Arc<Tensor>) -> TractResult<Arc<Tensor>> {
+        let axes = self.resolved_axes(input.rank())?;
+        if axes.len() == input.rank() {
+            return Ok(self.eval_global::<T>(input.into_tensor())?.into_arc_tensor());
+        }
+        let n = T::from(axes.iter().map(|&a| input.shape()[a]).product::<usize>()).unwrap();
+        let mut output = input.into_tensor().into_array::<T>()?;
+        let mean = Self::reduce(&output, &axes, n);
+        Zip::from(&mut output).and_broadcast(&mean).apply(|x, &m| *x = *x - m);
+        if self.normalize_variance {
+            let mut std = Self::reduce(&output.mapv(|x| x * x), &axes, n);
+            let epsilon = T::from(self.epsilon).unwrap();
+            std.mapv_inplace(|v| v.sqrt() + epsilon);
+            Zip::from(&mut output).and_broadcast(&std).apply(|x, &s| *x = *x / s);
+        }
+        Ok(output.into_arc_tensor())
+    }
+}
+
+impl Op for Mvn {
+    fn name(&self) -> Cow<str> {
+        "Mvn".into()
+    }
+
+    fn info(&self) -> TractResult<Vec<String>> {
+        Ok(vec![format!(
+            "axes: {:?}, normalize_variance: {}, epsilon: {}",
+            self.axes, self.normalize_variance, self.epsilon
+        )])
+    }
+
+    fn validation(&self) -> Validation {
+        Validation::Rounding
+    }
+
+    op_as_typed_op!();
+    not_a_pulsed_op!();
+}
+
+impl StatelessOp for Mvn {
+    fn eval(&self, mut inputs: TVec<Arc<Tensor>>) -> TractResult<TVec<Arc<Tensor>>> {
+        let input = args_1!(inputs);
+        let output = match input.datum_type() {
+            DatumType::F32 => self.eval_t::<f32>(input)?,
+            DatumType::F64 => self.eval_t::<f64>(input)?,
+            dt => bail!("Mvn does not support {:?}", dt),
+        };
+        Ok(tvec!(output))
+    }
+}
+
+impl InferenceRulesOp for Mvn {
+    fn rules<'r, 'p: 'r, 's: 'r>(
+        &'s self,
+        s: &mut Solver<'r>,
+        inputs: &'p [TensorProxy],
+        outputs: &'p [TensorProxy],
+    ) -> InferenceResult {
+        check_input_arity(&inputs, 1)?;
+        check_output_arity(&outputs, 1)?;
+        s.equals(&inputs[0].datum_type, &outputs[0].datum_type)?;
+        s.equals(&inputs[0].shape, &outputs[0].shape)?;
+        s.given(&inputs[0].rank, move |_, rank| {
+            self.resolved_axes(rank as usize)?;
+            Ok(())
+        })
+    }
+
+    inference_op_as_op!();
+    to_typed!();
+}
+
+impl TypedOp for Mvn {
+    fn output_facts(&self, inputs: &[&TypedFact]) -> TractResult<TVec<TypedFact>> {
+        self.resolved_axes(inputs[0].rank())?;
+        Ok(tvec!(TypedFact::dt_shape(inputs[0].datum_type, inputs[0].shape.clone())?))
+    }
+
+    typed_op_as_op!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn input() -> Tensor {
+        tensor4(&[[[[1f32, 2.0]], [[3.0, 5.0]]], [[[0.5, -1.0]], [[8.0, 4.0]]]])
+    }
+
+    fn assert_close(found: &Tensor, expected: &Tensor) -> TractResult<()> {
+        assert_eq!(found.shape(), expected.shape());
+        for (f, e) in found.as_slice::<f32>()?.iter().zip(expected.as_slice::<f32>()?.iter()) {
+            assert!((f - e).abs() < 1e-5, "{} != {}", f, e);
+        }
+        Ok(())
+    }
+
+    // Reference values from the ONNX reference implementation, with the
+    // default axes [0, 2, 3].
+    #[test]
+    fn per_channel() -> TractResult<()> {
+        let output = Mvn::default().eval(tvec!(input().into()))?;
+        let expected = tensor4(&[
+            [[[0.34641f32, 1.270171]], [[-1.069045, 0.0]]],
+            [[[-0.11547, -1.501111]], [[1.603567, -0.534522]]],
+        ]);
+        assert_close(&output[0], &expected)
+    }
+
+    #[test]
+    fn mean_only() -> TractResult<()> {
+        let output = Mvn::new(vec![0, 2, 3], false, 1e-9).eval(tvec!(input().into()))?;
+        let expected =
+            tensor4(&[[[[0.375f32, 1.375]], [[-2.0, 0.0]]], [[[-0.125, -1.625]], [[3.0, -1.0]]]]);
+        assert_close(&output[0], &expected)
+    }
+
+    #[test]
+    fn global() -> TractResult<()> {
+        let output = Mvn::new(vec![0, 1, 2, -1], true, 1e-9).eval(tvec!(input().into()))?;
+        let expected = tensor4(&[
+            [[[-0.679211f32, -0.304474]], [[0.070263, 0.819737]]],
+            [[[-0.866579, -1.428685]], [[1.943949, 0.445]]],
+        ]);
+        assert_close(&output[0], &expected)
+    }
+
+    // Normalizing each row matches sklearn's preprocessing.scale applied to
+    // the transposed data.
+    #[test]
+    fn f64_input() -> TractResult<()> {
+        let input = tensor2(&[[1f64, 2.0, 3.0], [10.0, 10.0, 40.0]]);
+        let output = Mvn::new(vec![1], true, 0.0).eval(tvec!(input.into()))?;
+        let found = output[0].as_slice::<f64>()?;
+        let expected = [-1.224745, 0.0, 1.224745, -0.707107, -0.707107, 1.414214];
+        for (f, e) in found.iter().zip(expected.iter()) {
+            assert!((f - e).abs() < 1e-6, "{} != {}", f, e);
+        }
+        Ok(())
+    }
+}
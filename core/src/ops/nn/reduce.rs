@@ -1,4 +1,5 @@
 use crate::internal::*;
+use crate::ops::math::log_sum_exp_t;
 use ndarray::prelude::*;
 use num_traits::cast::AsPrimitive;
 
@@ -130,14 +131,6 @@ where
     v.scalar_sum().ln()
 }
 
-fn log_sum_exp_t<'a, T>(v: ArrayViewD<'a, T>) -> T
-where
-    T: Copy + Datum + num_traits::Zero + num_traits::Float,
-{
-    let max = v.fold(T::min_value(), |acc, &v| if acc > v { acc } else { v });
-    max + v.fold(T::zero(), |acc, &v| acc + (v - max).exp()).ln()
-}
-
 fn max_t<'a, T>(v: ArrayViewD<'a, T>) -> T
 where
     T: Copy + Datum + num_traits::Bounded + ::std::cmp::PartialOrd,
@@ -321,8 +314,8 @@ impl InferenceRulesOp for Reduce {
 
 #[derive(Clone, Debug, new)]
 pub struct TypedReduce {
-    axes: TVec<usize>,
-    reducer: Reducer,
+    pub axes: TVec<usize>,
+    pub reducer: Reducer,
 }
 
 impl Op for TypedReduce {
@@ -353,6 +346,28 @@ impl TypedOp for TypedReduce {
         Ok(tvec!(TypedFact::dt_shape(inputs[0].datum_type, &*shape)?))
     }
 
+    fn codegen(
+        &self,
+        model: &TypedModel,
+        node: &TypedNode,
+    ) -> TractResult<Option<TypedModelPatch>> {
+        // a float mean or max over H and W of a NCHW input is a global pool.
+        // done at codegen, so the normalization fusions still see the mean.
+        let input = model.outlet_fact(node.inputs[0])?;
+        if input.rank() != 4
+            || &*self.axes != &[2, 3]
+            || !(input.datum_type == f32::datum_type() || input.datum_type == f64::datum_type())
+        {
+            return Ok(None);
+        }
+        let op: Box<dyn TypedOp> = match self.reducer {
+            Reducer::Mean => Box::new(super::GlobalAvgPool::default()),
+            Reducer::Max => Box::new(super::GlobalMaxPool::default()),
+            _ => return Ok(None),
+        };
+        Ok(Some(TypedModelPatch::single_unary_op(model, node, op)?))
+    }
+
     #[allow(unused_variables)]
     fn invariants(&self, model: &TypedModel, node: &TypedNode) -> TractResult<Invariants> {
         let input = model.outlet_fact(node.inputs[0])?;
@@ -406,3 +421,42 @@ impl PulsedOp for TypedReduce {
     pulsed_op_as_op!();
     pulsed_op_to_typed_op!();
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ops::nn::{GlobalAvgPool, GlobalMaxPool};
+
+    fn optimized(reducer: Reducer, axes: TVec<usize>) -> TractResult<TypedModel> {
+        let mut model = TypedModel::default();
+        let fact = TypedFact::dt_shape(f32::datum_type(), [1, 3, 4, 4].as_ref())?;
+        let x = model.add_source("x", fact)?;
+        let y = model.wire_node("reduce", TypedReduce::new(axes, reducer), &[x])?;
+        model.set_output_outlets(&y)?;
+        model.into_optimized()
+    }
+
+    #[test]
+    fn spatial_mean_and_max_are_global_pools() -> TractResult<()> {
+        let model = optimized(Reducer::Mean, tvec!(2, 3))?;
+        assert!(model.node(model.output_outlets()?[0].node).op_is::<GlobalAvgPool>());
+        let model = optimized(Reducer::Max, tvec!(2, 3))?;
+        assert!(model.node(model.output_outlets()?[0].node).op_is::<GlobalMaxPool>());
+        let input = ndarray::Array4::from_shape_fn((1, 3, 4, 4), |(_, c, h, w)| {
+            (c * 16 + h * 4 + w) as f32
+        });
+        let output = SimplePlan::new(&model)?.run(tvec!(input.into_tensor()))?;
+        let expected = ndarray::arr1(&[15f32, 31.0, 47.0]).into_shape((1, 3, 1, 1))?;
+        assert_eq!(output[0], expected.into_arc_tensor());
+        Ok(())
+    }
+
+    #[test]
+    fn other_reductions_are_kept() -> TractResult<()> {
+        let model = optimized(Reducer::Mean, tvec!(1, 2, 3))?;
+        assert!(model.node(model.output_outlets()?[0].node).op_is::<TypedReduce>());
+        let model = optimized(Reducer::Sum, tvec!(2, 3))?;
+        assert!(model.node(model.output_outlets()?[0].node).op_is::<TypedReduce>());
+        Ok(())
+    }
+}
@@ -0,0 +1,318 @@
+use crate::internal::*;
+use crate::ops::binary::UnaryOp;
+use crate::ops::math::{Add, Div, Mul, Pow, Recip, Rsqrt, Sqrt};
+use crate::ops::nn::{Reducer, TypedReduce};
+use crate::optim::{is_binary, is_element_wise, single_use};
+use num_traits::Float;
+
+/// Root mean square normalization over the last axis, as in LLaMA:
+/// `x / sqrt(mean(x^2) + epsilon) * weight`.
+///
+/// The optional weight broadcasts to the last axis. Each row is scaled by
+/// its largest absolute value before squaring, so `x^2` can not overflow.
+#[derive(Debug, Clone, new)]
+pub struct RMSNorm {
+    pub epsilon: f32,
+}
+
+impl RMSNorm {
+    fn eval_t<T: Datum + Float>(&self, inputs: &[Arc<Tensor>]) -> TractResult<TVec<Arc<Tensor>>> {
+        let input = &inputs[0];
+        if input.rank() == 0 {
+            bail!("RMSNorm expects at least one axis")
+        }
+        let dim = input.shape()[input.rank() - 1];
+        let weight = if let Some(weight) = inputs.get(1) {
+            let view = weight.to_array_view::<T>()?;
+            let view = view.broadcast(dim).ok_or_else(|| {
+                format!("Can not broadcast RMSNorm weight {:?} to {}", weight.shape(), dim)
+            })?;
+            view.iter().cloned().collect()
+        } else {
+            vec![T::one(); dim]
+        };
+        let epsilon = T::from(self.epsilon).unwrap();
+        let n = T::from(dim).unwrap();
+        let mut output = input.as_ref().clone();
+        for row in output.as_slice_mut::<T>()?.chunks_mut(dim.max(1)) {
+            let max = row.iter().fold(T::zero(), |acc, x| acc.max(x.abs()));
+            if max == T::zero() {
+                continue;
+            }
+            let mean_sq =
+                row.iter().map(|&x| (x / max) * (x / max)).fold(T::zero(), |a, b| a + b) / n;
+            // x / sqrt(mean(x^2) + eps) == (x / max) / sqrt(mean((x / max)^2) + eps / max^2)
+            let inv_rms = (mean_sq + epsilon / max / max).sqrt().recip() / max;
+            row.iter_mut().zip(weight.iter()).for_each(|(x, &w)| *x = *x * inv_rms * w);
+        }
+        Ok(tvec!(output.into_arc_tensor()))
+    }
+}
+
+impl Op for RMSNorm {
+    fn name(&self) -> Cow<str> {
+        "RMSNorm".into()
+    }
+
+    fn info(&self) -> TractResult<Vec<String>> {
+        Ok(vec![format!("epsilon: {}", self.epsilon)])
+    }
+
+    fn validation(&self) -> Validation {
+        Validation::Rounding
+    }
+
+    op_as_typed_op!();
+    not_a_pulsed_op!();
+}
+
+impl StatelessOp for RMSNorm {
+    fn eval(&self, inputs: TVec<Arc<Tensor>>) -> TractResult<TVec<Arc<Tensor>>> {
+        if inputs.is_empty() || inputs.len() > 2 {
+            bail!("RMSNorm expects 1 or 2 inputs, got {}", inputs.len())
+        }
+        dispatch_floatlike!(Self::eval_t(inputs[0].datum_type())(self, &*inputs))
+    }
+}
+
+impl InferenceRulesOp for RMSNorm {
+    fn rules<'r, 'p: 'r, 's: 'r>(
+        &'s self,
+        s: &mut Solver<'r>,
+        inputs: &'p [TensorProxy],
+        outputs: &'p [TensorProxy],
+    ) -> InferenceResult {
+        if inputs.is_empty() || inputs.len() > 2 {
+            bail!("RMSNorm expects 1 or 2 inputs, got {}", inputs.len())
+        }
+        check_output_arity(&outputs, 1)?;
+        for input in &inputs[1..] {
+            s.equals(&input.datum_type, &inputs[0].datum_type)?;
+        }
+        s.equals(&outputs[0].datum_type, &inputs[0].datum_type)?;
+        s.equals(&outputs[0].shape, &inputs[0].shape)
+    }
+
+    inference_op_as_op!();
+    to_typed!();
+}
+
+impl TypedOp for RMSNorm {
+    fn output_facts(&self, inputs: &[&TypedFact]) -> TractResult<TVec<TypedFact>> {
+        Ok(tvec!(TypedFact::dt_shape(inputs[0].datum_type, inputs[0].shape.clone())?))
+    }
+
+    fn cost(&self, inputs: &[&TypedFact]) -> TractResult<TVec<(Cost, TDim)>> {
+        let len = inputs[0].shape.iter().product::<TDim>();
+        Ok(tvec!((Cost::FMA(inputs[0].datum_type), len * 3)))
+    }
+
+    typed_op_as_op!();
+}
+
+/// Scalar f32 constant.
+fn scalar(t: &Tensor) -> Option<f32> {
+    if t.len() == 1 {
+        t.cast_to::<f32>().ok()?.as_slice::<f32>().ok().map(|s| s[0])
+    } else {
+        None
+    }
+}
+
+/// Match `x^2`, as a power or a product.
+fn is_square_of(model: &TypedModel, node: &TypedNode, x: OutletId) -> bool {
+    if is_binary::<Mul>(node) {
+        node.inputs[0] == x && node.inputs[1] == x
+    } else if is_binary::<Pow>(node) {
+        node.inputs[0] == x
+            && model
+                .outlet_fact(node.inputs[1])
+                .ok()
+                .and_then(|f| f.konst.as_ref())
+                .and_then(|k| scalar(k))
+                == Some(2.0)
+    } else {
+        false
+    }
+}
+
+/// Match `1 / sqrt(mean(x^2, last axis) + eps)`, returning eps.
+fn inv_rms(model: &TypedModel, outlet: OutletId, x: OutletId) -> Option<f32> {
+    let mut node = single_use(model, outlet)?;
+    let recip = node.op_as::<UnaryOp>().filter(|op| op.mini_op.is::<Div>());
+    if is_element_wise::<Rsqrt>(node) {
+        node = single_use(model, node.inputs[0])?;
+    } else if is_element_wise::<Recip>(node) || recip.and_then(|op| scalar(&op.a)) == Some(1.0) {
+        let sqrt = single_use(model, node.inputs[0])?;
+        if !is_element_wise::<Sqrt>(sqrt) {
+            return None;
+        }
+        node = single_use(model, sqrt.inputs[0])?;
+    } else {
+        return None;
+    }
+    let eps =
+        node.op_as::<UnaryOp>().filter(|op| op.mini_op.is::<Add>()).and_then(|op| scalar(&op.a))?;
+    let mean = single_use(model, node.inputs[0])?;
+    let rank = model.outlet_fact(x).ok()?.rank();
+    match mean.op_as::<TypedReduce>() {
+        Some(TypedReduce { axes, reducer: Reducer::Mean }) if **axes == [rank - 1] => (),
+        _ => return None,
+    }
+    let square = single_use(model, mean.inputs[0])?;
+    if is_square_of(model, square, x) {
+        Some(eps)
+    } else {
+        None
+    }
+}
+
+/// Replace `x * (1 / sqrt(mean(x^2) + eps))` by `RMSNorm(x)`.
+pub(crate) fn fuse_rms_norm(
+    model: &TypedModel,
+    node: &TypedNode,
+) -> TractResult<Option<TypedModelPatch>> {
+    if !is_binary::<Mul>(node) {
+        return Ok(None);
+    }
+    for &(x, inv) in &[(0, 1), (1, 0)] {
+        if let Some(eps) = inv_rms(model, node.inputs[inv], node.inputs[x]) {
+            let mut patch = TypedModelPatch::default();
+            let x = patch.tap_model(model, node.inputs[x])?;
+            let fused = patch.wire_node(&*node.name, RMSNorm::new(eps), &[x])?[0];
+            patch.shunt_outside(OutletId::new(node.id, 0), fused)?;
+            return Ok(Some(patch));
+        }
+    }
+    Ok(None)
+}
+
+/// Fold a multiplication by a weight over the last axis in a preceding
+/// unweighted `RMSNorm`.
+pub(crate) fn fuse_rms_norm_weight(
+    model: &TypedModel,
+    node: &TypedNode,
+) -> TractResult<Option<TypedModelPatch>> {
+    let (norm, weight) = if let Some(op) = node.op_as::<UnaryOp>() {
+        if !op.mini_op.is::<Mul>() {
+            return Ok(None);
+        }
+        (node.inputs[0], None)
+    } else if is_binary::<Mul>(node) {
+        if model.node(node.inputs[0].node).op_is::<RMSNorm>() {
+            (node.inputs[0], Some(node.inputs[1]))
+        } else {
+            (node.inputs[1], Some(node.inputs[0]))
+        }
+    } else {
+        return Ok(None);
+    };
+    let norm = match single_use(model, norm) {
+        Some(norm) if norm.inputs.len() == 1 => norm,
+        _ => return Ok(None),
+    };
+    let op = if let Some(op) = norm.op_as::<RMSNorm>() { op } else { return Ok(None) };
+    let x = model.outlet_fact(norm.inputs[0])?;
+    let (dt, shape): (_, TVec<TDim>) = match weight {
+        Some(w) => {
+            let fact = model.outlet_fact(w)?;
+            (fact.datum_type, fact.shape.iter().collect())
+        }
+        None => {
+            let a = &node.op_as::<UnaryOp>().unwrap().a;
+            (a.datum_type(), a.shape().iter().map(|d| d.to_dim()).collect())
+        }
+    };
+    let dim = x.shape.dim(x.rank() - 1);
+    let over_last_axis = shape.len() <= x.rank()
+        && shape.last() == Some(&dim)
+        && shape.iter().rev().skip(1).all(|d| *d == 1.to_dim());
+    if dt != x.datum_type || !over_last_axis {
+        return Ok(None);
+    }
+    let mut patch = TypedModelPatch::default();
+    let input = patch.tap_model(model, norm.inputs[0])?;
+    let weight = match weight {
+        Some(w) => patch.tap_model(model, w)?,
+        None => {
+            let a = node.op_as::<UnaryOp>().unwrap().a.clone();
+            patch.add_const(format!("{}.weight", node.name), a)?
+        }
+    };
+    let fused = patch.wire_node(&*norm.name, op.clone(), &[input, weight])?[0];
+    patch.shunt_outside(OutletId::new(node.id, 0), fused)?;
+    Ok(Some(patch))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ops::math;
+    use crate::ops::nn::Reduce;
+
+    #[test]
+    fn matches_pytorch() -> TractResult<()> {
+        // torch.nn.RMSNorm(4, eps=1e-6) with weight [1, 0.5, 2, -1]
+        let x = rctensor2(&[[1f32, 2., 3., 4.], [-1., 0., 1., 0.5]]);
+        let weight = rctensor1(&[1f32, 0.5, 2., -1.]);
+        let found = RMSNorm::new(1e-6).eval(tvec!(x, weight))?.remove(0);
+        let expected = tensor2(&[
+            [0.36514835f32, 0.36514835, 2.19089008, -1.46059339],
+            [-1.33333215, 0., 2.6666643, -0.66666607],
+        ]);
+        found.close_enough(&expected, true)
+    }
+
+    #[test]
+    fn large_values() -> TractResult<()> {
+        // x^2 overflows f32, the normalized values do not
+        let x = rctensor1(&[3e20f32, -4e20]);
+        let found = RMSNorm::new(1e-6).eval(tvec!(x))?.remove(0);
+        let rms = (12.5f32).sqrt();
+        found.close_enough(&tensor1(&[3. / rms, -4. / rms]), true)
+    }
+
+    #[test]
+    fn output_facts() -> TractResult<()> {
+        let fact = TypedFact::dt_shape(f32::datum_type(), [2, 5, 8].as_ref())?;
+        let weight = TypedFact::dt_shape(f32::datum_type(), [8].as_ref())?;
+        let facts = RMSNorm::new(1e-5).output_facts(&[&fact, &weight])?;
+        assert_eq!(facts[0], fact);
+        Ok(())
+    }
+
+    // x * weight / sqrt(mean(x^2) + eps), as exported from PyTorch
+    fn decomposed(shape: &[usize], weight: Tensor, eps: f32) -> TractResult<TypedModel> {
+        let mut model = InferenceModel::default();
+        let x = model.add_source("x", InferenceFact::dt_shape(f32::datum_type(), shape))?;
+        let two = model.add_const("two", tensor0(2f32))?;
+        let eps = model.add_const("eps", tensor0(eps))?;
+        let weight = model.add_const("weight", weight)?;
+        let sq = model.wire_node("sq", math::pow::bin(), &[x, two])?;
+        let mean = Reduce::new(Some(vec![-1]), true, Reducer::Mean);
+        let mean = model.wire_node("mean", mean, &sq)?[0];
+        let mean_eps = model.wire_node("mean_eps", math::add::bin(), &[mean, eps])?;
+        let rms = model.wire_node("rms", math::sqrt(), &mean_eps)?;
+        let inv = model.wire_node("inv", math::recip(), &rms)?[0];
+        let norm = model.wire_node("norm", math::mul::bin(), &[x, inv])?[0];
+        let o = model.wire_node("scaled", math::mul::bin(), &[norm, weight])?;
+        model.set_output_outlets(&o)?;
+        model.into_typed()
+    }
+
+    #[test]
+    fn fused_matches_decomposed() -> TractResult<()> {
+        let weight = tensor1(&[0.5f32, 1.0, -2.0, 1.5, 0.25, 3.0]);
+        let model = decomposed(&[2, 3, 6], weight, 1e-5)?;
+        let x = tensor3(&[
+            [[1f32, -2., 3., 0.5, 0., 4.], [0.1, 0.2, 0.3, 0.4, 0.5, 0.6], [0.; 6]],
+            [[-5f32, 5., -5., 5., -5., 5.], [1e-3, 0., 0., 0., 0., 0.], [9., 8., 7., 6., 5., 4.]],
+        ]);
+        let reference = SimplePlan::new(model.clone())?.run(tvec!(x.clone()))?.remove(0);
+        let model = model.declutter()?;
+        assert_eq!(model.nodes().len(), 3);
+        assert!(model.nodes().iter().any(|n| n.op_is::<RMSNorm>()));
+        let found = SimplePlan::new(model)?.run(tvec!(x))?.remove(0);
+        found.close_enough(&reference, true)
+    }
+}
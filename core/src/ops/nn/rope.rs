@@ -0,0 +1,356 @@
+use crate::internal::*;
+use crate::ops::array::{NormConcat, NormConcatSlice, Slice};
+use crate::ops::binary::UnaryOp;
+use crate::ops::element_wise::ElementWiseOp;
+use crate::ops::math::{Add, Mul, Neg};
+use crate::optim::is_binary;
+use ndarray::*;
+
+/// Rotary positional embedding, `x * cos + rotate_half(x) * sin`.
+///
+/// `rotate_half` splits the last axis of `x` in two halves `x1` and `x2` and
+/// concatenates `-x2` and `x1`. `cos` and `sin` must broadcast to the shape
+/// of `x`, whose last axis must have an even length.
+#[derive(Debug, Clone, new, Default)]
+pub struct RotaryEmbedding;
+
+impl RotaryEmbedding {
+    fn eval_f32(
+        &self,
+        x: ArrayViewD<f32>,
+        cos: ArrayViewD<f32>,
+        sin: ArrayViewD<f32>,
+    ) -> TractResult<ArrayD<f32>> {
+        let axis = Axis(x.ndim().saturating_sub(1));
+        if x.ndim() == 0 || x.shape()[axis.0] % 2 != 0 {
+            bail!("RotaryEmbedding expects an even last axis, got {:?}", x.shape())
+        }
+        let (cos, sin) = (broadcast(&cos, x.shape())?, broadcast(&sin, x.shape())?);
+        let half = x.shape()[axis.0] / 2;
+        let mut output = ArrayD::<f32>::zeros(x.shape());
+        Zip::from(output.lanes_mut(axis))
+            .and(x.lanes(axis))
+            .and(cos.lanes(axis))
+            .and(sin.lanes(axis))
+            .apply(|mut o, x, c, s| {
+                for i in 0..half {
+                    o[i] = x[i] * c[i] - x[i + half] * s[i];
+                    o[i + half] = x[i + half] * c[i + half] + x[i] * s[i + half];
+                }
+            });
+        Ok(output)
+    }
+}
+
+fn broadcast<'a>(t: &'a ArrayViewD<f32>, shape: &[usize]) -> TractResult<ArrayViewD<'a, f32>> {
+    Ok(t.broadcast(shape).ok_or_else(|| {
+        format!("RotaryEmbedding can not broadcast {:?} to {:?}", t.shape(), shape)
+    })?)
+}
+
+impl Op for RotaryEmbedding {
+    fn name(&self) -> Cow<str> {
+        "RotaryEmbedding".into()
+    }
+
+    op_as_typed_op!();
+    not_a_pulsed_op!();
+}
+
+impl StatelessOp for RotaryEmbedding {
+    fn eval(&self, inputs: TVec<Arc<Tensor>>) -> TractResult<TVec<Arc<Tensor>>> {
+        let output = self.eval_f32(
+            inputs[0].to_array_view()?,
+            inputs[1].to_array_view()?,
+            inputs[2].to_array_view()?,
+        )?;
+        Ok(tvec!(output.into_arc_tensor()))
+    }
+}
+
+impl InferenceRulesOp for RotaryEmbedding {
+    fn rules<'r, 'p: 'r, 's: 'r>(
+        &'s self,
+        s: &mut Solver<'r>,
+        inputs: &'p [TensorProxy],
+        outputs: &'p [TensorProxy],
+    ) -> InferenceResult {
+        check_input_arity(&inputs, 3)?;
+        check_output_arity(&outputs, 1)?;
+        for input in inputs {
+            s.equals(&input.datum_type, f32::datum_type())?;
+        }
+        s.equals(&outputs[0].datum_type, f32::datum_type())?;
+        s.equals(&outputs[0].shape, &inputs[0].shape)
+    }
+
+    inference_op_as_op!();
+    to_typed!();
+}
+
+impl TypedOp for RotaryEmbedding {
+    fn output_facts(&self, inputs: &[&TypedFact]) -> TractResult<TVec<TypedFact>> {
+        if inputs.iter().any(|f| f.datum_type != f32::datum_type()) {
+            bail!("RotaryEmbedding only supports f32")
+        }
+        if inputs[1].rank() > inputs[0].rank() || inputs[2].rank() > inputs[0].rank() {
+            bail!("RotaryEmbedding cos and sin must broadcast to x, got {:?}", inputs)
+        }
+        Ok(tvec!(TypedFact::dt_shape(f32::datum_type(), inputs[0].shape.clone())?))
+    }
+
+    fn cost(&self, inputs: &[&TypedFact]) -> TractResult<TVec<(Cost, TDim)>> {
+        Ok(tvec!((Cost::FMA(f32::datum_type()), inputs[0].shape.iter().product::<TDim>() * 2)))
+    }
+
+    typed_op_as_op!();
+}
+
+/// An operand of a multiplication: another wire, or the constant of a
+/// decluttered unary op.
+enum Operand {
+    Wire(OutletId),
+    Konst(Arc<Tensor>),
+}
+
+/// Operands of a multiplication node.
+fn mul_operands(node: &TypedNode) -> Option<(Operand, Operand)> {
+    if is_binary::<Mul>(node) {
+        Some((Operand::Wire(node.inputs[0]), Operand::Wire(node.inputs[1])))
+    } else if let Some(op) = node.op_as::<UnaryOp>().filter(|op| op.mini_op.is::<Mul>()) {
+        Some((Operand::Konst(op.a.clone()), Operand::Wire(node.inputs[0])))
+    } else {
+        None
+    }
+}
+
+/// Axis and bounds of a slice node.
+fn slice_bounds(node: &TypedNode) -> Option<(usize, usize, usize)> {
+    if let Some(op) = node.op_as::<Slice<usize>>() {
+        Some((op.axis, op.start, op.end))
+    } else if let Some(op) = node.op_as::<Slice<TDim>>() {
+        Some((op.axis, op.start.to_integer().ok()? as usize, op.end.to_integer().ok()? as usize))
+    } else {
+        None
+    }
+}
+
+/// Match `rotate_half(x)`, returning x.
+fn rotate_half(model: &TypedModel, outlet: OutletId) -> Option<OutletId> {
+    let concat = model.node(outlet.node);
+    let op = concat.op_as::<NormConcat>()?;
+    if concat.inputs.len() != 2 || !op.slices.iter().all(NormConcatSlice::is_var) {
+        return None;
+    }
+    let neg = model.node(concat.inputs[0].node);
+    let negated = neg.op_as::<ElementWiseOp>().map(|op| op.0.is::<Neg>()).unwrap_or(false)
+        || neg
+            .op_as::<UnaryOp>()
+            .filter(|op| op.mini_op.is::<Mul>() && op.a.len() == 1)
+            .and_then(|op| {
+                op.a.cast_to::<f32>()
+                    .ok()
+                    .map(|a| a.as_slice::<f32>().map(|a| a == [-1.0]).unwrap_or(false))
+            })
+            .unwrap_or(false);
+    if !negated {
+        return None;
+    }
+    let (x2, x1) = (model.node(neg.inputs[0].node), model.node(concat.inputs[1].node));
+    let x = x1.inputs.get(0)?;
+    if x2.inputs.get(0) != Some(x) {
+        return None;
+    }
+    let fact = model.outlet_fact(*x).ok()?;
+    let rank = fact.rank();
+    let dim = fact.shape.dim(rank.checked_sub(1)?).to_integer().ok()? as usize;
+    if op.axis + 1 != rank || dim % 2 != 0 {
+        return None;
+    }
+    if slice_bounds(x1)? == (rank - 1, 0, dim / 2) && slice_bounds(x2)? == (rank - 1, dim / 2, dim)
+    {
+        Some(*x)
+    } else {
+        None
+    }
+}
+
+/// Among the operands of a multiplication, find the one matching `pred` and
+/// return its result along with the other operand.
+fn split_operands<T>(
+    node: &TypedNode,
+    pred: impl Fn(OutletId) -> Option<T>,
+) -> Option<(T, Operand)> {
+    let (a, b) = mul_operands(node)?;
+    if let Operand::Wire(b_wire) = b {
+        if let Some(found) = pred(b_wire) {
+            return Some((found, a));
+        }
+    }
+    if let (Operand::Wire(a_wire), Operand::Wire(b_wire)) = (&a, &b) {
+        if let Some(found) = pred(*a_wire) {
+            return Some((found, Operand::Wire(*b_wire)));
+        }
+    }
+    None
+}
+
+/// Replace `x * cos + rotate_half(x) * sin`, as found in ONNX exports, by
+/// a `RotaryEmbedding`.
+pub(crate) fn fuse_rotary_embedding(
+    model: &TypedModel,
+    node: &TypedNode,
+) -> TractResult<Option<TypedModelPatch>> {
+    if !is_binary::<Add>(node) {
+        return Ok(None);
+    }
+    let mut matched = None;
+    for &(c, s) in &[(0, 1), (1, 0)] {
+        let sin_mul = model.node(node.inputs[s].node);
+        let cos_mul = model.node(node.inputs[c].node);
+        if let Some((x, sin)) = split_operands(sin_mul, |o| rotate_half(model, o)) {
+            if let Some(((), cos)) =
+                split_operands(cos_mul, |o| if o == x { Some(()) } else { None })
+            {
+                matched = Some((x, cos, sin));
+                break;
+            }
+        }
+    }
+    let (x, cos, sin) = if let Some(matched) = matched {
+        matched
+    } else {
+        return Ok(None);
+    };
+    let x_fact = model.outlet_fact(x)?;
+    let mut patch = TypedModelPatch::default();
+    let mut inputs = tvec!(patch.tap_model(model, x)?);
+    for (name, operand) in &[("cos", cos), ("sin", sin)] {
+        let (datum_type, rank) = match operand {
+            Operand::Wire(o) => {
+                let fact = model.outlet_fact(*o)?;
+                (fact.datum_type, fact.rank())
+            }
+            Operand::Konst(t) => (t.datum_type(), t.rank()),
+        };
+        if datum_type != f32::datum_type() || rank > x_fact.rank() {
+            return Ok(None);
+        }
+        inputs.push(match operand {
+            Operand::Wire(o) => patch.tap_model(model, *o)?,
+            Operand::Konst(t) => patch.add_const(format!("{}.{}", node.name, name), t.clone())?,
+        });
+    }
+    if x_fact.datum_type != f32::datum_type() {
+        return Ok(None);
+    }
+    let fused = patch.wire_node(&*node.name, RotaryEmbedding, &*inputs)?[0];
+    patch.shunt_outside(OutletId::new(node.id, 0), fused)?;
+    Ok(Some(patch))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ops::binary::TypedBinOp;
+    use crate::ops::{array, math};
+
+    /// cos and sin tables for positions 0..seq, as in the Hugging Face
+    /// PyTorch `LlamaRotaryEmbedding`.
+    fn tables(seq: usize, dim: usize) -> (Array2<f32>, Array2<f32>) {
+        let inv_freq: Vec<f32> =
+            (0..dim / 2).map(|i| 10000f32.powf(-((2 * i) as f32) / dim as f32)).collect();
+        let emb = Array2::from_shape_fn((seq, dim), |(p, i)| p as f32 * inv_freq[i % (dim / 2)]);
+        (emb.mapv(f32::cos), emb.mapv(f32::sin))
+    }
+
+    /// PyTorch `apply_rotary_pos_emb`, transcribed with ndarray.
+    fn reference(x: &ArrayD<f32>, cos: &Array2<f32>, sin: &Array2<f32>) -> ArrayD<f32> {
+        let last = Axis(x.ndim() - 1);
+        let half = x.shape()[last.0] / 2;
+        let x1 = x.slice_axis(last, (0..half).into());
+        let x2 = x.slice_axis(last, (half..).into());
+        let neg_x2 = x2.mapv(|v| -v);
+        let rotated = ndarray::stack(last, &[neg_x2.view(), x1]).unwrap();
+        x * cos + &(rotated * sin)
+    }
+
+    fn x(shape: &[usize]) -> ArrayD<f32> {
+        let len = shape.iter().product::<usize>();
+        let data = (0..len).map(|i| ((i * 7 % 13) as f32 - 6.0) * 0.25).collect();
+        ArrayD::from_shape_vec(shape, data).unwrap()
+    }
+
+    #[test]
+    fn quarter_turn() -> TractResult<()> {
+        let x = arr1(&[1f32, 2., 3., 4.]).into_dyn();
+        let cos = arr1(&[0f32; 4]).into_dyn();
+        let sin = arr1(&[1f32; 4]).into_dyn();
+        let found = RotaryEmbedding.eval_f32(x.view(), cos.view(), sin.view())?;
+        assert_eq!(found, arr1(&[-3f32, -4., 1., 2.]).into_dyn());
+        Ok(())
+    }
+
+    #[test]
+    fn matches_reference() -> TractResult<()> {
+        let x = x(&[2, 3, 5, 8]);
+        let (cos, sin) = tables(5, 8);
+        let found =
+            RotaryEmbedding.eval_f32(x.view(), cos.view().into_dyn(), sin.view().into_dyn())?;
+        found.into_tensor().close_enough(&reference(&x, &cos, &sin).into_tensor(), true)
+    }
+
+    // x * cos + concat(-x[.., h..], x[.., ..h]) * sin, as separate nodes
+    fn decomposed(shape: &[usize], cos: Tensor, sin: Tensor, konst: bool) -> TypedModel {
+        let mut model = TypedModel::default();
+        let fact = TypedFact::dt_shape(f32::datum_type(), shape).unwrap();
+        let x = model.add_source("x", fact).unwrap();
+        let mut table = |name: &str, t: Tensor| {
+            if konst {
+                model.add_const(name, t).unwrap()
+            } else {
+                let fact = TypedFact::dt_shape(f32::datum_type(), t.shape()).unwrap();
+                model.add_source(name, fact).unwrap()
+            }
+        };
+        let (cos, sin) = (table("cos", cos), table("sin", sin));
+        let (axis, dim) = (shape.len() - 1, shape[shape.len() - 1]);
+        let x1 = model.wire_node("x1", Slice::new(axis, 0, dim / 2), &[x]).unwrap();
+        let x2 = model.wire_node("x2", Slice::new(axis, dim / 2, dim), &[x]).unwrap();
+        let neg = model.wire_node("neg", math::neg(), &x2).unwrap();
+        let rotated = model.wire_node("rotated", array::Concat::new(-1), &[neg[0], x1[0]]).unwrap();
+        let mul = || TypedBinOp(Box::new(Mul));
+        let a = model.wire_node("x_cos", mul(), &[x, cos]).unwrap();
+        let b = model.wire_node("rot_sin", mul(), &[rotated[0], sin]).unwrap();
+        let o = model.wire_node("rope", TypedBinOp(Box::new(Add)), &[a[0], b[0]]).unwrap();
+        model.set_output_outlets(&o).unwrap();
+        model
+    }
+
+    fn check(konst: bool) -> TractResult<()> {
+        let shape = [1, 2, 6, 8];
+        let x = x(&shape);
+        let (cos, sin) = tables(6, 8);
+        let model = decomposed(&shape, cos.clone().into_tensor(), sin.clone().into_tensor(), konst);
+        let mut inputs = tvec!(x.clone().into_tensor());
+        if !konst {
+            inputs.push(cos.clone().into_tensor());
+            inputs.push(sin.clone().into_tensor());
+        }
+        let model = model.declutter()?;
+        assert!(model.nodes().iter().any(|n| n.op_is::<RotaryEmbedding>()));
+        assert!(model.nodes().iter().all(|n| !n.op_is::<NormConcat>()));
+        let found = SimplePlan::new(model)?.run(inputs)?.remove(0);
+        found.close_enough(&reference(&x, &cos, &sin).into_tensor(), true)
+    }
+
+    #[test]
+    fn fused_matches_reference() -> TractResult<()> {
+        check(false)
+    }
+
+    #[test]
+    fn fused_matches_reference_const_tables() -> TractResult<()> {
+        check(true)
+    }
+}
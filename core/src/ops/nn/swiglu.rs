@@ -0,0 +1,173 @@
+use crate::internal::*;
+use crate::ops::binary::TypedBinOp;
+use crate::ops::math::Mul;
+use crate::ops::nn::Sigmoid;
+use crate::optim::{is_binary, is_element_wise, single_use};
+
+// Silu, aka Swish with beta = 1: `x * sigmoid(x)`.
+element_wise!(silu, Silu, [f32] => |_, xs| {
+    xs.iter_mut().for_each(|x| *x = silu_f32(*x));
+    Ok(())
+};
+    cost: |dt| {tvec!((Cost::FMA(dt), 12), (Cost::Div(dt), 1))}
+);
+
+// Gated linear units, `act(gate) * value`, with Silu (LLaMA FFN) or Gelu.
+bin_to_super_type!(swi_glu, SwiGLU,
+     cost: |dt| tvec!((Cost::FMA(dt), 13), (Cost::Div(dt), 1)),
+     [f32] => |c, gate, value| *c = silu_f32(*gate) * value);
+bin_to_super_type!(ge_glu, GeGLU,
+     cost: |dt| tvec!((Cost::FMA(dt), 16), (Cost::Div(dt), 1)),
+     [f32] => |c, gate, value| *c = gelu_f32(*gate) * value);
+
+fn silu_f32(x: f32) -> f32 {
+    x / (1.0 + (-x).exp())
+}
+
+/// Exact (erf based) Gelu, as PyTorch's default `F.gelu`.
+fn gelu_f32(x: f32) -> f32 {
+    0.5 * x * (1.0 + erf_f32(x * std::f32::consts::FRAC_1_SQRT_2))
+}
+
+/// Abramowitz and Stegun 7.1.26, absolute error below 1.5e-7.
+fn erf_f32(x: f32) -> f32 {
+    let t = 1.0 / (1.0 + 0.327_591_1 * x.abs());
+    let poly = t
+        * (0.254_829_6
+            + t * (-0.284_496_74 + t * (1.421_413_8 + t * (-1.453_152 + t * 1.061_405_4))));
+    (1.0 - poly * (-x * x).exp()).copysign(x)
+}
+
+fn f32_inputs(model: &TypedModel, node: &TypedNode) -> TractResult<bool> {
+    Ok(model.node_input_facts(node.id)?.iter().all(|f| f.datum_type == f32::datum_type()))
+}
+
+/// Replace `sigmoid(x) * x` by `Silu(x)`.
+pub(crate) fn fuse_silu(
+    model: &TypedModel,
+    node: &TypedNode,
+) -> TractResult<Option<TypedModelPatch>> {
+    if !is_binary::<Mul>(node) || !f32_inputs(model, node)? {
+        return Ok(None);
+    }
+    for &(s, x) in &[(0, 1), (1, 0)] {
+        if let Some(sigmoid) = single_use(model, node.inputs[s]) {
+            if is_element_wise::<Sigmoid>(sigmoid) && sigmoid.inputs[0] == node.inputs[x] {
+                let mut patch = TypedModelPatch::default();
+                let x = patch.tap_model(model, node.inputs[x])?;
+                let fused = patch.wire_node(&*node.name, silu(), &[x])?[0];
+                patch.shunt_outside(OutletId::new(node.id, 0), fused)?;
+                return Ok(Some(patch));
+            }
+        }
+    }
+    Ok(None)
+}
+
+/// Replace `Silu(gate) * value` by `SwiGLU(gate, value)`. Runs after
+/// `fuse_silu` has matched `sigmoid(gate) * gate`.
+pub(crate) fn fuse_swiglu(
+    model: &TypedModel,
+    node: &TypedNode,
+) -> TractResult<Option<TypedModelPatch>> {
+    if !is_binary::<Mul>(node) || !f32_inputs(model, node)? {
+        return Ok(None);
+    }
+    for &(g, v) in &[(0, 1), (1, 0)] {
+        if let Some(silu) = single_use(model, node.inputs[g]) {
+            if is_element_wise::<Silu>(silu) {
+                let mut patch = TypedModelPatch::default();
+                let gate = patch.tap_model(model, silu.inputs[0])?;
+                let value = patch.tap_model(model, node.inputs[v])?;
+                let op = TypedBinOp(Box::new(SwiGLU));
+                let fused = patch.wire_node(&*node.name, op, &[gate, value])?[0];
+                patch.shunt_outside(OutletId::new(node.id, 0), fused)?;
+                return Ok(Some(patch));
+            }
+        }
+    }
+    Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ops::binary::BinMiniOp;
+    use crate::ops::nn::sigmoid;
+    use ndarray::*;
+
+    fn eval(op: Box<dyn BinMiniOp>, gate: &[f32], value: &[f32]) -> TractResult<Arc<Tensor>> {
+        let inputs = tvec!(rctensor1(gate), rctensor1(value));
+        Ok(TypedBinOp(op).eval(inputs)?.remove(0))
+    }
+
+    #[test]
+    fn silu_matches_pytorch() -> TractResult<()> {
+        // torch.nn.functional.silu(torch.tensor([-4., -1., 0., 0.5, 3.]))
+        let found = silu().eval(tvec!(rctensor1(&[-4f32, -1., 0., 0.5, 3.])))?.remove(0);
+        let expected = tensor1(&[-0.07194484f32, -0.26894142, 0., 0.31122967, 2.85772238]);
+        found.close_enough(&expected, true)
+    }
+
+    #[test]
+    fn geglu_matches_pytorch() -> TractResult<()> {
+        // torch.nn.functional.gelu(gate) * value
+        let found = eval(Box::new(GeGLU), &[-2., -0.5, 0., 1., 2.5], &[1., 2., 3., -1., 0.5])?;
+        let expected = tensor1(&[-0.04550026f32, -0.30853754, 0., -0.84134475, 1.24223792]);
+        found.close_enough(&expected, true)
+    }
+
+    #[test]
+    fn swiglu_matches_manual() -> TractResult<()> {
+        let gate = [-3f32, -0.2, 0.0, 0.7, 4.0];
+        let value = [0.5f32, -1.5, 2.0, 3.0, -0.25];
+        let found = eval(Box::new(SwiGLU), &gate, &value)?;
+        let manual: Vec<f32> = gate
+            .iter()
+            .zip(value.iter())
+            .map(|(g, v)| g * (1.0 / (1.0 + (-g).exp())) * v)
+            .collect();
+        found.close_enough(&tensor1(&manual), true)
+    }
+
+    #[test]
+    fn shape_inference() -> TractResult<()> {
+        let mut model = InferenceModel::default();
+        let gate =
+            model.add_source("gate", InferenceFact::dt_shape(f32::datum_type(), tvec!(2, 3, 8)))?;
+        let value = model.add_source("value", InferenceFact::default())?;
+        let o = model.wire_node("swiglu", swi_glu::bin(), &[gate, value])?;
+        model.set_output_outlets(&o)?;
+        model.set_input_fact(1, InferenceFact::dt_shape(f32::datum_type(), tvec!(3, 1)))?;
+        let model = model.into_typed()?;
+        assert_eq!(
+            model.outlet_fact(model.output_outlets()?[0])?,
+            &TypedFact::dt_shape(f32::datum_type(), [2, 3, 8].as_ref())?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn fused_matches_decomposed() -> TractResult<()> {
+        let mut model = TypedModel::default();
+        let fact = TypedFact::dt_shape(f32::datum_type(), [4, 6].as_ref())?;
+        let gate = model.add_source("gate", fact.clone())?;
+        let value = model.add_source("value", fact)?;
+        let s = model.wire_node("sigmoid", sigmoid(), &[gate])?;
+        let mul = || TypedBinOp(Box::new(Mul));
+        let activated = model.wire_node("silu", mul(), &[gate, s[0]])?;
+        let o = model.wire_node("ffn", mul(), &[activated[0], value])?;
+        model.set_output_outlets(&o)?;
+
+        let gate = Array2::from_shape_fn((4, 6), |(i, j)| (i * 6 + j) as f32 * 0.3 - 3.0);
+        let value = Array2::from_shape_fn((4, 6), |(i, j)| ((i + 2 * j) % 5) as f32 - 2.0);
+        let inputs = tvec!(gate.into_tensor(), value.into_tensor());
+        let reference = SimplePlan::new(model.clone())?.run(inputs.clone())?.remove(0);
+        let model = model.declutter()?;
+        assert_eq!(model.nodes().len(), 3);
+        assert!(model.nodes().iter().all(|n| !is_element_wise::<Sigmoid>(n)));
+        assert!(model.nodes().iter().any(|n| is_binary::<SwiGLU>(n)));
+        let found = SimplePlan::new(model)?.run(inputs)?.remove(0);
+        found.close_enough(&reference, true)
+    }
+}
@@ -0,0 +1,18 @@
+use crate::internal::*;
+use crate::ops::element_wise::ElementWiseOp;
+
+/// Evaluate `op` on `input` as f32 then as f64, and compare each output to
+/// `reference`, computed in f64.
+pub fn check_against_reference(
+    op: &ElementWiseOp,
+    input: &[f32],
+    reference: impl Fn(f64) -> f64,
+) -> TractResult<()> {
+    let expected: Vec<f32> = input.iter().map(|&x| reference(x as f64) as f32).collect();
+    let found = op.eval(tvec!(rctensor1(input)))?.remove(0);
+    found.close_enough(&tensor1(&expected), true)?;
+    let input: Vec<f64> = input.iter().map(|&x| x as f64).collect();
+    let expected: Vec<f64> = input.iter().map(|&x| reference(x)).collect();
+    let found = op.eval(tvec!(rctensor1(&input)))?.remove(0);
+    found.close_enough(&tensor1(&expected), true)
+}
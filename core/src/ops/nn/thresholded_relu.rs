@@ -0,0 +1,145 @@
+use crate::internal::*;
+use crate::ops::binary::UnaryOp;
+use crate::ops::logic::{Greatser, Iff, Lesser};
+use crate::optim::{is_binary, single_use};
+
+// ThresholdedRelu, as ONNX: `x` above `alpha`, zero elsewhere.
+//
+// tract-linalg has no SIMD kernel for it yet: both types run a scalar loop.
+element_wise!(threshold_relu, ThresholdRelu { alpha: f32 },
+    [f32] => |e, xs| {
+        xs.iter_mut().for_each(|x| *x = if *x > e.alpha { *x } else { 0.0 });
+        Ok(())
+    },
+    [f64] => |e, xs| {
+        let alpha = e.alpha as f64;
+        xs.iter_mut().for_each(|x| *x = if *x > alpha { *x } else { 0.0 });
+        Ok(())
+});
+
+fn scalar_konst(model: &TypedModel, outlet: OutletId) -> TractResult<Option<f32>> {
+    match &model.outlet_fact(outlet)?.konst {
+        Some(k) if k.len() == 1 => Ok(Some(k.cast_to_scalar::<f32>()?)),
+        _ => Ok(None),
+    }
+}
+
+/// The threshold of a `x > alpha` or `alpha < x` node.
+fn threshold(model: &TypedModel, cond: &TypedNode, x: OutletId) -> TractResult<Option<f32>> {
+    if is_binary::<Greatser>(cond) && cond.inputs[0] == x {
+        return scalar_konst(model, cond.inputs[1]);
+    }
+    if let Some(op) = cond.op_as::<UnaryOp>() {
+        if op.mini_op.is::<Lesser>() && op.a.len() == 1 && cond.inputs[0] == x {
+            return Ok(Some(op.a.cast_to_scalar::<f32>()?));
+        }
+    }
+    Ok(None)
+}
+
+/// Replace `Where(x > alpha, x, 0)`, PyTorch's export of a zero valued
+/// `Threshold`, by `ThresholdRelu(x)`.
+///
+/// `Relu(x - alpha) + alpha` is sometimes given as an equivalent, but it is
+/// `max(x, alpha)`, so it is left alone.
+pub(crate) fn fuse_threshold_relu(
+    model: &TypedModel,
+    node: &TypedNode,
+) -> TractResult<Option<TypedModelPatch>> {
+    if !node.op_is::<Iff>() {
+        return Ok(None);
+    }
+    let x = node.inputs[1];
+    let fact = model.outlet_fact(x)?;
+    if !(fact.datum_type == f32::datum_type() || fact.datum_type == f64::datum_type())
+        || fact.shape != node.outputs[0].fact.shape
+        || scalar_konst(model, node.inputs[2])? != Some(0.0)
+    {
+        return Ok(None);
+    }
+    if let Some(cond) = single_use(model, node.inputs[0]) {
+        if let Some(alpha) = threshold(model, cond, x)? {
+            let mut patch = TypedModelPatch::default();
+            let x = patch.tap_model(model, x)?;
+            let fused = patch.wire_node(&*node.name, threshold_relu(alpha), &[x])?[0];
+            patch.shunt_outside(OutletId::new(node.id, 0), fused)?;
+            return Ok(Some(patch));
+        }
+    }
+    Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ops::binary::TypedBinOp;
+    use crate::optim::is_element_wise;
+
+    const INPUT: [f32; 7] = [-2.0, -1.0, -0.5, 0.0, 0.5, 1.0, 2.0];
+
+    fn run(alpha: f32) -> TractResult<Arc<Tensor>> {
+        Ok(threshold_relu(alpha).eval(tvec!(rctensor1(&INPUT)))?.remove(0))
+    }
+
+    #[test]
+    fn zero_alpha_is_relu() -> TractResult<()> {
+        let relu: Vec<f32> = INPUT.iter().map(|x| x.max(0.0)).collect();
+        assert_eq!(run(0.0)?, rctensor1(&relu));
+        Ok(())
+    }
+
+    #[test]
+    fn alpha() -> TractResult<()> {
+        assert_eq!(run(1.0)?, rctensor1(&[0f32, 0.0, 0.0, 0.0, 0.0, 0.0, 2.0]));
+        assert_eq!(run(-1.0)?, rctensor1(&[0f32, 0.0, -0.5, 0.0, 0.5, 1.0, 2.0]));
+        let found = threshold_relu(0.5).eval(tvec!(rctensor1(&[-1f64, 0.5, 0.75])))?;
+        assert_eq!(found[0], rctensor1(&[0f64, 0.0, 0.75]));
+        Ok(())
+    }
+
+    fn where_model(flipped: bool) -> TractResult<TypedModel> {
+        let mut model = TypedModel::default();
+        let fact = TypedFact::dt_shape(f32::datum_type(), [INPUT.len()].as_ref())?;
+        let x = model.add_source("x", fact)?;
+        let alpha = rctensor0(0.5f32);
+        let cond = if flipped {
+            model.wire_node("cond", UnaryOp::new(Box::new(Lesser), alpha), &[x])?[0]
+        } else {
+            let alpha = model.add_const("alpha", alpha)?;
+            model.wire_node("cond", TypedBinOp(Box::new(Greatser)), &[x, alpha])?[0]
+        };
+        let zero = model.add_const("zero", rctensor0(0f32))?;
+        let y = model.wire_node("where", Iff, &[cond, x, zero])?;
+        model.set_output_outlets(&y)?;
+        Ok(model)
+    }
+
+    fn check_fused(model: TypedModel) -> TractResult<()> {
+        let input = tvec!(tensor1(&INPUT));
+        let reference = SimplePlan::new(model.clone())?.run(input.clone())?.remove(0);
+        let model = model.declutter()?;
+        let output = model.node(model.output_outlets()?[0].node);
+        assert!(is_element_wise::<ThresholdRelu>(output));
+        assert_eq!(model.nodes().len(), 2);
+        let found = SimplePlan::new(model)?.run(input)?.remove(0);
+        assert_eq!(found, reference);
+        assert_eq!(found, run(0.5)?);
+        Ok(())
+    }
+
+    #[test]
+    fn fuse_where() -> TractResult<()> {
+        check_fused(where_model(false)?)?;
+        check_fused(where_model(true)?)
+    }
+
+    #[test]
+    fn fused_is_kept() -> TractResult<()> {
+        let mut model = TypedModel::default();
+        let fact = TypedFact::dt_shape(f32::datum_type(), [INPUT.len()].as_ref())?;
+        let x = model.add_source("x", fact)?;
+        let y = model.wire_node("threshold", threshold_relu(0.5), &[x])?;
+        model.set_output_outlets(&y)?;
+        check_fused(model)
+    }
+}
@@ -0,0 +1,219 @@
+//! The float ops codegen makes that serialization formats store, lowered to
+//! plain data.
+//!
+//! tract-pb and tract-flatbuf both go through this module, and only map its
+//! types to their own messages. Matrix products are rebuilt with the kernel
+//! the loading host selects, and their packed weights used as they are:
+//! loading fails if this kernel does not pack them as the one of the saving
+//! host did.
+use crate::internal::*;
+use crate::ops::cnn::conv::{DepthWise, Im2Col};
+use crate::ops::cnn::{AvgPoolFixed, MaxPoolFixed};
+use crate::ops::matmul::phy::{MatMatMulPackB, MatMatMulUnaryFinite};
+use crate::ops::matmul::MMMWrapper;
+use ndarray::ArrayD;
+use tract_linalg::frame::{PackA, PackB};
+use tract_linalg::mmm::{FusedSpec, MatMatMul, MatrixStoreSpec};
+
+pub type FloatMatMatMul = MatMatMulUnaryFinite<f32, f32, f32, f32>;
+
+/// One of the ops serialization formats store.
+#[derive(Debug, Clone, Copy)]
+pub enum PhysicalOp<'a> {
+    Im2Col(&'a Im2Col<f32>),
+    MatMatMulPackB(&'a MatMatMulPackB<f32>),
+    MatMatMul(&'a FloatMatMatMul),
+    MaxPoolFixed(&'a MaxPoolFixed<f32>),
+    AvgPoolFixed(&'a AvgPoolFixed<f32>),
+    DepthWise(&'a DepthWise<f32>),
+}
+
+impl<'a> PhysicalOp<'a> {
+    pub fn from_op(op: &'a dyn Op) -> Option<PhysicalOp<'a>> {
+        if let Some(op) = op.downcast_ref::<Im2Col<f32>>() {
+            Some(PhysicalOp::Im2Col(op))
+        } else if let Some(op) = op.downcast_ref::<MatMatMulPackB<f32>>() {
+            Some(PhysicalOp::MatMatMulPackB(op))
+        } else if let Some(op) = op.downcast_ref::<FloatMatMatMul>() {
+            Some(PhysicalOp::MatMatMul(op))
+        } else if let Some(op) = op.downcast_ref::<MaxPoolFixed<f32>>() {
+            Some(PhysicalOp::MaxPoolFixed(op))
+        } else if let Some(op) = op.downcast_ref::<AvgPoolFixed<f32>>() {
+            Some(PhysicalOp::AvgPoolFixed(op))
+        } else if let Some(op) = op.downcast_ref::<DepthWise<f32>>() {
+            Some(PhysicalOp::DepthWise(op))
+        } else {
+            None
+        }
+    }
+}
+
+/// How a matrix of a product is stored, offsets and strides in elements.
+#[derive(Debug, Clone, PartialEq)]
+pub enum StoreParts {
+    Packed,
+    Strides { row_stride: isize, col_stride: isize },
+    OffsetsAndPtrs { row_offsets: TVec<isize>, col_offsets: TVec<isize> },
+    VecStride { stride: isize },
+}
+
+impl StoreParts {
+    fn from_spec(spec: &MatrixStoreSpec) -> StoreParts {
+        let size = std::mem::size_of::<f32>() as isize;
+        let elements = |bytes: &[isize]| bytes.iter().map(|o| o / size).collect();
+        match spec {
+            MatrixStoreSpec::Packed { .. } => StoreParts::Packed,
+            MatrixStoreSpec::Strides { row_byte_stride, col_byte_stride, .. } => {
+                StoreParts::Strides {
+                    row_stride: row_byte_stride / size,
+                    col_stride: col_byte_stride / size,
+                }
+            }
+            MatrixStoreSpec::OffsetsAndPtrs { row_byte_offsets, col_byte_offsets, .. } => {
+                // the kernel setter appends four copies of the last row offset
+                let rows = &row_byte_offsets[..row_byte_offsets.len().saturating_sub(4)];
+                StoreParts::OffsetsAndPtrs {
+                    row_offsets: elements(rows),
+                    col_offsets: elements(col_byte_offsets),
+                }
+            }
+            MatrixStoreSpec::VecStride { byte_stride, .. } => {
+                StoreParts::VecStride { stride: byte_stride / size }
+            }
+        }
+    }
+}
+
+/// The kernel of a product: its dimensions, how it packs A and B, and how
+/// it stores B and C.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MmmParts {
+    pub m: usize,
+    pub k: usize,
+    pub n: usize,
+    pub mr: usize,
+    pub nr: usize,
+    pub a_alignment: usize,
+    pub b_alignment: usize,
+    pub b_storage: StoreParts,
+    pub c_storage: StoreParts,
+}
+
+impl MmmParts {
+    fn from_mmm(mmm: &dyn MatMatMul<f32, f32, f32, f32>) -> MmmParts {
+        let (a_pack, b_pack) = (mmm.a_pack(), mmm.b_pack());
+        MmmParts {
+            m: mmm.m(),
+            k: mmm.k(),
+            n: mmm.n(),
+            mr: a_pack.mr(),
+            nr: b_pack.nr(),
+            a_alignment: a_pack.alignment(),
+            b_alignment: b_pack.alignment(),
+            b_storage: StoreParts::from_spec(mmm.b_storage()),
+            c_storage: StoreParts::from_spec(mmm.c_storage()),
+        }
+    }
+
+    /// The kernel this host selects for the product, storing B and C as
+    /// described.
+    fn to_host_mmm(&self) -> TractResult<Box<dyn MatMatMul<f32, f32, f32, f32>>> {
+        let (m, k, n) = (self.m, self.k, self.n);
+        let mut mmm = (tract_linalg::ops().smmm)(m, k, n);
+        let (a_pack, b_pack) = (mmm.a_pack(), mmm.b_pack());
+        if a_pack != PackA::new(k, m, self.mr, self.a_alignment)
+            || b_pack != PackB::new(k, n, self.nr, self.b_alignment)
+        {
+            bail!(
+                "Weights are packed for a {}x{} kernel, but this host uses a {}x{} one: \
+                 load the decluttered model instead",
+                self.mr,
+                self.nr,
+                a_pack.mr(),
+                b_pack.nr()
+            )
+        }
+        unsafe {
+            match &self.b_storage {
+                StoreParts::Packed => (),
+                StoreParts::OffsetsAndPtrs { row_offsets, col_offsets } => {
+                    if row_offsets.is_empty() || col_offsets.is_empty() {
+                        bail!("Missing offsets for a matrix stored by offsets")
+                    }
+                    mmm.b_from_data_and_offsets(row_offsets, col_offsets)
+                }
+                StoreParts::VecStride { stride } => mmm.b_vec_from_data_and_stride(*stride),
+                StoreParts::Strides { .. } => bail!("B can not be stored by strides"),
+            }
+            match &self.c_storage {
+                StoreParts::Strides { row_stride, col_stride } => {
+                    mmm.c_from_data_and_strides(*row_stride, *col_stride)
+                }
+                StoreParts::VecStride { stride } => mmm.c_vec_from_data_and_stride(*stride),
+                _ => bail!("C must be stored by strides"),
+            }
+        }
+        Ok(mmm)
+    }
+}
+
+/// A float matrix product, with its packed A matrices.
+#[derive(Debug, Clone)]
+pub struct MatMatMulParts {
+    pub mmm: MmmParts,
+    pub c_trans: bool,
+    pub bc_c_shape: TVec<usize>,
+    pub c_fact: TypedFact,
+    pub c_prefix_dim_and_stride: Option<(TVec<usize>, TVec<isize>)>,
+    pub packed_as: ArrayD<Arc<Tensor>>,
+    pub fused_ops: Option<ArrayD<Vec<FusedSpec<f32>>>>,
+}
+
+impl MatMatMulParts {
+    pub fn from_op(op: &FloatMatMatMul) -> TractResult<MatMatMulParts> {
+        let mmm = match &op.mmm {
+            MMMWrapper::Plain(mmm) => mmm,
+            MMMWrapper::Quant(_) => bail!("No serialization for quantized matrix products"),
+        };
+        Ok(MatMatMulParts {
+            mmm: MmmParts::from_mmm(&**mmm),
+            c_trans: op.c_trans,
+            bc_c_shape: op.bc_c_shape.clone(),
+            c_fact: op.c_fact.clone(),
+            c_prefix_dim_and_stride: op.c_prefix_dim_and_stride.clone(),
+            packed_as: op.packed_as.clone(),
+            fused_ops: op.fused_ops.clone(),
+        })
+    }
+
+    /// Rebuild the op with the kernel of this host.
+    ///
+    /// Packed A matrices not aligned as the kernel needs are copied, so
+    /// mapped data is used in place.
+    pub fn into_op(self) -> TractResult<FloatMatMatMul> {
+        let mmm = self.mmm.to_host_mmm()?;
+        let a_pack = mmm.a_pack();
+        let mut packed_as = self.packed_as;
+        for pa in packed_as.iter_mut() {
+            if pa.len() != a_pack.len() {
+                bail!("Expected packed A of {} values, found {}", a_pack.len(), pa.len())
+            }
+            if pa.as_ptr::<f32>()? as usize % a_pack.alignment() != 0 {
+                let mut aligned = unsafe {
+                    Tensor::uninitialized_aligned::<f32>(pa.shape(), a_pack.alignment())?
+                };
+                aligned.as_slice_mut::<f32>()?.copy_from_slice(pa.as_slice::<f32>()?);
+                *pa = aligned.into_arc_tensor();
+            }
+        }
+        Ok(MatMatMulUnaryFinite {
+            c_trans: self.c_trans,
+            bc_c_shape: self.bc_c_shape,
+            c_fact: self.c_fact,
+            c_prefix_dim_and_stride: self.c_prefix_dim_and_stride,
+            packed_as,
+            fused_ops: self.fused_ops,
+            mmm: MMMWrapper::Plain(mmm),
+        })
+    }
+}
@@ -0,0 +1,318 @@
+use crate::datum::unpack_4bit;
+use crate::internal::*;
+use crate::ops::matmul::MatMul;
+
+/// Dequantization of a I4 or U4 tensor, typically a constant holding the
+/// packed weights of a model, to f32 or f16.
+///
+/// The op is deliberately not stateless, so that constant propagation does
+/// not expand the weights to floats. When it feeds the B operand of a plain
+/// f32 MatMul, declutter fuses both into a `MatMul4Bit`.
+#[derive(Debug, Clone, new)]
+pub struct Dequantize4Bit {
+    pub scale: f32,
+    pub zero_point: i8,
+    pub datum_type: DatumType,
+}
+
+impl Dequantize4Bit {
+    fn dequantize(&self, input: &Tensor) -> TractResult<Tensor> {
+        let output = input.dequantize_i4(self.scale, self.zero_point)?;
+        match self.datum_type {
+            DatumType::F32 => Ok(output),
+            DatumType::F16 => output.to_f16(),
+            dt => bail!("Can only dequantize to f32 or f16, got {:?}", dt),
+        }
+    }
+}
+
+impl Op for Dequantize4Bit {
+    fn name(&self) -> Cow<str> {
+        "Dequantize4Bit".into()
+    }
+
+    fn info(&self) -> TractResult<Vec<String>> {
+        Ok(vec![format!(
+            "scale: {} zero point: {} to {:?}",
+            self.scale, self.zero_point, self.datum_type
+        )])
+    }
+
+    op_as_typed_op!();
+    not_a_pulsed_op!();
+}
+
+impl StatefullOp for Dequantize4Bit {
+    fn state(
+        &self,
+        _session: &mut SessionState,
+        _node_id: usize,
+    ) -> TractResult<Option<Box<dyn OpState>>> {
+        Ok(Some(Box::new(Dequantize4BitState)))
+    }
+}
+
+#[derive(Clone, Debug)]
+struct Dequantize4BitState;
+
+impl OpState for Dequantize4BitState {
+    fn eval(
+        &mut self,
+        _session: &mut SessionState,
+        op: &dyn Op,
+        mut inputs: TVec<Arc<Tensor>>,
+    ) -> TractResult<TVec<Arc<Tensor>>> {
+        let op = op.downcast_ref::<Dequantize4Bit>().ok_or("Wrong Op type")?;
+        let input = args_1!(inputs);
+        Ok(tvec!(op.dequantize(&input)?.into_arc_tensor()))
+    }
+}
+
+impl TypedOp for Dequantize4Bit {
+    fn output_facts(&self, inputs: &[&TypedFact]) -> TractResult<TVec<TypedFact>> {
+        if !inputs[0].datum_type.is_packed() {
+            bail!("Dequantize4Bit expects a I4 or U4 input, got {:?}", inputs[0])
+        }
+        Ok(tvec!(TypedFact::dt_shape(self.datum_type, inputs[0].shape.clone())?))
+    }
+
+    fn declutter(
+        &self,
+        model: &TypedModel,
+        node: &TypedNode,
+    ) -> TractResult<Option<TypedModelPatch>> {
+        let weights = if let Some(weights) = &model.outlet_fact(node.inputs[0])?.konst {
+            weights.clone()
+        } else {
+            return Ok(None);
+        };
+        if node.outputs[0].successors.len() != 1 {
+            return Ok(None);
+        }
+        let succ = model.node(node.outputs[0].successors[0].node);
+        let mm = if let Some(mm) = succ.op_as::<MatMul>() {
+            mm
+        } else {
+            return Ok(None);
+        };
+        if self.datum_type != DatumType::F32
+            || weights.rank() != 2
+            || succ.inputs[1] != OutletId::new(node.id, 0)
+            || succ.inputs[0].node == node.id
+            || mm.a_trans
+            || mm.c_trans
+            || mm.q_params().is_some()
+            || model.outlet_fact(succ.inputs[0])?.datum_type != DatumType::F32
+        {
+            return Ok(None);
+        }
+        let op = MatMul4Bit::new(weights, mm.b_trans, self.scale, self.zero_point);
+        let mut patch = TypedModelPatch::default();
+        let a = patch.tap_model(model, succ.inputs[0])?;
+        let fused = patch.wire_node(&*succ.name, op, &[a])?[0];
+        patch.shunt_outside(OutletId::new(succ.id, 0), fused)?;
+        Ok(Some(patch))
+    }
+
+    typed_op_as_op!();
+}
+
+/// Product of a f32 input by a constant matrix of 4-bit packed weights.
+///
+/// The weights are dequantized one row at a time while accumulating the
+/// product, the full f32 matrix is never allocated. The input is `[.., m, k]`,
+/// the weights are `[k, n]`, or `[n, k]` when `b_trans` is set.
+#[derive(Debug, Clone, new)]
+pub struct MatMul4Bit {
+    pub weights: Arc<Tensor>,
+    pub b_trans: bool,
+    pub scale: f32,
+    pub zero_point: i8,
+}
+
+impl MatMul4Bit {
+    fn k_n(&self) -> (usize, usize) {
+        let shape = self.weights.shape();
+        if self.b_trans {
+            (shape[1], shape[0])
+        } else {
+            (shape[0], shape[1])
+        }
+    }
+
+    /// Dequantized value of each of the 16 possible nibbles.
+    fn lut(&self) -> [f32; 16] {
+        let signed = self.weights.datum_type() == DatumType::I4;
+        let mut lut = [0f32; 16];
+        for (nibble, v) in lut.iter_mut().enumerate() {
+            let q = unpack_4bit(&[nibble as u8], 0, signed) as i32;
+            *v = (q - self.zero_point as i32) as f32 * self.scale;
+        }
+        lut
+    }
+
+    /// Dequantize the `ix`-th row of the weights matrix, as stored.
+    fn weights_row(lut: &[f32; 16], bytes: &[u8], ix: usize, row: &mut [f32]) {
+        let mut offset = ix * row.len();
+        let mut row = &mut row[..];
+        if offset % 2 == 1 && !row.is_empty() {
+            row[0] = lut[(bytes[offset / 2] >> 4) as usize];
+            row = &mut row[1..];
+            offset += 1;
+        }
+        let bytes = &bytes[offset / 2..];
+        let half = row.len() / 2;
+        let mut pairs = row.chunks_exact_mut(2);
+        for (pair, byte) in (&mut pairs).zip(bytes.iter()) {
+            pair[0] = lut[(byte & 0x0f) as usize];
+            pair[1] = lut[(byte >> 4) as usize];
+        }
+        if let [last] = pairs.into_remainder() {
+            *last = lut[(bytes[half] & 0x0f) as usize];
+        }
+    }
+}
+
+impl Op for MatMul4Bit {
+    fn name(&self) -> Cow<str> {
+        "MatMul4Bit".into()
+    }
+
+    fn info(&self) -> TractResult<Vec<String>> {
+        Ok(vec![
+            format!(
+                "b_trans: {:?} scale: {} zero point: {}",
+                self.b_trans, self.scale, self.zero_point
+            ),
+            format!("weights: {:?}", self.weights),
+        ])
+    }
+
+    op_as_typed_op!();
+    not_a_pulsed_op!();
+}
+
+impl StatelessOp for MatMul4Bit {
+    fn eval(&self, mut inputs: TVec<Arc<Tensor>>) -> TractResult<TVec<Arc<Tensor>>> {
+        let a = args_1!(inputs);
+        let (k, n) = self.k_n();
+        if a.rank() < 2 || a.shape()[a.rank() - 1] != k {
+            bail!("MatMul4Bit can not multiply {:?} by {:?}", a.shape(), self.weights.shape())
+        }
+        let mut shape: TVec<usize> = a.shape().into();
+        shape[a.rank() - 1] = n;
+        let bytes = self.weights.as_packed_bytes()?;
+        let lut = self.lut();
+        let a = a.as_slice::<f32>()?;
+        let rows = a.len() / k;
+        let mut c = vec![0f32; rows * n];
+        if self.b_trans {
+            let mut row = vec![0f32; k];
+            for j in 0..n {
+                Self::weights_row(&lut, bytes, j, &mut row);
+                for r in 0..rows {
+                    let a = &a[r * k..][..k];
+                    c[r * n + j] = a.iter().zip(row.iter()).map(|(a, b)| a * b).sum();
+                }
+            }
+        } else {
+            let mut row = vec![0f32; n];
+            for l in 0..k {
+                Self::weights_row(&lut, bytes, l, &mut row);
+                for r in 0..rows {
+                    let x = a[r * k + l];
+                    c[r * n..][..n].iter_mut().zip(row.iter()).for_each(|(c, b)| *c += x * b);
+                }
+            }
+        }
+        Ok(tvec!(ndarray::ArrayD::from_shape_vec(&*shape, c)?.into_arc_tensor()))
+    }
+}
+
+impl TypedOp for MatMul4Bit {
+    fn output_facts(&self, inputs: &[&TypedFact]) -> TractResult<TVec<TypedFact>> {
+        let (k, n) = self.k_n();
+        let rank = inputs[0].rank();
+        if rank < 2 || inputs[0].shape.dim(rank - 1).to_integer().ok() != Some(k as i32) {
+            bail!("MatMul4Bit can not multiply {:?} by {:?}", inputs[0], self.weights.shape())
+        }
+        let mut shape: TVec<TDim> = inputs[0].shape.iter().collect();
+        shape[rank - 1] = n.to_dim();
+        Ok(tvec!(TypedFact::dt_shape(f32::datum_type(), &*shape)?))
+    }
+
+    fn cost(&self, inputs: &[&TypedFact]) -> TractResult<TVec<(Cost, TDim)>> {
+        let rank = inputs[0].rank();
+        let rows = inputs[0].shape.iter().take(rank - 1).product::<TDim>();
+        Ok(tvec!((Cost::FMA(f32::datum_type()), rows * self.weights.len().to_dim())))
+    }
+
+    typed_op_as_op!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    fn model(weights: Tensor, m: usize, b_trans: bool) -> TypedModel {
+        let k = weights.shape()[b_trans as usize];
+        let mut model = TypedModel::default();
+        let fact = TypedFact::dt_shape(f32::datum_type(), [m, k].as_ref()).unwrap();
+        let a = model.add_source("a", fact).unwrap();
+        let w = model.add_const("w", weights).unwrap();
+        let op = Dequantize4Bit::new(0.1, -3, f32::datum_type());
+        let w = model.wire_node("dequant", op, &[w]).unwrap();
+        let mm = MatMul::default().with_b_trans(b_trans);
+        let c = model.wire_node("mm", mm, &[a, w[0]]).unwrap();
+        model.set_output_outlets(&c).unwrap();
+        model
+    }
+
+    fn strat() -> BoxedStrategy<((usize, usize, usize), bool, Vec<u8>, Vec<f32>)> {
+        (1usize..4, 1usize..12, 1usize..8, any::<bool>())
+            .prop_flat_map(|(m, k, n, b_trans)| {
+                (
+                    Just((m, k, n)),
+                    Just(b_trans),
+                    proptest::collection::vec(any::<u8>(), (k * n + 1) / 2),
+                    proptest::collection::vec(-1.0f32..1.0, m * k),
+                )
+            })
+            .boxed()
+    }
+
+    proptest! {
+        #[test]
+        fn fused_matches_dequantized(((m, k, n), b_trans, w, a) in strat()) {
+            let shape = if b_trans { [n, k] } else { [k, n] };
+            let weights = Tensor::from_i4_packed_bytes(&w, &shape).unwrap();
+            let a = Tensor::from(ndarray::ArrayD::from_shape_vec(&[m, k][..], a).unwrap());
+            let model = model(weights, m, b_trans);
+            let reference =
+                SimplePlan::new(model.clone()).unwrap().run(tvec!(a.clone())).unwrap().remove(0);
+
+            let model = model.declutter().unwrap();
+            prop_assert!(model.nodes().iter().any(|n| n.op_is::<MatMul4Bit>()));
+            prop_assert!(model.nodes().iter().all(|n| !n.op_is::<Dequantize4Bit>()));
+            let found = SimplePlan::new(model).unwrap().run(tvec!(a)).unwrap().remove(0);
+            prop_assert!(found.close_enough(&reference, true).is_ok());
+        }
+    }
+
+    #[test]
+    fn not_const_folded() -> TractResult<()> {
+        let weights = Tensor::from_u4_packed_bytes(&[0x10, 0x32], &[2, 2])?;
+        let mut model = TypedModel::default();
+        let w = model.add_const("w", weights)?;
+        let op = Dequantize4Bit::new(0.5, 0, DatumType::F16);
+        let w = model.wire_node("dequant", op, &[w])?;
+        model.set_output_outlets(&w)?;
+        assert!(model.outlet_fact(w[0])?.konst.is_none());
+        let model = model.declutter()?;
+        assert!(model.nodes().iter().any(|n| n.op_is::<Dequantize4Bit>()));
+        let output = SimplePlan::new(model)?.run(tvec!())?.remove(0);
+        assert_eq!(*output, tensor2(&[[0f32, 0.5], [1.0, 1.5]]).to_f16()?);
+        Ok(())
+    }
+}
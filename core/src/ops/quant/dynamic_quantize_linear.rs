@@ -0,0 +1,217 @@
+use crate::internal::*;
+
+/// Quantization of a f32 tensor to u8, with the scale and zero point
+/// computed from the input values, as in ONNX.
+///
+/// The quantized range is extended to include zero. Outputs are the quantized
+/// tensor, the scale (a f32 scalar) and the zero point (a u8 scalar).
+#[derive(Debug, Clone, new, Default)]
+pub struct DynamicQuantizeLinear;
+
+fn saturate_u8(x: f32) -> u8 {
+    x.round().max(0.0).min(255.0) as u8
+}
+
+impl DynamicQuantizeLinear {
+    /// Scale and zero point for the given values.
+    pub fn params(xs: &[f32]) -> (f32, u8) {
+        let min = xs.iter().cloned().fold(0.0f32, f32::min);
+        let max = xs.iter().cloned().fold(0.0f32, f32::max);
+        let scale = (max - min) / 255.0;
+        if scale == 0.0 {
+            return (0.0, 0);
+        }
+        (scale, saturate_u8(-min / scale))
+    }
+}
+
+impl Op for DynamicQuantizeLinear {
+    fn name(&self) -> Cow<str> {
+        "DynamicQuantizeLinear".into()
+    }
+
+    fn validation(&self) -> Validation {
+        Validation::Rounding
+    }
+
+    op_as_typed_op!();
+    not_a_pulsed_op!();
+}
+
+impl StatelessOp for DynamicQuantizeLinear {
+    fn eval(&self, mut inputs: TVec<Arc<Tensor>>) -> TractResult<TVec<Arc<Tensor>>> {
+        let input = args_1!(inputs);
+        let xs = input.as_slice::<f32>()?;
+        let (scale, zero_point) = Self::params(xs);
+        let mut output = unsafe { Tensor::uninitialized::<u8>(input.shape())? };
+        if scale == 0.0 {
+            output.as_slice_mut::<u8>()?.iter_mut().for_each(|y| *y = 0);
+        } else {
+            output
+                .as_slice_mut::<u8>()?
+                .iter_mut()
+                .zip(xs.iter())
+                .for_each(|(y, x)| *y = saturate_u8((x / scale).round() + zero_point as f32));
+        }
+        Ok(tvec!(output.into_arc_tensor(), rctensor0(scale), rctensor0(zero_point)))
+    }
+}
+
+impl InferenceRulesOp for DynamicQuantizeLinear {
+    fn rules<'r, 'p: 'r, 's: 'r>(
+        &'s self,
+        s: &mut Solver<'r>,
+        inputs: &'p [TensorProxy],
+        outputs: &'p [TensorProxy],
+    ) -> InferenceResult {
+        check_input_arity(&inputs, 1)?;
+        check_output_arity(&outputs, 3)?;
+        s.equals(&inputs[0].datum_type, f32::datum_type())?;
+        s.equals(&outputs[0].datum_type, u8::datum_type())?;
+        s.equals(&inputs[0].shape, &outputs[0].shape)?;
+        s.equals(&outputs[1].datum_type, f32::datum_type())?;
+        s.equals(&outputs[1].rank, 0)?;
+        s.equals(&outputs[2].datum_type, u8::datum_type())?;
+        s.equals(&outputs[2].rank, 0)?;
+        Ok(())
+    }
+
+    inference_op_as_op!();
+    to_typed!();
+}
+
+impl TypedOp for DynamicQuantizeLinear {
+    fn output_facts(&self, inputs: &[&TypedFact]) -> TractResult<TVec<TypedFact>> {
+        if inputs[0].datum_type != f32::datum_type() {
+            bail!("DynamicQuantizeLinear expects a f32 input, got {:?}", inputs[0])
+        }
+        Ok(tvec!(
+            TypedFact::dt_shape(u8::datum_type(), inputs[0].shape.clone())?,
+            TypedFact::dt_shape(f32::datum_type(), [0usize; 0].as_ref())?,
+            TypedFact::dt_shape(u8::datum_type(), [0usize; 0].as_ref())?
+        ))
+    }
+
+    typed_op_as_op!();
+}
+
+/// Symmetric quantization of a f32 tensor to i8, with the scale computed
+/// from the input values: it maps the largest absolute value to 127, and
+/// the zero point is 0.
+///
+/// Outputs are the quantized tensor and the scale (a f32 scalar).
+#[derive(Debug, Clone, new, Default)]
+pub struct DynamicQuantizeLinearI8;
+
+impl DynamicQuantizeLinearI8 {
+    /// Scale for the given values.
+    pub fn scale(xs: &[f32]) -> f32 {
+        xs.iter().fold(0.0f32, |acc, x| acc.max(x.abs())) / 127.0
+    }
+}
+
+impl Op for DynamicQuantizeLinearI8 {
+    fn name(&self) -> Cow<str> {
+        "DynamicQuantizeLinearI8".into()
+    }
+
+    fn validation(&self) -> Validation {
+        Validation::Rounding
+    }
+
+    op_as_typed_op!();
+    not_a_pulsed_op!();
+}
+
+impl StatelessOp for DynamicQuantizeLinearI8 {
+    fn eval(&self, mut inputs: TVec<Arc<Tensor>>) -> TractResult<TVec<Arc<Tensor>>> {
+        let input = args_1!(inputs);
+        let xs = input.as_slice::<f32>()?;
+        let scale = Self::scale(xs);
+        let mut output = unsafe { Tensor::uninitialized::<i8>(input.shape())? };
+        if scale == 0.0 {
+            output.as_slice_mut::<i8>()?.iter_mut().for_each(|y| *y = 0);
+        } else {
+            output
+                .as_slice_mut::<i8>()?
+                .iter_mut()
+                .zip(xs.iter())
+                .for_each(|(y, x)| *y = (x / scale).round().max(-127.0).min(127.0) as i8);
+        }
+        Ok(tvec!(output.into_arc_tensor(), rctensor0(scale)))
+    }
+}
+
+impl TypedOp for DynamicQuantizeLinearI8 {
+    fn output_facts(&self, inputs: &[&TypedFact]) -> TractResult<TVec<TypedFact>> {
+        if inputs[0].datum_type != f32::datum_type() {
+            bail!("DynamicQuantizeLinearI8 expects a f32 input, got {:?}", inputs[0])
+        }
+        Ok(tvec!(
+            TypedFact::dt_shape(i8::datum_type(), inputs[0].shape.clone())?,
+            TypedFact::dt_shape(f32::datum_type(), [0usize; 0].as_ref())?
+        ))
+    }
+
+    typed_op_as_op!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(input: Tensor) -> TractResult<(Tensor, f32, u8)> {
+        let mut outputs = DynamicQuantizeLinear.eval(tvec!(input.into()))?;
+        let zero_point = *outputs.remove(2).to_scalar::<u8>()?;
+        let scale = *outputs.remove(1).to_scalar::<f32>()?;
+        Ok((outputs.remove(0).into_tensor(), scale, zero_point))
+    }
+
+    // reference values from the ONNX operator documentation
+    #[test]
+    fn mixed_signs() -> TractResult<()> {
+        let (y, scale, zero_point) = run(tensor1(&[0f32, 2., -3., -2.5, 1.34, 0.5]))?;
+        assert!((scale - 0.019_607_844).abs() < 1e-7);
+        assert_eq!(zero_point, 153);
+        assert_eq!(y, tensor1(&[153u8, 255, 0, 26, 221, 179]));
+        Ok(())
+    }
+
+    #[test]
+    fn all_negative() -> TractResult<()> {
+        let (y, scale, zero_point) = run(tensor1(&[-1f32, -2.1, -1.3, -2.5, -3.34, -4.]))?;
+        assert!((scale - 0.015_686_275).abs() < 1e-7);
+        assert_eq!(zero_point, 255);
+        assert_eq!(y, tensor1(&[191u8, 121, 172, 96, 42, 0]));
+        Ok(())
+    }
+
+    #[test]
+    fn all_positive() -> TractResult<()> {
+        let input =
+            tensor2(&[[1f32, 2.1, 1.3, 2.5], [3.34, 4.0, 1.5, 2.6], [3.9, 4.0, 3.0, 2.345]]);
+        let (y, scale, zero_point) = run(input)?;
+        assert!((scale - 0.015_686_275).abs() < 1e-7);
+        assert_eq!(zero_point, 0);
+        assert_eq!(y, tensor2(&[[64u8, 134, 83, 159], [213, 255, 96, 166], [249, 255, 191, 149]]));
+        Ok(())
+    }
+
+    #[test]
+    fn zeros() -> TractResult<()> {
+        let (y, scale, zero_point) = run(tensor1(&[0f32, 0.]))?;
+        assert_eq!((scale, zero_point), (0.0, 0));
+        assert_eq!(y, tensor1(&[0u8, 0]));
+        Ok(())
+    }
+
+    #[test]
+    fn symmetric_i8() -> TractResult<()> {
+        let mut outputs =
+            DynamicQuantizeLinearI8.eval(tvec!(rctensor1(&[0f32, 2., -3., -2.5, 1.34, 0.5])))?;
+        let scale = *outputs.remove(1).to_scalar::<f32>()?;
+        assert!((scale - 3.0 / 127.0).abs() < 1e-7);
+        assert_eq!(*outputs[0], tensor1(&[0i8, 85, -127, -106, 57, 21]));
+        Ok(())
+    }
+}
@@ -4,7 +4,17 @@ use num_traits::AsPrimitive;
 use num_traits::Zero;
 use tract_linalg::lut::Lut;
 
-#[derive(Clone, Debug)]
+mod dequantize_4bit;
+mod dynamic_quantize_linear;
+mod qlinear_conv;
+mod qlinear_matmul;
+
+pub use self::dequantize_4bit::{Dequantize4Bit, MatMul4Bit};
+pub use self::dynamic_quantize_linear::{DynamicQuantizeLinear, DynamicQuantizeLinearI8};
+pub use self::qlinear_conv::QLinearConv;
+pub use self::qlinear_matmul::{LinearQuant, QLinearMatMul};
+
+#[derive(Clone, Debug, PartialEq)]
 pub struct QParams {
     pub c_datum_type: DatumType,
     pub zero_point_a: Option<Arc<Tensor>>,
@@ -80,6 +90,33 @@ impl QParams {
     }
 }
 
+/// Quantization parameters varying along the output channel axis, for
+/// filters quantized per output channel.
+///
+/// `scale` is the requantization factor of each channel (input scale times
+/// filter scale over output scale). `zero_point` is the output zero point,
+/// either one per channel or a single one shared by all channels.
+#[derive(Clone, Debug, PartialEq, new)]
+pub struct PerChannelQuantizationParams {
+    pub c_datum_type: DatumType,
+    pub scale: Vec<f32>,
+    pub zero_point: Vec<i32>,
+}
+
+impl PerChannelQuantizationParams {
+    pub fn channels(&self) -> usize {
+        self.scale.len()
+    }
+
+    pub fn zero_point(&self, channel: usize) -> i32 {
+        if self.zero_point.len() == 1 {
+            self.zero_point[0]
+        } else {
+            self.zero_point[channel]
+        }
+    }
+}
+
 pub fn quantize_linear_f32_u8(x: f32, scale: f32, zero_point: i32) -> u8 {
     (((x * scale).round() as i32) + zero_point as i32)
         .max(u8::min_value() as i32)
@@ -210,11 +247,14 @@ impl TypedOp for DequantizeLinearF32 {
                 None
             };
             if let Some((scale, zero_point, dt)) = q_params {
-                // first, try Op::quantize() on all ops in the chain
+                // first, try Op::quantize() on all ops in the chain, which is
+                // only valid if the values are requantized as they were
+                let same_params =
+                    (self.scale * scale - 1.0).abs() < 1e-6 && self.zero_point == zero_point;
                 let mut patch = TypedModelPatch::default();
                 let mut wire: OutletId = patch.tap_model(model, node.inputs[0])?.into();
                 let mut next = model.single_succ(node.id)?.unwrap();
-                loop {
+                while same_params {
                     if let Some(op) = next
                         .op
                         .quantize(model, node, dt, scale, zero_point)
@@ -306,6 +346,87 @@ impl PulsedOp for DequantizeLinearF32 {
     pulsed_op_to_typed_op!();
 }
 
+/// Requantization of i32 accumulators, the slices along `axis` using the
+/// scales and zero points of their channel.
+#[derive(Clone, Debug, new)]
+pub struct RequantizePerChannel {
+    pub params: PerChannelQuantizationParams,
+    pub axis: usize,
+}
+
+impl RequantizePerChannel {
+    fn eval_t<T: Datum>(
+        &self,
+        input: &Tensor,
+        quantize: impl Fn(f32, f32, i32) -> T,
+    ) -> TractResult<Tensor> {
+        let input = input.to_array_view::<i32>()?;
+        if input.shape()[self.axis] != self.params.channels() {
+            bail!(
+                "Expected {} channels on axis {}, got {:?}",
+                self.params.channels(),
+                self.axis,
+                input.shape()
+            )
+        }
+        let mut output = unsafe { Tensor::uninitialized::<T>(input.shape())? };
+        let mut view = output.to_array_view_mut::<T>()?;
+        for (c, (x, mut y)) in input
+            .axis_iter(ndarray::Axis(self.axis))
+            .zip(view.axis_iter_mut(ndarray::Axis(self.axis)))
+            .enumerate()
+        {
+            let (scale, zero_point) = (self.params.scale[c], self.params.zero_point(c));
+            ndarray::Zip::from(&mut y)
+                .and(&x)
+                .apply(|y, &x| *y = quantize(x as f32, scale, zero_point));
+        }
+        Ok(output)
+    }
+}
+
+impl Op for RequantizePerChannel {
+    fn name(&self) -> Cow<str> {
+        "RequantizePerChannel".into()
+    }
+
+    fn info(&self) -> TractResult<Vec<String>> {
+        Ok(vec![format!(
+            "axis: {} scales: {:?} zero_points: {:?}",
+            self.axis, self.params.scale, self.params.zero_point
+        )])
+    }
+
+    fn validation(&self) -> Validation {
+        Validation::Rounding
+    }
+
+    canonic!();
+    op_as_typed_op!();
+    not_a_pulsed_op!();
+}
+
+impl StatelessOp for RequantizePerChannel {
+    fn eval(&self, inputs: TVec<Arc<Tensor>>) -> TractResult<TVec<Arc<Tensor>>> {
+        let output = match self.params.c_datum_type {
+            DatumType::I8 => self.eval_t(&inputs[0], quantize_linear_f32_i8)?,
+            DatumType::U8 => self.eval_t(&inputs[0], quantize_linear_f32_u8)?,
+            dt => bail!("Unsupported type {:?}", dt),
+        };
+        Ok(tvec!(output.into_arc_tensor()))
+    }
+}
+
+impl TypedOp for RequantizePerChannel {
+    fn output_facts(&self, inputs: &[&TypedFact]) -> TractResult<TVec<TypedFact>> {
+        let mut fact = inputs[0].clone();
+        fact.datum_type = self.params.c_datum_type;
+        Ok(tvec!(fact))
+    }
+
+    typed_op_as_op!();
+}
+
 element_wise_oop!(lookup_table, LookupTable {table: Box<dyn Lut>},
     [i8] => i8 |op, xs, ys| {
         ys.copy_from_slice(xs);
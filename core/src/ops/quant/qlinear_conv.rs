@@ -0,0 +1,399 @@
+use super::{quantize_linear_f32_i8, quantize_linear_f32_u8, LinearQuant};
+use crate::internal::*;
+use crate::ops::cnn::PaddingSpec;
+use ndarray::*;
+
+/// Convolution of quantized tensors, as ONNX QLinearConv.
+///
+/// The input is NCHW and the kernel OIHW, both u8 or i8. The kernel scale and
+/// zero point are either scalars or one per output channel. Products are
+/// accumulated in i32 from the raw quantized values, the zero points being
+/// accounted for afterwards:
+///
+/// `sum((x - x0) * (k - k0)) = sum(x * k) - k0 * sum(x) - x0 * sum(k) + n * x0 * k0`
+///
+/// where the kernel sums only depend on the kernel. When the kernel is a
+/// constant, declutter folds it in the op with its sums. The optional bias is
+/// i32, with a scale of input scale times kernel scale, and is added to the
+/// accumulators before they get requantized.
+#[derive(Debug, Clone)]
+pub struct QLinearConv {
+    pub padding: PaddingSpec,
+    pub strides: Option<TVec<usize>>,
+    pub dilations: Option<TVec<usize>>,
+    pub group: usize,
+    pub x: LinearQuant,
+    pub k_scale: Vec<f32>,
+    pub k_zero_point: Vec<i32>,
+    pub y: LinearQuant,
+    pub y_datum_type: DatumType,
+    pub bias: Option<Arc<Tensor>>,
+    pub kernel: Option<(Arc<Tensor>, Vec<i32>)>,
+}
+
+fn at<T: Copy>(v: &[T], channel: usize) -> T {
+    if v.len() == 1 {
+        v[0]
+    } else {
+        v[channel]
+    }
+}
+
+fn to_i32(t: &Tensor) -> TractResult<ArrayD<i32>> {
+    match t.datum_type() {
+        DatumType::U8 => Ok(t.to_array_view::<u8>()?.mapv(|x| x as i32)),
+        DatumType::I8 => Ok(t.to_array_view::<i8>()?.mapv(|x| x as i32)),
+        dt => bail!("QLinearConv expects u8 or i8 tensors, got {:?}", dt),
+    }
+}
+
+impl QLinearConv {
+    pub fn new(
+        x: LinearQuant,
+        k_scale: Vec<f32>,
+        k_zero_point: Vec<i32>,
+        y: LinearQuant,
+        y_datum_type: DatumType,
+    ) -> QLinearConv {
+        QLinearConv {
+            padding: PaddingSpec::Valid,
+            strides: None,
+            dilations: None,
+            group: 1,
+            x,
+            k_scale,
+            k_zero_point,
+            y,
+            y_datum_type,
+            bias: None,
+            kernel: None,
+        }
+    }
+
+    pub fn padding(self, padding: PaddingSpec) -> QLinearConv {
+        QLinearConv { padding, ..self }
+    }
+
+    pub fn strides(self, strides: TVec<usize>) -> QLinearConv {
+        QLinearConv { strides: Some(strides), ..self }
+    }
+
+    pub fn dilations(self, dilations: TVec<usize>) -> QLinearConv {
+        QLinearConv { dilations: Some(dilations), ..self }
+    }
+
+    pub fn group(self, group: usize) -> QLinearConv {
+        QLinearConv { group, ..self }
+    }
+
+    pub fn bias(self, bias: Arc<Tensor>) -> QLinearConv {
+        QLinearConv { bias: Some(bias), ..self }
+    }
+
+    /// Fold a constant kernel in the op, precomputing its sums.
+    pub fn with_kernel(self, kernel: Arc<Tensor>) -> TractResult<QLinearConv> {
+        let sums = Self::kernel_sums(&kernel)?;
+        Ok(QLinearConv { kernel: Some((kernel, sums)), ..self })
+    }
+
+    /// Sum of the kernel values, for each output channel.
+    fn kernel_sums(kernel: &Tensor) -> TractResult<Vec<i32>> {
+        let kernel = to_i32(kernel)?;
+        Ok(kernel.outer_iter().map(|k| k.sum()).collect())
+    }
+
+    fn ones(&self, rank: usize) -> TVec<usize> {
+        tvec!(1; rank)
+    }
+
+    fn output_shape<D: DimLike>(&self, ishape: &[D], kshape: &[usize]) -> TractResult<TVec<D>> {
+        if ishape.len() < 3 || ishape.len() != kshape.len() {
+            bail!("QLinearConv input {:?} and kernel {:?} ranks mismatch", ishape, kshape)
+        }
+        if kshape[0] % self.group != 0 || ishape[1] != D::from(kshape[1] * self.group) {
+            bail!(
+                "QLinearConv input {:?} and kernel {:?} channels mismatch for {} groups",
+                ishape,
+                kshape,
+                self.group
+            )
+        }
+        let spatial = ishape.len() - 2;
+        let computed = self.padding.compute(
+            &ishape[2..],
+            &kshape[2..],
+            self.dilations.as_ref().unwrap_or(&self.ones(spatial)),
+            self.strides.as_ref().unwrap_or(&self.ones(spatial)),
+        );
+        let mut shape: TVec<D> = tvec!(ishape[0].clone(), kshape[0].into());
+        shape.extend(computed.into_iter().map(|d| d.output));
+        Ok(shape)
+    }
+
+    /// The accumulators, zero point corrected and biased, before
+    /// requantization.
+    fn accumulate(&self, x: &Tensor, kernel: &Tensor, sums: &[i32]) -> TractResult<ArrayD<i32>> {
+        let x = to_i32(x)?;
+        let k = to_i32(kernel)?;
+        let spatial = x.ndim() - 2;
+        let ones = self.ones(spatial);
+        let strides = self.strides.as_ref().unwrap_or(&ones);
+        let dilations = self.dilations.as_ref().unwrap_or(&ones);
+        let computed = self.padding.compute(&x.shape()[2..], &k.shape()[2..], dilations, strides);
+        let oshape = self.output_shape(x.shape(), k.shape())?;
+        let (channels_per_group, outputs_per_group) = (k.shape()[1], k.shape()[0] / self.group);
+        let kernel_len = k.len() / k.shape()[0];
+        let bias = self.bias.as_ref().map(|b| b.cast_to::<i32>()).transpose()?;
+        let bias = bias.as_ref().map(|b| b.as_slice::<i32>()).transpose()?;
+        let x0 = self.x.zero_point;
+        let mut output = ArrayD::<i32>::zeros(&*oshape);
+        let mut icoords: TVec<usize> = tvec!(0; x.ndim());
+        for (ocoords, acc) in output.indexed_iter_mut() {
+            let (n, o) = (ocoords[0], ocoords[1]);
+            let group = o / outputs_per_group;
+            let kernel = k.index_axis(Axis(0), o);
+            let (mut raw, mut x_sum) = (0i32, 0i32);
+            for (kcoords, &kv) in kernel.indexed_iter() {
+                let c = kcoords[0];
+                icoords[0] = n;
+                icoords[1] = group * channels_per_group + c;
+                let mut valid = true;
+                for d in 0..spatial {
+                    let i = (ocoords[2 + d] * strides[d] + kcoords[1 + d] * dilations[d]) as isize
+                        - computed[d].pad_before as isize;
+                    if i < 0 || i as usize >= x.shape()[2 + d] {
+                        valid = false;
+                        break;
+                    }
+                    icoords[2 + d] = i as usize;
+                }
+                // padding is zero, so it is quantized as the zero point
+                let xv = if valid { x[&*icoords] } else { x0 };
+                raw += xv * kv;
+                x_sum += xv;
+            }
+            let k0 = at(&self.k_zero_point, o);
+            *acc = raw - k0 * x_sum - x0 * sums[o] + kernel_len as i32 * x0 * k0;
+            if let Some(bias) = bias {
+                *acc += bias[o];
+            }
+        }
+        Ok(output)
+    }
+}
+
+impl Op for QLinearConv {
+    fn name(&self) -> Cow<str> {
+        "QLinearConv".into()
+    }
+
+    fn info(&self) -> TractResult<Vec<String>> {
+        Ok(vec![
+            format!(
+                "padding: {:?} strides: {:?} dilations: {:?} group: {}",
+                self.padding, self.strides, self.dilations, self.group
+            ),
+            format!(
+                "x: {:?} k scale: {:?} k zero point: {:?} y: {:?} ({:?})",
+                self.x, self.k_scale, self.k_zero_point, self.y, self.y_datum_type
+            ),
+            format!("constant kernel: {}", self.kernel.is_some()),
+        ])
+    }
+
+    fn validation(&self) -> Validation {
+        Validation::Rounding
+    }
+
+    op_as_typed_op!();
+    not_a_pulsed_op!();
+}
+
+impl StatelessOp for QLinearConv {
+    fn eval(&self, inputs: TVec<Arc<Tensor>>) -> TractResult<TVec<Arc<Tensor>>> {
+        let acc = if let Some((kernel, sums)) = &self.kernel {
+            self.accumulate(&inputs[0], kernel, sums)?
+        } else {
+            self.accumulate(&inputs[0], &inputs[1], &Self::kernel_sums(&inputs[1])?)?
+        };
+        let y0 = self.y.zero_point;
+        let scale = |o: usize| self.x.scale * at(&self.k_scale, o) / self.y.scale;
+        let output = match self.y_datum_type {
+            DatumType::U8 => Array::from_shape_fn(acc.raw_dim(), |c| {
+                quantize_linear_f32_u8(acc[&c] as f32, scale(c[1]), y0)
+            })
+            .into_tensor(),
+            DatumType::I8 => Array::from_shape_fn(acc.raw_dim(), |c| {
+                quantize_linear_f32_i8(acc[&c] as f32, scale(c[1]), y0)
+            })
+            .into_tensor(),
+            dt => bail!("QLinearConv output must be u8 or i8, got {:?}", dt),
+        };
+        Ok(tvec!(output.into_arc_tensor()))
+    }
+}
+
+impl TypedOp for QLinearConv {
+    fn output_facts(&self, inputs: &[&TypedFact]) -> TractResult<TVec<TypedFact>> {
+        let kshape: TVec<usize> = if let Some((kernel, _)) = &self.kernel {
+            kernel.shape().into()
+        } else {
+            inputs[1]
+                .shape
+                .iter()
+                .map(|d| Ok(d.to_integer()? as usize))
+                .collect::<TractResult<_>>()
+                .chain_err(|| "QLinearConv kernel shape must be known")?
+        };
+        let channels = kshape.get(0).cloned().unwrap_or(0);
+        for (name, len) in
+            &[("kernel scale", self.k_scale.len()), ("kernel zero point", self.k_zero_point.len())]
+        {
+            if *len != 1 && *len != channels {
+                bail!("QLinearConv {} must have 1 or {} values, got {}", name, channels, len)
+            }
+        }
+        if let Some(bias) = &self.bias {
+            if bias.len() != channels {
+                bail!("QLinearConv bias must have {} values, got {:?}", channels, bias.shape())
+            }
+        }
+        let oshape = self.output_shape(&*inputs[0].shape.to_tvec(), &*kshape)?;
+        Ok(tvec!(TypedFact::dt_shape(self.y_datum_type, &*oshape)?))
+    }
+
+    fn declutter(
+        &self,
+        model: &TypedModel,
+        node: &TypedNode,
+    ) -> TractResult<Option<TypedModelPatch>> {
+        if self.kernel.is_some() {
+            return Ok(None);
+        }
+        if let Some(kernel) = &model.outlet_fact(node.inputs[1])?.konst {
+            let op = self.clone().with_kernel(kernel.clone())?;
+            return Ok(Some(TypedModelPatch::replace_single_op(
+                model,
+                node,
+                &node.inputs[..1],
+                op,
+            )?));
+        }
+        Ok(None)
+    }
+
+    typed_op_as_op!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ops::cnn::Conv;
+    use crate::ops::quant::DynamicQuantizeLinear;
+
+    // deterministic values in [-1, 1)
+    fn values(len: usize, seed: u32) -> Vec<f32> {
+        let mut state = seed;
+        (0..len)
+            .map(|_| {
+                state = state.wrapping_mul(1_103_515_245).wrapping_add(12345);
+                ((state >> 8) % 2000) as f32 / 1000.0 - 1.0
+            })
+            .collect()
+    }
+
+    fn quantize(values: &[f32], shape: &[usize]) -> (Tensor, LinearQuant) {
+        let (scale, zero_point) = DynamicQuantizeLinear::params(values);
+        let q = LinearQuant::new(scale, zero_point as i32);
+        let data = Tensor::from(ArrayD::from_shape_vec(shape, values.to_vec()).unwrap());
+        (q.quantize(&data, u8::datum_type()).unwrap(), q)
+    }
+
+    fn compare_with_float(
+        conv: Conv,
+        q_conv: QLinearConv,
+        xshape: &[usize],
+        kshape: &[usize],
+    ) -> TractResult<()> {
+        let x = values(xshape.iter().product(), 1);
+        let k = values(kshape.iter().product(), 2);
+        let bias = values(kshape[0], 3);
+        let fx = Tensor::from(ArrayD::from_shape_vec(xshape, x.clone())?);
+        let fk = Tensor::from(ArrayD::from_shape_vec(kshape, k.clone())?);
+        let reference =
+            conv.bias_input(2).eval(tvec!(fx.into(), fk.into(), rctensor1(&bias)))?.remove(0);
+        let reference = reference.as_slice::<f32>()?;
+        let (qx, x_q) = quantize(&x, xshape);
+        let (qk, k_q) = quantize(&k, kshape);
+        let (_, y_q) = quantize(reference, &[reference.len()]);
+        let bias_scale = x_q.scale * k_q.scale;
+        let qbias: Vec<i32> = bias.iter().map(|b| (b / bias_scale).round() as i32).collect();
+        let op = QLinearConv {
+            x: x_q,
+            k_scale: vec![k_q.scale],
+            k_zero_point: vec![k_q.zero_point],
+            y: y_q,
+            ..q_conv
+        }
+        .bias(rctensor1(&qbias));
+        let plain = op.eval(tvec!(qx.clone().into(), qk.clone().into()))?.remove(0);
+        let folded = op.clone().with_kernel(qk.into())?.eval(tvec!(qx.into()))?.remove(0);
+        assert_eq!(plain, folded);
+        let found = y_q.dequantize(&plain)?;
+        let range = reference.iter().fold(0f32, |m, r| m.max(r.abs()));
+        for (f, r) in found.as_slice::<f32>()?.iter().zip(reference.iter()) {
+            assert!((f - r).abs() <= 0.01 * range, "{} != {}", f, r);
+        }
+        Ok(())
+    }
+
+    fn q_conv() -> QLinearConv {
+        QLinearConv::new(
+            LinearQuant::new(1.0, 0),
+            vec![1.0],
+            vec![0],
+            LinearQuant::new(1.0, 0),
+            u8::datum_type(),
+        )
+    }
+
+    #[test]
+    fn float_reference_2d() -> TractResult<()> {
+        let padding = PaddingSpec::Explicit(tvec!(1, 0), tvec!(1, 2));
+        compare_with_float(
+            Conv::default().padding(padding.clone()).kernel_shape(tvec!(3, 3)),
+            q_conv().padding(padding),
+            &[1, 3, 6, 5],
+            &[4, 3, 3, 3],
+        )
+    }
+
+    #[test]
+    fn float_reference_groups_strides_dilations() -> TractResult<()> {
+        let conv = Conv::default()
+            .group(2)
+            .strides(tvec!(2))
+            .dilations(tvec!(2))
+            .padding(PaddingSpec::SameUpper)
+            .kernel_shape(tvec!(3));
+        let q_conv =
+            q_conv().group(2).strides(tvec!(2)).dilations(tvec!(2)).padding(PaddingSpec::SameUpper);
+        compare_with_float(conv, q_conv, &[2, 4, 11], &[6, 2, 3])
+    }
+
+    #[test]
+    fn constant_kernel_is_folded() -> TractResult<()> {
+        let mut model = TypedModel::default();
+        let x =
+            model.add_source("x", TypedFact::dt_shape(u8::datum_type(), [1, 1, 3].as_ref())?)?;
+        let k = model.add_const("k", tensor3(&[[[1u8, 2]]]))?;
+        let y = model.wire_node("conv", q_conv(), &[x, k])?[0];
+        model.set_output_outlets(&[y])?;
+        let model = model.declutter()?;
+        let conv = model.nodes().iter().find_map(|n| n.op_as::<QLinearConv>()).unwrap();
+        assert_eq!(conv.kernel.as_ref().unwrap().1, vec![3]);
+        let y = SimplePlan::new(&model)?.run(tvec!(tensor3(&[[[1u8, 2, 3]]])))?.remove(0);
+        assert_eq!(*y, tensor3(&[[[5u8, 8]]]));
+        Ok(())
+    }
+}
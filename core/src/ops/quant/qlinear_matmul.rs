@@ -0,0 +1,226 @@
+use super::{quantize_linear_f32_i8, quantize_linear_f32_u8};
+use crate::internal::*;
+use crate::ops::matmul::{infer_shapes, MatMul};
+
+/// Scale and zero point of a linearly quantized tensor: the real value of q
+/// is `(q - zero_point) * scale`.
+#[derive(Debug, Clone, Copy, PartialEq, new)]
+pub struct LinearQuant {
+    pub scale: f32,
+    pub zero_point: i32,
+}
+
+impl LinearQuant {
+    /// From scale and zero point tensors, as found in ONNX QLinear ops.
+    pub fn from_tensors(scale: &Tensor, zero_point: &Tensor) -> TractResult<LinearQuant> {
+        if scale.len() != 1 || zero_point.len() != 1 {
+            bail!(
+                "Only per-tensor quantization is supported, got scale {:?} and zero point {:?}",
+                scale.shape(),
+                zero_point.shape()
+            )
+        }
+        let zero_point = match zero_point.datum_type() {
+            DatumType::U8 => zero_point.as_slice::<u8>()?[0] as i32,
+            DatumType::I8 => zero_point.as_slice::<i8>()?[0] as i32,
+            dt => bail!("Zero point must be u8 or i8, got {:?}", dt),
+        };
+        Ok(LinearQuant::new(scale.cast_to::<f32>()?.as_slice::<f32>()?[0], zero_point))
+    }
+
+    pub fn dequantize(&self, input: &Tensor) -> TractResult<Tensor> {
+        let (scale, zero_point) = (self.scale, self.zero_point);
+        let output = match input.datum_type() {
+            DatumType::U8 => {
+                input.to_array_view::<u8>()?.map(|&x| (x as i32 - zero_point) as f32 * scale)
+            }
+            DatumType::I8 => {
+                input.to_array_view::<i8>()?.map(|&x| (x as i32 - zero_point) as f32 * scale)
+            }
+            dt => bail!("Expected a u8 or i8 tensor, got {:?}", dt),
+        };
+        Ok(output.into_tensor())
+    }
+
+    pub fn quantize(&self, input: &Tensor, dt: DatumType) -> TractResult<Tensor> {
+        let (scale, zero_point) = (self.scale.recip(), self.zero_point);
+        let input = input.to_array_view::<f32>()?;
+        let output = match dt {
+            DatumType::U8 => {
+                input.map(|&x| quantize_linear_f32_u8(x, scale, zero_point)).into_tensor()
+            }
+            DatumType::I8 => {
+                input.map(|&x| quantize_linear_f32_i8(x, scale, zero_point)).into_tensor()
+            }
+            dt => bail!("Can only quantize to u8 or i8, got {:?}", dt),
+        };
+        Ok(output)
+    }
+}
+
+/// Matrix product of two quantized tensors, as ONNX QLinearMatMul.
+///
+/// A and B are dequantized, multiplied in f32, and the result is quantized
+/// again to `y_datum_type`. When B is a constant, it is dequantized once and
+/// for all by declutter, and the op then only takes A as input.
+#[derive(Debug, Clone)]
+pub struct QLinearMatMul {
+    pub a: LinearQuant,
+    pub b: LinearQuant,
+    pub y: LinearQuant,
+    pub y_datum_type: DatumType,
+    pub b_dequantized: Option<Arc<Tensor>>,
+}
+
+impl QLinearMatMul {
+    pub fn new(
+        a: LinearQuant,
+        b: LinearQuant,
+        y: LinearQuant,
+        y_datum_type: DatumType,
+    ) -> QLinearMatMul {
+        QLinearMatMul { a, b, y, y_datum_type, b_dequantized: None }
+    }
+}
+
+impl Op for QLinearMatMul {
+    fn name(&self) -> Cow<str> {
+        "QLinearMatMul".into()
+    }
+
+    fn info(&self) -> TractResult<Vec<String>> {
+        Ok(vec![
+            format!("a: {:?} b: {:?} y: {:?} ({:?})", self.a, self.b, self.y, self.y_datum_type),
+            format!("constant b: {}", self.b_dequantized.is_some()),
+        ])
+    }
+
+    fn validation(&self) -> Validation {
+        Validation::Rounding
+    }
+
+    op_as_typed_op!();
+    not_a_pulsed_op!();
+}
+
+impl StatelessOp for QLinearMatMul {
+    fn eval(&self, inputs: TVec<Arc<Tensor>>) -> TractResult<TVec<Arc<Tensor>>> {
+        let a = self.a.dequantize(&inputs[0])?;
+        let b = if let Some(b) = &self.b_dequantized {
+            b.clone()
+        } else {
+            self.b.dequantize(&inputs[1])?.into_arc_tensor()
+        };
+        let y = MatMul::default().eval(tvec!(a.into_arc_tensor(), b))?.remove(0);
+        Ok(tvec!(self.y.quantize(&y, self.y_datum_type)?.into_arc_tensor()))
+    }
+}
+
+impl TypedOp for QLinearMatMul {
+    fn output_facts(&self, inputs: &[&TypedFact]) -> TractResult<TVec<TypedFact>> {
+        let b_shape: TVec<TDim> = if let Some(b) = &self.b_dequantized {
+            b.shape().iter().map(|d| d.to_dim()).collect()
+        } else {
+            inputs[1].shape.iter().collect()
+        };
+        let (_, _, _, c_shape) =
+            infer_shapes(inputs[0].shape.iter().collect(), b_shape, false, false, false)?;
+        Ok(tvec!(TypedFact::dt_shape(self.y_datum_type, &*c_shape)?))
+    }
+
+    fn declutter(
+        &self,
+        model: &TypedModel,
+        node: &TypedNode,
+    ) -> TractResult<Option<TypedModelPatch>> {
+        if self.b_dequantized.is_some() {
+            return Ok(None);
+        }
+        if let Some(b) = &model.outlet_fact(node.inputs[1])?.konst {
+            let op = QLinearMatMul {
+                b_dequantized: Some(self.b.dequantize(b)?.into_arc_tensor()),
+                ..self.clone()
+            };
+            return Ok(Some(TypedModelPatch::replace_single_op(
+                model,
+                node,
+                &node.inputs[..1],
+                op,
+            )?));
+        }
+        Ok(None)
+    }
+
+    typed_op_as_op!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ops::quant::DynamicQuantizeLinear;
+    use proptest::prelude::*;
+
+    fn quantize(values: &[f32], shape: &[usize]) -> (Tensor, LinearQuant) {
+        let (scale, zero_point) = DynamicQuantizeLinear::params(values);
+        let q = LinearQuant::new(scale, zero_point as i32);
+        let data = Tensor::from(ndarray::ArrayD::from_shape_vec(shape, values.to_vec()).unwrap());
+        (q.quantize(&data, u8::datum_type()).unwrap(), q)
+    }
+
+    fn strat() -> BoxedStrategy<((usize, usize, usize), Vec<f32>, Vec<f32>)> {
+        (1usize..5, 1usize..20, 1usize..6)
+            .prop_flat_map(|(m, k, n)| {
+                (
+                    Just((m, k, n)),
+                    proptest::collection::vec(-1.0f32..1.0, m * k),
+                    proptest::collection::vec(-1.0f32..1.0, k * n),
+                )
+            })
+            .boxed()
+    }
+
+    proptest! {
+        #[test]
+        fn float_reference(((m, k, n), a, b) in strat()) {
+            let (qa, a_q) = quantize(&a, &[m, k]);
+            let (qb, b_q) = quantize(&b, &[k, n]);
+            let reference: Vec<f32> = (0..m * n)
+                .map(|ix| (0..k).map(|l| a[ix / n * k + l] * b[l * n + ix % n]).sum())
+                .collect();
+            let (_, y_q) = quantize(&reference, &[m, n]);
+            let op = QLinearMatMul::new(a_q, b_q, y_q, u8::datum_type());
+            let plain = op.eval(tvec!(qa.clone().into(), qb.clone().into())).unwrap().remove(0);
+
+            let mut model = TypedModel::default();
+            let source = model
+                .add_source("a", TypedFact::dt_shape(u8::datum_type(), [m, k].as_ref()).unwrap())
+                .unwrap();
+            let b = model.add_const("b", qb).unwrap();
+            let y = model.wire_node("mm", op, &[source, b]).unwrap()[0];
+            model.set_output_outlets(&[y]).unwrap();
+            let model = model.declutter().unwrap();
+            prop_assert!(model.nodes().iter().all(|n| n.name != "b"));
+            let decluttered = SimplePlan::new(model).unwrap().run(tvec!(qa)).unwrap().remove(0);
+            prop_assert_eq!(&plain, &decluttered);
+
+            // rounding of both operands on each product, then of the output
+            let tolerance = k as f32 * (a_q.scale + b_q.scale) / 2.0 * 1.01 + y_q.scale;
+            let found = y_q.dequantize(&plain).unwrap();
+            for (f, r) in found.as_slice::<f32>().unwrap().iter().zip(reference.iter()) {
+                prop_assert!((f - r).abs() <= tolerance, "{} != {}", f, r);
+            }
+        }
+    }
+
+    #[test]
+    fn int8() -> TractResult<()> {
+        let a = tensor2(&[[-2i8, 4], [6, 0]]);
+        let b = tensor2(&[[1i8, -3], [5, 2]]);
+        let q = LinearQuant::new(0.5, 0);
+        let op = QLinearMatMul::new(q, q, LinearQuant::new(0.25, -10), i8::datum_type());
+        let y = op.eval(tvec!(a.into(), b.into()))?.remove(0);
+        // a.b in reals is [[4.5, 3.5], [1.5, -4.5]]
+        assert_eq!(*y, tensor2(&[[8i8, 4], [-4, -28]]));
+        Ok(())
+    }
+}
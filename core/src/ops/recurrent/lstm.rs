@@ -0,0 +1,378 @@
+use crate::internal::*;
+use ndarray::*;
+
+/// Long short-term memory cell, running over a sequence.
+///
+/// The input is laid out as [sequence, batch, input], the output are the
+/// hidden states for each step, as [sequence, batch, hidden]. Hidden and cell
+/// state are kept in the op state from one evaluation to the next, so a long
+/// sequence can be fed in chunks.
+///
+/// Weights follow the ONNX conventions, gates being stacked in input, output,
+/// forget, cell order.
+#[derive(Debug, Clone)]
+pub struct Lstm {
+    /// Input weights, [4 * hidden, input].
+    pub w: Arc<Tensor>,
+    /// Recurrence weights, [4 * hidden, hidden].
+    pub r: Arc<Tensor>,
+    /// Input biases followed by recurrence biases, [8 * hidden].
+    pub b: Option<Arc<Tensor>>,
+    /// Initial hidden state, [batch, hidden]. Zero if absent.
+    pub initial_h: Option<Arc<Tensor>>,
+    /// Initial cell state, [batch, hidden]. Zero if absent.
+    pub initial_c: Option<Arc<Tensor>>,
+    /// Length of each sequence in the batch, for packed sequences.
+    pub sequence_lens: Option<TVec<usize>>,
+}
+
+impl Lstm {
+    pub fn new(w: Arc<Tensor>, r: Arc<Tensor>, b: Option<Arc<Tensor>>) -> TractResult<Lstm> {
+        if w.rank() != 2 || w.shape()[0] % 4 != 0 {
+            bail!("LSTM input weights must be [4*hidden, input], got {:?}", w.shape())
+        }
+        let hidden = w.shape()[0] / 4;
+        if r.shape() != &[4 * hidden, hidden] {
+            bail!(
+                "LSTM recurrence weights must be [{}, {}], got {:?}",
+                4 * hidden,
+                hidden,
+                r.shape()
+            )
+        }
+        if let Some(b) = &b {
+            if b.shape() != &[8 * hidden] {
+                bail!("LSTM biases must be [{}], got {:?}", 8 * hidden, b.shape())
+            }
+        }
+        Ok(Lstm { w, r, b, initial_h: None, initial_c: None, sequence_lens: None })
+    }
+
+    /// Set the initial hidden and cell states, [batch, hidden].
+    pub fn with_initial_state(self, h: Arc<Tensor>, c: Arc<Tensor>) -> Lstm {
+        Lstm { initial_h: Some(h), initial_c: Some(c), ..self }
+    }
+
+    /// Set the length of each sequence of the batch.
+    ///
+    /// Past its length, a sequence state is left untouched and its output is
+    /// zero.
+    pub fn set_sequence_lens(&mut self, lens: &[usize]) {
+        self.sequence_lens = Some(lens.into());
+    }
+
+    pub fn hidden_size(&self) -> usize {
+        self.r.shape()[1]
+    }
+
+    fn initial(&self, init: &Option<Arc<Tensor>>, batch: usize) -> TractResult<Array2<f32>> {
+        match init {
+            Some(t) => Ok(t.to_array_view::<f32>()?.into_dimensionality()?.to_owned()),
+            None => Ok(Array2::zeros((batch, self.hidden_size()))),
+        }
+    }
+}
+
+impl Op for Lstm {
+    fn name(&self) -> Cow<str> {
+        "Lstm".into()
+    }
+
+    fn info(&self) -> TractResult<Vec<String>> {
+        Ok(vec![format!("hidden: {}", self.hidden_size())])
+    }
+
+    fn validation(&self) -> Validation {
+        Validation::Rounding
+    }
+
+    op_as_typed_op!();
+    not_a_pulsed_op!();
+}
+
+impl StatefullOp for Lstm {
+    fn state(
+        &self,
+        _session: &mut SessionState,
+        _node_id: usize,
+    ) -> TractResult<Option<Box<dyn OpState>>> {
+        Ok(Some(Box::new(LstmState::default())))
+    }
+}
+
+/// Hidden and cell state of an Lstm.
+#[derive(Clone, Debug, Default)]
+pub struct LstmState {
+    h: Option<Array2<f32>>,
+    c: Option<Array2<f32>>,
+    steps: usize,
+}
+
+impl LstmState {
+    /// Current hidden state, if a step has been run.
+    pub fn h(&self) -> Option<ArrayView2<f32>> {
+        self.h.as_ref().map(|h| h.view())
+    }
+
+    /// Current cell state, if a step has been run.
+    pub fn c(&self) -> Option<ArrayView2<f32>> {
+        self.c.as_ref().map(|c| c.view())
+    }
+
+    /// Forget the hidden and cell states.
+    pub fn reset(&mut self) {
+        self.h = None;
+        self.c = None;
+        self.steps = 0;
+    }
+
+    /// Run one step on x, [batch, input], returning the new hidden state.
+    pub fn step(&mut self, op: &Lstm, x: ArrayView2<f32>) -> TractResult<Array2<f32>> {
+        let batch = x.shape()[0];
+        let hidden = op.hidden_size();
+        if self.h.is_none() {
+            self.h = Some(op.initial(&op.initial_h, batch)?);
+            self.c = Some(op.initial(&op.initial_c, batch)?);
+        }
+        let steps = self.steps;
+        let h = self.h.as_mut().unwrap();
+        let c = self.c.as_mut().unwrap();
+        if h.shape() != &[batch, hidden] || c.shape() != &[batch, hidden] {
+            bail!("LSTM state is {:?}, input batch is {}", h.shape(), batch)
+        }
+        let w = op.w.to_array_view::<f32>()?.into_dimensionality::<Ix2>()?;
+        let r = op.r.to_array_view::<f32>()?.into_dimensionality::<Ix2>()?;
+        let mut gates = x.dot(&w.t()) + h.dot(&r.t());
+        if let Some(b) = &op.b {
+            let b = b.to_array_view::<f32>()?.into_dimensionality::<Ix1>()?;
+            gates += &b.slice(s![..4 * hidden]);
+            gates += &b.slice(s![4 * hidden..]);
+        }
+        let sigmoid = |x: f32| 1.0 / (1.0 + (-x).exp());
+        let gate = |ix: usize| gates.slice(s![.., ix * hidden..(ix + 1) * hidden]);
+        let i = gate(0).mapv(sigmoid);
+        let o = gate(1).mapv(sigmoid);
+        let f = gate(2).mapv(sigmoid);
+        let cell = gate(3).mapv(f32::tanh);
+        let new_c = f * &*c + i * cell;
+        let new_h = o * new_c.mapv(f32::tanh);
+        let mut output = Array2::zeros((batch, hidden));
+        for n in 0..batch {
+            let active = op.sequence_lens.as_ref().map(|l| steps < l[n]).unwrap_or(true);
+            if active {
+                c.row_mut(n).assign(&new_c.row(n));
+                h.row_mut(n).assign(&new_h.row(n));
+                output.row_mut(n).assign(&new_h.row(n));
+            }
+        }
+        self.steps += 1;
+        Ok(output)
+    }
+}
+
+impl OpState for LstmState {
+    fn eval(
+        &mut self,
+        _session: &mut SessionState,
+        op: &dyn Op,
+        mut inputs: TVec<Arc<Tensor>>,
+    ) -> TractResult<TVec<Arc<Tensor>>> {
+        let op = op.downcast_ref::<Lstm>().ok_or("Wrong Op type")?;
+        let x = args_1!(inputs);
+        let x = x.to_array_view::<f32>()?.into_dimensionality::<Ix3>()?;
+        let mut output = Array3::zeros((x.shape()[0], x.shape()[1], op.hidden_size()));
+        for t in 0..x.shape()[0] {
+            let h = self.step(op, x.index_axis(Axis(0), t))?;
+            output.index_axis_mut(Axis(0), t).assign(&h);
+        }
+        Ok(tvec!(output.into_arc_tensor()))
+    }
+
+    fn reset(&mut self) -> TractResult<()> {
+        LstmState::reset(self);
+        Ok(())
+    }
+}
+
+impl InferenceRulesOp for Lstm {
+    fn rules<'r, 'p: 'r, 's: 'r>(
+        &'s self,
+        s: &mut Solver<'r>,
+        inputs: &'p [TensorProxy],
+        outputs: &'p [TensorProxy],
+    ) -> InferenceResult {
+        check_input_arity(&inputs, 1)?;
+        check_output_arity(&outputs, 1)?;
+        s.equals(&inputs[0].datum_type, f32::datum_type())?;
+        s.equals(&outputs[0].datum_type, f32::datum_type())?;
+        s.equals(&inputs[0].rank, 3)?;
+        s.equals(&outputs[0].rank, 3)?;
+        s.equals(&inputs[0].shape[2], self.w.shape()[1].to_dim())?;
+        s.equals(&outputs[0].shape[0], &inputs[0].shape[0])?;
+        s.equals(&outputs[0].shape[1], &inputs[0].shape[1])?;
+        s.equals(&outputs[0].shape[2], self.hidden_size().to_dim())?;
+        Ok(())
+    }
+
+    inference_op_as_op!();
+    to_typed!();
+}
+
+impl TypedOp for Lstm {
+    fn output_facts(&self, inputs: &[&TypedFact]) -> TractResult<TVec<TypedFact>> {
+        if inputs[0].rank() != 3 || inputs[0].shape.dim(2) != self.w.shape()[1].to_dim() {
+            bail!(
+                "LSTM expects a [sequence, batch, {}] input, got {:?}",
+                self.w.shape()[1],
+                inputs[0]
+            )
+        }
+        let mut shape = inputs[0].shape.to_tvec();
+        shape[2] = self.hidden_size().to_dim();
+        Ok(tvec!(TypedFact::dt_shape(f32::datum_type(), &*shape)?))
+    }
+
+    typed_op_as_op!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Weights and reference values follow torch.nn.LSTM(2, 3) definitions:
+    // gates are stacked as input, forget, cell, output and both bias vectors
+    // are added.
+    fn torch_weights() -> (Array2<f32>, Array2<f32>, Array1<f32>, Array1<f32>) {
+        let w = arr2(&[
+            [0.4474, 0.3947],
+            [0.3421, 0.2895],
+            [0.2368, 0.1842],
+            [0.1316, 0.0789],
+            [0.0263, -0.0263],
+            [-0.0789, -0.1316],
+            [-0.1842, -0.2368],
+            [-0.2895, -0.3421],
+            [-0.3947, -0.4474],
+            [-0.5, 0.4474],
+            [0.3947, 0.3421],
+            [0.2895, 0.2368],
+        ]);
+        let r = arr2(&[
+            [0.3947, 0.3421, 0.2895],
+            [0.2368, 0.1842, 0.1316],
+            [0.0789, 0.0263, -0.0263],
+            [-0.0789, -0.1316, -0.1842],
+            [-0.2368, -0.2895, -0.3421],
+            [-0.3947, -0.4474, -0.5],
+            [0.4474, 0.3947, 0.3421],
+            [0.2895, 0.2368, 0.1842],
+            [0.1316, 0.0789, 0.0263],
+            [-0.0263, -0.0789, -0.1316],
+            [-0.1842, -0.2368, -0.2895],
+            [-0.3421, -0.3947, -0.4474],
+        ]);
+        let b_ih = arr1(&[
+            0.0455, 0.2273, 0.4091, -0.4091, -0.2273, -0.0455, 0.1364, 0.3182, -0.5, -0.3182,
+            -0.1364, 0.0455,
+        ]);
+        let b_hh = arr1(&[
+            -0.3182, 0.3182, -0.0455, -0.4091, 0.2273, -0.1364, -0.5, 0.1364, -0.2273, 0.4091,
+            0.0455, -0.3182,
+        ]);
+        (w, r, b_ih, b_hh)
+    }
+
+    // torch stacks gates as i, f, g, o, ONNX as i, o, f, c
+    fn reorder<D: RemoveAxis>(t: ArrayView<f32, D>) -> Tensor {
+        let h = t.shape()[0] / 4;
+        let blocks: Vec<_> = [0, 3, 1, 2]
+            .iter()
+            .map(|&g| t.slice_axis(Axis(0), (g * h..(g + 1) * h).into()))
+            .collect();
+        stack(Axis(0), &blocks).unwrap().into_tensor()
+    }
+
+    fn lstm() -> Lstm {
+        let (w, r, b_ih, b_hh) = torch_weights();
+        let b_ih = reorder(b_ih.view()).into_array::<f32>().unwrap();
+        let b_hh = reorder(b_hh.view()).into_array::<f32>().unwrap();
+        let b = b_ih.iter().chain(b_hh.iter()).cloned().collect::<Vec<f32>>();
+        Lstm::new(
+            reorder(w.view()).into_arc_tensor(),
+            reorder(r.view()).into_arc_tensor(),
+            Some(rctensor1(&b)),
+        )
+        .unwrap()
+    }
+
+    fn x() -> Array3<f32> {
+        arr3(&[[[0.5, -1.0]], [[1.5, 0.25]], [[-0.75, 2.0]]])
+    }
+
+    fn expected() -> Tensor {
+        tensor3(&[
+            [[-0.029647f32, 0.147189, -0.102231]],
+            [[-0.140310, 0.084656, -0.340287]],
+            [[-0.363117, 0.001220, -0.378908]],
+        ])
+    }
+
+    fn model(op: Lstm, steps: usize) -> TractResult<TypedModel> {
+        let mut model = TypedModel::default();
+        let fact = TypedFact::dt_shape(f32::datum_type(), [steps, 1, 2].as_ref())?;
+        let x = model.add_source("x", fact)?;
+        let y = model.wire_node("lstm", op, &[x])?[0];
+        model.set_output_outlets(&[y])?;
+        Ok(model)
+    }
+
+    #[test]
+    fn torch_reference() -> TractResult<()> {
+        let model = model(lstm(), 3)?;
+        let output = SimplePlan::new(&model)?.run(tvec!(x().into()))?;
+        output[0].close_enough(&expected(), true)
+    }
+
+    #[test]
+    fn state_is_kept_across_runs() -> TractResult<()> {
+        let model = model(lstm(), 1)?;
+        let plan = SimplePlan::new(&model)?;
+        let mut state = SimpleState::new(&plan)?;
+        let expected = expected().into_array::<f32>()?;
+        let steps = x();
+        for t in 0..3 {
+            let step = steps.slice_axis(Axis(0), (t..t + 1).into()).to_owned();
+            let output = state.run(tvec!(step.into()))?;
+            let expected = expected.slice_axis(Axis(0), (t..t + 1).into()).to_owned();
+            output[0].close_enough(&expected.into(), true)?;
+        }
+        let lstm_state = state.states[model.output_outlets()?[0].node].as_ref().unwrap();
+        assert!(format!("{:?}", lstm_state).contains("steps: 3"));
+
+        state.session_state.reset_state();
+        let output =
+            state.run(tvec!(steps.slice_axis(Axis(0), (0..1).into()).to_owned().into()))?;
+        output[0].close_enough(&expected.slice_axis(Axis(0), (0..1).into()).to_owned().into(), true)
+    }
+
+    #[test]
+    fn sequence_lens() -> TractResult<()> {
+        let mut op = lstm();
+        op.set_sequence_lens(&[3, 1]);
+        let x = stack(Axis(1), &[x().view(), x().view()])?;
+        let mut state = LstmState::default();
+        let mut outputs = vec![];
+        for t in 0..3 {
+            outputs.push(state.step(&op, x.index_axis(Axis(0), t))?);
+        }
+        let expected = expected().into_array::<f32>()?;
+        for t in 0..3 {
+            let full = Tensor::from(outputs[t].row(0).to_owned());
+            full.close_enough(&expected.slice(s![t, 0, ..]).to_owned().into(), true)?;
+        }
+        assert_eq!(outputs[1].row(1), Array1::<f32>::zeros(3));
+        assert_eq!(outputs[2].row(1), Array1::<f32>::zeros(3));
+        assert_eq!(state.h().unwrap().row(1), outputs[0].row(1));
+        Ok(())
+    }
+}
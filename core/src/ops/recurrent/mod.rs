@@ -0,0 +1,4 @@
+//! Recurrent cells, keeping their state across evaluations.
+pub mod lstm;
+
+pub use self::lstm::{Lstm, LstmState};
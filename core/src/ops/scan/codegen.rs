@@ -147,7 +147,7 @@ impl OpState for State {
                 .input_mapping
                 .iter()
                 .filter_map(|it| match it {
-                    InputMapping::Scan { axis, slot, chunk } => Some((*slot, *axis, *chunk)),
+                    InputMapping::Scan { axis, slot, chunk, .. } => Some((*slot, *axis, *chunk)),
                     _ => None,
                 })
                 .next()
@@ -189,12 +189,13 @@ impl OpState for State {
                 .map(|m| {
                     Ok(match m {
                         InputMapping::State { .. } => Some(self.hidden_state.pop().unwrap()),
-                        InputMapping::Scan { slot, axis, chunk } => {
+                        InputMapping::Scan { slot, axis, chunk, reverse } => {
+                            let chunk_ix = if *reverse { iters - 1 - i } else { i };
                             Some(dispatch_datum!(Self::slice_input_t(inputs[*slot].datum_type())(
                                 self,
                                 inputs[*slot].as_ref(),
                                 *axis,
-                                i,
+                                chunk_ix,
                                 *chunk
                             ))?)
                         }
@@ -234,6 +235,13 @@ impl OpState for State {
 
         Ok(outputs.into_iter().map(Arc::new).collect())
     }
+
+    /// The hidden state is initialized again on the next evaluation.
+    fn reset(&mut self) -> TractResult<()> {
+        self.position = 0;
+        self.hidden_state.clear();
+        self.model_state.reset_state()
+    }
 }
 
 impl TypedOp for Codegen {
@@ -246,7 +254,7 @@ impl TypedOp for Codegen {
                 .input_mapping
                 .iter()
                 .filter_map(|it| match it {
-                    InputMapping::Scan { axis, slot, chunk } => Some((*slot, *axis, *chunk)),
+                    InputMapping::Scan { axis, slot, chunk, .. } => Some((*slot, *axis, *chunk)),
                     _ => None,
                 })
                 .next()
@@ -277,7 +285,7 @@ impl TypedOp for Codegen {
                 .input_mapping
                 .iter()
                 .filter_map(|it| match it {
-                    InputMapping::Scan { axis, slot, chunk } => Some((*slot, *axis, *chunk)),
+                    InputMapping::Scan { axis, slot, chunk, .. } => Some((*slot, *axis, *chunk)),
                     _ => None,
                 })
                 .next()
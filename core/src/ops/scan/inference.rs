@@ -47,10 +47,11 @@ impl InferenceScan {
             .enumerate()
             .map(|(ix, im)| {
                 Ok(match im {
-                    InputMapping::Scan { axis, slot, chunk: _ } => InputMapping::Scan {
+                    InputMapping::Scan { axis, slot, chunk: _, reverse } => InputMapping::Scan {
                         axis: *axis,
                         slot: *slot,
                         chunk: typed_model.input_fact(ix)?.shape.dim(*axis),
+                        reverse: *reverse,
                     },
                     InputMapping::Full { slot } => InputMapping::Full { slot: *slot },
                     InputMapping::State { initializer } => {
@@ -177,7 +178,7 @@ impl InferenceScan {
             match i {
                 InputMapping::State { .. } => {}
                 InputMapping::Full { slot } => {
-                    if inputs[*slot].unify_with(self.body.input_fact_mut(ix)?)? {
+                    if inputs[*slot].unify_with_mut(self.body.input_fact_mut(ix)?)? {
                         changed = true;
                     }
                 }
@@ -8,11 +8,15 @@ mod typed;
 pub use inference::InferenceScan;
 pub use typed::TypedScan;
 
+/// How a body input is fed from the outer inputs.
+///
+/// A `Scan` input is cut in chunks along `axis`, one per iteration. With
+/// `reverse`, the chunks are fed from the last one to the first one.
 #[derive(Clone, new)]
 pub enum InputMapping<C: Clone> {
     Full { slot: usize },
     State { initializer: StateInitializer },
-    Scan { slot: usize, axis: usize, chunk: C },
+    Scan { slot: usize, axis: usize, chunk: C, reverse: bool },
 }
 
 impl<C: Clone> InputMapping<C> {
@@ -25,7 +29,7 @@ impl<C: Clone> InputMapping<C> {
 
     pub fn as_scan(&self) -> Option<(usize, usize, C)> {
         match self {
-            InputMapping::Scan { slot, axis, chunk } => Some((*slot, *axis, chunk.clone())),
+            InputMapping::Scan { slot, axis, chunk, .. } => Some((*slot, *axis, chunk.clone())),
             _ => None,
         }
     }
@@ -57,8 +61,12 @@ impl<C: Clone + fmt::Debug> fmt::Debug for InputMapping<C> {
             InputMapping::State { initializer } => {
                 write!(fmt, "State initialized by {:?}", initializer)
             }
-            InputMapping::Scan { slot, axis, chunk } => {
-                write!(fmt, "Scan inlet {}, axis: {}, chunk: {:?}.", slot, axis, chunk)
+            InputMapping::Scan { slot, axis, chunk, reverse } => {
+                write!(fmt, "Scan inlet {}, axis: {}, chunk: {:?}.", slot, axis, chunk)?;
+                if *reverse {
+                    write!(fmt, " Reversed.")?;
+                }
+                Ok(())
             }
         }
     }
@@ -111,3 +119,86 @@ impl fmt::Debug for StateInitializer {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ops::math;
+    use ndarray::*;
+
+    fn fact(shape: &[usize]) -> TypedFact {
+        TypedFact::dt_shape(f32::datum_type(), shape).unwrap()
+    }
+
+    /// Running average of the rows of a [5, 3] input.
+    fn running_average(reverse: bool) -> TractResult<TypedModel> {
+        let mut body = TypedModel::default();
+        let sum = body.add_source("sum", fact(&[1, 3]))?;
+        let count = body.add_source("count", fact(&[]))?;
+        let x = body.add_source("x", fact(&[1, 3]))?;
+        let sum = body.wire_node("new_sum", math::add::bin(), &[sum, x])?[0];
+        let count = body.wire_node("new_count", math::add::unary(rctensor0(1f32)), &[count])?[0];
+        let avg = body.wire_node("avg", math::div::bin(), &[sum, count])?[0];
+        body.set_output_outlets(&[sum, count, avg])?;
+
+        let state = |slot| InputMapping::State { initializer: StateInitializer::FromInput(slot) };
+        let input_mapping = vec![
+            state(0),
+            state(1),
+            InputMapping::Scan { slot: 2, axis: 0, chunk: 1.to_dim(), reverse },
+        ];
+        let last_value = |slot| OutputMapping::new(None, 0, 1.to_dim(), None, Some(slot), true);
+        let output_mapping = vec![
+            last_value(0),
+            last_value(1),
+            OutputMapping::new(Some(2), 0, 1.to_dim(), None, None, false),
+        ];
+        let op = TypedScan::new(body, input_mapping, output_mapping, None)?;
+
+        let mut model = TypedModel::default();
+        let sum = model.add_const("sum", rctensor2(&[[0f32; 3]]))?;
+        let count = model.add_const("count", rctensor0(0f32))?;
+        let x = model.add_source("x", fact(&[5, 3]))?;
+        let scanned = model.wire_node("scan", op, &[sum, count, x])?;
+        model.set_output_outlets(&scanned)?;
+        Ok(model)
+    }
+
+    fn input() -> Array2<f32> {
+        Array2::from_shape_fn((5, 3), |(i, j)| (i * 3 + j) as f32 * 0.5 - 2.0)
+    }
+
+    fn expected(rows: &[ArrayView1<f32>]) -> Array2<f32> {
+        let mut sum = Array1::<f32>::zeros(3);
+        let mut averages = Array2::<f32>::zeros((rows.len(), 3));
+        for (i, row) in rows.iter().enumerate() {
+            sum = sum + row;
+            averages.row_mut(i).assign(&(&sum / (i + 1) as f32));
+        }
+        averages
+    }
+
+    #[test]
+    fn running_average_forward_and_reversed() -> TractResult<()> {
+        let x = input();
+        for &reverse in &[false, true] {
+            let mut rows: Vec<_> = x.outer_iter().collect();
+            if reverse {
+                rows.reverse();
+            }
+            let model = running_average(reverse)?;
+            assert_eq!(
+                model.outlet_fact(model.output_outlets()?[2])?.shape.to_tvec(),
+                tvec!(5.to_dim(), 3.to_dim())
+            );
+            for model in &[model.clone(), model.into_optimized()?] {
+                let result = SimplePlan::new(model)?.run(tvec!(x.clone().into_tensor()))?;
+                let sum = x.sum_axis(Axis(0)).insert_axis(Axis(0));
+                result[0].close_enough(&sum.into_tensor(), true)?;
+                assert_eq!(result[1], rctensor0(5f32));
+                result[2].close_enough(&expected(&rows).into_tensor(), true)?;
+            }
+        }
+        Ok(())
+    }
+}
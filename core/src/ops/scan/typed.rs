@@ -22,10 +22,11 @@ impl TypedScan {
             .iter()
             .map(|im| {
                 Ok(match im {
-                    InputMapping::Scan { axis, slot, chunk } => InputMapping::Scan {
+                    InputMapping::Scan { axis, slot, chunk, reverse } => InputMapping::Scan {
                         axis: *axis,
                         slot: *slot,
                         chunk: chunk.to_integer()? as usize,
+                        reverse: *reverse,
                     },
                     InputMapping::Full { slot } => InputMapping::Full { slot: *slot },
                     InputMapping::State { initializer } => {
@@ -95,10 +96,11 @@ impl TypedScan {
                 InputMapping::Full { slot } => {
                     InputMapping::Full { slot: *slot - (*slot > discarded) as usize }
                 }
-                InputMapping::Scan { slot, axis, chunk } => InputMapping::Scan {
+                InputMapping::Scan { slot, axis, chunk, reverse } => InputMapping::Scan {
                     slot: *slot - (*slot > discarded) as usize,
                     axis: *axis,
                     chunk: chunk.clone(),
+                    reverse: *reverse,
                 },
                 InputMapping::State { initializer } => {
                     let initializer = match initializer {
@@ -258,7 +260,7 @@ impl TypedScan {
         node: &TypedNode,
     ) -> TractResult<Option<TypedModelPatch>> {
         for (model_input, input) in self.input_mapping.iter().enumerate() {
-            if let Some((slot, axis, chunk)) = input.as_scan() {
+            if let InputMapping::Scan { slot, axis, chunk, reverse } = input.clone() {
                 let scan_source = self.body.input_outlets()?[model_input];
                 let scan_source_node = self.body.node(scan_source.node);
                 for successor in &scan_source_node.outputs[0].successors {
@@ -308,6 +310,7 @@ impl TypedScan {
                             axis: axis_after,
                             chunk: chunk.clone(),
                             slot: node.inputs.len(),
+                            reverse,
                         });
 
                         let new_op = Self {
@@ -439,7 +442,9 @@ impl TypedOp for TypedScan {
                 .input_mapping
                 .iter()
                 .filter_map(|it| match it {
-                    InputMapping::Scan { axis, slot, chunk } => Some((*slot, *axis, chunk.clone())),
+                    InputMapping::Scan { axis, slot, chunk, .. } => {
+                        Some((*slot, *axis, chunk.clone()))
+                    }
                     _ => None,
                 })
                 .next()
@@ -529,9 +534,10 @@ impl TypedOp for TypedScan {
                 if let Some(removed_axis) = tracking.outlets.get(&self.body.input_outlets()?[ix]) {
                     Ok(match m {
                         InputMapping::Full { .. } => m.clone(),
-                        InputMapping::Scan { axis, chunk, slot } => {
+                        InputMapping::Scan { axis, chunk, slot, reverse } => {
                             let axis = *axis - (*axis > *removed_axis) as usize;
-                            InputMapping::Scan { axis, slot: *slot, chunk: chunk.clone() }
+                            let (slot, chunk, reverse) = (*slot, chunk.clone(), *reverse);
+                            InputMapping::Scan { axis, slot, chunk, reverse }
                         }
                         InputMapping::State { initializer } => match initializer {
                             StateInitializer::FromInput(fi) => InputMapping::State {
@@ -611,6 +617,12 @@ impl TypedOp for TypedScan {
                 bail!("Scan pulsification limited to scanning axis");
             }
         }
+        if self.input_mapping.iter().any(|m| match m {
+            InputMapping::Scan { reverse, .. } => *reverse,
+            _ => false,
+        }) {
+            bail!("Can not pulsify a reversed Scan input");
+        }
 
         let pulse_inputs = node.inputs.iter().map(|i| mapping[i]).collect::<TVec<_>>();
 
@@ -0,0 +1,147 @@
+use num_complex::Complex;
+
+type C32 = Complex<f32>;
+
+/// Forward discrete Fourier transform of a given length.
+///
+/// Powers of two use an iterative radix-2 Cooley-Tukey, other lengths go
+/// through Bluestein's algorithm on a power of two. Twiddles are computed in
+/// f64 once, when the plan is built.
+#[derive(Clone, Debug)]
+pub(crate) struct FftPlan {
+    len: usize,
+    twiddles: Vec<C32>,
+    bluestein: Option<Bluestein>,
+}
+
+#[derive(Clone, Debug)]
+struct Bluestein {
+    /// exp(-i.pi.n^2/len), for n in 0..len
+    chirp: Vec<C32>,
+    /// FFT of the conjugated chirp, wrapped around, on the padded length
+    chirp_fft: Vec<C32>,
+    inner: Box<FftPlan>,
+}
+
+fn twiddles(len: usize) -> Vec<C32> {
+    (0..len / 2)
+        .map(|k| {
+            let angle = -2.0 * std::f64::consts::PI * k as f64 / len as f64;
+            Complex::new(angle.cos() as f32, angle.sin() as f32)
+        })
+        .collect()
+}
+
+impl FftPlan {
+    pub fn new(len: usize) -> FftPlan {
+        if len.is_power_of_two() || len == 0 {
+            return FftPlan { len, twiddles: twiddles(len), bluestein: None };
+        }
+        let padded = (2 * len - 1).next_power_of_two();
+        let inner = FftPlan::new(padded);
+        let chirp: Vec<C32> = (0..len)
+            .map(|n| {
+                // n^2 mod 2.len keeps the angle small
+                let n2 = (n as u64 * n as u64) % (2 * len as u64);
+                let angle = -std::f64::consts::PI * n2 as f64 / len as f64;
+                Complex::new(angle.cos() as f32, angle.sin() as f32)
+            })
+            .collect();
+        let mut chirp_fft = vec![C32::default(); padded];
+        chirp_fft[0] = chirp[0].conj();
+        for n in 1..len {
+            chirp_fft[n] = chirp[n].conj();
+            chirp_fft[padded - n] = chirp[n].conj();
+        }
+        inner.process(&mut chirp_fft);
+        FftPlan {
+            len,
+            twiddles: vec![],
+            bluestein: Some(Bluestein { chirp, chirp_fft, inner: Box::new(inner) }),
+        }
+    }
+
+    /// Transform `data` in place. Its length must be the plan length.
+    pub fn process(&self, data: &mut [C32]) {
+        debug_assert_eq!(data.len(), self.len);
+        if let Some(b) = &self.bluestein {
+            let padded = b.chirp_fft.len();
+            let mut work = vec![C32::default(); padded];
+            for n in 0..self.len {
+                work[n] = data[n] * b.chirp[n];
+            }
+            b.inner.process(&mut work);
+            work.iter_mut().zip(b.chirp_fft.iter()).for_each(|(w, c)| *w *= c);
+            // inverse FFT through conjugation
+            work.iter_mut().for_each(|w| *w = w.conj());
+            b.inner.process(&mut work);
+            let scale = 1.0 / padded as f32;
+            for k in 0..self.len {
+                data[k] = work[k].conj() * scale * b.chirp[k];
+            }
+        } else {
+            self.radix2(data)
+        }
+    }
+
+    fn radix2(&self, data: &mut [C32]) {
+        let len = data.len();
+        if len < 2 {
+            return;
+        }
+        let bits = len.trailing_zeros();
+        for i in 0..len {
+            let j = i.reverse_bits() >> (std::mem::size_of::<usize>() as u32 * 8 - bits);
+            if i < j {
+                data.swap(i, j);
+            }
+        }
+        let mut size = 2;
+        while size <= len {
+            let half = size / 2;
+            let stride = len / size;
+            for start in (0..len).step_by(size) {
+                for k in 0..half {
+                    let t = data[start + k + half] * self.twiddles[k * stride];
+                    let u = data[start + k];
+                    data[start + k] = u + t;
+                    data[start + k + half] = u - t;
+                }
+            }
+            size *= 2;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::collection::vec;
+    use proptest::prelude::*;
+
+    fn naive_dft(data: &[C32]) -> Vec<C32> {
+        let len = data.len();
+        (0..len)
+            .map(|k| {
+                data.iter().enumerate().fold(Complex::new(0f64, 0f64), |acc, (n, x)| {
+                    let angle = -2.0 * std::f64::consts::PI * ((k * n) % len) as f64 / len as f64;
+                    acc + Complex::new(x.re as f64, x.im as f64)
+                        * Complex::new(angle.cos(), angle.sin())
+                })
+            })
+            .map(|c| Complex::new(c.re as f32, c.im as f32))
+            .collect()
+    }
+
+    proptest! {
+        #[test]
+        fn matches_naive_dft(data in vec((-1f32..1f32, -1f32..1f32), 1..70)) {
+            let mut data: Vec<C32> = data.into_iter().map(|(re, im)| Complex::new(re, im)).collect();
+            let expected = naive_dft(&data);
+            FftPlan::new(data.len()).process(&mut data);
+            for (found, expected) in data.iter().zip(expected.iter()) {
+                prop_assert!((found - expected).norm() < 1e-4, "{:?} {:?}", data, expected);
+            }
+        }
+    }
+}
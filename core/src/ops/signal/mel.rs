@@ -0,0 +1,209 @@
+use crate::internal::*;
+use ndarray::*;
+
+/// Mel filterbank, as in ONNX opset 17.
+///
+/// Inputs are scalars: `num_mel_bins`, `dft_length`, `sample_rate`,
+/// `lower_edge_hertz` and `upper_edge_hertz`. The output is a
+/// `[dft_length / 2 + 1, num_mel_bins]` matrix of triangular filters, evenly
+/// spaced on the HTK mel scale, to be multiplied with a onesided spectrogram.
+///
+/// The matrix only depends on the inputs values, which are constant in
+/// practice: the op is then evaluated when the model is built and replaced by
+/// a Const by constant propagation.
+#[derive(Debug, Clone, new)]
+pub struct MelWeightMatrix {
+    pub datum_type: DatumType,
+}
+
+/// HTK mel scale.
+pub fn hz_to_mel(hz: f64) -> f64 {
+    2595.0 * (1.0 + hz / 700.0).log10()
+}
+
+/// Inverse of `hz_to_mel`.
+pub fn mel_to_hz(mel: f64) -> f64 {
+    700.0 * (10f64.powf(mel / 2595.0) - 1.0)
+}
+
+impl MelWeightMatrix {
+    fn shape(num_mel_bins: &Tensor, dft_length: &Tensor) -> TractResult<(usize, usize)> {
+        let num_mel_bins = num_mel_bins.cast_to_scalar::<i64>()?;
+        let dft_length = dft_length.cast_to_scalar::<i64>()?;
+        if num_mel_bins <= 0 || dft_length <= 0 {
+            bail!(
+                "MelWeightMatrix needs positive num_mel_bins and dft_length, got {} and {}",
+                num_mel_bins,
+                dft_length
+            )
+        }
+        Ok((dft_length as usize / 2 + 1, num_mel_bins as usize))
+    }
+
+    fn make(&self, inputs: &[Arc<Tensor>]) -> TractResult<Arc<Tensor>> {
+        let (spectrogram_bins, num_mel_bins) = Self::shape(&inputs[0], &inputs[1])?;
+        let dft_length = inputs[1].cast_to_scalar::<i64>()? as f64;
+        let sample_rate = inputs[2].cast_to_scalar::<f64>()?;
+        let lower = inputs[3].cast_to_scalar::<f64>()?;
+        let upper = inputs[4].cast_to_scalar::<f64>()?;
+        if sample_rate <= 0.0 || lower < 0.0 || upper <= lower || upper > sample_rate / 2.0 {
+            bail!(
+                "MelWeightMatrix edges must satisfy 0 <= lower < upper <= sample_rate / 2, \
+                 got {}, {} and {}",
+                lower,
+                upper,
+                sample_rate
+            )
+        }
+        // num_mel_bins + 2 edges, leftmost and rightmost are only filter
+        // boundaries. The mel step divides by the edge count, as the ONNX
+        // reference does.
+        let low_mel = hz_to_mel(lower);
+        let mel_step = (hz_to_mel(upper) - low_mel) / (num_mel_bins + 2) as f64;
+        let edges: Vec<usize> = (0..num_mel_bins + 2)
+            .map(|i| {
+                let hz = mel_to_hz(low_mel + mel_step * i as f64);
+                (((dft_length + 1.0) * hz) / sample_rate).floor() as usize
+            })
+            .collect();
+        let mut matrix = Array2::<f64>::zeros((spectrogram_bins, num_mel_bins));
+        for (i, edge) in edges.windows(3).enumerate() {
+            let (left, center, right) = (edge[0], edge[1], edge[2]);
+            if center == left {
+                matrix[(center, i)] = 1.0;
+            } else {
+                for j in left..=center {
+                    matrix[(j, i)] = (j - left) as f64 / (center - left) as f64;
+                }
+            }
+            for j in center..right {
+                matrix[(j, i)] = (right - j) as f64 / (right - center) as f64;
+            }
+        }
+        Ok(matrix.into_tensor().cast_to_dt(self.datum_type)?.into_owned().into_arc_tensor())
+    }
+}
+
+impl Op for MelWeightMatrix {
+    fn name(&self) -> Cow<str> {
+        "MelWeightMatrix".into()
+    }
+
+    fn info(&self) -> TractResult<Vec<String>> {
+        Ok(vec![format!("output datum type: {:?}", self.datum_type)])
+    }
+
+    op_as_typed_op!();
+    not_a_pulsed_op!();
+}
+
+impl StatelessOp for MelWeightMatrix {
+    fn eval(&self, inputs: TVec<Arc<Tensor>>) -> TractResult<TVec<Arc<Tensor>>> {
+        Ok(tvec!(self.make(&inputs)?))
+    }
+}
+
+impl InferenceRulesOp for MelWeightMatrix {
+    fn rules<'r, 'p: 'r, 's: 'r>(
+        &'s self,
+        s: &mut Solver<'r>,
+        inputs: &'p [TensorProxy],
+        outputs: &'p [TensorProxy],
+    ) -> InferenceResult {
+        check_input_arity(&inputs, 5)?;
+        check_output_arity(&outputs, 1)?;
+        for input in inputs {
+            s.equals(&input.rank, 0)?;
+        }
+        s.equals(&outputs[0].datum_type, self.datum_type)?;
+        s.equals(&outputs[0].rank, 2)?;
+        s.given_2(&inputs[0].value, &inputs[1].value, move |s, num_mel_bins, dft_length| {
+            let (rows, cols) = Self::shape(&num_mel_bins, &dft_length)?;
+            s.equals(&outputs[0].shape[0], rows.to_dim())?;
+            s.equals(&outputs[0].shape[1], cols.to_dim())
+        })
+    }
+
+    inference_op_as_op!();
+    to_typed!();
+}
+
+impl TypedOp for MelWeightMatrix {
+    fn output_facts(&self, inputs: &[&TypedFact]) -> TractResult<TVec<TypedFact>> {
+        if let (Some(num_mel_bins), Some(dft_length)) = (&inputs[0].konst, &inputs[1].konst) {
+            let (rows, cols) = Self::shape(num_mel_bins, dft_length)?;
+            Ok(tvec!(TypedFact::dt_shape(self.datum_type, [rows, cols].as_ref())?))
+        } else {
+            bail!("MelWeightMatrix num_mel_bins and dft_length must be constants")
+        }
+    }
+
+    typed_op_as_op!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ops::konst::Const;
+    use crate::ops::matmul::MatMul;
+
+    fn inputs(mel: i64, dft: i64, rate: i64, lower: f32, upper: f32) -> [Arc<Tensor>; 5] {
+        [rctensor0(mel), rctensor0(dft), rctensor0(rate), rctensor0(lower), rctensor0(upper)]
+    }
+
+    #[test]
+    fn matches_onnx_reference() -> TractResult<()> {
+        // edges fall on spectrogram bins [0, 1, 2, 4, 8]
+        let found =
+            MelWeightMatrix::new(f32::datum_type()).make(&inputs(3, 32, 16000, 100.0, 7000.0))?;
+        let mut expected = Array2::<f32>::zeros((17, 3));
+        expected[(1, 0)] = 1.0;
+        expected[(2, 1)] = 1.0;
+        expected[(3, 1)] = 0.5;
+        expected[(3, 2)] = 0.5;
+        for (j, w) in [1.0, 0.75, 0.5, 0.25].iter().enumerate() {
+            expected[(4 + j, 2)] = *w;
+        }
+        found.close_enough(&expected.into_tensor(), false)
+    }
+
+    #[test]
+    fn mel_scale_round_trip() {
+        assert!((hz_to_mel(1000.0) - 1000.0).abs() < 0.1);
+        for hz in &[0.0, 440.0, 8000.0] {
+            assert!((mel_to_hz(hz_to_mel(*hz)) - hz).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn invalid_edges() {
+        let op = MelWeightMatrix::new(f32::datum_type());
+        assert!(op.make(&inputs(3, 32, 16000, 100.0, 9000.0)).is_err());
+        assert!(op.make(&inputs(3, 32, 16000, 500.0, 100.0)).is_err());
+    }
+
+    #[test]
+    fn folded_by_const_propagation() -> TractResult<()> {
+        let mut model = TypedModel::default();
+        let spectrogram = model.add_source(
+            "spectrogram",
+            TypedFact::dt_shape(f32::datum_type(), [10, 257].as_ref())?,
+        )?;
+        let params: TVec<OutletId> = inputs(40, 512, 16000, 20.0, 8000.0)
+            .iter()
+            .enumerate()
+            .map(|(ix, t)| model.add_const(format!("param.{}", ix), t.clone()))
+            .collect::<TractResult<_>>()?;
+        let mel = model.wire_node("mel", MelWeightMatrix::new(f32::datum_type()), &*params)?;
+        let konst = model.outlet_fact(mel[0])?.konst.clone().unwrap();
+        assert_eq!(konst.shape(), &[257, 40]);
+        let o = model.wire_node("matmul", MatMul::default(), &[spectrogram, mel[0]])?;
+        model.set_output_outlets(&o)?;
+        assert!(model.propagate_constants_with_strategy(ConstPropagationStrategy::AlwaysCopy)?);
+        let model = crate::model::compact::compact(&model)?;
+        assert!(model.nodes().iter().all(|n| !n.op_is::<MelWeightMatrix>()));
+        assert_eq!(model.nodes().iter().filter(|n| n.op_is::<Const>()).count(), 1);
+        assert_eq!(model.nodes().len(), 3);
+        Ok(())
+    }
+}
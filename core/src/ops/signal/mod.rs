@@ -0,0 +1,8 @@
+//! Signal processing, for audio feature extraction.
+
+mod fft;
+mod mel;
+mod stft;
+
+pub use self::mel::{hz_to_mel, mel_to_hz, MelWeightMatrix};
+pub use self::stft::STFT;
@@ -0,0 +1,297 @@
+use super::fft::FftPlan;
+use crate::internal::*;
+use ndarray::*;
+use num_complex::Complex;
+
+/// Short-time Fourier transform, as in ONNX opset 17.
+///
+/// Inputs are the signal, `[batch, length, 1]` for a real signal or
+/// `[batch, length, 2]` for a complex one, and an optional window to apply
+/// to each frame. The frame length is taken from the window when it is not
+/// given.
+///
+/// The output is `[batch, frames, bins, 2]`, the last axis holding the real
+/// and imaginary parts. Frames start every `frame_step` samples, and only
+/// the frames fitting in the signal are computed. With `onesided`, the
+/// redundant negative frequencies of a real signal are dropped, leaving
+/// `frame_length / 2 + 1` bins.
+#[derive(Debug, Clone, new)]
+pub struct STFT {
+    pub frame_step: usize,
+    pub frame_length: Option<usize>,
+    pub onesided: bool,
+}
+
+impl STFT {
+    fn resolve_frame_length(&self, window: Option<usize>) -> TractResult<usize> {
+        match (self.frame_length, window) {
+            (Some(frame), Some(window)) if frame != window => {
+                bail!("STFT frame length is {} but the window has {} samples", frame, window)
+            }
+            (Some(frame), _) | (None, Some(frame)) => Ok(frame),
+            (None, None) => bail!("STFT needs a frame length or a window"),
+        }
+    }
+
+    fn bins(&self, frame_length: usize) -> usize {
+        if self.onesided {
+            frame_length / 2 + 1
+        } else {
+            frame_length
+        }
+    }
+
+    fn frames(&self, length: usize, frame_length: usize) -> TractResult<usize> {
+        if self.frame_step == 0 {
+            bail!("STFT frame step must be positive")
+        }
+        if length < frame_length {
+            bail!("STFT signal of {} samples is shorter than a frame ({})", length, frame_length)
+        }
+        Ok((length - frame_length) / self.frame_step + 1)
+    }
+
+    fn output_shape(&self, signal: &[TDim], window: Option<&TDim>) -> TractResult<TVec<TDim>> {
+        if signal.len() != 3 {
+            bail!("STFT signal must be [batch, length, 1 or 2], got {:?}", signal)
+        }
+        let window = window.map(|w| w.to_integer().map(|w| w as usize)).transpose()?;
+        let frame_length = self.resolve_frame_length(window)?;
+        let frames = if let Ok(length) = signal[1].to_integer() {
+            self.frames(length as usize, frame_length)?.to_dim()
+        } else {
+            (signal[1].clone() - frame_length.to_dim()) / self.frame_step.to_dim() + 1.to_dim()
+        };
+        Ok(tvec!(signal[0].clone(), frames, self.bins(frame_length).to_dim(), 2.to_dim()))
+    }
+
+    fn eval_f32(
+        &self,
+        signal: ArrayView3<f32>,
+        window: Option<ArrayView1<f32>>,
+    ) -> TractResult<Array4<f32>> {
+        let frame_length = self.resolve_frame_length(window.as_ref().map(|w| w.len()))?;
+        let (batch, length, parts) = signal.dim();
+        if parts != 1 && parts != 2 {
+            bail!("STFT signal last axis must be 1 (real) or 2 (complex), got {}", parts)
+        }
+        if parts == 2 && self.onesided {
+            bail!("STFT can only be onesided on a real signal")
+        }
+        let frames = self.frames(length, frame_length)?;
+        let bins = self.bins(frame_length);
+        let plan = FftPlan::new(frame_length);
+        let mut buffer = vec![Complex::<f32>::default(); frame_length];
+        let mut output = Array4::<f32>::zeros((batch, frames, bins, 2));
+        for b in 0..batch {
+            for f in 0..frames {
+                let frame = signal.slice(s![
+                    b,
+                    f * self.frame_step..f * self.frame_step + frame_length,
+                    ..
+                ]);
+                for (n, sample) in frame.outer_iter().enumerate() {
+                    let im = if parts == 2 { sample[1] } else { 0.0 };
+                    let w = window.as_ref().map(|w| w[n]).unwrap_or(1.0);
+                    buffer[n] = Complex::new(sample[0] * w, im * w);
+                }
+                plan.process(&mut buffer);
+                for (k, c) in buffer.iter().take(bins).enumerate() {
+                    output[(b, f, k, 0)] = c.re;
+                    output[(b, f, k, 1)] = c.im;
+                }
+            }
+        }
+        Ok(output)
+    }
+}
+
+impl Op for STFT {
+    fn name(&self) -> Cow<str> {
+        "STFT".into()
+    }
+
+    fn info(&self) -> TractResult<Vec<String>> {
+        Ok(vec![format!(
+            "frame_step: {}, frame_length: {:?}, onesided: {}",
+            self.frame_step, self.frame_length, self.onesided
+        )])
+    }
+
+    fn validation(&self) -> Validation {
+        Validation::Rounding
+    }
+
+    op_as_typed_op!();
+    not_a_pulsed_op!();
+}
+
+impl StatelessOp for STFT {
+    fn eval(&self, inputs: TVec<Arc<Tensor>>) -> TractResult<TVec<Arc<Tensor>>> {
+        let signal = inputs[0].to_array_view::<f32>()?.into_dimensionality()?;
+        let window = if let Some(w) = inputs.get(1) {
+            Some(w.to_array_view::<f32>()?.into_dimensionality()?)
+        } else {
+            None
+        };
+        Ok(tvec!(self.eval_f32(signal, window)?.into_arc_tensor()))
+    }
+}
+
+impl InferenceRulesOp for STFT {
+    fn rules<'r, 'p: 'r, 's: 'r>(
+        &'s self,
+        s: &mut Solver<'r>,
+        inputs: &'p [TensorProxy],
+        outputs: &'p [TensorProxy],
+    ) -> InferenceResult {
+        if inputs.is_empty() || inputs.len() > 2 {
+            bail!("STFT expects a signal and an optional window, got {} inputs", inputs.len())
+        }
+        check_output_arity(&outputs, 1)?;
+        for input in inputs {
+            s.equals(&input.datum_type, f32::datum_type())?;
+        }
+        s.equals(&outputs[0].datum_type, f32::datum_type())?;
+        s.equals(&inputs[0].rank, 3)?;
+        s.equals(&outputs[0].rank, 4)?;
+        s.equals(&outputs[0].shape[0], &inputs[0].shape[0])?;
+        s.equals(&outputs[0].shape[3], 2.to_dim())?;
+        if inputs.len() == 2 {
+            s.equals(&inputs[1].rank, 1)?;
+            s.given_2(&inputs[0].shape, &inputs[1].shape, move |s, signal, window| {
+                s.equals(&outputs[0].shape, self.output_shape(&signal, Some(&window[0]))?)
+            })
+        } else {
+            s.given(&inputs[0].shape, move |s, signal| {
+                s.equals(&outputs[0].shape, self.output_shape(&signal, None)?)
+            })
+        }
+    }
+
+    inference_op_as_op!();
+    to_typed!();
+}
+
+impl TypedOp for STFT {
+    fn output_facts(&self, inputs: &[&TypedFact]) -> TractResult<TVec<TypedFact>> {
+        if inputs.iter().any(|f| f.datum_type != f32::datum_type()) {
+            bail!("STFT only supports f32")
+        }
+        let signal: TVec<TDim> = inputs[0].shape.iter().collect();
+        let window = inputs.get(1).map(|w| w.shape.dim(0));
+        let shape = self.output_shape(&signal, window.as_ref())?;
+        Ok(tvec!(TypedFact::dt_shape(f32::datum_type(), &*shape)?))
+    }
+
+    fn cost(&self, inputs: &[&TypedFact]) -> TractResult<TVec<(Cost, TDim)>> {
+        let signal: TVec<TDim> = inputs[0].shape.iter().collect();
+        let window = inputs.get(1).map(|w| w.shape.dim(0));
+        let shape = self.output_shape(&signal, window.as_ref())?;
+        let frame_length = self.resolve_frame_length(
+            window.map(|w| w.to_integer().map(|w| w as usize)).transpose()?,
+        )?;
+        let log = (frame_length.next_power_of_two().trailing_zeros() as usize).max(1);
+        // complex multiply-adds: a radix-2 pass, or three for Bluestein
+        let per_frame = frame_length * log * if frame_length.is_power_of_two() { 4 } else { 24 };
+        Ok(tvec!((Cost::FMA(f32::datum_type()), shape[0].clone() * &shape[1] * per_frame.to_dim())))
+    }
+
+    typed_op_as_op!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f32::consts::PI;
+
+    fn check(found: ArrayView2<f32>, expected: &[(f32, f32)]) {
+        assert_eq!(found.shape(), &[expected.len(), 2]);
+        for (k, &(re, im)) in expected.iter().enumerate() {
+            assert!(
+                (found[(k, 0)] - re).abs() < 1e-3 && (found[(k, 1)] - im).abs() < 1e-3,
+                "bin {}: found {:?}, expected {:?}",
+                k,
+                found.row(k),
+                (re, im)
+            );
+        }
+    }
+
+    #[test]
+    fn sinusoid_matches_numpy_rfft() -> TractResult<()> {
+        // np.fft.rfft(np.cos(2 * np.pi * 3 * n / 16) + 0.5 * np.sin(2 * np.pi * 5 * n / 16))
+        // for each frame: 8 at bin 3, -4j at bin 5, zero elsewhere
+        let signal = Array3::from_shape_fn((1, 40, 1), |(_, n, _)| {
+            let n = n as f32;
+            (2.0 * PI * 3.0 * n / 16.0).cos() + 0.5 * (2.0 * PI * 5.0 * n / 16.0).sin()
+        });
+        let stft = STFT::new(8, Some(16), true);
+        let found = stft.eval_f32(signal.view(), None)?;
+        assert_eq!(found.shape(), &[1, 4, 9, 2]);
+        let mut expected = vec![(0f32, 0f32); 9];
+        expected[3] = (8.0, 0.0);
+        expected[5] = (0.0, -4.0);
+        // consecutive frames are 8 samples, or 1.5 and 2.5 periods, apart
+        let sign = |f: usize, k: usize| if f * k % 2 == 1 { -1.0 } else { 1.0 };
+        for f in 0..4 {
+            let expected: Vec<_> = expected
+                .iter()
+                .enumerate()
+                .map(|(k, &(re, im))| (re * sign(f, k), im * sign(f, k)))
+                .collect();
+            check(found.slice(s![0, f, .., ..]), &expected);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn windowed_frames_two_sided() -> TractResult<()> {
+        // np.fft.fft(np.hanning(5) * x) for x = [1, 2, 3, 4, 5]: the window
+        // is [0, .5, 1, .5, 0], so the frame is [0, 1, 3, 2, 0]
+        let signal = Array3::from_shape_fn((1, 5, 1), |(_, n, _)| n as f32 + 1.0);
+        let window = arr1(&[0f32, 0.5, 1.0, 0.5, 0.0]);
+        let found = STFT::new(1, None, false).eval_f32(signal.view(), Some(window.view()))?;
+        assert_eq!(found.shape(), &[1, 1, 5, 2]);
+        check(
+            found.slice(s![0, 0, .., ..]),
+            &[
+                (6.0, 0.0),
+                (-3.73606798, -1.53884177),
+                (0.73606798, 0.36327126),
+                (0.73606798, -0.36327126),
+                (-3.73606798, 1.53884177),
+            ],
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn complex_signal() -> TractResult<()> {
+        // a complex exponential at bin 1 is a single peak
+        let signal = Array3::from_shape_fn((1, 6, 2), |(_, n, c)| {
+            let angle = 2.0 * PI * n as f32 / 6.0;
+            if c == 0 {
+                angle.cos()
+            } else {
+                angle.sin()
+            }
+        });
+        let found = STFT::new(6, Some(6), false).eval_f32(signal.view(), None)?;
+        check(
+            found.slice(s![0, 0, .., ..]),
+            &[(0., 0.), (6., 0.), (0., 0.), (0., 0.), (0., 0.), (0., 0.)],
+        );
+        assert!(STFT::new(6, Some(6), true).eval_f32(signal.view(), None).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn output_facts() -> TractResult<()> {
+        let signal = TypedFact::dt_shape(f32::datum_type(), [2, 16000, 1].as_ref())?;
+        let window = TypedFact::dt_shape(f32::datum_type(), [400].as_ref())?;
+        let facts = STFT::new(160, None, true).output_facts(&[&signal, &window])?;
+        assert_eq!(facts[0], TypedFact::dt_shape(f32::datum_type(), [2, 98, 201, 2].as_ref())?);
+        Ok(())
+    }
+}
@@ -12,6 +12,76 @@ impl UnimplementedOp {
     }
 }
 
+/// An external implementation for an operator tract does not support.
+pub type UnimplementedOpHandler =
+    Arc<dyn Fn(TVec<Arc<Tensor>>) -> TractResult<TVec<Arc<Tensor>>> + Send + Sync>;
+
+/// Fallback implementations for unimplemented operators, by operator name.
+///
+/// The registry lives in the SessionState, and is looked up when the
+/// execution state of an UnimplementedOp is built.
+#[derive(Clone, Default)]
+pub struct UnimplementedOpRegistry(HashMap<String, UnimplementedOpHandler>);
+
+impl UnimplementedOpRegistry {
+    /// Register a handler for operators named `op_name`.
+    pub fn register(&mut self, op_name: &str, handler: UnimplementedOpHandler) {
+        self.0.insert(op_name.to_string(), handler);
+    }
+
+    /// Get the handler for operators named `op_name`, if any.
+    pub fn get(&self, op_name: &str) -> Option<&UnimplementedOpHandler> {
+        self.0.get(op_name)
+    }
+}
+
+impl std::fmt::Debug for UnimplementedOpRegistry {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(fmt, "UnimplementedOpRegistry({:?})", self.0.keys().collect::<Vec<_>>())
+    }
+}
+
+#[derive(Clone)]
+struct FallbackState(UnimplementedOpHandler);
+
+impl std::fmt::Debug for FallbackState {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(fmt, "FallbackState")
+    }
+}
+
+impl OpState for FallbackState {
+    fn eval(
+        &mut self,
+        _session: &mut SessionState,
+        _op: &dyn Op,
+        inputs: TVec<Arc<Tensor>>,
+    ) -> TractResult<TVec<Arc<Tensor>>> {
+        (self.0)(inputs)
+    }
+}
+
+/// Check a model for unimplemented operators.
+///
+/// Fails with a single error listing every unimplemented operator, grouped by
+/// name and message, along with the ids of the nodes using it.
+pub fn check_unimplemented_ops(model: &InferenceModel) -> TractResult<()> {
+    let mut found: Vec<(String, String, Vec<usize>)> = vec![];
+    for node in model.nodes() {
+        if let Some(op) = node.op_as::<UnimplementedOp>() {
+            if let Some(entry) = found.iter_mut().find(|e| e.0 == op.name && e.1 == op.message) {
+                entry.2.push(node.id);
+            } else {
+                found.push((op.name.clone(), op.message.clone(), vec![node.id]));
+            }
+        }
+    }
+    if found.len() > 0 {
+        bail!(TractErrorKind::UnimplementedOps(found))
+    }
+    Ok(())
+}
+
 impl Op for UnimplementedOp {
     fn name(&self) -> Cow<str> {
         format!("Unimplemented({})", self.name).into()
@@ -24,9 +94,12 @@ impl Op for UnimplementedOp {
 impl StatefullOp for UnimplementedOp {
     fn state(
         &self,
-        _session: &mut SessionState,
+        session: &mut SessionState,
         node_id: usize,
     ) -> TractResult<Option<Box<dyn OpState>>> {
+        if let Some(handler) = session.unimplemented_ops.get(&self.name) {
+            return Ok(Some(Box::new(FallbackState(handler.clone()))));
+        }
         bail!("unimplemented operation: #{} {}", node_id, self.name)
     }
 }
@@ -53,3 +126,52 @@ impl InferenceRulesOp for UnimplementedOp {
         bail!("Operator can not be made a TypedOp.")
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn report_all_unimplemented() -> TractResult<()> {
+        let mut model = InferenceModel::default();
+        let a = model.add_source("a", InferenceFact::default())?;
+        let b = model.wire_node("b", UnimplementedOp::new("Foo", "foo"), &[a])?[0];
+        let c = model.wire_node("c", UnimplementedOp::new("Bar", "bar"), &[b])?[0];
+        let d = model.wire_node("d", UnimplementedOp::new("Foo", "foo"), &[c])?[0];
+        model.set_output_outlets(&[d])?;
+        match check_unimplemented_ops(&model) {
+            Err(TractError(TractErrorKind::UnimplementedOps(ops), _)) => assert_eq!(
+                ops,
+                vec!(
+                    ("Foo".to_string(), "foo".to_string(), vec!(b.node, d.node)),
+                    ("Bar".to_string(), "bar".to_string(), vec!(c.node))
+                )
+            ),
+            other => panic!("unexpected {:?}", other),
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn fallback_handler() -> TractResult<()> {
+        let mut model = InferenceModel::default();
+        let a = model.add_source("a", InferenceFact::default())?;
+        let b = model.wire_node("b", UnimplementedOp::new("Twice", "twice"), &[a])?[0];
+        model.set_output_outlets(&[b])?;
+        let plan = SimplePlan::new(&model)?;
+        assert!(SimpleState::new(&plan).is_err());
+
+        let mut session = SessionState::default();
+        session.unimplemented_ops.register(
+            "Twice",
+            Arc::new(|inputs: TVec<Arc<Tensor>>| {
+                let doubled = inputs[0].to_array_view::<f32>()?.mapv(|x| x * 2.0);
+                Ok(tvec!(doubled.into_arc_tensor()))
+            }),
+        );
+        let mut state = SimpleState::new_with_session_state(&plan, session)?;
+        let result = state.run(tvec!(tensor1(&[1f32, 2.0])))?;
+        assert_eq!(result[0], rctensor1(&[2f32, 4.0]));
+        Ok(())
+    }
+}
@@ -0,0 +1,4 @@
+//! Feature map sampling ops for vision models.
+pub mod roi_align;
+
+pub use self::roi_align::{CoordinateTransformation, RoiAlign};
@@ -0,0 +1,312 @@
+use crate::internal::*;
+use ndarray::*;
+use num_traits::Float;
+
+/// How region coordinates are mapped to feature map pixels.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CoordinateTransformation {
+    /// Pixel centers are at half integers, region coordinates are shifted
+    /// by half a pixel (torchvision `aligned=True`).
+    HalfPixel,
+    /// Legacy mapping, with no shift and regions at least one pixel wide
+    /// (torchvision `aligned=False`).
+    OutputHalfPixel,
+}
+
+impl Default for CoordinateTransformation {
+    fn default() -> CoordinateTransformation {
+        CoordinateTransformation::HalfPixel
+    }
+}
+
+/// Region of interest align, as in ONNX, with average pooling.
+///
+/// Inputs are the [N, C, H, W] feature map, the [num_rois, 4] regions as
+/// (x1, y1, x2, y2) and the batch index of each region. Each region is split
+/// in output_height x output_width bins, and each bin is the average of
+/// bilinearly interpolated samples on a regular grid. With a zero
+/// `sampling_ratio`, the grid is adapted to the bin size.
+///
+/// Output is [num_rois, C, output_height, output_width].
+#[derive(Debug, Clone, new)]
+pub struct RoiAlign {
+    pub output_height: usize,
+    pub output_width: usize,
+    pub sampling_ratio: usize,
+    pub spatial_scale: f32,
+    pub coordinate_transformation: CoordinateTransformation,
+}
+
+impl Default for RoiAlign {
+    fn default() -> RoiAlign {
+        RoiAlign {
+            output_height: 1,
+            output_width: 1,
+            sampling_ratio: 0,
+            spatial_scale: 1.0,
+            coordinate_transformation: CoordinateTransformation::default(),
+        }
+    }
+}
+
+impl RoiAlign {
+    fn bilinear<T: Datum + Float>(feature: ArrayView2<T>, y: T, x: T) -> T {
+        let (height, width) = feature.dim();
+        let (h, w) = (T::from(height).unwrap(), T::from(width).unwrap());
+        if y < -T::one() || y > h || x < -T::one() || x > w {
+            return T::zero();
+        }
+        let (mut y, mut x) = (y.max(T::zero()), x.max(T::zero()));
+        let (mut y_low, mut x_low) = (y.to_usize().unwrap(), x.to_usize().unwrap());
+        let y_high = if y_low >= height - 1 {
+            y_low = height - 1;
+            y = T::from(y_low).unwrap();
+            y_low
+        } else {
+            y_low + 1
+        };
+        let x_high = if x_low >= width - 1 {
+            x_low = width - 1;
+            x = T::from(x_low).unwrap();
+            x_low
+        } else {
+            x_low + 1
+        };
+        let ly = y - T::from(y_low).unwrap();
+        let lx = x - T::from(x_low).unwrap();
+        let (hy, hx) = (T::one() - ly, T::one() - lx);
+        hy * hx * feature[(y_low, x_low)]
+            + hy * lx * feature[(y_low, x_high)]
+            + ly * hx * feature[(y_high, x_low)]
+            + ly * lx * feature[(y_high, x_high)]
+    }
+
+    /// Coordinates of the regularly spaced samples in a bin, along one axis.
+    fn samples<T: Float>(start: T, bin: T, ix: usize, grid: usize) -> impl Iterator<Item = T> {
+        let bin_start = start + T::from(ix).unwrap() * bin;
+        let step = bin / T::from(grid).unwrap();
+        (0..grid).map(move |i| bin_start + (T::from(i).unwrap() + T::from(0.5).unwrap()) * step)
+    }
+
+    fn eval_t<T: Datum + Float>(
+        &self,
+        data: &Tensor,
+        rois: &Tensor,
+        batch_indices: &[i64],
+    ) -> TractResult<Arc<Tensor>> {
+        let data = data.to_array_view::<T>()?.into_dimensionality::<Ix4>()?;
+        let rois = rois.to_array_view::<T>()?.into_dimensionality::<Ix2>()?;
+        if rois.shape()[1] != 4 || rois.shape()[0] != batch_indices.len() {
+            bail!(
+                "RoiAlign expects [num_rois, 4] rois and as many batch indices, got {:?} and {}",
+                rois.shape(),
+                batch_indices.len()
+            )
+        }
+        let (oh, ow) = (self.output_height, self.output_width);
+        let mut output = Array4::<T>::zeros((rois.shape()[0], data.shape()[1], oh, ow));
+        let scale = T::from(self.spatial_scale).unwrap();
+        let offset = match self.coordinate_transformation {
+            CoordinateTransformation::HalfPixel => T::from(0.5).unwrap(),
+            CoordinateTransformation::OutputHalfPixel => T::zero(),
+        };
+        for (ix, roi) in rois.outer_iter().enumerate() {
+            let batch = batch_indices[ix];
+            if batch < 0 || batch as usize >= data.shape()[0] {
+                bail!("RoiAlign batch index {} out of bounds", batch)
+            }
+            let start_w = roi[0] * scale - offset;
+            let start_h = roi[1] * scale - offset;
+            let mut roi_width = roi[2] * scale - offset - start_w;
+            let mut roi_height = roi[3] * scale - offset - start_h;
+            if self.coordinate_transformation == CoordinateTransformation::OutputHalfPixel {
+                roi_width = roi_width.max(T::one());
+                roi_height = roi_height.max(T::one());
+            }
+            let bin_h = roi_height / T::from(oh).unwrap();
+            let bin_w = roi_width / T::from(ow).unwrap();
+            let (grid_h, grid_w) = if self.sampling_ratio > 0 {
+                (self.sampling_ratio, self.sampling_ratio)
+            } else {
+                (bin_h.ceil().to_usize().unwrap_or(0), bin_w.ceil().to_usize().unwrap_or(0))
+            };
+            let count = T::from((grid_h * grid_w).max(1)).unwrap();
+            for (feature, mut pooled) in data
+                .index_axis(Axis(0), batch as usize)
+                .outer_iter()
+                .zip(output.index_axis_mut(Axis(0), ix).outer_iter_mut())
+            {
+                for ((ph, pw), pooled) in pooled.indexed_iter_mut() {
+                    let mut sum = T::zero();
+                    for y in Self::samples(start_h, bin_h, ph, grid_h) {
+                        for x in Self::samples(start_w, bin_w, pw, grid_w) {
+                            sum = sum + Self::bilinear(feature, y, x);
+                        }
+                    }
+                    *pooled = sum / count;
+                }
+            }
+        }
+        Ok(output.into_arc_tensor())
+    }
+}
+
+impl Op for RoiAlign {
+    fn name(&self) -> Cow<str> {
+        "RoiAlign".into()
+    }
+
+    fn info(&self) -> TractResult<Vec<String>> {
+        Ok(vec![format!(
+            "output: {}x{}, sampling_ratio: {}, spatial_scale: {}, {:?}",
+            self.output_height,
+            self.output_width,
+            self.sampling_ratio,
+            self.spatial_scale,
+            self.coordinate_transformation
+        )])
+    }
+
+    fn validation(&self) -> Validation {
+        Validation::Rounding
+    }
+
+    op_as_typed_op!();
+    not_a_pulsed_op!();
+}
+
+impl StatelessOp for RoiAlign {
+    fn eval(&self, mut inputs: TVec<Arc<Tensor>>) -> TractResult<TVec<Arc<Tensor>>> {
+        let (data, rois, batch_indices) = args_3!(inputs);
+        let batch_indices = batch_indices.cast_to::<i64>()?;
+        let batch_indices = batch_indices.as_slice::<i64>()?;
+        Ok(tvec!(dispatch_floatlike!(Self::eval_t(data.datum_type())(
+            self,
+            &data,
+            &rois,
+            batch_indices
+        ))?))
+    }
+}
+
+impl InferenceRulesOp for RoiAlign {
+    fn rules<'r, 'p: 'r, 's: 'r>(
+        &'s self,
+        s: &mut Solver<'r>,
+        inputs: &'p [TensorProxy],
+        outputs: &'p [TensorProxy],
+    ) -> InferenceResult {
+        check_input_arity(&inputs, 3)?;
+        check_output_arity(&outputs, 1)?;
+        s.equals(&inputs[0].datum_type, &outputs[0].datum_type)?;
+        s.equals(&inputs[1].datum_type, &outputs[0].datum_type)?;
+        s.equals(&inputs[0].rank, 4)?;
+        s.equals(&inputs[1].rank, 2)?;
+        s.equals(&inputs[1].shape[1], 4.to_dim())?;
+        s.equals(&inputs[2].rank, 1)?;
+        s.equals(&inputs[2].shape[0], &inputs[1].shape[0])?;
+        s.equals(&outputs[0].rank, 4)?;
+        s.equals(&outputs[0].shape[0], &inputs[1].shape[0])?;
+        s.equals(&outputs[0].shape[1], &inputs[0].shape[1])?;
+        s.equals(&outputs[0].shape[2], self.output_height.to_dim())?;
+        s.equals(&outputs[0].shape[3], self.output_width.to_dim())?;
+        Ok(())
+    }
+
+    inference_op_as_op!();
+    to_typed!();
+}
+
+impl TypedOp for RoiAlign {
+    fn output_facts(&self, inputs: &[&TypedFact]) -> TractResult<TVec<TypedFact>> {
+        if inputs[0].rank() != 4 || inputs[1].rank() != 2 {
+            bail!("RoiAlign expects a [N, C, H, W] input and [num_rois, 4] rois")
+        }
+        let shape = [
+            inputs[1].shape.dim(0),
+            inputs[0].shape.dim(1),
+            self.output_height.to_dim(),
+            self.output_width.to_dim(),
+        ];
+        Ok(tvec!(TypedFact::dt_shape(inputs[0].datum_type, shape.as_ref())?))
+    }
+
+    typed_op_as_op!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Reference values from a line by line python port of torchvision's
+    // roi_align CPU kernel.
+
+    fn features() -> Tensor {
+        let data = Array4::from_shape_fn((1, 2, 4, 4), |(_, c, y, x)| {
+            if c == 0 {
+                (4 * y + x) as f32
+            } else {
+                (y as f32 - x as f32).powi(2) / 2.0
+            }
+        });
+        data.into()
+    }
+
+    fn rois() -> Tensor {
+        tensor2(&[[0f32, 0., 3., 3.], [1., 0.5, 3.5, 2.]])
+    }
+
+    fn run(op: RoiAlign, rois: Tensor, batch_indices: Tensor) -> TractResult<Arc<Tensor>> {
+        Ok(op.eval(tvec!(features().into(), rois.into(), batch_indices.into()))?.remove(0))
+    }
+
+    fn assert_close(found: &Tensor, expected: &Tensor) -> TractResult<()> {
+        assert_eq!(found.shape(), expected.shape());
+        for (f, e) in found.as_slice::<f32>()?.iter().zip(expected.as_slice::<f32>()?.iter()) {
+            assert!((f - e).abs() < 1e-5, "{} != {}", f, e);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn half_pixel() -> TractResult<()> {
+        let op = RoiAlign::new(2, 2, 2, 1.0, CoordinateTransformation::HalfPixel);
+        let output = run(op, rois(), tensor1(&[0i64, 0]))?;
+        let expected = tensor4(&[
+            [[[1.5625f32, 3.0], [7.3125, 8.75]], [[0.214844, 1.296875], [1.296875, 0.3125]]],
+            [[[2.625, 3.875], [5.625, 6.875]], [[0.546875, 2.234375], [0.234375, 0.984375]]],
+        ]);
+        assert_close(&output, &expected)
+    }
+
+    #[test]
+    fn output_half_pixel() -> TractResult<()> {
+        let op = RoiAlign::new(2, 2, 2, 1.0, CoordinateTransformation::OutputHalfPixel);
+        let output = run(op, rois(), tensor1(&[0i64, 0]))?;
+        let expected = tensor4(&[
+            [[[3.75f32, 5.25], [9.75, 11.25]], [[0.3125, 1.4375], [1.4375, 0.3125]]],
+            [[[5.125, 6.28125], [8.125, 9.28125]], [[0.484375, 1.988281], [0.234375, 0.871094]]],
+        ]);
+        assert_close(&output, &expected)
+    }
+
+    #[test]
+    fn adaptive_sampling() -> TractResult<()> {
+        let op = RoiAlign::new(2, 2, 0, 1.0, CoordinateTransformation::HalfPixel);
+        let output = run(op, rois(), tensor1(&[0i64, 0]))?;
+        let expected = tensor4(&[
+            [[[1.5625f32, 3.0], [7.3125, 8.75]], [[0.214844, 1.296875], [1.296875, 0.3125]]],
+            [[[2.625, 3.875], [5.625, 6.875]], [[0.546875, 2.234375], [0.203125, 0.953125]]],
+        ]);
+        assert_close(&output, &expected)
+    }
+
+    #[test]
+    fn spatial_scale() -> TractResult<()> {
+        let op = RoiAlign::new(2, 2, 0, 0.5, CoordinateTransformation::OutputHalfPixel);
+        let output = run(op, tensor2(&[[0f32, 0., 6., 6.]]), tensor1(&[0i64]))?;
+        let expected =
+            tensor4(&[[[[3.75f32, 5.25], [9.75, 11.25]], [[0.3125, 1.4375], [1.4375, 0.3125]]]]);
+        assert_close(&output, &expected)
+    }
+}
@@ -0,0 +1,91 @@
+use crate::errors::TractResultExt;
+use crate::internal::*;
+use crate::ops::binary::{BinMiniOp, MergeOp, TypedBinOp};
+use crate::ops::element_wise::{ElementWiseMiniOp, ElementWiseOp};
+
+pub(crate) type Matcher = fn(&TypedModel, &TypedNode) -> TractResult<Option<TypedModelPatch>>;
+
+/// Matchers for decomposed forms of fused operators, called on the last node
+/// of the decomposition.
+fn matchers() -> Vec<Matcher> {
+    vec![
+        crate::ops::nn::fuse_attention,
+        crate::ops::nn::fuse_rotary_embedding,
+        crate::ops::nn::fuse_silu,
+        crate::ops::nn::fuse_mish,
+        crate::ops::nn::fuse_swiglu,
+        crate::ops::nn::fuse_rms_norm,
+        crate::ops::nn::fuse_rms_norm_weight,
+        crate::ops::nn::fuse_threshold_relu,
+        crate::ops::math::fuse_log_sum_exp,
+        crate::passes::fuse::conv_batchnorm,
+        crate::passes::fuse::layer_norm,
+    ]
+}
+
+/// Apply a single matcher until it stops matching, leaving the replaced
+/// nodes dangling. Returns the number of fusions.
+pub(crate) fn fuse_all(model: &mut TypedModel, matcher: Matcher) -> TractResult<usize> {
+    let mut done = 0;
+    'search: loop {
+        for id in model.eval_order()? {
+            if let Some(patch) = matcher(model, &model.nodes()[id])? {
+                patch.apply(model)?;
+                done += 1;
+                continue 'search;
+            }
+        }
+        return Ok(done);
+    }
+}
+
+/// Replace subgraphs computing a known fused operator, like transformers
+/// attention, by the fused operator.
+#[derive(Debug)]
+pub struct FusePatterns;
+
+impl super::TypedPass for FusePatterns {
+    fn pass(&self, model: &mut TypedModel) -> TractResult<bool> {
+        let mut done_something = false;
+        for id in model.eval_order()? {
+            for matcher in matchers() {
+                let patch = {
+                    let node = &model.nodes()[id];
+                    matcher(model, node).chain_err(|| format!("{:?} node {}", self, node))?
+                };
+                if let Some(patch) = patch {
+                    debug!("Fusing a pattern ending at {}", model.nodes()[id]);
+                    patch.apply(model)?;
+                    done_something = true;
+                    break;
+                }
+            }
+        }
+        Ok(done_something)
+    }
+}
+
+/// A node with a single output, used by a single node.
+pub(crate) fn single_use(model: &TypedModel, outlet: OutletId) -> Option<&TypedNode> {
+    let node = model.node(outlet.node);
+    if node.outputs.len() == 1 && node.outputs[0].successors.len() == 1 {
+        Some(node)
+    } else {
+        None
+    }
+}
+
+/// Is this node a binary `Op`, broadcasting or not.
+pub(crate) fn is_binary<Op: BinMiniOp>(node: &TypedNode) -> bool {
+    if let Some(op) = node.op_as::<TypedBinOp>() {
+        op.0.is::<Op>()
+    } else if let Some(op) = node.op_as::<MergeOp>() {
+        op.0.is::<Op>()
+    } else {
+        false
+    }
+}
+
+pub(crate) fn is_element_wise<Op: ElementWiseMiniOp>(node: &TypedNode) -> bool {
+    node.op_as::<ElementWiseOp>().map(|op| op.0.is::<Op>()).unwrap_or(false)
+}
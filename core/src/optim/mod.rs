@@ -2,10 +2,14 @@ use crate::model::*;
 use crate::TractResult;
 use std::fmt::Debug;
 
+mod fuse_patterns;
 mod prop_const;
 mod push_split_down;
 
-use self::prop_const::PropConst;
+use self::fuse_patterns::FusePatterns;
+pub(crate) use self::fuse_patterns::{fuse_all, is_binary, is_element_wise, single_use};
+pub(crate) use self::prop_const::PropConst;
+pub use self::prop_const::ConstPropagationStrategy;
 use self::push_split_down::PushSplitDown;
 
 use crate::errors::TractResultExt;
@@ -18,12 +22,65 @@ pub trait TypedPass: Debug + Send + Sync {
     fn pass(&self, model: &mut TypedModel) -> TractResult<bool>;
 }
 
+/// What each optimisation pass changed in a model, for debugging passes.
+///
+/// Auditing clones the model before each pass: it is meant for development,
+/// not for production use.
+#[derive(Debug, Clone, Default)]
+pub struct PassAudit {
+    /// The name of each pass that changed the model, and what it changed.
+    pub steps: Vec<(String, ModelDiff)>,
+}
+
+impl PassAudit {
+    /// Run a pass on the model, recording its changes if auditing.
+    pub(crate) fn step<R>(
+        audit: Option<&mut PassAudit>,
+        name: &str,
+        model: &mut TypedModel,
+        pass: impl FnOnce(&mut TypedModel) -> TractResult<R>,
+    ) -> TractResult<R> {
+        if let Some(audit) = audit {
+            let before = model.clone();
+            let result = pass(model)?;
+            let diff = model_diff(&before, model);
+            if !diff.is_empty() {
+                audit.steps.push((name.to_string(), diff));
+            }
+            Ok(result)
+        } else {
+            pass(model)
+        }
+    }
+
+    /// Compact the model, recording the nodes it drops if auditing.
+    pub(crate) fn compact(
+        audit: Option<&mut PassAudit>,
+        model: &mut TypedModel,
+    ) -> TractResult<()> {
+        PassAudit::step(audit, "compact", model, |m| {
+            *m = crate::model::compact::compact(m)?;
+            Ok(())
+        })
+    }
+
+    /// A human-readable summary of the changes, pass by pass.
+    pub fn display(&self) -> String {
+        self.steps.iter().map(|(name, diff)| format!("{}:\n{}", name, diff)).collect()
+    }
+}
+
 pub fn incorporate() -> Vec<Box<dyn IncorporatePass>> {
     vec![Box::new(IncorporateOps)]
 }
 
 pub fn declutter() -> Vec<Box<dyn TypedPass>> {
-    vec![Box::new(PropConst) as _, Box::new(DeclutterOps), Box::new(PushSplitDown)]
+    vec![
+        Box::new(PropConst::default()) as _,
+        Box::new(DeclutterOps),
+        Box::new(FusePatterns),
+        Box::new(PushSplitDown),
+    ]
 }
 
 pub fn codegen() -> Vec<Box<dyn TypedPass>> {
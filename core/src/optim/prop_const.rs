@@ -1,61 +1,436 @@
+use crate::internal::*;
+use crate::model::constants::{connected_components, Component};
 use crate::model::order::eval_order_for_nodes;
-use crate::model::*;
-use crate::TractResult;
-use bit_set;
+use crate::ops::konst::Const;
+use bit_set::BitSet;
+use itertools::Itertools;
+use std::hash::{Hash, Hasher};
 
-#[derive(Debug)]
-pub struct PropConst;
+/// Strategies for replacing constant subgraphs by Const nodes.
+///
+/// The simplest is to prune all nodes but the sinks of each constant
+/// component, and to replace the latter with Const nodes. This might however
+/// increase the size of the model dramatically in cases like the one below,
+/// where we'll end up storing two large constants instead of one while only
+/// getting a neglectible performance boost from the operation.
+///
+/// ```text
+///                                     +---------------------+
+///                                 +--^+ Simple operation 1  +-->
+///             +---------------+   |   +---------------------+
+///             | Const (large) +---+
+///             +---------------+   |   +---------------------+
+///                                 +--^+ Simple operation 2  +-->
+///                                     +---------------------+
+/// ```
+///
+/// We can also search for the lowest common ancestor of all the sinks in
+/// each connected component, and prune every node and edge that isn't part
+/// of a path between that ancestor and a sink. If no such ancestor exists,
+/// we don't do anything. This way we guarantee that we don't increase the
+/// size of the model, but we might miss some optimisations.
+///
+/// In between, the copies can be weighed against the work they save: when
+/// evaluating the nodes below the common ancestor costs more flops than the
+/// extra bytes stored, times `FLOPS_PER_STORED_BYTE`, the sinks are copied.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConstPropagationStrategy {
+    /// Replace every sink of constant components by a Const node.
+    AlwaysCopy,
+    /// Only replace the lowest common ancestor of each constant component
+    /// sinks by a Const node.
+    LcaOnly,
+    /// Replace the sinks when they save enough flops, the lowest common
+    /// ancestor otherwise.
+    CostBased,
+}
+
+/// How many flops saved at runtime justify storing one more byte in the
+/// model.
+pub const FLOPS_PER_STORED_BYTE: u64 = 16;
+
+impl Default for ConstPropagationStrategy {
+    fn default() -> ConstPropagationStrategy {
+        ConstPropagationStrategy::AlwaysCopy
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct PropConst(pub ConstPropagationStrategy);
 
 impl super::TypedPass for PropConst {
     fn pass(&self, model: &mut TypedModel) -> TractResult<bool> {
-        let mut replaced = 0;
-        let mut done = bit_set::BitSet::with_capacity(model.nodes().len());
-        let mut needed: Vec<usize> = vec![];
-        for t in model.output_outlets()?.iter().map(|n| n.node) {
-            needed.push(t);
-        }
-        while let Some(&node) = needed.last() {
-            if done.contains(node) {
-                needed.pop();
+        match self.0 {
+            ConstPropagationStrategy::AlwaysCopy => always_copy(model),
+            ConstPropagationStrategy::LcaOnly => lca_only(model),
+            ConstPropagationStrategy::CostBased => cost_based(model),
+        }
+    }
+}
+
+fn always_copy(model: &mut TypedModel) -> TractResult<bool> {
+    let mut replaced = 0;
+    let mut konsts = ConstCache::for_model(model);
+    let mut done = BitSet::with_capacity(model.nodes().len());
+    let mut needed: Vec<usize> = vec![];
+    for t in model.output_outlets()?.iter().map(|n| n.node) {
+        needed.push(t);
+    }
+    while let Some(&node) = needed.last() {
+        if done.contains(node) {
+            needed.pop();
+            continue;
+        }
+        if model.nodes()[node].inputs.iter().all(|i| done.contains(i.node)) {
+            needed.pop();
+            done.insert(node);
+        } else {
+            trace!("Looking at node {} inputs", model.nodes()[node]);
+            for ix in 0..model.nodes()[node].inputs.len() {
+                let source = model.nodes()[node].inputs[ix];
+                if model.nodes()[source.node].op().name() != "Const"
+                    && model.outlet_fact(source)?.konst.is_some()
+                    && eval_order_for_nodes(
+                        model.nodes(),
+                        &model.input_outlets()?.iter().map(|n| n.node).collect::<Vec<_>>(),
+                        &[source.node],
+                    )?
+                    .into_iter()
+                    .all(|n| model.nodes()[n].op().as_stateless().is_some())
+                {
+                    let konst = model.outlet_fact(source)?.konst.clone().unwrap();
+                    trace!(
+                        "   Replacing node {} input {} by a constant instead of {:?}",
+                        model.nodes()[node],
+                        ix,
+                        source
+                    );
+                    let id = konsts.wire(model, konst)?;
+                    model.add_edge(id, InletId::new(node, ix))?;
+                    model.check_edges()?;
+                    replaced += 1;
+                } else {
+                    needed.push(source.node);
+                }
+            }
+        }
+    }
+    debug!("Replaced {} inputs by constants", replaced);
+    Ok(replaced > 0)
+}
+
+fn lca_only(model: &mut TypedModel) -> TractResult<bool> {
+    let mut replaced = 0;
+    let mut konsts = ConstCache::for_model(model);
+    for component in connected_components(model)? {
+        let sinks: Vec<usize> = component.outputs.iter().map(|o| o.node).unique().collect();
+        let target = if let Some(target) = lowest_common_dominator(model, &component, &sinks)? {
+            target
+        } else {
+            continue;
+        };
+        if model.node(target).op_is::<Const>() {
+            continue;
+        }
+        trace!("   Replacing node {} by a constant", model.node(target));
+        for slot in 0..model.node(target).outputs.len() {
+            let outlet = OutletId::new(target, slot);
+            let successors = model.node(target).outputs[slot].successors.clone();
+            if successors.len() == 0 {
+                continue;
+            }
+            let konst = model.outlet_fact(outlet)?.konst.clone().unwrap();
+            let id = konsts.wire(model, konst)?;
+            for succ in successors {
+                model.add_edge(id, succ)?;
+                replaced += 1;
+            }
+        }
+    }
+    debug!("Replaced {} inputs by constants", replaced);
+    Ok(replaced > 0)
+}
+
+fn cost_based(model: &mut TypedModel) -> TractResult<bool> {
+    let mut replaced = 0;
+    let mut konsts = ConstCache::for_model(model);
+    for component in connected_components(model)? {
+        let sinks: Vec<usize> = component.outputs.iter().map(|o| o.node).unique().collect();
+        let target = lowest_common_dominator(model, &component, &sinks)?;
+        let outlets: Vec<OutletId> = if worth_copying(model, &component, target, &sinks)? {
+            component.outputs.clone()
+        } else if let Some(target) = target {
+            (0..model.node(target).outputs.len()).map(|s| OutletId::new(target, s)).collect()
+        } else {
+            continue;
+        };
+        for outlet in outlets {
+            if model.node(outlet.node).op_is::<Const>() {
+                continue;
+            }
+            let successors = model.node(outlet.node).outputs[outlet.slot].successors.clone();
+            if successors.len() == 0 {
                 continue;
             }
-            if model.nodes()[node].inputs.iter().all(|i| done.contains(i.node)) {
-                needed.pop();
-                done.insert(node);
-            } else {
-                trace!("Looking at node {} inputs", model.nodes()[node]);
-                for ix in 0..model.nodes()[node].inputs.len() {
-                    let source = model.nodes()[node].inputs[ix];
-                    if model.nodes()[source.node].op().name() != "Const"
-                        && model.outlet_fact(source)?.konst.is_some()
-                        && eval_order_for_nodes(
-                            model.nodes(),
-                            &model.input_outlets()?.iter().map(|n| n.node).collect::<Vec<_>>(),
-                            &[source.node],
-                        )?
-                        .into_iter()
-                        .all(|n| model.nodes()[n].op().as_stateless().is_some())
-                    {
-                        let konst = model.outlet_fact(source)?.konst.clone().unwrap();
-                        let id = model.nodes().len();
-                        trace!(
-                            "   Replacing node {} input {} by a constant instead of {:?}",
-                            model.nodes()[node],
-                            ix,
-                            source
-                        );
-                        let id = model.add_const(format!("Const-{}", id), konst.clone())?;
-                        model.add_edge(id, InletId::new(node, ix))?;
-                        model.check_edges()?;
-                        model.set_outlet_fact(id, konst.into())?;
-                        replaced += 1;
-                    } else {
-                        needed.push(source.node);
-                    }
+            trace!("   Replacing {:?} by a constant", outlet);
+            let konst = model.outlet_fact(outlet)?.konst.clone().unwrap();
+            let id = konsts.wire(model, konst)?;
+            for succ in successors {
+                model.add_edge(id, succ)?;
+                replaced += 1;
+            }
+        }
+    }
+    debug!("Replaced {} inputs by constants", replaced);
+    Ok(replaced > 0)
+}
+
+/// Compare the flops spent to compute the sinks, from `target` or from the
+/// component Const nodes if there is no common ancestor, with the bytes
+/// copying the sinks would add.
+fn worth_copying(
+    model: &TypedModel,
+    component: &Component,
+    target: Option<usize>,
+    sinks: &[usize],
+) -> TractResult<bool> {
+    let nodes: Vec<usize> = component.nodes().collect();
+    let roots: Vec<usize> = if let Some(target) = target {
+        vec![target]
+    } else {
+        nodes.iter().cloned().filter(|&n| model.node(n).op_is::<Const>()).collect()
+    };
+    let mut stored = 0;
+    for outlet in &component.outputs {
+        stored += crate::ops::cost::fact_bytes(model.outlet_fact(*outlet)?)?;
+    }
+    let mut dropped = 0;
+    for &root in &roots {
+        for output in &model.node(root).outputs {
+            dropped += crate::ops::cost::fact_bytes(&output.fact)?;
+        }
+    }
+    if stored <= dropped {
+        return Ok(true);
+    }
+    let mut below: BitSet = roots.iter().cloned().collect();
+    let mut flops = 0;
+    for node in eval_order_for_nodes(model.nodes(), &[], sinks)? {
+        let node = model.node(node);
+        if below.contains(node.id)
+            || !nodes.contains(&node.id)
+            || !node.inputs.iter().any(|i| below.contains(i.node))
+        {
+            continue;
+        }
+        below.insert(node.id);
+        let inputs = model.node_input_facts(node.id)?;
+        flops += crate::ops::cost::op_cost(node.op.as_ref(), &*inputs)?.flops;
+    }
+    Ok(flops >= (stored - dropped) * FLOPS_PER_STORED_BYTE)
+}
+
+/// Find the deepest node of a constant component that lies on every path
+/// leading to all of the `sinks`.
+fn lowest_common_dominator(
+    model: &TypedModel,
+    component: &Component,
+    sinks: &[usize],
+) -> TractResult<Option<usize>> {
+    if sinks.len() == 0 {
+        return Ok(None);
+    }
+    let nodes: Vec<usize> = component.nodes().collect();
+    let mut dominators: HashMap<usize, BitSet> = HashMap::new();
+    for node in eval_order_for_nodes(model.nodes(), &[], sinks)? {
+        if !nodes.contains(&node) {
+            continue;
+        }
+        let mut dom: Option<BitSet> = None;
+        for input in &model.node(node).inputs {
+            let input_dom = &dominators[&input.node];
+            match dom {
+                None => dom = Some(input_dom.clone()),
+                Some(ref mut dom) => dom.intersect_with(input_dom),
+            }
+        }
+        let mut dom = dom.unwrap_or_else(BitSet::new);
+        dom.insert(node);
+        dominators.insert(node, dom);
+    }
+    let mut common = dominators[&sinks[0]].clone();
+    for sink in &sinks[1..] {
+        common.intersect_with(&dominators[sink]);
+    }
+    Ok(common.iter().max_by_key(|n| dominators[n].len()))
+}
+
+/// Const nodes of a model, indexed by a hash of their tensor content.
+///
+/// Used to reuse an existing Const node instead of creating a new one when
+/// the same tensor value shows up more than once.
+struct ConstCache(HashMap<u64, Vec<OutletId>>);
+
+impl ConstCache {
+    fn for_model(model: &TypedModel) -> ConstCache {
+        let mut cache = ConstCache(HashMap::new());
+        for node in model.nodes() {
+            if node.op_is::<Const>() {
+                if let Some(k) = &node.outputs[0].fact.konst {
+                    cache.0.entry(tensor_hash(k)).or_insert_with(Vec::new).push(node.id.into());
                 }
             }
         }
-        debug!("Replaced {} inputs by constants", replaced);
-        Ok(replaced > 0)
+        cache
+    }
+
+    /// Find a Const node producing `konst`, or add one to the model.
+    fn wire(&mut self, model: &mut TypedModel, konst: Arc<Tensor>) -> TractResult<OutletId> {
+        let candidates = self.0.entry(tensor_hash(&konst)).or_insert_with(Vec::new);
+        for &candidate in candidates.iter() {
+            if model.outlet_fact(candidate)?.konst.as_ref().map(|k| **k == *konst).unwrap_or(false)
+            {
+                return Ok(candidate);
+            }
+        }
+        let id = model.nodes().len();
+        let id = model.add_const(format!("Const-{}", id), konst.clone())?;
+        model.set_outlet_fact(id, konst.into())?;
+        candidates.push(id);
+        Ok(id)
+    }
+}
+
+/// Hash a tensor from its type, shape and raw data buffer.
+///
+/// Types holding pointers (String, TDim, Blob) are only hashed on type and
+/// shape, so actual equality must be checked on collision.
+fn tensor_hash(t: &Tensor) -> u64 {
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    t.datum_type().hash(&mut hasher);
+    t.shape().hash(&mut hasher);
+    match t.datum_type() {
+        DatumType::String | DatumType::TDim | DatumType::Blob => (),
+        _ => unsafe { t.as_bytes() }.hash(&mut hasher),
+    }
+    hasher.finish()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::ops::math;
+    use crate::optim::TypedPass;
+
+    #[test]
+    fn dedup_f32_consts() -> TractResult<()> {
+        let mut model = TypedModel::default();
+        let x = model.add_source("x", TypedFact::dt_shape(f32::datum_type(), [3usize].as_ref())?)?;
+        let c = model.add_const("c", rctensor1(&[1f32, 2.0, 3.0]))?;
+        let two = model.add_const("two", rctensor0(2f32))?;
+        let k1 = model.wire_node("k1", math::add::bin(), &[c, c])?[0];
+        let k2 = model.wire_node("k2", math::mul::bin(), &[c, two])?[0];
+        let a = model.wire_node("a", math::add::bin(), &[x, k1])?[0];
+        let b = model.wire_node("b", math::mul::bin(), &[x, k2])?[0];
+        model.set_output_outlets(&[a, b])?;
+        assert!(PropConst::default().pass(&mut model)?);
+        let model = crate::model::compact::compact(&model)?;
+        assert_eq!(model.nodes().iter().filter(|n| n.op_is::<Const>()).count(), 1);
+        let result = SimplePlan::new(&model)?.run(tvec!(tensor1(&[1f32, 1.0, 1.0])))?;
+        assert_eq!(result[0], rctensor1(&[3f32, 5.0, 7.0]));
+        assert_eq!(result[1], rctensor1(&[2f32, 4.0, 6.0]));
+        Ok(())
+    }
+
+    fn const_nodes(model: &TypedModel) -> Vec<Arc<Tensor>> {
+        model
+            .nodes()
+            .iter()
+            .filter(|n| n.op_is::<Const>())
+            .map(|n| n.outputs[0].fact.konst.clone().unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn lca_only_does_not_duplicate_large_const() -> TractResult<()> {
+        let len = 10 * 1024 * 1024 / 4;
+        let fact = TypedFact::dt_shape(f32::datum_type(), [len].as_ref())?;
+        let mut model = TypedModel::default();
+        let x = model.add_source("x", fact)?;
+        let big = model.add_const("big", ndarray::Array1::<f32>::zeros(len).into_arc_tensor())?;
+        let one = model.add_const("one", rctensor0(1f32))?;
+        let two = model.add_const("two", rctensor0(2f32))?;
+        let k1 = model.wire_node("k1", math::add::bin(), &[big, one])?[0];
+        let k2 = model.wire_node("k2", math::add::bin(), &[big, two])?[0];
+        let a = model.wire_node("a", math::add::bin(), &[x, k1])?[0];
+        let b = model.wire_node("b", math::add::bin(), &[x, k2])?[0];
+        model.set_output_outlets(&[a, b])?;
+
+        let mut copied = model.clone();
+        copied.propagate_constants_with_strategy(ConstPropagationStrategy::AlwaysCopy)?;
+        let copied = crate::model::compact::compact(&copied)?;
+        assert_eq!(const_nodes(&copied).iter().filter(|k| k.len() == len).count(), 2);
+
+        model.propagate_constants_with_strategy(ConstPropagationStrategy::LcaOnly)?;
+        let model = crate::model::compact::compact(&model)?;
+        assert_eq!(const_nodes(&model).iter().filter(|k| k.len() == len).count(), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn lca_only_replaces_common_ancestor() -> TractResult<()> {
+        let mut model = TypedModel::default();
+        let x = model.add_source("x", TypedFact::dt_shape(f32::datum_type(), [2usize].as_ref())?)?;
+        let c = model.add_const("c", rctensor1(&[1f32, -2.0]))?;
+        let n = model.wire_node("n", math::neg(), &[c])?[0];
+        let k1 = model.wire_node("k1", math::abs(), &[n])?[0];
+        let k2 = model.wire_node("k2", math::neg(), &[n])?[0];
+        let a = model.wire_node("a", math::add::bin(), &[x, k1])?[0];
+        let b = model.wire_node("b", math::add::bin(), &[x, k2])?[0];
+        model.set_output_outlets(&[a, b])?;
+        assert!(model.propagate_constants_with_strategy(ConstPropagationStrategy::LcaOnly)?);
+        let model = crate::model::compact::compact(&model)?;
+        assert_eq!(const_nodes(&model), vec!(rctensor1(&[-1f32, 2.0])));
+        let result = SimplePlan::new(&model)?.run(tvec!(tensor1(&[0f32, 0.0])))?;
+        assert_eq!(result[0], rctensor1(&[1f32, 2.0]));
+        assert_eq!(result[1], rctensor1(&[1f32, -2.0]));
+        Ok(())
+    }
+
+    #[test]
+    fn cost_based_copies_expensive_sinks_only() -> TractResult<()> {
+        use crate::ops::matmul::MatMul;
+        let len = 1024 * 1024;
+        let mut model = TypedModel::default();
+        let x = model.add_source("x", TypedFact::dt_shape(f32::datum_type(), [len].as_ref())?)?;
+        let big = model.add_const("big", ndarray::Array1::<f32>::zeros(len).into_arc_tensor())?;
+        let one = model.add_const("one", rctensor0(1f32))?;
+        let two = model.add_const("two", rctensor0(2f32))?;
+        let k1 = model.wire_node("k1", math::add::bin(), &[big, one])?[0];
+        let k2 = model.wire_node("k2", math::add::bin(), &[big, two])?[0];
+        let a = model.wire_node("a", math::add::bin(), &[x, k1])?[0];
+        let b = model.wire_node("b", math::add::bin(), &[x, k2])?[0];
+        model.set_output_outlets(&[a, b])?;
+        model.propagate_constants_with_strategy(ConstPropagationStrategy::CostBased)?;
+        let model = crate::model::compact::compact(&model)?;
+        assert_eq!(const_nodes(&model).iter().filter(|k| k.len() == len).count(), 1);
+
+        let mut model = TypedModel::default();
+        let x =
+            model.add_source("x", TypedFact::dt_shape(f32::datum_type(), [32, 32].as_ref())?)?;
+        let c = ndarray::Array2::from_shape_fn((32, 32), |(i, j)| (i * 32 + j) as f32);
+        let c = model.add_const("c", c.into_arc_tensor())?;
+        let k1 = model.wire_node("k1", MatMul::default(), &[c, c])?[0];
+        let k2 = model.wire_node("k2", MatMul::default().with_a_trans(true), &[c, c])?[0];
+        let a = model.wire_node("a", math::add::bin(), &[x, k1])?[0];
+        let b = model.wire_node("b", math::add::bin(), &[x, k2])?[0];
+        model.set_output_outlets(&[a, b])?;
+        let mut lca = model.clone();
+        assert!(!lca.propagate_constants_with_strategy(ConstPropagationStrategy::LcaOnly)?);
+        assert!(model.propagate_constants_with_strategy(ConstPropagationStrategy::CostBased)?);
+        let model = crate::model::compact::compact(&model)?;
+        assert!(model.nodes().iter().all(|n| !n.op_is::<MatMul>()));
+        assert_eq!(const_nodes(&model).len(), 2);
+        Ok(())
     }
 }
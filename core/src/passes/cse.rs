@@ -0,0 +1,71 @@
+//! Common subexpression elimination.
+use crate::internal::*;
+
+/// Merge nodes computing the same thing from the same inputs.
+///
+/// Two nodes are merged when they have the same op name, the same inputs, in
+/// the same order, and their ops compare equal with `Op::same_as`. Only
+/// stateless ops are considered. Consumers of the duplicate are rewired to the
+/// first node found in evaluation order, then dead nodes are eliminated.
+///
+/// Returns the number of merged nodes.
+pub fn eliminate_common_subexpressions(model: &mut TypedModel) -> TractResult<usize> {
+    let mut merged = 0;
+    loop {
+        let mut patch = TypedModelPatch::default();
+        let mut seen: HashMap<(String, Vec<OutletId>), Vec<usize>> = HashMap::new();
+        for n in model.eval_order()? {
+            let node = model.node(n);
+            if node.inputs.is_empty() || node.op().as_stateless().is_none() {
+                continue;
+            }
+            let candidates = seen
+                .entry((node.op().name().to_string(), node.inputs.clone()))
+                .or_insert_with(Vec::new);
+            if let Some(&canonical) = candidates.iter().find(|&&c| model.node(c).same_as(node)) {
+                for slot in 0..node.outputs.len() {
+                    let tap = patch.tap_model(model, OutletId::new(canonical, slot))?;
+                    patch.shunt_outside(OutletId::new(n, slot), tap)?;
+                }
+                patch.obliterate(n)?;
+                merged += 1;
+            } else {
+                candidates.push(n);
+            }
+        }
+        if patch.is_empty() {
+            break;
+        }
+        patch.apply(model)?;
+    }
+    super::eliminate_dead_nodes(model)?;
+    Ok(merged)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ops::math;
+    use crate::ops::matmul::MatMul;
+
+    #[test]
+    fn duplicate_matmul() -> TractResult<()> {
+        let mut model = TypedModel::default();
+        let fact = TypedFact::dt_shape(f32::datum_type(), [2usize, 2].as_ref())?;
+        let a = model.add_source("a", fact.clone())?;
+        let b = model.add_source("b", fact)?;
+        let mm1 = model.wire_node("mm1", MatMul::default(), &[a, b])?[0];
+        let mm2 = model.wire_node("mm2", MatMul::default(), &[a, b])?[0];
+        let sum = model.wire_node("sum", math::add::bin(), &[mm1, mm2])?[0];
+        model.set_output_outlets(&[sum])?;
+        assert_eq!(eliminate_common_subexpressions(&mut model)?, 1);
+        assert_eq!(model.nodes().len(), 4);
+        let sum = model.node(model.output_outlets()?[0].node);
+        assert_eq!(sum.inputs[0], sum.inputs[1]);
+        let a = tensor2(&[[1f32, 2.0], [3.0, 4.0]]);
+        let b = tensor2(&[[1f32, 0.0], [0.0, 1.0]]);
+        let result = SimplePlan::new(&model)?.run(tvec!(a, b))?;
+        assert_eq!(result[0], rctensor2(&[[2f32, 4.0], [6.0, 8.0]]));
+        Ok(())
+    }
+}
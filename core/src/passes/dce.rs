@@ -0,0 +1,43 @@
+//! Dead node elimination.
+use crate::internal::*;
+use crate::model::compact;
+
+/// Remove nodes that do not contribute to the model outputs.
+///
+/// Nodes are kept if they can be reached walking backward from the model
+/// outputs, following both inputs and control inputs. Model inputs are always
+/// kept. Surviving nodes are renumbered compactly, so node ids and outlet ids
+/// obtained before the call are invalidated.
+///
+/// Returns the number of removed nodes.
+pub fn eliminate_dead_nodes(model: &mut TypedModel) -> TractResult<usize> {
+    let before = model.nodes().len();
+    *model = compact::compact(model)?;
+    Ok(before - model.nodes().len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ops::math;
+
+    #[test]
+    fn dangling_branch() -> TractResult<()> {
+        let mut model = TypedModel::default();
+        let fact = TypedFact::dt_shape(f32::datum_type(), [2usize].as_ref())?;
+        let a = model.add_source("a", fact)?;
+        let neg = model.wire_node("neg", math::neg(), &[a])?[0];
+        let abs = model.wire_node("abs", math::abs(), &[a])?[0];
+        let _dangling = model.wire_node("dangling", math::exp(), &[abs])?;
+        model.set_output_outlets(&[neg])?;
+        assert_eq!(eliminate_dead_nodes(&mut model)?, 2);
+        assert_eq!(model.nodes().len(), 2);
+        assert!(model.node_by_name("dangling").is_err());
+        let output = model.output_outlets()?[0];
+        assert_eq!(model.node(output.node).name, "neg");
+        assert_eq!(model.node(output.node).inputs, model.input_outlets()?);
+        let result = SimplePlan::new(&model)?.run(tvec!(tensor1(&[1f32, -2.0])))?;
+        assert_eq!(result[0], rctensor1(&[-1f32, 2.0]));
+        Ok(())
+    }
+}
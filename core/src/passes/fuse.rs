@@ -0,0 +1,428 @@
+//! Op fusions.
+use crate::internal::*;
+use crate::ops::binary::{BinMiniOp, UnaryOp};
+use crate::ops::cnn::{ConvUnary, KernelFormat};
+use crate::ops::math::{Add, Div, Mul, Pow, Sqrt, Sub};
+use crate::ops::nn::{LayerNorm, Reducer, TypedReduce};
+use ndarray::Axis;
+
+use crate::optim::{is_binary, is_element_wise, single_use};
+
+/// Extract per-channel values from a tensor broadcast against a tensor of
+/// rank `rank` with `c` channels on `c_axis`.
+fn per_channel(t: &Tensor, rank: usize, c_axis: usize, c: usize) -> Option<Vec<f32>> {
+    if t.datum_type() != f32::datum_type() || t.rank() > rank {
+        return None;
+    }
+    let offset = rank - t.rank();
+    for (ix, &d) in t.shape().iter().enumerate() {
+        if d != 1 && (ix + offset != c_axis || d != c) {
+            return None;
+        }
+    }
+    let values = t.as_slice::<f32>().ok()?;
+    Some(if values.len() == 1 { vec![values[0]; c] } else { values.to_vec() })
+}
+
+fn fused_conv(
+    model: &TypedModel,
+    conv: &TypedNode,
+    mul: &TypedNode,
+    add: &TypedNode,
+) -> TractResult<Option<ConvUnary>> {
+    let op = conv.op_as::<ConvUnary>().unwrap();
+    let (slope, inter) = match (mul.op_as::<UnaryOp>(), add.op_as::<UnaryOp>()) {
+        (Some(mul), Some(add)) => (&mul.a, &add.a),
+        _ => return Ok(None),
+    };
+    if op.q_params.is_some() || op.kernel.datum_type() != f32::datum_type() {
+        return Ok(None);
+    }
+    let output_axis = match op.kernel_fmt {
+        KernelFormat::OIHW => 0,
+        KernelFormat::HWIO if op.group == 1 => op.kernel.rank() - 1,
+        _ => return Ok(None),
+    };
+    let output_shape = conv.outputs[0].fact.shape.to_tvec();
+    let c_axis = op.pool_spec.data_format.shape(&output_shape).c_axis();
+    let c = op.kernel.shape()[output_axis];
+    let (slope, inter) = match (
+        per_channel(slope, output_shape.len(), c_axis, c),
+        per_channel(inter, output_shape.len(), c_axis, c),
+    ) {
+        (Some(slope), Some(inter)) => (slope, inter),
+        _ => return Ok(None),
+    };
+    // the fused conv must produce the same fact as the add
+    let add_fact = model.outlet_fact(OutletId::new(add.id, 0))?;
+    let conv_fact = &conv.outputs[0].fact;
+    if add_fact.datum_type != conv_fact.datum_type || add_fact.shape != conv_fact.shape {
+        return Ok(None);
+    }
+    let mut kernel = op.kernel.clone().into_tensor().into_array::<f32>()?;
+    for (o, mut k) in kernel.axis_iter_mut(Axis(output_axis)).enumerate() {
+        k *= slope[o];
+    }
+    let bias = match &op.bias {
+        Some(bias) => match per_channel(bias, 1, 0, c) {
+            Some(bias) => bias,
+            None => return Ok(None),
+        },
+        None => vec![0.0; c],
+    };
+    let bias: Vec<f32> = (0..c).map(|o| bias[o] * slope[o] + inter[o]).collect();
+    Ok(Some(ConvUnary {
+        kernel: kernel.into_arc_tensor(),
+        bias: Some(rctensor1(&bias)),
+        ..op.clone()
+    }))
+}
+
+/// A node with a single output, used once, and not a model output.
+fn intermediate(model: &TypedModel, outlet: OutletId) -> TractResult<Option<&TypedNode>> {
+    match single_use(model, outlet) {
+        Some(node) if !model.output_outlets()?.contains(&outlet) => Ok(Some(node)),
+        _ => Ok(None),
+    }
+}
+
+fn is_unary<Op: BinMiniOp>(node: &TypedNode) -> bool {
+    node.op_as::<UnaryOp>().map(|op| op.mini_op.is::<Op>()).unwrap_or(false)
+}
+
+/// Matcher for `fuse_conv_batchnorm`, called on the addition.
+pub(crate) fn conv_batchnorm(
+    model: &TypedModel,
+    add: &TypedNode,
+) -> TractResult<Option<TypedModelPatch>> {
+    if !is_unary::<Add>(add) {
+        return Ok(None);
+    }
+    let mul = match intermediate(model, add.inputs[0])? {
+        Some(mul) if is_unary::<Mul>(mul) => mul,
+        _ => return Ok(None),
+    };
+    let conv = match intermediate(model, mul.inputs[0])? {
+        Some(conv) if conv.op_is::<ConvUnary>() => conv,
+        _ => return Ok(None),
+    };
+    if let Some(op) = fused_conv(model, conv, mul, add)? {
+        let mut patch = TypedModelPatch::default();
+        let tap = patch.tap_model(model, conv.inputs[0])?;
+        let fused = patch.wire_node(&*conv.name, op, &[tap])?[0];
+        patch.shunt_outside(OutletId::new(add.id, 0), fused)?;
+        Ok(Some(patch))
+    } else {
+        Ok(None)
+    }
+}
+
+/// Fuse batch normalization into the preceding convolution.
+///
+/// Once translated to a TypedModel, a BatchNorm with constant parameters is a
+/// per-channel multiplication followed by a per-channel addition. Its slope is
+/// absorbed in the convolution kernel, and the intercept in the bias, so that
+/// with `slope = gamma / sqrt(var + eps)`:
+///
+/// ```text
+/// W' = W * slope
+/// b' = (b - mean) * slope + beta
+/// ```
+///
+/// The convolution and the multiplication outputs must have no other uses.
+/// The fusion is part of declutter: this runs it alone.
+///
+/// Returns the number of fusions.
+pub fn fuse_conv_batchnorm(model: &mut TypedModel) -> TractResult<usize> {
+    let done = crate::optim::fuse_all(model, conv_batchnorm)?;
+    super::eliminate_dead_nodes(model)?;
+    Ok(done)
+}
+
+/// Reduce a constant broadcast against the trailing `norm_rank` axes of a
+/// tensor of rank `rank` to these trailing axes.
+fn per_normalized(t: &Tensor, rank: usize, norm_rank: usize) -> Option<Tensor> {
+    if t.datum_type() != f32::datum_type() || t.rank() > rank {
+        return None;
+    }
+    let extra = t.rank().saturating_sub(norm_rank);
+    if t.shape()[..extra].iter().any(|&d| d != 1) {
+        return None;
+    }
+    Some(t.to_array_view::<f32>().ok()?.into_shape(&t.shape()[extra..]).ok()?.to_owned().into())
+}
+
+/// The constant and input of a unary `Op` node.
+fn unary_const<Op: BinMiniOp>(
+    model: &TypedModel,
+    outlet: OutletId,
+) -> TractResult<Option<(&TypedNode, Arc<Tensor>)>> {
+    match intermediate(model, outlet)? {
+        Some(node) if is_unary::<Op>(node) => {
+            Ok(Some((node, node.op_as::<UnaryOp>().unwrap().a.clone())))
+        }
+        _ => Ok(None),
+    }
+}
+
+fn mean_axes(node: &TypedNode) -> Option<&TVec<usize>> {
+    match node.op_as::<TypedReduce>() {
+        Some(TypedReduce { axes, reducer: Reducer::Mean }) => Some(axes),
+        _ => None,
+    }
+}
+
+/// Matcher for `fuse_layer_norm`, called on the bias addition.
+pub(crate) fn layer_norm(
+    model: &TypedModel,
+    node: &TypedNode,
+) -> TractResult<Option<TypedModelPatch>> {
+    if !is_unary::<Add>(node) {
+        return Ok(None);
+    }
+    let bias = node.op_as::<UnaryOp>().unwrap().a.clone();
+    let (mul, scale) = match unary_const::<Mul>(model, node.inputs[0])? {
+        Some(found) => found,
+        None => return Ok(None),
+    };
+    let div = match intermediate(model, mul.inputs[0])? {
+        Some(div) if is_binary::<Div>(div) => div,
+        _ => return Ok(None),
+    };
+    let sqrt = match intermediate(model, div.inputs[1])? {
+        Some(sqrt) if is_element_wise::<Sqrt>(sqrt) => sqrt,
+        _ => return Ok(None),
+    };
+    let (add_eps, eps) = match unary_const::<Add>(model, sqrt.inputs[0])? {
+        Some(found) => found,
+        None => return Ok(None),
+    };
+    let var = match intermediate(model, add_eps.inputs[0])? {
+        Some(var) if mean_axes(var).is_some() => var,
+        _ => return Ok(None),
+    };
+    let pow = match intermediate(model, var.inputs[0])? {
+        Some(pow) if is_binary::<Pow>(pow) && pow.inputs[0] == div.inputs[0] => pow,
+        _ => return Ok(None),
+    };
+    // the centered input feeds both the variance and the division
+    let sub = model.node(div.inputs[0].node);
+    if !is_binary::<Sub>(sub)
+        || sub.outputs[0].successors.len() != 2
+        || model.output_outlets()?.contains(&div.inputs[0])
+    {
+        return Ok(None);
+    }
+    let mean = match intermediate(model, sub.inputs[1])? {
+        Some(mean) if mean_axes(mean).is_some() && mean.inputs[0] == sub.inputs[0] => mean,
+        _ => return Ok(None),
+    };
+    let input = model.outlet_fact(mean.inputs[0])?;
+    if input.datum_type != f32::datum_type() {
+        return Ok(None);
+    }
+    let rank = input.rank();
+    let axes = mean_axes(mean).unwrap();
+    if axes.is_empty()
+        || Some(axes) != mean_axes(var)
+        || axes.iter().enumerate().any(|(ix, &ax)| ax != rank - axes.len() + ix)
+    {
+        return Ok(None);
+    }
+    match &model.outlet_fact(pow.inputs[1])?.konst {
+        Some(exp) if exp.datum_type() == f32::datum_type() && exp.len() == 1 => {
+            if exp.as_slice::<f32>()?[0] != 2.0 {
+                return Ok(None);
+            }
+        }
+        _ => return Ok(None),
+    }
+    if eps.datum_type() != f32::datum_type() || eps.len() != 1 {
+        return Ok(None);
+    }
+    let norm_rank = axes.len();
+    let (scale, bias) =
+        match (per_normalized(&scale, rank, norm_rank), per_normalized(&bias, rank, norm_rank)) {
+            (Some(scale), Some(bias)) => (scale, bias),
+            _ => return Ok(None),
+        };
+    if node.outputs[0].fact.shape != input.shape {
+        return Ok(None);
+    }
+    let op = LayerNorm::new((rank - norm_rank) as i64, eps.as_slice::<f32>()?[0]);
+    let name = &*node.name;
+    let mut patch = TypedModelPatch::default();
+    let tap = patch.tap_model(model, mean.inputs[0])?;
+    let scale = patch.add_const(format!("{}.scale", name), scale)?;
+    let bias = patch.add_const(format!("{}.bias", name), bias)?;
+    let fused = patch.wire_node(name, op, &[tap, scale, bias])?[0];
+    patch.shunt_outside(OutletId::new(node.id, 0), fused)?;
+    Ok(Some(patch))
+}
+
+/// Fuse the decomposed layer normalization found in ONNX exports prior to
+/// opset 17 into a single LayerNorm:
+///
+/// ```text
+/// d = x - ReduceMean(x)
+/// y = d / Sqrt(ReduceMean(Pow(d, 2)) + eps) * scale + bias
+/// ```
+///
+/// The mean and variance must be taken over trailing axes, epsilon, scale
+/// and bias must be constants, and the intermediate values must have no
+/// other uses. The fusion is part of declutter: this runs it alone.
+///
+/// Returns the number of fusions.
+pub fn fuse_layer_norm(model: &mut TypedModel) -> TractResult<usize> {
+    let done = crate::optim::fuse_all(model, layer_norm)?;
+    super::eliminate_dead_nodes(model)?;
+    Ok(done)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ops::cnn::Conv;
+    use crate::ops::math;
+
+    fn conv_with_batchnorm(bias: Tensor) -> TractResult<TypedModel> {
+        let mut model = TypedModel::default();
+        let x = model
+            .add_source("x", TypedFact::dt_shape(f32::datum_type(), [1usize, 2, 3, 3].as_ref())?)?;
+        let kernel = tensor4(&[[[[1f32]], [[2.0]]], [[[-1.0]], [[0.5]]], [[[0.0]], [[3.0]]]]);
+        let conv =
+            ConvUnary::new(&Conv::default(), kernel.into_arc_tensor(), 1, Some(bias.into()), None)?;
+        let conv = model.wire_node("conv", conv, &[x])?[0];
+        let slope = tensor3(&[[[2f32]], [[0.5]], [[-1.0]]]).into_arc_tensor();
+        let inter = tensor3(&[[[1f32]], [[0.0]], [[2.0]]]).into_arc_tensor();
+        let mul = model.wire_node("bn-mul", math::mul::unary(slope), &[conv])?[0];
+        let add = model.wire_node("bn", math::add::unary(inter), &[mul])?[0];
+        model.set_output_outlets(&[add])?;
+        Ok(model)
+    }
+
+    fn conv_input() -> TractResult<Tensor> {
+        Ok(ndarray::Array1::range(0f32, 18.0, 1.0).into_shape((1, 2, 3, 3))?.into())
+    }
+
+    #[test]
+    fn conv_batchnorm() -> TractResult<()> {
+        let mut model = conv_with_batchnorm(tensor1(&[0.5f32, 1.0, -1.0]))?;
+        let expected = SimplePlan::new(&model)?.run(tvec!(conv_input()?))?;
+        assert_eq!(fuse_conv_batchnorm(&mut model)?, 1);
+        assert_eq!(model.nodes().len(), 2);
+        let fused = model.node(model.output_outlets()?[0].node);
+        assert!(fused.op_is::<ConvUnary>());
+        let found = SimplePlan::new(&model)?.run(tvec!(conv_input()?))?;
+        found[0].close_enough(&expected[0], true)
+    }
+
+    #[test]
+    fn conv_batchnorm_in_declutter() -> TractResult<()> {
+        let model = conv_with_batchnorm(tensor1(&[0.5f32, 1.0, -1.0]))?;
+        let expected = SimplePlan::new(&model)?.run(tvec!(conv_input()?))?;
+        let model = model.declutter()?;
+        assert_eq!(model.nodes().len(), 2);
+        assert!(model.node(model.output_outlets()?[0].node).op_is::<ConvUnary>());
+        let found = SimplePlan::new(&model.into_optimized()?)?.run(tvec!(conv_input()?))?;
+        found[0].close_enough(&expected[0], true)
+    }
+
+    #[test]
+    fn conv_batchnorm_unusual_bias_is_skipped() -> TractResult<()> {
+        let bias = tensor2(&[[0.5f32], [1.0], [-1.0]]);
+        let mut model = conv_with_batchnorm(bias.clone())?;
+        assert_eq!(fuse_conv_batchnorm(&mut model)?, 0);
+        let model = conv_with_batchnorm(bias)?.declutter()?;
+        assert_eq!(model.nodes().len(), 4);
+        Ok(())
+    }
+
+    fn decomposed_layer_norm() -> TractResult<TypedModel> {
+        use crate::ops::nn::Reduce;
+        let mut model = InferenceModel::default();
+        let fact = InferenceFact::dt_shape(f32::datum_type(), [2usize, 3, 4].as_ref());
+        let x = model.add_source("x", fact)?;
+        let two = model.add_const("two", tensor0(2f32))?;
+        let eps = model.add_const("eps", tensor0(1e-5f32))?;
+        let scale = model.add_const("scale", tensor1(&[1f32, 0.5, 2.0, -1.0]))?;
+        let bias = model.add_const("bias", tensor1(&[0f32, 1.0, -1.0, 0.5]))?;
+        let mean_op = Reduce::new(Some(vec![-1]), true, Reducer::Mean);
+        let mean = model.wire_node("mean", mean_op.clone(), &[x])?[0];
+        let d = model.wire_node("d", math::sub::bin(), &[x, mean])?[0];
+        let sq = model.wire_node("sq", math::pow::bin(), &[d, two])?[0];
+        let var = model.wire_node("var", mean_op, &[sq])?[0];
+        let var_eps = model.wire_node("var_eps", math::add::bin(), &[var, eps])?[0];
+        let std = model.wire_node("std", math::sqrt(), &[var_eps])?[0];
+        let norm = model.wire_node("norm", math::div::bin(), &[d, std])?[0];
+        let scaled = model.wire_node("scaled", math::mul::bin(), &[norm, scale])?[0];
+        let output = model.wire_node("ln", math::add::bin(), &[scaled, bias])?[0];
+        model.set_output_outlets(&[output])?;
+        model.into_typed()
+    }
+
+    // The same, as left by declutter without the fusion.
+    fn unfused_layer_norm() -> TractResult<TypedModel> {
+        use crate::ops::binary::TypedBinOp;
+        let mut model = TypedModel::default();
+        let fact = TypedFact::dt_shape(f32::datum_type(), [2usize, 3, 4].as_ref())?;
+        let x = model.add_source("x", fact)?;
+        let two = model.add_const("two", rctensor0(2f32))?;
+        let mean_op = TypedReduce::new(tvec!(2), Reducer::Mean);
+        let mean = model.wire_node("mean", mean_op.clone(), &[x])?[0];
+        let d = model.wire_node("d", TypedBinOp(Box::new(Sub)), &[x, mean])?[0];
+        let sq = model.wire_node("sq", TypedBinOp(Box::new(Pow)), &[d, two])?[0];
+        let var = model.wire_node("var", mean_op, &[sq])?[0];
+        let var_eps = model.wire_node("var_eps", math::add::unary(rctensor0(1e-5f32)), &[var])?;
+        let std = model.wire_node("std", math::sqrt(), &var_eps)?[0];
+        let norm = model.wire_node("norm", TypedBinOp(Box::new(Div)), &[d, std])?[0];
+        let scale = rctensor1(&[1f32, 0.5, 2.0, -1.0]);
+        let scaled = model.wire_node("scaled", math::mul::unary(scale), &[norm])?;
+        let bias = rctensor1(&[0f32, 1.0, -1.0, 0.5]);
+        let output = model.wire_node("ln", math::add::unary(bias), &scaled)?;
+        model.set_output_outlets(&output)?;
+        Ok(model)
+    }
+
+    fn layer_norm_input() -> TractResult<Tensor> {
+        Ok(ndarray::Array1::range(0f32, 24.0, 1.0)
+            .mapv(|x| (x * 0.7).sin() * 10.0)
+            .into_shape((2, 3, 4))?
+            .into())
+    }
+
+    fn assert_close(found: &Tensor, expected: &Tensor) -> TractResult<()> {
+        let found = found.to_array_view::<f32>()?;
+        let expected = expected.to_array_view::<f32>()?;
+        for (f, e) in found.iter().zip(expected.iter()) {
+            assert!((f - e).abs() < 1e-5, "{} != {}", f, e);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn fuse_layer_norm_alone() -> TractResult<()> {
+        let mut model = unfused_layer_norm()?;
+        let expected = SimplePlan::new(&model)?.run(tvec!(layer_norm_input()?))?;
+        assert_eq!(fuse_layer_norm(&mut model)?, 1);
+        let fused = model.node(model.output_outlets()?[0].node);
+        assert!(fused.op_is::<LayerNorm>());
+        assert_eq!(model.nodes().len(), 4);
+        let found = SimplePlan::new(&model)?.run(tvec!(layer_norm_input()?))?;
+        assert_close(&found[0], &expected[0])
+    }
+
+    #[test]
+    fn layer_norm_in_declutter() -> TractResult<()> {
+        let model = decomposed_layer_norm()?;
+        let expected = SimplePlan::new(&model)?.run(tvec!(layer_norm_input()?))?;
+        let decluttered = model.declutter()?;
+        let fused = decluttered.node(decluttered.output_outlets()?[0].node);
+        assert!(fused.op_is::<LayerNorm>());
+        assert_eq!(decluttered.nodes().len(), 4);
+        let optimized = decomposed_layer_norm()?.into_optimized()?;
+        assert!(optimized.nodes().iter().any(|n| n.op_is::<LayerNorm>()));
+        let found = SimplePlan::new(&optimized)?.run(tvec!(layer_norm_input()?))?;
+        assert_close(&found[0], &expected[0])
+    }
+}
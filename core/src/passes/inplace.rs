@@ -0,0 +1,143 @@
+//! In-place evaluation of element-wise operators.
+use std::collections::HashSet;
+
+use crate::internal::*;
+use crate::ops::binary::{MergeOpUnicast, UnaryOp};
+use crate::ops::element_wise::ElementWiseOp;
+use crate::ops::konst::Const;
+use crate::ops::source::TypedSource;
+
+/// Index of the input an op can overwrite with its output, if any.
+fn inplace_slot(model: &TypedModel, node: &TypedNode) -> TractResult<Option<usize>> {
+    if let Some(op) = node.op_as::<UnaryOp>() {
+        let input = model.outlet_fact(node.inputs[0])?;
+        if op.a.datum_type() == input.datum_type {
+            return Ok(Some(0));
+        }
+    } else if node.op_is::<ElementWiseOp>() {
+        return Ok(Some(0));
+    } else if node.op_is::<MergeOpUnicast>() {
+        return Ok(Some(1));
+    }
+    Ok(None)
+}
+
+/// Find the outlets whose tensor can be overwritten by their consumer.
+///
+/// Returns a map from the input outlet to the output outlet reusing its
+/// buffer. A pair is reported when the consumer is an element-wise op able to
+/// write over this input, the outlet feeds nothing else and is not a model
+/// output, and input and output share datum type and shape. Outlets of sources
+/// and constants are never reported: their tensors are held by the session or
+/// the op itself. Neither are outputs of multi-output nodes, as the plan keeps
+/// all the outputs of a node until the last one is consumed.
+pub fn mark_inplace_candidates(model: &TypedModel) -> TractResult<HashMap<OutletId, OutletId>> {
+    let outputs = model.output_outlets()?;
+    let mut candidates = HashMap::new();
+    for node in model.nodes() {
+        if node.outputs.len() != 1 {
+            continue;
+        }
+        let slot = if let Some(slot) = inplace_slot(model, node)? { slot } else { continue };
+        let input = node.inputs[slot];
+        let prec = model.node(input.node);
+        if prec.op_is::<TypedSource>() || prec.op_is::<Const>() || prec.outputs.len() != 1 {
+            continue;
+        }
+        if prec.outputs[input.slot].successors.len() != 1 || outputs.contains(&input) {
+            continue;
+        }
+        let input_fact = model.outlet_fact(input)?;
+        let output_fact = &node.outputs[0].fact;
+        if input_fact.datum_type != output_fact.datum_type || input_fact.shape != output_fact.shape
+        {
+            continue;
+        }
+        candidates.insert(input, OutletId::new(node.id, 0));
+    }
+    Ok(candidates)
+}
+
+/// Flag the ops found by `mark_inplace_candidates` for in-place evaluation.
+///
+/// Only `UnaryOp` needs a flag: the other element-wise ops always reuse an
+/// input they hold the only reference to. Flags on ops that are no longer
+/// candidates are cleared. Returns the number of flagged ops.
+pub fn set_inplace(model: &mut TypedModel) -> TractResult<usize> {
+    let candidates = mark_inplace_candidates(model)?;
+    let consumers: HashSet<usize> = candidates.values().map(|o| o.node).collect();
+    let mut flagged = 0;
+    for id in 0..model.nodes().len() {
+        if let Some(op) = model.node_mut(id).op_as_mut::<UnaryOp>() {
+            op.inplace = consumers.contains(&id);
+            if op.inplace {
+                flagged += 1;
+            }
+        }
+    }
+    Ok(flagged)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ops::math;
+
+    #[test]
+    fn chain() -> TractResult<()> {
+        let mut model = TypedModel::default();
+        let fact = TypedFact::dt_shape(f32::datum_type(), [2usize, 3].as_ref())?;
+        let a = model.add_source("a", fact)?;
+        let neg = model.wire_node("neg", math::neg(), &[a])?[0];
+        let bias = rctensor2(&[[1f32], [2.0]]);
+        let add = model.wire_node("add", math::add::unary(bias), &[neg])?[0];
+        let scale = model.wire_node("scale", math::mul::unary(rctensor0(2f32)), &[add])?[0];
+        let relu = model.wire_node("relu", math::max::unary(rctensor0(0f32)), &[scale])?[0];
+        let abs = model.wire_node("abs", math::abs(), &[scale])?[0];
+        model.set_output_outlets(&[relu, abs])?;
+
+        let candidates = mark_inplace_candidates(&model)?;
+        // neg reads a source, scale has two consumers
+        assert_eq!(candidates.len(), 2);
+        assert_eq!(candidates[&neg], add);
+        assert_eq!(candidates[&add], scale);
+
+        assert_eq!(set_inplace(&mut model)?, 2);
+        assert!(model.node(add.node).op_as::<UnaryOp>().unwrap().inplace);
+        assert!(!model.node(relu.node).op_as::<UnaryOp>().unwrap().inplace);
+
+        let input = tensor2(&[[1f32, -2.0, 3.0], [-4.0, 5.0, -6.0]]);
+        let result = SimplePlan::new(&model)?.run(tvec!(input))?;
+        assert_eq!(result[0], rctensor2(&[[0f32, 6.0, 0.0], [12.0, 0.0, 16.0]]));
+        assert_eq!(result[1], rctensor2(&[[0f32, 6.0, 4.0], [12.0, 6.0, 16.0]]));
+        Ok(())
+    }
+
+    #[test]
+    fn reuses_buffer() -> TractResult<()> {
+        let mut model = TypedModel::default();
+        let fact = TypedFact::dt_shape(f32::datum_type(), [4usize].as_ref())?;
+        let a = model.add_source("a", fact)?;
+        let neg = model.wire_node("neg", math::neg(), &[a])?[0];
+        let add = model.wire_node("add", math::add::unary(rctensor0(1f32)), &[neg])?[0];
+        model.set_output_outlets(&[add])?;
+        set_inplace(&mut model)?;
+        let plan = SimplePlan::new(&model)?;
+        let mut state = SimpleState::new(&plan)?;
+        let mut neg_ptr = None;
+        let result = state.run_plan_with_eval(
+            tvec!(tensor1(&[1f32, 2.0, 3.0, 4.0])),
+            0,
+            |session, op_state, node, inputs| {
+                let outputs = crate::plan::eval(session, op_state, node, inputs)?;
+                if node.id == neg.node {
+                    neg_ptr = Some(outputs[0].as_slice::<f32>()?.as_ptr());
+                }
+                Ok(outputs)
+            },
+        )?;
+        assert_eq!(result[0], rctensor1(&[0f32, -1.0, -2.0, -3.0]));
+        assert_eq!(neg_ptr, Some(result[0].as_slice::<f32>()?.as_ptr()));
+        Ok(())
+    }
+}
@@ -0,0 +1,23 @@
+//! Standalone transformations on TypedModel.
+//!
+//! Unlike the declutter and codegen passes, these can be called one at a
+//! time on a model, and report how much of the graph they changed.
+pub mod cse;
+pub mod dce;
+pub mod fuse;
+pub mod inplace;
+pub mod nan_checks;
+pub mod pattern;
+pub mod quantize;
+pub mod simplify;
+pub mod transpose;
+
+pub use self::cse::eliminate_common_subexpressions;
+pub use self::dce::eliminate_dead_nodes;
+pub use self::fuse::{fuse_conv_batchnorm, fuse_layer_norm};
+pub use self::inplace::{mark_inplace_candidates, set_inplace};
+pub use self::nan_checks::{insert_nan_checks, remove_nan_checks};
+pub use self::pattern::{PatternInput, PatternMatcher, PatternNode};
+pub use self::quantize::quantize_dynamic_range;
+pub use self::simplify::{simplify_algebra, RewriteRule};
+pub use self::transpose::fuse_transposes;
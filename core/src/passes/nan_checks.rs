@@ -0,0 +1,93 @@
+//! Insertion and removal of non-finite value checks.
+use crate::internal::*;
+use crate::ops::debug::CheckFinite;
+
+/// Insert a `CheckFinite` op after every floating point output of the model.
+///
+/// Evaluating the model will then fail at the first node producing a NaN or
+/// an infinite value, with an error naming that node. Outputs already
+/// checked are left alone, so calling this twice is harmless.
+pub fn insert_nan_checks(model: &mut TypedModel) -> TractResult<()> {
+    for id in 0..model.nodes().len() {
+        if model.node(id).op_is::<CheckFinite>() {
+            continue;
+        }
+        for slot in 0..model.node(id).outputs.len() {
+            let outlet = OutletId::new(id, slot);
+            let node = model.node(id);
+            if !node.outputs[slot].fact.datum_type.is_float()
+                || node.outputs[slot]
+                    .successors
+                    .iter()
+                    .any(|s| model.node(s.node).op_is::<CheckFinite>())
+            {
+                continue;
+            }
+            let successors = node.outputs[slot].successors.clone();
+            let name = if slot == 0 {
+                format!("{}.check_finite", node.name)
+            } else {
+                format!("{}.check_finite-{}", node.name, slot)
+            };
+            let op = CheckFinite::new(node.name.clone());
+            let check = model.wire_node(name, op, &[outlet])?[0];
+            for succ in successors {
+                model.add_edge(check, succ)?;
+            }
+            let outputs: TVec<OutletId> = model
+                .output_outlets()?
+                .iter()
+                .map(|&o| if o == outlet { check } else { o })
+                .collect();
+            model.set_output_outlets(&outputs)?;
+        }
+    }
+    Ok(())
+}
+
+/// Remove all `CheckFinite` ops from the model.
+///
+/// Node ids and outlet ids obtained before the call are invalidated.
+pub fn remove_nan_checks(model: &mut TypedModel) -> TractResult<()> {
+    for id in 0..model.nodes().len() {
+        if model.node(id).op_is::<CheckFinite>() {
+            TypedModelPatch::shunt_one_op(model, model.node(id))?.apply(model)?;
+        }
+    }
+    super::eliminate_dead_nodes(model)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ops::math;
+
+    #[test]
+    fn first_nan_is_reported() -> TractResult<()> {
+        let mut model = TypedModel::default();
+        let fact = TypedFact::dt_shape(f32::datum_type(), [2usize].as_ref())?;
+        let a = model.add_source("a", fact)?;
+        let ln = model.wire_node("ln", math::ln(), &[a])?[0];
+        let abs = model.wire_node("abs", math::abs(), &[ln])?[0];
+        model.set_output_outlets(&[abs])?;
+        let nodes = model.nodes().len();
+
+        insert_nan_checks(&mut model)?;
+        insert_nan_checks(&mut model)?;
+        assert_eq!(model.nodes().len(), 2 * nodes);
+        let plan = SimplePlan::new(&model)?;
+        assert!(plan.run(tvec!(tensor1(&[1f32, 2.0])))?[0]
+            .close_enough(&tensor1(&[0f32, 2f32.ln()]), true)
+            .is_ok());
+        let err = plan.run(tvec!(tensor1(&[1f32, -1.0]))).unwrap_err();
+        assert!(format!("{:?}", err).contains("output of ln"));
+
+        remove_nan_checks(&mut model)?;
+        assert_eq!(model.nodes().len(), nodes);
+        assert!(model.nodes().iter().all(|n| !n.op_is::<CheckFinite>()));
+        let output = model.output_outlets()?[0];
+        assert_eq!(model.node(output.node).name, "abs");
+        Ok(())
+    }
+}
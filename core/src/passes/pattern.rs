@@ -0,0 +1,152 @@
+//! Subgraph pattern matching.
+//!
+//! Patterns describe op names and connectivity only. The fusions of the
+//! FusePatterns optimisation, which need to check op attributes while they
+//! walk the graph, are written as matcher functions instead.
+use crate::internal::*;
+
+/// An input of a pattern node.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PatternInput {
+    /// Fed by any outlet.
+    Any,
+    /// Fed by the first output of another pattern node, by index.
+    Node(usize),
+}
+
+/// A node of a pattern.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PatternNode {
+    /// Name of the op, as returned by `Op::name`.
+    pub op: String,
+    /// Inputs of the node. If not empty, the node must have exactly as many
+    /// inputs.
+    pub inputs: Vec<PatternInput>,
+}
+
+impl PatternNode {
+    pub fn new(op: impl Into<String>, inputs: Vec<PatternInput>) -> PatternNode {
+        PatternNode { op: op.into(), inputs }
+    }
+}
+
+/// Finds subgraphs matching a pattern.
+///
+/// The last node of the pattern is its root. All other nodes must be reached
+/// from the root following `PatternInput::Node` inputs. A match maps each
+/// pattern node index to a model node id.
+///
+/// Matching only considers op names and connectivity: callers are expected
+/// to check attributes, and whether the intermediate nodes are used outside
+/// the match, before rewriting.
+#[derive(Debug, Clone)]
+pub struct PatternMatcher {
+    pattern: Vec<PatternNode>,
+}
+
+impl PatternMatcher {
+    pub fn new(pattern: Vec<PatternNode>) -> PatternMatcher {
+        PatternMatcher { pattern }
+    }
+
+    /// All the matches in the model, in evaluation order of the root node.
+    pub fn find_all(&self, model: &TypedModel) -> TractResult<Vec<HashMap<usize, usize>>> {
+        let mut matches = vec![];
+        if self.pattern.is_empty() {
+            return Ok(matches);
+        }
+        for n in model.eval_order()? {
+            let mut assignment = HashMap::new();
+            if self.match_node(model, self.pattern.len() - 1, n, &mut assignment) {
+                if assignment.len() != self.pattern.len() {
+                    bail!(
+                        "Pattern nodes must all be reachable from the last one: {:?}",
+                        self.pattern
+                    )
+                }
+                matches.push(assignment);
+            }
+        }
+        Ok(matches)
+    }
+
+    /// Call `rewrite` on matches until it stops changing the model.
+    ///
+    /// `rewrite` returns true if it has changed the model, in which case the
+    /// matches are searched again. Returns the number of rewrites.
+    pub fn rewrite<F>(&self, model: &mut TypedModel, mut rewrite: F) -> TractResult<usize>
+    where
+        F: FnMut(&mut TypedModel, &HashMap<usize, usize>) -> TractResult<bool>,
+    {
+        let mut done = 0;
+        'search: loop {
+            for m in self.find_all(model)? {
+                if rewrite(model, &m)? {
+                    done += 1;
+                    continue 'search;
+                }
+            }
+            return Ok(done);
+        }
+    }
+
+    fn match_node(
+        &self,
+        model: &TypedModel,
+        ix: usize,
+        node: usize,
+        assignment: &mut HashMap<usize, usize>,
+    ) -> bool {
+        if let Some(&assigned) = assignment.get(&ix) {
+            return assigned == node;
+        }
+        let pattern = &self.pattern[ix];
+        let node = model.node(node);
+        if node.op().name() != pattern.op {
+            return false;
+        }
+        if !pattern.inputs.is_empty() && pattern.inputs.len() != node.inputs.len() {
+            return false;
+        }
+        assignment.insert(ix, node.id);
+        for (input, outlet) in pattern.inputs.iter().zip(node.inputs.iter()) {
+            if let PatternInput::Node(prec) = input {
+                if outlet.slot != 0 || !self.match_node(model, *prec, outlet.node, assignment) {
+                    return false;
+                }
+            }
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ops::binary::TypedBinOp;
+    use crate::ops::math;
+
+    fn add_relu() -> PatternMatcher {
+        PatternMatcher::new(vec![
+            PatternNode::new("AddTyped", vec![PatternInput::Any, PatternInput::Any]),
+            PatternNode::new("MaxUnary", vec![PatternInput::Node(0)]),
+        ])
+    }
+
+    #[test]
+    fn add_then_relu() -> TractResult<()> {
+        let mut model = TypedModel::default();
+        let fact = TypedFact::dt_shape(f32::datum_type(), [2usize].as_ref())?;
+        let a = model.add_source("a", fact.clone())?;
+        let b = model.add_source("b", fact)?;
+        let add = model.wire_node("add", TypedBinOp(Box::new(math::Add)), &[a, b])?[0];
+        let relu = model.wire_node("relu", math::max::unary(rctensor0(0f32)), &[add])?[0];
+        let other = model.wire_node("other", math::max::unary(rctensor0(0f32)), &[a])?[0];
+        model.set_output_outlets(&[relu, other])?;
+        let matches = add_relu().find_all(&model)?;
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0][&0], add.node);
+        assert_eq!(matches[0][&1], relu.node);
+        Ok(())
+    }
+}
@@ -0,0 +1,189 @@
+//! Post-training weight quantization.
+use crate::internal::*;
+use crate::ops::binary::TypedBinOp;
+use crate::ops::math;
+use crate::ops::matmul::{MatMul, MatMulUnary};
+use crate::ops::quant::{DequantizeLinearF32, DynamicQuantizeLinearI8, QParams};
+
+/// Symmetric per-tensor int8 quantization: the scale maps the largest
+/// absolute value to 127, and the zero point is 0.
+fn quantize_weights(weights: &Tensor) -> TractResult<Option<(Tensor, f32)>> {
+    if weights.datum_type() != f32::datum_type() {
+        return Ok(None);
+    }
+    let values = weights.as_slice::<f32>()?;
+    let max = values.iter().fold(0.0f32, |acc, x| acc.max(x.abs()));
+    if max == 0.0 || !max.is_finite() {
+        return Ok(None);
+    }
+    let scale = max / 127.0;
+    let q: Vec<i8> =
+        values.iter().map(|&x| (x / scale).round().max(-127.0).min(127.0) as i8).collect();
+    Ok(Some((Tensor::from(ndarray::Array::from_shape_vec(weights.shape(), q)?), scale)))
+}
+
+/// Dynamic range quantization, as in ONNX Runtime: constant float weights
+/// are stored as int8, activations stay float between ops and are quantized
+/// on the fly for the products.
+///
+/// Only matrix products benefit from it. A MatMul (or MatMulUnary) with a
+/// constant float operand and a variable float one becomes an int8
+/// product: the constant is replaced by its int8 equivalent, using a scale
+/// of `max(abs(weights)) / 127`, the variable goes through a
+/// DynamicQuantizeLinearI8, and the i32 result through a DequantizeLinear
+/// and the scale of the variable. Other ops, like the element-wise ones
+/// applying biases, are left untouched, as are products which are already
+/// quantized.
+///
+/// Codegen keeps the int8 weights, packed for the int8 matrix product
+/// kernel.
+///
+/// Node ids and outlet ids obtained before the call are invalidated.
+///
+/// Returns the number of quantized ops.
+pub fn quantize_dynamic_range(model: &mut TypedModel) -> TractResult<usize> {
+    let mut done = 0;
+    for id in model.eval_order()? {
+        let node = model.node(id);
+        // operands, either tapped from the model or embedded in the op
+        let (op, operands): (MatMul, TVec<(Option<OutletId>, Option<Arc<Tensor>>)>) =
+            if let Some(op) = node.op_as::<MatMul>() {
+                let operands = node
+                    .inputs
+                    .iter()
+                    .map(|&i| Ok((Some(i), model.outlet_fact(i)?.konst.clone())))
+                    .collect::<TractResult<_>>()?;
+                (op.clone(), operands)
+            } else if let Some(op) = node.op_as::<MatMulUnary>() {
+                (op.to_binary(), tvec!((None, Some(op.a().clone())), (Some(node.inputs[0]), None)))
+            } else {
+                continue;
+            };
+        if op.q_params().is_some() {
+            continue;
+        }
+        let (weights_ix, konst, input) = match (&operands[0], &operands[1]) {
+            ((_, Some(konst)), (Some(input), None)) => (0, konst, *input),
+            ((Some(input), None), (_, Some(konst))) => (1, konst, *input),
+            _ => continue,
+        };
+        if model.outlet_fact(input)?.datum_type != f32::datum_type() {
+            continue;
+        }
+        let (weights, scale) = if let Some(q) = quantize_weights(konst)? {
+            q
+        } else {
+            continue;
+        };
+        let mut patch = TypedModelPatch::default();
+        let weights = patch.add_const(format!("{}.weights", node.name), weights)?;
+        let input = patch.tap_model(model, input)?;
+        let quantized = patch.wire_node(
+            format!("{}.quantize-input", node.name),
+            DynamicQuantizeLinearI8,
+            &[input],
+        )?;
+        let mut wires = tvec!(quantized[0]);
+        wires.insert(weights_ix, weights);
+        let op = op.with_q_params(QParams::new(i32::datum_type()));
+        let wire = patch.wire_node(format!("{}.product", node.name), op, &wires)?[0];
+        let dequant = DequantizeLinearF32::new(scale, 0);
+        let wire = patch.wire_node(format!("{}.dequantize", node.name), dequant, &[wire])?[0];
+        let rescale = TypedBinOp(Box::new(math::Mul));
+        let wire = patch.wire_node(&*node.name, rescale, &[wire, quantized[1]])?[0];
+        patch.shunt_outside(OutletId::new(id, 0), wire)?;
+        patch.apply(model)?;
+        done += 1;
+    }
+    super::eliminate_dead_nodes(model)?;
+    Ok(done)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ops::matmul::phy::MatMatMulUnaryFinite;
+
+    fn weights(rows: usize, cols: usize, seed: f32) -> Tensor {
+        let values = (0..rows * cols).map(|i| ((i as f32 + seed) * 0.73).sin()).collect();
+        Tensor::from(ndarray::Array::from_shape_vec((rows, cols), values).unwrap())
+    }
+
+    fn two_layers() -> TractResult<TypedModel> {
+        let mut model = TypedModel::default();
+        let x =
+            model.add_source("x", TypedFact::dt_shape(f32::datum_type(), [2usize, 4].as_ref())?)?;
+        let w1 = model.add_const("w1", weights(4, 8, 0.0))?;
+        let y = model.wire_node("fc1", MatMul::default(), &[x, w1])?[0];
+        let y = model.wire_node("relu", math::scalar_max(tensor0(0f32)), &[y])?[0];
+        let w2 = model.add_const("w2", weights(8, 3, 5.0))?;
+        let y = model.wire_node("fc2", MatMul::default(), &[y, w2])?[0];
+        let bias = model.add_const("bias", tensor1(&[0.5f32, -0.25, 1.0]))?;
+        let y = model.wire_node("bias", math::add::bin(), &[y, bias])?[0];
+        model.set_output_outlets(&[y])?;
+        Ok(model)
+    }
+
+    #[test]
+    fn two_layer_linear() -> TractResult<()> {
+        let x = tensor2(&[[1f32, -2.0, 0.5, 3.0], [0.25, 1.5, -1.0, -0.75]]);
+        let model = two_layers()?;
+        let expected = SimplePlan::new(&model)?.run(tvec!(x.clone()))?.remove(0);
+
+        let mut quantized = model.clone();
+        assert_eq!(quantize_dynamic_range(&mut quantized)?, 2);
+        let int8 = quantized
+            .nodes()
+            .iter()
+            .filter_map(|n| n.outputs[0].fact.konst.as_ref())
+            .filter(|k| k.datum_type() == i8::datum_type())
+            .count();
+        assert_eq!(int8, 2);
+        assert_eq!(
+            quantized.nodes().iter().filter(|n| n.op_is::<DequantizeLinearF32>()).count(),
+            2
+        );
+        let bias = quantized.node_by_name("bias")?;
+        assert_eq!(quantized.outlet_fact(bias.inputs[1])?.datum_type, f32::datum_type());
+
+        let found = SimplePlan::new(&quantized)?.run(tvec!(x.clone()))?.remove(0);
+        let optimized = quantized.into_optimized()?;
+        let products = optimized
+            .nodes()
+            .iter()
+            .filter_map(|n| n.op_as::<MatMatMulUnaryFinite<i8, i8, i32, i32>>())
+            .collect::<Vec<_>>();
+        assert_eq!(products.len(), 2);
+        for product in products {
+            assert!(product.packed_as.iter().all(|pa| pa.datum_type() == i8::datum_type()));
+        }
+        assert!(!optimized
+            .nodes()
+            .iter()
+            .any(|n| n.op_is::<MatMatMulUnaryFinite<f32, f32, f32, f32>>()));
+        let optimized = SimplePlan::new(optimized)?.run(tvec!(x))?.remove(0);
+        let found = found.as_slice::<f32>()?;
+        for ((f, o), e) in
+            found.iter().zip(optimized.as_slice::<f32>()?).zip(expected.as_slice::<f32>()?)
+        {
+            assert!((f - e).abs() < 0.05, "{} != {}", f, e);
+            assert!((f - o).abs() < 1e-5, "{} != {}", f, o);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn unary_weights() -> TractResult<()> {
+        let x = tensor2(&[[1f32, -2.0, 0.5, 3.0], [0.25, 1.5, -1.0, -0.75]]);
+        let mut model = two_layers()?.declutter()?;
+        assert!(model.nodes().iter().any(|n| n.op_is::<MatMulUnary>()));
+        let expected = SimplePlan::new(&model)?.run(tvec!(x.clone()))?.remove(0);
+        assert_eq!(quantize_dynamic_range(&mut model)?, 2);
+        assert!(!model.nodes().iter().any(|n| n.op_is::<MatMulUnary>()));
+        let found = SimplePlan::new(&model)?.run(tvec!(x))?.remove(0);
+        for (f, e) in found.as_slice::<f32>()?.iter().zip(expected.as_slice::<f32>()?) {
+            assert!((f - e).abs() < 0.05, "{} != {}", f, e);
+        }
+        Ok(())
+    }
+}
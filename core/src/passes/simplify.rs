@@ -0,0 +1,260 @@
+//! Algebraic simplifications.
+use crate::internal::*;
+use crate::ops::array::{NormConcat, TypedReshape};
+use crate::ops::binary::{BinMiniOp, TypedBinOp, UnaryOp};
+use crate::ops::math::{Add, Div, Mul, Sub};
+
+/// A local rewrite of the graph around a node.
+pub trait RewriteRule: std::fmt::Debug {
+    /// Check if the rule can be applied to the node.
+    fn matches(&self, model: &TypedModel, node: &TypedNode) -> bool;
+
+    /// Rewrite the graph around the node. Returns true if the model has been
+    /// changed.
+    ///
+    /// Only called on nodes the rule matches.
+    fn apply(&self, model: &mut TypedModel, node: usize) -> TractResult<bool>;
+}
+
+/// The rules used by `simplify_algebra`.
+pub fn default_rules() -> Vec<Box<dyn RewriteRule>> {
+    vec![
+        Box::new(MulByOne),
+        Box::new(AddZero),
+        Box::new(SubZero),
+        Box::new(DivByOne),
+        Box::new(SingleInputConcat),
+        Box::new(ReshapeReshape),
+    ]
+}
+
+/// Apply `default_rules` until they stop matching, then eliminate dead nodes.
+///
+/// Returns the number of rewrites applied.
+pub fn simplify_algebra(model: &mut TypedModel) -> TractResult<usize> {
+    simplify_algebra_with_rules(model, &default_rules())
+}
+
+/// Apply the rules until they stop matching, then eliminate dead nodes.
+///
+/// Returns the number of rewrites applied.
+pub fn simplify_algebra_with_rules(
+    model: &mut TypedModel,
+    rules: &[Box<dyn RewriteRule>],
+) -> TractResult<usize> {
+    let mut done = 0;
+    loop {
+        let before = done;
+        for n in model.eval_order()? {
+            for rule in rules {
+                if rule.matches(model, model.node(n)) && rule.apply(model, n)? {
+                    done += 1;
+                    break;
+                }
+            }
+        }
+        if done == before {
+            break;
+        }
+    }
+    super::eliminate_dead_nodes(model)?;
+    Ok(done)
+}
+
+fn is_uniformly(t: &Tensor, value: f64) -> bool {
+    t.len() > 0
+        && t.is_uniform().unwrap_or(false)
+        && t.cast_to::<f64>().ok().and_then(|t| t.as_slice::<f64>().ok().map(|s| s[0] == value))
+            == Some(true)
+}
+
+/// Find the operand of a binary node the output would be equal to, because
+/// the other operand only contains the neutral element.
+fn neutral_operand<M: BinMiniOp>(
+    model: &TypedModel,
+    node: &TypedNode,
+    neutral: f64,
+    commutative: bool,
+) -> Option<OutletId> {
+    let operand = if let Some(op) = node.op_as::<TypedBinOp>() {
+        if !op.0.is::<M>() {
+            return None;
+        }
+        let is_neutral = |ix: usize| {
+            model
+                .outlet_fact(node.inputs[ix])
+                .ok()
+                .and_then(|f| f.konst.as_ref())
+                .map(|k| is_uniformly(k, neutral))
+                .unwrap_or(false)
+        };
+        if is_neutral(1) {
+            node.inputs[0]
+        } else if commutative && is_neutral(0) {
+            node.inputs[1]
+        } else {
+            return None;
+        }
+    } else if let Some(op) = node.op_as::<UnaryOp>() {
+        // UnaryOp computes `a op x`
+        if !op.mini_op.is::<M>() || !commutative || !is_uniformly(&op.a, neutral) {
+            return None;
+        }
+        node.inputs[0]
+    } else {
+        return None;
+    };
+    // the neutral operand must not broadcast or cast the other one
+    let input = model.outlet_fact(operand).ok()?;
+    let output = &node.outputs[0].fact;
+    if input.datum_type == output.datum_type && input.shape == output.shape {
+        Some(operand)
+    } else {
+        None
+    }
+}
+
+fn shunt(model: &mut TypedModel, node: usize, by: OutletId) -> TractResult<bool> {
+    let mut patch = TypedModelPatch::default();
+    let tap = patch.tap_model(model, by)?;
+    patch.shunt_outside(OutletId::new(node, 0), tap)?;
+    patch.apply(model)?;
+    Ok(true)
+}
+
+macro_rules! neutral_operand_rule {
+    ($(#[$doc:meta])* $Rule:ident, $Mini:ty, $neutral:expr, $commutative:expr) => {
+        $(#[$doc])*
+        #[derive(Debug, Clone, Default)]
+        pub struct $Rule;
+
+        impl RewriteRule for $Rule {
+            fn matches(&self, model: &TypedModel, node: &TypedNode) -> bool {
+                neutral_operand::<$Mini>(model, node, $neutral, $commutative).is_some()
+            }
+
+            fn apply(&self, model: &mut TypedModel, node: usize) -> TractResult<bool> {
+                if let Some(operand) =
+                    neutral_operand::<$Mini>(model, model.node(node), $neutral, $commutative)
+                {
+                    shunt(model, node, operand)
+                } else {
+                    Ok(false)
+                }
+            }
+        }
+    };
+}
+
+neutral_operand_rule!(
+    /// `x * 1 → x` and `1 * x → x`
+    MulByOne, Mul, 1.0, true
+);
+neutral_operand_rule!(
+    /// `x + 0 → x` and `0 + x → x`
+    AddZero, Add, 0.0, true
+);
+neutral_operand_rule!(
+    /// `x - 0 → x`
+    SubZero, Sub, 0.0, false
+);
+neutral_operand_rule!(
+    /// `x / 1 → x`
+    DivByOne, Div, 1.0, false
+);
+
+/// `concat([x]) → x`
+#[derive(Debug, Clone, Default)]
+pub struct SingleInputConcat;
+
+impl RewriteRule for SingleInputConcat {
+    fn matches(&self, _model: &TypedModel, node: &TypedNode) -> bool {
+        node.op_as::<NormConcat>()
+            .map(|op| op.slices.len() == 1 && op.slices[0].is_var())
+            .unwrap_or(false)
+    }
+
+    fn apply(&self, model: &mut TypedModel, node: usize) -> TractResult<bool> {
+        let input = model.node(node).inputs[0];
+        shunt(model, node, input)
+    }
+}
+
+/// `reshape(reshape(x, s1), s2) → reshape(x, s2)`
+#[derive(Debug, Clone, Default)]
+pub struct ReshapeReshape;
+
+impl RewriteRule for ReshapeReshape {
+    fn matches(&self, model: &TypedModel, node: &TypedNode) -> bool {
+        node.op_is::<TypedReshape>() && model.node(node.inputs[0].node).op_is::<TypedReshape>()
+    }
+
+    fn apply(&self, model: &mut TypedModel, node: usize) -> TractResult<bool> {
+        let prec = model.node(node).inputs[0].node;
+        let input = model.node(prec).inputs[0];
+        model.add_edge(input, InletId::new(node, 0))?;
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ops::array::NormConcatSlice;
+    use crate::ops::math;
+
+    fn source(model: &mut TypedModel, shape: &[usize]) -> TractResult<OutletId> {
+        model.add_source("x", TypedFact::dt_shape(f32::datum_type(), shape)?)
+    }
+
+    #[test]
+    fn neutral_operands() -> TractResult<()> {
+        let mut model = TypedModel::default();
+        let x = source(&mut model, &[2])?;
+        let one = model.add_const("one", rctensor1(&[1f32, 1.0]))?;
+        let zero = model.add_const("zero", rctensor0(0f32))?;
+        let a = model.wire_node("a", TypedBinOp(Box::new(Mul)), &[x, one])?[0];
+        let b = model.wire_node("b", TypedBinOp(Box::new(Mul)), &[one, a])?[0];
+        let c = model.wire_node("c", math::add::unary(rctensor0(0f32)), &[b])?[0];
+        let d = model.wire_node("d", TypedBinOp(Box::new(Sub)), &[c, zero])?[0];
+        let e = model.wire_node("e", TypedBinOp(Box::new(Div)), &[d, one])?[0];
+        let f = model.wire_node("f", math::neg(), &[e])?[0];
+        model.set_output_outlets(&[f])?;
+        assert_eq!(simplify_algebra(&mut model)?, 5);
+        assert_eq!(model.nodes().len(), 2);
+        let result = SimplePlan::new(&model)?.run(tvec!(tensor1(&[1f32, 2.0])))?;
+        assert_eq!(result[0], rctensor1(&[-1f32, -2.0]));
+        Ok(())
+    }
+
+    #[test]
+    fn non_neutral_operands() -> TractResult<()> {
+        let mut model = TypedModel::default();
+        let x = source(&mut model, &[1])?;
+        let zero = model.add_const("zero", rctensor0(0f32))?;
+        let ones = model.add_const("ones", rctensor1(&[1f32, 1.0]))?;
+        let a = model.wire_node("a", TypedBinOp(Box::new(Sub)), &[zero, x])?[0];
+        let b = model.wire_node("b", TypedBinOp(Box::new(Mul)), &[a, ones])?[0];
+        model.set_output_outlets(&[b])?;
+        assert_eq!(simplify_algebra(&mut model)?, 0);
+        assert_eq!(model.nodes().len(), 5);
+        Ok(())
+    }
+
+    #[test]
+    fn concat_and_reshapes() -> TractResult<()> {
+        let mut model = TypedModel::default();
+        let x = source(&mut model, &[2, 3])?;
+        let concat = NormConcat::new(0, tvec!(NormConcatSlice::Var));
+        let a = model.wire_node("a", concat, &[x])?[0];
+        let b = model.wire_node("b", TypedReshape::new(tvec!(6.to_dim())), &[a])?[0];
+        let c = model.wire_node("c", TypedReshape::new(tvec!(3.to_dim(), 2.to_dim())), &[b])?[0];
+        model.set_output_outlets(&[c])?;
+        assert_eq!(simplify_algebra(&mut model)?, 2);
+        assert_eq!(model.nodes().len(), 2);
+        let reshape = model.node(model.output_outlets()?[0].node);
+        assert_eq!(reshape.name, "c");
+        assert_eq!(reshape.inputs, model.input_outlets()?);
+        Ok(())
+    }
+}
@@ -0,0 +1,129 @@
+//! Transpose fusion.
+use crate::internal::*;
+use crate::ops::array::PermuteAxes;
+
+fn permutation(model: &TypedModel, node: &TypedNode) -> TractResult<Option<Vec<usize>>> {
+    if let Some(op) = node.op_as::<PermuteAxes>() {
+        if let Some(axes) = &op.axes {
+            Ok(Some(axes.clone()))
+        } else {
+            Ok(Some((0..model.outlet_fact(node.inputs[0])?.shape.rank()).rev().collect()))
+        }
+    } else {
+        Ok(None)
+    }
+}
+
+/// Compose chains of PermuteAxes.
+///
+/// A PermuteAxes fed by another PermuteAxes is replaced by a single one
+/// applying the composed permutation. Permutations equal to the identity are
+/// removed. The intermediate PermuteAxes is only fused if its output is not
+/// used anywhere else.
+///
+/// Returns the number of PermuteAxes removed.
+pub fn fuse_transposes(model: &mut TypedModel) -> TractResult<usize> {
+    let mut done = 0;
+    loop {
+        let before = done;
+        for n in model.eval_order()? {
+            let node = model.node(n);
+            let mut perm = if let Some(perm) = permutation(model, node)? {
+                perm
+            } else {
+                continue;
+            };
+            let mut input = node.inputs[0];
+            let prec = model.node(input.node);
+            if let Some(prec_perm) = permutation(model, prec)? {
+                if prec.outputs[0].successors.len() == 1
+                    && !model.output_outlets()?.contains(&input)
+                {
+                    perm = perm.iter().map(|&axis| prec_perm[axis]).collect();
+                    input = prec.inputs[0];
+                    done += 1;
+                }
+            }
+            if perm.iter().enumerate().all(|(ix, &axis)| ix == axis) {
+                let mut patch = TypedModelPatch::default();
+                let tap = patch.tap_model(model, input)?;
+                patch.shunt_outside(OutletId::new(n, 0), tap)?;
+                patch.apply(model)?;
+                done += 1;
+            } else if input != node.inputs[0] {
+                model.node_mut(n).op = Box::new(PermuteAxes::new(Some(perm)));
+                model.add_edge(input, InletId::new(n, 0))?;
+            }
+        }
+        if done == before {
+            break;
+        }
+    }
+    super::eliminate_dead_nodes(model)?;
+    Ok(done)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ops::math;
+
+    fn permute(model: &mut TypedModel, name: &str, axes: &[usize], input: OutletId) -> OutletId {
+        model.wire_node(name, PermuteAxes::new(Some(axes.to_vec())), &[input]).unwrap()[0]
+    }
+
+    fn source(model: &mut TypedModel) -> TractResult<OutletId> {
+        model.add_source("x", TypedFact::dt_shape(f32::datum_type(), [1usize, 2, 3, 4].as_ref())?)
+    }
+
+    #[test]
+    fn identity() -> TractResult<()> {
+        let mut model = TypedModel::default();
+        let x = source(&mut model)?;
+        let nhwc = permute(&mut model, "nhwc", &[0, 2, 3, 1], x);
+        let nchw = permute(&mut model, "nchw", &[0, 3, 1, 2], nhwc);
+        let neg = model.wire_node("neg", math::neg(), &[nchw])?[0];
+        model.set_output_outlets(&[neg])?;
+        assert_eq!(fuse_transposes(&mut model)?, 2);
+        assert_eq!(model.nodes().len(), 2);
+        let neg = model.node(model.output_outlets()?[0].node);
+        assert_eq!(neg.inputs, model.input_outlets()?);
+        Ok(())
+    }
+
+    #[test]
+    fn partial() -> TractResult<()> {
+        let mut model = TypedModel::default();
+        let x = source(&mut model)?;
+        let a = permute(&mut model, "a", &[0, 2, 3, 1], x);
+        let b = permute(&mut model, "b", &[1, 0, 2, 3], a);
+        let c = permute(&mut model, "c", &[0, 1, 3, 2], b);
+        model.set_output_outlets(&[c])?;
+        let input: Tensor =
+            ndarray::Array1::range(0f32, 24.0, 1.0).into_shape((1, 2, 3, 4)).unwrap().into();
+        let expected = SimplePlan::new(&model)?.run(tvec!(input.clone()))?;
+        assert_eq!(fuse_transposes(&mut model)?, 2);
+        assert_eq!(model.nodes().len(), 2);
+        let fused = model.node(model.output_outlets()?[0].node);
+        assert_eq!(fused.op_as::<PermuteAxes>().unwrap().axes, Some(vec!(2, 0, 1, 3)));
+        assert_eq!(
+            model.outlet_fact(model.output_outlets()?[0])?.shape.to_tvec(),
+            tvec!(3.to_dim(), 1.to_dim(), 2.to_dim(), 4.to_dim())
+        );
+        assert_eq!(SimplePlan::new(&model)?.run(tvec!(input))?, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn branching() -> TractResult<()> {
+        let mut model = TypedModel::default();
+        let x = source(&mut model)?;
+        let nhwc = permute(&mut model, "nhwc", &[0, 2, 3, 1], x);
+        let nchw = permute(&mut model, "nchw", &[0, 3, 1, 2], nhwc);
+        let neg = model.wire_node("neg", math::neg(), &[nhwc])?[0];
+        model.set_output_outlets(&[nchw, neg])?;
+        assert_eq!(fuse_transposes(&mut model)?, 0);
+        assert_eq!(model.nodes().len(), 4);
+        Ok(())
+    }
+}
@@ -1,18 +1,158 @@
 use std::borrow::Borrow;
+use std::convert::TryFrom;
 use std::fmt::{Debug, Display};
 use std::marker::PhantomData;
+use std::sync::Mutex;
 
 use crate::internal::*;
+use crate::memory::pool::ActivePool;
+use crate::memory::TensorPool;
 use crate::model::order::eval_order_for_nodes;
 use crate::model::{Fact, ModelImpl, OutletId};
 
+#[cfg(feature = "async")]
+pub mod r#async;
+#[cfg(feature = "async")]
+pub use self::r#async::AsyncSimplePlan;
+
 #[derive(Debug, Default)]
 pub struct SessionState {
     pub inputs: HashMap<usize, Arc<Tensor>>,
     pub known_stream_len: Option<usize>,
     pub tensors: HashMap<String, Tensor>,
+    pub unimplemented_ops: crate::ops::unimpl::UnimplementedOpRegistry,
+    reset_pending: bool,
+    kv_cache_reset_pending: bool,
+}
+
+impl SessionState {
+    /// Ask every stateful op (like recurrent cells) to forget its state.
+    ///
+    /// The reset is applied at the start of the next run of the
+    /// `SimpleState` owning this session, like `SimpleState::reset_state`
+    /// would do right away.
+    pub fn reset_state(&mut self) {
+        self.reset_pending = true;
+    }
+
+    /// Ask the `KVCache` ops to forget the keys and values they accumulated,
+    /// to start decoding a new sequence. Other op states are kept.
+    ///
+    /// The reset is applied at the start of the next run, like
+    /// `SimpleState::reset_kv_cache` would do right away.
+    pub fn reset_kv_cache(&mut self) {
+        self.kv_cache_reset_pending = true;
+    }
+}
+
+/// Options controlling the behaviour of a `SimplePlan` at run time.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SimplePlanOptions {
+    /// Record the bytes allocated by each node in a `MemoryTracker`.
+    pub track_memory: bool,
+    /// Number of input shapes for which `run_dynamic` keeps a specialized
+    /// plan around.
+    pub specialized_plans: usize,
+    /// Recycle intermediate tensors through this pool.
+    pub tensor_pool: Option<Arc<TensorPool>>,
+    /// Fail a run taking longer than this. Checked after each node
+    /// evaluation.
+    #[cfg(feature = "timeout")]
+    pub timeout: Option<std::time::Duration>,
+}
+
+impl Default for SimplePlanOptions {
+    fn default() -> SimplePlanOptions {
+        SimplePlanOptions {
+            track_memory: false,
+            specialized_plans: 4,
+            tensor_pool: None,
+            #[cfg(feature = "timeout")]
+            timeout: None,
+        }
+    }
+}
+
+impl SimplePlanOptions {
+    /// Recycle intermediate tensors through `pool`: while the plan runs,
+    /// dropped tensors are given to the pool, and tensor allocations are
+    /// served by the pool when it can.
+    pub fn with_tensor_pool(self, pool: Arc<TensorPool>) -> SimplePlanOptions {
+        SimplePlanOptions { tensor_pool: Some(pool), ..self }
+    }
+
+    /// Make runs fail once they have been running for longer than `timeout`.
+    ///
+    /// The check happens between node evaluations, so a run can overshoot
+    /// by the duration of one node.
+    #[cfg(feature = "timeout")]
+    pub fn with_timeout(self, timeout: std::time::Duration) -> SimplePlanOptions {
+        SimplePlanOptions { timeout: Some(timeout), ..self }
+    }
+}
+
+type SpecializedPlans =
+    Arc<Mutex<Vec<(TVec<TVec<usize>>, Arc<SimplePlan<TypedFact, Box<dyn TypedOp>, TypedModel>>)>>>;
+
+/// Records the size of the tensors produced during plan executions.
+///
+/// Sizes are computed from the tensors length and datum type, and cumulated
+/// over runs. The peak is the largest amount of bytes alive at a time in a
+/// single run.
+#[derive(Debug, Clone, Default)]
+pub struct MemoryTracker {
+    per_node: HashMap<usize, usize>,
+    total: usize,
+    live: usize,
+    peak: usize,
 }
 
+impl MemoryTracker {
+    fn bytes(tensors: &[Arc<Tensor>]) -> usize {
+        tensors.iter().map(|t| t.datum_type().storage_size(t.len())).sum()
+    }
+
+    fn allocated(&mut self, node: usize, bytes: usize) {
+        *self.per_node.entry(node).or_insert(0) += bytes;
+        self.total += bytes;
+        self.live += bytes;
+        self.peak = self.peak.max(self.live);
+    }
+
+    fn released(&mut self, bytes: usize) {
+        self.live = self.live.saturating_sub(bytes);
+    }
+
+    /// Largest amount of bytes held by the plan values at a time.
+    pub fn peak_bytes(&self) -> usize {
+        self.peak
+    }
+
+    /// Sum of the bytes produced by all nodes.
+    pub fn total_allocated_bytes(&self) -> usize {
+        self.total
+    }
+
+    /// Bytes produced by each node, as (node_id, bytes), by node id.
+    pub fn per_node(&self) -> Vec<(usize, usize)> {
+        let mut v: Vec<(usize, usize)> = self.per_node.iter().map(|(&n, &b)| (n, b)).collect();
+        v.sort();
+        v
+    }
+
+    /// Forget everything recorded so far.
+    pub fn reset(&mut self) {
+        *self = MemoryTracker::default()
+    }
+}
+
+/// An evaluation order for a model, computing some of its outputs.
+///
+/// `SimplePlan::run` evaluates in a fresh `SimpleState`, so stateful
+/// ops (recurrent cells, `KVCache`) start over at each call. To keep their
+/// states across runs, run a `SimpleState` built from the plan, and reset
+/// them between independent sequences with `SimpleState::reset_state` (or
+/// `SimpleState::reset_kv_cache` for the caches alone).
 #[derive(Debug, Clone)]
 pub struct SimplePlan<TI, O, M>
 where
@@ -24,6 +164,9 @@ where
     pub outputs: Vec<OutletId>,
     pub order: Vec<usize>,
     pub flush_lists: Vec<TVec<usize>>,
+    pub options: SimplePlanOptions,
+    specialized: SpecializedPlans,
+    decluttered: Option<Arc<TypedModel>>,
     _casper: PhantomData<(TI, O)>,
 }
 
@@ -35,8 +178,16 @@ where
 {
     /// This contructor returns a plan that will compute all the model default outputs in one pass.
     pub fn new(model: M) -> TractResult<SimplePlan<TI, O, M>> {
+        Self::new_with_options(model, SimplePlanOptions::default())
+    }
+    /// This contructor returns a plan that will compute all the model default outputs in one
+    /// pass, using the specified options.
+    pub fn new_with_options(
+        model: M,
+        options: SimplePlanOptions,
+    ) -> TractResult<SimplePlan<TI, O, M>> {
         let outputs = model.borrow().output_outlets()?.iter().cloned().collect::<Vec<OutletId>>();
-        Self::new_for_outputs(model, &outputs)
+        Self::new_for_outputs_with_options(model, &outputs, options)
     }
     /// This contructor returns a plan that will compute the specified output.
     pub fn new_for_output(model: M, output: OutletId) -> TractResult<SimplePlan<TI, O, M>> {
@@ -44,6 +195,15 @@ where
     }
     /// This contructor returns a plan that will compute all specified outputs in one pass.
     pub fn new_for_outputs(model: M, outputs: &[OutletId]) -> TractResult<SimplePlan<TI, O, M>> {
+        Self::new_for_outputs_with_options(model, outputs, SimplePlanOptions::default())
+    }
+    /// This contructor returns a plan that will compute all specified outputs in one pass,
+    /// using the specified options.
+    pub fn new_for_outputs_with_options(
+        model: M,
+        outputs: &[OutletId],
+        options: SimplePlanOptions,
+    ) -> TractResult<SimplePlan<TI, O, M>> {
         let inputs = model.borrow().input_outlets()?.iter().map(|n| n.node).collect::<Vec<usize>>();
         let outputs_nodes = outputs.iter().map(|n| n.node).collect::<Vec<usize>>();
         let order = eval_order_for_nodes(model.borrow().nodes(), &inputs, &outputs_nodes)?;
@@ -67,20 +227,219 @@ where
             order,
             flush_lists,
             outputs: outputs.to_vec(),
+            options,
+            specialized: Arc::new(Mutex::new(vec![])),
+            decluttered: None,
             _casper: PhantomData,
         })
     }
 
+    /// Run the plan in a new `SimpleState`, so no op state is kept.
     pub fn run(&self, inputs: TVec<Tensor>) -> TractResult<TVec<Arc<Tensor>>> {
         let mut state = SimpleState::new(self)?;
         state.run(inputs)
     }
 
+    /// Run the plan, capturing the outputs of some nodes on the way.
+    ///
+    /// Returns the plan outputs and the outputs of each node in
+    /// `trace_nodes`, keyed by node id. The model is left untouched, so
+    /// tracing an optimized model does not require adding outputs and
+    /// optimizing it again. Traced nodes must be evaluated by the plan to
+    /// compute its outputs.
+    pub fn run_with_trace(
+        &self,
+        inputs: TVec<Tensor>,
+        trace_nodes: &[usize],
+    ) -> TractResult<(TVec<Arc<Tensor>>, HashMap<usize, TVec<Arc<Tensor>>>)> {
+        if let Some(missing) = trace_nodes.iter().find(|n| !self.order.contains(n)) {
+            bail!("Can not trace node {}: it is not needed to compute the plan outputs", missing)
+        }
+        let mut trace = HashMap::new();
+        let mut state = SimpleState::new(self)?;
+        let outputs = state.run_plan_with_eval(inputs, 0, |session, state, node, inputs| {
+            let outputs = self::eval(session, state, node, inputs)?;
+            if trace_nodes.contains(&node.id) {
+                trace.insert(node.id, outputs.clone());
+            }
+            Ok(outputs)
+        })?;
+        Ok((outputs, trace))
+    }
+
+    /// Run the plan on a batch of independent input sets, spreading the runs
+    /// over the rayon thread pool.
+    ///
+    /// Each run gets its own `SimpleState`, so no session or op state is
+    /// shared between threads. Results are in the order of `inputs`.
+    #[cfg(feature = "parallel")]
+    pub fn run_batch_parallel(
+        &self,
+        inputs: Vec<TVec<Tensor>>,
+    ) -> TractResult<Vec<TVec<Arc<Tensor>>>>
+    where
+        Self: Sync,
+    {
+        use rayon::iter::{IntoParallelIterator, ParallelIterator};
+        inputs.into_par_iter().map(|inputs| self.run(inputs)).collect()
+    }
+
+    /// Run the plan, writing the results in caller-provided tensors.
+    ///
+    /// See `SimpleState::run_into`.
+    pub fn run_into(&self, inputs: TVec<Tensor>, outputs: &mut [Tensor]) -> TractResult<()> {
+        let mut state = SimpleState::new(self)?;
+        state.run_into(inputs, outputs)
+    }
+
+    /// Allocate tensors with the datum types and shapes of the plan outputs,
+    /// for `run_into`.
+    ///
+    /// Fails if some output type or shape is not fully determined by the model.
+    pub fn allocate_outputs(&self) -> TractResult<TVec<Tensor>> {
+        self.outputs
+            .iter()
+            .map(|&outlet| {
+                let fact = self.model().outlet_fact(outlet)?.to_tensor_fact();
+                let dt = fact
+                    .datum_type
+                    .concretize()
+                    .ok_or_else(|| format!("Unknown datum type for output {:?}", outlet))?;
+                let shape = fact
+                    .shape
+                    .as_concrete_finite()?
+                    .ok_or_else(|| format!("Unknown shape for output {:?}", outlet))?;
+                Ok(dispatch_datum!(self::default_tensor(dt)(&shape)))
+            })
+            .collect()
+    }
+
     pub fn model(&self) -> &ModelImpl<TI, O> {
         self.model.borrow()
     }
 }
 
+impl<M> SimplePlan<TypedFact, Box<dyn TypedOp>, M>
+where
+    M: Borrow<TypedModel>,
+{
+    /// Run the plan on inputs that may not match the model input facts.
+    ///
+    /// If the input shapes are compatible with the model input facts, this
+    /// is the same as `run`. Otherwise, the decluttered model is specialized
+    /// for the actual input shapes: shapes are inferred again, and only the
+    /// codegen passes run, not the whole optimisation. The resulting plans
+    /// are cached for the last `options.specialized_plans` distinct input
+    /// shapes.
+    ///
+    /// The decluttered model is the one kept by `new_dynamic`, or the plan
+    /// model itself if it is not optimized. A plan built from an optimized
+    /// model by the other constructors only runs on shapes compatible with
+    /// its input facts.
+    pub fn run_dynamic(&self, inputs: TVec<Tensor>) -> TractResult<TVec<Arc<Tensor>>> {
+        let model = self.model();
+        let mut matching = true;
+        let mut compatible = true;
+        for (ix, input) in inputs.iter().enumerate() {
+            let fact = model.input_fact(ix)?;
+            if fact.shape.rank() != input.rank() {
+                matching = false;
+                compatible = false;
+                continue;
+            }
+            for (d, &i) in fact.shape.iter().zip(input.shape()) {
+                match d.to_integer() {
+                    Ok(d) if d as usize == i => (),
+                    Ok(_) => {
+                        matching = false;
+                        compatible = false;
+                    }
+                    Err(_) => matching = false,
+                }
+            }
+        }
+        if matching || (compatible && model.is_optimized()) {
+            return self.run(inputs);
+        }
+        let shapes: TVec<TVec<usize>> = inputs.iter().map(|t| t.shape().into()).collect();
+        let plan = {
+            let mut cache = self.specialized.lock().map_err(|_| "Poisoned specialized plans")?;
+            if let Some(pos) = cache.iter().position(|(s, _)| s == &shapes) {
+                let entry = cache.remove(pos);
+                let plan = entry.1.clone();
+                cache.push(entry);
+                plan
+            } else {
+                let plan = Arc::new(self.specialize(&inputs)?);
+                if self.options.specialized_plans > 0 {
+                    if cache.len() == self.options.specialized_plans {
+                        cache.remove(0);
+                    }
+                    cache.push((shapes, plan.clone()));
+                }
+                plan
+            }
+        };
+        plan.run(inputs)
+    }
+
+    /// Number of plans currently cached by `run_dynamic`.
+    pub fn specialized_plans_count(&self) -> usize {
+        self.specialized.lock().map(|c| c.len()).unwrap_or(0)
+    }
+
+    fn specialize(
+        &self,
+        inputs: &[Tensor],
+    ) -> TractResult<SimplePlan<TypedFact, Box<dyn TypedOp>, TypedModel>> {
+        let (mut model, outputs) = if let Some(decluttered) = &self.decluttered {
+            ((**decluttered).clone(), decluttered.output_outlets()?.to_vec())
+        } else if !self.model().is_optimized() {
+            (self.model().clone(), self.outputs.clone())
+        } else {
+            bail!(
+                "Can not specialize an optimized model without its decluttered version for \
+                 inputs {:?}",
+                inputs.iter().map(|t| t.shape()).collect::<Vec<_>>()
+            )
+        };
+        for (ix, input) in inputs.iter().enumerate() {
+            let mut fact = model.input_fact(ix)?.clone();
+            fact.shape = ShapeInfo::try_from(input.shape())?;
+            let id = model.input_outlets()?[ix].node;
+            model.node_mut(id).op = Box::new(crate::ops::source::TypedSource::new(fact.clone()));
+            model.set_input_fact(ix, fact)?;
+        }
+        crate::model::infer_shapes(&mut model)
+            .chain_err(|| format!("Specializing plan for inputs {:?}", inputs))?;
+        model.set_output_outlets(&outputs)?;
+        SimplePlan::new_with_options(model.codegen()?, self.options.clone())
+    }
+}
+
+impl SimplePlan<TypedFact, Box<dyn TypedOp>, TypedModel> {
+    /// Build a plan running the optimized version of `model`, for
+    /// `run_dynamic`.
+    ///
+    /// The decluttered model is kept along, to specialize the plan for input
+    /// shapes that its input facts do not allow.
+    pub fn new_dynamic(model: TypedModel) -> TractResult<Self> {
+        Self::new_dynamic_with_options(model, SimplePlanOptions::default())
+    }
+
+    /// Same as `new_dynamic`, using the specified options.
+    pub fn new_dynamic_with_options(
+        model: TypedModel,
+        options: SimplePlanOptions,
+    ) -> TractResult<Self> {
+        let decluttered = model.declutter()?;
+        let optimized = crate::model::compact::compact(&decluttered.clone().codegen()?)?;
+        let mut plan = SimplePlan::new_with_options(optimized, options)?;
+        plan.decluttered = Some(Arc::new(decluttered));
+        Ok(plan)
+    }
+}
+
 #[derive(Debug)]
 pub struct SimpleState<TI, O, M, P>
 where
@@ -93,6 +452,7 @@ where
     pub states: Vec<Option<Box<dyn OpState>>>,
     pub session_state: SessionState,
     pub values: Vec<Option<TVec<Arc<Tensor>>>>,
+    pub memory_tracker: Option<MemoryTracker>,
     _phantom: PhantomData<(M, TI, O)>,
 }
 
@@ -116,6 +476,7 @@ where
             states,
             session_state: SessionState::default(),
             values: self.values.clone(),
+            memory_tracker: self.memory_tracker.clone(),
             _phantom: PhantomData,
         }
     }
@@ -132,16 +493,42 @@ where
         Self::new_multiplan(vec![plan])
     }
 
+    /// Build a state using a pre-configured SessionState.
+    pub fn new_with_session_state(
+        plan: P,
+        session: SessionState,
+    ) -> TractResult<SimpleState<TI, O, M, P>> {
+        Self::new_multiplan_with_session_state(vec![plan], session)
+    }
+
     pub fn new_multiplan(plans: Vec<P>) -> TractResult<SimpleState<TI, O, M, P>> {
+        Self::new_multiplan_with_session_state(plans, SessionState::default())
+    }
+
+    fn new_multiplan_with_session_state(
+        plans: Vec<P>,
+        mut session: SessionState,
+    ) -> TractResult<SimpleState<TI, O, M, P>> {
         let values = vec![None; plans[0].borrow().model.borrow().nodes().len()];
-        let mut session = SessionState::default();
         let model = plans[0].borrow().model();
         let states = model
             .nodes()
             .iter()
             .map(|n: &BaseNode<TI, O>| n.op().state(&mut session, n.id))
             .collect::<TractResult<_>>()?;
-        Ok(SimpleState { plans, states, session_state: session, values, _phantom: PhantomData })
+        let memory_tracker = if plans[0].borrow().options.track_memory {
+            Some(MemoryTracker::default())
+        } else {
+            None
+        };
+        Ok(SimpleState {
+            plans,
+            states,
+            session_state: session,
+            values,
+            memory_tracker,
+            _phantom: PhantomData,
+        })
     }
 
     /// Reset wires state.
@@ -163,6 +550,30 @@ where
         Ok(())
     }
 
+    /// Reset the state of every stateful op (like recurrent cells or
+    /// `KVCache`), to process a new independent sequence.
+    ///
+    /// Ops running a nested model reset the states of the nested ops too.
+    pub fn reset_state(&mut self) -> TractResult<()> {
+        for state in self.states.iter_mut().flatten() {
+            state.reset()?;
+        }
+        Ok(())
+    }
+
+    /// Empty the `KVCache` ops, leaving the other op states untouched.
+    ///
+    /// Only the caches of this plan are reset: a cache inside a nested model
+    /// (the body of a loop, for instance) is only reached by `reset_state`.
+    pub fn reset_kv_cache(&mut self) -> TractResult<()> {
+        for state in self.states.iter_mut().flatten() {
+            if let Some(cache) = state.downcast_mut::<crate::ops::nn::KVCacheState>() {
+                cache.reset()?;
+            }
+        }
+        Ok(())
+    }
+
     pub fn run(&mut self, inputs: TVec<Tensor>) -> TractResult<TVec<Arc<Tensor>>> {
         self.run_plan(inputs, 0)
     }
@@ -172,7 +583,86 @@ where
         inputs: TVec<Tensor>,
         plan: usize,
     ) -> TractResult<TVec<Arc<Tensor>>> {
+        self.run_plan_with_eval(inputs, plan, self::eval)
+    }
+
+    /// Run the plan, writing the results in caller-provided tensors.
+    ///
+    /// The nodes computing the outputs allocate their results from the
+    /// `outputs` tensors of the right datum type and shape, so they write
+    /// straight into them, and a loop can hand the same tensors over and over
+    /// without allocating them. An output the final node does not compute in
+    /// such a tensor (a constant, or a value forwarded from its input)
+    /// replaces it, with a warning.
+    pub fn run_into(&mut self, inputs: TVec<Tensor>, outputs: &mut [Tensor]) -> TractResult<()> {
+        let expected = self.plans[0].borrow().outputs.len();
+        if outputs.len() != expected {
+            bail!("Plan has {} outputs, got {} tensors", expected, outputs.len());
+        }
+        let buffers = outputs.iter().map(data_ptr).collect::<Vec<_>>();
+        let staged = Arc::new(TensorPool::new(outputs.len()));
+        for output in outputs.iter_mut() {
+            staged.put(std::mem::replace(output, Tensor::default()));
+        }
+        let results = self.run_plan_staging_outputs(inputs, 0, self::eval, Some(&staged))?;
+        for (ix, (output, result)) in outputs.iter_mut().zip(results.into_iter()).enumerate() {
+            *output = result.into_tensor();
+            if buffers[ix].is_none() || data_ptr(output) != buffers[ix] {
+                warn!(
+                    "run_into: output {} ({:?} {:?}) not computed in the given tensor",
+                    ix,
+                    output.datum_type(),
+                    output.shape()
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// Run a plan, delegating the evaluation of each node to `eval`.
+    ///
+    /// `eval` is called with the op state of the node, if any, and is
+    /// expected to call `plan::eval` or an equivalent.
+    pub fn run_plan_with_eval<Eval>(
+        &mut self,
+        inputs: TVec<Tensor>,
+        plan: usize,
+        eval: Eval,
+    ) -> TractResult<TVec<Arc<Tensor>>>
+    where
+        Eval: FnMut(
+            &mut SessionState,
+            Option<&mut Box<dyn OpState>>,
+            &BaseNode<TI, O>,
+            TVec<Arc<Tensor>>,
+        ) -> TractResult<TVec<Arc<Tensor>>>,
+    {
+        self.run_plan_staging_outputs(inputs, plan, eval, None)
+    }
+
+    /// Run a plan, the nodes computing its outputs allocating from `staged`.
+    fn run_plan_staging_outputs<Eval>(
+        &mut self,
+        inputs: TVec<Tensor>,
+        plan: usize,
+        mut eval: Eval,
+        staged: Option<&Arc<TensorPool>>,
+    ) -> TractResult<TVec<Arc<Tensor>>>
+    where
+        Eval: FnMut(
+            &mut SessionState,
+            Option<&mut Box<dyn OpState>>,
+            &BaseNode<TI, O>,
+            TVec<Arc<Tensor>>,
+        ) -> TractResult<TVec<Arc<Tensor>>>,
+    {
         let mut result = tvec!();
+        if std::mem::replace(&mut self.session_state.reset_pending, false) {
+            self.reset_state()?;
+        }
+        if std::mem::replace(&mut self.session_state.kv_cache_reset_pending, false) {
+            self.reset_kv_cache()?;
+        }
         {
             self.set_inputs(inputs)?;
             let &mut SimpleState {
@@ -180,10 +670,15 @@ where
                 ref mut session_state,
                 ref mut states,
                 ref mut values,
+                ref mut memory_tracker,
                 ..
             } = self;
             let plan = plans[plan].borrow();
             let model = plan.model().borrow();
+            let active_pool =
+                plan.options.tensor_pool.as_ref().map(|p| ActivePool::activate(p.clone()));
+            #[cfg(feature = "timeout")]
+            let started = std::time::Instant::now();
             for (step, n) in plan.order.iter().enumerate() {
                 let node = model.node(*n);
                 trace!("Running step {}, node {}", step, node);
@@ -197,8 +692,15 @@ where
                     inputs.push(prec[i.slot].clone().into())
                 }
 
+                // drop the values this node is the last consumer of before
+                // running it, so that ops evaluating in place get the only
+                // reference to their input
+                let mut flushed_bytes = 0;
                 for flush in &plan.flush_lists[step] {
                     trace!("  flushing node {} {}", flush, node);
+                    if let (Some(_), Some(vs)) = (&memory_tracker, &values[*flush]) {
+                        flushed_bytes += MemoryTracker::bytes(vs);
+                    }
                     values[*flush] = None;
                 }
 
@@ -214,7 +716,7 @@ where
                     }
                     for (ix, (v, f)) in inputs.iter().zip(facts.iter()).enumerate() {
                         if f.to_tensor_fact().shape.is_concrete()
-                            && f.to_tensor_fact().stream_info()?.is_some()
+                            && f.to_tensor_fact().shape.as_concrete_finite()?.is_none()
                         {
                             continue;
                         }
@@ -231,11 +733,31 @@ where
                     }
                 }
 
-                let vs = match states[node.id] {
-                    Some(ref mut state) => state.eval(session_state, node.op(), inputs),
-                    None => node.op().as_stateless().expect("as_stateless").eval(inputs),
+                let staging = staged.filter(|_| plan.outputs.iter().any(|o| o.node == node.id));
+                let vs = if let Some(staged) = staging {
+                    // keep the inputs shared, so that ops evaluating in place
+                    // copy them into the staged tensors instead
+                    let _inputs = inputs.clone();
+                    let _staged = ActivePool::activate(staged.clone());
+                    eval(session_state, states[node.id].as_mut(), node, inputs)?
+                } else {
+                    eval(session_state, states[node.id].as_mut(), node, inputs)?
+                };
+
+                #[cfg(feature = "timeout")]
+                {
+                    if let Some(timeout) = plan.options.timeout {
+                        let elapsed = started.elapsed();
+                        if elapsed > timeout {
+                            bail!(
+                                "Timed out after {:?} (timeout is {:?}), while running {}",
+                                elapsed,
+                                timeout,
+                                node
+                            );
+                        }
+                    }
                 }
-                .chain_err(|| format!("Evaluating {}", node))?;
 
                 if cfg!(debug_assertions) {
                     let facts = model.node_output_facts(node.id)?;
@@ -252,7 +774,7 @@ where
                             continue;
                         }
                         if f.to_tensor_fact().shape.is_concrete()
-                            && f.to_tensor_fact().stream_info()?.is_some()
+                            && f.to_tensor_fact().shape.as_concrete_finite()?.is_none()
                         {
                             continue;
                         }
@@ -269,11 +791,23 @@ where
                     }
                 }
 
+                if let Some(tracker) = memory_tracker {
+                    tracker.allocated(node.id, MemoryTracker::bytes(&vs));
+                    tracker.released(flushed_bytes);
+                }
+
                 values[node.id] = Some(vs);
             }
+            if let Some(tracker) = memory_tracker {
+                tracker.live = 0;
+            }
             for output in &plan.outputs {
                 result.push(values[output.node].as_ref().unwrap()[output.slot].clone())
             }
+            if active_pool.is_some() {
+                // release the values while the pool is still there to take them
+                values.iter_mut().for_each(|vs| *vs = None);
+            }
         }
         self.reset_wires()?;
         Ok(result)
@@ -398,3 +932,339 @@ where
         self.plan().model()
     }
 }
+
+fn data_ptr_t<T: Datum>(t: &Tensor) -> TractResult<*const u8> {
+    Ok(t.as_ptr::<T>()? as *const u8)
+}
+
+fn data_ptr(t: &Tensor) -> Option<*const u8> {
+    let ptr = || -> TractResult<*const u8> { dispatch_datum!(self::data_ptr_t(t.datum_type())(t)) };
+    if t.is_null() || t.len() == 0 {
+        None
+    } else {
+        ptr().ok()
+    }
+}
+
+fn default_tensor<T: Datum>(shape: &[usize]) -> Tensor {
+    ndarray::ArrayD::<T>::default(shape).into()
+}
+
+/// Evaluate a node, using its op state if it has one.
+pub fn eval<TI, O>(
+    session_state: &mut SessionState,
+    state: Option<&mut Box<dyn OpState>>,
+    node: &BaseNode<TI, O>,
+    inputs: TVec<Arc<Tensor>>,
+) -> TractResult<TVec<Arc<Tensor>>>
+where
+    TI: Fact + Clone + 'static,
+    O: Debug + Display + AsRef<dyn Op> + AsMut<dyn Op> + Clone + 'static,
+{
+    match state {
+        Some(state) => state.eval(session_state, node.op(), inputs),
+        None => node.op().as_stateless().expect("as_stateless").eval(inputs),
+    }
+    .chain_err(|| format!("Evaluating {}", node))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ops::math;
+
+    #[test]
+    fn memory_tracking() -> TractResult<()> {
+        let mut model = TypedModel::default();
+        let fact = TypedFact::dt_shape(f32::datum_type(), [16usize].as_ref())?;
+        let a = model.add_source("a", fact)?;
+        let neg = model.wire_node("neg", math::neg(), &[a])?[0];
+        let abs = model.wire_node("abs", math::abs(), &[neg])?[0];
+        model.set_output_outlets(&[abs])?;
+        let options = SimplePlanOptions { track_memory: true, ..SimplePlanOptions::default() };
+        let plan = SimplePlan::new_with_options(&model, options)?;
+        let mut state = SimpleState::new(&plan)?;
+        state.run(tvec!(ndarray::Array1::<f32>::zeros(16).into()))?;
+        let tracker = state.memory_tracker.as_ref().unwrap();
+        assert_eq!(tracker.per_node(), vec!((a.node, 64), (neg.node, 64), (abs.node, 64)));
+        assert_eq!(tracker.total_allocated_bytes(), 192);
+        assert_eq!(tracker.peak_bytes(), 128);
+
+        let mut state = SimpleState::new(SimplePlan::new(&model)?)?;
+        state.run(tvec!(ndarray::Array1::<f32>::zeros(16).into()))?;
+        assert!(state.memory_tracker.is_none());
+        Ok(())
+    }
+    #[test]
+    fn run_with_trace() -> TractResult<()> {
+        let mut model = TypedModel::default();
+        let fact = TypedFact::dt_shape(f32::datum_type(), [3usize].as_ref())?;
+        let a = model.add_source("a", fact)?;
+        let neg = model.wire_node("neg", math::neg(), &[a])?[0];
+        let hidden = model.wire_node("hidden", math::abs(), &[neg])?[0];
+        let out = model.wire_node("out", math::neg(), &[hidden])?[0];
+        let orphan = model.wire_node("orphan", math::abs(), &[a])?[0];
+        model.set_output_outlets(&[out])?;
+        let input = tensor1(&[-1f32, 2.0, -3.0]);
+
+        let plan = SimplePlan::new(&model)?;
+        let (outputs, trace) = plan.run_with_trace(tvec!(input.clone()), &[hidden.node])?;
+        assert_eq!(outputs, plan.run(tvec!(input.clone()))?);
+        assert_eq!(trace.len(), 1);
+        let submodel = model.extract_submodel(&[a], &[hidden])?;
+        let expected = SimplePlan::new(&submodel)?.run(tvec!(input.clone()))?;
+        assert_eq!(trace[&hidden.node], expected);
+        assert!(plan.run_with_trace(tvec!(input), &[orphan.node]).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn reset_state() -> TractResult<()> {
+        use crate::ops::nn::KVCache;
+        let mut model = TypedModel::default();
+        let x = model.add_source("x", TypedFact::dt_shape(f32::datum_type(), [1, 2].as_ref())?)?;
+        let cache = model.wire_node("cache", KVCache::new(0, 8), &[x, x])?;
+        model.set_output_outlets(&cache[0..1])?;
+        let plan = SimplePlan::new(&model)?;
+        let mut state = SimpleState::new(&plan)?;
+        let sequence = |state: &mut SimpleState<_, _, _, _>, tokens: &[f32]| {
+            tokens
+                .iter()
+                .map(|&t| Ok(state.run(tvec!(tensor2(&[[t, -t]])))?.remove(0)))
+                .collect::<TractResult<Vec<_>>>()
+        };
+        let first = sequence(&mut state, &[1.0, 2.0])?;
+        assert_eq!(first[1], rctensor2(&[[1f32, -1.0], [2.0, -2.0]]));
+        let second = sequence(&mut state, &[3.0])?;
+        assert_eq!(second[0].shape(), &[3, 2]);
+        state.reset_state()?;
+        assert_eq!(sequence(&mut state, &[1.0, 2.0])?, first);
+        Ok(())
+    }
+
+    #[test]
+    fn tensor_pool() -> TractResult<()> {
+        let mut model = TypedModel::default();
+        let fact = TypedFact::dt_shape(f32::datum_type(), [2usize, 3].as_ref())?;
+        let a = model.add_source("a", fact)?;
+        let add = model.wire_node("add", math::add::unary(rctensor0(1f32)), &[a])?[0];
+        let mul = model.wire_node("mul", math::mul::unary(rctensor0(2f32)), &[add])?[0];
+        model.set_output_outlets(&[mul])?;
+        let pool = Arc::new(TensorPool::default());
+        let options = SimplePlanOptions::default().with_tensor_pool(pool.clone());
+        let plan = SimplePlan::new_with_options(&model, options)?;
+
+        let input = tensor2(&[[1f32, 2.0, 3.0], [4.0, 5.0, 6.0]]);
+        let result = plan.run(tvec!(input.clone()))?;
+        assert_eq!(pool.hits(), 0);
+        // add output, released when mul ran
+        assert_eq!(pool.len(), 1);
+        drop(result);
+        let result = plan.run(tvec!(input))?;
+        assert_eq!(*result[0], tensor2(&[[4f32, 6.0, 8.0], [10.0, 12.0, 14.0]]));
+        assert_eq!(pool.hits(), 1);
+        Ok(())
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn run_batch_parallel() -> TractResult<()> {
+        let mut model = TypedModel::default();
+        let fact = TypedFact::dt_shape(f32::datum_type(), [2usize].as_ref())?;
+        let a = model.add_source("a", fact)?;
+        let add = model.wire_node("add", math::add::unary(rctensor0(1f32)), &[a])?[0];
+        model.set_output_outlets(&[add])?;
+        let plan = SimplePlan::new(&model)?;
+        let inputs: Vec<TVec<Tensor>> =
+            (0..16).map(|i| tvec!(tensor1(&[i as f32, -(i as f32)]))).collect();
+        let outputs = plan.run_batch_parallel(inputs.clone())?;
+        assert_eq!(outputs.len(), 16);
+        for (input, output) in inputs.into_iter().zip(outputs) {
+            assert_eq!(output, plan.run(input)?);
+        }
+        Ok(())
+    }
+
+    // sleeps, then passes its input through
+    #[cfg(feature = "timeout")]
+    #[derive(Debug, Clone)]
+    struct Sleep(std::time::Duration);
+
+    #[cfg(feature = "timeout")]
+    impl Op for Sleep {
+        fn name(&self) -> Cow<str> {
+            "Sleep".into()
+        }
+        op_as_typed_op!();
+        not_a_pulsed_op!();
+    }
+
+    #[cfg(feature = "timeout")]
+    impl StatelessOp for Sleep {
+        fn eval(&self, inputs: TVec<Arc<Tensor>>) -> TractResult<TVec<Arc<Tensor>>> {
+            std::thread::sleep(self.0);
+            Ok(inputs)
+        }
+    }
+
+    #[cfg(feature = "timeout")]
+    impl TypedOp for Sleep {
+        fn output_facts(&self, inputs: &[&TypedFact]) -> TractResult<TVec<TypedFact>> {
+            Ok(tvec!(inputs[0].clone()))
+        }
+        typed_op_as_op!();
+    }
+
+    #[cfg(feature = "timeout")]
+    #[test]
+    fn timeout() -> TractResult<()> {
+        use std::time::Duration;
+        let mut model = TypedModel::default();
+        let fact = TypedFact::dt_shape(f32::datum_type(), [1usize].as_ref())?;
+        let mut x = model.add_source("a", fact)?;
+        for i in 0..3 {
+            x = model.wire_node(format!("sleep{}", i), Sleep(Duration::from_millis(50)), &[x])?[0];
+        }
+        model.set_output_outlets(&[x])?;
+
+        let options = SimplePlanOptions::default().with_timeout(Duration::from_millis(75));
+        let plan = SimplePlan::new_with_options(&model, options)?;
+        let err = plan.run(tvec!(tensor1(&[1f32]))).unwrap_err().to_string();
+        assert!(err.starts_with("Timed out after"), "{}", err);
+        assert!(err.contains("sleep1"), "{}", err);
+
+        let options = SimplePlanOptions::default().with_timeout(Duration::from_secs(60));
+        let plan = SimplePlan::new_with_options(&model, options)?;
+        assert_eq!(plan.run(tvec!(tensor1(&[1f32])))?[0], rctensor1(&[1f32]));
+        Ok(())
+    }
+
+    #[test]
+    fn run_into() -> TractResult<()> {
+        let mut model = TypedModel::default();
+        let fact = TypedFact::dt_shape(f32::datum_type(), [3usize].as_ref())?;
+        let a = model.add_source("a", fact)?;
+        let neg = model.wire_node("neg", math::neg(), &[a])?[0];
+        model.set_output_outlets(&[neg])?;
+        let plan = SimplePlan::new(&model)?;
+
+        let mut outputs = plan.allocate_outputs()?;
+        assert_eq!(outputs[0], tensor1(&[0f32, 0.0, 0.0]));
+        let buffer = outputs[0].as_ptr::<f32>()?;
+        plan.run_into(tvec!(tensor1(&[1f32, 2.0, 3.0])), &mut outputs)?;
+        assert_eq!(outputs[0], tensor1(&[-1f32, -2.0, -3.0]));
+        assert_eq!(outputs[0].as_ptr::<f32>()?, buffer);
+
+        let mut outputs = [tensor1(&[0i32])];
+        plan.run_into(tvec!(tensor1(&[1f32, 2.0, 3.0])), &mut outputs)?;
+        assert_eq!(outputs[0], tensor1(&[-1f32, -2.0, -3.0]));
+
+        assert!(plan.run_into(tvec!(tensor1(&[1f32, 2.0, 3.0])), &mut []).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn run_into_allocating_op() -> TractResult<()> {
+        let mut model = TypedModel::default();
+        let fact = TypedFact::dt_shape(f32::datum_type(), [3usize].as_ref())?;
+        let a = model.add_source("a", fact.clone())?;
+        let b = model.add_source("b", fact)?;
+        let sum = model.wire_node("sum", math::add::bin(), &[a, b])?[0];
+        model.set_output_outlets(&[sum])?;
+        let plan = SimplePlan::new(&model)?;
+        let mut state = SimpleState::new(&plan)?;
+
+        let mut outputs = plan.allocate_outputs()?;
+        let buffer = outputs[0].as_ptr::<f32>()?;
+        for i in 0..3 {
+            let b = tensor1(&[i as f32; 3]);
+            state.run_into(tvec!(tensor1(&[1f32, 2.0, 3.0]), b), &mut outputs)?;
+            assert_eq!(outputs[0], tensor1(&[1f32 + i as f32, 2.0 + i as f32, 3.0 + i as f32]));
+            assert_eq!(outputs[0].as_ptr::<f32>()?, buffer);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn dynamic_shapes() -> TractResult<()> {
+        let mut model = TypedModel::default();
+        let fact = TypedFact::dt_shape(f32::datum_type(), [2usize, 3].as_ref())?;
+        let a = model.add_source("a", fact)?;
+        let neg = model.wire_node("neg", math::neg(), &[a])?[0];
+        let sum = model.wire_node("sum", math::add::unary(rctensor0(1f32)), &[neg])?[0];
+        model.set_output_outlets(&[sum])?;
+        let plan = SimplePlan::new(&model)?;
+
+        let output = plan.run_dynamic(tvec!(ndarray::Array2::<f32>::ones((2, 3)).into()))?;
+        assert_eq!(output[0].shape(), &[2, 3]);
+        assert_eq!(plan.specialized_plans_count(), 0);
+
+        for _ in 0..2 {
+            let output = plan.run_dynamic(tvec!(ndarray::Array2::<f32>::ones((5, 3)).into()))?;
+            output[0].close_enough(&ndarray::Array2::<f32>::zeros((5, 3)).into(), false)?;
+            assert_eq!(plan.specialized_plans_count(), 1);
+        }
+        for n in 6..12 {
+            plan.run_dynamic(tvec!(ndarray::Array2::<f32>::ones((n, 3)).into()))?;
+        }
+        assert_eq!(plan.specialized_plans_count(), plan.options.specialized_plans);
+        Ok(())
+    }
+
+    // a MatMul and a Conv, over a symbolic axis
+    fn symbolic(n: TDim) -> TractResult<TypedModel> {
+        use crate::ops::cnn::Conv;
+        use crate::ops::matmul::MatMulUnary;
+        let values = |shape: &[usize]| {
+            let len = shape.iter().product::<usize>();
+            let data = (0..len).map(|i| (i % 7) as f32 - 3.0).collect();
+            ndarray::ArrayD::from_shape_vec(shape, data).unwrap().into_tensor()
+        };
+        let mut model = TypedModel::default();
+        let fact = TypedFact::dt_shape(f32::datum_type(), [n, 2.to_dim(), 4.to_dim()].as_ref())?;
+        let x = model.add_source("x", fact)?;
+        let kernel = values(&[3, 2, 3]);
+        let conv = Conv::default().kernel_shape(tvec!(3));
+        let conv = conv.to_unary(&[model.outlet_fact(x)?, &TypedFact::from(kernel)])?.unwrap();
+        let y = model.wire_node("conv", conv, &[x])?[0];
+        let mm = MatMulUnary::new(values(&[5, 3]).into_arc_tensor(), false, false, false, None);
+        let y = model.wire_node("matmul", mm, &[y])?[0];
+        model.set_output_outlets(&[y])?;
+        model.declutter()
+    }
+
+    #[test]
+    fn dynamic_shapes_specialize_and_optimize() -> TractResult<()> {
+        let input = |n: usize| {
+            let data = (0..n * 8).map(|i| i as f32 * 0.25).collect();
+            ndarray::ArrayD::from_shape_vec(vec![n, 2, 4], data).unwrap().into_tensor()
+        };
+        let model = symbolic(TDim::sym("N"))?;
+        let plan = SimplePlan::new(&model)?;
+        for &n in &[1, 3, 1] {
+            let expected = SimplePlan::new(symbolic(n.to_dim())?)?.run(tvec!(input(n)))?;
+            assert_eq!(expected[0].shape(), &[n, 5, 2]);
+            assert_eq!(plan.run_dynamic(tvec!(input(n)))?, expected);
+        }
+        assert_eq!(plan.specialized_plans_count(), 2);
+        let specialized = plan.specialized.lock().unwrap();
+        assert!(specialized.iter().all(|(_, plan)| plan.model().is_optimized()));
+        Ok(())
+    }
+
+    #[test]
+    fn dynamic_shapes_optimized() -> TractResult<()> {
+        let input = ndarray::ArrayD::<f32>::ones(vec![3, 2, 4]).into_tensor();
+        let plan = SimplePlan::new(symbolic(TDim::sym("N"))?.into_optimized()?)?;
+        let expected = SimplePlan::new(symbolic(3.to_dim())?)?.run(tvec!(input.clone()))?;
+        assert_eq!(plan.run_dynamic(tvec!(input.clone()))?, expected);
+        assert_eq!(plan.specialized_plans_count(), 0);
+        let fixed = SimplePlan::new(symbolic(2.to_dim())?.into_optimized()?)?;
+        assert!(fixed.run_dynamic(tvec!(input.clone())).is_err());
+        let fixed = SimplePlan::new_dynamic(symbolic(2.to_dim())?)?;
+        assert!(fixed.model().is_optimized());
+        assert_eq!(fixed.run_dynamic(tvec!(input))?, expected);
+        assert_eq!(fixed.specialized_plans_count(), 1);
+        Ok(())
+    }
+}
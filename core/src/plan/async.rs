@@ -0,0 +1,129 @@
+//! Running plans from async code.
+use std::sync::Arc;
+
+use tokio::sync::Semaphore;
+
+use crate::internal::*;
+use crate::model::TypedSimplePlan;
+
+/// A typed plan that can be awaited from an async runtime.
+///
+/// Each call to `run` is executed on the tokio blocking thread pool, so the
+/// async executor threads never block on inference. The plan is shared by
+/// all calls, each of them getting its own `SimpleState`.
+#[derive(Debug, Clone)]
+pub struct AsyncSimplePlan {
+    plan: Arc<TypedSimplePlan<TypedModel>>,
+    semaphore: Option<Arc<Semaphore>>,
+}
+
+impl AsyncSimplePlan {
+    pub fn new(plan: TypedSimplePlan<TypedModel>) -> AsyncSimplePlan {
+        AsyncSimplePlan { plan: Arc::new(plan), semaphore: None }
+    }
+
+    /// Allow at most `n` runs at the same time. Further callers wait for a
+    /// run to finish.
+    pub fn with_concurrency(self, n: usize) -> AsyncSimplePlan {
+        AsyncSimplePlan { semaphore: Some(Arc::new(Semaphore::new(n))), ..self }
+    }
+
+    pub fn plan(&self) -> &TypedSimplePlan<TypedModel> {
+        &self.plan
+    }
+
+    pub async fn run(&self, inputs: TVec<Tensor>) -> TractResult<TVec<Arc<Tensor>>> {
+        let _permit = match &self.semaphore {
+            Some(semaphore) => Some(
+                semaphore
+                    .clone()
+                    .acquire_owned()
+                    .await
+                    .map_err(|_| "Inference semaphore closed")?,
+            ),
+            None => None,
+        };
+        let plan = self.plan.clone();
+        tokio::task::spawn_blocking(move || plan.run(inputs))
+            .await
+            .map_err(|e| format!("Inference task failed: {}", e))?
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ops::math;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    fn plus_one() -> TractResult<AsyncSimplePlan> {
+        let mut model = TypedModel::default();
+        let fact = TypedFact::dt_shape(f32::datum_type(), [2usize].as_ref())?;
+        let a = model.add_source("a", fact)?;
+        let add = model.wire_node("add", math::add::unary(rctensor0(1f32)), &[a])?;
+        model.set_output_outlets(&add)?;
+        Ok(AsyncSimplePlan::new(SimplePlan::new(model)?))
+    }
+
+    #[tokio::test]
+    async fn run() -> TractResult<()> {
+        let plan = plus_one()?;
+        let result = plan.run(tvec!(tensor1(&[1f32, 2.0]))).await?;
+        assert_eq!(result[0], rctensor1(&[2f32, 3.0]));
+        assert!(plan.run(tvec!(tensor1(&[1i32, 2]))).await.is_err());
+        Ok(())
+    }
+
+    static RUNNING: AtomicUsize = AtomicUsize::new(0);
+    static MAX_RUNNING: AtomicUsize = AtomicUsize::new(0);
+
+    // records how many evaluations overlap
+    #[derive(Debug, Clone)]
+    struct Slow;
+
+    impl Op for Slow {
+        fn name(&self) -> Cow<str> {
+            "Slow".into()
+        }
+        op_as_typed_op!();
+        not_a_pulsed_op!();
+    }
+
+    impl StatelessOp for Slow {
+        fn eval(&self, inputs: TVec<Arc<Tensor>>) -> TractResult<TVec<Arc<Tensor>>> {
+            let running = RUNNING.fetch_add(1, Ordering::SeqCst) + 1;
+            MAX_RUNNING.fetch_max(running, Ordering::SeqCst);
+            std::thread::sleep(std::time::Duration::from_millis(20));
+            RUNNING.fetch_sub(1, Ordering::SeqCst);
+            Ok(inputs)
+        }
+    }
+
+    impl TypedOp for Slow {
+        fn output_facts(&self, inputs: &[&TypedFact]) -> TractResult<TVec<TypedFact>> {
+            Ok(tvec!(inputs[0].clone()))
+        }
+        typed_op_as_op!();
+    }
+
+    #[tokio::test]
+    async fn concurrency() -> TractResult<()> {
+        let mut model = TypedModel::default();
+        let fact = TypedFact::dt_shape(f32::datum_type(), [1usize].as_ref())?;
+        let a = model.add_source("a", fact)?;
+        let slow = model.wire_node("slow", Slow, &[a])?;
+        model.set_output_outlets(&slow)?;
+        let plan = AsyncSimplePlan::new(SimplePlan::new(model)?).with_concurrency(2);
+        let runs: Vec<_> = (0..6)
+            .map(|i| {
+                let plan = plan.clone();
+                tokio::spawn(async move { plan.run(tvec!(tensor1(&[i as f32]))).await })
+            })
+            .collect();
+        for (i, run) in runs.into_iter().enumerate() {
+            assert_eq!(run.await.unwrap()?[0], rctensor1(&[i as f32]));
+        }
+        assert!(MAX_RUNNING.load(Ordering::SeqCst) <= 2);
+        Ok(())
+    }
+}
@@ -0,0 +1,129 @@
+//! Per-node timing of plan executions.
+use std::borrow::Borrow;
+use std::fmt::{Debug, Display};
+use std::time::{Duration, Instant};
+
+use crate::internal::*;
+use crate::model::{Fact, ModelImpl};
+use crate::plan::{SimplePlan, SimpleState};
+
+/// Accumulated timings for a node.
+#[derive(Clone, Debug, PartialEq)]
+pub struct NodeProfile {
+    pub node_id: usize,
+    pub op_name: String,
+    pub total: Duration,
+    pub calls: usize,
+}
+
+/// Runs a plan, recording the time spent evaluating each node.
+///
+/// When disabled, runs go through the plain `SimpleState::run` path.
+#[derive(Debug)]
+pub struct Profiler<TI, O, M, P>
+where
+    TI: Fact + Clone + 'static,
+    O: Debug + Display + AsRef<dyn Op> + AsMut<dyn Op> + Clone + 'static,
+    M: Borrow<ModelImpl<TI, O>>,
+    P: Borrow<SimplePlan<TI, O, M>> + Clone,
+{
+    state: SimpleState<TI, O, M, P>,
+    enabled: bool,
+    profiles: HashMap<usize, NodeProfile>,
+}
+
+impl<TI, O, M, P> Profiler<TI, O, M, P>
+where
+    TI: Fact + Clone + 'static,
+    O: Debug + Display + AsRef<dyn Op> + AsMut<dyn Op> + Clone + 'static,
+    M: Borrow<ModelImpl<TI, O>>,
+    P: Borrow<SimplePlan<TI, O, M>> + Clone,
+{
+    /// Build an enabled profiler.
+    pub fn new(plan: P) -> TractResult<Profiler<TI, O, M, P>> {
+        Ok(Profiler { state: SimpleState::new(plan)?, enabled: true, profiles: HashMap::new() })
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled
+    }
+
+    pub fn run(&mut self, inputs: TVec<Tensor>) -> TractResult<TVec<Arc<Tensor>>> {
+        if !self.enabled {
+            return self.state.run(inputs);
+        }
+        let profiles = &mut self.profiles;
+        self.state.run_plan_with_eval(inputs, 0, |session, state, node, inputs| {
+            let start = Instant::now();
+            let result = crate::plan::eval(session, state, node, inputs);
+            let elapsed = start.elapsed();
+            let profile = profiles.entry(node.id).or_insert_with(|| NodeProfile {
+                node_id: node.id,
+                op_name: node.op().name().to_string(),
+                total: Duration::default(),
+                calls: 0,
+            });
+            profile.total += elapsed;
+            profile.calls += 1;
+            result
+        })
+    }
+
+    /// Profiles of the evaluated nodes, by node id.
+    pub fn report(&self) -> Vec<NodeProfile> {
+        let mut report: Vec<NodeProfile> = self.profiles.values().cloned().collect();
+        report.sort_by_key(|p| p.node_id);
+        report
+    }
+
+    /// The `n` nodes with the highest total time, slowest first.
+    pub fn top_ops(&self, n: usize) -> Vec<NodeProfile> {
+        let mut report = self.report();
+        report.sort_by(|a, b| b.total.cmp(&a.total));
+        report.truncate(n);
+        report
+    }
+
+    /// Forget the recorded timings.
+    pub fn reset(&mut self) {
+        self.profiles.clear()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ops::math;
+    use crate::ops::matmul::MatMul;
+
+    #[test]
+    fn times_are_recorded() -> TractResult<()> {
+        let mut model = TypedModel::default();
+        let fact = TypedFact::dt_shape(f32::datum_type(), [64usize, 64].as_ref())?;
+        let a = model.add_source("a", fact)?;
+        let mm = model.wire_node("mm", MatMul::default(), &[a, a])?[0];
+        let neg = model.wire_node("neg", math::neg(), &[mm])?[0];
+        model.set_output_outlets(&[neg])?;
+        let plan = SimplePlan::new(&model)?;
+        let mut profiler = Profiler::new(&plan)?;
+        let input = ndarray::Array2::<f32>::ones((64, 64));
+        for _ in 0..2 {
+            profiler.run(tvec!(input.clone().into()))?;
+        }
+        let report = profiler.report();
+        assert_eq!(report.len(), 3);
+        assert!(report.iter().all(|p| p.calls == 2));
+        assert!(report.iter().map(|p| p.total).sum::<Duration>() > Duration::default());
+        assert_eq!(profiler.top_ops(1).len(), 1);
+
+        profiler.reset();
+        profiler.set_enabled(false);
+        profiler.run(tvec!(input.into()))?;
+        assert!(profiler.report().is_empty());
+        Ok(())
+    }
+}
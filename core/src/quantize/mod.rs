@@ -0,0 +1,8 @@
+//! Post-training quantization of float models.
+//!
+//! Dynamic range quantization, which needs no data, is a standalone pass,
+//! see `passes::quantize_dynamic_range`. Quantizing activations with static
+//! scales requires to observe their ranges on a calibration dataset first.
+pub mod static_quant;
+
+pub use self::static_quant::{Calibrator, Histogram, HistogramCollector, ScaleMethod};
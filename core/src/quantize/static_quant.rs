@@ -0,0 +1,343 @@
+//! Static quantization, calibrated on sample inputs.
+use std::sync::Mutex;
+
+use crate::internal::*;
+use crate::ops::quant::{quantize_linear_u8, DequantizeLinearF32};
+
+const BINS: usize = 2048;
+
+/// Distribution of the values observed at an outlet.
+///
+/// Bins cover a range symmetric around zero, doubled (merging bins by pairs)
+/// each time a value falls outside of it, so that no prior knowledge of the
+/// values magnitude is needed.
+#[derive(Debug, Clone, Default)]
+pub struct Histogram {
+    range: f32,
+    bins: Vec<u64>,
+    min: f32,
+    max: f32,
+}
+
+impl Histogram {
+    pub fn update(&mut self, values: &[f32]) {
+        let values = values.iter().cloned().filter(|x| x.is_finite());
+        let max_abs = values.clone().fold(0.0f32, |acc, x| acc.max(x.abs()));
+        if self.bins.is_empty() {
+            self.range = if max_abs > 0.0 { max_abs } else { 1.0 };
+            self.bins = vec![0; BINS];
+            self.min = std::f32::INFINITY;
+            self.max = std::f32::NEG_INFINITY;
+        }
+        while max_abs > self.range {
+            let mut bins = vec![0; BINS];
+            for (ix, &count) in self.bins.iter().enumerate() {
+                bins[BINS / 4 + ix / 2] += count;
+            }
+            self.bins = bins;
+            self.range *= 2.0;
+        }
+        for x in values {
+            let bin = ((x + self.range) / (2.0 * self.range) * BINS as f32) as usize;
+            self.bins[bin.min(BINS - 1)] += 1;
+            self.min = self.min.min(x);
+            self.max = self.max.max(x);
+        }
+    }
+
+    pub fn count(&self) -> u64 {
+        self.bins.iter().sum()
+    }
+
+    /// Smallest and largest observed values.
+    pub fn min_max(&self) -> Option<(f32, f32)> {
+        if self.count() == 0 {
+            None
+        } else {
+            Some((self.min, self.max))
+        }
+    }
+
+    /// Narrowest range (up to the bins width) holding `fraction` of the
+    /// observed values, the same share of outliers being left out on both
+    /// sides.
+    pub fn range_covering(&self, fraction: f64) -> Option<(f32, f32)> {
+        let (min, max) = self.min_max()?;
+        let tail = (1.0 - fraction.max(0.0).min(1.0)) / 2.0 * self.count() as f64;
+        let width = 2.0 * self.range / BINS as f32;
+        let edge = |ix: usize| -self.range + ix as f32 * width;
+        let mut seen = 0;
+        let mut low = 0;
+        while (seen + self.bins[low]) as f64 <= tail {
+            seen += self.bins[low];
+            low += 1;
+        }
+        seen = 0;
+        let mut high = BINS - 1;
+        while (seen + self.bins[high]) as f64 <= tail {
+            seen += self.bins[high];
+            high -= 1;
+        }
+        Some((edge(low).max(min), edge(high + 1).min(max)))
+    }
+}
+
+/// Records the distribution of the values flowing through it.
+#[derive(Debug, Clone, new)]
+pub struct HistogramCollector {
+    pub histogram: Arc<Mutex<Histogram>>,
+}
+
+impl Op for HistogramCollector {
+    fn name(&self) -> Cow<str> {
+        "HistogramCollector".into()
+    }
+
+    op_as_typed_op!();
+    not_a_pulsed_op!();
+}
+
+impl StatelessOp for HistogramCollector {
+    fn eval(&self, inputs: TVec<Arc<Tensor>>) -> TractResult<TVec<Arc<Tensor>>> {
+        self.histogram
+            .lock()
+            .map_err(|_| "Poisoned histogram lock")?
+            .update(inputs[0].as_slice::<f32>()?);
+        Ok(inputs)
+    }
+}
+
+impl TypedOp for HistogramCollector {
+    fn output_facts(&self, inputs: &[&TypedFact]) -> TractResult<TVec<TypedFact>> {
+        Ok(tvec!(inputs[0].clone()))
+    }
+
+    typed_op_as_op!();
+}
+
+/// How to pick the quantized range of an activation from its histogram.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ScaleMethod {
+    /// Cover all the observed values.
+    MinMax,
+    /// Cover the given percentage of the observed values, clipping outliers.
+    Percentile(f64),
+}
+
+/// Calibrates and applies static quantization to a float model.
+///
+/// `Calibrator::new` instruments a copy of the model with a
+/// HistogramCollector after every non constant f32 outlet. Each call to
+/// `feed` runs one calibration sample through it, then `compute_scales`
+/// derives a u8 scale and zero point for each outlet from the gathered
+/// histograms, and `apply` quantizes the original model: the activations
+/// go through QuantizeLinear/DequantizeLinear pairs, and the weights are
+/// quantized to int8 as by `passes::quantize_dynamic_range`.
+///
+/// Outlets are remembered by node name, so `apply` expects the model given
+/// to `new` (or one with the same node names).
+#[derive(Debug)]
+pub struct Calibrator {
+    plan: TypedSimplePlan<TypedModel>,
+    histograms: Vec<((String, usize), Arc<Mutex<Histogram>>)>,
+    scales: Option<HashMap<(String, usize), (f32, u8)>>,
+}
+
+impl Calibrator {
+    pub fn new(model: &TypedModel) -> TractResult<Calibrator> {
+        let mut model = model.clone();
+        let mut histograms = vec![];
+        for id in 0..model.nodes().len() {
+            for slot in 0..model.node(id).outputs.len() {
+                let outlet = OutletId::new(id, slot);
+                let node = model.node(id);
+                let fact = &node.outputs[slot].fact;
+                if fact.datum_type != f32::datum_type() || fact.konst.is_some() {
+                    continue;
+                }
+                let histogram = Arc::new(Mutex::new(Histogram::default()));
+                histograms.push(((node.name.clone(), slot), histogram.clone()));
+                let successors = node.outputs[slot].successors.clone();
+                let name = format!("{}.histogram-{}", node.name, slot);
+                let collector =
+                    model.wire_node(name, HistogramCollector::new(histogram), &[outlet])?[0];
+                for succ in successors {
+                    model.add_edge(collector, succ)?;
+                }
+                let outputs: TVec<OutletId> = model
+                    .output_outlets()?
+                    .iter()
+                    .map(|&o| if o == outlet { collector } else { o })
+                    .collect();
+                model.set_output_outlets(&outputs)?;
+            }
+        }
+        Ok(Calibrator { plan: SimplePlan::new(model)?, histograms, scales: None })
+    }
+
+    /// Run a calibration sample through the model, updating the histograms.
+    pub fn feed(&mut self, inputs: TVec<Tensor>) -> TractResult<()> {
+        self.plan.run(inputs)?;
+        Ok(())
+    }
+
+    /// Scale and zero point of each observed outlet, once computed.
+    pub fn scales(&self) -> Option<&HashMap<(String, usize), (f32, u8)>> {
+        self.scales.as_ref()
+    }
+
+    /// Derive the u8 quantization parameters of each observed outlet. The
+    /// range is extended to include zero, so that it is exactly
+    /// representable.
+    pub fn compute_scales(&mut self, method: ScaleMethod) -> TractResult<()> {
+        let mut scales = HashMap::new();
+        for (outlet, histogram) in &self.histograms {
+            let histogram = histogram.lock().map_err(|_| "Poisoned histogram lock")?;
+            let range = match method {
+                ScaleMethod::MinMax => histogram.min_max(),
+                ScaleMethod::Percentile(p) => histogram.range_covering(p / 100.0),
+            };
+            let (min, max) = if let Some(range) = range {
+                range
+            } else {
+                bail!("No calibration data seen for {}:{}", outlet.0, outlet.1)
+            };
+            let (min, max) = (min.min(0.0), max.max(0.0));
+            let scale = if max > min { (max - min) / 255.0 } else { 1.0 };
+            let zero_point = (-min / scale).round().max(0.0).min(255.0) as u8;
+            scales.insert(outlet.clone(), (scale, zero_point));
+        }
+        self.scales = Some(scales);
+        Ok(())
+    }
+
+    /// Quantize weights and activations of `model`.
+    ///
+    /// Node ids and outlet ids obtained before the call are invalidated.
+    pub fn apply(&self, model: &mut TypedModel) -> TractResult<()> {
+        let scales = self.scales.as_ref().ok_or("Scales must be computed before applying them")?;
+        for ((name, slot), _) in &self.histograms {
+            let (scale, zero_point) = scales[&(name.clone(), *slot)];
+            let outlet = OutletId::new(model.node_by_name(name)?.id, *slot);
+            let successors = model.outlet_successors(outlet).to_vec();
+            let quant = quantize_linear_u8(scale.recip(), zero_point);
+            let wire = model.wire_node(format!("{}.quantize-{}", name, slot), quant, &[outlet])?;
+            let dequant = DequantizeLinearF32::new(scale, zero_point as i32);
+            let wire = model.wire_node(format!("{}.dequantize-{}", name, slot), dequant, &wire)?[0];
+            for succ in successors {
+                model.add_edge(wire, succ)?;
+            }
+            let outputs: TVec<OutletId> = model
+                .output_outlets()?
+                .iter()
+                .map(|&o| if o == outlet { wire } else { o })
+                .collect();
+            model.set_output_outlets(&outputs)?;
+        }
+        crate::passes::quantize_dynamic_range(model)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ops::element_wise::ElementWiseOp;
+    use crate::ops::math;
+    use crate::ops::matmul::MatMul;
+    use crate::ops::quant::QuantizeLinearU8;
+
+    #[test]
+    fn histogram_grows() {
+        let mut histogram = Histogram::default();
+        histogram.update(&[0.5, -0.25]);
+        histogram.update(&(0..1000).map(|i| i as f32 / 100.0 - 2.0).collect::<Vec<_>>());
+        assert_eq!(histogram.count(), 1002);
+        assert_eq!(histogram.min_max(), Some((-2.0, 7.99)));
+        let (low, high) = histogram.range_covering(0.9).unwrap();
+        assert!((low - -1.5).abs() < 0.02, "{}", low);
+        assert!((high - 7.5).abs() < 0.02, "{}", high);
+    }
+
+    // deterministic pseudo-random values in [-1, 1]
+    fn noise(shape: &[usize], seed: usize) -> Tensor {
+        let len = shape.iter().product();
+        let values = (0..len).map(|i| ((i * 7 + seed * 31) as f32 * 1.37).sin()).collect();
+        Tensor::from(ndarray::ArrayD::from_shape_vec(shape, values).unwrap())
+    }
+
+    fn two_layers() -> TractResult<TypedModel> {
+        let mut model = TypedModel::default();
+        let x =
+            model.add_source("x", TypedFact::dt_shape(f32::datum_type(), [4usize, 6].as_ref())?)?;
+        let w1 = model.add_const("w1", noise(&[6, 8], 1))?;
+        let y = model.wire_node("fc1", MatMul::default(), &[x, w1])?[0];
+        let y = model.wire_node("relu", math::scalar_max(tensor0(0f32)), &[y])?[0];
+        let w2 = model.add_const("w2", noise(&[8, 3], 2))?;
+        let y = model.wire_node("fc2", MatMul::default(), &[y, w2])?[0];
+        let bias = model.add_const("bias", tensor1(&[0.5f32, -0.25, 1.0]))?;
+        let y = model.wire_node("bias", math::add::bin(), &[y, bias])?[0];
+        model.set_output_outlets(&[y])?;
+        Ok(model)
+    }
+
+    fn quantized(method: ScaleMethod) -> TractResult<TypedModel> {
+        let mut model = two_layers()?;
+        let mut calibrator = Calibrator::new(&model)?;
+        for seed in 0..32 {
+            calibrator.feed(tvec!(noise(&[4, 6], seed)))?;
+        }
+        calibrator.compute_scales(method)?;
+        calibrator.apply(&mut model)?;
+        Ok(model)
+    }
+
+    // worst error on the test set, relative to the largest float output
+    fn error(model: &TypedModel) -> TractResult<f32> {
+        let float = SimplePlan::new(two_layers()?)?;
+        let quantized = SimplePlan::new(model)?;
+        let (mut worst, mut magnitude) = (0.0f32, 0.0f32);
+        for seed in 100..116 {
+            let expected = float.run(tvec!(noise(&[4, 6], seed)))?.remove(0);
+            let found = quantized.run(tvec!(noise(&[4, 6], seed)))?.remove(0);
+            for (f, e) in found.as_slice::<f32>()?.iter().zip(expected.as_slice::<f32>()?) {
+                worst = worst.max((f - e).abs());
+                magnitude = magnitude.max(e.abs());
+            }
+        }
+        Ok(worst / magnitude)
+    }
+
+    #[test]
+    fn static_quantization_accuracy() -> TractResult<()> {
+        let model = quantized(ScaleMethod::MinMax)?;
+        // input, fc1, relu, fc2 and bias outputs
+        let quantizers = model.nodes().iter().filter(|n| {
+            n.op_as::<ElementWiseOp>().map(|op| op.0.downcast_ref::<QuantizeLinearU8>().is_some())
+                == Some(true)
+        });
+        assert_eq!(quantizers.count(), 5);
+        assert!(!model.nodes().iter().any(|n| n.op_is::<HistogramCollector>()));
+        assert!(error(&model)? < 0.03);
+        let optimized = model.into_optimized()?;
+        assert!(error(&optimized)? < 0.03);
+        Ok(())
+    }
+
+    #[test]
+    fn percentile_clips_range() -> TractResult<()> {
+        let model = two_layers()?;
+        let mut calibrator = Calibrator::new(&model)?;
+        for seed in 0..32 {
+            calibrator.feed(tvec!(noise(&[4, 6], seed)))?;
+        }
+        calibrator.compute_scales(ScaleMethod::MinMax)?;
+        let min_max = calibrator.scales().unwrap().clone();
+        calibrator.compute_scales(ScaleMethod::Percentile(99.0))?;
+        for (outlet, (scale, _)) in calibrator.scales().unwrap() {
+            assert!(*scale <= min_max[outlet].0, "{:?}", outlet);
+        }
+        assert!(error(&quantized(ScaleMethod::Percentile(99.0))?)? < 0.05);
+        Ok(())
+    }
+}
@@ -1,10 +1,13 @@
 //! `Tensor`, tract main data object of interest.
+use crate::datum::unpack_4bit;
 use crate::internal::*;
 use ndarray::prelude::*;
 use std::alloc;
 use std::fmt;
 use std::mem::{align_of, size_of};
+use std::path::Path;
 
+use tract_linalg::bf16::bf16;
 use tract_linalg::f16::f16;
 
 #[cfg(feature = "serialize")]
@@ -20,6 +23,8 @@ pub struct Tensor {
     shape: TVec<usize>,
     layout: alloc::Layout,
     data: *mut u8,
+    /// Read-only file mapping holding the data, if any.
+    mmap: Option<Arc<memmap2::Mmap>>,
 }
 
 unsafe impl Send for Tensor {}
@@ -63,7 +68,19 @@ impl Drop for Tensor {
                     .for_each(|s| std::ptr::drop_in_place(s as *mut TDim));
             }
         }
-        if !self.data.is_null() && self.layout.size() > 0 {
+        if !self.data.is_null() && self.layout.size() > 0 && self.mmap.is_none() {
+            if !self.null && crate::memory::pool::recycles(self.dt) {
+                let tensor = Tensor {
+                    null: false,
+                    dt: self.dt,
+                    shape: std::mem::replace(&mut self.shape, tvec!()),
+                    layout: self.layout,
+                    data: std::mem::replace(&mut self.data, std::ptr::null_mut()),
+                    mmap: None,
+                };
+                crate::memory::pool::recycle(tensor);
+                return;
+            }
             unsafe { alloc::dealloc(self.data, self.layout) }
         }
     }
@@ -99,7 +116,10 @@ impl Tensor {
         } else if dt == TDim::datum_type() {
             return Ok(ndarray::ArrayD::<TDim>::default(shape).into());
         }
-        let bytes = shape.iter().cloned().product::<usize>() * dt.size_of();
+        if let Some(t) = crate::memory::pool::take(dt, shape, alignment) {
+            return Ok(t);
+        }
+        let bytes = dt.storage_size(shape.iter().cloned().product::<usize>());
         let layout = alloc::Layout::from_size_align(bytes, alignment)?;
         let data = if bytes == 0 {
             std::ptr::null()
@@ -108,7 +128,7 @@ impl Tensor {
             assert!(!ptr.is_null());
             ptr
         } as *mut u8;
-        Ok(Tensor { null: false, layout, dt, shape: shape.into(), data })
+        Ok(Tensor { null: false, layout, dt, shape: shape.into(), data, mmap: None })
     }
 
     /// Create an tensor from raw data.
@@ -123,11 +143,108 @@ impl Tensor {
         shape: &[usize],
         content: &[u8],
     ) -> TractResult<Tensor> {
-        let bytes = shape.iter().cloned().product::<usize>() * dt.size_of();
+        let bytes = dt.storage_size(shape.iter().cloned().product::<usize>());
         let layout = alloc::Layout::from_size_align(bytes, dt.alignment())?;
         let data = alloc::alloc(layout);
         content.as_ptr().copy_to_nonoverlapping(data, bytes);
-        Ok(Tensor { null: false, dt, shape: shape.into(), data, layout })
+        Ok(Tensor { null: false, dt, shape: shape.into(), data, layout, mmap: None })
+    }
+
+    /// Create a tensor backed by a read-only memory mapping of `len` values
+    /// of `dt` found at `offset` in the file at `path`.
+    ///
+    /// The data is not copied: pages are loaded lazily by the OS, and shared
+    /// with other mappings of the same file. Mutable accesses to the tensor
+    /// will first copy the data to the heap, leaving the file untouched. If
+    /// `offset` is not suitably aligned for `dt`, the data is copied right away.
+    ///
+    /// The file must not be modified while the tensor is alive.
+    pub fn from_mmap(
+        path: &Path,
+        offset: u64,
+        shape: &[usize],
+        dt: DatumType,
+    ) -> TractResult<Tensor> {
+        if dt == DatumType::String || dt == DatumType::TDim || dt == DatumType::Blob {
+            bail!("Can not map tensors of type {:?}", dt)
+        }
+        let len = dt.storage_size(shape.iter().product::<usize>());
+        let file = std::fs::File::open(path)?;
+        if file.metadata()?.len() < offset + len as u64 {
+            bail!("{:?} is too short for {} bytes at offset {}", path, len, offset)
+        }
+        if len == 0 {
+            return unsafe { Tensor::uninitialized_dt(dt, shape) };
+        }
+        let mmap = unsafe { memmap2::MmapOptions::new().offset(offset).len(len).map(&file)? };
+        if (mmap.as_ptr() as usize) % dt.alignment() != 0 {
+            return unsafe { Tensor::from_raw_dt(dt, shape, &mmap) };
+        }
+        Ok(Tensor {
+            null: false,
+            dt,
+            shape: shape.into(),
+            data: mmap.as_ptr() as *mut u8,
+            layout: alloc::Layout::from_size_align(len, dt.alignment())?,
+            mmap: Some(Arc::new(mmap)),
+        })
+    }
+
+    /// Create a tensor viewing `shape` values of `dt` found at `offset` in an
+    /// existing read-only mapping, that the tensor keeps alive.
+    ///
+    /// Same as `from_mmap`, but many tensors can share a single mapping of a
+    /// file. If `offset` is not suitably aligned for `dt`, the data is copied.
+    pub fn from_shared_mmap(
+        mmap: Arc<memmap2::Mmap>,
+        offset: usize,
+        shape: &[usize],
+        dt: DatumType,
+    ) -> TractResult<Tensor> {
+        if dt == DatumType::String || dt == DatumType::TDim || dt == DatumType::Blob {
+            bail!("Can not map tensors of type {:?}", dt)
+        }
+        let len = dt.storage_size(shape.iter().product::<usize>());
+        if mmap.len() < offset + len {
+            bail!("Mapping is too short for {} bytes at offset {}", len, offset)
+        }
+        if len == 0 {
+            return unsafe { Tensor::uninitialized_dt(dt, shape) };
+        }
+        let data = unsafe { mmap.as_ptr().add(offset) };
+        if (data as usize) % dt.alignment() != 0 {
+            return unsafe { Tensor::from_raw_dt(dt, shape, &mmap[offset..offset + len]) };
+        }
+        Ok(Tensor {
+            null: false,
+            dt,
+            shape: shape.into(),
+            data: data as *mut u8,
+            layout: alloc::Layout::from_size_align(len, dt.alignment())?,
+            mmap: Some(mmap),
+        })
+    }
+
+    /// Alignment of the tensor data, in bytes.
+    pub(crate) fn alignment(&self) -> usize {
+        self.layout.align()
+    }
+
+    /// Check whether the tensor data is a file mapping.
+    pub fn is_mmapped(&self) -> bool {
+        self.mmap.is_some()
+    }
+
+    /// Copy a mapped tensor data to the heap, before a mutable access.
+    fn detach_mmap(&mut self) {
+        if self.mmap.is_some() {
+            unsafe {
+                let data = alloc::alloc(self.layout);
+                self.data.copy_to_nonoverlapping(data, self.layout.size());
+                self.data = data;
+            }
+            self.mmap = None;
+        }
     }
 
     /// Creates a null tensor (this is rare, and should stay that way).
@@ -143,6 +260,7 @@ impl Tensor {
             shape: shape.into(),
             data: std::ptr::null::<u8>() as *mut u8,
             layout: alloc::Layout::from_size_align(0, dt.size_of())?,
+            mmap: None,
         })
     }
 
@@ -167,10 +285,9 @@ impl Tensor {
     }
 
     /// Reshape the tensor to `shape`.
-    pub unsafe fn into_shape(self, shape: &[usize]) -> TractResult<Tensor> {
-        let t = Tensor { shape: shape.into(), ..self };
-        std::mem::forget(self);
-        Ok(t)
+    pub unsafe fn into_shape(mut self, shape: &[usize]) -> TractResult<Tensor> {
+        self.shape = shape.into();
+        Ok(self)
     }
 
     pub fn remove_axis(&mut self, axis: usize) -> TractResult<()> {
@@ -205,6 +322,16 @@ impl Tensor {
     ///
     /// `force_full` will force the tensor to be dump in full even if it is big.
     pub fn dump(&self, force_full: bool) -> TractResult<String> {
+        if self.dt.is_packed() && !self.is_null() {
+            use itertools::Itertools;
+            let spec = InferenceFact::dt_shape(self.dt, &*self.shape);
+            let bytes = self.as_packed_bytes()?;
+            let shown = if force_full { self.len() } else { self.len().min(12) };
+            let values =
+                (0..shown).map(|ix| unpack_4bit(bytes, ix, self.dt == DatumType::I4)).join(", ");
+            let more = if shown < self.len() { "..." } else { "" };
+            return Ok(format!("{} {}{}", spec.format_dt_shape(), values, more));
+        }
         dispatch_datum!(Self::dump_t(self.dt)(self, force_full))
     }
 
@@ -285,6 +412,7 @@ impl Tensor {
 
     /// Transform the data as a mutable `ndarray::Array`.
     pub fn to_array_view_mut<'a, D: Datum>(&'a mut self) -> TractResult<ArrayViewMutD<'a, D>> {
+        self.detach_mmap();
         if self.len() != 0 {
             unsafe {
                 return Ok(ArrayViewMutD::from_shape_ptr(&*self.shape, self.data as *mut D));
@@ -302,6 +430,7 @@ impl Tensor {
 
     /// Access the data as a mutable pointer.
     pub fn as_ptr_mut<D: Datum>(&mut self) -> TractResult<*mut D> {
+        self.detach_mmap();
         self.as_ptr::<D>().map(|p| p as *mut D)
     }
 
@@ -315,6 +444,18 @@ impl Tensor {
         unsafe { Ok(std::slice::from_raw_parts_mut::<D>(self.as_ptr_mut()?, self.len())) }
     }
 
+    /// Access the raw data buffer as bytes.
+    ///
+    /// Only meaningful for plain datum types: String, TDim and Blob tensors
+    /// contain pointers.
+    pub unsafe fn as_bytes(&self) -> &[u8] {
+        if self.data.is_null() {
+            &[]
+        } else {
+            std::slice::from_raw_parts(self.data, self.layout.size())
+        }
+    }
+
     /// Access the data as a scalar.
     pub fn to_scalar<'a, D: Datum>(&'a self) -> TractResult<&D> {
         unsafe { Ok(&*(self.as_ptr::<D>()?)) }
@@ -367,6 +508,10 @@ impl Tensor {
             (F32, F16) => self.cast::<f32, f16>()?,
             (F16, F64) => self.cast::<f16, f64>()?,
             (F64, F16) => self.cast::<f64, f16>()?,
+            (BF16, F32) => self.cast::<bf16, f32>()?,
+            (F32, BF16) => self.cast::<f32, bf16>()?,
+            (BF16, F64) => self.cast::<bf16, f64>()?,
+            (F64, BF16) => self.cast::<f64, bf16>()?,
             (F32, F64) => self.cast::<f32, f64>()?,
             (F64, F32) => self.cast::<f64, f32>()?,
 
@@ -399,6 +544,8 @@ impl Tensor {
             (U16, F32) => self.cast::<u16, f32>()?,
             (U8, I32) => self.cast::<u8, i32>()?,
             (U16, I32) => self.cast::<u16, i32>()?,
+            (U8, I64) => self.cast::<u8, i64>()?,
+            (U16, I64) => self.cast::<u16, i64>()?,
 
             (F32, Bool) => self.cast::<f32, bool>()?,
             (F32, I8) => self.cast::<f32, i8>()?,
@@ -426,6 +573,82 @@ impl Tensor {
         casted.to_scalar::<D>().map(|&x| x)
     }
 
+    /// Convert to a f16 tensor, rounding floats to the nearest half.
+    pub fn to_f16(&self) -> TractResult<Tensor> {
+        Ok(self.cast_to::<f16>()?.into_owned())
+    }
+
+    /// Create a f16 tensor from `half` crate values.
+    pub fn from_f16_slice(shape: &[usize], data: &[half::f16]) -> TractResult<Tensor> {
+        let data: Vec<f16> = data.iter().map(|&x| f16(x)).collect();
+        Ok(ArrayD::from_shape_vec(shape, data)?.into())
+    }
+
+    /// Convert to a bf16 tensor, rounding floats to the nearest bfloat16.
+    pub fn cast_to_bf16(&self) -> TractResult<Tensor> {
+        Ok(self.cast_to::<bf16>()?.into_owned())
+    }
+
+    /// Widen a bf16 tensor to f32.
+    pub fn cast_from_bf16(&self) -> TractResult<Tensor> {
+        if self.dt != DatumType::BF16 {
+            bail!("Expected a bf16 tensor, got {:?}", self.dt)
+        }
+        Ok(self.cast_to::<f32>()?.into_owned())
+    }
+
+    /// Create a I4 tensor from bytes holding two values each, the first one
+    /// in the low bits.
+    pub fn from_i4_packed_bytes(data: &[u8], shape: &[usize]) -> TractResult<Tensor> {
+        Self::from_packed_bytes(DatumType::I4, data, shape)
+    }
+
+    /// Create a U4 tensor from bytes holding two values each, the first one
+    /// in the low bits.
+    pub fn from_u4_packed_bytes(data: &[u8], shape: &[usize]) -> TractResult<Tensor> {
+        Self::from_packed_bytes(DatumType::U4, data, shape)
+    }
+
+    fn from_packed_bytes(dt: DatumType, data: &[u8], shape: &[usize]) -> TractResult<Tensor> {
+        let len = shape.iter().product::<usize>();
+        if data.len() != dt.storage_size(len) {
+            bail!("{} {:?} values need {} bytes, got {}", len, dt, dt.storage_size(len), data.len())
+        }
+        if len == 0 {
+            return unsafe { Tensor::uninitialized_dt(dt, shape) };
+        }
+        let tensor = unsafe { Tensor::from_raw_dt(dt, shape, data)? };
+        if len % 2 == 1 {
+            // keep the unused high bits of the last byte clear for comparisons
+            unsafe { *tensor.data.add(len / 2) &= 0x0f };
+        }
+        Ok(tensor)
+    }
+
+    /// Access the data of a I4 or U4 tensor.
+    pub fn as_packed_bytes(&self) -> TractResult<&[u8]> {
+        if !self.dt.is_packed() {
+            bail!("Expected a packed tensor, got {:?}", self.dt)
+        }
+        if self.is_null() {
+            bail!("Null tensor")
+        }
+        Ok(unsafe { self.as_bytes() })
+    }
+
+    /// Dequantize a I4 or U4 tensor to f32, as `(q - zero_point) * scale`.
+    ///
+    /// Use `to_f16` on the result for a f16 tensor.
+    pub fn dequantize_i4(&self, scale: f32, zero_point: i8) -> TractResult<Tensor> {
+        let bytes = self.as_packed_bytes()?;
+        let signed = self.dt == DatumType::I4;
+        let mut output = unsafe { Tensor::uninitialized::<f32>(&*self.shape)? };
+        output.as_slice_mut::<f32>()?.iter_mut().enumerate().for_each(|(ix, y)| {
+            *y = (unpack_4bit(bytes, ix, signed) as i32 - zero_point as i32) as f32 * scale
+        });
+        Ok(output)
+    }
+
     /// Strict equality test on tensors.
     fn eq_t<D: Datum>(&self, other: &Tensor) -> TractResult<bool> {
         Ok(self.to_array_view::<D>()? == other.to_array_view::<D>()?)
@@ -433,6 +656,9 @@ impl Tensor {
 
     /// Strict equality test on tensors.
     fn eq_dt(&self, other: &Tensor) -> TractResult<bool> {
+        if self.dt.is_packed() {
+            return Ok(self.as_packed_bytes()? == other.as_packed_bytes()?);
+        }
         dispatch_datum!(Self::eq_t(self.dt)(self, other))
     }
 
@@ -446,27 +672,42 @@ impl Tensor {
         let layout =
             alloc::Layout::from_size_align(vec.len() * size_of::<T>(), align_of::<T>()).unwrap();
         let data = Box::into_raw(vec) as *mut u8;
-        Tensor { null: false, dt: T::datum_type(), shape, layout, data }
+        Tensor { null: false, dt: T::datum_type(), shape, layout, data, mmap: None }
     }
 
     pub fn deep_clone(&self) -> Tensor {
         if self.dt == DatumType::String {
             let data: Vec<String> = self.as_slice::<String>().unwrap().to_vec();
-            let t = Tensor { data: data.as_ptr() as *mut u8, shape: self.shape.clone(), ..*self };
+            let t = Tensor {
+                data: data.as_ptr() as *mut u8,
+                shape: self.shape.clone(),
+                mmap: None,
+                ..*self
+            };
             std::mem::forget(data);
             t
         } else if self.dt == DatumType::TDim {
             let data: Vec<TDim> = self.as_slice::<TDim>().unwrap().to_vec();
-            let t = Tensor { data: data.as_ptr() as *mut u8, shape: self.shape.clone(), ..*self };
+            let t = Tensor {
+                data: data.as_ptr() as *mut u8,
+                shape: self.shape.clone(),
+                mmap: None,
+                ..*self
+            };
             std::mem::forget(data);
             t
         } else if self.null {
-            Tensor { shape: self.shape.clone(), ..*self }
+            Tensor { shape: self.shape.clone(), mmap: None, ..*self }
+        } else if let Some(t) = crate::memory::pool::take(self.dt, &self.shape, self.layout.align())
+        {
+            // a pooled buffer of the same type and shape has the same size
+            unsafe { self.data.copy_to_nonoverlapping(t.data, self.layout.size()) };
+            t
         } else {
             unsafe {
                 let data = alloc::alloc(self.layout) as *mut u8;
                 self.data.copy_to_nonoverlapping(data, self.layout.size());
-                Tensor { data, shape: self.shape.clone(), ..*self }
+                Tensor { data, shape: self.shape.clone(), mmap: None, ..*self }
             }
         }
     }
@@ -589,3 +830,122 @@ impl IntoArcTensor for Arc<Tensor> {
         self
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn f16_round_trip() -> TractResult<()> {
+        let t = tensor2(&[[1f32, -0.5, 0.25], [1024.0, 0.0, -65504.0]]);
+        let half = t.to_f16()?;
+        assert_eq!(half.datum_type(), DatumType::F16);
+        assert_eq!(half.cast_to::<f32>()?.into_owned(), t);
+        let values: Vec<half::f16> = half.as_slice::<f16>()?.iter().map(|x| x.0).collect();
+        assert_eq!(Tensor::from_f16_slice(&[2, 3], &values)?, half);
+        Ok(())
+    }
+
+    #[test]
+    fn f16_raw_bytes_round_trip() -> TractResult<()> {
+        let t = tensor1(&[0.1f32, 3.25, -2.5e-3]).to_f16()?;
+        let bytes = unsafe { t.as_bytes() };
+        assert_eq!(bytes.len(), 6);
+        let back = unsafe { Tensor::from_raw::<f16>(&[3], bytes)? };
+        assert_eq!(back, t);
+        assert_eq!(back.as_slice::<f16>()?[0].to_bits(), half::f16::from_f32(0.1).to_bits());
+        Ok(())
+    }
+
+    #[test]
+    fn bf16_round_trip() -> TractResult<()> {
+        // exactly representable: 8 bits of mantissa, but the f32 exponent range
+        let t = tensor2(&[[1f32, -0.5, 0.25], [1.5e30, 0.0, -3.0e-30]]);
+        let t = t.cast_to_bf16()?.cast_from_bf16()?.cast_to_bf16()?;
+        assert_eq!(t.datum_type(), DatumType::BF16);
+        let back = t.cast_from_bf16()?;
+        assert_eq!(back.datum_type(), DatumType::F32);
+        assert_eq!(back.cast_to_bf16()?, t);
+        assert_eq!(back.as_slice::<f32>()?[..3], [1.0, -0.5, 0.25]);
+        let bytes = unsafe { t.as_bytes() };
+        let raw = unsafe { Tensor::from_raw::<bf16>(&[2, 3], bytes)? };
+        assert_eq!(raw, t);
+        assert_eq!(raw.as_slice::<bf16>()?[0].to_bits(), 0x3f80);
+        assert!(tensor1(&[1f32]).cast_from_bf16().is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn mmap() -> TractResult<()> {
+        let path = std::env::temp_dir().join(format!("tract-mmap-{}.bin", std::process::id()));
+        let values = [1f32, 2.0, 3.0, 4.0, 5.0, 6.0];
+        let mut bytes = vec![0u8; 8];
+        bytes.extend(values.iter().flat_map(|v| v.to_le_bytes().to_vec()));
+        std::fs::write(&path, &bytes)?;
+
+        let t = Tensor::from_mmap(&path, 8, &[2, 2], f32::datum_type())?;
+        assert!(t.is_mmapped());
+        assert_eq!(t, tensor2(&[[1f32, 2.0], [3.0, 4.0]]));
+        let shared = t.into_arc_tensor();
+        let clone = (*shared).clone();
+        assert!(!clone.is_mmapped());
+        assert_eq!(&clone, &*shared);
+
+        // mutation copies out of the mapping, leaving the file alone
+        let mut t = shared.into_tensor();
+        t.as_slice_mut::<f32>()?[0] = 42.0;
+        assert!(!t.is_mmapped());
+        assert_eq!(t, tensor2(&[[42f32, 2.0], [3.0, 4.0]]));
+        assert_eq!(std::fs::read(&path)?, bytes);
+
+        // unaligned offset
+        let t = Tensor::from_mmap(&path, 10, &[2], DatumType::I16)?;
+        assert_eq!(t.as_slice::<i16>()?, &[0x3f80, 0]);
+        let t = Tensor::from_mmap(&path, 9, &[1], f32::datum_type())?;
+        assert!(!t.is_mmapped());
+        assert_eq!(t.as_slice::<f32>()?[0].to_bits(), 0x003f_8000);
+
+        assert!(Tensor::from_mmap(&path, 12, &[6], f32::datum_type()).is_err());
+        std::fs::remove_file(&path)?;
+        Ok(())
+    }
+
+    #[test]
+    fn shared_mmap() -> TractResult<()> {
+        let path = std::env::temp_dir().join(format!("tract-shmmap-{}.bin", std::process::id()));
+        let values = [1f32, 2.0, 3.0, 4.0];
+        let bytes: Vec<u8> = values.iter().flat_map(|v| v.to_le_bytes().to_vec()).collect();
+        std::fs::write(&path, &bytes)?;
+        let mmap = Arc::new(unsafe { memmap2::Mmap::map(&std::fs::File::open(&path)?)? });
+        let a = Tensor::from_shared_mmap(mmap.clone(), 0, &[2], f32::datum_type())?;
+        let b = Tensor::from_shared_mmap(mmap.clone(), 8, &[2], f32::datum_type())?;
+        assert!(a.is_mmapped() && b.is_mmapped());
+        assert_eq!(a, tensor1(&[1f32, 2.0]));
+        assert_eq!(b, tensor1(&[3f32, 4.0]));
+        assert!(Tensor::from_shared_mmap(mmap, 8, &[3], f32::datum_type()).is_err());
+        std::fs::remove_file(&path)?;
+        Ok(())
+    }
+
+    #[test]
+    fn i4_packed() -> TractResult<()> {
+        // 1, 2, -8, -1, 7 and some garbage in the unused bits
+        let t = Tensor::from_i4_packed_bytes(&[0x21, 0xf8, 0x57], &[5])?;
+        assert_eq!(t.datum_type(), DatumType::I4);
+        assert_eq!(t.dequantize_i4(0.5, 1)?, tensor1(&[0f32, 0.5, -4.5, -1.0, 3.0]));
+        assert_eq!(t, Tensor::from_i4_packed_bytes(&[0x21, 0xf8, 0x07], &[5])?);
+        assert_ne!(t, Tensor::from_u4_packed_bytes(&[0x21, 0xf8, 0x07], &[5])?);
+        assert_eq!(format!("{:?}", t), "5xI4 1, 2, -8, -1, 7");
+        assert!(Tensor::from_i4_packed_bytes(&[0x21, 0xf8], &[5]).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn u4_packed() -> TractResult<()> {
+        let t = Tensor::from_u4_packed_bytes(&[0x21, 0xf8], &[2, 2])?;
+        assert_eq!(t.dequantize_i4(0.25, 8)?, tensor2(&[[-1.75f32, -1.5], [0.0, 1.75]]));
+        assert_eq!(t.dequantize_i4(1.0, 0)?.to_f16()?.datum_type(), DatumType::F16);
+        assert!(tensor1(&[1u8]).dequantize_i4(1.0, 0).is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,39 @@
+use tract_core::internal::*;
+use tract_core::ndarray::{arr2, Array2};
+use tract_core::ops::{math, matmul::MatMulUnary};
+
+fn linear(name: &str, w: Array2<f32>, b: &[f32]) -> TractResult<TypedModel> {
+    let k = w.shape()[0];
+    let mut model = TypedModel::default();
+    let x = model.add_source(name, TypedFact::dt_shape(f32::datum_type(), [1, k].as_ref())?)?;
+    let matmul = MatMulUnary::new(Tensor::from(w).into_arc_tensor(), true, true, true, None);
+    let y = model.wire_node(format!("{}.matmul", name), matmul, &[x])?[0];
+    let bias = math::add::unary(rctensor1(b));
+    let y = model.wire_node(format!("{}.bias", name), bias, &[y])?[0];
+    model.set_output_outlets(&[y])?;
+    Ok(model)
+}
+
+fn run(model: TypedModel, input: Tensor) -> TractResult<Arc<Tensor>> {
+    Ok(SimplePlan::new(model.into_optimized()?)?.run(tvec!(input))?.remove(0))
+}
+
+#[test]
+fn chained_linear_layers() -> TractResult<()> {
+    let first = linear("layer", arr2(&[[1f32, 0.0, 2.0], [0.0, 1.0, -1.0]]), &[0.5, 0.0, 0.0])?;
+    let second = linear("layer", arr2(&[[1f32, 2.0], [1.0, 0.0], [-1.0, 1.0]]), &[-1.0, 1.0])?;
+    let input: Tensor = arr2(&[[1f32, 2.0]]).into();
+
+    let hidden = run(first.clone(), input.clone())?;
+    assert_eq!(hidden, arr2(&[[1.5f32, 2.0, 0.0]]).into_arc_tensor());
+    let sequential = run(second.clone(), hidden.as_ref().clone())?;
+
+    let chained = first.chain(second, &[(0, 0)])?;
+    assert_eq!(chained.input_outlets()?.len(), 1);
+    assert_eq!(chained.output_outlets()?.len(), 1);
+    assert!(chained.node_by_name("layer.matmul.1").is_ok());
+    let result = run(chained, input)?;
+    assert_eq!(result, sequential);
+    assert_eq!(result, arr2(&[[2.5f32, 4.0]]).into_arc_tensor());
+    Ok(())
+}
@@ -0,0 +1,46 @@
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::routing::post;
+use axum::{Json, Router};
+
+use tract_core::internal::*;
+use tract_core::ops::math;
+use tract_core::plan::AsyncSimplePlan;
+
+// y = relu(2x - 1), on vectors of 4 values
+fn plan() -> TractResult<AsyncSimplePlan> {
+    let mut model = TypedModel::default();
+    let fact = TypedFact::dt_shape(f32::datum_type(), [4usize].as_ref())?;
+    let x = model.add_source("x", fact)?;
+    let x = model.wire_node("mul", math::mul::unary(rctensor0(2f32)), &[x])?;
+    let x = model.wire_node("sub", math::add::unary(rctensor0(-1f32)), &x)?;
+    let y = model.wire_node("relu", math::max::unary(rctensor0(0f32)), &x)?;
+    model.set_output_outlets(&y)?;
+    let plan = SimplePlan::new(model.into_optimized()?)?;
+    // at most two inferences at a time, other requests wait
+    Ok(AsyncSimplePlan::new(plan).with_concurrency(2))
+}
+
+async fn infer(
+    State(plan): State<AsyncSimplePlan>,
+    Json(x): Json<Vec<f32>>,
+) -> Result<Json<Vec<f32>>, (StatusCode, String)> {
+    let input = tract_core::ndarray::Array1::from(x).into_tensor();
+    let result =
+        plan.run(tvec!(input)).await.map_err(|e| (StatusCode::BAD_REQUEST, e.to_string()))?;
+    let y = result[0]
+        .as_slice::<f32>()
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    Ok(Json(y.to_vec()))
+}
+
+#[tokio::main]
+async fn main() -> TractResult<()> {
+    let app = Router::new().route("/infer", post(infer)).with_state(plan()?);
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:3000")
+        .await
+        .map_err(|e| format!("Could not bind: {}", e))?;
+    println!("try: curl -H 'Content-Type: application/json' -d '[0,1,2,3]' localhost:3000/infer");
+    axum::serve(listener, app).await.map_err(|e| format!("Server failed: {}", e))?;
+    Ok(())
+}
@@ -0,0 +1,422 @@
+//! Binary serialization of tract TypedModels, as flatbuffers.
+//!
+//! The graph is stored as a flatbuffer (see `schema/tract.fbs`), followed
+//! by a section holding the data of all tensors, each one aligned on
+//! `ALIGNMENT` bytes. `load` maps the file in memory and the tensors of
+//! the loaded model are views into the mapping: the weights are neither
+//! read nor copied up front, which matters for big models on small devices.
+//!
+//! Decluttered models can be loaded anywhere: call `into_optimized()` on
+//! the loaded model, tensors that codegen does not repack (constants, for
+//! instance) stay in the mapping.
+//!
+//! Float models can also be saved after codegen, and run as they are loaded,
+//! their packed weights in the mapping. The matrix products of such a model
+//! are bound to the way the kernel of the host that optimized it packs their
+//! weights: loading it fails on a host whose kernel packs them differently,
+//! an other CPU family for instance.
+//!
+//! ```no_run
+//! # use tract_core::internal::*;
+//! # fn f(model: TypedModel) -> TractResult<()> {
+//! let model = model.declutter()?;
+//! tract_flatbuf::save(&model, "model.tfb")?;
+//! let plan = SimplePlan::new(tract_flatbuf::load("model.tfb")?.into_optimized()?)?;
+//!
+//! tract_flatbuf::save(&model.into_optimized()?, "optimized.tfb")?;
+//! let plan = SimplePlan::new(tract_flatbuf::load("optimized.tfb")?)?;
+//! # Ok(()) }
+//! ```
+#[macro_use]
+extern crate error_chain;
+extern crate planus;
+extern crate tract_core;
+extern crate tract_linalg;
+
+pub mod model;
+pub mod ops;
+pub mod phy;
+pub mod tensor;
+
+#[allow(clippy::all, unused_imports)]
+mod tract_generated;
+
+pub use model::{model_for_fb, model_to_fb};
+pub use tract_generated::tract as fb;
+
+use std::convert::TryFrom;
+use std::path::Path;
+use tract_core::internal::*;
+use tract_core::memmap2::Mmap;
+
+/// First bytes of serialized models.
+pub const MAGIC: [u8; 4] = *b"TRFB";
+/// Version of the format written by this crate, and the only one it reads.
+pub const FORMAT_VERSION: u32 = 2;
+/// Alignment of the data section, and of each tensor in it, in bytes.
+pub const ALIGNMENT: usize = 64;
+
+const HEADER_LEN: usize = 16;
+
+fn data_section_start(flatbuffer_len: usize) -> usize {
+    (HEADER_LEN + flatbuffer_len + ALIGNMENT - 1) / ALIGNMENT * ALIGNMENT
+}
+
+/// Serialize a model to bytes.
+pub fn to_bytes(model: &TypedModel) -> TractResult<Vec<u8>> {
+    let mut data = tensor::DataWriter::default();
+    let model = model_to_fb(model, &mut data)?;
+    let mut builder = planus::Builder::new();
+    let flatbuffer = builder.finish(&model, None);
+    let start = data_section_start(flatbuffer.len());
+    let mut bytes = Vec::with_capacity(start + data.data.len());
+    bytes.extend_from_slice(&MAGIC);
+    bytes.extend_from_slice(&FORMAT_VERSION.to_le_bytes());
+    bytes.extend_from_slice(&(flatbuffer.len() as u64).to_le_bytes());
+    bytes.extend_from_slice(flatbuffer);
+    bytes.resize(start, 0);
+    bytes.extend_from_slice(&data.data);
+    Ok(bytes)
+}
+
+fn read(bytes: &[u8], mmap: Option<Arc<Mmap>>) -> TractResult<TypedModel> {
+    if bytes.len() < HEADER_LEN || bytes[0..4] != MAGIC {
+        bail!("Not a tract flatbuffer model")
+    }
+    let mut word = [0u8; 4];
+    word.copy_from_slice(&bytes[4..8]);
+    let version = u32::from_le_bytes(word);
+    if version != FORMAT_VERSION {
+        bail!(
+            "Unsupported tract model format version {} (this is version {})",
+            version,
+            FORMAT_VERSION
+        )
+    }
+    let mut long = [0u8; 8];
+    long.copy_from_slice(&bytes[8..16]);
+    let flatbuffer_len = u64::from_le_bytes(long) as usize;
+    if bytes.len() < HEADER_LEN + flatbuffer_len {
+        bail!("Truncated model ({} bytes)", bytes.len())
+    }
+    let flatbuffer = &bytes[HEADER_LEN..HEADER_LEN + flatbuffer_len];
+    let model = <fb::ModelRef as planus::ReadAsRoot>::read_as_root(flatbuffer)
+        .and_then(fb::Model::try_from)
+        .map_err(|e| format!("Invalid model: {}", e))?;
+    let data = tensor::DataReader { data: bytes, mmap, start: data_section_start(flatbuffer_len) };
+    model_for_fb(&model, &data)
+}
+
+/// Load a model from bytes produced by `to_bytes`, copying the tensors.
+pub fn from_bytes(bytes: &[u8]) -> TractResult<TypedModel> {
+    read(bytes, None)
+}
+
+/// Serialize a model to the file at `path`.
+pub fn save(model: &TypedModel, path: impl AsRef<Path>) -> TractResult<()> {
+    std::fs::write(path, to_bytes(model)?)?;
+    Ok(())
+}
+
+/// Load a model from the file at `path`, mapping it in memory.
+///
+/// The file must not be modified while the model, or any tensor taken from
+/// it, is alive.
+pub fn load(path: impl AsRef<Path>) -> TractResult<TypedModel> {
+    let path = path.as_ref();
+    let file = std::fs::File::open(path).chain_err(|| format!("Could not open {:?}", path))?;
+    let mmap = Arc::new(unsafe { Mmap::map(&file)? });
+    read(&mmap, Some(mmap.clone()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tract_core::ndarray;
+    use tract_core::ops::binary::TypedBinOp;
+    use tract_core::ops::cnn::{AvgPool, Conv, ConvUnary, MaxPool, PaddingSpec, PoolSpec};
+    use tract_core::ops::control_flow::If;
+    use tract_core::ops::math;
+    use tract_core::ops::matmul::phy::MatMatMulUnaryFinite;
+    use tract_core::ops::nn::{DataFormat, Reducer, TypedReduce};
+    use tract_core::ops::scan::{InputMapping, OutputMapping, StateInitializer, TypedScan};
+
+    const C: usize = 8;
+
+    fn values(shape: &[usize], seed: usize) -> Tensor {
+        let len = shape.iter().product::<usize>();
+        let data = (0..len).map(|i| ((i * 7 + seed) % 19) as f32 / 19.0 - 0.5).collect();
+        ndarray::ArrayD::from_shape_vec(shape, data).unwrap().into()
+    }
+
+    // conv stem, two residual blocks, max pool, global mean and a dense head
+    fn resnet() -> TractResult<TypedModel> {
+        let mut model = TypedModel::default();
+        let fact = TypedFact::dt_shape(f32::datum_type(), [1, C, 8, 8].as_ref())?;
+        let mut x = model.add_source("input", fact)?;
+        for b in 0..2 {
+            let residual = x;
+            for c in 0..2 {
+                let name = format!("block{}.{}", b, c);
+                let kernel = values(&[C, C, 3, 3], b * 2 + c);
+                let conv =
+                    Conv::default().padding(PaddingSpec::SameUpper).kernel_shape(tvec!(3, 3));
+                let conv =
+                    conv.to_unary(&[model.outlet_fact(x)?, &TypedFact::from(kernel)])?.unwrap();
+                x = model.wire_node(format!("{}.conv", name), conv, &[x])?[0];
+                let scale = values(&[C, 1, 1], 3).into_arc_tensor();
+                x = model.wire_node(format!("{}.scale", name), math::mul::unary(scale), &[x])?[0];
+                if c == 1 {
+                    let add = TypedBinOp(Box::new(math::Add));
+                    x = model.wire_node(format!("{}.residual", name), add, &[residual, x])?[0];
+                }
+                let relu = math::max::unary(rctensor0(0f32));
+                x = model.wire_node(format!("{}.relu", name), relu, &[x])?[0];
+            }
+        }
+        let pool_spec = PoolSpec::new(
+            DataFormat::NCHW,
+            tvec!(2, 2),
+            PaddingSpec::Valid,
+            None,
+            Some(tvec!(2, 2)),
+            None,
+        );
+        x = model.wire_node("pool", MaxPool::new(pool_spec, None), &[x])?[0];
+        let mean = TypedReduce::new(tvec!(2, 3), Reducer::Mean);
+        x = model.wire_node("mean", mean, &[x])?[0];
+        let flatten = tract_core::ops::array::FiniteReshape::new(tvec!(C, 1));
+        x = model.wire_node("flatten", flatten, &[x])?[0];
+        let dense = tract_core::ops::matmul::MatMulUnary::new(
+            values(&[10, C], 5).into_arc_tensor(),
+            false,
+            false,
+            false,
+            None,
+        );
+        x = model.wire_node("dense", dense, &[x])?[0];
+        model.set_output_outlets(&[x])?;
+        model.set_outlet_label(x, "logits".to_string());
+        model.declutter()
+    }
+
+    fn run(model: TypedModel) -> TractResult<Arc<Tensor>> {
+        let plan = SimplePlan::new(model.into_optimized()?)?;
+        Ok(plan.run(tvec!(values(&[1, C, 8, 8], 11)))?.remove(0))
+    }
+
+    #[test]
+    fn round_trip_resnet() -> TractResult<()> {
+        let model = resnet()?;
+        let bytes = to_bytes(&model)?;
+        let reloaded = from_bytes(&bytes)?;
+        assert_eq!(reloaded.nodes().len(), model.nodes().len());
+        for (a, b) in model.nodes().iter().zip(reloaded.nodes().iter()) {
+            assert_eq!(a.name, b.name);
+            assert_eq!(a.op().name(), b.op().name());
+            assert_eq!(a.inputs, b.inputs);
+        }
+        assert_eq!(reloaded.find_outlet_label("logits"), model.output_outlets()?.get(0).cloned());
+        assert_eq!(to_bytes(&reloaded)?, bytes);
+        assert_eq!(run(reloaded)?, run(model)?);
+        Ok(())
+    }
+
+    #[test]
+    fn mapped_weights() -> TractResult<()> {
+        let model = resnet()?;
+        let path = std::env::temp_dir().join(format!("tract-flatbuf-{}.tfb", std::process::id()));
+        save(&model, &path)?;
+        let reloaded = load(&path)?;
+        let mut convs = 0;
+        for node in reloaded.nodes() {
+            if let Some(conv) = node.op_as::<ConvUnary>() {
+                assert!(conv.kernel.is_mmapped());
+                assert!(conv.kernel.as_ptr::<f32>()? as usize % ALIGNMENT == 0);
+                convs += 1;
+            }
+        }
+        assert_eq!(convs, 4);
+        let result = run(reloaded)?;
+        std::fs::remove_file(&path)?;
+        assert_eq!(result, run(model)?);
+        Ok(())
+    }
+
+    #[test]
+    fn checks_header() -> TractResult<()> {
+        let mut bytes = to_bytes(&resnet()?)?;
+        bytes[4] += 1;
+        let err = from_bytes(&bytes).unwrap_err().to_string();
+        assert!(err.contains("format version"), "{}", err);
+        bytes[0] = 0;
+        let err = from_bytes(&bytes).unwrap_err().to_string();
+        assert!(err.starts_with("Not a tract"), "{}", err);
+        assert!(from_bytes(&bytes[..10]).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn optimized_resnet() -> TractResult<()> {
+        let optimized = resnet()?.into_optimized()?;
+        let path = std::env::temp_dir()
+            .join(format!("tract-flatbuf-optimized-{}.tfb", std::process::id()));
+        save(&optimized, &path)?;
+        let reloaded = load(&path)?;
+        assert!(reloaded.is_optimized());
+        assert_eq!(reloaded.nodes().len(), optimized.nodes().len());
+        for (a, b) in optimized.nodes().iter().zip(reloaded.nodes().iter()) {
+            assert_eq!(a.name, b.name);
+            assert_eq!(a.op().name(), b.op().name());
+        }
+        let mut products = 0;
+        for node in reloaded.nodes() {
+            if let Some(op) = node.op_as::<MatMatMulUnaryFinite<f32, f32, f32, f32>>() {
+                assert!(op.packed_as().iter().all(|pa| pa.is_mmapped()));
+                products += 1;
+            }
+        }
+        assert_eq!(products, 5);
+        assert_eq!(to_bytes(&reloaded)?, to_bytes(&optimized)?);
+        let input = values(&[1, C, 8, 8], 11);
+        let found = SimplePlan::new(reloaded)?.run(tvec!(input.clone()))?.remove(0);
+        std::fs::remove_file(&path)?;
+        assert_eq!(found, SimplePlan::new(optimized)?.run(tvec!(input))?.remove(0));
+        Ok(())
+    }
+
+    // depth-wise and valid convolutions, an average pool, and a product
+    // packing its input with a fused relu
+    fn physical() -> TractResult<TypedModel> {
+        let mut model = TypedModel::default();
+        let fact = TypedFact::dt_shape(f32::datum_type(), [1, C, 8, 8].as_ref())?;
+        let mut x = model.add_source("input", fact)?;
+        let convs = [
+            (Conv::default().padding(PaddingSpec::SameUpper).group(C), values(&[C, 1, 3, 3], 1)),
+            (Conv::default(), values(&[C, C, 3, 3], 2)),
+        ];
+        for (ix, (conv, kernel)) in convs.iter().enumerate() {
+            let conv = conv.clone().kernel_shape(tvec!(3, 3));
+            let conv = conv.to_unary(&[model.outlet_fact(x)?, &TypedFact::from(kernel.clone())])?;
+            x = model.wire_node(format!("conv{}", ix), conv.unwrap(), &[x])?[0];
+        }
+        let pool_spec = PoolSpec::new(
+            DataFormat::NCHW,
+            tvec!(2, 2),
+            PaddingSpec::Valid,
+            None,
+            Some(tvec!(2, 2)),
+            None,
+        );
+        x = model.wire_node("pool", AvgPool::new(pool_spec, false), &[x])?[0];
+        let flatten = tract_core::ops::array::FiniteReshape::new(tvec!(C, 9));
+        x = model.wire_node("flatten", flatten, &[x])?[0];
+        let a = values(&[4, C], 5).into_arc_tensor();
+        let product = tract_core::ops::matmul::MatMulUnary::new(a, false, false, false, None);
+        x = model.wire_node("product", product, &[x])?[0];
+        x = model.wire_node("relu", math::scalar_max(tensor0(0f32)), &[x])?[0];
+        model.set_output_outlets(&[x])?;
+        model.declutter()
+    }
+
+    #[test]
+    fn optimized_physical_ops() -> TractResult<()> {
+        let optimized = physical()?.into_optimized()?;
+        let names = optimized.nodes().iter().map(|n| n.op().name()).collect::<Vec<_>>();
+        for name in &["Conv::DepthWise<F32>", "AvgPool::Fixed<F32>", "MatMatMulPackB"] {
+            assert!(names.iter().any(|n| n == name), "{:?}", names);
+        }
+        let bytes = to_bytes(&optimized)?;
+        let reloaded = from_bytes(&bytes)?;
+        assert!(reloaded.is_optimized());
+        let product = reloaded.node_by_name("product-matmatmul")?;
+        let product = product.op_as::<MatMatMulUnaryFinite<f32, f32, f32, f32>>().unwrap();
+        assert!(product.fused_ops().is_some());
+        assert_eq!(to_bytes(&reloaded)?, bytes);
+        let input = values(&[1, C, 8, 8], 11);
+        let found = SimplePlan::new(reloaded)?.run(tvec!(input.clone()))?.remove(0);
+        let expected = SimplePlan::new(optimized)?.run(tvec!(input.clone()))?.remove(0);
+        assert_eq!(found, expected);
+        let decluttered = SimplePlan::new(physical()?)?.run(tvec!(input))?.remove(0);
+        found.close_enough(&decluttered, true)
+    }
+
+    #[test]
+    fn checks_kernel_packing() -> TractResult<()> {
+        let mut data = tensor::DataWriter::default();
+        let mut proto = model_to_fb(&resnet()?.into_optimized()?, &mut data)?;
+        for node in proto.nodes.iter_mut() {
+            if let fb::Op::MatMatMul(op) = &mut node.op {
+                op.mmm.mr += 1;
+            }
+        }
+        let data = tensor::DataReader { data: &data.data, mmap: None, start: 0 };
+        let err = model_for_fb(&proto, &data).unwrap_err();
+        let err = err.iter().map(|e| e.to_string()).collect::<Vec<_>>().join(": ");
+        assert!(err.contains("load the decluttered model"), "{}", err);
+        Ok(())
+    }
+
+    fn branch(bias: Option<Tensor>) -> TractResult<TypedModel> {
+        let mut body = TypedModel::default();
+        let x = body.add_source("x", TypedFact::dt_shape(f32::datum_type(), [3].as_ref())?)?;
+        let y = if let Some(bias) = bias {
+            body.wire_node("add", math::add::unary(bias.into_arc_tensor()), &[x])?[0]
+        } else {
+            body.wire_node("neg", math::neg(), &[x])?[0]
+        };
+        body.set_output_outlets(&[y])?;
+        Ok(body)
+    }
+
+    #[test]
+    fn round_trip_if() -> TractResult<()> {
+        let mut model = TypedModel::default();
+        let cond = TypedFact::dt_shape(bool::datum_type(), [0usize; 0].as_ref())?;
+        let cond = model.add_source("cond", cond)?;
+        let x = model.add_source("x", TypedFact::dt_shape(f32::datum_type(), [3].as_ref())?)?;
+        let op = If::new(branch(Some(tensor1(&[1f32, 2.0, 3.0])))?, branch(None)?);
+        let y = model.wire_node("if", op, &[cond, x])?[0];
+        model.set_output_outlets(&[y])?;
+        let bytes = to_bytes(&model)?;
+        let reloaded = from_bytes(&bytes)?;
+        let op = reloaded.node_by_name("if")?.op_as::<If>().unwrap();
+        assert_eq!(op.then_body.node_names().collect::<Vec<_>>(), &["x", "add"]);
+        assert_eq!(op.else_body.node_names().collect::<Vec<_>>(), &["x", "neg"]);
+        assert_eq!(to_bytes(&reloaded)?, bytes);
+        let plan = SimplePlan::new(reloaded.into_optimized()?)?;
+        let x = tensor1(&[1f32, -2.0, 0.5]);
+        assert_eq!(plan.run(tvec!(tensor0(true), x.clone()))?[0], rctensor1(&[2f32, 0.0, 3.5]));
+        assert_eq!(plan.run(tvec!(tensor0(false), x))?[0], rctensor1(&[-1f32, 2.0, -0.5]));
+        Ok(())
+    }
+
+    #[test]
+    fn round_trip_reversed_scan() -> TractResult<()> {
+        let fact = TypedFact::dt_shape(f32::datum_type(), [1].as_ref())?;
+        let mut body = TypedModel::default();
+        let acc = body.add_source("acc", fact.clone())?;
+        let x = body.add_source("x", fact)?;
+        let sum = body.wire_node("sum", TypedBinOp(Box::new(math::Add)), &[acc, x])?;
+        body.set_output_outlets(&sum)?;
+        let input_mapping = vec![
+            InputMapping::State { initializer: StateInitializer::Value(rctensor1(&[0f32])) },
+            InputMapping::Scan { slot: 0, axis: 0, chunk: 1.to_dim(), reverse: true },
+        ];
+        let output_mapping = vec![OutputMapping::new(Some(0), 0, 1.to_dim(), None, None, true)];
+        let scan = TypedScan::new(body, input_mapping, output_mapping, None)?;
+        let mut model = TypedModel::default();
+        let x = model.add_source("x", TypedFact::dt_shape(f32::datum_type(), [4].as_ref())?)?;
+        let y = model.wire_node("scan", scan, &[x])?;
+        model.set_output_outlets(&y)?;
+        let bytes = to_bytes(&model)?;
+        let reloaded = from_bytes(&bytes)?;
+        let op = reloaded.node_by_name("scan")?.op_as::<TypedScan>().unwrap();
+        assert!(op.input_mapping[1].as_scan().is_some());
+        assert_eq!(to_bytes(&reloaded)?, bytes);
+        let plan = SimplePlan::new(reloaded.into_optimized()?)?;
+        let found = plan.run(tvec!(tensor1(&[1f32, 2.0, 3.0, 4.0])))?.remove(0);
+        assert_eq!(found, rctensor1(&[4f32, 7.0, 9.0, 10.0]));
+        Ok(())
+    }
+}
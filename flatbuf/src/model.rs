@@ -0,0 +1,90 @@
+use crate::fb;
+use crate::ops::{op_from_fb, op_to_fb};
+use crate::tensor::{DataReader, DataWriter};
+use tract_core::internal::*;
+
+fn outlet_to_fb(outlet: OutletId) -> fb::Outlet {
+    fb::Outlet { node: outlet.node as u32, slot: outlet.slot as u32 }
+}
+
+fn outlet_from_fb(outlet: &fb::Outlet) -> OutletId {
+    OutletId::new(outlet.node as usize, outlet.slot as usize)
+}
+
+/// Convert a TypedModel to its flatbuffer form, appending the tensor data
+/// to `data`.
+///
+/// Only models made of the operators listed in `ops.rs` and `phy.rs` can be
+/// serialized: this covers decluttered models, and float models after
+/// codegen.
+pub fn model_to_fb(model: &TypedModel, data: &mut DataWriter) -> TractResult<fb::Model> {
+    let mut nodes = vec![];
+    let mut labels = vec![];
+    for node in model.nodes() {
+        let op = op_to_fb(&*node.op, data).chain_err(|| format!("Serializing {}", node))?;
+        let output_facts = node
+            .outputs
+            .iter()
+            .map(|o| data.fact(&o.fact))
+            .collect::<TractResult<_>>()
+            .chain_err(|| format!("Serializing {}", node))?;
+        nodes.push(fb::Node {
+            name: node.name.clone(),
+            inputs: node.inputs.iter().cloned().map(outlet_to_fb).collect(),
+            op,
+            output_facts,
+        });
+        for slot in 0..node.outputs.len() {
+            let outlet = OutletId::new(node.id, slot);
+            if let Some(label) = model.outlet_label(outlet) {
+                labels.push(fb::OutletLabel {
+                    outlet: outlet_to_fb(outlet),
+                    label: label.to_string(),
+                });
+            }
+        }
+    }
+    Ok(fb::Model {
+        nodes,
+        inputs: model.input_outlets()?.iter().cloned().map(outlet_to_fb).collect(),
+        outputs: model.output_outlets()?.iter().cloned().map(outlet_to_fb).collect(),
+        labels: Some(labels),
+        optimized: model.is_optimized(),
+    })
+}
+
+/// Rebuild a TypedModel from its flatbuffer form.
+///
+/// Nodes keep their ids, and no optimisation pass is run.
+pub fn model_for_fb(proto: &fb::Model, data: &DataReader) -> TractResult<TypedModel> {
+    let mut model = TypedModel::default();
+    for (id, node) in proto.nodes.iter().enumerate() {
+        let facts = node
+            .output_facts
+            .iter()
+            .map(|f| data.fact(f))
+            .collect::<TractResult<TVec<_>>>()
+            .chain_err(|| format!("Loading node #{} \"{}\"", id, node.name))?;
+        let op = op_from_fb(&node.op, &facts, data)
+            .chain_err(|| format!("Loading node #{} \"{}\"", id, node.name))?;
+        model.add_node(&*node.name, op, facts)?;
+    }
+    for (id, node) in proto.nodes.iter().enumerate() {
+        for (ix, input) in node.inputs.iter().enumerate() {
+            let input = outlet_from_fb(input);
+            if input.node >= proto.nodes.len() {
+                bail!("Node \"{}\" input {} refers to a missing node", node.name, ix)
+            }
+            model.add_edge(input, InletId::new(id, ix))?;
+        }
+    }
+    for label in proto.labels.iter().flatten() {
+        model.set_outlet_label(outlet_from_fb(&label.outlet), label.label.clone());
+    }
+    let inputs: TVec<_> = proto.inputs.iter().map(outlet_from_fb).collect();
+    model.set_input_outlets(&inputs)?;
+    let outputs: TVec<_> = proto.outputs.iter().map(outlet_from_fb).collect();
+    model.set_output_outlets(&outputs)?;
+    model.set_optimized(proto.optimized);
+    Ok(model)
+}
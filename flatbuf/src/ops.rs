@@ -0,0 +1,595 @@
+//! Serialization of the supported operators.
+use crate::fb;
+use crate::model::{model_for_fb, model_to_fb};
+use crate::phy;
+use crate::tensor::*;
+
+use tract_core::internal::*;
+use tract_core::ops;
+use tract_core::ops::binary::{BinMiniOp, MergeOp, MergeOpUnicast, TypedBinOp, UnaryOp};
+use tract_core::ops::cnn::{
+    AvgPool, ConvAlgorithmSelector, ConvUnary, KernelFormat, MaxPool, PaddingSpec, PoolSpec,
+};
+use tract_core::ops::control_flow::{If, Loop};
+use tract_core::ops::element_wise::{ElementWiseMiniOp, ElementWiseOp};
+use tract_core::ops::nn::{DataFormat, GlobalAvgPool, GlobalMaxPool, Reducer, TypedReduce};
+use tract_core::ops::quant::QParams;
+use tract_core::ops::scan::{InputMapping, OutputMapping, StateInitializer, TypedScan};
+
+pub(crate) fn bin_mini_op(name: &str) -> TractResult<Box<dyn BinMiniOp>> {
+    use tract_core::ops::logic::*;
+    use tract_core::ops::math::*;
+    Ok(match name {
+        "Add" => Box::new(Add),
+        "Sub" => Box::new(Sub),
+        "Mul" => Box::new(Mul),
+        "Div" => Box::new(Div),
+        "Rem" => Box::new(Rem),
+        "Min" => Box::new(Min),
+        "Max" => Box::new(Max),
+        "Pow" => Box::new(Pow),
+        "ShiftLeft" => Box::new(ShiftLeft),
+        "ShiftRight" => Box::new(ShiftRight),
+        "FlippedShiftLeft" => Box::new(FlippedShiftLeft),
+        "FlippedShiftRight" => Box::new(FlippedShiftRight),
+        "And" => Box::new(And),
+        "Or" => Box::new(Or),
+        "Xor" => Box::new(Xor),
+        "Equals" => Box::new(Equals),
+        "Lesser" => Box::new(Lesser),
+        "LesserEqual" => Box::new(LesserEqual),
+        "Greatser" => Box::new(Greatser),
+        "GreaterEqual" => Box::new(GreaterEqual),
+        _ => bail!("Unknown binary operator {}", name),
+    })
+}
+
+fn element_wise_to_fb(
+    mini_op: &dyn ElementWiseMiniOp,
+    data: &mut DataWriter,
+) -> TractResult<fb::ElementWise> {
+    use tract_core::ops::math::{ScalarMax, ScalarMin, ScalarMinMax};
+    use tract_core::ops::nn::*;
+    let any = mini_op.as_any();
+    let params = if let Some(op) = any.downcast_ref::<Elu>() {
+        vec![op.alpha]
+    } else if let Some(op) = any.downcast_ref::<HardSigmoid>() {
+        vec![op.alpha, op.beta]
+    } else if let Some(op) = any.downcast_ref::<LeakyRelu>() {
+        vec![op.alpha]
+    } else if let Some(op) = any.downcast_ref::<ParametricSoftplus>() {
+        vec![op.alpha, op.beta]
+    } else if let Some(op) = any.downcast_ref::<ScaledTanh>() {
+        vec![op.alpha, op.beta]
+    } else if let Some(op) = any.downcast_ref::<Selu>() {
+        vec![op.alpha, op.gamma]
+    } else if let Some(op) = any.downcast_ref::<ThresholdRelu>() {
+        vec![op.alpha]
+    } else {
+        vec![]
+    };
+    let tensor_params = if let Some(op) = any.downcast_ref::<ScalarMin>() {
+        vec![data.tensor(&op.min)?]
+    } else if let Some(op) = any.downcast_ref::<ScalarMax>() {
+        vec![data.tensor(&op.max)?]
+    } else if let Some(op) = any.downcast_ref::<ScalarMinMax>() {
+        vec![data.tensor(&op.min)?, data.tensor(&op.max)?]
+    } else {
+        vec![]
+    };
+    Ok(fb::ElementWise {
+        mini_op: mini_op.name(),
+        params: Some(params),
+        tensor_params: Some(tensor_params),
+    })
+}
+
+fn element_wise_from_fb(
+    fb: &fb::ElementWise,
+    data: &DataReader,
+) -> TractResult<Box<dyn ElementWiseMiniOp>> {
+    use tract_core::ops::logic::*;
+    use tract_core::ops::math::*;
+    use tract_core::ops::nn::*;
+    let p = |ix: usize| -> TractResult<f32> {
+        fb.params
+            .iter()
+            .flatten()
+            .nth(ix)
+            .cloned()
+            .ok_or_else(|| format!("Missing parameter {} for {}", ix, fb.mini_op).into())
+    };
+    let t = |ix: usize| -> TractResult<Tensor> {
+        let t = fb.tensor_params.iter().flatten().nth(ix);
+        data.tensor(t.ok_or_else(|| format!("Missing tensor {} for {}", ix, fb.mini_op))?)
+    };
+    Ok(match &*fb.mini_op {
+        "Softplus" => Box::new(Softplus {}),
+        "Softsign" => Box::new(Softsign {}),
+        "Sigmoid" => Box::new(Sigmoid {}),
+        "Elu" => Box::new(Elu { alpha: p(0)? }),
+        "HardSigmoid" => Box::new(HardSigmoid { alpha: p(0)?, beta: p(1)? }),
+        "LeakyRelu" => Box::new(LeakyRelu { alpha: p(0)? }),
+        "ParametricSoftplus" => Box::new(ParametricSoftplus { alpha: p(0)?, beta: p(1)? }),
+        "ScaledTanh" => Box::new(ScaledTanh { alpha: p(0)?, beta: p(1)? }),
+        "Selu" => Box::new(Selu { alpha: p(0)?, gamma: p(1)? }),
+        "ThresholdRelu" => Box::new(ThresholdRelu { alpha: p(0)? }),
+        "Abs" => Box::new(Abs {}),
+        "Exp" => Box::new(Exp {}),
+        "Ln" => Box::new(Ln {}),
+        "Sqrt" => Box::new(Sqrt {}),
+        "Recip" => Box::new(Recip {}),
+        "Rsqrt" => Box::new(Rsqrt {}),
+        "Ceil" => Box::new(Ceil {}),
+        "Floor" => Box::new(Floor {}),
+        "ScalarMinMax" => Box::new(ScalarMinMax { min: t(0)?, max: t(1)? }),
+        "ScalarMin" => Box::new(ScalarMin { min: t(0)? }),
+        "ScalarMax" => Box::new(ScalarMax { max: t(0)? }),
+        "Cos" => Box::new(Cos {}),
+        "Sin" => Box::new(Sin {}),
+        "Tan" => Box::new(Tan {}),
+        "Acos" => Box::new(Acos {}),
+        "Asin" => Box::new(Asin {}),
+        "Atan" => Box::new(Atan {}),
+        "Cosh" => Box::new(Cosh {}),
+        "Sinh" => Box::new(Sinh {}),
+        "Tanh" => Box::new(Tanh {}),
+        "Acosh" => Box::new(Acosh {}),
+        "Asinh" => Box::new(Asinh {}),
+        "Atanh" => Box::new(Atanh {}),
+        "Neg" => Box::new(Neg {}),
+        "Sign" => Box::new(Sign {}),
+        "Not" => Box::new(Not {}),
+        _ => bail!("Unknown element-wise operator {}", fb.mini_op),
+    })
+}
+
+pub(crate) fn usizes_to_fb(it: &[usize]) -> Vec<u64> {
+    it.iter().map(|&i| i as u64).collect()
+}
+
+pub(crate) fn usizes_from_fb(it: &[u64]) -> TVec<usize> {
+    it.iter().map(|&i| i as usize).collect()
+}
+
+pub(crate) fn data_format_to_fb(data_format: DataFormat) -> fb::DataFormat {
+    match data_format {
+        DataFormat::NCHW => fb::DataFormat::Nchw,
+        DataFormat::NHWC => fb::DataFormat::Nhwc,
+        DataFormat::CHW => fb::DataFormat::Chw,
+        DataFormat::HWC => fb::DataFormat::Hwc,
+    }
+}
+
+pub(crate) fn data_format_from_fb(data_format: fb::DataFormat) -> DataFormat {
+    match data_format {
+        fb::DataFormat::Nchw => DataFormat::NCHW,
+        fb::DataFormat::Nhwc => DataFormat::NHWC,
+        fb::DataFormat::Chw => DataFormat::CHW,
+        fb::DataFormat::Hwc => DataFormat::HWC,
+    }
+}
+
+pub(crate) fn padding_to_fb(padding: &PaddingSpec) -> Box<fb::Padding> {
+    let mode = |mode| fb::Padding { mode, before: None, after: None };
+    Box::new(match padding {
+        PaddingSpec::Valid => mode(fb::PaddingMode::Valid),
+        PaddingSpec::SameUpper => mode(fb::PaddingMode::SameUpper),
+        PaddingSpec::SameLower => mode(fb::PaddingMode::SameLower),
+        PaddingSpec::Explicit(before, after) => fb::Padding {
+            mode: fb::PaddingMode::Explicit,
+            before: Some(usizes_to_fb(before)),
+            after: Some(usizes_to_fb(after)),
+        },
+    })
+}
+
+pub(crate) fn padding_from_fb(padding: &fb::Padding) -> PaddingSpec {
+    let optional = |it: &Option<Vec<u64>>| it.as_ref().map(|it| usizes_from_fb(it));
+    match padding.mode {
+        fb::PaddingMode::Valid => PaddingSpec::Valid,
+        fb::PaddingMode::SameUpper => PaddingSpec::SameUpper,
+        fb::PaddingMode::SameLower => PaddingSpec::SameLower,
+        fb::PaddingMode::Explicit => PaddingSpec::Explicit(
+            optional(&padding.before).unwrap_or_default(),
+            optional(&padding.after).unwrap_or_default(),
+        ),
+    }
+}
+
+fn pool_spec_to_fb(spec: &PoolSpec) -> Box<fb::PoolSpec> {
+    Box::new(fb::PoolSpec {
+        data_format: data_format_to_fb(spec.data_format),
+        kernel_shape: usizes_to_fb(&spec.kernel_shape),
+        padding: padding_to_fb(&spec.padding),
+        dilations: spec.dilations.as_ref().map(|d| usizes_to_fb(d)),
+        strides: spec.strides.as_ref().map(|d| usizes_to_fb(d)),
+        output_channel_override: spec.output_channel_override.map(|c| c as u64),
+    })
+}
+
+fn pool_spec_from_fb(spec: &fb::PoolSpec) -> PoolSpec {
+    let optional = |it: &Option<Vec<u64>>| it.as_ref().map(|it| usizes_from_fb(it));
+    PoolSpec {
+        data_format: data_format_from_fb(spec.data_format),
+        kernel_shape: usizes_from_fb(&spec.kernel_shape),
+        padding: padding_from_fb(&spec.padding),
+        dilations: optional(&spec.dilations),
+        strides: optional(&spec.strides),
+        output_channel_override: spec.output_channel_override.map(|c| c as usize),
+    }
+}
+
+fn q_params_to_fb(q: &QParams, data: &mut DataWriter) -> TractResult<Box<fb::QParams>> {
+    let mut tensor = |t: &Option<Arc<Tensor>>| t.as_ref().map(|t| data.arc_tensor(t)).transpose();
+    Ok(Box::new(fb::QParams {
+        c_datum_type: datum_type_to_fb(q.c_datum_type)?,
+        zero_point_a: tensor(&q.zero_point_a)?,
+        zero_point_b: tensor(&q.zero_point_b)?,
+        zero_point_c: tensor(&q.zero_point_c)?,
+        scale_factor: q.scale_factor,
+    }))
+}
+
+fn q_params_from_fb(q: &fb::QParams, data: &DataReader) -> TractResult<QParams> {
+    let tensor = |t: &Option<Box<fb::Tensor>>| t.as_ref().map(|t| data.arc_tensor(t)).transpose();
+    Ok(QParams {
+        c_datum_type: datum_type_from_fb(q.c_datum_type),
+        zero_point_a: tensor(&q.zero_point_a)?,
+        zero_point_b: tensor(&q.zero_point_b)?,
+        zero_point_c: tensor(&q.zero_point_c)?,
+        scale_factor: q.scale_factor,
+    })
+}
+
+fn conv_to_fb(conv: &ConvUnary, data: &mut DataWriter) -> TractResult<fb::Conv> {
+    let kernel_format = match conv.kernel_fmt {
+        KernelFormat::OIHW => fb::KernelFormat::Oihw,
+        KernelFormat::HWIO => fb::KernelFormat::Hwio,
+    };
+    let algorithm = match conv.algorithm {
+        ConvAlgorithmSelector::Auto => fb::ConvAlgorithm::Auto,
+        ConvAlgorithmSelector::Gemm => fb::ConvAlgorithm::Gemm,
+        ConvAlgorithmSelector::Winograd => fb::ConvAlgorithm::Winograd,
+        ConvAlgorithmSelector::Fft => fb::ConvAlgorithm::Fft,
+    };
+    Ok(fb::Conv {
+        pool_spec: pool_spec_to_fb(&conv.pool_spec),
+        kernel_format,
+        kernel: data.arc_tensor(&conv.kernel)?,
+        group: conv.group as u64,
+        bias: conv.bias.as_ref().map(|b| data.arc_tensor(b)).transpose()?,
+        q_params: conv.q_params.as_ref().map(|q| q_params_to_fb(q, data)).transpose()?,
+        algorithm,
+    })
+}
+
+fn conv_from_fb(conv: &fb::Conv, data: &DataReader) -> TractResult<ConvUnary> {
+    let kernel_fmt = match conv.kernel_format {
+        fb::KernelFormat::Oihw => KernelFormat::OIHW,
+        fb::KernelFormat::Hwio => KernelFormat::HWIO,
+    };
+    let algorithm = match conv.algorithm {
+        fb::ConvAlgorithm::Auto => ConvAlgorithmSelector::Auto,
+        fb::ConvAlgorithm::Gemm => ConvAlgorithmSelector::Gemm,
+        fb::ConvAlgorithm::Winograd => ConvAlgorithmSelector::Winograd,
+        fb::ConvAlgorithm::Fft => ConvAlgorithmSelector::Fft,
+    };
+    Ok(ConvUnary {
+        pool_spec: pool_spec_from_fb(&conv.pool_spec),
+        kernel_fmt,
+        kernel: data.arc_tensor(&conv.kernel)?,
+        group: conv.group as usize,
+        bias: conv.bias.as_ref().map(|b| data.arc_tensor(b)).transpose()?,
+        q_params: conv.q_params.as_ref().map(|q| q_params_from_fb(q, data)).transpose()?,
+        algorithm,
+    })
+}
+
+fn reducer_to_fb(reducer: Reducer) -> fb::Reducer {
+    match reducer {
+        Reducer::L1 => fb::Reducer::L1,
+        Reducer::L2 => fb::Reducer::L2,
+        Reducer::LogSum => fb::Reducer::LogSum,
+        Reducer::LogSumExp => fb::Reducer::LogSumExp,
+        Reducer::Max => fb::Reducer::Max,
+        Reducer::Mean => fb::Reducer::Mean,
+        Reducer::Min => fb::Reducer::Min,
+        Reducer::Prod => fb::Reducer::Prod,
+        Reducer::Sum => fb::Reducer::Sum,
+        Reducer::SumSquare => fb::Reducer::SumSquare,
+    }
+}
+
+fn reducer_from_fb(reducer: fb::Reducer) -> Reducer {
+    match reducer {
+        fb::Reducer::L1 => Reducer::L1,
+        fb::Reducer::L2 => Reducer::L2,
+        fb::Reducer::LogSum => Reducer::LogSum,
+        fb::Reducer::LogSumExp => Reducer::LogSumExp,
+        fb::Reducer::Max => Reducer::Max,
+        fb::Reducer::Mean => Reducer::Mean,
+        fb::Reducer::Min => Reducer::Min,
+        fb::Reducer::Prod => Reducer::Prod,
+        fb::Reducer::Sum => Reducer::Sum,
+        fb::Reducer::SumSquare => Reducer::SumSquare,
+    }
+}
+
+fn dim_to_u64(d: &TDim) -> TractResult<u64> {
+    match d.as_const() {
+        Some(d) => Ok(d as u64),
+        None => bail!("Symbolic dimension {} can not be serialized", d),
+    }
+}
+
+fn scan_input_to_fb(
+    input: &InputMapping<TDim>,
+    data: &mut DataWriter,
+) -> TractResult<fb::ScanInput> {
+    let mut fb = fb::ScanInput::default();
+    match input {
+        InputMapping::Full { slot } => {
+            fb.kind = fb::ScanInputKind::Full;
+            fb.slot = *slot as u64;
+        }
+        InputMapping::State { initializer: StateInitializer::FromInput(slot) } => {
+            fb.kind = fb::ScanInputKind::State;
+            fb.slot = *slot as u64;
+        }
+        InputMapping::State { initializer: StateInitializer::Value(value) } => {
+            fb.kind = fb::ScanInputKind::State;
+            fb.value = Some(data.arc_tensor(value)?);
+        }
+        InputMapping::Scan { slot, axis, chunk, reverse } => {
+            fb.kind = fb::ScanInputKind::Scan;
+            fb.slot = *slot as u64;
+            fb.axis = *axis as u64;
+            fb.chunk = dim_to_u64(chunk)?;
+            fb.reverse = *reverse;
+        }
+    }
+    Ok(fb)
+}
+
+fn scan_input_from_fb(fb: &fb::ScanInput, data: &DataReader) -> TractResult<InputMapping<TDim>> {
+    let slot = fb.slot as usize;
+    Ok(match fb.kind {
+        fb::ScanInputKind::Full => InputMapping::Full { slot },
+        fb::ScanInputKind::State => {
+            let initializer = match fb.value.as_ref() {
+                Some(value) => StateInitializer::Value(data.arc_tensor(value)?),
+                None => StateInitializer::FromInput(slot),
+            };
+            InputMapping::State { initializer }
+        }
+        fb::ScanInputKind::Scan => InputMapping::Scan {
+            slot,
+            axis: fb.axis as usize,
+            chunk: TDim::from(fb.chunk as usize),
+            reverse: fb.reverse,
+        },
+    })
+}
+
+fn scan_output_to_fb(output: &OutputMapping<TDim, TDim>) -> TractResult<fb::ScanOutput> {
+    Ok(fb::ScanOutput {
+        full_slot: output.full_slot.map(|s| s as u64),
+        axis: output.axis as u64,
+        chunk: dim_to_u64(&output.chunk)?,
+        full_dim_hint: output.full_dim_hint.as_ref().map(dim_to_u64).transpose()?,
+        last_value_slot: output.last_value_slot.map(|s| s as u64),
+        state: output.state,
+    })
+}
+
+fn scan_output_from_fb(fb: &fb::ScanOutput) -> OutputMapping<TDim, TDim> {
+    OutputMapping::new(
+        fb.full_slot.map(|s| s as usize),
+        fb.axis as usize,
+        TDim::from(fb.chunk as usize),
+        fb.full_dim_hint.map(|d| TDim::from(d as usize)),
+        fb.last_value_slot.map(|s| s as usize),
+        fb.state,
+    )
+}
+
+/// Serialize an operator, if it is one of the supported ones.
+///
+/// Tensors the operator holds are appended to `data`.
+pub fn op_to_fb(op: &dyn TypedOp, data: &mut DataWriter) -> TractResult<fb::Op> {
+    let op = op.as_op();
+    Ok(if op.downcast_ref::<ops::source::TypedSource>().is_some() {
+        fb::Op::Source(Box::new(fb::Source {}))
+    } else if let Some(op) = op.downcast_ref::<ops::konst::Const>() {
+        fb::Op::Const(Box::new(fb::Const { value: data.arc_tensor(&op.value)? }))
+    } else if let Some(op) = op.downcast_ref::<UnaryOp>() {
+        fb::Op::Unary(Box::new(fb::Unary {
+            mini_op: op.mini_op.name().to_string(),
+            a: data.arc_tensor(&op.a)?,
+            inplace: op.inplace,
+        }))
+    } else if let Some(op) = op.downcast_ref::<TypedBinOp>() {
+        fb::Op::Binary(Box::new(fb::Binary { mini_op: op.0.name().to_string() }))
+    } else if let Some(op) = op.downcast_ref::<MergeOp>() {
+        fb::Op::Merge(Box::new(fb::Merge { mini_op: op.0.name().to_string() }))
+    } else if let Some(op) = op.downcast_ref::<MergeOpUnicast>() {
+        fb::Op::MergeUnicast(Box::new(fb::MergeUnicast { mini_op: op.0.name().to_string() }))
+    } else if let Some(op) = op.downcast_ref::<ElementWiseOp>() {
+        fb::Op::ElementWise(Box::new(element_wise_to_fb(&*op.0, data)?))
+    } else if let Some(op) = op.downcast_ref::<ConvUnary>() {
+        fb::Op::Conv(Box::new(conv_to_fb(op, data)?))
+    } else if let Some(op) = op.downcast_ref::<MaxPool>() {
+        fb::Op::MaxPool(Box::new(fb::MaxPool {
+            pool_spec: pool_spec_to_fb(&op.pool_spec),
+            index_datum_type: op.with_index_outputs.map(datum_type_to_fb).transpose()?,
+        }))
+    } else if let Some(op) = op.downcast_ref::<AvgPool>() {
+        fb::Op::AvgPool(Box::new(fb::AvgPool {
+            pool_spec: pool_spec_to_fb(&op.pool_spec),
+            count_include_pad: op.count_include_pad,
+        }))
+    } else if op.downcast_ref::<GlobalAvgPool>().is_some() {
+        fb::Op::GlobalAvgPool(Box::new(fb::GlobalAvgPool {}))
+    } else if op.downcast_ref::<GlobalMaxPool>().is_some() {
+        fb::Op::GlobalMaxPool(Box::new(fb::GlobalMaxPool {}))
+    } else if let Some(op) = op.downcast_ref::<TypedReduce>() {
+        fb::Op::Reduce(Box::new(fb::Reduce {
+            reducer: reducer_to_fb(op.reducer),
+            axes: usizes_to_fb(&op.axes),
+        }))
+    } else if let Some(op) = op.downcast_ref::<ops::matmul::MatMulUnary>() {
+        fb::Op::MatMul(Box::new(fb::MatMul {
+            a: data.arc_tensor(&op.a)?,
+            a_trans: op.a_trans,
+            b_trans: op.b_trans,
+            c_trans: op.c_trans,
+            q_params: op.q_params.as_ref().map(|q| q_params_to_fb(q, data)).transpose()?,
+        }))
+    } else if let Some(op) = op.downcast_ref::<ops::array::AddDims>() {
+        fb::Op::AddDims(Box::new(fb::AddDims { axes: usizes_to_fb(&op.axes) }))
+    } else if let Some(op) = op.downcast_ref::<ops::array::RmDims>() {
+        fb::Op::RmDims(Box::new(fb::RmDims { axes: usizes_to_fb(&op.axes) }))
+    } else if let Some(op) = op.downcast_ref::<ops::array::PermuteAxes>() {
+        fb::Op::PermuteAxes(Box::new(fb::PermuteAxes {
+            axes: op.axes.as_ref().map(|a| usizes_to_fb(a)),
+        }))
+    } else if let Some(op) = op.downcast_ref::<ops::array::FiniteReshape>() {
+        fb::Op::Reshape(Box::new(fb::Reshape { shape: usizes_to_fb(&op.shape) }))
+    } else if let Some(op) = op.downcast_ref::<ops::array::Slice<usize>>() {
+        fb::Op::Slice(Box::new(fb::Slice {
+            axis: op.axis as u64,
+            start: op.start as u64,
+            end: op.end as u64,
+            dims: false,
+        }))
+    } else if let Some(op) = op.downcast_ref::<ops::array::Slice<TDim>>() {
+        fb::Op::Slice(Box::new(fb::Slice {
+            axis: op.axis as u64,
+            start: dim_to_u64(&op.start)?,
+            end: dim_to_u64(&op.end)?,
+            dims: true,
+        }))
+    } else if let Some(op) = op.downcast_ref::<If>() {
+        fb::Op::If(Box::new(fb::If {
+            then_body: body_to_fb(&op.then_body, data).chain_err(|| "Serializing then body")?,
+            else_body: body_to_fb(&op.else_body, data).chain_err(|| "Serializing else body")?,
+        }))
+    } else if let Some(op) = op.downcast_ref::<Loop>() {
+        fb::Op::Loop(Box::new(fb::Loop {
+            body: body_to_fb(&op.body, data).chain_err(|| "Serializing loop body")?,
+            has_trip_count: op.has_trip_count,
+            has_cond: op.has_cond,
+        }))
+    } else if let Some(op) = op.downcast_ref::<TypedScan>() {
+        fb::Op::Scan(Box::new(fb::Scan {
+            body: body_to_fb(&op.body, data).chain_err(|| "Serializing scan body")?,
+            skip: op.skip as u64,
+            seq_length_input_slot: op.seq_length_input_slot.map(|s| s as u64),
+            input_mapping: op
+                .input_mapping
+                .iter()
+                .map(|i| scan_input_to_fb(i, data))
+                .collect::<TractResult<_>>()?,
+            output_mapping: op
+                .output_mapping
+                .iter()
+                .map(scan_output_to_fb)
+                .collect::<TractResult<_>>()?,
+        }))
+    } else if let Some(op) = phy::op_to_fb(op, data)? {
+        op
+    } else {
+        bail!("No serialization for {} operators", op.name())
+    })
+}
+
+/// Rebuild an operator. `output_facts` are the facts of the node outputs.
+pub fn op_from_fb(
+    op: &fb::Op,
+    output_facts: &[TypedFact],
+    data: &DataReader,
+) -> TractResult<Box<dyn TypedOp>> {
+    Ok(match op {
+        fb::Op::Source(_) => {
+            let fact = output_facts.get(0).ok_or("Source without output fact")?;
+            Box::new(ops::source::TypedSource::new(fact.clone()))
+        }
+        fb::Op::Const(k) => Box::new(ops::konst::Const::new(data.arc_tensor(&k.value)?)),
+        fb::Op::Unary(u) => {
+            let mut op = UnaryOp::new(bin_mini_op(&u.mini_op)?, data.arc_tensor(&u.a)?);
+            op.inplace = u.inplace;
+            Box::new(op)
+        }
+        fb::Op::Binary(b) => Box::new(TypedBinOp(bin_mini_op(&b.mini_op)?)),
+        fb::Op::Merge(b) => Box::new(MergeOp(bin_mini_op(&b.mini_op)?)),
+        fb::Op::ElementWise(e) => Box::new(ElementWiseOp(element_wise_from_fb(e, data)?)),
+        fb::Op::Conv(c) => Box::new(conv_from_fb(c, data)?),
+        fb::Op::MaxPool(p) => Box::new(MaxPool::new(
+            pool_spec_from_fb(&p.pool_spec),
+            p.index_datum_type.map(datum_type_from_fb),
+        )),
+        fb::Op::AvgPool(p) => {
+            Box::new(AvgPool::new(pool_spec_from_fb(&p.pool_spec), p.count_include_pad))
+        }
+        fb::Op::GlobalAvgPool(_) => Box::new(GlobalAvgPool::default()),
+        fb::Op::GlobalMaxPool(_) => Box::new(GlobalMaxPool::default()),
+        fb::Op::Reduce(r) => {
+            Box::new(TypedReduce::new(usizes_from_fb(&r.axes), reducer_from_fb(r.reducer)))
+        }
+        fb::Op::MatMul(m) => Box::new(ops::matmul::MatMulUnary::new(
+            data.arc_tensor(&m.a)?,
+            m.a_trans,
+            m.b_trans,
+            m.c_trans,
+            m.q_params.as_ref().map(|q| q_params_from_fb(q, data)).transpose()?,
+        )),
+        fb::Op::AddDims(a) => Box::new(ops::array::AddDims::new(usizes_from_fb(&a.axes).to_vec())),
+        fb::Op::RmDims(a) => Box::new(ops::array::RmDims::new(usizes_from_fb(&a.axes).to_vec())),
+        fb::Op::PermuteAxes(a) => Box::new(ops::array::PermuteAxes::new(
+            a.axes.as_ref().map(|axes| usizes_from_fb(axes).to_vec()),
+        )),
+        fb::Op::Reshape(s) => Box::new(ops::array::FiniteReshape::new(usizes_from_fb(&s.shape))),
+        fb::Op::Slice(s) => {
+            let (axis, start, end) = (s.axis as usize, s.start as usize, s.end as usize);
+            if s.dims {
+                Box::new(ops::array::Slice::new(axis, TDim::from(start), TDim::from(end)))
+            } else {
+                Box::new(ops::array::Slice::new(axis, start, end))
+            }
+        }
+        fb::Op::If(i) => Box::new(If::new(
+            model_for_fb(&i.then_body, data).chain_err(|| "Loading then body")?,
+            model_for_fb(&i.else_body, data).chain_err(|| "Loading else body")?,
+        )),
+        fb::Op::Loop(l) => Box::new(Loop::new(
+            model_for_fb(&l.body, data).chain_err(|| "Loading loop body")?,
+            l.has_trip_count,
+            l.has_cond,
+        )),
+        fb::Op::Scan(s) => {
+            let mut op = TypedScan::new(
+                model_for_fb(&s.body, data).chain_err(|| "Loading scan body")?,
+                s.input_mapping
+                    .iter()
+                    .map(|i| scan_input_from_fb(i, data))
+                    .collect::<TractResult<_>>()?,
+                s.output_mapping.iter().map(scan_output_from_fb).collect(),
+                s.seq_length_input_slot.map(|s| s as usize),
+            )?;
+            op.skip = s.skip as usize;
+            Box::new(op)
+        }
+        fb::Op::Im2Col(op) => Box::new(phy::im2col_from_fb(op)),
+        fb::Op::MatMatMulPackB(op) => Box::new(phy::pack_b_op_from_fb(op)),
+        fb::Op::MatMatMul(op) => Box::new(phy::mat_mat_mul_from_fb(op, data)?),
+        fb::Op::MergeUnicast(op) => Box::new(MergeOpUnicast(bin_mini_op(&op.mini_op)?)),
+        fb::Op::MaxPoolFixed(op) => Box::new(phy::max_pool_from_fb(op)),
+        fb::Op::AvgPoolFixed(op) => Box::new(phy::avg_pool_from_fb(op)),
+        fb::Op::DepthWise(op) => Box::new(phy::depth_wise_from_fb(op, data)?),
+    })
+}
+
+fn body_to_fb(body: &TypedModel, data: &mut DataWriter) -> TractResult<Box<fb::Model>> {
+    Ok(Box::new(model_to_fb(body, data)?))
+}
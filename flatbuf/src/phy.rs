@@ -0,0 +1,365 @@
+//! Serialization of the operators of optimized models.
+//!
+//! This covers the float operators codegen makes from the ones in `ops.rs`,
+//! as `tract_core::ops::physical` lowers them.
+use crate::fb;
+use crate::ops::{
+    data_format_from_fb, data_format_to_fb, padding_from_fb, padding_to_fb, usizes_from_fb,
+    usizes_to_fb,
+};
+use crate::tensor::*;
+
+use tract_core::internal::*;
+use tract_core::ndarray::ArrayD;
+use tract_core::ops::cnn::conv::{DepthWise, Im2Col};
+use tract_core::ops::cnn::{AvgPoolFixed, MaxPoolFixed, Patch, PatchSpec};
+use tract_core::ops::matmul::phy::MatMatMulPackB;
+use tract_core::ops::nn::DataShape;
+use tract_core::ops::physical::*;
+use tract_linalg::frame::PackB;
+use tract_linalg::mmm::FusedSpec;
+
+fn isizes_to_fb(it: &[isize]) -> Vec<i64> {
+    it.iter().map(|&i| i as i64).collect()
+}
+
+fn isizes_from_fb(it: &[i64]) -> TVec<isize> {
+    it.iter().map(|&i| i as isize).collect()
+}
+
+fn patch_to_fb(patch: &Patch) -> Box<fb::PatchSpec> {
+    let spec = &patch.spec;
+    Box::new(fb::PatchSpec {
+        input_shape: usizes_to_fb(&spec.input_shape),
+        input_inner_stride: spec.input_inner_stride as u64,
+        output_inner_stride: spec.output_inner_stride as u64,
+        kernel_shape: usizes_to_fb(&spec.kernel_shape),
+        strides: usizes_to_fb(&spec.strides),
+        dilations: usizes_to_fb(&spec.dilations),
+        padding: padding_to_fb(&spec.padding),
+    })
+}
+
+fn patch_from_fb(spec: &fb::PatchSpec) -> Patch {
+    PatchSpec {
+        input_shape: usizes_from_fb(&spec.input_shape),
+        input_inner_stride: spec.input_inner_stride as usize,
+        output_inner_stride: spec.output_inner_stride as usize,
+        kernel_shape: usizes_from_fb(&spec.kernel_shape),
+        strides: usizes_from_fb(&spec.strides),
+        dilations: usizes_from_fb(&spec.dilations),
+        padding: padding_from_fb(&spec.padding),
+    }
+    .into_patch()
+}
+
+fn data_shape_to_fb(shape: &DataShape) -> Box<fb::DataShape> {
+    Box::new(fb::DataShape {
+        data_format: data_format_to_fb(shape.fmt),
+        shape: usizes_to_fb(&shape.shape),
+    })
+}
+
+fn data_shape_from_fb(shape: &fb::DataShape) -> DataShape {
+    data_format_from_fb(shape.data_format).shape(usizes_from_fb(&shape.shape))
+}
+
+fn pack_b_to_fb(pack: &PackB<f32>) -> Box<fb::PackB> {
+    Box::new(fb::PackB {
+        k: pack.k() as u64,
+        n: pack.n() as u64,
+        nr: pack.nr() as u64,
+        alignment: pack.alignment() as u64,
+    })
+}
+
+fn pack_b_from_fb(pack: &fb::PackB) -> PackB<f32> {
+    PackB::new(pack.k as usize, pack.n as usize, pack.nr as usize, pack.alignment as usize)
+}
+
+fn store_to_fb(store: &StoreParts) -> Box<fb::MatrixStore> {
+    let mut fb = fb::MatrixStore::default();
+    match store {
+        StoreParts::Packed => fb.kind = fb::MatrixStoreKind::Packed,
+        StoreParts::Strides { row_stride, col_stride } => {
+            fb.kind = fb::MatrixStoreKind::Strides;
+            fb.row_stride = *row_stride as i64;
+            fb.col_stride = *col_stride as i64;
+        }
+        StoreParts::OffsetsAndPtrs { row_offsets, col_offsets } => {
+            fb.kind = fb::MatrixStoreKind::OffsetsAndPtrs;
+            fb.row_offsets = Some(isizes_to_fb(row_offsets));
+            fb.col_offsets = Some(isizes_to_fb(col_offsets));
+        }
+        StoreParts::VecStride { stride } => {
+            fb.kind = fb::MatrixStoreKind::VecStride;
+            fb.row_stride = *stride as i64;
+        }
+    }
+    Box::new(fb)
+}
+
+fn store_from_fb(fb: &fb::MatrixStore) -> StoreParts {
+    let offsets = |it: &Option<Vec<i64>>| it.as_deref().map(isizes_from_fb).unwrap_or_default();
+    match fb.kind {
+        fb::MatrixStoreKind::Packed => StoreParts::Packed,
+        fb::MatrixStoreKind::Strides => StoreParts::Strides {
+            row_stride: fb.row_stride as isize,
+            col_stride: fb.col_stride as isize,
+        },
+        fb::MatrixStoreKind::OffsetsAndPtrs => StoreParts::OffsetsAndPtrs {
+            row_offsets: offsets(&fb.row_offsets),
+            col_offsets: offsets(&fb.col_offsets),
+        },
+        fb::MatrixStoreKind::VecStride => StoreParts::VecStride { stride: fb.row_stride as isize },
+    }
+}
+
+fn mmm_to_fb(mmm: &MmmParts) -> Box<fb::Mmm> {
+    Box::new(fb::Mmm {
+        m: mmm.m as u64,
+        k: mmm.k as u64,
+        n: mmm.n as u64,
+        mr: mmm.mr as u64,
+        nr: mmm.nr as u64,
+        a_alignment: mmm.a_alignment as u64,
+        b_alignment: mmm.b_alignment as u64,
+        b_storage: store_to_fb(&mmm.b_storage),
+        c_storage: store_to_fb(&mmm.c_storage),
+    })
+}
+
+fn mmm_from_fb(fb: &fb::Mmm) -> MmmParts {
+    MmmParts {
+        m: fb.m as usize,
+        k: fb.k as usize,
+        n: fb.n as usize,
+        mr: fb.mr as usize,
+        nr: fb.nr as usize,
+        a_alignment: fb.a_alignment as usize,
+        b_alignment: fb.b_alignment as usize,
+        b_storage: store_from_fb(&fb.b_storage),
+        c_storage: store_from_fb(&fb.c_storage),
+    }
+}
+
+fn fused_to_fb(spec: &FusedSpec<f32>) -> fb::Fused {
+    let mut fb = fb::Fused::default();
+    match spec {
+        FusedSpec::Min(x) => {
+            fb.kind = fb::FusedKind::Min;
+            fb.scalar = *x;
+        }
+        FusedSpec::Max(x) => {
+            fb.kind = fb::FusedKind::Max;
+            fb.scalar = *x;
+        }
+        FusedSpec::AddC => fb.kind = fb::FusedKind::AddC,
+        FusedSpec::PerRowMul(rows) => {
+            fb.kind = fb::FusedKind::PerRowMul;
+            fb.rows = Some(rows.clone());
+        }
+        FusedSpec::PerRowAdd(rows) => {
+            fb.kind = fb::FusedKind::PerRowAdd;
+            fb.rows = Some(rows.clone());
+        }
+        FusedSpec::PerColMul(cols) => {
+            fb.kind = fb::FusedKind::PerColMul;
+            fb.cols = Some(cols.clone());
+        }
+        FusedSpec::PerColAdd(cols) => {
+            fb.kind = fb::FusedKind::PerColAdd;
+            fb.cols = Some(cols.clone());
+        }
+        FusedSpec::AddRowColProducts(rows, cols) => {
+            fb.kind = fb::FusedKind::AddRowColProducts;
+            fb.rows = Some(rows.clone());
+            fb.cols = Some(cols.clone());
+        }
+        FusedSpec::ScalarMul(x) => {
+            fb.kind = fb::FusedKind::ScalarMul;
+            fb.scalar = *x;
+        }
+        FusedSpec::ScalarAdd(x) => {
+            fb.kind = fb::FusedKind::ScalarAdd;
+            fb.scalar = *x;
+        }
+        FusedSpec::QTowardsEven(x, shift) => {
+            fb.kind = fb::FusedKind::QTowardsEven;
+            fb.scalar = *x;
+            fb.shift = *shift as u64;
+        }
+        FusedSpec::QTowardsPlusInf(x, shift) => {
+            fb.kind = fb::FusedKind::QTowardsPlusInf;
+            fb.scalar = *x;
+            fb.shift = *shift as u64;
+        }
+    }
+    fb
+}
+
+fn fused_from_fb(fb: &fb::Fused) -> FusedSpec<f32> {
+    let rows = || fb.rows.clone().unwrap_or_default();
+    let cols = || fb.cols.clone().unwrap_or_default();
+    match fb.kind {
+        fb::FusedKind::Min => FusedSpec::Min(fb.scalar),
+        fb::FusedKind::Max => FusedSpec::Max(fb.scalar),
+        fb::FusedKind::AddC => FusedSpec::AddC,
+        fb::FusedKind::PerRowMul => FusedSpec::PerRowMul(rows()),
+        fb::FusedKind::PerRowAdd => FusedSpec::PerRowAdd(rows()),
+        fb::FusedKind::PerColMul => FusedSpec::PerColMul(cols()),
+        fb::FusedKind::PerColAdd => FusedSpec::PerColAdd(cols()),
+        fb::FusedKind::AddRowColProducts => FusedSpec::AddRowColProducts(rows(), cols()),
+        fb::FusedKind::ScalarMul => FusedSpec::ScalarMul(fb.scalar),
+        fb::FusedKind::ScalarAdd => FusedSpec::ScalarAdd(fb.scalar),
+        fb::FusedKind::QTowardsEven => FusedSpec::QTowardsEven(fb.scalar, fb.shift as usize),
+        fb::FusedKind::QTowardsPlusInf => FusedSpec::QTowardsPlusInf(fb.scalar, fb.shift as usize),
+    }
+}
+
+fn mat_mat_mul_to_fb(op: &FloatMatMatMul, data: &mut DataWriter) -> TractResult<fb::MatMatMul> {
+    let op = MatMatMulParts::from_op(op)?;
+    let (c_prefix_dims, c_prefix_strides) = match &op.c_prefix_dim_and_stride {
+        Some((dims, strides)) => (Some(usizes_to_fb(dims)), Some(isizes_to_fb(strides))),
+        None => (None, None),
+    };
+    Ok(fb::MatMatMul {
+        mmm: mmm_to_fb(&op.mmm),
+        c_trans: op.c_trans,
+        bc_c_shape: usizes_to_fb(&op.bc_c_shape),
+        c_fact: Box::new(data.fact(&op.c_fact)?),
+        c_prefix_dims,
+        c_prefix_strides,
+        packed_as_shape: usizes_to_fb(op.packed_as.shape()),
+        packed_as: op.packed_as.iter().map(|pa| data.tensor(pa)).collect::<TractResult<_>>()?,
+        fused_ops_shape: op.fused_ops.as_ref().map(|f| usizes_to_fb(f.shape())),
+        fused_ops: op.fused_ops.as_ref().map(|f| {
+            f.iter()
+                .map(|ops| fb::FusedOps { ops: ops.iter().map(fused_to_fb).collect() })
+                .collect()
+        }),
+    })
+}
+
+pub fn mat_mat_mul_from_fb(fb: &fb::MatMatMul, data: &DataReader) -> TractResult<FloatMatMatMul> {
+    let packed_as =
+        fb.packed_as.iter().map(|pa| data.arc_tensor(pa)).collect::<TractResult<Vec<_>>>()?;
+    let packed_as = ArrayD::from_shape_vec(&*usizes_from_fb(&fb.packed_as_shape), packed_as)?;
+    let fused_ops = match (&fb.fused_ops_shape, &fb.fused_ops) {
+        (Some(shape), Some(ops)) => {
+            let ops = ops.iter().map(|ops| ops.ops.iter().map(fused_from_fb).collect()).collect();
+            Some(ArrayD::from_shape_vec(&*usizes_from_fb(shape), ops)?)
+        }
+        _ => None,
+    };
+    let c_prefix_dim_and_stride = match (&fb.c_prefix_dims, &fb.c_prefix_strides) {
+        (Some(dims), Some(strides)) => Some((usizes_from_fb(dims), isizes_from_fb(strides))),
+        _ => None,
+    };
+    MatMatMulParts {
+        mmm: mmm_from_fb(&fb.mmm),
+        c_trans: fb.c_trans,
+        bc_c_shape: usizes_from_fb(&fb.bc_c_shape),
+        c_fact: data.fact(&fb.c_fact)?,
+        c_prefix_dim_and_stride,
+        packed_as,
+        fused_ops,
+    }
+    .into_op()
+}
+
+pub fn im2col_from_fb(fb: &fb::Im2Col) -> Im2Col<f32> {
+    Im2Col::new(
+        patch_from_fb(&fb.patch),
+        data_shape_from_fb(&fb.input_shape),
+        fb.m as usize,
+        fb.k as usize,
+        fb.n as usize,
+        fb.group as usize,
+        fb.ci_per_group as usize,
+        pack_b_from_fb(&fb.b_pack),
+        fb.pad_value,
+    )
+}
+
+pub fn pack_b_op_from_fb(fb: &fb::MatMatMulPackB) -> MatMatMulPackB<f32> {
+    MatMatMulPackB::new(
+        pack_b_from_fb(&fb.pack_b),
+        fb.row_stride as isize,
+        fb.col_stride as isize,
+        usizes_from_fb(&fb.output_shape),
+    )
+}
+
+pub fn max_pool_from_fb(fb: &fb::MaxPoolFixed) -> MaxPoolFixed<f32> {
+    MaxPoolFixed::new(
+        patch_from_fb(&fb.patch),
+        data_shape_from_fb(&fb.input_shape),
+        data_shape_from_fb(&fb.output_shape),
+        fb.index_datum_type.map(datum_type_from_fb),
+    )
+}
+
+pub fn avg_pool_from_fb(fb: &fb::AvgPoolFixed) -> AvgPoolFixed<f32> {
+    AvgPoolFixed::new(
+        patch_from_fb(&fb.patch),
+        data_shape_from_fb(&fb.input_shape),
+        data_shape_from_fb(&fb.output_shape),
+        fb.count_include_pad,
+    )
+}
+
+pub fn depth_wise_from_fb(fb: &fb::DepthWise, data: &DataReader) -> TractResult<DepthWise<f32>> {
+    Ok(DepthWise::new(
+        patch_from_fb(&fb.patch),
+        data_shape_from_fb(&fb.input_shape),
+        data_shape_from_fb(&fb.output_shape),
+        data.tensor(&fb.kernel)?.into_array::<f32>()?,
+        fb.bias.clone(),
+    ))
+}
+
+/// Serialize an operator of an optimized model, if it is one of the
+/// supported ones.
+pub fn op_to_fb(op: &dyn Op, data: &mut DataWriter) -> TractResult<Option<fb::Op>> {
+    let op = if let Some(op) = PhysicalOp::from_op(op) { op } else { return Ok(None) };
+    Ok(Some(match op {
+        PhysicalOp::Im2Col(op) => fb::Op::Im2Col(Box::new(fb::Im2Col {
+            patch: patch_to_fb(&op.patch),
+            input_shape: data_shape_to_fb(&op.input_shape),
+            m: op.m as u64,
+            k: op.k as u64,
+            n: op.n as u64,
+            group: op.group as u64,
+            ci_per_group: op.ci_per_group as u64,
+            b_pack: pack_b_to_fb(&op.b_pack),
+            pad_value: op.pad_value(),
+        })),
+        PhysicalOp::MatMatMulPackB(op) => fb::Op::MatMatMulPackB(Box::new(fb::MatMatMulPackB {
+            pack_b: pack_b_to_fb(op.pack_b()),
+            row_stride: op.strides().0 as i64,
+            col_stride: op.strides().1 as i64,
+            output_shape: usizes_to_fb(op.output_shape()),
+        })),
+        PhysicalOp::MatMatMul(op) => fb::Op::MatMatMul(Box::new(mat_mat_mul_to_fb(op, data)?)),
+        PhysicalOp::MaxPoolFixed(op) => fb::Op::MaxPoolFixed(Box::new(fb::MaxPoolFixed {
+            patch: patch_to_fb(op.patch()),
+            input_shape: data_shape_to_fb(op.input_shape()),
+            output_shape: data_shape_to_fb(op.output_shape()),
+            index_datum_type: op.with_index_outputs().map(datum_type_to_fb).transpose()?,
+        })),
+        PhysicalOp::AvgPoolFixed(op) => fb::Op::AvgPoolFixed(Box::new(fb::AvgPoolFixed {
+            patch: patch_to_fb(op.patch()),
+            input_shape: data_shape_to_fb(op.input_shape()),
+            output_shape: data_shape_to_fb(op.output_shape()),
+            count_include_pad: op.count_include_pad(),
+        })),
+        PhysicalOp::DepthWise(op) => fb::Op::DepthWise(Box::new(fb::DepthWise {
+            patch: patch_to_fb(op.patch()),
+            input_shape: data_shape_to_fb(op.input_shape()),
+            output_shape: data_shape_to_fb(op.output_shape()),
+            kernel: Box::new(data.tensor(&op.kernel_chw().clone().into_tensor())?),
+            bias: op.bias().map(|b| b.to_vec()),
+        })),
+    }))
+}
@@ -0,0 +1,171 @@
+//! Tensors, facts and the data section.
+use crate::fb;
+use tract_core::internal::*;
+use tract_core::memmap2::Mmap;
+
+pub fn datum_type_to_fb(dt: DatumType) -> TractResult<fb::DatumType> {
+    Ok(match dt {
+        DatumType::Bool => fb::DatumType::Bool,
+        DatumType::U8 => fb::DatumType::U8,
+        DatumType::U16 => fb::DatumType::U16,
+        DatumType::I8 => fb::DatumType::I8,
+        DatumType::I16 => fb::DatumType::I16,
+        DatumType::I32 => fb::DatumType::I32,
+        DatumType::I64 => fb::DatumType::I64,
+        DatumType::F16 => fb::DatumType::F16,
+        DatumType::BF16 => fb::DatumType::Bf16,
+        DatumType::F32 => fb::DatumType::F32,
+        DatumType::F64 => fb::DatumType::F64,
+        DatumType::TDim => fb::DatumType::TDim,
+        DatumType::String => fb::DatumType::String,
+        DatumType::Blob => bail!("Blob tensors can not be serialized"),
+        DatumType::I4 | DatumType::U4 => bail!("{:?} tensors can not be serialized", dt),
+    })
+}
+
+pub fn datum_type_from_fb(dt: fb::DatumType) -> DatumType {
+    match dt {
+        fb::DatumType::Bool => DatumType::Bool,
+        fb::DatumType::U8 => DatumType::U8,
+        fb::DatumType::U16 => DatumType::U16,
+        fb::DatumType::I8 => DatumType::I8,
+        fb::DatumType::I16 => DatumType::I16,
+        fb::DatumType::I32 => DatumType::I32,
+        fb::DatumType::I64 => DatumType::I64,
+        fb::DatumType::F16 => DatumType::F16,
+        fb::DatumType::Bf16 => DatumType::BF16,
+        fb::DatumType::F32 => DatumType::F32,
+        fb::DatumType::F64 => DatumType::F64,
+        fb::DatumType::TDim => DatumType::TDim,
+        fb::DatumType::String => DatumType::String,
+    }
+}
+
+fn shape_from_fb(shape: &[u64]) -> TVec<usize> {
+    shape.iter().map(|&d| d as usize).collect()
+}
+
+/// Accumulates the tensor data section while a model is serialized.
+#[derive(Default)]
+pub struct DataWriter {
+    pub data: Vec<u8>,
+}
+
+impl DataWriter {
+    /// Serialize a tensor, appending its data to the section.
+    pub fn tensor(&mut self, t: &Tensor) -> TractResult<fb::Tensor> {
+        let mut proto = fb::Tensor {
+            datum_type: datum_type_to_fb(t.datum_type())?,
+            shape: t.shape().iter().map(|&d| d as u64).collect(),
+            ..fb::Tensor::default()
+        };
+        match t.datum_type() {
+            DatumType::String => proto.strings = Some(t.as_slice::<String>()?.to_vec()),
+            DatumType::TDim => {
+                let mut dims = vec![];
+                for d in t.as_slice::<TDim>()? {
+                    match d.as_const() {
+                        Some(d) => dims.push(d as i64),
+                        None => bail!("Symbolic dimension {} can not be serialized", d),
+                    }
+                }
+                proto.dims = Some(dims);
+            }
+            _ => {
+                if cfg!(target_endian = "big") {
+                    bail!("Tensor serialization assumes a little endian host")
+                }
+                let padding =
+                    (crate::ALIGNMENT - self.data.len() % crate::ALIGNMENT) % crate::ALIGNMENT;
+                self.data.resize(self.data.len() + padding, 0);
+                let bytes = unsafe { t.as_bytes() };
+                proto.data =
+                    Some(fb::Chunk { offset: self.data.len() as u64, len: bytes.len() as u64 });
+                self.data.extend_from_slice(bytes);
+            }
+        }
+        Ok(proto)
+    }
+
+    pub fn arc_tensor(&mut self, t: &Arc<Tensor>) -> TractResult<Box<fb::Tensor>> {
+        Ok(Box::new(self.tensor(t)?))
+    }
+
+    pub fn fact(&mut self, fact: &TypedFact) -> TractResult<fb::TypedFact> {
+        let shape = if let Some(shape) = fact.shape.as_finite() {
+            shape.iter().map(|&d| d as u64).collect()
+        } else {
+            bail!("Symbolic shape {:?} can not be serialized", fact.shape)
+        };
+        Ok(fb::TypedFact {
+            datum_type: datum_type_to_fb(fact.datum_type)?,
+            shape,
+            konst: fact.konst.as_ref().map(|k| self.arc_tensor(k)).transpose()?,
+        })
+    }
+}
+
+/// Gives access to the tensor data section while a model is loaded.
+///
+/// When the file is mapped, tensors are views into the mapping. Otherwise,
+/// their data is copied.
+pub struct DataReader<'a> {
+    pub data: &'a [u8],
+    pub mmap: Option<Arc<Mmap>>,
+    /// Offset of the data section in `data` (and in the mapping).
+    pub start: usize,
+}
+
+impl<'a> DataReader<'a> {
+    pub fn tensor(&self, t: &fb::Tensor) -> TractResult<Tensor> {
+        let dt = datum_type_from_fb(t.datum_type);
+        let shape = shape_from_fb(&t.shape);
+        match dt {
+            DatumType::String => {
+                let strings = t.strings.clone().unwrap_or_default();
+                Ok(tract_core::ndarray::ArrayD::from_shape_vec(&*shape, strings)?.into())
+            }
+            DatumType::TDim => {
+                let dims = t.dims.iter().flatten().map(|&d| TDim::from(d)).collect();
+                Ok(tract_core::ndarray::ArrayD::<TDim>::from_shape_vec(&*shape, dims)?.into())
+            }
+            _ => {
+                let len = shape.iter().product::<usize>() * dt.size_of();
+                let chunk = t.data.as_ref().ok_or("Missing tensor data")?;
+                if chunk.len as usize != len {
+                    bail!(
+                        "Expected {} bytes for a {:?} tensor of shape {:?}, found {}",
+                        len,
+                        dt,
+                        shape,
+                        chunk.len
+                    )
+                }
+                let offset = self.start + chunk.offset as usize;
+                if self.data.len() < offset + len {
+                    bail!("Tensor data at {} is out of the data section", chunk.offset)
+                }
+                if let Some(mmap) = &self.mmap {
+                    Tensor::from_shared_mmap(mmap.clone(), offset, &shape, dt)
+                } else if len == 0 {
+                    unsafe { Tensor::uninitialized_dt(dt, &shape) }
+                } else {
+                    unsafe { Tensor::from_raw_dt(dt, &shape, &self.data[offset..offset + len]) }
+                }
+            }
+        }
+    }
+
+    pub fn arc_tensor(&self, t: &fb::Tensor) -> TractResult<Arc<Tensor>> {
+        Ok(self.tensor(t)?.into_arc_tensor())
+    }
+
+    pub fn fact(&self, fact: &fb::TypedFact) -> TractResult<TypedFact> {
+        let mut typed =
+            TypedFact::dt_shape(datum_type_from_fb(fact.datum_type), &*shape_from_fb(&fact.shape))?;
+        if let Some(konst) = &fact.konst {
+            typed.konst = Some(self.arc_tensor(konst)?);
+        }
+        Ok(typed)
+    }
+}
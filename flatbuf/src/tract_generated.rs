@@ -0,0 +1,20177 @@
+pub use root::*;
+
+const _: () = ::planus::check_version_compatibility("planus-1.3.0");
+
+/// The root namespace
+///
+/// Generated from these locations:
+/// * File `schema/tract.fbs`
+#[no_implicit_prelude]
+#[allow(clippy::needless_lifetimes)]
+mod root {
+    /// The namespace `tract`
+    ///
+    /// Generated from these locations:
+    /// * File `schema/tract.fbs`
+    pub mod tract {
+        /// The struct `Outlet` in the namespace `tract`
+        ///
+        /// Generated from these locations:
+        /// * Struct `Outlet` in the file `schema/tract.fbs:17`
+        #[derive(
+            Copy,
+            Clone,
+            Debug,
+            PartialEq,
+            PartialOrd,
+            Eq,
+            Ord,
+            Hash,
+            Default,
+            ::serde::Serialize,
+            ::serde::Deserialize,
+        )]
+        pub struct Outlet {
+            /// The field `node` in the struct `Outlet`
+            pub node: u32,
+
+            /// The field `slot` in the struct `Outlet`
+            pub slot: u32,
+        }
+
+        /// # Safety
+        /// The Planus compiler correctly calculates `ALIGNMENT` and `SIZE`.
+        unsafe impl ::planus::Primitive for Outlet {
+            const ALIGNMENT: usize = 4;
+            const SIZE: usize = 8;
+        }
+
+        #[allow(clippy::identity_op)]
+        impl ::planus::WriteAsPrimitive<Outlet> for Outlet {
+            #[inline]
+            fn write<const N: usize>(&self, cursor: ::planus::Cursor<'_, N>, buffer_position: u32) {
+                let (cur, cursor) = cursor.split::<4, 4>();
+                self.node.write(cur, buffer_position - 0);
+                let (cur, cursor) = cursor.split::<4, 0>();
+                self.slot.write(cur, buffer_position - 4);
+                cursor.finish([]);
+            }
+        }
+
+        impl ::planus::WriteAsOffset<Outlet> for Outlet {
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<Outlet> {
+                unsafe {
+                    builder.write_with(8, 3, |buffer_position, bytes| {
+                        let bytes = bytes.as_mut_ptr();
+
+                        ::planus::WriteAsPrimitive::write(
+                            self,
+                            ::planus::Cursor::new(
+                                &mut *(bytes as *mut [::core::mem::MaybeUninit<u8>; 8]),
+                            ),
+                            buffer_position,
+                        );
+                    });
+                }
+                builder.current_offset()
+            }
+        }
+
+        impl ::planus::WriteAs<Outlet> for Outlet {
+            type Prepared = Self;
+            #[inline]
+            fn prepare(&self, _builder: &mut ::planus::Builder) -> Self {
+                *self
+            }
+        }
+
+        impl ::planus::WriteAsOptional<Outlet> for Outlet {
+            type Prepared = Self;
+            #[inline]
+            fn prepare(&self, _builder: &mut ::planus::Builder) -> ::core::option::Option<Self> {
+                ::core::option::Option::Some(*self)
+            }
+        }
+
+        /// Reference to a deserialized [Outlet].
+        #[derive(Copy, Clone)]
+        pub struct OutletRef<'a>(::planus::ArrayWithStartOffset<'a, 8>);
+
+        impl<'a> OutletRef<'a> {
+            /// Getter for the [`node` field](Outlet#structfield.node).
+            pub fn node(&self) -> u32 {
+                let buffer = self.0.advance_as_array::<4>(0).unwrap();
+
+                u32::from_le_bytes(*buffer.as_array())
+            }
+
+            /// Getter for the [`slot` field](Outlet#structfield.slot).
+            pub fn slot(&self) -> u32 {
+                let buffer = self.0.advance_as_array::<4>(4).unwrap();
+
+                u32::from_le_bytes(*buffer.as_array())
+            }
+        }
+
+        impl<'a> ::core::fmt::Debug for OutletRef<'a> {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                let mut f = f.debug_struct("OutletRef");
+                f.field("node", &self.node());
+                f.field("slot", &self.slot());
+                f.finish()
+            }
+        }
+
+        impl<'a> ::core::convert::From<::planus::ArrayWithStartOffset<'a, 8>> for OutletRef<'a> {
+            fn from(array: ::planus::ArrayWithStartOffset<'a, 8>) -> Self {
+                Self(array)
+            }
+        }
+
+        impl<'a> ::core::convert::From<OutletRef<'a>> for Outlet {
+            #[allow(unreachable_code)]
+            fn from(value: OutletRef<'a>) -> Self {
+                Self { node: value.node(), slot: value.slot() }
+            }
+        }
+
+        impl<'a, 'b> ::core::cmp::PartialEq<OutletRef<'a>> for OutletRef<'b> {
+            fn eq(&self, other: &OutletRef<'_>) -> bool {
+                self.node() == other.node() && self.slot() == other.slot()
+            }
+        }
+
+        impl<'a> ::core::cmp::Eq for OutletRef<'a> {}
+        impl<'a, 'b> ::core::cmp::PartialOrd<OutletRef<'a>> for OutletRef<'b> {
+            fn partial_cmp(
+                &self,
+                other: &OutletRef<'_>,
+            ) -> ::core::option::Option<::core::cmp::Ordering> {
+                ::core::option::Option::Some(::core::cmp::Ord::cmp(self, other))
+            }
+        }
+
+        impl<'a> ::core::cmp::Ord for OutletRef<'a> {
+            fn cmp(&self, other: &OutletRef<'_>) -> ::core::cmp::Ordering {
+                self.node().cmp(&other.node()).then_with(|| self.slot().cmp(&other.slot()))
+            }
+        }
+
+        impl<'a> ::core::hash::Hash for OutletRef<'a> {
+            fn hash<H: ::core::hash::Hasher>(&self, state: &mut H) {
+                self.node().hash(state);
+                self.slot().hash(state);
+            }
+        }
+
+        impl<'a> ::planus::TableRead<'a> for OutletRef<'a> {
+            #[inline]
+            fn from_buffer(
+                buffer: ::planus::SliceWithStartOffset<'a>,
+                offset: usize,
+            ) -> ::core::result::Result<Self, ::planus::errors::ErrorKind> {
+                let buffer = buffer.advance_as_array::<8>(offset)?;
+                ::core::result::Result::Ok(Self(buffer))
+            }
+        }
+
+        impl<'a> ::planus::VectorRead<'a> for OutletRef<'a> {
+            const STRIDE: usize = 8;
+
+            #[inline]
+            unsafe fn from_buffer(
+                buffer: ::planus::SliceWithStartOffset<'a>,
+                offset: usize,
+            ) -> Self {
+                Self(unsafe { buffer.unchecked_advance_as_array(offset) })
+            }
+        }
+
+        /// # Safety
+        /// The planus compiler generates implementations that initialize
+        /// the bytes in `write_values`.
+        unsafe impl ::planus::VectorWrite<Outlet> for Outlet {
+            const STRIDE: usize = 8;
+
+            type Value = Outlet;
+
+            #[inline]
+            fn prepare(&self, _builder: &mut ::planus::Builder) -> Self::Value {
+                *self
+            }
+
+            #[inline]
+            unsafe fn write_values(
+                values: &[Outlet],
+                bytes: *mut ::core::mem::MaybeUninit<u8>,
+                buffer_position: u32,
+            ) {
+                let bytes = bytes as *mut [::core::mem::MaybeUninit<u8>; 8];
+                for (i, v) in ::core::iter::Iterator::enumerate(values.iter()) {
+                    ::planus::WriteAsPrimitive::write(
+                        v,
+                        ::planus::Cursor::new(unsafe { &mut *bytes.add(i) }),
+                        buffer_position - (8 * i) as u32,
+                    );
+                }
+            }
+        }
+
+        /// The table `OutletLabel` in the namespace `tract`
+        ///
+        /// Generated from these locations:
+        /// * Table `OutletLabel` in the file `schema/tract.fbs:22`
+        #[derive(
+            Clone,
+            Debug,
+            PartialEq,
+            PartialOrd,
+            Eq,
+            Ord,
+            Hash,
+            ::serde::Serialize,
+            ::serde::Deserialize,
+        )]
+        pub struct OutletLabel {
+            /// The field `outlet` in the table `OutletLabel`
+            pub outlet: self::Outlet,
+            /// The field `label` in the table `OutletLabel`
+            pub label: ::planus::alloc::string::String,
+        }
+
+        #[allow(clippy::derivable_impls)]
+        impl ::core::default::Default for OutletLabel {
+            fn default() -> Self {
+                Self {
+                    outlet: ::core::default::Default::default(),
+                    label: ::core::default::Default::default(),
+                }
+            }
+        }
+
+        impl OutletLabel {
+            /// Creates a [OutletLabelBuilder] for serializing an instance of this table.
+            #[inline]
+            pub fn builder() -> OutletLabelBuilder<()> {
+                OutletLabelBuilder(())
+            }
+
+            #[allow(clippy::too_many_arguments)]
+            pub fn create(
+                builder: &mut ::planus::Builder,
+                field_outlet: impl ::planus::WriteAs<self::Outlet>,
+                field_label: impl ::planus::WriteAs<::planus::Offset<str>>,
+            ) -> ::planus::Offset<Self> {
+                let prepared_outlet = field_outlet.prepare(builder);
+                let prepared_label = field_label.prepare(builder);
+
+                let mut table_writer: ::planus::table_writer::TableWriter<8> =
+                    ::core::default::Default::default();
+                table_writer.write_entry::<self::Outlet>(0);
+                table_writer.write_entry::<::planus::Offset<str>>(1);
+
+                unsafe {
+                    table_writer.finish(builder, |object_writer| {
+                        object_writer.write::<_, _, 8>(&prepared_outlet);
+                        object_writer.write::<_, _, 4>(&prepared_label);
+                    });
+                }
+                builder.current_offset()
+            }
+        }
+
+        impl ::planus::WriteAs<::planus::Offset<OutletLabel>> for OutletLabel {
+            type Prepared = ::planus::Offset<Self>;
+
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<OutletLabel> {
+                ::planus::WriteAsOffset::prepare(self, builder)
+            }
+        }
+
+        impl ::planus::WriteAsOptional<::planus::Offset<OutletLabel>> for OutletLabel {
+            type Prepared = ::planus::Offset<Self>;
+
+            #[inline]
+            fn prepare(
+                &self,
+                builder: &mut ::planus::Builder,
+            ) -> ::core::option::Option<::planus::Offset<OutletLabel>> {
+                ::core::option::Option::Some(::planus::WriteAsOffset::prepare(self, builder))
+            }
+        }
+
+        impl ::planus::WriteAsOffset<OutletLabel> for OutletLabel {
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<OutletLabel> {
+                OutletLabel::create(builder, self.outlet, &self.label)
+            }
+        }
+
+        /// Builder for serializing an instance of the [OutletLabel] type.
+        ///
+        /// Can be created using the [OutletLabel::builder] method.
+        #[derive(Debug)]
+        #[must_use]
+        pub struct OutletLabelBuilder<State>(State);
+
+        impl OutletLabelBuilder<()> {
+            /// Setter for the [`outlet` field](OutletLabel#structfield.outlet).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn outlet<T0>(self, value: T0) -> OutletLabelBuilder<(T0,)>
+            where
+                T0: ::planus::WriteAs<self::Outlet>,
+            {
+                OutletLabelBuilder((value,))
+            }
+        }
+
+        impl<T0> OutletLabelBuilder<(T0,)> {
+            /// Setter for the [`label` field](OutletLabel#structfield.label).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn label<T1>(self, value: T1) -> OutletLabelBuilder<(T0, T1)>
+            where
+                T1: ::planus::WriteAs<::planus::Offset<str>>,
+            {
+                let (v0,) = self.0;
+                OutletLabelBuilder((v0, value))
+            }
+        }
+
+        impl<T0, T1> OutletLabelBuilder<(T0, T1)> {
+            /// Finish writing the builder to get an [Offset](::planus::Offset) to a serialized [OutletLabel].
+            #[inline]
+            pub fn finish(self, builder: &mut ::planus::Builder) -> ::planus::Offset<OutletLabel>
+            where
+                Self: ::planus::WriteAsOffset<OutletLabel>,
+            {
+                ::planus::WriteAsOffset::prepare(&self, builder)
+            }
+        }
+
+        impl<T0: ::planus::WriteAs<self::Outlet>, T1: ::planus::WriteAs<::planus::Offset<str>>>
+            ::planus::WriteAs<::planus::Offset<OutletLabel>> for OutletLabelBuilder<(T0, T1)>
+        {
+            type Prepared = ::planus::Offset<OutletLabel>;
+
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<OutletLabel> {
+                ::planus::WriteAsOffset::prepare(self, builder)
+            }
+        }
+
+        impl<T0: ::planus::WriteAs<self::Outlet>, T1: ::planus::WriteAs<::planus::Offset<str>>>
+            ::planus::WriteAsOptional<::planus::Offset<OutletLabel>>
+            for OutletLabelBuilder<(T0, T1)>
+        {
+            type Prepared = ::planus::Offset<OutletLabel>;
+
+            #[inline]
+            fn prepare(
+                &self,
+                builder: &mut ::planus::Builder,
+            ) -> ::core::option::Option<::planus::Offset<OutletLabel>> {
+                ::core::option::Option::Some(::planus::WriteAsOffset::prepare(self, builder))
+            }
+        }
+
+        impl<T0: ::planus::WriteAs<self::Outlet>, T1: ::planus::WriteAs<::planus::Offset<str>>>
+            ::planus::WriteAsOffset<OutletLabel> for OutletLabelBuilder<(T0, T1)>
+        {
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<OutletLabel> {
+                let (v0, v1) = &self.0;
+                OutletLabel::create(builder, v0, v1)
+            }
+        }
+
+        /// Reference to a deserialized [OutletLabel].
+        #[derive(Copy, Clone)]
+        pub struct OutletLabelRef<'a>(#[allow(dead_code)] ::planus::table_reader::Table<'a>);
+
+        impl<'a> OutletLabelRef<'a> {
+            /// Getter for the [`outlet` field](OutletLabel#structfield.outlet).
+            #[inline]
+            pub fn outlet(&self) -> ::planus::Result<self::OutletRef<'a>> {
+                self.0.access_required(0, "OutletLabel", "outlet")
+            }
+
+            /// Getter for the [`label` field](OutletLabel#structfield.label).
+            #[inline]
+            pub fn label(&self) -> ::planus::Result<&'a ::core::primitive::str> {
+                self.0.access_required(1, "OutletLabel", "label")
+            }
+        }
+
+        impl<'a> ::core::fmt::Debug for OutletLabelRef<'a> {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                let mut f = f.debug_struct("OutletLabelRef");
+                f.field("outlet", &self.outlet());
+                f.field("label", &self.label());
+                f.finish()
+            }
+        }
+
+        impl<'a> ::core::convert::TryFrom<OutletLabelRef<'a>> for OutletLabel {
+            type Error = ::planus::Error;
+
+            #[allow(unreachable_code)]
+            fn try_from(value: OutletLabelRef<'a>) -> ::planus::Result<Self> {
+                ::core::result::Result::Ok(Self {
+                    outlet: ::core::convert::Into::into(value.outlet()?),
+                    label: ::core::convert::Into::into(value.label()?),
+                })
+            }
+        }
+
+        impl<'a> ::planus::TableRead<'a> for OutletLabelRef<'a> {
+            #[inline]
+            fn from_buffer(
+                buffer: ::planus::SliceWithStartOffset<'a>,
+                offset: usize,
+            ) -> ::core::result::Result<Self, ::planus::errors::ErrorKind> {
+                ::core::result::Result::Ok(Self(::planus::table_reader::Table::from_buffer(
+                    buffer, offset,
+                )?))
+            }
+        }
+
+        impl<'a> ::planus::VectorReadInner<'a> for OutletLabelRef<'a> {
+            type Error = ::planus::Error;
+            const STRIDE: usize = 4;
+
+            unsafe fn from_buffer(
+                buffer: ::planus::SliceWithStartOffset<'a>,
+                offset: usize,
+            ) -> ::planus::Result<Self> {
+                ::planus::TableRead::from_buffer(buffer, offset).map_err(|error_kind| {
+                    error_kind.with_error_location(
+                        "[OutletLabelRef]",
+                        "get",
+                        buffer.offset_from_start,
+                    )
+                })
+            }
+        }
+
+        /// # Safety
+        /// The planus compiler generates implementations that initialize
+        /// the bytes in `write_values`.
+        unsafe impl ::planus::VectorWrite<::planus::Offset<OutletLabel>> for OutletLabel {
+            type Value = ::planus::Offset<OutletLabel>;
+            const STRIDE: usize = 4;
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> Self::Value {
+                ::planus::WriteAs::prepare(self, builder)
+            }
+
+            #[inline]
+            unsafe fn write_values(
+                values: &[::planus::Offset<OutletLabel>],
+                bytes: *mut ::core::mem::MaybeUninit<u8>,
+                buffer_position: u32,
+            ) {
+                let bytes = bytes as *mut [::core::mem::MaybeUninit<u8>; 4];
+                for (i, v) in ::core::iter::Iterator::enumerate(values.iter()) {
+                    ::planus::WriteAsPrimitive::write(
+                        v,
+                        ::planus::Cursor::new(unsafe { &mut *bytes.add(i) }),
+                        buffer_position - (Self::STRIDE * i) as u32,
+                    );
+                }
+            }
+        }
+
+        impl<'a> ::planus::ReadAsRoot<'a> for OutletLabelRef<'a> {
+            fn read_as_root(slice: &'a [u8]) -> ::planus::Result<Self> {
+                ::planus::TableRead::from_buffer(
+                    ::planus::SliceWithStartOffset { buffer: slice, offset_from_start: 0 },
+                    0,
+                )
+                .map_err(|error_kind| {
+                    error_kind.with_error_location("[OutletLabelRef]", "read_as_root", 0)
+                })
+            }
+        }
+
+        /// The enum `DatumType` in the namespace `tract`
+        ///
+        /// Generated from these locations:
+        /// * Enum `DatumType` in the file `schema/tract.fbs:27`
+        #[derive(
+            Copy,
+            Clone,
+            Debug,
+            PartialEq,
+            Eq,
+            PartialOrd,
+            Ord,
+            Hash,
+            ::serde::Serialize,
+            ::serde::Deserialize,
+        )]
+        #[repr(u8)]
+        pub enum DatumType {
+            /// The variant `Bool` in the enum `DatumType`
+            Bool = 0,
+
+            /// The variant `U8` in the enum `DatumType`
+            U8 = 1,
+
+            /// The variant `U16` in the enum `DatumType`
+            U16 = 2,
+
+            /// The variant `I8` in the enum `DatumType`
+            I8 = 3,
+
+            /// The variant `I16` in the enum `DatumType`
+            I16 = 4,
+
+            /// The variant `I32` in the enum `DatumType`
+            I32 = 5,
+
+            /// The variant `I64` in the enum `DatumType`
+            I64 = 6,
+
+            /// The variant `F16` in the enum `DatumType`
+            F16 = 7,
+
+            /// The variant `BF16` in the enum `DatumType`
+            Bf16 = 8,
+
+            /// The variant `F32` in the enum `DatumType`
+            F32 = 9,
+
+            /// The variant `F64` in the enum `DatumType`
+            F64 = 10,
+
+            /// The variant `TDim` in the enum `DatumType`
+            TDim = 11,
+
+            /// The variant `String` in the enum `DatumType`
+            String = 12,
+        }
+
+        impl DatumType {
+            /// Array containing all valid variants of DatumType
+            pub const ENUM_VALUES: [Self; 13] = [
+                Self::Bool,
+                Self::U8,
+                Self::U16,
+                Self::I8,
+                Self::I16,
+                Self::I32,
+                Self::I64,
+                Self::F16,
+                Self::Bf16,
+                Self::F32,
+                Self::F64,
+                Self::TDim,
+                Self::String,
+            ];
+        }
+
+        impl ::core::convert::TryFrom<u8> for DatumType {
+            type Error = ::planus::errors::UnknownEnumTagKind;
+            #[inline]
+            fn try_from(
+                value: u8,
+            ) -> ::core::result::Result<Self, ::planus::errors::UnknownEnumTagKind> {
+                #[allow(clippy::match_single_binding)]
+                match value {
+                    0 => ::core::result::Result::Ok(DatumType::Bool),
+                    1 => ::core::result::Result::Ok(DatumType::U8),
+                    2 => ::core::result::Result::Ok(DatumType::U16),
+                    3 => ::core::result::Result::Ok(DatumType::I8),
+                    4 => ::core::result::Result::Ok(DatumType::I16),
+                    5 => ::core::result::Result::Ok(DatumType::I32),
+                    6 => ::core::result::Result::Ok(DatumType::I64),
+                    7 => ::core::result::Result::Ok(DatumType::F16),
+                    8 => ::core::result::Result::Ok(DatumType::Bf16),
+                    9 => ::core::result::Result::Ok(DatumType::F32),
+                    10 => ::core::result::Result::Ok(DatumType::F64),
+                    11 => ::core::result::Result::Ok(DatumType::TDim),
+                    12 => ::core::result::Result::Ok(DatumType::String),
+
+                    _ => ::core::result::Result::Err(::planus::errors::UnknownEnumTagKind {
+                        tag: value as i128,
+                    }),
+                }
+            }
+        }
+
+        impl ::core::convert::From<DatumType> for u8 {
+            #[inline]
+            fn from(value: DatumType) -> Self {
+                value as u8
+            }
+        }
+
+        /// # Safety
+        /// The Planus compiler correctly calculates `ALIGNMENT` and `SIZE`.
+        unsafe impl ::planus::Primitive for DatumType {
+            const ALIGNMENT: usize = 1;
+            const SIZE: usize = 1;
+        }
+
+        impl ::planus::WriteAsPrimitive<DatumType> for DatumType {
+            #[inline]
+            fn write<const N: usize>(&self, cursor: ::planus::Cursor<'_, N>, buffer_position: u32) {
+                (*self as u8).write(cursor, buffer_position);
+            }
+        }
+
+        impl ::planus::WriteAs<DatumType> for DatumType {
+            type Prepared = Self;
+
+            #[inline]
+            fn prepare(&self, _builder: &mut ::planus::Builder) -> DatumType {
+                *self
+            }
+        }
+
+        impl ::planus::WriteAsDefault<DatumType, DatumType> for DatumType {
+            type Prepared = Self;
+
+            #[inline]
+            fn prepare(
+                &self,
+                _builder: &mut ::planus::Builder,
+                default: &DatumType,
+            ) -> ::core::option::Option<DatumType> {
+                if self == default {
+                    ::core::option::Option::None
+                } else {
+                    ::core::option::Option::Some(*self)
+                }
+            }
+        }
+
+        impl ::planus::WriteAsOptional<DatumType> for DatumType {
+            type Prepared = Self;
+
+            #[inline]
+            fn prepare(
+                &self,
+                _builder: &mut ::planus::Builder,
+            ) -> ::core::option::Option<DatumType> {
+                ::core::option::Option::Some(*self)
+            }
+        }
+
+        impl<'buf> ::planus::TableRead<'buf> for DatumType {
+            #[inline]
+            fn from_buffer(
+                buffer: ::planus::SliceWithStartOffset<'buf>,
+                offset: usize,
+            ) -> ::core::result::Result<Self, ::planus::errors::ErrorKind> {
+                let n: u8 = ::planus::TableRead::from_buffer(buffer, offset)?;
+                ::core::result::Result::Ok(::core::convert::TryInto::try_into(n)?)
+            }
+        }
+
+        impl<'buf> ::planus::VectorReadInner<'buf> for DatumType {
+            type Error = ::planus::errors::UnknownEnumTag;
+            const STRIDE: usize = 1;
+            #[inline]
+            unsafe fn from_buffer(
+                buffer: ::planus::SliceWithStartOffset<'buf>,
+                offset: usize,
+            ) -> ::core::result::Result<Self, ::planus::errors::UnknownEnumTag> {
+                let value = unsafe { *buffer.buffer.get_unchecked(offset) };
+                let value: ::core::result::Result<Self, _> =
+                    ::core::convert::TryInto::try_into(value);
+                value.map_err(|error_kind| {
+                    error_kind.with_error_location(
+                        "DatumType",
+                        "VectorRead::from_buffer",
+                        buffer.offset_from_start,
+                    )
+                })
+            }
+        }
+
+        /// # Safety
+        /// The planus compiler generates implementations that initialize
+        /// the bytes in `write_values`.
+        unsafe impl ::planus::VectorWrite<DatumType> for DatumType {
+            const STRIDE: usize = 1;
+
+            type Value = Self;
+
+            #[inline]
+            fn prepare(&self, _builder: &mut ::planus::Builder) -> Self {
+                *self
+            }
+
+            #[inline]
+            unsafe fn write_values(
+                values: &[Self],
+                bytes: *mut ::core::mem::MaybeUninit<u8>,
+                buffer_position: u32,
+            ) {
+                let bytes = bytes as *mut [::core::mem::MaybeUninit<u8>; 1];
+                for (i, v) in ::core::iter::Iterator::enumerate(values.iter()) {
+                    ::planus::WriteAsPrimitive::write(
+                        v,
+                        ::planus::Cursor::new(unsafe { &mut *bytes.add(i) }),
+                        buffer_position - i as u32,
+                    );
+                }
+            }
+        }
+
+        /// The struct `Chunk` in the namespace `tract`
+        ///
+        /// Generated from these locations:
+        /// * Struct `Chunk` in the file `schema/tract.fbs:44`
+        #[derive(
+            Copy,
+            Clone,
+            Debug,
+            PartialEq,
+            PartialOrd,
+            Eq,
+            Ord,
+            Hash,
+            Default,
+            ::serde::Serialize,
+            ::serde::Deserialize,
+        )]
+        pub struct Chunk {
+            /// The field `offset` in the struct `Chunk`
+            pub offset: u64,
+
+            /// The field `len` in the struct `Chunk`
+            pub len: u64,
+        }
+
+        /// # Safety
+        /// The Planus compiler correctly calculates `ALIGNMENT` and `SIZE`.
+        unsafe impl ::planus::Primitive for Chunk {
+            const ALIGNMENT: usize = 8;
+            const SIZE: usize = 16;
+        }
+
+        #[allow(clippy::identity_op)]
+        impl ::planus::WriteAsPrimitive<Chunk> for Chunk {
+            #[inline]
+            fn write<const N: usize>(&self, cursor: ::planus::Cursor<'_, N>, buffer_position: u32) {
+                let (cur, cursor) = cursor.split::<8, 8>();
+                self.offset.write(cur, buffer_position - 0);
+                let (cur, cursor) = cursor.split::<8, 0>();
+                self.len.write(cur, buffer_position - 8);
+                cursor.finish([]);
+            }
+        }
+
+        impl ::planus::WriteAsOffset<Chunk> for Chunk {
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<Chunk> {
+                unsafe {
+                    builder.write_with(16, 7, |buffer_position, bytes| {
+                        let bytes = bytes.as_mut_ptr();
+
+                        ::planus::WriteAsPrimitive::write(
+                            self,
+                            ::planus::Cursor::new(
+                                &mut *(bytes as *mut [::core::mem::MaybeUninit<u8>; 16]),
+                            ),
+                            buffer_position,
+                        );
+                    });
+                }
+                builder.current_offset()
+            }
+        }
+
+        impl ::planus::WriteAs<Chunk> for Chunk {
+            type Prepared = Self;
+            #[inline]
+            fn prepare(&self, _builder: &mut ::planus::Builder) -> Self {
+                *self
+            }
+        }
+
+        impl ::planus::WriteAsOptional<Chunk> for Chunk {
+            type Prepared = Self;
+            #[inline]
+            fn prepare(&self, _builder: &mut ::planus::Builder) -> ::core::option::Option<Self> {
+                ::core::option::Option::Some(*self)
+            }
+        }
+
+        /// Reference to a deserialized [Chunk].
+        #[derive(Copy, Clone)]
+        pub struct ChunkRef<'a>(::planus::ArrayWithStartOffset<'a, 16>);
+
+        impl<'a> ChunkRef<'a> {
+            /// Getter for the [`offset` field](Chunk#structfield.offset).
+            pub fn offset(&self) -> u64 {
+                let buffer = self.0.advance_as_array::<8>(0).unwrap();
+
+                u64::from_le_bytes(*buffer.as_array())
+            }
+
+            /// Getter for the [`len` field](Chunk#structfield.len).
+            pub fn len(&self) -> u64 {
+                let buffer = self.0.advance_as_array::<8>(8).unwrap();
+
+                u64::from_le_bytes(*buffer.as_array())
+            }
+        }
+
+        impl<'a> ::core::fmt::Debug for ChunkRef<'a> {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                let mut f = f.debug_struct("ChunkRef");
+                f.field("offset", &self.offset());
+                f.field("len", &self.len());
+                f.finish()
+            }
+        }
+
+        impl<'a> ::core::convert::From<::planus::ArrayWithStartOffset<'a, 16>> for ChunkRef<'a> {
+            fn from(array: ::planus::ArrayWithStartOffset<'a, 16>) -> Self {
+                Self(array)
+            }
+        }
+
+        impl<'a> ::core::convert::From<ChunkRef<'a>> for Chunk {
+            #[allow(unreachable_code)]
+            fn from(value: ChunkRef<'a>) -> Self {
+                Self { offset: value.offset(), len: value.len() }
+            }
+        }
+
+        impl<'a, 'b> ::core::cmp::PartialEq<ChunkRef<'a>> for ChunkRef<'b> {
+            fn eq(&self, other: &ChunkRef<'_>) -> bool {
+                self.offset() == other.offset() && self.len() == other.len()
+            }
+        }
+
+        impl<'a> ::core::cmp::Eq for ChunkRef<'a> {}
+        impl<'a, 'b> ::core::cmp::PartialOrd<ChunkRef<'a>> for ChunkRef<'b> {
+            fn partial_cmp(
+                &self,
+                other: &ChunkRef<'_>,
+            ) -> ::core::option::Option<::core::cmp::Ordering> {
+                ::core::option::Option::Some(::core::cmp::Ord::cmp(self, other))
+            }
+        }
+
+        impl<'a> ::core::cmp::Ord for ChunkRef<'a> {
+            fn cmp(&self, other: &ChunkRef<'_>) -> ::core::cmp::Ordering {
+                self.offset().cmp(&other.offset()).then_with(|| self.len().cmp(&other.len()))
+            }
+        }
+
+        impl<'a> ::core::hash::Hash for ChunkRef<'a> {
+            fn hash<H: ::core::hash::Hasher>(&self, state: &mut H) {
+                self.offset().hash(state);
+                self.len().hash(state);
+            }
+        }
+
+        impl<'a> ::planus::TableRead<'a> for ChunkRef<'a> {
+            #[inline]
+            fn from_buffer(
+                buffer: ::planus::SliceWithStartOffset<'a>,
+                offset: usize,
+            ) -> ::core::result::Result<Self, ::planus::errors::ErrorKind> {
+                let buffer = buffer.advance_as_array::<16>(offset)?;
+                ::core::result::Result::Ok(Self(buffer))
+            }
+        }
+
+        impl<'a> ::planus::VectorRead<'a> for ChunkRef<'a> {
+            const STRIDE: usize = 16;
+
+            #[inline]
+            unsafe fn from_buffer(
+                buffer: ::planus::SliceWithStartOffset<'a>,
+                offset: usize,
+            ) -> Self {
+                Self(unsafe { buffer.unchecked_advance_as_array(offset) })
+            }
+        }
+
+        /// # Safety
+        /// The planus compiler generates implementations that initialize
+        /// the bytes in `write_values`.
+        unsafe impl ::planus::VectorWrite<Chunk> for Chunk {
+            const STRIDE: usize = 16;
+
+            type Value = Chunk;
+
+            #[inline]
+            fn prepare(&self, _builder: &mut ::planus::Builder) -> Self::Value {
+                *self
+            }
+
+            #[inline]
+            unsafe fn write_values(
+                values: &[Chunk],
+                bytes: *mut ::core::mem::MaybeUninit<u8>,
+                buffer_position: u32,
+            ) {
+                let bytes = bytes as *mut [::core::mem::MaybeUninit<u8>; 16];
+                for (i, v) in ::core::iter::Iterator::enumerate(values.iter()) {
+                    ::planus::WriteAsPrimitive::write(
+                        v,
+                        ::planus::Cursor::new(unsafe { &mut *bytes.add(i) }),
+                        buffer_position - (16 * i) as u32,
+                    );
+                }
+            }
+        }
+
+        /// The table `Tensor` in the namespace `tract`
+        ///
+        /// Generated from these locations:
+        /// * Table `Tensor` in the file `schema/tract.fbs:49`
+        #[derive(
+            Clone,
+            Debug,
+            PartialEq,
+            PartialOrd,
+            Eq,
+            Ord,
+            Hash,
+            ::serde::Serialize,
+            ::serde::Deserialize,
+        )]
+        pub struct Tensor {
+            /// The field `datum_type` in the table `Tensor`
+            pub datum_type: self::DatumType,
+            /// The field `shape` in the table `Tensor`
+            pub shape: ::planus::alloc::vec::Vec<u64>,
+            /// The field `data` in the table `Tensor`
+            pub data: ::core::option::Option<self::Chunk>,
+            /// The field `strings` in the table `Tensor`
+            pub strings:
+                ::core::option::Option<::planus::alloc::vec::Vec<::planus::alloc::string::String>>,
+            /// The field `dims` in the table `Tensor`
+            pub dims: ::core::option::Option<::planus::alloc::vec::Vec<i64>>,
+        }
+
+        #[allow(clippy::derivable_impls)]
+        impl ::core::default::Default for Tensor {
+            fn default() -> Self {
+                Self {
+                    datum_type: self::DatumType::Bool,
+                    shape: ::core::default::Default::default(),
+                    data: ::core::default::Default::default(),
+                    strings: ::core::default::Default::default(),
+                    dims: ::core::default::Default::default(),
+                }
+            }
+        }
+
+        impl Tensor {
+            /// Creates a [TensorBuilder] for serializing an instance of this table.
+            #[inline]
+            pub fn builder() -> TensorBuilder<()> {
+                TensorBuilder(())
+            }
+
+            #[allow(clippy::too_many_arguments)]
+            pub fn create(
+                builder: &mut ::planus::Builder,
+                field_datum_type: impl ::planus::WriteAsDefault<self::DatumType, self::DatumType>,
+                field_shape: impl ::planus::WriteAs<::planus::Offset<[u64]>>,
+                field_data: impl ::planus::WriteAsOptional<self::Chunk>,
+                field_strings: impl ::planus::WriteAsOptional<::planus::Offset<[::planus::Offset<str>]>>,
+                field_dims: impl ::planus::WriteAsOptional<::planus::Offset<[i64]>>,
+            ) -> ::planus::Offset<Self> {
+                let prepared_datum_type = field_datum_type.prepare(builder, &self::DatumType::Bool);
+                let prepared_shape = field_shape.prepare(builder);
+                let prepared_data = field_data.prepare(builder);
+                let prepared_strings = field_strings.prepare(builder);
+                let prepared_dims = field_dims.prepare(builder);
+
+                let mut table_writer: ::planus::table_writer::TableWriter<14> =
+                    ::core::default::Default::default();
+                if prepared_data.is_some() {
+                    table_writer.write_entry::<self::Chunk>(2);
+                }
+                table_writer.write_entry::<::planus::Offset<[u64]>>(1);
+                if prepared_strings.is_some() {
+                    table_writer.write_entry::<::planus::Offset<[::planus::Offset<str>]>>(3);
+                }
+                if prepared_dims.is_some() {
+                    table_writer.write_entry::<::planus::Offset<[i64]>>(4);
+                }
+                if prepared_datum_type.is_some() {
+                    table_writer.write_entry::<self::DatumType>(0);
+                }
+
+                unsafe {
+                    table_writer.finish(builder, |object_writer| {
+                        if let ::core::option::Option::Some(prepared_data) = prepared_data {
+                            object_writer.write::<_, _, 16>(&prepared_data);
+                        }
+                        object_writer.write::<_, _, 4>(&prepared_shape);
+                        if let ::core::option::Option::Some(prepared_strings) = prepared_strings {
+                            object_writer.write::<_, _, 4>(&prepared_strings);
+                        }
+                        if let ::core::option::Option::Some(prepared_dims) = prepared_dims {
+                            object_writer.write::<_, _, 4>(&prepared_dims);
+                        }
+                        if let ::core::option::Option::Some(prepared_datum_type) =
+                            prepared_datum_type
+                        {
+                            object_writer.write::<_, _, 1>(&prepared_datum_type);
+                        }
+                    });
+                }
+                builder.current_offset()
+            }
+        }
+
+        impl ::planus::WriteAs<::planus::Offset<Tensor>> for Tensor {
+            type Prepared = ::planus::Offset<Self>;
+
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<Tensor> {
+                ::planus::WriteAsOffset::prepare(self, builder)
+            }
+        }
+
+        impl ::planus::WriteAsOptional<::planus::Offset<Tensor>> for Tensor {
+            type Prepared = ::planus::Offset<Self>;
+
+            #[inline]
+            fn prepare(
+                &self,
+                builder: &mut ::planus::Builder,
+            ) -> ::core::option::Option<::planus::Offset<Tensor>> {
+                ::core::option::Option::Some(::planus::WriteAsOffset::prepare(self, builder))
+            }
+        }
+
+        impl ::planus::WriteAsOffset<Tensor> for Tensor {
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<Tensor> {
+                Tensor::create(
+                    builder,
+                    self.datum_type,
+                    &self.shape,
+                    self.data,
+                    &self.strings,
+                    &self.dims,
+                )
+            }
+        }
+
+        /// Builder for serializing an instance of the [Tensor] type.
+        ///
+        /// Can be created using the [Tensor::builder] method.
+        #[derive(Debug)]
+        #[must_use]
+        pub struct TensorBuilder<State>(State);
+
+        impl TensorBuilder<()> {
+            /// Setter for the [`datum_type` field](Tensor#structfield.datum_type).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn datum_type<T0>(self, value: T0) -> TensorBuilder<(T0,)>
+            where
+                T0: ::planus::WriteAsDefault<self::DatumType, self::DatumType>,
+            {
+                TensorBuilder((value,))
+            }
+
+            /// Sets the [`datum_type` field](Tensor#structfield.datum_type) to the default value.
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn datum_type_as_default(self) -> TensorBuilder<(::planus::DefaultValue,)> {
+                self.datum_type(::planus::DefaultValue)
+            }
+        }
+
+        impl<T0> TensorBuilder<(T0,)> {
+            /// Setter for the [`shape` field](Tensor#structfield.shape).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn shape<T1>(self, value: T1) -> TensorBuilder<(T0, T1)>
+            where
+                T1: ::planus::WriteAs<::planus::Offset<[u64]>>,
+            {
+                let (v0,) = self.0;
+                TensorBuilder((v0, value))
+            }
+        }
+
+        impl<T0, T1> TensorBuilder<(T0, T1)> {
+            /// Setter for the [`data` field](Tensor#structfield.data).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn data<T2>(self, value: T2) -> TensorBuilder<(T0, T1, T2)>
+            where
+                T2: ::planus::WriteAsOptional<self::Chunk>,
+            {
+                let (v0, v1) = self.0;
+                TensorBuilder((v0, v1, value))
+            }
+
+            /// Sets the [`data` field](Tensor#structfield.data) to null.
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn data_as_null(self) -> TensorBuilder<(T0, T1, ())> {
+                self.data(())
+            }
+        }
+
+        impl<T0, T1, T2> TensorBuilder<(T0, T1, T2)> {
+            /// Setter for the [`strings` field](Tensor#structfield.strings).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn strings<T3>(self, value: T3) -> TensorBuilder<(T0, T1, T2, T3)>
+            where
+                T3: ::planus::WriteAsOptional<::planus::Offset<[::planus::Offset<str>]>>,
+            {
+                let (v0, v1, v2) = self.0;
+                TensorBuilder((v0, v1, v2, value))
+            }
+
+            /// Sets the [`strings` field](Tensor#structfield.strings) to null.
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn strings_as_null(self) -> TensorBuilder<(T0, T1, T2, ())> {
+                self.strings(())
+            }
+        }
+
+        impl<T0, T1, T2, T3> TensorBuilder<(T0, T1, T2, T3)> {
+            /// Setter for the [`dims` field](Tensor#structfield.dims).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn dims<T4>(self, value: T4) -> TensorBuilder<(T0, T1, T2, T3, T4)>
+            where
+                T4: ::planus::WriteAsOptional<::planus::Offset<[i64]>>,
+            {
+                let (v0, v1, v2, v3) = self.0;
+                TensorBuilder((v0, v1, v2, v3, value))
+            }
+
+            /// Sets the [`dims` field](Tensor#structfield.dims) to null.
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn dims_as_null(self) -> TensorBuilder<(T0, T1, T2, T3, ())> {
+                self.dims(())
+            }
+        }
+
+        impl<T0, T1, T2, T3, T4> TensorBuilder<(T0, T1, T2, T3, T4)> {
+            /// Finish writing the builder to get an [Offset](::planus::Offset) to a serialized [Tensor].
+            #[inline]
+            pub fn finish(self, builder: &mut ::planus::Builder) -> ::planus::Offset<Tensor>
+            where
+                Self: ::planus::WriteAsOffset<Tensor>,
+            {
+                ::planus::WriteAsOffset::prepare(&self, builder)
+            }
+        }
+
+        impl<
+                T0: ::planus::WriteAsDefault<self::DatumType, self::DatumType>,
+                T1: ::planus::WriteAs<::planus::Offset<[u64]>>,
+                T2: ::planus::WriteAsOptional<self::Chunk>,
+                T3: ::planus::WriteAsOptional<::planus::Offset<[::planus::Offset<str>]>>,
+                T4: ::planus::WriteAsOptional<::planus::Offset<[i64]>>,
+            > ::planus::WriteAs<::planus::Offset<Tensor>> for TensorBuilder<(T0, T1, T2, T3, T4)>
+        {
+            type Prepared = ::planus::Offset<Tensor>;
+
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<Tensor> {
+                ::planus::WriteAsOffset::prepare(self, builder)
+            }
+        }
+
+        impl<
+                T0: ::planus::WriteAsDefault<self::DatumType, self::DatumType>,
+                T1: ::planus::WriteAs<::planus::Offset<[u64]>>,
+                T2: ::planus::WriteAsOptional<self::Chunk>,
+                T3: ::planus::WriteAsOptional<::planus::Offset<[::planus::Offset<str>]>>,
+                T4: ::planus::WriteAsOptional<::planus::Offset<[i64]>>,
+            > ::planus::WriteAsOptional<::planus::Offset<Tensor>>
+            for TensorBuilder<(T0, T1, T2, T3, T4)>
+        {
+            type Prepared = ::planus::Offset<Tensor>;
+
+            #[inline]
+            fn prepare(
+                &self,
+                builder: &mut ::planus::Builder,
+            ) -> ::core::option::Option<::planus::Offset<Tensor>> {
+                ::core::option::Option::Some(::planus::WriteAsOffset::prepare(self, builder))
+            }
+        }
+
+        impl<
+                T0: ::planus::WriteAsDefault<self::DatumType, self::DatumType>,
+                T1: ::planus::WriteAs<::planus::Offset<[u64]>>,
+                T2: ::planus::WriteAsOptional<self::Chunk>,
+                T3: ::planus::WriteAsOptional<::planus::Offset<[::planus::Offset<str>]>>,
+                T4: ::planus::WriteAsOptional<::planus::Offset<[i64]>>,
+            > ::planus::WriteAsOffset<Tensor> for TensorBuilder<(T0, T1, T2, T3, T4)>
+        {
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<Tensor> {
+                let (v0, v1, v2, v3, v4) = &self.0;
+                Tensor::create(builder, v0, v1, v2, v3, v4)
+            }
+        }
+
+        /// Reference to a deserialized [Tensor].
+        #[derive(Copy, Clone)]
+        pub struct TensorRef<'a>(#[allow(dead_code)] ::planus::table_reader::Table<'a>);
+
+        impl<'a> TensorRef<'a> {
+            /// Getter for the [`datum_type` field](Tensor#structfield.datum_type).
+            #[inline]
+            pub fn datum_type(&self) -> ::planus::Result<self::DatumType> {
+                ::core::result::Result::Ok(
+                    self.0.access(0, "Tensor", "datum_type")?.unwrap_or(self::DatumType::Bool),
+                )
+            }
+
+            /// Getter for the [`shape` field](Tensor#structfield.shape).
+            #[inline]
+            pub fn shape(&self) -> ::planus::Result<::planus::Vector<'a, u64>> {
+                self.0.access_required(1, "Tensor", "shape")
+            }
+
+            /// Getter for the [`data` field](Tensor#structfield.data).
+            #[inline]
+            pub fn data(&self) -> ::planus::Result<::core::option::Option<self::ChunkRef<'a>>> {
+                self.0.access(2, "Tensor", "data")
+            }
+
+            /// Getter for the [`strings` field](Tensor#structfield.strings).
+            #[inline]
+            pub fn strings(
+                &self,
+            ) -> ::planus::Result<
+                ::core::option::Option<
+                    ::planus::Vector<'a, ::planus::Result<&'a ::core::primitive::str>>,
+                >,
+            > {
+                self.0.access(3, "Tensor", "strings")
+            }
+
+            /// Getter for the [`dims` field](Tensor#structfield.dims).
+            #[inline]
+            pub fn dims(
+                &self,
+            ) -> ::planus::Result<::core::option::Option<::planus::Vector<'a, i64>>> {
+                self.0.access(4, "Tensor", "dims")
+            }
+        }
+
+        impl<'a> ::core::fmt::Debug for TensorRef<'a> {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                let mut f = f.debug_struct("TensorRef");
+                f.field("datum_type", &self.datum_type());
+                f.field("shape", &self.shape());
+                if let ::core::option::Option::Some(field_data) = self.data().transpose() {
+                    f.field("data", &field_data);
+                }
+                if let ::core::option::Option::Some(field_strings) = self.strings().transpose() {
+                    f.field("strings", &field_strings);
+                }
+                if let ::core::option::Option::Some(field_dims) = self.dims().transpose() {
+                    f.field("dims", &field_dims);
+                }
+                f.finish()
+            }
+        }
+
+        impl<'a> ::core::convert::TryFrom<TensorRef<'a>> for Tensor {
+            type Error = ::planus::Error;
+
+            #[allow(unreachable_code)]
+            fn try_from(value: TensorRef<'a>) -> ::planus::Result<Self> {
+                ::core::result::Result::Ok(Self {
+                    datum_type: ::core::convert::TryInto::try_into(value.datum_type()?)?,
+                    shape: value.shape()?.to_vec()?,
+                    data: value.data()?.map(::core::convert::Into::into),
+                    strings: if let ::core::option::Option::Some(strings) = value.strings()? {
+                        ::core::option::Option::Some(strings.to_vec_result()?)
+                    } else {
+                        ::core::option::Option::None
+                    },
+                    dims: if let ::core::option::Option::Some(dims) = value.dims()? {
+                        ::core::option::Option::Some(dims.to_vec()?)
+                    } else {
+                        ::core::option::Option::None
+                    },
+                })
+            }
+        }
+
+        impl<'a> ::planus::TableRead<'a> for TensorRef<'a> {
+            #[inline]
+            fn from_buffer(
+                buffer: ::planus::SliceWithStartOffset<'a>,
+                offset: usize,
+            ) -> ::core::result::Result<Self, ::planus::errors::ErrorKind> {
+                ::core::result::Result::Ok(Self(::planus::table_reader::Table::from_buffer(
+                    buffer, offset,
+                )?))
+            }
+        }
+
+        impl<'a> ::planus::VectorReadInner<'a> for TensorRef<'a> {
+            type Error = ::planus::Error;
+            const STRIDE: usize = 4;
+
+            unsafe fn from_buffer(
+                buffer: ::planus::SliceWithStartOffset<'a>,
+                offset: usize,
+            ) -> ::planus::Result<Self> {
+                ::planus::TableRead::from_buffer(buffer, offset).map_err(|error_kind| {
+                    error_kind.with_error_location("[TensorRef]", "get", buffer.offset_from_start)
+                })
+            }
+        }
+
+        /// # Safety
+        /// The planus compiler generates implementations that initialize
+        /// the bytes in `write_values`.
+        unsafe impl ::planus::VectorWrite<::planus::Offset<Tensor>> for Tensor {
+            type Value = ::planus::Offset<Tensor>;
+            const STRIDE: usize = 4;
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> Self::Value {
+                ::planus::WriteAs::prepare(self, builder)
+            }
+
+            #[inline]
+            unsafe fn write_values(
+                values: &[::planus::Offset<Tensor>],
+                bytes: *mut ::core::mem::MaybeUninit<u8>,
+                buffer_position: u32,
+            ) {
+                let bytes = bytes as *mut [::core::mem::MaybeUninit<u8>; 4];
+                for (i, v) in ::core::iter::Iterator::enumerate(values.iter()) {
+                    ::planus::WriteAsPrimitive::write(
+                        v,
+                        ::planus::Cursor::new(unsafe { &mut *bytes.add(i) }),
+                        buffer_position - (Self::STRIDE * i) as u32,
+                    );
+                }
+            }
+        }
+
+        impl<'a> ::planus::ReadAsRoot<'a> for TensorRef<'a> {
+            fn read_as_root(slice: &'a [u8]) -> ::planus::Result<Self> {
+                ::planus::TableRead::from_buffer(
+                    ::planus::SliceWithStartOffset { buffer: slice, offset_from_start: 0 },
+                    0,
+                )
+                .map_err(|error_kind| {
+                    error_kind.with_error_location("[TensorRef]", "read_as_root", 0)
+                })
+            }
+        }
+
+        /// The table `TypedFact` in the namespace `tract`
+        ///
+        /// Generated from these locations:
+        /// * Table `TypedFact` in the file `schema/tract.fbs:58`
+        #[derive(
+            Clone,
+            Debug,
+            PartialEq,
+            PartialOrd,
+            Eq,
+            Ord,
+            Hash,
+            ::serde::Serialize,
+            ::serde::Deserialize,
+        )]
+        pub struct TypedFact {
+            /// The field `datum_type` in the table `TypedFact`
+            pub datum_type: self::DatumType,
+            /// The field `shape` in the table `TypedFact`
+            pub shape: ::planus::alloc::vec::Vec<u64>,
+            /// The field `konst` in the table `TypedFact`
+            pub konst: ::core::option::Option<::planus::alloc::boxed::Box<self::Tensor>>,
+        }
+
+        #[allow(clippy::derivable_impls)]
+        impl ::core::default::Default for TypedFact {
+            fn default() -> Self {
+                Self {
+                    datum_type: self::DatumType::Bool,
+                    shape: ::core::default::Default::default(),
+                    konst: ::core::default::Default::default(),
+                }
+            }
+        }
+
+        impl TypedFact {
+            /// Creates a [TypedFactBuilder] for serializing an instance of this table.
+            #[inline]
+            pub fn builder() -> TypedFactBuilder<()> {
+                TypedFactBuilder(())
+            }
+
+            #[allow(clippy::too_many_arguments)]
+            pub fn create(
+                builder: &mut ::planus::Builder,
+                field_datum_type: impl ::planus::WriteAsDefault<self::DatumType, self::DatumType>,
+                field_shape: impl ::planus::WriteAs<::planus::Offset<[u64]>>,
+                field_konst: impl ::planus::WriteAsOptional<::planus::Offset<self::Tensor>>,
+            ) -> ::planus::Offset<Self> {
+                let prepared_datum_type = field_datum_type.prepare(builder, &self::DatumType::Bool);
+                let prepared_shape = field_shape.prepare(builder);
+                let prepared_konst = field_konst.prepare(builder);
+
+                let mut table_writer: ::planus::table_writer::TableWriter<10> =
+                    ::core::default::Default::default();
+                table_writer.write_entry::<::planus::Offset<[u64]>>(1);
+                if prepared_konst.is_some() {
+                    table_writer.write_entry::<::planus::Offset<self::Tensor>>(2);
+                }
+                if prepared_datum_type.is_some() {
+                    table_writer.write_entry::<self::DatumType>(0);
+                }
+
+                unsafe {
+                    table_writer.finish(builder, |object_writer| {
+                        object_writer.write::<_, _, 4>(&prepared_shape);
+                        if let ::core::option::Option::Some(prepared_konst) = prepared_konst {
+                            object_writer.write::<_, _, 4>(&prepared_konst);
+                        }
+                        if let ::core::option::Option::Some(prepared_datum_type) =
+                            prepared_datum_type
+                        {
+                            object_writer.write::<_, _, 1>(&prepared_datum_type);
+                        }
+                    });
+                }
+                builder.current_offset()
+            }
+        }
+
+        impl ::planus::WriteAs<::planus::Offset<TypedFact>> for TypedFact {
+            type Prepared = ::planus::Offset<Self>;
+
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<TypedFact> {
+                ::planus::WriteAsOffset::prepare(self, builder)
+            }
+        }
+
+        impl ::planus::WriteAsOptional<::planus::Offset<TypedFact>> for TypedFact {
+            type Prepared = ::planus::Offset<Self>;
+
+            #[inline]
+            fn prepare(
+                &self,
+                builder: &mut ::planus::Builder,
+            ) -> ::core::option::Option<::planus::Offset<TypedFact>> {
+                ::core::option::Option::Some(::planus::WriteAsOffset::prepare(self, builder))
+            }
+        }
+
+        impl ::planus::WriteAsOffset<TypedFact> for TypedFact {
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<TypedFact> {
+                TypedFact::create(builder, self.datum_type, &self.shape, &self.konst)
+            }
+        }
+
+        /// Builder for serializing an instance of the [TypedFact] type.
+        ///
+        /// Can be created using the [TypedFact::builder] method.
+        #[derive(Debug)]
+        #[must_use]
+        pub struct TypedFactBuilder<State>(State);
+
+        impl TypedFactBuilder<()> {
+            /// Setter for the [`datum_type` field](TypedFact#structfield.datum_type).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn datum_type<T0>(self, value: T0) -> TypedFactBuilder<(T0,)>
+            where
+                T0: ::planus::WriteAsDefault<self::DatumType, self::DatumType>,
+            {
+                TypedFactBuilder((value,))
+            }
+
+            /// Sets the [`datum_type` field](TypedFact#structfield.datum_type) to the default value.
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn datum_type_as_default(self) -> TypedFactBuilder<(::planus::DefaultValue,)> {
+                self.datum_type(::planus::DefaultValue)
+            }
+        }
+
+        impl<T0> TypedFactBuilder<(T0,)> {
+            /// Setter for the [`shape` field](TypedFact#structfield.shape).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn shape<T1>(self, value: T1) -> TypedFactBuilder<(T0, T1)>
+            where
+                T1: ::planus::WriteAs<::planus::Offset<[u64]>>,
+            {
+                let (v0,) = self.0;
+                TypedFactBuilder((v0, value))
+            }
+        }
+
+        impl<T0, T1> TypedFactBuilder<(T0, T1)> {
+            /// Setter for the [`konst` field](TypedFact#structfield.konst).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn konst<T2>(self, value: T2) -> TypedFactBuilder<(T0, T1, T2)>
+            where
+                T2: ::planus::WriteAsOptional<::planus::Offset<self::Tensor>>,
+            {
+                let (v0, v1) = self.0;
+                TypedFactBuilder((v0, v1, value))
+            }
+
+            /// Sets the [`konst` field](TypedFact#structfield.konst) to null.
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn konst_as_null(self) -> TypedFactBuilder<(T0, T1, ())> {
+                self.konst(())
+            }
+        }
+
+        impl<T0, T1, T2> TypedFactBuilder<(T0, T1, T2)> {
+            /// Finish writing the builder to get an [Offset](::planus::Offset) to a serialized [TypedFact].
+            #[inline]
+            pub fn finish(self, builder: &mut ::planus::Builder) -> ::planus::Offset<TypedFact>
+            where
+                Self: ::planus::WriteAsOffset<TypedFact>,
+            {
+                ::planus::WriteAsOffset::prepare(&self, builder)
+            }
+        }
+
+        impl<
+                T0: ::planus::WriteAsDefault<self::DatumType, self::DatumType>,
+                T1: ::planus::WriteAs<::planus::Offset<[u64]>>,
+                T2: ::planus::WriteAsOptional<::planus::Offset<self::Tensor>>,
+            > ::planus::WriteAs<::planus::Offset<TypedFact>> for TypedFactBuilder<(T0, T1, T2)>
+        {
+            type Prepared = ::planus::Offset<TypedFact>;
+
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<TypedFact> {
+                ::planus::WriteAsOffset::prepare(self, builder)
+            }
+        }
+
+        impl<
+                T0: ::planus::WriteAsDefault<self::DatumType, self::DatumType>,
+                T1: ::planus::WriteAs<::planus::Offset<[u64]>>,
+                T2: ::planus::WriteAsOptional<::planus::Offset<self::Tensor>>,
+            > ::planus::WriteAsOptional<::planus::Offset<TypedFact>>
+            for TypedFactBuilder<(T0, T1, T2)>
+        {
+            type Prepared = ::planus::Offset<TypedFact>;
+
+            #[inline]
+            fn prepare(
+                &self,
+                builder: &mut ::planus::Builder,
+            ) -> ::core::option::Option<::planus::Offset<TypedFact>> {
+                ::core::option::Option::Some(::planus::WriteAsOffset::prepare(self, builder))
+            }
+        }
+
+        impl<
+                T0: ::planus::WriteAsDefault<self::DatumType, self::DatumType>,
+                T1: ::planus::WriteAs<::planus::Offset<[u64]>>,
+                T2: ::planus::WriteAsOptional<::planus::Offset<self::Tensor>>,
+            > ::planus::WriteAsOffset<TypedFact> for TypedFactBuilder<(T0, T1, T2)>
+        {
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<TypedFact> {
+                let (v0, v1, v2) = &self.0;
+                TypedFact::create(builder, v0, v1, v2)
+            }
+        }
+
+        /// Reference to a deserialized [TypedFact].
+        #[derive(Copy, Clone)]
+        pub struct TypedFactRef<'a>(#[allow(dead_code)] ::planus::table_reader::Table<'a>);
+
+        impl<'a> TypedFactRef<'a> {
+            /// Getter for the [`datum_type` field](TypedFact#structfield.datum_type).
+            #[inline]
+            pub fn datum_type(&self) -> ::planus::Result<self::DatumType> {
+                ::core::result::Result::Ok(
+                    self.0.access(0, "TypedFact", "datum_type")?.unwrap_or(self::DatumType::Bool),
+                )
+            }
+
+            /// Getter for the [`shape` field](TypedFact#structfield.shape).
+            #[inline]
+            pub fn shape(&self) -> ::planus::Result<::planus::Vector<'a, u64>> {
+                self.0.access_required(1, "TypedFact", "shape")
+            }
+
+            /// Getter for the [`konst` field](TypedFact#structfield.konst).
+            #[inline]
+            pub fn konst(&self) -> ::planus::Result<::core::option::Option<self::TensorRef<'a>>> {
+                self.0.access(2, "TypedFact", "konst")
+            }
+        }
+
+        impl<'a> ::core::fmt::Debug for TypedFactRef<'a> {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                let mut f = f.debug_struct("TypedFactRef");
+                f.field("datum_type", &self.datum_type());
+                f.field("shape", &self.shape());
+                if let ::core::option::Option::Some(field_konst) = self.konst().transpose() {
+                    f.field("konst", &field_konst);
+                }
+                f.finish()
+            }
+        }
+
+        impl<'a> ::core::convert::TryFrom<TypedFactRef<'a>> for TypedFact {
+            type Error = ::planus::Error;
+
+            #[allow(unreachable_code)]
+            fn try_from(value: TypedFactRef<'a>) -> ::planus::Result<Self> {
+                ::core::result::Result::Ok(Self {
+                    datum_type: ::core::convert::TryInto::try_into(value.datum_type()?)?,
+                    shape: value.shape()?.to_vec()?,
+                    konst: if let ::core::option::Option::Some(konst) = value.konst()? {
+                        ::core::option::Option::Some(::planus::alloc::boxed::Box::new(
+                            ::core::convert::TryInto::try_into(konst)?,
+                        ))
+                    } else {
+                        ::core::option::Option::None
+                    },
+                })
+            }
+        }
+
+        impl<'a> ::planus::TableRead<'a> for TypedFactRef<'a> {
+            #[inline]
+            fn from_buffer(
+                buffer: ::planus::SliceWithStartOffset<'a>,
+                offset: usize,
+            ) -> ::core::result::Result<Self, ::planus::errors::ErrorKind> {
+                ::core::result::Result::Ok(Self(::planus::table_reader::Table::from_buffer(
+                    buffer, offset,
+                )?))
+            }
+        }
+
+        impl<'a> ::planus::VectorReadInner<'a> for TypedFactRef<'a> {
+            type Error = ::planus::Error;
+            const STRIDE: usize = 4;
+
+            unsafe fn from_buffer(
+                buffer: ::planus::SliceWithStartOffset<'a>,
+                offset: usize,
+            ) -> ::planus::Result<Self> {
+                ::planus::TableRead::from_buffer(buffer, offset).map_err(|error_kind| {
+                    error_kind.with_error_location(
+                        "[TypedFactRef]",
+                        "get",
+                        buffer.offset_from_start,
+                    )
+                })
+            }
+        }
+
+        /// # Safety
+        /// The planus compiler generates implementations that initialize
+        /// the bytes in `write_values`.
+        unsafe impl ::planus::VectorWrite<::planus::Offset<TypedFact>> for TypedFact {
+            type Value = ::planus::Offset<TypedFact>;
+            const STRIDE: usize = 4;
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> Self::Value {
+                ::planus::WriteAs::prepare(self, builder)
+            }
+
+            #[inline]
+            unsafe fn write_values(
+                values: &[::planus::Offset<TypedFact>],
+                bytes: *mut ::core::mem::MaybeUninit<u8>,
+                buffer_position: u32,
+            ) {
+                let bytes = bytes as *mut [::core::mem::MaybeUninit<u8>; 4];
+                for (i, v) in ::core::iter::Iterator::enumerate(values.iter()) {
+                    ::planus::WriteAsPrimitive::write(
+                        v,
+                        ::planus::Cursor::new(unsafe { &mut *bytes.add(i) }),
+                        buffer_position - (Self::STRIDE * i) as u32,
+                    );
+                }
+            }
+        }
+
+        impl<'a> ::planus::ReadAsRoot<'a> for TypedFactRef<'a> {
+            fn read_as_root(slice: &'a [u8]) -> ::planus::Result<Self> {
+                ::planus::TableRead::from_buffer(
+                    ::planus::SliceWithStartOffset { buffer: slice, offset_from_start: 0 },
+                    0,
+                )
+                .map_err(|error_kind| {
+                    error_kind.with_error_location("[TypedFactRef]", "read_as_root", 0)
+                })
+            }
+        }
+
+        /// The table `Source` in the namespace `tract`
+        ///
+        /// Generated from these locations:
+        /// * Table `Source` in the file `schema/tract.fbs:64`
+        #[derive(
+            Clone,
+            Debug,
+            PartialEq,
+            PartialOrd,
+            Eq,
+            Ord,
+            Hash,
+            ::serde::Serialize,
+            ::serde::Deserialize,
+        )]
+        pub struct Source {}
+
+        #[allow(clippy::derivable_impls)]
+        impl ::core::default::Default for Source {
+            fn default() -> Self {
+                Self {}
+            }
+        }
+
+        impl Source {
+            /// Creates a [SourceBuilder] for serializing an instance of this table.
+            #[inline]
+            pub fn builder() -> SourceBuilder<()> {
+                SourceBuilder(())
+            }
+
+            #[allow(clippy::too_many_arguments)]
+            pub fn create(builder: &mut ::planus::Builder) -> ::planus::Offset<Self> {
+                let table_writer: ::planus::table_writer::TableWriter<4> =
+                    ::core::default::Default::default();
+                unsafe {
+                    table_writer.finish(builder, |_table_writer| {});
+                }
+                builder.current_offset()
+            }
+        }
+
+        impl ::planus::WriteAs<::planus::Offset<Source>> for Source {
+            type Prepared = ::planus::Offset<Self>;
+
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<Source> {
+                ::planus::WriteAsOffset::prepare(self, builder)
+            }
+        }
+
+        impl ::planus::WriteAsOptional<::planus::Offset<Source>> for Source {
+            type Prepared = ::planus::Offset<Self>;
+
+            #[inline]
+            fn prepare(
+                &self,
+                builder: &mut ::planus::Builder,
+            ) -> ::core::option::Option<::planus::Offset<Source>> {
+                ::core::option::Option::Some(::planus::WriteAsOffset::prepare(self, builder))
+            }
+        }
+
+        impl ::planus::WriteAsOffset<Source> for Source {
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<Source> {
+                Source::create(builder)
+            }
+        }
+
+        /// Builder for serializing an instance of the [Source] type.
+        ///
+        /// Can be created using the [Source::builder] method.
+        #[derive(Debug)]
+        #[must_use]
+        pub struct SourceBuilder<State>(State);
+
+        impl SourceBuilder<()> {
+            /// Finish writing the builder to get an [Offset](::planus::Offset) to a serialized [Source].
+            #[inline]
+            pub fn finish(self, builder: &mut ::planus::Builder) -> ::planus::Offset<Source>
+            where
+                Self: ::planus::WriteAsOffset<Source>,
+            {
+                ::planus::WriteAsOffset::prepare(&self, builder)
+            }
+        }
+
+        impl ::planus::WriteAs<::planus::Offset<Source>> for SourceBuilder<()> {
+            type Prepared = ::planus::Offset<Source>;
+
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<Source> {
+                ::planus::WriteAsOffset::prepare(self, builder)
+            }
+        }
+
+        impl ::planus::WriteAsOptional<::planus::Offset<Source>> for SourceBuilder<()> {
+            type Prepared = ::planus::Offset<Source>;
+
+            #[inline]
+            fn prepare(
+                &self,
+                builder: &mut ::planus::Builder,
+            ) -> ::core::option::Option<::planus::Offset<Source>> {
+                ::core::option::Option::Some(::planus::WriteAsOffset::prepare(self, builder))
+            }
+        }
+
+        impl ::planus::WriteAsOffset<Source> for SourceBuilder<()> {
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<Source> {
+                Source::create(builder)
+            }
+        }
+
+        /// Reference to a deserialized [Source].
+        #[derive(Copy, Clone)]
+        pub struct SourceRef<'a>(#[allow(dead_code)] ::planus::table_reader::Table<'a>);
+
+        impl<'a> SourceRef<'a> {}
+
+        impl<'a> ::core::fmt::Debug for SourceRef<'a> {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                let mut f = f.debug_struct("SourceRef");
+
+                f.finish()
+            }
+        }
+
+        impl<'a> ::core::convert::TryFrom<SourceRef<'a>> for Source {
+            type Error = ::planus::Error;
+
+            fn try_from(_value: SourceRef<'a>) -> ::planus::Result<Self> {
+                ::core::result::Result::Ok(Self {})
+            }
+        }
+
+        impl<'a> ::planus::TableRead<'a> for SourceRef<'a> {
+            #[inline]
+            fn from_buffer(
+                buffer: ::planus::SliceWithStartOffset<'a>,
+                offset: usize,
+            ) -> ::core::result::Result<Self, ::planus::errors::ErrorKind> {
+                ::core::result::Result::Ok(Self(::planus::table_reader::Table::from_buffer(
+                    buffer, offset,
+                )?))
+            }
+        }
+
+        impl<'a> ::planus::VectorReadInner<'a> for SourceRef<'a> {
+            type Error = ::planus::Error;
+            const STRIDE: usize = 4;
+
+            unsafe fn from_buffer(
+                buffer: ::planus::SliceWithStartOffset<'a>,
+                offset: usize,
+            ) -> ::planus::Result<Self> {
+                ::planus::TableRead::from_buffer(buffer, offset).map_err(|error_kind| {
+                    error_kind.with_error_location("[SourceRef]", "get", buffer.offset_from_start)
+                })
+            }
+        }
+
+        /// # Safety
+        /// The planus compiler generates implementations that initialize
+        /// the bytes in `write_values`.
+        unsafe impl ::planus::VectorWrite<::planus::Offset<Source>> for Source {
+            type Value = ::planus::Offset<Source>;
+            const STRIDE: usize = 4;
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> Self::Value {
+                ::planus::WriteAs::prepare(self, builder)
+            }
+
+            #[inline]
+            unsafe fn write_values(
+                values: &[::planus::Offset<Source>],
+                bytes: *mut ::core::mem::MaybeUninit<u8>,
+                buffer_position: u32,
+            ) {
+                let bytes = bytes as *mut [::core::mem::MaybeUninit<u8>; 4];
+                for (i, v) in ::core::iter::Iterator::enumerate(values.iter()) {
+                    ::planus::WriteAsPrimitive::write(
+                        v,
+                        ::planus::Cursor::new(unsafe { &mut *bytes.add(i) }),
+                        buffer_position - (Self::STRIDE * i) as u32,
+                    );
+                }
+            }
+        }
+
+        impl<'a> ::planus::ReadAsRoot<'a> for SourceRef<'a> {
+            fn read_as_root(slice: &'a [u8]) -> ::planus::Result<Self> {
+                ::planus::TableRead::from_buffer(
+                    ::planus::SliceWithStartOffset { buffer: slice, offset_from_start: 0 },
+                    0,
+                )
+                .map_err(|error_kind| {
+                    error_kind.with_error_location("[SourceRef]", "read_as_root", 0)
+                })
+            }
+        }
+
+        /// The table `Const` in the namespace `tract`
+        ///
+        /// Generated from these locations:
+        /// * Table `Const` in the file `schema/tract.fbs:66`
+        #[derive(
+            Clone,
+            Debug,
+            PartialEq,
+            PartialOrd,
+            Eq,
+            Ord,
+            Hash,
+            ::serde::Serialize,
+            ::serde::Deserialize,
+        )]
+        pub struct Const {
+            /// The field `value` in the table `Const`
+            pub value: ::planus::alloc::boxed::Box<self::Tensor>,
+        }
+
+        #[allow(clippy::derivable_impls)]
+        impl ::core::default::Default for Const {
+            fn default() -> Self {
+                Self { value: ::core::default::Default::default() }
+            }
+        }
+
+        impl Const {
+            /// Creates a [ConstBuilder] for serializing an instance of this table.
+            #[inline]
+            pub fn builder() -> ConstBuilder<()> {
+                ConstBuilder(())
+            }
+
+            #[allow(clippy::too_many_arguments)]
+            pub fn create(
+                builder: &mut ::planus::Builder,
+                field_value: impl ::planus::WriteAs<::planus::Offset<self::Tensor>>,
+            ) -> ::planus::Offset<Self> {
+                let prepared_value = field_value.prepare(builder);
+
+                let mut table_writer: ::planus::table_writer::TableWriter<6> =
+                    ::core::default::Default::default();
+                table_writer.write_entry::<::planus::Offset<self::Tensor>>(0);
+
+                unsafe {
+                    table_writer.finish(builder, |object_writer| {
+                        object_writer.write::<_, _, 4>(&prepared_value);
+                    });
+                }
+                builder.current_offset()
+            }
+        }
+
+        impl ::planus::WriteAs<::planus::Offset<Const>> for Const {
+            type Prepared = ::planus::Offset<Self>;
+
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<Const> {
+                ::planus::WriteAsOffset::prepare(self, builder)
+            }
+        }
+
+        impl ::planus::WriteAsOptional<::planus::Offset<Const>> for Const {
+            type Prepared = ::planus::Offset<Self>;
+
+            #[inline]
+            fn prepare(
+                &self,
+                builder: &mut ::planus::Builder,
+            ) -> ::core::option::Option<::planus::Offset<Const>> {
+                ::core::option::Option::Some(::planus::WriteAsOffset::prepare(self, builder))
+            }
+        }
+
+        impl ::planus::WriteAsOffset<Const> for Const {
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<Const> {
+                Const::create(builder, &self.value)
+            }
+        }
+
+        /// Builder for serializing an instance of the [Const] type.
+        ///
+        /// Can be created using the [Const::builder] method.
+        #[derive(Debug)]
+        #[must_use]
+        pub struct ConstBuilder<State>(State);
+
+        impl ConstBuilder<()> {
+            /// Setter for the [`value` field](Const#structfield.value).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn value<T0>(self, value: T0) -> ConstBuilder<(T0,)>
+            where
+                T0: ::planus::WriteAs<::planus::Offset<self::Tensor>>,
+            {
+                ConstBuilder((value,))
+            }
+        }
+
+        impl<T0> ConstBuilder<(T0,)> {
+            /// Finish writing the builder to get an [Offset](::planus::Offset) to a serialized [Const].
+            #[inline]
+            pub fn finish(self, builder: &mut ::planus::Builder) -> ::planus::Offset<Const>
+            where
+                Self: ::planus::WriteAsOffset<Const>,
+            {
+                ::planus::WriteAsOffset::prepare(&self, builder)
+            }
+        }
+
+        impl<T0: ::planus::WriteAs<::planus::Offset<self::Tensor>>>
+            ::planus::WriteAs<::planus::Offset<Const>> for ConstBuilder<(T0,)>
+        {
+            type Prepared = ::planus::Offset<Const>;
+
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<Const> {
+                ::planus::WriteAsOffset::prepare(self, builder)
+            }
+        }
+
+        impl<T0: ::planus::WriteAs<::planus::Offset<self::Tensor>>>
+            ::planus::WriteAsOptional<::planus::Offset<Const>> for ConstBuilder<(T0,)>
+        {
+            type Prepared = ::planus::Offset<Const>;
+
+            #[inline]
+            fn prepare(
+                &self,
+                builder: &mut ::planus::Builder,
+            ) -> ::core::option::Option<::planus::Offset<Const>> {
+                ::core::option::Option::Some(::planus::WriteAsOffset::prepare(self, builder))
+            }
+        }
+
+        impl<T0: ::planus::WriteAs<::planus::Offset<self::Tensor>>> ::planus::WriteAsOffset<Const>
+            for ConstBuilder<(T0,)>
+        {
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<Const> {
+                let (v0,) = &self.0;
+                Const::create(builder, v0)
+            }
+        }
+
+        /// Reference to a deserialized [Const].
+        #[derive(Copy, Clone)]
+        pub struct ConstRef<'a>(#[allow(dead_code)] ::planus::table_reader::Table<'a>);
+
+        impl<'a> ConstRef<'a> {
+            /// Getter for the [`value` field](Const#structfield.value).
+            #[inline]
+            pub fn value(&self) -> ::planus::Result<self::TensorRef<'a>> {
+                self.0.access_required(0, "Const", "value")
+            }
+        }
+
+        impl<'a> ::core::fmt::Debug for ConstRef<'a> {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                let mut f = f.debug_struct("ConstRef");
+                f.field("value", &self.value());
+                f.finish()
+            }
+        }
+
+        impl<'a> ::core::convert::TryFrom<ConstRef<'a>> for Const {
+            type Error = ::planus::Error;
+
+            #[allow(unreachable_code)]
+            fn try_from(value: ConstRef<'a>) -> ::planus::Result<Self> {
+                ::core::result::Result::Ok(Self {
+                    value: ::planus::alloc::boxed::Box::new(::core::convert::TryInto::try_into(
+                        value.value()?,
+                    )?),
+                })
+            }
+        }
+
+        impl<'a> ::planus::TableRead<'a> for ConstRef<'a> {
+            #[inline]
+            fn from_buffer(
+                buffer: ::planus::SliceWithStartOffset<'a>,
+                offset: usize,
+            ) -> ::core::result::Result<Self, ::planus::errors::ErrorKind> {
+                ::core::result::Result::Ok(Self(::planus::table_reader::Table::from_buffer(
+                    buffer, offset,
+                )?))
+            }
+        }
+
+        impl<'a> ::planus::VectorReadInner<'a> for ConstRef<'a> {
+            type Error = ::planus::Error;
+            const STRIDE: usize = 4;
+
+            unsafe fn from_buffer(
+                buffer: ::planus::SliceWithStartOffset<'a>,
+                offset: usize,
+            ) -> ::planus::Result<Self> {
+                ::planus::TableRead::from_buffer(buffer, offset).map_err(|error_kind| {
+                    error_kind.with_error_location("[ConstRef]", "get", buffer.offset_from_start)
+                })
+            }
+        }
+
+        /// # Safety
+        /// The planus compiler generates implementations that initialize
+        /// the bytes in `write_values`.
+        unsafe impl ::planus::VectorWrite<::planus::Offset<Const>> for Const {
+            type Value = ::planus::Offset<Const>;
+            const STRIDE: usize = 4;
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> Self::Value {
+                ::planus::WriteAs::prepare(self, builder)
+            }
+
+            #[inline]
+            unsafe fn write_values(
+                values: &[::planus::Offset<Const>],
+                bytes: *mut ::core::mem::MaybeUninit<u8>,
+                buffer_position: u32,
+            ) {
+                let bytes = bytes as *mut [::core::mem::MaybeUninit<u8>; 4];
+                for (i, v) in ::core::iter::Iterator::enumerate(values.iter()) {
+                    ::planus::WriteAsPrimitive::write(
+                        v,
+                        ::planus::Cursor::new(unsafe { &mut *bytes.add(i) }),
+                        buffer_position - (Self::STRIDE * i) as u32,
+                    );
+                }
+            }
+        }
+
+        impl<'a> ::planus::ReadAsRoot<'a> for ConstRef<'a> {
+            fn read_as_root(slice: &'a [u8]) -> ::planus::Result<Self> {
+                ::planus::TableRead::from_buffer(
+                    ::planus::SliceWithStartOffset { buffer: slice, offset_from_start: 0 },
+                    0,
+                )
+                .map_err(|error_kind| {
+                    error_kind.with_error_location("[ConstRef]", "read_as_root", 0)
+                })
+            }
+        }
+
+        /// The table `Unary` in the namespace `tract`
+        ///
+        /// Generated from these locations:
+        /// * Table `Unary` in the file `schema/tract.fbs:70`
+        #[derive(
+            Clone,
+            Debug,
+            PartialEq,
+            PartialOrd,
+            Eq,
+            Ord,
+            Hash,
+            ::serde::Serialize,
+            ::serde::Deserialize,
+        )]
+        pub struct Unary {
+            /// The field `mini_op` in the table `Unary`
+            pub mini_op: ::planus::alloc::string::String,
+            /// The field `a` in the table `Unary`
+            pub a: ::planus::alloc::boxed::Box<self::Tensor>,
+            /// The field `inplace` in the table `Unary`
+            pub inplace: bool,
+        }
+
+        #[allow(clippy::derivable_impls)]
+        impl ::core::default::Default for Unary {
+            fn default() -> Self {
+                Self {
+                    mini_op: ::core::default::Default::default(),
+                    a: ::core::default::Default::default(),
+                    inplace: false,
+                }
+            }
+        }
+
+        impl Unary {
+            /// Creates a [UnaryBuilder] for serializing an instance of this table.
+            #[inline]
+            pub fn builder() -> UnaryBuilder<()> {
+                UnaryBuilder(())
+            }
+
+            #[allow(clippy::too_many_arguments)]
+            pub fn create(
+                builder: &mut ::planus::Builder,
+                field_mini_op: impl ::planus::WriteAs<::planus::Offset<str>>,
+                field_a: impl ::planus::WriteAs<::planus::Offset<self::Tensor>>,
+                field_inplace: impl ::planus::WriteAsDefault<bool, bool>,
+            ) -> ::planus::Offset<Self> {
+                let prepared_mini_op = field_mini_op.prepare(builder);
+                let prepared_a = field_a.prepare(builder);
+                let prepared_inplace = field_inplace.prepare(builder, &false);
+
+                let mut table_writer: ::planus::table_writer::TableWriter<10> =
+                    ::core::default::Default::default();
+                table_writer.write_entry::<::planus::Offset<str>>(0);
+                table_writer.write_entry::<::planus::Offset<self::Tensor>>(1);
+                if prepared_inplace.is_some() {
+                    table_writer.write_entry::<bool>(2);
+                }
+
+                unsafe {
+                    table_writer.finish(builder, |object_writer| {
+                        object_writer.write::<_, _, 4>(&prepared_mini_op);
+                        object_writer.write::<_, _, 4>(&prepared_a);
+                        if let ::core::option::Option::Some(prepared_inplace) = prepared_inplace {
+                            object_writer.write::<_, _, 1>(&prepared_inplace);
+                        }
+                    });
+                }
+                builder.current_offset()
+            }
+        }
+
+        impl ::planus::WriteAs<::planus::Offset<Unary>> for Unary {
+            type Prepared = ::planus::Offset<Self>;
+
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<Unary> {
+                ::planus::WriteAsOffset::prepare(self, builder)
+            }
+        }
+
+        impl ::planus::WriteAsOptional<::planus::Offset<Unary>> for Unary {
+            type Prepared = ::planus::Offset<Self>;
+
+            #[inline]
+            fn prepare(
+                &self,
+                builder: &mut ::planus::Builder,
+            ) -> ::core::option::Option<::planus::Offset<Unary>> {
+                ::core::option::Option::Some(::planus::WriteAsOffset::prepare(self, builder))
+            }
+        }
+
+        impl ::planus::WriteAsOffset<Unary> for Unary {
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<Unary> {
+                Unary::create(builder, &self.mini_op, &self.a, self.inplace)
+            }
+        }
+
+        /// Builder for serializing an instance of the [Unary] type.
+        ///
+        /// Can be created using the [Unary::builder] method.
+        #[derive(Debug)]
+        #[must_use]
+        pub struct UnaryBuilder<State>(State);
+
+        impl UnaryBuilder<()> {
+            /// Setter for the [`mini_op` field](Unary#structfield.mini_op).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn mini_op<T0>(self, value: T0) -> UnaryBuilder<(T0,)>
+            where
+                T0: ::planus::WriteAs<::planus::Offset<str>>,
+            {
+                UnaryBuilder((value,))
+            }
+        }
+
+        impl<T0> UnaryBuilder<(T0,)> {
+            /// Setter for the [`a` field](Unary#structfield.a).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn a<T1>(self, value: T1) -> UnaryBuilder<(T0, T1)>
+            where
+                T1: ::planus::WriteAs<::planus::Offset<self::Tensor>>,
+            {
+                let (v0,) = self.0;
+                UnaryBuilder((v0, value))
+            }
+        }
+
+        impl<T0, T1> UnaryBuilder<(T0, T1)> {
+            /// Setter for the [`inplace` field](Unary#structfield.inplace).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn inplace<T2>(self, value: T2) -> UnaryBuilder<(T0, T1, T2)>
+            where
+                T2: ::planus::WriteAsDefault<bool, bool>,
+            {
+                let (v0, v1) = self.0;
+                UnaryBuilder((v0, v1, value))
+            }
+
+            /// Sets the [`inplace` field](Unary#structfield.inplace) to the default value.
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn inplace_as_default(self) -> UnaryBuilder<(T0, T1, ::planus::DefaultValue)> {
+                self.inplace(::planus::DefaultValue)
+            }
+        }
+
+        impl<T0, T1, T2> UnaryBuilder<(T0, T1, T2)> {
+            /// Finish writing the builder to get an [Offset](::planus::Offset) to a serialized [Unary].
+            #[inline]
+            pub fn finish(self, builder: &mut ::planus::Builder) -> ::planus::Offset<Unary>
+            where
+                Self: ::planus::WriteAsOffset<Unary>,
+            {
+                ::planus::WriteAsOffset::prepare(&self, builder)
+            }
+        }
+
+        impl<
+                T0: ::planus::WriteAs<::planus::Offset<str>>,
+                T1: ::planus::WriteAs<::planus::Offset<self::Tensor>>,
+                T2: ::planus::WriteAsDefault<bool, bool>,
+            > ::planus::WriteAs<::planus::Offset<Unary>> for UnaryBuilder<(T0, T1, T2)>
+        {
+            type Prepared = ::planus::Offset<Unary>;
+
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<Unary> {
+                ::planus::WriteAsOffset::prepare(self, builder)
+            }
+        }
+
+        impl<
+                T0: ::planus::WriteAs<::planus::Offset<str>>,
+                T1: ::planus::WriteAs<::planus::Offset<self::Tensor>>,
+                T2: ::planus::WriteAsDefault<bool, bool>,
+            > ::planus::WriteAsOptional<::planus::Offset<Unary>> for UnaryBuilder<(T0, T1, T2)>
+        {
+            type Prepared = ::planus::Offset<Unary>;
+
+            #[inline]
+            fn prepare(
+                &self,
+                builder: &mut ::planus::Builder,
+            ) -> ::core::option::Option<::planus::Offset<Unary>> {
+                ::core::option::Option::Some(::planus::WriteAsOffset::prepare(self, builder))
+            }
+        }
+
+        impl<
+                T0: ::planus::WriteAs<::planus::Offset<str>>,
+                T1: ::planus::WriteAs<::planus::Offset<self::Tensor>>,
+                T2: ::planus::WriteAsDefault<bool, bool>,
+            > ::planus::WriteAsOffset<Unary> for UnaryBuilder<(T0, T1, T2)>
+        {
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<Unary> {
+                let (v0, v1, v2) = &self.0;
+                Unary::create(builder, v0, v1, v2)
+            }
+        }
+
+        /// Reference to a deserialized [Unary].
+        #[derive(Copy, Clone)]
+        pub struct UnaryRef<'a>(#[allow(dead_code)] ::planus::table_reader::Table<'a>);
+
+        impl<'a> UnaryRef<'a> {
+            /// Getter for the [`mini_op` field](Unary#structfield.mini_op).
+            #[inline]
+            pub fn mini_op(&self) -> ::planus::Result<&'a ::core::primitive::str> {
+                self.0.access_required(0, "Unary", "mini_op")
+            }
+
+            /// Getter for the [`a` field](Unary#structfield.a).
+            #[inline]
+            pub fn a(&self) -> ::planus::Result<self::TensorRef<'a>> {
+                self.0.access_required(1, "Unary", "a")
+            }
+
+            /// Getter for the [`inplace` field](Unary#structfield.inplace).
+            #[inline]
+            pub fn inplace(&self) -> ::planus::Result<bool> {
+                ::core::result::Result::Ok(self.0.access(2, "Unary", "inplace")?.unwrap_or(false))
+            }
+        }
+
+        impl<'a> ::core::fmt::Debug for UnaryRef<'a> {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                let mut f = f.debug_struct("UnaryRef");
+                f.field("mini_op", &self.mini_op());
+                f.field("a", &self.a());
+                f.field("inplace", &self.inplace());
+                f.finish()
+            }
+        }
+
+        impl<'a> ::core::convert::TryFrom<UnaryRef<'a>> for Unary {
+            type Error = ::planus::Error;
+
+            #[allow(unreachable_code)]
+            fn try_from(value: UnaryRef<'a>) -> ::planus::Result<Self> {
+                ::core::result::Result::Ok(Self {
+                    mini_op: ::core::convert::Into::into(value.mini_op()?),
+                    a: ::planus::alloc::boxed::Box::new(::core::convert::TryInto::try_into(
+                        value.a()?,
+                    )?),
+                    inplace: ::core::convert::TryInto::try_into(value.inplace()?)?,
+                })
+            }
+        }
+
+        impl<'a> ::planus::TableRead<'a> for UnaryRef<'a> {
+            #[inline]
+            fn from_buffer(
+                buffer: ::planus::SliceWithStartOffset<'a>,
+                offset: usize,
+            ) -> ::core::result::Result<Self, ::planus::errors::ErrorKind> {
+                ::core::result::Result::Ok(Self(::planus::table_reader::Table::from_buffer(
+                    buffer, offset,
+                )?))
+            }
+        }
+
+        impl<'a> ::planus::VectorReadInner<'a> for UnaryRef<'a> {
+            type Error = ::planus::Error;
+            const STRIDE: usize = 4;
+
+            unsafe fn from_buffer(
+                buffer: ::planus::SliceWithStartOffset<'a>,
+                offset: usize,
+            ) -> ::planus::Result<Self> {
+                ::planus::TableRead::from_buffer(buffer, offset).map_err(|error_kind| {
+                    error_kind.with_error_location("[UnaryRef]", "get", buffer.offset_from_start)
+                })
+            }
+        }
+
+        /// # Safety
+        /// The planus compiler generates implementations that initialize
+        /// the bytes in `write_values`.
+        unsafe impl ::planus::VectorWrite<::planus::Offset<Unary>> for Unary {
+            type Value = ::planus::Offset<Unary>;
+            const STRIDE: usize = 4;
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> Self::Value {
+                ::planus::WriteAs::prepare(self, builder)
+            }
+
+            #[inline]
+            unsafe fn write_values(
+                values: &[::planus::Offset<Unary>],
+                bytes: *mut ::core::mem::MaybeUninit<u8>,
+                buffer_position: u32,
+            ) {
+                let bytes = bytes as *mut [::core::mem::MaybeUninit<u8>; 4];
+                for (i, v) in ::core::iter::Iterator::enumerate(values.iter()) {
+                    ::planus::WriteAsPrimitive::write(
+                        v,
+                        ::planus::Cursor::new(unsafe { &mut *bytes.add(i) }),
+                        buffer_position - (Self::STRIDE * i) as u32,
+                    );
+                }
+            }
+        }
+
+        impl<'a> ::planus::ReadAsRoot<'a> for UnaryRef<'a> {
+            fn read_as_root(slice: &'a [u8]) -> ::planus::Result<Self> {
+                ::planus::TableRead::from_buffer(
+                    ::planus::SliceWithStartOffset { buffer: slice, offset_from_start: 0 },
+                    0,
+                )
+                .map_err(|error_kind| {
+                    error_kind.with_error_location("[UnaryRef]", "read_as_root", 0)
+                })
+            }
+        }
+
+        /// The table `Binary` in the namespace `tract`
+        ///
+        /// Generated from these locations:
+        /// * Table `Binary` in the file `schema/tract.fbs:76`
+        #[derive(
+            Clone,
+            Debug,
+            PartialEq,
+            PartialOrd,
+            Eq,
+            Ord,
+            Hash,
+            ::serde::Serialize,
+            ::serde::Deserialize,
+        )]
+        pub struct Binary {
+            /// The field `mini_op` in the table `Binary`
+            pub mini_op: ::planus::alloc::string::String,
+        }
+
+        #[allow(clippy::derivable_impls)]
+        impl ::core::default::Default for Binary {
+            fn default() -> Self {
+                Self { mini_op: ::core::default::Default::default() }
+            }
+        }
+
+        impl Binary {
+            /// Creates a [BinaryBuilder] for serializing an instance of this table.
+            #[inline]
+            pub fn builder() -> BinaryBuilder<()> {
+                BinaryBuilder(())
+            }
+
+            #[allow(clippy::too_many_arguments)]
+            pub fn create(
+                builder: &mut ::planus::Builder,
+                field_mini_op: impl ::planus::WriteAs<::planus::Offset<str>>,
+            ) -> ::planus::Offset<Self> {
+                let prepared_mini_op = field_mini_op.prepare(builder);
+
+                let mut table_writer: ::planus::table_writer::TableWriter<6> =
+                    ::core::default::Default::default();
+                table_writer.write_entry::<::planus::Offset<str>>(0);
+
+                unsafe {
+                    table_writer.finish(builder, |object_writer| {
+                        object_writer.write::<_, _, 4>(&prepared_mini_op);
+                    });
+                }
+                builder.current_offset()
+            }
+        }
+
+        impl ::planus::WriteAs<::planus::Offset<Binary>> for Binary {
+            type Prepared = ::planus::Offset<Self>;
+
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<Binary> {
+                ::planus::WriteAsOffset::prepare(self, builder)
+            }
+        }
+
+        impl ::planus::WriteAsOptional<::planus::Offset<Binary>> for Binary {
+            type Prepared = ::planus::Offset<Self>;
+
+            #[inline]
+            fn prepare(
+                &self,
+                builder: &mut ::planus::Builder,
+            ) -> ::core::option::Option<::planus::Offset<Binary>> {
+                ::core::option::Option::Some(::planus::WriteAsOffset::prepare(self, builder))
+            }
+        }
+
+        impl ::planus::WriteAsOffset<Binary> for Binary {
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<Binary> {
+                Binary::create(builder, &self.mini_op)
+            }
+        }
+
+        /// Builder for serializing an instance of the [Binary] type.
+        ///
+        /// Can be created using the [Binary::builder] method.
+        #[derive(Debug)]
+        #[must_use]
+        pub struct BinaryBuilder<State>(State);
+
+        impl BinaryBuilder<()> {
+            /// Setter for the [`mini_op` field](Binary#structfield.mini_op).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn mini_op<T0>(self, value: T0) -> BinaryBuilder<(T0,)>
+            where
+                T0: ::planus::WriteAs<::planus::Offset<str>>,
+            {
+                BinaryBuilder((value,))
+            }
+        }
+
+        impl<T0> BinaryBuilder<(T0,)> {
+            /// Finish writing the builder to get an [Offset](::planus::Offset) to a serialized [Binary].
+            #[inline]
+            pub fn finish(self, builder: &mut ::planus::Builder) -> ::planus::Offset<Binary>
+            where
+                Self: ::planus::WriteAsOffset<Binary>,
+            {
+                ::planus::WriteAsOffset::prepare(&self, builder)
+            }
+        }
+
+        impl<T0: ::planus::WriteAs<::planus::Offset<str>>>
+            ::planus::WriteAs<::planus::Offset<Binary>> for BinaryBuilder<(T0,)>
+        {
+            type Prepared = ::planus::Offset<Binary>;
+
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<Binary> {
+                ::planus::WriteAsOffset::prepare(self, builder)
+            }
+        }
+
+        impl<T0: ::planus::WriteAs<::planus::Offset<str>>>
+            ::planus::WriteAsOptional<::planus::Offset<Binary>> for BinaryBuilder<(T0,)>
+        {
+            type Prepared = ::planus::Offset<Binary>;
+
+            #[inline]
+            fn prepare(
+                &self,
+                builder: &mut ::planus::Builder,
+            ) -> ::core::option::Option<::planus::Offset<Binary>> {
+                ::core::option::Option::Some(::planus::WriteAsOffset::prepare(self, builder))
+            }
+        }
+
+        impl<T0: ::planus::WriteAs<::planus::Offset<str>>> ::planus::WriteAsOffset<Binary>
+            for BinaryBuilder<(T0,)>
+        {
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<Binary> {
+                let (v0,) = &self.0;
+                Binary::create(builder, v0)
+            }
+        }
+
+        /// Reference to a deserialized [Binary].
+        #[derive(Copy, Clone)]
+        pub struct BinaryRef<'a>(#[allow(dead_code)] ::planus::table_reader::Table<'a>);
+
+        impl<'a> BinaryRef<'a> {
+            /// Getter for the [`mini_op` field](Binary#structfield.mini_op).
+            #[inline]
+            pub fn mini_op(&self) -> ::planus::Result<&'a ::core::primitive::str> {
+                self.0.access_required(0, "Binary", "mini_op")
+            }
+        }
+
+        impl<'a> ::core::fmt::Debug for BinaryRef<'a> {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                let mut f = f.debug_struct("BinaryRef");
+                f.field("mini_op", &self.mini_op());
+                f.finish()
+            }
+        }
+
+        impl<'a> ::core::convert::TryFrom<BinaryRef<'a>> for Binary {
+            type Error = ::planus::Error;
+
+            #[allow(unreachable_code)]
+            fn try_from(value: BinaryRef<'a>) -> ::planus::Result<Self> {
+                ::core::result::Result::Ok(Self {
+                    mini_op: ::core::convert::Into::into(value.mini_op()?),
+                })
+            }
+        }
+
+        impl<'a> ::planus::TableRead<'a> for BinaryRef<'a> {
+            #[inline]
+            fn from_buffer(
+                buffer: ::planus::SliceWithStartOffset<'a>,
+                offset: usize,
+            ) -> ::core::result::Result<Self, ::planus::errors::ErrorKind> {
+                ::core::result::Result::Ok(Self(::planus::table_reader::Table::from_buffer(
+                    buffer, offset,
+                )?))
+            }
+        }
+
+        impl<'a> ::planus::VectorReadInner<'a> for BinaryRef<'a> {
+            type Error = ::planus::Error;
+            const STRIDE: usize = 4;
+
+            unsafe fn from_buffer(
+                buffer: ::planus::SliceWithStartOffset<'a>,
+                offset: usize,
+            ) -> ::planus::Result<Self> {
+                ::planus::TableRead::from_buffer(buffer, offset).map_err(|error_kind| {
+                    error_kind.with_error_location("[BinaryRef]", "get", buffer.offset_from_start)
+                })
+            }
+        }
+
+        /// # Safety
+        /// The planus compiler generates implementations that initialize
+        /// the bytes in `write_values`.
+        unsafe impl ::planus::VectorWrite<::planus::Offset<Binary>> for Binary {
+            type Value = ::planus::Offset<Binary>;
+            const STRIDE: usize = 4;
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> Self::Value {
+                ::planus::WriteAs::prepare(self, builder)
+            }
+
+            #[inline]
+            unsafe fn write_values(
+                values: &[::planus::Offset<Binary>],
+                bytes: *mut ::core::mem::MaybeUninit<u8>,
+                buffer_position: u32,
+            ) {
+                let bytes = bytes as *mut [::core::mem::MaybeUninit<u8>; 4];
+                for (i, v) in ::core::iter::Iterator::enumerate(values.iter()) {
+                    ::planus::WriteAsPrimitive::write(
+                        v,
+                        ::planus::Cursor::new(unsafe { &mut *bytes.add(i) }),
+                        buffer_position - (Self::STRIDE * i) as u32,
+                    );
+                }
+            }
+        }
+
+        impl<'a> ::planus::ReadAsRoot<'a> for BinaryRef<'a> {
+            fn read_as_root(slice: &'a [u8]) -> ::planus::Result<Self> {
+                ::planus::TableRead::from_buffer(
+                    ::planus::SliceWithStartOffset { buffer: slice, offset_from_start: 0 },
+                    0,
+                )
+                .map_err(|error_kind| {
+                    error_kind.with_error_location("[BinaryRef]", "read_as_root", 0)
+                })
+            }
+        }
+
+        /// The table `Merge` in the namespace `tract`
+        ///
+        /// Generated from these locations:
+        /// * Table `Merge` in the file `schema/tract.fbs:80`
+        #[derive(
+            Clone,
+            Debug,
+            PartialEq,
+            PartialOrd,
+            Eq,
+            Ord,
+            Hash,
+            ::serde::Serialize,
+            ::serde::Deserialize,
+        )]
+        pub struct Merge {
+            /// The field `mini_op` in the table `Merge`
+            pub mini_op: ::planus::alloc::string::String,
+        }
+
+        #[allow(clippy::derivable_impls)]
+        impl ::core::default::Default for Merge {
+            fn default() -> Self {
+                Self { mini_op: ::core::default::Default::default() }
+            }
+        }
+
+        impl Merge {
+            /// Creates a [MergeBuilder] for serializing an instance of this table.
+            #[inline]
+            pub fn builder() -> MergeBuilder<()> {
+                MergeBuilder(())
+            }
+
+            #[allow(clippy::too_many_arguments)]
+            pub fn create(
+                builder: &mut ::planus::Builder,
+                field_mini_op: impl ::planus::WriteAs<::planus::Offset<str>>,
+            ) -> ::planus::Offset<Self> {
+                let prepared_mini_op = field_mini_op.prepare(builder);
+
+                let mut table_writer: ::planus::table_writer::TableWriter<6> =
+                    ::core::default::Default::default();
+                table_writer.write_entry::<::planus::Offset<str>>(0);
+
+                unsafe {
+                    table_writer.finish(builder, |object_writer| {
+                        object_writer.write::<_, _, 4>(&prepared_mini_op);
+                    });
+                }
+                builder.current_offset()
+            }
+        }
+
+        impl ::planus::WriteAs<::planus::Offset<Merge>> for Merge {
+            type Prepared = ::planus::Offset<Self>;
+
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<Merge> {
+                ::planus::WriteAsOffset::prepare(self, builder)
+            }
+        }
+
+        impl ::planus::WriteAsOptional<::planus::Offset<Merge>> for Merge {
+            type Prepared = ::planus::Offset<Self>;
+
+            #[inline]
+            fn prepare(
+                &self,
+                builder: &mut ::planus::Builder,
+            ) -> ::core::option::Option<::planus::Offset<Merge>> {
+                ::core::option::Option::Some(::planus::WriteAsOffset::prepare(self, builder))
+            }
+        }
+
+        impl ::planus::WriteAsOffset<Merge> for Merge {
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<Merge> {
+                Merge::create(builder, &self.mini_op)
+            }
+        }
+
+        /// Builder for serializing an instance of the [Merge] type.
+        ///
+        /// Can be created using the [Merge::builder] method.
+        #[derive(Debug)]
+        #[must_use]
+        pub struct MergeBuilder<State>(State);
+
+        impl MergeBuilder<()> {
+            /// Setter for the [`mini_op` field](Merge#structfield.mini_op).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn mini_op<T0>(self, value: T0) -> MergeBuilder<(T0,)>
+            where
+                T0: ::planus::WriteAs<::planus::Offset<str>>,
+            {
+                MergeBuilder((value,))
+            }
+        }
+
+        impl<T0> MergeBuilder<(T0,)> {
+            /// Finish writing the builder to get an [Offset](::planus::Offset) to a serialized [Merge].
+            #[inline]
+            pub fn finish(self, builder: &mut ::planus::Builder) -> ::planus::Offset<Merge>
+            where
+                Self: ::planus::WriteAsOffset<Merge>,
+            {
+                ::planus::WriteAsOffset::prepare(&self, builder)
+            }
+        }
+
+        impl<T0: ::planus::WriteAs<::planus::Offset<str>>>
+            ::planus::WriteAs<::planus::Offset<Merge>> for MergeBuilder<(T0,)>
+        {
+            type Prepared = ::planus::Offset<Merge>;
+
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<Merge> {
+                ::planus::WriteAsOffset::prepare(self, builder)
+            }
+        }
+
+        impl<T0: ::planus::WriteAs<::planus::Offset<str>>>
+            ::planus::WriteAsOptional<::planus::Offset<Merge>> for MergeBuilder<(T0,)>
+        {
+            type Prepared = ::planus::Offset<Merge>;
+
+            #[inline]
+            fn prepare(
+                &self,
+                builder: &mut ::planus::Builder,
+            ) -> ::core::option::Option<::planus::Offset<Merge>> {
+                ::core::option::Option::Some(::planus::WriteAsOffset::prepare(self, builder))
+            }
+        }
+
+        impl<T0: ::planus::WriteAs<::planus::Offset<str>>> ::planus::WriteAsOffset<Merge>
+            for MergeBuilder<(T0,)>
+        {
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<Merge> {
+                let (v0,) = &self.0;
+                Merge::create(builder, v0)
+            }
+        }
+
+        /// Reference to a deserialized [Merge].
+        #[derive(Copy, Clone)]
+        pub struct MergeRef<'a>(#[allow(dead_code)] ::planus::table_reader::Table<'a>);
+
+        impl<'a> MergeRef<'a> {
+            /// Getter for the [`mini_op` field](Merge#structfield.mini_op).
+            #[inline]
+            pub fn mini_op(&self) -> ::planus::Result<&'a ::core::primitive::str> {
+                self.0.access_required(0, "Merge", "mini_op")
+            }
+        }
+
+        impl<'a> ::core::fmt::Debug for MergeRef<'a> {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                let mut f = f.debug_struct("MergeRef");
+                f.field("mini_op", &self.mini_op());
+                f.finish()
+            }
+        }
+
+        impl<'a> ::core::convert::TryFrom<MergeRef<'a>> for Merge {
+            type Error = ::planus::Error;
+
+            #[allow(unreachable_code)]
+            fn try_from(value: MergeRef<'a>) -> ::planus::Result<Self> {
+                ::core::result::Result::Ok(Self {
+                    mini_op: ::core::convert::Into::into(value.mini_op()?),
+                })
+            }
+        }
+
+        impl<'a> ::planus::TableRead<'a> for MergeRef<'a> {
+            #[inline]
+            fn from_buffer(
+                buffer: ::planus::SliceWithStartOffset<'a>,
+                offset: usize,
+            ) -> ::core::result::Result<Self, ::planus::errors::ErrorKind> {
+                ::core::result::Result::Ok(Self(::planus::table_reader::Table::from_buffer(
+                    buffer, offset,
+                )?))
+            }
+        }
+
+        impl<'a> ::planus::VectorReadInner<'a> for MergeRef<'a> {
+            type Error = ::planus::Error;
+            const STRIDE: usize = 4;
+
+            unsafe fn from_buffer(
+                buffer: ::planus::SliceWithStartOffset<'a>,
+                offset: usize,
+            ) -> ::planus::Result<Self> {
+                ::planus::TableRead::from_buffer(buffer, offset).map_err(|error_kind| {
+                    error_kind.with_error_location("[MergeRef]", "get", buffer.offset_from_start)
+                })
+            }
+        }
+
+        /// # Safety
+        /// The planus compiler generates implementations that initialize
+        /// the bytes in `write_values`.
+        unsafe impl ::planus::VectorWrite<::planus::Offset<Merge>> for Merge {
+            type Value = ::planus::Offset<Merge>;
+            const STRIDE: usize = 4;
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> Self::Value {
+                ::planus::WriteAs::prepare(self, builder)
+            }
+
+            #[inline]
+            unsafe fn write_values(
+                values: &[::planus::Offset<Merge>],
+                bytes: *mut ::core::mem::MaybeUninit<u8>,
+                buffer_position: u32,
+            ) {
+                let bytes = bytes as *mut [::core::mem::MaybeUninit<u8>; 4];
+                for (i, v) in ::core::iter::Iterator::enumerate(values.iter()) {
+                    ::planus::WriteAsPrimitive::write(
+                        v,
+                        ::planus::Cursor::new(unsafe { &mut *bytes.add(i) }),
+                        buffer_position - (Self::STRIDE * i) as u32,
+                    );
+                }
+            }
+        }
+
+        impl<'a> ::planus::ReadAsRoot<'a> for MergeRef<'a> {
+            fn read_as_root(slice: &'a [u8]) -> ::planus::Result<Self> {
+                ::planus::TableRead::from_buffer(
+                    ::planus::SliceWithStartOffset { buffer: slice, offset_from_start: 0 },
+                    0,
+                )
+                .map_err(|error_kind| {
+                    error_kind.with_error_location("[MergeRef]", "read_as_root", 0)
+                })
+            }
+        }
+
+        /// The table `ElementWise` in the namespace `tract`
+        ///
+        /// Generated from these locations:
+        /// * Table `ElementWise` in the file `schema/tract.fbs:84`
+        #[derive(Clone, Debug, PartialEq, PartialOrd, ::serde::Serialize, ::serde::Deserialize)]
+        pub struct ElementWise {
+            /// The field `mini_op` in the table `ElementWise`
+            pub mini_op: ::planus::alloc::string::String,
+            /// The field `params` in the table `ElementWise`
+            pub params: ::core::option::Option<::planus::alloc::vec::Vec<f32>>,
+            /// The field `tensor_params` in the table `ElementWise`
+            pub tensor_params: ::core::option::Option<::planus::alloc::vec::Vec<self::Tensor>>,
+        }
+
+        #[allow(clippy::derivable_impls)]
+        impl ::core::default::Default for ElementWise {
+            fn default() -> Self {
+                Self {
+                    mini_op: ::core::default::Default::default(),
+                    params: ::core::default::Default::default(),
+                    tensor_params: ::core::default::Default::default(),
+                }
+            }
+        }
+
+        impl ElementWise {
+            /// Creates a [ElementWiseBuilder] for serializing an instance of this table.
+            #[inline]
+            pub fn builder() -> ElementWiseBuilder<()> {
+                ElementWiseBuilder(())
+            }
+
+            #[allow(clippy::too_many_arguments)]
+            pub fn create(
+                builder: &mut ::planus::Builder,
+                field_mini_op: impl ::planus::WriteAs<::planus::Offset<str>>,
+                field_params: impl ::planus::WriteAsOptional<::planus::Offset<[f32]>>,
+                field_tensor_params: impl ::planus::WriteAsOptional<
+                    ::planus::Offset<[::planus::Offset<self::Tensor>]>,
+                >,
+            ) -> ::planus::Offset<Self> {
+                let prepared_mini_op = field_mini_op.prepare(builder);
+                let prepared_params = field_params.prepare(builder);
+                let prepared_tensor_params = field_tensor_params.prepare(builder);
+
+                let mut table_writer: ::planus::table_writer::TableWriter<10> =
+                    ::core::default::Default::default();
+                table_writer.write_entry::<::planus::Offset<str>>(0);
+                if prepared_params.is_some() {
+                    table_writer.write_entry::<::planus::Offset<[f32]>>(1);
+                }
+                if prepared_tensor_params.is_some() {
+                    table_writer
+                        .write_entry::<::planus::Offset<[::planus::Offset<self::Tensor>]>>(2);
+                }
+
+                unsafe {
+                    table_writer.finish(builder, |object_writer| {
+                        object_writer.write::<_, _, 4>(&prepared_mini_op);
+                        if let ::core::option::Option::Some(prepared_params) = prepared_params {
+                            object_writer.write::<_, _, 4>(&prepared_params);
+                        }
+                        if let ::core::option::Option::Some(prepared_tensor_params) =
+                            prepared_tensor_params
+                        {
+                            object_writer.write::<_, _, 4>(&prepared_tensor_params);
+                        }
+                    });
+                }
+                builder.current_offset()
+            }
+        }
+
+        impl ::planus::WriteAs<::planus::Offset<ElementWise>> for ElementWise {
+            type Prepared = ::planus::Offset<Self>;
+
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<ElementWise> {
+                ::planus::WriteAsOffset::prepare(self, builder)
+            }
+        }
+
+        impl ::planus::WriteAsOptional<::planus::Offset<ElementWise>> for ElementWise {
+            type Prepared = ::planus::Offset<Self>;
+
+            #[inline]
+            fn prepare(
+                &self,
+                builder: &mut ::planus::Builder,
+            ) -> ::core::option::Option<::planus::Offset<ElementWise>> {
+                ::core::option::Option::Some(::planus::WriteAsOffset::prepare(self, builder))
+            }
+        }
+
+        impl ::planus::WriteAsOffset<ElementWise> for ElementWise {
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<ElementWise> {
+                ElementWise::create(builder, &self.mini_op, &self.params, &self.tensor_params)
+            }
+        }
+
+        /// Builder for serializing an instance of the [ElementWise] type.
+        ///
+        /// Can be created using the [ElementWise::builder] method.
+        #[derive(Debug)]
+        #[must_use]
+        pub struct ElementWiseBuilder<State>(State);
+
+        impl ElementWiseBuilder<()> {
+            /// Setter for the [`mini_op` field](ElementWise#structfield.mini_op).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn mini_op<T0>(self, value: T0) -> ElementWiseBuilder<(T0,)>
+            where
+                T0: ::planus::WriteAs<::planus::Offset<str>>,
+            {
+                ElementWiseBuilder((value,))
+            }
+        }
+
+        impl<T0> ElementWiseBuilder<(T0,)> {
+            /// Setter for the [`params` field](ElementWise#structfield.params).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn params<T1>(self, value: T1) -> ElementWiseBuilder<(T0, T1)>
+            where
+                T1: ::planus::WriteAsOptional<::planus::Offset<[f32]>>,
+            {
+                let (v0,) = self.0;
+                ElementWiseBuilder((v0, value))
+            }
+
+            /// Sets the [`params` field](ElementWise#structfield.params) to null.
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn params_as_null(self) -> ElementWiseBuilder<(T0, ())> {
+                self.params(())
+            }
+        }
+
+        impl<T0, T1> ElementWiseBuilder<(T0, T1)> {
+            /// Setter for the [`tensor_params` field](ElementWise#structfield.tensor_params).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn tensor_params<T2>(self, value: T2) -> ElementWiseBuilder<(T0, T1, T2)>
+            where
+                T2: ::planus::WriteAsOptional<::planus::Offset<[::planus::Offset<self::Tensor>]>>,
+            {
+                let (v0, v1) = self.0;
+                ElementWiseBuilder((v0, v1, value))
+            }
+
+            /// Sets the [`tensor_params` field](ElementWise#structfield.tensor_params) to null.
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn tensor_params_as_null(self) -> ElementWiseBuilder<(T0, T1, ())> {
+                self.tensor_params(())
+            }
+        }
+
+        impl<T0, T1, T2> ElementWiseBuilder<(T0, T1, T2)> {
+            /// Finish writing the builder to get an [Offset](::planus::Offset) to a serialized [ElementWise].
+            #[inline]
+            pub fn finish(self, builder: &mut ::planus::Builder) -> ::planus::Offset<ElementWise>
+            where
+                Self: ::planus::WriteAsOffset<ElementWise>,
+            {
+                ::planus::WriteAsOffset::prepare(&self, builder)
+            }
+        }
+
+        impl<
+                T0: ::planus::WriteAs<::planus::Offset<str>>,
+                T1: ::planus::WriteAsOptional<::planus::Offset<[f32]>>,
+                T2: ::planus::WriteAsOptional<::planus::Offset<[::planus::Offset<self::Tensor>]>>,
+            > ::planus::WriteAs<::planus::Offset<ElementWise>>
+            for ElementWiseBuilder<(T0, T1, T2)>
+        {
+            type Prepared = ::planus::Offset<ElementWise>;
+
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<ElementWise> {
+                ::planus::WriteAsOffset::prepare(self, builder)
+            }
+        }
+
+        impl<
+                T0: ::planus::WriteAs<::planus::Offset<str>>,
+                T1: ::planus::WriteAsOptional<::planus::Offset<[f32]>>,
+                T2: ::planus::WriteAsOptional<::planus::Offset<[::planus::Offset<self::Tensor>]>>,
+            > ::planus::WriteAsOptional<::planus::Offset<ElementWise>>
+            for ElementWiseBuilder<(T0, T1, T2)>
+        {
+            type Prepared = ::planus::Offset<ElementWise>;
+
+            #[inline]
+            fn prepare(
+                &self,
+                builder: &mut ::planus::Builder,
+            ) -> ::core::option::Option<::planus::Offset<ElementWise>> {
+                ::core::option::Option::Some(::planus::WriteAsOffset::prepare(self, builder))
+            }
+        }
+
+        impl<
+                T0: ::planus::WriteAs<::planus::Offset<str>>,
+                T1: ::planus::WriteAsOptional<::planus::Offset<[f32]>>,
+                T2: ::planus::WriteAsOptional<::planus::Offset<[::planus::Offset<self::Tensor>]>>,
+            > ::planus::WriteAsOffset<ElementWise> for ElementWiseBuilder<(T0, T1, T2)>
+        {
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<ElementWise> {
+                let (v0, v1, v2) = &self.0;
+                ElementWise::create(builder, v0, v1, v2)
+            }
+        }
+
+        /// Reference to a deserialized [ElementWise].
+        #[derive(Copy, Clone)]
+        pub struct ElementWiseRef<'a>(#[allow(dead_code)] ::planus::table_reader::Table<'a>);
+
+        impl<'a> ElementWiseRef<'a> {
+            /// Getter for the [`mini_op` field](ElementWise#structfield.mini_op).
+            #[inline]
+            pub fn mini_op(&self) -> ::planus::Result<&'a ::core::primitive::str> {
+                self.0.access_required(0, "ElementWise", "mini_op")
+            }
+
+            /// Getter for the [`params` field](ElementWise#structfield.params).
+            #[inline]
+            pub fn params(
+                &self,
+            ) -> ::planus::Result<::core::option::Option<::planus::Vector<'a, f32>>> {
+                self.0.access(1, "ElementWise", "params")
+            }
+
+            /// Getter for the [`tensor_params` field](ElementWise#structfield.tensor_params).
+            #[inline]
+            pub fn tensor_params(
+                &self,
+            ) -> ::planus::Result<
+                ::core::option::Option<::planus::Vector<'a, ::planus::Result<self::TensorRef<'a>>>>,
+            > {
+                self.0.access(2, "ElementWise", "tensor_params")
+            }
+        }
+
+        impl<'a> ::core::fmt::Debug for ElementWiseRef<'a> {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                let mut f = f.debug_struct("ElementWiseRef");
+                f.field("mini_op", &self.mini_op());
+                if let ::core::option::Option::Some(field_params) = self.params().transpose() {
+                    f.field("params", &field_params);
+                }
+                if let ::core::option::Option::Some(field_tensor_params) =
+                    self.tensor_params().transpose()
+                {
+                    f.field("tensor_params", &field_tensor_params);
+                }
+                f.finish()
+            }
+        }
+
+        impl<'a> ::core::convert::TryFrom<ElementWiseRef<'a>> for ElementWise {
+            type Error = ::planus::Error;
+
+            #[allow(unreachable_code)]
+            fn try_from(value: ElementWiseRef<'a>) -> ::planus::Result<Self> {
+                ::core::result::Result::Ok(Self {
+                    mini_op: ::core::convert::Into::into(value.mini_op()?),
+                    params: if let ::core::option::Option::Some(params) = value.params()? {
+                        ::core::option::Option::Some(params.to_vec()?)
+                    } else {
+                        ::core::option::Option::None
+                    },
+                    tensor_params: if let ::core::option::Option::Some(tensor_params) =
+                        value.tensor_params()?
+                    {
+                        ::core::option::Option::Some(tensor_params.to_vec_result()?)
+                    } else {
+                        ::core::option::Option::None
+                    },
+                })
+            }
+        }
+
+        impl<'a> ::planus::TableRead<'a> for ElementWiseRef<'a> {
+            #[inline]
+            fn from_buffer(
+                buffer: ::planus::SliceWithStartOffset<'a>,
+                offset: usize,
+            ) -> ::core::result::Result<Self, ::planus::errors::ErrorKind> {
+                ::core::result::Result::Ok(Self(::planus::table_reader::Table::from_buffer(
+                    buffer, offset,
+                )?))
+            }
+        }
+
+        impl<'a> ::planus::VectorReadInner<'a> for ElementWiseRef<'a> {
+            type Error = ::planus::Error;
+            const STRIDE: usize = 4;
+
+            unsafe fn from_buffer(
+                buffer: ::planus::SliceWithStartOffset<'a>,
+                offset: usize,
+            ) -> ::planus::Result<Self> {
+                ::planus::TableRead::from_buffer(buffer, offset).map_err(|error_kind| {
+                    error_kind.with_error_location(
+                        "[ElementWiseRef]",
+                        "get",
+                        buffer.offset_from_start,
+                    )
+                })
+            }
+        }
+
+        /// # Safety
+        /// The planus compiler generates implementations that initialize
+        /// the bytes in `write_values`.
+        unsafe impl ::planus::VectorWrite<::planus::Offset<ElementWise>> for ElementWise {
+            type Value = ::planus::Offset<ElementWise>;
+            const STRIDE: usize = 4;
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> Self::Value {
+                ::planus::WriteAs::prepare(self, builder)
+            }
+
+            #[inline]
+            unsafe fn write_values(
+                values: &[::planus::Offset<ElementWise>],
+                bytes: *mut ::core::mem::MaybeUninit<u8>,
+                buffer_position: u32,
+            ) {
+                let bytes = bytes as *mut [::core::mem::MaybeUninit<u8>; 4];
+                for (i, v) in ::core::iter::Iterator::enumerate(values.iter()) {
+                    ::planus::WriteAsPrimitive::write(
+                        v,
+                        ::planus::Cursor::new(unsafe { &mut *bytes.add(i) }),
+                        buffer_position - (Self::STRIDE * i) as u32,
+                    );
+                }
+            }
+        }
+
+        impl<'a> ::planus::ReadAsRoot<'a> for ElementWiseRef<'a> {
+            fn read_as_root(slice: &'a [u8]) -> ::planus::Result<Self> {
+                ::planus::TableRead::from_buffer(
+                    ::planus::SliceWithStartOffset { buffer: slice, offset_from_start: 0 },
+                    0,
+                )
+                .map_err(|error_kind| {
+                    error_kind.with_error_location("[ElementWiseRef]", "read_as_root", 0)
+                })
+            }
+        }
+
+        /// The enum `PaddingMode` in the namespace `tract`
+        ///
+        /// Generated from these locations:
+        /// * Enum `PaddingMode` in the file `schema/tract.fbs:90`
+        #[derive(
+            Copy,
+            Clone,
+            Debug,
+            PartialEq,
+            Eq,
+            PartialOrd,
+            Ord,
+            Hash,
+            ::serde::Serialize,
+            ::serde::Deserialize,
+        )]
+        #[repr(u8)]
+        pub enum PaddingMode {
+            /// The variant `Valid` in the enum `PaddingMode`
+            Valid = 0,
+
+            /// The variant `SameUpper` in the enum `PaddingMode`
+            SameUpper = 1,
+
+            /// The variant `SameLower` in the enum `PaddingMode`
+            SameLower = 2,
+
+            /// The variant `Explicit` in the enum `PaddingMode`
+            Explicit = 3,
+        }
+
+        impl PaddingMode {
+            /// Array containing all valid variants of PaddingMode
+            pub const ENUM_VALUES: [Self; 4] =
+                [Self::Valid, Self::SameUpper, Self::SameLower, Self::Explicit];
+        }
+
+        impl ::core::convert::TryFrom<u8> for PaddingMode {
+            type Error = ::planus::errors::UnknownEnumTagKind;
+            #[inline]
+            fn try_from(
+                value: u8,
+            ) -> ::core::result::Result<Self, ::planus::errors::UnknownEnumTagKind> {
+                #[allow(clippy::match_single_binding)]
+                match value {
+                    0 => ::core::result::Result::Ok(PaddingMode::Valid),
+                    1 => ::core::result::Result::Ok(PaddingMode::SameUpper),
+                    2 => ::core::result::Result::Ok(PaddingMode::SameLower),
+                    3 => ::core::result::Result::Ok(PaddingMode::Explicit),
+
+                    _ => ::core::result::Result::Err(::planus::errors::UnknownEnumTagKind {
+                        tag: value as i128,
+                    }),
+                }
+            }
+        }
+
+        impl ::core::convert::From<PaddingMode> for u8 {
+            #[inline]
+            fn from(value: PaddingMode) -> Self {
+                value as u8
+            }
+        }
+
+        /// # Safety
+        /// The Planus compiler correctly calculates `ALIGNMENT` and `SIZE`.
+        unsafe impl ::planus::Primitive for PaddingMode {
+            const ALIGNMENT: usize = 1;
+            const SIZE: usize = 1;
+        }
+
+        impl ::planus::WriteAsPrimitive<PaddingMode> for PaddingMode {
+            #[inline]
+            fn write<const N: usize>(&self, cursor: ::planus::Cursor<'_, N>, buffer_position: u32) {
+                (*self as u8).write(cursor, buffer_position);
+            }
+        }
+
+        impl ::planus::WriteAs<PaddingMode> for PaddingMode {
+            type Prepared = Self;
+
+            #[inline]
+            fn prepare(&self, _builder: &mut ::planus::Builder) -> PaddingMode {
+                *self
+            }
+        }
+
+        impl ::planus::WriteAsDefault<PaddingMode, PaddingMode> for PaddingMode {
+            type Prepared = Self;
+
+            #[inline]
+            fn prepare(
+                &self,
+                _builder: &mut ::planus::Builder,
+                default: &PaddingMode,
+            ) -> ::core::option::Option<PaddingMode> {
+                if self == default {
+                    ::core::option::Option::None
+                } else {
+                    ::core::option::Option::Some(*self)
+                }
+            }
+        }
+
+        impl ::planus::WriteAsOptional<PaddingMode> for PaddingMode {
+            type Prepared = Self;
+
+            #[inline]
+            fn prepare(
+                &self,
+                _builder: &mut ::planus::Builder,
+            ) -> ::core::option::Option<PaddingMode> {
+                ::core::option::Option::Some(*self)
+            }
+        }
+
+        impl<'buf> ::planus::TableRead<'buf> for PaddingMode {
+            #[inline]
+            fn from_buffer(
+                buffer: ::planus::SliceWithStartOffset<'buf>,
+                offset: usize,
+            ) -> ::core::result::Result<Self, ::planus::errors::ErrorKind> {
+                let n: u8 = ::planus::TableRead::from_buffer(buffer, offset)?;
+                ::core::result::Result::Ok(::core::convert::TryInto::try_into(n)?)
+            }
+        }
+
+        impl<'buf> ::planus::VectorReadInner<'buf> for PaddingMode {
+            type Error = ::planus::errors::UnknownEnumTag;
+            const STRIDE: usize = 1;
+            #[inline]
+            unsafe fn from_buffer(
+                buffer: ::planus::SliceWithStartOffset<'buf>,
+                offset: usize,
+            ) -> ::core::result::Result<Self, ::planus::errors::UnknownEnumTag> {
+                let value = unsafe { *buffer.buffer.get_unchecked(offset) };
+                let value: ::core::result::Result<Self, _> =
+                    ::core::convert::TryInto::try_into(value);
+                value.map_err(|error_kind| {
+                    error_kind.with_error_location(
+                        "PaddingMode",
+                        "VectorRead::from_buffer",
+                        buffer.offset_from_start,
+                    )
+                })
+            }
+        }
+
+        /// # Safety
+        /// The planus compiler generates implementations that initialize
+        /// the bytes in `write_values`.
+        unsafe impl ::planus::VectorWrite<PaddingMode> for PaddingMode {
+            const STRIDE: usize = 1;
+
+            type Value = Self;
+
+            #[inline]
+            fn prepare(&self, _builder: &mut ::planus::Builder) -> Self {
+                *self
+            }
+
+            #[inline]
+            unsafe fn write_values(
+                values: &[Self],
+                bytes: *mut ::core::mem::MaybeUninit<u8>,
+                buffer_position: u32,
+            ) {
+                let bytes = bytes as *mut [::core::mem::MaybeUninit<u8>; 1];
+                for (i, v) in ::core::iter::Iterator::enumerate(values.iter()) {
+                    ::planus::WriteAsPrimitive::write(
+                        v,
+                        ::planus::Cursor::new(unsafe { &mut *bytes.add(i) }),
+                        buffer_position - i as u32,
+                    );
+                }
+            }
+        }
+
+        /// The table `Padding` in the namespace `tract`
+        ///
+        /// Generated from these locations:
+        /// * Table `Padding` in the file `schema/tract.fbs:97`
+        #[derive(
+            Clone,
+            Debug,
+            PartialEq,
+            PartialOrd,
+            Eq,
+            Ord,
+            Hash,
+            ::serde::Serialize,
+            ::serde::Deserialize,
+        )]
+        pub struct Padding {
+            /// The field `mode` in the table `Padding`
+            pub mode: self::PaddingMode,
+            /// The field `before` in the table `Padding`
+            pub before: ::core::option::Option<::planus::alloc::vec::Vec<u64>>,
+            /// The field `after` in the table `Padding`
+            pub after: ::core::option::Option<::planus::alloc::vec::Vec<u64>>,
+        }
+
+        #[allow(clippy::derivable_impls)]
+        impl ::core::default::Default for Padding {
+            fn default() -> Self {
+                Self {
+                    mode: self::PaddingMode::Valid,
+                    before: ::core::default::Default::default(),
+                    after: ::core::default::Default::default(),
+                }
+            }
+        }
+
+        impl Padding {
+            /// Creates a [PaddingBuilder] for serializing an instance of this table.
+            #[inline]
+            pub fn builder() -> PaddingBuilder<()> {
+                PaddingBuilder(())
+            }
+
+            #[allow(clippy::too_many_arguments)]
+            pub fn create(
+                builder: &mut ::planus::Builder,
+                field_mode: impl ::planus::WriteAsDefault<self::PaddingMode, self::PaddingMode>,
+                field_before: impl ::planus::WriteAsOptional<::planus::Offset<[u64]>>,
+                field_after: impl ::planus::WriteAsOptional<::planus::Offset<[u64]>>,
+            ) -> ::planus::Offset<Self> {
+                let prepared_mode = field_mode.prepare(builder, &self::PaddingMode::Valid);
+                let prepared_before = field_before.prepare(builder);
+                let prepared_after = field_after.prepare(builder);
+
+                let mut table_writer: ::planus::table_writer::TableWriter<10> =
+                    ::core::default::Default::default();
+                if prepared_before.is_some() {
+                    table_writer.write_entry::<::planus::Offset<[u64]>>(1);
+                }
+                if prepared_after.is_some() {
+                    table_writer.write_entry::<::planus::Offset<[u64]>>(2);
+                }
+                if prepared_mode.is_some() {
+                    table_writer.write_entry::<self::PaddingMode>(0);
+                }
+
+                unsafe {
+                    table_writer.finish(builder, |object_writer| {
+                        if let ::core::option::Option::Some(prepared_before) = prepared_before {
+                            object_writer.write::<_, _, 4>(&prepared_before);
+                        }
+                        if let ::core::option::Option::Some(prepared_after) = prepared_after {
+                            object_writer.write::<_, _, 4>(&prepared_after);
+                        }
+                        if let ::core::option::Option::Some(prepared_mode) = prepared_mode {
+                            object_writer.write::<_, _, 1>(&prepared_mode);
+                        }
+                    });
+                }
+                builder.current_offset()
+            }
+        }
+
+        impl ::planus::WriteAs<::planus::Offset<Padding>> for Padding {
+            type Prepared = ::planus::Offset<Self>;
+
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<Padding> {
+                ::planus::WriteAsOffset::prepare(self, builder)
+            }
+        }
+
+        impl ::planus::WriteAsOptional<::planus::Offset<Padding>> for Padding {
+            type Prepared = ::planus::Offset<Self>;
+
+            #[inline]
+            fn prepare(
+                &self,
+                builder: &mut ::planus::Builder,
+            ) -> ::core::option::Option<::planus::Offset<Padding>> {
+                ::core::option::Option::Some(::planus::WriteAsOffset::prepare(self, builder))
+            }
+        }
+
+        impl ::planus::WriteAsOffset<Padding> for Padding {
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<Padding> {
+                Padding::create(builder, self.mode, &self.before, &self.after)
+            }
+        }
+
+        /// Builder for serializing an instance of the [Padding] type.
+        ///
+        /// Can be created using the [Padding::builder] method.
+        #[derive(Debug)]
+        #[must_use]
+        pub struct PaddingBuilder<State>(State);
+
+        impl PaddingBuilder<()> {
+            /// Setter for the [`mode` field](Padding#structfield.mode).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn mode<T0>(self, value: T0) -> PaddingBuilder<(T0,)>
+            where
+                T0: ::planus::WriteAsDefault<self::PaddingMode, self::PaddingMode>,
+            {
+                PaddingBuilder((value,))
+            }
+
+            /// Sets the [`mode` field](Padding#structfield.mode) to the default value.
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn mode_as_default(self) -> PaddingBuilder<(::planus::DefaultValue,)> {
+                self.mode(::planus::DefaultValue)
+            }
+        }
+
+        impl<T0> PaddingBuilder<(T0,)> {
+            /// Setter for the [`before` field](Padding#structfield.before).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn before<T1>(self, value: T1) -> PaddingBuilder<(T0, T1)>
+            where
+                T1: ::planus::WriteAsOptional<::planus::Offset<[u64]>>,
+            {
+                let (v0,) = self.0;
+                PaddingBuilder((v0, value))
+            }
+
+            /// Sets the [`before` field](Padding#structfield.before) to null.
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn before_as_null(self) -> PaddingBuilder<(T0, ())> {
+                self.before(())
+            }
+        }
+
+        impl<T0, T1> PaddingBuilder<(T0, T1)> {
+            /// Setter for the [`after` field](Padding#structfield.after).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn after<T2>(self, value: T2) -> PaddingBuilder<(T0, T1, T2)>
+            where
+                T2: ::planus::WriteAsOptional<::planus::Offset<[u64]>>,
+            {
+                let (v0, v1) = self.0;
+                PaddingBuilder((v0, v1, value))
+            }
+
+            /// Sets the [`after` field](Padding#structfield.after) to null.
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn after_as_null(self) -> PaddingBuilder<(T0, T1, ())> {
+                self.after(())
+            }
+        }
+
+        impl<T0, T1, T2> PaddingBuilder<(T0, T1, T2)> {
+            /// Finish writing the builder to get an [Offset](::planus::Offset) to a serialized [Padding].
+            #[inline]
+            pub fn finish(self, builder: &mut ::planus::Builder) -> ::planus::Offset<Padding>
+            where
+                Self: ::planus::WriteAsOffset<Padding>,
+            {
+                ::planus::WriteAsOffset::prepare(&self, builder)
+            }
+        }
+
+        impl<
+                T0: ::planus::WriteAsDefault<self::PaddingMode, self::PaddingMode>,
+                T1: ::planus::WriteAsOptional<::planus::Offset<[u64]>>,
+                T2: ::planus::WriteAsOptional<::planus::Offset<[u64]>>,
+            > ::planus::WriteAs<::planus::Offset<Padding>> for PaddingBuilder<(T0, T1, T2)>
+        {
+            type Prepared = ::planus::Offset<Padding>;
+
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<Padding> {
+                ::planus::WriteAsOffset::prepare(self, builder)
+            }
+        }
+
+        impl<
+                T0: ::planus::WriteAsDefault<self::PaddingMode, self::PaddingMode>,
+                T1: ::planus::WriteAsOptional<::planus::Offset<[u64]>>,
+                T2: ::planus::WriteAsOptional<::planus::Offset<[u64]>>,
+            > ::planus::WriteAsOptional<::planus::Offset<Padding>>
+            for PaddingBuilder<(T0, T1, T2)>
+        {
+            type Prepared = ::planus::Offset<Padding>;
+
+            #[inline]
+            fn prepare(
+                &self,
+                builder: &mut ::planus::Builder,
+            ) -> ::core::option::Option<::planus::Offset<Padding>> {
+                ::core::option::Option::Some(::planus::WriteAsOffset::prepare(self, builder))
+            }
+        }
+
+        impl<
+                T0: ::planus::WriteAsDefault<self::PaddingMode, self::PaddingMode>,
+                T1: ::planus::WriteAsOptional<::planus::Offset<[u64]>>,
+                T2: ::planus::WriteAsOptional<::planus::Offset<[u64]>>,
+            > ::planus::WriteAsOffset<Padding> for PaddingBuilder<(T0, T1, T2)>
+        {
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<Padding> {
+                let (v0, v1, v2) = &self.0;
+                Padding::create(builder, v0, v1, v2)
+            }
+        }
+
+        /// Reference to a deserialized [Padding].
+        #[derive(Copy, Clone)]
+        pub struct PaddingRef<'a>(#[allow(dead_code)] ::planus::table_reader::Table<'a>);
+
+        impl<'a> PaddingRef<'a> {
+            /// Getter for the [`mode` field](Padding#structfield.mode).
+            #[inline]
+            pub fn mode(&self) -> ::planus::Result<self::PaddingMode> {
+                ::core::result::Result::Ok(
+                    self.0.access(0, "Padding", "mode")?.unwrap_or(self::PaddingMode::Valid),
+                )
+            }
+
+            /// Getter for the [`before` field](Padding#structfield.before).
+            #[inline]
+            pub fn before(
+                &self,
+            ) -> ::planus::Result<::core::option::Option<::planus::Vector<'a, u64>>> {
+                self.0.access(1, "Padding", "before")
+            }
+
+            /// Getter for the [`after` field](Padding#structfield.after).
+            #[inline]
+            pub fn after(
+                &self,
+            ) -> ::planus::Result<::core::option::Option<::planus::Vector<'a, u64>>> {
+                self.0.access(2, "Padding", "after")
+            }
+        }
+
+        impl<'a> ::core::fmt::Debug for PaddingRef<'a> {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                let mut f = f.debug_struct("PaddingRef");
+                f.field("mode", &self.mode());
+                if let ::core::option::Option::Some(field_before) = self.before().transpose() {
+                    f.field("before", &field_before);
+                }
+                if let ::core::option::Option::Some(field_after) = self.after().transpose() {
+                    f.field("after", &field_after);
+                }
+                f.finish()
+            }
+        }
+
+        impl<'a> ::core::convert::TryFrom<PaddingRef<'a>> for Padding {
+            type Error = ::planus::Error;
+
+            #[allow(unreachable_code)]
+            fn try_from(value: PaddingRef<'a>) -> ::planus::Result<Self> {
+                ::core::result::Result::Ok(Self {
+                    mode: ::core::convert::TryInto::try_into(value.mode()?)?,
+                    before: if let ::core::option::Option::Some(before) = value.before()? {
+                        ::core::option::Option::Some(before.to_vec()?)
+                    } else {
+                        ::core::option::Option::None
+                    },
+                    after: if let ::core::option::Option::Some(after) = value.after()? {
+                        ::core::option::Option::Some(after.to_vec()?)
+                    } else {
+                        ::core::option::Option::None
+                    },
+                })
+            }
+        }
+
+        impl<'a> ::planus::TableRead<'a> for PaddingRef<'a> {
+            #[inline]
+            fn from_buffer(
+                buffer: ::planus::SliceWithStartOffset<'a>,
+                offset: usize,
+            ) -> ::core::result::Result<Self, ::planus::errors::ErrorKind> {
+                ::core::result::Result::Ok(Self(::planus::table_reader::Table::from_buffer(
+                    buffer, offset,
+                )?))
+            }
+        }
+
+        impl<'a> ::planus::VectorReadInner<'a> for PaddingRef<'a> {
+            type Error = ::planus::Error;
+            const STRIDE: usize = 4;
+
+            unsafe fn from_buffer(
+                buffer: ::planus::SliceWithStartOffset<'a>,
+                offset: usize,
+            ) -> ::planus::Result<Self> {
+                ::planus::TableRead::from_buffer(buffer, offset).map_err(|error_kind| {
+                    error_kind.with_error_location("[PaddingRef]", "get", buffer.offset_from_start)
+                })
+            }
+        }
+
+        /// # Safety
+        /// The planus compiler generates implementations that initialize
+        /// the bytes in `write_values`.
+        unsafe impl ::planus::VectorWrite<::planus::Offset<Padding>> for Padding {
+            type Value = ::planus::Offset<Padding>;
+            const STRIDE: usize = 4;
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> Self::Value {
+                ::planus::WriteAs::prepare(self, builder)
+            }
+
+            #[inline]
+            unsafe fn write_values(
+                values: &[::planus::Offset<Padding>],
+                bytes: *mut ::core::mem::MaybeUninit<u8>,
+                buffer_position: u32,
+            ) {
+                let bytes = bytes as *mut [::core::mem::MaybeUninit<u8>; 4];
+                for (i, v) in ::core::iter::Iterator::enumerate(values.iter()) {
+                    ::planus::WriteAsPrimitive::write(
+                        v,
+                        ::planus::Cursor::new(unsafe { &mut *bytes.add(i) }),
+                        buffer_position - (Self::STRIDE * i) as u32,
+                    );
+                }
+            }
+        }
+
+        impl<'a> ::planus::ReadAsRoot<'a> for PaddingRef<'a> {
+            fn read_as_root(slice: &'a [u8]) -> ::planus::Result<Self> {
+                ::planus::TableRead::from_buffer(
+                    ::planus::SliceWithStartOffset { buffer: slice, offset_from_start: 0 },
+                    0,
+                )
+                .map_err(|error_kind| {
+                    error_kind.with_error_location("[PaddingRef]", "read_as_root", 0)
+                })
+            }
+        }
+
+        /// The enum `DataFormat` in the namespace `tract`
+        ///
+        /// Generated from these locations:
+        /// * Enum `DataFormat` in the file `schema/tract.fbs:103`
+        #[derive(
+            Copy,
+            Clone,
+            Debug,
+            PartialEq,
+            Eq,
+            PartialOrd,
+            Ord,
+            Hash,
+            ::serde::Serialize,
+            ::serde::Deserialize,
+        )]
+        #[repr(u8)]
+        pub enum DataFormat {
+            /// The variant `NCHW` in the enum `DataFormat`
+            Nchw = 0,
+
+            /// The variant `NHWC` in the enum `DataFormat`
+            Nhwc = 1,
+
+            /// The variant `CHW` in the enum `DataFormat`
+            Chw = 2,
+
+            /// The variant `HWC` in the enum `DataFormat`
+            Hwc = 3,
+        }
+
+        impl DataFormat {
+            /// Array containing all valid variants of DataFormat
+            pub const ENUM_VALUES: [Self; 4] = [Self::Nchw, Self::Nhwc, Self::Chw, Self::Hwc];
+        }
+
+        impl ::core::convert::TryFrom<u8> for DataFormat {
+            type Error = ::planus::errors::UnknownEnumTagKind;
+            #[inline]
+            fn try_from(
+                value: u8,
+            ) -> ::core::result::Result<Self, ::planus::errors::UnknownEnumTagKind> {
+                #[allow(clippy::match_single_binding)]
+                match value {
+                    0 => ::core::result::Result::Ok(DataFormat::Nchw),
+                    1 => ::core::result::Result::Ok(DataFormat::Nhwc),
+                    2 => ::core::result::Result::Ok(DataFormat::Chw),
+                    3 => ::core::result::Result::Ok(DataFormat::Hwc),
+
+                    _ => ::core::result::Result::Err(::planus::errors::UnknownEnumTagKind {
+                        tag: value as i128,
+                    }),
+                }
+            }
+        }
+
+        impl ::core::convert::From<DataFormat> for u8 {
+            #[inline]
+            fn from(value: DataFormat) -> Self {
+                value as u8
+            }
+        }
+
+        /// # Safety
+        /// The Planus compiler correctly calculates `ALIGNMENT` and `SIZE`.
+        unsafe impl ::planus::Primitive for DataFormat {
+            const ALIGNMENT: usize = 1;
+            const SIZE: usize = 1;
+        }
+
+        impl ::planus::WriteAsPrimitive<DataFormat> for DataFormat {
+            #[inline]
+            fn write<const N: usize>(&self, cursor: ::planus::Cursor<'_, N>, buffer_position: u32) {
+                (*self as u8).write(cursor, buffer_position);
+            }
+        }
+
+        impl ::planus::WriteAs<DataFormat> for DataFormat {
+            type Prepared = Self;
+
+            #[inline]
+            fn prepare(&self, _builder: &mut ::planus::Builder) -> DataFormat {
+                *self
+            }
+        }
+
+        impl ::planus::WriteAsDefault<DataFormat, DataFormat> for DataFormat {
+            type Prepared = Self;
+
+            #[inline]
+            fn prepare(
+                &self,
+                _builder: &mut ::planus::Builder,
+                default: &DataFormat,
+            ) -> ::core::option::Option<DataFormat> {
+                if self == default {
+                    ::core::option::Option::None
+                } else {
+                    ::core::option::Option::Some(*self)
+                }
+            }
+        }
+
+        impl ::planus::WriteAsOptional<DataFormat> for DataFormat {
+            type Prepared = Self;
+
+            #[inline]
+            fn prepare(
+                &self,
+                _builder: &mut ::planus::Builder,
+            ) -> ::core::option::Option<DataFormat> {
+                ::core::option::Option::Some(*self)
+            }
+        }
+
+        impl<'buf> ::planus::TableRead<'buf> for DataFormat {
+            #[inline]
+            fn from_buffer(
+                buffer: ::planus::SliceWithStartOffset<'buf>,
+                offset: usize,
+            ) -> ::core::result::Result<Self, ::planus::errors::ErrorKind> {
+                let n: u8 = ::planus::TableRead::from_buffer(buffer, offset)?;
+                ::core::result::Result::Ok(::core::convert::TryInto::try_into(n)?)
+            }
+        }
+
+        impl<'buf> ::planus::VectorReadInner<'buf> for DataFormat {
+            type Error = ::planus::errors::UnknownEnumTag;
+            const STRIDE: usize = 1;
+            #[inline]
+            unsafe fn from_buffer(
+                buffer: ::planus::SliceWithStartOffset<'buf>,
+                offset: usize,
+            ) -> ::core::result::Result<Self, ::planus::errors::UnknownEnumTag> {
+                let value = unsafe { *buffer.buffer.get_unchecked(offset) };
+                let value: ::core::result::Result<Self, _> =
+                    ::core::convert::TryInto::try_into(value);
+                value.map_err(|error_kind| {
+                    error_kind.with_error_location(
+                        "DataFormat",
+                        "VectorRead::from_buffer",
+                        buffer.offset_from_start,
+                    )
+                })
+            }
+        }
+
+        /// # Safety
+        /// The planus compiler generates implementations that initialize
+        /// the bytes in `write_values`.
+        unsafe impl ::planus::VectorWrite<DataFormat> for DataFormat {
+            const STRIDE: usize = 1;
+
+            type Value = Self;
+
+            #[inline]
+            fn prepare(&self, _builder: &mut ::planus::Builder) -> Self {
+                *self
+            }
+
+            #[inline]
+            unsafe fn write_values(
+                values: &[Self],
+                bytes: *mut ::core::mem::MaybeUninit<u8>,
+                buffer_position: u32,
+            ) {
+                let bytes = bytes as *mut [::core::mem::MaybeUninit<u8>; 1];
+                for (i, v) in ::core::iter::Iterator::enumerate(values.iter()) {
+                    ::planus::WriteAsPrimitive::write(
+                        v,
+                        ::planus::Cursor::new(unsafe { &mut *bytes.add(i) }),
+                        buffer_position - i as u32,
+                    );
+                }
+            }
+        }
+
+        /// The table `PoolSpec` in the namespace `tract`
+        ///
+        /// Generated from these locations:
+        /// * Table `PoolSpec` in the file `schema/tract.fbs:110`
+        #[derive(
+            Clone,
+            Debug,
+            PartialEq,
+            PartialOrd,
+            Eq,
+            Ord,
+            Hash,
+            ::serde::Serialize,
+            ::serde::Deserialize,
+        )]
+        pub struct PoolSpec {
+            /// The field `data_format` in the table `PoolSpec`
+            pub data_format: self::DataFormat,
+            /// The field `kernel_shape` in the table `PoolSpec`
+            pub kernel_shape: ::planus::alloc::vec::Vec<u64>,
+            /// The field `padding` in the table `PoolSpec`
+            pub padding: ::planus::alloc::boxed::Box<self::Padding>,
+            /// The field `dilations` in the table `PoolSpec`
+            pub dilations: ::core::option::Option<::planus::alloc::vec::Vec<u64>>,
+            /// The field `strides` in the table `PoolSpec`
+            pub strides: ::core::option::Option<::planus::alloc::vec::Vec<u64>>,
+            /// The field `output_channel_override` in the table `PoolSpec`
+            pub output_channel_override: ::core::option::Option<u64>,
+        }
+
+        #[allow(clippy::derivable_impls)]
+        impl ::core::default::Default for PoolSpec {
+            fn default() -> Self {
+                Self {
+                    data_format: self::DataFormat::Nchw,
+                    kernel_shape: ::core::default::Default::default(),
+                    padding: ::core::default::Default::default(),
+                    dilations: ::core::default::Default::default(),
+                    strides: ::core::default::Default::default(),
+                    output_channel_override: ::core::default::Default::default(),
+                }
+            }
+        }
+
+        impl PoolSpec {
+            /// Creates a [PoolSpecBuilder] for serializing an instance of this table.
+            #[inline]
+            pub fn builder() -> PoolSpecBuilder<()> {
+                PoolSpecBuilder(())
+            }
+
+            #[allow(clippy::too_many_arguments)]
+            pub fn create(
+                builder: &mut ::planus::Builder,
+                field_data_format: impl ::planus::WriteAsDefault<self::DataFormat, self::DataFormat>,
+                field_kernel_shape: impl ::planus::WriteAs<::planus::Offset<[u64]>>,
+                field_padding: impl ::planus::WriteAs<::planus::Offset<self::Padding>>,
+                field_dilations: impl ::planus::WriteAsOptional<::planus::Offset<[u64]>>,
+                field_strides: impl ::planus::WriteAsOptional<::planus::Offset<[u64]>>,
+                field_output_channel_override: impl ::planus::WriteAsOptional<u64>,
+            ) -> ::planus::Offset<Self> {
+                let prepared_data_format =
+                    field_data_format.prepare(builder, &self::DataFormat::Nchw);
+                let prepared_kernel_shape = field_kernel_shape.prepare(builder);
+                let prepared_padding = field_padding.prepare(builder);
+                let prepared_dilations = field_dilations.prepare(builder);
+                let prepared_strides = field_strides.prepare(builder);
+                let prepared_output_channel_override =
+                    field_output_channel_override.prepare(builder);
+
+                let mut table_writer: ::planus::table_writer::TableWriter<16> =
+                    ::core::default::Default::default();
+                if prepared_output_channel_override.is_some() {
+                    table_writer.write_entry::<u64>(5);
+                }
+                table_writer.write_entry::<::planus::Offset<[u64]>>(1);
+                table_writer.write_entry::<::planus::Offset<self::Padding>>(2);
+                if prepared_dilations.is_some() {
+                    table_writer.write_entry::<::planus::Offset<[u64]>>(3);
+                }
+                if prepared_strides.is_some() {
+                    table_writer.write_entry::<::planus::Offset<[u64]>>(4);
+                }
+                if prepared_data_format.is_some() {
+                    table_writer.write_entry::<self::DataFormat>(0);
+                }
+
+                unsafe {
+                    table_writer.finish(builder, |object_writer| {
+                        if let ::core::option::Option::Some(prepared_output_channel_override) =
+                            prepared_output_channel_override
+                        {
+                            object_writer.write::<_, _, 8>(&prepared_output_channel_override);
+                        }
+                        object_writer.write::<_, _, 4>(&prepared_kernel_shape);
+                        object_writer.write::<_, _, 4>(&prepared_padding);
+                        if let ::core::option::Option::Some(prepared_dilations) = prepared_dilations
+                        {
+                            object_writer.write::<_, _, 4>(&prepared_dilations);
+                        }
+                        if let ::core::option::Option::Some(prepared_strides) = prepared_strides {
+                            object_writer.write::<_, _, 4>(&prepared_strides);
+                        }
+                        if let ::core::option::Option::Some(prepared_data_format) =
+                            prepared_data_format
+                        {
+                            object_writer.write::<_, _, 1>(&prepared_data_format);
+                        }
+                    });
+                }
+                builder.current_offset()
+            }
+        }
+
+        impl ::planus::WriteAs<::planus::Offset<PoolSpec>> for PoolSpec {
+            type Prepared = ::planus::Offset<Self>;
+
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<PoolSpec> {
+                ::planus::WriteAsOffset::prepare(self, builder)
+            }
+        }
+
+        impl ::planus::WriteAsOptional<::planus::Offset<PoolSpec>> for PoolSpec {
+            type Prepared = ::planus::Offset<Self>;
+
+            #[inline]
+            fn prepare(
+                &self,
+                builder: &mut ::planus::Builder,
+            ) -> ::core::option::Option<::planus::Offset<PoolSpec>> {
+                ::core::option::Option::Some(::planus::WriteAsOffset::prepare(self, builder))
+            }
+        }
+
+        impl ::planus::WriteAsOffset<PoolSpec> for PoolSpec {
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<PoolSpec> {
+                PoolSpec::create(
+                    builder,
+                    self.data_format,
+                    &self.kernel_shape,
+                    &self.padding,
+                    &self.dilations,
+                    &self.strides,
+                    self.output_channel_override,
+                )
+            }
+        }
+
+        /// Builder for serializing an instance of the [PoolSpec] type.
+        ///
+        /// Can be created using the [PoolSpec::builder] method.
+        #[derive(Debug)]
+        #[must_use]
+        pub struct PoolSpecBuilder<State>(State);
+
+        impl PoolSpecBuilder<()> {
+            /// Setter for the [`data_format` field](PoolSpec#structfield.data_format).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn data_format<T0>(self, value: T0) -> PoolSpecBuilder<(T0,)>
+            where
+                T0: ::planus::WriteAsDefault<self::DataFormat, self::DataFormat>,
+            {
+                PoolSpecBuilder((value,))
+            }
+
+            /// Sets the [`data_format` field](PoolSpec#structfield.data_format) to the default value.
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn data_format_as_default(self) -> PoolSpecBuilder<(::planus::DefaultValue,)> {
+                self.data_format(::planus::DefaultValue)
+            }
+        }
+
+        impl<T0> PoolSpecBuilder<(T0,)> {
+            /// Setter for the [`kernel_shape` field](PoolSpec#structfield.kernel_shape).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn kernel_shape<T1>(self, value: T1) -> PoolSpecBuilder<(T0, T1)>
+            where
+                T1: ::planus::WriteAs<::planus::Offset<[u64]>>,
+            {
+                let (v0,) = self.0;
+                PoolSpecBuilder((v0, value))
+            }
+        }
+
+        impl<T0, T1> PoolSpecBuilder<(T0, T1)> {
+            /// Setter for the [`padding` field](PoolSpec#structfield.padding).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn padding<T2>(self, value: T2) -> PoolSpecBuilder<(T0, T1, T2)>
+            where
+                T2: ::planus::WriteAs<::planus::Offset<self::Padding>>,
+            {
+                let (v0, v1) = self.0;
+                PoolSpecBuilder((v0, v1, value))
+            }
+        }
+
+        impl<T0, T1, T2> PoolSpecBuilder<(T0, T1, T2)> {
+            /// Setter for the [`dilations` field](PoolSpec#structfield.dilations).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn dilations<T3>(self, value: T3) -> PoolSpecBuilder<(T0, T1, T2, T3)>
+            where
+                T3: ::planus::WriteAsOptional<::planus::Offset<[u64]>>,
+            {
+                let (v0, v1, v2) = self.0;
+                PoolSpecBuilder((v0, v1, v2, value))
+            }
+
+            /// Sets the [`dilations` field](PoolSpec#structfield.dilations) to null.
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn dilations_as_null(self) -> PoolSpecBuilder<(T0, T1, T2, ())> {
+                self.dilations(())
+            }
+        }
+
+        impl<T0, T1, T2, T3> PoolSpecBuilder<(T0, T1, T2, T3)> {
+            /// Setter for the [`strides` field](PoolSpec#structfield.strides).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn strides<T4>(self, value: T4) -> PoolSpecBuilder<(T0, T1, T2, T3, T4)>
+            where
+                T4: ::planus::WriteAsOptional<::planus::Offset<[u64]>>,
+            {
+                let (v0, v1, v2, v3) = self.0;
+                PoolSpecBuilder((v0, v1, v2, v3, value))
+            }
+
+            /// Sets the [`strides` field](PoolSpec#structfield.strides) to null.
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn strides_as_null(self) -> PoolSpecBuilder<(T0, T1, T2, T3, ())> {
+                self.strides(())
+            }
+        }
+
+        impl<T0, T1, T2, T3, T4> PoolSpecBuilder<(T0, T1, T2, T3, T4)> {
+            /// Setter for the [`output_channel_override` field](PoolSpec#structfield.output_channel_override).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn output_channel_override<T5>(
+                self,
+                value: T5,
+            ) -> PoolSpecBuilder<(T0, T1, T2, T3, T4, T5)>
+            where
+                T5: ::planus::WriteAsOptional<u64>,
+            {
+                let (v0, v1, v2, v3, v4) = self.0;
+                PoolSpecBuilder((v0, v1, v2, v3, v4, value))
+            }
+
+            /// Sets the [`output_channel_override` field](PoolSpec#structfield.output_channel_override) to null.
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn output_channel_override_as_null(
+                self,
+            ) -> PoolSpecBuilder<(T0, T1, T2, T3, T4, ())> {
+                self.output_channel_override(())
+            }
+        }
+
+        impl<T0, T1, T2, T3, T4, T5> PoolSpecBuilder<(T0, T1, T2, T3, T4, T5)> {
+            /// Finish writing the builder to get an [Offset](::planus::Offset) to a serialized [PoolSpec].
+            #[inline]
+            pub fn finish(self, builder: &mut ::planus::Builder) -> ::planus::Offset<PoolSpec>
+            where
+                Self: ::planus::WriteAsOffset<PoolSpec>,
+            {
+                ::planus::WriteAsOffset::prepare(&self, builder)
+            }
+        }
+
+        impl<
+                T0: ::planus::WriteAsDefault<self::DataFormat, self::DataFormat>,
+                T1: ::planus::WriteAs<::planus::Offset<[u64]>>,
+                T2: ::planus::WriteAs<::planus::Offset<self::Padding>>,
+                T3: ::planus::WriteAsOptional<::planus::Offset<[u64]>>,
+                T4: ::planus::WriteAsOptional<::planus::Offset<[u64]>>,
+                T5: ::planus::WriteAsOptional<u64>,
+            > ::planus::WriteAs<::planus::Offset<PoolSpec>>
+            for PoolSpecBuilder<(T0, T1, T2, T3, T4, T5)>
+        {
+            type Prepared = ::planus::Offset<PoolSpec>;
+
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<PoolSpec> {
+                ::planus::WriteAsOffset::prepare(self, builder)
+            }
+        }
+
+        impl<
+                T0: ::planus::WriteAsDefault<self::DataFormat, self::DataFormat>,
+                T1: ::planus::WriteAs<::planus::Offset<[u64]>>,
+                T2: ::planus::WriteAs<::planus::Offset<self::Padding>>,
+                T3: ::planus::WriteAsOptional<::planus::Offset<[u64]>>,
+                T4: ::planus::WriteAsOptional<::planus::Offset<[u64]>>,
+                T5: ::planus::WriteAsOptional<u64>,
+            > ::planus::WriteAsOptional<::planus::Offset<PoolSpec>>
+            for PoolSpecBuilder<(T0, T1, T2, T3, T4, T5)>
+        {
+            type Prepared = ::planus::Offset<PoolSpec>;
+
+            #[inline]
+            fn prepare(
+                &self,
+                builder: &mut ::planus::Builder,
+            ) -> ::core::option::Option<::planus::Offset<PoolSpec>> {
+                ::core::option::Option::Some(::planus::WriteAsOffset::prepare(self, builder))
+            }
+        }
+
+        impl<
+                T0: ::planus::WriteAsDefault<self::DataFormat, self::DataFormat>,
+                T1: ::planus::WriteAs<::planus::Offset<[u64]>>,
+                T2: ::planus::WriteAs<::planus::Offset<self::Padding>>,
+                T3: ::planus::WriteAsOptional<::planus::Offset<[u64]>>,
+                T4: ::planus::WriteAsOptional<::planus::Offset<[u64]>>,
+                T5: ::planus::WriteAsOptional<u64>,
+            > ::planus::WriteAsOffset<PoolSpec> for PoolSpecBuilder<(T0, T1, T2, T3, T4, T5)>
+        {
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<PoolSpec> {
+                let (v0, v1, v2, v3, v4, v5) = &self.0;
+                PoolSpec::create(builder, v0, v1, v2, v3, v4, v5)
+            }
+        }
+
+        /// Reference to a deserialized [PoolSpec].
+        #[derive(Copy, Clone)]
+        pub struct PoolSpecRef<'a>(#[allow(dead_code)] ::planus::table_reader::Table<'a>);
+
+        impl<'a> PoolSpecRef<'a> {
+            /// Getter for the [`data_format` field](PoolSpec#structfield.data_format).
+            #[inline]
+            pub fn data_format(&self) -> ::planus::Result<self::DataFormat> {
+                ::core::result::Result::Ok(
+                    self.0.access(0, "PoolSpec", "data_format")?.unwrap_or(self::DataFormat::Nchw),
+                )
+            }
+
+            /// Getter for the [`kernel_shape` field](PoolSpec#structfield.kernel_shape).
+            #[inline]
+            pub fn kernel_shape(&self) -> ::planus::Result<::planus::Vector<'a, u64>> {
+                self.0.access_required(1, "PoolSpec", "kernel_shape")
+            }
+
+            /// Getter for the [`padding` field](PoolSpec#structfield.padding).
+            #[inline]
+            pub fn padding(&self) -> ::planus::Result<self::PaddingRef<'a>> {
+                self.0.access_required(2, "PoolSpec", "padding")
+            }
+
+            /// Getter for the [`dilations` field](PoolSpec#structfield.dilations).
+            #[inline]
+            pub fn dilations(
+                &self,
+            ) -> ::planus::Result<::core::option::Option<::planus::Vector<'a, u64>>> {
+                self.0.access(3, "PoolSpec", "dilations")
+            }
+
+            /// Getter for the [`strides` field](PoolSpec#structfield.strides).
+            #[inline]
+            pub fn strides(
+                &self,
+            ) -> ::planus::Result<::core::option::Option<::planus::Vector<'a, u64>>> {
+                self.0.access(4, "PoolSpec", "strides")
+            }
+
+            /// Getter for the [`output_channel_override` field](PoolSpec#structfield.output_channel_override).
+            #[inline]
+            pub fn output_channel_override(&self) -> ::planus::Result<::core::option::Option<u64>> {
+                self.0.access(5, "PoolSpec", "output_channel_override")
+            }
+        }
+
+        impl<'a> ::core::fmt::Debug for PoolSpecRef<'a> {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                let mut f = f.debug_struct("PoolSpecRef");
+                f.field("data_format", &self.data_format());
+                f.field("kernel_shape", &self.kernel_shape());
+                f.field("padding", &self.padding());
+                if let ::core::option::Option::Some(field_dilations) = self.dilations().transpose()
+                {
+                    f.field("dilations", &field_dilations);
+                }
+                if let ::core::option::Option::Some(field_strides) = self.strides().transpose() {
+                    f.field("strides", &field_strides);
+                }
+                if let ::core::option::Option::Some(field_output_channel_override) =
+                    self.output_channel_override().transpose()
+                {
+                    f.field("output_channel_override", &field_output_channel_override);
+                }
+                f.finish()
+            }
+        }
+
+        impl<'a> ::core::convert::TryFrom<PoolSpecRef<'a>> for PoolSpec {
+            type Error = ::planus::Error;
+
+            #[allow(unreachable_code)]
+            fn try_from(value: PoolSpecRef<'a>) -> ::planus::Result<Self> {
+                ::core::result::Result::Ok(Self {
+                    data_format: ::core::convert::TryInto::try_into(value.data_format()?)?,
+                    kernel_shape: value.kernel_shape()?.to_vec()?,
+                    padding: ::planus::alloc::boxed::Box::new(::core::convert::TryInto::try_into(
+                        value.padding()?,
+                    )?),
+                    dilations: if let ::core::option::Option::Some(dilations) = value.dilations()? {
+                        ::core::option::Option::Some(dilations.to_vec()?)
+                    } else {
+                        ::core::option::Option::None
+                    },
+                    strides: if let ::core::option::Option::Some(strides) = value.strides()? {
+                        ::core::option::Option::Some(strides.to_vec()?)
+                    } else {
+                        ::core::option::Option::None
+                    },
+                    output_channel_override: if let ::core::option::Option::Some(
+                        output_channel_override,
+                    ) = value.output_channel_override()?
+                    {
+                        ::core::option::Option::Some(::core::convert::TryInto::try_into(
+                            output_channel_override,
+                        )?)
+                    } else {
+                        ::core::option::Option::None
+                    },
+                })
+            }
+        }
+
+        impl<'a> ::planus::TableRead<'a> for PoolSpecRef<'a> {
+            #[inline]
+            fn from_buffer(
+                buffer: ::planus::SliceWithStartOffset<'a>,
+                offset: usize,
+            ) -> ::core::result::Result<Self, ::planus::errors::ErrorKind> {
+                ::core::result::Result::Ok(Self(::planus::table_reader::Table::from_buffer(
+                    buffer, offset,
+                )?))
+            }
+        }
+
+        impl<'a> ::planus::VectorReadInner<'a> for PoolSpecRef<'a> {
+            type Error = ::planus::Error;
+            const STRIDE: usize = 4;
+
+            unsafe fn from_buffer(
+                buffer: ::planus::SliceWithStartOffset<'a>,
+                offset: usize,
+            ) -> ::planus::Result<Self> {
+                ::planus::TableRead::from_buffer(buffer, offset).map_err(|error_kind| {
+                    error_kind.with_error_location("[PoolSpecRef]", "get", buffer.offset_from_start)
+                })
+            }
+        }
+
+        /// # Safety
+        /// The planus compiler generates implementations that initialize
+        /// the bytes in `write_values`.
+        unsafe impl ::planus::VectorWrite<::planus::Offset<PoolSpec>> for PoolSpec {
+            type Value = ::planus::Offset<PoolSpec>;
+            const STRIDE: usize = 4;
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> Self::Value {
+                ::planus::WriteAs::prepare(self, builder)
+            }
+
+            #[inline]
+            unsafe fn write_values(
+                values: &[::planus::Offset<PoolSpec>],
+                bytes: *mut ::core::mem::MaybeUninit<u8>,
+                buffer_position: u32,
+            ) {
+                let bytes = bytes as *mut [::core::mem::MaybeUninit<u8>; 4];
+                for (i, v) in ::core::iter::Iterator::enumerate(values.iter()) {
+                    ::planus::WriteAsPrimitive::write(
+                        v,
+                        ::planus::Cursor::new(unsafe { &mut *bytes.add(i) }),
+                        buffer_position - (Self::STRIDE * i) as u32,
+                    );
+                }
+            }
+        }
+
+        impl<'a> ::planus::ReadAsRoot<'a> for PoolSpecRef<'a> {
+            fn read_as_root(slice: &'a [u8]) -> ::planus::Result<Self> {
+                ::planus::TableRead::from_buffer(
+                    ::planus::SliceWithStartOffset { buffer: slice, offset_from_start: 0 },
+                    0,
+                )
+                .map_err(|error_kind| {
+                    error_kind.with_error_location("[PoolSpecRef]", "read_as_root", 0)
+                })
+            }
+        }
+
+        /// The table `QParams` in the namespace `tract`
+        ///
+        /// Generated from these locations:
+        /// * Table `QParams` in the file `schema/tract.fbs:119`
+        #[derive(Clone, Debug, PartialEq, PartialOrd, ::serde::Serialize, ::serde::Deserialize)]
+        pub struct QParams {
+            /// The field `c_datum_type` in the table `QParams`
+            pub c_datum_type: self::DatumType,
+            /// The field `zero_point_a` in the table `QParams`
+            pub zero_point_a: ::core::option::Option<::planus::alloc::boxed::Box<self::Tensor>>,
+            /// The field `zero_point_b` in the table `QParams`
+            pub zero_point_b: ::core::option::Option<::planus::alloc::boxed::Box<self::Tensor>>,
+            /// The field `zero_point_c` in the table `QParams`
+            pub zero_point_c: ::core::option::Option<::planus::alloc::boxed::Box<self::Tensor>>,
+            /// The field `scale_factor` in the table `QParams`
+            pub scale_factor: ::core::option::Option<f32>,
+        }
+
+        #[allow(clippy::derivable_impls)]
+        impl ::core::default::Default for QParams {
+            fn default() -> Self {
+                Self {
+                    c_datum_type: self::DatumType::Bool,
+                    zero_point_a: ::core::default::Default::default(),
+                    zero_point_b: ::core::default::Default::default(),
+                    zero_point_c: ::core::default::Default::default(),
+                    scale_factor: ::core::default::Default::default(),
+                }
+            }
+        }
+
+        impl QParams {
+            /// Creates a [QParamsBuilder] for serializing an instance of this table.
+            #[inline]
+            pub fn builder() -> QParamsBuilder<()> {
+                QParamsBuilder(())
+            }
+
+            #[allow(clippy::too_many_arguments)]
+            pub fn create(
+                builder: &mut ::planus::Builder,
+                field_c_datum_type: impl ::planus::WriteAsDefault<self::DatumType, self::DatumType>,
+                field_zero_point_a: impl ::planus::WriteAsOptional<::planus::Offset<self::Tensor>>,
+                field_zero_point_b: impl ::planus::WriteAsOptional<::planus::Offset<self::Tensor>>,
+                field_zero_point_c: impl ::planus::WriteAsOptional<::planus::Offset<self::Tensor>>,
+                field_scale_factor: impl ::planus::WriteAsOptional<f32>,
+            ) -> ::planus::Offset<Self> {
+                let prepared_c_datum_type =
+                    field_c_datum_type.prepare(builder, &self::DatumType::Bool);
+                let prepared_zero_point_a = field_zero_point_a.prepare(builder);
+                let prepared_zero_point_b = field_zero_point_b.prepare(builder);
+                let prepared_zero_point_c = field_zero_point_c.prepare(builder);
+                let prepared_scale_factor = field_scale_factor.prepare(builder);
+
+                let mut table_writer: ::planus::table_writer::TableWriter<14> =
+                    ::core::default::Default::default();
+                if prepared_zero_point_a.is_some() {
+                    table_writer.write_entry::<::planus::Offset<self::Tensor>>(1);
+                }
+                if prepared_zero_point_b.is_some() {
+                    table_writer.write_entry::<::planus::Offset<self::Tensor>>(2);
+                }
+                if prepared_zero_point_c.is_some() {
+                    table_writer.write_entry::<::planus::Offset<self::Tensor>>(3);
+                }
+                if prepared_scale_factor.is_some() {
+                    table_writer.write_entry::<f32>(4);
+                }
+                if prepared_c_datum_type.is_some() {
+                    table_writer.write_entry::<self::DatumType>(0);
+                }
+
+                unsafe {
+                    table_writer.finish(builder, |object_writer| {
+                        if let ::core::option::Option::Some(prepared_zero_point_a) =
+                            prepared_zero_point_a
+                        {
+                            object_writer.write::<_, _, 4>(&prepared_zero_point_a);
+                        }
+                        if let ::core::option::Option::Some(prepared_zero_point_b) =
+                            prepared_zero_point_b
+                        {
+                            object_writer.write::<_, _, 4>(&prepared_zero_point_b);
+                        }
+                        if let ::core::option::Option::Some(prepared_zero_point_c) =
+                            prepared_zero_point_c
+                        {
+                            object_writer.write::<_, _, 4>(&prepared_zero_point_c);
+                        }
+                        if let ::core::option::Option::Some(prepared_scale_factor) =
+                            prepared_scale_factor
+                        {
+                            object_writer.write::<_, _, 4>(&prepared_scale_factor);
+                        }
+                        if let ::core::option::Option::Some(prepared_c_datum_type) =
+                            prepared_c_datum_type
+                        {
+                            object_writer.write::<_, _, 1>(&prepared_c_datum_type);
+                        }
+                    });
+                }
+                builder.current_offset()
+            }
+        }
+
+        impl ::planus::WriteAs<::planus::Offset<QParams>> for QParams {
+            type Prepared = ::planus::Offset<Self>;
+
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<QParams> {
+                ::planus::WriteAsOffset::prepare(self, builder)
+            }
+        }
+
+        impl ::planus::WriteAsOptional<::planus::Offset<QParams>> for QParams {
+            type Prepared = ::planus::Offset<Self>;
+
+            #[inline]
+            fn prepare(
+                &self,
+                builder: &mut ::planus::Builder,
+            ) -> ::core::option::Option<::planus::Offset<QParams>> {
+                ::core::option::Option::Some(::planus::WriteAsOffset::prepare(self, builder))
+            }
+        }
+
+        impl ::planus::WriteAsOffset<QParams> for QParams {
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<QParams> {
+                QParams::create(
+                    builder,
+                    self.c_datum_type,
+                    &self.zero_point_a,
+                    &self.zero_point_b,
+                    &self.zero_point_c,
+                    self.scale_factor,
+                )
+            }
+        }
+
+        /// Builder for serializing an instance of the [QParams] type.
+        ///
+        /// Can be created using the [QParams::builder] method.
+        #[derive(Debug)]
+        #[must_use]
+        pub struct QParamsBuilder<State>(State);
+
+        impl QParamsBuilder<()> {
+            /// Setter for the [`c_datum_type` field](QParams#structfield.c_datum_type).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn c_datum_type<T0>(self, value: T0) -> QParamsBuilder<(T0,)>
+            where
+                T0: ::planus::WriteAsDefault<self::DatumType, self::DatumType>,
+            {
+                QParamsBuilder((value,))
+            }
+
+            /// Sets the [`c_datum_type` field](QParams#structfield.c_datum_type) to the default value.
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn c_datum_type_as_default(self) -> QParamsBuilder<(::planus::DefaultValue,)> {
+                self.c_datum_type(::planus::DefaultValue)
+            }
+        }
+
+        impl<T0> QParamsBuilder<(T0,)> {
+            /// Setter for the [`zero_point_a` field](QParams#structfield.zero_point_a).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn zero_point_a<T1>(self, value: T1) -> QParamsBuilder<(T0, T1)>
+            where
+                T1: ::planus::WriteAsOptional<::planus::Offset<self::Tensor>>,
+            {
+                let (v0,) = self.0;
+                QParamsBuilder((v0, value))
+            }
+
+            /// Sets the [`zero_point_a` field](QParams#structfield.zero_point_a) to null.
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn zero_point_a_as_null(self) -> QParamsBuilder<(T0, ())> {
+                self.zero_point_a(())
+            }
+        }
+
+        impl<T0, T1> QParamsBuilder<(T0, T1)> {
+            /// Setter for the [`zero_point_b` field](QParams#structfield.zero_point_b).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn zero_point_b<T2>(self, value: T2) -> QParamsBuilder<(T0, T1, T2)>
+            where
+                T2: ::planus::WriteAsOptional<::planus::Offset<self::Tensor>>,
+            {
+                let (v0, v1) = self.0;
+                QParamsBuilder((v0, v1, value))
+            }
+
+            /// Sets the [`zero_point_b` field](QParams#structfield.zero_point_b) to null.
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn zero_point_b_as_null(self) -> QParamsBuilder<(T0, T1, ())> {
+                self.zero_point_b(())
+            }
+        }
+
+        impl<T0, T1, T2> QParamsBuilder<(T0, T1, T2)> {
+            /// Setter for the [`zero_point_c` field](QParams#structfield.zero_point_c).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn zero_point_c<T3>(self, value: T3) -> QParamsBuilder<(T0, T1, T2, T3)>
+            where
+                T3: ::planus::WriteAsOptional<::planus::Offset<self::Tensor>>,
+            {
+                let (v0, v1, v2) = self.0;
+                QParamsBuilder((v0, v1, v2, value))
+            }
+
+            /// Sets the [`zero_point_c` field](QParams#structfield.zero_point_c) to null.
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn zero_point_c_as_null(self) -> QParamsBuilder<(T0, T1, T2, ())> {
+                self.zero_point_c(())
+            }
+        }
+
+        impl<T0, T1, T2, T3> QParamsBuilder<(T0, T1, T2, T3)> {
+            /// Setter for the [`scale_factor` field](QParams#structfield.scale_factor).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn scale_factor<T4>(self, value: T4) -> QParamsBuilder<(T0, T1, T2, T3, T4)>
+            where
+                T4: ::planus::WriteAsOptional<f32>,
+            {
+                let (v0, v1, v2, v3) = self.0;
+                QParamsBuilder((v0, v1, v2, v3, value))
+            }
+
+            /// Sets the [`scale_factor` field](QParams#structfield.scale_factor) to null.
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn scale_factor_as_null(self) -> QParamsBuilder<(T0, T1, T2, T3, ())> {
+                self.scale_factor(())
+            }
+        }
+
+        impl<T0, T1, T2, T3, T4> QParamsBuilder<(T0, T1, T2, T3, T4)> {
+            /// Finish writing the builder to get an [Offset](::planus::Offset) to a serialized [QParams].
+            #[inline]
+            pub fn finish(self, builder: &mut ::planus::Builder) -> ::planus::Offset<QParams>
+            where
+                Self: ::planus::WriteAsOffset<QParams>,
+            {
+                ::planus::WriteAsOffset::prepare(&self, builder)
+            }
+        }
+
+        impl<
+                T0: ::planus::WriteAsDefault<self::DatumType, self::DatumType>,
+                T1: ::planus::WriteAsOptional<::planus::Offset<self::Tensor>>,
+                T2: ::planus::WriteAsOptional<::planus::Offset<self::Tensor>>,
+                T3: ::planus::WriteAsOptional<::planus::Offset<self::Tensor>>,
+                T4: ::planus::WriteAsOptional<f32>,
+            > ::planus::WriteAs<::planus::Offset<QParams>>
+            for QParamsBuilder<(T0, T1, T2, T3, T4)>
+        {
+            type Prepared = ::planus::Offset<QParams>;
+
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<QParams> {
+                ::planus::WriteAsOffset::prepare(self, builder)
+            }
+        }
+
+        impl<
+                T0: ::planus::WriteAsDefault<self::DatumType, self::DatumType>,
+                T1: ::planus::WriteAsOptional<::planus::Offset<self::Tensor>>,
+                T2: ::planus::WriteAsOptional<::planus::Offset<self::Tensor>>,
+                T3: ::planus::WriteAsOptional<::planus::Offset<self::Tensor>>,
+                T4: ::planus::WriteAsOptional<f32>,
+            > ::planus::WriteAsOptional<::planus::Offset<QParams>>
+            for QParamsBuilder<(T0, T1, T2, T3, T4)>
+        {
+            type Prepared = ::planus::Offset<QParams>;
+
+            #[inline]
+            fn prepare(
+                &self,
+                builder: &mut ::planus::Builder,
+            ) -> ::core::option::Option<::planus::Offset<QParams>> {
+                ::core::option::Option::Some(::planus::WriteAsOffset::prepare(self, builder))
+            }
+        }
+
+        impl<
+                T0: ::planus::WriteAsDefault<self::DatumType, self::DatumType>,
+                T1: ::planus::WriteAsOptional<::planus::Offset<self::Tensor>>,
+                T2: ::planus::WriteAsOptional<::planus::Offset<self::Tensor>>,
+                T3: ::planus::WriteAsOptional<::planus::Offset<self::Tensor>>,
+                T4: ::planus::WriteAsOptional<f32>,
+            > ::planus::WriteAsOffset<QParams> for QParamsBuilder<(T0, T1, T2, T3, T4)>
+        {
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<QParams> {
+                let (v0, v1, v2, v3, v4) = &self.0;
+                QParams::create(builder, v0, v1, v2, v3, v4)
+            }
+        }
+
+        /// Reference to a deserialized [QParams].
+        #[derive(Copy, Clone)]
+        pub struct QParamsRef<'a>(#[allow(dead_code)] ::planus::table_reader::Table<'a>);
+
+        impl<'a> QParamsRef<'a> {
+            /// Getter for the [`c_datum_type` field](QParams#structfield.c_datum_type).
+            #[inline]
+            pub fn c_datum_type(&self) -> ::planus::Result<self::DatumType> {
+                ::core::result::Result::Ok(
+                    self.0.access(0, "QParams", "c_datum_type")?.unwrap_or(self::DatumType::Bool),
+                )
+            }
+
+            /// Getter for the [`zero_point_a` field](QParams#structfield.zero_point_a).
+            #[inline]
+            pub fn zero_point_a(
+                &self,
+            ) -> ::planus::Result<::core::option::Option<self::TensorRef<'a>>> {
+                self.0.access(1, "QParams", "zero_point_a")
+            }
+
+            /// Getter for the [`zero_point_b` field](QParams#structfield.zero_point_b).
+            #[inline]
+            pub fn zero_point_b(
+                &self,
+            ) -> ::planus::Result<::core::option::Option<self::TensorRef<'a>>> {
+                self.0.access(2, "QParams", "zero_point_b")
+            }
+
+            /// Getter for the [`zero_point_c` field](QParams#structfield.zero_point_c).
+            #[inline]
+            pub fn zero_point_c(
+                &self,
+            ) -> ::planus::Result<::core::option::Option<self::TensorRef<'a>>> {
+                self.0.access(3, "QParams", "zero_point_c")
+            }
+
+            /// Getter for the [`scale_factor` field](QParams#structfield.scale_factor).
+            #[inline]
+            pub fn scale_factor(&self) -> ::planus::Result<::core::option::Option<f32>> {
+                self.0.access(4, "QParams", "scale_factor")
+            }
+        }
+
+        impl<'a> ::core::fmt::Debug for QParamsRef<'a> {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                let mut f = f.debug_struct("QParamsRef");
+                f.field("c_datum_type", &self.c_datum_type());
+                if let ::core::option::Option::Some(field_zero_point_a) =
+                    self.zero_point_a().transpose()
+                {
+                    f.field("zero_point_a", &field_zero_point_a);
+                }
+                if let ::core::option::Option::Some(field_zero_point_b) =
+                    self.zero_point_b().transpose()
+                {
+                    f.field("zero_point_b", &field_zero_point_b);
+                }
+                if let ::core::option::Option::Some(field_zero_point_c) =
+                    self.zero_point_c().transpose()
+                {
+                    f.field("zero_point_c", &field_zero_point_c);
+                }
+                if let ::core::option::Option::Some(field_scale_factor) =
+                    self.scale_factor().transpose()
+                {
+                    f.field("scale_factor", &field_scale_factor);
+                }
+                f.finish()
+            }
+        }
+
+        impl<'a> ::core::convert::TryFrom<QParamsRef<'a>> for QParams {
+            type Error = ::planus::Error;
+
+            #[allow(unreachable_code)]
+            fn try_from(value: QParamsRef<'a>) -> ::planus::Result<Self> {
+                ::core::result::Result::Ok(Self {
+                    c_datum_type: ::core::convert::TryInto::try_into(value.c_datum_type()?)?,
+                    zero_point_a: if let ::core::option::Option::Some(zero_point_a) =
+                        value.zero_point_a()?
+                    {
+                        ::core::option::Option::Some(::planus::alloc::boxed::Box::new(
+                            ::core::convert::TryInto::try_into(zero_point_a)?,
+                        ))
+                    } else {
+                        ::core::option::Option::None
+                    },
+                    zero_point_b: if let ::core::option::Option::Some(zero_point_b) =
+                        value.zero_point_b()?
+                    {
+                        ::core::option::Option::Some(::planus::alloc::boxed::Box::new(
+                            ::core::convert::TryInto::try_into(zero_point_b)?,
+                        ))
+                    } else {
+                        ::core::option::Option::None
+                    },
+                    zero_point_c: if let ::core::option::Option::Some(zero_point_c) =
+                        value.zero_point_c()?
+                    {
+                        ::core::option::Option::Some(::planus::alloc::boxed::Box::new(
+                            ::core::convert::TryInto::try_into(zero_point_c)?,
+                        ))
+                    } else {
+                        ::core::option::Option::None
+                    },
+                    scale_factor: if let ::core::option::Option::Some(scale_factor) =
+                        value.scale_factor()?
+                    {
+                        ::core::option::Option::Some(::core::convert::TryInto::try_into(
+                            scale_factor,
+                        )?)
+                    } else {
+                        ::core::option::Option::None
+                    },
+                })
+            }
+        }
+
+        impl<'a> ::planus::TableRead<'a> for QParamsRef<'a> {
+            #[inline]
+            fn from_buffer(
+                buffer: ::planus::SliceWithStartOffset<'a>,
+                offset: usize,
+            ) -> ::core::result::Result<Self, ::planus::errors::ErrorKind> {
+                ::core::result::Result::Ok(Self(::planus::table_reader::Table::from_buffer(
+                    buffer, offset,
+                )?))
+            }
+        }
+
+        impl<'a> ::planus::VectorReadInner<'a> for QParamsRef<'a> {
+            type Error = ::planus::Error;
+            const STRIDE: usize = 4;
+
+            unsafe fn from_buffer(
+                buffer: ::planus::SliceWithStartOffset<'a>,
+                offset: usize,
+            ) -> ::planus::Result<Self> {
+                ::planus::TableRead::from_buffer(buffer, offset).map_err(|error_kind| {
+                    error_kind.with_error_location("[QParamsRef]", "get", buffer.offset_from_start)
+                })
+            }
+        }
+
+        /// # Safety
+        /// The planus compiler generates implementations that initialize
+        /// the bytes in `write_values`.
+        unsafe impl ::planus::VectorWrite<::planus::Offset<QParams>> for QParams {
+            type Value = ::planus::Offset<QParams>;
+            const STRIDE: usize = 4;
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> Self::Value {
+                ::planus::WriteAs::prepare(self, builder)
+            }
+
+            #[inline]
+            unsafe fn write_values(
+                values: &[::planus::Offset<QParams>],
+                bytes: *mut ::core::mem::MaybeUninit<u8>,
+                buffer_position: u32,
+            ) {
+                let bytes = bytes as *mut [::core::mem::MaybeUninit<u8>; 4];
+                for (i, v) in ::core::iter::Iterator::enumerate(values.iter()) {
+                    ::planus::WriteAsPrimitive::write(
+                        v,
+                        ::planus::Cursor::new(unsafe { &mut *bytes.add(i) }),
+                        buffer_position - (Self::STRIDE * i) as u32,
+                    );
+                }
+            }
+        }
+
+        impl<'a> ::planus::ReadAsRoot<'a> for QParamsRef<'a> {
+            fn read_as_root(slice: &'a [u8]) -> ::planus::Result<Self> {
+                ::planus::TableRead::from_buffer(
+                    ::planus::SliceWithStartOffset { buffer: slice, offset_from_start: 0 },
+                    0,
+                )
+                .map_err(|error_kind| {
+                    error_kind.with_error_location("[QParamsRef]", "read_as_root", 0)
+                })
+            }
+        }
+
+        /// The enum `KernelFormat` in the namespace `tract`
+        ///
+        /// Generated from these locations:
+        /// * Enum `KernelFormat` in the file `schema/tract.fbs:127`
+        #[derive(
+            Copy,
+            Clone,
+            Debug,
+            PartialEq,
+            Eq,
+            PartialOrd,
+            Ord,
+            Hash,
+            ::serde::Serialize,
+            ::serde::Deserialize,
+        )]
+        #[repr(u8)]
+        pub enum KernelFormat {
+            /// The variant `OIHW` in the enum `KernelFormat`
+            Oihw = 0,
+
+            /// The variant `HWIO` in the enum `KernelFormat`
+            Hwio = 1,
+        }
+
+        impl KernelFormat {
+            /// Array containing all valid variants of KernelFormat
+            pub const ENUM_VALUES: [Self; 2] = [Self::Oihw, Self::Hwio];
+        }
+
+        impl ::core::convert::TryFrom<u8> for KernelFormat {
+            type Error = ::planus::errors::UnknownEnumTagKind;
+            #[inline]
+            fn try_from(
+                value: u8,
+            ) -> ::core::result::Result<Self, ::planus::errors::UnknownEnumTagKind> {
+                #[allow(clippy::match_single_binding)]
+                match value {
+                    0 => ::core::result::Result::Ok(KernelFormat::Oihw),
+                    1 => ::core::result::Result::Ok(KernelFormat::Hwio),
+
+                    _ => ::core::result::Result::Err(::planus::errors::UnknownEnumTagKind {
+                        tag: value as i128,
+                    }),
+                }
+            }
+        }
+
+        impl ::core::convert::From<KernelFormat> for u8 {
+            #[inline]
+            fn from(value: KernelFormat) -> Self {
+                value as u8
+            }
+        }
+
+        /// # Safety
+        /// The Planus compiler correctly calculates `ALIGNMENT` and `SIZE`.
+        unsafe impl ::planus::Primitive for KernelFormat {
+            const ALIGNMENT: usize = 1;
+            const SIZE: usize = 1;
+        }
+
+        impl ::planus::WriteAsPrimitive<KernelFormat> for KernelFormat {
+            #[inline]
+            fn write<const N: usize>(&self, cursor: ::planus::Cursor<'_, N>, buffer_position: u32) {
+                (*self as u8).write(cursor, buffer_position);
+            }
+        }
+
+        impl ::planus::WriteAs<KernelFormat> for KernelFormat {
+            type Prepared = Self;
+
+            #[inline]
+            fn prepare(&self, _builder: &mut ::planus::Builder) -> KernelFormat {
+                *self
+            }
+        }
+
+        impl ::planus::WriteAsDefault<KernelFormat, KernelFormat> for KernelFormat {
+            type Prepared = Self;
+
+            #[inline]
+            fn prepare(
+                &self,
+                _builder: &mut ::planus::Builder,
+                default: &KernelFormat,
+            ) -> ::core::option::Option<KernelFormat> {
+                if self == default {
+                    ::core::option::Option::None
+                } else {
+                    ::core::option::Option::Some(*self)
+                }
+            }
+        }
+
+        impl ::planus::WriteAsOptional<KernelFormat> for KernelFormat {
+            type Prepared = Self;
+
+            #[inline]
+            fn prepare(
+                &self,
+                _builder: &mut ::planus::Builder,
+            ) -> ::core::option::Option<KernelFormat> {
+                ::core::option::Option::Some(*self)
+            }
+        }
+
+        impl<'buf> ::planus::TableRead<'buf> for KernelFormat {
+            #[inline]
+            fn from_buffer(
+                buffer: ::planus::SliceWithStartOffset<'buf>,
+                offset: usize,
+            ) -> ::core::result::Result<Self, ::planus::errors::ErrorKind> {
+                let n: u8 = ::planus::TableRead::from_buffer(buffer, offset)?;
+                ::core::result::Result::Ok(::core::convert::TryInto::try_into(n)?)
+            }
+        }
+
+        impl<'buf> ::planus::VectorReadInner<'buf> for KernelFormat {
+            type Error = ::planus::errors::UnknownEnumTag;
+            const STRIDE: usize = 1;
+            #[inline]
+            unsafe fn from_buffer(
+                buffer: ::planus::SliceWithStartOffset<'buf>,
+                offset: usize,
+            ) -> ::core::result::Result<Self, ::planus::errors::UnknownEnumTag> {
+                let value = unsafe { *buffer.buffer.get_unchecked(offset) };
+                let value: ::core::result::Result<Self, _> =
+                    ::core::convert::TryInto::try_into(value);
+                value.map_err(|error_kind| {
+                    error_kind.with_error_location(
+                        "KernelFormat",
+                        "VectorRead::from_buffer",
+                        buffer.offset_from_start,
+                    )
+                })
+            }
+        }
+
+        /// # Safety
+        /// The planus compiler generates implementations that initialize
+        /// the bytes in `write_values`.
+        unsafe impl ::planus::VectorWrite<KernelFormat> for KernelFormat {
+            const STRIDE: usize = 1;
+
+            type Value = Self;
+
+            #[inline]
+            fn prepare(&self, _builder: &mut ::planus::Builder) -> Self {
+                *self
+            }
+
+            #[inline]
+            unsafe fn write_values(
+                values: &[Self],
+                bytes: *mut ::core::mem::MaybeUninit<u8>,
+                buffer_position: u32,
+            ) {
+                let bytes = bytes as *mut [::core::mem::MaybeUninit<u8>; 1];
+                for (i, v) in ::core::iter::Iterator::enumerate(values.iter()) {
+                    ::planus::WriteAsPrimitive::write(
+                        v,
+                        ::planus::Cursor::new(unsafe { &mut *bytes.add(i) }),
+                        buffer_position - i as u32,
+                    );
+                }
+            }
+        }
+
+        /// The enum `ConvAlgorithm` in the namespace `tract`
+        ///
+        /// Generated from these locations:
+        /// * Enum `ConvAlgorithm` in the file `schema/tract.fbs:132`
+        #[derive(
+            Copy,
+            Clone,
+            Debug,
+            PartialEq,
+            Eq,
+            PartialOrd,
+            Ord,
+            Hash,
+            ::serde::Serialize,
+            ::serde::Deserialize,
+        )]
+        #[repr(u8)]
+        pub enum ConvAlgorithm {
+            /// The variant `Auto` in the enum `ConvAlgorithm`
+            Auto = 0,
+
+            /// The variant `Gemm` in the enum `ConvAlgorithm`
+            Gemm = 1,
+
+            /// The variant `Winograd` in the enum `ConvAlgorithm`
+            Winograd = 2,
+
+            /// The variant `Fft` in the enum `ConvAlgorithm`
+            Fft = 3,
+        }
+
+        impl ConvAlgorithm {
+            /// Array containing all valid variants of ConvAlgorithm
+            pub const ENUM_VALUES: [Self; 4] = [Self::Auto, Self::Gemm, Self::Winograd, Self::Fft];
+        }
+
+        impl ::core::convert::TryFrom<u8> for ConvAlgorithm {
+            type Error = ::planus::errors::UnknownEnumTagKind;
+            #[inline]
+            fn try_from(
+                value: u8,
+            ) -> ::core::result::Result<Self, ::planus::errors::UnknownEnumTagKind> {
+                #[allow(clippy::match_single_binding)]
+                match value {
+                    0 => ::core::result::Result::Ok(ConvAlgorithm::Auto),
+                    1 => ::core::result::Result::Ok(ConvAlgorithm::Gemm),
+                    2 => ::core::result::Result::Ok(ConvAlgorithm::Winograd),
+                    3 => ::core::result::Result::Ok(ConvAlgorithm::Fft),
+
+                    _ => ::core::result::Result::Err(::planus::errors::UnknownEnumTagKind {
+                        tag: value as i128,
+                    }),
+                }
+            }
+        }
+
+        impl ::core::convert::From<ConvAlgorithm> for u8 {
+            #[inline]
+            fn from(value: ConvAlgorithm) -> Self {
+                value as u8
+            }
+        }
+
+        /// # Safety
+        /// The Planus compiler correctly calculates `ALIGNMENT` and `SIZE`.
+        unsafe impl ::planus::Primitive for ConvAlgorithm {
+            const ALIGNMENT: usize = 1;
+            const SIZE: usize = 1;
+        }
+
+        impl ::planus::WriteAsPrimitive<ConvAlgorithm> for ConvAlgorithm {
+            #[inline]
+            fn write<const N: usize>(&self, cursor: ::planus::Cursor<'_, N>, buffer_position: u32) {
+                (*self as u8).write(cursor, buffer_position);
+            }
+        }
+
+        impl ::planus::WriteAs<ConvAlgorithm> for ConvAlgorithm {
+            type Prepared = Self;
+
+            #[inline]
+            fn prepare(&self, _builder: &mut ::planus::Builder) -> ConvAlgorithm {
+                *self
+            }
+        }
+
+        impl ::planus::WriteAsDefault<ConvAlgorithm, ConvAlgorithm> for ConvAlgorithm {
+            type Prepared = Self;
+
+            #[inline]
+            fn prepare(
+                &self,
+                _builder: &mut ::planus::Builder,
+                default: &ConvAlgorithm,
+            ) -> ::core::option::Option<ConvAlgorithm> {
+                if self == default {
+                    ::core::option::Option::None
+                } else {
+                    ::core::option::Option::Some(*self)
+                }
+            }
+        }
+
+        impl ::planus::WriteAsOptional<ConvAlgorithm> for ConvAlgorithm {
+            type Prepared = Self;
+
+            #[inline]
+            fn prepare(
+                &self,
+                _builder: &mut ::planus::Builder,
+            ) -> ::core::option::Option<ConvAlgorithm> {
+                ::core::option::Option::Some(*self)
+            }
+        }
+
+        impl<'buf> ::planus::TableRead<'buf> for ConvAlgorithm {
+            #[inline]
+            fn from_buffer(
+                buffer: ::planus::SliceWithStartOffset<'buf>,
+                offset: usize,
+            ) -> ::core::result::Result<Self, ::planus::errors::ErrorKind> {
+                let n: u8 = ::planus::TableRead::from_buffer(buffer, offset)?;
+                ::core::result::Result::Ok(::core::convert::TryInto::try_into(n)?)
+            }
+        }
+
+        impl<'buf> ::planus::VectorReadInner<'buf> for ConvAlgorithm {
+            type Error = ::planus::errors::UnknownEnumTag;
+            const STRIDE: usize = 1;
+            #[inline]
+            unsafe fn from_buffer(
+                buffer: ::planus::SliceWithStartOffset<'buf>,
+                offset: usize,
+            ) -> ::core::result::Result<Self, ::planus::errors::UnknownEnumTag> {
+                let value = unsafe { *buffer.buffer.get_unchecked(offset) };
+                let value: ::core::result::Result<Self, _> =
+                    ::core::convert::TryInto::try_into(value);
+                value.map_err(|error_kind| {
+                    error_kind.with_error_location(
+                        "ConvAlgorithm",
+                        "VectorRead::from_buffer",
+                        buffer.offset_from_start,
+                    )
+                })
+            }
+        }
+
+        /// # Safety
+        /// The planus compiler generates implementations that initialize
+        /// the bytes in `write_values`.
+        unsafe impl ::planus::VectorWrite<ConvAlgorithm> for ConvAlgorithm {
+            const STRIDE: usize = 1;
+
+            type Value = Self;
+
+            #[inline]
+            fn prepare(&self, _builder: &mut ::planus::Builder) -> Self {
+                *self
+            }
+
+            #[inline]
+            unsafe fn write_values(
+                values: &[Self],
+                bytes: *mut ::core::mem::MaybeUninit<u8>,
+                buffer_position: u32,
+            ) {
+                let bytes = bytes as *mut [::core::mem::MaybeUninit<u8>; 1];
+                for (i, v) in ::core::iter::Iterator::enumerate(values.iter()) {
+                    ::planus::WriteAsPrimitive::write(
+                        v,
+                        ::planus::Cursor::new(unsafe { &mut *bytes.add(i) }),
+                        buffer_position - i as u32,
+                    );
+                }
+            }
+        }
+
+        /// The table `Conv` in the namespace `tract`
+        ///
+        /// Generated from these locations:
+        /// * Table `Conv` in the file `schema/tract.fbs:139`
+        #[derive(Clone, Debug, PartialEq, PartialOrd, ::serde::Serialize, ::serde::Deserialize)]
+        pub struct Conv {
+            /// The field `pool_spec` in the table `Conv`
+            pub pool_spec: ::planus::alloc::boxed::Box<self::PoolSpec>,
+            /// The field `kernel_format` in the table `Conv`
+            pub kernel_format: self::KernelFormat,
+            /// The field `kernel` in the table `Conv`
+            pub kernel: ::planus::alloc::boxed::Box<self::Tensor>,
+            /// The field `group` in the table `Conv`
+            pub group: u64,
+            /// The field `bias` in the table `Conv`
+            pub bias: ::core::option::Option<::planus::alloc::boxed::Box<self::Tensor>>,
+            /// The field `q_params` in the table `Conv`
+            pub q_params: ::core::option::Option<::planus::alloc::boxed::Box<self::QParams>>,
+            /// The field `algorithm` in the table `Conv`
+            pub algorithm: self::ConvAlgorithm,
+        }
+
+        #[allow(clippy::derivable_impls)]
+        impl ::core::default::Default for Conv {
+            fn default() -> Self {
+                Self {
+                    pool_spec: ::core::default::Default::default(),
+                    kernel_format: self::KernelFormat::Oihw,
+                    kernel: ::core::default::Default::default(),
+                    group: 0,
+                    bias: ::core::default::Default::default(),
+                    q_params: ::core::default::Default::default(),
+                    algorithm: self::ConvAlgorithm::Auto,
+                }
+            }
+        }
+
+        impl Conv {
+            /// Creates a [ConvBuilder] for serializing an instance of this table.
+            #[inline]
+            pub fn builder() -> ConvBuilder<()> {
+                ConvBuilder(())
+            }
+
+            #[allow(clippy::too_many_arguments)]
+            pub fn create(
+                builder: &mut ::planus::Builder,
+                field_pool_spec: impl ::planus::WriteAs<::planus::Offset<self::PoolSpec>>,
+                field_kernel_format: impl ::planus::WriteAsDefault<
+                    self::KernelFormat,
+                    self::KernelFormat,
+                >,
+                field_kernel: impl ::planus::WriteAs<::planus::Offset<self::Tensor>>,
+                field_group: impl ::planus::WriteAsDefault<u64, u64>,
+                field_bias: impl ::planus::WriteAsOptional<::planus::Offset<self::Tensor>>,
+                field_q_params: impl ::planus::WriteAsOptional<::planus::Offset<self::QParams>>,
+                field_algorithm: impl ::planus::WriteAsDefault<self::ConvAlgorithm, self::ConvAlgorithm>,
+            ) -> ::planus::Offset<Self> {
+                let prepared_pool_spec = field_pool_spec.prepare(builder);
+                let prepared_kernel_format =
+                    field_kernel_format.prepare(builder, &self::KernelFormat::Oihw);
+                let prepared_kernel = field_kernel.prepare(builder);
+                let prepared_group = field_group.prepare(builder, &0);
+                let prepared_bias = field_bias.prepare(builder);
+                let prepared_q_params = field_q_params.prepare(builder);
+                let prepared_algorithm =
+                    field_algorithm.prepare(builder, &self::ConvAlgorithm::Auto);
+
+                let mut table_writer: ::planus::table_writer::TableWriter<18> =
+                    ::core::default::Default::default();
+                if prepared_group.is_some() {
+                    table_writer.write_entry::<u64>(3);
+                }
+                table_writer.write_entry::<::planus::Offset<self::PoolSpec>>(0);
+                table_writer.write_entry::<::planus::Offset<self::Tensor>>(2);
+                if prepared_bias.is_some() {
+                    table_writer.write_entry::<::planus::Offset<self::Tensor>>(4);
+                }
+                if prepared_q_params.is_some() {
+                    table_writer.write_entry::<::planus::Offset<self::QParams>>(5);
+                }
+                if prepared_kernel_format.is_some() {
+                    table_writer.write_entry::<self::KernelFormat>(1);
+                }
+                if prepared_algorithm.is_some() {
+                    table_writer.write_entry::<self::ConvAlgorithm>(6);
+                }
+
+                unsafe {
+                    table_writer.finish(builder, |object_writer| {
+                        if let ::core::option::Option::Some(prepared_group) = prepared_group {
+                            object_writer.write::<_, _, 8>(&prepared_group);
+                        }
+                        object_writer.write::<_, _, 4>(&prepared_pool_spec);
+                        object_writer.write::<_, _, 4>(&prepared_kernel);
+                        if let ::core::option::Option::Some(prepared_bias) = prepared_bias {
+                            object_writer.write::<_, _, 4>(&prepared_bias);
+                        }
+                        if let ::core::option::Option::Some(prepared_q_params) = prepared_q_params {
+                            object_writer.write::<_, _, 4>(&prepared_q_params);
+                        }
+                        if let ::core::option::Option::Some(prepared_kernel_format) =
+                            prepared_kernel_format
+                        {
+                            object_writer.write::<_, _, 1>(&prepared_kernel_format);
+                        }
+                        if let ::core::option::Option::Some(prepared_algorithm) = prepared_algorithm
+                        {
+                            object_writer.write::<_, _, 1>(&prepared_algorithm);
+                        }
+                    });
+                }
+                builder.current_offset()
+            }
+        }
+
+        impl ::planus::WriteAs<::planus::Offset<Conv>> for Conv {
+            type Prepared = ::planus::Offset<Self>;
+
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<Conv> {
+                ::planus::WriteAsOffset::prepare(self, builder)
+            }
+        }
+
+        impl ::planus::WriteAsOptional<::planus::Offset<Conv>> for Conv {
+            type Prepared = ::planus::Offset<Self>;
+
+            #[inline]
+            fn prepare(
+                &self,
+                builder: &mut ::planus::Builder,
+            ) -> ::core::option::Option<::planus::Offset<Conv>> {
+                ::core::option::Option::Some(::planus::WriteAsOffset::prepare(self, builder))
+            }
+        }
+
+        impl ::planus::WriteAsOffset<Conv> for Conv {
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<Conv> {
+                Conv::create(
+                    builder,
+                    &self.pool_spec,
+                    self.kernel_format,
+                    &self.kernel,
+                    self.group,
+                    &self.bias,
+                    &self.q_params,
+                    self.algorithm,
+                )
+            }
+        }
+
+        /// Builder for serializing an instance of the [Conv] type.
+        ///
+        /// Can be created using the [Conv::builder] method.
+        #[derive(Debug)]
+        #[must_use]
+        pub struct ConvBuilder<State>(State);
+
+        impl ConvBuilder<()> {
+            /// Setter for the [`pool_spec` field](Conv#structfield.pool_spec).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn pool_spec<T0>(self, value: T0) -> ConvBuilder<(T0,)>
+            where
+                T0: ::planus::WriteAs<::planus::Offset<self::PoolSpec>>,
+            {
+                ConvBuilder((value,))
+            }
+        }
+
+        impl<T0> ConvBuilder<(T0,)> {
+            /// Setter for the [`kernel_format` field](Conv#structfield.kernel_format).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn kernel_format<T1>(self, value: T1) -> ConvBuilder<(T0, T1)>
+            where
+                T1: ::planus::WriteAsDefault<self::KernelFormat, self::KernelFormat>,
+            {
+                let (v0,) = self.0;
+                ConvBuilder((v0, value))
+            }
+
+            /// Sets the [`kernel_format` field](Conv#structfield.kernel_format) to the default value.
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn kernel_format_as_default(self) -> ConvBuilder<(T0, ::planus::DefaultValue)> {
+                self.kernel_format(::planus::DefaultValue)
+            }
+        }
+
+        impl<T0, T1> ConvBuilder<(T0, T1)> {
+            /// Setter for the [`kernel` field](Conv#structfield.kernel).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn kernel<T2>(self, value: T2) -> ConvBuilder<(T0, T1, T2)>
+            where
+                T2: ::planus::WriteAs<::planus::Offset<self::Tensor>>,
+            {
+                let (v0, v1) = self.0;
+                ConvBuilder((v0, v1, value))
+            }
+        }
+
+        impl<T0, T1, T2> ConvBuilder<(T0, T1, T2)> {
+            /// Setter for the [`group` field](Conv#structfield.group).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn group<T3>(self, value: T3) -> ConvBuilder<(T0, T1, T2, T3)>
+            where
+                T3: ::planus::WriteAsDefault<u64, u64>,
+            {
+                let (v0, v1, v2) = self.0;
+                ConvBuilder((v0, v1, v2, value))
+            }
+
+            /// Sets the [`group` field](Conv#structfield.group) to the default value.
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn group_as_default(self) -> ConvBuilder<(T0, T1, T2, ::planus::DefaultValue)> {
+                self.group(::planus::DefaultValue)
+            }
+        }
+
+        impl<T0, T1, T2, T3> ConvBuilder<(T0, T1, T2, T3)> {
+            /// Setter for the [`bias` field](Conv#structfield.bias).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn bias<T4>(self, value: T4) -> ConvBuilder<(T0, T1, T2, T3, T4)>
+            where
+                T4: ::planus::WriteAsOptional<::planus::Offset<self::Tensor>>,
+            {
+                let (v0, v1, v2, v3) = self.0;
+                ConvBuilder((v0, v1, v2, v3, value))
+            }
+
+            /// Sets the [`bias` field](Conv#structfield.bias) to null.
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn bias_as_null(self) -> ConvBuilder<(T0, T1, T2, T3, ())> {
+                self.bias(())
+            }
+        }
+
+        impl<T0, T1, T2, T3, T4> ConvBuilder<(T0, T1, T2, T3, T4)> {
+            /// Setter for the [`q_params` field](Conv#structfield.q_params).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn q_params<T5>(self, value: T5) -> ConvBuilder<(T0, T1, T2, T3, T4, T5)>
+            where
+                T5: ::planus::WriteAsOptional<::planus::Offset<self::QParams>>,
+            {
+                let (v0, v1, v2, v3, v4) = self.0;
+                ConvBuilder((v0, v1, v2, v3, v4, value))
+            }
+
+            /// Sets the [`q_params` field](Conv#structfield.q_params) to null.
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn q_params_as_null(self) -> ConvBuilder<(T0, T1, T2, T3, T4, ())> {
+                self.q_params(())
+            }
+        }
+
+        impl<T0, T1, T2, T3, T4, T5> ConvBuilder<(T0, T1, T2, T3, T4, T5)> {
+            /// Setter for the [`algorithm` field](Conv#structfield.algorithm).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn algorithm<T6>(self, value: T6) -> ConvBuilder<(T0, T1, T2, T3, T4, T5, T6)>
+            where
+                T6: ::planus::WriteAsDefault<self::ConvAlgorithm, self::ConvAlgorithm>,
+            {
+                let (v0, v1, v2, v3, v4, v5) = self.0;
+                ConvBuilder((v0, v1, v2, v3, v4, v5, value))
+            }
+
+            /// Sets the [`algorithm` field](Conv#structfield.algorithm) to the default value.
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn algorithm_as_default(
+                self,
+            ) -> ConvBuilder<(T0, T1, T2, T3, T4, T5, ::planus::DefaultValue)> {
+                self.algorithm(::planus::DefaultValue)
+            }
+        }
+
+        impl<T0, T1, T2, T3, T4, T5, T6> ConvBuilder<(T0, T1, T2, T3, T4, T5, T6)> {
+            /// Finish writing the builder to get an [Offset](::planus::Offset) to a serialized [Conv].
+            #[inline]
+            pub fn finish(self, builder: &mut ::planus::Builder) -> ::planus::Offset<Conv>
+            where
+                Self: ::planus::WriteAsOffset<Conv>,
+            {
+                ::planus::WriteAsOffset::prepare(&self, builder)
+            }
+        }
+
+        impl<
+                T0: ::planus::WriteAs<::planus::Offset<self::PoolSpec>>,
+                T1: ::planus::WriteAsDefault<self::KernelFormat, self::KernelFormat>,
+                T2: ::planus::WriteAs<::planus::Offset<self::Tensor>>,
+                T3: ::planus::WriteAsDefault<u64, u64>,
+                T4: ::planus::WriteAsOptional<::planus::Offset<self::Tensor>>,
+                T5: ::planus::WriteAsOptional<::planus::Offset<self::QParams>>,
+                T6: ::planus::WriteAsDefault<self::ConvAlgorithm, self::ConvAlgorithm>,
+            > ::planus::WriteAs<::planus::Offset<Conv>>
+            for ConvBuilder<(T0, T1, T2, T3, T4, T5, T6)>
+        {
+            type Prepared = ::planus::Offset<Conv>;
+
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<Conv> {
+                ::planus::WriteAsOffset::prepare(self, builder)
+            }
+        }
+
+        impl<
+                T0: ::planus::WriteAs<::planus::Offset<self::PoolSpec>>,
+                T1: ::planus::WriteAsDefault<self::KernelFormat, self::KernelFormat>,
+                T2: ::planus::WriteAs<::planus::Offset<self::Tensor>>,
+                T3: ::planus::WriteAsDefault<u64, u64>,
+                T4: ::planus::WriteAsOptional<::planus::Offset<self::Tensor>>,
+                T5: ::planus::WriteAsOptional<::planus::Offset<self::QParams>>,
+                T6: ::planus::WriteAsDefault<self::ConvAlgorithm, self::ConvAlgorithm>,
+            > ::planus::WriteAsOptional<::planus::Offset<Conv>>
+            for ConvBuilder<(T0, T1, T2, T3, T4, T5, T6)>
+        {
+            type Prepared = ::planus::Offset<Conv>;
+
+            #[inline]
+            fn prepare(
+                &self,
+                builder: &mut ::planus::Builder,
+            ) -> ::core::option::Option<::planus::Offset<Conv>> {
+                ::core::option::Option::Some(::planus::WriteAsOffset::prepare(self, builder))
+            }
+        }
+
+        impl<
+                T0: ::planus::WriteAs<::planus::Offset<self::PoolSpec>>,
+                T1: ::planus::WriteAsDefault<self::KernelFormat, self::KernelFormat>,
+                T2: ::planus::WriteAs<::planus::Offset<self::Tensor>>,
+                T3: ::planus::WriteAsDefault<u64, u64>,
+                T4: ::planus::WriteAsOptional<::planus::Offset<self::Tensor>>,
+                T5: ::planus::WriteAsOptional<::planus::Offset<self::QParams>>,
+                T6: ::planus::WriteAsDefault<self::ConvAlgorithm, self::ConvAlgorithm>,
+            > ::planus::WriteAsOffset<Conv> for ConvBuilder<(T0, T1, T2, T3, T4, T5, T6)>
+        {
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<Conv> {
+                let (v0, v1, v2, v3, v4, v5, v6) = &self.0;
+                Conv::create(builder, v0, v1, v2, v3, v4, v5, v6)
+            }
+        }
+
+        /// Reference to a deserialized [Conv].
+        #[derive(Copy, Clone)]
+        pub struct ConvRef<'a>(#[allow(dead_code)] ::planus::table_reader::Table<'a>);
+
+        impl<'a> ConvRef<'a> {
+            /// Getter for the [`pool_spec` field](Conv#structfield.pool_spec).
+            #[inline]
+            pub fn pool_spec(&self) -> ::planus::Result<self::PoolSpecRef<'a>> {
+                self.0.access_required(0, "Conv", "pool_spec")
+            }
+
+            /// Getter for the [`kernel_format` field](Conv#structfield.kernel_format).
+            #[inline]
+            pub fn kernel_format(&self) -> ::planus::Result<self::KernelFormat> {
+                ::core::result::Result::Ok(
+                    self.0.access(1, "Conv", "kernel_format")?.unwrap_or(self::KernelFormat::Oihw),
+                )
+            }
+
+            /// Getter for the [`kernel` field](Conv#structfield.kernel).
+            #[inline]
+            pub fn kernel(&self) -> ::planus::Result<self::TensorRef<'a>> {
+                self.0.access_required(2, "Conv", "kernel")
+            }
+
+            /// Getter for the [`group` field](Conv#structfield.group).
+            #[inline]
+            pub fn group(&self) -> ::planus::Result<u64> {
+                ::core::result::Result::Ok(self.0.access(3, "Conv", "group")?.unwrap_or(0))
+            }
+
+            /// Getter for the [`bias` field](Conv#structfield.bias).
+            #[inline]
+            pub fn bias(&self) -> ::planus::Result<::core::option::Option<self::TensorRef<'a>>> {
+                self.0.access(4, "Conv", "bias")
+            }
+
+            /// Getter for the [`q_params` field](Conv#structfield.q_params).
+            #[inline]
+            pub fn q_params(
+                &self,
+            ) -> ::planus::Result<::core::option::Option<self::QParamsRef<'a>>> {
+                self.0.access(5, "Conv", "q_params")
+            }
+
+            /// Getter for the [`algorithm` field](Conv#structfield.algorithm).
+            #[inline]
+            pub fn algorithm(&self) -> ::planus::Result<self::ConvAlgorithm> {
+                ::core::result::Result::Ok(
+                    self.0.access(6, "Conv", "algorithm")?.unwrap_or(self::ConvAlgorithm::Auto),
+                )
+            }
+        }
+
+        impl<'a> ::core::fmt::Debug for ConvRef<'a> {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                let mut f = f.debug_struct("ConvRef");
+                f.field("pool_spec", &self.pool_spec());
+                f.field("kernel_format", &self.kernel_format());
+                f.field("kernel", &self.kernel());
+                f.field("group", &self.group());
+                if let ::core::option::Option::Some(field_bias) = self.bias().transpose() {
+                    f.field("bias", &field_bias);
+                }
+                if let ::core::option::Option::Some(field_q_params) = self.q_params().transpose() {
+                    f.field("q_params", &field_q_params);
+                }
+                f.field("algorithm", &self.algorithm());
+                f.finish()
+            }
+        }
+
+        impl<'a> ::core::convert::TryFrom<ConvRef<'a>> for Conv {
+            type Error = ::planus::Error;
+
+            #[allow(unreachable_code)]
+            fn try_from(value: ConvRef<'a>) -> ::planus::Result<Self> {
+                ::core::result::Result::Ok(Self {
+                    pool_spec: ::planus::alloc::boxed::Box::new(
+                        ::core::convert::TryInto::try_into(value.pool_spec()?)?,
+                    ),
+                    kernel_format: ::core::convert::TryInto::try_into(value.kernel_format()?)?,
+                    kernel: ::planus::alloc::boxed::Box::new(::core::convert::TryInto::try_into(
+                        value.kernel()?,
+                    )?),
+                    group: ::core::convert::TryInto::try_into(value.group()?)?,
+                    bias: if let ::core::option::Option::Some(bias) = value.bias()? {
+                        ::core::option::Option::Some(::planus::alloc::boxed::Box::new(
+                            ::core::convert::TryInto::try_into(bias)?,
+                        ))
+                    } else {
+                        ::core::option::Option::None
+                    },
+                    q_params: if let ::core::option::Option::Some(q_params) = value.q_params()? {
+                        ::core::option::Option::Some(::planus::alloc::boxed::Box::new(
+                            ::core::convert::TryInto::try_into(q_params)?,
+                        ))
+                    } else {
+                        ::core::option::Option::None
+                    },
+                    algorithm: ::core::convert::TryInto::try_into(value.algorithm()?)?,
+                })
+            }
+        }
+
+        impl<'a> ::planus::TableRead<'a> for ConvRef<'a> {
+            #[inline]
+            fn from_buffer(
+                buffer: ::planus::SliceWithStartOffset<'a>,
+                offset: usize,
+            ) -> ::core::result::Result<Self, ::planus::errors::ErrorKind> {
+                ::core::result::Result::Ok(Self(::planus::table_reader::Table::from_buffer(
+                    buffer, offset,
+                )?))
+            }
+        }
+
+        impl<'a> ::planus::VectorReadInner<'a> for ConvRef<'a> {
+            type Error = ::planus::Error;
+            const STRIDE: usize = 4;
+
+            unsafe fn from_buffer(
+                buffer: ::planus::SliceWithStartOffset<'a>,
+                offset: usize,
+            ) -> ::planus::Result<Self> {
+                ::planus::TableRead::from_buffer(buffer, offset).map_err(|error_kind| {
+                    error_kind.with_error_location("[ConvRef]", "get", buffer.offset_from_start)
+                })
+            }
+        }
+
+        /// # Safety
+        /// The planus compiler generates implementations that initialize
+        /// the bytes in `write_values`.
+        unsafe impl ::planus::VectorWrite<::planus::Offset<Conv>> for Conv {
+            type Value = ::planus::Offset<Conv>;
+            const STRIDE: usize = 4;
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> Self::Value {
+                ::planus::WriteAs::prepare(self, builder)
+            }
+
+            #[inline]
+            unsafe fn write_values(
+                values: &[::planus::Offset<Conv>],
+                bytes: *mut ::core::mem::MaybeUninit<u8>,
+                buffer_position: u32,
+            ) {
+                let bytes = bytes as *mut [::core::mem::MaybeUninit<u8>; 4];
+                for (i, v) in ::core::iter::Iterator::enumerate(values.iter()) {
+                    ::planus::WriteAsPrimitive::write(
+                        v,
+                        ::planus::Cursor::new(unsafe { &mut *bytes.add(i) }),
+                        buffer_position - (Self::STRIDE * i) as u32,
+                    );
+                }
+            }
+        }
+
+        impl<'a> ::planus::ReadAsRoot<'a> for ConvRef<'a> {
+            fn read_as_root(slice: &'a [u8]) -> ::planus::Result<Self> {
+                ::planus::TableRead::from_buffer(
+                    ::planus::SliceWithStartOffset { buffer: slice, offset_from_start: 0 },
+                    0,
+                )
+                .map_err(|error_kind| {
+                    error_kind.with_error_location("[ConvRef]", "read_as_root", 0)
+                })
+            }
+        }
+
+        /// The table `MaxPool` in the namespace `tract`
+        ///
+        /// Generated from these locations:
+        /// * Table `MaxPool` in the file `schema/tract.fbs:149`
+        #[derive(
+            Clone,
+            Debug,
+            PartialEq,
+            PartialOrd,
+            Eq,
+            Ord,
+            Hash,
+            ::serde::Serialize,
+            ::serde::Deserialize,
+        )]
+        pub struct MaxPool {
+            /// The field `pool_spec` in the table `MaxPool`
+            pub pool_spec: ::planus::alloc::boxed::Box<self::PoolSpec>,
+            /// The field `index_datum_type` in the table `MaxPool`
+            pub index_datum_type: ::core::option::Option<self::DatumType>,
+        }
+
+        #[allow(clippy::derivable_impls)]
+        impl ::core::default::Default for MaxPool {
+            fn default() -> Self {
+                Self {
+                    pool_spec: ::core::default::Default::default(),
+                    index_datum_type: ::core::default::Default::default(),
+                }
+            }
+        }
+
+        impl MaxPool {
+            /// Creates a [MaxPoolBuilder] for serializing an instance of this table.
+            #[inline]
+            pub fn builder() -> MaxPoolBuilder<()> {
+                MaxPoolBuilder(())
+            }
+
+            #[allow(clippy::too_many_arguments)]
+            pub fn create(
+                builder: &mut ::planus::Builder,
+                field_pool_spec: impl ::planus::WriteAs<::planus::Offset<self::PoolSpec>>,
+                field_index_datum_type: impl ::planus::WriteAsOptional<self::DatumType>,
+            ) -> ::planus::Offset<Self> {
+                let prepared_pool_spec = field_pool_spec.prepare(builder);
+                let prepared_index_datum_type = field_index_datum_type.prepare(builder);
+
+                let mut table_writer: ::planus::table_writer::TableWriter<8> =
+                    ::core::default::Default::default();
+                table_writer.write_entry::<::planus::Offset<self::PoolSpec>>(0);
+                if prepared_index_datum_type.is_some() {
+                    table_writer.write_entry::<self::DatumType>(1);
+                }
+
+                unsafe {
+                    table_writer.finish(builder, |object_writer| {
+                        object_writer.write::<_, _, 4>(&prepared_pool_spec);
+                        if let ::core::option::Option::Some(prepared_index_datum_type) =
+                            prepared_index_datum_type
+                        {
+                            object_writer.write::<_, _, 1>(&prepared_index_datum_type);
+                        }
+                    });
+                }
+                builder.current_offset()
+            }
+        }
+
+        impl ::planus::WriteAs<::planus::Offset<MaxPool>> for MaxPool {
+            type Prepared = ::planus::Offset<Self>;
+
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<MaxPool> {
+                ::planus::WriteAsOffset::prepare(self, builder)
+            }
+        }
+
+        impl ::planus::WriteAsOptional<::planus::Offset<MaxPool>> for MaxPool {
+            type Prepared = ::planus::Offset<Self>;
+
+            #[inline]
+            fn prepare(
+                &self,
+                builder: &mut ::planus::Builder,
+            ) -> ::core::option::Option<::planus::Offset<MaxPool>> {
+                ::core::option::Option::Some(::planus::WriteAsOffset::prepare(self, builder))
+            }
+        }
+
+        impl ::planus::WriteAsOffset<MaxPool> for MaxPool {
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<MaxPool> {
+                MaxPool::create(builder, &self.pool_spec, self.index_datum_type)
+            }
+        }
+
+        /// Builder for serializing an instance of the [MaxPool] type.
+        ///
+        /// Can be created using the [MaxPool::builder] method.
+        #[derive(Debug)]
+        #[must_use]
+        pub struct MaxPoolBuilder<State>(State);
+
+        impl MaxPoolBuilder<()> {
+            /// Setter for the [`pool_spec` field](MaxPool#structfield.pool_spec).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn pool_spec<T0>(self, value: T0) -> MaxPoolBuilder<(T0,)>
+            where
+                T0: ::planus::WriteAs<::planus::Offset<self::PoolSpec>>,
+            {
+                MaxPoolBuilder((value,))
+            }
+        }
+
+        impl<T0> MaxPoolBuilder<(T0,)> {
+            /// Setter for the [`index_datum_type` field](MaxPool#structfield.index_datum_type).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn index_datum_type<T1>(self, value: T1) -> MaxPoolBuilder<(T0, T1)>
+            where
+                T1: ::planus::WriteAsOptional<self::DatumType>,
+            {
+                let (v0,) = self.0;
+                MaxPoolBuilder((v0, value))
+            }
+
+            /// Sets the [`index_datum_type` field](MaxPool#structfield.index_datum_type) to null.
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn index_datum_type_as_null(self) -> MaxPoolBuilder<(T0, ())> {
+                self.index_datum_type(())
+            }
+        }
+
+        impl<T0, T1> MaxPoolBuilder<(T0, T1)> {
+            /// Finish writing the builder to get an [Offset](::planus::Offset) to a serialized [MaxPool].
+            #[inline]
+            pub fn finish(self, builder: &mut ::planus::Builder) -> ::planus::Offset<MaxPool>
+            where
+                Self: ::planus::WriteAsOffset<MaxPool>,
+            {
+                ::planus::WriteAsOffset::prepare(&self, builder)
+            }
+        }
+
+        impl<
+                T0: ::planus::WriteAs<::planus::Offset<self::PoolSpec>>,
+                T1: ::planus::WriteAsOptional<self::DatumType>,
+            > ::planus::WriteAs<::planus::Offset<MaxPool>> for MaxPoolBuilder<(T0, T1)>
+        {
+            type Prepared = ::planus::Offset<MaxPool>;
+
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<MaxPool> {
+                ::planus::WriteAsOffset::prepare(self, builder)
+            }
+        }
+
+        impl<
+                T0: ::planus::WriteAs<::planus::Offset<self::PoolSpec>>,
+                T1: ::planus::WriteAsOptional<self::DatumType>,
+            > ::planus::WriteAsOptional<::planus::Offset<MaxPool>> for MaxPoolBuilder<(T0, T1)>
+        {
+            type Prepared = ::planus::Offset<MaxPool>;
+
+            #[inline]
+            fn prepare(
+                &self,
+                builder: &mut ::planus::Builder,
+            ) -> ::core::option::Option<::planus::Offset<MaxPool>> {
+                ::core::option::Option::Some(::planus::WriteAsOffset::prepare(self, builder))
+            }
+        }
+
+        impl<
+                T0: ::planus::WriteAs<::planus::Offset<self::PoolSpec>>,
+                T1: ::planus::WriteAsOptional<self::DatumType>,
+            > ::planus::WriteAsOffset<MaxPool> for MaxPoolBuilder<(T0, T1)>
+        {
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<MaxPool> {
+                let (v0, v1) = &self.0;
+                MaxPool::create(builder, v0, v1)
+            }
+        }
+
+        /// Reference to a deserialized [MaxPool].
+        #[derive(Copy, Clone)]
+        pub struct MaxPoolRef<'a>(#[allow(dead_code)] ::planus::table_reader::Table<'a>);
+
+        impl<'a> MaxPoolRef<'a> {
+            /// Getter for the [`pool_spec` field](MaxPool#structfield.pool_spec).
+            #[inline]
+            pub fn pool_spec(&self) -> ::planus::Result<self::PoolSpecRef<'a>> {
+                self.0.access_required(0, "MaxPool", "pool_spec")
+            }
+
+            /// Getter for the [`index_datum_type` field](MaxPool#structfield.index_datum_type).
+            #[inline]
+            pub fn index_datum_type(
+                &self,
+            ) -> ::planus::Result<::core::option::Option<self::DatumType>> {
+                self.0.access(1, "MaxPool", "index_datum_type")
+            }
+        }
+
+        impl<'a> ::core::fmt::Debug for MaxPoolRef<'a> {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                let mut f = f.debug_struct("MaxPoolRef");
+                f.field("pool_spec", &self.pool_spec());
+                if let ::core::option::Option::Some(field_index_datum_type) =
+                    self.index_datum_type().transpose()
+                {
+                    f.field("index_datum_type", &field_index_datum_type);
+                }
+                f.finish()
+            }
+        }
+
+        impl<'a> ::core::convert::TryFrom<MaxPoolRef<'a>> for MaxPool {
+            type Error = ::planus::Error;
+
+            #[allow(unreachable_code)]
+            fn try_from(value: MaxPoolRef<'a>) -> ::planus::Result<Self> {
+                ::core::result::Result::Ok(Self {
+                    pool_spec: ::planus::alloc::boxed::Box::new(
+                        ::core::convert::TryInto::try_into(value.pool_spec()?)?,
+                    ),
+                    index_datum_type: if let ::core::option::Option::Some(index_datum_type) =
+                        value.index_datum_type()?
+                    {
+                        ::core::option::Option::Some(::core::convert::TryInto::try_into(
+                            index_datum_type,
+                        )?)
+                    } else {
+                        ::core::option::Option::None
+                    },
+                })
+            }
+        }
+
+        impl<'a> ::planus::TableRead<'a> for MaxPoolRef<'a> {
+            #[inline]
+            fn from_buffer(
+                buffer: ::planus::SliceWithStartOffset<'a>,
+                offset: usize,
+            ) -> ::core::result::Result<Self, ::planus::errors::ErrorKind> {
+                ::core::result::Result::Ok(Self(::planus::table_reader::Table::from_buffer(
+                    buffer, offset,
+                )?))
+            }
+        }
+
+        impl<'a> ::planus::VectorReadInner<'a> for MaxPoolRef<'a> {
+            type Error = ::planus::Error;
+            const STRIDE: usize = 4;
+
+            unsafe fn from_buffer(
+                buffer: ::planus::SliceWithStartOffset<'a>,
+                offset: usize,
+            ) -> ::planus::Result<Self> {
+                ::planus::TableRead::from_buffer(buffer, offset).map_err(|error_kind| {
+                    error_kind.with_error_location("[MaxPoolRef]", "get", buffer.offset_from_start)
+                })
+            }
+        }
+
+        /// # Safety
+        /// The planus compiler generates implementations that initialize
+        /// the bytes in `write_values`.
+        unsafe impl ::planus::VectorWrite<::planus::Offset<MaxPool>> for MaxPool {
+            type Value = ::planus::Offset<MaxPool>;
+            const STRIDE: usize = 4;
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> Self::Value {
+                ::planus::WriteAs::prepare(self, builder)
+            }
+
+            #[inline]
+            unsafe fn write_values(
+                values: &[::planus::Offset<MaxPool>],
+                bytes: *mut ::core::mem::MaybeUninit<u8>,
+                buffer_position: u32,
+            ) {
+                let bytes = bytes as *mut [::core::mem::MaybeUninit<u8>; 4];
+                for (i, v) in ::core::iter::Iterator::enumerate(values.iter()) {
+                    ::planus::WriteAsPrimitive::write(
+                        v,
+                        ::planus::Cursor::new(unsafe { &mut *bytes.add(i) }),
+                        buffer_position - (Self::STRIDE * i) as u32,
+                    );
+                }
+            }
+        }
+
+        impl<'a> ::planus::ReadAsRoot<'a> for MaxPoolRef<'a> {
+            fn read_as_root(slice: &'a [u8]) -> ::planus::Result<Self> {
+                ::planus::TableRead::from_buffer(
+                    ::planus::SliceWithStartOffset { buffer: slice, offset_from_start: 0 },
+                    0,
+                )
+                .map_err(|error_kind| {
+                    error_kind.with_error_location("[MaxPoolRef]", "read_as_root", 0)
+                })
+            }
+        }
+
+        /// The table `AvgPool` in the namespace `tract`
+        ///
+        /// Generated from these locations:
+        /// * Table `AvgPool` in the file `schema/tract.fbs:154`
+        #[derive(
+            Clone,
+            Debug,
+            PartialEq,
+            PartialOrd,
+            Eq,
+            Ord,
+            Hash,
+            ::serde::Serialize,
+            ::serde::Deserialize,
+        )]
+        pub struct AvgPool {
+            /// The field `pool_spec` in the table `AvgPool`
+            pub pool_spec: ::planus::alloc::boxed::Box<self::PoolSpec>,
+            /// The field `count_include_pad` in the table `AvgPool`
+            pub count_include_pad: bool,
+        }
+
+        #[allow(clippy::derivable_impls)]
+        impl ::core::default::Default for AvgPool {
+            fn default() -> Self {
+                Self { pool_spec: ::core::default::Default::default(), count_include_pad: false }
+            }
+        }
+
+        impl AvgPool {
+            /// Creates a [AvgPoolBuilder] for serializing an instance of this table.
+            #[inline]
+            pub fn builder() -> AvgPoolBuilder<()> {
+                AvgPoolBuilder(())
+            }
+
+            #[allow(clippy::too_many_arguments)]
+            pub fn create(
+                builder: &mut ::planus::Builder,
+                field_pool_spec: impl ::planus::WriteAs<::planus::Offset<self::PoolSpec>>,
+                field_count_include_pad: impl ::planus::WriteAsDefault<bool, bool>,
+            ) -> ::planus::Offset<Self> {
+                let prepared_pool_spec = field_pool_spec.prepare(builder);
+                let prepared_count_include_pad = field_count_include_pad.prepare(builder, &false);
+
+                let mut table_writer: ::planus::table_writer::TableWriter<8> =
+                    ::core::default::Default::default();
+                table_writer.write_entry::<::planus::Offset<self::PoolSpec>>(0);
+                if prepared_count_include_pad.is_some() {
+                    table_writer.write_entry::<bool>(1);
+                }
+
+                unsafe {
+                    table_writer.finish(builder, |object_writer| {
+                        object_writer.write::<_, _, 4>(&prepared_pool_spec);
+                        if let ::core::option::Option::Some(prepared_count_include_pad) =
+                            prepared_count_include_pad
+                        {
+                            object_writer.write::<_, _, 1>(&prepared_count_include_pad);
+                        }
+                    });
+                }
+                builder.current_offset()
+            }
+        }
+
+        impl ::planus::WriteAs<::planus::Offset<AvgPool>> for AvgPool {
+            type Prepared = ::planus::Offset<Self>;
+
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<AvgPool> {
+                ::planus::WriteAsOffset::prepare(self, builder)
+            }
+        }
+
+        impl ::planus::WriteAsOptional<::planus::Offset<AvgPool>> for AvgPool {
+            type Prepared = ::planus::Offset<Self>;
+
+            #[inline]
+            fn prepare(
+                &self,
+                builder: &mut ::planus::Builder,
+            ) -> ::core::option::Option<::planus::Offset<AvgPool>> {
+                ::core::option::Option::Some(::planus::WriteAsOffset::prepare(self, builder))
+            }
+        }
+
+        impl ::planus::WriteAsOffset<AvgPool> for AvgPool {
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<AvgPool> {
+                AvgPool::create(builder, &self.pool_spec, self.count_include_pad)
+            }
+        }
+
+        /// Builder for serializing an instance of the [AvgPool] type.
+        ///
+        /// Can be created using the [AvgPool::builder] method.
+        #[derive(Debug)]
+        #[must_use]
+        pub struct AvgPoolBuilder<State>(State);
+
+        impl AvgPoolBuilder<()> {
+            /// Setter for the [`pool_spec` field](AvgPool#structfield.pool_spec).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn pool_spec<T0>(self, value: T0) -> AvgPoolBuilder<(T0,)>
+            where
+                T0: ::planus::WriteAs<::planus::Offset<self::PoolSpec>>,
+            {
+                AvgPoolBuilder((value,))
+            }
+        }
+
+        impl<T0> AvgPoolBuilder<(T0,)> {
+            /// Setter for the [`count_include_pad` field](AvgPool#structfield.count_include_pad).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn count_include_pad<T1>(self, value: T1) -> AvgPoolBuilder<(T0, T1)>
+            where
+                T1: ::planus::WriteAsDefault<bool, bool>,
+            {
+                let (v0,) = self.0;
+                AvgPoolBuilder((v0, value))
+            }
+
+            /// Sets the [`count_include_pad` field](AvgPool#structfield.count_include_pad) to the default value.
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn count_include_pad_as_default(
+                self,
+            ) -> AvgPoolBuilder<(T0, ::planus::DefaultValue)> {
+                self.count_include_pad(::planus::DefaultValue)
+            }
+        }
+
+        impl<T0, T1> AvgPoolBuilder<(T0, T1)> {
+            /// Finish writing the builder to get an [Offset](::planus::Offset) to a serialized [AvgPool].
+            #[inline]
+            pub fn finish(self, builder: &mut ::planus::Builder) -> ::planus::Offset<AvgPool>
+            where
+                Self: ::planus::WriteAsOffset<AvgPool>,
+            {
+                ::planus::WriteAsOffset::prepare(&self, builder)
+            }
+        }
+
+        impl<
+                T0: ::planus::WriteAs<::planus::Offset<self::PoolSpec>>,
+                T1: ::planus::WriteAsDefault<bool, bool>,
+            > ::planus::WriteAs<::planus::Offset<AvgPool>> for AvgPoolBuilder<(T0, T1)>
+        {
+            type Prepared = ::planus::Offset<AvgPool>;
+
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<AvgPool> {
+                ::planus::WriteAsOffset::prepare(self, builder)
+            }
+        }
+
+        impl<
+                T0: ::planus::WriteAs<::planus::Offset<self::PoolSpec>>,
+                T1: ::planus::WriteAsDefault<bool, bool>,
+            > ::planus::WriteAsOptional<::planus::Offset<AvgPool>> for AvgPoolBuilder<(T0, T1)>
+        {
+            type Prepared = ::planus::Offset<AvgPool>;
+
+            #[inline]
+            fn prepare(
+                &self,
+                builder: &mut ::planus::Builder,
+            ) -> ::core::option::Option<::planus::Offset<AvgPool>> {
+                ::core::option::Option::Some(::planus::WriteAsOffset::prepare(self, builder))
+            }
+        }
+
+        impl<
+                T0: ::planus::WriteAs<::planus::Offset<self::PoolSpec>>,
+                T1: ::planus::WriteAsDefault<bool, bool>,
+            > ::planus::WriteAsOffset<AvgPool> for AvgPoolBuilder<(T0, T1)>
+        {
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<AvgPool> {
+                let (v0, v1) = &self.0;
+                AvgPool::create(builder, v0, v1)
+            }
+        }
+
+        /// Reference to a deserialized [AvgPool].
+        #[derive(Copy, Clone)]
+        pub struct AvgPoolRef<'a>(#[allow(dead_code)] ::planus::table_reader::Table<'a>);
+
+        impl<'a> AvgPoolRef<'a> {
+            /// Getter for the [`pool_spec` field](AvgPool#structfield.pool_spec).
+            #[inline]
+            pub fn pool_spec(&self) -> ::planus::Result<self::PoolSpecRef<'a>> {
+                self.0.access_required(0, "AvgPool", "pool_spec")
+            }
+
+            /// Getter for the [`count_include_pad` field](AvgPool#structfield.count_include_pad).
+            #[inline]
+            pub fn count_include_pad(&self) -> ::planus::Result<bool> {
+                ::core::result::Result::Ok(
+                    self.0.access(1, "AvgPool", "count_include_pad")?.unwrap_or(false),
+                )
+            }
+        }
+
+        impl<'a> ::core::fmt::Debug for AvgPoolRef<'a> {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                let mut f = f.debug_struct("AvgPoolRef");
+                f.field("pool_spec", &self.pool_spec());
+                f.field("count_include_pad", &self.count_include_pad());
+                f.finish()
+            }
+        }
+
+        impl<'a> ::core::convert::TryFrom<AvgPoolRef<'a>> for AvgPool {
+            type Error = ::planus::Error;
+
+            #[allow(unreachable_code)]
+            fn try_from(value: AvgPoolRef<'a>) -> ::planus::Result<Self> {
+                ::core::result::Result::Ok(Self {
+                    pool_spec: ::planus::alloc::boxed::Box::new(
+                        ::core::convert::TryInto::try_into(value.pool_spec()?)?,
+                    ),
+                    count_include_pad: ::core::convert::TryInto::try_into(
+                        value.count_include_pad()?,
+                    )?,
+                })
+            }
+        }
+
+        impl<'a> ::planus::TableRead<'a> for AvgPoolRef<'a> {
+            #[inline]
+            fn from_buffer(
+                buffer: ::planus::SliceWithStartOffset<'a>,
+                offset: usize,
+            ) -> ::core::result::Result<Self, ::planus::errors::ErrorKind> {
+                ::core::result::Result::Ok(Self(::planus::table_reader::Table::from_buffer(
+                    buffer, offset,
+                )?))
+            }
+        }
+
+        impl<'a> ::planus::VectorReadInner<'a> for AvgPoolRef<'a> {
+            type Error = ::planus::Error;
+            const STRIDE: usize = 4;
+
+            unsafe fn from_buffer(
+                buffer: ::planus::SliceWithStartOffset<'a>,
+                offset: usize,
+            ) -> ::planus::Result<Self> {
+                ::planus::TableRead::from_buffer(buffer, offset).map_err(|error_kind| {
+                    error_kind.with_error_location("[AvgPoolRef]", "get", buffer.offset_from_start)
+                })
+            }
+        }
+
+        /// # Safety
+        /// The planus compiler generates implementations that initialize
+        /// the bytes in `write_values`.
+        unsafe impl ::planus::VectorWrite<::planus::Offset<AvgPool>> for AvgPool {
+            type Value = ::planus::Offset<AvgPool>;
+            const STRIDE: usize = 4;
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> Self::Value {
+                ::planus::WriteAs::prepare(self, builder)
+            }
+
+            #[inline]
+            unsafe fn write_values(
+                values: &[::planus::Offset<AvgPool>],
+                bytes: *mut ::core::mem::MaybeUninit<u8>,
+                buffer_position: u32,
+            ) {
+                let bytes = bytes as *mut [::core::mem::MaybeUninit<u8>; 4];
+                for (i, v) in ::core::iter::Iterator::enumerate(values.iter()) {
+                    ::planus::WriteAsPrimitive::write(
+                        v,
+                        ::planus::Cursor::new(unsafe { &mut *bytes.add(i) }),
+                        buffer_position - (Self::STRIDE * i) as u32,
+                    );
+                }
+            }
+        }
+
+        impl<'a> ::planus::ReadAsRoot<'a> for AvgPoolRef<'a> {
+            fn read_as_root(slice: &'a [u8]) -> ::planus::Result<Self> {
+                ::planus::TableRead::from_buffer(
+                    ::planus::SliceWithStartOffset { buffer: slice, offset_from_start: 0 },
+                    0,
+                )
+                .map_err(|error_kind| {
+                    error_kind.with_error_location("[AvgPoolRef]", "read_as_root", 0)
+                })
+            }
+        }
+
+        /// The table `GlobalAvgPool` in the namespace `tract`
+        ///
+        /// Generated from these locations:
+        /// * Table `GlobalAvgPool` in the file `schema/tract.fbs:159`
+        #[derive(
+            Clone,
+            Debug,
+            PartialEq,
+            PartialOrd,
+            Eq,
+            Ord,
+            Hash,
+            ::serde::Serialize,
+            ::serde::Deserialize,
+        )]
+        pub struct GlobalAvgPool {}
+
+        #[allow(clippy::derivable_impls)]
+        impl ::core::default::Default for GlobalAvgPool {
+            fn default() -> Self {
+                Self {}
+            }
+        }
+
+        impl GlobalAvgPool {
+            /// Creates a [GlobalAvgPoolBuilder] for serializing an instance of this table.
+            #[inline]
+            pub fn builder() -> GlobalAvgPoolBuilder<()> {
+                GlobalAvgPoolBuilder(())
+            }
+
+            #[allow(clippy::too_many_arguments)]
+            pub fn create(builder: &mut ::planus::Builder) -> ::planus::Offset<Self> {
+                let table_writer: ::planus::table_writer::TableWriter<4> =
+                    ::core::default::Default::default();
+                unsafe {
+                    table_writer.finish(builder, |_table_writer| {});
+                }
+                builder.current_offset()
+            }
+        }
+
+        impl ::planus::WriteAs<::planus::Offset<GlobalAvgPool>> for GlobalAvgPool {
+            type Prepared = ::planus::Offset<Self>;
+
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<GlobalAvgPool> {
+                ::planus::WriteAsOffset::prepare(self, builder)
+            }
+        }
+
+        impl ::planus::WriteAsOptional<::planus::Offset<GlobalAvgPool>> for GlobalAvgPool {
+            type Prepared = ::planus::Offset<Self>;
+
+            #[inline]
+            fn prepare(
+                &self,
+                builder: &mut ::planus::Builder,
+            ) -> ::core::option::Option<::planus::Offset<GlobalAvgPool>> {
+                ::core::option::Option::Some(::planus::WriteAsOffset::prepare(self, builder))
+            }
+        }
+
+        impl ::planus::WriteAsOffset<GlobalAvgPool> for GlobalAvgPool {
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<GlobalAvgPool> {
+                GlobalAvgPool::create(builder)
+            }
+        }
+
+        /// Builder for serializing an instance of the [GlobalAvgPool] type.
+        ///
+        /// Can be created using the [GlobalAvgPool::builder] method.
+        #[derive(Debug)]
+        #[must_use]
+        pub struct GlobalAvgPoolBuilder<State>(State);
+
+        impl GlobalAvgPoolBuilder<()> {
+            /// Finish writing the builder to get an [Offset](::planus::Offset) to a serialized [GlobalAvgPool].
+            #[inline]
+            pub fn finish(self, builder: &mut ::planus::Builder) -> ::planus::Offset<GlobalAvgPool>
+            where
+                Self: ::planus::WriteAsOffset<GlobalAvgPool>,
+            {
+                ::planus::WriteAsOffset::prepare(&self, builder)
+            }
+        }
+
+        impl ::planus::WriteAs<::planus::Offset<GlobalAvgPool>> for GlobalAvgPoolBuilder<()> {
+            type Prepared = ::planus::Offset<GlobalAvgPool>;
+
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<GlobalAvgPool> {
+                ::planus::WriteAsOffset::prepare(self, builder)
+            }
+        }
+
+        impl ::planus::WriteAsOptional<::planus::Offset<GlobalAvgPool>> for GlobalAvgPoolBuilder<()> {
+            type Prepared = ::planus::Offset<GlobalAvgPool>;
+
+            #[inline]
+            fn prepare(
+                &self,
+                builder: &mut ::planus::Builder,
+            ) -> ::core::option::Option<::planus::Offset<GlobalAvgPool>> {
+                ::core::option::Option::Some(::planus::WriteAsOffset::prepare(self, builder))
+            }
+        }
+
+        impl ::planus::WriteAsOffset<GlobalAvgPool> for GlobalAvgPoolBuilder<()> {
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<GlobalAvgPool> {
+                GlobalAvgPool::create(builder)
+            }
+        }
+
+        /// Reference to a deserialized [GlobalAvgPool].
+        #[derive(Copy, Clone)]
+        pub struct GlobalAvgPoolRef<'a>(#[allow(dead_code)] ::planus::table_reader::Table<'a>);
+
+        impl<'a> GlobalAvgPoolRef<'a> {}
+
+        impl<'a> ::core::fmt::Debug for GlobalAvgPoolRef<'a> {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                let mut f = f.debug_struct("GlobalAvgPoolRef");
+
+                f.finish()
+            }
+        }
+
+        impl<'a> ::core::convert::TryFrom<GlobalAvgPoolRef<'a>> for GlobalAvgPool {
+            type Error = ::planus::Error;
+
+            fn try_from(_value: GlobalAvgPoolRef<'a>) -> ::planus::Result<Self> {
+                ::core::result::Result::Ok(Self {})
+            }
+        }
+
+        impl<'a> ::planus::TableRead<'a> for GlobalAvgPoolRef<'a> {
+            #[inline]
+            fn from_buffer(
+                buffer: ::planus::SliceWithStartOffset<'a>,
+                offset: usize,
+            ) -> ::core::result::Result<Self, ::planus::errors::ErrorKind> {
+                ::core::result::Result::Ok(Self(::planus::table_reader::Table::from_buffer(
+                    buffer, offset,
+                )?))
+            }
+        }
+
+        impl<'a> ::planus::VectorReadInner<'a> for GlobalAvgPoolRef<'a> {
+            type Error = ::planus::Error;
+            const STRIDE: usize = 4;
+
+            unsafe fn from_buffer(
+                buffer: ::planus::SliceWithStartOffset<'a>,
+                offset: usize,
+            ) -> ::planus::Result<Self> {
+                ::planus::TableRead::from_buffer(buffer, offset).map_err(|error_kind| {
+                    error_kind.with_error_location(
+                        "[GlobalAvgPoolRef]",
+                        "get",
+                        buffer.offset_from_start,
+                    )
+                })
+            }
+        }
+
+        /// # Safety
+        /// The planus compiler generates implementations that initialize
+        /// the bytes in `write_values`.
+        unsafe impl ::planus::VectorWrite<::planus::Offset<GlobalAvgPool>> for GlobalAvgPool {
+            type Value = ::planus::Offset<GlobalAvgPool>;
+            const STRIDE: usize = 4;
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> Self::Value {
+                ::planus::WriteAs::prepare(self, builder)
+            }
+
+            #[inline]
+            unsafe fn write_values(
+                values: &[::planus::Offset<GlobalAvgPool>],
+                bytes: *mut ::core::mem::MaybeUninit<u8>,
+                buffer_position: u32,
+            ) {
+                let bytes = bytes as *mut [::core::mem::MaybeUninit<u8>; 4];
+                for (i, v) in ::core::iter::Iterator::enumerate(values.iter()) {
+                    ::planus::WriteAsPrimitive::write(
+                        v,
+                        ::planus::Cursor::new(unsafe { &mut *bytes.add(i) }),
+                        buffer_position - (Self::STRIDE * i) as u32,
+                    );
+                }
+            }
+        }
+
+        impl<'a> ::planus::ReadAsRoot<'a> for GlobalAvgPoolRef<'a> {
+            fn read_as_root(slice: &'a [u8]) -> ::planus::Result<Self> {
+                ::planus::TableRead::from_buffer(
+                    ::planus::SliceWithStartOffset { buffer: slice, offset_from_start: 0 },
+                    0,
+                )
+                .map_err(|error_kind| {
+                    error_kind.with_error_location("[GlobalAvgPoolRef]", "read_as_root", 0)
+                })
+            }
+        }
+
+        /// The table `GlobalMaxPool` in the namespace `tract`
+        ///
+        /// Generated from these locations:
+        /// * Table `GlobalMaxPool` in the file `schema/tract.fbs:161`
+        #[derive(
+            Clone,
+            Debug,
+            PartialEq,
+            PartialOrd,
+            Eq,
+            Ord,
+            Hash,
+            ::serde::Serialize,
+            ::serde::Deserialize,
+        )]
+        pub struct GlobalMaxPool {}
+
+        #[allow(clippy::derivable_impls)]
+        impl ::core::default::Default for GlobalMaxPool {
+            fn default() -> Self {
+                Self {}
+            }
+        }
+
+        impl GlobalMaxPool {
+            /// Creates a [GlobalMaxPoolBuilder] for serializing an instance of this table.
+            #[inline]
+            pub fn builder() -> GlobalMaxPoolBuilder<()> {
+                GlobalMaxPoolBuilder(())
+            }
+
+            #[allow(clippy::too_many_arguments)]
+            pub fn create(builder: &mut ::planus::Builder) -> ::planus::Offset<Self> {
+                let table_writer: ::planus::table_writer::TableWriter<4> =
+                    ::core::default::Default::default();
+                unsafe {
+                    table_writer.finish(builder, |_table_writer| {});
+                }
+                builder.current_offset()
+            }
+        }
+
+        impl ::planus::WriteAs<::planus::Offset<GlobalMaxPool>> for GlobalMaxPool {
+            type Prepared = ::planus::Offset<Self>;
+
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<GlobalMaxPool> {
+                ::planus::WriteAsOffset::prepare(self, builder)
+            }
+        }
+
+        impl ::planus::WriteAsOptional<::planus::Offset<GlobalMaxPool>> for GlobalMaxPool {
+            type Prepared = ::planus::Offset<Self>;
+
+            #[inline]
+            fn prepare(
+                &self,
+                builder: &mut ::planus::Builder,
+            ) -> ::core::option::Option<::planus::Offset<GlobalMaxPool>> {
+                ::core::option::Option::Some(::planus::WriteAsOffset::prepare(self, builder))
+            }
+        }
+
+        impl ::planus::WriteAsOffset<GlobalMaxPool> for GlobalMaxPool {
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<GlobalMaxPool> {
+                GlobalMaxPool::create(builder)
+            }
+        }
+
+        /// Builder for serializing an instance of the [GlobalMaxPool] type.
+        ///
+        /// Can be created using the [GlobalMaxPool::builder] method.
+        #[derive(Debug)]
+        #[must_use]
+        pub struct GlobalMaxPoolBuilder<State>(State);
+
+        impl GlobalMaxPoolBuilder<()> {
+            /// Finish writing the builder to get an [Offset](::planus::Offset) to a serialized [GlobalMaxPool].
+            #[inline]
+            pub fn finish(self, builder: &mut ::planus::Builder) -> ::planus::Offset<GlobalMaxPool>
+            where
+                Self: ::planus::WriteAsOffset<GlobalMaxPool>,
+            {
+                ::planus::WriteAsOffset::prepare(&self, builder)
+            }
+        }
+
+        impl ::planus::WriteAs<::planus::Offset<GlobalMaxPool>> for GlobalMaxPoolBuilder<()> {
+            type Prepared = ::planus::Offset<GlobalMaxPool>;
+
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<GlobalMaxPool> {
+                ::planus::WriteAsOffset::prepare(self, builder)
+            }
+        }
+
+        impl ::planus::WriteAsOptional<::planus::Offset<GlobalMaxPool>> for GlobalMaxPoolBuilder<()> {
+            type Prepared = ::planus::Offset<GlobalMaxPool>;
+
+            #[inline]
+            fn prepare(
+                &self,
+                builder: &mut ::planus::Builder,
+            ) -> ::core::option::Option<::planus::Offset<GlobalMaxPool>> {
+                ::core::option::Option::Some(::planus::WriteAsOffset::prepare(self, builder))
+            }
+        }
+
+        impl ::planus::WriteAsOffset<GlobalMaxPool> for GlobalMaxPoolBuilder<()> {
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<GlobalMaxPool> {
+                GlobalMaxPool::create(builder)
+            }
+        }
+
+        /// Reference to a deserialized [GlobalMaxPool].
+        #[derive(Copy, Clone)]
+        pub struct GlobalMaxPoolRef<'a>(#[allow(dead_code)] ::planus::table_reader::Table<'a>);
+
+        impl<'a> GlobalMaxPoolRef<'a> {}
+
+        impl<'a> ::core::fmt::Debug for GlobalMaxPoolRef<'a> {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                let mut f = f.debug_struct("GlobalMaxPoolRef");
+
+                f.finish()
+            }
+        }
+
+        impl<'a> ::core::convert::TryFrom<GlobalMaxPoolRef<'a>> for GlobalMaxPool {
+            type Error = ::planus::Error;
+
+            fn try_from(_value: GlobalMaxPoolRef<'a>) -> ::planus::Result<Self> {
+                ::core::result::Result::Ok(Self {})
+            }
+        }
+
+        impl<'a> ::planus::TableRead<'a> for GlobalMaxPoolRef<'a> {
+            #[inline]
+            fn from_buffer(
+                buffer: ::planus::SliceWithStartOffset<'a>,
+                offset: usize,
+            ) -> ::core::result::Result<Self, ::planus::errors::ErrorKind> {
+                ::core::result::Result::Ok(Self(::planus::table_reader::Table::from_buffer(
+                    buffer, offset,
+                )?))
+            }
+        }
+
+        impl<'a> ::planus::VectorReadInner<'a> for GlobalMaxPoolRef<'a> {
+            type Error = ::planus::Error;
+            const STRIDE: usize = 4;
+
+            unsafe fn from_buffer(
+                buffer: ::planus::SliceWithStartOffset<'a>,
+                offset: usize,
+            ) -> ::planus::Result<Self> {
+                ::planus::TableRead::from_buffer(buffer, offset).map_err(|error_kind| {
+                    error_kind.with_error_location(
+                        "[GlobalMaxPoolRef]",
+                        "get",
+                        buffer.offset_from_start,
+                    )
+                })
+            }
+        }
+
+        /// # Safety
+        /// The planus compiler generates implementations that initialize
+        /// the bytes in `write_values`.
+        unsafe impl ::planus::VectorWrite<::planus::Offset<GlobalMaxPool>> for GlobalMaxPool {
+            type Value = ::planus::Offset<GlobalMaxPool>;
+            const STRIDE: usize = 4;
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> Self::Value {
+                ::planus::WriteAs::prepare(self, builder)
+            }
+
+            #[inline]
+            unsafe fn write_values(
+                values: &[::planus::Offset<GlobalMaxPool>],
+                bytes: *mut ::core::mem::MaybeUninit<u8>,
+                buffer_position: u32,
+            ) {
+                let bytes = bytes as *mut [::core::mem::MaybeUninit<u8>; 4];
+                for (i, v) in ::core::iter::Iterator::enumerate(values.iter()) {
+                    ::planus::WriteAsPrimitive::write(
+                        v,
+                        ::planus::Cursor::new(unsafe { &mut *bytes.add(i) }),
+                        buffer_position - (Self::STRIDE * i) as u32,
+                    );
+                }
+            }
+        }
+
+        impl<'a> ::planus::ReadAsRoot<'a> for GlobalMaxPoolRef<'a> {
+            fn read_as_root(slice: &'a [u8]) -> ::planus::Result<Self> {
+                ::planus::TableRead::from_buffer(
+                    ::planus::SliceWithStartOffset { buffer: slice, offset_from_start: 0 },
+                    0,
+                )
+                .map_err(|error_kind| {
+                    error_kind.with_error_location("[GlobalMaxPoolRef]", "read_as_root", 0)
+                })
+            }
+        }
+
+        /// The enum `Reducer` in the namespace `tract`
+        ///
+        /// Generated from these locations:
+        /// * Enum `Reducer` in the file `schema/tract.fbs:163`
+        #[derive(
+            Copy,
+            Clone,
+            Debug,
+            PartialEq,
+            Eq,
+            PartialOrd,
+            Ord,
+            Hash,
+            ::serde::Serialize,
+            ::serde::Deserialize,
+        )]
+        #[repr(u8)]
+        pub enum Reducer {
+            /// The variant `L1` in the enum `Reducer`
+            L1 = 0,
+
+            /// The variant `L2` in the enum `Reducer`
+            L2 = 1,
+
+            /// The variant `LogSum` in the enum `Reducer`
+            LogSum = 2,
+
+            /// The variant `LogSumExp` in the enum `Reducer`
+            LogSumExp = 3,
+
+            /// The variant `Max` in the enum `Reducer`
+            Max = 4,
+
+            /// The variant `Mean` in the enum `Reducer`
+            Mean = 5,
+
+            /// The variant `Min` in the enum `Reducer`
+            Min = 6,
+
+            /// The variant `Prod` in the enum `Reducer`
+            Prod = 7,
+
+            /// The variant `Sum` in the enum `Reducer`
+            Sum = 8,
+
+            /// The variant `SumSquare` in the enum `Reducer`
+            SumSquare = 9,
+        }
+
+        impl Reducer {
+            /// Array containing all valid variants of Reducer
+            pub const ENUM_VALUES: [Self; 10] = [
+                Self::L1,
+                Self::L2,
+                Self::LogSum,
+                Self::LogSumExp,
+                Self::Max,
+                Self::Mean,
+                Self::Min,
+                Self::Prod,
+                Self::Sum,
+                Self::SumSquare,
+            ];
+        }
+
+        impl ::core::convert::TryFrom<u8> for Reducer {
+            type Error = ::planus::errors::UnknownEnumTagKind;
+            #[inline]
+            fn try_from(
+                value: u8,
+            ) -> ::core::result::Result<Self, ::planus::errors::UnknownEnumTagKind> {
+                #[allow(clippy::match_single_binding)]
+                match value {
+                    0 => ::core::result::Result::Ok(Reducer::L1),
+                    1 => ::core::result::Result::Ok(Reducer::L2),
+                    2 => ::core::result::Result::Ok(Reducer::LogSum),
+                    3 => ::core::result::Result::Ok(Reducer::LogSumExp),
+                    4 => ::core::result::Result::Ok(Reducer::Max),
+                    5 => ::core::result::Result::Ok(Reducer::Mean),
+                    6 => ::core::result::Result::Ok(Reducer::Min),
+                    7 => ::core::result::Result::Ok(Reducer::Prod),
+                    8 => ::core::result::Result::Ok(Reducer::Sum),
+                    9 => ::core::result::Result::Ok(Reducer::SumSquare),
+
+                    _ => ::core::result::Result::Err(::planus::errors::UnknownEnumTagKind {
+                        tag: value as i128,
+                    }),
+                }
+            }
+        }
+
+        impl ::core::convert::From<Reducer> for u8 {
+            #[inline]
+            fn from(value: Reducer) -> Self {
+                value as u8
+            }
+        }
+
+        /// # Safety
+        /// The Planus compiler correctly calculates `ALIGNMENT` and `SIZE`.
+        unsafe impl ::planus::Primitive for Reducer {
+            const ALIGNMENT: usize = 1;
+            const SIZE: usize = 1;
+        }
+
+        impl ::planus::WriteAsPrimitive<Reducer> for Reducer {
+            #[inline]
+            fn write<const N: usize>(&self, cursor: ::planus::Cursor<'_, N>, buffer_position: u32) {
+                (*self as u8).write(cursor, buffer_position);
+            }
+        }
+
+        impl ::planus::WriteAs<Reducer> for Reducer {
+            type Prepared = Self;
+
+            #[inline]
+            fn prepare(&self, _builder: &mut ::planus::Builder) -> Reducer {
+                *self
+            }
+        }
+
+        impl ::planus::WriteAsDefault<Reducer, Reducer> for Reducer {
+            type Prepared = Self;
+
+            #[inline]
+            fn prepare(
+                &self,
+                _builder: &mut ::planus::Builder,
+                default: &Reducer,
+            ) -> ::core::option::Option<Reducer> {
+                if self == default {
+                    ::core::option::Option::None
+                } else {
+                    ::core::option::Option::Some(*self)
+                }
+            }
+        }
+
+        impl ::planus::WriteAsOptional<Reducer> for Reducer {
+            type Prepared = Self;
+
+            #[inline]
+            fn prepare(&self, _builder: &mut ::planus::Builder) -> ::core::option::Option<Reducer> {
+                ::core::option::Option::Some(*self)
+            }
+        }
+
+        impl<'buf> ::planus::TableRead<'buf> for Reducer {
+            #[inline]
+            fn from_buffer(
+                buffer: ::planus::SliceWithStartOffset<'buf>,
+                offset: usize,
+            ) -> ::core::result::Result<Self, ::planus::errors::ErrorKind> {
+                let n: u8 = ::planus::TableRead::from_buffer(buffer, offset)?;
+                ::core::result::Result::Ok(::core::convert::TryInto::try_into(n)?)
+            }
+        }
+
+        impl<'buf> ::planus::VectorReadInner<'buf> for Reducer {
+            type Error = ::planus::errors::UnknownEnumTag;
+            const STRIDE: usize = 1;
+            #[inline]
+            unsafe fn from_buffer(
+                buffer: ::planus::SliceWithStartOffset<'buf>,
+                offset: usize,
+            ) -> ::core::result::Result<Self, ::planus::errors::UnknownEnumTag> {
+                let value = unsafe { *buffer.buffer.get_unchecked(offset) };
+                let value: ::core::result::Result<Self, _> =
+                    ::core::convert::TryInto::try_into(value);
+                value.map_err(|error_kind| {
+                    error_kind.with_error_location(
+                        "Reducer",
+                        "VectorRead::from_buffer",
+                        buffer.offset_from_start,
+                    )
+                })
+            }
+        }
+
+        /// # Safety
+        /// The planus compiler generates implementations that initialize
+        /// the bytes in `write_values`.
+        unsafe impl ::planus::VectorWrite<Reducer> for Reducer {
+            const STRIDE: usize = 1;
+
+            type Value = Self;
+
+            #[inline]
+            fn prepare(&self, _builder: &mut ::planus::Builder) -> Self {
+                *self
+            }
+
+            #[inline]
+            unsafe fn write_values(
+                values: &[Self],
+                bytes: *mut ::core::mem::MaybeUninit<u8>,
+                buffer_position: u32,
+            ) {
+                let bytes = bytes as *mut [::core::mem::MaybeUninit<u8>; 1];
+                for (i, v) in ::core::iter::Iterator::enumerate(values.iter()) {
+                    ::planus::WriteAsPrimitive::write(
+                        v,
+                        ::planus::Cursor::new(unsafe { &mut *bytes.add(i) }),
+                        buffer_position - i as u32,
+                    );
+                }
+            }
+        }
+
+        /// The table `Reduce` in the namespace `tract`
+        ///
+        /// Generated from these locations:
+        /// * Table `Reduce` in the file `schema/tract.fbs:176`
+        #[derive(
+            Clone,
+            Debug,
+            PartialEq,
+            PartialOrd,
+            Eq,
+            Ord,
+            Hash,
+            ::serde::Serialize,
+            ::serde::Deserialize,
+        )]
+        pub struct Reduce {
+            /// The field `reducer` in the table `Reduce`
+            pub reducer: self::Reducer,
+            /// The field `axes` in the table `Reduce`
+            pub axes: ::planus::alloc::vec::Vec<u64>,
+        }
+
+        #[allow(clippy::derivable_impls)]
+        impl ::core::default::Default for Reduce {
+            fn default() -> Self {
+                Self { reducer: self::Reducer::L1, axes: ::core::default::Default::default() }
+            }
+        }
+
+        impl Reduce {
+            /// Creates a [ReduceBuilder] for serializing an instance of this table.
+            #[inline]
+            pub fn builder() -> ReduceBuilder<()> {
+                ReduceBuilder(())
+            }
+
+            #[allow(clippy::too_many_arguments)]
+            pub fn create(
+                builder: &mut ::planus::Builder,
+                field_reducer: impl ::planus::WriteAsDefault<self::Reducer, self::Reducer>,
+                field_axes: impl ::planus::WriteAs<::planus::Offset<[u64]>>,
+            ) -> ::planus::Offset<Self> {
+                let prepared_reducer = field_reducer.prepare(builder, &self::Reducer::L1);
+                let prepared_axes = field_axes.prepare(builder);
+
+                let mut table_writer: ::planus::table_writer::TableWriter<8> =
+                    ::core::default::Default::default();
+                table_writer.write_entry::<::planus::Offset<[u64]>>(1);
+                if prepared_reducer.is_some() {
+                    table_writer.write_entry::<self::Reducer>(0);
+                }
+
+                unsafe {
+                    table_writer.finish(builder, |object_writer| {
+                        object_writer.write::<_, _, 4>(&prepared_axes);
+                        if let ::core::option::Option::Some(prepared_reducer) = prepared_reducer {
+                            object_writer.write::<_, _, 1>(&prepared_reducer);
+                        }
+                    });
+                }
+                builder.current_offset()
+            }
+        }
+
+        impl ::planus::WriteAs<::planus::Offset<Reduce>> for Reduce {
+            type Prepared = ::planus::Offset<Self>;
+
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<Reduce> {
+                ::planus::WriteAsOffset::prepare(self, builder)
+            }
+        }
+
+        impl ::planus::WriteAsOptional<::planus::Offset<Reduce>> for Reduce {
+            type Prepared = ::planus::Offset<Self>;
+
+            #[inline]
+            fn prepare(
+                &self,
+                builder: &mut ::planus::Builder,
+            ) -> ::core::option::Option<::planus::Offset<Reduce>> {
+                ::core::option::Option::Some(::planus::WriteAsOffset::prepare(self, builder))
+            }
+        }
+
+        impl ::planus::WriteAsOffset<Reduce> for Reduce {
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<Reduce> {
+                Reduce::create(builder, self.reducer, &self.axes)
+            }
+        }
+
+        /// Builder for serializing an instance of the [Reduce] type.
+        ///
+        /// Can be created using the [Reduce::builder] method.
+        #[derive(Debug)]
+        #[must_use]
+        pub struct ReduceBuilder<State>(State);
+
+        impl ReduceBuilder<()> {
+            /// Setter for the [`reducer` field](Reduce#structfield.reducer).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn reducer<T0>(self, value: T0) -> ReduceBuilder<(T0,)>
+            where
+                T0: ::planus::WriteAsDefault<self::Reducer, self::Reducer>,
+            {
+                ReduceBuilder((value,))
+            }
+
+            /// Sets the [`reducer` field](Reduce#structfield.reducer) to the default value.
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn reducer_as_default(self) -> ReduceBuilder<(::planus::DefaultValue,)> {
+                self.reducer(::planus::DefaultValue)
+            }
+        }
+
+        impl<T0> ReduceBuilder<(T0,)> {
+            /// Setter for the [`axes` field](Reduce#structfield.axes).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn axes<T1>(self, value: T1) -> ReduceBuilder<(T0, T1)>
+            where
+                T1: ::planus::WriteAs<::planus::Offset<[u64]>>,
+            {
+                let (v0,) = self.0;
+                ReduceBuilder((v0, value))
+            }
+        }
+
+        impl<T0, T1> ReduceBuilder<(T0, T1)> {
+            /// Finish writing the builder to get an [Offset](::planus::Offset) to a serialized [Reduce].
+            #[inline]
+            pub fn finish(self, builder: &mut ::planus::Builder) -> ::planus::Offset<Reduce>
+            where
+                Self: ::planus::WriteAsOffset<Reduce>,
+            {
+                ::planus::WriteAsOffset::prepare(&self, builder)
+            }
+        }
+
+        impl<
+                T0: ::planus::WriteAsDefault<self::Reducer, self::Reducer>,
+                T1: ::planus::WriteAs<::planus::Offset<[u64]>>,
+            > ::planus::WriteAs<::planus::Offset<Reduce>> for ReduceBuilder<(T0, T1)>
+        {
+            type Prepared = ::planus::Offset<Reduce>;
+
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<Reduce> {
+                ::planus::WriteAsOffset::prepare(self, builder)
+            }
+        }
+
+        impl<
+                T0: ::planus::WriteAsDefault<self::Reducer, self::Reducer>,
+                T1: ::planus::WriteAs<::planus::Offset<[u64]>>,
+            > ::planus::WriteAsOptional<::planus::Offset<Reduce>> for ReduceBuilder<(T0, T1)>
+        {
+            type Prepared = ::planus::Offset<Reduce>;
+
+            #[inline]
+            fn prepare(
+                &self,
+                builder: &mut ::planus::Builder,
+            ) -> ::core::option::Option<::planus::Offset<Reduce>> {
+                ::core::option::Option::Some(::planus::WriteAsOffset::prepare(self, builder))
+            }
+        }
+
+        impl<
+                T0: ::planus::WriteAsDefault<self::Reducer, self::Reducer>,
+                T1: ::planus::WriteAs<::planus::Offset<[u64]>>,
+            > ::planus::WriteAsOffset<Reduce> for ReduceBuilder<(T0, T1)>
+        {
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<Reduce> {
+                let (v0, v1) = &self.0;
+                Reduce::create(builder, v0, v1)
+            }
+        }
+
+        /// Reference to a deserialized [Reduce].
+        #[derive(Copy, Clone)]
+        pub struct ReduceRef<'a>(#[allow(dead_code)] ::planus::table_reader::Table<'a>);
+
+        impl<'a> ReduceRef<'a> {
+            /// Getter for the [`reducer` field](Reduce#structfield.reducer).
+            #[inline]
+            pub fn reducer(&self) -> ::planus::Result<self::Reducer> {
+                ::core::result::Result::Ok(
+                    self.0.access(0, "Reduce", "reducer")?.unwrap_or(self::Reducer::L1),
+                )
+            }
+
+            /// Getter for the [`axes` field](Reduce#structfield.axes).
+            #[inline]
+            pub fn axes(&self) -> ::planus::Result<::planus::Vector<'a, u64>> {
+                self.0.access_required(1, "Reduce", "axes")
+            }
+        }
+
+        impl<'a> ::core::fmt::Debug for ReduceRef<'a> {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                let mut f = f.debug_struct("ReduceRef");
+                f.field("reducer", &self.reducer());
+                f.field("axes", &self.axes());
+                f.finish()
+            }
+        }
+
+        impl<'a> ::core::convert::TryFrom<ReduceRef<'a>> for Reduce {
+            type Error = ::planus::Error;
+
+            #[allow(unreachable_code)]
+            fn try_from(value: ReduceRef<'a>) -> ::planus::Result<Self> {
+                ::core::result::Result::Ok(Self {
+                    reducer: ::core::convert::TryInto::try_into(value.reducer()?)?,
+                    axes: value.axes()?.to_vec()?,
+                })
+            }
+        }
+
+        impl<'a> ::planus::TableRead<'a> for ReduceRef<'a> {
+            #[inline]
+            fn from_buffer(
+                buffer: ::planus::SliceWithStartOffset<'a>,
+                offset: usize,
+            ) -> ::core::result::Result<Self, ::planus::errors::ErrorKind> {
+                ::core::result::Result::Ok(Self(::planus::table_reader::Table::from_buffer(
+                    buffer, offset,
+                )?))
+            }
+        }
+
+        impl<'a> ::planus::VectorReadInner<'a> for ReduceRef<'a> {
+            type Error = ::planus::Error;
+            const STRIDE: usize = 4;
+
+            unsafe fn from_buffer(
+                buffer: ::planus::SliceWithStartOffset<'a>,
+                offset: usize,
+            ) -> ::planus::Result<Self> {
+                ::planus::TableRead::from_buffer(buffer, offset).map_err(|error_kind| {
+                    error_kind.with_error_location("[ReduceRef]", "get", buffer.offset_from_start)
+                })
+            }
+        }
+
+        /// # Safety
+        /// The planus compiler generates implementations that initialize
+        /// the bytes in `write_values`.
+        unsafe impl ::planus::VectorWrite<::planus::Offset<Reduce>> for Reduce {
+            type Value = ::planus::Offset<Reduce>;
+            const STRIDE: usize = 4;
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> Self::Value {
+                ::planus::WriteAs::prepare(self, builder)
+            }
+
+            #[inline]
+            unsafe fn write_values(
+                values: &[::planus::Offset<Reduce>],
+                bytes: *mut ::core::mem::MaybeUninit<u8>,
+                buffer_position: u32,
+            ) {
+                let bytes = bytes as *mut [::core::mem::MaybeUninit<u8>; 4];
+                for (i, v) in ::core::iter::Iterator::enumerate(values.iter()) {
+                    ::planus::WriteAsPrimitive::write(
+                        v,
+                        ::planus::Cursor::new(unsafe { &mut *bytes.add(i) }),
+                        buffer_position - (Self::STRIDE * i) as u32,
+                    );
+                }
+            }
+        }
+
+        impl<'a> ::planus::ReadAsRoot<'a> for ReduceRef<'a> {
+            fn read_as_root(slice: &'a [u8]) -> ::planus::Result<Self> {
+                ::planus::TableRead::from_buffer(
+                    ::planus::SliceWithStartOffset { buffer: slice, offset_from_start: 0 },
+                    0,
+                )
+                .map_err(|error_kind| {
+                    error_kind.with_error_location("[ReduceRef]", "read_as_root", 0)
+                })
+            }
+        }
+
+        /// The table `MatMul` in the namespace `tract`
+        ///
+        /// Generated from these locations:
+        /// * Table `MatMul` in the file `schema/tract.fbs:181`
+        #[derive(Clone, Debug, PartialEq, PartialOrd, ::serde::Serialize, ::serde::Deserialize)]
+        pub struct MatMul {
+            /// The field `a` in the table `MatMul`
+            pub a: ::planus::alloc::boxed::Box<self::Tensor>,
+            /// The field `a_trans` in the table `MatMul`
+            pub a_trans: bool,
+            /// The field `b_trans` in the table `MatMul`
+            pub b_trans: bool,
+            /// The field `c_trans` in the table `MatMul`
+            pub c_trans: bool,
+            /// The field `q_params` in the table `MatMul`
+            pub q_params: ::core::option::Option<::planus::alloc::boxed::Box<self::QParams>>,
+        }
+
+        #[allow(clippy::derivable_impls)]
+        impl ::core::default::Default for MatMul {
+            fn default() -> Self {
+                Self {
+                    a: ::core::default::Default::default(),
+                    a_trans: false,
+                    b_trans: false,
+                    c_trans: false,
+                    q_params: ::core::default::Default::default(),
+                }
+            }
+        }
+
+        impl MatMul {
+            /// Creates a [MatMulBuilder] for serializing an instance of this table.
+            #[inline]
+            pub fn builder() -> MatMulBuilder<()> {
+                MatMulBuilder(())
+            }
+
+            #[allow(clippy::too_many_arguments)]
+            pub fn create(
+                builder: &mut ::planus::Builder,
+                field_a: impl ::planus::WriteAs<::planus::Offset<self::Tensor>>,
+                field_a_trans: impl ::planus::WriteAsDefault<bool, bool>,
+                field_b_trans: impl ::planus::WriteAsDefault<bool, bool>,
+                field_c_trans: impl ::planus::WriteAsDefault<bool, bool>,
+                field_q_params: impl ::planus::WriteAsOptional<::planus::Offset<self::QParams>>,
+            ) -> ::planus::Offset<Self> {
+                let prepared_a = field_a.prepare(builder);
+                let prepared_a_trans = field_a_trans.prepare(builder, &false);
+                let prepared_b_trans = field_b_trans.prepare(builder, &false);
+                let prepared_c_trans = field_c_trans.prepare(builder, &false);
+                let prepared_q_params = field_q_params.prepare(builder);
+
+                let mut table_writer: ::planus::table_writer::TableWriter<14> =
+                    ::core::default::Default::default();
+                table_writer.write_entry::<::planus::Offset<self::Tensor>>(0);
+                if prepared_q_params.is_some() {
+                    table_writer.write_entry::<::planus::Offset<self::QParams>>(4);
+                }
+                if prepared_a_trans.is_some() {
+                    table_writer.write_entry::<bool>(1);
+                }
+                if prepared_b_trans.is_some() {
+                    table_writer.write_entry::<bool>(2);
+                }
+                if prepared_c_trans.is_some() {
+                    table_writer.write_entry::<bool>(3);
+                }
+
+                unsafe {
+                    table_writer.finish(builder, |object_writer| {
+                        object_writer.write::<_, _, 4>(&prepared_a);
+                        if let ::core::option::Option::Some(prepared_q_params) = prepared_q_params {
+                            object_writer.write::<_, _, 4>(&prepared_q_params);
+                        }
+                        if let ::core::option::Option::Some(prepared_a_trans) = prepared_a_trans {
+                            object_writer.write::<_, _, 1>(&prepared_a_trans);
+                        }
+                        if let ::core::option::Option::Some(prepared_b_trans) = prepared_b_trans {
+                            object_writer.write::<_, _, 1>(&prepared_b_trans);
+                        }
+                        if let ::core::option::Option::Some(prepared_c_trans) = prepared_c_trans {
+                            object_writer.write::<_, _, 1>(&prepared_c_trans);
+                        }
+                    });
+                }
+                builder.current_offset()
+            }
+        }
+
+        impl ::planus::WriteAs<::planus::Offset<MatMul>> for MatMul {
+            type Prepared = ::planus::Offset<Self>;
+
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<MatMul> {
+                ::planus::WriteAsOffset::prepare(self, builder)
+            }
+        }
+
+        impl ::planus::WriteAsOptional<::planus::Offset<MatMul>> for MatMul {
+            type Prepared = ::planus::Offset<Self>;
+
+            #[inline]
+            fn prepare(
+                &self,
+                builder: &mut ::planus::Builder,
+            ) -> ::core::option::Option<::planus::Offset<MatMul>> {
+                ::core::option::Option::Some(::planus::WriteAsOffset::prepare(self, builder))
+            }
+        }
+
+        impl ::planus::WriteAsOffset<MatMul> for MatMul {
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<MatMul> {
+                MatMul::create(
+                    builder,
+                    &self.a,
+                    self.a_trans,
+                    self.b_trans,
+                    self.c_trans,
+                    &self.q_params,
+                )
+            }
+        }
+
+        /// Builder for serializing an instance of the [MatMul] type.
+        ///
+        /// Can be created using the [MatMul::builder] method.
+        #[derive(Debug)]
+        #[must_use]
+        pub struct MatMulBuilder<State>(State);
+
+        impl MatMulBuilder<()> {
+            /// Setter for the [`a` field](MatMul#structfield.a).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn a<T0>(self, value: T0) -> MatMulBuilder<(T0,)>
+            where
+                T0: ::planus::WriteAs<::planus::Offset<self::Tensor>>,
+            {
+                MatMulBuilder((value,))
+            }
+        }
+
+        impl<T0> MatMulBuilder<(T0,)> {
+            /// Setter for the [`a_trans` field](MatMul#structfield.a_trans).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn a_trans<T1>(self, value: T1) -> MatMulBuilder<(T0, T1)>
+            where
+                T1: ::planus::WriteAsDefault<bool, bool>,
+            {
+                let (v0,) = self.0;
+                MatMulBuilder((v0, value))
+            }
+
+            /// Sets the [`a_trans` field](MatMul#structfield.a_trans) to the default value.
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn a_trans_as_default(self) -> MatMulBuilder<(T0, ::planus::DefaultValue)> {
+                self.a_trans(::planus::DefaultValue)
+            }
+        }
+
+        impl<T0, T1> MatMulBuilder<(T0, T1)> {
+            /// Setter for the [`b_trans` field](MatMul#structfield.b_trans).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn b_trans<T2>(self, value: T2) -> MatMulBuilder<(T0, T1, T2)>
+            where
+                T2: ::planus::WriteAsDefault<bool, bool>,
+            {
+                let (v0, v1) = self.0;
+                MatMulBuilder((v0, v1, value))
+            }
+
+            /// Sets the [`b_trans` field](MatMul#structfield.b_trans) to the default value.
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn b_trans_as_default(self) -> MatMulBuilder<(T0, T1, ::planus::DefaultValue)> {
+                self.b_trans(::planus::DefaultValue)
+            }
+        }
+
+        impl<T0, T1, T2> MatMulBuilder<(T0, T1, T2)> {
+            /// Setter for the [`c_trans` field](MatMul#structfield.c_trans).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn c_trans<T3>(self, value: T3) -> MatMulBuilder<(T0, T1, T2, T3)>
+            where
+                T3: ::planus::WriteAsDefault<bool, bool>,
+            {
+                let (v0, v1, v2) = self.0;
+                MatMulBuilder((v0, v1, v2, value))
+            }
+
+            /// Sets the [`c_trans` field](MatMul#structfield.c_trans) to the default value.
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn c_trans_as_default(self) -> MatMulBuilder<(T0, T1, T2, ::planus::DefaultValue)> {
+                self.c_trans(::planus::DefaultValue)
+            }
+        }
+
+        impl<T0, T1, T2, T3> MatMulBuilder<(T0, T1, T2, T3)> {
+            /// Setter for the [`q_params` field](MatMul#structfield.q_params).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn q_params<T4>(self, value: T4) -> MatMulBuilder<(T0, T1, T2, T3, T4)>
+            where
+                T4: ::planus::WriteAsOptional<::planus::Offset<self::QParams>>,
+            {
+                let (v0, v1, v2, v3) = self.0;
+                MatMulBuilder((v0, v1, v2, v3, value))
+            }
+
+            /// Sets the [`q_params` field](MatMul#structfield.q_params) to null.
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn q_params_as_null(self) -> MatMulBuilder<(T0, T1, T2, T3, ())> {
+                self.q_params(())
+            }
+        }
+
+        impl<T0, T1, T2, T3, T4> MatMulBuilder<(T0, T1, T2, T3, T4)> {
+            /// Finish writing the builder to get an [Offset](::planus::Offset) to a serialized [MatMul].
+            #[inline]
+            pub fn finish(self, builder: &mut ::planus::Builder) -> ::planus::Offset<MatMul>
+            where
+                Self: ::planus::WriteAsOffset<MatMul>,
+            {
+                ::planus::WriteAsOffset::prepare(&self, builder)
+            }
+        }
+
+        impl<
+                T0: ::planus::WriteAs<::planus::Offset<self::Tensor>>,
+                T1: ::planus::WriteAsDefault<bool, bool>,
+                T2: ::planus::WriteAsDefault<bool, bool>,
+                T3: ::planus::WriteAsDefault<bool, bool>,
+                T4: ::planus::WriteAsOptional<::planus::Offset<self::QParams>>,
+            > ::planus::WriteAs<::planus::Offset<MatMul>> for MatMulBuilder<(T0, T1, T2, T3, T4)>
+        {
+            type Prepared = ::planus::Offset<MatMul>;
+
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<MatMul> {
+                ::planus::WriteAsOffset::prepare(self, builder)
+            }
+        }
+
+        impl<
+                T0: ::planus::WriteAs<::planus::Offset<self::Tensor>>,
+                T1: ::planus::WriteAsDefault<bool, bool>,
+                T2: ::planus::WriteAsDefault<bool, bool>,
+                T3: ::planus::WriteAsDefault<bool, bool>,
+                T4: ::planus::WriteAsOptional<::planus::Offset<self::QParams>>,
+            > ::planus::WriteAsOptional<::planus::Offset<MatMul>>
+            for MatMulBuilder<(T0, T1, T2, T3, T4)>
+        {
+            type Prepared = ::planus::Offset<MatMul>;
+
+            #[inline]
+            fn prepare(
+                &self,
+                builder: &mut ::planus::Builder,
+            ) -> ::core::option::Option<::planus::Offset<MatMul>> {
+                ::core::option::Option::Some(::planus::WriteAsOffset::prepare(self, builder))
+            }
+        }
+
+        impl<
+                T0: ::planus::WriteAs<::planus::Offset<self::Tensor>>,
+                T1: ::planus::WriteAsDefault<bool, bool>,
+                T2: ::planus::WriteAsDefault<bool, bool>,
+                T3: ::planus::WriteAsDefault<bool, bool>,
+                T4: ::planus::WriteAsOptional<::planus::Offset<self::QParams>>,
+            > ::planus::WriteAsOffset<MatMul> for MatMulBuilder<(T0, T1, T2, T3, T4)>
+        {
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<MatMul> {
+                let (v0, v1, v2, v3, v4) = &self.0;
+                MatMul::create(builder, v0, v1, v2, v3, v4)
+            }
+        }
+
+        /// Reference to a deserialized [MatMul].
+        #[derive(Copy, Clone)]
+        pub struct MatMulRef<'a>(#[allow(dead_code)] ::planus::table_reader::Table<'a>);
+
+        impl<'a> MatMulRef<'a> {
+            /// Getter for the [`a` field](MatMul#structfield.a).
+            #[inline]
+            pub fn a(&self) -> ::planus::Result<self::TensorRef<'a>> {
+                self.0.access_required(0, "MatMul", "a")
+            }
+
+            /// Getter for the [`a_trans` field](MatMul#structfield.a_trans).
+            #[inline]
+            pub fn a_trans(&self) -> ::planus::Result<bool> {
+                ::core::result::Result::Ok(self.0.access(1, "MatMul", "a_trans")?.unwrap_or(false))
+            }
+
+            /// Getter for the [`b_trans` field](MatMul#structfield.b_trans).
+            #[inline]
+            pub fn b_trans(&self) -> ::planus::Result<bool> {
+                ::core::result::Result::Ok(self.0.access(2, "MatMul", "b_trans")?.unwrap_or(false))
+            }
+
+            /// Getter for the [`c_trans` field](MatMul#structfield.c_trans).
+            #[inline]
+            pub fn c_trans(&self) -> ::planus::Result<bool> {
+                ::core::result::Result::Ok(self.0.access(3, "MatMul", "c_trans")?.unwrap_or(false))
+            }
+
+            /// Getter for the [`q_params` field](MatMul#structfield.q_params).
+            #[inline]
+            pub fn q_params(
+                &self,
+            ) -> ::planus::Result<::core::option::Option<self::QParamsRef<'a>>> {
+                self.0.access(4, "MatMul", "q_params")
+            }
+        }
+
+        impl<'a> ::core::fmt::Debug for MatMulRef<'a> {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                let mut f = f.debug_struct("MatMulRef");
+                f.field("a", &self.a());
+                f.field("a_trans", &self.a_trans());
+                f.field("b_trans", &self.b_trans());
+                f.field("c_trans", &self.c_trans());
+                if let ::core::option::Option::Some(field_q_params) = self.q_params().transpose() {
+                    f.field("q_params", &field_q_params);
+                }
+                f.finish()
+            }
+        }
+
+        impl<'a> ::core::convert::TryFrom<MatMulRef<'a>> for MatMul {
+            type Error = ::planus::Error;
+
+            #[allow(unreachable_code)]
+            fn try_from(value: MatMulRef<'a>) -> ::planus::Result<Self> {
+                ::core::result::Result::Ok(Self {
+                    a: ::planus::alloc::boxed::Box::new(::core::convert::TryInto::try_into(
+                        value.a()?,
+                    )?),
+                    a_trans: ::core::convert::TryInto::try_into(value.a_trans()?)?,
+                    b_trans: ::core::convert::TryInto::try_into(value.b_trans()?)?,
+                    c_trans: ::core::convert::TryInto::try_into(value.c_trans()?)?,
+                    q_params: if let ::core::option::Option::Some(q_params) = value.q_params()? {
+                        ::core::option::Option::Some(::planus::alloc::boxed::Box::new(
+                            ::core::convert::TryInto::try_into(q_params)?,
+                        ))
+                    } else {
+                        ::core::option::Option::None
+                    },
+                })
+            }
+        }
+
+        impl<'a> ::planus::TableRead<'a> for MatMulRef<'a> {
+            #[inline]
+            fn from_buffer(
+                buffer: ::planus::SliceWithStartOffset<'a>,
+                offset: usize,
+            ) -> ::core::result::Result<Self, ::planus::errors::ErrorKind> {
+                ::core::result::Result::Ok(Self(::planus::table_reader::Table::from_buffer(
+                    buffer, offset,
+                )?))
+            }
+        }
+
+        impl<'a> ::planus::VectorReadInner<'a> for MatMulRef<'a> {
+            type Error = ::planus::Error;
+            const STRIDE: usize = 4;
+
+            unsafe fn from_buffer(
+                buffer: ::planus::SliceWithStartOffset<'a>,
+                offset: usize,
+            ) -> ::planus::Result<Self> {
+                ::planus::TableRead::from_buffer(buffer, offset).map_err(|error_kind| {
+                    error_kind.with_error_location("[MatMulRef]", "get", buffer.offset_from_start)
+                })
+            }
+        }
+
+        /// # Safety
+        /// The planus compiler generates implementations that initialize
+        /// the bytes in `write_values`.
+        unsafe impl ::planus::VectorWrite<::planus::Offset<MatMul>> for MatMul {
+            type Value = ::planus::Offset<MatMul>;
+            const STRIDE: usize = 4;
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> Self::Value {
+                ::planus::WriteAs::prepare(self, builder)
+            }
+
+            #[inline]
+            unsafe fn write_values(
+                values: &[::planus::Offset<MatMul>],
+                bytes: *mut ::core::mem::MaybeUninit<u8>,
+                buffer_position: u32,
+            ) {
+                let bytes = bytes as *mut [::core::mem::MaybeUninit<u8>; 4];
+                for (i, v) in ::core::iter::Iterator::enumerate(values.iter()) {
+                    ::planus::WriteAsPrimitive::write(
+                        v,
+                        ::planus::Cursor::new(unsafe { &mut *bytes.add(i) }),
+                        buffer_position - (Self::STRIDE * i) as u32,
+                    );
+                }
+            }
+        }
+
+        impl<'a> ::planus::ReadAsRoot<'a> for MatMulRef<'a> {
+            fn read_as_root(slice: &'a [u8]) -> ::planus::Result<Self> {
+                ::planus::TableRead::from_buffer(
+                    ::planus::SliceWithStartOffset { buffer: slice, offset_from_start: 0 },
+                    0,
+                )
+                .map_err(|error_kind| {
+                    error_kind.with_error_location("[MatMulRef]", "read_as_root", 0)
+                })
+            }
+        }
+
+        /// The table `AddDims` in the namespace `tract`
+        ///
+        /// Generated from these locations:
+        /// * Table `AddDims` in the file `schema/tract.fbs:189`
+        #[derive(
+            Clone,
+            Debug,
+            PartialEq,
+            PartialOrd,
+            Eq,
+            Ord,
+            Hash,
+            ::serde::Serialize,
+            ::serde::Deserialize,
+        )]
+        pub struct AddDims {
+            /// The field `axes` in the table `AddDims`
+            pub axes: ::planus::alloc::vec::Vec<u64>,
+        }
+
+        #[allow(clippy::derivable_impls)]
+        impl ::core::default::Default for AddDims {
+            fn default() -> Self {
+                Self { axes: ::core::default::Default::default() }
+            }
+        }
+
+        impl AddDims {
+            /// Creates a [AddDimsBuilder] for serializing an instance of this table.
+            #[inline]
+            pub fn builder() -> AddDimsBuilder<()> {
+                AddDimsBuilder(())
+            }
+
+            #[allow(clippy::too_many_arguments)]
+            pub fn create(
+                builder: &mut ::planus::Builder,
+                field_axes: impl ::planus::WriteAs<::planus::Offset<[u64]>>,
+            ) -> ::planus::Offset<Self> {
+                let prepared_axes = field_axes.prepare(builder);
+
+                let mut table_writer: ::planus::table_writer::TableWriter<6> =
+                    ::core::default::Default::default();
+                table_writer.write_entry::<::planus::Offset<[u64]>>(0);
+
+                unsafe {
+                    table_writer.finish(builder, |object_writer| {
+                        object_writer.write::<_, _, 4>(&prepared_axes);
+                    });
+                }
+                builder.current_offset()
+            }
+        }
+
+        impl ::planus::WriteAs<::planus::Offset<AddDims>> for AddDims {
+            type Prepared = ::planus::Offset<Self>;
+
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<AddDims> {
+                ::planus::WriteAsOffset::prepare(self, builder)
+            }
+        }
+
+        impl ::planus::WriteAsOptional<::planus::Offset<AddDims>> for AddDims {
+            type Prepared = ::planus::Offset<Self>;
+
+            #[inline]
+            fn prepare(
+                &self,
+                builder: &mut ::planus::Builder,
+            ) -> ::core::option::Option<::planus::Offset<AddDims>> {
+                ::core::option::Option::Some(::planus::WriteAsOffset::prepare(self, builder))
+            }
+        }
+
+        impl ::planus::WriteAsOffset<AddDims> for AddDims {
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<AddDims> {
+                AddDims::create(builder, &self.axes)
+            }
+        }
+
+        /// Builder for serializing an instance of the [AddDims] type.
+        ///
+        /// Can be created using the [AddDims::builder] method.
+        #[derive(Debug)]
+        #[must_use]
+        pub struct AddDimsBuilder<State>(State);
+
+        impl AddDimsBuilder<()> {
+            /// Setter for the [`axes` field](AddDims#structfield.axes).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn axes<T0>(self, value: T0) -> AddDimsBuilder<(T0,)>
+            where
+                T0: ::planus::WriteAs<::planus::Offset<[u64]>>,
+            {
+                AddDimsBuilder((value,))
+            }
+        }
+
+        impl<T0> AddDimsBuilder<(T0,)> {
+            /// Finish writing the builder to get an [Offset](::planus::Offset) to a serialized [AddDims].
+            #[inline]
+            pub fn finish(self, builder: &mut ::planus::Builder) -> ::planus::Offset<AddDims>
+            where
+                Self: ::planus::WriteAsOffset<AddDims>,
+            {
+                ::planus::WriteAsOffset::prepare(&self, builder)
+            }
+        }
+
+        impl<T0: ::planus::WriteAs<::planus::Offset<[u64]>>>
+            ::planus::WriteAs<::planus::Offset<AddDims>> for AddDimsBuilder<(T0,)>
+        {
+            type Prepared = ::planus::Offset<AddDims>;
+
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<AddDims> {
+                ::planus::WriteAsOffset::prepare(self, builder)
+            }
+        }
+
+        impl<T0: ::planus::WriteAs<::planus::Offset<[u64]>>>
+            ::planus::WriteAsOptional<::planus::Offset<AddDims>> for AddDimsBuilder<(T0,)>
+        {
+            type Prepared = ::planus::Offset<AddDims>;
+
+            #[inline]
+            fn prepare(
+                &self,
+                builder: &mut ::planus::Builder,
+            ) -> ::core::option::Option<::planus::Offset<AddDims>> {
+                ::core::option::Option::Some(::planus::WriteAsOffset::prepare(self, builder))
+            }
+        }
+
+        impl<T0: ::planus::WriteAs<::planus::Offset<[u64]>>> ::planus::WriteAsOffset<AddDims>
+            for AddDimsBuilder<(T0,)>
+        {
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<AddDims> {
+                let (v0,) = &self.0;
+                AddDims::create(builder, v0)
+            }
+        }
+
+        /// Reference to a deserialized [AddDims].
+        #[derive(Copy, Clone)]
+        pub struct AddDimsRef<'a>(#[allow(dead_code)] ::planus::table_reader::Table<'a>);
+
+        impl<'a> AddDimsRef<'a> {
+            /// Getter for the [`axes` field](AddDims#structfield.axes).
+            #[inline]
+            pub fn axes(&self) -> ::planus::Result<::planus::Vector<'a, u64>> {
+                self.0.access_required(0, "AddDims", "axes")
+            }
+        }
+
+        impl<'a> ::core::fmt::Debug for AddDimsRef<'a> {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                let mut f = f.debug_struct("AddDimsRef");
+                f.field("axes", &self.axes());
+                f.finish()
+            }
+        }
+
+        impl<'a> ::core::convert::TryFrom<AddDimsRef<'a>> for AddDims {
+            type Error = ::planus::Error;
+
+            #[allow(unreachable_code)]
+            fn try_from(value: AddDimsRef<'a>) -> ::planus::Result<Self> {
+                ::core::result::Result::Ok(Self { axes: value.axes()?.to_vec()? })
+            }
+        }
+
+        impl<'a> ::planus::TableRead<'a> for AddDimsRef<'a> {
+            #[inline]
+            fn from_buffer(
+                buffer: ::planus::SliceWithStartOffset<'a>,
+                offset: usize,
+            ) -> ::core::result::Result<Self, ::planus::errors::ErrorKind> {
+                ::core::result::Result::Ok(Self(::planus::table_reader::Table::from_buffer(
+                    buffer, offset,
+                )?))
+            }
+        }
+
+        impl<'a> ::planus::VectorReadInner<'a> for AddDimsRef<'a> {
+            type Error = ::planus::Error;
+            const STRIDE: usize = 4;
+
+            unsafe fn from_buffer(
+                buffer: ::planus::SliceWithStartOffset<'a>,
+                offset: usize,
+            ) -> ::planus::Result<Self> {
+                ::planus::TableRead::from_buffer(buffer, offset).map_err(|error_kind| {
+                    error_kind.with_error_location("[AddDimsRef]", "get", buffer.offset_from_start)
+                })
+            }
+        }
+
+        /// # Safety
+        /// The planus compiler generates implementations that initialize
+        /// the bytes in `write_values`.
+        unsafe impl ::planus::VectorWrite<::planus::Offset<AddDims>> for AddDims {
+            type Value = ::planus::Offset<AddDims>;
+            const STRIDE: usize = 4;
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> Self::Value {
+                ::planus::WriteAs::prepare(self, builder)
+            }
+
+            #[inline]
+            unsafe fn write_values(
+                values: &[::planus::Offset<AddDims>],
+                bytes: *mut ::core::mem::MaybeUninit<u8>,
+                buffer_position: u32,
+            ) {
+                let bytes = bytes as *mut [::core::mem::MaybeUninit<u8>; 4];
+                for (i, v) in ::core::iter::Iterator::enumerate(values.iter()) {
+                    ::planus::WriteAsPrimitive::write(
+                        v,
+                        ::planus::Cursor::new(unsafe { &mut *bytes.add(i) }),
+                        buffer_position - (Self::STRIDE * i) as u32,
+                    );
+                }
+            }
+        }
+
+        impl<'a> ::planus::ReadAsRoot<'a> for AddDimsRef<'a> {
+            fn read_as_root(slice: &'a [u8]) -> ::planus::Result<Self> {
+                ::planus::TableRead::from_buffer(
+                    ::planus::SliceWithStartOffset { buffer: slice, offset_from_start: 0 },
+                    0,
+                )
+                .map_err(|error_kind| {
+                    error_kind.with_error_location("[AddDimsRef]", "read_as_root", 0)
+                })
+            }
+        }
+
+        /// The table `RmDims` in the namespace `tract`
+        ///
+        /// Generated from these locations:
+        /// * Table `RmDims` in the file `schema/tract.fbs:193`
+        #[derive(
+            Clone,
+            Debug,
+            PartialEq,
+            PartialOrd,
+            Eq,
+            Ord,
+            Hash,
+            ::serde::Serialize,
+            ::serde::Deserialize,
+        )]
+        pub struct RmDims {
+            /// The field `axes` in the table `RmDims`
+            pub axes: ::planus::alloc::vec::Vec<u64>,
+        }
+
+        #[allow(clippy::derivable_impls)]
+        impl ::core::default::Default for RmDims {
+            fn default() -> Self {
+                Self { axes: ::core::default::Default::default() }
+            }
+        }
+
+        impl RmDims {
+            /// Creates a [RmDimsBuilder] for serializing an instance of this table.
+            #[inline]
+            pub fn builder() -> RmDimsBuilder<()> {
+                RmDimsBuilder(())
+            }
+
+            #[allow(clippy::too_many_arguments)]
+            pub fn create(
+                builder: &mut ::planus::Builder,
+                field_axes: impl ::planus::WriteAs<::planus::Offset<[u64]>>,
+            ) -> ::planus::Offset<Self> {
+                let prepared_axes = field_axes.prepare(builder);
+
+                let mut table_writer: ::planus::table_writer::TableWriter<6> =
+                    ::core::default::Default::default();
+                table_writer.write_entry::<::planus::Offset<[u64]>>(0);
+
+                unsafe {
+                    table_writer.finish(builder, |object_writer| {
+                        object_writer.write::<_, _, 4>(&prepared_axes);
+                    });
+                }
+                builder.current_offset()
+            }
+        }
+
+        impl ::planus::WriteAs<::planus::Offset<RmDims>> for RmDims {
+            type Prepared = ::planus::Offset<Self>;
+
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<RmDims> {
+                ::planus::WriteAsOffset::prepare(self, builder)
+            }
+        }
+
+        impl ::planus::WriteAsOptional<::planus::Offset<RmDims>> for RmDims {
+            type Prepared = ::planus::Offset<Self>;
+
+            #[inline]
+            fn prepare(
+                &self,
+                builder: &mut ::planus::Builder,
+            ) -> ::core::option::Option<::planus::Offset<RmDims>> {
+                ::core::option::Option::Some(::planus::WriteAsOffset::prepare(self, builder))
+            }
+        }
+
+        impl ::planus::WriteAsOffset<RmDims> for RmDims {
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<RmDims> {
+                RmDims::create(builder, &self.axes)
+            }
+        }
+
+        /// Builder for serializing an instance of the [RmDims] type.
+        ///
+        /// Can be created using the [RmDims::builder] method.
+        #[derive(Debug)]
+        #[must_use]
+        pub struct RmDimsBuilder<State>(State);
+
+        impl RmDimsBuilder<()> {
+            /// Setter for the [`axes` field](RmDims#structfield.axes).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn axes<T0>(self, value: T0) -> RmDimsBuilder<(T0,)>
+            where
+                T0: ::planus::WriteAs<::planus::Offset<[u64]>>,
+            {
+                RmDimsBuilder((value,))
+            }
+        }
+
+        impl<T0> RmDimsBuilder<(T0,)> {
+            /// Finish writing the builder to get an [Offset](::planus::Offset) to a serialized [RmDims].
+            #[inline]
+            pub fn finish(self, builder: &mut ::planus::Builder) -> ::planus::Offset<RmDims>
+            where
+                Self: ::planus::WriteAsOffset<RmDims>,
+            {
+                ::planus::WriteAsOffset::prepare(&self, builder)
+            }
+        }
+
+        impl<T0: ::planus::WriteAs<::planus::Offset<[u64]>>>
+            ::planus::WriteAs<::planus::Offset<RmDims>> for RmDimsBuilder<(T0,)>
+        {
+            type Prepared = ::planus::Offset<RmDims>;
+
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<RmDims> {
+                ::planus::WriteAsOffset::prepare(self, builder)
+            }
+        }
+
+        impl<T0: ::planus::WriteAs<::planus::Offset<[u64]>>>
+            ::planus::WriteAsOptional<::planus::Offset<RmDims>> for RmDimsBuilder<(T0,)>
+        {
+            type Prepared = ::planus::Offset<RmDims>;
+
+            #[inline]
+            fn prepare(
+                &self,
+                builder: &mut ::planus::Builder,
+            ) -> ::core::option::Option<::planus::Offset<RmDims>> {
+                ::core::option::Option::Some(::planus::WriteAsOffset::prepare(self, builder))
+            }
+        }
+
+        impl<T0: ::planus::WriteAs<::planus::Offset<[u64]>>> ::planus::WriteAsOffset<RmDims>
+            for RmDimsBuilder<(T0,)>
+        {
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<RmDims> {
+                let (v0,) = &self.0;
+                RmDims::create(builder, v0)
+            }
+        }
+
+        /// Reference to a deserialized [RmDims].
+        #[derive(Copy, Clone)]
+        pub struct RmDimsRef<'a>(#[allow(dead_code)] ::planus::table_reader::Table<'a>);
+
+        impl<'a> RmDimsRef<'a> {
+            /// Getter for the [`axes` field](RmDims#structfield.axes).
+            #[inline]
+            pub fn axes(&self) -> ::planus::Result<::planus::Vector<'a, u64>> {
+                self.0.access_required(0, "RmDims", "axes")
+            }
+        }
+
+        impl<'a> ::core::fmt::Debug for RmDimsRef<'a> {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                let mut f = f.debug_struct("RmDimsRef");
+                f.field("axes", &self.axes());
+                f.finish()
+            }
+        }
+
+        impl<'a> ::core::convert::TryFrom<RmDimsRef<'a>> for RmDims {
+            type Error = ::planus::Error;
+
+            #[allow(unreachable_code)]
+            fn try_from(value: RmDimsRef<'a>) -> ::planus::Result<Self> {
+                ::core::result::Result::Ok(Self { axes: value.axes()?.to_vec()? })
+            }
+        }
+
+        impl<'a> ::planus::TableRead<'a> for RmDimsRef<'a> {
+            #[inline]
+            fn from_buffer(
+                buffer: ::planus::SliceWithStartOffset<'a>,
+                offset: usize,
+            ) -> ::core::result::Result<Self, ::planus::errors::ErrorKind> {
+                ::core::result::Result::Ok(Self(::planus::table_reader::Table::from_buffer(
+                    buffer, offset,
+                )?))
+            }
+        }
+
+        impl<'a> ::planus::VectorReadInner<'a> for RmDimsRef<'a> {
+            type Error = ::planus::Error;
+            const STRIDE: usize = 4;
+
+            unsafe fn from_buffer(
+                buffer: ::planus::SliceWithStartOffset<'a>,
+                offset: usize,
+            ) -> ::planus::Result<Self> {
+                ::planus::TableRead::from_buffer(buffer, offset).map_err(|error_kind| {
+                    error_kind.with_error_location("[RmDimsRef]", "get", buffer.offset_from_start)
+                })
+            }
+        }
+
+        /// # Safety
+        /// The planus compiler generates implementations that initialize
+        /// the bytes in `write_values`.
+        unsafe impl ::planus::VectorWrite<::planus::Offset<RmDims>> for RmDims {
+            type Value = ::planus::Offset<RmDims>;
+            const STRIDE: usize = 4;
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> Self::Value {
+                ::planus::WriteAs::prepare(self, builder)
+            }
+
+            #[inline]
+            unsafe fn write_values(
+                values: &[::planus::Offset<RmDims>],
+                bytes: *mut ::core::mem::MaybeUninit<u8>,
+                buffer_position: u32,
+            ) {
+                let bytes = bytes as *mut [::core::mem::MaybeUninit<u8>; 4];
+                for (i, v) in ::core::iter::Iterator::enumerate(values.iter()) {
+                    ::planus::WriteAsPrimitive::write(
+                        v,
+                        ::planus::Cursor::new(unsafe { &mut *bytes.add(i) }),
+                        buffer_position - (Self::STRIDE * i) as u32,
+                    );
+                }
+            }
+        }
+
+        impl<'a> ::planus::ReadAsRoot<'a> for RmDimsRef<'a> {
+            fn read_as_root(slice: &'a [u8]) -> ::planus::Result<Self> {
+                ::planus::TableRead::from_buffer(
+                    ::planus::SliceWithStartOffset { buffer: slice, offset_from_start: 0 },
+                    0,
+                )
+                .map_err(|error_kind| {
+                    error_kind.with_error_location("[RmDimsRef]", "read_as_root", 0)
+                })
+            }
+        }
+
+        /// The table `PermuteAxes` in the namespace `tract`
+        ///
+        /// Generated from these locations:
+        /// * Table `PermuteAxes` in the file `schema/tract.fbs:197`
+        #[derive(
+            Clone,
+            Debug,
+            PartialEq,
+            PartialOrd,
+            Eq,
+            Ord,
+            Hash,
+            ::serde::Serialize,
+            ::serde::Deserialize,
+        )]
+        pub struct PermuteAxes {
+            /// The field `axes` in the table `PermuteAxes`
+            pub axes: ::core::option::Option<::planus::alloc::vec::Vec<u64>>,
+        }
+
+        #[allow(clippy::derivable_impls)]
+        impl ::core::default::Default for PermuteAxes {
+            fn default() -> Self {
+                Self { axes: ::core::default::Default::default() }
+            }
+        }
+
+        impl PermuteAxes {
+            /// Creates a [PermuteAxesBuilder] for serializing an instance of this table.
+            #[inline]
+            pub fn builder() -> PermuteAxesBuilder<()> {
+                PermuteAxesBuilder(())
+            }
+
+            #[allow(clippy::too_many_arguments)]
+            pub fn create(
+                builder: &mut ::planus::Builder,
+                field_axes: impl ::planus::WriteAsOptional<::planus::Offset<[u64]>>,
+            ) -> ::planus::Offset<Self> {
+                let prepared_axes = field_axes.prepare(builder);
+
+                let mut table_writer: ::planus::table_writer::TableWriter<6> =
+                    ::core::default::Default::default();
+                if prepared_axes.is_some() {
+                    table_writer.write_entry::<::planus::Offset<[u64]>>(0);
+                }
+
+                unsafe {
+                    table_writer.finish(builder, |object_writer| {
+                        if let ::core::option::Option::Some(prepared_axes) = prepared_axes {
+                            object_writer.write::<_, _, 4>(&prepared_axes);
+                        }
+                    });
+                }
+                builder.current_offset()
+            }
+        }
+
+        impl ::planus::WriteAs<::planus::Offset<PermuteAxes>> for PermuteAxes {
+            type Prepared = ::planus::Offset<Self>;
+
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<PermuteAxes> {
+                ::planus::WriteAsOffset::prepare(self, builder)
+            }
+        }
+
+        impl ::planus::WriteAsOptional<::planus::Offset<PermuteAxes>> for PermuteAxes {
+            type Prepared = ::planus::Offset<Self>;
+
+            #[inline]
+            fn prepare(
+                &self,
+                builder: &mut ::planus::Builder,
+            ) -> ::core::option::Option<::planus::Offset<PermuteAxes>> {
+                ::core::option::Option::Some(::planus::WriteAsOffset::prepare(self, builder))
+            }
+        }
+
+        impl ::planus::WriteAsOffset<PermuteAxes> for PermuteAxes {
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<PermuteAxes> {
+                PermuteAxes::create(builder, &self.axes)
+            }
+        }
+
+        /// Builder for serializing an instance of the [PermuteAxes] type.
+        ///
+        /// Can be created using the [PermuteAxes::builder] method.
+        #[derive(Debug)]
+        #[must_use]
+        pub struct PermuteAxesBuilder<State>(State);
+
+        impl PermuteAxesBuilder<()> {
+            /// Setter for the [`axes` field](PermuteAxes#structfield.axes).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn axes<T0>(self, value: T0) -> PermuteAxesBuilder<(T0,)>
+            where
+                T0: ::planus::WriteAsOptional<::planus::Offset<[u64]>>,
+            {
+                PermuteAxesBuilder((value,))
+            }
+
+            /// Sets the [`axes` field](PermuteAxes#structfield.axes) to null.
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn axes_as_null(self) -> PermuteAxesBuilder<((),)> {
+                self.axes(())
+            }
+        }
+
+        impl<T0> PermuteAxesBuilder<(T0,)> {
+            /// Finish writing the builder to get an [Offset](::planus::Offset) to a serialized [PermuteAxes].
+            #[inline]
+            pub fn finish(self, builder: &mut ::planus::Builder) -> ::planus::Offset<PermuteAxes>
+            where
+                Self: ::planus::WriteAsOffset<PermuteAxes>,
+            {
+                ::planus::WriteAsOffset::prepare(&self, builder)
+            }
+        }
+
+        impl<T0: ::planus::WriteAsOptional<::planus::Offset<[u64]>>>
+            ::planus::WriteAs<::planus::Offset<PermuteAxes>> for PermuteAxesBuilder<(T0,)>
+        {
+            type Prepared = ::planus::Offset<PermuteAxes>;
+
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<PermuteAxes> {
+                ::planus::WriteAsOffset::prepare(self, builder)
+            }
+        }
+
+        impl<T0: ::planus::WriteAsOptional<::planus::Offset<[u64]>>>
+            ::planus::WriteAsOptional<::planus::Offset<PermuteAxes>> for PermuteAxesBuilder<(T0,)>
+        {
+            type Prepared = ::planus::Offset<PermuteAxes>;
+
+            #[inline]
+            fn prepare(
+                &self,
+                builder: &mut ::planus::Builder,
+            ) -> ::core::option::Option<::planus::Offset<PermuteAxes>> {
+                ::core::option::Option::Some(::planus::WriteAsOffset::prepare(self, builder))
+            }
+        }
+
+        impl<T0: ::planus::WriteAsOptional<::planus::Offset<[u64]>>>
+            ::planus::WriteAsOffset<PermuteAxes> for PermuteAxesBuilder<(T0,)>
+        {
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<PermuteAxes> {
+                let (v0,) = &self.0;
+                PermuteAxes::create(builder, v0)
+            }
+        }
+
+        /// Reference to a deserialized [PermuteAxes].
+        #[derive(Copy, Clone)]
+        pub struct PermuteAxesRef<'a>(#[allow(dead_code)] ::planus::table_reader::Table<'a>);
+
+        impl<'a> PermuteAxesRef<'a> {
+            /// Getter for the [`axes` field](PermuteAxes#structfield.axes).
+            #[inline]
+            pub fn axes(
+                &self,
+            ) -> ::planus::Result<::core::option::Option<::planus::Vector<'a, u64>>> {
+                self.0.access(0, "PermuteAxes", "axes")
+            }
+        }
+
+        impl<'a> ::core::fmt::Debug for PermuteAxesRef<'a> {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                let mut f = f.debug_struct("PermuteAxesRef");
+                if let ::core::option::Option::Some(field_axes) = self.axes().transpose() {
+                    f.field("axes", &field_axes);
+                }
+                f.finish()
+            }
+        }
+
+        impl<'a> ::core::convert::TryFrom<PermuteAxesRef<'a>> for PermuteAxes {
+            type Error = ::planus::Error;
+
+            #[allow(unreachable_code)]
+            fn try_from(value: PermuteAxesRef<'a>) -> ::planus::Result<Self> {
+                ::core::result::Result::Ok(Self {
+                    axes: if let ::core::option::Option::Some(axes) = value.axes()? {
+                        ::core::option::Option::Some(axes.to_vec()?)
+                    } else {
+                        ::core::option::Option::None
+                    },
+                })
+            }
+        }
+
+        impl<'a> ::planus::TableRead<'a> for PermuteAxesRef<'a> {
+            #[inline]
+            fn from_buffer(
+                buffer: ::planus::SliceWithStartOffset<'a>,
+                offset: usize,
+            ) -> ::core::result::Result<Self, ::planus::errors::ErrorKind> {
+                ::core::result::Result::Ok(Self(::planus::table_reader::Table::from_buffer(
+                    buffer, offset,
+                )?))
+            }
+        }
+
+        impl<'a> ::planus::VectorReadInner<'a> for PermuteAxesRef<'a> {
+            type Error = ::planus::Error;
+            const STRIDE: usize = 4;
+
+            unsafe fn from_buffer(
+                buffer: ::planus::SliceWithStartOffset<'a>,
+                offset: usize,
+            ) -> ::planus::Result<Self> {
+                ::planus::TableRead::from_buffer(buffer, offset).map_err(|error_kind| {
+                    error_kind.with_error_location(
+                        "[PermuteAxesRef]",
+                        "get",
+                        buffer.offset_from_start,
+                    )
+                })
+            }
+        }
+
+        /// # Safety
+        /// The planus compiler generates implementations that initialize
+        /// the bytes in `write_values`.
+        unsafe impl ::planus::VectorWrite<::planus::Offset<PermuteAxes>> for PermuteAxes {
+            type Value = ::planus::Offset<PermuteAxes>;
+            const STRIDE: usize = 4;
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> Self::Value {
+                ::planus::WriteAs::prepare(self, builder)
+            }
+
+            #[inline]
+            unsafe fn write_values(
+                values: &[::planus::Offset<PermuteAxes>],
+                bytes: *mut ::core::mem::MaybeUninit<u8>,
+                buffer_position: u32,
+            ) {
+                let bytes = bytes as *mut [::core::mem::MaybeUninit<u8>; 4];
+                for (i, v) in ::core::iter::Iterator::enumerate(values.iter()) {
+                    ::planus::WriteAsPrimitive::write(
+                        v,
+                        ::planus::Cursor::new(unsafe { &mut *bytes.add(i) }),
+                        buffer_position - (Self::STRIDE * i) as u32,
+                    );
+                }
+            }
+        }
+
+        impl<'a> ::planus::ReadAsRoot<'a> for PermuteAxesRef<'a> {
+            fn read_as_root(slice: &'a [u8]) -> ::planus::Result<Self> {
+                ::planus::TableRead::from_buffer(
+                    ::planus::SliceWithStartOffset { buffer: slice, offset_from_start: 0 },
+                    0,
+                )
+                .map_err(|error_kind| {
+                    error_kind.with_error_location("[PermuteAxesRef]", "read_as_root", 0)
+                })
+            }
+        }
+
+        /// The table `Reshape` in the namespace `tract`
+        ///
+        /// Generated from these locations:
+        /// * Table `Reshape` in the file `schema/tract.fbs:202`
+        #[derive(
+            Clone,
+            Debug,
+            PartialEq,
+            PartialOrd,
+            Eq,
+            Ord,
+            Hash,
+            ::serde::Serialize,
+            ::serde::Deserialize,
+        )]
+        pub struct Reshape {
+            /// The field `shape` in the table `Reshape`
+            pub shape: ::planus::alloc::vec::Vec<u64>,
+        }
+
+        #[allow(clippy::derivable_impls)]
+        impl ::core::default::Default for Reshape {
+            fn default() -> Self {
+                Self { shape: ::core::default::Default::default() }
+            }
+        }
+
+        impl Reshape {
+            /// Creates a [ReshapeBuilder] for serializing an instance of this table.
+            #[inline]
+            pub fn builder() -> ReshapeBuilder<()> {
+                ReshapeBuilder(())
+            }
+
+            #[allow(clippy::too_many_arguments)]
+            pub fn create(
+                builder: &mut ::planus::Builder,
+                field_shape: impl ::planus::WriteAs<::planus::Offset<[u64]>>,
+            ) -> ::planus::Offset<Self> {
+                let prepared_shape = field_shape.prepare(builder);
+
+                let mut table_writer: ::planus::table_writer::TableWriter<6> =
+                    ::core::default::Default::default();
+                table_writer.write_entry::<::planus::Offset<[u64]>>(0);
+
+                unsafe {
+                    table_writer.finish(builder, |object_writer| {
+                        object_writer.write::<_, _, 4>(&prepared_shape);
+                    });
+                }
+                builder.current_offset()
+            }
+        }
+
+        impl ::planus::WriteAs<::planus::Offset<Reshape>> for Reshape {
+            type Prepared = ::planus::Offset<Self>;
+
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<Reshape> {
+                ::planus::WriteAsOffset::prepare(self, builder)
+            }
+        }
+
+        impl ::planus::WriteAsOptional<::planus::Offset<Reshape>> for Reshape {
+            type Prepared = ::planus::Offset<Self>;
+
+            #[inline]
+            fn prepare(
+                &self,
+                builder: &mut ::planus::Builder,
+            ) -> ::core::option::Option<::planus::Offset<Reshape>> {
+                ::core::option::Option::Some(::planus::WriteAsOffset::prepare(self, builder))
+            }
+        }
+
+        impl ::planus::WriteAsOffset<Reshape> for Reshape {
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<Reshape> {
+                Reshape::create(builder, &self.shape)
+            }
+        }
+
+        /// Builder for serializing an instance of the [Reshape] type.
+        ///
+        /// Can be created using the [Reshape::builder] method.
+        #[derive(Debug)]
+        #[must_use]
+        pub struct ReshapeBuilder<State>(State);
+
+        impl ReshapeBuilder<()> {
+            /// Setter for the [`shape` field](Reshape#structfield.shape).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn shape<T0>(self, value: T0) -> ReshapeBuilder<(T0,)>
+            where
+                T0: ::planus::WriteAs<::planus::Offset<[u64]>>,
+            {
+                ReshapeBuilder((value,))
+            }
+        }
+
+        impl<T0> ReshapeBuilder<(T0,)> {
+            /// Finish writing the builder to get an [Offset](::planus::Offset) to a serialized [Reshape].
+            #[inline]
+            pub fn finish(self, builder: &mut ::planus::Builder) -> ::planus::Offset<Reshape>
+            where
+                Self: ::planus::WriteAsOffset<Reshape>,
+            {
+                ::planus::WriteAsOffset::prepare(&self, builder)
+            }
+        }
+
+        impl<T0: ::planus::WriteAs<::planus::Offset<[u64]>>>
+            ::planus::WriteAs<::planus::Offset<Reshape>> for ReshapeBuilder<(T0,)>
+        {
+            type Prepared = ::planus::Offset<Reshape>;
+
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<Reshape> {
+                ::planus::WriteAsOffset::prepare(self, builder)
+            }
+        }
+
+        impl<T0: ::planus::WriteAs<::planus::Offset<[u64]>>>
+            ::planus::WriteAsOptional<::planus::Offset<Reshape>> for ReshapeBuilder<(T0,)>
+        {
+            type Prepared = ::planus::Offset<Reshape>;
+
+            #[inline]
+            fn prepare(
+                &self,
+                builder: &mut ::planus::Builder,
+            ) -> ::core::option::Option<::planus::Offset<Reshape>> {
+                ::core::option::Option::Some(::planus::WriteAsOffset::prepare(self, builder))
+            }
+        }
+
+        impl<T0: ::planus::WriteAs<::planus::Offset<[u64]>>> ::planus::WriteAsOffset<Reshape>
+            for ReshapeBuilder<(T0,)>
+        {
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<Reshape> {
+                let (v0,) = &self.0;
+                Reshape::create(builder, v0)
+            }
+        }
+
+        /// Reference to a deserialized [Reshape].
+        #[derive(Copy, Clone)]
+        pub struct ReshapeRef<'a>(#[allow(dead_code)] ::planus::table_reader::Table<'a>);
+
+        impl<'a> ReshapeRef<'a> {
+            /// Getter for the [`shape` field](Reshape#structfield.shape).
+            #[inline]
+            pub fn shape(&self) -> ::planus::Result<::planus::Vector<'a, u64>> {
+                self.0.access_required(0, "Reshape", "shape")
+            }
+        }
+
+        impl<'a> ::core::fmt::Debug for ReshapeRef<'a> {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                let mut f = f.debug_struct("ReshapeRef");
+                f.field("shape", &self.shape());
+                f.finish()
+            }
+        }
+
+        impl<'a> ::core::convert::TryFrom<ReshapeRef<'a>> for Reshape {
+            type Error = ::planus::Error;
+
+            #[allow(unreachable_code)]
+            fn try_from(value: ReshapeRef<'a>) -> ::planus::Result<Self> {
+                ::core::result::Result::Ok(Self { shape: value.shape()?.to_vec()? })
+            }
+        }
+
+        impl<'a> ::planus::TableRead<'a> for ReshapeRef<'a> {
+            #[inline]
+            fn from_buffer(
+                buffer: ::planus::SliceWithStartOffset<'a>,
+                offset: usize,
+            ) -> ::core::result::Result<Self, ::planus::errors::ErrorKind> {
+                ::core::result::Result::Ok(Self(::planus::table_reader::Table::from_buffer(
+                    buffer, offset,
+                )?))
+            }
+        }
+
+        impl<'a> ::planus::VectorReadInner<'a> for ReshapeRef<'a> {
+            type Error = ::planus::Error;
+            const STRIDE: usize = 4;
+
+            unsafe fn from_buffer(
+                buffer: ::planus::SliceWithStartOffset<'a>,
+                offset: usize,
+            ) -> ::planus::Result<Self> {
+                ::planus::TableRead::from_buffer(buffer, offset).map_err(|error_kind| {
+                    error_kind.with_error_location("[ReshapeRef]", "get", buffer.offset_from_start)
+                })
+            }
+        }
+
+        /// # Safety
+        /// The planus compiler generates implementations that initialize
+        /// the bytes in `write_values`.
+        unsafe impl ::planus::VectorWrite<::planus::Offset<Reshape>> for Reshape {
+            type Value = ::planus::Offset<Reshape>;
+            const STRIDE: usize = 4;
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> Self::Value {
+                ::planus::WriteAs::prepare(self, builder)
+            }
+
+            #[inline]
+            unsafe fn write_values(
+                values: &[::planus::Offset<Reshape>],
+                bytes: *mut ::core::mem::MaybeUninit<u8>,
+                buffer_position: u32,
+            ) {
+                let bytes = bytes as *mut [::core::mem::MaybeUninit<u8>; 4];
+                for (i, v) in ::core::iter::Iterator::enumerate(values.iter()) {
+                    ::planus::WriteAsPrimitive::write(
+                        v,
+                        ::planus::Cursor::new(unsafe { &mut *bytes.add(i) }),
+                        buffer_position - (Self::STRIDE * i) as u32,
+                    );
+                }
+            }
+        }
+
+        impl<'a> ::planus::ReadAsRoot<'a> for ReshapeRef<'a> {
+            fn read_as_root(slice: &'a [u8]) -> ::planus::Result<Self> {
+                ::planus::TableRead::from_buffer(
+                    ::planus::SliceWithStartOffset { buffer: slice, offset_from_start: 0 },
+                    0,
+                )
+                .map_err(|error_kind| {
+                    error_kind.with_error_location("[ReshapeRef]", "read_as_root", 0)
+                })
+            }
+        }
+
+        /// The table `Slice` in the namespace `tract`
+        ///
+        /// Generated from these locations:
+        /// * Table `Slice` in the file `schema/tract.fbs:206`
+        #[derive(
+            Clone,
+            Debug,
+            PartialEq,
+            PartialOrd,
+            Eq,
+            Ord,
+            Hash,
+            ::serde::Serialize,
+            ::serde::Deserialize,
+        )]
+        pub struct Slice {
+            /// The field `axis` in the table `Slice`
+            pub axis: u64,
+            /// The field `start` in the table `Slice`
+            pub start: u64,
+            /// The field `end` in the table `Slice`
+            pub end: u64,
+            /// The field `dims` in the table `Slice`
+            pub dims: bool,
+        }
+
+        #[allow(clippy::derivable_impls)]
+        impl ::core::default::Default for Slice {
+            fn default() -> Self {
+                Self { axis: 0, start: 0, end: 0, dims: false }
+            }
+        }
+
+        impl Slice {
+            /// Creates a [SliceBuilder] for serializing an instance of this table.
+            #[inline]
+            pub fn builder() -> SliceBuilder<()> {
+                SliceBuilder(())
+            }
+
+            #[allow(clippy::too_many_arguments)]
+            pub fn create(
+                builder: &mut ::planus::Builder,
+                field_axis: impl ::planus::WriteAsDefault<u64, u64>,
+                field_start: impl ::planus::WriteAsDefault<u64, u64>,
+                field_end: impl ::planus::WriteAsDefault<u64, u64>,
+                field_dims: impl ::planus::WriteAsDefault<bool, bool>,
+            ) -> ::planus::Offset<Self> {
+                let prepared_axis = field_axis.prepare(builder, &0);
+                let prepared_start = field_start.prepare(builder, &0);
+                let prepared_end = field_end.prepare(builder, &0);
+                let prepared_dims = field_dims.prepare(builder, &false);
+
+                let mut table_writer: ::planus::table_writer::TableWriter<12> =
+                    ::core::default::Default::default();
+                if prepared_axis.is_some() {
+                    table_writer.write_entry::<u64>(0);
+                }
+                if prepared_start.is_some() {
+                    table_writer.write_entry::<u64>(1);
+                }
+                if prepared_end.is_some() {
+                    table_writer.write_entry::<u64>(2);
+                }
+                if prepared_dims.is_some() {
+                    table_writer.write_entry::<bool>(3);
+                }
+
+                unsafe {
+                    table_writer.finish(builder, |object_writer| {
+                        if let ::core::option::Option::Some(prepared_axis) = prepared_axis {
+                            object_writer.write::<_, _, 8>(&prepared_axis);
+                        }
+                        if let ::core::option::Option::Some(prepared_start) = prepared_start {
+                            object_writer.write::<_, _, 8>(&prepared_start);
+                        }
+                        if let ::core::option::Option::Some(prepared_end) = prepared_end {
+                            object_writer.write::<_, _, 8>(&prepared_end);
+                        }
+                        if let ::core::option::Option::Some(prepared_dims) = prepared_dims {
+                            object_writer.write::<_, _, 1>(&prepared_dims);
+                        }
+                    });
+                }
+                builder.current_offset()
+            }
+        }
+
+        impl ::planus::WriteAs<::planus::Offset<Slice>> for Slice {
+            type Prepared = ::planus::Offset<Self>;
+
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<Slice> {
+                ::planus::WriteAsOffset::prepare(self, builder)
+            }
+        }
+
+        impl ::planus::WriteAsOptional<::planus::Offset<Slice>> for Slice {
+            type Prepared = ::planus::Offset<Self>;
+
+            #[inline]
+            fn prepare(
+                &self,
+                builder: &mut ::planus::Builder,
+            ) -> ::core::option::Option<::planus::Offset<Slice>> {
+                ::core::option::Option::Some(::planus::WriteAsOffset::prepare(self, builder))
+            }
+        }
+
+        impl ::planus::WriteAsOffset<Slice> for Slice {
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<Slice> {
+                Slice::create(builder, self.axis, self.start, self.end, self.dims)
+            }
+        }
+
+        /// Builder for serializing an instance of the [Slice] type.
+        ///
+        /// Can be created using the [Slice::builder] method.
+        #[derive(Debug)]
+        #[must_use]
+        pub struct SliceBuilder<State>(State);
+
+        impl SliceBuilder<()> {
+            /// Setter for the [`axis` field](Slice#structfield.axis).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn axis<T0>(self, value: T0) -> SliceBuilder<(T0,)>
+            where
+                T0: ::planus::WriteAsDefault<u64, u64>,
+            {
+                SliceBuilder((value,))
+            }
+
+            /// Sets the [`axis` field](Slice#structfield.axis) to the default value.
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn axis_as_default(self) -> SliceBuilder<(::planus::DefaultValue,)> {
+                self.axis(::planus::DefaultValue)
+            }
+        }
+
+        impl<T0> SliceBuilder<(T0,)> {
+            /// Setter for the [`start` field](Slice#structfield.start).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn start<T1>(self, value: T1) -> SliceBuilder<(T0, T1)>
+            where
+                T1: ::planus::WriteAsDefault<u64, u64>,
+            {
+                let (v0,) = self.0;
+                SliceBuilder((v0, value))
+            }
+
+            /// Sets the [`start` field](Slice#structfield.start) to the default value.
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn start_as_default(self) -> SliceBuilder<(T0, ::planus::DefaultValue)> {
+                self.start(::planus::DefaultValue)
+            }
+        }
+
+        impl<T0, T1> SliceBuilder<(T0, T1)> {
+            /// Setter for the [`end` field](Slice#structfield.end).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn end<T2>(self, value: T2) -> SliceBuilder<(T0, T1, T2)>
+            where
+                T2: ::planus::WriteAsDefault<u64, u64>,
+            {
+                let (v0, v1) = self.0;
+                SliceBuilder((v0, v1, value))
+            }
+
+            /// Sets the [`end` field](Slice#structfield.end) to the default value.
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn end_as_default(self) -> SliceBuilder<(T0, T1, ::planus::DefaultValue)> {
+                self.end(::planus::DefaultValue)
+            }
+        }
+
+        impl<T0, T1, T2> SliceBuilder<(T0, T1, T2)> {
+            /// Setter for the [`dims` field](Slice#structfield.dims).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn dims<T3>(self, value: T3) -> SliceBuilder<(T0, T1, T2, T3)>
+            where
+                T3: ::planus::WriteAsDefault<bool, bool>,
+            {
+                let (v0, v1, v2) = self.0;
+                SliceBuilder((v0, v1, v2, value))
+            }
+
+            /// Sets the [`dims` field](Slice#structfield.dims) to the default value.
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn dims_as_default(self) -> SliceBuilder<(T0, T1, T2, ::planus::DefaultValue)> {
+                self.dims(::planus::DefaultValue)
+            }
+        }
+
+        impl<T0, T1, T2, T3> SliceBuilder<(T0, T1, T2, T3)> {
+            /// Finish writing the builder to get an [Offset](::planus::Offset) to a serialized [Slice].
+            #[inline]
+            pub fn finish(self, builder: &mut ::planus::Builder) -> ::planus::Offset<Slice>
+            where
+                Self: ::planus::WriteAsOffset<Slice>,
+            {
+                ::planus::WriteAsOffset::prepare(&self, builder)
+            }
+        }
+
+        impl<
+                T0: ::planus::WriteAsDefault<u64, u64>,
+                T1: ::planus::WriteAsDefault<u64, u64>,
+                T2: ::planus::WriteAsDefault<u64, u64>,
+                T3: ::planus::WriteAsDefault<bool, bool>,
+            > ::planus::WriteAs<::planus::Offset<Slice>> for SliceBuilder<(T0, T1, T2, T3)>
+        {
+            type Prepared = ::planus::Offset<Slice>;
+
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<Slice> {
+                ::planus::WriteAsOffset::prepare(self, builder)
+            }
+        }
+
+        impl<
+                T0: ::planus::WriteAsDefault<u64, u64>,
+                T1: ::planus::WriteAsDefault<u64, u64>,
+                T2: ::planus::WriteAsDefault<u64, u64>,
+                T3: ::planus::WriteAsDefault<bool, bool>,
+            > ::planus::WriteAsOptional<::planus::Offset<Slice>>
+            for SliceBuilder<(T0, T1, T2, T3)>
+        {
+            type Prepared = ::planus::Offset<Slice>;
+
+            #[inline]
+            fn prepare(
+                &self,
+                builder: &mut ::planus::Builder,
+            ) -> ::core::option::Option<::planus::Offset<Slice>> {
+                ::core::option::Option::Some(::planus::WriteAsOffset::prepare(self, builder))
+            }
+        }
+
+        impl<
+                T0: ::planus::WriteAsDefault<u64, u64>,
+                T1: ::planus::WriteAsDefault<u64, u64>,
+                T2: ::planus::WriteAsDefault<u64, u64>,
+                T3: ::planus::WriteAsDefault<bool, bool>,
+            > ::planus::WriteAsOffset<Slice> for SliceBuilder<(T0, T1, T2, T3)>
+        {
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<Slice> {
+                let (v0, v1, v2, v3) = &self.0;
+                Slice::create(builder, v0, v1, v2, v3)
+            }
+        }
+
+        /// Reference to a deserialized [Slice].
+        #[derive(Copy, Clone)]
+        pub struct SliceRef<'a>(#[allow(dead_code)] ::planus::table_reader::Table<'a>);
+
+        impl<'a> SliceRef<'a> {
+            /// Getter for the [`axis` field](Slice#structfield.axis).
+            #[inline]
+            pub fn axis(&self) -> ::planus::Result<u64> {
+                ::core::result::Result::Ok(self.0.access(0, "Slice", "axis")?.unwrap_or(0))
+            }
+
+            /// Getter for the [`start` field](Slice#structfield.start).
+            #[inline]
+            pub fn start(&self) -> ::planus::Result<u64> {
+                ::core::result::Result::Ok(self.0.access(1, "Slice", "start")?.unwrap_or(0))
+            }
+
+            /// Getter for the [`end` field](Slice#structfield.end).
+            #[inline]
+            pub fn end(&self) -> ::planus::Result<u64> {
+                ::core::result::Result::Ok(self.0.access(2, "Slice", "end")?.unwrap_or(0))
+            }
+
+            /// Getter for the [`dims` field](Slice#structfield.dims).
+            #[inline]
+            pub fn dims(&self) -> ::planus::Result<bool> {
+                ::core::result::Result::Ok(self.0.access(3, "Slice", "dims")?.unwrap_or(false))
+            }
+        }
+
+        impl<'a> ::core::fmt::Debug for SliceRef<'a> {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                let mut f = f.debug_struct("SliceRef");
+                f.field("axis", &self.axis());
+                f.field("start", &self.start());
+                f.field("end", &self.end());
+                f.field("dims", &self.dims());
+                f.finish()
+            }
+        }
+
+        impl<'a> ::core::convert::TryFrom<SliceRef<'a>> for Slice {
+            type Error = ::planus::Error;
+
+            #[allow(unreachable_code)]
+            fn try_from(value: SliceRef<'a>) -> ::planus::Result<Self> {
+                ::core::result::Result::Ok(Self {
+                    axis: ::core::convert::TryInto::try_into(value.axis()?)?,
+                    start: ::core::convert::TryInto::try_into(value.start()?)?,
+                    end: ::core::convert::TryInto::try_into(value.end()?)?,
+                    dims: ::core::convert::TryInto::try_into(value.dims()?)?,
+                })
+            }
+        }
+
+        impl<'a> ::planus::TableRead<'a> for SliceRef<'a> {
+            #[inline]
+            fn from_buffer(
+                buffer: ::planus::SliceWithStartOffset<'a>,
+                offset: usize,
+            ) -> ::core::result::Result<Self, ::planus::errors::ErrorKind> {
+                ::core::result::Result::Ok(Self(::planus::table_reader::Table::from_buffer(
+                    buffer, offset,
+                )?))
+            }
+        }
+
+        impl<'a> ::planus::VectorReadInner<'a> for SliceRef<'a> {
+            type Error = ::planus::Error;
+            const STRIDE: usize = 4;
+
+            unsafe fn from_buffer(
+                buffer: ::planus::SliceWithStartOffset<'a>,
+                offset: usize,
+            ) -> ::planus::Result<Self> {
+                ::planus::TableRead::from_buffer(buffer, offset).map_err(|error_kind| {
+                    error_kind.with_error_location("[SliceRef]", "get", buffer.offset_from_start)
+                })
+            }
+        }
+
+        /// # Safety
+        /// The planus compiler generates implementations that initialize
+        /// the bytes in `write_values`.
+        unsafe impl ::planus::VectorWrite<::planus::Offset<Slice>> for Slice {
+            type Value = ::planus::Offset<Slice>;
+            const STRIDE: usize = 4;
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> Self::Value {
+                ::planus::WriteAs::prepare(self, builder)
+            }
+
+            #[inline]
+            unsafe fn write_values(
+                values: &[::planus::Offset<Slice>],
+                bytes: *mut ::core::mem::MaybeUninit<u8>,
+                buffer_position: u32,
+            ) {
+                let bytes = bytes as *mut [::core::mem::MaybeUninit<u8>; 4];
+                for (i, v) in ::core::iter::Iterator::enumerate(values.iter()) {
+                    ::planus::WriteAsPrimitive::write(
+                        v,
+                        ::planus::Cursor::new(unsafe { &mut *bytes.add(i) }),
+                        buffer_position - (Self::STRIDE * i) as u32,
+                    );
+                }
+            }
+        }
+
+        impl<'a> ::planus::ReadAsRoot<'a> for SliceRef<'a> {
+            fn read_as_root(slice: &'a [u8]) -> ::planus::Result<Self> {
+                ::planus::TableRead::from_buffer(
+                    ::planus::SliceWithStartOffset { buffer: slice, offset_from_start: 0 },
+                    0,
+                )
+                .map_err(|error_kind| {
+                    error_kind.with_error_location("[SliceRef]", "read_as_root", 0)
+                })
+            }
+        }
+
+        /// The table `If` in the namespace `tract`
+        ///
+        /// Generated from these locations:
+        /// * Table `If` in the file `schema/tract.fbs:218`
+        #[derive(Clone, Debug, PartialEq, PartialOrd, ::serde::Serialize, ::serde::Deserialize)]
+        pub struct If {
+            /// The field `then_body` in the table `If`
+            pub then_body: ::planus::alloc::boxed::Box<self::Model>,
+            /// The field `else_body` in the table `If`
+            pub else_body: ::planus::alloc::boxed::Box<self::Model>,
+        }
+
+        #[allow(clippy::derivable_impls)]
+        impl ::core::default::Default for If {
+            fn default() -> Self {
+                Self {
+                    then_body: ::core::default::Default::default(),
+                    else_body: ::core::default::Default::default(),
+                }
+            }
+        }
+
+        impl If {
+            /// Creates a [IfBuilder] for serializing an instance of this table.
+            #[inline]
+            pub fn builder() -> IfBuilder<()> {
+                IfBuilder(())
+            }
+
+            #[allow(clippy::too_many_arguments)]
+            pub fn create(
+                builder: &mut ::planus::Builder,
+                field_then_body: impl ::planus::WriteAs<::planus::Offset<self::Model>>,
+                field_else_body: impl ::planus::WriteAs<::planus::Offset<self::Model>>,
+            ) -> ::planus::Offset<Self> {
+                let prepared_then_body = field_then_body.prepare(builder);
+                let prepared_else_body = field_else_body.prepare(builder);
+
+                let mut table_writer: ::planus::table_writer::TableWriter<8> =
+                    ::core::default::Default::default();
+                table_writer.write_entry::<::planus::Offset<self::Model>>(0);
+                table_writer.write_entry::<::planus::Offset<self::Model>>(1);
+
+                unsafe {
+                    table_writer.finish(builder, |object_writer| {
+                        object_writer.write::<_, _, 4>(&prepared_then_body);
+                        object_writer.write::<_, _, 4>(&prepared_else_body);
+                    });
+                }
+                builder.current_offset()
+            }
+        }
+
+        impl ::planus::WriteAs<::planus::Offset<If>> for If {
+            type Prepared = ::planus::Offset<Self>;
+
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<If> {
+                ::planus::WriteAsOffset::prepare(self, builder)
+            }
+        }
+
+        impl ::planus::WriteAsOptional<::planus::Offset<If>> for If {
+            type Prepared = ::planus::Offset<Self>;
+
+            #[inline]
+            fn prepare(
+                &self,
+                builder: &mut ::planus::Builder,
+            ) -> ::core::option::Option<::planus::Offset<If>> {
+                ::core::option::Option::Some(::planus::WriteAsOffset::prepare(self, builder))
+            }
+        }
+
+        impl ::planus::WriteAsOffset<If> for If {
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<If> {
+                If::create(builder, &self.then_body, &self.else_body)
+            }
+        }
+
+        /// Builder for serializing an instance of the [If] type.
+        ///
+        /// Can be created using the [If::builder] method.
+        #[derive(Debug)]
+        #[must_use]
+        pub struct IfBuilder<State>(State);
+
+        impl IfBuilder<()> {
+            /// Setter for the [`then_body` field](If#structfield.then_body).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn then_body<T0>(self, value: T0) -> IfBuilder<(T0,)>
+            where
+                T0: ::planus::WriteAs<::planus::Offset<self::Model>>,
+            {
+                IfBuilder((value,))
+            }
+        }
+
+        impl<T0> IfBuilder<(T0,)> {
+            /// Setter for the [`else_body` field](If#structfield.else_body).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn else_body<T1>(self, value: T1) -> IfBuilder<(T0, T1)>
+            where
+                T1: ::planus::WriteAs<::planus::Offset<self::Model>>,
+            {
+                let (v0,) = self.0;
+                IfBuilder((v0, value))
+            }
+        }
+
+        impl<T0, T1> IfBuilder<(T0, T1)> {
+            /// Finish writing the builder to get an [Offset](::planus::Offset) to a serialized [If].
+            #[inline]
+            pub fn finish(self, builder: &mut ::planus::Builder) -> ::planus::Offset<If>
+            where
+                Self: ::planus::WriteAsOffset<If>,
+            {
+                ::planus::WriteAsOffset::prepare(&self, builder)
+            }
+        }
+
+        impl<
+                T0: ::planus::WriteAs<::planus::Offset<self::Model>>,
+                T1: ::planus::WriteAs<::planus::Offset<self::Model>>,
+            > ::planus::WriteAs<::planus::Offset<If>> for IfBuilder<(T0, T1)>
+        {
+            type Prepared = ::planus::Offset<If>;
+
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<If> {
+                ::planus::WriteAsOffset::prepare(self, builder)
+            }
+        }
+
+        impl<
+                T0: ::planus::WriteAs<::planus::Offset<self::Model>>,
+                T1: ::planus::WriteAs<::planus::Offset<self::Model>>,
+            > ::planus::WriteAsOptional<::planus::Offset<If>> for IfBuilder<(T0, T1)>
+        {
+            type Prepared = ::planus::Offset<If>;
+
+            #[inline]
+            fn prepare(
+                &self,
+                builder: &mut ::planus::Builder,
+            ) -> ::core::option::Option<::planus::Offset<If>> {
+                ::core::option::Option::Some(::planus::WriteAsOffset::prepare(self, builder))
+            }
+        }
+
+        impl<
+                T0: ::planus::WriteAs<::planus::Offset<self::Model>>,
+                T1: ::planus::WriteAs<::planus::Offset<self::Model>>,
+            > ::planus::WriteAsOffset<If> for IfBuilder<(T0, T1)>
+        {
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<If> {
+                let (v0, v1) = &self.0;
+                If::create(builder, v0, v1)
+            }
+        }
+
+        /// Reference to a deserialized [If].
+        #[derive(Copy, Clone)]
+        pub struct IfRef<'a>(#[allow(dead_code)] ::planus::table_reader::Table<'a>);
+
+        impl<'a> IfRef<'a> {
+            /// Getter for the [`then_body` field](If#structfield.then_body).
+            #[inline]
+            pub fn then_body(&self) -> ::planus::Result<self::ModelRef<'a>> {
+                self.0.access_required(0, "If", "then_body")
+            }
+
+            /// Getter for the [`else_body` field](If#structfield.else_body).
+            #[inline]
+            pub fn else_body(&self) -> ::planus::Result<self::ModelRef<'a>> {
+                self.0.access_required(1, "If", "else_body")
+            }
+        }
+
+        impl<'a> ::core::fmt::Debug for IfRef<'a> {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                let mut f = f.debug_struct("IfRef");
+                f.field("then_body", &self.then_body());
+                f.field("else_body", &self.else_body());
+                f.finish()
+            }
+        }
+
+        impl<'a> ::core::convert::TryFrom<IfRef<'a>> for If {
+            type Error = ::planus::Error;
+
+            #[allow(unreachable_code)]
+            fn try_from(value: IfRef<'a>) -> ::planus::Result<Self> {
+                ::core::result::Result::Ok(Self {
+                    then_body: ::planus::alloc::boxed::Box::new(
+                        ::core::convert::TryInto::try_into(value.then_body()?)?,
+                    ),
+                    else_body: ::planus::alloc::boxed::Box::new(
+                        ::core::convert::TryInto::try_into(value.else_body()?)?,
+                    ),
+                })
+            }
+        }
+
+        impl<'a> ::planus::TableRead<'a> for IfRef<'a> {
+            #[inline]
+            fn from_buffer(
+                buffer: ::planus::SliceWithStartOffset<'a>,
+                offset: usize,
+            ) -> ::core::result::Result<Self, ::planus::errors::ErrorKind> {
+                ::core::result::Result::Ok(Self(::planus::table_reader::Table::from_buffer(
+                    buffer, offset,
+                )?))
+            }
+        }
+
+        impl<'a> ::planus::VectorReadInner<'a> for IfRef<'a> {
+            type Error = ::planus::Error;
+            const STRIDE: usize = 4;
+
+            unsafe fn from_buffer(
+                buffer: ::planus::SliceWithStartOffset<'a>,
+                offset: usize,
+            ) -> ::planus::Result<Self> {
+                ::planus::TableRead::from_buffer(buffer, offset).map_err(|error_kind| {
+                    error_kind.with_error_location("[IfRef]", "get", buffer.offset_from_start)
+                })
+            }
+        }
+
+        /// # Safety
+        /// The planus compiler generates implementations that initialize
+        /// the bytes in `write_values`.
+        unsafe impl ::planus::VectorWrite<::planus::Offset<If>> for If {
+            type Value = ::planus::Offset<If>;
+            const STRIDE: usize = 4;
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> Self::Value {
+                ::planus::WriteAs::prepare(self, builder)
+            }
+
+            #[inline]
+            unsafe fn write_values(
+                values: &[::planus::Offset<If>],
+                bytes: *mut ::core::mem::MaybeUninit<u8>,
+                buffer_position: u32,
+            ) {
+                let bytes = bytes as *mut [::core::mem::MaybeUninit<u8>; 4];
+                for (i, v) in ::core::iter::Iterator::enumerate(values.iter()) {
+                    ::planus::WriteAsPrimitive::write(
+                        v,
+                        ::planus::Cursor::new(unsafe { &mut *bytes.add(i) }),
+                        buffer_position - (Self::STRIDE * i) as u32,
+                    );
+                }
+            }
+        }
+
+        impl<'a> ::planus::ReadAsRoot<'a> for IfRef<'a> {
+            fn read_as_root(slice: &'a [u8]) -> ::planus::Result<Self> {
+                ::planus::TableRead::from_buffer(
+                    ::planus::SliceWithStartOffset { buffer: slice, offset_from_start: 0 },
+                    0,
+                )
+                .map_err(|error_kind| error_kind.with_error_location("[IfRef]", "read_as_root", 0))
+            }
+        }
+
+        /// The table `Loop` in the namespace `tract`
+        ///
+        /// Generated from these locations:
+        /// * Table `Loop` in the file `schema/tract.fbs:223`
+        #[derive(Clone, Debug, PartialEq, PartialOrd, ::serde::Serialize, ::serde::Deserialize)]
+        pub struct Loop {
+            /// The field `body` in the table `Loop`
+            pub body: ::planus::alloc::boxed::Box<self::Model>,
+            /// The field `has_trip_count` in the table `Loop`
+            pub has_trip_count: bool,
+            /// The field `has_cond` in the table `Loop`
+            pub has_cond: bool,
+        }
+
+        #[allow(clippy::derivable_impls)]
+        impl ::core::default::Default for Loop {
+            fn default() -> Self {
+                Self {
+                    body: ::core::default::Default::default(),
+                    has_trip_count: false,
+                    has_cond: false,
+                }
+            }
+        }
+
+        impl Loop {
+            /// Creates a [LoopBuilder] for serializing an instance of this table.
+            #[inline]
+            pub fn builder() -> LoopBuilder<()> {
+                LoopBuilder(())
+            }
+
+            #[allow(clippy::too_many_arguments)]
+            pub fn create(
+                builder: &mut ::planus::Builder,
+                field_body: impl ::planus::WriteAs<::planus::Offset<self::Model>>,
+                field_has_trip_count: impl ::planus::WriteAsDefault<bool, bool>,
+                field_has_cond: impl ::planus::WriteAsDefault<bool, bool>,
+            ) -> ::planus::Offset<Self> {
+                let prepared_body = field_body.prepare(builder);
+                let prepared_has_trip_count = field_has_trip_count.prepare(builder, &false);
+                let prepared_has_cond = field_has_cond.prepare(builder, &false);
+
+                let mut table_writer: ::planus::table_writer::TableWriter<10> =
+                    ::core::default::Default::default();
+                table_writer.write_entry::<::planus::Offset<self::Model>>(0);
+                if prepared_has_trip_count.is_some() {
+                    table_writer.write_entry::<bool>(1);
+                }
+                if prepared_has_cond.is_some() {
+                    table_writer.write_entry::<bool>(2);
+                }
+
+                unsafe {
+                    table_writer.finish(builder, |object_writer| {
+                        object_writer.write::<_, _, 4>(&prepared_body);
+                        if let ::core::option::Option::Some(prepared_has_trip_count) =
+                            prepared_has_trip_count
+                        {
+                            object_writer.write::<_, _, 1>(&prepared_has_trip_count);
+                        }
+                        if let ::core::option::Option::Some(prepared_has_cond) = prepared_has_cond {
+                            object_writer.write::<_, _, 1>(&prepared_has_cond);
+                        }
+                    });
+                }
+                builder.current_offset()
+            }
+        }
+
+        impl ::planus::WriteAs<::planus::Offset<Loop>> for Loop {
+            type Prepared = ::planus::Offset<Self>;
+
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<Loop> {
+                ::planus::WriteAsOffset::prepare(self, builder)
+            }
+        }
+
+        impl ::planus::WriteAsOptional<::planus::Offset<Loop>> for Loop {
+            type Prepared = ::planus::Offset<Self>;
+
+            #[inline]
+            fn prepare(
+                &self,
+                builder: &mut ::planus::Builder,
+            ) -> ::core::option::Option<::planus::Offset<Loop>> {
+                ::core::option::Option::Some(::planus::WriteAsOffset::prepare(self, builder))
+            }
+        }
+
+        impl ::planus::WriteAsOffset<Loop> for Loop {
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<Loop> {
+                Loop::create(builder, &self.body, self.has_trip_count, self.has_cond)
+            }
+        }
+
+        /// Builder for serializing an instance of the [Loop] type.
+        ///
+        /// Can be created using the [Loop::builder] method.
+        #[derive(Debug)]
+        #[must_use]
+        pub struct LoopBuilder<State>(State);
+
+        impl LoopBuilder<()> {
+            /// Setter for the [`body` field](Loop#structfield.body).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn body<T0>(self, value: T0) -> LoopBuilder<(T0,)>
+            where
+                T0: ::planus::WriteAs<::planus::Offset<self::Model>>,
+            {
+                LoopBuilder((value,))
+            }
+        }
+
+        impl<T0> LoopBuilder<(T0,)> {
+            /// Setter for the [`has_trip_count` field](Loop#structfield.has_trip_count).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn has_trip_count<T1>(self, value: T1) -> LoopBuilder<(T0, T1)>
+            where
+                T1: ::planus::WriteAsDefault<bool, bool>,
+            {
+                let (v0,) = self.0;
+                LoopBuilder((v0, value))
+            }
+
+            /// Sets the [`has_trip_count` field](Loop#structfield.has_trip_count) to the default value.
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn has_trip_count_as_default(self) -> LoopBuilder<(T0, ::planus::DefaultValue)> {
+                self.has_trip_count(::planus::DefaultValue)
+            }
+        }
+
+        impl<T0, T1> LoopBuilder<(T0, T1)> {
+            /// Setter for the [`has_cond` field](Loop#structfield.has_cond).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn has_cond<T2>(self, value: T2) -> LoopBuilder<(T0, T1, T2)>
+            where
+                T2: ::planus::WriteAsDefault<bool, bool>,
+            {
+                let (v0, v1) = self.0;
+                LoopBuilder((v0, v1, value))
+            }
+
+            /// Sets the [`has_cond` field](Loop#structfield.has_cond) to the default value.
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn has_cond_as_default(self) -> LoopBuilder<(T0, T1, ::planus::DefaultValue)> {
+                self.has_cond(::planus::DefaultValue)
+            }
+        }
+
+        impl<T0, T1, T2> LoopBuilder<(T0, T1, T2)> {
+            /// Finish writing the builder to get an [Offset](::planus::Offset) to a serialized [Loop].
+            #[inline]
+            pub fn finish(self, builder: &mut ::planus::Builder) -> ::planus::Offset<Loop>
+            where
+                Self: ::planus::WriteAsOffset<Loop>,
+            {
+                ::planus::WriteAsOffset::prepare(&self, builder)
+            }
+        }
+
+        impl<
+                T0: ::planus::WriteAs<::planus::Offset<self::Model>>,
+                T1: ::planus::WriteAsDefault<bool, bool>,
+                T2: ::planus::WriteAsDefault<bool, bool>,
+            > ::planus::WriteAs<::planus::Offset<Loop>> for LoopBuilder<(T0, T1, T2)>
+        {
+            type Prepared = ::planus::Offset<Loop>;
+
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<Loop> {
+                ::planus::WriteAsOffset::prepare(self, builder)
+            }
+        }
+
+        impl<
+                T0: ::planus::WriteAs<::planus::Offset<self::Model>>,
+                T1: ::planus::WriteAsDefault<bool, bool>,
+                T2: ::planus::WriteAsDefault<bool, bool>,
+            > ::planus::WriteAsOptional<::planus::Offset<Loop>> for LoopBuilder<(T0, T1, T2)>
+        {
+            type Prepared = ::planus::Offset<Loop>;
+
+            #[inline]
+            fn prepare(
+                &self,
+                builder: &mut ::planus::Builder,
+            ) -> ::core::option::Option<::planus::Offset<Loop>> {
+                ::core::option::Option::Some(::planus::WriteAsOffset::prepare(self, builder))
+            }
+        }
+
+        impl<
+                T0: ::planus::WriteAs<::planus::Offset<self::Model>>,
+                T1: ::planus::WriteAsDefault<bool, bool>,
+                T2: ::planus::WriteAsDefault<bool, bool>,
+            > ::planus::WriteAsOffset<Loop> for LoopBuilder<(T0, T1, T2)>
+        {
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<Loop> {
+                let (v0, v1, v2) = &self.0;
+                Loop::create(builder, v0, v1, v2)
+            }
+        }
+
+        /// Reference to a deserialized [Loop].
+        #[derive(Copy, Clone)]
+        pub struct LoopRef<'a>(#[allow(dead_code)] ::planus::table_reader::Table<'a>);
+
+        impl<'a> LoopRef<'a> {
+            /// Getter for the [`body` field](Loop#structfield.body).
+            #[inline]
+            pub fn body(&self) -> ::planus::Result<self::ModelRef<'a>> {
+                self.0.access_required(0, "Loop", "body")
+            }
+
+            /// Getter for the [`has_trip_count` field](Loop#structfield.has_trip_count).
+            #[inline]
+            pub fn has_trip_count(&self) -> ::planus::Result<bool> {
+                ::core::result::Result::Ok(
+                    self.0.access(1, "Loop", "has_trip_count")?.unwrap_or(false),
+                )
+            }
+
+            /// Getter for the [`has_cond` field](Loop#structfield.has_cond).
+            #[inline]
+            pub fn has_cond(&self) -> ::planus::Result<bool> {
+                ::core::result::Result::Ok(self.0.access(2, "Loop", "has_cond")?.unwrap_or(false))
+            }
+        }
+
+        impl<'a> ::core::fmt::Debug for LoopRef<'a> {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                let mut f = f.debug_struct("LoopRef");
+                f.field("body", &self.body());
+                f.field("has_trip_count", &self.has_trip_count());
+                f.field("has_cond", &self.has_cond());
+                f.finish()
+            }
+        }
+
+        impl<'a> ::core::convert::TryFrom<LoopRef<'a>> for Loop {
+            type Error = ::planus::Error;
+
+            #[allow(unreachable_code)]
+            fn try_from(value: LoopRef<'a>) -> ::planus::Result<Self> {
+                ::core::result::Result::Ok(Self {
+                    body: ::planus::alloc::boxed::Box::new(::core::convert::TryInto::try_into(
+                        value.body()?,
+                    )?),
+                    has_trip_count: ::core::convert::TryInto::try_into(value.has_trip_count()?)?,
+                    has_cond: ::core::convert::TryInto::try_into(value.has_cond()?)?,
+                })
+            }
+        }
+
+        impl<'a> ::planus::TableRead<'a> for LoopRef<'a> {
+            #[inline]
+            fn from_buffer(
+                buffer: ::planus::SliceWithStartOffset<'a>,
+                offset: usize,
+            ) -> ::core::result::Result<Self, ::planus::errors::ErrorKind> {
+                ::core::result::Result::Ok(Self(::planus::table_reader::Table::from_buffer(
+                    buffer, offset,
+                )?))
+            }
+        }
+
+        impl<'a> ::planus::VectorReadInner<'a> for LoopRef<'a> {
+            type Error = ::planus::Error;
+            const STRIDE: usize = 4;
+
+            unsafe fn from_buffer(
+                buffer: ::planus::SliceWithStartOffset<'a>,
+                offset: usize,
+            ) -> ::planus::Result<Self> {
+                ::planus::TableRead::from_buffer(buffer, offset).map_err(|error_kind| {
+                    error_kind.with_error_location("[LoopRef]", "get", buffer.offset_from_start)
+                })
+            }
+        }
+
+        /// # Safety
+        /// The planus compiler generates implementations that initialize
+        /// the bytes in `write_values`.
+        unsafe impl ::planus::VectorWrite<::planus::Offset<Loop>> for Loop {
+            type Value = ::planus::Offset<Loop>;
+            const STRIDE: usize = 4;
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> Self::Value {
+                ::planus::WriteAs::prepare(self, builder)
+            }
+
+            #[inline]
+            unsafe fn write_values(
+                values: &[::planus::Offset<Loop>],
+                bytes: *mut ::core::mem::MaybeUninit<u8>,
+                buffer_position: u32,
+            ) {
+                let bytes = bytes as *mut [::core::mem::MaybeUninit<u8>; 4];
+                for (i, v) in ::core::iter::Iterator::enumerate(values.iter()) {
+                    ::planus::WriteAsPrimitive::write(
+                        v,
+                        ::planus::Cursor::new(unsafe { &mut *bytes.add(i) }),
+                        buffer_position - (Self::STRIDE * i) as u32,
+                    );
+                }
+            }
+        }
+
+        impl<'a> ::planus::ReadAsRoot<'a> for LoopRef<'a> {
+            fn read_as_root(slice: &'a [u8]) -> ::planus::Result<Self> {
+                ::planus::TableRead::from_buffer(
+                    ::planus::SliceWithStartOffset { buffer: slice, offset_from_start: 0 },
+                    0,
+                )
+                .map_err(|error_kind| {
+                    error_kind.with_error_location("[LoopRef]", "read_as_root", 0)
+                })
+            }
+        }
+
+        /// The enum `ScanInputKind` in the namespace `tract`
+        ///
+        /// Generated from these locations:
+        /// * Enum `ScanInputKind` in the file `schema/tract.fbs:229`
+        #[derive(
+            Copy,
+            Clone,
+            Debug,
+            PartialEq,
+            Eq,
+            PartialOrd,
+            Ord,
+            Hash,
+            ::serde::Serialize,
+            ::serde::Deserialize,
+        )]
+        #[repr(u8)]
+        pub enum ScanInputKind {
+            /// The variant `Full` in the enum `ScanInputKind`
+            Full = 0,
+
+            /// The variant `State` in the enum `ScanInputKind`
+            State = 1,
+
+            /// The variant `Scan` in the enum `ScanInputKind`
+            Scan = 2,
+        }
+
+        impl ScanInputKind {
+            /// Array containing all valid variants of ScanInputKind
+            pub const ENUM_VALUES: [Self; 3] = [Self::Full, Self::State, Self::Scan];
+        }
+
+        impl ::core::convert::TryFrom<u8> for ScanInputKind {
+            type Error = ::planus::errors::UnknownEnumTagKind;
+            #[inline]
+            fn try_from(
+                value: u8,
+            ) -> ::core::result::Result<Self, ::planus::errors::UnknownEnumTagKind> {
+                #[allow(clippy::match_single_binding)]
+                match value {
+                    0 => ::core::result::Result::Ok(ScanInputKind::Full),
+                    1 => ::core::result::Result::Ok(ScanInputKind::State),
+                    2 => ::core::result::Result::Ok(ScanInputKind::Scan),
+
+                    _ => ::core::result::Result::Err(::planus::errors::UnknownEnumTagKind {
+                        tag: value as i128,
+                    }),
+                }
+            }
+        }
+
+        impl ::core::convert::From<ScanInputKind> for u8 {
+            #[inline]
+            fn from(value: ScanInputKind) -> Self {
+                value as u8
+            }
+        }
+
+        /// # Safety
+        /// The Planus compiler correctly calculates `ALIGNMENT` and `SIZE`.
+        unsafe impl ::planus::Primitive for ScanInputKind {
+            const ALIGNMENT: usize = 1;
+            const SIZE: usize = 1;
+        }
+
+        impl ::planus::WriteAsPrimitive<ScanInputKind> for ScanInputKind {
+            #[inline]
+            fn write<const N: usize>(&self, cursor: ::planus::Cursor<'_, N>, buffer_position: u32) {
+                (*self as u8).write(cursor, buffer_position);
+            }
+        }
+
+        impl ::planus::WriteAs<ScanInputKind> for ScanInputKind {
+            type Prepared = Self;
+
+            #[inline]
+            fn prepare(&self, _builder: &mut ::planus::Builder) -> ScanInputKind {
+                *self
+            }
+        }
+
+        impl ::planus::WriteAsDefault<ScanInputKind, ScanInputKind> for ScanInputKind {
+            type Prepared = Self;
+
+            #[inline]
+            fn prepare(
+                &self,
+                _builder: &mut ::planus::Builder,
+                default: &ScanInputKind,
+            ) -> ::core::option::Option<ScanInputKind> {
+                if self == default {
+                    ::core::option::Option::None
+                } else {
+                    ::core::option::Option::Some(*self)
+                }
+            }
+        }
+
+        impl ::planus::WriteAsOptional<ScanInputKind> for ScanInputKind {
+            type Prepared = Self;
+
+            #[inline]
+            fn prepare(
+                &self,
+                _builder: &mut ::planus::Builder,
+            ) -> ::core::option::Option<ScanInputKind> {
+                ::core::option::Option::Some(*self)
+            }
+        }
+
+        impl<'buf> ::planus::TableRead<'buf> for ScanInputKind {
+            #[inline]
+            fn from_buffer(
+                buffer: ::planus::SliceWithStartOffset<'buf>,
+                offset: usize,
+            ) -> ::core::result::Result<Self, ::planus::errors::ErrorKind> {
+                let n: u8 = ::planus::TableRead::from_buffer(buffer, offset)?;
+                ::core::result::Result::Ok(::core::convert::TryInto::try_into(n)?)
+            }
+        }
+
+        impl<'buf> ::planus::VectorReadInner<'buf> for ScanInputKind {
+            type Error = ::planus::errors::UnknownEnumTag;
+            const STRIDE: usize = 1;
+            #[inline]
+            unsafe fn from_buffer(
+                buffer: ::planus::SliceWithStartOffset<'buf>,
+                offset: usize,
+            ) -> ::core::result::Result<Self, ::planus::errors::UnknownEnumTag> {
+                let value = unsafe { *buffer.buffer.get_unchecked(offset) };
+                let value: ::core::result::Result<Self, _> =
+                    ::core::convert::TryInto::try_into(value);
+                value.map_err(|error_kind| {
+                    error_kind.with_error_location(
+                        "ScanInputKind",
+                        "VectorRead::from_buffer",
+                        buffer.offset_from_start,
+                    )
+                })
+            }
+        }
+
+        /// # Safety
+        /// The planus compiler generates implementations that initialize
+        /// the bytes in `write_values`.
+        unsafe impl ::planus::VectorWrite<ScanInputKind> for ScanInputKind {
+            const STRIDE: usize = 1;
+
+            type Value = Self;
+
+            #[inline]
+            fn prepare(&self, _builder: &mut ::planus::Builder) -> Self {
+                *self
+            }
+
+            #[inline]
+            unsafe fn write_values(
+                values: &[Self],
+                bytes: *mut ::core::mem::MaybeUninit<u8>,
+                buffer_position: u32,
+            ) {
+                let bytes = bytes as *mut [::core::mem::MaybeUninit<u8>; 1];
+                for (i, v) in ::core::iter::Iterator::enumerate(values.iter()) {
+                    ::planus::WriteAsPrimitive::write(
+                        v,
+                        ::planus::Cursor::new(unsafe { &mut *bytes.add(i) }),
+                        buffer_position - i as u32,
+                    );
+                }
+            }
+        }
+
+        /// The table `ScanInput` in the namespace `tract`
+        ///
+        /// Generated from these locations:
+        /// * Table `ScanInput` in the file `schema/tract.fbs:235`
+        #[derive(
+            Clone,
+            Debug,
+            PartialEq,
+            PartialOrd,
+            Eq,
+            Ord,
+            Hash,
+            ::serde::Serialize,
+            ::serde::Deserialize,
+        )]
+        pub struct ScanInput {
+            /// The field `kind` in the table `ScanInput`
+            pub kind: self::ScanInputKind,
+            /// The field `slot` in the table `ScanInput`
+            pub slot: u64,
+            /// The field `value` in the table `ScanInput`
+            pub value: ::core::option::Option<::planus::alloc::boxed::Box<self::Tensor>>,
+            /// The field `axis` in the table `ScanInput`
+            pub axis: u64,
+            /// The field `chunk` in the table `ScanInput`
+            pub chunk: u64,
+            /// The field `reverse` in the table `ScanInput`
+            pub reverse: bool,
+        }
+
+        #[allow(clippy::derivable_impls)]
+        impl ::core::default::Default for ScanInput {
+            fn default() -> Self {
+                Self {
+                    kind: self::ScanInputKind::Full,
+                    slot: 0,
+                    value: ::core::default::Default::default(),
+                    axis: 0,
+                    chunk: 0,
+                    reverse: false,
+                }
+            }
+        }
+
+        impl ScanInput {
+            /// Creates a [ScanInputBuilder] for serializing an instance of this table.
+            #[inline]
+            pub fn builder() -> ScanInputBuilder<()> {
+                ScanInputBuilder(())
+            }
+
+            #[allow(clippy::too_many_arguments)]
+            pub fn create(
+                builder: &mut ::planus::Builder,
+                field_kind: impl ::planus::WriteAsDefault<self::ScanInputKind, self::ScanInputKind>,
+                field_slot: impl ::planus::WriteAsDefault<u64, u64>,
+                field_value: impl ::planus::WriteAsOptional<::planus::Offset<self::Tensor>>,
+                field_axis: impl ::planus::WriteAsDefault<u64, u64>,
+                field_chunk: impl ::planus::WriteAsDefault<u64, u64>,
+                field_reverse: impl ::planus::WriteAsDefault<bool, bool>,
+            ) -> ::planus::Offset<Self> {
+                let prepared_kind = field_kind.prepare(builder, &self::ScanInputKind::Full);
+                let prepared_slot = field_slot.prepare(builder, &0);
+                let prepared_value = field_value.prepare(builder);
+                let prepared_axis = field_axis.prepare(builder, &0);
+                let prepared_chunk = field_chunk.prepare(builder, &0);
+                let prepared_reverse = field_reverse.prepare(builder, &false);
+
+                let mut table_writer: ::planus::table_writer::TableWriter<16> =
+                    ::core::default::Default::default();
+                if prepared_slot.is_some() {
+                    table_writer.write_entry::<u64>(1);
+                }
+                if prepared_axis.is_some() {
+                    table_writer.write_entry::<u64>(3);
+                }
+                if prepared_chunk.is_some() {
+                    table_writer.write_entry::<u64>(4);
+                }
+                if prepared_value.is_some() {
+                    table_writer.write_entry::<::planus::Offset<self::Tensor>>(2);
+                }
+                if prepared_kind.is_some() {
+                    table_writer.write_entry::<self::ScanInputKind>(0);
+                }
+                if prepared_reverse.is_some() {
+                    table_writer.write_entry::<bool>(5);
+                }
+
+                unsafe {
+                    table_writer.finish(builder, |object_writer| {
+                        if let ::core::option::Option::Some(prepared_slot) = prepared_slot {
+                            object_writer.write::<_, _, 8>(&prepared_slot);
+                        }
+                        if let ::core::option::Option::Some(prepared_axis) = prepared_axis {
+                            object_writer.write::<_, _, 8>(&prepared_axis);
+                        }
+                        if let ::core::option::Option::Some(prepared_chunk) = prepared_chunk {
+                            object_writer.write::<_, _, 8>(&prepared_chunk);
+                        }
+                        if let ::core::option::Option::Some(prepared_value) = prepared_value {
+                            object_writer.write::<_, _, 4>(&prepared_value);
+                        }
+                        if let ::core::option::Option::Some(prepared_kind) = prepared_kind {
+                            object_writer.write::<_, _, 1>(&prepared_kind);
+                        }
+                        if let ::core::option::Option::Some(prepared_reverse) = prepared_reverse {
+                            object_writer.write::<_, _, 1>(&prepared_reverse);
+                        }
+                    });
+                }
+                builder.current_offset()
+            }
+        }
+
+        impl ::planus::WriteAs<::planus::Offset<ScanInput>> for ScanInput {
+            type Prepared = ::planus::Offset<Self>;
+
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<ScanInput> {
+                ::planus::WriteAsOffset::prepare(self, builder)
+            }
+        }
+
+        impl ::planus::WriteAsOptional<::planus::Offset<ScanInput>> for ScanInput {
+            type Prepared = ::planus::Offset<Self>;
+
+            #[inline]
+            fn prepare(
+                &self,
+                builder: &mut ::planus::Builder,
+            ) -> ::core::option::Option<::planus::Offset<ScanInput>> {
+                ::core::option::Option::Some(::planus::WriteAsOffset::prepare(self, builder))
+            }
+        }
+
+        impl ::planus::WriteAsOffset<ScanInput> for ScanInput {
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<ScanInput> {
+                ScanInput::create(
+                    builder,
+                    self.kind,
+                    self.slot,
+                    &self.value,
+                    self.axis,
+                    self.chunk,
+                    self.reverse,
+                )
+            }
+        }
+
+        /// Builder for serializing an instance of the [ScanInput] type.
+        ///
+        /// Can be created using the [ScanInput::builder] method.
+        #[derive(Debug)]
+        #[must_use]
+        pub struct ScanInputBuilder<State>(State);
+
+        impl ScanInputBuilder<()> {
+            /// Setter for the [`kind` field](ScanInput#structfield.kind).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn kind<T0>(self, value: T0) -> ScanInputBuilder<(T0,)>
+            where
+                T0: ::planus::WriteAsDefault<self::ScanInputKind, self::ScanInputKind>,
+            {
+                ScanInputBuilder((value,))
+            }
+
+            /// Sets the [`kind` field](ScanInput#structfield.kind) to the default value.
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn kind_as_default(self) -> ScanInputBuilder<(::planus::DefaultValue,)> {
+                self.kind(::planus::DefaultValue)
+            }
+        }
+
+        impl<T0> ScanInputBuilder<(T0,)> {
+            /// Setter for the [`slot` field](ScanInput#structfield.slot).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn slot<T1>(self, value: T1) -> ScanInputBuilder<(T0, T1)>
+            where
+                T1: ::planus::WriteAsDefault<u64, u64>,
+            {
+                let (v0,) = self.0;
+                ScanInputBuilder((v0, value))
+            }
+
+            /// Sets the [`slot` field](ScanInput#structfield.slot) to the default value.
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn slot_as_default(self) -> ScanInputBuilder<(T0, ::planus::DefaultValue)> {
+                self.slot(::planus::DefaultValue)
+            }
+        }
+
+        impl<T0, T1> ScanInputBuilder<(T0, T1)> {
+            /// Setter for the [`value` field](ScanInput#structfield.value).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn value<T2>(self, value: T2) -> ScanInputBuilder<(T0, T1, T2)>
+            where
+                T2: ::planus::WriteAsOptional<::planus::Offset<self::Tensor>>,
+            {
+                let (v0, v1) = self.0;
+                ScanInputBuilder((v0, v1, value))
+            }
+
+            /// Sets the [`value` field](ScanInput#structfield.value) to null.
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn value_as_null(self) -> ScanInputBuilder<(T0, T1, ())> {
+                self.value(())
+            }
+        }
+
+        impl<T0, T1, T2> ScanInputBuilder<(T0, T1, T2)> {
+            /// Setter for the [`axis` field](ScanInput#structfield.axis).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn axis<T3>(self, value: T3) -> ScanInputBuilder<(T0, T1, T2, T3)>
+            where
+                T3: ::planus::WriteAsDefault<u64, u64>,
+            {
+                let (v0, v1, v2) = self.0;
+                ScanInputBuilder((v0, v1, v2, value))
+            }
+
+            /// Sets the [`axis` field](ScanInput#structfield.axis) to the default value.
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn axis_as_default(self) -> ScanInputBuilder<(T0, T1, T2, ::planus::DefaultValue)> {
+                self.axis(::planus::DefaultValue)
+            }
+        }
+
+        impl<T0, T1, T2, T3> ScanInputBuilder<(T0, T1, T2, T3)> {
+            /// Setter for the [`chunk` field](ScanInput#structfield.chunk).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn chunk<T4>(self, value: T4) -> ScanInputBuilder<(T0, T1, T2, T3, T4)>
+            where
+                T4: ::planus::WriteAsDefault<u64, u64>,
+            {
+                let (v0, v1, v2, v3) = self.0;
+                ScanInputBuilder((v0, v1, v2, v3, value))
+            }
+
+            /// Sets the [`chunk` field](ScanInput#structfield.chunk) to the default value.
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn chunk_as_default(
+                self,
+            ) -> ScanInputBuilder<(T0, T1, T2, T3, ::planus::DefaultValue)> {
+                self.chunk(::planus::DefaultValue)
+            }
+        }
+
+        impl<T0, T1, T2, T3, T4> ScanInputBuilder<(T0, T1, T2, T3, T4)> {
+            /// Setter for the [`reverse` field](ScanInput#structfield.reverse).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn reverse<T5>(self, value: T5) -> ScanInputBuilder<(T0, T1, T2, T3, T4, T5)>
+            where
+                T5: ::planus::WriteAsDefault<bool, bool>,
+            {
+                let (v0, v1, v2, v3, v4) = self.0;
+                ScanInputBuilder((v0, v1, v2, v3, v4, value))
+            }
+
+            /// Sets the [`reverse` field](ScanInput#structfield.reverse) to the default value.
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn reverse_as_default(
+                self,
+            ) -> ScanInputBuilder<(T0, T1, T2, T3, T4, ::planus::DefaultValue)> {
+                self.reverse(::planus::DefaultValue)
+            }
+        }
+
+        impl<T0, T1, T2, T3, T4, T5> ScanInputBuilder<(T0, T1, T2, T3, T4, T5)> {
+            /// Finish writing the builder to get an [Offset](::planus::Offset) to a serialized [ScanInput].
+            #[inline]
+            pub fn finish(self, builder: &mut ::planus::Builder) -> ::planus::Offset<ScanInput>
+            where
+                Self: ::planus::WriteAsOffset<ScanInput>,
+            {
+                ::planus::WriteAsOffset::prepare(&self, builder)
+            }
+        }
+
+        impl<
+                T0: ::planus::WriteAsDefault<self::ScanInputKind, self::ScanInputKind>,
+                T1: ::planus::WriteAsDefault<u64, u64>,
+                T2: ::planus::WriteAsOptional<::planus::Offset<self::Tensor>>,
+                T3: ::planus::WriteAsDefault<u64, u64>,
+                T4: ::planus::WriteAsDefault<u64, u64>,
+                T5: ::planus::WriteAsDefault<bool, bool>,
+            > ::planus::WriteAs<::planus::Offset<ScanInput>>
+            for ScanInputBuilder<(T0, T1, T2, T3, T4, T5)>
+        {
+            type Prepared = ::planus::Offset<ScanInput>;
+
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<ScanInput> {
+                ::planus::WriteAsOffset::prepare(self, builder)
+            }
+        }
+
+        impl<
+                T0: ::planus::WriteAsDefault<self::ScanInputKind, self::ScanInputKind>,
+                T1: ::planus::WriteAsDefault<u64, u64>,
+                T2: ::planus::WriteAsOptional<::planus::Offset<self::Tensor>>,
+                T3: ::planus::WriteAsDefault<u64, u64>,
+                T4: ::planus::WriteAsDefault<u64, u64>,
+                T5: ::planus::WriteAsDefault<bool, bool>,
+            > ::planus::WriteAsOptional<::planus::Offset<ScanInput>>
+            for ScanInputBuilder<(T0, T1, T2, T3, T4, T5)>
+        {
+            type Prepared = ::planus::Offset<ScanInput>;
+
+            #[inline]
+            fn prepare(
+                &self,
+                builder: &mut ::planus::Builder,
+            ) -> ::core::option::Option<::planus::Offset<ScanInput>> {
+                ::core::option::Option::Some(::planus::WriteAsOffset::prepare(self, builder))
+            }
+        }
+
+        impl<
+                T0: ::planus::WriteAsDefault<self::ScanInputKind, self::ScanInputKind>,
+                T1: ::planus::WriteAsDefault<u64, u64>,
+                T2: ::planus::WriteAsOptional<::planus::Offset<self::Tensor>>,
+                T3: ::planus::WriteAsDefault<u64, u64>,
+                T4: ::planus::WriteAsDefault<u64, u64>,
+                T5: ::planus::WriteAsDefault<bool, bool>,
+            > ::planus::WriteAsOffset<ScanInput> for ScanInputBuilder<(T0, T1, T2, T3, T4, T5)>
+        {
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<ScanInput> {
+                let (v0, v1, v2, v3, v4, v5) = &self.0;
+                ScanInput::create(builder, v0, v1, v2, v3, v4, v5)
+            }
+        }
+
+        /// Reference to a deserialized [ScanInput].
+        #[derive(Copy, Clone)]
+        pub struct ScanInputRef<'a>(#[allow(dead_code)] ::planus::table_reader::Table<'a>);
+
+        impl<'a> ScanInputRef<'a> {
+            /// Getter for the [`kind` field](ScanInput#structfield.kind).
+            #[inline]
+            pub fn kind(&self) -> ::planus::Result<self::ScanInputKind> {
+                ::core::result::Result::Ok(
+                    self.0.access(0, "ScanInput", "kind")?.unwrap_or(self::ScanInputKind::Full),
+                )
+            }
+
+            /// Getter for the [`slot` field](ScanInput#structfield.slot).
+            #[inline]
+            pub fn slot(&self) -> ::planus::Result<u64> {
+                ::core::result::Result::Ok(self.0.access(1, "ScanInput", "slot")?.unwrap_or(0))
+            }
+
+            /// Getter for the [`value` field](ScanInput#structfield.value).
+            #[inline]
+            pub fn value(&self) -> ::planus::Result<::core::option::Option<self::TensorRef<'a>>> {
+                self.0.access(2, "ScanInput", "value")
+            }
+
+            /// Getter for the [`axis` field](ScanInput#structfield.axis).
+            #[inline]
+            pub fn axis(&self) -> ::planus::Result<u64> {
+                ::core::result::Result::Ok(self.0.access(3, "ScanInput", "axis")?.unwrap_or(0))
+            }
+
+            /// Getter for the [`chunk` field](ScanInput#structfield.chunk).
+            #[inline]
+            pub fn chunk(&self) -> ::planus::Result<u64> {
+                ::core::result::Result::Ok(self.0.access(4, "ScanInput", "chunk")?.unwrap_or(0))
+            }
+
+            /// Getter for the [`reverse` field](ScanInput#structfield.reverse).
+            #[inline]
+            pub fn reverse(&self) -> ::planus::Result<bool> {
+                ::core::result::Result::Ok(
+                    self.0.access(5, "ScanInput", "reverse")?.unwrap_or(false),
+                )
+            }
+        }
+
+        impl<'a> ::core::fmt::Debug for ScanInputRef<'a> {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                let mut f = f.debug_struct("ScanInputRef");
+                f.field("kind", &self.kind());
+                f.field("slot", &self.slot());
+                if let ::core::option::Option::Some(field_value) = self.value().transpose() {
+                    f.field("value", &field_value);
+                }
+                f.field("axis", &self.axis());
+                f.field("chunk", &self.chunk());
+                f.field("reverse", &self.reverse());
+                f.finish()
+            }
+        }
+
+        impl<'a> ::core::convert::TryFrom<ScanInputRef<'a>> for ScanInput {
+            type Error = ::planus::Error;
+
+            #[allow(unreachable_code)]
+            fn try_from(value: ScanInputRef<'a>) -> ::planus::Result<Self> {
+                ::core::result::Result::Ok(Self {
+                    kind: ::core::convert::TryInto::try_into(value.kind()?)?,
+                    slot: ::core::convert::TryInto::try_into(value.slot()?)?,
+                    value: if let ::core::option::Option::Some(value) = value.value()? {
+                        ::core::option::Option::Some(::planus::alloc::boxed::Box::new(
+                            ::core::convert::TryInto::try_into(value)?,
+                        ))
+                    } else {
+                        ::core::option::Option::None
+                    },
+                    axis: ::core::convert::TryInto::try_into(value.axis()?)?,
+                    chunk: ::core::convert::TryInto::try_into(value.chunk()?)?,
+                    reverse: ::core::convert::TryInto::try_into(value.reverse()?)?,
+                })
+            }
+        }
+
+        impl<'a> ::planus::TableRead<'a> for ScanInputRef<'a> {
+            #[inline]
+            fn from_buffer(
+                buffer: ::planus::SliceWithStartOffset<'a>,
+                offset: usize,
+            ) -> ::core::result::Result<Self, ::planus::errors::ErrorKind> {
+                ::core::result::Result::Ok(Self(::planus::table_reader::Table::from_buffer(
+                    buffer, offset,
+                )?))
+            }
+        }
+
+        impl<'a> ::planus::VectorReadInner<'a> for ScanInputRef<'a> {
+            type Error = ::planus::Error;
+            const STRIDE: usize = 4;
+
+            unsafe fn from_buffer(
+                buffer: ::planus::SliceWithStartOffset<'a>,
+                offset: usize,
+            ) -> ::planus::Result<Self> {
+                ::planus::TableRead::from_buffer(buffer, offset).map_err(|error_kind| {
+                    error_kind.with_error_location(
+                        "[ScanInputRef]",
+                        "get",
+                        buffer.offset_from_start,
+                    )
+                })
+            }
+        }
+
+        /// # Safety
+        /// The planus compiler generates implementations that initialize
+        /// the bytes in `write_values`.
+        unsafe impl ::planus::VectorWrite<::planus::Offset<ScanInput>> for ScanInput {
+            type Value = ::planus::Offset<ScanInput>;
+            const STRIDE: usize = 4;
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> Self::Value {
+                ::planus::WriteAs::prepare(self, builder)
+            }
+
+            #[inline]
+            unsafe fn write_values(
+                values: &[::planus::Offset<ScanInput>],
+                bytes: *mut ::core::mem::MaybeUninit<u8>,
+                buffer_position: u32,
+            ) {
+                let bytes = bytes as *mut [::core::mem::MaybeUninit<u8>; 4];
+                for (i, v) in ::core::iter::Iterator::enumerate(values.iter()) {
+                    ::planus::WriteAsPrimitive::write(
+                        v,
+                        ::planus::Cursor::new(unsafe { &mut *bytes.add(i) }),
+                        buffer_position - (Self::STRIDE * i) as u32,
+                    );
+                }
+            }
+        }
+
+        impl<'a> ::planus::ReadAsRoot<'a> for ScanInputRef<'a> {
+            fn read_as_root(slice: &'a [u8]) -> ::planus::Result<Self> {
+                ::planus::TableRead::from_buffer(
+                    ::planus::SliceWithStartOffset { buffer: slice, offset_from_start: 0 },
+                    0,
+                )
+                .map_err(|error_kind| {
+                    error_kind.with_error_location("[ScanInputRef]", "read_as_root", 0)
+                })
+            }
+        }
+
+        /// The table `ScanOutput` in the namespace `tract`
+        ///
+        /// Generated from these locations:
+        /// * Table `ScanOutput` in the file `schema/tract.fbs:246`
+        #[derive(
+            Clone,
+            Debug,
+            PartialEq,
+            PartialOrd,
+            Eq,
+            Ord,
+            Hash,
+            ::serde::Serialize,
+            ::serde::Deserialize,
+        )]
+        pub struct ScanOutput {
+            /// The field `full_slot` in the table `ScanOutput`
+            pub full_slot: ::core::option::Option<u64>,
+            /// The field `axis` in the table `ScanOutput`
+            pub axis: u64,
+            /// The field `chunk` in the table `ScanOutput`
+            pub chunk: u64,
+            /// The field `full_dim_hint` in the table `ScanOutput`
+            pub full_dim_hint: ::core::option::Option<u64>,
+            /// The field `last_value_slot` in the table `ScanOutput`
+            pub last_value_slot: ::core::option::Option<u64>,
+            /// The field `state` in the table `ScanOutput`
+            pub state: bool,
+        }
+
+        #[allow(clippy::derivable_impls)]
+        impl ::core::default::Default for ScanOutput {
+            fn default() -> Self {
+                Self {
+                    full_slot: ::core::default::Default::default(),
+                    axis: 0,
+                    chunk: 0,
+                    full_dim_hint: ::core::default::Default::default(),
+                    last_value_slot: ::core::default::Default::default(),
+                    state: false,
+                }
+            }
+        }
+
+        impl ScanOutput {
+            /// Creates a [ScanOutputBuilder] for serializing an instance of this table.
+            #[inline]
+            pub fn builder() -> ScanOutputBuilder<()> {
+                ScanOutputBuilder(())
+            }
+
+            #[allow(clippy::too_many_arguments)]
+            pub fn create(
+                builder: &mut ::planus::Builder,
+                field_full_slot: impl ::planus::WriteAsOptional<u64>,
+                field_axis: impl ::planus::WriteAsDefault<u64, u64>,
+                field_chunk: impl ::planus::WriteAsDefault<u64, u64>,
+                field_full_dim_hint: impl ::planus::WriteAsOptional<u64>,
+                field_last_value_slot: impl ::planus::WriteAsOptional<u64>,
+                field_state: impl ::planus::WriteAsDefault<bool, bool>,
+            ) -> ::planus::Offset<Self> {
+                let prepared_full_slot = field_full_slot.prepare(builder);
+                let prepared_axis = field_axis.prepare(builder, &0);
+                let prepared_chunk = field_chunk.prepare(builder, &0);
+                let prepared_full_dim_hint = field_full_dim_hint.prepare(builder);
+                let prepared_last_value_slot = field_last_value_slot.prepare(builder);
+                let prepared_state = field_state.prepare(builder, &false);
+
+                let mut table_writer: ::planus::table_writer::TableWriter<16> =
+                    ::core::default::Default::default();
+                if prepared_full_slot.is_some() {
+                    table_writer.write_entry::<u64>(0);
+                }
+                if prepared_axis.is_some() {
+                    table_writer.write_entry::<u64>(1);
+                }
+                if prepared_chunk.is_some() {
+                    table_writer.write_entry::<u64>(2);
+                }
+                if prepared_full_dim_hint.is_some() {
+                    table_writer.write_entry::<u64>(3);
+                }
+                if prepared_last_value_slot.is_some() {
+                    table_writer.write_entry::<u64>(4);
+                }
+                if prepared_state.is_some() {
+                    table_writer.write_entry::<bool>(5);
+                }
+
+                unsafe {
+                    table_writer.finish(builder, |object_writer| {
+                        if let ::core::option::Option::Some(prepared_full_slot) = prepared_full_slot
+                        {
+                            object_writer.write::<_, _, 8>(&prepared_full_slot);
+                        }
+                        if let ::core::option::Option::Some(prepared_axis) = prepared_axis {
+                            object_writer.write::<_, _, 8>(&prepared_axis);
+                        }
+                        if let ::core::option::Option::Some(prepared_chunk) = prepared_chunk {
+                            object_writer.write::<_, _, 8>(&prepared_chunk);
+                        }
+                        if let ::core::option::Option::Some(prepared_full_dim_hint) =
+                            prepared_full_dim_hint
+                        {
+                            object_writer.write::<_, _, 8>(&prepared_full_dim_hint);
+                        }
+                        if let ::core::option::Option::Some(prepared_last_value_slot) =
+                            prepared_last_value_slot
+                        {
+                            object_writer.write::<_, _, 8>(&prepared_last_value_slot);
+                        }
+                        if let ::core::option::Option::Some(prepared_state) = prepared_state {
+                            object_writer.write::<_, _, 1>(&prepared_state);
+                        }
+                    });
+                }
+                builder.current_offset()
+            }
+        }
+
+        impl ::planus::WriteAs<::planus::Offset<ScanOutput>> for ScanOutput {
+            type Prepared = ::planus::Offset<Self>;
+
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<ScanOutput> {
+                ::planus::WriteAsOffset::prepare(self, builder)
+            }
+        }
+
+        impl ::planus::WriteAsOptional<::planus::Offset<ScanOutput>> for ScanOutput {
+            type Prepared = ::planus::Offset<Self>;
+
+            #[inline]
+            fn prepare(
+                &self,
+                builder: &mut ::planus::Builder,
+            ) -> ::core::option::Option<::planus::Offset<ScanOutput>> {
+                ::core::option::Option::Some(::planus::WriteAsOffset::prepare(self, builder))
+            }
+        }
+
+        impl ::planus::WriteAsOffset<ScanOutput> for ScanOutput {
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<ScanOutput> {
+                ScanOutput::create(
+                    builder,
+                    self.full_slot,
+                    self.axis,
+                    self.chunk,
+                    self.full_dim_hint,
+                    self.last_value_slot,
+                    self.state,
+                )
+            }
+        }
+
+        /// Builder for serializing an instance of the [ScanOutput] type.
+        ///
+        /// Can be created using the [ScanOutput::builder] method.
+        #[derive(Debug)]
+        #[must_use]
+        pub struct ScanOutputBuilder<State>(State);
+
+        impl ScanOutputBuilder<()> {
+            /// Setter for the [`full_slot` field](ScanOutput#structfield.full_slot).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn full_slot<T0>(self, value: T0) -> ScanOutputBuilder<(T0,)>
+            where
+                T0: ::planus::WriteAsOptional<u64>,
+            {
+                ScanOutputBuilder((value,))
+            }
+
+            /// Sets the [`full_slot` field](ScanOutput#structfield.full_slot) to null.
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn full_slot_as_null(self) -> ScanOutputBuilder<((),)> {
+                self.full_slot(())
+            }
+        }
+
+        impl<T0> ScanOutputBuilder<(T0,)> {
+            /// Setter for the [`axis` field](ScanOutput#structfield.axis).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn axis<T1>(self, value: T1) -> ScanOutputBuilder<(T0, T1)>
+            where
+                T1: ::planus::WriteAsDefault<u64, u64>,
+            {
+                let (v0,) = self.0;
+                ScanOutputBuilder((v0, value))
+            }
+
+            /// Sets the [`axis` field](ScanOutput#structfield.axis) to the default value.
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn axis_as_default(self) -> ScanOutputBuilder<(T0, ::planus::DefaultValue)> {
+                self.axis(::planus::DefaultValue)
+            }
+        }
+
+        impl<T0, T1> ScanOutputBuilder<(T0, T1)> {
+            /// Setter for the [`chunk` field](ScanOutput#structfield.chunk).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn chunk<T2>(self, value: T2) -> ScanOutputBuilder<(T0, T1, T2)>
+            where
+                T2: ::planus::WriteAsDefault<u64, u64>,
+            {
+                let (v0, v1) = self.0;
+                ScanOutputBuilder((v0, v1, value))
+            }
+
+            /// Sets the [`chunk` field](ScanOutput#structfield.chunk) to the default value.
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn chunk_as_default(self) -> ScanOutputBuilder<(T0, T1, ::planus::DefaultValue)> {
+                self.chunk(::planus::DefaultValue)
+            }
+        }
+
+        impl<T0, T1, T2> ScanOutputBuilder<(T0, T1, T2)> {
+            /// Setter for the [`full_dim_hint` field](ScanOutput#structfield.full_dim_hint).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn full_dim_hint<T3>(self, value: T3) -> ScanOutputBuilder<(T0, T1, T2, T3)>
+            where
+                T3: ::planus::WriteAsOptional<u64>,
+            {
+                let (v0, v1, v2) = self.0;
+                ScanOutputBuilder((v0, v1, v2, value))
+            }
+
+            /// Sets the [`full_dim_hint` field](ScanOutput#structfield.full_dim_hint) to null.
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn full_dim_hint_as_null(self) -> ScanOutputBuilder<(T0, T1, T2, ())> {
+                self.full_dim_hint(())
+            }
+        }
+
+        impl<T0, T1, T2, T3> ScanOutputBuilder<(T0, T1, T2, T3)> {
+            /// Setter for the [`last_value_slot` field](ScanOutput#structfield.last_value_slot).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn last_value_slot<T4>(self, value: T4) -> ScanOutputBuilder<(T0, T1, T2, T3, T4)>
+            where
+                T4: ::planus::WriteAsOptional<u64>,
+            {
+                let (v0, v1, v2, v3) = self.0;
+                ScanOutputBuilder((v0, v1, v2, v3, value))
+            }
+
+            /// Sets the [`last_value_slot` field](ScanOutput#structfield.last_value_slot) to null.
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn last_value_slot_as_null(self) -> ScanOutputBuilder<(T0, T1, T2, T3, ())> {
+                self.last_value_slot(())
+            }
+        }
+
+        impl<T0, T1, T2, T3, T4> ScanOutputBuilder<(T0, T1, T2, T3, T4)> {
+            /// Setter for the [`state` field](ScanOutput#structfield.state).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn state<T5>(self, value: T5) -> ScanOutputBuilder<(T0, T1, T2, T3, T4, T5)>
+            where
+                T5: ::planus::WriteAsDefault<bool, bool>,
+            {
+                let (v0, v1, v2, v3, v4) = self.0;
+                ScanOutputBuilder((v0, v1, v2, v3, v4, value))
+            }
+
+            /// Sets the [`state` field](ScanOutput#structfield.state) to the default value.
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn state_as_default(
+                self,
+            ) -> ScanOutputBuilder<(T0, T1, T2, T3, T4, ::planus::DefaultValue)> {
+                self.state(::planus::DefaultValue)
+            }
+        }
+
+        impl<T0, T1, T2, T3, T4, T5> ScanOutputBuilder<(T0, T1, T2, T3, T4, T5)> {
+            /// Finish writing the builder to get an [Offset](::planus::Offset) to a serialized [ScanOutput].
+            #[inline]
+            pub fn finish(self, builder: &mut ::planus::Builder) -> ::planus::Offset<ScanOutput>
+            where
+                Self: ::planus::WriteAsOffset<ScanOutput>,
+            {
+                ::planus::WriteAsOffset::prepare(&self, builder)
+            }
+        }
+
+        impl<
+                T0: ::planus::WriteAsOptional<u64>,
+                T1: ::planus::WriteAsDefault<u64, u64>,
+                T2: ::planus::WriteAsDefault<u64, u64>,
+                T3: ::planus::WriteAsOptional<u64>,
+                T4: ::planus::WriteAsOptional<u64>,
+                T5: ::planus::WriteAsDefault<bool, bool>,
+            > ::planus::WriteAs<::planus::Offset<ScanOutput>>
+            for ScanOutputBuilder<(T0, T1, T2, T3, T4, T5)>
+        {
+            type Prepared = ::planus::Offset<ScanOutput>;
+
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<ScanOutput> {
+                ::planus::WriteAsOffset::prepare(self, builder)
+            }
+        }
+
+        impl<
+                T0: ::planus::WriteAsOptional<u64>,
+                T1: ::planus::WriteAsDefault<u64, u64>,
+                T2: ::planus::WriteAsDefault<u64, u64>,
+                T3: ::planus::WriteAsOptional<u64>,
+                T4: ::planus::WriteAsOptional<u64>,
+                T5: ::planus::WriteAsDefault<bool, bool>,
+            > ::planus::WriteAsOptional<::planus::Offset<ScanOutput>>
+            for ScanOutputBuilder<(T0, T1, T2, T3, T4, T5)>
+        {
+            type Prepared = ::planus::Offset<ScanOutput>;
+
+            #[inline]
+            fn prepare(
+                &self,
+                builder: &mut ::planus::Builder,
+            ) -> ::core::option::Option<::planus::Offset<ScanOutput>> {
+                ::core::option::Option::Some(::planus::WriteAsOffset::prepare(self, builder))
+            }
+        }
+
+        impl<
+                T0: ::planus::WriteAsOptional<u64>,
+                T1: ::planus::WriteAsDefault<u64, u64>,
+                T2: ::planus::WriteAsDefault<u64, u64>,
+                T3: ::planus::WriteAsOptional<u64>,
+                T4: ::planus::WriteAsOptional<u64>,
+                T5: ::planus::WriteAsDefault<bool, bool>,
+            > ::planus::WriteAsOffset<ScanOutput> for ScanOutputBuilder<(T0, T1, T2, T3, T4, T5)>
+        {
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<ScanOutput> {
+                let (v0, v1, v2, v3, v4, v5) = &self.0;
+                ScanOutput::create(builder, v0, v1, v2, v3, v4, v5)
+            }
+        }
+
+        /// Reference to a deserialized [ScanOutput].
+        #[derive(Copy, Clone)]
+        pub struct ScanOutputRef<'a>(#[allow(dead_code)] ::planus::table_reader::Table<'a>);
+
+        impl<'a> ScanOutputRef<'a> {
+            /// Getter for the [`full_slot` field](ScanOutput#structfield.full_slot).
+            #[inline]
+            pub fn full_slot(&self) -> ::planus::Result<::core::option::Option<u64>> {
+                self.0.access(0, "ScanOutput", "full_slot")
+            }
+
+            /// Getter for the [`axis` field](ScanOutput#structfield.axis).
+            #[inline]
+            pub fn axis(&self) -> ::planus::Result<u64> {
+                ::core::result::Result::Ok(self.0.access(1, "ScanOutput", "axis")?.unwrap_or(0))
+            }
+
+            /// Getter for the [`chunk` field](ScanOutput#structfield.chunk).
+            #[inline]
+            pub fn chunk(&self) -> ::planus::Result<u64> {
+                ::core::result::Result::Ok(self.0.access(2, "ScanOutput", "chunk")?.unwrap_or(0))
+            }
+
+            /// Getter for the [`full_dim_hint` field](ScanOutput#structfield.full_dim_hint).
+            #[inline]
+            pub fn full_dim_hint(&self) -> ::planus::Result<::core::option::Option<u64>> {
+                self.0.access(3, "ScanOutput", "full_dim_hint")
+            }
+
+            /// Getter for the [`last_value_slot` field](ScanOutput#structfield.last_value_slot).
+            #[inline]
+            pub fn last_value_slot(&self) -> ::planus::Result<::core::option::Option<u64>> {
+                self.0.access(4, "ScanOutput", "last_value_slot")
+            }
+
+            /// Getter for the [`state` field](ScanOutput#structfield.state).
+            #[inline]
+            pub fn state(&self) -> ::planus::Result<bool> {
+                ::core::result::Result::Ok(
+                    self.0.access(5, "ScanOutput", "state")?.unwrap_or(false),
+                )
+            }
+        }
+
+        impl<'a> ::core::fmt::Debug for ScanOutputRef<'a> {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                let mut f = f.debug_struct("ScanOutputRef");
+                if let ::core::option::Option::Some(field_full_slot) = self.full_slot().transpose()
+                {
+                    f.field("full_slot", &field_full_slot);
+                }
+                f.field("axis", &self.axis());
+                f.field("chunk", &self.chunk());
+                if let ::core::option::Option::Some(field_full_dim_hint) =
+                    self.full_dim_hint().transpose()
+                {
+                    f.field("full_dim_hint", &field_full_dim_hint);
+                }
+                if let ::core::option::Option::Some(field_last_value_slot) =
+                    self.last_value_slot().transpose()
+                {
+                    f.field("last_value_slot", &field_last_value_slot);
+                }
+                f.field("state", &self.state());
+                f.finish()
+            }
+        }
+
+        impl<'a> ::core::convert::TryFrom<ScanOutputRef<'a>> for ScanOutput {
+            type Error = ::planus::Error;
+
+            #[allow(unreachable_code)]
+            fn try_from(value: ScanOutputRef<'a>) -> ::planus::Result<Self> {
+                ::core::result::Result::Ok(Self {
+                    full_slot: if let ::core::option::Option::Some(full_slot) = value.full_slot()? {
+                        ::core::option::Option::Some(::core::convert::TryInto::try_into(full_slot)?)
+                    } else {
+                        ::core::option::Option::None
+                    },
+                    axis: ::core::convert::TryInto::try_into(value.axis()?)?,
+                    chunk: ::core::convert::TryInto::try_into(value.chunk()?)?,
+                    full_dim_hint: if let ::core::option::Option::Some(full_dim_hint) =
+                        value.full_dim_hint()?
+                    {
+                        ::core::option::Option::Some(::core::convert::TryInto::try_into(
+                            full_dim_hint,
+                        )?)
+                    } else {
+                        ::core::option::Option::None
+                    },
+                    last_value_slot: if let ::core::option::Option::Some(last_value_slot) =
+                        value.last_value_slot()?
+                    {
+                        ::core::option::Option::Some(::core::convert::TryInto::try_into(
+                            last_value_slot,
+                        )?)
+                    } else {
+                        ::core::option::Option::None
+                    },
+                    state: ::core::convert::TryInto::try_into(value.state()?)?,
+                })
+            }
+        }
+
+        impl<'a> ::planus::TableRead<'a> for ScanOutputRef<'a> {
+            #[inline]
+            fn from_buffer(
+                buffer: ::planus::SliceWithStartOffset<'a>,
+                offset: usize,
+            ) -> ::core::result::Result<Self, ::planus::errors::ErrorKind> {
+                ::core::result::Result::Ok(Self(::planus::table_reader::Table::from_buffer(
+                    buffer, offset,
+                )?))
+            }
+        }
+
+        impl<'a> ::planus::VectorReadInner<'a> for ScanOutputRef<'a> {
+            type Error = ::planus::Error;
+            const STRIDE: usize = 4;
+
+            unsafe fn from_buffer(
+                buffer: ::planus::SliceWithStartOffset<'a>,
+                offset: usize,
+            ) -> ::planus::Result<Self> {
+                ::planus::TableRead::from_buffer(buffer, offset).map_err(|error_kind| {
+                    error_kind.with_error_location(
+                        "[ScanOutputRef]",
+                        "get",
+                        buffer.offset_from_start,
+                    )
+                })
+            }
+        }
+
+        /// # Safety
+        /// The planus compiler generates implementations that initialize
+        /// the bytes in `write_values`.
+        unsafe impl ::planus::VectorWrite<::planus::Offset<ScanOutput>> for ScanOutput {
+            type Value = ::planus::Offset<ScanOutput>;
+            const STRIDE: usize = 4;
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> Self::Value {
+                ::planus::WriteAs::prepare(self, builder)
+            }
+
+            #[inline]
+            unsafe fn write_values(
+                values: &[::planus::Offset<ScanOutput>],
+                bytes: *mut ::core::mem::MaybeUninit<u8>,
+                buffer_position: u32,
+            ) {
+                let bytes = bytes as *mut [::core::mem::MaybeUninit<u8>; 4];
+                for (i, v) in ::core::iter::Iterator::enumerate(values.iter()) {
+                    ::planus::WriteAsPrimitive::write(
+                        v,
+                        ::planus::Cursor::new(unsafe { &mut *bytes.add(i) }),
+                        buffer_position - (Self::STRIDE * i) as u32,
+                    );
+                }
+            }
+        }
+
+        impl<'a> ::planus::ReadAsRoot<'a> for ScanOutputRef<'a> {
+            fn read_as_root(slice: &'a [u8]) -> ::planus::Result<Self> {
+                ::planus::TableRead::from_buffer(
+                    ::planus::SliceWithStartOffset { buffer: slice, offset_from_start: 0 },
+                    0,
+                )
+                .map_err(|error_kind| {
+                    error_kind.with_error_location("[ScanOutputRef]", "read_as_root", 0)
+                })
+            }
+        }
+
+        /// The table `Scan` in the namespace `tract`
+        ///
+        /// Generated from these locations:
+        /// * Table `Scan` in the file `schema/tract.fbs:255`
+        #[derive(Clone, Debug, PartialEq, PartialOrd, ::serde::Serialize, ::serde::Deserialize)]
+        pub struct Scan {
+            /// The field `body` in the table `Scan`
+            pub body: ::planus::alloc::boxed::Box<self::Model>,
+            /// The field `skip` in the table `Scan`
+            pub skip: u64,
+            /// The field `seq_length_input_slot` in the table `Scan`
+            pub seq_length_input_slot: ::core::option::Option<u64>,
+            /// The field `input_mapping` in the table `Scan`
+            pub input_mapping: ::planus::alloc::vec::Vec<self::ScanInput>,
+            /// The field `output_mapping` in the table `Scan`
+            pub output_mapping: ::planus::alloc::vec::Vec<self::ScanOutput>,
+        }
+
+        #[allow(clippy::derivable_impls)]
+        impl ::core::default::Default for Scan {
+            fn default() -> Self {
+                Self {
+                    body: ::core::default::Default::default(),
+                    skip: 0,
+                    seq_length_input_slot: ::core::default::Default::default(),
+                    input_mapping: ::core::default::Default::default(),
+                    output_mapping: ::core::default::Default::default(),
+                }
+            }
+        }
+
+        impl Scan {
+            /// Creates a [ScanBuilder] for serializing an instance of this table.
+            #[inline]
+            pub fn builder() -> ScanBuilder<()> {
+                ScanBuilder(())
+            }
+
+            #[allow(clippy::too_many_arguments)]
+            pub fn create(
+                builder: &mut ::planus::Builder,
+                field_body: impl ::planus::WriteAs<::planus::Offset<self::Model>>,
+                field_skip: impl ::planus::WriteAsDefault<u64, u64>,
+                field_seq_length_input_slot: impl ::planus::WriteAsOptional<u64>,
+                field_input_mapping: impl ::planus::WriteAs<
+                    ::planus::Offset<[::planus::Offset<self::ScanInput>]>,
+                >,
+                field_output_mapping: impl ::planus::WriteAs<
+                    ::planus::Offset<[::planus::Offset<self::ScanOutput>]>,
+                >,
+            ) -> ::planus::Offset<Self> {
+                let prepared_body = field_body.prepare(builder);
+                let prepared_skip = field_skip.prepare(builder, &0);
+                let prepared_seq_length_input_slot = field_seq_length_input_slot.prepare(builder);
+                let prepared_input_mapping = field_input_mapping.prepare(builder);
+                let prepared_output_mapping = field_output_mapping.prepare(builder);
+
+                let mut table_writer: ::planus::table_writer::TableWriter<14> =
+                    ::core::default::Default::default();
+                if prepared_skip.is_some() {
+                    table_writer.write_entry::<u64>(1);
+                }
+                if prepared_seq_length_input_slot.is_some() {
+                    table_writer.write_entry::<u64>(2);
+                }
+                table_writer.write_entry::<::planus::Offset<self::Model>>(0);
+                table_writer
+                    .write_entry::<::planus::Offset<[::planus::Offset<self::ScanInput>]>>(3);
+                table_writer
+                    .write_entry::<::planus::Offset<[::planus::Offset<self::ScanOutput>]>>(4);
+
+                unsafe {
+                    table_writer.finish(builder, |object_writer| {
+                        if let ::core::option::Option::Some(prepared_skip) = prepared_skip {
+                            object_writer.write::<_, _, 8>(&prepared_skip);
+                        }
+                        if let ::core::option::Option::Some(prepared_seq_length_input_slot) =
+                            prepared_seq_length_input_slot
+                        {
+                            object_writer.write::<_, _, 8>(&prepared_seq_length_input_slot);
+                        }
+                        object_writer.write::<_, _, 4>(&prepared_body);
+                        object_writer.write::<_, _, 4>(&prepared_input_mapping);
+                        object_writer.write::<_, _, 4>(&prepared_output_mapping);
+                    });
+                }
+                builder.current_offset()
+            }
+        }
+
+        impl ::planus::WriteAs<::planus::Offset<Scan>> for Scan {
+            type Prepared = ::planus::Offset<Self>;
+
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<Scan> {
+                ::planus::WriteAsOffset::prepare(self, builder)
+            }
+        }
+
+        impl ::planus::WriteAsOptional<::planus::Offset<Scan>> for Scan {
+            type Prepared = ::planus::Offset<Self>;
+
+            #[inline]
+            fn prepare(
+                &self,
+                builder: &mut ::planus::Builder,
+            ) -> ::core::option::Option<::planus::Offset<Scan>> {
+                ::core::option::Option::Some(::planus::WriteAsOffset::prepare(self, builder))
+            }
+        }
+
+        impl ::planus::WriteAsOffset<Scan> for Scan {
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<Scan> {
+                Scan::create(
+                    builder,
+                    &self.body,
+                    self.skip,
+                    self.seq_length_input_slot,
+                    &self.input_mapping,
+                    &self.output_mapping,
+                )
+            }
+        }
+
+        /// Builder for serializing an instance of the [Scan] type.
+        ///
+        /// Can be created using the [Scan::builder] method.
+        #[derive(Debug)]
+        #[must_use]
+        pub struct ScanBuilder<State>(State);
+
+        impl ScanBuilder<()> {
+            /// Setter for the [`body` field](Scan#structfield.body).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn body<T0>(self, value: T0) -> ScanBuilder<(T0,)>
+            where
+                T0: ::planus::WriteAs<::planus::Offset<self::Model>>,
+            {
+                ScanBuilder((value,))
+            }
+        }
+
+        impl<T0> ScanBuilder<(T0,)> {
+            /// Setter for the [`skip` field](Scan#structfield.skip).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn skip<T1>(self, value: T1) -> ScanBuilder<(T0, T1)>
+            where
+                T1: ::planus::WriteAsDefault<u64, u64>,
+            {
+                let (v0,) = self.0;
+                ScanBuilder((v0, value))
+            }
+
+            /// Sets the [`skip` field](Scan#structfield.skip) to the default value.
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn skip_as_default(self) -> ScanBuilder<(T0, ::planus::DefaultValue)> {
+                self.skip(::planus::DefaultValue)
+            }
+        }
+
+        impl<T0, T1> ScanBuilder<(T0, T1)> {
+            /// Setter for the [`seq_length_input_slot` field](Scan#structfield.seq_length_input_slot).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn seq_length_input_slot<T2>(self, value: T2) -> ScanBuilder<(T0, T1, T2)>
+            where
+                T2: ::planus::WriteAsOptional<u64>,
+            {
+                let (v0, v1) = self.0;
+                ScanBuilder((v0, v1, value))
+            }
+
+            /// Sets the [`seq_length_input_slot` field](Scan#structfield.seq_length_input_slot) to null.
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn seq_length_input_slot_as_null(self) -> ScanBuilder<(T0, T1, ())> {
+                self.seq_length_input_slot(())
+            }
+        }
+
+        impl<T0, T1, T2> ScanBuilder<(T0, T1, T2)> {
+            /// Setter for the [`input_mapping` field](Scan#structfield.input_mapping).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn input_mapping<T3>(self, value: T3) -> ScanBuilder<(T0, T1, T2, T3)>
+            where
+                T3: ::planus::WriteAs<::planus::Offset<[::planus::Offset<self::ScanInput>]>>,
+            {
+                let (v0, v1, v2) = self.0;
+                ScanBuilder((v0, v1, v2, value))
+            }
+        }
+
+        impl<T0, T1, T2, T3> ScanBuilder<(T0, T1, T2, T3)> {
+            /// Setter for the [`output_mapping` field](Scan#structfield.output_mapping).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn output_mapping<T4>(self, value: T4) -> ScanBuilder<(T0, T1, T2, T3, T4)>
+            where
+                T4: ::planus::WriteAs<::planus::Offset<[::planus::Offset<self::ScanOutput>]>>,
+            {
+                let (v0, v1, v2, v3) = self.0;
+                ScanBuilder((v0, v1, v2, v3, value))
+            }
+        }
+
+        impl<T0, T1, T2, T3, T4> ScanBuilder<(T0, T1, T2, T3, T4)> {
+            /// Finish writing the builder to get an [Offset](::planus::Offset) to a serialized [Scan].
+            #[inline]
+            pub fn finish(self, builder: &mut ::planus::Builder) -> ::planus::Offset<Scan>
+            where
+                Self: ::planus::WriteAsOffset<Scan>,
+            {
+                ::planus::WriteAsOffset::prepare(&self, builder)
+            }
+        }
+
+        impl<
+                T0: ::planus::WriteAs<::planus::Offset<self::Model>>,
+                T1: ::planus::WriteAsDefault<u64, u64>,
+                T2: ::planus::WriteAsOptional<u64>,
+                T3: ::planus::WriteAs<::planus::Offset<[::planus::Offset<self::ScanInput>]>>,
+                T4: ::planus::WriteAs<::planus::Offset<[::planus::Offset<self::ScanOutput>]>>,
+            > ::planus::WriteAs<::planus::Offset<Scan>> for ScanBuilder<(T0, T1, T2, T3, T4)>
+        {
+            type Prepared = ::planus::Offset<Scan>;
+
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<Scan> {
+                ::planus::WriteAsOffset::prepare(self, builder)
+            }
+        }
+
+        impl<
+                T0: ::planus::WriteAs<::planus::Offset<self::Model>>,
+                T1: ::planus::WriteAsDefault<u64, u64>,
+                T2: ::planus::WriteAsOptional<u64>,
+                T3: ::planus::WriteAs<::planus::Offset<[::planus::Offset<self::ScanInput>]>>,
+                T4: ::planus::WriteAs<::planus::Offset<[::planus::Offset<self::ScanOutput>]>>,
+            > ::planus::WriteAsOptional<::planus::Offset<Scan>>
+            for ScanBuilder<(T0, T1, T2, T3, T4)>
+        {
+            type Prepared = ::planus::Offset<Scan>;
+
+            #[inline]
+            fn prepare(
+                &self,
+                builder: &mut ::planus::Builder,
+            ) -> ::core::option::Option<::planus::Offset<Scan>> {
+                ::core::option::Option::Some(::planus::WriteAsOffset::prepare(self, builder))
+            }
+        }
+
+        impl<
+                T0: ::planus::WriteAs<::planus::Offset<self::Model>>,
+                T1: ::planus::WriteAsDefault<u64, u64>,
+                T2: ::planus::WriteAsOptional<u64>,
+                T3: ::planus::WriteAs<::planus::Offset<[::planus::Offset<self::ScanInput>]>>,
+                T4: ::planus::WriteAs<::planus::Offset<[::planus::Offset<self::ScanOutput>]>>,
+            > ::planus::WriteAsOffset<Scan> for ScanBuilder<(T0, T1, T2, T3, T4)>
+        {
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<Scan> {
+                let (v0, v1, v2, v3, v4) = &self.0;
+                Scan::create(builder, v0, v1, v2, v3, v4)
+            }
+        }
+
+        /// Reference to a deserialized [Scan].
+        #[derive(Copy, Clone)]
+        pub struct ScanRef<'a>(#[allow(dead_code)] ::planus::table_reader::Table<'a>);
+
+        impl<'a> ScanRef<'a> {
+            /// Getter for the [`body` field](Scan#structfield.body).
+            #[inline]
+            pub fn body(&self) -> ::planus::Result<self::ModelRef<'a>> {
+                self.0.access_required(0, "Scan", "body")
+            }
+
+            /// Getter for the [`skip` field](Scan#structfield.skip).
+            #[inline]
+            pub fn skip(&self) -> ::planus::Result<u64> {
+                ::core::result::Result::Ok(self.0.access(1, "Scan", "skip")?.unwrap_or(0))
+            }
+
+            /// Getter for the [`seq_length_input_slot` field](Scan#structfield.seq_length_input_slot).
+            #[inline]
+            pub fn seq_length_input_slot(&self) -> ::planus::Result<::core::option::Option<u64>> {
+                self.0.access(2, "Scan", "seq_length_input_slot")
+            }
+
+            /// Getter for the [`input_mapping` field](Scan#structfield.input_mapping).
+            #[inline]
+            pub fn input_mapping(
+                &self,
+            ) -> ::planus::Result<::planus::Vector<'a, ::planus::Result<self::ScanInputRef<'a>>>>
+            {
+                self.0.access_required(3, "Scan", "input_mapping")
+            }
+
+            /// Getter for the [`output_mapping` field](Scan#structfield.output_mapping).
+            #[inline]
+            pub fn output_mapping(
+                &self,
+            ) -> ::planus::Result<::planus::Vector<'a, ::planus::Result<self::ScanOutputRef<'a>>>>
+            {
+                self.0.access_required(4, "Scan", "output_mapping")
+            }
+        }
+
+        impl<'a> ::core::fmt::Debug for ScanRef<'a> {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                let mut f = f.debug_struct("ScanRef");
+                f.field("body", &self.body());
+                f.field("skip", &self.skip());
+                if let ::core::option::Option::Some(field_seq_length_input_slot) =
+                    self.seq_length_input_slot().transpose()
+                {
+                    f.field("seq_length_input_slot", &field_seq_length_input_slot);
+                }
+                f.field("input_mapping", &self.input_mapping());
+                f.field("output_mapping", &self.output_mapping());
+                f.finish()
+            }
+        }
+
+        impl<'a> ::core::convert::TryFrom<ScanRef<'a>> for Scan {
+            type Error = ::planus::Error;
+
+            #[allow(unreachable_code)]
+            fn try_from(value: ScanRef<'a>) -> ::planus::Result<Self> {
+                ::core::result::Result::Ok(Self {
+                    body: ::planus::alloc::boxed::Box::new(::core::convert::TryInto::try_into(
+                        value.body()?,
+                    )?),
+                    skip: ::core::convert::TryInto::try_into(value.skip()?)?,
+                    seq_length_input_slot: if let ::core::option::Option::Some(
+                        seq_length_input_slot,
+                    ) = value.seq_length_input_slot()?
+                    {
+                        ::core::option::Option::Some(::core::convert::TryInto::try_into(
+                            seq_length_input_slot,
+                        )?)
+                    } else {
+                        ::core::option::Option::None
+                    },
+                    input_mapping: value.input_mapping()?.to_vec_result()?,
+                    output_mapping: value.output_mapping()?.to_vec_result()?,
+                })
+            }
+        }
+
+        impl<'a> ::planus::TableRead<'a> for ScanRef<'a> {
+            #[inline]
+            fn from_buffer(
+                buffer: ::planus::SliceWithStartOffset<'a>,
+                offset: usize,
+            ) -> ::core::result::Result<Self, ::planus::errors::ErrorKind> {
+                ::core::result::Result::Ok(Self(::planus::table_reader::Table::from_buffer(
+                    buffer, offset,
+                )?))
+            }
+        }
+
+        impl<'a> ::planus::VectorReadInner<'a> for ScanRef<'a> {
+            type Error = ::planus::Error;
+            const STRIDE: usize = 4;
+
+            unsafe fn from_buffer(
+                buffer: ::planus::SliceWithStartOffset<'a>,
+                offset: usize,
+            ) -> ::planus::Result<Self> {
+                ::planus::TableRead::from_buffer(buffer, offset).map_err(|error_kind| {
+                    error_kind.with_error_location("[ScanRef]", "get", buffer.offset_from_start)
+                })
+            }
+        }
+
+        /// # Safety
+        /// The planus compiler generates implementations that initialize
+        /// the bytes in `write_values`.
+        unsafe impl ::planus::VectorWrite<::planus::Offset<Scan>> for Scan {
+            type Value = ::planus::Offset<Scan>;
+            const STRIDE: usize = 4;
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> Self::Value {
+                ::planus::WriteAs::prepare(self, builder)
+            }
+
+            #[inline]
+            unsafe fn write_values(
+                values: &[::planus::Offset<Scan>],
+                bytes: *mut ::core::mem::MaybeUninit<u8>,
+                buffer_position: u32,
+            ) {
+                let bytes = bytes as *mut [::core::mem::MaybeUninit<u8>; 4];
+                for (i, v) in ::core::iter::Iterator::enumerate(values.iter()) {
+                    ::planus::WriteAsPrimitive::write(
+                        v,
+                        ::planus::Cursor::new(unsafe { &mut *bytes.add(i) }),
+                        buffer_position - (Self::STRIDE * i) as u32,
+                    );
+                }
+            }
+        }
+
+        impl<'a> ::planus::ReadAsRoot<'a> for ScanRef<'a> {
+            fn read_as_root(slice: &'a [u8]) -> ::planus::Result<Self> {
+                ::planus::TableRead::from_buffer(
+                    ::planus::SliceWithStartOffset { buffer: slice, offset_from_start: 0 },
+                    0,
+                )
+                .map_err(|error_kind| {
+                    error_kind.with_error_location("[ScanRef]", "read_as_root", 0)
+                })
+            }
+        }
+
+        /// The table `PatchSpec` in the namespace `tract`
+        ///
+        /// Generated from these locations:
+        /// * Table `PatchSpec` in the file `schema/tract.fbs:269`
+        #[derive(
+            Clone,
+            Debug,
+            PartialEq,
+            PartialOrd,
+            Eq,
+            Ord,
+            Hash,
+            ::serde::Serialize,
+            ::serde::Deserialize,
+        )]
+        pub struct PatchSpec {
+            /// The field `input_shape` in the table `PatchSpec`
+            pub input_shape: ::planus::alloc::vec::Vec<u64>,
+            /// The field `input_inner_stride` in the table `PatchSpec`
+            pub input_inner_stride: u64,
+            /// The field `output_inner_stride` in the table `PatchSpec`
+            pub output_inner_stride: u64,
+            /// The field `kernel_shape` in the table `PatchSpec`
+            pub kernel_shape: ::planus::alloc::vec::Vec<u64>,
+            /// The field `strides` in the table `PatchSpec`
+            pub strides: ::planus::alloc::vec::Vec<u64>,
+            /// The field `dilations` in the table `PatchSpec`
+            pub dilations: ::planus::alloc::vec::Vec<u64>,
+            /// The field `padding` in the table `PatchSpec`
+            pub padding: ::planus::alloc::boxed::Box<self::Padding>,
+        }
+
+        #[allow(clippy::derivable_impls)]
+        impl ::core::default::Default for PatchSpec {
+            fn default() -> Self {
+                Self {
+                    input_shape: ::core::default::Default::default(),
+                    input_inner_stride: 0,
+                    output_inner_stride: 0,
+                    kernel_shape: ::core::default::Default::default(),
+                    strides: ::core::default::Default::default(),
+                    dilations: ::core::default::Default::default(),
+                    padding: ::core::default::Default::default(),
+                }
+            }
+        }
+
+        impl PatchSpec {
+            /// Creates a [PatchSpecBuilder] for serializing an instance of this table.
+            #[inline]
+            pub fn builder() -> PatchSpecBuilder<()> {
+                PatchSpecBuilder(())
+            }
+
+            #[allow(clippy::too_many_arguments)]
+            pub fn create(
+                builder: &mut ::planus::Builder,
+                field_input_shape: impl ::planus::WriteAs<::planus::Offset<[u64]>>,
+                field_input_inner_stride: impl ::planus::WriteAsDefault<u64, u64>,
+                field_output_inner_stride: impl ::planus::WriteAsDefault<u64, u64>,
+                field_kernel_shape: impl ::planus::WriteAs<::planus::Offset<[u64]>>,
+                field_strides: impl ::planus::WriteAs<::planus::Offset<[u64]>>,
+                field_dilations: impl ::planus::WriteAs<::planus::Offset<[u64]>>,
+                field_padding: impl ::planus::WriteAs<::planus::Offset<self::Padding>>,
+            ) -> ::planus::Offset<Self> {
+                let prepared_input_shape = field_input_shape.prepare(builder);
+                let prepared_input_inner_stride = field_input_inner_stride.prepare(builder, &0);
+                let prepared_output_inner_stride = field_output_inner_stride.prepare(builder, &0);
+                let prepared_kernel_shape = field_kernel_shape.prepare(builder);
+                let prepared_strides = field_strides.prepare(builder);
+                let prepared_dilations = field_dilations.prepare(builder);
+                let prepared_padding = field_padding.prepare(builder);
+
+                let mut table_writer: ::planus::table_writer::TableWriter<18> =
+                    ::core::default::Default::default();
+                if prepared_input_inner_stride.is_some() {
+                    table_writer.write_entry::<u64>(1);
+                }
+                if prepared_output_inner_stride.is_some() {
+                    table_writer.write_entry::<u64>(2);
+                }
+                table_writer.write_entry::<::planus::Offset<[u64]>>(0);
+                table_writer.write_entry::<::planus::Offset<[u64]>>(3);
+                table_writer.write_entry::<::planus::Offset<[u64]>>(4);
+                table_writer.write_entry::<::planus::Offset<[u64]>>(5);
+                table_writer.write_entry::<::planus::Offset<self::Padding>>(6);
+
+                unsafe {
+                    table_writer.finish(builder, |object_writer| {
+                        if let ::core::option::Option::Some(prepared_input_inner_stride) =
+                            prepared_input_inner_stride
+                        {
+                            object_writer.write::<_, _, 8>(&prepared_input_inner_stride);
+                        }
+                        if let ::core::option::Option::Some(prepared_output_inner_stride) =
+                            prepared_output_inner_stride
+                        {
+                            object_writer.write::<_, _, 8>(&prepared_output_inner_stride);
+                        }
+                        object_writer.write::<_, _, 4>(&prepared_input_shape);
+                        object_writer.write::<_, _, 4>(&prepared_kernel_shape);
+                        object_writer.write::<_, _, 4>(&prepared_strides);
+                        object_writer.write::<_, _, 4>(&prepared_dilations);
+                        object_writer.write::<_, _, 4>(&prepared_padding);
+                    });
+                }
+                builder.current_offset()
+            }
+        }
+
+        impl ::planus::WriteAs<::planus::Offset<PatchSpec>> for PatchSpec {
+            type Prepared = ::planus::Offset<Self>;
+
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<PatchSpec> {
+                ::planus::WriteAsOffset::prepare(self, builder)
+            }
+        }
+
+        impl ::planus::WriteAsOptional<::planus::Offset<PatchSpec>> for PatchSpec {
+            type Prepared = ::planus::Offset<Self>;
+
+            #[inline]
+            fn prepare(
+                &self,
+                builder: &mut ::planus::Builder,
+            ) -> ::core::option::Option<::planus::Offset<PatchSpec>> {
+                ::core::option::Option::Some(::planus::WriteAsOffset::prepare(self, builder))
+            }
+        }
+
+        impl ::planus::WriteAsOffset<PatchSpec> for PatchSpec {
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<PatchSpec> {
+                PatchSpec::create(
+                    builder,
+                    &self.input_shape,
+                    self.input_inner_stride,
+                    self.output_inner_stride,
+                    &self.kernel_shape,
+                    &self.strides,
+                    &self.dilations,
+                    &self.padding,
+                )
+            }
+        }
+
+        /// Builder for serializing an instance of the [PatchSpec] type.
+        ///
+        /// Can be created using the [PatchSpec::builder] method.
+        #[derive(Debug)]
+        #[must_use]
+        pub struct PatchSpecBuilder<State>(State);
+
+        impl PatchSpecBuilder<()> {
+            /// Setter for the [`input_shape` field](PatchSpec#structfield.input_shape).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn input_shape<T0>(self, value: T0) -> PatchSpecBuilder<(T0,)>
+            where
+                T0: ::planus::WriteAs<::planus::Offset<[u64]>>,
+            {
+                PatchSpecBuilder((value,))
+            }
+        }
+
+        impl<T0> PatchSpecBuilder<(T0,)> {
+            /// Setter for the [`input_inner_stride` field](PatchSpec#structfield.input_inner_stride).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn input_inner_stride<T1>(self, value: T1) -> PatchSpecBuilder<(T0, T1)>
+            where
+                T1: ::planus::WriteAsDefault<u64, u64>,
+            {
+                let (v0,) = self.0;
+                PatchSpecBuilder((v0, value))
+            }
+
+            /// Sets the [`input_inner_stride` field](PatchSpec#structfield.input_inner_stride) to the default value.
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn input_inner_stride_as_default(
+                self,
+            ) -> PatchSpecBuilder<(T0, ::planus::DefaultValue)> {
+                self.input_inner_stride(::planus::DefaultValue)
+            }
+        }
+
+        impl<T0, T1> PatchSpecBuilder<(T0, T1)> {
+            /// Setter for the [`output_inner_stride` field](PatchSpec#structfield.output_inner_stride).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn output_inner_stride<T2>(self, value: T2) -> PatchSpecBuilder<(T0, T1, T2)>
+            where
+                T2: ::planus::WriteAsDefault<u64, u64>,
+            {
+                let (v0, v1) = self.0;
+                PatchSpecBuilder((v0, v1, value))
+            }
+
+            /// Sets the [`output_inner_stride` field](PatchSpec#structfield.output_inner_stride) to the default value.
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn output_inner_stride_as_default(
+                self,
+            ) -> PatchSpecBuilder<(T0, T1, ::planus::DefaultValue)> {
+                self.output_inner_stride(::planus::DefaultValue)
+            }
+        }
+
+        impl<T0, T1, T2> PatchSpecBuilder<(T0, T1, T2)> {
+            /// Setter for the [`kernel_shape` field](PatchSpec#structfield.kernel_shape).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn kernel_shape<T3>(self, value: T3) -> PatchSpecBuilder<(T0, T1, T2, T3)>
+            where
+                T3: ::planus::WriteAs<::planus::Offset<[u64]>>,
+            {
+                let (v0, v1, v2) = self.0;
+                PatchSpecBuilder((v0, v1, v2, value))
+            }
+        }
+
+        impl<T0, T1, T2, T3> PatchSpecBuilder<(T0, T1, T2, T3)> {
+            /// Setter for the [`strides` field](PatchSpec#structfield.strides).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn strides<T4>(self, value: T4) -> PatchSpecBuilder<(T0, T1, T2, T3, T4)>
+            where
+                T4: ::planus::WriteAs<::planus::Offset<[u64]>>,
+            {
+                let (v0, v1, v2, v3) = self.0;
+                PatchSpecBuilder((v0, v1, v2, v3, value))
+            }
+        }
+
+        impl<T0, T1, T2, T3, T4> PatchSpecBuilder<(T0, T1, T2, T3, T4)> {
+            /// Setter for the [`dilations` field](PatchSpec#structfield.dilations).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn dilations<T5>(self, value: T5) -> PatchSpecBuilder<(T0, T1, T2, T3, T4, T5)>
+            where
+                T5: ::planus::WriteAs<::planus::Offset<[u64]>>,
+            {
+                let (v0, v1, v2, v3, v4) = self.0;
+                PatchSpecBuilder((v0, v1, v2, v3, v4, value))
+            }
+        }
+
+        impl<T0, T1, T2, T3, T4, T5> PatchSpecBuilder<(T0, T1, T2, T3, T4, T5)> {
+            /// Setter for the [`padding` field](PatchSpec#structfield.padding).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn padding<T6>(self, value: T6) -> PatchSpecBuilder<(T0, T1, T2, T3, T4, T5, T6)>
+            where
+                T6: ::planus::WriteAs<::planus::Offset<self::Padding>>,
+            {
+                let (v0, v1, v2, v3, v4, v5) = self.0;
+                PatchSpecBuilder((v0, v1, v2, v3, v4, v5, value))
+            }
+        }
+
+        impl<T0, T1, T2, T3, T4, T5, T6> PatchSpecBuilder<(T0, T1, T2, T3, T4, T5, T6)> {
+            /// Finish writing the builder to get an [Offset](::planus::Offset) to a serialized [PatchSpec].
+            #[inline]
+            pub fn finish(self, builder: &mut ::planus::Builder) -> ::planus::Offset<PatchSpec>
+            where
+                Self: ::planus::WriteAsOffset<PatchSpec>,
+            {
+                ::planus::WriteAsOffset::prepare(&self, builder)
+            }
+        }
+
+        impl<
+                T0: ::planus::WriteAs<::planus::Offset<[u64]>>,
+                T1: ::planus::WriteAsDefault<u64, u64>,
+                T2: ::planus::WriteAsDefault<u64, u64>,
+                T3: ::planus::WriteAs<::planus::Offset<[u64]>>,
+                T4: ::planus::WriteAs<::planus::Offset<[u64]>>,
+                T5: ::planus::WriteAs<::planus::Offset<[u64]>>,
+                T6: ::planus::WriteAs<::planus::Offset<self::Padding>>,
+            > ::planus::WriteAs<::planus::Offset<PatchSpec>>
+            for PatchSpecBuilder<(T0, T1, T2, T3, T4, T5, T6)>
+        {
+            type Prepared = ::planus::Offset<PatchSpec>;
+
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<PatchSpec> {
+                ::planus::WriteAsOffset::prepare(self, builder)
+            }
+        }
+
+        impl<
+                T0: ::planus::WriteAs<::planus::Offset<[u64]>>,
+                T1: ::planus::WriteAsDefault<u64, u64>,
+                T2: ::planus::WriteAsDefault<u64, u64>,
+                T3: ::planus::WriteAs<::planus::Offset<[u64]>>,
+                T4: ::planus::WriteAs<::planus::Offset<[u64]>>,
+                T5: ::planus::WriteAs<::planus::Offset<[u64]>>,
+                T6: ::planus::WriteAs<::planus::Offset<self::Padding>>,
+            > ::planus::WriteAsOptional<::planus::Offset<PatchSpec>>
+            for PatchSpecBuilder<(T0, T1, T2, T3, T4, T5, T6)>
+        {
+            type Prepared = ::planus::Offset<PatchSpec>;
+
+            #[inline]
+            fn prepare(
+                &self,
+                builder: &mut ::planus::Builder,
+            ) -> ::core::option::Option<::planus::Offset<PatchSpec>> {
+                ::core::option::Option::Some(::planus::WriteAsOffset::prepare(self, builder))
+            }
+        }
+
+        impl<
+                T0: ::planus::WriteAs<::planus::Offset<[u64]>>,
+                T1: ::planus::WriteAsDefault<u64, u64>,
+                T2: ::planus::WriteAsDefault<u64, u64>,
+                T3: ::planus::WriteAs<::planus::Offset<[u64]>>,
+                T4: ::planus::WriteAs<::planus::Offset<[u64]>>,
+                T5: ::planus::WriteAs<::planus::Offset<[u64]>>,
+                T6: ::planus::WriteAs<::planus::Offset<self::Padding>>,
+            > ::planus::WriteAsOffset<PatchSpec>
+            for PatchSpecBuilder<(T0, T1, T2, T3, T4, T5, T6)>
+        {
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<PatchSpec> {
+                let (v0, v1, v2, v3, v4, v5, v6) = &self.0;
+                PatchSpec::create(builder, v0, v1, v2, v3, v4, v5, v6)
+            }
+        }
+
+        /// Reference to a deserialized [PatchSpec].
+        #[derive(Copy, Clone)]
+        pub struct PatchSpecRef<'a>(#[allow(dead_code)] ::planus::table_reader::Table<'a>);
+
+        impl<'a> PatchSpecRef<'a> {
+            /// Getter for the [`input_shape` field](PatchSpec#structfield.input_shape).
+            #[inline]
+            pub fn input_shape(&self) -> ::planus::Result<::planus::Vector<'a, u64>> {
+                self.0.access_required(0, "PatchSpec", "input_shape")
+            }
+
+            /// Getter for the [`input_inner_stride` field](PatchSpec#structfield.input_inner_stride).
+            #[inline]
+            pub fn input_inner_stride(&self) -> ::planus::Result<u64> {
+                ::core::result::Result::Ok(
+                    self.0.access(1, "PatchSpec", "input_inner_stride")?.unwrap_or(0),
+                )
+            }
+
+            /// Getter for the [`output_inner_stride` field](PatchSpec#structfield.output_inner_stride).
+            #[inline]
+            pub fn output_inner_stride(&self) -> ::planus::Result<u64> {
+                ::core::result::Result::Ok(
+                    self.0.access(2, "PatchSpec", "output_inner_stride")?.unwrap_or(0),
+                )
+            }
+
+            /// Getter for the [`kernel_shape` field](PatchSpec#structfield.kernel_shape).
+            #[inline]
+            pub fn kernel_shape(&self) -> ::planus::Result<::planus::Vector<'a, u64>> {
+                self.0.access_required(3, "PatchSpec", "kernel_shape")
+            }
+
+            /// Getter for the [`strides` field](PatchSpec#structfield.strides).
+            #[inline]
+            pub fn strides(&self) -> ::planus::Result<::planus::Vector<'a, u64>> {
+                self.0.access_required(4, "PatchSpec", "strides")
+            }
+
+            /// Getter for the [`dilations` field](PatchSpec#structfield.dilations).
+            #[inline]
+            pub fn dilations(&self) -> ::planus::Result<::planus::Vector<'a, u64>> {
+                self.0.access_required(5, "PatchSpec", "dilations")
+            }
+
+            /// Getter for the [`padding` field](PatchSpec#structfield.padding).
+            #[inline]
+            pub fn padding(&self) -> ::planus::Result<self::PaddingRef<'a>> {
+                self.0.access_required(6, "PatchSpec", "padding")
+            }
+        }
+
+        impl<'a> ::core::fmt::Debug for PatchSpecRef<'a> {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                let mut f = f.debug_struct("PatchSpecRef");
+                f.field("input_shape", &self.input_shape());
+                f.field("input_inner_stride", &self.input_inner_stride());
+                f.field("output_inner_stride", &self.output_inner_stride());
+                f.field("kernel_shape", &self.kernel_shape());
+                f.field("strides", &self.strides());
+                f.field("dilations", &self.dilations());
+                f.field("padding", &self.padding());
+                f.finish()
+            }
+        }
+
+        impl<'a> ::core::convert::TryFrom<PatchSpecRef<'a>> for PatchSpec {
+            type Error = ::planus::Error;
+
+            #[allow(unreachable_code)]
+            fn try_from(value: PatchSpecRef<'a>) -> ::planus::Result<Self> {
+                ::core::result::Result::Ok(Self {
+                    input_shape: value.input_shape()?.to_vec()?,
+                    input_inner_stride: ::core::convert::TryInto::try_into(
+                        value.input_inner_stride()?,
+                    )?,
+                    output_inner_stride: ::core::convert::TryInto::try_into(
+                        value.output_inner_stride()?,
+                    )?,
+                    kernel_shape: value.kernel_shape()?.to_vec()?,
+                    strides: value.strides()?.to_vec()?,
+                    dilations: value.dilations()?.to_vec()?,
+                    padding: ::planus::alloc::boxed::Box::new(::core::convert::TryInto::try_into(
+                        value.padding()?,
+                    )?),
+                })
+            }
+        }
+
+        impl<'a> ::planus::TableRead<'a> for PatchSpecRef<'a> {
+            #[inline]
+            fn from_buffer(
+                buffer: ::planus::SliceWithStartOffset<'a>,
+                offset: usize,
+            ) -> ::core::result::Result<Self, ::planus::errors::ErrorKind> {
+                ::core::result::Result::Ok(Self(::planus::table_reader::Table::from_buffer(
+                    buffer, offset,
+                )?))
+            }
+        }
+
+        impl<'a> ::planus::VectorReadInner<'a> for PatchSpecRef<'a> {
+            type Error = ::planus::Error;
+            const STRIDE: usize = 4;
+
+            unsafe fn from_buffer(
+                buffer: ::planus::SliceWithStartOffset<'a>,
+                offset: usize,
+            ) -> ::planus::Result<Self> {
+                ::planus::TableRead::from_buffer(buffer, offset).map_err(|error_kind| {
+                    error_kind.with_error_location(
+                        "[PatchSpecRef]",
+                        "get",
+                        buffer.offset_from_start,
+                    )
+                })
+            }
+        }
+
+        /// # Safety
+        /// The planus compiler generates implementations that initialize
+        /// the bytes in `write_values`.
+        unsafe impl ::planus::VectorWrite<::planus::Offset<PatchSpec>> for PatchSpec {
+            type Value = ::planus::Offset<PatchSpec>;
+            const STRIDE: usize = 4;
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> Self::Value {
+                ::planus::WriteAs::prepare(self, builder)
+            }
+
+            #[inline]
+            unsafe fn write_values(
+                values: &[::planus::Offset<PatchSpec>],
+                bytes: *mut ::core::mem::MaybeUninit<u8>,
+                buffer_position: u32,
+            ) {
+                let bytes = bytes as *mut [::core::mem::MaybeUninit<u8>; 4];
+                for (i, v) in ::core::iter::Iterator::enumerate(values.iter()) {
+                    ::planus::WriteAsPrimitive::write(
+                        v,
+                        ::planus::Cursor::new(unsafe { &mut *bytes.add(i) }),
+                        buffer_position - (Self::STRIDE * i) as u32,
+                    );
+                }
+            }
+        }
+
+        impl<'a> ::planus::ReadAsRoot<'a> for PatchSpecRef<'a> {
+            fn read_as_root(slice: &'a [u8]) -> ::planus::Result<Self> {
+                ::planus::TableRead::from_buffer(
+                    ::planus::SliceWithStartOffset { buffer: slice, offset_from_start: 0 },
+                    0,
+                )
+                .map_err(|error_kind| {
+                    error_kind.with_error_location("[PatchSpecRef]", "read_as_root", 0)
+                })
+            }
+        }
+
+        /// The table `DataShape` in the namespace `tract`
+        ///
+        /// Generated from these locations:
+        /// * Table `DataShape` in the file `schema/tract.fbs:279`
+        #[derive(
+            Clone,
+            Debug,
+            PartialEq,
+            PartialOrd,
+            Eq,
+            Ord,
+            Hash,
+            ::serde::Serialize,
+            ::serde::Deserialize,
+        )]
+        pub struct DataShape {
+            /// The field `data_format` in the table `DataShape`
+            pub data_format: self::DataFormat,
+            /// The field `shape` in the table `DataShape`
+            pub shape: ::planus::alloc::vec::Vec<u64>,
+        }
+
+        #[allow(clippy::derivable_impls)]
+        impl ::core::default::Default for DataShape {
+            fn default() -> Self {
+                Self {
+                    data_format: self::DataFormat::Nchw,
+                    shape: ::core::default::Default::default(),
+                }
+            }
+        }
+
+        impl DataShape {
+            /// Creates a [DataShapeBuilder] for serializing an instance of this table.
+            #[inline]
+            pub fn builder() -> DataShapeBuilder<()> {
+                DataShapeBuilder(())
+            }
+
+            #[allow(clippy::too_many_arguments)]
+            pub fn create(
+                builder: &mut ::planus::Builder,
+                field_data_format: impl ::planus::WriteAsDefault<self::DataFormat, self::DataFormat>,
+                field_shape: impl ::planus::WriteAs<::planus::Offset<[u64]>>,
+            ) -> ::planus::Offset<Self> {
+                let prepared_data_format =
+                    field_data_format.prepare(builder, &self::DataFormat::Nchw);
+                let prepared_shape = field_shape.prepare(builder);
+
+                let mut table_writer: ::planus::table_writer::TableWriter<8> =
+                    ::core::default::Default::default();
+                table_writer.write_entry::<::planus::Offset<[u64]>>(1);
+                if prepared_data_format.is_some() {
+                    table_writer.write_entry::<self::DataFormat>(0);
+                }
+
+                unsafe {
+                    table_writer.finish(builder, |object_writer| {
+                        object_writer.write::<_, _, 4>(&prepared_shape);
+                        if let ::core::option::Option::Some(prepared_data_format) =
+                            prepared_data_format
+                        {
+                            object_writer.write::<_, _, 1>(&prepared_data_format);
+                        }
+                    });
+                }
+                builder.current_offset()
+            }
+        }
+
+        impl ::planus::WriteAs<::planus::Offset<DataShape>> for DataShape {
+            type Prepared = ::planus::Offset<Self>;
+
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<DataShape> {
+                ::planus::WriteAsOffset::prepare(self, builder)
+            }
+        }
+
+        impl ::planus::WriteAsOptional<::planus::Offset<DataShape>> for DataShape {
+            type Prepared = ::planus::Offset<Self>;
+
+            #[inline]
+            fn prepare(
+                &self,
+                builder: &mut ::planus::Builder,
+            ) -> ::core::option::Option<::planus::Offset<DataShape>> {
+                ::core::option::Option::Some(::planus::WriteAsOffset::prepare(self, builder))
+            }
+        }
+
+        impl ::planus::WriteAsOffset<DataShape> for DataShape {
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<DataShape> {
+                DataShape::create(builder, self.data_format, &self.shape)
+            }
+        }
+
+        /// Builder for serializing an instance of the [DataShape] type.
+        ///
+        /// Can be created using the [DataShape::builder] method.
+        #[derive(Debug)]
+        #[must_use]
+        pub struct DataShapeBuilder<State>(State);
+
+        impl DataShapeBuilder<()> {
+            /// Setter for the [`data_format` field](DataShape#structfield.data_format).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn data_format<T0>(self, value: T0) -> DataShapeBuilder<(T0,)>
+            where
+                T0: ::planus::WriteAsDefault<self::DataFormat, self::DataFormat>,
+            {
+                DataShapeBuilder((value,))
+            }
+
+            /// Sets the [`data_format` field](DataShape#structfield.data_format) to the default value.
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn data_format_as_default(self) -> DataShapeBuilder<(::planus::DefaultValue,)> {
+                self.data_format(::planus::DefaultValue)
+            }
+        }
+
+        impl<T0> DataShapeBuilder<(T0,)> {
+            /// Setter for the [`shape` field](DataShape#structfield.shape).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn shape<T1>(self, value: T1) -> DataShapeBuilder<(T0, T1)>
+            where
+                T1: ::planus::WriteAs<::planus::Offset<[u64]>>,
+            {
+                let (v0,) = self.0;
+                DataShapeBuilder((v0, value))
+            }
+        }
+
+        impl<T0, T1> DataShapeBuilder<(T0, T1)> {
+            /// Finish writing the builder to get an [Offset](::planus::Offset) to a serialized [DataShape].
+            #[inline]
+            pub fn finish(self, builder: &mut ::planus::Builder) -> ::planus::Offset<DataShape>
+            where
+                Self: ::planus::WriteAsOffset<DataShape>,
+            {
+                ::planus::WriteAsOffset::prepare(&self, builder)
+            }
+        }
+
+        impl<
+                T0: ::planus::WriteAsDefault<self::DataFormat, self::DataFormat>,
+                T1: ::planus::WriteAs<::planus::Offset<[u64]>>,
+            > ::planus::WriteAs<::planus::Offset<DataShape>> for DataShapeBuilder<(T0, T1)>
+        {
+            type Prepared = ::planus::Offset<DataShape>;
+
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<DataShape> {
+                ::planus::WriteAsOffset::prepare(self, builder)
+            }
+        }
+
+        impl<
+                T0: ::planus::WriteAsDefault<self::DataFormat, self::DataFormat>,
+                T1: ::planus::WriteAs<::planus::Offset<[u64]>>,
+            > ::planus::WriteAsOptional<::planus::Offset<DataShape>>
+            for DataShapeBuilder<(T0, T1)>
+        {
+            type Prepared = ::planus::Offset<DataShape>;
+
+            #[inline]
+            fn prepare(
+                &self,
+                builder: &mut ::planus::Builder,
+            ) -> ::core::option::Option<::planus::Offset<DataShape>> {
+                ::core::option::Option::Some(::planus::WriteAsOffset::prepare(self, builder))
+            }
+        }
+
+        impl<
+                T0: ::planus::WriteAsDefault<self::DataFormat, self::DataFormat>,
+                T1: ::planus::WriteAs<::planus::Offset<[u64]>>,
+            > ::planus::WriteAsOffset<DataShape> for DataShapeBuilder<(T0, T1)>
+        {
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<DataShape> {
+                let (v0, v1) = &self.0;
+                DataShape::create(builder, v0, v1)
+            }
+        }
+
+        /// Reference to a deserialized [DataShape].
+        #[derive(Copy, Clone)]
+        pub struct DataShapeRef<'a>(#[allow(dead_code)] ::planus::table_reader::Table<'a>);
+
+        impl<'a> DataShapeRef<'a> {
+            /// Getter for the [`data_format` field](DataShape#structfield.data_format).
+            #[inline]
+            pub fn data_format(&self) -> ::planus::Result<self::DataFormat> {
+                ::core::result::Result::Ok(
+                    self.0.access(0, "DataShape", "data_format")?.unwrap_or(self::DataFormat::Nchw),
+                )
+            }
+
+            /// Getter for the [`shape` field](DataShape#structfield.shape).
+            #[inline]
+            pub fn shape(&self) -> ::planus::Result<::planus::Vector<'a, u64>> {
+                self.0.access_required(1, "DataShape", "shape")
+            }
+        }
+
+        impl<'a> ::core::fmt::Debug for DataShapeRef<'a> {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                let mut f = f.debug_struct("DataShapeRef");
+                f.field("data_format", &self.data_format());
+                f.field("shape", &self.shape());
+                f.finish()
+            }
+        }
+
+        impl<'a> ::core::convert::TryFrom<DataShapeRef<'a>> for DataShape {
+            type Error = ::planus::Error;
+
+            #[allow(unreachable_code)]
+            fn try_from(value: DataShapeRef<'a>) -> ::planus::Result<Self> {
+                ::core::result::Result::Ok(Self {
+                    data_format: ::core::convert::TryInto::try_into(value.data_format()?)?,
+                    shape: value.shape()?.to_vec()?,
+                })
+            }
+        }
+
+        impl<'a> ::planus::TableRead<'a> for DataShapeRef<'a> {
+            #[inline]
+            fn from_buffer(
+                buffer: ::planus::SliceWithStartOffset<'a>,
+                offset: usize,
+            ) -> ::core::result::Result<Self, ::planus::errors::ErrorKind> {
+                ::core::result::Result::Ok(Self(::planus::table_reader::Table::from_buffer(
+                    buffer, offset,
+                )?))
+            }
+        }
+
+        impl<'a> ::planus::VectorReadInner<'a> for DataShapeRef<'a> {
+            type Error = ::planus::Error;
+            const STRIDE: usize = 4;
+
+            unsafe fn from_buffer(
+                buffer: ::planus::SliceWithStartOffset<'a>,
+                offset: usize,
+            ) -> ::planus::Result<Self> {
+                ::planus::TableRead::from_buffer(buffer, offset).map_err(|error_kind| {
+                    error_kind.with_error_location(
+                        "[DataShapeRef]",
+                        "get",
+                        buffer.offset_from_start,
+                    )
+                })
+            }
+        }
+
+        /// # Safety
+        /// The planus compiler generates implementations that initialize
+        /// the bytes in `write_values`.
+        unsafe impl ::planus::VectorWrite<::planus::Offset<DataShape>> for DataShape {
+            type Value = ::planus::Offset<DataShape>;
+            const STRIDE: usize = 4;
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> Self::Value {
+                ::planus::WriteAs::prepare(self, builder)
+            }
+
+            #[inline]
+            unsafe fn write_values(
+                values: &[::planus::Offset<DataShape>],
+                bytes: *mut ::core::mem::MaybeUninit<u8>,
+                buffer_position: u32,
+            ) {
+                let bytes = bytes as *mut [::core::mem::MaybeUninit<u8>; 4];
+                for (i, v) in ::core::iter::Iterator::enumerate(values.iter()) {
+                    ::planus::WriteAsPrimitive::write(
+                        v,
+                        ::planus::Cursor::new(unsafe { &mut *bytes.add(i) }),
+                        buffer_position - (Self::STRIDE * i) as u32,
+                    );
+                }
+            }
+        }
+
+        impl<'a> ::planus::ReadAsRoot<'a> for DataShapeRef<'a> {
+            fn read_as_root(slice: &'a [u8]) -> ::planus::Result<Self> {
+                ::planus::TableRead::from_buffer(
+                    ::planus::SliceWithStartOffset { buffer: slice, offset_from_start: 0 },
+                    0,
+                )
+                .map_err(|error_kind| {
+                    error_kind.with_error_location("[DataShapeRef]", "read_as_root", 0)
+                })
+            }
+        }
+
+        /// The table `PackB` in the namespace `tract`
+        ///
+        /// Generated from these locations:
+        /// * Table `PackB` in the file `schema/tract.fbs:284`
+        #[derive(
+            Clone,
+            Debug,
+            PartialEq,
+            PartialOrd,
+            Eq,
+            Ord,
+            Hash,
+            ::serde::Serialize,
+            ::serde::Deserialize,
+        )]
+        pub struct PackB {
+            /// The field `k` in the table `PackB`
+            pub k: u64,
+            /// The field `n` in the table `PackB`
+            pub n: u64,
+            /// The field `nr` in the table `PackB`
+            pub nr: u64,
+            /// The field `alignment` in the table `PackB`
+            pub alignment: u64,
+        }
+
+        #[allow(clippy::derivable_impls)]
+        impl ::core::default::Default for PackB {
+            fn default() -> Self {
+                Self { k: 0, n: 0, nr: 0, alignment: 0 }
+            }
+        }
+
+        impl PackB {
+            /// Creates a [PackBBuilder] for serializing an instance of this table.
+            #[inline]
+            pub fn builder() -> PackBBuilder<()> {
+                PackBBuilder(())
+            }
+
+            #[allow(clippy::too_many_arguments)]
+            pub fn create(
+                builder: &mut ::planus::Builder,
+                field_k: impl ::planus::WriteAsDefault<u64, u64>,
+                field_n: impl ::planus::WriteAsDefault<u64, u64>,
+                field_nr: impl ::planus::WriteAsDefault<u64, u64>,
+                field_alignment: impl ::planus::WriteAsDefault<u64, u64>,
+            ) -> ::planus::Offset<Self> {
+                let prepared_k = field_k.prepare(builder, &0);
+                let prepared_n = field_n.prepare(builder, &0);
+                let prepared_nr = field_nr.prepare(builder, &0);
+                let prepared_alignment = field_alignment.prepare(builder, &0);
+
+                let mut table_writer: ::planus::table_writer::TableWriter<12> =
+                    ::core::default::Default::default();
+                if prepared_k.is_some() {
+                    table_writer.write_entry::<u64>(0);
+                }
+                if prepared_n.is_some() {
+                    table_writer.write_entry::<u64>(1);
+                }
+                if prepared_nr.is_some() {
+                    table_writer.write_entry::<u64>(2);
+                }
+                if prepared_alignment.is_some() {
+                    table_writer.write_entry::<u64>(3);
+                }
+
+                unsafe {
+                    table_writer.finish(builder, |object_writer| {
+                        if let ::core::option::Option::Some(prepared_k) = prepared_k {
+                            object_writer.write::<_, _, 8>(&prepared_k);
+                        }
+                        if let ::core::option::Option::Some(prepared_n) = prepared_n {
+                            object_writer.write::<_, _, 8>(&prepared_n);
+                        }
+                        if let ::core::option::Option::Some(prepared_nr) = prepared_nr {
+                            object_writer.write::<_, _, 8>(&prepared_nr);
+                        }
+                        if let ::core::option::Option::Some(prepared_alignment) = prepared_alignment
+                        {
+                            object_writer.write::<_, _, 8>(&prepared_alignment);
+                        }
+                    });
+                }
+                builder.current_offset()
+            }
+        }
+
+        impl ::planus::WriteAs<::planus::Offset<PackB>> for PackB {
+            type Prepared = ::planus::Offset<Self>;
+
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<PackB> {
+                ::planus::WriteAsOffset::prepare(self, builder)
+            }
+        }
+
+        impl ::planus::WriteAsOptional<::planus::Offset<PackB>> for PackB {
+            type Prepared = ::planus::Offset<Self>;
+
+            #[inline]
+            fn prepare(
+                &self,
+                builder: &mut ::planus::Builder,
+            ) -> ::core::option::Option<::planus::Offset<PackB>> {
+                ::core::option::Option::Some(::planus::WriteAsOffset::prepare(self, builder))
+            }
+        }
+
+        impl ::planus::WriteAsOffset<PackB> for PackB {
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<PackB> {
+                PackB::create(builder, self.k, self.n, self.nr, self.alignment)
+            }
+        }
+
+        /// Builder for serializing an instance of the [PackB] type.
+        ///
+        /// Can be created using the [PackB::builder] method.
+        #[derive(Debug)]
+        #[must_use]
+        pub struct PackBBuilder<State>(State);
+
+        impl PackBBuilder<()> {
+            /// Setter for the [`k` field](PackB#structfield.k).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn k<T0>(self, value: T0) -> PackBBuilder<(T0,)>
+            where
+                T0: ::planus::WriteAsDefault<u64, u64>,
+            {
+                PackBBuilder((value,))
+            }
+
+            /// Sets the [`k` field](PackB#structfield.k) to the default value.
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn k_as_default(self) -> PackBBuilder<(::planus::DefaultValue,)> {
+                self.k(::planus::DefaultValue)
+            }
+        }
+
+        impl<T0> PackBBuilder<(T0,)> {
+            /// Setter for the [`n` field](PackB#structfield.n).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn n<T1>(self, value: T1) -> PackBBuilder<(T0, T1)>
+            where
+                T1: ::planus::WriteAsDefault<u64, u64>,
+            {
+                let (v0,) = self.0;
+                PackBBuilder((v0, value))
+            }
+
+            /// Sets the [`n` field](PackB#structfield.n) to the default value.
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn n_as_default(self) -> PackBBuilder<(T0, ::planus::DefaultValue)> {
+                self.n(::planus::DefaultValue)
+            }
+        }
+
+        impl<T0, T1> PackBBuilder<(T0, T1)> {
+            /// Setter for the [`nr` field](PackB#structfield.nr).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn nr<T2>(self, value: T2) -> PackBBuilder<(T0, T1, T2)>
+            where
+                T2: ::planus::WriteAsDefault<u64, u64>,
+            {
+                let (v0, v1) = self.0;
+                PackBBuilder((v0, v1, value))
+            }
+
+            /// Sets the [`nr` field](PackB#structfield.nr) to the default value.
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn nr_as_default(self) -> PackBBuilder<(T0, T1, ::planus::DefaultValue)> {
+                self.nr(::planus::DefaultValue)
+            }
+        }
+
+        impl<T0, T1, T2> PackBBuilder<(T0, T1, T2)> {
+            /// Setter for the [`alignment` field](PackB#structfield.alignment).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn alignment<T3>(self, value: T3) -> PackBBuilder<(T0, T1, T2, T3)>
+            where
+                T3: ::planus::WriteAsDefault<u64, u64>,
+            {
+                let (v0, v1, v2) = self.0;
+                PackBBuilder((v0, v1, v2, value))
+            }
+
+            /// Sets the [`alignment` field](PackB#structfield.alignment) to the default value.
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn alignment_as_default(
+                self,
+            ) -> PackBBuilder<(T0, T1, T2, ::planus::DefaultValue)> {
+                self.alignment(::planus::DefaultValue)
+            }
+        }
+
+        impl<T0, T1, T2, T3> PackBBuilder<(T0, T1, T2, T3)> {
+            /// Finish writing the builder to get an [Offset](::planus::Offset) to a serialized [PackB].
+            #[inline]
+            pub fn finish(self, builder: &mut ::planus::Builder) -> ::planus::Offset<PackB>
+            where
+                Self: ::planus::WriteAsOffset<PackB>,
+            {
+                ::planus::WriteAsOffset::prepare(&self, builder)
+            }
+        }
+
+        impl<
+                T0: ::planus::WriteAsDefault<u64, u64>,
+                T1: ::planus::WriteAsDefault<u64, u64>,
+                T2: ::planus::WriteAsDefault<u64, u64>,
+                T3: ::planus::WriteAsDefault<u64, u64>,
+            > ::planus::WriteAs<::planus::Offset<PackB>> for PackBBuilder<(T0, T1, T2, T3)>
+        {
+            type Prepared = ::planus::Offset<PackB>;
+
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<PackB> {
+                ::planus::WriteAsOffset::prepare(self, builder)
+            }
+        }
+
+        impl<
+                T0: ::planus::WriteAsDefault<u64, u64>,
+                T1: ::planus::WriteAsDefault<u64, u64>,
+                T2: ::planus::WriteAsDefault<u64, u64>,
+                T3: ::planus::WriteAsDefault<u64, u64>,
+            > ::planus::WriteAsOptional<::planus::Offset<PackB>>
+            for PackBBuilder<(T0, T1, T2, T3)>
+        {
+            type Prepared = ::planus::Offset<PackB>;
+
+            #[inline]
+            fn prepare(
+                &self,
+                builder: &mut ::planus::Builder,
+            ) -> ::core::option::Option<::planus::Offset<PackB>> {
+                ::core::option::Option::Some(::planus::WriteAsOffset::prepare(self, builder))
+            }
+        }
+
+        impl<
+                T0: ::planus::WriteAsDefault<u64, u64>,
+                T1: ::planus::WriteAsDefault<u64, u64>,
+                T2: ::planus::WriteAsDefault<u64, u64>,
+                T3: ::planus::WriteAsDefault<u64, u64>,
+            > ::planus::WriteAsOffset<PackB> for PackBBuilder<(T0, T1, T2, T3)>
+        {
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<PackB> {
+                let (v0, v1, v2, v3) = &self.0;
+                PackB::create(builder, v0, v1, v2, v3)
+            }
+        }
+
+        /// Reference to a deserialized [PackB].
+        #[derive(Copy, Clone)]
+        pub struct PackBRef<'a>(#[allow(dead_code)] ::planus::table_reader::Table<'a>);
+
+        impl<'a> PackBRef<'a> {
+            /// Getter for the [`k` field](PackB#structfield.k).
+            #[inline]
+            pub fn k(&self) -> ::planus::Result<u64> {
+                ::core::result::Result::Ok(self.0.access(0, "PackB", "k")?.unwrap_or(0))
+            }
+
+            /// Getter for the [`n` field](PackB#structfield.n).
+            #[inline]
+            pub fn n(&self) -> ::planus::Result<u64> {
+                ::core::result::Result::Ok(self.0.access(1, "PackB", "n")?.unwrap_or(0))
+            }
+
+            /// Getter for the [`nr` field](PackB#structfield.nr).
+            #[inline]
+            pub fn nr(&self) -> ::planus::Result<u64> {
+                ::core::result::Result::Ok(self.0.access(2, "PackB", "nr")?.unwrap_or(0))
+            }
+
+            /// Getter for the [`alignment` field](PackB#structfield.alignment).
+            #[inline]
+            pub fn alignment(&self) -> ::planus::Result<u64> {
+                ::core::result::Result::Ok(self.0.access(3, "PackB", "alignment")?.unwrap_or(0))
+            }
+        }
+
+        impl<'a> ::core::fmt::Debug for PackBRef<'a> {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                let mut f = f.debug_struct("PackBRef");
+                f.field("k", &self.k());
+                f.field("n", &self.n());
+                f.field("nr", &self.nr());
+                f.field("alignment", &self.alignment());
+                f.finish()
+            }
+        }
+
+        impl<'a> ::core::convert::TryFrom<PackBRef<'a>> for PackB {
+            type Error = ::planus::Error;
+
+            #[allow(unreachable_code)]
+            fn try_from(value: PackBRef<'a>) -> ::planus::Result<Self> {
+                ::core::result::Result::Ok(Self {
+                    k: ::core::convert::TryInto::try_into(value.k()?)?,
+                    n: ::core::convert::TryInto::try_into(value.n()?)?,
+                    nr: ::core::convert::TryInto::try_into(value.nr()?)?,
+                    alignment: ::core::convert::TryInto::try_into(value.alignment()?)?,
+                })
+            }
+        }
+
+        impl<'a> ::planus::TableRead<'a> for PackBRef<'a> {
+            #[inline]
+            fn from_buffer(
+                buffer: ::planus::SliceWithStartOffset<'a>,
+                offset: usize,
+            ) -> ::core::result::Result<Self, ::planus::errors::ErrorKind> {
+                ::core::result::Result::Ok(Self(::planus::table_reader::Table::from_buffer(
+                    buffer, offset,
+                )?))
+            }
+        }
+
+        impl<'a> ::planus::VectorReadInner<'a> for PackBRef<'a> {
+            type Error = ::planus::Error;
+            const STRIDE: usize = 4;
+
+            unsafe fn from_buffer(
+                buffer: ::planus::SliceWithStartOffset<'a>,
+                offset: usize,
+            ) -> ::planus::Result<Self> {
+                ::planus::TableRead::from_buffer(buffer, offset).map_err(|error_kind| {
+                    error_kind.with_error_location("[PackBRef]", "get", buffer.offset_from_start)
+                })
+            }
+        }
+
+        /// # Safety
+        /// The planus compiler generates implementations that initialize
+        /// the bytes in `write_values`.
+        unsafe impl ::planus::VectorWrite<::planus::Offset<PackB>> for PackB {
+            type Value = ::planus::Offset<PackB>;
+            const STRIDE: usize = 4;
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> Self::Value {
+                ::planus::WriteAs::prepare(self, builder)
+            }
+
+            #[inline]
+            unsafe fn write_values(
+                values: &[::planus::Offset<PackB>],
+                bytes: *mut ::core::mem::MaybeUninit<u8>,
+                buffer_position: u32,
+            ) {
+                let bytes = bytes as *mut [::core::mem::MaybeUninit<u8>; 4];
+                for (i, v) in ::core::iter::Iterator::enumerate(values.iter()) {
+                    ::planus::WriteAsPrimitive::write(
+                        v,
+                        ::planus::Cursor::new(unsafe { &mut *bytes.add(i) }),
+                        buffer_position - (Self::STRIDE * i) as u32,
+                    );
+                }
+            }
+        }
+
+        impl<'a> ::planus::ReadAsRoot<'a> for PackBRef<'a> {
+            fn read_as_root(slice: &'a [u8]) -> ::planus::Result<Self> {
+                ::planus::TableRead::from_buffer(
+                    ::planus::SliceWithStartOffset { buffer: slice, offset_from_start: 0 },
+                    0,
+                )
+                .map_err(|error_kind| {
+                    error_kind.with_error_location("[PackBRef]", "read_as_root", 0)
+                })
+            }
+        }
+
+        /// The table `Im2Col` in the namespace `tract`
+        ///
+        /// Generated from these locations:
+        /// * Table `Im2Col` in the file `schema/tract.fbs:291`
+        #[derive(Clone, Debug, PartialEq, PartialOrd, ::serde::Serialize, ::serde::Deserialize)]
+        pub struct Im2Col {
+            /// The field `patch` in the table `Im2Col`
+            pub patch: ::planus::alloc::boxed::Box<self::PatchSpec>,
+            /// The field `input_shape` in the table `Im2Col`
+            pub input_shape: ::planus::alloc::boxed::Box<self::DataShape>,
+            /// The field `m` in the table `Im2Col`
+            pub m: u64,
+            /// The field `k` in the table `Im2Col`
+            pub k: u64,
+            /// The field `n` in the table `Im2Col`
+            pub n: u64,
+            /// The field `group` in the table `Im2Col`
+            pub group: u64,
+            /// The field `ci_per_group` in the table `Im2Col`
+            pub ci_per_group: u64,
+            /// The field `b_pack` in the table `Im2Col`
+            pub b_pack: ::planus::alloc::boxed::Box<self::PackB>,
+            /// The field `pad_value` in the table `Im2Col`
+            pub pad_value: f32,
+        }
+
+        #[allow(clippy::derivable_impls)]
+        impl ::core::default::Default for Im2Col {
+            fn default() -> Self {
+                Self {
+                    patch: ::core::default::Default::default(),
+                    input_shape: ::core::default::Default::default(),
+                    m: 0,
+                    k: 0,
+                    n: 0,
+                    group: 0,
+                    ci_per_group: 0,
+                    b_pack: ::core::default::Default::default(),
+                    pad_value: 0.0,
+                }
+            }
+        }
+
+        impl Im2Col {
+            /// Creates a [Im2ColBuilder] for serializing an instance of this table.
+            #[inline]
+            pub fn builder() -> Im2ColBuilder<()> {
+                Im2ColBuilder(())
+            }
+
+            #[allow(clippy::too_many_arguments)]
+            pub fn create(
+                builder: &mut ::planus::Builder,
+                field_patch: impl ::planus::WriteAs<::planus::Offset<self::PatchSpec>>,
+                field_input_shape: impl ::planus::WriteAs<::planus::Offset<self::DataShape>>,
+                field_m: impl ::planus::WriteAsDefault<u64, u64>,
+                field_k: impl ::planus::WriteAsDefault<u64, u64>,
+                field_n: impl ::planus::WriteAsDefault<u64, u64>,
+                field_group: impl ::planus::WriteAsDefault<u64, u64>,
+                field_ci_per_group: impl ::planus::WriteAsDefault<u64, u64>,
+                field_b_pack: impl ::planus::WriteAs<::planus::Offset<self::PackB>>,
+                field_pad_value: impl ::planus::WriteAsDefault<f32, f32>,
+            ) -> ::planus::Offset<Self> {
+                let prepared_patch = field_patch.prepare(builder);
+                let prepared_input_shape = field_input_shape.prepare(builder);
+                let prepared_m = field_m.prepare(builder, &0);
+                let prepared_k = field_k.prepare(builder, &0);
+                let prepared_n = field_n.prepare(builder, &0);
+                let prepared_group = field_group.prepare(builder, &0);
+                let prepared_ci_per_group = field_ci_per_group.prepare(builder, &0);
+                let prepared_b_pack = field_b_pack.prepare(builder);
+                let prepared_pad_value = field_pad_value.prepare(builder, &0.0);
+
+                let mut table_writer: ::planus::table_writer::TableWriter<22> =
+                    ::core::default::Default::default();
+                if prepared_m.is_some() {
+                    table_writer.write_entry::<u64>(2);
+                }
+                if prepared_k.is_some() {
+                    table_writer.write_entry::<u64>(3);
+                }
+                if prepared_n.is_some() {
+                    table_writer.write_entry::<u64>(4);
+                }
+                if prepared_group.is_some() {
+                    table_writer.write_entry::<u64>(5);
+                }
+                if prepared_ci_per_group.is_some() {
+                    table_writer.write_entry::<u64>(6);
+                }
+                table_writer.write_entry::<::planus::Offset<self::PatchSpec>>(0);
+                table_writer.write_entry::<::planus::Offset<self::DataShape>>(1);
+                table_writer.write_entry::<::planus::Offset<self::PackB>>(7);
+                if prepared_pad_value.is_some() {
+                    table_writer.write_entry::<f32>(8);
+                }
+
+                unsafe {
+                    table_writer.finish(builder, |object_writer| {
+                        if let ::core::option::Option::Some(prepared_m) = prepared_m {
+                            object_writer.write::<_, _, 8>(&prepared_m);
+                        }
+                        if let ::core::option::Option::Some(prepared_k) = prepared_k {
+                            object_writer.write::<_, _, 8>(&prepared_k);
+                        }
+                        if let ::core::option::Option::Some(prepared_n) = prepared_n {
+                            object_writer.write::<_, _, 8>(&prepared_n);
+                        }
+                        if let ::core::option::Option::Some(prepared_group) = prepared_group {
+                            object_writer.write::<_, _, 8>(&prepared_group);
+                        }
+                        if let ::core::option::Option::Some(prepared_ci_per_group) =
+                            prepared_ci_per_group
+                        {
+                            object_writer.write::<_, _, 8>(&prepared_ci_per_group);
+                        }
+                        object_writer.write::<_, _, 4>(&prepared_patch);
+                        object_writer.write::<_, _, 4>(&prepared_input_shape);
+                        object_writer.write::<_, _, 4>(&prepared_b_pack);
+                        if let ::core::option::Option::Some(prepared_pad_value) = prepared_pad_value
+                        {
+                            object_writer.write::<_, _, 4>(&prepared_pad_value);
+                        }
+                    });
+                }
+                builder.current_offset()
+            }
+        }
+
+        impl ::planus::WriteAs<::planus::Offset<Im2Col>> for Im2Col {
+            type Prepared = ::planus::Offset<Self>;
+
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<Im2Col> {
+                ::planus::WriteAsOffset::prepare(self, builder)
+            }
+        }
+
+        impl ::planus::WriteAsOptional<::planus::Offset<Im2Col>> for Im2Col {
+            type Prepared = ::planus::Offset<Self>;
+
+            #[inline]
+            fn prepare(
+                &self,
+                builder: &mut ::planus::Builder,
+            ) -> ::core::option::Option<::planus::Offset<Im2Col>> {
+                ::core::option::Option::Some(::planus::WriteAsOffset::prepare(self, builder))
+            }
+        }
+
+        impl ::planus::WriteAsOffset<Im2Col> for Im2Col {
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<Im2Col> {
+                Im2Col::create(
+                    builder,
+                    &self.patch,
+                    &self.input_shape,
+                    self.m,
+                    self.k,
+                    self.n,
+                    self.group,
+                    self.ci_per_group,
+                    &self.b_pack,
+                    self.pad_value,
+                )
+            }
+        }
+
+        /// Builder for serializing an instance of the [Im2Col] type.
+        ///
+        /// Can be created using the [Im2Col::builder] method.
+        #[derive(Debug)]
+        #[must_use]
+        pub struct Im2ColBuilder<State>(State);
+
+        impl Im2ColBuilder<()> {
+            /// Setter for the [`patch` field](Im2Col#structfield.patch).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn patch<T0>(self, value: T0) -> Im2ColBuilder<(T0,)>
+            where
+                T0: ::planus::WriteAs<::planus::Offset<self::PatchSpec>>,
+            {
+                Im2ColBuilder((value,))
+            }
+        }
+
+        impl<T0> Im2ColBuilder<(T0,)> {
+            /// Setter for the [`input_shape` field](Im2Col#structfield.input_shape).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn input_shape<T1>(self, value: T1) -> Im2ColBuilder<(T0, T1)>
+            where
+                T1: ::planus::WriteAs<::planus::Offset<self::DataShape>>,
+            {
+                let (v0,) = self.0;
+                Im2ColBuilder((v0, value))
+            }
+        }
+
+        impl<T0, T1> Im2ColBuilder<(T0, T1)> {
+            /// Setter for the [`m` field](Im2Col#structfield.m).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn m<T2>(self, value: T2) -> Im2ColBuilder<(T0, T1, T2)>
+            where
+                T2: ::planus::WriteAsDefault<u64, u64>,
+            {
+                let (v0, v1) = self.0;
+                Im2ColBuilder((v0, v1, value))
+            }
+
+            /// Sets the [`m` field](Im2Col#structfield.m) to the default value.
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn m_as_default(self) -> Im2ColBuilder<(T0, T1, ::planus::DefaultValue)> {
+                self.m(::planus::DefaultValue)
+            }
+        }
+
+        impl<T0, T1, T2> Im2ColBuilder<(T0, T1, T2)> {
+            /// Setter for the [`k` field](Im2Col#structfield.k).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn k<T3>(self, value: T3) -> Im2ColBuilder<(T0, T1, T2, T3)>
+            where
+                T3: ::planus::WriteAsDefault<u64, u64>,
+            {
+                let (v0, v1, v2) = self.0;
+                Im2ColBuilder((v0, v1, v2, value))
+            }
+
+            /// Sets the [`k` field](Im2Col#structfield.k) to the default value.
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn k_as_default(self) -> Im2ColBuilder<(T0, T1, T2, ::planus::DefaultValue)> {
+                self.k(::planus::DefaultValue)
+            }
+        }
+
+        impl<T0, T1, T2, T3> Im2ColBuilder<(T0, T1, T2, T3)> {
+            /// Setter for the [`n` field](Im2Col#structfield.n).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn n<T4>(self, value: T4) -> Im2ColBuilder<(T0, T1, T2, T3, T4)>
+            where
+                T4: ::planus::WriteAsDefault<u64, u64>,
+            {
+                let (v0, v1, v2, v3) = self.0;
+                Im2ColBuilder((v0, v1, v2, v3, value))
+            }
+
+            /// Sets the [`n` field](Im2Col#structfield.n) to the default value.
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn n_as_default(self) -> Im2ColBuilder<(T0, T1, T2, T3, ::planus::DefaultValue)> {
+                self.n(::planus::DefaultValue)
+            }
+        }
+
+        impl<T0, T1, T2, T3, T4> Im2ColBuilder<(T0, T1, T2, T3, T4)> {
+            /// Setter for the [`group` field](Im2Col#structfield.group).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn group<T5>(self, value: T5) -> Im2ColBuilder<(T0, T1, T2, T3, T4, T5)>
+            where
+                T5: ::planus::WriteAsDefault<u64, u64>,
+            {
+                let (v0, v1, v2, v3, v4) = self.0;
+                Im2ColBuilder((v0, v1, v2, v3, v4, value))
+            }
+
+            /// Sets the [`group` field](Im2Col#structfield.group) to the default value.
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn group_as_default(
+                self,
+            ) -> Im2ColBuilder<(T0, T1, T2, T3, T4, ::planus::DefaultValue)> {
+                self.group(::planus::DefaultValue)
+            }
+        }
+
+        impl<T0, T1, T2, T3, T4, T5> Im2ColBuilder<(T0, T1, T2, T3, T4, T5)> {
+            /// Setter for the [`ci_per_group` field](Im2Col#structfield.ci_per_group).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn ci_per_group<T6>(self, value: T6) -> Im2ColBuilder<(T0, T1, T2, T3, T4, T5, T6)>
+            where
+                T6: ::planus::WriteAsDefault<u64, u64>,
+            {
+                let (v0, v1, v2, v3, v4, v5) = self.0;
+                Im2ColBuilder((v0, v1, v2, v3, v4, v5, value))
+            }
+
+            /// Sets the [`ci_per_group` field](Im2Col#structfield.ci_per_group) to the default value.
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn ci_per_group_as_default(
+                self,
+            ) -> Im2ColBuilder<(T0, T1, T2, T3, T4, T5, ::planus::DefaultValue)> {
+                self.ci_per_group(::planus::DefaultValue)
+            }
+        }
+
+        impl<T0, T1, T2, T3, T4, T5, T6> Im2ColBuilder<(T0, T1, T2, T3, T4, T5, T6)> {
+            /// Setter for the [`b_pack` field](Im2Col#structfield.b_pack).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn b_pack<T7>(self, value: T7) -> Im2ColBuilder<(T0, T1, T2, T3, T4, T5, T6, T7)>
+            where
+                T7: ::planus::WriteAs<::planus::Offset<self::PackB>>,
+            {
+                let (v0, v1, v2, v3, v4, v5, v6) = self.0;
+                Im2ColBuilder((v0, v1, v2, v3, v4, v5, v6, value))
+            }
+        }
+
+        impl<T0, T1, T2, T3, T4, T5, T6, T7> Im2ColBuilder<(T0, T1, T2, T3, T4, T5, T6, T7)> {
+            /// Setter for the [`pad_value` field](Im2Col#structfield.pad_value).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn pad_value<T8>(
+                self,
+                value: T8,
+            ) -> Im2ColBuilder<(T0, T1, T2, T3, T4, T5, T6, T7, T8)>
+            where
+                T8: ::planus::WriteAsDefault<f32, f32>,
+            {
+                let (v0, v1, v2, v3, v4, v5, v6, v7) = self.0;
+                Im2ColBuilder((v0, v1, v2, v3, v4, v5, v6, v7, value))
+            }
+
+            /// Sets the [`pad_value` field](Im2Col#structfield.pad_value) to the default value.
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn pad_value_as_default(
+                self,
+            ) -> Im2ColBuilder<(T0, T1, T2, T3, T4, T5, T6, T7, ::planus::DefaultValue)>
+            {
+                self.pad_value(::planus::DefaultValue)
+            }
+        }
+
+        impl<T0, T1, T2, T3, T4, T5, T6, T7, T8> Im2ColBuilder<(T0, T1, T2, T3, T4, T5, T6, T7, T8)> {
+            /// Finish writing the builder to get an [Offset](::planus::Offset) to a serialized [Im2Col].
+            #[inline]
+            pub fn finish(self, builder: &mut ::planus::Builder) -> ::planus::Offset<Im2Col>
+            where
+                Self: ::planus::WriteAsOffset<Im2Col>,
+            {
+                ::planus::WriteAsOffset::prepare(&self, builder)
+            }
+        }
+
+        impl<
+                T0: ::planus::WriteAs<::planus::Offset<self::PatchSpec>>,
+                T1: ::planus::WriteAs<::planus::Offset<self::DataShape>>,
+                T2: ::planus::WriteAsDefault<u64, u64>,
+                T3: ::planus::WriteAsDefault<u64, u64>,
+                T4: ::planus::WriteAsDefault<u64, u64>,
+                T5: ::planus::WriteAsDefault<u64, u64>,
+                T6: ::planus::WriteAsDefault<u64, u64>,
+                T7: ::planus::WriteAs<::planus::Offset<self::PackB>>,
+                T8: ::planus::WriteAsDefault<f32, f32>,
+            > ::planus::WriteAs<::planus::Offset<Im2Col>>
+            for Im2ColBuilder<(T0, T1, T2, T3, T4, T5, T6, T7, T8)>
+        {
+            type Prepared = ::planus::Offset<Im2Col>;
+
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<Im2Col> {
+                ::planus::WriteAsOffset::prepare(self, builder)
+            }
+        }
+
+        impl<
+                T0: ::planus::WriteAs<::planus::Offset<self::PatchSpec>>,
+                T1: ::planus::WriteAs<::planus::Offset<self::DataShape>>,
+                T2: ::planus::WriteAsDefault<u64, u64>,
+                T3: ::planus::WriteAsDefault<u64, u64>,
+                T4: ::planus::WriteAsDefault<u64, u64>,
+                T5: ::planus::WriteAsDefault<u64, u64>,
+                T6: ::planus::WriteAsDefault<u64, u64>,
+                T7: ::planus::WriteAs<::planus::Offset<self::PackB>>,
+                T8: ::planus::WriteAsDefault<f32, f32>,
+            > ::planus::WriteAsOptional<::planus::Offset<Im2Col>>
+            for Im2ColBuilder<(T0, T1, T2, T3, T4, T5, T6, T7, T8)>
+        {
+            type Prepared = ::planus::Offset<Im2Col>;
+
+            #[inline]
+            fn prepare(
+                &self,
+                builder: &mut ::planus::Builder,
+            ) -> ::core::option::Option<::planus::Offset<Im2Col>> {
+                ::core::option::Option::Some(::planus::WriteAsOffset::prepare(self, builder))
+            }
+        }
+
+        impl<
+                T0: ::planus::WriteAs<::planus::Offset<self::PatchSpec>>,
+                T1: ::planus::WriteAs<::planus::Offset<self::DataShape>>,
+                T2: ::planus::WriteAsDefault<u64, u64>,
+                T3: ::planus::WriteAsDefault<u64, u64>,
+                T4: ::planus::WriteAsDefault<u64, u64>,
+                T5: ::planus::WriteAsDefault<u64, u64>,
+                T6: ::planus::WriteAsDefault<u64, u64>,
+                T7: ::planus::WriteAs<::planus::Offset<self::PackB>>,
+                T8: ::planus::WriteAsDefault<f32, f32>,
+            > ::planus::WriteAsOffset<Im2Col>
+            for Im2ColBuilder<(T0, T1, T2, T3, T4, T5, T6, T7, T8)>
+        {
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<Im2Col> {
+                let (v0, v1, v2, v3, v4, v5, v6, v7, v8) = &self.0;
+                Im2Col::create(builder, v0, v1, v2, v3, v4, v5, v6, v7, v8)
+            }
+        }
+
+        /// Reference to a deserialized [Im2Col].
+        #[derive(Copy, Clone)]
+        pub struct Im2ColRef<'a>(#[allow(dead_code)] ::planus::table_reader::Table<'a>);
+
+        impl<'a> Im2ColRef<'a> {
+            /// Getter for the [`patch` field](Im2Col#structfield.patch).
+            #[inline]
+            pub fn patch(&self) -> ::planus::Result<self::PatchSpecRef<'a>> {
+                self.0.access_required(0, "Im2Col", "patch")
+            }
+
+            /// Getter for the [`input_shape` field](Im2Col#structfield.input_shape).
+            #[inline]
+            pub fn input_shape(&self) -> ::planus::Result<self::DataShapeRef<'a>> {
+                self.0.access_required(1, "Im2Col", "input_shape")
+            }
+
+            /// Getter for the [`m` field](Im2Col#structfield.m).
+            #[inline]
+            pub fn m(&self) -> ::planus::Result<u64> {
+                ::core::result::Result::Ok(self.0.access(2, "Im2Col", "m")?.unwrap_or(0))
+            }
+
+            /// Getter for the [`k` field](Im2Col#structfield.k).
+            #[inline]
+            pub fn k(&self) -> ::planus::Result<u64> {
+                ::core::result::Result::Ok(self.0.access(3, "Im2Col", "k")?.unwrap_or(0))
+            }
+
+            /// Getter for the [`n` field](Im2Col#structfield.n).
+            #[inline]
+            pub fn n(&self) -> ::planus::Result<u64> {
+                ::core::result::Result::Ok(self.0.access(4, "Im2Col", "n")?.unwrap_or(0))
+            }
+
+            /// Getter for the [`group` field](Im2Col#structfield.group).
+            #[inline]
+            pub fn group(&self) -> ::planus::Result<u64> {
+                ::core::result::Result::Ok(self.0.access(5, "Im2Col", "group")?.unwrap_or(0))
+            }
+
+            /// Getter for the [`ci_per_group` field](Im2Col#structfield.ci_per_group).
+            #[inline]
+            pub fn ci_per_group(&self) -> ::planus::Result<u64> {
+                ::core::result::Result::Ok(self.0.access(6, "Im2Col", "ci_per_group")?.unwrap_or(0))
+            }
+
+            /// Getter for the [`b_pack` field](Im2Col#structfield.b_pack).
+            #[inline]
+            pub fn b_pack(&self) -> ::planus::Result<self::PackBRef<'a>> {
+                self.0.access_required(7, "Im2Col", "b_pack")
+            }
+
+            /// Getter for the [`pad_value` field](Im2Col#structfield.pad_value).
+            #[inline]
+            pub fn pad_value(&self) -> ::planus::Result<f32> {
+                ::core::result::Result::Ok(self.0.access(8, "Im2Col", "pad_value")?.unwrap_or(0.0))
+            }
+        }
+
+        impl<'a> ::core::fmt::Debug for Im2ColRef<'a> {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                let mut f = f.debug_struct("Im2ColRef");
+                f.field("patch", &self.patch());
+                f.field("input_shape", &self.input_shape());
+                f.field("m", &self.m());
+                f.field("k", &self.k());
+                f.field("n", &self.n());
+                f.field("group", &self.group());
+                f.field("ci_per_group", &self.ci_per_group());
+                f.field("b_pack", &self.b_pack());
+                f.field("pad_value", &self.pad_value());
+                f.finish()
+            }
+        }
+
+        impl<'a> ::core::convert::TryFrom<Im2ColRef<'a>> for Im2Col {
+            type Error = ::planus::Error;
+
+            #[allow(unreachable_code)]
+            fn try_from(value: Im2ColRef<'a>) -> ::planus::Result<Self> {
+                ::core::result::Result::Ok(Self {
+                    patch: ::planus::alloc::boxed::Box::new(::core::convert::TryInto::try_into(
+                        value.patch()?,
+                    )?),
+                    input_shape: ::planus::alloc::boxed::Box::new(
+                        ::core::convert::TryInto::try_into(value.input_shape()?)?,
+                    ),
+                    m: ::core::convert::TryInto::try_into(value.m()?)?,
+                    k: ::core::convert::TryInto::try_into(value.k()?)?,
+                    n: ::core::convert::TryInto::try_into(value.n()?)?,
+                    group: ::core::convert::TryInto::try_into(value.group()?)?,
+                    ci_per_group: ::core::convert::TryInto::try_into(value.ci_per_group()?)?,
+                    b_pack: ::planus::alloc::boxed::Box::new(::core::convert::TryInto::try_into(
+                        value.b_pack()?,
+                    )?),
+                    pad_value: ::core::convert::TryInto::try_into(value.pad_value()?)?,
+                })
+            }
+        }
+
+        impl<'a> ::planus::TableRead<'a> for Im2ColRef<'a> {
+            #[inline]
+            fn from_buffer(
+                buffer: ::planus::SliceWithStartOffset<'a>,
+                offset: usize,
+            ) -> ::core::result::Result<Self, ::planus::errors::ErrorKind> {
+                ::core::result::Result::Ok(Self(::planus::table_reader::Table::from_buffer(
+                    buffer, offset,
+                )?))
+            }
+        }
+
+        impl<'a> ::planus::VectorReadInner<'a> for Im2ColRef<'a> {
+            type Error = ::planus::Error;
+            const STRIDE: usize = 4;
+
+            unsafe fn from_buffer(
+                buffer: ::planus::SliceWithStartOffset<'a>,
+                offset: usize,
+            ) -> ::planus::Result<Self> {
+                ::planus::TableRead::from_buffer(buffer, offset).map_err(|error_kind| {
+                    error_kind.with_error_location("[Im2ColRef]", "get", buffer.offset_from_start)
+                })
+            }
+        }
+
+        /// # Safety
+        /// The planus compiler generates implementations that initialize
+        /// the bytes in `write_values`.
+        unsafe impl ::planus::VectorWrite<::planus::Offset<Im2Col>> for Im2Col {
+            type Value = ::planus::Offset<Im2Col>;
+            const STRIDE: usize = 4;
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> Self::Value {
+                ::planus::WriteAs::prepare(self, builder)
+            }
+
+            #[inline]
+            unsafe fn write_values(
+                values: &[::planus::Offset<Im2Col>],
+                bytes: *mut ::core::mem::MaybeUninit<u8>,
+                buffer_position: u32,
+            ) {
+                let bytes = bytes as *mut [::core::mem::MaybeUninit<u8>; 4];
+                for (i, v) in ::core::iter::Iterator::enumerate(values.iter()) {
+                    ::planus::WriteAsPrimitive::write(
+                        v,
+                        ::planus::Cursor::new(unsafe { &mut *bytes.add(i) }),
+                        buffer_position - (Self::STRIDE * i) as u32,
+                    );
+                }
+            }
+        }
+
+        impl<'a> ::planus::ReadAsRoot<'a> for Im2ColRef<'a> {
+            fn read_as_root(slice: &'a [u8]) -> ::planus::Result<Self> {
+                ::planus::TableRead::from_buffer(
+                    ::planus::SliceWithStartOffset { buffer: slice, offset_from_start: 0 },
+                    0,
+                )
+                .map_err(|error_kind| {
+                    error_kind.with_error_location("[Im2ColRef]", "read_as_root", 0)
+                })
+            }
+        }
+
+        /// The table `MatMatMulPackB` in the namespace `tract`
+        ///
+        /// Generated from these locations:
+        /// * Table `MatMatMulPackB` in the file `schema/tract.fbs:303`
+        #[derive(
+            Clone,
+            Debug,
+            PartialEq,
+            PartialOrd,
+            Eq,
+            Ord,
+            Hash,
+            ::serde::Serialize,
+            ::serde::Deserialize,
+        )]
+        pub struct MatMatMulPackB {
+            /// The field `pack_b` in the table `MatMatMulPackB`
+            pub pack_b: ::planus::alloc::boxed::Box<self::PackB>,
+            /// The field `row_stride` in the table `MatMatMulPackB`
+            pub row_stride: i64,
+            /// The field `col_stride` in the table `MatMatMulPackB`
+            pub col_stride: i64,
+            /// The field `output_shape` in the table `MatMatMulPackB`
+            pub output_shape: ::planus::alloc::vec::Vec<u64>,
+        }
+
+        #[allow(clippy::derivable_impls)]
+        impl ::core::default::Default for MatMatMulPackB {
+            fn default() -> Self {
+                Self {
+                    pack_b: ::core::default::Default::default(),
+                    row_stride: 0,
+                    col_stride: 0,
+                    output_shape: ::core::default::Default::default(),
+                }
+            }
+        }
+
+        impl MatMatMulPackB {
+            /// Creates a [MatMatMulPackBBuilder] for serializing an instance of this table.
+            #[inline]
+            pub fn builder() -> MatMatMulPackBBuilder<()> {
+                MatMatMulPackBBuilder(())
+            }
+
+            #[allow(clippy::too_many_arguments)]
+            pub fn create(
+                builder: &mut ::planus::Builder,
+                field_pack_b: impl ::planus::WriteAs<::planus::Offset<self::PackB>>,
+                field_row_stride: impl ::planus::WriteAsDefault<i64, i64>,
+                field_col_stride: impl ::planus::WriteAsDefault<i64, i64>,
+                field_output_shape: impl ::planus::WriteAs<::planus::Offset<[u64]>>,
+            ) -> ::planus::Offset<Self> {
+                let prepared_pack_b = field_pack_b.prepare(builder);
+                let prepared_row_stride = field_row_stride.prepare(builder, &0);
+                let prepared_col_stride = field_col_stride.prepare(builder, &0);
+                let prepared_output_shape = field_output_shape.prepare(builder);
+
+                let mut table_writer: ::planus::table_writer::TableWriter<12> =
+                    ::core::default::Default::default();
+                if prepared_row_stride.is_some() {
+                    table_writer.write_entry::<i64>(1);
+                }
+                if prepared_col_stride.is_some() {
+                    table_writer.write_entry::<i64>(2);
+                }
+                table_writer.write_entry::<::planus::Offset<self::PackB>>(0);
+                table_writer.write_entry::<::planus::Offset<[u64]>>(3);
+
+                unsafe {
+                    table_writer.finish(builder, |object_writer| {
+                        if let ::core::option::Option::Some(prepared_row_stride) =
+                            prepared_row_stride
+                        {
+                            object_writer.write::<_, _, 8>(&prepared_row_stride);
+                        }
+                        if let ::core::option::Option::Some(prepared_col_stride) =
+                            prepared_col_stride
+                        {
+                            object_writer.write::<_, _, 8>(&prepared_col_stride);
+                        }
+                        object_writer.write::<_, _, 4>(&prepared_pack_b);
+                        object_writer.write::<_, _, 4>(&prepared_output_shape);
+                    });
+                }
+                builder.current_offset()
+            }
+        }
+
+        impl ::planus::WriteAs<::planus::Offset<MatMatMulPackB>> for MatMatMulPackB {
+            type Prepared = ::planus::Offset<Self>;
+
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<MatMatMulPackB> {
+                ::planus::WriteAsOffset::prepare(self, builder)
+            }
+        }
+
+        impl ::planus::WriteAsOptional<::planus::Offset<MatMatMulPackB>> for MatMatMulPackB {
+            type Prepared = ::planus::Offset<Self>;
+
+            #[inline]
+            fn prepare(
+                &self,
+                builder: &mut ::planus::Builder,
+            ) -> ::core::option::Option<::planus::Offset<MatMatMulPackB>> {
+                ::core::option::Option::Some(::planus::WriteAsOffset::prepare(self, builder))
+            }
+        }
+
+        impl ::planus::WriteAsOffset<MatMatMulPackB> for MatMatMulPackB {
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<MatMatMulPackB> {
+                MatMatMulPackB::create(
+                    builder,
+                    &self.pack_b,
+                    self.row_stride,
+                    self.col_stride,
+                    &self.output_shape,
+                )
+            }
+        }
+
+        /// Builder for serializing an instance of the [MatMatMulPackB] type.
+        ///
+        /// Can be created using the [MatMatMulPackB::builder] method.
+        #[derive(Debug)]
+        #[must_use]
+        pub struct MatMatMulPackBBuilder<State>(State);
+
+        impl MatMatMulPackBBuilder<()> {
+            /// Setter for the [`pack_b` field](MatMatMulPackB#structfield.pack_b).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn pack_b<T0>(self, value: T0) -> MatMatMulPackBBuilder<(T0,)>
+            where
+                T0: ::planus::WriteAs<::planus::Offset<self::PackB>>,
+            {
+                MatMatMulPackBBuilder((value,))
+            }
+        }
+
+        impl<T0> MatMatMulPackBBuilder<(T0,)> {
+            /// Setter for the [`row_stride` field](MatMatMulPackB#structfield.row_stride).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn row_stride<T1>(self, value: T1) -> MatMatMulPackBBuilder<(T0, T1)>
+            where
+                T1: ::planus::WriteAsDefault<i64, i64>,
+            {
+                let (v0,) = self.0;
+                MatMatMulPackBBuilder((v0, value))
+            }
+
+            /// Sets the [`row_stride` field](MatMatMulPackB#structfield.row_stride) to the default value.
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn row_stride_as_default(
+                self,
+            ) -> MatMatMulPackBBuilder<(T0, ::planus::DefaultValue)> {
+                self.row_stride(::planus::DefaultValue)
+            }
+        }
+
+        impl<T0, T1> MatMatMulPackBBuilder<(T0, T1)> {
+            /// Setter for the [`col_stride` field](MatMatMulPackB#structfield.col_stride).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn col_stride<T2>(self, value: T2) -> MatMatMulPackBBuilder<(T0, T1, T2)>
+            where
+                T2: ::planus::WriteAsDefault<i64, i64>,
+            {
+                let (v0, v1) = self.0;
+                MatMatMulPackBBuilder((v0, v1, value))
+            }
+
+            /// Sets the [`col_stride` field](MatMatMulPackB#structfield.col_stride) to the default value.
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn col_stride_as_default(
+                self,
+            ) -> MatMatMulPackBBuilder<(T0, T1, ::planus::DefaultValue)> {
+                self.col_stride(::planus::DefaultValue)
+            }
+        }
+
+        impl<T0, T1, T2> MatMatMulPackBBuilder<(T0, T1, T2)> {
+            /// Setter for the [`output_shape` field](MatMatMulPackB#structfield.output_shape).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn output_shape<T3>(self, value: T3) -> MatMatMulPackBBuilder<(T0, T1, T2, T3)>
+            where
+                T3: ::planus::WriteAs<::planus::Offset<[u64]>>,
+            {
+                let (v0, v1, v2) = self.0;
+                MatMatMulPackBBuilder((v0, v1, v2, value))
+            }
+        }
+
+        impl<T0, T1, T2, T3> MatMatMulPackBBuilder<(T0, T1, T2, T3)> {
+            /// Finish writing the builder to get an [Offset](::planus::Offset) to a serialized [MatMatMulPackB].
+            #[inline]
+            pub fn finish(self, builder: &mut ::planus::Builder) -> ::planus::Offset<MatMatMulPackB>
+            where
+                Self: ::planus::WriteAsOffset<MatMatMulPackB>,
+            {
+                ::planus::WriteAsOffset::prepare(&self, builder)
+            }
+        }
+
+        impl<
+                T0: ::planus::WriteAs<::planus::Offset<self::PackB>>,
+                T1: ::planus::WriteAsDefault<i64, i64>,
+                T2: ::planus::WriteAsDefault<i64, i64>,
+                T3: ::planus::WriteAs<::planus::Offset<[u64]>>,
+            > ::planus::WriteAs<::planus::Offset<MatMatMulPackB>>
+            for MatMatMulPackBBuilder<(T0, T1, T2, T3)>
+        {
+            type Prepared = ::planus::Offset<MatMatMulPackB>;
+
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<MatMatMulPackB> {
+                ::planus::WriteAsOffset::prepare(self, builder)
+            }
+        }
+
+        impl<
+                T0: ::planus::WriteAs<::planus::Offset<self::PackB>>,
+                T1: ::planus::WriteAsDefault<i64, i64>,
+                T2: ::planus::WriteAsDefault<i64, i64>,
+                T3: ::planus::WriteAs<::planus::Offset<[u64]>>,
+            > ::planus::WriteAsOptional<::planus::Offset<MatMatMulPackB>>
+            for MatMatMulPackBBuilder<(T0, T1, T2, T3)>
+        {
+            type Prepared = ::planus::Offset<MatMatMulPackB>;
+
+            #[inline]
+            fn prepare(
+                &self,
+                builder: &mut ::planus::Builder,
+            ) -> ::core::option::Option<::planus::Offset<MatMatMulPackB>> {
+                ::core::option::Option::Some(::planus::WriteAsOffset::prepare(self, builder))
+            }
+        }
+
+        impl<
+                T0: ::planus::WriteAs<::planus::Offset<self::PackB>>,
+                T1: ::planus::WriteAsDefault<i64, i64>,
+                T2: ::planus::WriteAsDefault<i64, i64>,
+                T3: ::planus::WriteAs<::planus::Offset<[u64]>>,
+            > ::planus::WriteAsOffset<MatMatMulPackB> for MatMatMulPackBBuilder<(T0, T1, T2, T3)>
+        {
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<MatMatMulPackB> {
+                let (v0, v1, v2, v3) = &self.0;
+                MatMatMulPackB::create(builder, v0, v1, v2, v3)
+            }
+        }
+
+        /// Reference to a deserialized [MatMatMulPackB].
+        #[derive(Copy, Clone)]
+        pub struct MatMatMulPackBRef<'a>(#[allow(dead_code)] ::planus::table_reader::Table<'a>);
+
+        impl<'a> MatMatMulPackBRef<'a> {
+            /// Getter for the [`pack_b` field](MatMatMulPackB#structfield.pack_b).
+            #[inline]
+            pub fn pack_b(&self) -> ::planus::Result<self::PackBRef<'a>> {
+                self.0.access_required(0, "MatMatMulPackB", "pack_b")
+            }
+
+            /// Getter for the [`row_stride` field](MatMatMulPackB#structfield.row_stride).
+            #[inline]
+            pub fn row_stride(&self) -> ::planus::Result<i64> {
+                ::core::result::Result::Ok(
+                    self.0.access(1, "MatMatMulPackB", "row_stride")?.unwrap_or(0),
+                )
+            }
+
+            /// Getter for the [`col_stride` field](MatMatMulPackB#structfield.col_stride).
+            #[inline]
+            pub fn col_stride(&self) -> ::planus::Result<i64> {
+                ::core::result::Result::Ok(
+                    self.0.access(2, "MatMatMulPackB", "col_stride")?.unwrap_or(0),
+                )
+            }
+
+            /// Getter for the [`output_shape` field](MatMatMulPackB#structfield.output_shape).
+            #[inline]
+            pub fn output_shape(&self) -> ::planus::Result<::planus::Vector<'a, u64>> {
+                self.0.access_required(3, "MatMatMulPackB", "output_shape")
+            }
+        }
+
+        impl<'a> ::core::fmt::Debug for MatMatMulPackBRef<'a> {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                let mut f = f.debug_struct("MatMatMulPackBRef");
+                f.field("pack_b", &self.pack_b());
+                f.field("row_stride", &self.row_stride());
+                f.field("col_stride", &self.col_stride());
+                f.field("output_shape", &self.output_shape());
+                f.finish()
+            }
+        }
+
+        impl<'a> ::core::convert::TryFrom<MatMatMulPackBRef<'a>> for MatMatMulPackB {
+            type Error = ::planus::Error;
+
+            #[allow(unreachable_code)]
+            fn try_from(value: MatMatMulPackBRef<'a>) -> ::planus::Result<Self> {
+                ::core::result::Result::Ok(Self {
+                    pack_b: ::planus::alloc::boxed::Box::new(::core::convert::TryInto::try_into(
+                        value.pack_b()?,
+                    )?),
+                    row_stride: ::core::convert::TryInto::try_into(value.row_stride()?)?,
+                    col_stride: ::core::convert::TryInto::try_into(value.col_stride()?)?,
+                    output_shape: value.output_shape()?.to_vec()?,
+                })
+            }
+        }
+
+        impl<'a> ::planus::TableRead<'a> for MatMatMulPackBRef<'a> {
+            #[inline]
+            fn from_buffer(
+                buffer: ::planus::SliceWithStartOffset<'a>,
+                offset: usize,
+            ) -> ::core::result::Result<Self, ::planus::errors::ErrorKind> {
+                ::core::result::Result::Ok(Self(::planus::table_reader::Table::from_buffer(
+                    buffer, offset,
+                )?))
+            }
+        }
+
+        impl<'a> ::planus::VectorReadInner<'a> for MatMatMulPackBRef<'a> {
+            type Error = ::planus::Error;
+            const STRIDE: usize = 4;
+
+            unsafe fn from_buffer(
+                buffer: ::planus::SliceWithStartOffset<'a>,
+                offset: usize,
+            ) -> ::planus::Result<Self> {
+                ::planus::TableRead::from_buffer(buffer, offset).map_err(|error_kind| {
+                    error_kind.with_error_location(
+                        "[MatMatMulPackBRef]",
+                        "get",
+                        buffer.offset_from_start,
+                    )
+                })
+            }
+        }
+
+        /// # Safety
+        /// The planus compiler generates implementations that initialize
+        /// the bytes in `write_values`.
+        unsafe impl ::planus::VectorWrite<::planus::Offset<MatMatMulPackB>> for MatMatMulPackB {
+            type Value = ::planus::Offset<MatMatMulPackB>;
+            const STRIDE: usize = 4;
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> Self::Value {
+                ::planus::WriteAs::prepare(self, builder)
+            }
+
+            #[inline]
+            unsafe fn write_values(
+                values: &[::planus::Offset<MatMatMulPackB>],
+                bytes: *mut ::core::mem::MaybeUninit<u8>,
+                buffer_position: u32,
+            ) {
+                let bytes = bytes as *mut [::core::mem::MaybeUninit<u8>; 4];
+                for (i, v) in ::core::iter::Iterator::enumerate(values.iter()) {
+                    ::planus::WriteAsPrimitive::write(
+                        v,
+                        ::planus::Cursor::new(unsafe { &mut *bytes.add(i) }),
+                        buffer_position - (Self::STRIDE * i) as u32,
+                    );
+                }
+            }
+        }
+
+        impl<'a> ::planus::ReadAsRoot<'a> for MatMatMulPackBRef<'a> {
+            fn read_as_root(slice: &'a [u8]) -> ::planus::Result<Self> {
+                ::planus::TableRead::from_buffer(
+                    ::planus::SliceWithStartOffset { buffer: slice, offset_from_start: 0 },
+                    0,
+                )
+                .map_err(|error_kind| {
+                    error_kind.with_error_location("[MatMatMulPackBRef]", "read_as_root", 0)
+                })
+            }
+        }
+
+        /// The enum `MatrixStoreKind` in the namespace `tract`
+        ///
+        /// Generated from these locations:
+        /// * Enum `MatrixStoreKind` in the file `schema/tract.fbs:310`
+        #[derive(
+            Copy,
+            Clone,
+            Debug,
+            PartialEq,
+            Eq,
+            PartialOrd,
+            Ord,
+            Hash,
+            ::serde::Serialize,
+            ::serde::Deserialize,
+        )]
+        #[repr(u8)]
+        pub enum MatrixStoreKind {
+            /// The variant `Packed` in the enum `MatrixStoreKind`
+            Packed = 0,
+
+            /// The variant `Strides` in the enum `MatrixStoreKind`
+            Strides = 1,
+
+            /// The variant `OffsetsAndPtrs` in the enum `MatrixStoreKind`
+            OffsetsAndPtrs = 2,
+
+            /// The variant `VecStride` in the enum `MatrixStoreKind`
+            VecStride = 3,
+        }
+
+        impl MatrixStoreKind {
+            /// Array containing all valid variants of MatrixStoreKind
+            pub const ENUM_VALUES: [Self; 4] =
+                [Self::Packed, Self::Strides, Self::OffsetsAndPtrs, Self::VecStride];
+        }
+
+        impl ::core::convert::TryFrom<u8> for MatrixStoreKind {
+            type Error = ::planus::errors::UnknownEnumTagKind;
+            #[inline]
+            fn try_from(
+                value: u8,
+            ) -> ::core::result::Result<Self, ::planus::errors::UnknownEnumTagKind> {
+                #[allow(clippy::match_single_binding)]
+                match value {
+                    0 => ::core::result::Result::Ok(MatrixStoreKind::Packed),
+                    1 => ::core::result::Result::Ok(MatrixStoreKind::Strides),
+                    2 => ::core::result::Result::Ok(MatrixStoreKind::OffsetsAndPtrs),
+                    3 => ::core::result::Result::Ok(MatrixStoreKind::VecStride),
+
+                    _ => ::core::result::Result::Err(::planus::errors::UnknownEnumTagKind {
+                        tag: value as i128,
+                    }),
+                }
+            }
+        }
+
+        impl ::core::convert::From<MatrixStoreKind> for u8 {
+            #[inline]
+            fn from(value: MatrixStoreKind) -> Self {
+                value as u8
+            }
+        }
+
+        /// # Safety
+        /// The Planus compiler correctly calculates `ALIGNMENT` and `SIZE`.
+        unsafe impl ::planus::Primitive for MatrixStoreKind {
+            const ALIGNMENT: usize = 1;
+            const SIZE: usize = 1;
+        }
+
+        impl ::planus::WriteAsPrimitive<MatrixStoreKind> for MatrixStoreKind {
+            #[inline]
+            fn write<const N: usize>(&self, cursor: ::planus::Cursor<'_, N>, buffer_position: u32) {
+                (*self as u8).write(cursor, buffer_position);
+            }
+        }
+
+        impl ::planus::WriteAs<MatrixStoreKind> for MatrixStoreKind {
+            type Prepared = Self;
+
+            #[inline]
+            fn prepare(&self, _builder: &mut ::planus::Builder) -> MatrixStoreKind {
+                *self
+            }
+        }
+
+        impl ::planus::WriteAsDefault<MatrixStoreKind, MatrixStoreKind> for MatrixStoreKind {
+            type Prepared = Self;
+
+            #[inline]
+            fn prepare(
+                &self,
+                _builder: &mut ::planus::Builder,
+                default: &MatrixStoreKind,
+            ) -> ::core::option::Option<MatrixStoreKind> {
+                if self == default {
+                    ::core::option::Option::None
+                } else {
+                    ::core::option::Option::Some(*self)
+                }
+            }
+        }
+
+        impl ::planus::WriteAsOptional<MatrixStoreKind> for MatrixStoreKind {
+            type Prepared = Self;
+
+            #[inline]
+            fn prepare(
+                &self,
+                _builder: &mut ::planus::Builder,
+            ) -> ::core::option::Option<MatrixStoreKind> {
+                ::core::option::Option::Some(*self)
+            }
+        }
+
+        impl<'buf> ::planus::TableRead<'buf> for MatrixStoreKind {
+            #[inline]
+            fn from_buffer(
+                buffer: ::planus::SliceWithStartOffset<'buf>,
+                offset: usize,
+            ) -> ::core::result::Result<Self, ::planus::errors::ErrorKind> {
+                let n: u8 = ::planus::TableRead::from_buffer(buffer, offset)?;
+                ::core::result::Result::Ok(::core::convert::TryInto::try_into(n)?)
+            }
+        }
+
+        impl<'buf> ::planus::VectorReadInner<'buf> for MatrixStoreKind {
+            type Error = ::planus::errors::UnknownEnumTag;
+            const STRIDE: usize = 1;
+            #[inline]
+            unsafe fn from_buffer(
+                buffer: ::planus::SliceWithStartOffset<'buf>,
+                offset: usize,
+            ) -> ::core::result::Result<Self, ::planus::errors::UnknownEnumTag> {
+                let value = unsafe { *buffer.buffer.get_unchecked(offset) };
+                let value: ::core::result::Result<Self, _> =
+                    ::core::convert::TryInto::try_into(value);
+                value.map_err(|error_kind| {
+                    error_kind.with_error_location(
+                        "MatrixStoreKind",
+                        "VectorRead::from_buffer",
+                        buffer.offset_from_start,
+                    )
+                })
+            }
+        }
+
+        /// # Safety
+        /// The planus compiler generates implementations that initialize
+        /// the bytes in `write_values`.
+        unsafe impl ::planus::VectorWrite<MatrixStoreKind> for MatrixStoreKind {
+            const STRIDE: usize = 1;
+
+            type Value = Self;
+
+            #[inline]
+            fn prepare(&self, _builder: &mut ::planus::Builder) -> Self {
+                *self
+            }
+
+            #[inline]
+            unsafe fn write_values(
+                values: &[Self],
+                bytes: *mut ::core::mem::MaybeUninit<u8>,
+                buffer_position: u32,
+            ) {
+                let bytes = bytes as *mut [::core::mem::MaybeUninit<u8>; 1];
+                for (i, v) in ::core::iter::Iterator::enumerate(values.iter()) {
+                    ::planus::WriteAsPrimitive::write(
+                        v,
+                        ::planus::Cursor::new(unsafe { &mut *bytes.add(i) }),
+                        buffer_position - i as u32,
+                    );
+                }
+            }
+        }
+
+        /// The table `MatrixStore` in the namespace `tract`
+        ///
+        /// Generated from these locations:
+        /// * Table `MatrixStore` in the file `schema/tract.fbs:318`
+        #[derive(
+            Clone,
+            Debug,
+            PartialEq,
+            PartialOrd,
+            Eq,
+            Ord,
+            Hash,
+            ::serde::Serialize,
+            ::serde::Deserialize,
+        )]
+        pub struct MatrixStore {
+            /// The field `kind` in the table `MatrixStore`
+            pub kind: self::MatrixStoreKind,
+            /// The field `row_stride` in the table `MatrixStore`
+            pub row_stride: i64,
+            /// The field `col_stride` in the table `MatrixStore`
+            pub col_stride: i64,
+            /// The field `row_offsets` in the table `MatrixStore`
+            pub row_offsets: ::core::option::Option<::planus::alloc::vec::Vec<i64>>,
+            /// The field `col_offsets` in the table `MatrixStore`
+            pub col_offsets: ::core::option::Option<::planus::alloc::vec::Vec<i64>>,
+        }
+
+        #[allow(clippy::derivable_impls)]
+        impl ::core::default::Default for MatrixStore {
+            fn default() -> Self {
+                Self {
+                    kind: self::MatrixStoreKind::Packed,
+                    row_stride: 0,
+                    col_stride: 0,
+                    row_offsets: ::core::default::Default::default(),
+                    col_offsets: ::core::default::Default::default(),
+                }
+            }
+        }
+
+        impl MatrixStore {
+            /// Creates a [MatrixStoreBuilder] for serializing an instance of this table.
+            #[inline]
+            pub fn builder() -> MatrixStoreBuilder<()> {
+                MatrixStoreBuilder(())
+            }
+
+            #[allow(clippy::too_many_arguments)]
+            pub fn create(
+                builder: &mut ::planus::Builder,
+                field_kind: impl ::planus::WriteAsDefault<self::MatrixStoreKind, self::MatrixStoreKind>,
+                field_row_stride: impl ::planus::WriteAsDefault<i64, i64>,
+                field_col_stride: impl ::planus::WriteAsDefault<i64, i64>,
+                field_row_offsets: impl ::planus::WriteAsOptional<::planus::Offset<[i64]>>,
+                field_col_offsets: impl ::planus::WriteAsOptional<::planus::Offset<[i64]>>,
+            ) -> ::planus::Offset<Self> {
+                let prepared_kind = field_kind.prepare(builder, &self::MatrixStoreKind::Packed);
+                let prepared_row_stride = field_row_stride.prepare(builder, &0);
+                let prepared_col_stride = field_col_stride.prepare(builder, &0);
+                let prepared_row_offsets = field_row_offsets.prepare(builder);
+                let prepared_col_offsets = field_col_offsets.prepare(builder);
+
+                let mut table_writer: ::planus::table_writer::TableWriter<14> =
+                    ::core::default::Default::default();
+                if prepared_row_stride.is_some() {
+                    table_writer.write_entry::<i64>(1);
+                }
+                if prepared_col_stride.is_some() {
+                    table_writer.write_entry::<i64>(2);
+                }
+                if prepared_row_offsets.is_some() {
+                    table_writer.write_entry::<::planus::Offset<[i64]>>(3);
+                }
+                if prepared_col_offsets.is_some() {
+                    table_writer.write_entry::<::planus::Offset<[i64]>>(4);
+                }
+                if prepared_kind.is_some() {
+                    table_writer.write_entry::<self::MatrixStoreKind>(0);
+                }
+
+                unsafe {
+                    table_writer.finish(builder, |object_writer| {
+                        if let ::core::option::Option::Some(prepared_row_stride) =
+                            prepared_row_stride
+                        {
+                            object_writer.write::<_, _, 8>(&prepared_row_stride);
+                        }
+                        if let ::core::option::Option::Some(prepared_col_stride) =
+                            prepared_col_stride
+                        {
+                            object_writer.write::<_, _, 8>(&prepared_col_stride);
+                        }
+                        if let ::core::option::Option::Some(prepared_row_offsets) =
+                            prepared_row_offsets
+                        {
+                            object_writer.write::<_, _, 4>(&prepared_row_offsets);
+                        }
+                        if let ::core::option::Option::Some(prepared_col_offsets) =
+                            prepared_col_offsets
+                        {
+                            object_writer.write::<_, _, 4>(&prepared_col_offsets);
+                        }
+                        if let ::core::option::Option::Some(prepared_kind) = prepared_kind {
+                            object_writer.write::<_, _, 1>(&prepared_kind);
+                        }
+                    });
+                }
+                builder.current_offset()
+            }
+        }
+
+        impl ::planus::WriteAs<::planus::Offset<MatrixStore>> for MatrixStore {
+            type Prepared = ::planus::Offset<Self>;
+
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<MatrixStore> {
+                ::planus::WriteAsOffset::prepare(self, builder)
+            }
+        }
+
+        impl ::planus::WriteAsOptional<::planus::Offset<MatrixStore>> for MatrixStore {
+            type Prepared = ::planus::Offset<Self>;
+
+            #[inline]
+            fn prepare(
+                &self,
+                builder: &mut ::planus::Builder,
+            ) -> ::core::option::Option<::planus::Offset<MatrixStore>> {
+                ::core::option::Option::Some(::planus::WriteAsOffset::prepare(self, builder))
+            }
+        }
+
+        impl ::planus::WriteAsOffset<MatrixStore> for MatrixStore {
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<MatrixStore> {
+                MatrixStore::create(
+                    builder,
+                    self.kind,
+                    self.row_stride,
+                    self.col_stride,
+                    &self.row_offsets,
+                    &self.col_offsets,
+                )
+            }
+        }
+
+        /// Builder for serializing an instance of the [MatrixStore] type.
+        ///
+        /// Can be created using the [MatrixStore::builder] method.
+        #[derive(Debug)]
+        #[must_use]
+        pub struct MatrixStoreBuilder<State>(State);
+
+        impl MatrixStoreBuilder<()> {
+            /// Setter for the [`kind` field](MatrixStore#structfield.kind).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn kind<T0>(self, value: T0) -> MatrixStoreBuilder<(T0,)>
+            where
+                T0: ::planus::WriteAsDefault<self::MatrixStoreKind, self::MatrixStoreKind>,
+            {
+                MatrixStoreBuilder((value,))
+            }
+
+            /// Sets the [`kind` field](MatrixStore#structfield.kind) to the default value.
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn kind_as_default(self) -> MatrixStoreBuilder<(::planus::DefaultValue,)> {
+                self.kind(::planus::DefaultValue)
+            }
+        }
+
+        impl<T0> MatrixStoreBuilder<(T0,)> {
+            /// Setter for the [`row_stride` field](MatrixStore#structfield.row_stride).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn row_stride<T1>(self, value: T1) -> MatrixStoreBuilder<(T0, T1)>
+            where
+                T1: ::planus::WriteAsDefault<i64, i64>,
+            {
+                let (v0,) = self.0;
+                MatrixStoreBuilder((v0, value))
+            }
+
+            /// Sets the [`row_stride` field](MatrixStore#structfield.row_stride) to the default value.
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn row_stride_as_default(self) -> MatrixStoreBuilder<(T0, ::planus::DefaultValue)> {
+                self.row_stride(::planus::DefaultValue)
+            }
+        }
+
+        impl<T0, T1> MatrixStoreBuilder<(T0, T1)> {
+            /// Setter for the [`col_stride` field](MatrixStore#structfield.col_stride).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn col_stride<T2>(self, value: T2) -> MatrixStoreBuilder<(T0, T1, T2)>
+            where
+                T2: ::planus::WriteAsDefault<i64, i64>,
+            {
+                let (v0, v1) = self.0;
+                MatrixStoreBuilder((v0, v1, value))
+            }
+
+            /// Sets the [`col_stride` field](MatrixStore#structfield.col_stride) to the default value.
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn col_stride_as_default(
+                self,
+            ) -> MatrixStoreBuilder<(T0, T1, ::planus::DefaultValue)> {
+                self.col_stride(::planus::DefaultValue)
+            }
+        }
+
+        impl<T0, T1, T2> MatrixStoreBuilder<(T0, T1, T2)> {
+            /// Setter for the [`row_offsets` field](MatrixStore#structfield.row_offsets).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn row_offsets<T3>(self, value: T3) -> MatrixStoreBuilder<(T0, T1, T2, T3)>
+            where
+                T3: ::planus::WriteAsOptional<::planus::Offset<[i64]>>,
+            {
+                let (v0, v1, v2) = self.0;
+                MatrixStoreBuilder((v0, v1, v2, value))
+            }
+
+            /// Sets the [`row_offsets` field](MatrixStore#structfield.row_offsets) to null.
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn row_offsets_as_null(self) -> MatrixStoreBuilder<(T0, T1, T2, ())> {
+                self.row_offsets(())
+            }
+        }
+
+        impl<T0, T1, T2, T3> MatrixStoreBuilder<(T0, T1, T2, T3)> {
+            /// Setter for the [`col_offsets` field](MatrixStore#structfield.col_offsets).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn col_offsets<T4>(self, value: T4) -> MatrixStoreBuilder<(T0, T1, T2, T3, T4)>
+            where
+                T4: ::planus::WriteAsOptional<::planus::Offset<[i64]>>,
+            {
+                let (v0, v1, v2, v3) = self.0;
+                MatrixStoreBuilder((v0, v1, v2, v3, value))
+            }
+
+            /// Sets the [`col_offsets` field](MatrixStore#structfield.col_offsets) to null.
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn col_offsets_as_null(self) -> MatrixStoreBuilder<(T0, T1, T2, T3, ())> {
+                self.col_offsets(())
+            }
+        }
+
+        impl<T0, T1, T2, T3, T4> MatrixStoreBuilder<(T0, T1, T2, T3, T4)> {
+            /// Finish writing the builder to get an [Offset](::planus::Offset) to a serialized [MatrixStore].
+            #[inline]
+            pub fn finish(self, builder: &mut ::planus::Builder) -> ::planus::Offset<MatrixStore>
+            where
+                Self: ::planus::WriteAsOffset<MatrixStore>,
+            {
+                ::planus::WriteAsOffset::prepare(&self, builder)
+            }
+        }
+
+        impl<
+                T0: ::planus::WriteAsDefault<self::MatrixStoreKind, self::MatrixStoreKind>,
+                T1: ::planus::WriteAsDefault<i64, i64>,
+                T2: ::planus::WriteAsDefault<i64, i64>,
+                T3: ::planus::WriteAsOptional<::planus::Offset<[i64]>>,
+                T4: ::planus::WriteAsOptional<::planus::Offset<[i64]>>,
+            > ::planus::WriteAs<::planus::Offset<MatrixStore>>
+            for MatrixStoreBuilder<(T0, T1, T2, T3, T4)>
+        {
+            type Prepared = ::planus::Offset<MatrixStore>;
+
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<MatrixStore> {
+                ::planus::WriteAsOffset::prepare(self, builder)
+            }
+        }
+
+        impl<
+                T0: ::planus::WriteAsDefault<self::MatrixStoreKind, self::MatrixStoreKind>,
+                T1: ::planus::WriteAsDefault<i64, i64>,
+                T2: ::planus::WriteAsDefault<i64, i64>,
+                T3: ::planus::WriteAsOptional<::planus::Offset<[i64]>>,
+                T4: ::planus::WriteAsOptional<::planus::Offset<[i64]>>,
+            > ::planus::WriteAsOptional<::planus::Offset<MatrixStore>>
+            for MatrixStoreBuilder<(T0, T1, T2, T3, T4)>
+        {
+            type Prepared = ::planus::Offset<MatrixStore>;
+
+            #[inline]
+            fn prepare(
+                &self,
+                builder: &mut ::planus::Builder,
+            ) -> ::core::option::Option<::planus::Offset<MatrixStore>> {
+                ::core::option::Option::Some(::planus::WriteAsOffset::prepare(self, builder))
+            }
+        }
+
+        impl<
+                T0: ::planus::WriteAsDefault<self::MatrixStoreKind, self::MatrixStoreKind>,
+                T1: ::planus::WriteAsDefault<i64, i64>,
+                T2: ::planus::WriteAsDefault<i64, i64>,
+                T3: ::planus::WriteAsOptional<::planus::Offset<[i64]>>,
+                T4: ::planus::WriteAsOptional<::planus::Offset<[i64]>>,
+            > ::planus::WriteAsOffset<MatrixStore> for MatrixStoreBuilder<(T0, T1, T2, T3, T4)>
+        {
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<MatrixStore> {
+                let (v0, v1, v2, v3, v4) = &self.0;
+                MatrixStore::create(builder, v0, v1, v2, v3, v4)
+            }
+        }
+
+        /// Reference to a deserialized [MatrixStore].
+        #[derive(Copy, Clone)]
+        pub struct MatrixStoreRef<'a>(#[allow(dead_code)] ::planus::table_reader::Table<'a>);
+
+        impl<'a> MatrixStoreRef<'a> {
+            /// Getter for the [`kind` field](MatrixStore#structfield.kind).
+            #[inline]
+            pub fn kind(&self) -> ::planus::Result<self::MatrixStoreKind> {
+                ::core::result::Result::Ok(
+                    self.0
+                        .access(0, "MatrixStore", "kind")?
+                        .unwrap_or(self::MatrixStoreKind::Packed),
+                )
+            }
+
+            /// Getter for the [`row_stride` field](MatrixStore#structfield.row_stride).
+            #[inline]
+            pub fn row_stride(&self) -> ::planus::Result<i64> {
+                ::core::result::Result::Ok(
+                    self.0.access(1, "MatrixStore", "row_stride")?.unwrap_or(0),
+                )
+            }
+
+            /// Getter for the [`col_stride` field](MatrixStore#structfield.col_stride).
+            #[inline]
+            pub fn col_stride(&self) -> ::planus::Result<i64> {
+                ::core::result::Result::Ok(
+                    self.0.access(2, "MatrixStore", "col_stride")?.unwrap_or(0),
+                )
+            }
+
+            /// Getter for the [`row_offsets` field](MatrixStore#structfield.row_offsets).
+            #[inline]
+            pub fn row_offsets(
+                &self,
+            ) -> ::planus::Result<::core::option::Option<::planus::Vector<'a, i64>>> {
+                self.0.access(3, "MatrixStore", "row_offsets")
+            }
+
+            /// Getter for the [`col_offsets` field](MatrixStore#structfield.col_offsets).
+            #[inline]
+            pub fn col_offsets(
+                &self,
+            ) -> ::planus::Result<::core::option::Option<::planus::Vector<'a, i64>>> {
+                self.0.access(4, "MatrixStore", "col_offsets")
+            }
+        }
+
+        impl<'a> ::core::fmt::Debug for MatrixStoreRef<'a> {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                let mut f = f.debug_struct("MatrixStoreRef");
+                f.field("kind", &self.kind());
+                f.field("row_stride", &self.row_stride());
+                f.field("col_stride", &self.col_stride());
+                if let ::core::option::Option::Some(field_row_offsets) =
+                    self.row_offsets().transpose()
+                {
+                    f.field("row_offsets", &field_row_offsets);
+                }
+                if let ::core::option::Option::Some(field_col_offsets) =
+                    self.col_offsets().transpose()
+                {
+                    f.field("col_offsets", &field_col_offsets);
+                }
+                f.finish()
+            }
+        }
+
+        impl<'a> ::core::convert::TryFrom<MatrixStoreRef<'a>> for MatrixStore {
+            type Error = ::planus::Error;
+
+            #[allow(unreachable_code)]
+            fn try_from(value: MatrixStoreRef<'a>) -> ::planus::Result<Self> {
+                ::core::result::Result::Ok(Self {
+                    kind: ::core::convert::TryInto::try_into(value.kind()?)?,
+                    row_stride: ::core::convert::TryInto::try_into(value.row_stride()?)?,
+                    col_stride: ::core::convert::TryInto::try_into(value.col_stride()?)?,
+                    row_offsets: if let ::core::option::Option::Some(row_offsets) =
+                        value.row_offsets()?
+                    {
+                        ::core::option::Option::Some(row_offsets.to_vec()?)
+                    } else {
+                        ::core::option::Option::None
+                    },
+                    col_offsets: if let ::core::option::Option::Some(col_offsets) =
+                        value.col_offsets()?
+                    {
+                        ::core::option::Option::Some(col_offsets.to_vec()?)
+                    } else {
+                        ::core::option::Option::None
+                    },
+                })
+            }
+        }
+
+        impl<'a> ::planus::TableRead<'a> for MatrixStoreRef<'a> {
+            #[inline]
+            fn from_buffer(
+                buffer: ::planus::SliceWithStartOffset<'a>,
+                offset: usize,
+            ) -> ::core::result::Result<Self, ::planus::errors::ErrorKind> {
+                ::core::result::Result::Ok(Self(::planus::table_reader::Table::from_buffer(
+                    buffer, offset,
+                )?))
+            }
+        }
+
+        impl<'a> ::planus::VectorReadInner<'a> for MatrixStoreRef<'a> {
+            type Error = ::planus::Error;
+            const STRIDE: usize = 4;
+
+            unsafe fn from_buffer(
+                buffer: ::planus::SliceWithStartOffset<'a>,
+                offset: usize,
+            ) -> ::planus::Result<Self> {
+                ::planus::TableRead::from_buffer(buffer, offset).map_err(|error_kind| {
+                    error_kind.with_error_location(
+                        "[MatrixStoreRef]",
+                        "get",
+                        buffer.offset_from_start,
+                    )
+                })
+            }
+        }
+
+        /// # Safety
+        /// The planus compiler generates implementations that initialize
+        /// the bytes in `write_values`.
+        unsafe impl ::planus::VectorWrite<::planus::Offset<MatrixStore>> for MatrixStore {
+            type Value = ::planus::Offset<MatrixStore>;
+            const STRIDE: usize = 4;
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> Self::Value {
+                ::planus::WriteAs::prepare(self, builder)
+            }
+
+            #[inline]
+            unsafe fn write_values(
+                values: &[::planus::Offset<MatrixStore>],
+                bytes: *mut ::core::mem::MaybeUninit<u8>,
+                buffer_position: u32,
+            ) {
+                let bytes = bytes as *mut [::core::mem::MaybeUninit<u8>; 4];
+                for (i, v) in ::core::iter::Iterator::enumerate(values.iter()) {
+                    ::planus::WriteAsPrimitive::write(
+                        v,
+                        ::planus::Cursor::new(unsafe { &mut *bytes.add(i) }),
+                        buffer_position - (Self::STRIDE * i) as u32,
+                    );
+                }
+            }
+        }
+
+        impl<'a> ::planus::ReadAsRoot<'a> for MatrixStoreRef<'a> {
+            fn read_as_root(slice: &'a [u8]) -> ::planus::Result<Self> {
+                ::planus::TableRead::from_buffer(
+                    ::planus::SliceWithStartOffset { buffer: slice, offset_from_start: 0 },
+                    0,
+                )
+                .map_err(|error_kind| {
+                    error_kind.with_error_location("[MatrixStoreRef]", "read_as_root", 0)
+                })
+            }
+        }
+
+        /// The table `Mmm` in the namespace `tract`
+        ///
+        /// Generated from these locations:
+        /// * Table `Mmm` in the file `schema/tract.fbs:327`
+        #[derive(
+            Clone,
+            Debug,
+            PartialEq,
+            PartialOrd,
+            Eq,
+            Ord,
+            Hash,
+            ::serde::Serialize,
+            ::serde::Deserialize,
+        )]
+        pub struct Mmm {
+            /// The field `m` in the table `Mmm`
+            pub m: u64,
+            /// The field `k` in the table `Mmm`
+            pub k: u64,
+            /// The field `n` in the table `Mmm`
+            pub n: u64,
+            /// The field `mr` in the table `Mmm`
+            pub mr: u64,
+            /// The field `nr` in the table `Mmm`
+            pub nr: u64,
+            /// The field `a_alignment` in the table `Mmm`
+            pub a_alignment: u64,
+            /// The field `b_alignment` in the table `Mmm`
+            pub b_alignment: u64,
+            /// The field `b_storage` in the table `Mmm`
+            pub b_storage: ::planus::alloc::boxed::Box<self::MatrixStore>,
+            /// The field `c_storage` in the table `Mmm`
+            pub c_storage: ::planus::alloc::boxed::Box<self::MatrixStore>,
+        }
+
+        #[allow(clippy::derivable_impls)]
+        impl ::core::default::Default for Mmm {
+            fn default() -> Self {
+                Self {
+                    m: 0,
+                    k: 0,
+                    n: 0,
+                    mr: 0,
+                    nr: 0,
+                    a_alignment: 0,
+                    b_alignment: 0,
+                    b_storage: ::core::default::Default::default(),
+                    c_storage: ::core::default::Default::default(),
+                }
+            }
+        }
+
+        impl Mmm {
+            /// Creates a [MmmBuilder] for serializing an instance of this table.
+            #[inline]
+            pub fn builder() -> MmmBuilder<()> {
+                MmmBuilder(())
+            }
+
+            #[allow(clippy::too_many_arguments)]
+            pub fn create(
+                builder: &mut ::planus::Builder,
+                field_m: impl ::planus::WriteAsDefault<u64, u64>,
+                field_k: impl ::planus::WriteAsDefault<u64, u64>,
+                field_n: impl ::planus::WriteAsDefault<u64, u64>,
+                field_mr: impl ::planus::WriteAsDefault<u64, u64>,
+                field_nr: impl ::planus::WriteAsDefault<u64, u64>,
+                field_a_alignment: impl ::planus::WriteAsDefault<u64, u64>,
+                field_b_alignment: impl ::planus::WriteAsDefault<u64, u64>,
+                field_b_storage: impl ::planus::WriteAs<::planus::Offset<self::MatrixStore>>,
+                field_c_storage: impl ::planus::WriteAs<::planus::Offset<self::MatrixStore>>,
+            ) -> ::planus::Offset<Self> {
+                let prepared_m = field_m.prepare(builder, &0);
+                let prepared_k = field_k.prepare(builder, &0);
+                let prepared_n = field_n.prepare(builder, &0);
+                let prepared_mr = field_mr.prepare(builder, &0);
+                let prepared_nr = field_nr.prepare(builder, &0);
+                let prepared_a_alignment = field_a_alignment.prepare(builder, &0);
+                let prepared_b_alignment = field_b_alignment.prepare(builder, &0);
+                let prepared_b_storage = field_b_storage.prepare(builder);
+                let prepared_c_storage = field_c_storage.prepare(builder);
+
+                let mut table_writer: ::planus::table_writer::TableWriter<22> =
+                    ::core::default::Default::default();
+                if prepared_m.is_some() {
+                    table_writer.write_entry::<u64>(0);
+                }
+                if prepared_k.is_some() {
+                    table_writer.write_entry::<u64>(1);
+                }
+                if prepared_n.is_some() {
+                    table_writer.write_entry::<u64>(2);
+                }
+                if prepared_mr.is_some() {
+                    table_writer.write_entry::<u64>(3);
+                }
+                if prepared_nr.is_some() {
+                    table_writer.write_entry::<u64>(4);
+                }
+                if prepared_a_alignment.is_some() {
+                    table_writer.write_entry::<u64>(5);
+                }
+                if prepared_b_alignment.is_some() {
+                    table_writer.write_entry::<u64>(6);
+                }
+                table_writer.write_entry::<::planus::Offset<self::MatrixStore>>(7);
+                table_writer.write_entry::<::planus::Offset<self::MatrixStore>>(8);
+
+                unsafe {
+                    table_writer.finish(builder, |object_writer| {
+                        if let ::core::option::Option::Some(prepared_m) = prepared_m {
+                            object_writer.write::<_, _, 8>(&prepared_m);
+                        }
+                        if let ::core::option::Option::Some(prepared_k) = prepared_k {
+                            object_writer.write::<_, _, 8>(&prepared_k);
+                        }
+                        if let ::core::option::Option::Some(prepared_n) = prepared_n {
+                            object_writer.write::<_, _, 8>(&prepared_n);
+                        }
+                        if let ::core::option::Option::Some(prepared_mr) = prepared_mr {
+                            object_writer.write::<_, _, 8>(&prepared_mr);
+                        }
+                        if let ::core::option::Option::Some(prepared_nr) = prepared_nr {
+                            object_writer.write::<_, _, 8>(&prepared_nr);
+                        }
+                        if let ::core::option::Option::Some(prepared_a_alignment) =
+                            prepared_a_alignment
+                        {
+                            object_writer.write::<_, _, 8>(&prepared_a_alignment);
+                        }
+                        if let ::core::option::Option::Some(prepared_b_alignment) =
+                            prepared_b_alignment
+                        {
+                            object_writer.write::<_, _, 8>(&prepared_b_alignment);
+                        }
+                        object_writer.write::<_, _, 4>(&prepared_b_storage);
+                        object_writer.write::<_, _, 4>(&prepared_c_storage);
+                    });
+                }
+                builder.current_offset()
+            }
+        }
+
+        impl ::planus::WriteAs<::planus::Offset<Mmm>> for Mmm {
+            type Prepared = ::planus::Offset<Self>;
+
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<Mmm> {
+                ::planus::WriteAsOffset::prepare(self, builder)
+            }
+        }
+
+        impl ::planus::WriteAsOptional<::planus::Offset<Mmm>> for Mmm {
+            type Prepared = ::planus::Offset<Self>;
+
+            #[inline]
+            fn prepare(
+                &self,
+                builder: &mut ::planus::Builder,
+            ) -> ::core::option::Option<::planus::Offset<Mmm>> {
+                ::core::option::Option::Some(::planus::WriteAsOffset::prepare(self, builder))
+            }
+        }
+
+        impl ::planus::WriteAsOffset<Mmm> for Mmm {
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<Mmm> {
+                Mmm::create(
+                    builder,
+                    self.m,
+                    self.k,
+                    self.n,
+                    self.mr,
+                    self.nr,
+                    self.a_alignment,
+                    self.b_alignment,
+                    &self.b_storage,
+                    &self.c_storage,
+                )
+            }
+        }
+
+        /// Builder for serializing an instance of the [Mmm] type.
+        ///
+        /// Can be created using the [Mmm::builder] method.
+        #[derive(Debug)]
+        #[must_use]
+        pub struct MmmBuilder<State>(State);
+
+        impl MmmBuilder<()> {
+            /// Setter for the [`m` field](Mmm#structfield.m).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn m<T0>(self, value: T0) -> MmmBuilder<(T0,)>
+            where
+                T0: ::planus::WriteAsDefault<u64, u64>,
+            {
+                MmmBuilder((value,))
+            }
+
+            /// Sets the [`m` field](Mmm#structfield.m) to the default value.
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn m_as_default(self) -> MmmBuilder<(::planus::DefaultValue,)> {
+                self.m(::planus::DefaultValue)
+            }
+        }
+
+        impl<T0> MmmBuilder<(T0,)> {
+            /// Setter for the [`k` field](Mmm#structfield.k).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn k<T1>(self, value: T1) -> MmmBuilder<(T0, T1)>
+            where
+                T1: ::planus::WriteAsDefault<u64, u64>,
+            {
+                let (v0,) = self.0;
+                MmmBuilder((v0, value))
+            }
+
+            /// Sets the [`k` field](Mmm#structfield.k) to the default value.
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn k_as_default(self) -> MmmBuilder<(T0, ::planus::DefaultValue)> {
+                self.k(::planus::DefaultValue)
+            }
+        }
+
+        impl<T0, T1> MmmBuilder<(T0, T1)> {
+            /// Setter for the [`n` field](Mmm#structfield.n).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn n<T2>(self, value: T2) -> MmmBuilder<(T0, T1, T2)>
+            where
+                T2: ::planus::WriteAsDefault<u64, u64>,
+            {
+                let (v0, v1) = self.0;
+                MmmBuilder((v0, v1, value))
+            }
+
+            /// Sets the [`n` field](Mmm#structfield.n) to the default value.
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn n_as_default(self) -> MmmBuilder<(T0, T1, ::planus::DefaultValue)> {
+                self.n(::planus::DefaultValue)
+            }
+        }
+
+        impl<T0, T1, T2> MmmBuilder<(T0, T1, T2)> {
+            /// Setter for the [`mr` field](Mmm#structfield.mr).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn mr<T3>(self, value: T3) -> MmmBuilder<(T0, T1, T2, T3)>
+            where
+                T3: ::planus::WriteAsDefault<u64, u64>,
+            {
+                let (v0, v1, v2) = self.0;
+                MmmBuilder((v0, v1, v2, value))
+            }
+
+            /// Sets the [`mr` field](Mmm#structfield.mr) to the default value.
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn mr_as_default(self) -> MmmBuilder<(T0, T1, T2, ::planus::DefaultValue)> {
+                self.mr(::planus::DefaultValue)
+            }
+        }
+
+        impl<T0, T1, T2, T3> MmmBuilder<(T0, T1, T2, T3)> {
+            /// Setter for the [`nr` field](Mmm#structfield.nr).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn nr<T4>(self, value: T4) -> MmmBuilder<(T0, T1, T2, T3, T4)>
+            where
+                T4: ::planus::WriteAsDefault<u64, u64>,
+            {
+                let (v0, v1, v2, v3) = self.0;
+                MmmBuilder((v0, v1, v2, v3, value))
+            }
+
+            /// Sets the [`nr` field](Mmm#structfield.nr) to the default value.
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn nr_as_default(self) -> MmmBuilder<(T0, T1, T2, T3, ::planus::DefaultValue)> {
+                self.nr(::planus::DefaultValue)
+            }
+        }
+
+        impl<T0, T1, T2, T3, T4> MmmBuilder<(T0, T1, T2, T3, T4)> {
+            /// Setter for the [`a_alignment` field](Mmm#structfield.a_alignment).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn a_alignment<T5>(self, value: T5) -> MmmBuilder<(T0, T1, T2, T3, T4, T5)>
+            where
+                T5: ::planus::WriteAsDefault<u64, u64>,
+            {
+                let (v0, v1, v2, v3, v4) = self.0;
+                MmmBuilder((v0, v1, v2, v3, v4, value))
+            }
+
+            /// Sets the [`a_alignment` field](Mmm#structfield.a_alignment) to the default value.
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn a_alignment_as_default(
+                self,
+            ) -> MmmBuilder<(T0, T1, T2, T3, T4, ::planus::DefaultValue)> {
+                self.a_alignment(::planus::DefaultValue)
+            }
+        }
+
+        impl<T0, T1, T2, T3, T4, T5> MmmBuilder<(T0, T1, T2, T3, T4, T5)> {
+            /// Setter for the [`b_alignment` field](Mmm#structfield.b_alignment).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn b_alignment<T6>(self, value: T6) -> MmmBuilder<(T0, T1, T2, T3, T4, T5, T6)>
+            where
+                T6: ::planus::WriteAsDefault<u64, u64>,
+            {
+                let (v0, v1, v2, v3, v4, v5) = self.0;
+                MmmBuilder((v0, v1, v2, v3, v4, v5, value))
+            }
+
+            /// Sets the [`b_alignment` field](Mmm#structfield.b_alignment) to the default value.
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn b_alignment_as_default(
+                self,
+            ) -> MmmBuilder<(T0, T1, T2, T3, T4, T5, ::planus::DefaultValue)> {
+                self.b_alignment(::planus::DefaultValue)
+            }
+        }
+
+        impl<T0, T1, T2, T3, T4, T5, T6> MmmBuilder<(T0, T1, T2, T3, T4, T5, T6)> {
+            /// Setter for the [`b_storage` field](Mmm#structfield.b_storage).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn b_storage<T7>(self, value: T7) -> MmmBuilder<(T0, T1, T2, T3, T4, T5, T6, T7)>
+            where
+                T7: ::planus::WriteAs<::planus::Offset<self::MatrixStore>>,
+            {
+                let (v0, v1, v2, v3, v4, v5, v6) = self.0;
+                MmmBuilder((v0, v1, v2, v3, v4, v5, v6, value))
+            }
+        }
+
+        impl<T0, T1, T2, T3, T4, T5, T6, T7> MmmBuilder<(T0, T1, T2, T3, T4, T5, T6, T7)> {
+            /// Setter for the [`c_storage` field](Mmm#structfield.c_storage).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn c_storage<T8>(
+                self,
+                value: T8,
+            ) -> MmmBuilder<(T0, T1, T2, T3, T4, T5, T6, T7, T8)>
+            where
+                T8: ::planus::WriteAs<::planus::Offset<self::MatrixStore>>,
+            {
+                let (v0, v1, v2, v3, v4, v5, v6, v7) = self.0;
+                MmmBuilder((v0, v1, v2, v3, v4, v5, v6, v7, value))
+            }
+        }
+
+        impl<T0, T1, T2, T3, T4, T5, T6, T7, T8> MmmBuilder<(T0, T1, T2, T3, T4, T5, T6, T7, T8)> {
+            /// Finish writing the builder to get an [Offset](::planus::Offset) to a serialized [Mmm].
+            #[inline]
+            pub fn finish(self, builder: &mut ::planus::Builder) -> ::planus::Offset<Mmm>
+            where
+                Self: ::planus::WriteAsOffset<Mmm>,
+            {
+                ::planus::WriteAsOffset::prepare(&self, builder)
+            }
+        }
+
+        impl<
+                T0: ::planus::WriteAsDefault<u64, u64>,
+                T1: ::planus::WriteAsDefault<u64, u64>,
+                T2: ::planus::WriteAsDefault<u64, u64>,
+                T3: ::planus::WriteAsDefault<u64, u64>,
+                T4: ::planus::WriteAsDefault<u64, u64>,
+                T5: ::planus::WriteAsDefault<u64, u64>,
+                T6: ::planus::WriteAsDefault<u64, u64>,
+                T7: ::planus::WriteAs<::planus::Offset<self::MatrixStore>>,
+                T8: ::planus::WriteAs<::planus::Offset<self::MatrixStore>>,
+            > ::planus::WriteAs<::planus::Offset<Mmm>>
+            for MmmBuilder<(T0, T1, T2, T3, T4, T5, T6, T7, T8)>
+        {
+            type Prepared = ::planus::Offset<Mmm>;
+
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<Mmm> {
+                ::planus::WriteAsOffset::prepare(self, builder)
+            }
+        }
+
+        impl<
+                T0: ::planus::WriteAsDefault<u64, u64>,
+                T1: ::planus::WriteAsDefault<u64, u64>,
+                T2: ::planus::WriteAsDefault<u64, u64>,
+                T3: ::planus::WriteAsDefault<u64, u64>,
+                T4: ::planus::WriteAsDefault<u64, u64>,
+                T5: ::planus::WriteAsDefault<u64, u64>,
+                T6: ::planus::WriteAsDefault<u64, u64>,
+                T7: ::planus::WriteAs<::planus::Offset<self::MatrixStore>>,
+                T8: ::planus::WriteAs<::planus::Offset<self::MatrixStore>>,
+            > ::planus::WriteAsOptional<::planus::Offset<Mmm>>
+            for MmmBuilder<(T0, T1, T2, T3, T4, T5, T6, T7, T8)>
+        {
+            type Prepared = ::planus::Offset<Mmm>;
+
+            #[inline]
+            fn prepare(
+                &self,
+                builder: &mut ::planus::Builder,
+            ) -> ::core::option::Option<::planus::Offset<Mmm>> {
+                ::core::option::Option::Some(::planus::WriteAsOffset::prepare(self, builder))
+            }
+        }
+
+        impl<
+                T0: ::planus::WriteAsDefault<u64, u64>,
+                T1: ::planus::WriteAsDefault<u64, u64>,
+                T2: ::planus::WriteAsDefault<u64, u64>,
+                T3: ::planus::WriteAsDefault<u64, u64>,
+                T4: ::planus::WriteAsDefault<u64, u64>,
+                T5: ::planus::WriteAsDefault<u64, u64>,
+                T6: ::planus::WriteAsDefault<u64, u64>,
+                T7: ::planus::WriteAs<::planus::Offset<self::MatrixStore>>,
+                T8: ::planus::WriteAs<::planus::Offset<self::MatrixStore>>,
+            > ::planus::WriteAsOffset<Mmm> for MmmBuilder<(T0, T1, T2, T3, T4, T5, T6, T7, T8)>
+        {
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<Mmm> {
+                let (v0, v1, v2, v3, v4, v5, v6, v7, v8) = &self.0;
+                Mmm::create(builder, v0, v1, v2, v3, v4, v5, v6, v7, v8)
+            }
+        }
+
+        /// Reference to a deserialized [Mmm].
+        #[derive(Copy, Clone)]
+        pub struct MmmRef<'a>(#[allow(dead_code)] ::planus::table_reader::Table<'a>);
+
+        impl<'a> MmmRef<'a> {
+            /// Getter for the [`m` field](Mmm#structfield.m).
+            #[inline]
+            pub fn m(&self) -> ::planus::Result<u64> {
+                ::core::result::Result::Ok(self.0.access(0, "Mmm", "m")?.unwrap_or(0))
+            }
+
+            /// Getter for the [`k` field](Mmm#structfield.k).
+            #[inline]
+            pub fn k(&self) -> ::planus::Result<u64> {
+                ::core::result::Result::Ok(self.0.access(1, "Mmm", "k")?.unwrap_or(0))
+            }
+
+            /// Getter for the [`n` field](Mmm#structfield.n).
+            #[inline]
+            pub fn n(&self) -> ::planus::Result<u64> {
+                ::core::result::Result::Ok(self.0.access(2, "Mmm", "n")?.unwrap_or(0))
+            }
+
+            /// Getter for the [`mr` field](Mmm#structfield.mr).
+            #[inline]
+            pub fn mr(&self) -> ::planus::Result<u64> {
+                ::core::result::Result::Ok(self.0.access(3, "Mmm", "mr")?.unwrap_or(0))
+            }
+
+            /// Getter for the [`nr` field](Mmm#structfield.nr).
+            #[inline]
+            pub fn nr(&self) -> ::planus::Result<u64> {
+                ::core::result::Result::Ok(self.0.access(4, "Mmm", "nr")?.unwrap_or(0))
+            }
+
+            /// Getter for the [`a_alignment` field](Mmm#structfield.a_alignment).
+            #[inline]
+            pub fn a_alignment(&self) -> ::planus::Result<u64> {
+                ::core::result::Result::Ok(self.0.access(5, "Mmm", "a_alignment")?.unwrap_or(0))
+            }
+
+            /// Getter for the [`b_alignment` field](Mmm#structfield.b_alignment).
+            #[inline]
+            pub fn b_alignment(&self) -> ::planus::Result<u64> {
+                ::core::result::Result::Ok(self.0.access(6, "Mmm", "b_alignment")?.unwrap_or(0))
+            }
+
+            /// Getter for the [`b_storage` field](Mmm#structfield.b_storage).
+            #[inline]
+            pub fn b_storage(&self) -> ::planus::Result<self::MatrixStoreRef<'a>> {
+                self.0.access_required(7, "Mmm", "b_storage")
+            }
+
+            /// Getter for the [`c_storage` field](Mmm#structfield.c_storage).
+            #[inline]
+            pub fn c_storage(&self) -> ::planus::Result<self::MatrixStoreRef<'a>> {
+                self.0.access_required(8, "Mmm", "c_storage")
+            }
+        }
+
+        impl<'a> ::core::fmt::Debug for MmmRef<'a> {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                let mut f = f.debug_struct("MmmRef");
+                f.field("m", &self.m());
+                f.field("k", &self.k());
+                f.field("n", &self.n());
+                f.field("mr", &self.mr());
+                f.field("nr", &self.nr());
+                f.field("a_alignment", &self.a_alignment());
+                f.field("b_alignment", &self.b_alignment());
+                f.field("b_storage", &self.b_storage());
+                f.field("c_storage", &self.c_storage());
+                f.finish()
+            }
+        }
+
+        impl<'a> ::core::convert::TryFrom<MmmRef<'a>> for Mmm {
+            type Error = ::planus::Error;
+
+            #[allow(unreachable_code)]
+            fn try_from(value: MmmRef<'a>) -> ::planus::Result<Self> {
+                ::core::result::Result::Ok(Self {
+                    m: ::core::convert::TryInto::try_into(value.m()?)?,
+                    k: ::core::convert::TryInto::try_into(value.k()?)?,
+                    n: ::core::convert::TryInto::try_into(value.n()?)?,
+                    mr: ::core::convert::TryInto::try_into(value.mr()?)?,
+                    nr: ::core::convert::TryInto::try_into(value.nr()?)?,
+                    a_alignment: ::core::convert::TryInto::try_into(value.a_alignment()?)?,
+                    b_alignment: ::core::convert::TryInto::try_into(value.b_alignment()?)?,
+                    b_storage: ::planus::alloc::boxed::Box::new(
+                        ::core::convert::TryInto::try_into(value.b_storage()?)?,
+                    ),
+                    c_storage: ::planus::alloc::boxed::Box::new(
+                        ::core::convert::TryInto::try_into(value.c_storage()?)?,
+                    ),
+                })
+            }
+        }
+
+        impl<'a> ::planus::TableRead<'a> for MmmRef<'a> {
+            #[inline]
+            fn from_buffer(
+                buffer: ::planus::SliceWithStartOffset<'a>,
+                offset: usize,
+            ) -> ::core::result::Result<Self, ::planus::errors::ErrorKind> {
+                ::core::result::Result::Ok(Self(::planus::table_reader::Table::from_buffer(
+                    buffer, offset,
+                )?))
+            }
+        }
+
+        impl<'a> ::planus::VectorReadInner<'a> for MmmRef<'a> {
+            type Error = ::planus::Error;
+            const STRIDE: usize = 4;
+
+            unsafe fn from_buffer(
+                buffer: ::planus::SliceWithStartOffset<'a>,
+                offset: usize,
+            ) -> ::planus::Result<Self> {
+                ::planus::TableRead::from_buffer(buffer, offset).map_err(|error_kind| {
+                    error_kind.with_error_location("[MmmRef]", "get", buffer.offset_from_start)
+                })
+            }
+        }
+
+        /// # Safety
+        /// The planus compiler generates implementations that initialize
+        /// the bytes in `write_values`.
+        unsafe impl ::planus::VectorWrite<::planus::Offset<Mmm>> for Mmm {
+            type Value = ::planus::Offset<Mmm>;
+            const STRIDE: usize = 4;
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> Self::Value {
+                ::planus::WriteAs::prepare(self, builder)
+            }
+
+            #[inline]
+            unsafe fn write_values(
+                values: &[::planus::Offset<Mmm>],
+                bytes: *mut ::core::mem::MaybeUninit<u8>,
+                buffer_position: u32,
+            ) {
+                let bytes = bytes as *mut [::core::mem::MaybeUninit<u8>; 4];
+                for (i, v) in ::core::iter::Iterator::enumerate(values.iter()) {
+                    ::planus::WriteAsPrimitive::write(
+                        v,
+                        ::planus::Cursor::new(unsafe { &mut *bytes.add(i) }),
+                        buffer_position - (Self::STRIDE * i) as u32,
+                    );
+                }
+            }
+        }
+
+        impl<'a> ::planus::ReadAsRoot<'a> for MmmRef<'a> {
+            fn read_as_root(slice: &'a [u8]) -> ::planus::Result<Self> {
+                ::planus::TableRead::from_buffer(
+                    ::planus::SliceWithStartOffset { buffer: slice, offset_from_start: 0 },
+                    0,
+                )
+                .map_err(|error_kind| error_kind.with_error_location("[MmmRef]", "read_as_root", 0))
+            }
+        }
+
+        /// The enum `FusedKind` in the namespace `tract`
+        ///
+        /// Generated from these locations:
+        /// * Enum `FusedKind` in the file `schema/tract.fbs:339`
+        #[derive(
+            Copy,
+            Clone,
+            Debug,
+            PartialEq,
+            Eq,
+            PartialOrd,
+            Ord,
+            Hash,
+            ::serde::Serialize,
+            ::serde::Deserialize,
+        )]
+        #[repr(u8)]
+        pub enum FusedKind {
+            /// The variant `Min` in the enum `FusedKind`
+            Min = 0,
+
+            /// The variant `Max` in the enum `FusedKind`
+            Max = 1,
+
+            /// The variant `AddC` in the enum `FusedKind`
+            AddC = 2,
+
+            /// The variant `PerRowMul` in the enum `FusedKind`
+            PerRowMul = 3,
+
+            /// The variant `PerRowAdd` in the enum `FusedKind`
+            PerRowAdd = 4,
+
+            /// The variant `PerColMul` in the enum `FusedKind`
+            PerColMul = 5,
+
+            /// The variant `PerColAdd` in the enum `FusedKind`
+            PerColAdd = 6,
+
+            /// The variant `AddRowColProducts` in the enum `FusedKind`
+            AddRowColProducts = 7,
+
+            /// The variant `ScalarMul` in the enum `FusedKind`
+            ScalarMul = 8,
+
+            /// The variant `ScalarAdd` in the enum `FusedKind`
+            ScalarAdd = 9,
+
+            /// The variant `QTowardsEven` in the enum `FusedKind`
+            QTowardsEven = 10,
+
+            /// The variant `QTowardsPlusInf` in the enum `FusedKind`
+            QTowardsPlusInf = 11,
+        }
+
+        impl FusedKind {
+            /// Array containing all valid variants of FusedKind
+            pub const ENUM_VALUES: [Self; 12] = [
+                Self::Min,
+                Self::Max,
+                Self::AddC,
+                Self::PerRowMul,
+                Self::PerRowAdd,
+                Self::PerColMul,
+                Self::PerColAdd,
+                Self::AddRowColProducts,
+                Self::ScalarMul,
+                Self::ScalarAdd,
+                Self::QTowardsEven,
+                Self::QTowardsPlusInf,
+            ];
+        }
+
+        impl ::core::convert::TryFrom<u8> for FusedKind {
+            type Error = ::planus::errors::UnknownEnumTagKind;
+            #[inline]
+            fn try_from(
+                value: u8,
+            ) -> ::core::result::Result<Self, ::planus::errors::UnknownEnumTagKind> {
+                #[allow(clippy::match_single_binding)]
+                match value {
+                    0 => ::core::result::Result::Ok(FusedKind::Min),
+                    1 => ::core::result::Result::Ok(FusedKind::Max),
+                    2 => ::core::result::Result::Ok(FusedKind::AddC),
+                    3 => ::core::result::Result::Ok(FusedKind::PerRowMul),
+                    4 => ::core::result::Result::Ok(FusedKind::PerRowAdd),
+                    5 => ::core::result::Result::Ok(FusedKind::PerColMul),
+                    6 => ::core::result::Result::Ok(FusedKind::PerColAdd),
+                    7 => ::core::result::Result::Ok(FusedKind::AddRowColProducts),
+                    8 => ::core::result::Result::Ok(FusedKind::ScalarMul),
+                    9 => ::core::result::Result::Ok(FusedKind::ScalarAdd),
+                    10 => ::core::result::Result::Ok(FusedKind::QTowardsEven),
+                    11 => ::core::result::Result::Ok(FusedKind::QTowardsPlusInf),
+
+                    _ => ::core::result::Result::Err(::planus::errors::UnknownEnumTagKind {
+                        tag: value as i128,
+                    }),
+                }
+            }
+        }
+
+        impl ::core::convert::From<FusedKind> for u8 {
+            #[inline]
+            fn from(value: FusedKind) -> Self {
+                value as u8
+            }
+        }
+
+        /// # Safety
+        /// The Planus compiler correctly calculates `ALIGNMENT` and `SIZE`.
+        unsafe impl ::planus::Primitive for FusedKind {
+            const ALIGNMENT: usize = 1;
+            const SIZE: usize = 1;
+        }
+
+        impl ::planus::WriteAsPrimitive<FusedKind> for FusedKind {
+            #[inline]
+            fn write<const N: usize>(&self, cursor: ::planus::Cursor<'_, N>, buffer_position: u32) {
+                (*self as u8).write(cursor, buffer_position);
+            }
+        }
+
+        impl ::planus::WriteAs<FusedKind> for FusedKind {
+            type Prepared = Self;
+
+            #[inline]
+            fn prepare(&self, _builder: &mut ::planus::Builder) -> FusedKind {
+                *self
+            }
+        }
+
+        impl ::planus::WriteAsDefault<FusedKind, FusedKind> for FusedKind {
+            type Prepared = Self;
+
+            #[inline]
+            fn prepare(
+                &self,
+                _builder: &mut ::planus::Builder,
+                default: &FusedKind,
+            ) -> ::core::option::Option<FusedKind> {
+                if self == default {
+                    ::core::option::Option::None
+                } else {
+                    ::core::option::Option::Some(*self)
+                }
+            }
+        }
+
+        impl ::planus::WriteAsOptional<FusedKind> for FusedKind {
+            type Prepared = Self;
+
+            #[inline]
+            fn prepare(
+                &self,
+                _builder: &mut ::planus::Builder,
+            ) -> ::core::option::Option<FusedKind> {
+                ::core::option::Option::Some(*self)
+            }
+        }
+
+        impl<'buf> ::planus::TableRead<'buf> for FusedKind {
+            #[inline]
+            fn from_buffer(
+                buffer: ::planus::SliceWithStartOffset<'buf>,
+                offset: usize,
+            ) -> ::core::result::Result<Self, ::planus::errors::ErrorKind> {
+                let n: u8 = ::planus::TableRead::from_buffer(buffer, offset)?;
+                ::core::result::Result::Ok(::core::convert::TryInto::try_into(n)?)
+            }
+        }
+
+        impl<'buf> ::planus::VectorReadInner<'buf> for FusedKind {
+            type Error = ::planus::errors::UnknownEnumTag;
+            const STRIDE: usize = 1;
+            #[inline]
+            unsafe fn from_buffer(
+                buffer: ::planus::SliceWithStartOffset<'buf>,
+                offset: usize,
+            ) -> ::core::result::Result<Self, ::planus::errors::UnknownEnumTag> {
+                let value = unsafe { *buffer.buffer.get_unchecked(offset) };
+                let value: ::core::result::Result<Self, _> =
+                    ::core::convert::TryInto::try_into(value);
+                value.map_err(|error_kind| {
+                    error_kind.with_error_location(
+                        "FusedKind",
+                        "VectorRead::from_buffer",
+                        buffer.offset_from_start,
+                    )
+                })
+            }
+        }
+
+        /// # Safety
+        /// The planus compiler generates implementations that initialize
+        /// the bytes in `write_values`.
+        unsafe impl ::planus::VectorWrite<FusedKind> for FusedKind {
+            const STRIDE: usize = 1;
+
+            type Value = Self;
+
+            #[inline]
+            fn prepare(&self, _builder: &mut ::planus::Builder) -> Self {
+                *self
+            }
+
+            #[inline]
+            unsafe fn write_values(
+                values: &[Self],
+                bytes: *mut ::core::mem::MaybeUninit<u8>,
+                buffer_position: u32,
+            ) {
+                let bytes = bytes as *mut [::core::mem::MaybeUninit<u8>; 1];
+                for (i, v) in ::core::iter::Iterator::enumerate(values.iter()) {
+                    ::planus::WriteAsPrimitive::write(
+                        v,
+                        ::planus::Cursor::new(unsafe { &mut *bytes.add(i) }),
+                        buffer_position - i as u32,
+                    );
+                }
+            }
+        }
+
+        /// The table `Fused` in the namespace `tract`
+        ///
+        /// Generated from these locations:
+        /// * Table `Fused` in the file `schema/tract.fbs:354`
+        #[derive(Clone, Debug, PartialEq, PartialOrd, ::serde::Serialize, ::serde::Deserialize)]
+        pub struct Fused {
+            /// The field `kind` in the table `Fused`
+            pub kind: self::FusedKind,
+            /// The field `scalar` in the table `Fused`
+            pub scalar: f32,
+            /// The field `rows` in the table `Fused`
+            pub rows: ::core::option::Option<::planus::alloc::vec::Vec<f32>>,
+            /// The field `cols` in the table `Fused`
+            pub cols: ::core::option::Option<::planus::alloc::vec::Vec<f32>>,
+            /// The field `shift` in the table `Fused`
+            pub shift: u64,
+        }
+
+        #[allow(clippy::derivable_impls)]
+        impl ::core::default::Default for Fused {
+            fn default() -> Self {
+                Self {
+                    kind: self::FusedKind::Min,
+                    scalar: 0.0,
+                    rows: ::core::default::Default::default(),
+                    cols: ::core::default::Default::default(),
+                    shift: 0,
+                }
+            }
+        }
+
+        impl Fused {
+            /// Creates a [FusedBuilder] for serializing an instance of this table.
+            #[inline]
+            pub fn builder() -> FusedBuilder<()> {
+                FusedBuilder(())
+            }
+
+            #[allow(clippy::too_many_arguments)]
+            pub fn create(
+                builder: &mut ::planus::Builder,
+                field_kind: impl ::planus::WriteAsDefault<self::FusedKind, self::FusedKind>,
+                field_scalar: impl ::planus::WriteAsDefault<f32, f32>,
+                field_rows: impl ::planus::WriteAsOptional<::planus::Offset<[f32]>>,
+                field_cols: impl ::planus::WriteAsOptional<::planus::Offset<[f32]>>,
+                field_shift: impl ::planus::WriteAsDefault<u64, u64>,
+            ) -> ::planus::Offset<Self> {
+                let prepared_kind = field_kind.prepare(builder, &self::FusedKind::Min);
+                let prepared_scalar = field_scalar.prepare(builder, &0.0);
+                let prepared_rows = field_rows.prepare(builder);
+                let prepared_cols = field_cols.prepare(builder);
+                let prepared_shift = field_shift.prepare(builder, &0);
+
+                let mut table_writer: ::planus::table_writer::TableWriter<14> =
+                    ::core::default::Default::default();
+                if prepared_shift.is_some() {
+                    table_writer.write_entry::<u64>(4);
+                }
+                if prepared_scalar.is_some() {
+                    table_writer.write_entry::<f32>(1);
+                }
+                if prepared_rows.is_some() {
+                    table_writer.write_entry::<::planus::Offset<[f32]>>(2);
+                }
+                if prepared_cols.is_some() {
+                    table_writer.write_entry::<::planus::Offset<[f32]>>(3);
+                }
+                if prepared_kind.is_some() {
+                    table_writer.write_entry::<self::FusedKind>(0);
+                }
+
+                unsafe {
+                    table_writer.finish(builder, |object_writer| {
+                        if let ::core::option::Option::Some(prepared_shift) = prepared_shift {
+                            object_writer.write::<_, _, 8>(&prepared_shift);
+                        }
+                        if let ::core::option::Option::Some(prepared_scalar) = prepared_scalar {
+                            object_writer.write::<_, _, 4>(&prepared_scalar);
+                        }
+                        if let ::core::option::Option::Some(prepared_rows) = prepared_rows {
+                            object_writer.write::<_, _, 4>(&prepared_rows);
+                        }
+                        if let ::core::option::Option::Some(prepared_cols) = prepared_cols {
+                            object_writer.write::<_, _, 4>(&prepared_cols);
+                        }
+                        if let ::core::option::Option::Some(prepared_kind) = prepared_kind {
+                            object_writer.write::<_, _, 1>(&prepared_kind);
+                        }
+                    });
+                }
+                builder.current_offset()
+            }
+        }
+
+        impl ::planus::WriteAs<::planus::Offset<Fused>> for Fused {
+            type Prepared = ::planus::Offset<Self>;
+
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<Fused> {
+                ::planus::WriteAsOffset::prepare(self, builder)
+            }
+        }
+
+        impl ::planus::WriteAsOptional<::planus::Offset<Fused>> for Fused {
+            type Prepared = ::planus::Offset<Self>;
+
+            #[inline]
+            fn prepare(
+                &self,
+                builder: &mut ::planus::Builder,
+            ) -> ::core::option::Option<::planus::Offset<Fused>> {
+                ::core::option::Option::Some(::planus::WriteAsOffset::prepare(self, builder))
+            }
+        }
+
+        impl ::planus::WriteAsOffset<Fused> for Fused {
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<Fused> {
+                Fused::create(builder, self.kind, self.scalar, &self.rows, &self.cols, self.shift)
+            }
+        }
+
+        /// Builder for serializing an instance of the [Fused] type.
+        ///
+        /// Can be created using the [Fused::builder] method.
+        #[derive(Debug)]
+        #[must_use]
+        pub struct FusedBuilder<State>(State);
+
+        impl FusedBuilder<()> {
+            /// Setter for the [`kind` field](Fused#structfield.kind).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn kind<T0>(self, value: T0) -> FusedBuilder<(T0,)>
+            where
+                T0: ::planus::WriteAsDefault<self::FusedKind, self::FusedKind>,
+            {
+                FusedBuilder((value,))
+            }
+
+            /// Sets the [`kind` field](Fused#structfield.kind) to the default value.
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn kind_as_default(self) -> FusedBuilder<(::planus::DefaultValue,)> {
+                self.kind(::planus::DefaultValue)
+            }
+        }
+
+        impl<T0> FusedBuilder<(T0,)> {
+            /// Setter for the [`scalar` field](Fused#structfield.scalar).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn scalar<T1>(self, value: T1) -> FusedBuilder<(T0, T1)>
+            where
+                T1: ::planus::WriteAsDefault<f32, f32>,
+            {
+                let (v0,) = self.0;
+                FusedBuilder((v0, value))
+            }
+
+            /// Sets the [`scalar` field](Fused#structfield.scalar) to the default value.
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn scalar_as_default(self) -> FusedBuilder<(T0, ::planus::DefaultValue)> {
+                self.scalar(::planus::DefaultValue)
+            }
+        }
+
+        impl<T0, T1> FusedBuilder<(T0, T1)> {
+            /// Setter for the [`rows` field](Fused#structfield.rows).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn rows<T2>(self, value: T2) -> FusedBuilder<(T0, T1, T2)>
+            where
+                T2: ::planus::WriteAsOptional<::planus::Offset<[f32]>>,
+            {
+                let (v0, v1) = self.0;
+                FusedBuilder((v0, v1, value))
+            }
+
+            /// Sets the [`rows` field](Fused#structfield.rows) to null.
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn rows_as_null(self) -> FusedBuilder<(T0, T1, ())> {
+                self.rows(())
+            }
+        }
+
+        impl<T0, T1, T2> FusedBuilder<(T0, T1, T2)> {
+            /// Setter for the [`cols` field](Fused#structfield.cols).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn cols<T3>(self, value: T3) -> FusedBuilder<(T0, T1, T2, T3)>
+            where
+                T3: ::planus::WriteAsOptional<::planus::Offset<[f32]>>,
+            {
+                let (v0, v1, v2) = self.0;
+                FusedBuilder((v0, v1, v2, value))
+            }
+
+            /// Sets the [`cols` field](Fused#structfield.cols) to null.
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn cols_as_null(self) -> FusedBuilder<(T0, T1, T2, ())> {
+                self.cols(())
+            }
+        }
+
+        impl<T0, T1, T2, T3> FusedBuilder<(T0, T1, T2, T3)> {
+            /// Setter for the [`shift` field](Fused#structfield.shift).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn shift<T4>(self, value: T4) -> FusedBuilder<(T0, T1, T2, T3, T4)>
+            where
+                T4: ::planus::WriteAsDefault<u64, u64>,
+            {
+                let (v0, v1, v2, v3) = self.0;
+                FusedBuilder((v0, v1, v2, v3, value))
+            }
+
+            /// Sets the [`shift` field](Fused#structfield.shift) to the default value.
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn shift_as_default(
+                self,
+            ) -> FusedBuilder<(T0, T1, T2, T3, ::planus::DefaultValue)> {
+                self.shift(::planus::DefaultValue)
+            }
+        }
+
+        impl<T0, T1, T2, T3, T4> FusedBuilder<(T0, T1, T2, T3, T4)> {
+            /// Finish writing the builder to get an [Offset](::planus::Offset) to a serialized [Fused].
+            #[inline]
+            pub fn finish(self, builder: &mut ::planus::Builder) -> ::planus::Offset<Fused>
+            where
+                Self: ::planus::WriteAsOffset<Fused>,
+            {
+                ::planus::WriteAsOffset::prepare(&self, builder)
+            }
+        }
+
+        impl<
+                T0: ::planus::WriteAsDefault<self::FusedKind, self::FusedKind>,
+                T1: ::planus::WriteAsDefault<f32, f32>,
+                T2: ::planus::WriteAsOptional<::planus::Offset<[f32]>>,
+                T3: ::planus::WriteAsOptional<::planus::Offset<[f32]>>,
+                T4: ::planus::WriteAsDefault<u64, u64>,
+            > ::planus::WriteAs<::planus::Offset<Fused>> for FusedBuilder<(T0, T1, T2, T3, T4)>
+        {
+            type Prepared = ::planus::Offset<Fused>;
+
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<Fused> {
+                ::planus::WriteAsOffset::prepare(self, builder)
+            }
+        }
+
+        impl<
+                T0: ::planus::WriteAsDefault<self::FusedKind, self::FusedKind>,
+                T1: ::planus::WriteAsDefault<f32, f32>,
+                T2: ::planus::WriteAsOptional<::planus::Offset<[f32]>>,
+                T3: ::planus::WriteAsOptional<::planus::Offset<[f32]>>,
+                T4: ::planus::WriteAsDefault<u64, u64>,
+            > ::planus::WriteAsOptional<::planus::Offset<Fused>>
+            for FusedBuilder<(T0, T1, T2, T3, T4)>
+        {
+            type Prepared = ::planus::Offset<Fused>;
+
+            #[inline]
+            fn prepare(
+                &self,
+                builder: &mut ::planus::Builder,
+            ) -> ::core::option::Option<::planus::Offset<Fused>> {
+                ::core::option::Option::Some(::planus::WriteAsOffset::prepare(self, builder))
+            }
+        }
+
+        impl<
+                T0: ::planus::WriteAsDefault<self::FusedKind, self::FusedKind>,
+                T1: ::planus::WriteAsDefault<f32, f32>,
+                T2: ::planus::WriteAsOptional<::planus::Offset<[f32]>>,
+                T3: ::planus::WriteAsOptional<::planus::Offset<[f32]>>,
+                T4: ::planus::WriteAsDefault<u64, u64>,
+            > ::planus::WriteAsOffset<Fused> for FusedBuilder<(T0, T1, T2, T3, T4)>
+        {
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<Fused> {
+                let (v0, v1, v2, v3, v4) = &self.0;
+                Fused::create(builder, v0, v1, v2, v3, v4)
+            }
+        }
+
+        /// Reference to a deserialized [Fused].
+        #[derive(Copy, Clone)]
+        pub struct FusedRef<'a>(#[allow(dead_code)] ::planus::table_reader::Table<'a>);
+
+        impl<'a> FusedRef<'a> {
+            /// Getter for the [`kind` field](Fused#structfield.kind).
+            #[inline]
+            pub fn kind(&self) -> ::planus::Result<self::FusedKind> {
+                ::core::result::Result::Ok(
+                    self.0.access(0, "Fused", "kind")?.unwrap_or(self::FusedKind::Min),
+                )
+            }
+
+            /// Getter for the [`scalar` field](Fused#structfield.scalar).
+            #[inline]
+            pub fn scalar(&self) -> ::planus::Result<f32> {
+                ::core::result::Result::Ok(self.0.access(1, "Fused", "scalar")?.unwrap_or(0.0))
+            }
+
+            /// Getter for the [`rows` field](Fused#structfield.rows).
+            #[inline]
+            pub fn rows(
+                &self,
+            ) -> ::planus::Result<::core::option::Option<::planus::Vector<'a, f32>>> {
+                self.0.access(2, "Fused", "rows")
+            }
+
+            /// Getter for the [`cols` field](Fused#structfield.cols).
+            #[inline]
+            pub fn cols(
+                &self,
+            ) -> ::planus::Result<::core::option::Option<::planus::Vector<'a, f32>>> {
+                self.0.access(3, "Fused", "cols")
+            }
+
+            /// Getter for the [`shift` field](Fused#structfield.shift).
+            #[inline]
+            pub fn shift(&self) -> ::planus::Result<u64> {
+                ::core::result::Result::Ok(self.0.access(4, "Fused", "shift")?.unwrap_or(0))
+            }
+        }
+
+        impl<'a> ::core::fmt::Debug for FusedRef<'a> {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                let mut f = f.debug_struct("FusedRef");
+                f.field("kind", &self.kind());
+                f.field("scalar", &self.scalar());
+                if let ::core::option::Option::Some(field_rows) = self.rows().transpose() {
+                    f.field("rows", &field_rows);
+                }
+                if let ::core::option::Option::Some(field_cols) = self.cols().transpose() {
+                    f.field("cols", &field_cols);
+                }
+                f.field("shift", &self.shift());
+                f.finish()
+            }
+        }
+
+        impl<'a> ::core::convert::TryFrom<FusedRef<'a>> for Fused {
+            type Error = ::planus::Error;
+
+            #[allow(unreachable_code)]
+            fn try_from(value: FusedRef<'a>) -> ::planus::Result<Self> {
+                ::core::result::Result::Ok(Self {
+                    kind: ::core::convert::TryInto::try_into(value.kind()?)?,
+                    scalar: ::core::convert::TryInto::try_into(value.scalar()?)?,
+                    rows: if let ::core::option::Option::Some(rows) = value.rows()? {
+                        ::core::option::Option::Some(rows.to_vec()?)
+                    } else {
+                        ::core::option::Option::None
+                    },
+                    cols: if let ::core::option::Option::Some(cols) = value.cols()? {
+                        ::core::option::Option::Some(cols.to_vec()?)
+                    } else {
+                        ::core::option::Option::None
+                    },
+                    shift: ::core::convert::TryInto::try_into(value.shift()?)?,
+                })
+            }
+        }
+
+        impl<'a> ::planus::TableRead<'a> for FusedRef<'a> {
+            #[inline]
+            fn from_buffer(
+                buffer: ::planus::SliceWithStartOffset<'a>,
+                offset: usize,
+            ) -> ::core::result::Result<Self, ::planus::errors::ErrorKind> {
+                ::core::result::Result::Ok(Self(::planus::table_reader::Table::from_buffer(
+                    buffer, offset,
+                )?))
+            }
+        }
+
+        impl<'a> ::planus::VectorReadInner<'a> for FusedRef<'a> {
+            type Error = ::planus::Error;
+            const STRIDE: usize = 4;
+
+            unsafe fn from_buffer(
+                buffer: ::planus::SliceWithStartOffset<'a>,
+                offset: usize,
+            ) -> ::planus::Result<Self> {
+                ::planus::TableRead::from_buffer(buffer, offset).map_err(|error_kind| {
+                    error_kind.with_error_location("[FusedRef]", "get", buffer.offset_from_start)
+                })
+            }
+        }
+
+        /// # Safety
+        /// The planus compiler generates implementations that initialize
+        /// the bytes in `write_values`.
+        unsafe impl ::planus::VectorWrite<::planus::Offset<Fused>> for Fused {
+            type Value = ::planus::Offset<Fused>;
+            const STRIDE: usize = 4;
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> Self::Value {
+                ::planus::WriteAs::prepare(self, builder)
+            }
+
+            #[inline]
+            unsafe fn write_values(
+                values: &[::planus::Offset<Fused>],
+                bytes: *mut ::core::mem::MaybeUninit<u8>,
+                buffer_position: u32,
+            ) {
+                let bytes = bytes as *mut [::core::mem::MaybeUninit<u8>; 4];
+                for (i, v) in ::core::iter::Iterator::enumerate(values.iter()) {
+                    ::planus::WriteAsPrimitive::write(
+                        v,
+                        ::planus::Cursor::new(unsafe { &mut *bytes.add(i) }),
+                        buffer_position - (Self::STRIDE * i) as u32,
+                    );
+                }
+            }
+        }
+
+        impl<'a> ::planus::ReadAsRoot<'a> for FusedRef<'a> {
+            fn read_as_root(slice: &'a [u8]) -> ::planus::Result<Self> {
+                ::planus::TableRead::from_buffer(
+                    ::planus::SliceWithStartOffset { buffer: slice, offset_from_start: 0 },
+                    0,
+                )
+                .map_err(|error_kind| {
+                    error_kind.with_error_location("[FusedRef]", "read_as_root", 0)
+                })
+            }
+        }
+
+        /// The table `FusedOps` in the namespace `tract`
+        ///
+        /// Generated from these locations:
+        /// * Table `FusedOps` in the file `schema/tract.fbs:362`
+        #[derive(Clone, Debug, PartialEq, PartialOrd, ::serde::Serialize, ::serde::Deserialize)]
+        pub struct FusedOps {
+            /// The field `ops` in the table `FusedOps`
+            pub ops: ::planus::alloc::vec::Vec<self::Fused>,
+        }
+
+        #[allow(clippy::derivable_impls)]
+        impl ::core::default::Default for FusedOps {
+            fn default() -> Self {
+                Self { ops: ::core::default::Default::default() }
+            }
+        }
+
+        impl FusedOps {
+            /// Creates a [FusedOpsBuilder] for serializing an instance of this table.
+            #[inline]
+            pub fn builder() -> FusedOpsBuilder<()> {
+                FusedOpsBuilder(())
+            }
+
+            #[allow(clippy::too_many_arguments)]
+            pub fn create(
+                builder: &mut ::planus::Builder,
+                field_ops: impl ::planus::WriteAs<::planus::Offset<[::planus::Offset<self::Fused>]>>,
+            ) -> ::planus::Offset<Self> {
+                let prepared_ops = field_ops.prepare(builder);
+
+                let mut table_writer: ::planus::table_writer::TableWriter<6> =
+                    ::core::default::Default::default();
+                table_writer.write_entry::<::planus::Offset<[::planus::Offset<self::Fused>]>>(0);
+
+                unsafe {
+                    table_writer.finish(builder, |object_writer| {
+                        object_writer.write::<_, _, 4>(&prepared_ops);
+                    });
+                }
+                builder.current_offset()
+            }
+        }
+
+        impl ::planus::WriteAs<::planus::Offset<FusedOps>> for FusedOps {
+            type Prepared = ::planus::Offset<Self>;
+
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<FusedOps> {
+                ::planus::WriteAsOffset::prepare(self, builder)
+            }
+        }
+
+        impl ::planus::WriteAsOptional<::planus::Offset<FusedOps>> for FusedOps {
+            type Prepared = ::planus::Offset<Self>;
+
+            #[inline]
+            fn prepare(
+                &self,
+                builder: &mut ::planus::Builder,
+            ) -> ::core::option::Option<::planus::Offset<FusedOps>> {
+                ::core::option::Option::Some(::planus::WriteAsOffset::prepare(self, builder))
+            }
+        }
+
+        impl ::planus::WriteAsOffset<FusedOps> for FusedOps {
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<FusedOps> {
+                FusedOps::create(builder, &self.ops)
+            }
+        }
+
+        /// Builder for serializing an instance of the [FusedOps] type.
+        ///
+        /// Can be created using the [FusedOps::builder] method.
+        #[derive(Debug)]
+        #[must_use]
+        pub struct FusedOpsBuilder<State>(State);
+
+        impl FusedOpsBuilder<()> {
+            /// Setter for the [`ops` field](FusedOps#structfield.ops).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn ops<T0>(self, value: T0) -> FusedOpsBuilder<(T0,)>
+            where
+                T0: ::planus::WriteAs<::planus::Offset<[::planus::Offset<self::Fused>]>>,
+            {
+                FusedOpsBuilder((value,))
+            }
+        }
+
+        impl<T0> FusedOpsBuilder<(T0,)> {
+            /// Finish writing the builder to get an [Offset](::planus::Offset) to a serialized [FusedOps].
+            #[inline]
+            pub fn finish(self, builder: &mut ::planus::Builder) -> ::planus::Offset<FusedOps>
+            where
+                Self: ::planus::WriteAsOffset<FusedOps>,
+            {
+                ::planus::WriteAsOffset::prepare(&self, builder)
+            }
+        }
+
+        impl<T0: ::planus::WriteAs<::planus::Offset<[::planus::Offset<self::Fused>]>>>
+            ::planus::WriteAs<::planus::Offset<FusedOps>> for FusedOpsBuilder<(T0,)>
+        {
+            type Prepared = ::planus::Offset<FusedOps>;
+
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<FusedOps> {
+                ::planus::WriteAsOffset::prepare(self, builder)
+            }
+        }
+
+        impl<T0: ::planus::WriteAs<::planus::Offset<[::planus::Offset<self::Fused>]>>>
+            ::planus::WriteAsOptional<::planus::Offset<FusedOps>> for FusedOpsBuilder<(T0,)>
+        {
+            type Prepared = ::planus::Offset<FusedOps>;
+
+            #[inline]
+            fn prepare(
+                &self,
+                builder: &mut ::planus::Builder,
+            ) -> ::core::option::Option<::planus::Offset<FusedOps>> {
+                ::core::option::Option::Some(::planus::WriteAsOffset::prepare(self, builder))
+            }
+        }
+
+        impl<T0: ::planus::WriteAs<::planus::Offset<[::planus::Offset<self::Fused>]>>>
+            ::planus::WriteAsOffset<FusedOps> for FusedOpsBuilder<(T0,)>
+        {
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<FusedOps> {
+                let (v0,) = &self.0;
+                FusedOps::create(builder, v0)
+            }
+        }
+
+        /// Reference to a deserialized [FusedOps].
+        #[derive(Copy, Clone)]
+        pub struct FusedOpsRef<'a>(#[allow(dead_code)] ::planus::table_reader::Table<'a>);
+
+        impl<'a> FusedOpsRef<'a> {
+            /// Getter for the [`ops` field](FusedOps#structfield.ops).
+            #[inline]
+            pub fn ops(
+                &self,
+            ) -> ::planus::Result<::planus::Vector<'a, ::planus::Result<self::FusedRef<'a>>>>
+            {
+                self.0.access_required(0, "FusedOps", "ops")
+            }
+        }
+
+        impl<'a> ::core::fmt::Debug for FusedOpsRef<'a> {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                let mut f = f.debug_struct("FusedOpsRef");
+                f.field("ops", &self.ops());
+                f.finish()
+            }
+        }
+
+        impl<'a> ::core::convert::TryFrom<FusedOpsRef<'a>> for FusedOps {
+            type Error = ::planus::Error;
+
+            #[allow(unreachable_code)]
+            fn try_from(value: FusedOpsRef<'a>) -> ::planus::Result<Self> {
+                ::core::result::Result::Ok(Self { ops: value.ops()?.to_vec_result()? })
+            }
+        }
+
+        impl<'a> ::planus::TableRead<'a> for FusedOpsRef<'a> {
+            #[inline]
+            fn from_buffer(
+                buffer: ::planus::SliceWithStartOffset<'a>,
+                offset: usize,
+            ) -> ::core::result::Result<Self, ::planus::errors::ErrorKind> {
+                ::core::result::Result::Ok(Self(::planus::table_reader::Table::from_buffer(
+                    buffer, offset,
+                )?))
+            }
+        }
+
+        impl<'a> ::planus::VectorReadInner<'a> for FusedOpsRef<'a> {
+            type Error = ::planus::Error;
+            const STRIDE: usize = 4;
+
+            unsafe fn from_buffer(
+                buffer: ::planus::SliceWithStartOffset<'a>,
+                offset: usize,
+            ) -> ::planus::Result<Self> {
+                ::planus::TableRead::from_buffer(buffer, offset).map_err(|error_kind| {
+                    error_kind.with_error_location("[FusedOpsRef]", "get", buffer.offset_from_start)
+                })
+            }
+        }
+
+        /// # Safety
+        /// The planus compiler generates implementations that initialize
+        /// the bytes in `write_values`.
+        unsafe impl ::planus::VectorWrite<::planus::Offset<FusedOps>> for FusedOps {
+            type Value = ::planus::Offset<FusedOps>;
+            const STRIDE: usize = 4;
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> Self::Value {
+                ::planus::WriteAs::prepare(self, builder)
+            }
+
+            #[inline]
+            unsafe fn write_values(
+                values: &[::planus::Offset<FusedOps>],
+                bytes: *mut ::core::mem::MaybeUninit<u8>,
+                buffer_position: u32,
+            ) {
+                let bytes = bytes as *mut [::core::mem::MaybeUninit<u8>; 4];
+                for (i, v) in ::core::iter::Iterator::enumerate(values.iter()) {
+                    ::planus::WriteAsPrimitive::write(
+                        v,
+                        ::planus::Cursor::new(unsafe { &mut *bytes.add(i) }),
+                        buffer_position - (Self::STRIDE * i) as u32,
+                    );
+                }
+            }
+        }
+
+        impl<'a> ::planus::ReadAsRoot<'a> for FusedOpsRef<'a> {
+            fn read_as_root(slice: &'a [u8]) -> ::planus::Result<Self> {
+                ::planus::TableRead::from_buffer(
+                    ::planus::SliceWithStartOffset { buffer: slice, offset_from_start: 0 },
+                    0,
+                )
+                .map_err(|error_kind| {
+                    error_kind.with_error_location("[FusedOpsRef]", "read_as_root", 0)
+                })
+            }
+        }
+
+        /// The table `MatMatMul` in the namespace `tract`
+        ///
+        /// Generated from these locations:
+        /// * Table `MatMatMul` in the file `schema/tract.fbs:366`
+        #[derive(Clone, Debug, PartialEq, PartialOrd, ::serde::Serialize, ::serde::Deserialize)]
+        pub struct MatMatMul {
+            /// The field `mmm` in the table `MatMatMul`
+            pub mmm: ::planus::alloc::boxed::Box<self::Mmm>,
+            /// The field `c_trans` in the table `MatMatMul`
+            pub c_trans: bool,
+            /// The field `bc_c_shape` in the table `MatMatMul`
+            pub bc_c_shape: ::planus::alloc::vec::Vec<u64>,
+            /// The field `c_fact` in the table `MatMatMul`
+            pub c_fact: ::planus::alloc::boxed::Box<self::TypedFact>,
+            /// The field `c_prefix_dims` in the table `MatMatMul`
+            pub c_prefix_dims: ::core::option::Option<::planus::alloc::vec::Vec<u64>>,
+            /// The field `c_prefix_strides` in the table `MatMatMul`
+            pub c_prefix_strides: ::core::option::Option<::planus::alloc::vec::Vec<i64>>,
+            /// The field `packed_as_shape` in the table `MatMatMul`
+            pub packed_as_shape: ::planus::alloc::vec::Vec<u64>,
+            /// The field `packed_as` in the table `MatMatMul`
+            pub packed_as: ::planus::alloc::vec::Vec<self::Tensor>,
+            /// The field `fused_ops_shape` in the table `MatMatMul`
+            pub fused_ops_shape: ::core::option::Option<::planus::alloc::vec::Vec<u64>>,
+            /// The field `fused_ops` in the table `MatMatMul`
+            pub fused_ops: ::core::option::Option<::planus::alloc::vec::Vec<self::FusedOps>>,
+        }
+
+        #[allow(clippy::derivable_impls)]
+        impl ::core::default::Default for MatMatMul {
+            fn default() -> Self {
+                Self {
+                    mmm: ::core::default::Default::default(),
+                    c_trans: false,
+                    bc_c_shape: ::core::default::Default::default(),
+                    c_fact: ::core::default::Default::default(),
+                    c_prefix_dims: ::core::default::Default::default(),
+                    c_prefix_strides: ::core::default::Default::default(),
+                    packed_as_shape: ::core::default::Default::default(),
+                    packed_as: ::core::default::Default::default(),
+                    fused_ops_shape: ::core::default::Default::default(),
+                    fused_ops: ::core::default::Default::default(),
+                }
+            }
+        }
+
+        impl MatMatMul {
+            /// Creates a [MatMatMulBuilder] for serializing an instance of this table.
+            #[inline]
+            pub fn builder() -> MatMatMulBuilder<()> {
+                MatMatMulBuilder(())
+            }
+
+            #[allow(clippy::too_many_arguments)]
+            pub fn create(
+                builder: &mut ::planus::Builder,
+                field_mmm: impl ::planus::WriteAs<::planus::Offset<self::Mmm>>,
+                field_c_trans: impl ::planus::WriteAsDefault<bool, bool>,
+                field_bc_c_shape: impl ::planus::WriteAs<::planus::Offset<[u64]>>,
+                field_c_fact: impl ::planus::WriteAs<::planus::Offset<self::TypedFact>>,
+                field_c_prefix_dims: impl ::planus::WriteAsOptional<::planus::Offset<[u64]>>,
+                field_c_prefix_strides: impl ::planus::WriteAsOptional<::planus::Offset<[i64]>>,
+                field_packed_as_shape: impl ::planus::WriteAs<::planus::Offset<[u64]>>,
+                field_packed_as: impl ::planus::WriteAs<
+                    ::planus::Offset<[::planus::Offset<self::Tensor>]>,
+                >,
+                field_fused_ops_shape: impl ::planus::WriteAsOptional<::planus::Offset<[u64]>>,
+                field_fused_ops: impl ::planus::WriteAsOptional<
+                    ::planus::Offset<[::planus::Offset<self::FusedOps>]>,
+                >,
+            ) -> ::planus::Offset<Self> {
+                let prepared_mmm = field_mmm.prepare(builder);
+                let prepared_c_trans = field_c_trans.prepare(builder, &false);
+                let prepared_bc_c_shape = field_bc_c_shape.prepare(builder);
+                let prepared_c_fact = field_c_fact.prepare(builder);
+                let prepared_c_prefix_dims = field_c_prefix_dims.prepare(builder);
+                let prepared_c_prefix_strides = field_c_prefix_strides.prepare(builder);
+                let prepared_packed_as_shape = field_packed_as_shape.prepare(builder);
+                let prepared_packed_as = field_packed_as.prepare(builder);
+                let prepared_fused_ops_shape = field_fused_ops_shape.prepare(builder);
+                let prepared_fused_ops = field_fused_ops.prepare(builder);
+
+                let mut table_writer: ::planus::table_writer::TableWriter<24> =
+                    ::core::default::Default::default();
+                table_writer.write_entry::<::planus::Offset<self::Mmm>>(0);
+                table_writer.write_entry::<::planus::Offset<[u64]>>(2);
+                table_writer.write_entry::<::planus::Offset<self::TypedFact>>(3);
+                if prepared_c_prefix_dims.is_some() {
+                    table_writer.write_entry::<::planus::Offset<[u64]>>(4);
+                }
+                if prepared_c_prefix_strides.is_some() {
+                    table_writer.write_entry::<::planus::Offset<[i64]>>(5);
+                }
+                table_writer.write_entry::<::planus::Offset<[u64]>>(6);
+                table_writer.write_entry::<::planus::Offset<[::planus::Offset<self::Tensor>]>>(7);
+                if prepared_fused_ops_shape.is_some() {
+                    table_writer.write_entry::<::planus::Offset<[u64]>>(8);
+                }
+                if prepared_fused_ops.is_some() {
+                    table_writer
+                        .write_entry::<::planus::Offset<[::planus::Offset<self::FusedOps>]>>(9);
+                }
+                if prepared_c_trans.is_some() {
+                    table_writer.write_entry::<bool>(1);
+                }
+
+                unsafe {
+                    table_writer.finish(builder, |object_writer| {
+                        object_writer.write::<_, _, 4>(&prepared_mmm);
+                        object_writer.write::<_, _, 4>(&prepared_bc_c_shape);
+                        object_writer.write::<_, _, 4>(&prepared_c_fact);
+                        if let ::core::option::Option::Some(prepared_c_prefix_dims) =
+                            prepared_c_prefix_dims
+                        {
+                            object_writer.write::<_, _, 4>(&prepared_c_prefix_dims);
+                        }
+                        if let ::core::option::Option::Some(prepared_c_prefix_strides) =
+                            prepared_c_prefix_strides
+                        {
+                            object_writer.write::<_, _, 4>(&prepared_c_prefix_strides);
+                        }
+                        object_writer.write::<_, _, 4>(&prepared_packed_as_shape);
+                        object_writer.write::<_, _, 4>(&prepared_packed_as);
+                        if let ::core::option::Option::Some(prepared_fused_ops_shape) =
+                            prepared_fused_ops_shape
+                        {
+                            object_writer.write::<_, _, 4>(&prepared_fused_ops_shape);
+                        }
+                        if let ::core::option::Option::Some(prepared_fused_ops) = prepared_fused_ops
+                        {
+                            object_writer.write::<_, _, 4>(&prepared_fused_ops);
+                        }
+                        if let ::core::option::Option::Some(prepared_c_trans) = prepared_c_trans {
+                            object_writer.write::<_, _, 1>(&prepared_c_trans);
+                        }
+                    });
+                }
+                builder.current_offset()
+            }
+        }
+
+        impl ::planus::WriteAs<::planus::Offset<MatMatMul>> for MatMatMul {
+            type Prepared = ::planus::Offset<Self>;
+
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<MatMatMul> {
+                ::planus::WriteAsOffset::prepare(self, builder)
+            }
+        }
+
+        impl ::planus::WriteAsOptional<::planus::Offset<MatMatMul>> for MatMatMul {
+            type Prepared = ::planus::Offset<Self>;
+
+            #[inline]
+            fn prepare(
+                &self,
+                builder: &mut ::planus::Builder,
+            ) -> ::core::option::Option<::planus::Offset<MatMatMul>> {
+                ::core::option::Option::Some(::planus::WriteAsOffset::prepare(self, builder))
+            }
+        }
+
+        impl ::planus::WriteAsOffset<MatMatMul> for MatMatMul {
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<MatMatMul> {
+                MatMatMul::create(
+                    builder,
+                    &self.mmm,
+                    self.c_trans,
+                    &self.bc_c_shape,
+                    &self.c_fact,
+                    &self.c_prefix_dims,
+                    &self.c_prefix_strides,
+                    &self.packed_as_shape,
+                    &self.packed_as,
+                    &self.fused_ops_shape,
+                    &self.fused_ops,
+                )
+            }
+        }
+
+        /// Builder for serializing an instance of the [MatMatMul] type.
+        ///
+        /// Can be created using the [MatMatMul::builder] method.
+        #[derive(Debug)]
+        #[must_use]
+        pub struct MatMatMulBuilder<State>(State);
+
+        impl MatMatMulBuilder<()> {
+            /// Setter for the [`mmm` field](MatMatMul#structfield.mmm).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn mmm<T0>(self, value: T0) -> MatMatMulBuilder<(T0,)>
+            where
+                T0: ::planus::WriteAs<::planus::Offset<self::Mmm>>,
+            {
+                MatMatMulBuilder((value,))
+            }
+        }
+
+        impl<T0> MatMatMulBuilder<(T0,)> {
+            /// Setter for the [`c_trans` field](MatMatMul#structfield.c_trans).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn c_trans<T1>(self, value: T1) -> MatMatMulBuilder<(T0, T1)>
+            where
+                T1: ::planus::WriteAsDefault<bool, bool>,
+            {
+                let (v0,) = self.0;
+                MatMatMulBuilder((v0, value))
+            }
+
+            /// Sets the [`c_trans` field](MatMatMul#structfield.c_trans) to the default value.
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn c_trans_as_default(self) -> MatMatMulBuilder<(T0, ::planus::DefaultValue)> {
+                self.c_trans(::planus::DefaultValue)
+            }
+        }
+
+        impl<T0, T1> MatMatMulBuilder<(T0, T1)> {
+            /// Setter for the [`bc_c_shape` field](MatMatMul#structfield.bc_c_shape).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn bc_c_shape<T2>(self, value: T2) -> MatMatMulBuilder<(T0, T1, T2)>
+            where
+                T2: ::planus::WriteAs<::planus::Offset<[u64]>>,
+            {
+                let (v0, v1) = self.0;
+                MatMatMulBuilder((v0, v1, value))
+            }
+        }
+
+        impl<T0, T1, T2> MatMatMulBuilder<(T0, T1, T2)> {
+            /// Setter for the [`c_fact` field](MatMatMul#structfield.c_fact).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn c_fact<T3>(self, value: T3) -> MatMatMulBuilder<(T0, T1, T2, T3)>
+            where
+                T3: ::planus::WriteAs<::planus::Offset<self::TypedFact>>,
+            {
+                let (v0, v1, v2) = self.0;
+                MatMatMulBuilder((v0, v1, v2, value))
+            }
+        }
+
+        impl<T0, T1, T2, T3> MatMatMulBuilder<(T0, T1, T2, T3)> {
+            /// Setter for the [`c_prefix_dims` field](MatMatMul#structfield.c_prefix_dims).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn c_prefix_dims<T4>(self, value: T4) -> MatMatMulBuilder<(T0, T1, T2, T3, T4)>
+            where
+                T4: ::planus::WriteAsOptional<::planus::Offset<[u64]>>,
+            {
+                let (v0, v1, v2, v3) = self.0;
+                MatMatMulBuilder((v0, v1, v2, v3, value))
+            }
+
+            /// Sets the [`c_prefix_dims` field](MatMatMul#structfield.c_prefix_dims) to null.
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn c_prefix_dims_as_null(self) -> MatMatMulBuilder<(T0, T1, T2, T3, ())> {
+                self.c_prefix_dims(())
+            }
+        }
+
+        impl<T0, T1, T2, T3, T4> MatMatMulBuilder<(T0, T1, T2, T3, T4)> {
+            /// Setter for the [`c_prefix_strides` field](MatMatMul#structfield.c_prefix_strides).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn c_prefix_strides<T5>(
+                self,
+                value: T5,
+            ) -> MatMatMulBuilder<(T0, T1, T2, T3, T4, T5)>
+            where
+                T5: ::planus::WriteAsOptional<::planus::Offset<[i64]>>,
+            {
+                let (v0, v1, v2, v3, v4) = self.0;
+                MatMatMulBuilder((v0, v1, v2, v3, v4, value))
+            }
+
+            /// Sets the [`c_prefix_strides` field](MatMatMul#structfield.c_prefix_strides) to null.
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn c_prefix_strides_as_null(self) -> MatMatMulBuilder<(T0, T1, T2, T3, T4, ())> {
+                self.c_prefix_strides(())
+            }
+        }
+
+        impl<T0, T1, T2, T3, T4, T5> MatMatMulBuilder<(T0, T1, T2, T3, T4, T5)> {
+            /// Setter for the [`packed_as_shape` field](MatMatMul#structfield.packed_as_shape).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn packed_as_shape<T6>(
+                self,
+                value: T6,
+            ) -> MatMatMulBuilder<(T0, T1, T2, T3, T4, T5, T6)>
+            where
+                T6: ::planus::WriteAs<::planus::Offset<[u64]>>,
+            {
+                let (v0, v1, v2, v3, v4, v5) = self.0;
+                MatMatMulBuilder((v0, v1, v2, v3, v4, v5, value))
+            }
+        }
+
+        impl<T0, T1, T2, T3, T4, T5, T6> MatMatMulBuilder<(T0, T1, T2, T3, T4, T5, T6)> {
+            /// Setter for the [`packed_as` field](MatMatMul#structfield.packed_as).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn packed_as<T7>(
+                self,
+                value: T7,
+            ) -> MatMatMulBuilder<(T0, T1, T2, T3, T4, T5, T6, T7)>
+            where
+                T7: ::planus::WriteAs<::planus::Offset<[::planus::Offset<self::Tensor>]>>,
+            {
+                let (v0, v1, v2, v3, v4, v5, v6) = self.0;
+                MatMatMulBuilder((v0, v1, v2, v3, v4, v5, v6, value))
+            }
+        }
+
+        impl<T0, T1, T2, T3, T4, T5, T6, T7> MatMatMulBuilder<(T0, T1, T2, T3, T4, T5, T6, T7)> {
+            /// Setter for the [`fused_ops_shape` field](MatMatMul#structfield.fused_ops_shape).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn fused_ops_shape<T8>(
+                self,
+                value: T8,
+            ) -> MatMatMulBuilder<(T0, T1, T2, T3, T4, T5, T6, T7, T8)>
+            where
+                T8: ::planus::WriteAsOptional<::planus::Offset<[u64]>>,
+            {
+                let (v0, v1, v2, v3, v4, v5, v6, v7) = self.0;
+                MatMatMulBuilder((v0, v1, v2, v3, v4, v5, v6, v7, value))
+            }
+
+            /// Sets the [`fused_ops_shape` field](MatMatMul#structfield.fused_ops_shape) to null.
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn fused_ops_shape_as_null(
+                self,
+            ) -> MatMatMulBuilder<(T0, T1, T2, T3, T4, T5, T6, T7, ())> {
+                self.fused_ops_shape(())
+            }
+        }
+
+        impl<T0, T1, T2, T3, T4, T5, T6, T7, T8> MatMatMulBuilder<(T0, T1, T2, T3, T4, T5, T6, T7, T8)> {
+            /// Setter for the [`fused_ops` field](MatMatMul#structfield.fused_ops).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn fused_ops<T9>(
+                self,
+                value: T9,
+            ) -> MatMatMulBuilder<(T0, T1, T2, T3, T4, T5, T6, T7, T8, T9)>
+            where
+                T9: ::planus::WriteAsOptional<::planus::Offset<[::planus::Offset<self::FusedOps>]>>,
+            {
+                let (v0, v1, v2, v3, v4, v5, v6, v7, v8) = self.0;
+                MatMatMulBuilder((v0, v1, v2, v3, v4, v5, v6, v7, v8, value))
+            }
+
+            /// Sets the [`fused_ops` field](MatMatMul#structfield.fused_ops) to null.
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn fused_ops_as_null(
+                self,
+            ) -> MatMatMulBuilder<(T0, T1, T2, T3, T4, T5, T6, T7, T8, ())> {
+                self.fused_ops(())
+            }
+        }
+
+        impl<T0, T1, T2, T3, T4, T5, T6, T7, T8, T9>
+            MatMatMulBuilder<(T0, T1, T2, T3, T4, T5, T6, T7, T8, T9)>
+        {
+            /// Finish writing the builder to get an [Offset](::planus::Offset) to a serialized [MatMatMul].
+            #[inline]
+            pub fn finish(self, builder: &mut ::planus::Builder) -> ::planus::Offset<MatMatMul>
+            where
+                Self: ::planus::WriteAsOffset<MatMatMul>,
+            {
+                ::planus::WriteAsOffset::prepare(&self, builder)
+            }
+        }
+
+        impl<
+                T0: ::planus::WriteAs<::planus::Offset<self::Mmm>>,
+                T1: ::planus::WriteAsDefault<bool, bool>,
+                T2: ::planus::WriteAs<::planus::Offset<[u64]>>,
+                T3: ::planus::WriteAs<::planus::Offset<self::TypedFact>>,
+                T4: ::planus::WriteAsOptional<::planus::Offset<[u64]>>,
+                T5: ::planus::WriteAsOptional<::planus::Offset<[i64]>>,
+                T6: ::planus::WriteAs<::planus::Offset<[u64]>>,
+                T7: ::planus::WriteAs<::planus::Offset<[::planus::Offset<self::Tensor>]>>,
+                T8: ::planus::WriteAsOptional<::planus::Offset<[u64]>>,
+                T9: ::planus::WriteAsOptional<::planus::Offset<[::planus::Offset<self::FusedOps>]>>,
+            > ::planus::WriteAs<::planus::Offset<MatMatMul>>
+            for MatMatMulBuilder<(T0, T1, T2, T3, T4, T5, T6, T7, T8, T9)>
+        {
+            type Prepared = ::planus::Offset<MatMatMul>;
+
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<MatMatMul> {
+                ::planus::WriteAsOffset::prepare(self, builder)
+            }
+        }
+
+        impl<
+                T0: ::planus::WriteAs<::planus::Offset<self::Mmm>>,
+                T1: ::planus::WriteAsDefault<bool, bool>,
+                T2: ::planus::WriteAs<::planus::Offset<[u64]>>,
+                T3: ::planus::WriteAs<::planus::Offset<self::TypedFact>>,
+                T4: ::planus::WriteAsOptional<::planus::Offset<[u64]>>,
+                T5: ::planus::WriteAsOptional<::planus::Offset<[i64]>>,
+                T6: ::planus::WriteAs<::planus::Offset<[u64]>>,
+                T7: ::planus::WriteAs<::planus::Offset<[::planus::Offset<self::Tensor>]>>,
+                T8: ::planus::WriteAsOptional<::planus::Offset<[u64]>>,
+                T9: ::planus::WriteAsOptional<::planus::Offset<[::planus::Offset<self::FusedOps>]>>,
+            > ::planus::WriteAsOptional<::planus::Offset<MatMatMul>>
+            for MatMatMulBuilder<(T0, T1, T2, T3, T4, T5, T6, T7, T8, T9)>
+        {
+            type Prepared = ::planus::Offset<MatMatMul>;
+
+            #[inline]
+            fn prepare(
+                &self,
+                builder: &mut ::planus::Builder,
+            ) -> ::core::option::Option<::planus::Offset<MatMatMul>> {
+                ::core::option::Option::Some(::planus::WriteAsOffset::prepare(self, builder))
+            }
+        }
+
+        impl<
+                T0: ::planus::WriteAs<::planus::Offset<self::Mmm>>,
+                T1: ::planus::WriteAsDefault<bool, bool>,
+                T2: ::planus::WriteAs<::planus::Offset<[u64]>>,
+                T3: ::planus::WriteAs<::planus::Offset<self::TypedFact>>,
+                T4: ::planus::WriteAsOptional<::planus::Offset<[u64]>>,
+                T5: ::planus::WriteAsOptional<::planus::Offset<[i64]>>,
+                T6: ::planus::WriteAs<::planus::Offset<[u64]>>,
+                T7: ::planus::WriteAs<::planus::Offset<[::planus::Offset<self::Tensor>]>>,
+                T8: ::planus::WriteAsOptional<::planus::Offset<[u64]>>,
+                T9: ::planus::WriteAsOptional<::planus::Offset<[::planus::Offset<self::FusedOps>]>>,
+            > ::planus::WriteAsOffset<MatMatMul>
+            for MatMatMulBuilder<(T0, T1, T2, T3, T4, T5, T6, T7, T8, T9)>
+        {
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<MatMatMul> {
+                let (v0, v1, v2, v3, v4, v5, v6, v7, v8, v9) = &self.0;
+                MatMatMul::create(builder, v0, v1, v2, v3, v4, v5, v6, v7, v8, v9)
+            }
+        }
+
+        /// Reference to a deserialized [MatMatMul].
+        #[derive(Copy, Clone)]
+        pub struct MatMatMulRef<'a>(#[allow(dead_code)] ::planus::table_reader::Table<'a>);
+
+        impl<'a> MatMatMulRef<'a> {
+            /// Getter for the [`mmm` field](MatMatMul#structfield.mmm).
+            #[inline]
+            pub fn mmm(&self) -> ::planus::Result<self::MmmRef<'a>> {
+                self.0.access_required(0, "MatMatMul", "mmm")
+            }
+
+            /// Getter for the [`c_trans` field](MatMatMul#structfield.c_trans).
+            #[inline]
+            pub fn c_trans(&self) -> ::planus::Result<bool> {
+                ::core::result::Result::Ok(
+                    self.0.access(1, "MatMatMul", "c_trans")?.unwrap_or(false),
+                )
+            }
+
+            /// Getter for the [`bc_c_shape` field](MatMatMul#structfield.bc_c_shape).
+            #[inline]
+            pub fn bc_c_shape(&self) -> ::planus::Result<::planus::Vector<'a, u64>> {
+                self.0.access_required(2, "MatMatMul", "bc_c_shape")
+            }
+
+            /// Getter for the [`c_fact` field](MatMatMul#structfield.c_fact).
+            #[inline]
+            pub fn c_fact(&self) -> ::planus::Result<self::TypedFactRef<'a>> {
+                self.0.access_required(3, "MatMatMul", "c_fact")
+            }
+
+            /// Getter for the [`c_prefix_dims` field](MatMatMul#structfield.c_prefix_dims).
+            #[inline]
+            pub fn c_prefix_dims(
+                &self,
+            ) -> ::planus::Result<::core::option::Option<::planus::Vector<'a, u64>>> {
+                self.0.access(4, "MatMatMul", "c_prefix_dims")
+            }
+
+            /// Getter for the [`c_prefix_strides` field](MatMatMul#structfield.c_prefix_strides).
+            #[inline]
+            pub fn c_prefix_strides(
+                &self,
+            ) -> ::planus::Result<::core::option::Option<::planus::Vector<'a, i64>>> {
+                self.0.access(5, "MatMatMul", "c_prefix_strides")
+            }
+
+            /// Getter for the [`packed_as_shape` field](MatMatMul#structfield.packed_as_shape).
+            #[inline]
+            pub fn packed_as_shape(&self) -> ::planus::Result<::planus::Vector<'a, u64>> {
+                self.0.access_required(6, "MatMatMul", "packed_as_shape")
+            }
+
+            /// Getter for the [`packed_as` field](MatMatMul#structfield.packed_as).
+            #[inline]
+            pub fn packed_as(
+                &self,
+            ) -> ::planus::Result<::planus::Vector<'a, ::planus::Result<self::TensorRef<'a>>>>
+            {
+                self.0.access_required(7, "MatMatMul", "packed_as")
+            }
+
+            /// Getter for the [`fused_ops_shape` field](MatMatMul#structfield.fused_ops_shape).
+            #[inline]
+            pub fn fused_ops_shape(
+                &self,
+            ) -> ::planus::Result<::core::option::Option<::planus::Vector<'a, u64>>> {
+                self.0.access(8, "MatMatMul", "fused_ops_shape")
+            }
+
+            /// Getter for the [`fused_ops` field](MatMatMul#structfield.fused_ops).
+            #[inline]
+            pub fn fused_ops(
+                &self,
+            ) -> ::planus::Result<
+                ::core::option::Option<
+                    ::planus::Vector<'a, ::planus::Result<self::FusedOpsRef<'a>>>,
+                >,
+            > {
+                self.0.access(9, "MatMatMul", "fused_ops")
+            }
+        }
+
+        impl<'a> ::core::fmt::Debug for MatMatMulRef<'a> {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                let mut f = f.debug_struct("MatMatMulRef");
+                f.field("mmm", &self.mmm());
+                f.field("c_trans", &self.c_trans());
+                f.field("bc_c_shape", &self.bc_c_shape());
+                f.field("c_fact", &self.c_fact());
+                if let ::core::option::Option::Some(field_c_prefix_dims) =
+                    self.c_prefix_dims().transpose()
+                {
+                    f.field("c_prefix_dims", &field_c_prefix_dims);
+                }
+                if let ::core::option::Option::Some(field_c_prefix_strides) =
+                    self.c_prefix_strides().transpose()
+                {
+                    f.field("c_prefix_strides", &field_c_prefix_strides);
+                }
+                f.field("packed_as_shape", &self.packed_as_shape());
+                f.field("packed_as", &self.packed_as());
+                if let ::core::option::Option::Some(field_fused_ops_shape) =
+                    self.fused_ops_shape().transpose()
+                {
+                    f.field("fused_ops_shape", &field_fused_ops_shape);
+                }
+                if let ::core::option::Option::Some(field_fused_ops) = self.fused_ops().transpose()
+                {
+                    f.field("fused_ops", &field_fused_ops);
+                }
+                f.finish()
+            }
+        }
+
+        impl<'a> ::core::convert::TryFrom<MatMatMulRef<'a>> for MatMatMul {
+            type Error = ::planus::Error;
+
+            #[allow(unreachable_code)]
+            fn try_from(value: MatMatMulRef<'a>) -> ::planus::Result<Self> {
+                ::core::result::Result::Ok(Self {
+                    mmm: ::planus::alloc::boxed::Box::new(::core::convert::TryInto::try_into(
+                        value.mmm()?,
+                    )?),
+                    c_trans: ::core::convert::TryInto::try_into(value.c_trans()?)?,
+                    bc_c_shape: value.bc_c_shape()?.to_vec()?,
+                    c_fact: ::planus::alloc::boxed::Box::new(::core::convert::TryInto::try_into(
+                        value.c_fact()?,
+                    )?),
+                    c_prefix_dims: if let ::core::option::Option::Some(c_prefix_dims) =
+                        value.c_prefix_dims()?
+                    {
+                        ::core::option::Option::Some(c_prefix_dims.to_vec()?)
+                    } else {
+                        ::core::option::Option::None
+                    },
+                    c_prefix_strides: if let ::core::option::Option::Some(c_prefix_strides) =
+                        value.c_prefix_strides()?
+                    {
+                        ::core::option::Option::Some(c_prefix_strides.to_vec()?)
+                    } else {
+                        ::core::option::Option::None
+                    },
+                    packed_as_shape: value.packed_as_shape()?.to_vec()?,
+                    packed_as: value.packed_as()?.to_vec_result()?,
+                    fused_ops_shape: if let ::core::option::Option::Some(fused_ops_shape) =
+                        value.fused_ops_shape()?
+                    {
+                        ::core::option::Option::Some(fused_ops_shape.to_vec()?)
+                    } else {
+                        ::core::option::Option::None
+                    },
+                    fused_ops: if let ::core::option::Option::Some(fused_ops) = value.fused_ops()? {
+                        ::core::option::Option::Some(fused_ops.to_vec_result()?)
+                    } else {
+                        ::core::option::Option::None
+                    },
+                })
+            }
+        }
+
+        impl<'a> ::planus::TableRead<'a> for MatMatMulRef<'a> {
+            #[inline]
+            fn from_buffer(
+                buffer: ::planus::SliceWithStartOffset<'a>,
+                offset: usize,
+            ) -> ::core::result::Result<Self, ::planus::errors::ErrorKind> {
+                ::core::result::Result::Ok(Self(::planus::table_reader::Table::from_buffer(
+                    buffer, offset,
+                )?))
+            }
+        }
+
+        impl<'a> ::planus::VectorReadInner<'a> for MatMatMulRef<'a> {
+            type Error = ::planus::Error;
+            const STRIDE: usize = 4;
+
+            unsafe fn from_buffer(
+                buffer: ::planus::SliceWithStartOffset<'a>,
+                offset: usize,
+            ) -> ::planus::Result<Self> {
+                ::planus::TableRead::from_buffer(buffer, offset).map_err(|error_kind| {
+                    error_kind.with_error_location(
+                        "[MatMatMulRef]",
+                        "get",
+                        buffer.offset_from_start,
+                    )
+                })
+            }
+        }
+
+        /// # Safety
+        /// The planus compiler generates implementations that initialize
+        /// the bytes in `write_values`.
+        unsafe impl ::planus::VectorWrite<::planus::Offset<MatMatMul>> for MatMatMul {
+            type Value = ::planus::Offset<MatMatMul>;
+            const STRIDE: usize = 4;
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> Self::Value {
+                ::planus::WriteAs::prepare(self, builder)
+            }
+
+            #[inline]
+            unsafe fn write_values(
+                values: &[::planus::Offset<MatMatMul>],
+                bytes: *mut ::core::mem::MaybeUninit<u8>,
+                buffer_position: u32,
+            ) {
+                let bytes = bytes as *mut [::core::mem::MaybeUninit<u8>; 4];
+                for (i, v) in ::core::iter::Iterator::enumerate(values.iter()) {
+                    ::planus::WriteAsPrimitive::write(
+                        v,
+                        ::planus::Cursor::new(unsafe { &mut *bytes.add(i) }),
+                        buffer_position - (Self::STRIDE * i) as u32,
+                    );
+                }
+            }
+        }
+
+        impl<'a> ::planus::ReadAsRoot<'a> for MatMatMulRef<'a> {
+            fn read_as_root(slice: &'a [u8]) -> ::planus::Result<Self> {
+                ::planus::TableRead::from_buffer(
+                    ::planus::SliceWithStartOffset { buffer: slice, offset_from_start: 0 },
+                    0,
+                )
+                .map_err(|error_kind| {
+                    error_kind.with_error_location("[MatMatMulRef]", "read_as_root", 0)
+                })
+            }
+        }
+
+        /// The table `MergeUnicast` in the namespace `tract`
+        ///
+        /// Generated from these locations:
+        /// * Table `MergeUnicast` in the file `schema/tract.fbs:381`
+        #[derive(
+            Clone,
+            Debug,
+            PartialEq,
+            PartialOrd,
+            Eq,
+            Ord,
+            Hash,
+            ::serde::Serialize,
+            ::serde::Deserialize,
+        )]
+        pub struct MergeUnicast {
+            /// The field `mini_op` in the table `MergeUnicast`
+            pub mini_op: ::planus::alloc::string::String,
+        }
+
+        #[allow(clippy::derivable_impls)]
+        impl ::core::default::Default for MergeUnicast {
+            fn default() -> Self {
+                Self { mini_op: ::core::default::Default::default() }
+            }
+        }
+
+        impl MergeUnicast {
+            /// Creates a [MergeUnicastBuilder] for serializing an instance of this table.
+            #[inline]
+            pub fn builder() -> MergeUnicastBuilder<()> {
+                MergeUnicastBuilder(())
+            }
+
+            #[allow(clippy::too_many_arguments)]
+            pub fn create(
+                builder: &mut ::planus::Builder,
+                field_mini_op: impl ::planus::WriteAs<::planus::Offset<str>>,
+            ) -> ::planus::Offset<Self> {
+                let prepared_mini_op = field_mini_op.prepare(builder);
+
+                let mut table_writer: ::planus::table_writer::TableWriter<6> =
+                    ::core::default::Default::default();
+                table_writer.write_entry::<::planus::Offset<str>>(0);
+
+                unsafe {
+                    table_writer.finish(builder, |object_writer| {
+                        object_writer.write::<_, _, 4>(&prepared_mini_op);
+                    });
+                }
+                builder.current_offset()
+            }
+        }
+
+        impl ::planus::WriteAs<::planus::Offset<MergeUnicast>> for MergeUnicast {
+            type Prepared = ::planus::Offset<Self>;
+
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<MergeUnicast> {
+                ::planus::WriteAsOffset::prepare(self, builder)
+            }
+        }
+
+        impl ::planus::WriteAsOptional<::planus::Offset<MergeUnicast>> for MergeUnicast {
+            type Prepared = ::planus::Offset<Self>;
+
+            #[inline]
+            fn prepare(
+                &self,
+                builder: &mut ::planus::Builder,
+            ) -> ::core::option::Option<::planus::Offset<MergeUnicast>> {
+                ::core::option::Option::Some(::planus::WriteAsOffset::prepare(self, builder))
+            }
+        }
+
+        impl ::planus::WriteAsOffset<MergeUnicast> for MergeUnicast {
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<MergeUnicast> {
+                MergeUnicast::create(builder, &self.mini_op)
+            }
+        }
+
+        /// Builder for serializing an instance of the [MergeUnicast] type.
+        ///
+        /// Can be created using the [MergeUnicast::builder] method.
+        #[derive(Debug)]
+        #[must_use]
+        pub struct MergeUnicastBuilder<State>(State);
+
+        impl MergeUnicastBuilder<()> {
+            /// Setter for the [`mini_op` field](MergeUnicast#structfield.mini_op).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn mini_op<T0>(self, value: T0) -> MergeUnicastBuilder<(T0,)>
+            where
+                T0: ::planus::WriteAs<::planus::Offset<str>>,
+            {
+                MergeUnicastBuilder((value,))
+            }
+        }
+
+        impl<T0> MergeUnicastBuilder<(T0,)> {
+            /// Finish writing the builder to get an [Offset](::planus::Offset) to a serialized [MergeUnicast].
+            #[inline]
+            pub fn finish(self, builder: &mut ::planus::Builder) -> ::planus::Offset<MergeUnicast>
+            where
+                Self: ::planus::WriteAsOffset<MergeUnicast>,
+            {
+                ::planus::WriteAsOffset::prepare(&self, builder)
+            }
+        }
+
+        impl<T0: ::planus::WriteAs<::planus::Offset<str>>>
+            ::planus::WriteAs<::planus::Offset<MergeUnicast>> for MergeUnicastBuilder<(T0,)>
+        {
+            type Prepared = ::planus::Offset<MergeUnicast>;
+
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<MergeUnicast> {
+                ::planus::WriteAsOffset::prepare(self, builder)
+            }
+        }
+
+        impl<T0: ::planus::WriteAs<::planus::Offset<str>>>
+            ::planus::WriteAsOptional<::planus::Offset<MergeUnicast>>
+            for MergeUnicastBuilder<(T0,)>
+        {
+            type Prepared = ::planus::Offset<MergeUnicast>;
+
+            #[inline]
+            fn prepare(
+                &self,
+                builder: &mut ::planus::Builder,
+            ) -> ::core::option::Option<::planus::Offset<MergeUnicast>> {
+                ::core::option::Option::Some(::planus::WriteAsOffset::prepare(self, builder))
+            }
+        }
+
+        impl<T0: ::planus::WriteAs<::planus::Offset<str>>> ::planus::WriteAsOffset<MergeUnicast>
+            for MergeUnicastBuilder<(T0,)>
+        {
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<MergeUnicast> {
+                let (v0,) = &self.0;
+                MergeUnicast::create(builder, v0)
+            }
+        }
+
+        /// Reference to a deserialized [MergeUnicast].
+        #[derive(Copy, Clone)]
+        pub struct MergeUnicastRef<'a>(#[allow(dead_code)] ::planus::table_reader::Table<'a>);
+
+        impl<'a> MergeUnicastRef<'a> {
+            /// Getter for the [`mini_op` field](MergeUnicast#structfield.mini_op).
+            #[inline]
+            pub fn mini_op(&self) -> ::planus::Result<&'a ::core::primitive::str> {
+                self.0.access_required(0, "MergeUnicast", "mini_op")
+            }
+        }
+
+        impl<'a> ::core::fmt::Debug for MergeUnicastRef<'a> {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                let mut f = f.debug_struct("MergeUnicastRef");
+                f.field("mini_op", &self.mini_op());
+                f.finish()
+            }
+        }
+
+        impl<'a> ::core::convert::TryFrom<MergeUnicastRef<'a>> for MergeUnicast {
+            type Error = ::planus::Error;
+
+            #[allow(unreachable_code)]
+            fn try_from(value: MergeUnicastRef<'a>) -> ::planus::Result<Self> {
+                ::core::result::Result::Ok(Self {
+                    mini_op: ::core::convert::Into::into(value.mini_op()?),
+                })
+            }
+        }
+
+        impl<'a> ::planus::TableRead<'a> for MergeUnicastRef<'a> {
+            #[inline]
+            fn from_buffer(
+                buffer: ::planus::SliceWithStartOffset<'a>,
+                offset: usize,
+            ) -> ::core::result::Result<Self, ::planus::errors::ErrorKind> {
+                ::core::result::Result::Ok(Self(::planus::table_reader::Table::from_buffer(
+                    buffer, offset,
+                )?))
+            }
+        }
+
+        impl<'a> ::planus::VectorReadInner<'a> for MergeUnicastRef<'a> {
+            type Error = ::planus::Error;
+            const STRIDE: usize = 4;
+
+            unsafe fn from_buffer(
+                buffer: ::planus::SliceWithStartOffset<'a>,
+                offset: usize,
+            ) -> ::planus::Result<Self> {
+                ::planus::TableRead::from_buffer(buffer, offset).map_err(|error_kind| {
+                    error_kind.with_error_location(
+                        "[MergeUnicastRef]",
+                        "get",
+                        buffer.offset_from_start,
+                    )
+                })
+            }
+        }
+
+        /// # Safety
+        /// The planus compiler generates implementations that initialize
+        /// the bytes in `write_values`.
+        unsafe impl ::planus::VectorWrite<::planus::Offset<MergeUnicast>> for MergeUnicast {
+            type Value = ::planus::Offset<MergeUnicast>;
+            const STRIDE: usize = 4;
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> Self::Value {
+                ::planus::WriteAs::prepare(self, builder)
+            }
+
+            #[inline]
+            unsafe fn write_values(
+                values: &[::planus::Offset<MergeUnicast>],
+                bytes: *mut ::core::mem::MaybeUninit<u8>,
+                buffer_position: u32,
+            ) {
+                let bytes = bytes as *mut [::core::mem::MaybeUninit<u8>; 4];
+                for (i, v) in ::core::iter::Iterator::enumerate(values.iter()) {
+                    ::planus::WriteAsPrimitive::write(
+                        v,
+                        ::planus::Cursor::new(unsafe { &mut *bytes.add(i) }),
+                        buffer_position - (Self::STRIDE * i) as u32,
+                    );
+                }
+            }
+        }
+
+        impl<'a> ::planus::ReadAsRoot<'a> for MergeUnicastRef<'a> {
+            fn read_as_root(slice: &'a [u8]) -> ::planus::Result<Self> {
+                ::planus::TableRead::from_buffer(
+                    ::planus::SliceWithStartOffset { buffer: slice, offset_from_start: 0 },
+                    0,
+                )
+                .map_err(|error_kind| {
+                    error_kind.with_error_location("[MergeUnicastRef]", "read_as_root", 0)
+                })
+            }
+        }
+
+        /// The table `MaxPoolFixed` in the namespace `tract`
+        ///
+        /// Generated from these locations:
+        /// * Table `MaxPoolFixed` in the file `schema/tract.fbs:385`
+        #[derive(
+            Clone,
+            Debug,
+            PartialEq,
+            PartialOrd,
+            Eq,
+            Ord,
+            Hash,
+            ::serde::Serialize,
+            ::serde::Deserialize,
+        )]
+        pub struct MaxPoolFixed {
+            /// The field `patch` in the table `MaxPoolFixed`
+            pub patch: ::planus::alloc::boxed::Box<self::PatchSpec>,
+            /// The field `input_shape` in the table `MaxPoolFixed`
+            pub input_shape: ::planus::alloc::boxed::Box<self::DataShape>,
+            /// The field `output_shape` in the table `MaxPoolFixed`
+            pub output_shape: ::planus::alloc::boxed::Box<self::DataShape>,
+            /// The field `index_datum_type` in the table `MaxPoolFixed`
+            pub index_datum_type: ::core::option::Option<self::DatumType>,
+        }
+
+        #[allow(clippy::derivable_impls)]
+        impl ::core::default::Default for MaxPoolFixed {
+            fn default() -> Self {
+                Self {
+                    patch: ::core::default::Default::default(),
+                    input_shape: ::core::default::Default::default(),
+                    output_shape: ::core::default::Default::default(),
+                    index_datum_type: ::core::default::Default::default(),
+                }
+            }
+        }
+
+        impl MaxPoolFixed {
+            /// Creates a [MaxPoolFixedBuilder] for serializing an instance of this table.
+            #[inline]
+            pub fn builder() -> MaxPoolFixedBuilder<()> {
+                MaxPoolFixedBuilder(())
+            }
+
+            #[allow(clippy::too_many_arguments)]
+            pub fn create(
+                builder: &mut ::planus::Builder,
+                field_patch: impl ::planus::WriteAs<::planus::Offset<self::PatchSpec>>,
+                field_input_shape: impl ::planus::WriteAs<::planus::Offset<self::DataShape>>,
+                field_output_shape: impl ::planus::WriteAs<::planus::Offset<self::DataShape>>,
+                field_index_datum_type: impl ::planus::WriteAsOptional<self::DatumType>,
+            ) -> ::planus::Offset<Self> {
+                let prepared_patch = field_patch.prepare(builder);
+                let prepared_input_shape = field_input_shape.prepare(builder);
+                let prepared_output_shape = field_output_shape.prepare(builder);
+                let prepared_index_datum_type = field_index_datum_type.prepare(builder);
+
+                let mut table_writer: ::planus::table_writer::TableWriter<12> =
+                    ::core::default::Default::default();
+                table_writer.write_entry::<::planus::Offset<self::PatchSpec>>(0);
+                table_writer.write_entry::<::planus::Offset<self::DataShape>>(1);
+                table_writer.write_entry::<::planus::Offset<self::DataShape>>(2);
+                if prepared_index_datum_type.is_some() {
+                    table_writer.write_entry::<self::DatumType>(3);
+                }
+
+                unsafe {
+                    table_writer.finish(builder, |object_writer| {
+                        object_writer.write::<_, _, 4>(&prepared_patch);
+                        object_writer.write::<_, _, 4>(&prepared_input_shape);
+                        object_writer.write::<_, _, 4>(&prepared_output_shape);
+                        if let ::core::option::Option::Some(prepared_index_datum_type) =
+                            prepared_index_datum_type
+                        {
+                            object_writer.write::<_, _, 1>(&prepared_index_datum_type);
+                        }
+                    });
+                }
+                builder.current_offset()
+            }
+        }
+
+        impl ::planus::WriteAs<::planus::Offset<MaxPoolFixed>> for MaxPoolFixed {
+            type Prepared = ::planus::Offset<Self>;
+
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<MaxPoolFixed> {
+                ::planus::WriteAsOffset::prepare(self, builder)
+            }
+        }
+
+        impl ::planus::WriteAsOptional<::planus::Offset<MaxPoolFixed>> for MaxPoolFixed {
+            type Prepared = ::planus::Offset<Self>;
+
+            #[inline]
+            fn prepare(
+                &self,
+                builder: &mut ::planus::Builder,
+            ) -> ::core::option::Option<::planus::Offset<MaxPoolFixed>> {
+                ::core::option::Option::Some(::planus::WriteAsOffset::prepare(self, builder))
+            }
+        }
+
+        impl ::planus::WriteAsOffset<MaxPoolFixed> for MaxPoolFixed {
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<MaxPoolFixed> {
+                MaxPoolFixed::create(
+                    builder,
+                    &self.patch,
+                    &self.input_shape,
+                    &self.output_shape,
+                    self.index_datum_type,
+                )
+            }
+        }
+
+        /// Builder for serializing an instance of the [MaxPoolFixed] type.
+        ///
+        /// Can be created using the [MaxPoolFixed::builder] method.
+        #[derive(Debug)]
+        #[must_use]
+        pub struct MaxPoolFixedBuilder<State>(State);
+
+        impl MaxPoolFixedBuilder<()> {
+            /// Setter for the [`patch` field](MaxPoolFixed#structfield.patch).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn patch<T0>(self, value: T0) -> MaxPoolFixedBuilder<(T0,)>
+            where
+                T0: ::planus::WriteAs<::planus::Offset<self::PatchSpec>>,
+            {
+                MaxPoolFixedBuilder((value,))
+            }
+        }
+
+        impl<T0> MaxPoolFixedBuilder<(T0,)> {
+            /// Setter for the [`input_shape` field](MaxPoolFixed#structfield.input_shape).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn input_shape<T1>(self, value: T1) -> MaxPoolFixedBuilder<(T0, T1)>
+            where
+                T1: ::planus::WriteAs<::planus::Offset<self::DataShape>>,
+            {
+                let (v0,) = self.0;
+                MaxPoolFixedBuilder((v0, value))
+            }
+        }
+
+        impl<T0, T1> MaxPoolFixedBuilder<(T0, T1)> {
+            /// Setter for the [`output_shape` field](MaxPoolFixed#structfield.output_shape).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn output_shape<T2>(self, value: T2) -> MaxPoolFixedBuilder<(T0, T1, T2)>
+            where
+                T2: ::planus::WriteAs<::planus::Offset<self::DataShape>>,
+            {
+                let (v0, v1) = self.0;
+                MaxPoolFixedBuilder((v0, v1, value))
+            }
+        }
+
+        impl<T0, T1, T2> MaxPoolFixedBuilder<(T0, T1, T2)> {
+            /// Setter for the [`index_datum_type` field](MaxPoolFixed#structfield.index_datum_type).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn index_datum_type<T3>(self, value: T3) -> MaxPoolFixedBuilder<(T0, T1, T2, T3)>
+            where
+                T3: ::planus::WriteAsOptional<self::DatumType>,
+            {
+                let (v0, v1, v2) = self.0;
+                MaxPoolFixedBuilder((v0, v1, v2, value))
+            }
+
+            /// Sets the [`index_datum_type` field](MaxPoolFixed#structfield.index_datum_type) to null.
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn index_datum_type_as_null(self) -> MaxPoolFixedBuilder<(T0, T1, T2, ())> {
+                self.index_datum_type(())
+            }
+        }
+
+        impl<T0, T1, T2, T3> MaxPoolFixedBuilder<(T0, T1, T2, T3)> {
+            /// Finish writing the builder to get an [Offset](::planus::Offset) to a serialized [MaxPoolFixed].
+            #[inline]
+            pub fn finish(self, builder: &mut ::planus::Builder) -> ::planus::Offset<MaxPoolFixed>
+            where
+                Self: ::planus::WriteAsOffset<MaxPoolFixed>,
+            {
+                ::planus::WriteAsOffset::prepare(&self, builder)
+            }
+        }
+
+        impl<
+                T0: ::planus::WriteAs<::planus::Offset<self::PatchSpec>>,
+                T1: ::planus::WriteAs<::planus::Offset<self::DataShape>>,
+                T2: ::planus::WriteAs<::planus::Offset<self::DataShape>>,
+                T3: ::planus::WriteAsOptional<self::DatumType>,
+            > ::planus::WriteAs<::planus::Offset<MaxPoolFixed>>
+            for MaxPoolFixedBuilder<(T0, T1, T2, T3)>
+        {
+            type Prepared = ::planus::Offset<MaxPoolFixed>;
+
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<MaxPoolFixed> {
+                ::planus::WriteAsOffset::prepare(self, builder)
+            }
+        }
+
+        impl<
+                T0: ::planus::WriteAs<::planus::Offset<self::PatchSpec>>,
+                T1: ::planus::WriteAs<::planus::Offset<self::DataShape>>,
+                T2: ::planus::WriteAs<::planus::Offset<self::DataShape>>,
+                T3: ::planus::WriteAsOptional<self::DatumType>,
+            > ::planus::WriteAsOptional<::planus::Offset<MaxPoolFixed>>
+            for MaxPoolFixedBuilder<(T0, T1, T2, T3)>
+        {
+            type Prepared = ::planus::Offset<MaxPoolFixed>;
+
+            #[inline]
+            fn prepare(
+                &self,
+                builder: &mut ::planus::Builder,
+            ) -> ::core::option::Option<::planus::Offset<MaxPoolFixed>> {
+                ::core::option::Option::Some(::planus::WriteAsOffset::prepare(self, builder))
+            }
+        }
+
+        impl<
+                T0: ::planus::WriteAs<::planus::Offset<self::PatchSpec>>,
+                T1: ::planus::WriteAs<::planus::Offset<self::DataShape>>,
+                T2: ::planus::WriteAs<::planus::Offset<self::DataShape>>,
+                T3: ::planus::WriteAsOptional<self::DatumType>,
+            > ::planus::WriteAsOffset<MaxPoolFixed> for MaxPoolFixedBuilder<(T0, T1, T2, T3)>
+        {
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<MaxPoolFixed> {
+                let (v0, v1, v2, v3) = &self.0;
+                MaxPoolFixed::create(builder, v0, v1, v2, v3)
+            }
+        }
+
+        /// Reference to a deserialized [MaxPoolFixed].
+        #[derive(Copy, Clone)]
+        pub struct MaxPoolFixedRef<'a>(#[allow(dead_code)] ::planus::table_reader::Table<'a>);
+
+        impl<'a> MaxPoolFixedRef<'a> {
+            /// Getter for the [`patch` field](MaxPoolFixed#structfield.patch).
+            #[inline]
+            pub fn patch(&self) -> ::planus::Result<self::PatchSpecRef<'a>> {
+                self.0.access_required(0, "MaxPoolFixed", "patch")
+            }
+
+            /// Getter for the [`input_shape` field](MaxPoolFixed#structfield.input_shape).
+            #[inline]
+            pub fn input_shape(&self) -> ::planus::Result<self::DataShapeRef<'a>> {
+                self.0.access_required(1, "MaxPoolFixed", "input_shape")
+            }
+
+            /// Getter for the [`output_shape` field](MaxPoolFixed#structfield.output_shape).
+            #[inline]
+            pub fn output_shape(&self) -> ::planus::Result<self::DataShapeRef<'a>> {
+                self.0.access_required(2, "MaxPoolFixed", "output_shape")
+            }
+
+            /// Getter for the [`index_datum_type` field](MaxPoolFixed#structfield.index_datum_type).
+            #[inline]
+            pub fn index_datum_type(
+                &self,
+            ) -> ::planus::Result<::core::option::Option<self::DatumType>> {
+                self.0.access(3, "MaxPoolFixed", "index_datum_type")
+            }
+        }
+
+        impl<'a> ::core::fmt::Debug for MaxPoolFixedRef<'a> {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                let mut f = f.debug_struct("MaxPoolFixedRef");
+                f.field("patch", &self.patch());
+                f.field("input_shape", &self.input_shape());
+                f.field("output_shape", &self.output_shape());
+                if let ::core::option::Option::Some(field_index_datum_type) =
+                    self.index_datum_type().transpose()
+                {
+                    f.field("index_datum_type", &field_index_datum_type);
+                }
+                f.finish()
+            }
+        }
+
+        impl<'a> ::core::convert::TryFrom<MaxPoolFixedRef<'a>> for MaxPoolFixed {
+            type Error = ::planus::Error;
+
+            #[allow(unreachable_code)]
+            fn try_from(value: MaxPoolFixedRef<'a>) -> ::planus::Result<Self> {
+                ::core::result::Result::Ok(Self {
+                    patch: ::planus::alloc::boxed::Box::new(::core::convert::TryInto::try_into(
+                        value.patch()?,
+                    )?),
+                    input_shape: ::planus::alloc::boxed::Box::new(
+                        ::core::convert::TryInto::try_into(value.input_shape()?)?,
+                    ),
+                    output_shape: ::planus::alloc::boxed::Box::new(
+                        ::core::convert::TryInto::try_into(value.output_shape()?)?,
+                    ),
+                    index_datum_type: if let ::core::option::Option::Some(index_datum_type) =
+                        value.index_datum_type()?
+                    {
+                        ::core::option::Option::Some(::core::convert::TryInto::try_into(
+                            index_datum_type,
+                        )?)
+                    } else {
+                        ::core::option::Option::None
+                    },
+                })
+            }
+        }
+
+        impl<'a> ::planus::TableRead<'a> for MaxPoolFixedRef<'a> {
+            #[inline]
+            fn from_buffer(
+                buffer: ::planus::SliceWithStartOffset<'a>,
+                offset: usize,
+            ) -> ::core::result::Result<Self, ::planus::errors::ErrorKind> {
+                ::core::result::Result::Ok(Self(::planus::table_reader::Table::from_buffer(
+                    buffer, offset,
+                )?))
+            }
+        }
+
+        impl<'a> ::planus::VectorReadInner<'a> for MaxPoolFixedRef<'a> {
+            type Error = ::planus::Error;
+            const STRIDE: usize = 4;
+
+            unsafe fn from_buffer(
+                buffer: ::planus::SliceWithStartOffset<'a>,
+                offset: usize,
+            ) -> ::planus::Result<Self> {
+                ::planus::TableRead::from_buffer(buffer, offset).map_err(|error_kind| {
+                    error_kind.with_error_location(
+                        "[MaxPoolFixedRef]",
+                        "get",
+                        buffer.offset_from_start,
+                    )
+                })
+            }
+        }
+
+        /// # Safety
+        /// The planus compiler generates implementations that initialize
+        /// the bytes in `write_values`.
+        unsafe impl ::planus::VectorWrite<::planus::Offset<MaxPoolFixed>> for MaxPoolFixed {
+            type Value = ::planus::Offset<MaxPoolFixed>;
+            const STRIDE: usize = 4;
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> Self::Value {
+                ::planus::WriteAs::prepare(self, builder)
+            }
+
+            #[inline]
+            unsafe fn write_values(
+                values: &[::planus::Offset<MaxPoolFixed>],
+                bytes: *mut ::core::mem::MaybeUninit<u8>,
+                buffer_position: u32,
+            ) {
+                let bytes = bytes as *mut [::core::mem::MaybeUninit<u8>; 4];
+                for (i, v) in ::core::iter::Iterator::enumerate(values.iter()) {
+                    ::planus::WriteAsPrimitive::write(
+                        v,
+                        ::planus::Cursor::new(unsafe { &mut *bytes.add(i) }),
+                        buffer_position - (Self::STRIDE * i) as u32,
+                    );
+                }
+            }
+        }
+
+        impl<'a> ::planus::ReadAsRoot<'a> for MaxPoolFixedRef<'a> {
+            fn read_as_root(slice: &'a [u8]) -> ::planus::Result<Self> {
+                ::planus::TableRead::from_buffer(
+                    ::planus::SliceWithStartOffset { buffer: slice, offset_from_start: 0 },
+                    0,
+                )
+                .map_err(|error_kind| {
+                    error_kind.with_error_location("[MaxPoolFixedRef]", "read_as_root", 0)
+                })
+            }
+        }
+
+        /// The table `AvgPoolFixed` in the namespace `tract`
+        ///
+        /// Generated from these locations:
+        /// * Table `AvgPoolFixed` in the file `schema/tract.fbs:392`
+        #[derive(
+            Clone,
+            Debug,
+            PartialEq,
+            PartialOrd,
+            Eq,
+            Ord,
+            Hash,
+            ::serde::Serialize,
+            ::serde::Deserialize,
+        )]
+        pub struct AvgPoolFixed {
+            /// The field `patch` in the table `AvgPoolFixed`
+            pub patch: ::planus::alloc::boxed::Box<self::PatchSpec>,
+            /// The field `input_shape` in the table `AvgPoolFixed`
+            pub input_shape: ::planus::alloc::boxed::Box<self::DataShape>,
+            /// The field `output_shape` in the table `AvgPoolFixed`
+            pub output_shape: ::planus::alloc::boxed::Box<self::DataShape>,
+            /// The field `count_include_pad` in the table `AvgPoolFixed`
+            pub count_include_pad: bool,
+        }
+
+        #[allow(clippy::derivable_impls)]
+        impl ::core::default::Default for AvgPoolFixed {
+            fn default() -> Self {
+                Self {
+                    patch: ::core::default::Default::default(),
+                    input_shape: ::core::default::Default::default(),
+                    output_shape: ::core::default::Default::default(),
+                    count_include_pad: false,
+                }
+            }
+        }
+
+        impl AvgPoolFixed {
+            /// Creates a [AvgPoolFixedBuilder] for serializing an instance of this table.
+            #[inline]
+            pub fn builder() -> AvgPoolFixedBuilder<()> {
+                AvgPoolFixedBuilder(())
+            }
+
+            #[allow(clippy::too_many_arguments)]
+            pub fn create(
+                builder: &mut ::planus::Builder,
+                field_patch: impl ::planus::WriteAs<::planus::Offset<self::PatchSpec>>,
+                field_input_shape: impl ::planus::WriteAs<::planus::Offset<self::DataShape>>,
+                field_output_shape: impl ::planus::WriteAs<::planus::Offset<self::DataShape>>,
+                field_count_include_pad: impl ::planus::WriteAsDefault<bool, bool>,
+            ) -> ::planus::Offset<Self> {
+                let prepared_patch = field_patch.prepare(builder);
+                let prepared_input_shape = field_input_shape.prepare(builder);
+                let prepared_output_shape = field_output_shape.prepare(builder);
+                let prepared_count_include_pad = field_count_include_pad.prepare(builder, &false);
+
+                let mut table_writer: ::planus::table_writer::TableWriter<12> =
+                    ::core::default::Default::default();
+                table_writer.write_entry::<::planus::Offset<self::PatchSpec>>(0);
+                table_writer.write_entry::<::planus::Offset<self::DataShape>>(1);
+                table_writer.write_entry::<::planus::Offset<self::DataShape>>(2);
+                if prepared_count_include_pad.is_some() {
+                    table_writer.write_entry::<bool>(3);
+                }
+
+                unsafe {
+                    table_writer.finish(builder, |object_writer| {
+                        object_writer.write::<_, _, 4>(&prepared_patch);
+                        object_writer.write::<_, _, 4>(&prepared_input_shape);
+                        object_writer.write::<_, _, 4>(&prepared_output_shape);
+                        if let ::core::option::Option::Some(prepared_count_include_pad) =
+                            prepared_count_include_pad
+                        {
+                            object_writer.write::<_, _, 1>(&prepared_count_include_pad);
+                        }
+                    });
+                }
+                builder.current_offset()
+            }
+        }
+
+        impl ::planus::WriteAs<::planus::Offset<AvgPoolFixed>> for AvgPoolFixed {
+            type Prepared = ::planus::Offset<Self>;
+
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<AvgPoolFixed> {
+                ::planus::WriteAsOffset::prepare(self, builder)
+            }
+        }
+
+        impl ::planus::WriteAsOptional<::planus::Offset<AvgPoolFixed>> for AvgPoolFixed {
+            type Prepared = ::planus::Offset<Self>;
+
+            #[inline]
+            fn prepare(
+                &self,
+                builder: &mut ::planus::Builder,
+            ) -> ::core::option::Option<::planus::Offset<AvgPoolFixed>> {
+                ::core::option::Option::Some(::planus::WriteAsOffset::prepare(self, builder))
+            }
+        }
+
+        impl ::planus::WriteAsOffset<AvgPoolFixed> for AvgPoolFixed {
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<AvgPoolFixed> {
+                AvgPoolFixed::create(
+                    builder,
+                    &self.patch,
+                    &self.input_shape,
+                    &self.output_shape,
+                    self.count_include_pad,
+                )
+            }
+        }
+
+        /// Builder for serializing an instance of the [AvgPoolFixed] type.
+        ///
+        /// Can be created using the [AvgPoolFixed::builder] method.
+        #[derive(Debug)]
+        #[must_use]
+        pub struct AvgPoolFixedBuilder<State>(State);
+
+        impl AvgPoolFixedBuilder<()> {
+            /// Setter for the [`patch` field](AvgPoolFixed#structfield.patch).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn patch<T0>(self, value: T0) -> AvgPoolFixedBuilder<(T0,)>
+            where
+                T0: ::planus::WriteAs<::planus::Offset<self::PatchSpec>>,
+            {
+                AvgPoolFixedBuilder((value,))
+            }
+        }
+
+        impl<T0> AvgPoolFixedBuilder<(T0,)> {
+            /// Setter for the [`input_shape` field](AvgPoolFixed#structfield.input_shape).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn input_shape<T1>(self, value: T1) -> AvgPoolFixedBuilder<(T0, T1)>
+            where
+                T1: ::planus::WriteAs<::planus::Offset<self::DataShape>>,
+            {
+                let (v0,) = self.0;
+                AvgPoolFixedBuilder((v0, value))
+            }
+        }
+
+        impl<T0, T1> AvgPoolFixedBuilder<(T0, T1)> {
+            /// Setter for the [`output_shape` field](AvgPoolFixed#structfield.output_shape).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn output_shape<T2>(self, value: T2) -> AvgPoolFixedBuilder<(T0, T1, T2)>
+            where
+                T2: ::planus::WriteAs<::planus::Offset<self::DataShape>>,
+            {
+                let (v0, v1) = self.0;
+                AvgPoolFixedBuilder((v0, v1, value))
+            }
+        }
+
+        impl<T0, T1, T2> AvgPoolFixedBuilder<(T0, T1, T2)> {
+            /// Setter for the [`count_include_pad` field](AvgPoolFixed#structfield.count_include_pad).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn count_include_pad<T3>(self, value: T3) -> AvgPoolFixedBuilder<(T0, T1, T2, T3)>
+            where
+                T3: ::planus::WriteAsDefault<bool, bool>,
+            {
+                let (v0, v1, v2) = self.0;
+                AvgPoolFixedBuilder((v0, v1, v2, value))
+            }
+
+            /// Sets the [`count_include_pad` field](AvgPoolFixed#structfield.count_include_pad) to the default value.
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn count_include_pad_as_default(
+                self,
+            ) -> AvgPoolFixedBuilder<(T0, T1, T2, ::planus::DefaultValue)> {
+                self.count_include_pad(::planus::DefaultValue)
+            }
+        }
+
+        impl<T0, T1, T2, T3> AvgPoolFixedBuilder<(T0, T1, T2, T3)> {
+            /// Finish writing the builder to get an [Offset](::planus::Offset) to a serialized [AvgPoolFixed].
+            #[inline]
+            pub fn finish(self, builder: &mut ::planus::Builder) -> ::planus::Offset<AvgPoolFixed>
+            where
+                Self: ::planus::WriteAsOffset<AvgPoolFixed>,
+            {
+                ::planus::WriteAsOffset::prepare(&self, builder)
+            }
+        }
+
+        impl<
+                T0: ::planus::WriteAs<::planus::Offset<self::PatchSpec>>,
+                T1: ::planus::WriteAs<::planus::Offset<self::DataShape>>,
+                T2: ::planus::WriteAs<::planus::Offset<self::DataShape>>,
+                T3: ::planus::WriteAsDefault<bool, bool>,
+            > ::planus::WriteAs<::planus::Offset<AvgPoolFixed>>
+            for AvgPoolFixedBuilder<(T0, T1, T2, T3)>
+        {
+            type Prepared = ::planus::Offset<AvgPoolFixed>;
+
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<AvgPoolFixed> {
+                ::planus::WriteAsOffset::prepare(self, builder)
+            }
+        }
+
+        impl<
+                T0: ::planus::WriteAs<::planus::Offset<self::PatchSpec>>,
+                T1: ::planus::WriteAs<::planus::Offset<self::DataShape>>,
+                T2: ::planus::WriteAs<::planus::Offset<self::DataShape>>,
+                T3: ::planus::WriteAsDefault<bool, bool>,
+            > ::planus::WriteAsOptional<::planus::Offset<AvgPoolFixed>>
+            for AvgPoolFixedBuilder<(T0, T1, T2, T3)>
+        {
+            type Prepared = ::planus::Offset<AvgPoolFixed>;
+
+            #[inline]
+            fn prepare(
+                &self,
+                builder: &mut ::planus::Builder,
+            ) -> ::core::option::Option<::planus::Offset<AvgPoolFixed>> {
+                ::core::option::Option::Some(::planus::WriteAsOffset::prepare(self, builder))
+            }
+        }
+
+        impl<
+                T0: ::planus::WriteAs<::planus::Offset<self::PatchSpec>>,
+                T1: ::planus::WriteAs<::planus::Offset<self::DataShape>>,
+                T2: ::planus::WriteAs<::planus::Offset<self::DataShape>>,
+                T3: ::planus::WriteAsDefault<bool, bool>,
+            > ::planus::WriteAsOffset<AvgPoolFixed> for AvgPoolFixedBuilder<(T0, T1, T2, T3)>
+        {
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<AvgPoolFixed> {
+                let (v0, v1, v2, v3) = &self.0;
+                AvgPoolFixed::create(builder, v0, v1, v2, v3)
+            }
+        }
+
+        /// Reference to a deserialized [AvgPoolFixed].
+        #[derive(Copy, Clone)]
+        pub struct AvgPoolFixedRef<'a>(#[allow(dead_code)] ::planus::table_reader::Table<'a>);
+
+        impl<'a> AvgPoolFixedRef<'a> {
+            /// Getter for the [`patch` field](AvgPoolFixed#structfield.patch).
+            #[inline]
+            pub fn patch(&self) -> ::planus::Result<self::PatchSpecRef<'a>> {
+                self.0.access_required(0, "AvgPoolFixed", "patch")
+            }
+
+            /// Getter for the [`input_shape` field](AvgPoolFixed#structfield.input_shape).
+            #[inline]
+            pub fn input_shape(&self) -> ::planus::Result<self::DataShapeRef<'a>> {
+                self.0.access_required(1, "AvgPoolFixed", "input_shape")
+            }
+
+            /// Getter for the [`output_shape` field](AvgPoolFixed#structfield.output_shape).
+            #[inline]
+            pub fn output_shape(&self) -> ::planus::Result<self::DataShapeRef<'a>> {
+                self.0.access_required(2, "AvgPoolFixed", "output_shape")
+            }
+
+            /// Getter for the [`count_include_pad` field](AvgPoolFixed#structfield.count_include_pad).
+            #[inline]
+            pub fn count_include_pad(&self) -> ::planus::Result<bool> {
+                ::core::result::Result::Ok(
+                    self.0.access(3, "AvgPoolFixed", "count_include_pad")?.unwrap_or(false),
+                )
+            }
+        }
+
+        impl<'a> ::core::fmt::Debug for AvgPoolFixedRef<'a> {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                let mut f = f.debug_struct("AvgPoolFixedRef");
+                f.field("patch", &self.patch());
+                f.field("input_shape", &self.input_shape());
+                f.field("output_shape", &self.output_shape());
+                f.field("count_include_pad", &self.count_include_pad());
+                f.finish()
+            }
+        }
+
+        impl<'a> ::core::convert::TryFrom<AvgPoolFixedRef<'a>> for AvgPoolFixed {
+            type Error = ::planus::Error;
+
+            #[allow(unreachable_code)]
+            fn try_from(value: AvgPoolFixedRef<'a>) -> ::planus::Result<Self> {
+                ::core::result::Result::Ok(Self {
+                    patch: ::planus::alloc::boxed::Box::new(::core::convert::TryInto::try_into(
+                        value.patch()?,
+                    )?),
+                    input_shape: ::planus::alloc::boxed::Box::new(
+                        ::core::convert::TryInto::try_into(value.input_shape()?)?,
+                    ),
+                    output_shape: ::planus::alloc::boxed::Box::new(
+                        ::core::convert::TryInto::try_into(value.output_shape()?)?,
+                    ),
+                    count_include_pad: ::core::convert::TryInto::try_into(
+                        value.count_include_pad()?,
+                    )?,
+                })
+            }
+        }
+
+        impl<'a> ::planus::TableRead<'a> for AvgPoolFixedRef<'a> {
+            #[inline]
+            fn from_buffer(
+                buffer: ::planus::SliceWithStartOffset<'a>,
+                offset: usize,
+            ) -> ::core::result::Result<Self, ::planus::errors::ErrorKind> {
+                ::core::result::Result::Ok(Self(::planus::table_reader::Table::from_buffer(
+                    buffer, offset,
+                )?))
+            }
+        }
+
+        impl<'a> ::planus::VectorReadInner<'a> for AvgPoolFixedRef<'a> {
+            type Error = ::planus::Error;
+            const STRIDE: usize = 4;
+
+            unsafe fn from_buffer(
+                buffer: ::planus::SliceWithStartOffset<'a>,
+                offset: usize,
+            ) -> ::planus::Result<Self> {
+                ::planus::TableRead::from_buffer(buffer, offset).map_err(|error_kind| {
+                    error_kind.with_error_location(
+                        "[AvgPoolFixedRef]",
+                        "get",
+                        buffer.offset_from_start,
+                    )
+                })
+            }
+        }
+
+        /// # Safety
+        /// The planus compiler generates implementations that initialize
+        /// the bytes in `write_values`.
+        unsafe impl ::planus::VectorWrite<::planus::Offset<AvgPoolFixed>> for AvgPoolFixed {
+            type Value = ::planus::Offset<AvgPoolFixed>;
+            const STRIDE: usize = 4;
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> Self::Value {
+                ::planus::WriteAs::prepare(self, builder)
+            }
+
+            #[inline]
+            unsafe fn write_values(
+                values: &[::planus::Offset<AvgPoolFixed>],
+                bytes: *mut ::core::mem::MaybeUninit<u8>,
+                buffer_position: u32,
+            ) {
+                let bytes = bytes as *mut [::core::mem::MaybeUninit<u8>; 4];
+                for (i, v) in ::core::iter::Iterator::enumerate(values.iter()) {
+                    ::planus::WriteAsPrimitive::write(
+                        v,
+                        ::planus::Cursor::new(unsafe { &mut *bytes.add(i) }),
+                        buffer_position - (Self::STRIDE * i) as u32,
+                    );
+                }
+            }
+        }
+
+        impl<'a> ::planus::ReadAsRoot<'a> for AvgPoolFixedRef<'a> {
+            fn read_as_root(slice: &'a [u8]) -> ::planus::Result<Self> {
+                ::planus::TableRead::from_buffer(
+                    ::planus::SliceWithStartOffset { buffer: slice, offset_from_start: 0 },
+                    0,
+                )
+                .map_err(|error_kind| {
+                    error_kind.with_error_location("[AvgPoolFixedRef]", "read_as_root", 0)
+                })
+            }
+        }
+
+        /// The table `DepthWise` in the namespace `tract`
+        ///
+        /// Generated from these locations:
+        /// * Table `DepthWise` in the file `schema/tract.fbs:399`
+        #[derive(Clone, Debug, PartialEq, PartialOrd, ::serde::Serialize, ::serde::Deserialize)]
+        pub struct DepthWise {
+            /// The field `patch` in the table `DepthWise`
+            pub patch: ::planus::alloc::boxed::Box<self::PatchSpec>,
+            /// The field `input_shape` in the table `DepthWise`
+            pub input_shape: ::planus::alloc::boxed::Box<self::DataShape>,
+            /// The field `output_shape` in the table `DepthWise`
+            pub output_shape: ::planus::alloc::boxed::Box<self::DataShape>,
+            /// The field `kernel` in the table `DepthWise`
+            pub kernel: ::planus::alloc::boxed::Box<self::Tensor>,
+            /// The field `bias` in the table `DepthWise`
+            pub bias: ::core::option::Option<::planus::alloc::vec::Vec<f32>>,
+        }
+
+        #[allow(clippy::derivable_impls)]
+        impl ::core::default::Default for DepthWise {
+            fn default() -> Self {
+                Self {
+                    patch: ::core::default::Default::default(),
+                    input_shape: ::core::default::Default::default(),
+                    output_shape: ::core::default::Default::default(),
+                    kernel: ::core::default::Default::default(),
+                    bias: ::core::default::Default::default(),
+                }
+            }
+        }
+
+        impl DepthWise {
+            /// Creates a [DepthWiseBuilder] for serializing an instance of this table.
+            #[inline]
+            pub fn builder() -> DepthWiseBuilder<()> {
+                DepthWiseBuilder(())
+            }
+
+            #[allow(clippy::too_many_arguments)]
+            pub fn create(
+                builder: &mut ::planus::Builder,
+                field_patch: impl ::planus::WriteAs<::planus::Offset<self::PatchSpec>>,
+                field_input_shape: impl ::planus::WriteAs<::planus::Offset<self::DataShape>>,
+                field_output_shape: impl ::planus::WriteAs<::planus::Offset<self::DataShape>>,
+                field_kernel: impl ::planus::WriteAs<::planus::Offset<self::Tensor>>,
+                field_bias: impl ::planus::WriteAsOptional<::planus::Offset<[f32]>>,
+            ) -> ::planus::Offset<Self> {
+                let prepared_patch = field_patch.prepare(builder);
+                let prepared_input_shape = field_input_shape.prepare(builder);
+                let prepared_output_shape = field_output_shape.prepare(builder);
+                let prepared_kernel = field_kernel.prepare(builder);
+                let prepared_bias = field_bias.prepare(builder);
+
+                let mut table_writer: ::planus::table_writer::TableWriter<14> =
+                    ::core::default::Default::default();
+                table_writer.write_entry::<::planus::Offset<self::PatchSpec>>(0);
+                table_writer.write_entry::<::planus::Offset<self::DataShape>>(1);
+                table_writer.write_entry::<::planus::Offset<self::DataShape>>(2);
+                table_writer.write_entry::<::planus::Offset<self::Tensor>>(3);
+                if prepared_bias.is_some() {
+                    table_writer.write_entry::<::planus::Offset<[f32]>>(4);
+                }
+
+                unsafe {
+                    table_writer.finish(builder, |object_writer| {
+                        object_writer.write::<_, _, 4>(&prepared_patch);
+                        object_writer.write::<_, _, 4>(&prepared_input_shape);
+                        object_writer.write::<_, _, 4>(&prepared_output_shape);
+                        object_writer.write::<_, _, 4>(&prepared_kernel);
+                        if let ::core::option::Option::Some(prepared_bias) = prepared_bias {
+                            object_writer.write::<_, _, 4>(&prepared_bias);
+                        }
+                    });
+                }
+                builder.current_offset()
+            }
+        }
+
+        impl ::planus::WriteAs<::planus::Offset<DepthWise>> for DepthWise {
+            type Prepared = ::planus::Offset<Self>;
+
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<DepthWise> {
+                ::planus::WriteAsOffset::prepare(self, builder)
+            }
+        }
+
+        impl ::planus::WriteAsOptional<::planus::Offset<DepthWise>> for DepthWise {
+            type Prepared = ::planus::Offset<Self>;
+
+            #[inline]
+            fn prepare(
+                &self,
+                builder: &mut ::planus::Builder,
+            ) -> ::core::option::Option<::planus::Offset<DepthWise>> {
+                ::core::option::Option::Some(::planus::WriteAsOffset::prepare(self, builder))
+            }
+        }
+
+        impl ::planus::WriteAsOffset<DepthWise> for DepthWise {
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<DepthWise> {
+                DepthWise::create(
+                    builder,
+                    &self.patch,
+                    &self.input_shape,
+                    &self.output_shape,
+                    &self.kernel,
+                    &self.bias,
+                )
+            }
+        }
+
+        /// Builder for serializing an instance of the [DepthWise] type.
+        ///
+        /// Can be created using the [DepthWise::builder] method.
+        #[derive(Debug)]
+        #[must_use]
+        pub struct DepthWiseBuilder<State>(State);
+
+        impl DepthWiseBuilder<()> {
+            /// Setter for the [`patch` field](DepthWise#structfield.patch).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn patch<T0>(self, value: T0) -> DepthWiseBuilder<(T0,)>
+            where
+                T0: ::planus::WriteAs<::planus::Offset<self::PatchSpec>>,
+            {
+                DepthWiseBuilder((value,))
+            }
+        }
+
+        impl<T0> DepthWiseBuilder<(T0,)> {
+            /// Setter for the [`input_shape` field](DepthWise#structfield.input_shape).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn input_shape<T1>(self, value: T1) -> DepthWiseBuilder<(T0, T1)>
+            where
+                T1: ::planus::WriteAs<::planus::Offset<self::DataShape>>,
+            {
+                let (v0,) = self.0;
+                DepthWiseBuilder((v0, value))
+            }
+        }
+
+        impl<T0, T1> DepthWiseBuilder<(T0, T1)> {
+            /// Setter for the [`output_shape` field](DepthWise#structfield.output_shape).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn output_shape<T2>(self, value: T2) -> DepthWiseBuilder<(T0, T1, T2)>
+            where
+                T2: ::planus::WriteAs<::planus::Offset<self::DataShape>>,
+            {
+                let (v0, v1) = self.0;
+                DepthWiseBuilder((v0, v1, value))
+            }
+        }
+
+        impl<T0, T1, T2> DepthWiseBuilder<(T0, T1, T2)> {
+            /// Setter for the [`kernel` field](DepthWise#structfield.kernel).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn kernel<T3>(self, value: T3) -> DepthWiseBuilder<(T0, T1, T2, T3)>
+            where
+                T3: ::planus::WriteAs<::planus::Offset<self::Tensor>>,
+            {
+                let (v0, v1, v2) = self.0;
+                DepthWiseBuilder((v0, v1, v2, value))
+            }
+        }
+
+        impl<T0, T1, T2, T3> DepthWiseBuilder<(T0, T1, T2, T3)> {
+            /// Setter for the [`bias` field](DepthWise#structfield.bias).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn bias<T4>(self, value: T4) -> DepthWiseBuilder<(T0, T1, T2, T3, T4)>
+            where
+                T4: ::planus::WriteAsOptional<::planus::Offset<[f32]>>,
+            {
+                let (v0, v1, v2, v3) = self.0;
+                DepthWiseBuilder((v0, v1, v2, v3, value))
+            }
+
+            /// Sets the [`bias` field](DepthWise#structfield.bias) to null.
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn bias_as_null(self) -> DepthWiseBuilder<(T0, T1, T2, T3, ())> {
+                self.bias(())
+            }
+        }
+
+        impl<T0, T1, T2, T3, T4> DepthWiseBuilder<(T0, T1, T2, T3, T4)> {
+            /// Finish writing the builder to get an [Offset](::planus::Offset) to a serialized [DepthWise].
+            #[inline]
+            pub fn finish(self, builder: &mut ::planus::Builder) -> ::planus::Offset<DepthWise>
+            where
+                Self: ::planus::WriteAsOffset<DepthWise>,
+            {
+                ::planus::WriteAsOffset::prepare(&self, builder)
+            }
+        }
+
+        impl<
+                T0: ::planus::WriteAs<::planus::Offset<self::PatchSpec>>,
+                T1: ::planus::WriteAs<::planus::Offset<self::DataShape>>,
+                T2: ::planus::WriteAs<::planus::Offset<self::DataShape>>,
+                T3: ::planus::WriteAs<::planus::Offset<self::Tensor>>,
+                T4: ::planus::WriteAsOptional<::planus::Offset<[f32]>>,
+            > ::planus::WriteAs<::planus::Offset<DepthWise>>
+            for DepthWiseBuilder<(T0, T1, T2, T3, T4)>
+        {
+            type Prepared = ::planus::Offset<DepthWise>;
+
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<DepthWise> {
+                ::planus::WriteAsOffset::prepare(self, builder)
+            }
+        }
+
+        impl<
+                T0: ::planus::WriteAs<::planus::Offset<self::PatchSpec>>,
+                T1: ::planus::WriteAs<::planus::Offset<self::DataShape>>,
+                T2: ::planus::WriteAs<::planus::Offset<self::DataShape>>,
+                T3: ::planus::WriteAs<::planus::Offset<self::Tensor>>,
+                T4: ::planus::WriteAsOptional<::planus::Offset<[f32]>>,
+            > ::planus::WriteAsOptional<::planus::Offset<DepthWise>>
+            for DepthWiseBuilder<(T0, T1, T2, T3, T4)>
+        {
+            type Prepared = ::planus::Offset<DepthWise>;
+
+            #[inline]
+            fn prepare(
+                &self,
+                builder: &mut ::planus::Builder,
+            ) -> ::core::option::Option<::planus::Offset<DepthWise>> {
+                ::core::option::Option::Some(::planus::WriteAsOffset::prepare(self, builder))
+            }
+        }
+
+        impl<
+                T0: ::planus::WriteAs<::planus::Offset<self::PatchSpec>>,
+                T1: ::planus::WriteAs<::planus::Offset<self::DataShape>>,
+                T2: ::planus::WriteAs<::planus::Offset<self::DataShape>>,
+                T3: ::planus::WriteAs<::planus::Offset<self::Tensor>>,
+                T4: ::planus::WriteAsOptional<::planus::Offset<[f32]>>,
+            > ::planus::WriteAsOffset<DepthWise> for DepthWiseBuilder<(T0, T1, T2, T3, T4)>
+        {
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<DepthWise> {
+                let (v0, v1, v2, v3, v4) = &self.0;
+                DepthWise::create(builder, v0, v1, v2, v3, v4)
+            }
+        }
+
+        /// Reference to a deserialized [DepthWise].
+        #[derive(Copy, Clone)]
+        pub struct DepthWiseRef<'a>(#[allow(dead_code)] ::planus::table_reader::Table<'a>);
+
+        impl<'a> DepthWiseRef<'a> {
+            /// Getter for the [`patch` field](DepthWise#structfield.patch).
+            #[inline]
+            pub fn patch(&self) -> ::planus::Result<self::PatchSpecRef<'a>> {
+                self.0.access_required(0, "DepthWise", "patch")
+            }
+
+            /// Getter for the [`input_shape` field](DepthWise#structfield.input_shape).
+            #[inline]
+            pub fn input_shape(&self) -> ::planus::Result<self::DataShapeRef<'a>> {
+                self.0.access_required(1, "DepthWise", "input_shape")
+            }
+
+            /// Getter for the [`output_shape` field](DepthWise#structfield.output_shape).
+            #[inline]
+            pub fn output_shape(&self) -> ::planus::Result<self::DataShapeRef<'a>> {
+                self.0.access_required(2, "DepthWise", "output_shape")
+            }
+
+            /// Getter for the [`kernel` field](DepthWise#structfield.kernel).
+            #[inline]
+            pub fn kernel(&self) -> ::planus::Result<self::TensorRef<'a>> {
+                self.0.access_required(3, "DepthWise", "kernel")
+            }
+
+            /// Getter for the [`bias` field](DepthWise#structfield.bias).
+            #[inline]
+            pub fn bias(
+                &self,
+            ) -> ::planus::Result<::core::option::Option<::planus::Vector<'a, f32>>> {
+                self.0.access(4, "DepthWise", "bias")
+            }
+        }
+
+        impl<'a> ::core::fmt::Debug for DepthWiseRef<'a> {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                let mut f = f.debug_struct("DepthWiseRef");
+                f.field("patch", &self.patch());
+                f.field("input_shape", &self.input_shape());
+                f.field("output_shape", &self.output_shape());
+                f.field("kernel", &self.kernel());
+                if let ::core::option::Option::Some(field_bias) = self.bias().transpose() {
+                    f.field("bias", &field_bias);
+                }
+                f.finish()
+            }
+        }
+
+        impl<'a> ::core::convert::TryFrom<DepthWiseRef<'a>> for DepthWise {
+            type Error = ::planus::Error;
+
+            #[allow(unreachable_code)]
+            fn try_from(value: DepthWiseRef<'a>) -> ::planus::Result<Self> {
+                ::core::result::Result::Ok(Self {
+                    patch: ::planus::alloc::boxed::Box::new(::core::convert::TryInto::try_into(
+                        value.patch()?,
+                    )?),
+                    input_shape: ::planus::alloc::boxed::Box::new(
+                        ::core::convert::TryInto::try_into(value.input_shape()?)?,
+                    ),
+                    output_shape: ::planus::alloc::boxed::Box::new(
+                        ::core::convert::TryInto::try_into(value.output_shape()?)?,
+                    ),
+                    kernel: ::planus::alloc::boxed::Box::new(::core::convert::TryInto::try_into(
+                        value.kernel()?,
+                    )?),
+                    bias: if let ::core::option::Option::Some(bias) = value.bias()? {
+                        ::core::option::Option::Some(bias.to_vec()?)
+                    } else {
+                        ::core::option::Option::None
+                    },
+                })
+            }
+        }
+
+        impl<'a> ::planus::TableRead<'a> for DepthWiseRef<'a> {
+            #[inline]
+            fn from_buffer(
+                buffer: ::planus::SliceWithStartOffset<'a>,
+                offset: usize,
+            ) -> ::core::result::Result<Self, ::planus::errors::ErrorKind> {
+                ::core::result::Result::Ok(Self(::planus::table_reader::Table::from_buffer(
+                    buffer, offset,
+                )?))
+            }
+        }
+
+        impl<'a> ::planus::VectorReadInner<'a> for DepthWiseRef<'a> {
+            type Error = ::planus::Error;
+            const STRIDE: usize = 4;
+
+            unsafe fn from_buffer(
+                buffer: ::planus::SliceWithStartOffset<'a>,
+                offset: usize,
+            ) -> ::planus::Result<Self> {
+                ::planus::TableRead::from_buffer(buffer, offset).map_err(|error_kind| {
+                    error_kind.with_error_location(
+                        "[DepthWiseRef]",
+                        "get",
+                        buffer.offset_from_start,
+                    )
+                })
+            }
+        }
+
+        /// # Safety
+        /// The planus compiler generates implementations that initialize
+        /// the bytes in `write_values`.
+        unsafe impl ::planus::VectorWrite<::planus::Offset<DepthWise>> for DepthWise {
+            type Value = ::planus::Offset<DepthWise>;
+            const STRIDE: usize = 4;
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> Self::Value {
+                ::planus::WriteAs::prepare(self, builder)
+            }
+
+            #[inline]
+            unsafe fn write_values(
+                values: &[::planus::Offset<DepthWise>],
+                bytes: *mut ::core::mem::MaybeUninit<u8>,
+                buffer_position: u32,
+            ) {
+                let bytes = bytes as *mut [::core::mem::MaybeUninit<u8>; 4];
+                for (i, v) in ::core::iter::Iterator::enumerate(values.iter()) {
+                    ::planus::WriteAsPrimitive::write(
+                        v,
+                        ::planus::Cursor::new(unsafe { &mut *bytes.add(i) }),
+                        buffer_position - (Self::STRIDE * i) as u32,
+                    );
+                }
+            }
+        }
+
+        impl<'a> ::planus::ReadAsRoot<'a> for DepthWiseRef<'a> {
+            fn read_as_root(slice: &'a [u8]) -> ::planus::Result<Self> {
+                ::planus::TableRead::from_buffer(
+                    ::planus::SliceWithStartOffset { buffer: slice, offset_from_start: 0 },
+                    0,
+                )
+                .map_err(|error_kind| {
+                    error_kind.with_error_location("[DepthWiseRef]", "read_as_root", 0)
+                })
+            }
+        }
+
+        /// The union `Op` in the namespace `tract`
+        ///
+        /// Generated from these locations:
+        /// * Union `Op` in the file `schema/tract.fbs:407`
+        #[derive(Clone, Debug, PartialEq, PartialOrd, ::serde::Serialize, ::serde::Deserialize)]
+        pub enum Op {
+            /// The variant of type `Source` in the union `Op`
+            Source(::planus::alloc::boxed::Box<self::Source>),
+
+            /// The variant of type `Const` in the union `Op`
+            Const(::planus::alloc::boxed::Box<self::Const>),
+
+            /// The variant of type `Unary` in the union `Op`
+            Unary(::planus::alloc::boxed::Box<self::Unary>),
+
+            /// The variant of type `Binary` in the union `Op`
+            Binary(::planus::alloc::boxed::Box<self::Binary>),
+
+            /// The variant of type `Merge` in the union `Op`
+            Merge(::planus::alloc::boxed::Box<self::Merge>),
+
+            /// The variant of type `ElementWise` in the union `Op`
+            ElementWise(::planus::alloc::boxed::Box<self::ElementWise>),
+
+            /// The variant of type `Conv` in the union `Op`
+            Conv(::planus::alloc::boxed::Box<self::Conv>),
+
+            /// The variant of type `MaxPool` in the union `Op`
+            MaxPool(::planus::alloc::boxed::Box<self::MaxPool>),
+
+            /// The variant of type `AvgPool` in the union `Op`
+            AvgPool(::planus::alloc::boxed::Box<self::AvgPool>),
+
+            /// The variant of type `GlobalAvgPool` in the union `Op`
+            GlobalAvgPool(::planus::alloc::boxed::Box<self::GlobalAvgPool>),
+
+            /// The variant of type `GlobalMaxPool` in the union `Op`
+            GlobalMaxPool(::planus::alloc::boxed::Box<self::GlobalMaxPool>),
+
+            /// The variant of type `Reduce` in the union `Op`
+            Reduce(::planus::alloc::boxed::Box<self::Reduce>),
+
+            /// The variant of type `MatMul` in the union `Op`
+            MatMul(::planus::alloc::boxed::Box<self::MatMul>),
+
+            /// The variant of type `AddDims` in the union `Op`
+            AddDims(::planus::alloc::boxed::Box<self::AddDims>),
+
+            /// The variant of type `RmDims` in the union `Op`
+            RmDims(::planus::alloc::boxed::Box<self::RmDims>),
+
+            /// The variant of type `PermuteAxes` in the union `Op`
+            PermuteAxes(::planus::alloc::boxed::Box<self::PermuteAxes>),
+
+            /// The variant of type `Reshape` in the union `Op`
+            Reshape(::planus::alloc::boxed::Box<self::Reshape>),
+
+            /// The variant of type `Slice` in the union `Op`
+            Slice(::planus::alloc::boxed::Box<self::Slice>),
+
+            /// The variant of type `If` in the union `Op`
+            If(::planus::alloc::boxed::Box<self::If>),
+
+            /// The variant of type `Loop` in the union `Op`
+            Loop(::planus::alloc::boxed::Box<self::Loop>),
+
+            /// The variant of type `Scan` in the union `Op`
+            Scan(::planus::alloc::boxed::Box<self::Scan>),
+
+            /// The variant of type `Im2Col` in the union `Op`
+            Im2Col(::planus::alloc::boxed::Box<self::Im2Col>),
+
+            /// The variant of type `MatMatMulPackB` in the union `Op`
+            MatMatMulPackB(::planus::alloc::boxed::Box<self::MatMatMulPackB>),
+
+            /// The variant of type `MatMatMul` in the union `Op`
+            MatMatMul(::planus::alloc::boxed::Box<self::MatMatMul>),
+
+            /// The variant of type `MergeUnicast` in the union `Op`
+            MergeUnicast(::planus::alloc::boxed::Box<self::MergeUnicast>),
+
+            /// The variant of type `MaxPoolFixed` in the union `Op`
+            MaxPoolFixed(::planus::alloc::boxed::Box<self::MaxPoolFixed>),
+
+            /// The variant of type `AvgPoolFixed` in the union `Op`
+            AvgPoolFixed(::planus::alloc::boxed::Box<self::AvgPoolFixed>),
+
+            /// The variant of type `DepthWise` in the union `Op`
+            DepthWise(::planus::alloc::boxed::Box<self::DepthWise>),
+        }
+
+        impl Op {
+            /// Creates a [OpBuilder] for serializing an instance of this table.
+            #[inline]
+            pub fn builder() -> OpBuilder<::planus::Uninitialized> {
+                OpBuilder(::planus::Uninitialized)
+            }
+
+            #[inline]
+            pub fn create_source(
+                builder: &mut ::planus::Builder,
+                value: impl ::planus::WriteAsOffset<self::Source>,
+            ) -> ::planus::UnionOffset<Self> {
+                ::planus::UnionOffset::new(1, value.prepare(builder).downcast())
+            }
+
+            #[inline]
+            pub fn create_const(
+                builder: &mut ::planus::Builder,
+                value: impl ::planus::WriteAsOffset<self::Const>,
+            ) -> ::planus::UnionOffset<Self> {
+                ::planus::UnionOffset::new(2, value.prepare(builder).downcast())
+            }
+
+            #[inline]
+            pub fn create_unary(
+                builder: &mut ::planus::Builder,
+                value: impl ::planus::WriteAsOffset<self::Unary>,
+            ) -> ::planus::UnionOffset<Self> {
+                ::planus::UnionOffset::new(3, value.prepare(builder).downcast())
+            }
+
+            #[inline]
+            pub fn create_binary(
+                builder: &mut ::planus::Builder,
+                value: impl ::planus::WriteAsOffset<self::Binary>,
+            ) -> ::planus::UnionOffset<Self> {
+                ::planus::UnionOffset::new(4, value.prepare(builder).downcast())
+            }
+
+            #[inline]
+            pub fn create_merge(
+                builder: &mut ::planus::Builder,
+                value: impl ::planus::WriteAsOffset<self::Merge>,
+            ) -> ::planus::UnionOffset<Self> {
+                ::planus::UnionOffset::new(5, value.prepare(builder).downcast())
+            }
+
+            #[inline]
+            pub fn create_element_wise(
+                builder: &mut ::planus::Builder,
+                value: impl ::planus::WriteAsOffset<self::ElementWise>,
+            ) -> ::planus::UnionOffset<Self> {
+                ::planus::UnionOffset::new(6, value.prepare(builder).downcast())
+            }
+
+            #[inline]
+            pub fn create_conv(
+                builder: &mut ::planus::Builder,
+                value: impl ::planus::WriteAsOffset<self::Conv>,
+            ) -> ::planus::UnionOffset<Self> {
+                ::planus::UnionOffset::new(7, value.prepare(builder).downcast())
+            }
+
+            #[inline]
+            pub fn create_max_pool(
+                builder: &mut ::planus::Builder,
+                value: impl ::planus::WriteAsOffset<self::MaxPool>,
+            ) -> ::planus::UnionOffset<Self> {
+                ::planus::UnionOffset::new(8, value.prepare(builder).downcast())
+            }
+
+            #[inline]
+            pub fn create_avg_pool(
+                builder: &mut ::planus::Builder,
+                value: impl ::planus::WriteAsOffset<self::AvgPool>,
+            ) -> ::planus::UnionOffset<Self> {
+                ::planus::UnionOffset::new(9, value.prepare(builder).downcast())
+            }
+
+            #[inline]
+            pub fn create_global_avg_pool(
+                builder: &mut ::planus::Builder,
+                value: impl ::planus::WriteAsOffset<self::GlobalAvgPool>,
+            ) -> ::planus::UnionOffset<Self> {
+                ::planus::UnionOffset::new(10, value.prepare(builder).downcast())
+            }
+
+            #[inline]
+            pub fn create_global_max_pool(
+                builder: &mut ::planus::Builder,
+                value: impl ::planus::WriteAsOffset<self::GlobalMaxPool>,
+            ) -> ::planus::UnionOffset<Self> {
+                ::planus::UnionOffset::new(11, value.prepare(builder).downcast())
+            }
+
+            #[inline]
+            pub fn create_reduce(
+                builder: &mut ::planus::Builder,
+                value: impl ::planus::WriteAsOffset<self::Reduce>,
+            ) -> ::planus::UnionOffset<Self> {
+                ::planus::UnionOffset::new(12, value.prepare(builder).downcast())
+            }
+
+            #[inline]
+            pub fn create_mat_mul(
+                builder: &mut ::planus::Builder,
+                value: impl ::planus::WriteAsOffset<self::MatMul>,
+            ) -> ::planus::UnionOffset<Self> {
+                ::planus::UnionOffset::new(13, value.prepare(builder).downcast())
+            }
+
+            #[inline]
+            pub fn create_add_dims(
+                builder: &mut ::planus::Builder,
+                value: impl ::planus::WriteAsOffset<self::AddDims>,
+            ) -> ::planus::UnionOffset<Self> {
+                ::planus::UnionOffset::new(14, value.prepare(builder).downcast())
+            }
+
+            #[inline]
+            pub fn create_rm_dims(
+                builder: &mut ::planus::Builder,
+                value: impl ::planus::WriteAsOffset<self::RmDims>,
+            ) -> ::planus::UnionOffset<Self> {
+                ::planus::UnionOffset::new(15, value.prepare(builder).downcast())
+            }
+
+            #[inline]
+            pub fn create_permute_axes(
+                builder: &mut ::planus::Builder,
+                value: impl ::planus::WriteAsOffset<self::PermuteAxes>,
+            ) -> ::planus::UnionOffset<Self> {
+                ::planus::UnionOffset::new(16, value.prepare(builder).downcast())
+            }
+
+            #[inline]
+            pub fn create_reshape(
+                builder: &mut ::planus::Builder,
+                value: impl ::planus::WriteAsOffset<self::Reshape>,
+            ) -> ::planus::UnionOffset<Self> {
+                ::planus::UnionOffset::new(17, value.prepare(builder).downcast())
+            }
+
+            #[inline]
+            pub fn create_slice(
+                builder: &mut ::planus::Builder,
+                value: impl ::planus::WriteAsOffset<self::Slice>,
+            ) -> ::planus::UnionOffset<Self> {
+                ::planus::UnionOffset::new(18, value.prepare(builder).downcast())
+            }
+
+            #[inline]
+            pub fn create_if(
+                builder: &mut ::planus::Builder,
+                value: impl ::planus::WriteAsOffset<self::If>,
+            ) -> ::planus::UnionOffset<Self> {
+                ::planus::UnionOffset::new(19, value.prepare(builder).downcast())
+            }
+
+            #[inline]
+            pub fn create_loop(
+                builder: &mut ::planus::Builder,
+                value: impl ::planus::WriteAsOffset<self::Loop>,
+            ) -> ::planus::UnionOffset<Self> {
+                ::planus::UnionOffset::new(20, value.prepare(builder).downcast())
+            }
+
+            #[inline]
+            pub fn create_scan(
+                builder: &mut ::planus::Builder,
+                value: impl ::planus::WriteAsOffset<self::Scan>,
+            ) -> ::planus::UnionOffset<Self> {
+                ::planus::UnionOffset::new(21, value.prepare(builder).downcast())
+            }
+
+            #[inline]
+            pub fn create_im2_col(
+                builder: &mut ::planus::Builder,
+                value: impl ::planus::WriteAsOffset<self::Im2Col>,
+            ) -> ::planus::UnionOffset<Self> {
+                ::planus::UnionOffset::new(22, value.prepare(builder).downcast())
+            }
+
+            #[inline]
+            pub fn create_mat_mat_mul_pack_b(
+                builder: &mut ::planus::Builder,
+                value: impl ::planus::WriteAsOffset<self::MatMatMulPackB>,
+            ) -> ::planus::UnionOffset<Self> {
+                ::planus::UnionOffset::new(23, value.prepare(builder).downcast())
+            }
+
+            #[inline]
+            pub fn create_mat_mat_mul(
+                builder: &mut ::planus::Builder,
+                value: impl ::planus::WriteAsOffset<self::MatMatMul>,
+            ) -> ::planus::UnionOffset<Self> {
+                ::planus::UnionOffset::new(24, value.prepare(builder).downcast())
+            }
+
+            #[inline]
+            pub fn create_merge_unicast(
+                builder: &mut ::planus::Builder,
+                value: impl ::planus::WriteAsOffset<self::MergeUnicast>,
+            ) -> ::planus::UnionOffset<Self> {
+                ::planus::UnionOffset::new(25, value.prepare(builder).downcast())
+            }
+
+            #[inline]
+            pub fn create_max_pool_fixed(
+                builder: &mut ::planus::Builder,
+                value: impl ::planus::WriteAsOffset<self::MaxPoolFixed>,
+            ) -> ::planus::UnionOffset<Self> {
+                ::planus::UnionOffset::new(26, value.prepare(builder).downcast())
+            }
+
+            #[inline]
+            pub fn create_avg_pool_fixed(
+                builder: &mut ::planus::Builder,
+                value: impl ::planus::WriteAsOffset<self::AvgPoolFixed>,
+            ) -> ::planus::UnionOffset<Self> {
+                ::planus::UnionOffset::new(27, value.prepare(builder).downcast())
+            }
+
+            #[inline]
+            pub fn create_depth_wise(
+                builder: &mut ::planus::Builder,
+                value: impl ::planus::WriteAsOffset<self::DepthWise>,
+            ) -> ::planus::UnionOffset<Self> {
+                ::planus::UnionOffset::new(28, value.prepare(builder).downcast())
+            }
+        }
+
+        impl ::planus::WriteAsUnion<Op> for Op {
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::UnionOffset<Self> {
+                match self {
+                    Self::Source(value) => Self::create_source(builder, value),
+                    Self::Const(value) => Self::create_const(builder, value),
+                    Self::Unary(value) => Self::create_unary(builder, value),
+                    Self::Binary(value) => Self::create_binary(builder, value),
+                    Self::Merge(value) => Self::create_merge(builder, value),
+                    Self::ElementWise(value) => Self::create_element_wise(builder, value),
+                    Self::Conv(value) => Self::create_conv(builder, value),
+                    Self::MaxPool(value) => Self::create_max_pool(builder, value),
+                    Self::AvgPool(value) => Self::create_avg_pool(builder, value),
+                    Self::GlobalAvgPool(value) => Self::create_global_avg_pool(builder, value),
+                    Self::GlobalMaxPool(value) => Self::create_global_max_pool(builder, value),
+                    Self::Reduce(value) => Self::create_reduce(builder, value),
+                    Self::MatMul(value) => Self::create_mat_mul(builder, value),
+                    Self::AddDims(value) => Self::create_add_dims(builder, value),
+                    Self::RmDims(value) => Self::create_rm_dims(builder, value),
+                    Self::PermuteAxes(value) => Self::create_permute_axes(builder, value),
+                    Self::Reshape(value) => Self::create_reshape(builder, value),
+                    Self::Slice(value) => Self::create_slice(builder, value),
+                    Self::If(value) => Self::create_if(builder, value),
+                    Self::Loop(value) => Self::create_loop(builder, value),
+                    Self::Scan(value) => Self::create_scan(builder, value),
+                    Self::Im2Col(value) => Self::create_im2_col(builder, value),
+                    Self::MatMatMulPackB(value) => Self::create_mat_mat_mul_pack_b(builder, value),
+                    Self::MatMatMul(value) => Self::create_mat_mat_mul(builder, value),
+                    Self::MergeUnicast(value) => Self::create_merge_unicast(builder, value),
+                    Self::MaxPoolFixed(value) => Self::create_max_pool_fixed(builder, value),
+                    Self::AvgPoolFixed(value) => Self::create_avg_pool_fixed(builder, value),
+                    Self::DepthWise(value) => Self::create_depth_wise(builder, value),
+                }
+            }
+        }
+
+        impl ::planus::WriteAsOptionalUnion<Op> for Op {
+            #[inline]
+            fn prepare(
+                &self,
+                builder: &mut ::planus::Builder,
+            ) -> ::core::option::Option<::planus::UnionOffset<Self>> {
+                ::core::option::Option::Some(::planus::WriteAsUnion::prepare(self, builder))
+            }
+        }
+
+        /// Builder for serializing an instance of the [Op] type.
+        ///
+        /// Can be created using the [Op::builder] method.
+        #[derive(Debug)]
+        #[must_use]
+        pub struct OpBuilder<T>(T);
+
+        impl OpBuilder<::planus::Uninitialized> {
+            /// Creates an instance of the [`Source` variant](Op#variant.Source).
+            #[inline]
+            pub fn source<T>(self, value: T) -> OpBuilder<::planus::Initialized<1, T>>
+            where
+                T: ::planus::WriteAsOffset<self::Source>,
+            {
+                OpBuilder(::planus::Initialized(value))
+            }
+
+            /// Creates an instance of the [`Const` variant](Op#variant.Const).
+            #[inline]
+            pub fn const_<T>(self, value: T) -> OpBuilder<::planus::Initialized<2, T>>
+            where
+                T: ::planus::WriteAsOffset<self::Const>,
+            {
+                OpBuilder(::planus::Initialized(value))
+            }
+
+            /// Creates an instance of the [`Unary` variant](Op#variant.Unary).
+            #[inline]
+            pub fn unary<T>(self, value: T) -> OpBuilder<::planus::Initialized<3, T>>
+            where
+                T: ::planus::WriteAsOffset<self::Unary>,
+            {
+                OpBuilder(::planus::Initialized(value))
+            }
+
+            /// Creates an instance of the [`Binary` variant](Op#variant.Binary).
+            #[inline]
+            pub fn binary<T>(self, value: T) -> OpBuilder<::planus::Initialized<4, T>>
+            where
+                T: ::planus::WriteAsOffset<self::Binary>,
+            {
+                OpBuilder(::planus::Initialized(value))
+            }
+
+            /// Creates an instance of the [`Merge` variant](Op#variant.Merge).
+            #[inline]
+            pub fn merge<T>(self, value: T) -> OpBuilder<::planus::Initialized<5, T>>
+            where
+                T: ::planus::WriteAsOffset<self::Merge>,
+            {
+                OpBuilder(::planus::Initialized(value))
+            }
+
+            /// Creates an instance of the [`ElementWise` variant](Op#variant.ElementWise).
+            #[inline]
+            pub fn element_wise<T>(self, value: T) -> OpBuilder<::planus::Initialized<6, T>>
+            where
+                T: ::planus::WriteAsOffset<self::ElementWise>,
+            {
+                OpBuilder(::planus::Initialized(value))
+            }
+
+            /// Creates an instance of the [`Conv` variant](Op#variant.Conv).
+            #[inline]
+            pub fn conv<T>(self, value: T) -> OpBuilder<::planus::Initialized<7, T>>
+            where
+                T: ::planus::WriteAsOffset<self::Conv>,
+            {
+                OpBuilder(::planus::Initialized(value))
+            }
+
+            /// Creates an instance of the [`MaxPool` variant](Op#variant.MaxPool).
+            #[inline]
+            pub fn max_pool<T>(self, value: T) -> OpBuilder<::planus::Initialized<8, T>>
+            where
+                T: ::planus::WriteAsOffset<self::MaxPool>,
+            {
+                OpBuilder(::planus::Initialized(value))
+            }
+
+            /// Creates an instance of the [`AvgPool` variant](Op#variant.AvgPool).
+            #[inline]
+            pub fn avg_pool<T>(self, value: T) -> OpBuilder<::planus::Initialized<9, T>>
+            where
+                T: ::planus::WriteAsOffset<self::AvgPool>,
+            {
+                OpBuilder(::planus::Initialized(value))
+            }
+
+            /// Creates an instance of the [`GlobalAvgPool` variant](Op#variant.GlobalAvgPool).
+            #[inline]
+            pub fn global_avg_pool<T>(self, value: T) -> OpBuilder<::planus::Initialized<10, T>>
+            where
+                T: ::planus::WriteAsOffset<self::GlobalAvgPool>,
+            {
+                OpBuilder(::planus::Initialized(value))
+            }
+
+            /// Creates an instance of the [`GlobalMaxPool` variant](Op#variant.GlobalMaxPool).
+            #[inline]
+            pub fn global_max_pool<T>(self, value: T) -> OpBuilder<::planus::Initialized<11, T>>
+            where
+                T: ::planus::WriteAsOffset<self::GlobalMaxPool>,
+            {
+                OpBuilder(::planus::Initialized(value))
+            }
+
+            /// Creates an instance of the [`Reduce` variant](Op#variant.Reduce).
+            #[inline]
+            pub fn reduce<T>(self, value: T) -> OpBuilder<::planus::Initialized<12, T>>
+            where
+                T: ::planus::WriteAsOffset<self::Reduce>,
+            {
+                OpBuilder(::planus::Initialized(value))
+            }
+
+            /// Creates an instance of the [`MatMul` variant](Op#variant.MatMul).
+            #[inline]
+            pub fn mat_mul<T>(self, value: T) -> OpBuilder<::planus::Initialized<13, T>>
+            where
+                T: ::planus::WriteAsOffset<self::MatMul>,
+            {
+                OpBuilder(::planus::Initialized(value))
+            }
+
+            /// Creates an instance of the [`AddDims` variant](Op#variant.AddDims).
+            #[inline]
+            pub fn add_dims<T>(self, value: T) -> OpBuilder<::planus::Initialized<14, T>>
+            where
+                T: ::planus::WriteAsOffset<self::AddDims>,
+            {
+                OpBuilder(::planus::Initialized(value))
+            }
+
+            /// Creates an instance of the [`RmDims` variant](Op#variant.RmDims).
+            #[inline]
+            pub fn rm_dims<T>(self, value: T) -> OpBuilder<::planus::Initialized<15, T>>
+            where
+                T: ::planus::WriteAsOffset<self::RmDims>,
+            {
+                OpBuilder(::planus::Initialized(value))
+            }
+
+            /// Creates an instance of the [`PermuteAxes` variant](Op#variant.PermuteAxes).
+            #[inline]
+            pub fn permute_axes<T>(self, value: T) -> OpBuilder<::planus::Initialized<16, T>>
+            where
+                T: ::planus::WriteAsOffset<self::PermuteAxes>,
+            {
+                OpBuilder(::planus::Initialized(value))
+            }
+
+            /// Creates an instance of the [`Reshape` variant](Op#variant.Reshape).
+            #[inline]
+            pub fn reshape<T>(self, value: T) -> OpBuilder<::planus::Initialized<17, T>>
+            where
+                T: ::planus::WriteAsOffset<self::Reshape>,
+            {
+                OpBuilder(::planus::Initialized(value))
+            }
+
+            /// Creates an instance of the [`Slice` variant](Op#variant.Slice).
+            #[inline]
+            pub fn slice<T>(self, value: T) -> OpBuilder<::planus::Initialized<18, T>>
+            where
+                T: ::planus::WriteAsOffset<self::Slice>,
+            {
+                OpBuilder(::planus::Initialized(value))
+            }
+
+            /// Creates an instance of the [`If` variant](Op#variant.If).
+            #[inline]
+            pub fn if_<T>(self, value: T) -> OpBuilder<::planus::Initialized<19, T>>
+            where
+                T: ::planus::WriteAsOffset<self::If>,
+            {
+                OpBuilder(::planus::Initialized(value))
+            }
+
+            /// Creates an instance of the [`Loop` variant](Op#variant.Loop).
+            #[inline]
+            pub fn loop_<T>(self, value: T) -> OpBuilder<::planus::Initialized<20, T>>
+            where
+                T: ::planus::WriteAsOffset<self::Loop>,
+            {
+                OpBuilder(::planus::Initialized(value))
+            }
+
+            /// Creates an instance of the [`Scan` variant](Op#variant.Scan).
+            #[inline]
+            pub fn scan<T>(self, value: T) -> OpBuilder<::planus::Initialized<21, T>>
+            where
+                T: ::planus::WriteAsOffset<self::Scan>,
+            {
+                OpBuilder(::planus::Initialized(value))
+            }
+
+            /// Creates an instance of the [`Im2Col` variant](Op#variant.Im2Col).
+            #[inline]
+            pub fn im2_col<T>(self, value: T) -> OpBuilder<::planus::Initialized<22, T>>
+            where
+                T: ::planus::WriteAsOffset<self::Im2Col>,
+            {
+                OpBuilder(::planus::Initialized(value))
+            }
+
+            /// Creates an instance of the [`MatMatMulPackB` variant](Op#variant.MatMatMulPackB).
+            #[inline]
+            pub fn mat_mat_mul_pack_b<T>(self, value: T) -> OpBuilder<::planus::Initialized<23, T>>
+            where
+                T: ::planus::WriteAsOffset<self::MatMatMulPackB>,
+            {
+                OpBuilder(::planus::Initialized(value))
+            }
+
+            /// Creates an instance of the [`MatMatMul` variant](Op#variant.MatMatMul).
+            #[inline]
+            pub fn mat_mat_mul<T>(self, value: T) -> OpBuilder<::planus::Initialized<24, T>>
+            where
+                T: ::planus::WriteAsOffset<self::MatMatMul>,
+            {
+                OpBuilder(::planus::Initialized(value))
+            }
+
+            /// Creates an instance of the [`MergeUnicast` variant](Op#variant.MergeUnicast).
+            #[inline]
+            pub fn merge_unicast<T>(self, value: T) -> OpBuilder<::planus::Initialized<25, T>>
+            where
+                T: ::planus::WriteAsOffset<self::MergeUnicast>,
+            {
+                OpBuilder(::planus::Initialized(value))
+            }
+
+            /// Creates an instance of the [`MaxPoolFixed` variant](Op#variant.MaxPoolFixed).
+            #[inline]
+            pub fn max_pool_fixed<T>(self, value: T) -> OpBuilder<::planus::Initialized<26, T>>
+            where
+                T: ::planus::WriteAsOffset<self::MaxPoolFixed>,
+            {
+                OpBuilder(::planus::Initialized(value))
+            }
+
+            /// Creates an instance of the [`AvgPoolFixed` variant](Op#variant.AvgPoolFixed).
+            #[inline]
+            pub fn avg_pool_fixed<T>(self, value: T) -> OpBuilder<::planus::Initialized<27, T>>
+            where
+                T: ::planus::WriteAsOffset<self::AvgPoolFixed>,
+            {
+                OpBuilder(::planus::Initialized(value))
+            }
+
+            /// Creates an instance of the [`DepthWise` variant](Op#variant.DepthWise).
+            #[inline]
+            pub fn depth_wise<T>(self, value: T) -> OpBuilder<::planus::Initialized<28, T>>
+            where
+                T: ::planus::WriteAsOffset<self::DepthWise>,
+            {
+                OpBuilder(::planus::Initialized(value))
+            }
+        }
+
+        impl<const N: u8, T> OpBuilder<::planus::Initialized<N, T>> {
+            /// Finish writing the builder to get an [UnionOffset](::planus::UnionOffset) to a serialized [Op].
+            #[inline]
+            pub fn finish(self, builder: &mut ::planus::Builder) -> ::planus::UnionOffset<Op>
+            where
+                Self: ::planus::WriteAsUnion<Op>,
+            {
+                ::planus::WriteAsUnion::prepare(&self, builder)
+            }
+        }
+
+        impl<T> ::planus::WriteAsUnion<Op> for OpBuilder<::planus::Initialized<1, T>>
+        where
+            T: ::planus::WriteAsOffset<self::Source>,
+        {
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::UnionOffset<Op> {
+                ::planus::UnionOffset::new(1, (self.0).0.prepare(builder).downcast())
+            }
+        }
+
+        impl<T> ::planus::WriteAsOptionalUnion<Op> for OpBuilder<::planus::Initialized<1, T>>
+        where
+            T: ::planus::WriteAsOffset<self::Source>,
+        {
+            #[inline]
+            fn prepare(
+                &self,
+                builder: &mut ::planus::Builder,
+            ) -> ::core::option::Option<::planus::UnionOffset<Op>> {
+                ::core::option::Option::Some(::planus::WriteAsUnion::prepare(self, builder))
+            }
+        }
+        impl<T> ::planus::WriteAsUnion<Op> for OpBuilder<::planus::Initialized<2, T>>
+        where
+            T: ::planus::WriteAsOffset<self::Const>,
+        {
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::UnionOffset<Op> {
+                ::planus::UnionOffset::new(2, (self.0).0.prepare(builder).downcast())
+            }
+        }
+
+        impl<T> ::planus::WriteAsOptionalUnion<Op> for OpBuilder<::planus::Initialized<2, T>>
+        where
+            T: ::planus::WriteAsOffset<self::Const>,
+        {
+            #[inline]
+            fn prepare(
+                &self,
+                builder: &mut ::planus::Builder,
+            ) -> ::core::option::Option<::planus::UnionOffset<Op>> {
+                ::core::option::Option::Some(::planus::WriteAsUnion::prepare(self, builder))
+            }
+        }
+        impl<T> ::planus::WriteAsUnion<Op> for OpBuilder<::planus::Initialized<3, T>>
+        where
+            T: ::planus::WriteAsOffset<self::Unary>,
+        {
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::UnionOffset<Op> {
+                ::planus::UnionOffset::new(3, (self.0).0.prepare(builder).downcast())
+            }
+        }
+
+        impl<T> ::planus::WriteAsOptionalUnion<Op> for OpBuilder<::planus::Initialized<3, T>>
+        where
+            T: ::planus::WriteAsOffset<self::Unary>,
+        {
+            #[inline]
+            fn prepare(
+                &self,
+                builder: &mut ::planus::Builder,
+            ) -> ::core::option::Option<::planus::UnionOffset<Op>> {
+                ::core::option::Option::Some(::planus::WriteAsUnion::prepare(self, builder))
+            }
+        }
+        impl<T> ::planus::WriteAsUnion<Op> for OpBuilder<::planus::Initialized<4, T>>
+        where
+            T: ::planus::WriteAsOffset<self::Binary>,
+        {
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::UnionOffset<Op> {
+                ::planus::UnionOffset::new(4, (self.0).0.prepare(builder).downcast())
+            }
+        }
+
+        impl<T> ::planus::WriteAsOptionalUnion<Op> for OpBuilder<::planus::Initialized<4, T>>
+        where
+            T: ::planus::WriteAsOffset<self::Binary>,
+        {
+            #[inline]
+            fn prepare(
+                &self,
+                builder: &mut ::planus::Builder,
+            ) -> ::core::option::Option<::planus::UnionOffset<Op>> {
+                ::core::option::Option::Some(::planus::WriteAsUnion::prepare(self, builder))
+            }
+        }
+        impl<T> ::planus::WriteAsUnion<Op> for OpBuilder<::planus::Initialized<5, T>>
+        where
+            T: ::planus::WriteAsOffset<self::Merge>,
+        {
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::UnionOffset<Op> {
+                ::planus::UnionOffset::new(5, (self.0).0.prepare(builder).downcast())
+            }
+        }
+
+        impl<T> ::planus::WriteAsOptionalUnion<Op> for OpBuilder<::planus::Initialized<5, T>>
+        where
+            T: ::planus::WriteAsOffset<self::Merge>,
+        {
+            #[inline]
+            fn prepare(
+                &self,
+                builder: &mut ::planus::Builder,
+            ) -> ::core::option::Option<::planus::UnionOffset<Op>> {
+                ::core::option::Option::Some(::planus::WriteAsUnion::prepare(self, builder))
+            }
+        }
+        impl<T> ::planus::WriteAsUnion<Op> for OpBuilder<::planus::Initialized<6, T>>
+        where
+            T: ::planus::WriteAsOffset<self::ElementWise>,
+        {
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::UnionOffset<Op> {
+                ::planus::UnionOffset::new(6, (self.0).0.prepare(builder).downcast())
+            }
+        }
+
+        impl<T> ::planus::WriteAsOptionalUnion<Op> for OpBuilder<::planus::Initialized<6, T>>
+        where
+            T: ::planus::WriteAsOffset<self::ElementWise>,
+        {
+            #[inline]
+            fn prepare(
+                &self,
+                builder: &mut ::planus::Builder,
+            ) -> ::core::option::Option<::planus::UnionOffset<Op>> {
+                ::core::option::Option::Some(::planus::WriteAsUnion::prepare(self, builder))
+            }
+        }
+        impl<T> ::planus::WriteAsUnion<Op> for OpBuilder<::planus::Initialized<7, T>>
+        where
+            T: ::planus::WriteAsOffset<self::Conv>,
+        {
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::UnionOffset<Op> {
+                ::planus::UnionOffset::new(7, (self.0).0.prepare(builder).downcast())
+            }
+        }
+
+        impl<T> ::planus::WriteAsOptionalUnion<Op> for OpBuilder<::planus::Initialized<7, T>>
+        where
+            T: ::planus::WriteAsOffset<self::Conv>,
+        {
+            #[inline]
+            fn prepare(
+                &self,
+                builder: &mut ::planus::Builder,
+            ) -> ::core::option::Option<::planus::UnionOffset<Op>> {
+                ::core::option::Option::Some(::planus::WriteAsUnion::prepare(self, builder))
+            }
+        }
+        impl<T> ::planus::WriteAsUnion<Op> for OpBuilder<::planus::Initialized<8, T>>
+        where
+            T: ::planus::WriteAsOffset<self::MaxPool>,
+        {
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::UnionOffset<Op> {
+                ::planus::UnionOffset::new(8, (self.0).0.prepare(builder).downcast())
+            }
+        }
+
+        impl<T> ::planus::WriteAsOptionalUnion<Op> for OpBuilder<::planus::Initialized<8, T>>
+        where
+            T: ::planus::WriteAsOffset<self::MaxPool>,
+        {
+            #[inline]
+            fn prepare(
+                &self,
+                builder: &mut ::planus::Builder,
+            ) -> ::core::option::Option<::planus::UnionOffset<Op>> {
+                ::core::option::Option::Some(::planus::WriteAsUnion::prepare(self, builder))
+            }
+        }
+        impl<T> ::planus::WriteAsUnion<Op> for OpBuilder<::planus::Initialized<9, T>>
+        where
+            T: ::planus::WriteAsOffset<self::AvgPool>,
+        {
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::UnionOffset<Op> {
+                ::planus::UnionOffset::new(9, (self.0).0.prepare(builder).downcast())
+            }
+        }
+
+        impl<T> ::planus::WriteAsOptionalUnion<Op> for OpBuilder<::planus::Initialized<9, T>>
+        where
+            T: ::planus::WriteAsOffset<self::AvgPool>,
+        {
+            #[inline]
+            fn prepare(
+                &self,
+                builder: &mut ::planus::Builder,
+            ) -> ::core::option::Option<::planus::UnionOffset<Op>> {
+                ::core::option::Option::Some(::planus::WriteAsUnion::prepare(self, builder))
+            }
+        }
+        impl<T> ::planus::WriteAsUnion<Op> for OpBuilder<::planus::Initialized<10, T>>
+        where
+            T: ::planus::WriteAsOffset<self::GlobalAvgPool>,
+        {
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::UnionOffset<Op> {
+                ::planus::UnionOffset::new(10, (self.0).0.prepare(builder).downcast())
+            }
+        }
+
+        impl<T> ::planus::WriteAsOptionalUnion<Op> for OpBuilder<::planus::Initialized<10, T>>
+        where
+            T: ::planus::WriteAsOffset<self::GlobalAvgPool>,
+        {
+            #[inline]
+            fn prepare(
+                &self,
+                builder: &mut ::planus::Builder,
+            ) -> ::core::option::Option<::planus::UnionOffset<Op>> {
+                ::core::option::Option::Some(::planus::WriteAsUnion::prepare(self, builder))
+            }
+        }
+        impl<T> ::planus::WriteAsUnion<Op> for OpBuilder<::planus::Initialized<11, T>>
+        where
+            T: ::planus::WriteAsOffset<self::GlobalMaxPool>,
+        {
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::UnionOffset<Op> {
+                ::planus::UnionOffset::new(11, (self.0).0.prepare(builder).downcast())
+            }
+        }
+
+        impl<T> ::planus::WriteAsOptionalUnion<Op> for OpBuilder<::planus::Initialized<11, T>>
+        where
+            T: ::planus::WriteAsOffset<self::GlobalMaxPool>,
+        {
+            #[inline]
+            fn prepare(
+                &self,
+                builder: &mut ::planus::Builder,
+            ) -> ::core::option::Option<::planus::UnionOffset<Op>> {
+                ::core::option::Option::Some(::planus::WriteAsUnion::prepare(self, builder))
+            }
+        }
+        impl<T> ::planus::WriteAsUnion<Op> for OpBuilder<::planus::Initialized<12, T>>
+        where
+            T: ::planus::WriteAsOffset<self::Reduce>,
+        {
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::UnionOffset<Op> {
+                ::planus::UnionOffset::new(12, (self.0).0.prepare(builder).downcast())
+            }
+        }
+
+        impl<T> ::planus::WriteAsOptionalUnion<Op> for OpBuilder<::planus::Initialized<12, T>>
+        where
+            T: ::planus::WriteAsOffset<self::Reduce>,
+        {
+            #[inline]
+            fn prepare(
+                &self,
+                builder: &mut ::planus::Builder,
+            ) -> ::core::option::Option<::planus::UnionOffset<Op>> {
+                ::core::option::Option::Some(::planus::WriteAsUnion::prepare(self, builder))
+            }
+        }
+        impl<T> ::planus::WriteAsUnion<Op> for OpBuilder<::planus::Initialized<13, T>>
+        where
+            T: ::planus::WriteAsOffset<self::MatMul>,
+        {
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::UnionOffset<Op> {
+                ::planus::UnionOffset::new(13, (self.0).0.prepare(builder).downcast())
+            }
+        }
+
+        impl<T> ::planus::WriteAsOptionalUnion<Op> for OpBuilder<::planus::Initialized<13, T>>
+        where
+            T: ::planus::WriteAsOffset<self::MatMul>,
+        {
+            #[inline]
+            fn prepare(
+                &self,
+                builder: &mut ::planus::Builder,
+            ) -> ::core::option::Option<::planus::UnionOffset<Op>> {
+                ::core::option::Option::Some(::planus::WriteAsUnion::prepare(self, builder))
+            }
+        }
+        impl<T> ::planus::WriteAsUnion<Op> for OpBuilder<::planus::Initialized<14, T>>
+        where
+            T: ::planus::WriteAsOffset<self::AddDims>,
+        {
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::UnionOffset<Op> {
+                ::planus::UnionOffset::new(14, (self.0).0.prepare(builder).downcast())
+            }
+        }
+
+        impl<T> ::planus::WriteAsOptionalUnion<Op> for OpBuilder<::planus::Initialized<14, T>>
+        where
+            T: ::planus::WriteAsOffset<self::AddDims>,
+        {
+            #[inline]
+            fn prepare(
+                &self,
+                builder: &mut ::planus::Builder,
+            ) -> ::core::option::Option<::planus::UnionOffset<Op>> {
+                ::core::option::Option::Some(::planus::WriteAsUnion::prepare(self, builder))
+            }
+        }
+        impl<T> ::planus::WriteAsUnion<Op> for OpBuilder<::planus::Initialized<15, T>>
+        where
+            T: ::planus::WriteAsOffset<self::RmDims>,
+        {
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::UnionOffset<Op> {
+                ::planus::UnionOffset::new(15, (self.0).0.prepare(builder).downcast())
+            }
+        }
+
+        impl<T> ::planus::WriteAsOptionalUnion<Op> for OpBuilder<::planus::Initialized<15, T>>
+        where
+            T: ::planus::WriteAsOffset<self::RmDims>,
+        {
+            #[inline]
+            fn prepare(
+                &self,
+                builder: &mut ::planus::Builder,
+            ) -> ::core::option::Option<::planus::UnionOffset<Op>> {
+                ::core::option::Option::Some(::planus::WriteAsUnion::prepare(self, builder))
+            }
+        }
+        impl<T> ::planus::WriteAsUnion<Op> for OpBuilder<::planus::Initialized<16, T>>
+        where
+            T: ::planus::WriteAsOffset<self::PermuteAxes>,
+        {
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::UnionOffset<Op> {
+                ::planus::UnionOffset::new(16, (self.0).0.prepare(builder).downcast())
+            }
+        }
+
+        impl<T> ::planus::WriteAsOptionalUnion<Op> for OpBuilder<::planus::Initialized<16, T>>
+        where
+            T: ::planus::WriteAsOffset<self::PermuteAxes>,
+        {
+            #[inline]
+            fn prepare(
+                &self,
+                builder: &mut ::planus::Builder,
+            ) -> ::core::option::Option<::planus::UnionOffset<Op>> {
+                ::core::option::Option::Some(::planus::WriteAsUnion::prepare(self, builder))
+            }
+        }
+        impl<T> ::planus::WriteAsUnion<Op> for OpBuilder<::planus::Initialized<17, T>>
+        where
+            T: ::planus::WriteAsOffset<self::Reshape>,
+        {
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::UnionOffset<Op> {
+                ::planus::UnionOffset::new(17, (self.0).0.prepare(builder).downcast())
+            }
+        }
+
+        impl<T> ::planus::WriteAsOptionalUnion<Op> for OpBuilder<::planus::Initialized<17, T>>
+        where
+            T: ::planus::WriteAsOffset<self::Reshape>,
+        {
+            #[inline]
+            fn prepare(
+                &self,
+                builder: &mut ::planus::Builder,
+            ) -> ::core::option::Option<::planus::UnionOffset<Op>> {
+                ::core::option::Option::Some(::planus::WriteAsUnion::prepare(self, builder))
+            }
+        }
+        impl<T> ::planus::WriteAsUnion<Op> for OpBuilder<::planus::Initialized<18, T>>
+        where
+            T: ::planus::WriteAsOffset<self::Slice>,
+        {
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::UnionOffset<Op> {
+                ::planus::UnionOffset::new(18, (self.0).0.prepare(builder).downcast())
+            }
+        }
+
+        impl<T> ::planus::WriteAsOptionalUnion<Op> for OpBuilder<::planus::Initialized<18, T>>
+        where
+            T: ::planus::WriteAsOffset<self::Slice>,
+        {
+            #[inline]
+            fn prepare(
+                &self,
+                builder: &mut ::planus::Builder,
+            ) -> ::core::option::Option<::planus::UnionOffset<Op>> {
+                ::core::option::Option::Some(::planus::WriteAsUnion::prepare(self, builder))
+            }
+        }
+        impl<T> ::planus::WriteAsUnion<Op> for OpBuilder<::planus::Initialized<19, T>>
+        where
+            T: ::planus::WriteAsOffset<self::If>,
+        {
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::UnionOffset<Op> {
+                ::planus::UnionOffset::new(19, (self.0).0.prepare(builder).downcast())
+            }
+        }
+
+        impl<T> ::planus::WriteAsOptionalUnion<Op> for OpBuilder<::planus::Initialized<19, T>>
+        where
+            T: ::planus::WriteAsOffset<self::If>,
+        {
+            #[inline]
+            fn prepare(
+                &self,
+                builder: &mut ::planus::Builder,
+            ) -> ::core::option::Option<::planus::UnionOffset<Op>> {
+                ::core::option::Option::Some(::planus::WriteAsUnion::prepare(self, builder))
+            }
+        }
+        impl<T> ::planus::WriteAsUnion<Op> for OpBuilder<::planus::Initialized<20, T>>
+        where
+            T: ::planus::WriteAsOffset<self::Loop>,
+        {
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::UnionOffset<Op> {
+                ::planus::UnionOffset::new(20, (self.0).0.prepare(builder).downcast())
+            }
+        }
+
+        impl<T> ::planus::WriteAsOptionalUnion<Op> for OpBuilder<::planus::Initialized<20, T>>
+        where
+            T: ::planus::WriteAsOffset<self::Loop>,
+        {
+            #[inline]
+            fn prepare(
+                &self,
+                builder: &mut ::planus::Builder,
+            ) -> ::core::option::Option<::planus::UnionOffset<Op>> {
+                ::core::option::Option::Some(::planus::WriteAsUnion::prepare(self, builder))
+            }
+        }
+        impl<T> ::planus::WriteAsUnion<Op> for OpBuilder<::planus::Initialized<21, T>>
+        where
+            T: ::planus::WriteAsOffset<self::Scan>,
+        {
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::UnionOffset<Op> {
+                ::planus::UnionOffset::new(21, (self.0).0.prepare(builder).downcast())
+            }
+        }
+
+        impl<T> ::planus::WriteAsOptionalUnion<Op> for OpBuilder<::planus::Initialized<21, T>>
+        where
+            T: ::planus::WriteAsOffset<self::Scan>,
+        {
+            #[inline]
+            fn prepare(
+                &self,
+                builder: &mut ::planus::Builder,
+            ) -> ::core::option::Option<::planus::UnionOffset<Op>> {
+                ::core::option::Option::Some(::planus::WriteAsUnion::prepare(self, builder))
+            }
+        }
+        impl<T> ::planus::WriteAsUnion<Op> for OpBuilder<::planus::Initialized<22, T>>
+        where
+            T: ::planus::WriteAsOffset<self::Im2Col>,
+        {
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::UnionOffset<Op> {
+                ::planus::UnionOffset::new(22, (self.0).0.prepare(builder).downcast())
+            }
+        }
+
+        impl<T> ::planus::WriteAsOptionalUnion<Op> for OpBuilder<::planus::Initialized<22, T>>
+        where
+            T: ::planus::WriteAsOffset<self::Im2Col>,
+        {
+            #[inline]
+            fn prepare(
+                &self,
+                builder: &mut ::planus::Builder,
+            ) -> ::core::option::Option<::planus::UnionOffset<Op>> {
+                ::core::option::Option::Some(::planus::WriteAsUnion::prepare(self, builder))
+            }
+        }
+        impl<T> ::planus::WriteAsUnion<Op> for OpBuilder<::planus::Initialized<23, T>>
+        where
+            T: ::planus::WriteAsOffset<self::MatMatMulPackB>,
+        {
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::UnionOffset<Op> {
+                ::planus::UnionOffset::new(23, (self.0).0.prepare(builder).downcast())
+            }
+        }
+
+        impl<T> ::planus::WriteAsOptionalUnion<Op> for OpBuilder<::planus::Initialized<23, T>>
+        where
+            T: ::planus::WriteAsOffset<self::MatMatMulPackB>,
+        {
+            #[inline]
+            fn prepare(
+                &self,
+                builder: &mut ::planus::Builder,
+            ) -> ::core::option::Option<::planus::UnionOffset<Op>> {
+                ::core::option::Option::Some(::planus::WriteAsUnion::prepare(self, builder))
+            }
+        }
+        impl<T> ::planus::WriteAsUnion<Op> for OpBuilder<::planus::Initialized<24, T>>
+        where
+            T: ::planus::WriteAsOffset<self::MatMatMul>,
+        {
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::UnionOffset<Op> {
+                ::planus::UnionOffset::new(24, (self.0).0.prepare(builder).downcast())
+            }
+        }
+
+        impl<T> ::planus::WriteAsOptionalUnion<Op> for OpBuilder<::planus::Initialized<24, T>>
+        where
+            T: ::planus::WriteAsOffset<self::MatMatMul>,
+        {
+            #[inline]
+            fn prepare(
+                &self,
+                builder: &mut ::planus::Builder,
+            ) -> ::core::option::Option<::planus::UnionOffset<Op>> {
+                ::core::option::Option::Some(::planus::WriteAsUnion::prepare(self, builder))
+            }
+        }
+        impl<T> ::planus::WriteAsUnion<Op> for OpBuilder<::planus::Initialized<25, T>>
+        where
+            T: ::planus::WriteAsOffset<self::MergeUnicast>,
+        {
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::UnionOffset<Op> {
+                ::planus::UnionOffset::new(25, (self.0).0.prepare(builder).downcast())
+            }
+        }
+
+        impl<T> ::planus::WriteAsOptionalUnion<Op> for OpBuilder<::planus::Initialized<25, T>>
+        where
+            T: ::planus::WriteAsOffset<self::MergeUnicast>,
+        {
+            #[inline]
+            fn prepare(
+                &self,
+                builder: &mut ::planus::Builder,
+            ) -> ::core::option::Option<::planus::UnionOffset<Op>> {
+                ::core::option::Option::Some(::planus::WriteAsUnion::prepare(self, builder))
+            }
+        }
+        impl<T> ::planus::WriteAsUnion<Op> for OpBuilder<::planus::Initialized<26, T>>
+        where
+            T: ::planus::WriteAsOffset<self::MaxPoolFixed>,
+        {
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::UnionOffset<Op> {
+                ::planus::UnionOffset::new(26, (self.0).0.prepare(builder).downcast())
+            }
+        }
+
+        impl<T> ::planus::WriteAsOptionalUnion<Op> for OpBuilder<::planus::Initialized<26, T>>
+        where
+            T: ::planus::WriteAsOffset<self::MaxPoolFixed>,
+        {
+            #[inline]
+            fn prepare(
+                &self,
+                builder: &mut ::planus::Builder,
+            ) -> ::core::option::Option<::planus::UnionOffset<Op>> {
+                ::core::option::Option::Some(::planus::WriteAsUnion::prepare(self, builder))
+            }
+        }
+        impl<T> ::planus::WriteAsUnion<Op> for OpBuilder<::planus::Initialized<27, T>>
+        where
+            T: ::planus::WriteAsOffset<self::AvgPoolFixed>,
+        {
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::UnionOffset<Op> {
+                ::planus::UnionOffset::new(27, (self.0).0.prepare(builder).downcast())
+            }
+        }
+
+        impl<T> ::planus::WriteAsOptionalUnion<Op> for OpBuilder<::planus::Initialized<27, T>>
+        where
+            T: ::planus::WriteAsOffset<self::AvgPoolFixed>,
+        {
+            #[inline]
+            fn prepare(
+                &self,
+                builder: &mut ::planus::Builder,
+            ) -> ::core::option::Option<::planus::UnionOffset<Op>> {
+                ::core::option::Option::Some(::planus::WriteAsUnion::prepare(self, builder))
+            }
+        }
+        impl<T> ::planus::WriteAsUnion<Op> for OpBuilder<::planus::Initialized<28, T>>
+        where
+            T: ::planus::WriteAsOffset<self::DepthWise>,
+        {
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::UnionOffset<Op> {
+                ::planus::UnionOffset::new(28, (self.0).0.prepare(builder).downcast())
+            }
+        }
+
+        impl<T> ::planus::WriteAsOptionalUnion<Op> for OpBuilder<::planus::Initialized<28, T>>
+        where
+            T: ::planus::WriteAsOffset<self::DepthWise>,
+        {
+            #[inline]
+            fn prepare(
+                &self,
+                builder: &mut ::planus::Builder,
+            ) -> ::core::option::Option<::planus::UnionOffset<Op>> {
+                ::core::option::Option::Some(::planus::WriteAsUnion::prepare(self, builder))
+            }
+        }
+
+        /// Reference to a deserialized [Op].
+        #[derive(Copy, Clone, Debug)]
+        pub enum OpRef<'a> {
+            Source(self::SourceRef<'a>),
+            Const(self::ConstRef<'a>),
+            Unary(self::UnaryRef<'a>),
+            Binary(self::BinaryRef<'a>),
+            Merge(self::MergeRef<'a>),
+            ElementWise(self::ElementWiseRef<'a>),
+            Conv(self::ConvRef<'a>),
+            MaxPool(self::MaxPoolRef<'a>),
+            AvgPool(self::AvgPoolRef<'a>),
+            GlobalAvgPool(self::GlobalAvgPoolRef<'a>),
+            GlobalMaxPool(self::GlobalMaxPoolRef<'a>),
+            Reduce(self::ReduceRef<'a>),
+            MatMul(self::MatMulRef<'a>),
+            AddDims(self::AddDimsRef<'a>),
+            RmDims(self::RmDimsRef<'a>),
+            PermuteAxes(self::PermuteAxesRef<'a>),
+            Reshape(self::ReshapeRef<'a>),
+            Slice(self::SliceRef<'a>),
+            If(self::IfRef<'a>),
+            Loop(self::LoopRef<'a>),
+            Scan(self::ScanRef<'a>),
+            Im2Col(self::Im2ColRef<'a>),
+            MatMatMulPackB(self::MatMatMulPackBRef<'a>),
+            MatMatMul(self::MatMatMulRef<'a>),
+            MergeUnicast(self::MergeUnicastRef<'a>),
+            MaxPoolFixed(self::MaxPoolFixedRef<'a>),
+            AvgPoolFixed(self::AvgPoolFixedRef<'a>),
+            DepthWise(self::DepthWiseRef<'a>),
+        }
+
+        impl<'a> ::core::convert::TryFrom<OpRef<'a>> for Op {
+            type Error = ::planus::Error;
+
+            fn try_from(value: OpRef<'a>) -> ::planus::Result<Self> {
+                ::core::result::Result::Ok(match value {
+                    OpRef::Source(value) => Self::Source(::planus::alloc::boxed::Box::new(
+                        ::core::convert::TryFrom::try_from(value)?,
+                    )),
+
+                    OpRef::Const(value) => Self::Const(::planus::alloc::boxed::Box::new(
+                        ::core::convert::TryFrom::try_from(value)?,
+                    )),
+
+                    OpRef::Unary(value) => Self::Unary(::planus::alloc::boxed::Box::new(
+                        ::core::convert::TryFrom::try_from(value)?,
+                    )),
+
+                    OpRef::Binary(value) => Self::Binary(::planus::alloc::boxed::Box::new(
+                        ::core::convert::TryFrom::try_from(value)?,
+                    )),
+
+                    OpRef::Merge(value) => Self::Merge(::planus::alloc::boxed::Box::new(
+                        ::core::convert::TryFrom::try_from(value)?,
+                    )),
+
+                    OpRef::ElementWise(value) => {
+                        Self::ElementWise(::planus::alloc::boxed::Box::new(
+                            ::core::convert::TryFrom::try_from(value)?,
+                        ))
+                    }
+
+                    OpRef::Conv(value) => Self::Conv(::planus::alloc::boxed::Box::new(
+                        ::core::convert::TryFrom::try_from(value)?,
+                    )),
+
+                    OpRef::MaxPool(value) => Self::MaxPool(::planus::alloc::boxed::Box::new(
+                        ::core::convert::TryFrom::try_from(value)?,
+                    )),
+
+                    OpRef::AvgPool(value) => Self::AvgPool(::planus::alloc::boxed::Box::new(
+                        ::core::convert::TryFrom::try_from(value)?,
+                    )),
+
+                    OpRef::GlobalAvgPool(value) => {
+                        Self::GlobalAvgPool(::planus::alloc::boxed::Box::new(
+                            ::core::convert::TryFrom::try_from(value)?,
+                        ))
+                    }
+
+                    OpRef::GlobalMaxPool(value) => {
+                        Self::GlobalMaxPool(::planus::alloc::boxed::Box::new(
+                            ::core::convert::TryFrom::try_from(value)?,
+                        ))
+                    }
+
+                    OpRef::Reduce(value) => Self::Reduce(::planus::alloc::boxed::Box::new(
+                        ::core::convert::TryFrom::try_from(value)?,
+                    )),
+
+                    OpRef::MatMul(value) => Self::MatMul(::planus::alloc::boxed::Box::new(
+                        ::core::convert::TryFrom::try_from(value)?,
+                    )),
+
+                    OpRef::AddDims(value) => Self::AddDims(::planus::alloc::boxed::Box::new(
+                        ::core::convert::TryFrom::try_from(value)?,
+                    )),
+
+                    OpRef::RmDims(value) => Self::RmDims(::planus::alloc::boxed::Box::new(
+                        ::core::convert::TryFrom::try_from(value)?,
+                    )),
+
+                    OpRef::PermuteAxes(value) => {
+                        Self::PermuteAxes(::planus::alloc::boxed::Box::new(
+                            ::core::convert::TryFrom::try_from(value)?,
+                        ))
+                    }
+
+                    OpRef::Reshape(value) => Self::Reshape(::planus::alloc::boxed::Box::new(
+                        ::core::convert::TryFrom::try_from(value)?,
+                    )),
+
+                    OpRef::Slice(value) => Self::Slice(::planus::alloc::boxed::Box::new(
+                        ::core::convert::TryFrom::try_from(value)?,
+                    )),
+
+                    OpRef::If(value) => Self::If(::planus::alloc::boxed::Box::new(
+                        ::core::convert::TryFrom::try_from(value)?,
+                    )),
+
+                    OpRef::Loop(value) => Self::Loop(::planus::alloc::boxed::Box::new(
+                        ::core::convert::TryFrom::try_from(value)?,
+                    )),
+
+                    OpRef::Scan(value) => Self::Scan(::planus::alloc::boxed::Box::new(
+                        ::core::convert::TryFrom::try_from(value)?,
+                    )),
+
+                    OpRef::Im2Col(value) => Self::Im2Col(::planus::alloc::boxed::Box::new(
+                        ::core::convert::TryFrom::try_from(value)?,
+                    )),
+
+                    OpRef::MatMatMulPackB(value) => {
+                        Self::MatMatMulPackB(::planus::alloc::boxed::Box::new(
+                            ::core::convert::TryFrom::try_from(value)?,
+                        ))
+                    }
+
+                    OpRef::MatMatMul(value) => Self::MatMatMul(::planus::alloc::boxed::Box::new(
+                        ::core::convert::TryFrom::try_from(value)?,
+                    )),
+
+                    OpRef::MergeUnicast(value) => {
+                        Self::MergeUnicast(::planus::alloc::boxed::Box::new(
+                            ::core::convert::TryFrom::try_from(value)?,
+                        ))
+                    }
+
+                    OpRef::MaxPoolFixed(value) => {
+                        Self::MaxPoolFixed(::planus::alloc::boxed::Box::new(
+                            ::core::convert::TryFrom::try_from(value)?,
+                        ))
+                    }
+
+                    OpRef::AvgPoolFixed(value) => {
+                        Self::AvgPoolFixed(::planus::alloc::boxed::Box::new(
+                            ::core::convert::TryFrom::try_from(value)?,
+                        ))
+                    }
+
+                    OpRef::DepthWise(value) => Self::DepthWise(::planus::alloc::boxed::Box::new(
+                        ::core::convert::TryFrom::try_from(value)?,
+                    )),
+                })
+            }
+        }
+
+        impl<'a> ::planus::TableReadUnion<'a> for OpRef<'a> {
+            fn from_buffer(
+                buffer: ::planus::SliceWithStartOffset<'a>,
+                tag: u8,
+                field_offset: usize,
+            ) -> ::core::result::Result<Self, ::planus::errors::ErrorKind> {
+                match tag {
+                    1 => ::core::result::Result::Ok(Self::Source(
+                        ::planus::TableRead::from_buffer(buffer, field_offset)?,
+                    )),
+                    2 => ::core::result::Result::Ok(Self::Const(::planus::TableRead::from_buffer(
+                        buffer,
+                        field_offset,
+                    )?)),
+                    3 => ::core::result::Result::Ok(Self::Unary(::planus::TableRead::from_buffer(
+                        buffer,
+                        field_offset,
+                    )?)),
+                    4 => ::core::result::Result::Ok(Self::Binary(
+                        ::planus::TableRead::from_buffer(buffer, field_offset)?,
+                    )),
+                    5 => ::core::result::Result::Ok(Self::Merge(::planus::TableRead::from_buffer(
+                        buffer,
+                        field_offset,
+                    )?)),
+                    6 => ::core::result::Result::Ok(Self::ElementWise(
+                        ::planus::TableRead::from_buffer(buffer, field_offset)?,
+                    )),
+                    7 => ::core::result::Result::Ok(Self::Conv(::planus::TableRead::from_buffer(
+                        buffer,
+                        field_offset,
+                    )?)),
+                    8 => ::core::result::Result::Ok(Self::MaxPool(
+                        ::planus::TableRead::from_buffer(buffer, field_offset)?,
+                    )),
+                    9 => ::core::result::Result::Ok(Self::AvgPool(
+                        ::planus::TableRead::from_buffer(buffer, field_offset)?,
+                    )),
+                    10 => ::core::result::Result::Ok(Self::GlobalAvgPool(
+                        ::planus::TableRead::from_buffer(buffer, field_offset)?,
+                    )),
+                    11 => ::core::result::Result::Ok(Self::GlobalMaxPool(
+                        ::planus::TableRead::from_buffer(buffer, field_offset)?,
+                    )),
+                    12 => ::core::result::Result::Ok(Self::Reduce(
+                        ::planus::TableRead::from_buffer(buffer, field_offset)?,
+                    )),
+                    13 => ::core::result::Result::Ok(Self::MatMul(
+                        ::planus::TableRead::from_buffer(buffer, field_offset)?,
+                    )),
+                    14 => ::core::result::Result::Ok(Self::AddDims(
+                        ::planus::TableRead::from_buffer(buffer, field_offset)?,
+                    )),
+                    15 => ::core::result::Result::Ok(Self::RmDims(
+                        ::planus::TableRead::from_buffer(buffer, field_offset)?,
+                    )),
+                    16 => ::core::result::Result::Ok(Self::PermuteAxes(
+                        ::planus::TableRead::from_buffer(buffer, field_offset)?,
+                    )),
+                    17 => ::core::result::Result::Ok(Self::Reshape(
+                        ::planus::TableRead::from_buffer(buffer, field_offset)?,
+                    )),
+                    18 => ::core::result::Result::Ok(Self::Slice(
+                        ::planus::TableRead::from_buffer(buffer, field_offset)?,
+                    )),
+                    19 => ::core::result::Result::Ok(Self::If(::planus::TableRead::from_buffer(
+                        buffer,
+                        field_offset,
+                    )?)),
+                    20 => ::core::result::Result::Ok(Self::Loop(::planus::TableRead::from_buffer(
+                        buffer,
+                        field_offset,
+                    )?)),
+                    21 => ::core::result::Result::Ok(Self::Scan(::planus::TableRead::from_buffer(
+                        buffer,
+                        field_offset,
+                    )?)),
+                    22 => ::core::result::Result::Ok(Self::Im2Col(
+                        ::planus::TableRead::from_buffer(buffer, field_offset)?,
+                    )),
+                    23 => ::core::result::Result::Ok(Self::MatMatMulPackB(
+                        ::planus::TableRead::from_buffer(buffer, field_offset)?,
+                    )),
+                    24 => ::core::result::Result::Ok(Self::MatMatMul(
+                        ::planus::TableRead::from_buffer(buffer, field_offset)?,
+                    )),
+                    25 => ::core::result::Result::Ok(Self::MergeUnicast(
+                        ::planus::TableRead::from_buffer(buffer, field_offset)?,
+                    )),
+                    26 => ::core::result::Result::Ok(Self::MaxPoolFixed(
+                        ::planus::TableRead::from_buffer(buffer, field_offset)?,
+                    )),
+                    27 => ::core::result::Result::Ok(Self::AvgPoolFixed(
+                        ::planus::TableRead::from_buffer(buffer, field_offset)?,
+                    )),
+                    28 => ::core::result::Result::Ok(Self::DepthWise(
+                        ::planus::TableRead::from_buffer(buffer, field_offset)?,
+                    )),
+                    _ => {
+                        ::core::result::Result::Err(::planus::errors::ErrorKind::UnknownUnionTag {
+                            tag,
+                        })
+                    }
+                }
+            }
+        }
+
+        impl<'a> ::planus::VectorReadUnion<'a> for OpRef<'a> {
+            const VECTOR_NAME: &'static str = "[OpRef]";
+        }
+
+        /// The table `Node` in the namespace `tract`
+        ///
+        /// Generated from these locations:
+        /// * Table `Node` in the file `schema/tract.fbs:438`
+        #[derive(Clone, Debug, PartialEq, PartialOrd, ::serde::Serialize, ::serde::Deserialize)]
+        pub struct Node {
+            /// The field `name` in the table `Node`
+            pub name: ::planus::alloc::string::String,
+            /// The field `inputs` in the table `Node`
+            pub inputs: ::planus::alloc::vec::Vec<self::Outlet>,
+            /// The field `op` in the table `Node`
+            pub op: self::Op,
+            /// The field `output_facts` in the table `Node`
+            pub output_facts: ::planus::alloc::vec::Vec<self::TypedFact>,
+        }
+
+        impl Node {
+            /// Creates a [NodeBuilder] for serializing an instance of this table.
+            #[inline]
+            pub fn builder() -> NodeBuilder<()> {
+                NodeBuilder(())
+            }
+
+            #[allow(clippy::too_many_arguments)]
+            pub fn create(
+                builder: &mut ::planus::Builder,
+                field_name: impl ::planus::WriteAs<::planus::Offset<str>>,
+                field_inputs: impl ::planus::WriteAs<::planus::Offset<[self::Outlet]>>,
+                field_op: impl ::planus::WriteAsUnion<self::Op>,
+                field_output_facts: impl ::planus::WriteAs<
+                    ::planus::Offset<[::planus::Offset<self::TypedFact>]>,
+                >,
+            ) -> ::planus::Offset<Self> {
+                let prepared_name = field_name.prepare(builder);
+                let prepared_inputs = field_inputs.prepare(builder);
+                let prepared_op = field_op.prepare(builder);
+                let prepared_output_facts = field_output_facts.prepare(builder);
+
+                let mut table_writer: ::planus::table_writer::TableWriter<14> =
+                    ::core::default::Default::default();
+                table_writer.write_entry::<::planus::Offset<str>>(0);
+                table_writer.write_entry::<::planus::Offset<[self::Outlet]>>(1);
+                table_writer.write_entry::<::planus::Offset<self::Op>>(3);
+                table_writer
+                    .write_entry::<::planus::Offset<[::planus::Offset<self::TypedFact>]>>(4);
+                table_writer.write_entry::<u8>(2);
+
+                unsafe {
+                    table_writer.finish(builder, |object_writer| {
+                        object_writer.write::<_, _, 4>(&prepared_name);
+                        object_writer.write::<_, _, 4>(&prepared_inputs);
+                        object_writer.write::<_, _, 4>(&prepared_op.offset());
+                        object_writer.write::<_, _, 4>(&prepared_output_facts);
+                        object_writer.write::<_, _, 1>(&prepared_op.tag());
+                    });
+                }
+                builder.current_offset()
+            }
+        }
+
+        impl ::planus::WriteAs<::planus::Offset<Node>> for Node {
+            type Prepared = ::planus::Offset<Self>;
+
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<Node> {
+                ::planus::WriteAsOffset::prepare(self, builder)
+            }
+        }
+
+        impl ::planus::WriteAsOptional<::planus::Offset<Node>> for Node {
+            type Prepared = ::planus::Offset<Self>;
+
+            #[inline]
+            fn prepare(
+                &self,
+                builder: &mut ::planus::Builder,
+            ) -> ::core::option::Option<::planus::Offset<Node>> {
+                ::core::option::Option::Some(::planus::WriteAsOffset::prepare(self, builder))
+            }
+        }
+
+        impl ::planus::WriteAsOffset<Node> for Node {
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<Node> {
+                Node::create(builder, &self.name, &self.inputs, &self.op, &self.output_facts)
+            }
+        }
+
+        /// Builder for serializing an instance of the [Node] type.
+        ///
+        /// Can be created using the [Node::builder] method.
+        #[derive(Debug)]
+        #[must_use]
+        pub struct NodeBuilder<State>(State);
+
+        impl NodeBuilder<()> {
+            /// Setter for the [`name` field](Node#structfield.name).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn name<T0>(self, value: T0) -> NodeBuilder<(T0,)>
+            where
+                T0: ::planus::WriteAs<::planus::Offset<str>>,
+            {
+                NodeBuilder((value,))
+            }
+        }
+
+        impl<T0> NodeBuilder<(T0,)> {
+            /// Setter for the [`inputs` field](Node#structfield.inputs).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn inputs<T1>(self, value: T1) -> NodeBuilder<(T0, T1)>
+            where
+                T1: ::planus::WriteAs<::planus::Offset<[self::Outlet]>>,
+            {
+                let (v0,) = self.0;
+                NodeBuilder((v0, value))
+            }
+        }
+
+        impl<T0, T1> NodeBuilder<(T0, T1)> {
+            /// Setter for the [`op` field](Node#structfield.op).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn op<T2>(self, value: T2) -> NodeBuilder<(T0, T1, T2)>
+            where
+                T2: ::planus::WriteAsUnion<self::Op>,
+            {
+                let (v0, v1) = self.0;
+                NodeBuilder((v0, v1, value))
+            }
+        }
+
+        impl<T0, T1, T2> NodeBuilder<(T0, T1, T2)> {
+            /// Setter for the [`output_facts` field](Node#structfield.output_facts).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn output_facts<T3>(self, value: T3) -> NodeBuilder<(T0, T1, T2, T3)>
+            where
+                T3: ::planus::WriteAs<::planus::Offset<[::planus::Offset<self::TypedFact>]>>,
+            {
+                let (v0, v1, v2) = self.0;
+                NodeBuilder((v0, v1, v2, value))
+            }
+        }
+
+        impl<T0, T1, T2, T3> NodeBuilder<(T0, T1, T2, T3)> {
+            /// Finish writing the builder to get an [Offset](::planus::Offset) to a serialized [Node].
+            #[inline]
+            pub fn finish(self, builder: &mut ::planus::Builder) -> ::planus::Offset<Node>
+            where
+                Self: ::planus::WriteAsOffset<Node>,
+            {
+                ::planus::WriteAsOffset::prepare(&self, builder)
+            }
+        }
+
+        impl<
+                T0: ::planus::WriteAs<::planus::Offset<str>>,
+                T1: ::planus::WriteAs<::planus::Offset<[self::Outlet]>>,
+                T2: ::planus::WriteAsUnion<self::Op>,
+                T3: ::planus::WriteAs<::planus::Offset<[::planus::Offset<self::TypedFact>]>>,
+            > ::planus::WriteAs<::planus::Offset<Node>> for NodeBuilder<(T0, T1, T2, T3)>
+        {
+            type Prepared = ::planus::Offset<Node>;
+
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<Node> {
+                ::planus::WriteAsOffset::prepare(self, builder)
+            }
+        }
+
+        impl<
+                T0: ::planus::WriteAs<::planus::Offset<str>>,
+                T1: ::planus::WriteAs<::planus::Offset<[self::Outlet]>>,
+                T2: ::planus::WriteAsUnion<self::Op>,
+                T3: ::planus::WriteAs<::planus::Offset<[::planus::Offset<self::TypedFact>]>>,
+            > ::planus::WriteAsOptional<::planus::Offset<Node>> for NodeBuilder<(T0, T1, T2, T3)>
+        {
+            type Prepared = ::planus::Offset<Node>;
+
+            #[inline]
+            fn prepare(
+                &self,
+                builder: &mut ::planus::Builder,
+            ) -> ::core::option::Option<::planus::Offset<Node>> {
+                ::core::option::Option::Some(::planus::WriteAsOffset::prepare(self, builder))
+            }
+        }
+
+        impl<
+                T0: ::planus::WriteAs<::planus::Offset<str>>,
+                T1: ::planus::WriteAs<::planus::Offset<[self::Outlet]>>,
+                T2: ::planus::WriteAsUnion<self::Op>,
+                T3: ::planus::WriteAs<::planus::Offset<[::planus::Offset<self::TypedFact>]>>,
+            > ::planus::WriteAsOffset<Node> for NodeBuilder<(T0, T1, T2, T3)>
+        {
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<Node> {
+                let (v0, v1, v2, v3) = &self.0;
+                Node::create(builder, v0, v1, v2, v3)
+            }
+        }
+
+        /// Reference to a deserialized [Node].
+        #[derive(Copy, Clone)]
+        pub struct NodeRef<'a>(#[allow(dead_code)] ::planus::table_reader::Table<'a>);
+
+        impl<'a> NodeRef<'a> {
+            /// Getter for the [`name` field](Node#structfield.name).
+            #[inline]
+            pub fn name(&self) -> ::planus::Result<&'a ::core::primitive::str> {
+                self.0.access_required(0, "Node", "name")
+            }
+
+            /// Getter for the [`inputs` field](Node#structfield.inputs).
+            #[inline]
+            pub fn inputs(&self) -> ::planus::Result<::planus::Vector<'a, self::OutletRef<'a>>> {
+                self.0.access_required(1, "Node", "inputs")
+            }
+
+            /// Getter for the [`op` field](Node#structfield.op).
+            #[inline]
+            pub fn op(&self) -> ::planus::Result<self::OpRef<'a>> {
+                self.0.access_union_required(2, "Node", "op")
+            }
+
+            /// Getter for the [`output_facts` field](Node#structfield.output_facts).
+            #[inline]
+            pub fn output_facts(
+                &self,
+            ) -> ::planus::Result<::planus::Vector<'a, ::planus::Result<self::TypedFactRef<'a>>>>
+            {
+                self.0.access_required(4, "Node", "output_facts")
+            }
+        }
+
+        impl<'a> ::core::fmt::Debug for NodeRef<'a> {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                let mut f = f.debug_struct("NodeRef");
+                f.field("name", &self.name());
+                f.field("inputs", &self.inputs());
+                f.field("op", &self.op());
+                f.field("output_facts", &self.output_facts());
+                f.finish()
+            }
+        }
+
+        impl<'a> ::core::convert::TryFrom<NodeRef<'a>> for Node {
+            type Error = ::planus::Error;
+
+            #[allow(unreachable_code)]
+            fn try_from(value: NodeRef<'a>) -> ::planus::Result<Self> {
+                ::core::result::Result::Ok(Self {
+                    name: ::core::convert::Into::into(value.name()?),
+                    inputs: value.inputs()?.to_vec()?,
+                    op: ::core::convert::TryInto::try_into(value.op()?)?,
+                    output_facts: value.output_facts()?.to_vec_result()?,
+                })
+            }
+        }
+
+        impl<'a> ::planus::TableRead<'a> for NodeRef<'a> {
+            #[inline]
+            fn from_buffer(
+                buffer: ::planus::SliceWithStartOffset<'a>,
+                offset: usize,
+            ) -> ::core::result::Result<Self, ::planus::errors::ErrorKind> {
+                ::core::result::Result::Ok(Self(::planus::table_reader::Table::from_buffer(
+                    buffer, offset,
+                )?))
+            }
+        }
+
+        impl<'a> ::planus::VectorReadInner<'a> for NodeRef<'a> {
+            type Error = ::planus::Error;
+            const STRIDE: usize = 4;
+
+            unsafe fn from_buffer(
+                buffer: ::planus::SliceWithStartOffset<'a>,
+                offset: usize,
+            ) -> ::planus::Result<Self> {
+                ::planus::TableRead::from_buffer(buffer, offset).map_err(|error_kind| {
+                    error_kind.with_error_location("[NodeRef]", "get", buffer.offset_from_start)
+                })
+            }
+        }
+
+        /// # Safety
+        /// The planus compiler generates implementations that initialize
+        /// the bytes in `write_values`.
+        unsafe impl ::planus::VectorWrite<::planus::Offset<Node>> for Node {
+            type Value = ::planus::Offset<Node>;
+            const STRIDE: usize = 4;
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> Self::Value {
+                ::planus::WriteAs::prepare(self, builder)
+            }
+
+            #[inline]
+            unsafe fn write_values(
+                values: &[::planus::Offset<Node>],
+                bytes: *mut ::core::mem::MaybeUninit<u8>,
+                buffer_position: u32,
+            ) {
+                let bytes = bytes as *mut [::core::mem::MaybeUninit<u8>; 4];
+                for (i, v) in ::core::iter::Iterator::enumerate(values.iter()) {
+                    ::planus::WriteAsPrimitive::write(
+                        v,
+                        ::planus::Cursor::new(unsafe { &mut *bytes.add(i) }),
+                        buffer_position - (Self::STRIDE * i) as u32,
+                    );
+                }
+            }
+        }
+
+        impl<'a> ::planus::ReadAsRoot<'a> for NodeRef<'a> {
+            fn read_as_root(slice: &'a [u8]) -> ::planus::Result<Self> {
+                ::planus::TableRead::from_buffer(
+                    ::planus::SliceWithStartOffset { buffer: slice, offset_from_start: 0 },
+                    0,
+                )
+                .map_err(|error_kind| {
+                    error_kind.with_error_location("[NodeRef]", "read_as_root", 0)
+                })
+            }
+        }
+
+        /// The table `Model` in the namespace `tract`
+        ///
+        /// Generated from these locations:
+        /// * Table `Model` in the file `schema/tract.fbs:445`
+        #[derive(Clone, Debug, PartialEq, PartialOrd, ::serde::Serialize, ::serde::Deserialize)]
+        pub struct Model {
+            /// The field `nodes` in the table `Model`
+            pub nodes: ::planus::alloc::vec::Vec<self::Node>,
+            /// The field `inputs` in the table `Model`
+            pub inputs: ::planus::alloc::vec::Vec<self::Outlet>,
+            /// The field `outputs` in the table `Model`
+            pub outputs: ::planus::alloc::vec::Vec<self::Outlet>,
+            /// The field `labels` in the table `Model`
+            pub labels: ::core::option::Option<::planus::alloc::vec::Vec<self::OutletLabel>>,
+            /// The field `optimized` in the table `Model`
+            pub optimized: bool,
+        }
+
+        #[allow(clippy::derivable_impls)]
+        impl ::core::default::Default for Model {
+            fn default() -> Self {
+                Self {
+                    nodes: ::core::default::Default::default(),
+                    inputs: ::core::default::Default::default(),
+                    outputs: ::core::default::Default::default(),
+                    labels: ::core::default::Default::default(),
+                    optimized: false,
+                }
+            }
+        }
+
+        impl Model {
+            /// Creates a [ModelBuilder] for serializing an instance of this table.
+            #[inline]
+            pub fn builder() -> ModelBuilder<()> {
+                ModelBuilder(())
+            }
+
+            #[allow(clippy::too_many_arguments)]
+            pub fn create(
+                builder: &mut ::planus::Builder,
+                field_nodes: impl ::planus::WriteAs<::planus::Offset<[::planus::Offset<self::Node>]>>,
+                field_inputs: impl ::planus::WriteAs<::planus::Offset<[self::Outlet]>>,
+                field_outputs: impl ::planus::WriteAs<::planus::Offset<[self::Outlet]>>,
+                field_labels: impl ::planus::WriteAsOptional<
+                    ::planus::Offset<[::planus::Offset<self::OutletLabel>]>,
+                >,
+                field_optimized: impl ::planus::WriteAsDefault<bool, bool>,
+            ) -> ::planus::Offset<Self> {
+                let prepared_nodes = field_nodes.prepare(builder);
+                let prepared_inputs = field_inputs.prepare(builder);
+                let prepared_outputs = field_outputs.prepare(builder);
+                let prepared_labels = field_labels.prepare(builder);
+                let prepared_optimized = field_optimized.prepare(builder, &false);
+
+                let mut table_writer: ::planus::table_writer::TableWriter<14> =
+                    ::core::default::Default::default();
+                table_writer.write_entry::<::planus::Offset<[::planus::Offset<self::Node>]>>(0);
+                table_writer.write_entry::<::planus::Offset<[self::Outlet]>>(1);
+                table_writer.write_entry::<::planus::Offset<[self::Outlet]>>(2);
+                if prepared_labels.is_some() {
+                    table_writer
+                        .write_entry::<::planus::Offset<[::planus::Offset<self::OutletLabel>]>>(3);
+                }
+                if prepared_optimized.is_some() {
+                    table_writer.write_entry::<bool>(4);
+                }
+
+                unsafe {
+                    table_writer.finish(builder, |object_writer| {
+                        object_writer.write::<_, _, 4>(&prepared_nodes);
+                        object_writer.write::<_, _, 4>(&prepared_inputs);
+                        object_writer.write::<_, _, 4>(&prepared_outputs);
+                        if let ::core::option::Option::Some(prepared_labels) = prepared_labels {
+                            object_writer.write::<_, _, 4>(&prepared_labels);
+                        }
+                        if let ::core::option::Option::Some(prepared_optimized) = prepared_optimized
+                        {
+                            object_writer.write::<_, _, 1>(&prepared_optimized);
+                        }
+                    });
+                }
+                builder.current_offset()
+            }
+        }
+
+        impl ::planus::WriteAs<::planus::Offset<Model>> for Model {
+            type Prepared = ::planus::Offset<Self>;
+
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<Model> {
+                ::planus::WriteAsOffset::prepare(self, builder)
+            }
+        }
+
+        impl ::planus::WriteAsOptional<::planus::Offset<Model>> for Model {
+            type Prepared = ::planus::Offset<Self>;
+
+            #[inline]
+            fn prepare(
+                &self,
+                builder: &mut ::planus::Builder,
+            ) -> ::core::option::Option<::planus::Offset<Model>> {
+                ::core::option::Option::Some(::planus::WriteAsOffset::prepare(self, builder))
+            }
+        }
+
+        impl ::planus::WriteAsOffset<Model> for Model {
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<Model> {
+                Model::create(
+                    builder,
+                    &self.nodes,
+                    &self.inputs,
+                    &self.outputs,
+                    &self.labels,
+                    self.optimized,
+                )
+            }
+        }
+
+        /// Builder for serializing an instance of the [Model] type.
+        ///
+        /// Can be created using the [Model::builder] method.
+        #[derive(Debug)]
+        #[must_use]
+        pub struct ModelBuilder<State>(State);
+
+        impl ModelBuilder<()> {
+            /// Setter for the [`nodes` field](Model#structfield.nodes).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn nodes<T0>(self, value: T0) -> ModelBuilder<(T0,)>
+            where
+                T0: ::planus::WriteAs<::planus::Offset<[::planus::Offset<self::Node>]>>,
+            {
+                ModelBuilder((value,))
+            }
+        }
+
+        impl<T0> ModelBuilder<(T0,)> {
+            /// Setter for the [`inputs` field](Model#structfield.inputs).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn inputs<T1>(self, value: T1) -> ModelBuilder<(T0, T1)>
+            where
+                T1: ::planus::WriteAs<::planus::Offset<[self::Outlet]>>,
+            {
+                let (v0,) = self.0;
+                ModelBuilder((v0, value))
+            }
+        }
+
+        impl<T0, T1> ModelBuilder<(T0, T1)> {
+            /// Setter for the [`outputs` field](Model#structfield.outputs).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn outputs<T2>(self, value: T2) -> ModelBuilder<(T0, T1, T2)>
+            where
+                T2: ::planus::WriteAs<::planus::Offset<[self::Outlet]>>,
+            {
+                let (v0, v1) = self.0;
+                ModelBuilder((v0, v1, value))
+            }
+        }
+
+        impl<T0, T1, T2> ModelBuilder<(T0, T1, T2)> {
+            /// Setter for the [`labels` field](Model#structfield.labels).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn labels<T3>(self, value: T3) -> ModelBuilder<(T0, T1, T2, T3)>
+            where
+                T3: ::planus::WriteAsOptional<
+                    ::planus::Offset<[::planus::Offset<self::OutletLabel>]>,
+                >,
+            {
+                let (v0, v1, v2) = self.0;
+                ModelBuilder((v0, v1, v2, value))
+            }
+
+            /// Sets the [`labels` field](Model#structfield.labels) to null.
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn labels_as_null(self) -> ModelBuilder<(T0, T1, T2, ())> {
+                self.labels(())
+            }
+        }
+
+        impl<T0, T1, T2, T3> ModelBuilder<(T0, T1, T2, T3)> {
+            /// Setter for the [`optimized` field](Model#structfield.optimized).
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn optimized<T4>(self, value: T4) -> ModelBuilder<(T0, T1, T2, T3, T4)>
+            where
+                T4: ::planus::WriteAsDefault<bool, bool>,
+            {
+                let (v0, v1, v2, v3) = self.0;
+                ModelBuilder((v0, v1, v2, v3, value))
+            }
+
+            /// Sets the [`optimized` field](Model#structfield.optimized) to the default value.
+            #[inline]
+            #[allow(clippy::type_complexity)]
+            pub fn optimized_as_default(
+                self,
+            ) -> ModelBuilder<(T0, T1, T2, T3, ::planus::DefaultValue)> {
+                self.optimized(::planus::DefaultValue)
+            }
+        }
+
+        impl<T0, T1, T2, T3, T4> ModelBuilder<(T0, T1, T2, T3, T4)> {
+            /// Finish writing the builder to get an [Offset](::planus::Offset) to a serialized [Model].
+            #[inline]
+            pub fn finish(self, builder: &mut ::planus::Builder) -> ::planus::Offset<Model>
+            where
+                Self: ::planus::WriteAsOffset<Model>,
+            {
+                ::planus::WriteAsOffset::prepare(&self, builder)
+            }
+        }
+
+        impl<
+                T0: ::planus::WriteAs<::planus::Offset<[::planus::Offset<self::Node>]>>,
+                T1: ::planus::WriteAs<::planus::Offset<[self::Outlet]>>,
+                T2: ::planus::WriteAs<::planus::Offset<[self::Outlet]>>,
+                T3: ::planus::WriteAsOptional<::planus::Offset<[::planus::Offset<self::OutletLabel>]>>,
+                T4: ::planus::WriteAsDefault<bool, bool>,
+            > ::planus::WriteAs<::planus::Offset<Model>> for ModelBuilder<(T0, T1, T2, T3, T4)>
+        {
+            type Prepared = ::planus::Offset<Model>;
+
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<Model> {
+                ::planus::WriteAsOffset::prepare(self, builder)
+            }
+        }
+
+        impl<
+                T0: ::planus::WriteAs<::planus::Offset<[::planus::Offset<self::Node>]>>,
+                T1: ::planus::WriteAs<::planus::Offset<[self::Outlet]>>,
+                T2: ::planus::WriteAs<::planus::Offset<[self::Outlet]>>,
+                T3: ::planus::WriteAsOptional<::planus::Offset<[::planus::Offset<self::OutletLabel>]>>,
+                T4: ::planus::WriteAsDefault<bool, bool>,
+            > ::planus::WriteAsOptional<::planus::Offset<Model>>
+            for ModelBuilder<(T0, T1, T2, T3, T4)>
+        {
+            type Prepared = ::planus::Offset<Model>;
+
+            #[inline]
+            fn prepare(
+                &self,
+                builder: &mut ::planus::Builder,
+            ) -> ::core::option::Option<::planus::Offset<Model>> {
+                ::core::option::Option::Some(::planus::WriteAsOffset::prepare(self, builder))
+            }
+        }
+
+        impl<
+                T0: ::planus::WriteAs<::planus::Offset<[::planus::Offset<self::Node>]>>,
+                T1: ::planus::WriteAs<::planus::Offset<[self::Outlet]>>,
+                T2: ::planus::WriteAs<::planus::Offset<[self::Outlet]>>,
+                T3: ::planus::WriteAsOptional<::planus::Offset<[::planus::Offset<self::OutletLabel>]>>,
+                T4: ::planus::WriteAsDefault<bool, bool>,
+            > ::planus::WriteAsOffset<Model> for ModelBuilder<(T0, T1, T2, T3, T4)>
+        {
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> ::planus::Offset<Model> {
+                let (v0, v1, v2, v3, v4) = &self.0;
+                Model::create(builder, v0, v1, v2, v3, v4)
+            }
+        }
+
+        /// Reference to a deserialized [Model].
+        #[derive(Copy, Clone)]
+        pub struct ModelRef<'a>(#[allow(dead_code)] ::planus::table_reader::Table<'a>);
+
+        impl<'a> ModelRef<'a> {
+            /// Getter for the [`nodes` field](Model#structfield.nodes).
+            #[inline]
+            pub fn nodes(
+                &self,
+            ) -> ::planus::Result<::planus::Vector<'a, ::planus::Result<self::NodeRef<'a>>>>
+            {
+                self.0.access_required(0, "Model", "nodes")
+            }
+
+            /// Getter for the [`inputs` field](Model#structfield.inputs).
+            #[inline]
+            pub fn inputs(&self) -> ::planus::Result<::planus::Vector<'a, self::OutletRef<'a>>> {
+                self.0.access_required(1, "Model", "inputs")
+            }
+
+            /// Getter for the [`outputs` field](Model#structfield.outputs).
+            #[inline]
+            pub fn outputs(&self) -> ::planus::Result<::planus::Vector<'a, self::OutletRef<'a>>> {
+                self.0.access_required(2, "Model", "outputs")
+            }
+
+            /// Getter for the [`labels` field](Model#structfield.labels).
+            #[inline]
+            pub fn labels(
+                &self,
+            ) -> ::planus::Result<
+                ::core::option::Option<
+                    ::planus::Vector<'a, ::planus::Result<self::OutletLabelRef<'a>>>,
+                >,
+            > {
+                self.0.access(3, "Model", "labels")
+            }
+
+            /// Getter for the [`optimized` field](Model#structfield.optimized).
+            #[inline]
+            pub fn optimized(&self) -> ::planus::Result<bool> {
+                ::core::result::Result::Ok(self.0.access(4, "Model", "optimized")?.unwrap_or(false))
+            }
+        }
+
+        impl<'a> ::core::fmt::Debug for ModelRef<'a> {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                let mut f = f.debug_struct("ModelRef");
+                f.field("nodes", &self.nodes());
+                f.field("inputs", &self.inputs());
+                f.field("outputs", &self.outputs());
+                if let ::core::option::Option::Some(field_labels) = self.labels().transpose() {
+                    f.field("labels", &field_labels);
+                }
+                f.field("optimized", &self.optimized());
+                f.finish()
+            }
+        }
+
+        impl<'a> ::core::convert::TryFrom<ModelRef<'a>> for Model {
+            type Error = ::planus::Error;
+
+            #[allow(unreachable_code)]
+            fn try_from(value: ModelRef<'a>) -> ::planus::Result<Self> {
+                ::core::result::Result::Ok(Self {
+                    nodes: value.nodes()?.to_vec_result()?,
+                    inputs: value.inputs()?.to_vec()?,
+                    outputs: value.outputs()?.to_vec()?,
+                    labels: if let ::core::option::Option::Some(labels) = value.labels()? {
+                        ::core::option::Option::Some(labels.to_vec_result()?)
+                    } else {
+                        ::core::option::Option::None
+                    },
+                    optimized: ::core::convert::TryInto::try_into(value.optimized()?)?,
+                })
+            }
+        }
+
+        impl<'a> ::planus::TableRead<'a> for ModelRef<'a> {
+            #[inline]
+            fn from_buffer(
+                buffer: ::planus::SliceWithStartOffset<'a>,
+                offset: usize,
+            ) -> ::core::result::Result<Self, ::planus::errors::ErrorKind> {
+                ::core::result::Result::Ok(Self(::planus::table_reader::Table::from_buffer(
+                    buffer, offset,
+                )?))
+            }
+        }
+
+        impl<'a> ::planus::VectorReadInner<'a> for ModelRef<'a> {
+            type Error = ::planus::Error;
+            const STRIDE: usize = 4;
+
+            unsafe fn from_buffer(
+                buffer: ::planus::SliceWithStartOffset<'a>,
+                offset: usize,
+            ) -> ::planus::Result<Self> {
+                ::planus::TableRead::from_buffer(buffer, offset).map_err(|error_kind| {
+                    error_kind.with_error_location("[ModelRef]", "get", buffer.offset_from_start)
+                })
+            }
+        }
+
+        /// # Safety
+        /// The planus compiler generates implementations that initialize
+        /// the bytes in `write_values`.
+        unsafe impl ::planus::VectorWrite<::planus::Offset<Model>> for Model {
+            type Value = ::planus::Offset<Model>;
+            const STRIDE: usize = 4;
+            #[inline]
+            fn prepare(&self, builder: &mut ::planus::Builder) -> Self::Value {
+                ::planus::WriteAs::prepare(self, builder)
+            }
+
+            #[inline]
+            unsafe fn write_values(
+                values: &[::planus::Offset<Model>],
+                bytes: *mut ::core::mem::MaybeUninit<u8>,
+                buffer_position: u32,
+            ) {
+                let bytes = bytes as *mut [::core::mem::MaybeUninit<u8>; 4];
+                for (i, v) in ::core::iter::Iterator::enumerate(values.iter()) {
+                    ::planus::WriteAsPrimitive::write(
+                        v,
+                        ::planus::Cursor::new(unsafe { &mut *bytes.add(i) }),
+                        buffer_position - (Self::STRIDE * i) as u32,
+                    );
+                }
+            }
+        }
+
+        impl<'a> ::planus::ReadAsRoot<'a> for ModelRef<'a> {
+            fn read_as_root(slice: &'a [u8]) -> ::planus::Result<Self> {
+                ::planus::TableRead::from_buffer(
+                    ::planus::SliceWithStartOffset { buffer: slice, offset_from_start: 0 },
+                    0,
+                )
+                .map_err(|error_kind| {
+                    error_kind.with_error_location("[ModelRef]", "read_as_root", 0)
+                })
+            }
+        }
+    }
+}
@@ -161,6 +161,7 @@ fn incorporate_memory_ops_as_scans(
                 axis: 0,
                 chunk: (),
                 slot: ix,
+                reverse: false,
             });
             mapped_outputs.push(tract_core::ops::scan::OutputMapping {
                 state: false,
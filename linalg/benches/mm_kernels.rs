@@ -0,0 +1,73 @@
+extern crate criterion;
+extern crate tract_linalg;
+use criterion::*;
+
+#[allow(unused_imports)]
+use tract_linalg::frame::mmm::{MatMatMul, MatMatMulImpl};
+
+pub fn vec(len: usize, align: usize) -> *mut f32 {
+    let layout =
+        std::alloc::Layout::from_size_align(len * std::mem::size_of::<f32>(), align).unwrap();
+    unsafe { std::alloc::alloc_zeroed(layout) as *mut f32 }
+}
+
+fn run(be: &mut Bencher, mut mm: Box<dyn MatMatMul<f32, f32, f32, f32>>, k: usize, n: usize) {
+    let pa = vec(mm.a_pack().len(), mm.a_pack().alignment());
+    let mut c = vec![0.0; mm.m() * n];
+    if n == 1 {
+        let b = vec![0.0; k];
+        unsafe {
+            mm.b_vec_from_data();
+            mm.c_vec_from_data();
+        }
+        be.iter(move || unsafe { mm.run(pa, b.as_ptr(), c.as_mut_ptr(), &[]) });
+    } else {
+        let pb = vec(mm.b_pack().len(), mm.b_pack().alignment());
+        be.iter(move || unsafe { mm.run(pa, pb, c.as_mut_ptr(), &[]) });
+    }
+}
+
+fn kernels(c: &mut Criterion) {
+    for &(m, k, n) in &[(512usize, 512usize, 1usize), (512, 512, 512)] {
+        let mut group = c.benchmark_group(format!("{}x{}x{}", m, k, n));
+        group.throughput(Throughput::Elements((m * k * n) as u64));
+        group
+            .bench_function("generic", |be| run(be, (tract_linalg::generic().smmm)(m, k, n), k, n));
+        #[cfg(target_arch = "x86_64")]
+        {
+            use tract_linalg::x86_64_fma::avx2::SMatMatMul6x16;
+            use tract_linalg::x86_64_fma::mmm::SMatMatMul16x6;
+            if is_x86_feature_detected!("fma") {
+                group.bench_function("fma", |be| {
+                    let mm = MatMatMulImpl::<SMatMatMul16x6, _, _, _, _>::new(m, k, n);
+                    run(be, Box::new(mm), k, n)
+                });
+            }
+            if is_x86_feature_detected!("avx2") && is_x86_feature_detected!("fma") {
+                group.bench_function("avx2", |be| {
+                    let mm = MatMatMulImpl::<SMatMatMul6x16, _, _, _, _>::new(m, k, n);
+                    run(be, Box::new(mm), k, n)
+                });
+            }
+        }
+        #[cfg(target_arch = "aarch64")]
+        {
+            use tract_linalg::arm64::arm64simd::SMatMatMul8x8;
+            use tract_linalg::arm64::neon::SMatMatMul8x8Neon;
+            if std::arch::is_aarch64_feature_detected!("neon") {
+                group.bench_function("arm64simd", |be| {
+                    let mm = MatMatMulImpl::<SMatMatMul8x8, _, _, _, _>::new(m, k, n);
+                    run(be, Box::new(mm), k, n)
+                });
+                group.bench_function("neon", |be| {
+                    let mm = MatMatMulImpl::<SMatMatMul8x8Neon, _, _, _, _>::new(m, k, n);
+                    run(be, Box::new(mm), k, n)
+                });
+            }
+        }
+        group.finish();
+    }
+}
+
+criterion_group!(benches, kernels);
+criterion_main!(benches);
@@ -0,0 +1,313 @@
+use std::arch::aarch64::*;
+
+use crate::frame::mmm::LinearSpec::*;
+use crate::frame::mmm::PanelStore::*;
+use crate::frame::mmm::*;
+
+/// f32 kernel in NEON intrinsics: 8 rows of 8 columns, each row
+/// accumulated in two 4-float registers.
+#[derive(Copy, Clone, Debug)]
+pub struct SMatMatMul8x8Neon;
+
+impl MatMatMulKer<f32, f32, f32, f32> for SMatMatMul8x8Neon {
+    #[inline(always)]
+    fn name() -> &'static str {
+        "neon"
+    }
+    #[inline(always)]
+    fn mr() -> usize {
+        8
+    }
+    #[inline(always)]
+    fn nr() -> usize {
+        8
+    }
+    fn alignment_bytes_packed_a() -> usize {
+        16
+    }
+    fn alignment_bytes_packed_b() -> usize {
+        16
+    }
+    #[inline(never)]
+    fn kernel(spec: &MatMatMulKerSpec<f32, f32, f32, f32>) -> isize {
+        unsafe { kernel_8x8(spec) }
+    }
+}
+
+type Tile = [[float32x4_t; 2]; 8];
+
+#[inline(always)]
+unsafe fn load_row(ptr: *const f32, col_stride: isize) -> [float32x4_t; 2] {
+    if col_stride == 1 {
+        [vld1q_f32(ptr), vld1q_f32(ptr.offset(4))]
+    } else {
+        let mut row = [0f32; 8];
+        for j in 0..8 {
+            row[j] = *ptr.offset(j as isize * col_stride);
+        }
+        [vld1q_f32(row.as_ptr()), vld1q_f32(row.as_ptr().offset(4))]
+    }
+}
+
+#[inline(always)]
+unsafe fn store_row(ptr: *mut f32, col_stride: isize, values: [float32x4_t; 2]) {
+    if col_stride == 1 {
+        vst1q_f32(ptr, values[0]);
+        vst1q_f32(ptr.offset(4), values[1]);
+    } else {
+        let mut row = [0f32; 8];
+        vst1q_f32(row.as_mut_ptr(), values[0]);
+        vst1q_f32(row.as_mut_ptr().offset(4), values[1]);
+        for j in 0..8 {
+            *ptr.offset(j as isize * col_stride) = row[j];
+        }
+    }
+}
+
+#[inline(always)]
+unsafe fn fma_panels(ab: &mut Tile, a: *const f32, b0: float32x4_t, b1: float32x4_t) {
+    for r in 0..8 {
+        let ar = vdupq_n_f32(*a.offset(r as isize));
+        ab[r][0] = vfmaq_f32(ab[r][0], ar, b0);
+        ab[r][1] = vfmaq_f32(ab[r][1], ar, b1);
+    }
+}
+
+#[target_feature(enable = "neon")]
+unsafe fn kernel_8x8(spec: &MatMatMulKerSpec<f32, f32, f32, f32>) -> isize {
+    let mut ab: Tile = [[vdupq_n_f32(0.0); 2]; 8];
+    match (*spec.a, *spec.b, *spec.linear) {
+        (Packed { ptr: a }, Packed { ptr: b }, Mul { k }) => {
+            for i in 0..k as isize {
+                let b0 = vld1q_f32(b.offset(8 * i));
+                let b1 = vld1q_f32(b.offset(8 * i + 4));
+                fma_panels(&mut ab, a.offset(8 * i), b0, b1);
+            }
+        }
+        (Packed { ptr: a }, OffsetsAndPtrs { row_byte_offsets, col_ptrs }, Mul { k }) => {
+            let mut cols = [std::ptr::null::<f32>(); 8];
+            for j in 0..8 {
+                cols[j] = *col_ptrs.offset(j as isize);
+            }
+            let mut b = [0f32; 8];
+            for i in 0..k as isize {
+                let offset = *row_byte_offsets.offset(i) / 4;
+                for j in 0..8 {
+                    b[j] = *cols[j].offset(offset);
+                }
+                let b0 = vld1q_f32(b.as_ptr());
+                let b1 = vld1q_f32(b.as_ptr().offset(4));
+                fma_panels(&mut ab, a.offset(8 * i), b0, b1);
+            }
+        }
+        (Packed { ptr: a }, VecStride { ptr: b, byte_stride }, Mul { k }) => {
+            // a single column: accumulate the 8 rows in the lanes of two
+            // registers, with two independent pairs of accumulators.
+            let stride = byte_stride / 4;
+            let mut acc = [vdupq_n_f32(0.0); 4];
+            let k = k as isize;
+            let mut i = 0;
+            while i + 2 <= k {
+                for u in 0..2 {
+                    let a = a.offset(8 * (i + u as isize));
+                    let b = vdupq_n_f32(*b.offset((i + u as isize) * stride));
+                    acc[2 * u] = vfmaq_f32(acc[2 * u], vld1q_f32(a), b);
+                    acc[2 * u + 1] = vfmaq_f32(acc[2 * u + 1], vld1q_f32(a.offset(4)), b);
+                }
+                i += 2;
+            }
+            if i < k {
+                let a = a.offset(8 * i);
+                let b = vdupq_n_f32(*b.offset(i * stride));
+                acc[0] = vfmaq_f32(acc[0], vld1q_f32(a), b);
+                acc[1] = vfmaq_f32(acc[1], vld1q_f32(a.offset(4)), b);
+            }
+            let mut col = [0f32; 8];
+            vst1q_f32(col.as_mut_ptr(), vaddq_f32(acc[0], acc[2]));
+            vst1q_f32(col.as_mut_ptr().offset(4), vaddq_f32(acc[1], acc[3]));
+            for r in 0..8 {
+                ab[r][0] = vsetq_lane_f32::<0>(col[r], vdupq_n_f32(0.0));
+            }
+        }
+        _ => return 1,
+    }
+    let mut pnl = spec.non_linear;
+    loop {
+        if pnl.is_null() {
+            break;
+        }
+        match *pnl {
+            FusedKerSpec::Done => break,
+            FusedKerSpec::AddC => match *spec.c {
+                Strides { ptr: c, row_byte_stride, col_byte_stride } => {
+                    for r in 0..8 {
+                        let row = load_row(
+                            c.offset(r as isize * row_byte_stride / 4),
+                            col_byte_stride / 4,
+                        );
+                        ab[r][0] = vaddq_f32(ab[r][0], row[0]);
+                        ab[r][1] = vaddq_f32(ab[r][1], row[1]);
+                    }
+                }
+                _ => return 1,
+            },
+            FusedKerSpec::PerRowMul(v) => {
+                for r in 0..8 {
+                    let x = vdupq_n_f32(*v.offset(r as isize));
+                    ab[r][0] = vmulq_f32(ab[r][0], x);
+                    ab[r][1] = vmulq_f32(ab[r][1], x);
+                }
+            }
+            FusedKerSpec::PerRowAdd(v) => {
+                for r in 0..8 {
+                    let x = vdupq_n_f32(*v.offset(r as isize));
+                    ab[r][0] = vaddq_f32(ab[r][0], x);
+                    ab[r][1] = vaddq_f32(ab[r][1], x);
+                }
+            }
+            FusedKerSpec::PerColMul(v) => {
+                let x = load_row(v, 1);
+                for r in 0..8 {
+                    ab[r][0] = vmulq_f32(ab[r][0], x[0]);
+                    ab[r][1] = vmulq_f32(ab[r][1], x[1]);
+                }
+            }
+            FusedKerSpec::PerColAdd(v) => {
+                let x = load_row(v, 1);
+                for r in 0..8 {
+                    ab[r][0] = vaddq_f32(ab[r][0], x[0]);
+                    ab[r][1] = vaddq_f32(ab[r][1], x[1]);
+                }
+            }
+            FusedKerSpec::Min(m) => {
+                let m = vdupq_n_f32(m);
+                for r in 0..8 {
+                    ab[r][0] = vminq_f32(ab[r][0], m);
+                    ab[r][1] = vminq_f32(ab[r][1], m);
+                }
+            }
+            FusedKerSpec::Max(m) => {
+                let m = vdupq_n_f32(m);
+                for r in 0..8 {
+                    ab[r][0] = vmaxq_f32(ab[r][0], m);
+                    ab[r][1] = vmaxq_f32(ab[r][1], m);
+                }
+            }
+            FusedKerSpec::AddRowColProducts(rows, cols) => {
+                let cols = load_row(cols, 1);
+                for r in 0..8 {
+                    let x = vdupq_n_f32(*rows.offset(r as isize));
+                    ab[r][0] = vfmaq_f32(ab[r][0], x, cols[0]);
+                    ab[r][1] = vfmaq_f32(ab[r][1], x, cols[1]);
+                }
+            }
+            FusedKerSpec::ScalarMul(s) => {
+                let s = vdupq_n_f32(s);
+                for r in 0..8 {
+                    ab[r][0] = vmulq_f32(ab[r][0], s);
+                    ab[r][1] = vmulq_f32(ab[r][1], s);
+                }
+            }
+            FusedKerSpec::ScalarAdd(s) => {
+                let s = vdupq_n_f32(s);
+                for r in 0..8 {
+                    ab[r][0] = vaddq_f32(ab[r][0], s);
+                    ab[r][1] = vaddq_f32(ab[r][1], s);
+                }
+            }
+            FusedKerSpec::QTowardsEven(mult, shift)
+            | FusedKerSpec::QTowardsPlusInf(mult, shift) => {
+                let s = vdupq_n_f32(mult * 2f32.powi(-(shift as i32)));
+                for r in 0..8 {
+                    ab[r][0] = vmulq_f32(ab[r][0], s);
+                    ab[r][1] = vmulq_f32(ab[r][1], s);
+                }
+            }
+        }
+        pnl = pnl.add(1);
+    }
+    match *spec.c {
+        Strides { ptr: c, row_byte_stride, col_byte_stride } => {
+            for r in 0..8 {
+                store_row(c.offset(r as isize * row_byte_stride / 4), col_byte_stride / 4, ab[r]);
+            }
+        }
+        VecStride { ptr: c, byte_stride } => {
+            let c = c as *mut f32;
+            for r in 0..8 {
+                *c.offset(r as isize * byte_stride / 4) = vgetq_lane_f32::<0>(ab[r][0]);
+            }
+        }
+        _ => return 1,
+    }
+    0
+}
+
+test_mmm_kernel_f32!(
+    crate::arm64::neon::SMatMatMul8x8Neon,
+    test_SMatMatMul8x8Neon,
+    std::arch::is_aarch64_feature_detected!("neon")
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::align::Buffer;
+
+    fn run(
+        mut mmm: Box<dyn MatMatMul<f32, f32, f32, f32>>,
+        a: &[f32],
+        b: &[f32],
+        (m, k, n): (usize, usize, usize),
+        fused: &[FusedSpec<f32>],
+    ) -> Vec<f32> {
+        let mut c = vec![0f32; m * n];
+        unsafe {
+            let pa_spec = mmm.a_pack();
+            let mut pa = Buffer::uninitialized(pa_spec.len(), pa_spec.alignment());
+            pa_spec.pack(pa.as_mut_ptr(), a.as_ptr(), k as isize, 1);
+            if n == 1 {
+                mmm.b_vec_from_data();
+                mmm.c_vec_from_data();
+                mmm.run(pa.as_ptr(), b.as_ptr(), c.as_mut_ptr(), fused);
+            } else {
+                let pb_spec = mmm.b_pack();
+                let mut pb = Buffer::uninitialized(pb_spec.len(), pb_spec.alignment());
+                pb_spec.pack(pb.as_mut_ptr(), b.as_ptr(), n as isize, 1);
+                mmm.run(pa.as_ptr(), pb.as_ptr(), c.as_mut_ptr(), fused);
+            }
+        }
+        c
+    }
+
+    fn check(m: usize, k: usize, n: usize) {
+        if !std::arch::is_aarch64_feature_detected!("neon") {
+            return;
+        }
+        let a: Vec<f32> = (0..m * k).map(|i| ((i * 7 % 13) as f32 - 6.0) / 8.0).collect();
+        let b: Vec<f32> = (0..k * n).map(|i| ((i * 5 % 11) as f32 - 5.0) / 4.0).collect();
+        let bias: Vec<f32> = (0..m).map(|i| i as f32 / 16.0 - 1.0).collect();
+        let fused = [FusedSpec::PerRowAdd(bias), FusedSpec::Max(0.0)];
+        let neon = Box::new(MatMatMulImpl::<SMatMatMul8x8Neon, f32, f32, f32, f32>::new(m, k, n));
+        let found = run(neon, &a, &b, (m, k, n), &fused);
+        let expected = run((crate::generic().smmm)(m, k, n), &a, &b, (m, k, n), &fused);
+        for (f, e) in found.iter().zip(expected.iter()) {
+            assert!((f - e).abs() < 1e-3 * e.abs().max(1.0), "{} != {}", f, e);
+        }
+    }
+
+    #[test]
+    fn mat_vec_512() {
+        check(512, 512, 1)
+    }
+
+    #[test]
+    fn mat_mat_odd_sizes() {
+        check(37, 53, 29)
+    }
+
+    #[test]
+    fn mat_mat_full_tiles() {
+        check(16, 64, 32)
+    }
+}
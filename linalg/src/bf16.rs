@@ -0,0 +1,105 @@
+use std::{fmt, ops};
+
+/// Brain floating point: a f32 truncated to its 16 upper bits. Arithmetic
+/// goes through f32.
+#[allow(non_camel_case_types)]
+#[derive(Copy, Clone, Default, PartialEq, PartialOrd, Debug)]
+#[cfg_attr(feature = "serialize", derive(Serialize))]
+pub struct bf16(pub half::bf16);
+
+impl bf16 {
+    /// Build from the raw bit pattern (the 16 upper bits of a f32).
+    pub fn from_bits(bits: u16) -> bf16 {
+        bf16(half::bf16::from_bits(bits))
+    }
+
+    /// The raw bit pattern.
+    pub fn to_bits(self) -> u16 {
+        self.0.to_bits()
+    }
+
+    pub fn to_f32(self) -> f32 {
+        self.0.to_f32()
+    }
+
+    pub fn to_f64(self) -> f64 {
+        self.0.to_f64()
+    }
+}
+
+impl From<f32> for bf16 {
+    fn from(f: f32) -> bf16 {
+        bf16(half::bf16::from_f32(f))
+    }
+}
+
+impl From<f64> for bf16 {
+    fn from(f: f64) -> bf16 {
+        bf16(half::bf16::from_f64(f))
+    }
+}
+
+impl fmt::Display for bf16 {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        self.0.fmt(fmt)
+    }
+}
+
+impl std::str::FromStr for bf16 {
+    type Err = std::num::ParseFloatError;
+    fn from_str(s: &str) -> Result<bf16, Self::Err> {
+        s.parse::<f32>().map(|f| f.into())
+    }
+}
+
+impl num_traits::Zero for bf16 {
+    fn is_zero(&self) -> bool {
+        self.to_f32() == 0.0
+    }
+    fn zero() -> bf16 {
+        bf16(half::bf16::from_f32(0.0))
+    }
+}
+
+impl num_traits::One for bf16 {
+    fn one() -> bf16 {
+        bf16(half::bf16::from_f32(1.0))
+    }
+}
+
+impl ops::Neg for bf16 {
+    type Output = bf16;
+    fn neg(self) -> bf16 {
+        (-self.to_f32()).into()
+    }
+}
+
+macro_rules! binary_bf16 {
+    ($trait:ident, $f:ident, $assign_trait:ident, $assign_f:ident, $op:tt) => {
+        impl ops::$trait<bf16> for bf16 {
+            type Output = bf16;
+            fn $f(self, other: bf16) -> bf16 {
+                (self.to_f32() $op other.to_f32()).into()
+            }
+        }
+
+        impl ops::$trait<&bf16> for bf16 {
+            type Output = bf16;
+            fn $f(self, other: &bf16) -> bf16 {
+                (self.to_f32() $op other.to_f32()).into()
+            }
+        }
+
+        impl ops::$assign_trait<bf16> for bf16 {
+            fn $assign_f(&mut self, other: bf16) {
+                *self = (self.to_f32() $op other.to_f32()).into()
+            }
+        }
+    };
+}
+
+binary_bf16!(Add, add, AddAssign, add_assign, +);
+binary_bf16!(Sub, sub, SubAssign, sub_assign, -);
+binary_bf16!(Mul, mul, MulAssign, mul_assign, *);
+binary_bf16!(Div, div, DivAssign, div_assign, /);
+binary_bf16!(Rem, rem, RemAssign, rem_assign, %);
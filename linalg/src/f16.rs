@@ -216,6 +216,18 @@ impl num_traits::Signed for f16 {
     }
 }
 
+impl f16 {
+    /// Build from the raw IEEE 754 binary16 bit pattern.
+    pub fn from_bits(bits: u16) -> f16 {
+        f16(half::f16::from_bits(bits))
+    }
+
+    /// The raw IEEE 754 binary16 bit pattern.
+    pub fn to_bits(self) -> u16 {
+        self.0.to_bits()
+    }
+}
+
 impl From<f32> for f16 {
     fn from(f: f32) -> f16 {
         f16(half::f16::from_f32(f))
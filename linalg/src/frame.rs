@@ -5,6 +5,8 @@ pub mod mmm;
 pub mod pack_a;
 pub mod pack_b;
 #[macro_use]
+pub mod reduce;
+#[macro_use]
 pub mod sigmoid;
 #[macro_use]
 pub mod tanh;
@@ -15,5 +17,6 @@ pub use pack_b::PackB;
 pub use self::mmm::*;
 pub use self::qmmm::*;
 
+pub use self::reduce::ReduceImpl;
 pub use self::sigmoid::SigmoidImpl;
 pub use self::tanh::TanhImpl;
@@ -26,6 +26,13 @@ where
     unsafe fn set_zero_point_c_scalar(&mut self, value: TC);
     unsafe fn set_scale_factor(&mut self, factor: f32);
 
+    /// Zero point correction terms that only depend on the packed `a` (and
+    /// on the zero points). When A is constant, they can be computed once,
+    /// then passed to `run` as a fused op once the B zero point has been
+    /// cleared.
+    unsafe fn zero_point_b_terms(&self, a: *const TA) -> Option<FusedSpec<TI>>;
+    unsafe fn clear_zero_point_b(&mut self);
+
     unsafe fn run(&self, a: *const TA, b: *const TB, c: *mut TC, non_linear: &[FusedSpec<TI>]);
 }
 
@@ -133,6 +140,12 @@ where
                     }
                 }
             },
+            MatrixStoreSpec::VecStride { byte_stride, .. } => unsafe {
+                for k in 0..self.k {
+                    let offset = k as isize * byte_stride / std::mem::size_of::<TB>() as isize;
+                    result[0] = result[0] + (*b.offset(offset)).as_();
+                }
+            },
             b => panic!("Storage {:?} for B not supported for quantized ops", b),
         }
         result
@@ -248,6 +261,41 @@ where
         self.scale_factor = Some((int_multi.as_(), shift as usize));
     }
 
+    unsafe fn zero_point_b_terms(&self, a: *const TA) -> Option<FusedSpec<TI>> {
+        let b0 = self.zero_point_b.as_ref()?;
+        let mut sum_a_over_k = self.sum_a_over_k(a);
+        for m in 0..self.m {
+            sum_a_over_k[m] = sum_a_over_k[m].neg();
+            if let Some(ref a0) = self.zero_point_a {
+                match a0 {
+                    QuantizedParam::Scalar(a0) => {
+                        sum_a_over_k[m] = a0.as_() * self.k.as_() + sum_a_over_k[m];
+                    }
+                    QuantizedParam::Vector(a0) => {
+                        sum_a_over_k[m] = a0[m].as_() * self.k.as_() + sum_a_over_k[m];
+                    }
+                }
+            }
+        }
+        let term = match b0 {
+            QuantizedParam::Scalar(b0) => {
+                for m in 0..self.m {
+                    sum_a_over_k[m] = sum_a_over_k[m] * b0.as_();
+                }
+                FusedSpec::PerRowAdd(sum_a_over_k)
+            }
+            QuantizedParam::Vector(b0) => {
+                let b0 = b0.iter().map(|b| b.as_()).collect();
+                FusedSpec::AddRowColProducts(sum_a_over_k, b0)
+            }
+        };
+        Some(term)
+    }
+
+    unsafe fn clear_zero_point_b(&mut self) {
+        self.zero_point_b = None
+    }
+
     unsafe fn run(&self, a: *const TA, b: *const TB, c: *mut TC, non_linear: &[FusedSpec<TI>]) {
         /* SUM_k( A[m,k] * B[k,n] )
             = SUM_k( A'[m,k] * B'[k,n] )
@@ -274,33 +322,7 @@ where
             };
             non_linear.insert(0, term);
         }
-        if let Some(ref b0) = self.zero_point_b {
-            let mut sum_a_over_k = self.sum_a_over_k(a);
-            for m in 0..self.m {
-                sum_a_over_k[m] = sum_a_over_k[m].neg();
-                if let Some(ref a0) = self.zero_point_a {
-                    match a0 {
-                        QuantizedParam::Scalar(a0) => {
-                            sum_a_over_k[m] = a0.as_() * self.k.as_() + sum_a_over_k[m];
-                        }
-                        QuantizedParam::Vector(a0) => {
-                            sum_a_over_k[m] = a0[m].as_() * self.k.as_() + sum_a_over_k[m];
-                        }
-                    }
-                }
-            }
-            let term = match b0 {
-                QuantizedParam::Scalar(b0) => {
-                    for m in 0..self.m {
-                        sum_a_over_k[m] = sum_a_over_k[m] * b0.as_();
-                    }
-                    FusedSpec::PerRowAdd(sum_a_over_k)
-                }
-                QuantizedParam::Vector(b0) => {
-                    let b0 = b0.iter().map(|b| b.as_()).collect();
-                    FusedSpec::AddRowColProducts(sum_a_over_k, b0)
-                }
-            };
+        if let Some(term) = self.zero_point_b_terms(a) {
             non_linear.insert(0, term);
         }
         if let Some(scale) = self.scale_factor {
@@ -19,6 +19,18 @@ impl<T: Copy + Zero + Debug> PackA<T> {
         self.alignment
     }
 
+    pub fn k(&self) -> usize {
+        self.k
+    }
+
+    pub fn m(&self) -> usize {
+        self.m
+    }
+
+    pub fn mr(&self) -> usize {
+        self.mr
+    }
+
     pub fn len(&self) -> usize {
         (self.m + self.mr - 1) / self.mr * self.mr * self.k
     }
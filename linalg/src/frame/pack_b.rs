@@ -19,6 +19,18 @@ impl<T: Copy + Zero + Debug> PackB<T> {
         self.alignment
     }
 
+    pub fn k(&self) -> usize {
+        self.k
+    }
+
+    pub fn n(&self) -> usize {
+        self.n
+    }
+
+    pub fn nr(&self) -> usize {
+        self.nr
+    }
+
     pub fn len(&self) -> usize {
         (self.n + self.nr - 1) / self.nr * self.nr * self.k
     }
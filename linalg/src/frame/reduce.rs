@@ -0,0 +1,111 @@
+use num_traits::Float;
+use std::fmt::Debug;
+use std::marker::PhantomData;
+
+/// Horizontal reductions of a slice to a scalar.
+pub trait Reduce<T>: Send + Sync + Debug + dyn_clone::DynClone
+where
+    T: Copy + Debug + PartialEq + Send + Sync + Float,
+{
+    fn sum(&self, vec: &[T]) -> T;
+    fn max(&self, vec: &[T]) -> T;
+}
+
+dyn_clone::clone_trait_object!(<T> Reduce<T> where T: Copy + Float);
+
+#[derive(Debug, Clone, new)]
+pub struct ReduceImpl<K, T>
+where
+    T: Copy + Debug + PartialEq + Send + Sync + Float,
+    K: ReduceKer<T> + Clone,
+{
+    phantom: PhantomData<(K, T)>,
+}
+
+impl<K, T> Reduce<T> for ReduceImpl<K, T>
+where
+    T: Copy + Debug + PartialEq + Send + Sync + Float,
+    K: ReduceKer<T> + Clone,
+{
+    fn sum(&self, vec: &[T]) -> T {
+        let len = vec.len() / K::nr() * K::nr();
+        let mut acc = if len > 0 { K::sum(&vec[..len]) } else { T::zero() };
+        for &x in &vec[len..] {
+            acc = acc + x;
+        }
+        acc
+    }
+
+    fn max(&self, vec: &[T]) -> T {
+        let len = vec.len() / K::nr() * K::nr();
+        let mut acc = if len > 0 { K::max(&vec[..len]) } else { T::neg_infinity() };
+        for &x in &vec[len..] {
+            acc = acc.max(x);
+        }
+        acc
+    }
+}
+
+/// Reduction kernel, called on slices whose length is a non-zero multiple
+/// of `nr`.
+pub trait ReduceKer<T>: Send + Sync + Debug + dyn_clone::DynClone + Clone
+where
+    T: Copy + Debug + PartialEq + Send + Sync,
+{
+    fn name() -> &'static str;
+    fn nr() -> usize;
+    fn sum(vec: &[T]) -> T;
+    fn max(vec: &[T]) -> T;
+}
+
+#[cfg(test)]
+#[macro_use]
+pub mod test {
+    use super::ReduceKer;
+    use proptest::test_runner::TestCaseResult;
+
+    #[macro_export]
+    macro_rules! reduce_frame_tests {
+        ($cond:expr, $ker:ty) => {
+            proptest::proptest! {
+                #[test]
+                fn reduce(xs in proptest::collection::vec(-25f32..25.0, 0..100)) {
+                    if $cond {
+                        crate::frame::reduce::test::test_reduce::<$ker>(&*xs).unwrap()
+                    }
+                }
+            }
+
+            #[test]
+            fn reduce_empty() {
+                if $cond {
+                    crate::frame::reduce::test::test_reduce::<$ker>(&[]).unwrap()
+                }
+            }
+
+            #[test]
+            fn reduce_negative_max() {
+                if $cond {
+                    crate::frame::reduce::test::test_reduce::<$ker>(&[-3.0; 40]).unwrap()
+                }
+            }
+        };
+    }
+
+    pub fn test_reduce<K: ReduceKer<f32>>(values: &[f32]) -> TestCaseResult {
+        use crate::frame::reduce::Reduce;
+        let op = crate::frame::reduce::ReduceImpl::<K, f32>::new();
+        let expected = values.iter().map(|&x| x as f64).sum::<f64>();
+        let magnitude = values.iter().map(|x| x.abs()).sum::<f32>().max(1.0);
+        let found = op.sum(values);
+        proptest::prop_assert!(
+            (found as f64 - expected).abs() < 1e-5 * magnitude as f64,
+            "sum found: {} expected: {}",
+            found,
+            expected
+        );
+        let expected = values.iter().cloned().fold(f32::NEG_INFINITY, f32::max);
+        proptest::prop_assert_eq!(op.max(values), expected);
+        Ok(())
+    }
+}
@@ -1,9 +1,11 @@
 pub mod lut;
 pub mod mmm;
+pub mod reduce;
 pub mod sigmoid;
 pub mod tanh;
 
 pub use self::lut::GenericLut8;
 pub use self::mmm::GenericMmm4x4;
+pub use self::reduce::SReduce4;
 pub use self::sigmoid::SSigmoid4;
 pub use self::tanh::STanh4;
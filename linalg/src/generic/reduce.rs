@@ -0,0 +1,42 @@
+use crate::frame::reduce::ReduceKer;
+
+#[derive(Clone, Debug)]
+pub struct SReduce4;
+
+impl ReduceKer<f32> for SReduce4 {
+    fn name() -> &'static str {
+        "generic"
+    }
+
+    fn nr() -> usize {
+        4
+    }
+
+    fn sum(vec: &[f32]) -> f32 {
+        debug_assert!(vec.len() % Self::nr() == 0);
+        let mut acc = [0f32; 4];
+        for chunk in vec.chunks_exact(4) {
+            for i in 0..4 {
+                acc[i] += chunk[i];
+            }
+        }
+        (acc[0] + acc[1]) + (acc[2] + acc[3])
+    }
+
+    fn max(vec: &[f32]) -> f32 {
+        debug_assert!(vec.len() % Self::nr() == 0);
+        let mut acc = [f32::NEG_INFINITY; 4];
+        for chunk in vec.chunks_exact(4) {
+            for i in 0..4 {
+                acc[i] = acc[i].max(chunk[i]);
+            }
+        }
+        acc[0].max(acc[1]).max(acc[2].max(acc[3]))
+    }
+}
+
+#[cfg(test)]
+#[macro_use]
+pub mod test {
+    reduce_frame_tests!(true, crate::generic::reduce::SReduce4);
+}
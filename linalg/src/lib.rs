@@ -8,6 +8,7 @@ extern crate num_traits;
 extern crate proptest;
 
 pub mod align;
+pub mod bf16;
 pub mod f16;
 #[macro_use]
 pub mod frame;
@@ -22,8 +23,12 @@ pub mod arm64;
 #[cfg(any(target_arch = "arm", target_arch = "armv7"))]
 pub mod arm32;
 
+#[cfg(all(target_arch = "wasm32", target_feature = "simd128"))]
+pub mod wasm;
+
 pub use self::frame::lut;
 pub use self::frame::mmm;
+pub use self::frame::reduce;
 pub use self::frame::sigmoid;
 pub use self::frame::tanh;
 
@@ -43,6 +48,7 @@ pub struct Ops {
     pub qmmm_i8_i8: Box<
         dyn Fn(usize, usize, usize) -> Box<dyn mmm::QMatMatMul<i8, i8, i8, i32>> + Send + Sync,
     >,
+    pub sreduce: Box<dyn Fn() -> Box<dyn reduce::Reduce<f32>> + Send + Sync>,
     pub ssigmoid: Box<dyn Fn() -> Box<dyn sigmoid::Sigmoid<f32>> + Send + Sync>,
     pub stanh: Box<dyn Fn() -> Box<dyn tanh::Tanh<f32>> + Send + Sync>,
     pub lut_u8: Box<dyn Fn(&[u8]) -> Box<dyn lut::Lut> + Send + Sync>,
@@ -95,6 +101,7 @@ pub fn generic() -> Ops {
                 i32,
             >::new(m, k, n)))
         }),
+        sreduce: Box::new(|| Box::new(reduce::ReduceImpl::<generic::SReduce4, f32>::new())),
         ssigmoid: Box::new(|| Box::new(sigmoid::SigmoidImpl::<generic::SSigmoid4, f32>::new())),
         stanh: Box::new(|| Box::new(tanh::TanhImpl::<generic::STanh4, f32>::new())),
         lut_u8: Box::new(|table: &[u8]| Box::new(lut::LutImpl::<generic::GenericLut8>::new(table))),
@@ -107,20 +114,46 @@ pub fn best() -> Ops {
     #[cfg(target_arch = "x86_64")]
     {
         if is_x86_feature_detected!("fma") {
-            ops.smmm = Box::new(|m, k, n| {
-                Box::new(
-                    mmm::MatMatMulImpl::<x86_64_fma::mmm::SMatMatMul16x6, f32, f32, f32, f32>::new(
-                        m, k, n,
-                    ),
-                )
+            // the AVX2 kernel wins on matrix-vector products, the hand-written
+            // FMA one on all other shapes
+            let avx2 = is_x86_feature_detected!("avx2");
+            ops.smmm = Box::new(move |m, k, n| {
+                if avx2 && n == 1 {
+                    Box::new(mmm::MatMatMulImpl::<
+                        x86_64_fma::avx2::SMatMatMul6x16,
+                        f32,
+                        f32,
+                        f32,
+                        f32,
+                    >::new(m, k, n))
+                } else {
+                    Box::new(mmm::MatMatMulImpl::<
+                        x86_64_fma::mmm::SMatMatMul16x6,
+                        f32,
+                        f32,
+                        f32,
+                        f32,
+                    >::new(m, k, n))
+                }
             });
             log::info!("x86_64/fma activated");
+            if avx2 {
+                log::info!("x86_64/avx2 activated for matrix-vector products");
+            }
+        }
+        if is_x86_feature_detected!("avx") {
+            ops.sreduce = Box::new(|| {
+                Box::new(reduce::ReduceImpl::<x86_64_fma::reduce::SReduce16, f32>::new())
+            });
+            log::info!("x86_64/avx activated for reductions");
         }
     }
     #[cfg(any(target_arch = "arm", target_arch = "armv7"))]
     arm32::plug(&mut ops);
     #[cfg(target_arch = "aarch64")]
     arm64::plug(&mut ops);
+    #[cfg(all(target_arch = "wasm32", target_feature = "simd128"))]
+    wasm::plug(&mut ops);
     return ops;
 }
 
@@ -0,0 +1,12 @@
+pub mod mmm;
+
+use crate::Ops;
+
+use crate::frame::MatMatMulImpl;
+
+pub fn plug(ops: &mut Ops) {
+    log::info!("wasm/simd128 activated for smmm");
+    ops.smmm = Box::new(|m, k, n| {
+        Box::new(MatMatMulImpl::<mmm::SMatMatMul8x4, f32, f32, f32, f32>::new(m, k, n))
+    });
+}
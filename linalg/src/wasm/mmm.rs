@@ -0,0 +1,295 @@
+use std::arch::wasm32::*;
+
+use crate::frame::mmm::LinearSpec::*;
+use crate::frame::mmm::PanelStore::*;
+use crate::frame::mmm::*;
+
+/// f32 kernel in WebAssembly SIMD: 8 rows of 4 columns, one 128-bit
+/// register per row.
+///
+/// WebAssembly has no runtime feature detection: an engine without SIMD
+/// rejects the whole module, so this is only built when `simd128` is
+/// enabled at compile time (`-C target-feature=+simd128`).
+#[derive(Copy, Clone, Debug)]
+pub struct SMatMatMul8x4;
+
+impl MatMatMulKer<f32, f32, f32, f32> for SMatMatMul8x4 {
+    #[inline(always)]
+    fn name() -> &'static str {
+        "wasm-simd128"
+    }
+    #[inline(always)]
+    fn mr() -> usize {
+        8
+    }
+    #[inline(always)]
+    fn nr() -> usize {
+        4
+    }
+    fn alignment_bytes_packed_a() -> usize {
+        16
+    }
+    fn alignment_bytes_packed_b() -> usize {
+        16
+    }
+    #[inline(never)]
+    fn kernel(spec: &MatMatMulKerSpec<f32, f32, f32, f32>) -> isize {
+        unsafe { kernel_8x4(spec) }
+    }
+}
+
+type Tile = [v128; 8];
+
+#[inline(always)]
+unsafe fn load_row(ptr: *const f32, col_stride: isize) -> v128 {
+    if col_stride == 1 {
+        v128_load(ptr as *const v128)
+    } else {
+        f32x4(
+            *ptr,
+            *ptr.offset(col_stride),
+            *ptr.offset(2 * col_stride),
+            *ptr.offset(3 * col_stride),
+        )
+    }
+}
+
+#[inline(always)]
+unsafe fn store_row(ptr: *mut f32, col_stride: isize, values: v128) {
+    if col_stride == 1 {
+        v128_store(ptr as *mut v128, values);
+    } else {
+        *ptr = f32x4_extract_lane::<0>(values);
+        *ptr.offset(col_stride) = f32x4_extract_lane::<1>(values);
+        *ptr.offset(2 * col_stride) = f32x4_extract_lane::<2>(values);
+        *ptr.offset(3 * col_stride) = f32x4_extract_lane::<3>(values);
+    }
+}
+
+#[inline(always)]
+unsafe fn fma_panel(ab: &mut Tile, a: *const f32, b: v128) {
+    for r in 0..8 {
+        let ar = f32x4_splat(*a.offset(r as isize));
+        ab[r] = f32x4_add(ab[r], f32x4_mul(ar, b));
+    }
+}
+
+#[target_feature(enable = "simd128")]
+unsafe fn kernel_8x4(spec: &MatMatMulKerSpec<f32, f32, f32, f32>) -> isize {
+    let mut ab: Tile = [f32x4_splat(0.0); 8];
+    match (*spec.a, *spec.b, *spec.linear) {
+        (Packed { ptr: a }, Packed { ptr: b }, Mul { k }) => {
+            for i in 0..k as isize {
+                let b = v128_load(b.offset(4 * i) as *const v128);
+                fma_panel(&mut ab, a.offset(8 * i), b);
+            }
+        }
+        (Packed { ptr: a }, OffsetsAndPtrs { row_byte_offsets, col_ptrs }, Mul { k }) => {
+            let cols = [*col_ptrs, *col_ptrs.offset(1), *col_ptrs.offset(2), *col_ptrs.offset(3)];
+            for i in 0..k as isize {
+                let offset = *row_byte_offsets.offset(i) / 4;
+                let b = f32x4(
+                    *cols[0].offset(offset),
+                    *cols[1].offset(offset),
+                    *cols[2].offset(offset),
+                    *cols[3].offset(offset),
+                );
+                fma_panel(&mut ab, a.offset(8 * i), b);
+            }
+        }
+        (Packed { ptr: a }, VecStride { ptr: b, byte_stride }, Mul { k }) => {
+            // a single column: accumulate the 8 rows in the lanes of two
+            // registers.
+            let stride = byte_stride / 4;
+            let mut lo = f32x4_splat(0.0);
+            let mut hi = f32x4_splat(0.0);
+            for i in 0..k as isize {
+                let a = a.offset(8 * i);
+                let b = f32x4_splat(*b.offset(i * stride));
+                lo = f32x4_add(lo, f32x4_mul(v128_load(a as *const v128), b));
+                hi = f32x4_add(hi, f32x4_mul(v128_load(a.offset(4) as *const v128), b));
+            }
+            let mut col = [0f32; 8];
+            v128_store(col.as_mut_ptr() as *mut v128, lo);
+            v128_store(col.as_mut_ptr().offset(4) as *mut v128, hi);
+            for r in 0..8 {
+                ab[r] = f32x4(col[r], 0.0, 0.0, 0.0);
+            }
+        }
+        _ => return 1,
+    }
+    let mut pnl = spec.non_linear;
+    loop {
+        if pnl.is_null() {
+            break;
+        }
+        match *pnl {
+            FusedKerSpec::Done => break,
+            FusedKerSpec::AddC => match *spec.c {
+                Strides { ptr: c, row_byte_stride, col_byte_stride } => {
+                    for r in 0..8 {
+                        let row = load_row(
+                            c.offset(r as isize * row_byte_stride / 4),
+                            col_byte_stride / 4,
+                        );
+                        ab[r] = f32x4_add(ab[r], row);
+                    }
+                }
+                _ => return 1,
+            },
+            FusedKerSpec::PerRowMul(v) => {
+                for r in 0..8 {
+                    ab[r] = f32x4_mul(ab[r], f32x4_splat(*v.offset(r as isize)));
+                }
+            }
+            FusedKerSpec::PerRowAdd(v) => {
+                for r in 0..8 {
+                    ab[r] = f32x4_add(ab[r], f32x4_splat(*v.offset(r as isize)));
+                }
+            }
+            FusedKerSpec::PerColMul(v) => {
+                let x = load_row(v, 1);
+                for r in 0..8 {
+                    ab[r] = f32x4_mul(ab[r], x);
+                }
+            }
+            FusedKerSpec::PerColAdd(v) => {
+                let x = load_row(v, 1);
+                for r in 0..8 {
+                    ab[r] = f32x4_add(ab[r], x);
+                }
+            }
+            FusedKerSpec::Min(m) => {
+                let m = f32x4_splat(m);
+                for r in 0..8 {
+                    ab[r] = f32x4_min(ab[r], m);
+                }
+            }
+            FusedKerSpec::Max(m) => {
+                let m = f32x4_splat(m);
+                for r in 0..8 {
+                    ab[r] = f32x4_max(ab[r], m);
+                }
+            }
+            FusedKerSpec::AddRowColProducts(rows, cols) => {
+                let cols = load_row(cols, 1);
+                for r in 0..8 {
+                    let x = f32x4_splat(*rows.offset(r as isize));
+                    ab[r] = f32x4_add(ab[r], f32x4_mul(x, cols));
+                }
+            }
+            FusedKerSpec::ScalarMul(s) => {
+                let s = f32x4_splat(s);
+                for r in 0..8 {
+                    ab[r] = f32x4_mul(ab[r], s);
+                }
+            }
+            FusedKerSpec::ScalarAdd(s) => {
+                let s = f32x4_splat(s);
+                for r in 0..8 {
+                    ab[r] = f32x4_add(ab[r], s);
+                }
+            }
+            FusedKerSpec::QTowardsEven(mult, shift)
+            | FusedKerSpec::QTowardsPlusInf(mult, shift) => {
+                let s = f32x4_splat(mult * 2f32.powi(-(shift as i32)));
+                for r in 0..8 {
+                    ab[r] = f32x4_mul(ab[r], s);
+                }
+            }
+        }
+        pnl = pnl.add(1);
+    }
+    match *spec.c {
+        Strides { ptr: c, row_byte_stride, col_byte_stride } => {
+            for r in 0..8 {
+                store_row(c.offset(r as isize * row_byte_stride / 4), col_byte_stride / 4, ab[r]);
+            }
+        }
+        VecStride { ptr: c, byte_stride } => {
+            let c = c as *mut f32;
+            for r in 0..8 {
+                *c.offset(r as isize * byte_stride / 4) = f32x4_extract_lane::<0>(ab[r]);
+            }
+        }
+        _ => return 1,
+    }
+    0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::align::Buffer;
+    use wasm_bindgen_test::*;
+
+    fn run(
+        mut mmm: Box<dyn MatMatMul<f32, f32, f32, f32>>,
+        a: &[f32],
+        b: &[f32],
+        (m, k, n): (usize, usize, usize),
+        fused: &[FusedSpec<f32>],
+    ) -> Vec<f32> {
+        let mut c = vec![0f32; m * n];
+        unsafe {
+            let pa_spec = mmm.a_pack();
+            let mut pa = Buffer::uninitialized(pa_spec.len(), pa_spec.alignment());
+            pa_spec.pack(pa.as_mut_ptr(), a.as_ptr(), k as isize, 1);
+            if n == 1 {
+                mmm.b_vec_from_data();
+                mmm.c_vec_from_data();
+                mmm.run(pa.as_ptr(), b.as_ptr(), c.as_mut_ptr(), fused);
+            } else {
+                let pb_spec = mmm.b_pack();
+                let mut pb = Buffer::uninitialized(pb_spec.len(), pb_spec.alignment());
+                pb_spec.pack(pb.as_mut_ptr(), b.as_ptr(), n as isize, 1);
+                mmm.run(pa.as_ptr(), pb.as_ptr(), c.as_mut_ptr(), fused);
+            }
+        }
+        c
+    }
+
+    fn check(m: usize, k: usize, n: usize, fused: &[FusedSpec<f32>]) {
+        let a: Vec<f32> = (0..m * k).map(|i| ((i * 7 % 13) as f32 - 6.0) / 8.0).collect();
+        let b: Vec<f32> = (0..k * n).map(|i| ((i * 5 % 11) as f32 - 5.0) / 4.0).collect();
+        let simd = Box::new(MatMatMulImpl::<SMatMatMul8x4, f32, f32, f32, f32>::new(m, k, n));
+        let found = run(simd, &a, &b, (m, k, n), fused);
+        let expected = run((crate::generic().smmm)(m, k, n), &a, &b, (m, k, n), fused);
+        for (f, e) in found.iter().zip(expected.iter()) {
+            assert!((f - e).abs() < 1e-3 * e.abs().max(1.0), "{} != {}", f, e);
+        }
+    }
+
+    #[wasm_bindgen_test]
+    fn mat_vec() {
+        check(67, 129, 1, &[])
+    }
+
+    #[wasm_bindgen_test]
+    fn mat_mat() {
+        check(37, 53, 29, &[])
+    }
+
+    #[wasm_bindgen_test]
+    fn mat_mat_full_tiles() {
+        check(16, 64, 32, &[])
+    }
+
+    #[wasm_bindgen_test]
+    fn fused_bias_relu() {
+        let bias: Vec<f32> = (0..37).map(|i| i as f32 / 16.0 - 1.0).collect();
+        check(37, 53, 29, &[FusedSpec::PerRowAdd(bias), FusedSpec::Max(0.0)])
+    }
+
+    #[wasm_bindgen_test]
+    fn fused_per_col() {
+        let mul: Vec<f32> = (0..29).map(|i| i as f32 / 8.0).collect();
+        let add: Vec<f32> = (0..29).map(|i| 1.0 - i as f32 / 4.0).collect();
+        check(
+            37,
+            53,
+            29,
+            &[FusedSpec::PerColMul(mul), FusedSpec::PerColAdd(add), FusedSpec::Min(2.0)],
+        )
+    }
+}
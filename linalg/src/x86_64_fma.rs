@@ -1 +1,3 @@
+pub mod avx2;
 pub mod mmm;
+pub mod reduce;
@@ -0,0 +1,314 @@
+use std::arch::x86_64::*;
+
+use crate::frame::mmm::LinearSpec::*;
+use crate::frame::mmm::PanelStore::*;
+use crate::frame::mmm::*;
+
+/// f32 kernel in AVX2 intrinsics: 6 rows of 16 columns, each row
+/// accumulated in two 8-float registers.
+#[derive(Copy, Clone, Debug)]
+pub struct SMatMatMul6x16;
+
+impl MatMatMulKer<f32, f32, f32, f32> for SMatMatMul6x16 {
+    #[inline(always)]
+    fn name() -> &'static str {
+        "avx2"
+    }
+    #[inline(always)]
+    fn mr() -> usize {
+        6
+    }
+    #[inline(always)]
+    fn nr() -> usize {
+        16
+    }
+    fn alignment_bytes_packed_a() -> usize {
+        4
+    }
+    fn alignment_bytes_packed_b() -> usize {
+        32
+    }
+    #[inline(never)]
+    fn kernel(spec: &MatMatMulKerSpec<f32, f32, f32, f32>) -> isize {
+        unsafe { kernel_6x16(spec) }
+    }
+}
+
+type Tile = [[__m256; 2]; 6];
+
+#[inline(always)]
+unsafe fn load_row(ptr: *const f32, col_stride: isize) -> [__m256; 2] {
+    if col_stride == 1 {
+        [_mm256_loadu_ps(ptr), _mm256_loadu_ps(ptr.offset(8))]
+    } else {
+        let mut row = [0f32; 16];
+        for j in 0..16 {
+            row[j] = *ptr.offset(j as isize * col_stride);
+        }
+        [_mm256_loadu_ps(row.as_ptr()), _mm256_loadu_ps(row.as_ptr().offset(8))]
+    }
+}
+
+#[inline(always)]
+unsafe fn store_row(ptr: *mut f32, col_stride: isize, values: [__m256; 2]) {
+    if col_stride == 1 {
+        _mm256_storeu_ps(ptr, values[0]);
+        _mm256_storeu_ps(ptr.offset(8), values[1]);
+    } else {
+        let mut row = [0f32; 16];
+        _mm256_storeu_ps(row.as_mut_ptr(), values[0]);
+        _mm256_storeu_ps(row.as_mut_ptr().offset(8), values[1]);
+        for j in 0..16 {
+            *ptr.offset(j as isize * col_stride) = row[j];
+        }
+    }
+}
+
+#[inline(always)]
+unsafe fn fma_panels(ab: &mut Tile, a: *const f32, b0: __m256, b1: __m256) {
+    for r in 0..6 {
+        let ar = _mm256_broadcast_ss(&*a.offset(r as isize));
+        ab[r][0] = _mm256_fmadd_ps(ar, b0, ab[r][0]);
+        ab[r][1] = _mm256_fmadd_ps(ar, b1, ab[r][1]);
+    }
+}
+
+#[target_feature(enable = "avx2,fma")]
+unsafe fn kernel_6x16(spec: &MatMatMulKerSpec<f32, f32, f32, f32>) -> isize {
+    let mut ab: Tile = [[_mm256_setzero_ps(); 2]; 6];
+    match (*spec.a, *spec.b, *spec.linear) {
+        (Packed { ptr: a }, Packed { ptr: b }, Mul { k }) => {
+            for i in 0..k as isize {
+                let b0 = _mm256_loadu_ps(b.offset(16 * i));
+                let b1 = _mm256_loadu_ps(b.offset(16 * i + 8));
+                fma_panels(&mut ab, a.offset(6 * i), b0, b1);
+            }
+        }
+        (Packed { ptr: a }, OffsetsAndPtrs { row_byte_offsets, col_ptrs }, Mul { k }) => {
+            let mut cols = [std::ptr::null::<f32>(); 16];
+            for j in 0..16 {
+                cols[j] = *col_ptrs.offset(j as isize);
+            }
+            let mut b = [0f32; 16];
+            for i in 0..k as isize {
+                let offset = *row_byte_offsets.offset(i) / 4;
+                for j in 0..16 {
+                    b[j] = *cols[j].offset(offset);
+                }
+                let b0 = _mm256_loadu_ps(b.as_ptr());
+                let b1 = _mm256_loadu_ps(b.as_ptr().offset(8));
+                fma_panels(&mut ab, a.offset(6 * i), b0, b1);
+            }
+        }
+        (Packed { ptr: a }, VecStride { ptr: b, byte_stride }, Mul { k }) => {
+            // a single column: accumulate the 6 rows in the lanes of one
+            // register, with four independent accumulators.
+            let stride = byte_stride / 4;
+            let rows = _mm256_setr_epi32(-1, -1, -1, -1, -1, -1, 0, 0);
+            let mut acc = [_mm256_setzero_ps(); 4];
+            let k = k as isize;
+            let mut i = 0;
+            while i + 4 <= k {
+                for u in 0..4 {
+                    let a = _mm256_maskload_ps(a.offset(6 * (i + u as isize)), rows);
+                    let b = _mm256_broadcast_ss(&*b.offset((i + u as isize) * stride));
+                    acc[u] = _mm256_fmadd_ps(a, b, acc[u]);
+                }
+                i += 4;
+            }
+            while i < k {
+                let a = _mm256_maskload_ps(a.offset(6 * i), rows);
+                let b = _mm256_broadcast_ss(&*b.offset(i * stride));
+                acc[0] = _mm256_fmadd_ps(a, b, acc[0]);
+                i += 1;
+            }
+            let sum = _mm256_add_ps(_mm256_add_ps(acc[0], acc[1]), _mm256_add_ps(acc[2], acc[3]));
+            let mut col = [0f32; 8];
+            _mm256_storeu_ps(col.as_mut_ptr(), sum);
+            for r in 0..6 {
+                ab[r][0] = _mm256_setr_ps(col[r], 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0);
+            }
+        }
+        _ => return 1,
+    }
+    let mut pnl = spec.non_linear;
+    loop {
+        if pnl.is_null() {
+            break;
+        }
+        match *pnl {
+            FusedKerSpec::Done => break,
+            FusedKerSpec::AddC => match *spec.c {
+                Strides { ptr: c, row_byte_stride, col_byte_stride } => {
+                    for r in 0..6 {
+                        let row = load_row(
+                            c.offset(r as isize * row_byte_stride / 4),
+                            col_byte_stride / 4,
+                        );
+                        ab[r][0] = _mm256_add_ps(ab[r][0], row[0]);
+                        ab[r][1] = _mm256_add_ps(ab[r][1], row[1]);
+                    }
+                }
+                _ => return 1,
+            },
+            FusedKerSpec::PerRowMul(v) => {
+                for r in 0..6 {
+                    let x = _mm256_broadcast_ss(&*v.offset(r as isize));
+                    ab[r][0] = _mm256_mul_ps(ab[r][0], x);
+                    ab[r][1] = _mm256_mul_ps(ab[r][1], x);
+                }
+            }
+            FusedKerSpec::PerRowAdd(v) => {
+                for r in 0..6 {
+                    let x = _mm256_broadcast_ss(&*v.offset(r as isize));
+                    ab[r][0] = _mm256_add_ps(ab[r][0], x);
+                    ab[r][1] = _mm256_add_ps(ab[r][1], x);
+                }
+            }
+            FusedKerSpec::PerColMul(v) => {
+                let x = load_row(v, 1);
+                for r in 0..6 {
+                    ab[r][0] = _mm256_mul_ps(ab[r][0], x[0]);
+                    ab[r][1] = _mm256_mul_ps(ab[r][1], x[1]);
+                }
+            }
+            FusedKerSpec::PerColAdd(v) => {
+                let x = load_row(v, 1);
+                for r in 0..6 {
+                    ab[r][0] = _mm256_add_ps(ab[r][0], x[0]);
+                    ab[r][1] = _mm256_add_ps(ab[r][1], x[1]);
+                }
+            }
+            FusedKerSpec::Min(m) => {
+                let m = _mm256_set1_ps(m);
+                for r in 0..6 {
+                    ab[r][0] = _mm256_min_ps(ab[r][0], m);
+                    ab[r][1] = _mm256_min_ps(ab[r][1], m);
+                }
+            }
+            FusedKerSpec::Max(m) => {
+                let m = _mm256_set1_ps(m);
+                for r in 0..6 {
+                    ab[r][0] = _mm256_max_ps(ab[r][0], m);
+                    ab[r][1] = _mm256_max_ps(ab[r][1], m);
+                }
+            }
+            FusedKerSpec::AddRowColProducts(rows, cols) => {
+                let cols = load_row(cols, 1);
+                for r in 0..6 {
+                    let x = _mm256_broadcast_ss(&*rows.offset(r as isize));
+                    ab[r][0] = _mm256_fmadd_ps(x, cols[0], ab[r][0]);
+                    ab[r][1] = _mm256_fmadd_ps(x, cols[1], ab[r][1]);
+                }
+            }
+            FusedKerSpec::ScalarMul(s) => {
+                let s = _mm256_set1_ps(s);
+                for r in 0..6 {
+                    ab[r][0] = _mm256_mul_ps(ab[r][0], s);
+                    ab[r][1] = _mm256_mul_ps(ab[r][1], s);
+                }
+            }
+            FusedKerSpec::ScalarAdd(s) => {
+                let s = _mm256_set1_ps(s);
+                for r in 0..6 {
+                    ab[r][0] = _mm256_add_ps(ab[r][0], s);
+                    ab[r][1] = _mm256_add_ps(ab[r][1], s);
+                }
+            }
+            FusedKerSpec::QTowardsEven(mult, shift)
+            | FusedKerSpec::QTowardsPlusInf(mult, shift) => {
+                let s = _mm256_set1_ps(mult * 2f32.powi(-(shift as i32)));
+                for r in 0..6 {
+                    ab[r][0] = _mm256_mul_ps(ab[r][0], s);
+                    ab[r][1] = _mm256_mul_ps(ab[r][1], s);
+                }
+            }
+        }
+        pnl = pnl.add(1);
+    }
+    match *spec.c {
+        Strides { ptr: c, row_byte_stride, col_byte_stride } => {
+            for r in 0..6 {
+                store_row(c.offset(r as isize * row_byte_stride / 4), col_byte_stride / 4, ab[r]);
+            }
+        }
+        VecStride { ptr: c, byte_stride } => {
+            let c = c as *mut f32;
+            for r in 0..6 {
+                *c.offset(r as isize * byte_stride / 4) =
+                    _mm_cvtss_f32(_mm256_castps256_ps128(ab[r][0]));
+            }
+        }
+        _ => return 1,
+    }
+    0
+}
+
+test_mmm_kernel_f32!(
+    crate::x86_64_fma::avx2::SMatMatMul6x16,
+    test_SMatMatMul6x16,
+    is_x86_feature_detected!("avx2") && is_x86_feature_detected!("fma")
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::align::Buffer;
+
+    fn run(
+        mut mmm: Box<dyn MatMatMul<f32, f32, f32, f32>>,
+        a: &[f32],
+        b: &[f32],
+        (m, k, n): (usize, usize, usize),
+        fused: &[FusedSpec<f32>],
+    ) -> Vec<f32> {
+        let mut c = vec![0f32; m * n];
+        unsafe {
+            let pa_spec = mmm.a_pack();
+            let mut pa = Buffer::uninitialized(pa_spec.len(), pa_spec.alignment());
+            pa_spec.pack(pa.as_mut_ptr(), a.as_ptr(), k as isize, 1);
+            if n == 1 {
+                mmm.b_vec_from_data();
+                mmm.c_vec_from_data();
+                mmm.run(pa.as_ptr(), b.as_ptr(), c.as_mut_ptr(), fused);
+            } else {
+                let pb_spec = mmm.b_pack();
+                let mut pb = Buffer::uninitialized(pb_spec.len(), pb_spec.alignment());
+                pb_spec.pack(pb.as_mut_ptr(), b.as_ptr(), n as isize, 1);
+                mmm.run(pa.as_ptr(), pb.as_ptr(), c.as_mut_ptr(), fused);
+            }
+        }
+        c
+    }
+
+    fn check(m: usize, k: usize, n: usize) {
+        if !(is_x86_feature_detected!("avx2") && is_x86_feature_detected!("fma")) {
+            return;
+        }
+        let a: Vec<f32> = (0..m * k).map(|i| ((i * 7 % 13) as f32 - 6.0) / 8.0).collect();
+        let b: Vec<f32> = (0..k * n).map(|i| ((i * 5 % 11) as f32 - 5.0) / 4.0).collect();
+        let bias: Vec<f32> = (0..m).map(|i| i as f32 / 16.0 - 1.0).collect();
+        let fused = [FusedSpec::PerRowAdd(bias), FusedSpec::Max(0.0)];
+        let avx2 = Box::new(MatMatMulImpl::<SMatMatMul6x16, f32, f32, f32, f32>::new(m, k, n));
+        let found = run(avx2, &a, &b, (m, k, n), &fused);
+        let expected = run((crate::generic().smmm)(m, k, n), &a, &b, (m, k, n), &fused);
+        for (f, e) in found.iter().zip(expected.iter()) {
+            assert!((f - e).abs() < 1e-3 * e.abs().max(1.0), "{} != {}", f, e);
+        }
+    }
+
+    #[test]
+    fn mat_vec_512() {
+        check(512, 512, 1)
+    }
+
+    #[test]
+    fn mat_mat_odd_sizes() {
+        check(37, 53, 29)
+    }
+
+    #[test]
+    fn mat_mat_full_tiles() {
+        check(12, 64, 32)
+    }
+}
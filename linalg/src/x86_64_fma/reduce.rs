@@ -0,0 +1,65 @@
+use std::arch::x86_64::*;
+
+use crate::frame::reduce::ReduceKer;
+
+/// f32 reductions in AVX intrinsics, accumulating in two 8-float registers.
+#[derive(Copy, Clone, Debug)]
+pub struct SReduce16;
+
+impl ReduceKer<f32> for SReduce16 {
+    fn name() -> &'static str {
+        "avx"
+    }
+
+    fn nr() -> usize {
+        16
+    }
+
+    fn sum(vec: &[f32]) -> f32 {
+        unsafe { sum_16(vec) }
+    }
+
+    fn max(vec: &[f32]) -> f32 {
+        unsafe { max_16(vec) }
+    }
+}
+
+#[target_feature(enable = "avx")]
+unsafe fn sum_16(vec: &[f32]) -> f32 {
+    debug_assert!(vec.len() % 16 == 0);
+    let ptr = vec.as_ptr();
+    let mut a = _mm256_setzero_ps();
+    let mut b = _mm256_setzero_ps();
+    for i in (0..vec.len()).step_by(16) {
+        a = _mm256_add_ps(a, _mm256_loadu_ps(ptr.add(i)));
+        b = _mm256_add_ps(b, _mm256_loadu_ps(ptr.add(i + 8)));
+    }
+    let a = _mm256_add_ps(a, b);
+    let x = _mm_add_ps(_mm256_castps256_ps128(a), _mm256_extractf128_ps(a, 1));
+    let x = _mm_add_ps(x, _mm_movehl_ps(x, x));
+    let x = _mm_add_ss(x, _mm_shuffle_ps(x, x, 1));
+    _mm_cvtss_f32(x)
+}
+
+#[target_feature(enable = "avx")]
+unsafe fn max_16(vec: &[f32]) -> f32 {
+    debug_assert!(vec.len() % 16 == 0);
+    let ptr = vec.as_ptr();
+    let mut a = _mm256_set1_ps(f32::NEG_INFINITY);
+    let mut b = a;
+    for i in (0..vec.len()).step_by(16) {
+        a = _mm256_max_ps(a, _mm256_loadu_ps(ptr.add(i)));
+        b = _mm256_max_ps(b, _mm256_loadu_ps(ptr.add(i + 8)));
+    }
+    let a = _mm256_max_ps(a, b);
+    let x = _mm_max_ps(_mm256_castps256_ps128(a), _mm256_extractf128_ps(a, 1));
+    let x = _mm_max_ps(x, _mm_movehl_ps(x, x));
+    let x = _mm_max_ss(x, _mm_shuffle_ps(x, x, 1));
+    _mm_cvtss_f32(x)
+}
+
+#[cfg(test)]
+#[macro_use]
+pub mod test {
+    reduce_frame_tests!(is_x86_feature_detected!("avx"), crate::x86_64_fma::reduce::SReduce16);
+}
@@ -0,0 +1,292 @@
+//! Serialization of a TypedModel to ONNX.
+use std::convert::{TryFrom, TryInto};
+use std::path::Path;
+
+use prost::Message;
+
+use tract_core::internal::*;
+use tract_core::ops::array::{FiniteReshape, PermuteAxes, TypedReshape};
+use tract_core::ops::binary::{BinMiniOp, MergeOp, MergeOpUnicast, TypedBinOp, UnaryOp};
+use tract_core::ops::element_wise::ElementWiseOp;
+use tract_core::ops::matmul::MatMul;
+
+use crate::pb;
+use crate::pb::tensor_proto::DataType;
+
+/// Write a TypedModel as an ONNX protobuf file.
+pub fn export_to_onnx(model: &TypedModel, path: &Path) -> TractResult<()> {
+    let proto = model_to_proto(model)?;
+    let mut buffer = vec![];
+    proto.encode(&mut buffer).map_err(|e| format!("Encoding ONNX model: {:?}", e))?;
+    std::fs::write(path, buffer)?;
+    Ok(())
+}
+
+/// Convert a TypedModel to an ONNX model proto.
+///
+/// Fails on the first node with an op that has no ONNX equivalent.
+pub fn model_to_proto(model: &TypedModel) -> TractResult<pb::ModelProto> {
+    let mut graph = pb::GraphProto::default();
+    graph.name = "tract".to_string();
+    let inputs = model.input_outlets()?;
+    let outputs = model.output_outlets()?;
+    for &input in inputs {
+        graph.input.push(value_info(model, input)?);
+    }
+    for n in model.eval_order()? {
+        let node = model.node(n);
+        if inputs.iter().any(|i| i.node == n) {
+            continue;
+        }
+        if node.inputs.is_empty() {
+            if let Some(konst) = &node.outputs[0].fact.konst {
+                let mut tensor: pb::TensorProto = (&**konst).try_into()?;
+                tensor.name = outlet_name(model, OutletId::new(n, 0));
+                graph.initializer.push(tensor);
+                continue;
+            }
+        }
+        if let Some(pbnode) = node_to_proto(model, node, &mut graph.initializer)? {
+            graph.node.push(pbnode);
+            for slot in 0..node.outputs.len() {
+                let outlet = OutletId::new(n, slot);
+                if !outputs.contains(&outlet) {
+                    graph.value_info.push(value_info(model, outlet)?);
+                }
+            }
+        } else {
+            bail!("No ONNX equivalent for {} op in node {}", node.op().name(), node)
+        }
+    }
+    for &output in outputs {
+        graph.output.push(value_info(model, output)?);
+    }
+    Ok(pb::ModelProto {
+        ir_version: 6,
+        opset_import: vec![pb::OperatorSetIdProto { domain: String::new(), version: 11 }],
+        producer_name: "tract".to_string(),
+        producer_version: env!("CARGO_PKG_VERSION").to_string(),
+        graph: Some(graph),
+        ..pb::ModelProto::default()
+    })
+}
+
+fn outlet_name(model: &TypedModel, outlet: OutletId) -> String {
+    if let Some(label) = model.outlet_label(outlet) {
+        label.to_string()
+    } else if outlet.slot == 0 {
+        model.node(outlet.node).name.clone()
+    } else {
+        format!("{}:{}", model.node(outlet.node).name, outlet.slot)
+    }
+}
+
+fn value_info(model: &TypedModel, outlet: OutletId) -> TractResult<pb::ValueInfoProto> {
+    use pb::tensor_shape_proto::dimension::Value;
+    let fact = model.outlet_fact(outlet)?;
+    let dim = fact
+        .shape
+        .iter()
+        .map(|d| {
+            let value = if let Ok(d) = d.to_integer() {
+                Value::DimValue(d as i64)
+            } else {
+                Value::DimParam(format!("{:?}", d))
+            };
+            pb::tensor_shape_proto::Dimension { denotation: String::new(), value: Some(value) }
+        })
+        .collect();
+    let tensor = pb::type_proto::Tensor {
+        elem_type: DataType::try_from(fact.datum_type)? as i32,
+        shape: Some(pb::TensorShapeProto { dim }),
+    };
+    Ok(pb::ValueInfoProto {
+        name: outlet_name(model, outlet),
+        r#type: Some(pb::TypeProto {
+            denotation: String::new(),
+            value: Some(pb::type_proto::Value::TensorType(tensor)),
+        }),
+        doc_string: String::new(),
+    })
+}
+
+fn bin_op_type(op: &dyn BinMiniOp) -> Option<&'static str> {
+    let op_type = match op.name() {
+        "Add" => "Add",
+        "Sub" => "Sub",
+        "Mul" => "Mul",
+        "Div" => "Div",
+        "Min" => "Min",
+        "Max" => "Max",
+        "Pow" => "Pow",
+        "And" => "And",
+        "Or" => "Or",
+        "Xor" => "Xor",
+        "Equals" => "Equal",
+        "Lesser" => "Less",
+        "Greatser" => "Greater",
+        _ => return None,
+    };
+    Some(op_type)
+}
+
+fn element_wise_op_type(op: &ElementWiseOp) -> Option<&'static str> {
+    let op_type = match &*op.0.name() {
+        "Abs" => "Abs",
+        "Exp" => "Exp",
+        "Ln" => "Log",
+        "Sqrt" => "Sqrt",
+        "Recip" => "Reciprocal",
+        "Ceil" => "Ceil",
+        "Floor" => "Floor",
+        "Cos" => "Cos",
+        "Sin" => "Sin",
+        "Tan" => "Tan",
+        "Acos" => "Acos",
+        "Asin" => "Asin",
+        "Atan" => "Atan",
+        "Cosh" => "Cosh",
+        "Sinh" => "Sinh",
+        "Tanh" => "Tanh",
+        "Acosh" => "Acosh",
+        "Asinh" => "Asinh",
+        "Atanh" => "Atanh",
+        "Neg" => "Neg",
+        "Sign" => "Sign",
+        "Softplus" => "Softplus",
+        "Softsign" => "Softsign",
+        "Sigmoid" => "Sigmoid",
+        "Not" => "Not",
+        _ => return None,
+    };
+    Some(op_type)
+}
+
+/// Add a constant tensor as an initializer, returning its name.
+fn initializer(
+    initializers: &mut Vec<pb::TensorProto>,
+    name: String,
+    tensor: &Tensor,
+) -> TractResult<String> {
+    let mut proto: pb::TensorProto = tensor.try_into()?;
+    proto.name = name.clone();
+    initializers.push(proto);
+    Ok(name)
+}
+
+fn node_to_proto(
+    model: &TypedModel,
+    node: &TypedNode,
+    initializers: &mut Vec<pb::TensorProto>,
+) -> TractResult<Option<pb::NodeProto>> {
+    let mut inputs: Vec<String> = node.inputs.iter().map(|i| outlet_name(model, *i)).collect();
+    let mut attribute = vec![];
+    let op = node.op();
+    let op_type = if let Some(op) = op.downcast_ref::<TypedBinOp>() {
+        bin_op_type(&*op.0)
+    } else if let Some(op) = op.downcast_ref::<MergeOp>() {
+        bin_op_type(&*op.0)
+    } else if let Some(op) = op.downcast_ref::<MergeOpUnicast>() {
+        bin_op_type(&*op.0)
+    } else if let Some(op) = op.downcast_ref::<UnaryOp>() {
+        let op_type = bin_op_type(&*op.mini_op);
+        if op_type.is_some() {
+            let a = initializer(initializers, format!("{}.a", node.name), &op.a)?;
+            inputs.insert(0, a);
+        }
+        op_type
+    } else if let Some(op) = op.downcast_ref::<ElementWiseOp>() {
+        element_wise_op_type(op)
+    } else if let Some(op) = op.downcast_ref::<MatMul>() {
+        if op == &MatMul::default() {
+            Some("MatMul")
+        } else {
+            None
+        }
+    } else if let Some(op) = op.downcast_ref::<PermuteAxes>() {
+        if let Some(axes) = &op.axes {
+            attribute.push(pb::AttributeProto {
+                name: "perm".to_string(),
+                r#type: pb::attribute_proto::AttributeType::Ints as i32,
+                ints: axes.iter().map(|&a| a as i64).collect(),
+                ..pb::AttributeProto::default()
+            });
+        }
+        Some("Transpose")
+    } else if op.downcast_ref::<TypedReshape>().is_some()
+        || op.downcast_ref::<FiniteReshape>().is_some()
+    {
+        let shape = node.outputs[0]
+            .fact
+            .shape
+            .iter()
+            .map(|d| Ok(d.to_integer()? as i64))
+            .collect::<TractResult<Vec<i64>>>();
+        if let Ok(shape) = shape {
+            let shape = tensor1(&shape);
+            inputs.push(initializer(initializers, format!("{}.shape", node.name), &shape)?);
+            Some("Reshape")
+        } else {
+            None
+        }
+    } else {
+        None
+    };
+    Ok(op_type.map(|op_type| pb::NodeProto {
+        input: inputs,
+        output: (0..node.outputs.len())
+            .map(|s| outlet_name(model, OutletId::new(node.id, s)))
+            .collect(),
+        name: node.name.clone(),
+        op_type: op_type.to_string(),
+        domain: String::new(),
+        attribute,
+        doc_string: String::new(),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tract_core::ops::{math, nn};
+
+    fn model() -> TractResult<TypedModel> {
+        let mut model = TypedModel::default();
+        let fact = TypedFact::dt_shape(f32::datum_type(), [2usize, 3].as_ref())?;
+        let x = model.add_source("x", fact)?;
+        let w = model.add_const("w", tensor2(&[[1f32, -1.0], [0.5, 2.0], [0.0, 1.0]]))?;
+        let y = model.wire_node("fc", MatMul::default(), &[x, w])?;
+        let y = model.wire_node("exp", math::exp(), &y)?;
+        let y = model.wire_node("scale", math::mul::unary(rctensor0(0.5f32)), &y)?;
+        let y = model.wire_node("t", PermuteAxes::new(Some(vec![1, 0])), &y)?;
+        model.set_output_outlets(&y)?;
+        Ok(model)
+    }
+
+    #[test]
+    fn export_reload_run() -> TractResult<()> {
+        let model = model()?;
+        let input = tensor2(&[[1f32, 2.0, 3.0], [-1.0, 0.5, 0.0]]);
+        let expected = SimplePlan::new(&model)?.run(tvec!(input.clone()))?.remove(0);
+
+        let path = std::env::temp_dir().join(format!("tract-export-{}.onnx", std::process::id()));
+        export_to_onnx(&model, &path)?;
+        let reloaded = crate::onnx().model_for_path(&path)?.into_optimized()?;
+        std::fs::remove_file(&path)?;
+
+        assert_eq!(reloaded.input_outlets()?.len(), 1);
+        let found = SimplePlan::new(&reloaded)?.run(tvec!(input))?.remove(0);
+        found.close_enough(&expected, true)
+    }
+
+    #[test]
+    fn unexportable_op() -> TractResult<()> {
+        let mut model = model()?;
+        let y = model.output_outlets()?[0];
+        let y = model.wire_node("mish", nn::mish(), &[y])?;
+        model.set_output_outlets(&y)?;
+        let err = model_to_proto(&model).unwrap_err();
+        assert!(format!("{}", err).contains("Mish"), "{}", err);
+        Ok(())
+    }
+}
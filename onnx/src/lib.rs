@@ -8,11 +8,13 @@ extern crate error_chain;
 extern crate log;
 extern crate num_integer;
 extern crate num_traits;
+extern crate serde_json;
 #[allow(unused_imports)]
 #[macro_use]
 extern crate tract_core;
 extern crate tract_linalg;
 
+pub mod export;
 pub mod model;
 pub mod ops;
 
@@ -21,9 +23,11 @@ pub mod pb {
 }
 
 pub mod pb_helpers;
+pub mod safetensors;
 pub mod tensor;
 
-pub use model::Onnx;
+pub use model::{Onnx, OnnxLoadOptions};
+pub use safetensors::load_safetensors;
 use tract_core::internal::*;
 
 #[deprecated(note = "Please use onnx().model_for_path(..)")]
@@ -39,5 +43,5 @@ pub fn for_reader<R: std::io::Read>(mut r: R) -> TractResult<InferenceModel> {
 pub fn onnx() -> Onnx {
     let mut ops = crate::model::OnnxOpRegister::default();
     ops::register_all_ops(&mut ops);
-    Onnx { op_register: ops }
+    Onnx { op_register: ops, ..Onnx::default() }
 }
@@ -1,6 +1,7 @@
 use std::convert::TryInto;
 
 use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 
 use tract_core::internal::*;
 
@@ -56,7 +57,10 @@ impl<'a> ParsingContext<'a> {
         let mut initializers: HashMap<&str, Tensor> = graph
             .initializer
             .iter()
-            .map(|init| Ok((&*init.name, init.try_into()?)))
+            .map(|init| {
+                let dir = self.framework.load_options.external_data_dir.as_deref();
+                Ok((&*init.name, crate::tensor::load_tensor(init, dir)?))
+            })
             .collect::<TractResult<_>>()?;
         for (k, v) in initializers.iter() {
             trace!("Initializer: {} {:?}", k, v);
@@ -188,12 +192,41 @@ impl OnnxOpRegister {
     }
 }
 
+/// Options controlling how ONNX models are loaded.
+#[derive(Clone, Debug, Default)]
+pub struct OnnxLoadOptions {
+    /// Directory holding the files of tensors stored as external data.
+    /// Loading a model from a path defaults to the model directory.
+    pub external_data_dir: Option<PathBuf>,
+    /// Fail at load time, listing all of them, if the model contains
+    /// operators tract does not implement.
+    ///
+    /// Off by default: such operators may be pruned later, or be run through
+    /// a fallback handler registered in the SessionState.
+    pub check_unimplemented: bool,
+}
+
+impl OnnxLoadOptions {
+    pub fn external_data_dir(self, path: &Path) -> OnnxLoadOptions {
+        OnnxLoadOptions { external_data_dir: Some(path.to_owned()), ..self }
+    }
+
+    pub fn check_unimplemented(self, check_unimplemented: bool) -> OnnxLoadOptions {
+        OnnxLoadOptions { check_unimplemented, ..self }
+    }
+}
+
 #[derive(Clone, Default)]
 pub struct Onnx {
     pub op_register: OnnxOpRegister,
+    pub load_options: OnnxLoadOptions,
 }
 
 impl Onnx {
+    pub fn with_load_options(self, load_options: OnnxLoadOptions) -> Onnx {
+        Onnx { load_options, ..self }
+    }
+
     pub fn parse(&self, proto: &pb::ModelProto) -> TractResult<ParseResult> {
         let onnx_operator_set_version =
             proto.opset_import.iter().find(|import| import.domain == "").unwrap().version;
@@ -221,6 +254,390 @@ impl Framework<pb::ModelProto> for Onnx {
         if unresolved_inputs.len() > 0 {
             bail!("Could not resolve inputs at top-level: {:?}", unresolved_inputs)
         }
+        if self.load_options.check_unimplemented {
+            tract_core::ops::unimpl::check_unimplemented_ops(&model)?;
+        }
         Ok(model)
     }
+
+    fn model_for_path(&self, p: impl AsRef<Path>) -> TractResult<InferenceModel> {
+        let p = p.as_ref();
+        let mut r = std::fs::File::open(p).map_err(|e| format!("Could not open {:?}: {}", p, e))?;
+        match p.parent() {
+            Some(dir) if self.load_options.external_data_dir.is_none() => {
+                let options = self.load_options.clone().external_data_dir(dir);
+                self.clone().with_load_options(options).model_for_read(&mut r)
+            }
+            _ => self.model_for_read(&mut r),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pb::tensor_proto::{DataLocation, DataType};
+    use tract_core::ops::matmul::MatMul;
+    use tract_core::ops::unimpl::UnimplementedOp;
+
+    fn entry(key: &str, value: &str) -> pb::StringStringEntryProto {
+        pb::StringStringEntryProto { key: key.to_string(), value: value.to_string() }
+    }
+
+    // A MatMul against a weight stored at some offset of a side file.
+    fn externalized(dir: &Path, weights: &Tensor) -> TractResult<PathBuf> {
+        let mut model = TypedModel::default();
+        let x =
+            model.add_source("x", TypedFact::dt_shape(f32::datum_type(), [1usize, 3].as_ref())?)?;
+        let w = model.add_const("w", weights.clone())?;
+        let y = model.wire_node("fc", MatMul::default(), &[x, w])?[0];
+        model.set_output_outlets(&[y])?;
+        let mut proto = crate::export::model_to_proto(&model)?;
+        let graph = proto.graph.as_mut().unwrap();
+        let init = graph.initializer.iter_mut().find(|i| i.name == "w").unwrap();
+        let mut data = vec![0u8; 4096];
+        data.extend(std::mem::take(&mut init.raw_data));
+        let length = data.len() - 4096;
+        std::fs::write(dir.join("weights.bin"), data)?;
+        init.data_location = DataLocation::External as i32;
+        init.external_data = vec![
+            entry("location", "weights.bin"),
+            entry("offset", "4096"),
+            entry("length", &length.to_string()),
+        ];
+        let mut buffer = vec![];
+        proto.encode(&mut buffer).map_err(|e| format!("{:?}", e))?;
+        let path = dir.join("model.onnx");
+        std::fs::write(&path, buffer)?;
+        Ok(path)
+    }
+
+    fn run(model: InferenceModel) -> TractResult<Arc<Tensor>> {
+        let model = model.into_typed()?;
+        Ok(SimplePlan::new(&model)?.run(tvec!(tensor2(&[[1f32, 2.0, -1.0]])))?.remove(0))
+    }
+
+    #[test]
+    fn external_data() -> TractResult<()> {
+        let dir = std::env::temp_dir().join(format!("tract-external-{}", std::process::id()));
+        std::fs::create_dir_all(&dir)?;
+        let weights = tensor2(&[[0.5f32, -1.0], [2.0, 0.25], [1.5, 3.0]]);
+        let path = externalized(&dir, &weights)?;
+        let expected = tensor2(&[[3.0f32, -3.5]]);
+
+        assert_eq!(*run(crate::onnx().model_for_path(&path)?)?, expected);
+
+        let mut file = std::fs::File::open(&path)?;
+        assert!(crate::onnx().model_for_read(&mut file).is_err());
+        let options = OnnxLoadOptions::default().external_data_dir(&dir);
+        let onnx = crate::onnx().with_load_options(options);
+        let mut file = std::fs::File::open(&path)?;
+        assert_eq!(*run(onnx.model_for_read(&mut file)?)?, expected);
+
+        std::fs::write(dir.join("weights.bin"), vec![0u8; 4100])?;
+        let err = crate::onnx().model_for_path(&path).unwrap_err();
+        assert!(format!("{}", err).contains("past the end"), "{}", err);
+
+        std::fs::remove_file(dir.join("weights.bin"))?;
+        let err = crate::onnx().model_for_path(&path).unwrap_err();
+        assert!(format!("{}", err).contains("weights.bin"), "{}", err);
+
+        std::fs::remove_dir_all(&dir)?;
+        Ok(())
+    }
+
+    fn attr(name: &str, ty: pb::attribute_proto::AttributeType) -> pb::AttributeProto {
+        pb::AttributeProto { name: name.to_string(), r#type: ty as i32, ..Default::default() }
+    }
+
+    fn value_info(name: &str, dt: DataType, shape: Option<&[i64]>) -> pb::ValueInfoProto {
+        use pb::tensor_shape_proto::{dimension, Dimension};
+        let shape = shape.map(|shape| pb::TensorShapeProto {
+            dim: shape
+                .iter()
+                .map(|&d| Dimension {
+                    value: Some(dimension::Value::DimValue(d)),
+                    ..Default::default()
+                })
+                .collect(),
+        });
+        let tensor = pb::type_proto::Tensor { elem_type: dt as i32, shape };
+        pb::ValueInfoProto {
+            name: name.to_string(),
+            r#type: Some(pb::TypeProto {
+                value: Some(pb::type_proto::Value::TensorType(tensor)),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+
+    fn float_input(name: &str) -> pb::ValueInfoProto {
+        value_info(name, DataType::Float, None)
+    }
+
+    // A model made of a single node, its inputs and output f32 of unknown shape.
+    fn single_node_proto(
+        op_type: &str,
+        inputs: &[&str],
+        attribute: Vec<pb::AttributeProto>,
+    ) -> pb::ModelProto {
+        let node = pb::NodeProto {
+            name: "node".to_string(),
+            op_type: op_type.to_string(),
+            input: inputs.iter().map(|s| s.to_string()).collect(),
+            output: vec!["y".to_string()],
+            attribute,
+            ..Default::default()
+        };
+        let graph = pb::GraphProto {
+            node: vec![node],
+            input: inputs.iter().map(|i| float_input(i)).collect(),
+            output: vec![float_input("y")],
+            ..Default::default()
+        };
+        pb::ModelProto {
+            ir_version: 6,
+            opset_import: vec![pb::OperatorSetIdProto { domain: String::new(), version: 11 }],
+            graph: Some(graph),
+            ..Default::default()
+        }
+    }
+
+    fn single_node(
+        op_type: &str,
+        inputs: &[&str],
+        attribute: Vec<pb::AttributeProto>,
+    ) -> TractResult<InferenceModel> {
+        crate::onnx().model_for_proto_model(&single_node_proto(op_type, inputs, attribute))
+    }
+
+    fn error(result: TractResult<impl std::fmt::Debug>) -> String {
+        result.unwrap_err().iter().map(|e| e.to_string()).collect::<Vec<_>>().join(": ")
+    }
+
+    #[test]
+    fn invalid_conv_attributes() -> TractResult<()> {
+        use pb::attribute_proto::AttributeType::*;
+        let ints =
+            |name, values: &[i64]| pb::AttributeProto { ints: values.to_vec(), ..attr(name, Ints) };
+        let auto_pad = pb::AttributeProto { s: b"SAME_UPPER".to_vec(), ..attr("auto_pad", String) };
+        let err =
+            error(single_node("Conv", &["x", "w"], vec![ints("pads", &[1, 1, 1, 1]), auto_pad]));
+        assert!(err.contains("auto_pad"), "{}", err);
+
+        let model = single_node("Conv", &["x", "w"], vec![ints("pads", &[1, 1, 1])])?;
+        let err = error(model.into_typed());
+        assert!(err.contains("Invalid attributes for") && err.contains("padding"), "{}", err);
+
+        let attributes = vec![ints("pads", &[1, 1, 1, 1]), ints("strides", &[1, 1, 1])];
+        let err = error(single_node("Conv", &["x", "w"], attributes)?.into_typed());
+        assert!(err.contains("pads has 2 spatial axes, but strides has 3"), "{}", err);
+        Ok(())
+    }
+
+    #[test]
+    fn invalid_gemm_attributes() -> TractResult<()> {
+        use pb::attribute_proto::AttributeType::*;
+        let alpha = pb::AttributeProto { f: std::f32::INFINITY, ..attr("alpha", Float) };
+        let err = error(single_node("Gemm", &["a", "b", "c"], vec![alpha])?.into_typed());
+        assert!(err.contains("Gemm alpha and beta must be finite, got inf"), "{}", err);
+        Ok(())
+    }
+
+    #[test]
+    fn check_unimplemented_is_opt_in() -> TractResult<()> {
+        let proto = single_node_proto("NotAnOnnxOp", &["x"], vec![]);
+        let model = crate::onnx().model_for_proto_model(&proto)?;
+        assert!(model.node(model.output_outlets()?[0].node).op_is::<UnimplementedOp>());
+        let options = OnnxLoadOptions::default().check_unimplemented(true);
+        let err = error(crate::onnx().with_load_options(options).model_for_proto_model(&proto));
+        assert!(err.contains("NotAnOnnxOp"), "{}", err);
+        Ok(())
+    }
+
+    fn node(
+        op_type: &str,
+        inputs: &[&str],
+        outputs: &[&str],
+        attribute: Vec<pb::AttributeProto>,
+    ) -> pb::NodeProto {
+        pb::NodeProto {
+            name: outputs[0].to_string(),
+            op_type: op_type.to_string(),
+            input: inputs.iter().map(|s| s.to_string()).collect(),
+            output: outputs.iter().map(|s| s.to_string()).collect(),
+            attribute,
+            ..Default::default()
+        }
+    }
+
+    fn graph_attr(
+        name: &str,
+        node: Vec<pb::NodeProto>,
+        input: Vec<pb::ValueInfoProto>,
+        output: Vec<pb::ValueInfoProto>,
+    ) -> pb::AttributeProto {
+        let g =
+            pb::GraphProto { name: name.to_string(), node, input, output, ..Default::default() };
+        pb::AttributeProto { g: Some(g), ..attr(name, pb::attribute_proto::AttributeType::Graph) }
+    }
+
+    fn model_proto(
+        node: Vec<pb::NodeProto>,
+        input: Vec<pb::ValueInfoProto>,
+        output: &[&str],
+    ) -> pb::ModelProto {
+        let output = output.iter().map(|o| float_input(o)).collect();
+        let graph = pb::GraphProto { node, input, output, ..Default::default() };
+        pb::ModelProto {
+            ir_version: 6,
+            opset_import: vec![pb::OperatorSetIdProto { domain: String::new(), version: 11 }],
+            graph: Some(graph),
+            ..Default::default()
+        }
+    }
+
+    fn run_plain_and_optimized(
+        proto: &pb::ModelProto,
+        inputs: TVec<Tensor>,
+    ) -> TractResult<Vec<TVec<Arc<Tensor>>>> {
+        let mut model = crate::onnx().model_for_proto_model(proto)?;
+        model.analyse(false)?;
+        let plain = SimplePlan::new(&model)?.run(inputs.clone())?;
+        let optimized = SimplePlan::new(model.into_optimized()?)?.run(inputs)?;
+        Ok(vec![plain, optimized])
+    }
+
+    #[test]
+    fn if_closing_on_outer_values() -> TractResult<()> {
+        // then: x + bias, else: -x, with only then closing on bias
+        let then_branch = graph_attr(
+            "then_branch",
+            vec![node("Add", &["x", "bias"], &["sum"], vec![])],
+            vec![],
+            vec![float_input("sum")],
+        );
+        let else_branch = graph_attr(
+            "else_branch",
+            vec![node("Neg", &["x"], &["neg"], vec![])],
+            vec![],
+            vec![float_input("neg")],
+        );
+        let proto = model_proto(
+            vec![node("If", &["cond"], &["y"], vec![then_branch, else_branch])],
+            vec![
+                value_info("cond", DataType::Bool, Some(&[])),
+                value_info("x", DataType::Float, Some(&[3])),
+                value_info("bias", DataType::Float, Some(&[3])),
+            ],
+            &["y"],
+        );
+        let (x, bias) = (tensor1(&[1f32, -2.0, 0.5]), tensor1(&[1f32, 2.0, 3.0]));
+        for &(cond, ref expected) in
+            &[(true, rctensor1(&[2f32, 0.0, 3.5])), (false, rctensor1(&[-1f32, 2.0, -0.5]))]
+        {
+            let inputs = tvec!(tensor0(cond), x.clone(), bias.clone());
+            for outputs in run_plain_and_optimized(&proto, inputs)? {
+                assert_eq!(&outputs[0], expected);
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn loop_closing_on_outer_values() -> TractResult<()> {
+        // four times acc += x, with x from the outer graph, and the partial sums
+        let body = graph_attr(
+            "body",
+            vec![
+                node("Add", &["acc_in", "x"], &["acc_out"], vec![]),
+                node("Identity", &["cond_in"], &["cond_out"], vec![]),
+                node("Identity", &["acc_out"], &["partial"], vec![]),
+            ],
+            vec![
+                value_info("iter", DataType::Int64, Some(&[])),
+                value_info("cond_in", DataType::Bool, Some(&[])),
+                value_info("acc_in", DataType::Float, Some(&[3])),
+            ],
+            vec![
+                value_info("cond_out", DataType::Bool, None),
+                float_input("acc_out"),
+                float_input("partial"),
+            ],
+        );
+        let proto = model_proto(
+            vec![node("Loop", &["trip", "", "acc"], &["sum", "partials"], vec![body])],
+            vec![
+                value_info("trip", DataType::Int64, Some(&[])),
+                value_info("acc", DataType::Float, Some(&[3])),
+                value_info("x", DataType::Float, Some(&[3])),
+            ],
+            &["sum", "partials"],
+        );
+        let inputs = tvec!(tensor0(4i64), tensor1(&[0f32, 1.0, 2.0]), tensor1(&[1f32, -1.0, 0.5]));
+        let partials = tract_core::ndarray::arr2(&[
+            [1f32, 0.0, 2.5],
+            [2.0, -1.0, 3.0],
+            [3.0, -2.0, 3.5],
+            [4.0, -3.0, 4.0],
+        ]);
+        for outputs in run_plain_and_optimized(&proto, inputs)? {
+            assert_eq!(outputs.len(), 2);
+            assert_eq!(outputs[0], rctensor1(&[4f32, -3.0, 4.0]));
+            assert_eq!(outputs[1], partials.clone().into_arc_tensor());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn reversed_scan() -> TractResult<()> {
+        use pb::attribute_proto::AttributeType::*;
+        // running sum of the rows of xs, from the last one, scaled by an outer value
+        let body = graph_attr(
+            "body",
+            vec![
+                node("Add", &["acc_in", "row"], &["acc_out"], vec![]),
+                node("Mul", &["acc_out", "scale"], &["partial"], vec![]),
+            ],
+            vec![
+                value_info("acc_in", DataType::Float, Some(&[3])),
+                value_info("row", DataType::Float, Some(&[3])),
+            ],
+            vec![float_input("acc_out"), float_input("partial")],
+        );
+        let num_scan_inputs = pb::AttributeProto { i: 1, ..attr("num_scan_inputs", Int) };
+        let directions =
+            pb::AttributeProto { ints: vec![1], ..attr("scan_input_directions", Ints) };
+        let proto = model_proto(
+            vec![node(
+                "Scan",
+                &["acc", "xs"],
+                &["sum", "partials"],
+                vec![body, num_scan_inputs, directions],
+            )],
+            vec![
+                value_info("acc", DataType::Float, Some(&[3])),
+                value_info("xs", DataType::Float, Some(&[3, 3])),
+                value_info("scale", DataType::Float, Some(&[3])),
+            ],
+            &["sum", "partials"],
+        );
+        let xs = tract_core::ndarray::arr2(&[
+            [1f32, 2.0, 3.0],
+            [10.0, 20.0, 30.0],
+            [100.0, 200.0, 300.0],
+        ]);
+        let partials = tract_core::ndarray::arr2(&[
+            [100f32, 400.0, -300.0],
+            [110.0, 440.0, -330.0],
+            [111.0, 444.0, -333.0],
+        ]);
+        let inputs = tvec!(tensor1(&[0f32; 3]), xs.into_tensor(), tensor1(&[1f32, 2.0, -1.0]));
+        for outputs in run_plain_and_optimized(&proto, inputs)? {
+            assert_eq!(outputs[0], rctensor1(&[111f32, 222.0, 333.0]));
+            assert_eq!(outputs[1], partials.clone().into_arc_tensor());
+        }
+        Ok(())
+    }
 }
@@ -1,4 +1,4 @@
-mod compress;
+mod pad;
 mod slice;
 
 use tract_core::internal::*;
@@ -10,23 +10,37 @@ use crate::pb::*;
 use num_traits::AsPrimitive;
 
 pub fn register_all_ops(reg: &mut OnnxOpRegister) {
-    reg.insert("Compress", compress::compress);
+    reg.insert("Compress", |_, node| {
+        Ok((Box::new(tractops::array::Compress::new(node.get_attr_opt("axis")?)), vec![]))
+    });
     reg.insert("Concat", concat);
     reg.insert("ConstantLike", constant_like);
     reg.insert("ConstantOfShape", constant_of_shape);
-    reg.insert("Expand", |_, _| {
-        Ok((Box::new(tractops::array::MultiBroadcastTo::default()), vec![]))
-    });
+    reg.insert("DepthToSpace", depth_to_space);
+    reg.insert("Expand", |_, _| Ok((Box::new(tractops::array::Expand::default()), vec![])));
     reg.insert("EyeLike", eye_like);
     reg.insert("Flatten", flatten);
     reg.insert("Gather", gather);
-    reg.insert("Pad", pad);
+    reg.insert("GatherND", gather_nd);
+    reg.insert("OneHot", |_, node| {
+        let axis = node.get_attr_opt("axis")?.unwrap_or(-1);
+        Ok((Box::new(tractops::array::OneHot::new(axis)), vec![]))
+    });
+    reg.insert("Pad", pad::pad);
     reg.insert("Reshape", |_, _| Ok((Box::new(tractops::array::Reshape::default()), vec![])));
+    reg.insert("ReverseSequence", reverse_sequence);
+    reg.insert("ScatterND", scatter_nd);
     reg.insert("Shape", |_, _| Ok((Box::new(tractops::array::Shape::new(DatumType::I64)), vec![])));
     reg.insert("Size", |_, _| Ok((Box::new(tractops::array::Size::new(DatumType::I64)), vec![])));
     reg.insert("Transpose", transpose);
     reg.insert("Tile", |_, _| Ok((Box::new(tractops::array::Tile::default()), vec![])));
+    reg.insert("TopK", topk);
+    reg.insert("Trilu", trilu);
     reg.insert("Slice", slice::slice);
+    reg.insert("SpaceToDepth", |_, node| {
+        let block_size = node.get_attr("blocksize")?;
+        Ok((Box::new(tractops::array::SpaceToDepth::new(block_size)), vec![]))
+    });
     reg.insert("Split", split);
     reg.insert("Squeeze", squeeze);
     reg.insert("Unsqueeze", unsqueeze);
@@ -74,6 +88,20 @@ pub fn constant_of_shape(
     Ok((Box::new(tractops::array::ConstantOfShape::new(value)), vec![]))
 }
 
+pub fn depth_to_space(
+    _ctx: &ParsingContext,
+    node: &NodeProto,
+) -> TractResult<(Box<dyn InferenceOp>, Vec<String>)> {
+    use tractops::array::DepthToSpaceMode;
+    let block_size = node.get_attr("blocksize")?;
+    let mode = match node.get_attr_opt("mode")?.unwrap_or("DCR") {
+        "DCR" => DepthToSpaceMode::DCR,
+        "CRD" => DepthToSpaceMode::CRD,
+        mode => bail!("Unsupported DepthToSpace mode {}", mode),
+    };
+    Ok((Box::new(tractops::array::DepthToSpace::new(block_size, mode)), vec![]))
+}
+
 pub fn eye_like(
     _ctx: &ParsingContext,
     node: &NodeProto,
@@ -99,27 +127,39 @@ pub fn gather(
     Ok((Box::new(tractops::array::Gather::new(axis)), vec![]))
 }
 
-pub fn pad(
+pub fn gather_nd(
+    _ctx: &ParsingContext,
+    node: &NodeProto,
+) -> TractResult<(Box<dyn InferenceOp>, Vec<String>)> {
+    let batch_dims = node.get_attr_opt("batch_dims")?.unwrap_or(0);
+    Ok((Box::new(tractops::array::GatherNd::new(batch_dims)), vec![]))
+}
+
+pub fn reverse_sequence(
+    _ctx: &ParsingContext,
+    node: &NodeProto,
+) -> TractResult<(Box<dyn InferenceOp>, Vec<String>)> {
+    let batch_axis = node.get_attr_opt("batch_axis")?.unwrap_or(1);
+    let time_axis = node.get_attr_opt("time_axis")?.unwrap_or(0);
+    Ok((Box::new(tractops::array::ReverseSequence::new(batch_axis, time_axis)), vec![]))
+}
+
+pub fn scatter_nd(
     _ctx: &ParsingContext,
     node: &NodeProto,
 ) -> TractResult<(Box<dyn InferenceOp>, Vec<String>)> {
-    let value: f32 = node.get_attr_opt("value")?.unwrap_or(0.0);
-    let mode = match node.get_attr_opt("mode")? {
-        None | Some("constant") => None,
-        Some(mode) => node.check_value(
-            "mode",
-            match mode {
-                "reflect" => Ok(Some(tractops::array::PadMode::Reflect)),
-                "edge" => Ok(Some(tractops::array::PadMode::Edge)),
-                _ => Err(mode),
+    let reduction = match node.get_attr_opt("reduction")? {
+        None | Some("none") => tractops::array::ScatterReduction::None,
+        Some(reduction) => node.check_value(
+            "reduction",
+            match reduction {
+                "add" => Ok(tractops::array::ScatterReduction::Add),
+                "mul" => Ok(tractops::array::ScatterReduction::Mul),
+                _ => Err(reduction),
             },
         )?,
-    }
-    .unwrap_or_else(|| tractops::array::PadMode::Constant(Arc::new(value.into())));
-    let pads = node.get_attr_tvec("pads")?;
-    let rank = pads.len() / 2;
-    let pads = (0..rank).map(|ax| (pads[ax], pads[ax + rank])).collect();
-    Ok((Box::new(tractops::array::Pad::new(pads, mode)), vec![]))
+    };
+    Ok((Box::new(tractops::array::ScatterNd::new(reduction)), vec![]))
 }
 
 pub fn split(
@@ -139,6 +179,16 @@ pub fn squeeze(
     Ok((Box::new(tractops::array::Squeeze::new(axes)), vec![]))
 }
 
+pub fn topk(
+    _ctx: &ParsingContext,
+    node: &NodeProto,
+) -> TractResult<(Box<dyn InferenceOp>, Vec<String>)> {
+    let axis = node.get_attr_opt("axis")?.unwrap_or(-1);
+    let largest = node.get_attr_opt("largest")?.unwrap_or(true);
+    let sorted = node.get_attr_opt("sorted")?.unwrap_or(true);
+    Ok((Box::new(tractops::array::TopK::new(axis, largest, sorted)), vec![]))
+}
+
 pub fn transpose(
     _ctx: &ParsingContext,
     node: &NodeProto,
@@ -147,6 +197,14 @@ pub fn transpose(
     Ok((Box::new(tractops::array::PermuteAxes::new(perm)), vec![]))
 }
 
+pub fn trilu(
+    _ctx: &ParsingContext,
+    node: &NodeProto,
+) -> TractResult<(Box<dyn InferenceOp>, Vec<String>)> {
+    let upper = node.get_attr_opt("upper")?.unwrap_or(true);
+    Ok((Box::new(tractops::array::Trilu::new(upper)), vec![]))
+}
+
 pub fn unsqueeze(
     _ctx: &ParsingContext,
     node: &NodeProto,
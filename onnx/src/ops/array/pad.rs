@@ -0,0 +1,147 @@
+use crate::model::ParsingContext;
+use crate::pb::*;
+use tract_core::internal::*;
+use tract_core::ops::array::{Pad, PadMode};
+
+pub fn pad(
+    ctx: &ParsingContext,
+    node: &NodeProto,
+) -> TractResult<(Box<dyn InferenceOp>, Vec<String>)> {
+    if ctx.onnx_operator_set_version < 11 {
+        pad2(ctx, node)
+    } else {
+        pad11(ctx, node)
+    }
+}
+
+/// The padding mode, None for constant padding.
+fn mode(node: &NodeProto) -> TractResult<Option<PadMode>> {
+    match node.get_attr_opt("mode")? {
+        None | Some("constant") => Ok(None),
+        Some(mode) => node.check_value(
+            "mode",
+            match mode {
+                "reflect" => Ok(Some(PadMode::Reflect)),
+                "edge" => Ok(Some(PadMode::Edge)),
+                "wrap" => Ok(Some(PadMode::Wrap)),
+                _ => Err(mode),
+            },
+        ),
+    }
+}
+
+/// ONNX pads are all the begin pads, then all the end pads.
+fn pairs(pads: &[i64]) -> TractResult<Vec<(usize, usize)>> {
+    if pads.iter().any(|&p| p < 0) {
+        bail!("Negative pads are not supported, got {:?}", pads)
+    }
+    let rank = pads.len() / 2;
+    Ok((0..rank).map(|ax| (pads[ax] as usize, pads[ax + rank] as usize)).collect())
+}
+
+fn pad2(
+    _ctx: &ParsingContext,
+    node: &NodeProto,
+) -> TractResult<(Box<dyn InferenceOp>, Vec<String>)> {
+    let value: f32 = node.get_attr_opt("value")?.unwrap_or(0.0);
+    let mode = mode(node)?.unwrap_or_else(|| PadMode::Constant(Arc::new(value.into())));
+    let pads: TVec<i64> = node.get_attr_tvec("pads")?;
+    Ok((Box::new(Pad::new(pairs(&pads)?, mode)), vec![]))
+}
+
+fn pad11(
+    _ctx: &ParsingContext,
+    node: &NodeProto,
+) -> TractResult<(Box<dyn InferenceOp>, Vec<String>)> {
+    Ok((Box::new(Pad11::new(mode(node)?)), vec![]))
+}
+
+/// Pad-11 and later, taking the pads, and the optional constant value, as
+/// inputs.
+#[derive(Debug, Clone, new, Default)]
+pub struct Pad11 {
+    mode: Option<PadMode>,
+}
+
+impl Pad11 {
+    fn to_pad(&self, dt: DatumType, pads: &Tensor, value: Option<&Tensor>) -> TractResult<Pad> {
+        let pads = pairs(pads.cast_to::<i64>()?.as_slice::<i64>()?)?;
+        let mode = match (&self.mode, value) {
+            (Some(mode), _) => mode.clone(),
+            (None, Some(value)) => PadMode::Constant(value.cast_to_dt(dt)?.into_owned().into()),
+            (None, None) => PadMode::Constant(tensor0(0i64).cast_to_dt(dt)?.into_owned().into()),
+        };
+        Ok(Pad::new(pads, mode))
+    }
+}
+
+impl Op for Pad11 {
+    fn name(&self) -> Cow<str> {
+        "onnx.Pad11".into()
+    }
+
+    not_a_typed_op!();
+}
+
+impl StatelessOp for Pad11 {
+    fn eval(&self, mut inputs: TVec<Arc<Tensor>>) -> TractResult<TVec<Arc<Tensor>>> {
+        let input = inputs.remove(0);
+        let pad = self.to_pad(input.datum_type(), &inputs[0], inputs.get(1).map(|v| &**v))?;
+        pad.eval(tvec!(input))
+    }
+}
+
+impl InferenceRulesOp for Pad11 {
+    fn rules<'r, 'p: 'r, 's: 'r>(
+        &'s self,
+        s: &mut Solver<'r>,
+        inputs: &'p [TensorProxy],
+        outputs: &'p [TensorProxy],
+    ) -> TractResult<()> {
+        if inputs.len() < 2 || inputs.len() > 3 {
+            bail!("Pad expects 2 or 3 inputs, got {}", inputs.len())
+        }
+        check_output_arity(&outputs, 1)?;
+        s.equals(&inputs[0].datum_type, &outputs[0].datum_type)?;
+        s.equals(&inputs[0].rank, &outputs[0].rank)?;
+        s.equals(&inputs[1].rank, 1)?;
+        s.equals(&inputs[1].shape[0], 2 * inputs[0].rank.bex().to_dim())?;
+        if inputs.len() == 3 {
+            s.equals(&inputs[2].rank, 0)?;
+        }
+        s.given(&inputs[1].value, move |s, pads| {
+            for (ix, (bef, aft)) in
+                pairs(pads.cast_to::<i64>()?.as_slice::<i64>()?)?.into_iter().enumerate()
+            {
+                s.equals(
+                    &outputs[0].shape[ix],
+                    inputs[0].shape[ix].bex() + bef.to_dim() + aft.to_dim(),
+                )?;
+            }
+            Ok(())
+        })
+    }
+
+    fn to_typed(
+        &self,
+        source: &InferenceModel,
+        node: &InferenceNode,
+        target: &mut TypedModel,
+        mapping: &HashMap<OutletId, OutletId>,
+    ) -> TractResult<TVec<OutletId>> {
+        let pads = source.outlet_fact(node.inputs[1])?.value.concretize();
+        let pads = pads.ok_or("Pad11 translation requires constant pads")?;
+        let value = if let Some(input) = node.inputs.get(2) {
+            let value = source.outlet_fact(*input)?.value.concretize();
+            Some(value.ok_or("Pad11 translation requires a constant padding value")?)
+        } else {
+            None
+        };
+        let input = mapping[&node.inputs[0]];
+        let dt = target.outlet_fact(input)?.datum_type;
+        let op = self.to_pad(dt, &pads, value.as_ref().map(|v| &**v))?;
+        target.wire_node(&*node.name, op, &[input])
+    }
+
+    inference_op_as_op!();
+}
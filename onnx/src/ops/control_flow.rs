@@ -0,0 +1,60 @@
+use crate::model::{OnnxOpRegister, ParseResult, ParsingContext};
+use crate::pb::*;
+use tract_core::internal::*;
+
+use tract_core::ops::control_flow::{InferenceIf, InferenceLoop};
+
+pub fn register_all_ops(reg: &mut OnnxOpRegister) {
+    reg.insert("If", if_op);
+    reg.insert("Loop", loop_op);
+}
+
+/// Make `names`, the values a branch uses from the outer graph, the inputs of
+/// `model`, in order. The values the branch does not use are unused inputs.
+fn close_on(
+    model: &mut InferenceModel,
+    outlets_by_name: &HashMap<String, OutletId>,
+    names: &[String],
+) -> TractResult<()> {
+    let inputs = names
+        .iter()
+        .map(|name| match outlets_by_name.get(name) {
+            Some(outlet) => Ok(*outlet),
+            None => model.add_source(&**name, InferenceFact::default()),
+        })
+        .collect::<TractResult<Vec<_>>>()?;
+    model.set_input_outlets(&inputs)
+}
+
+pub fn if_op(
+    ctx: &ParsingContext,
+    node: &NodeProto,
+) -> TractResult<(Box<dyn InferenceOp>, Vec<String>)> {
+    let then_branch: &GraphProto = node.get_attr("then_branch")?;
+    let else_branch: &GraphProto = node.get_attr("else_branch")?;
+    let mut then_body = ctx.parse_graph(then_branch)?;
+    let mut else_body = ctx.parse_graph(else_branch)?;
+    let mut closures = then_body.unresolved_inputs.clone();
+    for name in &else_body.unresolved_inputs {
+        if !closures.contains(name) {
+            closures.push(name.clone());
+        }
+    }
+    for body in &mut [&mut then_body, &mut else_body] {
+        let ParseResult { model, outlets_by_name, .. } = &mut **body;
+        close_on(model, outlets_by_name, &closures)?;
+    }
+    Ok((Box::new(InferenceIf::new(then_body.model, else_body.model)), closures))
+}
+
+pub fn loop_op(
+    ctx: &ParsingContext,
+    node: &NodeProto,
+) -> TractResult<(Box<dyn InferenceOp>, Vec<String>)> {
+    let body: &GraphProto = node.get_attr("body")?;
+    let ParseResult { model, unresolved_inputs, .. } = ctx.parse_graph(body)?;
+    let has_trip_count = node.input.get(0).map(|s| !s.is_empty()).unwrap_or(false);
+    let has_cond = node.input.get(1).map(|s| !s.is_empty()).unwrap_or(false);
+    let op = InferenceLoop::new(model, has_trip_count, has_cond, unresolved_inputs.len());
+    Ok((Box::new(op), unresolved_inputs))
+}
@@ -0,0 +1,20 @@
+use crate::model::{OnnxOpRegister, ParsingContext};
+use crate::pb::*;
+use tract_core::internal::*;
+use tract_core::ops::detection::{BoxFormat, NonMaxSuppression};
+
+pub fn register_all_ops(reg: &mut OnnxOpRegister) {
+    reg.insert("NonMaxSuppression", non_max_suppression);
+}
+
+fn non_max_suppression(
+    _ctx: &ParsingContext,
+    node: &NodeProto,
+) -> TractResult<(Box<dyn InferenceOp>, Vec<String>)> {
+    let box_format = match node.get_attr_opt::<i64>("center_point_box")?.unwrap_or(0) {
+        0 => BoxFormat::Corners,
+        1 => BoxFormat::CenterPoint,
+        other => node.bail_attr("center_point_box", &format!("unexpected value: {}", other))?,
+    };
+    Ok((Box::new(NonMaxSuppression::new(box_format)), vec![]))
+}
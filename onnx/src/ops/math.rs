@@ -4,6 +4,7 @@ use crate::model::{OnnxOpRegister, ParsingContext};
 use crate::pb::*;
 use tract_core::internal::*;
 use tract_core::ops::binary::Nary;
+use tract_core::ops::{ComputeCost, OpCost};
 
 mod mat_mul_integer;
 
@@ -54,6 +55,25 @@ pub fn register_all_ops(reg: &mut OnnxOpRegister) {
     reg.insert("MatMulInteger", mat_mul_integer::mat_mul_integer);
     reg.insert("QLinearMatMul", mat_mul_integer::q_linear_mat_mul);
     reg.insert("Gemm", gemm);
+    reg.insert("Einsum", einsum);
+    reg.insert("CumSum", cumsum);
+}
+
+pub fn einsum(
+    _ctx: &ParsingContext,
+    node: &NodeProto,
+) -> TractResult<(Box<dyn InferenceOp>, Vec<String>)> {
+    let equation = node.get_attr::<&str>("equation")?;
+    Ok((Box::new(tractops::einsum::Einsum::new(equation)?), vec![]))
+}
+
+pub fn cumsum(
+    _ctx: &ParsingContext,
+    node: &NodeProto,
+) -> TractResult<(Box<dyn InferenceOp>, Vec<String>)> {
+    let exclusive = node.get_attr_opt::<i64>("exclusive")?.unwrap_or(0) == 1;
+    let reverse = node.get_attr_opt::<i64>("reverse")?.unwrap_or(0) == 1;
+    Ok((Box::new(tractops::math::CumSum::new(exclusive, reverse)), vec![]))
 }
 
 pub fn clip(
@@ -132,6 +152,13 @@ impl Op for Gemm {
         "Gemm".into()
     }
 
+    fn validate_attributes(&self) -> TractResult<()> {
+        if !self.alpha.is_finite() || !self.beta.is_finite() {
+            bail!("Gemm alpha and beta must be finite, got {} and {}", self.alpha, self.beta)
+        }
+        Ok(())
+    }
+
     fn incorporate(
         &self,
         model: &InferenceModel,
@@ -211,3 +238,32 @@ impl InferenceRulesOp for Gemm {
 
     inference_op_as_op!();
 }
+
+/// Gemm is not a typed op, this is how much the MatMul and scalings it is
+/// incorporated into cost.
+impl OpCost for Gemm {
+    fn compute_cost(&self, inputs: &[&TypedFact]) -> TractResult<ComputeCost> {
+        let dims = |fact: &TypedFact| -> TractResult<(u64, u64)> {
+            match fact.shape.as_finite() {
+                Some(shape) if shape.len() == 2 => Ok((shape[0] as u64, shape[1] as u64)),
+                _ => bail!("Gemm cost needs known rank 2 shapes, got {:?}", fact),
+            }
+        };
+        let (a0, a1) = dims(inputs[0])?;
+        let (m, k) = if self.trans_a { (a1, a0) } else { (a0, a1) };
+        let (b0, b1) = dims(inputs[1])?;
+        let n = if self.trans_b { b0 } else { b1 };
+        let mut flops = 2 * m * n * k;
+        if self.alpha != 1.0 {
+            flops += m * n;
+        }
+        if self.beta != 0.0 {
+            flops += m * n * if self.beta != 1.0 { 2 } else { 1 };
+        }
+        let mut memory_bytes = m * n * inputs[0].datum_type.size_of() as u64;
+        for fact in inputs {
+            memory_bytes += tract_core::ops::cost::fact_bytes(fact)?;
+        }
+        Ok(ComputeCost { flops, memory_bytes })
+    }
+}
@@ -1,7 +1,7 @@
 use crate::model::ParsingContext;
 use crate::pb::*;
 use tract_core::internal::*;
-use tract_core::ops::quant::QParams;
+use tract_core::ops::quant::{LinearQuant, QLinearMatMul as CoreQLinearMatMul, QParams};
 
 pub fn mat_mul_integer(
     _ctx: &ParsingContext,
@@ -138,22 +138,26 @@ impl Op for QLinearMatMul {
     not_a_typed_op!();
 }
 
+impl QLinearMatMul {
+    fn to_core(
+        &self,
+        quant: &[&Tensor],
+        y_datum_type: DatumType,
+    ) -> TractResult<CoreQLinearMatMul> {
+        Ok(CoreQLinearMatMul::new(
+            LinearQuant::from_tensors(quant[0], quant[1])?,
+            LinearQuant::from_tensors(quant[2], quant[3])?,
+            LinearQuant::from_tensors(quant[4], quant[5])?,
+            y_datum_type,
+        ))
+    }
+}
+
 impl StatelessOp for QLinearMatMul {
     fn eval(&self, mut inputs: TVec<Arc<Tensor>>) -> TractResult<TVec<Arc<Tensor>>> {
         let (a, a_scale, a_zp, b, b_scale, b_zp, y_scale, y_zp) = args_8!(inputs);
-        let scale = a_scale.to_scalar::<f32>()? * b_scale.to_scalar::<f32>()?
-            / y_scale.to_scalar::<f32>()?;
-        let mut qp = QParams::new(y_zp.datum_type()).with_scale_factor(scale);
-        if let Some(zp) = cleanup_zero_point(a_zp.into_tensor())? {
-            qp = qp.with_zero_point_a(&zp.into_arc_tensor())
-        }
-        if let Some(zp) = cleanup_zero_point(b_zp.into_tensor())? {
-            qp = qp.with_zero_point_b(&zp.into_arc_tensor())
-        }
-        if let Some(zp) = cleanup_zero_point(y_zp.into_tensor())? {
-            qp = qp.with_zero_point_c(&zp.into_arc_tensor())
-        }
-        let op = tract_core::ops::matmul::MatMul::default().with_q_params(qp);
+        let op =
+            self.to_core(&[&a_scale, &a_zp, &b_scale, &b_zp, &y_scale, &y_zp], y_zp.datum_type())?;
         op.eval(tvec!(a, b))
     }
 }
@@ -191,24 +195,18 @@ impl InferenceRulesOp for QLinearMatMul {
         target: &mut TypedModel,
         mapping: &HashMap<OutletId, OutletId>,
     ) -> TractResult<TVec<OutletId>> {
-        let mut qp = QParams::new(i32::datum_type());
-        let a_zp = target
-            .outlet_fact(mapping[&node.inputs[2]])?
-            .konst
-            .as_ref()
-            .ok_or("zero_point_a must be a constant")?;
-        if let Some(zp) = cleanup_zero_point(a_zp.clone().into_tensor())? {
-            qp = qp.with_zero_point_a(&zp.into_arc_tensor());
-        }
-        let b_zp = target
-            .outlet_fact(mapping[&node.inputs[5]])?
-            .konst
-            .as_ref()
-            .ok_or("zero_point_b must be a constant")?;
-        if let Some(zp) = cleanup_zero_point(b_zp.clone().into_tensor())? {
-            qp = qp.with_zero_point_b(&zp.into_arc_tensor());
-        }
-        let op = tract_core::ops::matmul::MatMul::default().with_q_params(qp);
+        let quant = [1, 2, 4, 5, 6, 7]
+            .iter()
+            .map(|&ix| {
+                let fact = target.outlet_fact(mapping[&node.inputs[ix]])?;
+                Ok(fact
+                    .konst
+                    .clone()
+                    .ok_or("QLinearMatMul scales and zero points must be constants")?)
+            })
+            .collect::<TractResult<Vec<Arc<Tensor>>>>()?;
+        let quant: Vec<&Tensor> = quant.iter().map(|t| &**t).collect();
+        let op = self.to_core(&quant, quant[5].datum_type())?;
         target.wire_node(&*node.name, op, &[mapping[&node.inputs[0]], mapping[&node.inputs[3]]])
     }
 
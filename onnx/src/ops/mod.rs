@@ -4,11 +4,15 @@ use tract_core::internal::*;
 
 mod array;
 mod category_mapper;
+mod control_flow;
+mod detection;
 mod logic;
 mod math;
 mod nn;
 mod quant;
 pub mod rec;
+mod signal;
+mod vision;
 
 pub fn register_all_ops(reg: &mut OnnxOpRegister) {
     reg.insert("Cast", cast);
@@ -18,11 +22,15 @@ pub fn register_all_ops(reg: &mut OnnxOpRegister) {
     });
     array::register_all_ops(reg);
     category_mapper::register_all_ops(reg);
+    control_flow::register_all_ops(reg);
+    detection::register_all_ops(reg);
     logic::register_all_ops(reg);
     math::register_all_ops(reg);
     nn::register_all_ops(reg);
     quant::register_all_ops(reg);
     rec::register_all_ops(reg);
+    signal::register_all_ops(reg);
+    vision::register_all_ops(reg);
 }
 
 fn konst(
@@ -7,11 +7,11 @@ use crate::model::{OnnxOpRegister, ParsingContext};
 use crate::pb::NodeProto;
 use crate::pb_helpers::OptionExt;
 
-use num_traits::AsPrimitive;
 use tractops::nn::Reducer;
 
 mod batch_norm;
 mod dropout;
+mod qlinear_conv;
 
 fn reduce(node: &NodeProto, reducer: Reducer) -> TractResult<(Box<dyn InferenceOp>, Vec<String>)> {
     let axes = node.get_attr_opt_vec("axes")?;
@@ -24,6 +24,7 @@ pub fn register_all_ops(reg: &mut OnnxOpRegister) {
     reg.insert("ArgMin", arg_max_min);
     reg.insert("AveragePool", average_pool);
     reg.insert("BatchNormalization", batch_normalization);
+    reg.insert("Celu", celu);
     reg.insert("Conv", conv);
     reg.insert("ConvInteger", conv_integer);
     reg.insert("Dropout", dropout::dropout);
@@ -35,14 +36,20 @@ pub fn register_all_ops(reg: &mut OnnxOpRegister) {
     reg.insert("GlobalMaxPool", |_, _| {
         Ok((Box::new(tractops::nn::GlobalMaxPool::default()), vec![]))
     });
+    reg.insert("GroupNormalization", group_normalization);
     reg.insert("Hardmax", layer_hard_max);
     reg.insert("HardSigmoid", hard_sigmoid);
+    reg.insert("HardSwish", |_, _| Ok((Box::new(tractops::nn::hard_swish()), vec![])));
+    reg.insert("InstanceNormalization", instance_normalization);
+    reg.insert("LayerNormalization", layer_normalization);
     reg.insert("LeakyRelu", leaky_relu);
     reg.insert("LogSoftmax", layer_log_soft_max);
     reg.insert("LRN", lrn);
     reg.insert("MaxPool", max_pool);
+    reg.insert("MeanVarianceNormalization", mean_variance_normalization);
+    reg.insert("Mish", |_, _| Ok((Box::new(tractops::nn::mish()), vec![])));
     reg.insert("ParametricSoftplus", parametric_softplus);
-    reg.insert("QLinearConv", conv_qlinear);
+    reg.insert("QLinearConv", qlinear_conv::qlinear_conv);
     reg.insert("PRelu", |_, _| Ok((Box::new(prelu::bin()), vec![])));
     reg.insert("ReduceL1", |_, node| reduce(node, Reducer::L1));
     reg.insert("ReduceL2", |_, node| reduce(node, Reducer::L2));
@@ -67,6 +74,10 @@ pub fn register_all_ops(reg: &mut OnnxOpRegister) {
 
 fn pad(node: &NodeProto) -> TractResult<PaddingSpec> {
     if let Some(pads) = node.get_attr_opt_tvec("pads")? {
+        let auto_pad: Option<&str> = node.get_attr_opt("auto_pad")?;
+        node.expect_attr("pads", auto_pad.unwrap_or("NOTSET") == "NOTSET", || {
+            format!("no auto_pad, got {}", auto_pad.unwrap())
+        })?;
         let len = pads.len();
         return Ok(PaddingSpec::Explicit(
             pads.iter().cloned().take(len / 2).collect(),
@@ -162,25 +173,6 @@ pub fn conv_integer(
     Ok((Box::new(op), vec![]))
 }
 
-pub fn conv_qlinear(
-    _ctx: &ParsingContext,
-    node: &NodeProto,
-) -> TractResult<(Box<dyn InferenceOp>, Vec<String>)> {
-    let mut op = common_conv(node)?;
-    op.x_scale_input = Some(1);
-    op.x_zero_point_input = Some(2);
-    op.k_input = Some(3);
-    op.k_scale_input = Some(4);
-    op.k_zero_point_input = Some(5);
-    op.y_scale_input = Some(6);
-    op.y_zero_point_input = Some(7);
-    if node.input.len() == 9 {
-        op.bias_input = Some(8);
-    }
-    op.override_bias_datum_type = Some(i32::datum_type());
-    Ok((Box::new(op), vec![]))
-}
-
 pub fn average_pool(
     _ctx: &ParsingContext,
     node: &NodeProto,
@@ -198,6 +190,14 @@ pub fn average_pool(
     ))
 }
 
+pub fn celu(
+    _ctx: &ParsingContext,
+    node: &NodeProto,
+) -> TractResult<(Box<dyn InferenceOp>, Vec<String>)> {
+    let alpha = node.get_attr_opt("alpha")?.unwrap_or(1.);
+    Ok((Box::new(tractops::nn::celu(alpha)), vec![]))
+}
+
 pub fn elu(
     _ctx: &ParsingContext,
     node: &NodeProto,
@@ -223,6 +223,23 @@ pub fn hard_sigmoid(
     Ok((Box::new(tractops::nn::hard_sigmoid(alpha, beta)), vec![]))
 }
 
+pub fn group_normalization(
+    _ctx: &ParsingContext,
+    node: &NodeProto,
+) -> TractResult<(Box<dyn InferenceOp>, Vec<String>)> {
+    let num_groups = node.get_attr("num_groups")?;
+    let epsilon = node.get_attr_opt("epsilon")?.unwrap_or(1e-5);
+    Ok((Box::new(tractops::nn::GroupNorm::new(num_groups, epsilon)), vec![]))
+}
+
+pub fn instance_normalization(
+    _ctx: &ParsingContext,
+    node: &NodeProto,
+) -> TractResult<(Box<dyn InferenceOp>, Vec<String>)> {
+    let epsilon = node.get_attr_opt("epsilon")?.unwrap_or(1e-5);
+    Ok((Box::new(tractops::nn::InstanceNorm::new(DataFormat::NCHW, epsilon)), vec![]))
+}
+
 pub fn layer_hard_max(
     _ctx: &ParsingContext,
     node: &NodeProto,
@@ -231,6 +248,15 @@ pub fn layer_hard_max(
     Ok((Box::new(tractops::nn::LayerHardmax::new(axis)), vec![]))
 }
 
+pub fn layer_normalization(
+    _ctx: &ParsingContext,
+    node: &NodeProto,
+) -> TractResult<(Box<dyn InferenceOp>, Vec<String>)> {
+    let axis = node.get_attr_opt("axis")?.unwrap_or(-1);
+    let epsilon = node.get_attr_opt("epsilon")?.unwrap_or(1e-5);
+    Ok((Box::new(tractops::nn::LayerNorm::new(axis, epsilon)), vec![]))
+}
+
 pub fn layer_log_soft_max(
     _ctx: &ParsingContext,
     node: &NodeProto,
@@ -282,6 +308,16 @@ pub fn max_pool(
     ))
 }
 
+pub fn mean_variance_normalization(
+    _ctx: &ParsingContext,
+    node: &NodeProto,
+) -> TractResult<(Box<dyn InferenceOp>, Vec<String>)> {
+    let axes = node.get_attr_opt_vec("axes")?.unwrap_or(vec![0, 2, 3]);
+    let normalize_variance = node.get_attr_opt("normalize_variance")?.unwrap_or(true);
+    let epsilon = node.get_attr_opt("epsilon")?.unwrap_or(1e-9);
+    Ok((Box::new(tractops::nn::Mvn::new(axes, normalize_variance, epsilon)), vec![]))
+}
+
 pub fn parametric_softplus(
     _ctx: &ParsingContext,
     node: &NodeProto,
@@ -328,33 +364,13 @@ pub fn scaled_tanh(
     Ok((Box::new(tractops::nn::scaled_tanh(alpha, beta)), vec![]))
 }
 
-element_wise!(shrink_op, Shrink { bias: f32, lambd: f32 },
-    [f16,f32,f64] => |s, xs| {
-        xs.iter_mut().for_each(|x| *x = shrink_value(*x, s));
-        Ok(())
-});
-
-fn shrink_value<T>(x: T, s: &Shrink) -> T
-where
-    T: Datum + ::num_traits::Float,
-    f32: ::num_traits::AsPrimitive<T>,
-{
-    if x < -s.lambd.as_() {
-        x + s.bias.as_()
-    } else if x > s.lambd.as_() {
-        x - s.bias.as_()
-    } else {
-        T::zero()
-    }
-}
-
 pub fn shrink(
     _ctx: &ParsingContext,
     node: &NodeProto,
 ) -> TractResult<(Box<dyn InferenceOp>, Vec<String>)> {
     let bias = node.get_attr_opt("bias")?.unwrap_or(0.0);
     let lambd = node.get_attr_opt("lambd")?.unwrap_or(0.5);
-    Ok((Box::new(shrink_op(bias, lambd)), vec![]))
+    Ok((Box::new(tractops::math::shrink(bias, lambd)), vec![]))
 }
 
 pub fn selu(
@@ -0,0 +1,121 @@
+use crate::model::ParsingContext;
+use crate::pb::NodeProto;
+use tract_core::internal::*;
+use tract_core::ops::cnn::Conv;
+use tract_core::ops::quant::{LinearQuant, QLinearConv as CoreQLinearConv};
+
+pub fn qlinear_conv(
+    _ctx: &ParsingContext,
+    node: &NodeProto,
+) -> TractResult<(Box<dyn InferenceOp>, Vec<String>)> {
+    let conv = super::common_conv(node)?;
+    Ok((Box::new(QLinearConv::new(conv, node.input.len() == 9)), vec![]))
+}
+
+/// The Conv only carries the geometry: padding, strides, dilations and group.
+#[derive(Debug, Clone, new)]
+struct QLinearConv {
+    conv: Conv,
+    has_bias: bool,
+}
+
+impl QLinearConv {
+    /// Build the core op from the x, w and y scales and zero points.
+    fn to_core(
+        &self,
+        quant: &[&Tensor],
+        bias: Option<Arc<Tensor>>,
+    ) -> TractResult<CoreQLinearConv> {
+        let k_scale = quant[2].cast_to::<f32>()?.as_slice::<f32>()?.to_vec();
+        let k_zero_point = quant[3].cast_to::<i32>()?.as_slice::<i32>()?.to_vec();
+        let mut op = CoreQLinearConv::new(
+            LinearQuant::from_tensors(quant[0], quant[1])?,
+            k_scale,
+            k_zero_point,
+            LinearQuant::from_tensors(quant[4], quant[5])?,
+            quant[5].datum_type(),
+        )
+        .padding(self.conv.padding.clone())
+        .group(self.conv.group.unwrap_or(1));
+        if let Some(strides) = &self.conv.strides {
+            op = op.strides(strides.clone());
+        }
+        if let Some(dilations) = &self.conv.dilations {
+            op = op.dilations(dilations.clone());
+        }
+        if let Some(bias) = bias {
+            op = op.bias(bias);
+        }
+        Ok(op)
+    }
+}
+
+impl Op for QLinearConv {
+    fn name(&self) -> Cow<str> {
+        "onnx.QLinearConv".into()
+    }
+
+    not_a_typed_op!();
+}
+
+impl StatelessOp for QLinearConv {
+    fn eval(&self, inputs: TVec<Arc<Tensor>>) -> TractResult<TVec<Arc<Tensor>>> {
+        let quant: Vec<&Tensor> = [1, 2, 4, 5, 6, 7].iter().map(|&ix| &*inputs[ix]).collect();
+        let op = self.to_core(&quant, inputs.get(8).cloned())?;
+        op.eval(tvec!(inputs[0].clone(), inputs[3].clone()))
+    }
+}
+
+impl InferenceRulesOp for QLinearConv {
+    fn rules<'r, 'p: 'r, 's: 'r>(
+        &'s self,
+        s: &mut Solver<'r>,
+        inputs: &'p [TensorProxy],
+        outputs: &'p [TensorProxy],
+    ) -> TractResult<()> {
+        check_input_arity(&inputs, 8 + self.has_bias as usize)?;
+        check_output_arity(&outputs, 1)?;
+        s.equals(&inputs[0].datum_type, &inputs[2].datum_type)?;
+        s.equals(&inputs[3].datum_type, &inputs[5].datum_type)?;
+        s.equals(&outputs[0].datum_type, &inputs[7].datum_type)?;
+        s.equals(&inputs[0].rank, &inputs[3].rank)?;
+        s.equals(&outputs[0].rank, &inputs[3].rank)?;
+        if self.has_bias {
+            s.equals(&inputs[8].datum_type, i32::datum_type())?;
+            s.equals(&inputs[8].rank, 1)?;
+            s.equals(&inputs[8].shape[0], &inputs[3].shape[0])?;
+        }
+        s.given_2(&inputs[0].shape, &inputs[3].shape, move |s, ishape, kshape| {
+            if kshape.iter().all(|d| d.to_integer().is_ok()) {
+                let kshape: TVec<usize> =
+                    kshape.iter().map(|d| d.to_integer().unwrap() as _).collect();
+                s.equals(&outputs[0].shape, self.conv.output_shape(&*ishape, &*kshape))?;
+            }
+            Ok(())
+        })
+    }
+
+    fn to_typed(
+        &self,
+        _source: &InferenceModel,
+        node: &InferenceNode,
+        target: &mut TypedModel,
+        mapping: &HashMap<OutletId, OutletId>,
+    ) -> TractResult<TVec<OutletId>> {
+        let konst = |ix: usize| -> TractResult<Arc<Tensor>> {
+            let fact = target.outlet_fact(mapping[&node.inputs[ix]])?;
+            Ok(fact
+                .konst
+                .clone()
+                .ok_or("QLinearConv scales, zero points and bias must be constants")?)
+        };
+        let quant =
+            [1, 2, 4, 5, 6, 7].iter().map(|&ix| konst(ix)).collect::<TractResult<Vec<_>>>()?;
+        let quant: Vec<&Tensor> = quant.iter().map(|t| &**t).collect();
+        let bias = if self.has_bias { Some(konst(8)?) } else { None };
+        let op = self.to_core(&quant, bias)?;
+        target.wire_node(&*node.name, op, &[mapping[&node.inputs[0]], mapping[&node.inputs[3]]])
+    }
+
+    inference_op_as_op!();
+}
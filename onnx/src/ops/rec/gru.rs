@@ -170,7 +170,12 @@ impl InferenceRulesOp for GRU {
         // scann inner interface: [chunk=1, batch_size, input_size]
         // onnx inner interface: [batch_size, input_size]
         outer_inputs.push(mapping[&node.inputs[0]]);
-        input_mapping.push(scan::InputMapping::Scan { slot: 0, axis: 0, chunk: 1.to_dim() });
+        input_mapping.push(scan::InputMapping::Scan {
+            slot: 0,
+            axis: 0,
+            chunk: 1.to_dim(),
+            reverse: false,
+        });
         let mut x_source_fact = x_fact.clone();
         x_source_fact.shape.set_dim(0, 1.to_dim())?;
         let x_source = body.add_source("x_source", x_source_fact)?.into();
@@ -18,6 +18,13 @@ pub fn scan(
     let num_scan_outputs = model.output_outlets()?.len() - num_hidden_state;
     let scan_output_axes =
         node.get_attr_opt_vec("scan_output_axes")?.unwrap_or(vec![0; num_scan_outputs]);
+    let scan_input_directions: Vec<i64> =
+        node.get_attr_opt_vec("scan_input_directions")?.unwrap_or(vec![0; num_scan_inputs]);
+    let scan_output_directions: Vec<i64> =
+        node.get_attr_opt_vec("scan_output_directions")?.unwrap_or(vec![]);
+    if scan_output_directions.iter().any(|&d| d != 0) {
+        bail!("Scan output directions other than forward are not supported")
+    }
 
     let mut mapped_inputs = vec![];
     let mut mapped_outputs = vec![];
@@ -51,6 +58,13 @@ pub fn scan(
             axis: *ax,
             slot: ix + num_hidden_state,
             chunk: (),
+            reverse: scan_input_directions.get(ix) == Some(&1),
+        });
+    }
+
+    for ix in 0..closure_inputs {
+        mapped_inputs.push(tract_core::ops::scan::InputMapping::Full {
+            slot: num_hidden_state + num_scan_inputs + ix,
         });
     }
 
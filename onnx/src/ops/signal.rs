@@ -0,0 +1,142 @@
+use crate::model::{optional_inputs, OnnxOpRegister, ParsingContext};
+use crate::pb::NodeProto;
+use tract_core::internal::*;
+use tract_core::ops::signal::{MelWeightMatrix, STFT as CoreSTFT};
+
+pub fn register_all_ops(reg: &mut OnnxOpRegister) {
+    reg.insert("MelWeightMatrix", mel_weight_matrix);
+    reg.insert("STFT", stft);
+}
+
+fn mel_weight_matrix(
+    _ctx: &ParsingContext,
+    node: &NodeProto,
+) -> TractResult<(Box<dyn InferenceOp>, Vec<String>)> {
+    let dt = node.get_attr_opt::<DatumType>("output_datatype")?.unwrap_or(DatumType::F32);
+    Ok((Box::new(MelWeightMatrix::new(dt)), vec![]))
+}
+
+fn stft(
+    _ctx: &ParsingContext,
+    node: &NodeProto,
+) -> TractResult<(Box<dyn InferenceOp>, Vec<String>)> {
+    let onesided = node.get_attr_opt::<i64>("onesided")?.unwrap_or(1) != 0;
+    let mut inputs = optional_inputs(node).skip(2);
+    let window = inputs.next().unwrap();
+    let frame_length = inputs.next().unwrap();
+    Ok((Box::new(STFT::new(onesided, window, frame_length)), vec![]))
+}
+
+/// Frame step and frame length are inputs in ONNX, they must be constants.
+#[derive(Debug, Clone, new)]
+struct STFT {
+    onesided: bool,
+    window: Option<usize>,
+    frame_length: Option<usize>,
+}
+
+impl STFT {
+    fn bins(&self, frame_length: usize) -> usize {
+        if self.onesided {
+            frame_length / 2 + 1
+        } else {
+            frame_length
+        }
+    }
+
+    fn to_core(&self, frame_step: &Tensor, frame_length: Option<&Tensor>) -> TractResult<CoreSTFT> {
+        let scalar = |t: &Tensor| -> TractResult<usize> {
+            let value = t.cast_to::<i64>()?.as_slice::<i64>()?[0];
+            if value <= 0 {
+                bail!("STFT frame step and length must be positive, got {}", value)
+            }
+            Ok(value as usize)
+        };
+        let frame_length = frame_length.map(|t| scalar(t)).transpose()?;
+        Ok(CoreSTFT::new(scalar(frame_step)?, frame_length, self.onesided))
+    }
+}
+
+impl Op for STFT {
+    fn name(&self) -> Cow<str> {
+        "onnx.STFT".into()
+    }
+
+    not_a_typed_op!();
+}
+
+impl StatelessOp for STFT {
+    fn eval(&self, inputs: TVec<Arc<Tensor>>) -> TractResult<TVec<Arc<Tensor>>> {
+        let op = self.to_core(&inputs[1], self.frame_length.map(|ix| &*inputs[ix]))?;
+        let mut core_inputs = tvec!(inputs[0].clone());
+        if let Some(window) = self.window {
+            core_inputs.push(inputs[window].clone());
+        }
+        op.eval(core_inputs)
+    }
+}
+
+impl InferenceRulesOp for STFT {
+    fn rules<'r, 'p: 'r, 's: 'r>(
+        &'s self,
+        s: &mut Solver<'r>,
+        inputs: &'p [TensorProxy],
+        outputs: &'p [TensorProxy],
+    ) -> TractResult<()> {
+        check_input_arity(
+            &inputs,
+            2 + self.window.is_some() as usize + self.frame_length.is_some() as usize,
+        )?;
+        check_output_arity(&outputs, 1)?;
+        s.equals(&outputs[0].datum_type, &inputs[0].datum_type)?;
+        s.equals(&inputs[0].rank, 3)?;
+        s.equals(&inputs[1].rank, 0)?;
+        s.equals(&outputs[0].rank, 4)?;
+        s.equals(&outputs[0].shape[0], &inputs[0].shape[0])?;
+        s.equals(&outputs[0].shape[3], 2.to_dim())?;
+        if let Some(window) = self.window {
+            s.equals(&inputs[window].datum_type, &inputs[0].datum_type)?;
+            s.equals(&inputs[window].rank, 1)?;
+        }
+        if let Some(frame_length) = self.frame_length {
+            s.equals(&inputs[frame_length].rank, 0)?;
+            s.given(&inputs[frame_length].value, move |s, frame_length| {
+                let frame_length = frame_length.cast_to::<i64>()?.as_slice::<i64>()?[0];
+                s.equals(&outputs[0].shape[2], self.bins(frame_length as usize).to_dim())
+            })?;
+        } else if let Some(window) = self.window {
+            s.given(&inputs[window].shape[0], move |s, len| {
+                if let Ok(len) = len.to_integer() {
+                    s.equals(&outputs[0].shape[2], self.bins(len as usize).to_dim())?;
+                }
+                Ok(())
+            })?;
+        }
+        Ok(())
+    }
+
+    fn to_typed(
+        &self,
+        source: &InferenceModel,
+        node: &InferenceNode,
+        target: &mut TypedModel,
+        mapping: &HashMap<OutletId, OutletId>,
+    ) -> TractResult<TVec<OutletId>> {
+        let konst = |ix: usize| -> TractResult<Arc<Tensor>> {
+            Ok(source
+                .outlet_fact(node.inputs[ix])?
+                .value
+                .concretize()
+                .ok_or("STFT frame step and frame length must be constants")?)
+        };
+        let frame_length = self.frame_length.map(|ix| konst(ix)).transpose()?;
+        let op = self.to_core(&*konst(1)?, frame_length.as_ref().map(|t| &**t))?;
+        let mut inputs = tvec!(mapping[&node.inputs[0]]);
+        if let Some(window) = self.window {
+            inputs.push(mapping[&node.inputs[window]]);
+        }
+        target.wire_node(&*node.name, op, &*inputs)
+    }
+
+    inference_op_as_op!();
+}
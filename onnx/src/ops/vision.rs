@@ -0,0 +1,41 @@
+use crate::model::{OnnxOpRegister, ParsingContext};
+use crate::pb::*;
+use tract_core::internal::*;
+use tract_core::ops::vision::{CoordinateTransformation, RoiAlign};
+
+pub fn register_all_ops(reg: &mut OnnxOpRegister) {
+    reg.insert("RoiAlign", roi_align);
+}
+
+fn roi_align(
+    _ctx: &ParsingContext,
+    node: &NodeProto,
+) -> TractResult<(Box<dyn InferenceOp>, Vec<String>)> {
+    let mode = node.get_attr_opt::<&str>("mode")?.unwrap_or("avg");
+    if mode != "avg" {
+        return node.bail_attr("mode", &format!("unsupported value: {}", mode));
+    }
+    let coordinate_transformation = match node
+        .get_attr_opt::<&str>("coordinate_transformation_mode")?
+        .unwrap_or("half_pixel")
+    {
+        "half_pixel" => CoordinateTransformation::HalfPixel,
+        "output_half_pixel" => CoordinateTransformation::OutputHalfPixel,
+        other => node
+            .bail_attr("coordinate_transformation_mode", &format!("unexpected value: {}", other))?,
+    };
+    let output_height = node.get_attr_opt("output_height")?.unwrap_or(1);
+    let output_width = node.get_attr_opt("output_width")?.unwrap_or(1);
+    let sampling_ratio = node.get_attr_opt("sampling_ratio")?.unwrap_or(0);
+    let spatial_scale = node.get_attr_opt("spatial_scale")?.unwrap_or(1.0);
+    Ok((
+        Box::new(RoiAlign::new(
+            output_height,
+            output_width,
+            sampling_ratio,
+            spatial_scale,
+            coordinate_transformation,
+        )),
+        vec![],
+    ))
+}
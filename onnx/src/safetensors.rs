@@ -0,0 +1,142 @@
+//! Weights in the safetensors format, as found on the HuggingFace hub.
+//!
+//! A safetensors file starts with the length of its header, as a little
+//! endian u64, followed by the header itself. This is a JSON object, mapping
+//! tensor names to their dtype, shape and data byte range, relative to the
+//! end of the header. An optional `__metadata__` entry holds free-form
+//! strings.
+use std::io::Read;
+use std::path::Path;
+
+use tract_core::internal::*;
+
+fn datum_type(dtype: &str) -> TractResult<DatumType> {
+    Ok(match dtype {
+        "BOOL" => DatumType::Bool,
+        "U8" => DatumType::U8,
+        "U16" => DatumType::U16,
+        "I8" => DatumType::I8,
+        "I16" => DatumType::I16,
+        "I32" => DatumType::I32,
+        "I64" => DatumType::I64,
+        "F16" => DatumType::F16,
+        "BF16" => DatumType::BF16,
+        "F32" => DatumType::F32,
+        "F64" => DatumType::F64,
+        _ => bail!("Unsupported safetensors dtype {}", dtype),
+    })
+}
+
+fn usize_array(value: &serde_json::Value, what: &str, name: &str) -> TractResult<Vec<usize>> {
+    value
+        .as_array()
+        .and_then(|a| a.iter().map(|v| v.as_u64().map(|v| v as usize)).collect())
+        .ok_or_else(|| format!("Invalid {} for tensor {}", what, name).into())
+}
+
+/// Load all the tensors of a safetensors file, by name.
+///
+/// The tensors are mapped from the file (see `Tensor::from_mmap`) rather
+/// than read, so the file must not be modified while they are alive.
+pub fn load_safetensors(path: &Path) -> TractResult<HashMap<String, Tensor>> {
+    if cfg!(target_endian = "big") {
+        bail!("safetensors loading is only supported on little endian platforms")
+    }
+    let mut file = std::fs::File::open(path)?;
+    let file_len = file.metadata()?.len();
+    let mut header_len = [0u8; 8];
+    file.read_exact(&mut header_len)?;
+    let header_len = u64::from_le_bytes(header_len);
+    if header_len > file_len - 8 {
+        bail!("Invalid safetensors header length {} in {:?}", header_len, path)
+    }
+    let mut header = vec![0u8; header_len as usize];
+    file.read_exact(&mut header)?;
+    let header: serde_json::Value = serde_json::from_slice(&header)
+        .map_err(|e| format!("Invalid safetensors header in {:?}: {}", path, e))?;
+    let header = header.as_object().ok_or("safetensors header must be an object")?;
+    let data_start = 8 + header_len;
+    let mut tensors = HashMap::new();
+    for (name, info) in header {
+        if name == "__metadata__" {
+            continue;
+        }
+        let dt =
+            datum_type(info["dtype"].as_str().ok_or_else(|| format!("No dtype for {}", name))?)?;
+        let shape = usize_array(&info["shape"], "shape", name)?;
+        let offsets = usize_array(&info["data_offsets"], "data_offsets", name)?;
+        if offsets.len() != 2 || offsets[0] > offsets[1] {
+            bail!("Invalid data_offsets {:?} for tensor {}", offsets, name)
+        }
+        let len = shape.iter().product::<usize>() * dt.size_of();
+        if offsets[1] - offsets[0] != len {
+            bail!(
+                "Tensor {} is {:?} {:?}, but spans {} bytes",
+                name,
+                dt,
+                shape,
+                offsets[1] - offsets[0]
+            )
+        }
+        let tensor = Tensor::from_mmap(path, data_start + offsets[0] as u64, &shape, dt)
+            .chain_err(|| format!("Loading tensor {}", name))?;
+        tensors.insert(name.clone(), tensor);
+    }
+    Ok(tensors)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A file laid out as the reference implementation does: header padded
+    // with spaces to a multiple of 8 bytes, then the tensors data.
+    fn write_fixture(path: &Path) -> TractResult<()> {
+        let mut header = String::from(concat!(
+            r#"{"__metadata__":{"format":"pt"},"#,
+            r#""ids":{"dtype":"I64","shape":[3],"data_offsets":[0,24]},"#,
+            r#""embed.weight":{"dtype":"F32","shape":[2,3],"data_offsets":[24,48]},"#,
+            r#""head.bias":{"dtype":"F16","shape":[2],"data_offsets":[48,52]},"#,
+            r#""scale":{"dtype":"BF16","shape":[1],"data_offsets":[52,54]},"#,
+            r#""mask":{"dtype":"BOOL","shape":[2],"data_offsets":[54,56]}}"#,
+        ));
+        while header.len() % 8 != 0 {
+            header.push(' ');
+        }
+        let mut bytes = (header.len() as u64).to_le_bytes().to_vec();
+        bytes.extend(header.as_bytes());
+        for id in &[7i64, -1, 40000] {
+            bytes.extend(&id.to_le_bytes());
+        }
+        for w in &[0.5f32, -1.25, 2.0, 0.0, 3.5, -0.125] {
+            bytes.extend(&w.to_le_bytes());
+        }
+        // 1.5 and -2.0 as f16, 0.75 as bf16
+        for bits in &[0x3e00u16, 0xc000, 0x3f40] {
+            bytes.extend(&bits.to_le_bytes());
+        }
+        bytes.extend(&[1u8, 0]);
+        std::fs::write(path, bytes)?;
+        Ok(())
+    }
+
+    #[test]
+    fn load() -> TractResult<()> {
+        let path = std::env::temp_dir().join(format!("tract-{}.safetensors", std::process::id()));
+        write_fixture(&path)?;
+        let tensors = load_safetensors(&path)?;
+        assert_eq!(tensors.len(), 5);
+        assert_eq!(tensors["ids"], tensor1(&[7i64, -1, 40000]));
+        let weight = &tensors["embed.weight"];
+        assert_eq!(weight.shape(), &[2, 3]);
+        assert!(weight.is_mmapped());
+        assert_eq!(*weight, tensor2(&[[0.5f32, -1.25, 2.0], [0.0, 3.5, -0.125]]));
+        let bias = &tensors["head.bias"];
+        assert_eq!(bias.datum_type(), DatumType::F16);
+        assert_eq!(*bias.cast_to::<f32>()?, tensor1(&[1.5f32, -2.0]));
+        assert_eq!(tensors["scale"].cast_from_bf16()?, tensor1(&[0.75f32]));
+        assert_eq!(tensors["mask"], tensor1(&[true, false]));
+        std::fs::remove_file(&path)?;
+        Ok(())
+    }
+}
@@ -2,6 +2,7 @@ use crate::pb::tensor_proto::DataType;
 use crate::pb::*;
 use prost::Message;
 use std::convert::{TryFrom, TryInto};
+use std::path::Path;
 use tract_core::internal::*;
 use tract_core::*;
 
@@ -17,6 +18,7 @@ impl TryFrom<DataType> for DatumType {
             DataType::Int32 => Ok(DatumType::I32),
             DataType::Int64 => Ok(DatumType::I64),
             DataType::Float16 => Ok(DatumType::F16),
+            DataType::Bfloat16 => Ok(DatumType::BF16),
             DataType::Float => Ok(DatumType::F32),
             DataType::Double => Ok(DatumType::F64),
             DataType::String => Ok(DatumType::String),
@@ -26,6 +28,27 @@ impl TryFrom<DataType> for DatumType {
 
 }
 
+impl TryFrom<DatumType> for DataType {
+    type Error = TractError;
+    fn try_from(t: DatumType) -> TractResult<DataType> {
+        match t {
+            DatumType::Bool => Ok(DataType::Bool),
+            DatumType::U8 => Ok(DataType::Uint8),
+            DatumType::U16 => Ok(DataType::Uint16),
+            DatumType::I8 => Ok(DataType::Int8),
+            DatumType::I16 => Ok(DataType::Int16),
+            DatumType::I32 => Ok(DataType::Int32),
+            DatumType::I64 => Ok(DataType::Int64),
+            DatumType::F16 => Ok(DataType::Float16),
+            DatumType::BF16 => Ok(DataType::Bfloat16),
+            DatumType::F32 => Ok(DataType::Float),
+            DatumType::F64 => Ok(DataType::Double),
+            DatumType::String => Ok(DataType::String),
+            _ => Err(format!("No ONNX equivalent for {:?}", t))?,
+        }
+    }
+}
+
 impl<'a> TryFrom<&'a type_proto::Tensor> for InferenceFact {
     type Error = TractError;
     fn try_from(t: &'a type_proto::Tensor) -> TractResult<InferenceFact> {
@@ -61,6 +84,9 @@ impl TryFrom<type_proto::Tensor> for InferenceFact {
 impl<'a> TryFrom<&'a TensorProto> for Tensor {
     type Error = TractError;
     fn try_from(t: &TensorProto) -> TractResult<Tensor> {
+        if t.data_location == tensor_proto::DataLocation::External as i32 {
+            bail!("Tensor {} data is external, it needs to be loaded with load_tensor", t.name)
+        }
         let dt = DataType::from_i32(t.data_type).unwrap().try_into()?;
         let shape: Vec<usize> = t.dims.iter().map(|&i| i as usize).collect();
         if t.raw_data.len() > 0 {
@@ -73,6 +99,7 @@ impl<'a> TryFrom<&'a TensorProto> for Tensor {
                     DatumType::I32 => Tensor::from_raw::<i32>(&*shape, &*t.raw_data),
                     DatumType::I64 => Tensor::from_raw::<i64>(&*shape, &*t.raw_data),
                     DatumType::F16 => Tensor::from_raw::<f16>(&*shape, &*t.raw_data),
+                    DatumType::BF16 => Tensor::from_raw::<bf16>(&*shape, &*t.raw_data),
                     DatumType::F32 => Tensor::from_raw::<f32>(&*shape, &*t.raw_data),
                     DatumType::F64 => Tensor::from_raw::<f64>(&*shape, &*t.raw_data),
                     DatumType::Bool => Ok(Tensor::from_raw::<u8>(&*shape, &*t.raw_data)?
@@ -113,6 +140,16 @@ impl<'a> TryFrom<&'a TensorProto> for Tensor {
                 DatumType::I64 => {
                     Array::from_shape_vec(&*shape, t.int64_data.to_vec())?.into()
                 }
+                DatumType::F16 => Array::from_shape_vec(
+                    &*shape,
+                    t.int32_data.iter().map(|&x| f16::from_bits(x as u16)).collect(),
+                )?
+                .into(),
+                DatumType::BF16 => Array::from_shape_vec(
+                    &*shape,
+                    t.int32_data.iter().map(|&x| bf16::from_bits(x as u16)).collect(),
+                )?
+                .into(),
                 DatumType::F32 => {
                     Array::from_shape_vec(&*shape, t.float_data.to_vec())?.into()
                 }
@@ -136,6 +173,22 @@ impl<'a> TryFrom<&'a TensorProto> for Tensor {
     }
 }
 
+impl<'a> TryFrom<&'a Tensor> for TensorProto {
+    type Error = TractError;
+    fn try_from(t: &Tensor) -> TractResult<TensorProto> {
+        let mut proto = TensorProto::default();
+        proto.data_type = DataType::try_from(t.datum_type())? as i32;
+        proto.dims = t.shape().iter().map(|&d| d as i64).collect();
+        if t.datum_type() == DatumType::String {
+            proto.string_data =
+                t.as_slice::<String>()?.iter().map(|s| s.as_bytes().to_vec()).collect();
+        } else {
+            proto.raw_data = unsafe { t.as_bytes().to_vec() };
+        }
+        Ok(proto)
+    }
+}
+
 impl TryFrom<TensorProto> for Tensor {
     type Error = TractError;
     fn try_from(t: TensorProto) -> TractResult<Tensor> {
@@ -143,6 +196,63 @@ impl TryFrom<TensorProto> for Tensor {
     }
 }
 
+/// Load a tensor, looking for its data in `external_data_dir` if it is
+/// stored externally.
+///
+/// External data is mapped from its file rather than read.
+pub fn load_tensor(t: &TensorProto, external_data_dir: Option<&Path>) -> TractResult<Tensor> {
+    if t.data_location != tensor_proto::DataLocation::External as i32 {
+        return t.try_into();
+    }
+    let dir = external_data_dir.ok_or_else(|| {
+        format!("Tensor {} data is external, but no external data directory is set", t.name)
+    })?;
+    let mut location = None;
+    let mut offset = 0u64;
+    let mut length = None;
+    for entry in &t.external_data {
+        match &*entry.key {
+            "location" => location = Some(&entry.value),
+            "offset" => offset = entry.value.parse()?,
+            "length" => length = Some(entry.value.parse::<u64>()?),
+            _ => (),
+        }
+    }
+    let location =
+        location.ok_or_else(|| format!("Tensor {} external data has no location", t.name))?;
+    let path = dir.join(location);
+    let file_len = std::fs::metadata(&path)
+        .map_err(|e| format!("External data file {:?} for tensor {}: {}", path, t.name, e))?
+        .len();
+    let dt: DatumType = DataType::from_i32(t.data_type).unwrap().try_into()?;
+    if dt == DatumType::String {
+        bail!("Tensor {}: external string data is not supported", t.name)
+    }
+    let shape: Vec<usize> = t.dims.iter().map(|&i| i as usize).collect();
+    let expected = (shape.iter().product::<usize>() * dt.size_of()) as u64;
+    let length = length.unwrap_or(expected);
+    if length != expected {
+        bail!(
+            "Tensor {} is {:?} {:?}, but its external data is {} bytes long",
+            t.name,
+            dt,
+            shape,
+            length
+        )
+    }
+    if offset + length > file_len {
+        bail!(
+            "Tensor {} external data ({} bytes at offset {}) goes past the end of {:?} ({} bytes)",
+            t.name,
+            length,
+            offset,
+            path,
+            file_len
+        )
+    }
+    Tensor::from_mmap(&path, offset, &shape, dt)
+}
+
 pub fn proto_from_reader<R: ::std::io::Read>(mut r: R) -> TractResult<TensorProto> {
     let mut v = vec![];
     r.read_to_end(&mut v)?;
@@ -0,0 +1,350 @@
+//! Binary serialization of tract TypedModels, as protocol buffers.
+//!
+//! Loading a model gives back the same graph, with no parsing of the
+//! original framework format and no optimisation pass to redo. Decluttered
+//! models can be loaded anywhere: call `codegen()` on the loaded model,
+//! which is cheap next to the decluttering passes.
+//!
+//! Float models can also be saved after codegen, and run as they are loaded.
+//! Their matrix products hold weights packed for the kernel of the host that
+//! optimized them: loading such a model fails on a host whose kernel packs
+//! them differently, an other CPU family for instance.
+//!
+//! ```no_run
+//! # use tract_core::internal::*;
+//! # fn f(model: TypedModel) -> TractResult<()> {
+//! let model = model.declutter()?;
+//! tract_pb::save(&model, "model.tpb")?;
+//! let plan = SimplePlan::new(tract_pb::load("model.tpb")?.codegen()?)?;
+//!
+//! tract_pb::save(&model.into_optimized()?, "optimized.tpb")?;
+//! let plan = SimplePlan::new(tract_pb::load("optimized.tpb")?)?;
+//! # Ok(()) }
+//! ```
+#[macro_use]
+extern crate error_chain;
+extern crate prost;
+extern crate tract_core;
+extern crate tract_linalg;
+
+pub mod model;
+pub mod ops;
+pub mod phy;
+pub mod tensor;
+
+pub mod pb {
+    include!(concat!(env!("OUT_DIR"), "/prost/tract.rs"));
+}
+
+pub use model::{model_for_proto, model_to_proto, TypedModelProto};
+
+use prost::Message;
+use std::path::Path;
+use tract_core::internal::*;
+
+/// Value of the `magic` field of serialized models.
+pub const MAGIC: u32 = 0x5452_4350;
+/// Version of the format written by this crate, and the only one it reads.
+pub const FORMAT_VERSION: u32 = 2;
+
+/// Serialize a model to bytes.
+pub fn to_bytes(model: &TypedModel) -> TractResult<Vec<u8>> {
+    let proto = model_to_proto(model)?;
+    let mut buffer = Vec::with_capacity(proto.encoded_len());
+    proto.encode(&mut buffer).map_err(|e| format!("{:?}", e))?;
+    Ok(buffer)
+}
+
+/// Load a model from bytes produced by `to_bytes`.
+pub fn from_bytes(bytes: &[u8]) -> TractResult<TypedModel> {
+    let proto = pb::ModelProto::decode(bytes).map_err(|e| format!("{:?}", e))?;
+    model_for_proto(&proto)
+}
+
+/// Serialize a model to the file at `path`.
+pub fn save(model: &TypedModel, path: impl AsRef<Path>) -> TractResult<()> {
+    std::fs::write(path, to_bytes(model)?)?;
+    Ok(())
+}
+
+/// Load a model from the file at `path`.
+pub fn load(path: impl AsRef<Path>) -> TractResult<TypedModel> {
+    let path = path.as_ref();
+    let bytes = std::fs::read(path).chain_err(|| format!("Could not read {:?}", path))?;
+    from_bytes(&bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tract_core::ndarray;
+    use tract_core::ops::binary::TypedBinOp;
+    use tract_core::ops::cnn::{AvgPool, Conv, MaxPool, PaddingSpec, PoolSpec};
+    use tract_core::ops::control_flow::If;
+    use tract_core::ops::math;
+    use tract_core::ops::matmul::phy::MatMatMulUnaryFinite;
+    use tract_core::ops::nn::{DataFormat, Reducer, TypedReduce};
+    use tract_core::ops::scan::{InputMapping, OutputMapping, StateInitializer, TypedScan};
+
+    const C: usize = 8;
+
+    fn values(shape: &[usize], seed: usize) -> Tensor {
+        let len = shape.iter().product::<usize>();
+        let data = (0..len).map(|i| ((i * 7 + seed) % 19) as f32 / 19.0 - 0.5).collect();
+        ndarray::ArrayD::from_shape_vec(shape, data).unwrap().into()
+    }
+
+    // conv stem, two residual blocks, max pool, global mean and a dense head
+    fn resnet() -> TractResult<TypedModel> {
+        let mut model = TypedModel::default();
+        let fact = TypedFact::dt_shape(f32::datum_type(), [1, C, 8, 8].as_ref())?;
+        let mut x = model.add_source("input", fact)?;
+        for b in 0..2 {
+            let residual = x;
+            for c in 0..2 {
+                let name = format!("block{}.{}", b, c);
+                let kernel = values(&[C, C, 3, 3], b * 2 + c);
+                let conv =
+                    Conv::default().padding(PaddingSpec::SameUpper).kernel_shape(tvec!(3, 3));
+                let conv =
+                    conv.to_unary(&[model.outlet_fact(x)?, &TypedFact::from(kernel)])?.unwrap();
+                x = model.wire_node(format!("{}.conv", name), conv, &[x])?[0];
+                let scale = values(&[C, 1, 1], 3).into_arc_tensor();
+                x = model.wire_node(format!("{}.scale", name), math::mul::unary(scale), &[x])?[0];
+                if c == 1 {
+                    let add = TypedBinOp(Box::new(math::Add));
+                    x = model.wire_node(format!("{}.residual", name), add, &[residual, x])?[0];
+                }
+                let relu = math::max::unary(rctensor0(0f32));
+                x = model.wire_node(format!("{}.relu", name), relu, &[x])?[0];
+            }
+        }
+        let pool_spec = PoolSpec::new(
+            DataFormat::NCHW,
+            tvec!(2, 2),
+            PaddingSpec::Valid,
+            None,
+            Some(tvec!(2, 2)),
+            None,
+        );
+        x = model.wire_node("pool", MaxPool::new(pool_spec, None), &[x])?[0];
+        let mean = TypedReduce::new(tvec!(2, 3), Reducer::Mean);
+        x = model.wire_node("mean", mean, &[x])?[0];
+        let flatten = tract_core::ops::array::FiniteReshape::new(tvec!(C, 1));
+        x = model.wire_node("flatten", flatten, &[x])?[0];
+        let dense = tract_core::ops::matmul::MatMulUnary::new(
+            values(&[10, C], 5).into_arc_tensor(),
+            false,
+            false,
+            false,
+            None,
+        );
+        x = model.wire_node("dense", dense, &[x])?[0];
+        model.set_output_outlets(&[x])?;
+        model.set_outlet_label(x, "logits".to_string());
+        model.declutter()
+    }
+
+    fn run(model: TypedModel) -> TractResult<Arc<Tensor>> {
+        let plan = SimplePlan::new(model.codegen()?)?;
+        Ok(plan.run(tvec!(values(&[1, C, 8, 8], 11)))?.remove(0))
+    }
+
+    #[test]
+    fn round_trip_resnet() -> TractResult<()> {
+        let model = resnet()?;
+        let bytes = to_bytes(&model)?;
+        let reloaded = from_bytes(&bytes)?;
+        assert_eq!(reloaded.nodes().len(), model.nodes().len());
+        for (a, b) in model.nodes().iter().zip(reloaded.nodes().iter()) {
+            assert_eq!(a.name, b.name);
+            assert_eq!(a.op().name(), b.op().name());
+            assert_eq!(a.inputs, b.inputs);
+        }
+        assert_eq!(reloaded.find_outlet_label("logits"), model.output_outlets()?.get(0).cloned());
+        assert_eq!(to_bytes(&reloaded)?, bytes);
+        assert_eq!(run(reloaded)?, run(model)?);
+        Ok(())
+    }
+
+    #[test]
+    fn save_and_load() -> TractResult<()> {
+        let model = resnet()?;
+        let path = std::env::temp_dir().join(format!("tract-pb-{}.tpb", std::process::id()));
+        save(&model, &path)?;
+        let reloaded = load(&path)?;
+        std::fs::remove_file(&path)?;
+        assert_eq!(run(reloaded)?, run(model)?);
+        Ok(())
+    }
+
+    #[test]
+    fn checks_magic_and_version() -> TractResult<()> {
+        let mut proto = model_to_proto(&resnet()?)?;
+        proto.version = FORMAT_VERSION + 1;
+        let err = model_for_proto(&proto).unwrap_err().to_string();
+        assert!(err.contains("format version"), "{}", err);
+        proto.magic = 0;
+        let err = model_for_proto(&proto).unwrap_err().to_string();
+        assert!(err.starts_with("Not a tract model"), "{}", err);
+        Ok(())
+    }
+
+    #[test]
+    fn optimized_resnet() -> TractResult<()> {
+        let optimized = resnet()?.into_optimized()?;
+        let bytes = to_bytes(&optimized)?;
+        let reloaded = from_bytes(&bytes)?;
+        assert!(reloaded.is_optimized());
+        assert_eq!(reloaded.nodes().len(), optimized.nodes().len());
+        for (a, b) in optimized.nodes().iter().zip(reloaded.nodes().iter()) {
+            assert_eq!(a.name, b.name);
+            assert_eq!(a.op().name(), b.op().name());
+        }
+        let products = reloaded
+            .nodes()
+            .iter()
+            .filter(|n| n.op_is::<MatMatMulUnaryFinite<f32, f32, f32, f32>>())
+            .count();
+        assert_eq!(products, 5);
+        assert_eq!(to_bytes(&reloaded)?, bytes);
+        let input = values(&[1, C, 8, 8], 11);
+        let found = SimplePlan::new(reloaded)?.run(tvec!(input.clone()))?.remove(0);
+        assert_eq!(found, SimplePlan::new(optimized)?.run(tvec!(input))?.remove(0));
+        Ok(())
+    }
+
+    // depth-wise and valid convolutions, an average pool, and a product
+    // packing its input with a fused relu
+    fn physical() -> TractResult<TypedModel> {
+        let mut model = TypedModel::default();
+        let fact = TypedFact::dt_shape(f32::datum_type(), [1, C, 8, 8].as_ref())?;
+        let mut x = model.add_source("input", fact)?;
+        let convs = [
+            (Conv::default().padding(PaddingSpec::SameUpper).group(C), values(&[C, 1, 3, 3], 1)),
+            (Conv::default(), values(&[C, C, 3, 3], 2)),
+        ];
+        for (ix, (conv, kernel)) in convs.iter().enumerate() {
+            let conv = conv.clone().kernel_shape(tvec!(3, 3));
+            let conv = conv.to_unary(&[model.outlet_fact(x)?, &TypedFact::from(kernel.clone())])?;
+            x = model.wire_node(format!("conv{}", ix), conv.unwrap(), &[x])?[0];
+        }
+        let pool_spec = PoolSpec::new(
+            DataFormat::NCHW,
+            tvec!(2, 2),
+            PaddingSpec::Valid,
+            None,
+            Some(tvec!(2, 2)),
+            None,
+        );
+        x = model.wire_node("pool", AvgPool::new(pool_spec, false), &[x])?[0];
+        let flatten = tract_core::ops::array::FiniteReshape::new(tvec!(C, 9));
+        x = model.wire_node("flatten", flatten, &[x])?[0];
+        let a = values(&[4, C], 5).into_arc_tensor();
+        let product = tract_core::ops::matmul::MatMulUnary::new(a, false, false, false, None);
+        x = model.wire_node("product", product, &[x])?[0];
+        x = model.wire_node("relu", math::scalar_max(tensor0(0f32)), &[x])?[0];
+        model.set_output_outlets(&[x])?;
+        model.declutter()
+    }
+
+    #[test]
+    fn optimized_physical_ops() -> TractResult<()> {
+        let optimized = physical()?.into_optimized()?;
+        let names = optimized.nodes().iter().map(|n| n.op().name()).collect::<Vec<_>>();
+        for name in &["Conv::DepthWise<F32>", "AvgPool::Fixed<F32>", "MatMatMulPackB"] {
+            assert!(names.iter().any(|n| n == name), "{:?}", names);
+        }
+        let bytes = to_bytes(&optimized)?;
+        let reloaded = from_bytes(&bytes)?;
+        assert!(reloaded.is_optimized());
+        let product = reloaded.node_by_name("product-matmatmul")?;
+        let product = product.op_as::<MatMatMulUnaryFinite<f32, f32, f32, f32>>().unwrap();
+        assert!(product.fused_ops().is_some());
+        assert_eq!(to_bytes(&reloaded)?, bytes);
+        let input = values(&[1, C, 8, 8], 11);
+        let found = SimplePlan::new(reloaded)?.run(tvec!(input.clone()))?.remove(0);
+        let expected = SimplePlan::new(optimized)?.run(tvec!(input.clone()))?.remove(0);
+        assert_eq!(found, expected);
+        let decluttered = SimplePlan::new(physical()?)?.run(tvec!(input))?.remove(0);
+        found.close_enough(&decluttered, true)
+    }
+
+    #[test]
+    fn checks_kernel_packing() -> TractResult<()> {
+        let mut proto = model_to_proto(&resnet()?.into_optimized()?)?;
+        for node in proto.nodes.iter_mut() {
+            if let Some(pb::op_proto::Op::MatMatMul(op)) =
+                node.op.as_mut().and_then(|o| o.op.as_mut())
+            {
+                op.mmm.as_mut().unwrap().mr += 1;
+            }
+        }
+        let err = model_for_proto(&proto).unwrap_err();
+        let err = err.iter().map(|e| e.to_string()).collect::<Vec<_>>().join(": ");
+        assert!(err.contains("load the decluttered model"), "{}", err);
+        Ok(())
+    }
+
+    fn branch(negate: bool) -> TractResult<TypedModel> {
+        let mut body = TypedModel::default();
+        let x = body.add_source("x", TypedFact::dt_shape(f32::datum_type(), [3].as_ref())?)?;
+        let y = if negate {
+            body.wire_node("neg", math::neg(), &[x])?[0]
+        } else {
+            let bias = body.add_const("bias", rctensor1(&[1f32, 2.0, 3.0]))?;
+            body.wire_node("add", TypedBinOp(Box::new(math::Add)), &[x, bias])?[0]
+        };
+        body.set_output_outlets(&[y])?;
+        Ok(body)
+    }
+
+    #[test]
+    fn round_trip_if() -> TractResult<()> {
+        let mut model = TypedModel::default();
+        let cond = model
+            .add_source("cond", TypedFact::dt_shape(bool::datum_type(), [0usize; 0].as_ref())?)?;
+        let x = model.add_source("x", TypedFact::dt_shape(f32::datum_type(), [3].as_ref())?)?;
+        let y = model.wire_node("if", If::new(branch(false)?, branch(true)?), &[cond, x])?[0];
+        model.set_output_outlets(&[y])?;
+        let model = model.declutter()?;
+        let reloaded = TypedModel::from_proto(&model.to_proto()?)?;
+        let op = reloaded.node_by_name("if")?.op_as::<If>().unwrap();
+        assert_eq!(op.then_body.node_names().collect::<Vec<_>>(), &["x", "add"]);
+        assert_eq!(op.else_body.node_names().collect::<Vec<_>>(), &["x", "neg"]);
+        assert_eq!(to_bytes(&reloaded)?, to_bytes(&model)?);
+        let plan = SimplePlan::new(reloaded.codegen()?)?;
+        let x = tensor1(&[1f32, -2.0, 0.5]);
+        assert_eq!(plan.run(tvec!(tensor0(true), x.clone()))?[0], rctensor1(&[2f32, 0.0, 3.5]));
+        assert_eq!(plan.run(tvec!(tensor0(false), x))?[0], rctensor1(&[-1f32, 2.0, -0.5]));
+        Ok(())
+    }
+
+    #[test]
+    fn round_trip_reversed_scan() -> TractResult<()> {
+        let fact = TypedFact::dt_shape(f32::datum_type(), [1].as_ref())?;
+        let mut body = TypedModel::default();
+        let acc = body.add_source("acc", fact.clone())?;
+        let x = body.add_source("x", fact)?;
+        let sum = body.wire_node("sum", TypedBinOp(Box::new(math::Add)), &[acc, x])?;
+        body.set_output_outlets(&sum)?;
+        let input_mapping = vec![
+            InputMapping::State { initializer: StateInitializer::Value(rctensor1(&[0f32])) },
+            InputMapping::Scan { slot: 0, axis: 0, chunk: 1.to_dim(), reverse: true },
+        ];
+        let output_mapping = vec![OutputMapping::new(Some(0), 0, 1.to_dim(), None, None, true)];
+        let scan = TypedScan::new(body, input_mapping, output_mapping, None)?;
+        let mut model = TypedModel::default();
+        let x = model.add_source("x", TypedFact::dt_shape(f32::datum_type(), [4].as_ref())?)?;
+        let y = model.wire_node("scan", scan, &[x])?;
+        model.set_output_outlets(&y)?;
+        let reloaded = from_bytes(&to_bytes(&model)?)?;
+        let op = reloaded.node_by_name("scan")?.op_as::<TypedScan>().unwrap();
+        assert!(op.input_mapping[1].as_scan().is_some());
+        assert_eq!(to_bytes(&reloaded)?, to_bytes(&model)?);
+        let plan = SimplePlan::new(reloaded.into_optimized()?)?;
+        let found = plan.run(tvec!(tensor1(&[1f32, 2.0, 3.0, 4.0])))?.remove(0);
+        assert_eq!(found, rctensor1(&[4f32, 7.0, 9.0, 10.0]));
+        Ok(())
+    }
+}
@@ -0,0 +1,115 @@
+use crate::ops::{op_from_pb, op_to_pb};
+use crate::pb::*;
+use std::convert::TryFrom;
+use tract_core::internal::*;
+
+fn outlet_to_pb(outlet: OutletId) -> OutletProto {
+    OutletProto { node: outlet.node as u32, slot: outlet.slot as u32 }
+}
+
+fn outlet_from_pb(outlet: &OutletProto) -> OutletId {
+    OutletId::new(outlet.node as usize, outlet.slot as usize)
+}
+
+/// Convert a TypedModel to its protobuf form.
+///
+/// Only models made of the operators listed in `ops.rs` and `phy.rs` can be
+/// serialized: this covers decluttered models, and float models after
+/// codegen.
+pub fn model_to_proto(model: &TypedModel) -> TractResult<ModelProto> {
+    let mut proto =
+        ModelProto { magic: crate::MAGIC, version: crate::FORMAT_VERSION, ..ModelProto::default() };
+    for node in model.nodes() {
+        let op = op_to_pb(&*node.op).chain_err(|| format!("Serializing {}", node))?;
+        proto.nodes.push(NodeProto {
+            name: node.name.clone(),
+            inputs: node.inputs.iter().cloned().map(outlet_to_pb).collect(),
+            op: Some(op),
+            output_facts: node
+                .outputs
+                .iter()
+                .map(|o| TypedFactProto::try_from(&o.fact))
+                .collect::<TractResult<_>>()
+                .chain_err(|| format!("Serializing {}", node))?,
+        });
+        for slot in 0..node.outputs.len() {
+            let outlet = OutletId::new(node.id, slot);
+            if let Some(label) = model.outlet_label(outlet) {
+                proto.labels.push(OutletLabelProto {
+                    outlet: Some(outlet_to_pb(outlet)),
+                    label: label.to_string(),
+                });
+            }
+        }
+    }
+    proto.inputs = model.input_outlets()?.iter().cloned().map(outlet_to_pb).collect();
+    proto.outputs = model.output_outlets()?.iter().cloned().map(outlet_to_pb).collect();
+    proto.optimized = model.is_optimized();
+    Ok(proto)
+}
+
+/// Rebuild a TypedModel from its protobuf form.
+///
+/// Nodes keep their ids, and no optimisation pass is run.
+pub fn model_for_proto(proto: &ModelProto) -> TractResult<TypedModel> {
+    if proto.magic != crate::MAGIC {
+        bail!("Not a tract model (magic is {:#x}, expected {:#x})", proto.magic, crate::MAGIC)
+    }
+    if proto.version != crate::FORMAT_VERSION {
+        bail!(
+            "Unsupported tract model format version {} (this is version {})",
+            proto.version,
+            crate::FORMAT_VERSION
+        )
+    }
+    let mut model = TypedModel::default();
+    for (id, node) in proto.nodes.iter().enumerate() {
+        let facts = node
+            .output_facts
+            .iter()
+            .map(TypedFact::try_from)
+            .collect::<TractResult<TVec<_>>>()
+            .chain_err(|| format!("Loading node #{} \"{}\"", id, node.name))?;
+        let op = node.op.as_ref().ok_or_else(|| format!("Node \"{}\" has no op", node.name))?;
+        let op = op_from_pb(op, &facts)
+            .chain_err(|| format!("Loading node #{} \"{}\"", id, node.name))?;
+        model.add_node(&*node.name, op, facts)?;
+    }
+    for (id, node) in proto.nodes.iter().enumerate() {
+        for (ix, input) in node.inputs.iter().enumerate() {
+            let input = outlet_from_pb(input);
+            if input.node >= proto.nodes.len() {
+                bail!("Node \"{}\" input {} refers to a missing node", node.name, ix)
+            }
+            model.add_edge(input, InletId::new(id, ix))?;
+        }
+    }
+    for label in &proto.labels {
+        let outlet = label.outlet.as_ref().ok_or("Label without outlet")?;
+        model.set_outlet_label(outlet_from_pb(outlet), label.label.clone());
+    }
+    let inputs: TVec<_> = proto.inputs.iter().map(outlet_from_pb).collect();
+    model.set_input_outlets(&inputs)?;
+    let outputs: TVec<_> = proto.outputs.iter().map(outlet_from_pb).collect();
+    model.set_output_outlets(&outputs)?;
+    model.set_optimized(proto.optimized);
+    Ok(model)
+}
+
+/// Protobuf conversions, as TypedModel methods.
+///
+/// Nested models of control flow operators are converted recursively.
+pub trait TypedModelProto: Sized {
+    fn to_proto(&self) -> TractResult<ModelProto>;
+    fn from_proto(proto: &ModelProto) -> TractResult<Self>;
+}
+
+impl TypedModelProto for TypedModel {
+    fn to_proto(&self) -> TractResult<ModelProto> {
+        model_to_proto(self)
+    }
+
+    fn from_proto(proto: &ModelProto) -> TractResult<TypedModel> {
+        model_for_proto(proto)
+    }
+}
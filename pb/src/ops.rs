@@ -0,0 +1,616 @@
+//! Serialization of the supported operators.
+use crate::model::{model_for_proto, model_to_proto};
+use crate::pb::op_proto::Op as OpEnum;
+use crate::pb::*;
+use crate::phy;
+use crate::tensor::*;
+
+use tract_core::internal::*;
+use tract_core::ops;
+use tract_core::ops::binary::{BinMiniOp, MergeOp, MergeOpUnicast, TypedBinOp, UnaryOp};
+use tract_core::ops::cnn::{
+    AvgPool, ConvAlgorithmSelector, ConvUnary, KernelFormat, MaxPool, PaddingSpec, PoolSpec,
+};
+use tract_core::ops::control_flow::{If, Loop};
+use tract_core::ops::element_wise::{ElementWiseMiniOp, ElementWiseOp};
+use tract_core::ops::nn::{DataFormat, GlobalAvgPool, GlobalMaxPool, Reducer, TypedReduce};
+use tract_core::ops::quant::QParams;
+use tract_core::ops::scan::{InputMapping, OutputMapping, StateInitializer, TypedScan};
+
+pub(crate) fn bin_mini_op(name: &str) -> TractResult<Box<dyn BinMiniOp>> {
+    use tract_core::ops::logic::*;
+    use tract_core::ops::math::*;
+    Ok(match name {
+        "Add" => Box::new(Add),
+        "Sub" => Box::new(Sub),
+        "Mul" => Box::new(Mul),
+        "Div" => Box::new(Div),
+        "Rem" => Box::new(Rem),
+        "Min" => Box::new(Min),
+        "Max" => Box::new(Max),
+        "Pow" => Box::new(Pow),
+        "ShiftLeft" => Box::new(ShiftLeft),
+        "ShiftRight" => Box::new(ShiftRight),
+        "FlippedShiftLeft" => Box::new(FlippedShiftLeft),
+        "FlippedShiftRight" => Box::new(FlippedShiftRight),
+        "And" => Box::new(And),
+        "Or" => Box::new(Or),
+        "Xor" => Box::new(Xor),
+        "Equals" => Box::new(Equals),
+        "Lesser" => Box::new(Lesser),
+        "LesserEqual" => Box::new(LesserEqual),
+        "Greatser" => Box::new(Greatser),
+        "GreaterEqual" => Box::new(GreaterEqual),
+        _ => bail!("Unknown binary operator {}", name),
+    })
+}
+
+fn element_wise_to_pb(mini_op: &dyn ElementWiseMiniOp) -> TractResult<ElementWiseProto> {
+    use tract_core::ops::math::{ScalarMax, ScalarMin, ScalarMinMax};
+    use tract_core::ops::nn::*;
+    let any = mini_op.as_any();
+    let params = if let Some(op) = any.downcast_ref::<Elu>() {
+        vec![op.alpha]
+    } else if let Some(op) = any.downcast_ref::<HardSigmoid>() {
+        vec![op.alpha, op.beta]
+    } else if let Some(op) = any.downcast_ref::<LeakyRelu>() {
+        vec![op.alpha]
+    } else if let Some(op) = any.downcast_ref::<ParametricSoftplus>() {
+        vec![op.alpha, op.beta]
+    } else if let Some(op) = any.downcast_ref::<ScaledTanh>() {
+        vec![op.alpha, op.beta]
+    } else if let Some(op) = any.downcast_ref::<Selu>() {
+        vec![op.alpha, op.gamma]
+    } else if let Some(op) = any.downcast_ref::<ThresholdRelu>() {
+        vec![op.alpha]
+    } else {
+        vec![]
+    };
+    let tensor_params = if let Some(op) = any.downcast_ref::<ScalarMin>() {
+        vec![tensor_to_pb(&op.min)?]
+    } else if let Some(op) = any.downcast_ref::<ScalarMax>() {
+        vec![tensor_to_pb(&op.max)?]
+    } else if let Some(op) = any.downcast_ref::<ScalarMinMax>() {
+        vec![tensor_to_pb(&op.min)?, tensor_to_pb(&op.max)?]
+    } else {
+        vec![]
+    };
+    Ok(ElementWiseProto { mini_op: mini_op.name(), params, tensor_params })
+}
+
+fn element_wise_from_pb(proto: &ElementWiseProto) -> TractResult<Box<dyn ElementWiseMiniOp>> {
+    use tract_core::ops::logic::*;
+    use tract_core::ops::math::*;
+    use tract_core::ops::nn::*;
+    let p = |ix: usize| -> TractResult<f32> {
+        proto
+            .params
+            .get(ix)
+            .cloned()
+            .ok_or_else(|| format!("Missing parameter {} for {}", ix, proto.mini_op).into())
+    };
+    let t = |ix: usize| -> TractResult<Tensor> {
+        Ok(tensor_from_pb(proto.tensor_params.get(ix))?.as_ref().clone())
+    };
+    Ok(match &*proto.mini_op {
+        "Softplus" => Box::new(Softplus {}),
+        "Softsign" => Box::new(Softsign {}),
+        "Sigmoid" => Box::new(Sigmoid {}),
+        "Elu" => Box::new(Elu { alpha: p(0)? }),
+        "HardSigmoid" => Box::new(HardSigmoid { alpha: p(0)?, beta: p(1)? }),
+        "LeakyRelu" => Box::new(LeakyRelu { alpha: p(0)? }),
+        "ParametricSoftplus" => Box::new(ParametricSoftplus { alpha: p(0)?, beta: p(1)? }),
+        "ScaledTanh" => Box::new(ScaledTanh { alpha: p(0)?, beta: p(1)? }),
+        "Selu" => Box::new(Selu { alpha: p(0)?, gamma: p(1)? }),
+        "ThresholdRelu" => Box::new(ThresholdRelu { alpha: p(0)? }),
+        "Abs" => Box::new(Abs {}),
+        "Exp" => Box::new(Exp {}),
+        "Ln" => Box::new(Ln {}),
+        "Sqrt" => Box::new(Sqrt {}),
+        "Recip" => Box::new(Recip {}),
+        "Rsqrt" => Box::new(Rsqrt {}),
+        "Ceil" => Box::new(Ceil {}),
+        "Floor" => Box::new(Floor {}),
+        "ScalarMinMax" => Box::new(ScalarMinMax { min: t(0)?, max: t(1)? }),
+        "ScalarMin" => Box::new(ScalarMin { min: t(0)? }),
+        "ScalarMax" => Box::new(ScalarMax { max: t(0)? }),
+        "Cos" => Box::new(Cos {}),
+        "Sin" => Box::new(Sin {}),
+        "Tan" => Box::new(Tan {}),
+        "Acos" => Box::new(Acos {}),
+        "Asin" => Box::new(Asin {}),
+        "Atan" => Box::new(Atan {}),
+        "Cosh" => Box::new(Cosh {}),
+        "Sinh" => Box::new(Sinh {}),
+        "Tanh" => Box::new(Tanh {}),
+        "Acosh" => Box::new(Acosh {}),
+        "Asinh" => Box::new(Asinh {}),
+        "Atanh" => Box::new(Atanh {}),
+        "Neg" => Box::new(Neg {}),
+        "Sign" => Box::new(Sign {}),
+        "Not" => Box::new(Not {}),
+        _ => bail!("Unknown element-wise operator {}", proto.mini_op),
+    })
+}
+
+pub(crate) fn usizes_to_pb(it: &[usize]) -> Vec<u64> {
+    it.iter().map(|&i| i as u64).collect()
+}
+
+pub(crate) fn usizes_from_pb(it: &[u64]) -> TVec<usize> {
+    it.iter().map(|&i| i as usize).collect()
+}
+
+pub(crate) fn data_format_to_pb(data_format: DataFormat) -> i32 {
+    use crate::pb::pool_spec_proto::DataFormat as DF;
+    let data_format = match data_format {
+        DataFormat::NCHW => DF::Nchw,
+        DataFormat::NHWC => DF::Nhwc,
+        DataFormat::CHW => DF::Chw,
+        DataFormat::HWC => DF::Hwc,
+    };
+    data_format as i32
+}
+
+pub(crate) fn data_format_from_pb(data_format: i32) -> TractResult<DataFormat> {
+    use crate::pb::pool_spec_proto::DataFormat as DF;
+    Ok(match DF::from_i32(data_format) {
+        Some(DF::Nchw) => DataFormat::NCHW,
+        Some(DF::Nhwc) => DataFormat::NHWC,
+        Some(DF::Chw) => DataFormat::CHW,
+        Some(DF::Hwc) => DataFormat::HWC,
+        None => bail!("Unknown data format {}", data_format),
+    })
+}
+
+pub(crate) fn padding_to_pb(padding: &PaddingSpec) -> PaddingProto {
+    use crate::pb::padding_proto::Mode;
+    match padding {
+        PaddingSpec::Valid => PaddingProto { mode: Mode::Valid as i32, ..PaddingProto::default() },
+        PaddingSpec::SameUpper => {
+            PaddingProto { mode: Mode::SameUpper as i32, ..PaddingProto::default() }
+        }
+        PaddingSpec::SameLower => {
+            PaddingProto { mode: Mode::SameLower as i32, ..PaddingProto::default() }
+        }
+        PaddingSpec::Explicit(before, after) => PaddingProto {
+            mode: Mode::Explicit as i32,
+            before: usizes_to_pb(before),
+            after: usizes_to_pb(after),
+        },
+    }
+}
+
+pub(crate) fn padding_from_pb(padding: Option<&PaddingProto>) -> TractResult<PaddingSpec> {
+    use crate::pb::padding_proto::Mode;
+    let padding = padding.ok_or("Missing padding")?;
+    Ok(match Mode::from_i32(padding.mode) {
+        Some(Mode::Valid) => PaddingSpec::Valid,
+        Some(Mode::SameUpper) => PaddingSpec::SameUpper,
+        Some(Mode::SameLower) => PaddingSpec::SameLower,
+        Some(Mode::Explicit) => {
+            PaddingSpec::Explicit(usizes_from_pb(&padding.before), usizes_from_pb(&padding.after))
+        }
+        None => bail!("Unknown padding mode {}", padding.mode),
+    })
+}
+
+fn pool_spec_to_pb(spec: &PoolSpec) -> PoolSpecProto {
+    PoolSpecProto {
+        data_format: data_format_to_pb(spec.data_format),
+        kernel_shape: usizes_to_pb(&spec.kernel_shape),
+        padding: Some(padding_to_pb(&spec.padding)),
+        dilations: spec.dilations.as_ref().map(|d| usizes_to_pb(d)).unwrap_or_default(),
+        strides: spec.strides.as_ref().map(|d| usizes_to_pb(d)).unwrap_or_default(),
+        output_channel_override: spec.output_channel_override.unwrap_or(0) as u64,
+    }
+}
+
+fn pool_spec_from_pb(spec: Option<&PoolSpecProto>) -> TractResult<PoolSpec> {
+    let spec = spec.ok_or("Missing pool spec")?;
+    let optional = |it: &[u64]| if it.is_empty() { None } else { Some(usizes_from_pb(it)) };
+    Ok(PoolSpec {
+        data_format: data_format_from_pb(spec.data_format)?,
+        kernel_shape: usizes_from_pb(&spec.kernel_shape),
+        padding: padding_from_pb(spec.padding.as_ref())?,
+        dilations: optional(&spec.dilations),
+        strides: optional(&spec.strides),
+        output_channel_override: if spec.output_channel_override == 0 {
+            None
+        } else {
+            Some(spec.output_channel_override as usize)
+        },
+    })
+}
+
+fn q_params_to_pb(q: &QParams) -> TractResult<QParamsProto> {
+    let tensor = |t: &Option<Arc<Tensor>>| t.as_ref().map(|t| tensor_to_pb(t)).transpose();
+    Ok(QParamsProto {
+        c_datum_type: datum_type_to_pb(q.c_datum_type)?,
+        zero_point_a: tensor(&q.zero_point_a)?,
+        zero_point_b: tensor(&q.zero_point_b)?,
+        zero_point_c: tensor(&q.zero_point_c)?,
+        has_scale_factor: q.scale_factor.is_some(),
+        scale_factor: q.scale_factor.unwrap_or(0.0),
+    })
+}
+
+fn q_params_from_pb(q: &QParamsProto) -> TractResult<QParams> {
+    let tensor = |t: &Option<TensorProto>| t.as_ref().map(|t| tensor_from_pb(Some(t))).transpose();
+    Ok(QParams {
+        c_datum_type: datum_type_from_pb(q.c_datum_type)?,
+        zero_point_a: tensor(&q.zero_point_a)?,
+        zero_point_b: tensor(&q.zero_point_b)?,
+        zero_point_c: tensor(&q.zero_point_c)?,
+        scale_factor: if q.has_scale_factor { Some(q.scale_factor) } else { None },
+    })
+}
+
+fn conv_to_pb(conv: &ConvUnary) -> TractResult<ConvProto> {
+    use crate::pb::conv_proto::{Algorithm, KernelFormat as KF};
+    let kernel_format = match conv.kernel_fmt {
+        KernelFormat::OIHW => KF::Oihw,
+        KernelFormat::HWIO => KF::Hwio,
+    };
+    let algorithm = match conv.algorithm {
+        ConvAlgorithmSelector::Auto => Algorithm::Auto,
+        ConvAlgorithmSelector::Gemm => Algorithm::Gemm,
+        ConvAlgorithmSelector::Winograd => Algorithm::Winograd,
+        ConvAlgorithmSelector::Fft => Algorithm::Fft,
+    };
+    Ok(ConvProto {
+        pool_spec: Some(pool_spec_to_pb(&conv.pool_spec)),
+        kernel_format: kernel_format as i32,
+        kernel: Some(tensor_to_pb(&conv.kernel)?),
+        group: conv.group as u64,
+        bias: conv.bias.as_ref().map(|b| tensor_to_pb(b)).transpose()?,
+        q_params: conv.q_params.as_ref().map(q_params_to_pb).transpose()?,
+        algorithm: algorithm as i32,
+    })
+}
+
+fn conv_from_pb(conv: &ConvProto) -> TractResult<ConvUnary> {
+    use crate::pb::conv_proto::{Algorithm, KernelFormat as KF};
+    let kernel_fmt = match KF::from_i32(conv.kernel_format) {
+        Some(KF::Oihw) => KernelFormat::OIHW,
+        Some(KF::Hwio) => KernelFormat::HWIO,
+        None => bail!("Unknown kernel format {}", conv.kernel_format),
+    };
+    let algorithm = match Algorithm::from_i32(conv.algorithm) {
+        Some(Algorithm::Auto) => ConvAlgorithmSelector::Auto,
+        Some(Algorithm::Gemm) => ConvAlgorithmSelector::Gemm,
+        Some(Algorithm::Winograd) => ConvAlgorithmSelector::Winograd,
+        Some(Algorithm::Fft) => ConvAlgorithmSelector::Fft,
+        None => bail!("Unknown convolution algorithm {}", conv.algorithm),
+    };
+    Ok(ConvUnary {
+        pool_spec: pool_spec_from_pb(conv.pool_spec.as_ref())?,
+        kernel_fmt,
+        kernel: tensor_from_pb(conv.kernel.as_ref())?,
+        group: conv.group as usize,
+        bias: conv.bias.as_ref().map(|b| tensor_from_pb(Some(b))).transpose()?,
+        q_params: conv.q_params.as_ref().map(q_params_from_pb).transpose()?,
+        algorithm,
+    })
+}
+
+fn reducer_to_pb(reducer: Reducer) -> reduce_proto::Reducer {
+    use crate::pb::reduce_proto::Reducer as R;
+    match reducer {
+        Reducer::L1 => R::L1,
+        Reducer::L2 => R::L2,
+        Reducer::LogSum => R::LogSum,
+        Reducer::LogSumExp => R::LogSumExp,
+        Reducer::Max => R::Max,
+        Reducer::Mean => R::Mean,
+        Reducer::Min => R::Min,
+        Reducer::Prod => R::Prod,
+        Reducer::Sum => R::Sum,
+        Reducer::SumSquare => R::SumSquare,
+    }
+}
+
+fn reducer_from_pb(reducer: i32) -> TractResult<Reducer> {
+    use crate::pb::reduce_proto::Reducer as R;
+    Ok(match R::from_i32(reducer) {
+        Some(R::L1) => Reducer::L1,
+        Some(R::L2) => Reducer::L2,
+        Some(R::LogSum) => Reducer::LogSum,
+        Some(R::LogSumExp) => Reducer::LogSumExp,
+        Some(R::Max) => Reducer::Max,
+        Some(R::Mean) => Reducer::Mean,
+        Some(R::Min) => Reducer::Min,
+        Some(R::Prod) => Reducer::Prod,
+        Some(R::Sum) => Reducer::Sum,
+        Some(R::SumSquare) => Reducer::SumSquare,
+        None => bail!("Unknown reducer {}", reducer),
+    })
+}
+
+fn dim_to_u64(d: &TDim) -> TractResult<u64> {
+    match d.as_const() {
+        Some(d) => Ok(d as u64),
+        None => bail!("Symbolic dimension {} can not be serialized", d),
+    }
+}
+
+fn scan_input_to_pb(input: &InputMapping<TDim>) -> TractResult<ScanInputProto> {
+    use scan_input_proto::Kind;
+    let mut proto = ScanInputProto::default();
+    match input {
+        InputMapping::Full { slot } => {
+            proto.kind = Kind::Full as i32;
+            proto.slot = *slot as u64;
+        }
+        InputMapping::State { initializer: StateInitializer::FromInput(slot) } => {
+            proto.kind = Kind::State as i32;
+            proto.slot = *slot as u64;
+        }
+        InputMapping::State { initializer: StateInitializer::Value(value) } => {
+            proto.kind = Kind::State as i32;
+            proto.value = Some(tensor_to_pb(value)?);
+        }
+        InputMapping::Scan { slot, axis, chunk, reverse } => {
+            proto.kind = Kind::Scan as i32;
+            proto.slot = *slot as u64;
+            proto.axis = *axis as u64;
+            proto.chunk = dim_to_u64(chunk)?;
+            proto.reverse = *reverse;
+        }
+    }
+    Ok(proto)
+}
+
+fn scan_input_from_pb(proto: &ScanInputProto) -> TractResult<InputMapping<TDim>> {
+    use scan_input_proto::Kind;
+    let slot = proto.slot as usize;
+    Ok(match Kind::from_i32(proto.kind) {
+        Some(Kind::Full) => InputMapping::Full { slot },
+        Some(Kind::State) => {
+            let initializer = match proto.value.as_ref() {
+                Some(value) => StateInitializer::Value(tensor_from_pb(Some(value))?),
+                None => StateInitializer::FromInput(slot),
+            };
+            InputMapping::State { initializer }
+        }
+        Some(Kind::Scan) => InputMapping::Scan {
+            slot,
+            axis: proto.axis as usize,
+            chunk: TDim::from(proto.chunk as usize),
+            reverse: proto.reverse,
+        },
+        None => bail!("Unknown scan input kind {}", proto.kind),
+    })
+}
+
+fn scan_output_to_pb(output: &OutputMapping<TDim, TDim>) -> TractResult<ScanOutputProto> {
+    Ok(ScanOutputProto {
+        has_full_slot: output.full_slot.is_some(),
+        full_slot: output.full_slot.unwrap_or(0) as u64,
+        axis: output.axis as u64,
+        chunk: dim_to_u64(&output.chunk)?,
+        has_full_dim_hint: output.full_dim_hint.is_some(),
+        full_dim_hint: output.full_dim_hint.as_ref().map(dim_to_u64).transpose()?.unwrap_or(0),
+        has_last_value_slot: output.last_value_slot.is_some(),
+        last_value_slot: output.last_value_slot.unwrap_or(0) as u64,
+        state: output.state,
+    })
+}
+
+fn scan_output_from_pb(proto: &ScanOutputProto) -> OutputMapping<TDim, TDim> {
+    OutputMapping::new(
+        if proto.has_full_slot { Some(proto.full_slot as usize) } else { None },
+        proto.axis as usize,
+        TDim::from(proto.chunk as usize),
+        if proto.has_full_dim_hint { Some(TDim::from(proto.full_dim_hint as usize)) } else { None },
+        if proto.has_last_value_slot { Some(proto.last_value_slot as usize) } else { None },
+        proto.state,
+    )
+}
+
+/// Serialize an operator, if it is one of the supported ones.
+pub fn op_to_pb(op: &dyn TypedOp) -> TractResult<OpProto> {
+    let op = op.as_op();
+    let proto = if op.downcast_ref::<ops::source::TypedSource>().is_some() {
+        OpEnum::Source(SourceProto {})
+    } else if let Some(op) = op.downcast_ref::<ops::konst::Const>() {
+        OpEnum::Konst(ConstProto { value: Some(tensor_to_pb(&op.value)?) })
+    } else if let Some(op) = op.downcast_ref::<UnaryOp>() {
+        OpEnum::Unary(UnaryProto {
+            mini_op: op.mini_op.name().to_string(),
+            a: Some(tensor_to_pb(&op.a)?),
+            inplace: op.inplace,
+        })
+    } else if let Some(op) = op.downcast_ref::<TypedBinOp>() {
+        OpEnum::Binary(BinaryProto { mini_op: op.0.name().to_string() })
+    } else if let Some(op) = op.downcast_ref::<MergeOp>() {
+        OpEnum::Merge(BinaryProto { mini_op: op.0.name().to_string() })
+    } else if let Some(op) = op.downcast_ref::<MergeOpUnicast>() {
+        OpEnum::MergeUnicast(BinaryProto { mini_op: op.0.name().to_string() })
+    } else if let Some(op) = op.downcast_ref::<ElementWiseOp>() {
+        OpEnum::ElementWise(element_wise_to_pb(&*op.0)?)
+    } else if let Some(op) = op.downcast_ref::<ConvUnary>() {
+        OpEnum::Conv(conv_to_pb(op)?)
+    } else if let Some(op) = op.downcast_ref::<MaxPool>() {
+        OpEnum::MaxPool(MaxPoolProto {
+            pool_spec: Some(pool_spec_to_pb(&op.pool_spec)),
+            with_index_outputs: op.with_index_outputs.is_some(),
+            index_datum_type: op.with_index_outputs.map(datum_type_to_pb).transpose()?.unwrap_or(0),
+        })
+    } else if let Some(op) = op.downcast_ref::<AvgPool>() {
+        OpEnum::AvgPool(AvgPoolProto {
+            pool_spec: Some(pool_spec_to_pb(&op.pool_spec)),
+            count_include_pad: op.count_include_pad,
+        })
+    } else if op.downcast_ref::<GlobalAvgPool>().is_some() {
+        OpEnum::GlobalPool(GlobalPoolProto { reducer: global_pool_proto::Reducer::Avg as i32 })
+    } else if op.downcast_ref::<GlobalMaxPool>().is_some() {
+        OpEnum::GlobalPool(GlobalPoolProto { reducer: global_pool_proto::Reducer::Max as i32 })
+    } else if let Some(op) = op.downcast_ref::<TypedReduce>() {
+        OpEnum::Reduce(ReduceProto {
+            reducer: reducer_to_pb(op.reducer) as i32,
+            axes: usizes_to_pb(&op.axes),
+        })
+    } else if let Some(op) = op.downcast_ref::<ops::matmul::MatMulUnary>() {
+        OpEnum::MatMul(MatMulProto {
+            a: Some(tensor_to_pb(&op.a)?),
+            a_trans: op.a_trans,
+            b_trans: op.b_trans,
+            c_trans: op.c_trans,
+            q_params: op.q_params.as_ref().map(q_params_to_pb).transpose()?,
+        })
+    } else if let Some(op) = op.downcast_ref::<ops::array::AddDims>() {
+        OpEnum::AddDims(AxesProto { axes: usizes_to_pb(&op.axes) })
+    } else if let Some(op) = op.downcast_ref::<ops::array::RmDims>() {
+        OpEnum::RmDims(AxesProto { axes: usizes_to_pb(&op.axes) })
+    } else if let Some(op) = op.downcast_ref::<ops::array::PermuteAxes>() {
+        OpEnum::PermuteAxes(AxesProto {
+            axes: op.axes.as_ref().map(|a| usizes_to_pb(a)).unwrap_or_default(),
+        })
+    } else if let Some(op) = op.downcast_ref::<ops::array::FiniteReshape>() {
+        OpEnum::Reshape(ShapeProto { shape: usizes_to_pb(&op.shape) })
+    } else if let Some(op) = op.downcast_ref::<ops::array::Slice<usize>>() {
+        OpEnum::Slice(SliceProto {
+            axis: op.axis as u64,
+            start: op.start as u64,
+            end: op.end as u64,
+            dims: false,
+        })
+    } else if let Some(op) = op.downcast_ref::<ops::array::Slice<TDim>>() {
+        OpEnum::Slice(SliceProto {
+            axis: op.axis as u64,
+            start: dim_to_u64(&op.start)?,
+            end: dim_to_u64(&op.end)?,
+            dims: true,
+        })
+    } else if let Some(op) = op.downcast_ref::<If>() {
+        OpEnum::If(IfProto {
+            then_body: Some(model_to_proto(&op.then_body).chain_err(|| "Serializing then body")?),
+            else_body: Some(model_to_proto(&op.else_body).chain_err(|| "Serializing else body")?),
+        })
+    } else if let Some(op) = op.downcast_ref::<Loop>() {
+        OpEnum::Loop(LoopProto {
+            body: Some(model_to_proto(&op.body).chain_err(|| "Serializing loop body")?),
+            has_trip_count: op.has_trip_count,
+            has_cond: op.has_cond,
+        })
+    } else if let Some(op) = op.downcast_ref::<TypedScan>() {
+        OpEnum::Scan(ScanProto {
+            body: Some(model_to_proto(&op.body).chain_err(|| "Serializing scan body")?),
+            skip: op.skip as u64,
+            has_seq_length_input_slot: op.seq_length_input_slot.is_some(),
+            seq_length_input_slot: op.seq_length_input_slot.unwrap_or(0) as u64,
+            input_mapping: op
+                .input_mapping
+                .iter()
+                .map(scan_input_to_pb)
+                .collect::<TractResult<_>>()?,
+            output_mapping: op
+                .output_mapping
+                .iter()
+                .map(scan_output_to_pb)
+                .collect::<TractResult<_>>()?,
+        })
+    } else if let Some(op) = phy::op_to_pb(op)? {
+        op
+    } else {
+        bail!("No serialization for {} operators", op.name())
+    };
+    Ok(OpProto { op: Some(proto) })
+}
+
+/// Rebuild an operator. `output_facts` are the facts of the node outputs.
+pub fn op_from_pb(op: &OpProto, output_facts: &[TypedFact]) -> TractResult<Box<dyn TypedOp>> {
+    let op = op.op.as_ref().ok_or("Missing operator")?;
+    Ok(match op {
+        OpEnum::Source(_) => {
+            let fact = output_facts.get(0).ok_or("Source without output fact")?;
+            Box::new(ops::source::TypedSource::new(fact.clone()))
+        }
+        OpEnum::Konst(k) => Box::new(ops::konst::Const::new(tensor_from_pb(k.value.as_ref())?)),
+        OpEnum::Unary(u) => {
+            let mut op = UnaryOp::new(bin_mini_op(&u.mini_op)?, tensor_from_pb(u.a.as_ref())?);
+            op.inplace = u.inplace;
+            Box::new(op)
+        }
+        OpEnum::Binary(b) => Box::new(TypedBinOp(bin_mini_op(&b.mini_op)?)),
+        OpEnum::Merge(b) => Box::new(MergeOp(bin_mini_op(&b.mini_op)?)),
+        OpEnum::ElementWise(e) => Box::new(ElementWiseOp(element_wise_from_pb(e)?)),
+        OpEnum::Conv(c) => Box::new(conv_from_pb(c)?),
+        OpEnum::MaxPool(p) => {
+            let with_index_outputs = if p.with_index_outputs {
+                Some(datum_type_from_pb(p.index_datum_type)?)
+            } else {
+                None
+            };
+            Box::new(MaxPool::new(pool_spec_from_pb(p.pool_spec.as_ref())?, with_index_outputs))
+        }
+        OpEnum::AvgPool(p) => {
+            Box::new(AvgPool::new(pool_spec_from_pb(p.pool_spec.as_ref())?, p.count_include_pad))
+        }
+        OpEnum::GlobalPool(p) => match global_pool_proto::Reducer::from_i32(p.reducer) {
+            Some(global_pool_proto::Reducer::Avg) => Box::new(GlobalAvgPool::default()),
+            Some(global_pool_proto::Reducer::Max) => Box::new(GlobalMaxPool::default()),
+            None => bail!("Unknown global pool reducer {}", p.reducer),
+        },
+        OpEnum::Reduce(r) => {
+            Box::new(TypedReduce::new(usizes_from_pb(&r.axes), reducer_from_pb(r.reducer)?))
+        }
+        OpEnum::MatMul(m) => Box::new(ops::matmul::MatMulUnary::new(
+            tensor_from_pb(m.a.as_ref())?,
+            m.a_trans,
+            m.b_trans,
+            m.c_trans,
+            m.q_params.as_ref().map(q_params_from_pb).transpose()?,
+        )),
+        OpEnum::AddDims(a) => Box::new(ops::array::AddDims::new(usizes_from_pb(&a.axes).to_vec())),
+        OpEnum::RmDims(a) => Box::new(ops::array::RmDims::new(usizes_from_pb(&a.axes).to_vec())),
+        OpEnum::PermuteAxes(a) => {
+            let axes = usizes_from_pb(&a.axes).to_vec();
+            Box::new(ops::array::PermuteAxes::new(if axes.is_empty() { None } else { Some(axes) }))
+        }
+        OpEnum::Reshape(s) => Box::new(ops::array::FiniteReshape::new(usizes_from_pb(&s.shape))),
+        OpEnum::Slice(s) => {
+            let (axis, start, end) = (s.axis as usize, s.start as usize, s.end as usize);
+            if s.dims {
+                Box::new(ops::array::Slice::new(axis, TDim::from(start), TDim::from(end)))
+            } else {
+                Box::new(ops::array::Slice::new(axis, start, end))
+            }
+        }
+        OpEnum::If(i) => Box::new(If::new(
+            body_from_pb(i.then_body.as_ref()).chain_err(|| "Loading then body")?,
+            body_from_pb(i.else_body.as_ref()).chain_err(|| "Loading else body")?,
+        )),
+        OpEnum::Loop(l) => Box::new(Loop::new(
+            body_from_pb(l.body.as_ref()).chain_err(|| "Loading loop body")?,
+            l.has_trip_count,
+            l.has_cond,
+        )),
+        OpEnum::Scan(s) => {
+            let mut op = TypedScan::new(
+                body_from_pb(s.body.as_ref()).chain_err(|| "Loading scan body")?,
+                s.input_mapping.iter().map(scan_input_from_pb).collect::<TractResult<_>>()?,
+                s.output_mapping.iter().map(scan_output_from_pb).collect(),
+                if s.has_seq_length_input_slot {
+                    Some(s.seq_length_input_slot as usize)
+                } else {
+                    None
+                },
+            )?;
+            op.skip = s.skip as usize;
+            Box::new(op)
+        }
+        OpEnum::Im2col(op) => Box::new(phy::im2col_from_pb(op)?),
+        OpEnum::MatMatMulPackB(op) => Box::new(phy::pack_b_op_from_pb(op)?),
+        OpEnum::MatMatMul(op) => Box::new(phy::mat_mat_mul_from_pb(op)?),
+        OpEnum::MergeUnicast(b) => Box::new(MergeOpUnicast(bin_mini_op(&b.mini_op)?)),
+        OpEnum::MaxPoolFixed(op) => Box::new(phy::max_pool_from_pb(op)?),
+        OpEnum::AvgPoolFixed(op) => Box::new(phy::avg_pool_from_pb(op)?),
+        OpEnum::DepthWise(op) => Box::new(phy::depth_wise_from_pb(op)?),
+    })
+}
+
+fn body_from_pb(body: Option<&ModelProto>) -> TractResult<TypedModel> {
+    model_for_proto(body.ok_or("Missing body")?)
+}
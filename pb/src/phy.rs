@@ -0,0 +1,397 @@
+//! Serialization of the operators of optimized models.
+//!
+//! This covers the float operators codegen makes from the ones in `ops.rs`,
+//! as `tract_core::ops::physical` lowers them.
+use crate::ops::{
+    data_format_from_pb, data_format_to_pb, padding_from_pb, padding_to_pb, usizes_from_pb,
+    usizes_to_pb,
+};
+use crate::pb::op_proto::Op as OpEnum;
+use crate::pb::*;
+use crate::tensor::*;
+use std::convert::TryFrom;
+
+use tract_core::internal::*;
+use tract_core::ndarray::ArrayD;
+use tract_core::ops::cnn::conv::{DepthWise, Im2Col};
+use tract_core::ops::cnn::{AvgPoolFixed, MaxPoolFixed, Patch, PatchSpec};
+use tract_core::ops::matmul::phy::MatMatMulPackB;
+use tract_core::ops::nn::DataShape;
+use tract_core::ops::physical::*;
+use tract_linalg::frame::PackB;
+use tract_linalg::mmm::FusedSpec;
+
+fn isizes_to_pb(it: &[isize]) -> Vec<i64> {
+    it.iter().map(|&i| i as i64).collect()
+}
+
+fn isizes_from_pb(it: &[i64]) -> TVec<isize> {
+    it.iter().map(|&i| i as isize).collect()
+}
+
+fn patch_to_pb(patch: &Patch) -> PatchSpecProto {
+    let spec = &patch.spec;
+    PatchSpecProto {
+        input_shape: usizes_to_pb(&spec.input_shape),
+        input_inner_stride: spec.input_inner_stride as u64,
+        output_inner_stride: spec.output_inner_stride as u64,
+        kernel_shape: usizes_to_pb(&spec.kernel_shape),
+        strides: usizes_to_pb(&spec.strides),
+        dilations: usizes_to_pb(&spec.dilations),
+        padding: Some(padding_to_pb(&spec.padding)),
+    }
+}
+
+fn patch_from_pb(spec: Option<&PatchSpecProto>) -> TractResult<Patch> {
+    let spec = spec.ok_or("Missing patch")?;
+    Ok(PatchSpec {
+        input_shape: usizes_from_pb(&spec.input_shape),
+        input_inner_stride: spec.input_inner_stride as usize,
+        output_inner_stride: spec.output_inner_stride as usize,
+        kernel_shape: usizes_from_pb(&spec.kernel_shape),
+        strides: usizes_from_pb(&spec.strides),
+        dilations: usizes_from_pb(&spec.dilations),
+        padding: padding_from_pb(spec.padding.as_ref())?,
+    }
+    .into_patch())
+}
+
+fn data_shape_to_pb(shape: &DataShape) -> DataShapeProto {
+    DataShapeProto { data_format: data_format_to_pb(shape.fmt), shape: usizes_to_pb(&shape.shape) }
+}
+
+fn data_shape_from_pb(shape: Option<&DataShapeProto>) -> TractResult<DataShape> {
+    let shape = shape.ok_or("Missing data shape")?;
+    Ok(data_format_from_pb(shape.data_format)?.shape(usizes_from_pb(&shape.shape)))
+}
+
+fn pack_b_to_pb(pack: &PackB<f32>) -> PackBProto {
+    PackBProto {
+        k: pack.k() as u64,
+        n: pack.n() as u64,
+        nr: pack.nr() as u64,
+        alignment: pack.alignment() as u64,
+    }
+}
+
+fn pack_b_from_pb(pack: Option<&PackBProto>) -> TractResult<PackB<f32>> {
+    let pack = pack.ok_or("Missing B packing")?;
+    Ok(PackB::new(pack.k as usize, pack.n as usize, pack.nr as usize, pack.alignment as usize))
+}
+
+fn store_to_pb(store: &StoreParts) -> MatrixStoreProto {
+    use crate::pb::matrix_store_proto::Kind;
+    let mut proto = MatrixStoreProto::default();
+    match store {
+        StoreParts::Packed => proto.kind = Kind::Packed as i32,
+        StoreParts::Strides { row_stride, col_stride } => {
+            proto.kind = Kind::Strides as i32;
+            proto.row_stride = *row_stride as i64;
+            proto.col_stride = *col_stride as i64;
+        }
+        StoreParts::OffsetsAndPtrs { row_offsets, col_offsets } => {
+            proto.kind = Kind::OffsetsAndPtrs as i32;
+            proto.row_offsets = isizes_to_pb(row_offsets);
+            proto.col_offsets = isizes_to_pb(col_offsets);
+        }
+        StoreParts::VecStride { stride } => {
+            proto.kind = Kind::VecStride as i32;
+            proto.row_stride = *stride as i64;
+        }
+    }
+    proto
+}
+
+fn store_from_pb(proto: Option<&MatrixStoreProto>) -> TractResult<StoreParts> {
+    use crate::pb::matrix_store_proto::Kind;
+    let proto = proto.ok_or("Missing matrix storage")?;
+    Ok(match Kind::from_i32(proto.kind) {
+        Some(Kind::Packed) => StoreParts::Packed,
+        Some(Kind::Strides) => StoreParts::Strides {
+            row_stride: proto.row_stride as isize,
+            col_stride: proto.col_stride as isize,
+        },
+        Some(Kind::OffsetsAndPtrs) => StoreParts::OffsetsAndPtrs {
+            row_offsets: isizes_from_pb(&proto.row_offsets),
+            col_offsets: isizes_from_pb(&proto.col_offsets),
+        },
+        Some(Kind::VecStride) => StoreParts::VecStride { stride: proto.row_stride as isize },
+        None => bail!("Unknown matrix storage {}", proto.kind),
+    })
+}
+
+fn mmm_to_pb(mmm: &MmmParts) -> MmmProto {
+    MmmProto {
+        m: mmm.m as u64,
+        k: mmm.k as u64,
+        n: mmm.n as u64,
+        mr: mmm.mr as u64,
+        nr: mmm.nr as u64,
+        a_alignment: mmm.a_alignment as u64,
+        b_alignment: mmm.b_alignment as u64,
+        b_storage: Some(store_to_pb(&mmm.b_storage)),
+        c_storage: Some(store_to_pb(&mmm.c_storage)),
+    }
+}
+
+fn mmm_from_pb(proto: Option<&MmmProto>) -> TractResult<MmmParts> {
+    let proto = proto.ok_or("Missing matrix product kernel")?;
+    Ok(MmmParts {
+        m: proto.m as usize,
+        k: proto.k as usize,
+        n: proto.n as usize,
+        mr: proto.mr as usize,
+        nr: proto.nr as usize,
+        a_alignment: proto.a_alignment as usize,
+        b_alignment: proto.b_alignment as usize,
+        b_storage: store_from_pb(proto.b_storage.as_ref())?,
+        c_storage: store_from_pb(proto.c_storage.as_ref())?,
+    })
+}
+
+fn fused_to_pb(spec: &FusedSpec<f32>) -> FusedProto {
+    use crate::pb::fused_proto::Kind;
+    let mut proto = FusedProto::default();
+    match spec {
+        FusedSpec::Min(x) => {
+            proto.kind = Kind::Min as i32;
+            proto.scalar = *x;
+        }
+        FusedSpec::Max(x) => {
+            proto.kind = Kind::Max as i32;
+            proto.scalar = *x;
+        }
+        FusedSpec::AddC => proto.kind = Kind::AddC as i32,
+        FusedSpec::PerRowMul(rows) => {
+            proto.kind = Kind::PerRowMul as i32;
+            proto.rows = rows.clone();
+        }
+        FusedSpec::PerRowAdd(rows) => {
+            proto.kind = Kind::PerRowAdd as i32;
+            proto.rows = rows.clone();
+        }
+        FusedSpec::PerColMul(cols) => {
+            proto.kind = Kind::PerColMul as i32;
+            proto.cols = cols.clone();
+        }
+        FusedSpec::PerColAdd(cols) => {
+            proto.kind = Kind::PerColAdd as i32;
+            proto.cols = cols.clone();
+        }
+        FusedSpec::AddRowColProducts(rows, cols) => {
+            proto.kind = Kind::AddRowColProducts as i32;
+            proto.rows = rows.clone();
+            proto.cols = cols.clone();
+        }
+        FusedSpec::ScalarMul(x) => {
+            proto.kind = Kind::ScalarMul as i32;
+            proto.scalar = *x;
+        }
+        FusedSpec::ScalarAdd(x) => {
+            proto.kind = Kind::ScalarAdd as i32;
+            proto.scalar = *x;
+        }
+        FusedSpec::QTowardsEven(x, shift) => {
+            proto.kind = Kind::QTowardsEven as i32;
+            proto.scalar = *x;
+            proto.shift = *shift as u64;
+        }
+        FusedSpec::QTowardsPlusInf(x, shift) => {
+            proto.kind = Kind::QTowardsPlusInf as i32;
+            proto.scalar = *x;
+            proto.shift = *shift as u64;
+        }
+    }
+    proto
+}
+
+fn fused_from_pb(proto: &FusedProto) -> TractResult<FusedSpec<f32>> {
+    use crate::pb::fused_proto::Kind;
+    let (rows, cols) = (|| proto.rows.clone(), || proto.cols.clone());
+    Ok(match Kind::from_i32(proto.kind) {
+        Some(Kind::Min) => FusedSpec::Min(proto.scalar),
+        Some(Kind::Max) => FusedSpec::Max(proto.scalar),
+        Some(Kind::AddC) => FusedSpec::AddC,
+        Some(Kind::PerRowMul) => FusedSpec::PerRowMul(rows()),
+        Some(Kind::PerRowAdd) => FusedSpec::PerRowAdd(rows()),
+        Some(Kind::PerColMul) => FusedSpec::PerColMul(cols()),
+        Some(Kind::PerColAdd) => FusedSpec::PerColAdd(cols()),
+        Some(Kind::AddRowColProducts) => FusedSpec::AddRowColProducts(rows(), cols()),
+        Some(Kind::ScalarMul) => FusedSpec::ScalarMul(proto.scalar),
+        Some(Kind::ScalarAdd) => FusedSpec::ScalarAdd(proto.scalar),
+        Some(Kind::QTowardsEven) => FusedSpec::QTowardsEven(proto.scalar, proto.shift as usize),
+        Some(Kind::QTowardsPlusInf) => {
+            FusedSpec::QTowardsPlusInf(proto.scalar, proto.shift as usize)
+        }
+        None => bail!("Unknown fused operation {}", proto.kind),
+    })
+}
+
+fn mat_mat_mul_to_pb(op: &FloatMatMatMul) -> TractResult<MatMatMulProto> {
+    let op = MatMatMulParts::from_op(op)?;
+    let mut proto = MatMatMulProto {
+        mmm: Some(mmm_to_pb(&op.mmm)),
+        c_trans: op.c_trans,
+        bc_c_shape: usizes_to_pb(&op.bc_c_shape),
+        c_fact: Some(TypedFactProto::try_from(&op.c_fact)?),
+        packed_as_shape: usizes_to_pb(op.packed_as.shape()),
+        packed_as: op.packed_as.iter().map(|pa| tensor_to_pb(pa)).collect::<TractResult<_>>()?,
+        ..MatMatMulProto::default()
+    };
+    if let Some((dims, strides)) = &op.c_prefix_dim_and_stride {
+        proto.has_c_prefix = true;
+        proto.c_prefix_dims = usizes_to_pb(dims);
+        proto.c_prefix_strides = isizes_to_pb(strides);
+    }
+    if let Some(fused_ops) = &op.fused_ops {
+        proto.has_fused_ops = true;
+        proto.fused_ops_shape = usizes_to_pb(fused_ops.shape());
+        proto.fused_ops = fused_ops
+            .iter()
+            .map(|ops| FusedOpsProto { ops: ops.iter().map(fused_to_pb).collect() })
+            .collect();
+    }
+    Ok(proto)
+}
+
+pub fn mat_mat_mul_from_pb(proto: &MatMatMulProto) -> TractResult<FloatMatMatMul> {
+    let packed_as = proto
+        .packed_as
+        .iter()
+        .map(|pa| Ok(Tensor::try_from(pa)?.into_arc_tensor()))
+        .collect::<TractResult<Vec<_>>>()?;
+    let packed_as = ArrayD::from_shape_vec(&*usizes_from_pb(&proto.packed_as_shape), packed_as)?;
+    let fused_ops = if proto.has_fused_ops {
+        let ops = proto
+            .fused_ops
+            .iter()
+            .map(|ops| ops.ops.iter().map(fused_from_pb).collect::<TractResult<_>>())
+            .collect::<TractResult<_>>()?;
+        Some(ArrayD::from_shape_vec(&*usizes_from_pb(&proto.fused_ops_shape), ops)?)
+    } else {
+        None
+    };
+    let c_prefix_dim_and_stride = if proto.has_c_prefix {
+        Some((usizes_from_pb(&proto.c_prefix_dims), isizes_from_pb(&proto.c_prefix_strides)))
+    } else {
+        None
+    };
+    let c_fact = proto.c_fact.as_ref().ok_or("Missing C fact")?;
+    MatMatMulParts {
+        mmm: mmm_from_pb(proto.mmm.as_ref())?,
+        c_trans: proto.c_trans,
+        bc_c_shape: usizes_from_pb(&proto.bc_c_shape),
+        c_fact: TypedFact::try_from(c_fact)?,
+        c_prefix_dim_and_stride,
+        packed_as,
+        fused_ops,
+    }
+    .into_op()
+}
+
+pub fn im2col_from_pb(proto: &Im2ColProto) -> TractResult<Im2Col<f32>> {
+    Ok(Im2Col::new(
+        patch_from_pb(proto.patch.as_ref())?,
+        data_shape_from_pb(proto.input_shape.as_ref())?,
+        proto.m as usize,
+        proto.k as usize,
+        proto.n as usize,
+        proto.group as usize,
+        proto.ci_per_group as usize,
+        pack_b_from_pb(proto.b_pack.as_ref())?,
+        proto.pad_value,
+    ))
+}
+
+pub fn pack_b_op_from_pb(proto: &MatMatMulPackBProto) -> TractResult<MatMatMulPackB<f32>> {
+    Ok(MatMatMulPackB::new(
+        pack_b_from_pb(proto.pack_b.as_ref())?,
+        proto.row_stride as isize,
+        proto.col_stride as isize,
+        usizes_from_pb(&proto.output_shape),
+    ))
+}
+
+pub fn max_pool_from_pb(proto: &MaxPoolFixedProto) -> TractResult<MaxPoolFixed<f32>> {
+    let with_index_outputs = if proto.with_index_outputs {
+        Some(datum_type_from_pb(proto.index_datum_type)?)
+    } else {
+        None
+    };
+    Ok(MaxPoolFixed::new(
+        patch_from_pb(proto.patch.as_ref())?,
+        data_shape_from_pb(proto.input_shape.as_ref())?,
+        data_shape_from_pb(proto.output_shape.as_ref())?,
+        with_index_outputs,
+    ))
+}
+
+pub fn avg_pool_from_pb(proto: &AvgPoolFixedProto) -> TractResult<AvgPoolFixed<f32>> {
+    Ok(AvgPoolFixed::new(
+        patch_from_pb(proto.patch.as_ref())?,
+        data_shape_from_pb(proto.input_shape.as_ref())?,
+        data_shape_from_pb(proto.output_shape.as_ref())?,
+        proto.count_include_pad,
+    ))
+}
+
+pub fn depth_wise_from_pb(proto: &DepthWiseProto) -> TractResult<DepthWise<f32>> {
+    Ok(DepthWise::new(
+        patch_from_pb(proto.patch.as_ref())?,
+        data_shape_from_pb(proto.input_shape.as_ref())?,
+        data_shape_from_pb(proto.output_shape.as_ref())?,
+        tensor_from_pb(proto.kernel.as_ref())?.into_tensor().into_array::<f32>()?,
+        if proto.has_bias { Some(proto.bias.clone()) } else { None },
+    ))
+}
+
+/// Serialize an operator of an optimized model, if it is one of the
+/// supported ones.
+pub fn op_to_pb(op: &dyn Op) -> TractResult<Option<OpEnum>> {
+    let op = if let Some(op) = PhysicalOp::from_op(op) { op } else { return Ok(None) };
+    Ok(Some(match op {
+        PhysicalOp::Im2Col(op) => OpEnum::Im2col(Im2ColProto {
+            patch: Some(patch_to_pb(&op.patch)),
+            input_shape: Some(data_shape_to_pb(&op.input_shape)),
+            m: op.m as u64,
+            k: op.k as u64,
+            n: op.n as u64,
+            group: op.group as u64,
+            ci_per_group: op.ci_per_group as u64,
+            b_pack: Some(pack_b_to_pb(&op.b_pack)),
+            pad_value: op.pad_value(),
+        }),
+        PhysicalOp::MatMatMulPackB(op) => OpEnum::MatMatMulPackB(MatMatMulPackBProto {
+            pack_b: Some(pack_b_to_pb(op.pack_b())),
+            row_stride: op.strides().0 as i64,
+            col_stride: op.strides().1 as i64,
+            output_shape: usizes_to_pb(op.output_shape()),
+        }),
+        PhysicalOp::MatMatMul(op) => OpEnum::MatMatMul(mat_mat_mul_to_pb(op)?),
+        PhysicalOp::MaxPoolFixed(op) => OpEnum::MaxPoolFixed(MaxPoolFixedProto {
+            patch: Some(patch_to_pb(op.patch())),
+            input_shape: Some(data_shape_to_pb(op.input_shape())),
+            output_shape: Some(data_shape_to_pb(op.output_shape())),
+            with_index_outputs: op.with_index_outputs().is_some(),
+            index_datum_type: op
+                .with_index_outputs()
+                .map(datum_type_to_pb)
+                .transpose()?
+                .unwrap_or(0),
+        }),
+        PhysicalOp::AvgPoolFixed(op) => OpEnum::AvgPoolFixed(AvgPoolFixedProto {
+            patch: Some(patch_to_pb(op.patch())),
+            input_shape: Some(data_shape_to_pb(op.input_shape())),
+            output_shape: Some(data_shape_to_pb(op.output_shape())),
+            count_include_pad: op.count_include_pad(),
+        }),
+        PhysicalOp::DepthWise(op) => OpEnum::DepthWise(DepthWiseProto {
+            patch: Some(patch_to_pb(op.patch())),
+            input_shape: Some(data_shape_to_pb(op.input_shape())),
+            output_shape: Some(data_shape_to_pb(op.output_shape())),
+            kernel: Some(tensor_to_pb(&op.kernel_chw().clone().into_tensor())?),
+            has_bias: op.bias().is_some(),
+            bias: op.bias().map(|b| b.to_vec()).unwrap_or_default(),
+        }),
+    }))
+}
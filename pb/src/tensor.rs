@@ -0,0 +1,166 @@
+use crate::pb::*;
+use std::convert::{TryFrom, TryInto};
+use tract_core::internal::*;
+
+impl TryFrom<DatumTypeProto> for DatumType {
+    type Error = TractError;
+    fn try_from(t: DatumTypeProto) -> TractResult<DatumType> {
+        Ok(match t {
+            DatumTypeProto::Bool => DatumType::Bool,
+            DatumTypeProto::U8 => DatumType::U8,
+            DatumTypeProto::U16 => DatumType::U16,
+            DatumTypeProto::I8 => DatumType::I8,
+            DatumTypeProto::I16 => DatumType::I16,
+            DatumTypeProto::I32 => DatumType::I32,
+            DatumTypeProto::I64 => DatumType::I64,
+            DatumTypeProto::F16 => DatumType::F16,
+            DatumTypeProto::Bf16 => DatumType::BF16,
+            DatumTypeProto::F32 => DatumType::F32,
+            DatumTypeProto::F64 => DatumType::F64,
+            DatumTypeProto::Tdim => DatumType::TDim,
+            DatumTypeProto::String => DatumType::String,
+        })
+    }
+}
+
+impl TryFrom<DatumType> for DatumTypeProto {
+    type Error = TractError;
+    fn try_from(t: DatumType) -> TractResult<DatumTypeProto> {
+        Ok(match t {
+            DatumType::Bool => DatumTypeProto::Bool,
+            DatumType::U8 => DatumTypeProto::U8,
+            DatumType::U16 => DatumTypeProto::U16,
+            DatumType::I8 => DatumTypeProto::I8,
+            DatumType::I16 => DatumTypeProto::I16,
+            DatumType::I32 => DatumTypeProto::I32,
+            DatumType::I64 => DatumTypeProto::I64,
+            DatumType::F16 => DatumTypeProto::F16,
+            DatumType::BF16 => DatumTypeProto::Bf16,
+            DatumType::F32 => DatumTypeProto::F32,
+            DatumType::F64 => DatumTypeProto::F64,
+            DatumType::TDim => DatumTypeProto::Tdim,
+            DatumType::String => DatumTypeProto::String,
+            DatumType::Blob => bail!("Blob tensors can not be serialized"),
+            DatumType::I4 | DatumType::U4 => bail!("{:?} tensors can not be serialized", t),
+        })
+    }
+}
+
+pub fn datum_type_to_pb(dt: DatumType) -> TractResult<i32> {
+    Ok(DatumTypeProto::try_from(dt)? as i32)
+}
+
+pub fn datum_type_from_pb(dt: i32) -> TractResult<DatumType> {
+    match DatumTypeProto::from_i32(dt) {
+        Some(dt) => dt.try_into(),
+        None => bail!("Unknown datum type {}", dt),
+    }
+}
+
+fn shape_from_pb(shape: &[u64]) -> TVec<usize> {
+    shape.iter().map(|&d| d as usize).collect()
+}
+
+impl TryFrom<&Tensor> for TensorProto {
+    type Error = TractError;
+    fn try_from(t: &Tensor) -> TractResult<TensorProto> {
+        let mut proto = TensorProto {
+            datum_type: datum_type_to_pb(t.datum_type())?,
+            shape: t.shape().iter().map(|&d| d as u64).collect(),
+            ..TensorProto::default()
+        };
+        match t.datum_type() {
+            DatumType::String => proto.strings = t.as_slice::<String>()?.to_vec(),
+            DatumType::TDim => {
+                for d in t.as_slice::<TDim>()? {
+                    match d.as_const() {
+                        Some(d) => proto.dims.push(d as i64),
+                        None => bail!("Symbolic dimension {} can not be serialized", d),
+                    }
+                }
+            }
+            _ => {
+                if cfg!(target_endian = "big") {
+                    bail!("Tensor serialization assumes a little endian host")
+                }
+                proto.data = unsafe { t.as_bytes() }.to_vec();
+            }
+        }
+        Ok(proto)
+    }
+}
+
+impl TryFrom<&TensorProto> for Tensor {
+    type Error = TractError;
+    fn try_from(t: &TensorProto) -> TractResult<Tensor> {
+        let dt = datum_type_from_pb(t.datum_type)?;
+        let shape = shape_from_pb(&t.shape);
+        let len = shape.iter().product::<usize>();
+        match dt {
+            DatumType::String => {
+                Ok(tract_core::ndarray::ArrayD::from_shape_vec(&*shape, t.strings.clone())?.into())
+            }
+            DatumType::TDim => {
+                let dims = t.dims.iter().map(|&d| TDim::from(d)).collect();
+                Ok(tract_core::ndarray::ArrayD::<TDim>::from_shape_vec(&*shape, dims)?.into())
+            }
+            _ => {
+                if t.data.len() != len * dt.size_of() {
+                    bail!(
+                        "Expected {} bytes for a {:?} tensor of shape {:?}, found {}",
+                        len * dt.size_of(),
+                        dt,
+                        shape,
+                        t.data.len()
+                    )
+                }
+                if len == 0 {
+                    unsafe { Tensor::uninitialized_dt(dt, &shape) }
+                } else {
+                    unsafe { Tensor::from_raw_dt(dt, &shape, &t.data) }
+                }
+            }
+        }
+    }
+}
+
+pub fn tensor_to_pb(t: &Tensor) -> TractResult<TensorProto> {
+    t.try_into()
+}
+
+pub fn tensor_from_pb(t: Option<&TensorProto>) -> TractResult<Arc<Tensor>> {
+    match t {
+        Some(t) => Ok(Tensor::try_from(t)?.into_arc_tensor()),
+        None => bail!("Missing tensor"),
+    }
+}
+
+impl TryFrom<&TypedFact> for TypedFactProto {
+    type Error = TractError;
+    fn try_from(fact: &TypedFact) -> TractResult<TypedFactProto> {
+        let shape = if let Some(shape) = fact.shape.as_finite() {
+            shape.iter().map(|&d| d as u64).collect()
+        } else {
+            bail!("Symbolic shape {:?} can not be serialized", fact.shape)
+        };
+        Ok(TypedFactProto {
+            datum_type: datum_type_to_pb(fact.datum_type)?,
+            shape,
+            konst: fact.konst.as_ref().map(|k| tensor_to_pb(k)).transpose()?,
+        })
+    }
+}
+
+impl TryFrom<&TypedFactProto> for TypedFact {
+    type Error = TractError;
+    fn try_from(fact: &TypedFactProto) -> TractResult<TypedFact> {
+        let mut typed = TypedFact::dt_shape(
+            datum_type_from_pb(fact.datum_type)?,
+            &*shape_from_pb(&fact.shape),
+        )?;
+        if let Some(konst) = &fact.konst {
+            typed.konst = Some(Tensor::try_from(konst)?.into_arc_tensor());
+        }
+        Ok(typed)
+    }
+}
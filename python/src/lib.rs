@@ -0,0 +1,171 @@
+//! Python bindings for tract.
+//!
+//! Input arrays are copied once into tract tensors. Output arrays are
+//! read-only views on the tensors computed by tract, that they keep alive.
+
+use numpy::ndarray::{ArrayViewD, IxDyn};
+use numpy::{Element, PyArray, PyArrayDescrMethods, PyArrayDyn, PyArrayMethods};
+use numpy::{PyUntypedArray, PyUntypedArrayMethods};
+use pyo3::exceptions::{PyRuntimeError, PyTypeError, PyValueError};
+use pyo3::prelude::*;
+
+use tract_core::internal::*;
+
+fn to_py(e: TractError) -> PyErr {
+    let causes: Vec<String> = e.iter().map(|e| e.to_string()).collect();
+    PyRuntimeError::new_err(causes.join(": "))
+}
+
+macro_rules! dispatch_numpy {
+    ($f:ident($dt:expr)($($args:expr),*) or $err:expr) => {
+        match $dt {
+            DatumType::Bool => $f::<bool>($($args),*),
+            DatumType::U8 => $f::<u8>($($args),*),
+            DatumType::U16 => $f::<u16>($($args),*),
+            DatumType::I8 => $f::<i8>($($args),*),
+            DatumType::I16 => $f::<i16>($($args),*),
+            DatumType::I32 => $f::<i32>($($args),*),
+            DatumType::I64 => $f::<i64>($($args),*),
+            DatumType::F32 => $f::<f32>($($args),*),
+            DatumType::F64 => $f::<f64>($($args),*),
+            _ => $err,
+        }
+    };
+}
+
+const DATUM_TYPES: &[DatumType] = &[
+    DatumType::Bool,
+    DatumType::U8,
+    DatumType::U16,
+    DatumType::I8,
+    DatumType::I16,
+    DatumType::I32,
+    DatumType::I64,
+    DatumType::F32,
+    DatumType::F64,
+];
+
+fn dtype_matches<T: Element>(array: &Bound<PyUntypedArray>) -> bool {
+    array.dtype().is_equiv_to(&numpy::dtype_bound::<T>(array.py()))
+}
+
+fn to_tensor<T: Element + Datum + Copy>(array: &Bound<PyUntypedArray>) -> PyResult<Tensor> {
+    let array = array.downcast::<PyArrayDyn<T>>()?.readonly();
+    let view = array.as_array();
+    let mut tensor = unsafe { Tensor::uninitialized::<T>(view.shape()).map_err(to_py)? };
+    if view.len() > 0 {
+        let data = tensor.as_slice_mut::<T>().map_err(to_py)?;
+        // iterates in logical (row-major) order, whatever the strides
+        for (d, v) in data.iter_mut().zip(view.iter()) {
+            *d = *v;
+        }
+    }
+    Ok(tensor)
+}
+
+fn tensor_from_numpy(array: &Bound<PyUntypedArray>) -> PyResult<Tensor> {
+    for &dt in DATUM_TYPES {
+        let matches = dispatch_numpy!(dtype_matches(dt)(array) or false);
+        if matches {
+            return dispatch_numpy!(to_tensor(dt)(array) or unreachable!());
+        }
+    }
+    Err(PyTypeError::new_err(format!("Unsupported array dtype {}", array.dtype())))
+}
+
+/// Owner of an output tensor, kept alive by the numpy arrays viewing it.
+#[pyclass]
+struct TensorOwner(#[allow(dead_code)] Arc<Tensor>);
+
+fn to_numpy<'py, T: Element + Datum>(
+    py: Python<'py>,
+    tensor: Arc<Tensor>,
+) -> PyResult<Bound<'py, PyAny>> {
+    let ptr = if tensor.len() == 0 {
+        std::ptr::NonNull::<T>::dangling().as_ptr() as *const T
+    } else {
+        tensor.as_ptr::<T>().map_err(to_py)?
+    };
+    let view = unsafe { ArrayViewD::<T>::from_shape_ptr(IxDyn(tensor.shape()), ptr) };
+    let owner = Bound::new(py, TensorOwner(tensor))?.into_any();
+    let array = unsafe { PyArray::borrow_from_array_bound(&view, owner) }.into_any();
+    // tensors can be shared with the model (constants, for instance)
+    array.getattr("flags")?.setattr("writeable", false)?;
+    Ok(array)
+}
+
+fn tensor_to_numpy(py: Python, tensor: Arc<Tensor>) -> PyResult<Bound<PyAny>> {
+    let dt = tensor.datum_type();
+    dispatch_numpy!(to_numpy(dt)(py, tensor)
+        or Err(PyTypeError::new_err(format!("Unsupported output type {:?}", dt))))
+}
+
+/// A typed model, and a plan to run it.
+#[pyclass(name = "TractModel")]
+struct Model {
+    plan: SimplePlan<TypedFact, Box<dyn TypedOp>, Arc<TypedModel>>,
+}
+
+impl Model {
+    fn new(model: TypedModel) -> PyResult<Model> {
+        Ok(Model { plan: SimplePlan::new(Arc::new(model)).map_err(to_py)? })
+    }
+}
+
+#[pymethods]
+impl Model {
+    /// Run the model on a list of arrays, one per input, returning a list of
+    /// read-only arrays, one per output.
+    fn run<'py>(
+        &self,
+        py: Python<'py>,
+        inputs: Vec<Bound<'py, PyUntypedArray>>,
+    ) -> PyResult<Vec<Bound<'py, PyAny>>> {
+        let expected = self.plan.model().input_outlets().map_err(to_py)?.len();
+        if inputs.len() != expected {
+            return Err(PyValueError::new_err(format!(
+                "Model expects {} inputs, got {}",
+                expected,
+                inputs.len()
+            )));
+        }
+        let inputs = inputs.iter().map(tensor_from_numpy).collect::<PyResult<TVec<Tensor>>>()?;
+        let outputs = py.allow_threads(|| self.plan.run(inputs)).map_err(to_py)?;
+        outputs.into_iter().map(|t| tensor_to_numpy(py, t)).collect()
+    }
+
+    /// Return an optimized copy of the model.
+    fn optimize(&self, py: Python) -> PyResult<Model> {
+        let model = self.plan.model().clone();
+        let optimized = py.allow_threads(|| model.into_optimized()).map_err(to_py)?;
+        Model::new(optimized)
+    }
+
+    fn __repr__(&self) -> String {
+        let model = self.plan.model();
+        format!(
+            "TractModel({} nodes, {} inputs, {} outputs)",
+            model.nodes().len(),
+            model.input_outlets().map(|o| o.len()).unwrap_or(0),
+            model.output_outlets().map(|o| o.len()).unwrap_or(0)
+        )
+    }
+}
+
+/// Load an ONNX model from the file at `path`.
+///
+/// The shapes of the model inputs must be fully determined by the file.
+#[pyfunction]
+fn load_onnx(py: Python, path: &str) -> PyResult<Model> {
+    let model = py
+        .allow_threads(|| tract_onnx::onnx().model_for_path(path)?.into_typed())
+        .map_err(to_py)?;
+    Model::new(model)
+}
+
+#[pymodule]
+fn tract(m: &Bound<PyModule>) -> PyResult<()> {
+    m.add_class::<Model>()?;
+    m.add_function(wrap_pyfunction!(load_onnx, m)?)?;
+    Ok(())
+}
@@ -0,0 +1,76 @@
+import numpy as np
+import onnx
+import onnxruntime
+import pytest
+from onnx import TensorProto, helper, numpy_helper
+
+import tract
+
+
+# y = relu(x @ w + b), z = sum(y, axis=1)
+@pytest.fixture
+def model_path(tmp_path):
+    rng = np.random.default_rng(0)
+    w = rng.standard_normal((8, 4)).astype(np.float32)
+    b = rng.standard_normal(4).astype(np.float32)
+    graph = helper.make_graph(
+        [
+            helper.make_node("MatMul", ["x", "w"], ["xw"]),
+            helper.make_node("Add", ["xw", "b"], ["xwb"]),
+            helper.make_node("Relu", ["xwb"], ["y"]),
+            helper.make_node("ReduceSum", ["y"], ["z"], axes=[1], keepdims=0),
+        ],
+        "mlp",
+        [helper.make_tensor_value_info("x", TensorProto.FLOAT, [3, 8])],
+        [
+            helper.make_tensor_value_info("y", TensorProto.FLOAT, [3, 4]),
+            helper.make_tensor_value_info("z", TensorProto.FLOAT, [3]),
+        ],
+        initializer=[numpy_helper.from_array(w, "w"), numpy_helper.from_array(b, "b")],
+    )
+    model = helper.make_model(graph, opset_imports=[helper.make_opsetid("", 11)])
+    path = tmp_path / "mlp.onnx"
+    onnx.save(model, str(path))
+    return str(path)
+
+
+def reference(path, x):
+    session = onnxruntime.InferenceSession(path, providers=["CPUExecutionProvider"])
+    return session.run(None, {"x": x})
+
+
+@pytest.mark.parametrize("optimize", [False, True])
+def test_matches_onnxruntime(model_path, optimize):
+    x = np.random.default_rng(1).standard_normal((3, 8)).astype(np.float32)
+    model = tract.load_onnx(model_path)
+    if optimize:
+        model = model.optimize()
+    found = model.run([x])
+    expected = reference(model_path, x)
+    assert len(found) == len(expected)
+    for f, e in zip(found, expected):
+        assert f.dtype == e.dtype
+        np.testing.assert_allclose(f, e, rtol=1e-5, atol=1e-6)
+
+
+def test_non_contiguous_input(model_path):
+    x = np.random.default_rng(2).standard_normal((8, 3)).astype(np.float32).T
+    assert not x.flags.c_contiguous
+    found = tract.load_onnx(model_path).run([x])
+    np.testing.assert_allclose(found[0], reference(model_path, np.ascontiguousarray(x))[0], rtol=1e-5)
+
+
+def test_outputs_are_read_only(model_path):
+    y = tract.load_onnx(model_path).run([np.zeros((3, 8), np.float32)])[0]
+    with pytest.raises(ValueError):
+        y[0, 0] = 1.0
+
+
+def test_errors(model_path):
+    model = tract.load_onnx(model_path)
+    with pytest.raises(ValueError):
+        model.run([])
+    with pytest.raises(TypeError):
+        model.run([np.zeros((3, 8), np.complex64)])
+    with pytest.raises(RuntimeError):
+        tract.load_onnx(model_path + ".missing")
@@ -66,6 +66,7 @@ impl From<Tensor> for TensorHolder {
         match m.datum_type() {
             DatumType::Bool => TensorHolder::Bool(Self::to_tensor(m.into_array().unwrap())),
             DatumType::F16 => unimplemented!(),
+            DatumType::BF16 => unimplemented!(),
             DatumType::F32 => TensorHolder::F32(Self::to_tensor(m.into_array().unwrap())),
             DatumType::F64 => TensorHolder::F64(Self::to_tensor(m.into_array().unwrap())),
             DatumType::I8 => TensorHolder::I8(Self::to_tensor(m.into_array().unwrap())),
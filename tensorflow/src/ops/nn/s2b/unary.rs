@@ -79,7 +79,8 @@ impl TypedOp for SpaceToBatchUnary {
                         kernel: conv_op.kernel.clone(),
                         group: conv_op.group,
                         bias: None,
-                        q_params: None
+                        q_params: None,
+                        algorithm: conv_op.algorithm,
                     };
                     let mut patch = TypedModelPatch::default();
                     let tap = patch.tap_model(&model, node.inputs[0])?;
@@ -17,6 +17,7 @@ impl TryFrom<DataType> for DatumType {
             DataType::DtInt32 => Ok(DatumType::I32),
             DataType::DtInt64 => Ok(DatumType::I64),
             DataType::DtHalf => Ok(DatumType::F16),
+            DataType::DtBfloat16 => Ok(DatumType::BF16),
             DataType::DtFloat => Ok(DatumType::F32),
             DataType::DtDouble => Ok(DatumType::F64),
             DataType::DtString => Ok(DatumType::Blob),
@@ -54,11 +55,13 @@ impl TryFrom<DatumType> for DataType {
             DatumType::I32 => Ok(DataType::DtInt32),
             DatumType::I64 => Ok(DataType::DtInt64),
             DatumType::F16 => Ok(DataType::DtHalf),
+            DatumType::BF16 => Ok(DataType::DtBfloat16),
             DatumType::F32 => Ok(DataType::DtFloat),
             DatumType::F64 => Ok(DataType::DtDouble),
             DatumType::Blob => Ok(DataType::DtString),
             DatumType::String => Ok(DataType::DtString),
             DatumType::TDim => bail!("Dimension is not translatable in protobuf"),
+            DatumType::I4 | DatumType::U4 => bail!("{:?} is not translatable in protobuf", dt),
         }
     }
 }